@@ -13,22 +13,24 @@ use nebula_config::Config;
 use nebula_debug::{DebugServer, DebugState, create_debug_server, get_debug_port};
 use nebula_lighting::{
     CascadedShadowConfig, CascadedShadowMaps, DirectionalLight, LightingAtmosphereConfig,
-    LightingContext, PointLight, PointLightFrustum, PointLightManager,
-    lighting_context_at_altitude, modulate_ambient_by_sun,
+    LightingContext, PointLight, PointLightFrustum, PointLightManager, WorldAabb,
+    lighting_context_at_altitude, modulate_ambient_by_sun, tint_ambient_by_sun,
 };
+use nebula_lod::QuadtreeSnapshotStore;
 use nebula_planet::{
     AtmosphereParams, AtmosphereRenderer, DayNightState, ImpostorConfig, ImpostorRenderer,
-    ImpostorState, LocalFrustum, OceanParams, OceanRenderer, OrbitalRenderer, OriginManager,
-    PlanetFaces, PlanetaryCoord, TransitionConfig, chunk_budget_for_altitude, create_orbit_camera,
-    generate_orbital_sphere, impostor_quad_size,
+    ImpostorState, LocalFrustum, MAX_ORBITAL_SUBDIVISION, OceanParams, OceanRenderer,
+    OrbitalRenderer, OriginManager, PlanetFaces, PlanetaryCoord, TransitionConfig,
+    chunk_budget_for_altitude, create_orbit_camera, generate_orbital_sphere,
+    generate_placeholder_texture, impostor_quad_size, orbit_camera_eye,
 };
 use nebula_render::{
     BloomConfig, BloomPipeline, BufferAllocator, Camera, CameraUniform, DepthBuffer, FrameEncoder,
-    IndexData, LIT_SHADER_SOURCE, LitPipeline, MeshBuffer, RenderContext, RenderPassBuilder,
-    SHADOW_SHADER_SOURCE, ShaderLibrary, ShadowPipeline, SurfaceWrapper, TEXTURED_SHADER_SOURCE,
-    TextureManager, TexturedPipeline, UNLIT_SHADER_SOURCE, UnlitPipeline, VertexPositionColor,
-    VertexPositionNormalUv, draw_lit, draw_textured, draw_unlit, init_render_context_blocking,
-    render_shadow_cascades,
+    IndexData, LIT_SHADER_SOURCE, LitPipeline, MeshBuffer, Overlay, OverlayRenderer, RenderContext,
+    RenderPassBuilder, SHADOW_SHADER_SOURCE, ShaderLibrary, ShadowPipeline, SurfaceWrapper,
+    TEXTURED_SHADER_SOURCE, TextureManager, TexturedPipeline, UNLIT_SHADER_SOURCE, UnlitPipeline,
+    VertexPositionColor, VertexPositionNormalUv, draw_lit, draw_textured, draw_unlit,
+    init_render_context_blocking, render_shadow_cascades,
 };
 use nebula_space::{
     DistantPlanet, ImpostorInstance, NebulaConfig, NebulaGenerator, OrbitalElements,
@@ -89,6 +91,12 @@ pub type CustomInputUpdateFn = Box<
 /// Returns the desired window title string. Applied after the simulation tick.
 pub type WindowTitleFn = Box<dyn FnMut() -> String>;
 
+/// Callback invoked each frame to fill the 2D overlay (HUD text, bars, reticles).
+///
+/// The overlay is cleared and sized to the surface before the call, and drawn
+/// over the finished 3D frame after it.
+pub type OverlayFn = Box<dyn FnMut(&mut Overlay)>;
+
 /// Application state that manages the window, GPU context, and tracks surface dimensions.
 pub struct AppState {
     /// The window handle, wrapped in `Arc` for sharing with the renderer.
@@ -109,6 +117,12 @@ pub struct AppState {
     pub custom_input_update: Option<CustomInputUpdateFn>,
     /// Optional callback to produce a dynamic window title each frame (e.g. HUD info).
     pub window_title_fn: Option<WindowTitleFn>,
+    /// Optional per-frame overlay callback.
+    pub overlay_fn: Option<OverlayFn>,
+    /// 2D draw list rebuilt each frame by `overlay_fn`.
+    pub overlay: Overlay,
+    /// Renderer for `overlay`, created when `overlay_fn` is set.
+    pub overlay_renderer: Option<OverlayRenderer>,
     /// Engine configuration.
     pub config: Config,
     /// Debug server (only in debug builds).
@@ -254,6 +268,9 @@ impl AppState {
             custom_update: None,
             custom_input_update: None,
             window_title_fn: None,
+            overlay_fn: None,
+            overlay: Overlay::new(DEFAULT_WIDTH as f32, DEFAULT_HEIGHT as f32),
+            overlay_renderer: None,
             config: Config::default(),
             debug_server,
             debug_state,
@@ -332,6 +349,7 @@ impl AppState {
             config.window.title =
                 format!("{} [Debug API :{}]", config.window.title, get_debug_port());
         }
+        let day_night = DayNightState::new(config.planet.day_length_seconds);
 
         Self {
             window: None,
@@ -343,6 +361,9 @@ impl AppState {
             custom_update: None,
             custom_input_update: None,
             window_title_fn: None,
+            overlay_fn: None,
+            overlay: Overlay::new(DEFAULT_WIDTH as f32, DEFAULT_HEIGHT as f32),
+            overlay_renderer: None,
             config,
             debug_server,
             debug_state,
@@ -373,7 +394,7 @@ impl AppState {
             point_light_buffer: None,
             atmosphere_renderer: None,
             atmosphere_bind_group: None,
-            day_night: DayNightState::new(1200.0), // 20 minutes per day
+            day_night,
             ocean_renderer: None,
             orbital_renderer: None,
             orbital_rotation: 0.0,
@@ -632,7 +653,7 @@ impl AppState {
             &gpu.device,
             &gpu.queue,
             gpu.surface_format,
-            self.impostor_config.texture_resolution,
+            &self.impostor_config,
         );
         info!(
             "Impostor renderer initialized: {}x{} texture",
@@ -679,6 +700,14 @@ impl AppState {
         let lens_flare = nebula_render::LensFlareRenderer::new(&gpu.device, hdr_format);
         self.lens_flare = Some(lens_flare);
 
+        // --- 2D overlay (HUD text and quads over the final frame) ---
+        if self.overlay_fn.is_some() {
+            match OverlayRenderer::new(&gpu.device, &gpu.queue, gpu.surface_format) {
+                Ok(renderer) => self.overlay_renderer = Some(renderer),
+                Err(e) => warn!("Overlay disabled: {e}"),
+            }
+        }
+
         // --- Distant planet impostor renderer (crescent-shaded billboards) ---
         let distant_impostor = PlanetImpostorRenderer::new(&gpu.device, hdr_format);
         self.distant_impostor = Some(distant_impostor);
@@ -773,7 +802,6 @@ impl AppState {
     fn initialize_orbital_renderer(&mut self, gpu: &RenderContext, planet_radius: f32) {
         use nebula_planet::orbital::texture::create_default_samplers;
 
-        let mesh = generate_orbital_sphere(5);
         let (terrain, biome) = create_default_samplers(42, planet_radius as f64);
         let tex_width = 512;
         let tex_height = 256;
@@ -784,7 +812,7 @@ impl AppState {
             &gpu.device,
             &gpu.queue,
             gpu.surface_format,
-            &mesh,
+            MAX_ORBITAL_SUBDIVISION,
             &terrain_pixels,
             tex_width,
             tex_height,
@@ -792,9 +820,9 @@ impl AppState {
         );
 
         info!(
-            "Orbital renderer initialized: {} vertices, {} triangles, {}x{} texture",
-            mesh.positions.len(),
-            mesh.indices.len() / 3,
+            "Orbital renderer initialized: subdivision {}, {} triangles, {}x{} texture",
+            orbital.subdivision(),
+            orbital.index_count() / 3,
             tex_width,
             tex_height
         );
@@ -903,7 +931,12 @@ impl AppState {
         // --- PBR Material Uniform ---
         self.initialize_pbr_material(gpu, &planet_pipeline);
 
-        let planet = PlanetFaces::new_demo(1, 42);
+        let mut planet = PlanetFaces::new_demo(1, 42).with_quadtree_store(
+            QuadtreeSnapshotStore::new(&self.config.planet.quadtree_cache_dir),
+        );
+        // The orbit camera starts at angle zero (see the planet face pass).
+        let radius = planet.planet_radius as f32;
+        planet.start_lod(orbit_camera_eye(radius, radius * 0.8, 0.0, 0.4));
         // Add demo point lights on the planet surface.
         self.initialize_demo_point_lights(planet.planet_radius as f32);
         let planet_radius = planet.planet_radius;
@@ -1009,7 +1042,11 @@ impl AppState {
     ) {
         use wgpu::util::DeviceExt;
 
-        let config = CascadedShadowConfig::default();
+        let config = CascadedShadowConfig {
+            max_distance: self.config.render.shadow_distance,
+            use_tight_fitting: self.config.render.shadow_tight_fitting,
+            ..Default::default()
+        };
         let shadow_maps = CascadedShadowMaps::new(&gpu.device, &config);
 
         // Shadow depth-only pipeline
@@ -1174,6 +1211,14 @@ impl ApplicationHandler for AppState {
             let inner_size = window.inner_size();
             self.surface_wrapper =
                 SurfaceWrapper::new(inner_size.width, inner_size.height, scale_factor);
+            if let Some(display) = self.config.window.display_index {
+                // Fullscreen is only applied when a target display is chosen.
+                if self.config.window.fullscreen {
+                    SurfaceWrapper::fullscreen_on_display(&window, display);
+                } else {
+                    self.surface_wrapper.move_to_display(&window, display);
+                }
+            }
             info!(
                 "Surface wrapper initialized: {}x{} (scale: {:.2})",
                 inner_size.width, inner_size.height, scale_factor
@@ -1343,6 +1388,7 @@ impl ApplicationHandler for AppState {
                 let chunk_budget = &mut self.chunk_budget;
                 let simulated_altitude = &mut self.simulated_altitude;
                 let planet_config = &self.config.planet;
+                let planet_faces = &mut self.planet_faces;
                 // When a custom input callback is set, it owns the camera.
                 let has_custom_input = custom_input_update.is_some();
 
@@ -1351,6 +1397,9 @@ impl ApplicationHandler for AppState {
                         *tick_count += 1;
                         *camera_time += dt;
                         day_night.tick(dt);
+                        if let Some(planet) = planet_faces.as_mut() {
+                            planet.quadtrees.tick(dt);
+                        }
                         // Slow planet rotation (~1 revolution per 10 minutes)
                         *orbital_rotation += (dt as f32) * 0.01;
 
@@ -1541,8 +1590,7 @@ impl ApplicationHandler for AppState {
                                     100.0,
                                 );
                                 let vp_rot = proj * view_rot;
-                                let inv_vp = vp_rot.inverse();
-                                skybox.update(&gpu.queue, inv_vp);
+                                skybox.update_camera(&gpu.queue, view_rot, proj);
 
                                 // Render skybox to HDR texture
                                 let hdr_clear = wgpu::Color {
@@ -1563,13 +1611,13 @@ impl ApplicationHandler for AppState {
                                 // Render sun corona to HDR (additive, after skybox)
                                 if let Some(sun_renderer) = &self.sun_renderer {
                                     let sun_dir = self.day_night.sun_direction;
-                                    let sun_props = SunProperties {
-                                        direction: sun_dir,
-                                        physical_diameter: 1_392_700.0,
-                                        distance: 149_597_870.0,
-                                        star_type: StarType::G,
-                                        luminosity: 1.0,
-                                    };
+                                    let sun_props = SunProperties::new(
+                                        sun_dir,
+                                        1_392_700.0,
+                                        149_597_870.0,
+                                        StarType::G,
+                                        1.0,
+                                    );
 
                                     // Build a Camera struct matching the skybox view
                                     let cam_forward = forward;
@@ -1603,8 +1651,9 @@ impl ApplicationHandler for AppState {
                                 }
 
                                 // Render lens flare to HDR (additive, after sun)
-                                if let Some(flare) = &self.lens_flare {
+                                if let Some(flare) = &mut self.lens_flare {
                                     let sun_dir = self.day_night.sun_direction;
+                                    flare.update_sun_angle(forward, sun_dir);
                                     let sun_brightness = 50.0_f32; // matches SunProperties::hdr_brightness
                                     let visible =
                                         flare.update(&gpu.queue, sun_dir, sun_brightness, vp_rot);
@@ -1687,6 +1736,15 @@ impl ApplicationHandler for AppState {
                             }
 
                             // === Pass 0: Orbital planet sphere (or clear-only) ===
+                            if let Some(orbital) = &mut self.orbital_renderer {
+                                let distance = if self.config.planet.free_fly_camera {
+                                    self.camera.position.length()
+                                } else {
+                                    // The orbit camera sits three radii above the surface.
+                                    orbital.planet_radius * 4.0
+                                };
+                                orbital.select_lod(&gpu.device, distance);
+                            }
                             if let (Some(orbital), Some(depth_buffer)) =
                                 (&self.orbital_renderer, &self.depth_buffer)
                             {
@@ -1741,11 +1799,11 @@ impl ApplicationHandler for AppState {
                             }
 
                             // === Pass 0.5: Impostor billboard for a distant planet ===
+                            let aspect =
+                                self.surface_width() as f32 / self.surface_height().max(1) as f32;
                             if let (Some(impostor), Some(depth_buffer)) =
-                                (&self.impostor_renderer, &self.depth_buffer)
+                                (&mut self.impostor_renderer, &self.depth_buffer)
                             {
-                                let aspect = self.surface_width() as f32
-                                    / self.surface_height().max(1) as f32;
                                 let orbit_angle = self.camera_time * 0.3;
                                 let planet_radius = self
                                     .planet_faces
@@ -1775,6 +1833,15 @@ impl ApplicationHandler for AppState {
                                 let cam_right = to_planet.cross(glam::Vec3::Y).normalize();
                                 let cam_up = cam_right.cross(to_planet).normalize();
 
+                                // The demo has a single distant planet, id 0.
+                                impostor.refresh_snapshot(
+                                    &gpu.queue,
+                                    0,
+                                    to_planet,
+                                    self.day_night.sun_direction,
+                                    generate_placeholder_texture,
+                                );
+
                                 let dist_to_planet = (distant_center - cam_pos).length() as f64;
                                 let half_size =
                                     impostor_quad_size(planet_radius as f64 * 0.5, dist_to_planet)
@@ -1832,12 +1899,18 @@ impl ApplicationHandler for AppState {
                                         aspect,
                                     );
 
+                                    let planet_cam_pos =
+                                        orbit_camera_eye(planet_radius, altitude, orbit_angle, 0.4);
+
                                     // Level 1 (coarse): face-level frustum culling
                                     let frustum = nebula_render::Frustum::from_view_projection(&vp);
                                     // Level 2 (fine): chunk-level culling via LocalFrustum
                                     let _local_frustum = LocalFrustum::from_view_proj(&vp);
                                     if let Some(planet) = &mut self.planet_faces {
+                                        planet.update_lod(planet_cam_pos);
                                         let visible_faces = planet.cull_faces(&frustum);
+                                        // Hierarchical quadtree culling of the far-terrain patches
+                                        let lod_cull = planet.cull_lod_nodes(planet_cam_pos, &vp);
 
                                         // Gather visible render data after face culling
                                         let (verts, idxs) = planet.visible_render_data();
@@ -1853,10 +1926,11 @@ impl ApplicationHandler for AppState {
                                         // Log culling stats periodically
                                         if self.tick_count.is_multiple_of(60) {
                                             info!(
-                                                "Frustum culled: {:.0}% of chunks ({} visible / {} loaded)",
+                                                "Frustum culled: {:.0}% of chunks ({} visible / {} loaded), {} quadtree subtrees",
                                                 100.0 - visible_pct,
                                                 visible_faces,
                                                 total_faces,
+                                                lod_cull.subtrees_culled,
                                             );
                                         }
 
@@ -1869,16 +1943,6 @@ impl ApplicationHandler for AppState {
                                             ));
                                         }
                                     }
-                                    // Compute camera position for PBR view direction.
-                                    let cam_dist_f64_planet =
-                                        planet_radius as f64 + altitude as f64;
-                                    let planet_cam_pos = glam::Vec3::new(
-                                        (orbit_angle.cos() * 0.4_f64.cos() * cam_dist_f64_planet)
-                                            as f32,
-                                        (0.4_f64.sin() * cam_dist_f64_planet) as f32,
-                                        (orbit_angle.sin() * 0.4_f64.cos() * cam_dist_f64_planet)
-                                            as f32,
-                                    );
                                     let uniform = CameraUniform {
                                         view_proj: vp.to_cols_array_2d(),
                                         camera_pos: [
@@ -1914,10 +1978,14 @@ impl ApplicationHandler for AppState {
                                         &self.lighting_atmo_config,
                                         &surface_ctx,
                                     );
-                                    // Modulate ambient by sun elevation (day/night).
+                                    // Tint and dim ambient by sun elevation (day/night).
                                     let sun_elev = self.day_night.sun_direction.y;
-                                    ctx.ambient_color =
-                                        modulate_ambient_by_sun(ctx.ambient_color, sun_elev);
+                                    let tinted = tint_ambient_by_sun(
+                                        ctx.ambient_color,
+                                        sun_elev,
+                                        &self.lighting_atmo_config,
+                                    );
+                                    ctx.ambient_color = modulate_ambient_by_sun(tinted, sun_elev);
                                     self.lighting_context = ctx.clone();
                                     if let Some(lcb) = &self.lighting_context_buffer {
                                         let lcu = ctx.to_uniform();
@@ -1949,8 +2017,23 @@ impl ApplicationHandler for AppState {
                                     // Update shadow cascade matrices.
                                     if let Some(shadow_maps) = &mut self.shadow_maps {
                                         let light_dir = self.sun_light.direction;
-                                        let inv_vp = vp.inverse();
-                                        shadow_maps.update_matrices(light_dir, inv_vp, 0.1);
+                                        shadow_maps
+                                            .config
+                                            .fit_splits(self.camera.near, self.camera.far);
+                                        // The planet plus the terrain height margin.
+                                        let extent = glam::Vec3::splat(planet_radius * 1.1);
+                                        shadow_maps.update_matrices_fitted(
+                                            light_dir,
+                                            glam::Mat4::look_at_rh(
+                                                planet_cam_pos,
+                                                glam::Vec3::ZERO,
+                                                glam::Vec3::Y,
+                                            ),
+                                            70.0_f32.to_radians(),
+                                            aspect,
+                                            self.camera.near,
+                                            &WorldAabb::new(-extent, extent),
+                                        );
 
                                         // Upload per-cascade light matrices.
                                         for (i, buf) in
@@ -2101,6 +2184,7 @@ impl ApplicationHandler for AppState {
                                     glam::Vec3::ZERO, // planet center at origin
                                     sun_dir,
                                     cam_pos,
+                                    planet_radius * 0.8,
                                     inv_vp,
                                     0.1,
                                     10000.0,
@@ -2185,6 +2269,24 @@ impl ApplicationHandler for AppState {
                                 }
                             }
 
+                            // === Final pass: 2D overlay over the finished frame ===
+                            let overlay_size =
+                                (self.surface_width() as f32, self.surface_height() as f32);
+                            if let (Some(overlay_fn), Some(renderer)) =
+                                (&mut self.overlay_fn, &mut self.overlay_renderer)
+                            {
+                                self.overlay.begin_frame(overlay_size.0, overlay_size.1);
+                                overlay_fn(&mut self.overlay);
+                                if !self.overlay.is_empty() {
+                                    renderer.prepare(&gpu.device, &gpu.queue, &self.overlay);
+                                    let pb = RenderPassBuilder::new()
+                                        .preserve_color()
+                                        .label("overlay-pass");
+                                    let mut pass = frame_encoder.begin_render_pass(&pb);
+                                    renderer.render(&mut pass);
+                                }
+                            }
+
                             // Capture screenshot if requested by the debug API
                             #[cfg(debug_assertions)]
                             let screenshot_readback = if self
@@ -2299,6 +2401,15 @@ impl ApplicationHandler for AppState {
             self.mouse_state.on_raw_motion(delta.0, delta.1);
         }
     }
+
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        // Keep the planet's LOD quadtrees for a warm start next launch.
+        if let Some(planet) = &self.planet_faces
+            && let Err(e) = planet.quadtrees.shutdown()
+        {
+            warn!("Failed to save planet quadtrees: {e}");
+        }
+    }
 }
 
 /// Deep space blue clear color as specified in the plan.
@@ -2447,6 +2558,46 @@ pub fn run_with_config_input_title_and_clear<T, F, C>(
     event_loop.run_app(&mut app).expect("Event loop failed");
 }
 
+/// Run the engine with a custom input handler, a 2D overlay, and dynamic clear color.
+///
+/// Like [`run_with_config_input_title_and_clear`] but draws the HUD on screen:
+/// `overlay_fn` is called each frame with an [`Overlay`] cleared and sized to
+/// the surface, and its text and rectangles are drawn over the final frame.
+pub fn run_with_config_input_overlay_and_clear<T, O, C>(
+    config: Config,
+    mut custom_state: T,
+    overlay_fn: O,
+    mut clear_color_fn: C,
+) where
+    T: FnMut(
+            f64,
+            &nebula_input::KeyboardState,
+            &nebula_input::MouseState,
+            &mut nebula_render::Camera,
+        ) + 'static,
+    O: FnMut(&mut Overlay) + 'static,
+    C: FnMut(u64) -> [f64; 4] + 'static,
+{
+    let event_loop = EventLoop::new().expect("Failed to create event loop");
+    let mut app = AppState::with_config(config);
+
+    app.custom_input_update = Some(Box::new(move |dt, kb, ms, cam| {
+        custom_state(dt, kb, ms, cam);
+    }));
+    app.overlay_fn = Some(Box::new(overlay_fn));
+    app.clear_color_fn = Some(Box::new(move |tick| {
+        let c = clear_color_fn(tick);
+        wgpu::Color {
+            r: c[0],
+            g: c[1],
+            b: c[2],
+            a: c[3],
+        }
+    }));
+
+    event_loop.run_app(&mut app).expect("Event loop failed");
+}
+
 /// Create six colored quad meshes, one per [`CubeFace`], arranged as a cube
 /// floating at `(0, 0, -5)` with half-extent 0.6.
 fn create_cube_face_meshes(allocator: &BufferAllocator) -> Vec<MeshBuffer> {
@@ -43,6 +43,14 @@ pub struct CliArgs {
     /// Path to config directory (overrides default location).
     #[arg(long)]
     pub config: Option<PathBuf>,
+
+    /// Record per-tick input to this file.
+    #[arg(long, conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Replay input previously captured with `--record` from this file.
+    #[arg(long)]
+    pub replay: Option<PathBuf>,
 }
 
 impl Config {
@@ -88,6 +96,8 @@ mod tests {
             render_distance: None,
             log_level: None,
             config: None,
+            record: None,
+            replay: None,
         };
         config.apply_cli_overrides(&args);
         assert_eq!(config.window.width, 1920);
@@ -110,6 +120,8 @@ mod tests {
             render_distance: None,
             log_level: None,
             config: None,
+            record: None,
+            replay: None,
         };
         config.apply_cli_overrides(&args);
         assert_eq!(config, original);
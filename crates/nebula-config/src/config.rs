@@ -19,6 +19,8 @@ pub struct Config {
     pub input: InputConfig,
     /// Network/multiplayer settings.
     pub network: NetworkConfig,
+    /// Dedicated server settings.
+    pub server: ServerConfig,
     /// Audio settings.
     pub audio: AudioConfig,
     /// Debug/development settings.
@@ -37,6 +39,9 @@ pub struct WindowConfig {
     pub height: u32,
     /// Start in fullscreen mode.
     pub fullscreen: bool,
+    /// Display to open the window on, as an index into the enumerated
+    /// displays. `None` leaves placement to the platform.
+    pub display_index: Option<usize>,
     /// Enable vsync (PresentMode::Fifo).
     pub vsync: bool,
     /// Window title.
@@ -53,6 +58,8 @@ pub struct RenderConfig {
     pub lod_bias: f32,
     /// Maximum shadow cascade distance.
     pub shadow_distance: f32,
+    /// Fit shadow cascades to the scene bounds instead of whole frustum slices.
+    pub shadow_tight_fitting: bool,
     /// Enable ambient occlusion.
     pub ambient_occlusion: bool,
     /// MSAA sample count (1, 2, 4).
@@ -87,6 +94,39 @@ pub struct NetworkConfig {
     pub max_players: u32,
     /// Tick rate for network updates (Hz).
     pub net_tick_rate: u32,
+    /// Transport used to connect to and host servers.
+    pub transport: TransportKind,
+}
+
+/// Which transport implementation a client or server should use.
+///
+/// `nebula-net` re-exports this type, so a new transport is added here once.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum TransportKind {
+    /// Length-prefixed frames over a TCP stream.
+    #[default]
+    Tcp,
+    /// Datagrams over UDP with a reliability layer for the reliable channel.
+    Udp,
+}
+
+/// Dedicated server configuration.
+///
+/// The player limit and listen port come from [`NetworkConfig`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ServerConfig {
+    /// Seed for world and terrain generation.
+    pub world_seed: u64,
+    /// Seconds between automatic world saves (0 = never).
+    pub autosave_interval_seconds: u32,
+    /// Directory that world snapshots are written to.
+    pub save_dir: String,
+    /// Simulation tick rate (Hz).
+    pub tick_rate: u32,
+    /// Most ticks simulated in one frame when catching up after a stall;
+    /// older backlog is dropped.
+    pub max_catch_up_ticks: u32,
 }
 
 /// Audio configuration.
@@ -131,6 +171,11 @@ pub struct PlanetConfig {
     pub camera_speed_m_s: f64,
     /// Atmosphere altitude in meters (atmosphere effect starts below this altitude).
     pub atmosphere_altitude_m: f64,
+    /// Length of one day/night cycle in real-time seconds.
+    pub day_length_seconds: f64,
+    /// Directory that planet LOD quadtrees are saved to on shutdown and
+    /// restored from on start.
+    pub quadtree_cache_dir: String,
 }
 
 impl Default for PlanetConfig {
@@ -141,6 +186,8 @@ impl Default for PlanetConfig {
             free_fly_camera: false,
             camera_speed_m_s: 1000.0,
             atmosphere_altitude_m: 100_000.0,
+            day_length_seconds: 1200.0,
+            quadtree_cache_dir: "./cache/quadtrees".to_string(),
         }
     }
 }
@@ -153,6 +200,7 @@ impl Default for WindowConfig {
             width: 1280,
             height: 720,
             fullscreen: false,
+            display_index: None,
             vsync: true,
             title: "Nebula Engine".to_string(),
         }
@@ -165,6 +213,7 @@ impl Default for RenderConfig {
             render_distance: 16,
             lod_bias: 1.0,
             shadow_distance: 256.0,
+            shadow_tight_fitting: true,
             ambient_occlusion: true,
             msaa_samples: 4,
             target_fps: 0,
@@ -190,6 +239,19 @@ impl Default for NetworkConfig {
             timeout_seconds: 30,
             max_players: 32,
             net_tick_rate: 20,
+            transport: TransportKind::Tcp,
+        }
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            world_seed: 0,
+            autosave_interval_seconds: 300,
+            save_dir: "./saves".to_string(),
+            tick_rate: 60,
+            max_catch_up_ticks: 5,
         }
     }
 }
@@ -299,6 +361,13 @@ mod tests {
         assert_eq!(config.audio, AudioConfig::default());
     }
 
+    #[test]
+    fn test_network_transport_parses() {
+        let config: Config = ron::from_str("(network: (transport: Udp))").unwrap();
+        assert_eq!(config.network.transport, TransportKind::Udp);
+        assert_eq!(NetworkConfig::default().transport, TransportKind::Tcp);
+    }
+
     #[test]
     fn test_extra_field_ignored() {
         let ron_str = "(future_setting: true)";
@@ -11,6 +11,6 @@ mod error;
 pub use cli::CliArgs;
 pub use config::{
     AudioConfig, Config, DebugConfig, InputConfig, NetworkConfig, PlanetConfig, RenderConfig,
-    WindowConfig,
+    ServerConfig, TransportKind, WindowConfig,
 };
 pub use error::ConfigError;
@@ -194,18 +194,23 @@ impl SectorCoord {
 
 // Space marker types (zero-sized)
 /// Marker type for universe-space coordinates  
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct UniverseSpace;
 
 /// Marker type for sector-space coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SectorSpace;
 
 /// Marker type for planet-space coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PlanetSpace;
 
 /// Marker type for chunk-space coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ChunkSpace;
 
 /// Marker type for local/camera-space coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct LocalSpace;
 
 /// Enumeration of all coordinate spaces for runtime inspection
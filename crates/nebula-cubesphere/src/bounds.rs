@@ -2,7 +2,7 @@
 
 use glam::DVec3;
 
-use crate::{ChunkAddress, FaceCoord, face_coord_to_sphere_everitt};
+use crate::{ChunkAddress, FaceCoord, ProjectionMethod, project};
 
 /// A bounding sphere in local f64 space (relative to planet center).
 #[derive(Clone, Copy, Debug)]
@@ -59,11 +59,13 @@ impl BoundingSphere {
     /// - `planet_radius`: radius of the planet in engine units (mm)
     /// - `min_height`: minimum terrain height (can be negative for valleys/oceans)
     /// - `max_height`: maximum terrain height above the sphere surface within this chunk
+    /// - `projection`: the planet's cube-to-sphere mapping
     pub fn from_chunk(
         addr: &ChunkAddress,
         planet_radius: f64,
         min_height: f64,
         max_height: f64,
+        projection: ProjectionMethod,
     ) -> Self {
         let (u_min, v_min, u_max, v_max) = addr.uv_bounds();
 
@@ -75,7 +77,7 @@ impl BoundingSphere {
         ];
         let center_fc = FaceCoord::new(addr.face, (u_min + u_max) * 0.5, (v_min + v_max) * 0.5);
 
-        let sphere_center = face_coord_to_sphere_everitt(&center_fc);
+        let sphere_center = project(&center_fc, projection);
 
         // Center at midpoint height
         let mid_radius = planet_radius + (min_height + max_height) * 0.5;
@@ -84,7 +86,7 @@ impl BoundingSphere {
         // Radius must encompass angular extent and height variation
         let mut max_dist_sq: f64 = 0.0;
         for corner_fc in &corners {
-            let corner_dir = face_coord_to_sphere_everitt(corner_fc);
+            let corner_dir = project(corner_fc, projection);
             for &h in &[min_height, max_height] {
                 let pos = corner_dir * (planet_radius + h);
                 let d = (pos - bs_center).length_squared();
@@ -127,12 +129,14 @@ impl ChunkAABB {
     /// Compute the AABB for a cubesphere chunk.
     ///
     /// Samples a grid of points on the chunk surface at both min and max
-    /// terrain heights to find the tightest enclosing AABB.
+    /// terrain heights, placed with the planet's `projection`, to find the
+    /// tightest enclosing AABB.
     pub fn from_chunk(
         addr: &ChunkAddress,
         planet_radius: f64,
         min_height: f64,
         max_height: f64,
+        projection: ProjectionMethod,
     ) -> Self {
         let (u_min, v_min, u_max, v_max) = addr.uv_bounds();
 
@@ -145,7 +149,7 @@ impl ChunkAABB {
                 let u = u_min + (u_max - u_min) * (f64::from(ui) / f64::from(samples));
                 let v = v_min + (v_max - v_min) * (f64::from(vi) / f64::from(samples));
                 let fc = FaceCoord::new(addr.face, u, v);
-                let dir = face_coord_to_sphere_everitt(&fc);
+                let dir = project(&fc, projection);
 
                 for &h in &[min_height, max_height] {
                     let pos = dir * (planet_radius + h);
@@ -201,7 +205,13 @@ mod tests {
     #[test]
     fn test_bounding_sphere_contains_all_chunk_vertices() {
         let addr = ChunkAddress::new(CubeFace::PosX, 10, 50, 50);
-        let bs = BoundingSphere::from_chunk(&addr, PLANET_RADIUS, 0.0, 10_000.0);
+        let bs = BoundingSphere::from_chunk(
+            &addr,
+            PLANET_RADIUS,
+            0.0,
+            10_000.0,
+            ProjectionMethod::default(),
+        );
 
         let (u_min, v_min, u_max, v_max) = addr.uv_bounds();
         let samples = 4u32;
@@ -210,7 +220,7 @@ mod tests {
                 let u = u_min + (u_max - u_min) * (f64::from(ui) / f64::from(samples));
                 let v = v_min + (v_max - v_min) * (f64::from(vi) / f64::from(samples));
                 let fc = FaceCoord::new(addr.face, u, v);
-                let dir = face_coord_to_sphere_everitt(&fc);
+                let dir = project(&fc, ProjectionMethod::Everitt);
 
                 for &h in &[0.0, 5_000.0, 10_000.0] {
                     let pos = dir * (PLANET_RADIUS + h);
@@ -229,8 +239,20 @@ mod tests {
     #[test]
     fn test_aabb_encloses_bounding_sphere() {
         let addr = ChunkAddress::new(CubeFace::NegY, 8, 20, 30);
-        let bs = BoundingSphere::from_chunk(&addr, PLANET_RADIUS, -1_000.0, 5_000.0);
-        let aabb = ChunkAABB::from_chunk(&addr, PLANET_RADIUS, -1_000.0, 5_000.0);
+        let bs = BoundingSphere::from_chunk(
+            &addr,
+            PLANET_RADIUS,
+            -1_000.0,
+            5_000.0,
+            ProjectionMethod::default(),
+        );
+        let aabb = ChunkAABB::from_chunk(
+            &addr,
+            PLANET_RADIUS,
+            -1_000.0,
+            5_000.0,
+            ProjectionMethod::default(),
+        );
 
         assert!(
             aabb.contains(bs.center),
@@ -248,7 +270,8 @@ mod tests {
     #[test]
     fn test_zero_height_chunk_bounds_match_sphere_surface() {
         let addr = ChunkAddress::new(CubeFace::PosZ, 10, 100, 100);
-        let bs = BoundingSphere::from_chunk(&addr, PLANET_RADIUS, 0.0, 0.0);
+        let bs =
+            BoundingSphere::from_chunk(&addr, PLANET_RADIUS, 0.0, 0.0, ProjectionMethod::default());
 
         let center_dist = bs.center.length();
         assert!(
@@ -261,8 +284,15 @@ mod tests {
     #[test]
     fn test_height_offset_expands_bounds() {
         let addr = ChunkAddress::new(CubeFace::PosY, 10, 50, 50);
-        let bs_flat = BoundingSphere::from_chunk(&addr, PLANET_RADIUS, 0.0, 0.0);
-        let bs_tall = BoundingSphere::from_chunk(&addr, PLANET_RADIUS, 0.0, 100_000.0);
+        let bs_flat =
+            BoundingSphere::from_chunk(&addr, PLANET_RADIUS, 0.0, 0.0, ProjectionMethod::default());
+        let bs_tall = BoundingSphere::from_chunk(
+            &addr,
+            PLANET_RADIUS,
+            0.0,
+            100_000.0,
+            ProjectionMethod::default(),
+        );
 
         assert!(
             bs_tall.radius > bs_flat.radius,
@@ -273,8 +303,15 @@ mod tests {
     #[test]
     fn test_negative_height_expands_bounds() {
         let addr = ChunkAddress::new(CubeFace::NegX, 10, 50, 50);
-        let bs_flat = BoundingSphere::from_chunk(&addr, PLANET_RADIUS, 0.0, 0.0);
-        let bs_deep = BoundingSphere::from_chunk(&addr, PLANET_RADIUS, -50_000.0, 0.0);
+        let bs_flat =
+            BoundingSphere::from_chunk(&addr, PLANET_RADIUS, 0.0, 0.0, ProjectionMethod::default());
+        let bs_deep = BoundingSphere::from_chunk(
+            &addr,
+            PLANET_RADIUS,
+            -50_000.0,
+            0.0,
+            ProjectionMethod::default(),
+        );
 
         assert!(
             bs_deep.radius > bs_flat.radius,
@@ -285,7 +322,13 @@ mod tests {
     #[test]
     fn test_world_aabb_from_local() {
         let addr = ChunkAddress::new(CubeFace::PosX, 15, 10, 10);
-        let local_aabb = ChunkAABB::from_chunk(&addr, PLANET_RADIUS, 0.0, 1_000.0);
+        let local_aabb = ChunkAABB::from_chunk(
+            &addr,
+            PLANET_RADIUS,
+            0.0,
+            1_000.0,
+            ProjectionMethod::default(),
+        );
         let world_aabb = WorldAABB::from_local(
             &local_aabb,
             1_000_000_000_000_i128,
@@ -299,4 +342,34 @@ mod tests {
         assert!(world_aabb.max_y > world_aabb.min_y);
         assert!(world_aabb.max_z > world_aabb.min_z);
     }
+
+    #[test]
+    fn test_projection_changes_chunk_bounds() {
+        // Away from the face centre and edges the two mappings disagree by
+        // far more than one chunk.
+        let addr = ChunkAddress::new(CubeFace::PosX, 10, 256, 256);
+        let everitt = BoundingSphere::from_chunk(
+            &addr,
+            PLANET_RADIUS,
+            0.0,
+            1_000.0,
+            ProjectionMethod::Everitt,
+        );
+        let cobe =
+            BoundingSphere::from_chunk(&addr, PLANET_RADIUS, 0.0, 1_000.0, ProjectionMethod::Cobe);
+        let shift = (cobe.center - everitt.center).length();
+        assert!(
+            shift > everitt.radius,
+            "COBE should move the chunk by more than its size: shift={shift}, radius={}",
+            everitt.radius
+        );
+
+        // The COBE bounds enclose the COBE surface, not the Everitt one.
+        let (u_min, v_min, _, _) = addr.uv_bounds();
+        let corner = FaceCoord::new(addr.face, u_min, v_min);
+        let aabb =
+            ChunkAABB::from_chunk(&addr, PLANET_RADIUS, 0.0, 1_000.0, ProjectionMethod::Cobe);
+        assert!(aabb.contains(project(&corner, ProjectionMethod::Cobe) * PLANET_RADIUS));
+        assert!(!aabb.contains(project(&corner, ProjectionMethod::Everitt) * PLANET_RADIUS));
+    }
 }
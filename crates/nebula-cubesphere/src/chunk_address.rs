@@ -10,6 +10,10 @@ use crate::{CubeFace, FaceCoord};
 /// - `x`, `y`: grid coordinates within the face at this LOD level.
 ///   At LOD `l`, the face is divided into a `grid_size(l) × grid_size(l)` grid,
 ///   where `grid_size(l) = MAX_CHUNKS_PER_AXIS >> l`.
+///
+/// Addresses are totally ordered lexicographically by face (in
+/// discriminant order), then `lod`, `x` and `y`. Sorted collections of
+/// addresses are therefore grouped per face and per LOD level.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ChunkAddress {
     /// Which cube face this chunk belongs to.
@@ -115,6 +119,71 @@ impl ChunkAddress {
             ChunkAddress::new(self.face, child_lod, cx + 1, cy + 1),
         ])
     }
+
+    /// A 64-bit Morton (Z-order) key for spatially coherent storage.
+    ///
+    /// The low 20 bits of `lod`, `x` and `y` are interleaved into the low
+    /// 60 bits (`lod` most significant within each bit triple, then `x`,
+    /// then `y`), and the face occupies the top 4 bits. Neighbouring chunks
+    /// on a face map to nearby keys, so hashing or bucketing by this key
+    /// keeps spatially close chunks together.
+    ///
+    /// The key agrees with [`Ord`] for any set of addresses that share a
+    /// LOD and span a single 2×2 block per face (e.g. the children of the
+    /// six face roots); across larger grids the Z curve deliberately
+    /// interleaves `x` and `y` instead of sorting row by row.
+    #[must_use]
+    pub fn z_order_key(&self) -> u64 {
+        let (lod, x, y) = (u64::from(self.lod), u64::from(self.x), u64::from(self.y));
+        let mut key = 0u64;
+        for bit in 0..20 {
+            key |= ((lod >> bit) & 1) << (3 * bit + 2)
+                | ((x >> bit) & 1) << (3 * bit + 1)
+                | ((y >> bit) & 1) << (3 * bit);
+        }
+        key | u64::from(self.face as u8) << 60
+    }
+
+    /// Pack this address into a compact `u64` key for hash maps and
+    /// network messages.
+    ///
+    /// Bit layout, most significant first:
+    ///
+    /// | bits    | field | width |
+    /// |---------|-------|-------|
+    /// | 63..61  | face  | 3     |
+    /// | 60..56  | lod   | 5     |
+    /// | 55..28  | x     | 28    |
+    /// | 27..0   | y     | 28    |
+    ///
+    /// Unlike [`z_order_key`](Self::z_order_key) the fields are not
+    /// interleaved, so the key is cheap to pack and unpack with
+    /// [`from_key`](Self::from_key).
+    #[must_use]
+    pub fn to_key(&self) -> u64 {
+        u64::from(self.face as u8) << 61
+            | u64::from(self.lod) << 56
+            | u64::from(self.x) << 28
+            | u64::from(self.y)
+    }
+
+    /// Unpack a key produced by [`to_key`](Self::to_key).
+    ///
+    /// Returns `None` if the face index is not a cube face, the LOD exceeds
+    /// [`Self::MAX_LOD`], or `x`/`y` lie outside the grid at that LOD.
+    #[must_use]
+    pub fn from_key(key: u64) -> Option<ChunkAddress> {
+        const COORD_MASK: u64 = (1 << 28) - 1;
+        let face = *CubeFace::ALL.get((key >> 61) as usize)?;
+        let lod = ((key >> 56) & 0x1f) as u8;
+        let x = ((key >> 28) & COORD_MASK) as u32;
+        let y = (key & COORD_MASK) as u32;
+        if lod > Self::MAX_LOD {
+            return None;
+        }
+        let size = Self::grid_size(lod);
+        (x < size && y < size).then_some(ChunkAddress { face, lod, x, y })
+    }
 }
 
 impl std::fmt::Display for ChunkAddress {
@@ -241,6 +310,95 @@ mod tests {
         assert!(a < b);
     }
 
+    /// The 24 top-level chunks: the four children of each face root.
+    fn root_children() -> Vec<ChunkAddress> {
+        CubeFace::ALL
+            .iter()
+            .flat_map(|&face| {
+                ChunkAddress::new(face, ChunkAddress::MAX_LOD, 0, 0)
+                    .children()
+                    .expect("root has children")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ord_matches_z_order_for_root_children() {
+        let mut by_ord = root_children();
+        by_ord.reverse();
+        let mut by_key = by_ord.clone();
+        by_ord.sort();
+        by_key.sort_by_key(ChunkAddress::z_order_key);
+        assert_eq!(by_ord.len(), 24);
+        assert_eq!(by_ord, by_key);
+    }
+
+    #[test]
+    fn test_ord_consistent_with_eq() {
+        let addrs = root_children();
+        for a in &addrs {
+            for b in &addrs {
+                assert_eq!(a == b, a.cmp(b) == std::cmp::Ordering::Equal);
+                assert_eq!(a.cmp(b), b.cmp(a).reverse());
+            }
+        }
+    }
+
+    #[test]
+    fn test_ord_is_lexicographic() {
+        let a = ChunkAddress::new(CubeFace::PosX, 3, 100, 0);
+        let b = ChunkAddress::new(CubeFace::PosX, 4, 0, 0);
+        let c = ChunkAddress::new(CubeFace::PosX, 4, 0, 1);
+        let d = ChunkAddress::new(CubeFace::PosX, 4, 1, 0);
+        assert!(a < b && b < c && c < d);
+    }
+
+    #[test]
+    fn test_z_order_key_interleaves_bits() {
+        let addr = ChunkAddress::new(CubeFace::PosX, 0, 0b11, 0b01);
+        // bit 0: x=1, y=1 -> 0b011; bit 1: x=1, y=0 -> 0b010
+        assert_eq!(addr.z_order_key(), 0b010_011);
+        let face = ChunkAddress::new(CubeFace::NegZ, 1, 0, 0);
+        assert_eq!(face.z_order_key(), 5 << 60 | 0b100);
+    }
+
+    #[test]
+    fn test_z_order_key_unique_across_faces() {
+        let keys: HashSet<u64> = root_children()
+            .iter()
+            .map(ChunkAddress::z_order_key)
+            .collect();
+        assert_eq!(keys.len(), 24);
+    }
+
+    #[test]
+    fn test_key_round_trip() {
+        for face in CubeFace::ALL {
+            for lod in [0, 1, 7, 19, ChunkAddress::MAX_LOD] {
+                let last = ChunkAddress::grid_size(lod) - 1;
+                for (x, y) in [(0, 0), (last, 0), (0, last), (last / 3, last)] {
+                    let addr = ChunkAddress::new(face, lod, x, y);
+                    assert_eq!(ChunkAddress::from_key(addr.to_key()), Some(addr));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_key_rejected() {
+        let addr = ChunkAddress::new(CubeFace::NegZ, 4, 3, 9);
+        // Face index 6 is not a cube face.
+        assert_eq!(ChunkAddress::from_key(addr.to_key() | 0b111 << 61), None);
+        // LOD 21 exceeds MAX_LOD.
+        assert_eq!(
+            ChunkAddress::from_key(addr.to_key() & !(0x1f << 56) | 21 << 56),
+            None
+        );
+        // x = grid_size(4) is one past the edge.
+        let x = u64::from(ChunkAddress::grid_size(4));
+        assert_eq!(ChunkAddress::from_key(4 << 56 | x << 28), None);
+    }
+
     #[test]
     #[should_panic(expected = "out of range")]
     fn test_invalid_coordinates_panic() {
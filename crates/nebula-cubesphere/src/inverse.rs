@@ -1,9 +1,11 @@
 //! Sphere-to-cube inverse projection: recover face and UV from a direction or sphere point.
 
+use std::f64::consts::FRAC_PI_4;
+
 use glam::DVec3;
 
-use crate::projection::face_coord_to_sphere_everitt;
-use crate::{CubeFace, FaceCoord};
+use crate::projection::project_st;
+use crate::{CubeFace, FaceCoord, ProjectionMethod};
 
 /// Determine which cube face a direction vector belongs to.
 ///
@@ -42,82 +44,121 @@ pub fn direction_to_face(dir: DVec3) -> CubeFace {
 #[must_use]
 pub fn direction_to_face_coord(dir: DVec3) -> FaceCoord {
     let face = direction_to_face(dir);
-    let n = face.normal();
-    let tang = face.tangent();
-    let bitan = face.bitangent();
+    let (s, t) = face_plane_st(dir, face);
+    FaceCoord::new(face, (s + 1.0) * 0.5, (t + 1.0) * 0.5)
+}
 
-    // Project onto the cube face plane.
-    let d = dir.dot(n);
+/// Project `dir` onto the plane of `face`, returning the face-plane
+/// coordinates `(s, t)` (each `[-1, 1]` on the face).
+fn face_plane_st(dir: DVec3, face: CubeFace) -> (f64, f64) {
+    let d = dir.dot(face.normal());
     // Guard against zero (degenerate direction along the face plane).
     if d.abs() < 1e-30 {
-        return FaceCoord::new(face, 0.5, 0.5);
+        return (0.0, 0.0);
     }
     let projected = dir / d;
-
-    let s = projected.dot(tang);
-    let t = projected.dot(bitan);
-
-    let u = (s + 1.0) * 0.5;
-    let v = (t + 1.0) * 0.5;
-
-    FaceCoord::new(face, u, v)
+    (
+        projected.dot(face.tangent()),
+        projected.dot(face.bitangent()),
+    )
 }
 
 /// Inverse of the Everitt cube-to-sphere mapping.
 ///
 /// Given a unit sphere point, returns the [`FaceCoord`] that would produce it
-/// via [`face_coord_to_sphere_everitt`]. Uses Newton-Raphson iteration for
-/// sub-epsilon accuracy. Typically converges in 3–5 iterations.
+/// via [`face_coord_to_sphere_everitt`](crate::face_coord_to_sphere_everitt).
+/// Uses Newton-Raphson iteration for sub-epsilon accuracy. Typically
+/// converges in 3–5 iterations.
 #[must_use]
 pub fn sphere_to_face_coord_everitt(sphere_point: DVec3) -> FaceCoord {
-    let approx = direction_to_face_coord(sphere_point);
-    let face = approx.face;
+    sphere_to_face_coord(sphere_point, ProjectionMethod::Everitt)
+}
 
-    let mut u = approx.u;
-    let mut v = approx.v;
-    let target = sphere_point.normalize();
+/// Inverse of [`project`](crate::project): the [`FaceCoord`] that `method`
+/// maps to `sphere_point`.
+///
+/// The face is chosen with [`direction_to_face`]; on face edges, where two
+/// faces share the point, use [`sphere_to_face_coord_on_face`] to pick one.
+#[must_use]
+pub fn sphere_to_face_coord(sphere_point: DVec3, method: ProjectionMethod) -> FaceCoord {
+    sphere_to_face_coord_on_face(sphere_point, direction_to_face(sphere_point), method)
+}
 
-    for _ in 0..10 {
-        let fc = FaceCoord::new_unchecked(face, u, v);
-        let current = face_coord_to_sphere_everitt(&fc);
-        let error = target - current;
+/// Like [`sphere_to_face_coord`], but inverts on the given `face`.
+///
+/// The point should lie on `face` (or its edge); the result is clamped to
+/// the face otherwise.
+#[must_use]
+pub fn sphere_to_face_coord_on_face(
+    sphere_point: DVec3,
+    face: CubeFace,
+    method: ProjectionMethod,
+) -> FaceCoord {
+    let (gs, gt) = face_plane_st(sphere_point, face);
+    let (s, t) = match method {
+        ProjectionMethod::Gnomonic => (gs, gt),
+        ProjectionMethod::TangentWarp => (gs.atan() / FRAC_PI_4, gt.atan() / FRAC_PI_4),
+        ProjectionMethod::Everitt | ProjectionMethod::Cobe => {
+            newton_inverse(sphere_point.normalize(), face, method, gs, gt)
+        }
+    };
+    FaceCoord::new(face, (s + 1.0) * 0.5, (t + 1.0) * 0.5)
+}
+
+/// Solve for the face-plane coordinates that `method` sends to `target`,
+/// starting from the gnomonic guess `(s, t)`. Used for the mappings without
+/// a closed-form inverse.
+///
+/// The Jacobian uses central differences, which stay valid on face edges
+/// because [`project_st`] accepts coordinates slightly past them.
+fn newton_inverse(
+    target: DVec3,
+    face: CubeFace,
+    method: ProjectionMethod,
+    mut s: f64,
+    mut t: f64,
+) -> (f64, f64) {
+    const H: f64 = 1e-7;
+    let at = |s, t| project_st(face, s, t, method);
 
-        if error.length() < 1e-14 {
+    for _ in 0..10 {
+        let error = target - at(s, t);
+        if error.length() < 1e-15 {
             break;
         }
 
-        let du = 1e-8;
-        let dv = 1e-8;
-        let fc_du = FaceCoord::new_unchecked(face, (u + du).min(1.0), v);
-        let fc_dv = FaceCoord::new_unchecked(face, u, (v + dv).min(1.0));
-        let dp_du = (face_coord_to_sphere_everitt(&fc_du) - current) / du;
-        let dp_dv = (face_coord_to_sphere_everitt(&fc_dv) - current) / dv;
+        let dp_ds = (at(s + H, t) - at(s - H, t)) / (2.0 * H);
+        let dp_dt = (at(s, t + H) - at(s, t - H)) / (2.0 * H);
 
-        let a11 = dp_du.dot(dp_du);
-        let a12 = dp_du.dot(dp_dv);
-        let a22 = dp_dv.dot(dp_dv);
-        let b1 = dp_du.dot(error);
-        let b2 = dp_dv.dot(error);
+        let a11 = dp_ds.dot(dp_ds);
+        let a12 = dp_ds.dot(dp_dt);
+        let a22 = dp_dt.dot(dp_dt);
+        let b1 = dp_ds.dot(error);
+        let b2 = dp_dt.dot(error);
 
         let det = a11 * a22 - a12 * a12;
         if det.abs() < 1e-20 {
             break;
         }
 
-        let delta_u = (a22 * b1 - a12 * b2) / det;
-        let delta_v = (a11 * b2 - a12 * b1) / det;
-
-        u = (u + delta_u).clamp(0.0, 1.0);
-        v = (v + delta_v).clamp(0.0, 1.0);
+        s += (a22 * b1 - a12 * b2) / det;
+        t += (a11 * b2 - a12 * b1) / det;
     }
 
-    FaceCoord::new(face, u, v)
+    (s.clamp(-1.0, 1.0), t.clamp(-1.0, 1.0))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::projection::face_coord_to_cube_point;
+    use crate::projection::{face_coord_to_cube_point, face_coord_to_sphere_everitt, project};
+
+    const ALL_METHODS: [ProjectionMethod; 4] = [
+        ProjectionMethod::Gnomonic,
+        ProjectionMethod::TangentWarp,
+        ProjectionMethod::Everitt,
+        ProjectionMethod::Cobe,
+    ];
 
     const EPSILON: f64 = 1e-9;
 
@@ -153,6 +194,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_roundtrip_all_methods_including_edges_and_corners() {
+        for method in ALL_METHODS {
+            for face in CubeFace::ALL {
+                for i in 0..=16 {
+                    for j in 0..=16 {
+                        let original = FaceCoord::new(face, i as f64 / 16.0, j as f64 / 16.0);
+                        let sphere_pt = project(&original, method);
+                        let recovered = sphere_to_face_coord_on_face(sphere_pt, face, method);
+                        let err = (recovered.u - original.u)
+                            .abs()
+                            .max((recovered.v - original.v).abs());
+                        assert!(
+                            err < 1e-12,
+                            "{method:?} {face:?} ({}, {}): error {err}",
+                            original.u,
+                            original.v
+                        );
+
+                        // Away from the edges the face is found automatically.
+                        if (1..16).contains(&i) && (1..16).contains(&j) {
+                            let auto = sphere_to_face_coord(sphere_pt, method);
+                            assert_eq!(auto.face, face);
+                            assert!((auto.u - original.u).abs() < 1e-12);
+                            assert!((auto.v - original.v).abs() < 1e-12);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_face_detection_axis_aligned_directions() {
         assert_eq!(direction_to_face(DVec3::X), CubeFace::PosX);
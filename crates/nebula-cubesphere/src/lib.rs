@@ -8,6 +8,7 @@ mod cube_face;
 mod face_coord;
 mod inverse;
 mod neighbor;
+mod planet_converter;
 mod planet_def;
 mod planet_registry;
 mod projection;
@@ -24,19 +25,25 @@ pub use corner::{
 pub use cross_face::{FaceEdgeAdjacency, face_adjacency, transform_uv_across_edge};
 pub use cube_face::CubeFace;
 pub use face_coord::FaceCoord;
-pub use inverse::{direction_to_face, direction_to_face_coord, sphere_to_face_coord_everitt};
+pub use inverse::{
+    direction_to_face, direction_to_face_coord, sphere_to_face_coord, sphere_to_face_coord_everitt,
+    sphere_to_face_coord_on_face,
+};
 pub use neighbor::{FaceDirection, LodNeighbor, SameFaceNeighbor};
-pub use planet_def::PlanetDef;
-pub use planet_registry::{PlanetRegistry, PlanetRegistryError};
+pub use planet_converter::{PlanetCoordinateConverter, PlanetId};
+pub use planet_def::{DEFAULT_INFLUENCE_RADIUS_FACTOR, PlanetDef};
+pub use planet_registry::{DistanceMode, PlanetRegistry, PlanetRegistryError};
 pub use projection::{
     ProjectionMethod, cube_to_sphere_everitt, face_coord_to_cube_point, face_coord_to_sphere,
-    face_coord_to_sphere_everitt, project,
+    face_coord_to_sphere_cobe, face_coord_to_sphere_everitt, face_coord_to_sphere_gnomonic,
+    project, projection_distortion,
 };
-pub use quadtree::{FaceQuadtree, QuadNode};
+pub use quadtree::{FaceQuadtree, QuadNode, QuadtreeBudgetError};
 pub use winding::{
     compute_winding_flip_table, emit_triangle, face_needs_winding_flip, generate_chunk_indices,
     generate_lod_transition_strip, triangle_winds_outward, validate_edge_winding,
 };
 pub use world_conv::{
-    face_grid_to_world_positions, face_uv_to_world_position, world_position_to_face_uv,
+    face_grid_to_world_positions, face_grid_to_world_positions_into, face_uv_to_world_position,
+    world_position_to_face_uv,
 };
@@ -0,0 +1,76 @@
+//! Per-planet coordinate converter between universe space and planet space.
+
+use nebula_coords::{
+    Invertible, PlanetSpace, Pos, Transition, UniverseSpace, Vec3I64, planet_to_universe,
+    universe_to_planet,
+};
+use nebula_math::Vec3I128;
+
+use crate::PlanetDef;
+
+/// Identifier of a planet within a [`PlanetRegistry`](crate::PlanetRegistry).
+///
+/// Wraps the index returned by [`PlanetRegistry::register`](crate::PlanetRegistry::register).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PlanetId(pub usize);
+
+/// Converts positions between universe space and one planet's local space.
+///
+/// Captures the planet origin once so callers no longer pass it to every
+/// [`universe_to_planet`] / [`planet_to_universe`] call, and implements
+/// [`Transition`] in both directions so it composes with `.then()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlanetCoordinateConverter {
+    /// Planet this converter belongs to.
+    pub planet_id: PlanetId,
+    /// Planet center in universe space (mm).
+    pub origin: Pos<UniverseSpace, Vec3I128>,
+    /// Base sphere radius in mm, saturated to `i64::MAX`.
+    pub radius_mm: i64,
+}
+
+impl PlanetCoordinateConverter {
+    /// Build the converter for `planet`, registered under `planet_id`.
+    pub fn new(planet_id: PlanetId, planet: &PlanetDef) -> Self {
+        let center = planet.center;
+        Self {
+            planet_id,
+            origin: Pos::new(Vec3I128::new(center.x, center.y, center.z)),
+            radius_mm: i64::try_from(planet.radius).unwrap_or(i64::MAX),
+        }
+    }
+}
+
+impl Transition<UniverseSpace, PlanetSpace> for PlanetCoordinateConverter {
+    type Input = Vec3I128;
+    type Output = Vec3I64;
+
+    fn apply(&self, pos: &Pos<UniverseSpace, Vec3I128>) -> Pos<PlanetSpace, Vec3I64> {
+        universe_to_planet(pos, &self.origin)
+    }
+}
+
+impl Transition<PlanetSpace, UniverseSpace> for PlanetCoordinateConverter {
+    type Input = Vec3I64;
+    type Output = Vec3I128;
+
+    fn apply(&self, pos: &Pos<PlanetSpace, Vec3I64>) -> Pos<UniverseSpace, Vec3I128> {
+        planet_to_universe(pos, &self.origin)
+    }
+}
+
+impl Invertible<UniverseSpace, PlanetSpace> for PlanetCoordinateConverter {
+    type Inverse = Self;
+
+    fn inverse(&self) -> Self::Inverse {
+        *self
+    }
+}
+
+impl Invertible<PlanetSpace, UniverseSpace> for PlanetCoordinateConverter {
+    type Inverse = Self;
+
+    fn inverse(&self) -> Self::Inverse {
+        *self
+    }
+}
@@ -2,6 +2,11 @@
 
 use nebula_math::WorldPosition;
 
+use crate::ProjectionMethod;
+
+/// Default [`PlanetDef::influence_radius`] in multiples of the planet radius.
+pub const DEFAULT_INFLUENCE_RADIUS_FACTOR: i128 = 50;
+
 /// Definition of a planet in the universe.
 ///
 /// This is the immutable specification of a planet. It does not contain
@@ -27,6 +32,19 @@ pub struct PlanetDef {
     /// Combined with chunk addresses to produce deterministic, reproducible
     /// terrain for any chunk on the planet.
     pub seed: u64,
+
+    /// Radius of the planet's sphere of influence in mm, measured from the
+    /// center. Beyond it the planet no longer anchors gravity, atmosphere,
+    /// or LOD for a position.
+    ///
+    /// Defaults to `radius × DEFAULT_INFLUENCE_RADIUS_FACTOR`.
+    pub influence_radius: i128,
+
+    /// Cube-to-sphere mapping the planet's terrain was generated with.
+    ///
+    /// Recorded when the definition is created so chunks keep their
+    /// positions if [`ProjectionMethod::default`] changes later.
+    pub projection: ProjectionMethod,
 }
 
 impl PlanetDef {
@@ -42,9 +60,26 @@ impl PlanetDef {
             center,
             radius,
             seed,
+            influence_radius: radius.saturating_mul(DEFAULT_INFLUENCE_RADIUS_FACTOR),
+            projection: ProjectionMethod::default(),
         }
     }
 
+    /// Replace the cube-to-sphere projection.
+    #[must_use]
+    pub fn with_projection(mut self, projection: ProjectionMethod) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    /// Replace the sphere-of-influence radius (mm). Values inside the planet
+    /// are raised to its radius, so the surface is always within reach.
+    #[must_use]
+    pub fn with_influence_radius(mut self, influence_radius: i128) -> Self {
+        self.influence_radius = influence_radius.max(self.radius);
+        self
+    }
+
     /// Earth-like planet preset (radius 6,371 km).
     pub fn earth_like(name: impl Into<String>, center: WorldPosition, seed: u64) -> Self {
         Self::new(name, center, 6_371_000_000, seed)
@@ -79,6 +114,17 @@ impl PlanetDef {
         let dist_sq = dx * dx + dy * dy + dz * dz;
         dist_sq <= (self.radius as f64).powi(2)
     }
+
+    /// Distance from the planet's center to `pos` in mm.
+    ///
+    /// The offset is taken in i128 before converting, so only the final
+    /// distance is rounded, never the (possibly huge) absolute coordinates.
+    pub fn distance_mm(&self, pos: &WorldPosition) -> f64 {
+        let dx = (pos.x - self.center.x) as f64;
+        let dy = (pos.y - self.center.y) as f64;
+        let dz = (pos.z - self.center.z) as f64;
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
 }
 
 #[cfg(test)]
@@ -140,6 +186,27 @@ mod tests {
         assert!((area - expected).abs() / expected < 1e-10);
     }
 
+    #[test]
+    fn test_influence_radius_defaults_to_radius_multiple() {
+        let planet = PlanetDef::moon_like("Luna", WorldPosition::default(), 1);
+        assert_eq!(
+            planet.influence_radius,
+            planet.radius * DEFAULT_INFLUENCE_RADIUS_FACTOR
+        );
+        let planet = planet.with_influence_radius(66_100_000_000);
+        assert_eq!(planet.influence_radius, 66_100_000_000);
+        let planet = planet.with_influence_radius(1_000);
+        assert_eq!(planet.influence_radius, planet.radius);
+    }
+
+    #[test]
+    fn test_projection_is_recorded() {
+        let planet = PlanetDef::earth_like("Terra", WorldPosition::default(), 42);
+        assert_eq!(planet.projection, ProjectionMethod::Everitt);
+        let planet = planet.with_projection(ProjectionMethod::Gnomonic);
+        assert_eq!(planet.projection, ProjectionMethod::Gnomonic);
+    }
+
     #[test]
     fn test_presets() {
         let earth = PlanetDef::earth_like("E", WorldPosition::default(), 1);
@@ -2,13 +2,34 @@
 
 use std::collections::HashMap;
 
-use crate::PlanetDef;
+use nebula_math::{UNITS_PER_METER, WorldPosition};
+
+use crate::{PlanetCoordinateConverter, PlanetDef, PlanetId};
+
+/// What proximity queries measure the distance to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DistanceMode {
+    /// The planet's center.
+    Center,
+    /// The planet's base sphere (center distance minus radius), negative
+    /// below the surface. With planets of different sizes this can rank
+    /// planets differently from [`Center`](Self::Center).
+    Surface,
+}
 
 /// Registry of all planets in the current universe.
 ///
-/// Provides lookup by name and validates that no two planets overlap.
+/// Provides lookup by name, validates that no two planets overlap, keeps a
+/// [`PlanetCoordinateConverter`] for each planet, and answers proximity
+/// queries ([`nearest`](Self::nearest),
+/// [`nearest_planet`](Self::nearest_planet),
+/// [`dominant_influence`](Self::dominant_influence),
+/// [`within`](Self::within), [`planets_within`](Self::planets_within)) so
+/// gravity sources, impostors, LOD anchors, and the camera can find nearby
+/// planets per frame.
 pub struct PlanetRegistry {
     planets: Vec<PlanetDef>,
+    converters: Vec<PlanetCoordinateConverter>,
     name_index: HashMap<String, usize>,
 }
 
@@ -17,6 +38,7 @@ impl PlanetRegistry {
     pub fn new() -> Self {
         Self {
             planets: Vec::new(),
+            converters: Vec::new(),
             name_index: HashMap::new(),
         }
     }
@@ -48,6 +70,8 @@ impl PlanetRegistry {
 
         let idx = self.planets.len();
         self.name_index.insert(planet.name.clone(), idx);
+        self.converters
+            .push(PlanetCoordinateConverter::new(PlanetId(idx), &planet));
         self.planets.push(planet);
         Ok(idx)
     }
@@ -62,6 +86,16 @@ impl PlanetRegistry {
         self.planets.get(idx)
     }
 
+    /// Look up the ID of a planet by name.
+    pub fn id_of(&self, name: &str) -> Option<PlanetId> {
+        self.name_index.get(name).map(|&idx| PlanetId(idx))
+    }
+
+    /// Coordinate converter for the planet with the given ID.
+    pub fn converter_for(&self, planet_id: PlanetId) -> Option<&PlanetCoordinateConverter> {
+        self.converters.get(planet_id.0)
+    }
+
     /// Number of registered planets.
     pub fn len(&self) -> usize {
         self.planets.len()
@@ -76,6 +110,87 @@ impl PlanetRegistry {
     pub fn iter(&self) -> impl Iterator<Item = &PlanetDef> {
         self.planets.iter()
     }
+
+    /// The planet closest to `pos` by `mode`, with that distance in meters.
+    pub fn nearest(&self, pos: &WorldPosition, mode: DistanceMode) -> Option<(PlanetId, f64)> {
+        self.distances_m(pos, mode)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// The planet whose surface is closest to `pos`, with the distance to
+    /// that surface in meters (negative below it).
+    ///
+    /// Shorthand for [`nearest`](Self::nearest) with
+    /// [`DistanceMode::Surface`] that returns the planet itself.
+    pub fn nearest_planet(&self, pos: &WorldPosition) -> Option<(&PlanetDef, f64)> {
+        self.nearest(pos, DistanceMode::Surface)
+            .map(|(id, dist)| (&self.planets[id.0], dist))
+    }
+
+    /// Planets whose surfaces are within `radius_m` meters of `pos`, nearest
+    /// surface first, with their surface distances in meters.
+    pub fn planets_within(
+        &self,
+        pos: &WorldPosition,
+        radius_m: f64,
+    ) -> impl Iterator<Item = (&PlanetDef, f64)> {
+        self.within(pos, radius_m, DistanceMode::Surface)
+            .map(|(id, dist)| (&self.planets[id.0], dist))
+    }
+
+    /// The planet whose sphere of influence `pos` is in.
+    ///
+    /// Where spheres overlap, the planet with the smallest distance relative
+    /// to its [`influence_radius`](PlanetDef::influence_radius) wins, so a
+    /// point equidistant from two planets belongs to the one with the larger
+    /// sphere of influence. `None` outside every sphere.
+    pub fn dominant_influence(&self, pos: &WorldPosition) -> Option<PlanetId> {
+        self.planets
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, planet)| {
+                let reach = planet.influence_radius as f64;
+                let dist = planet.distance_mm(pos);
+                (dist <= reach).then_some((PlanetId(idx), dist / reach))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id)
+    }
+
+    /// Planets within `max_distance_m` meters of `pos` by `mode`, nearest
+    /// first, with their distances in meters.
+    pub fn within(
+        &self,
+        pos: &WorldPosition,
+        max_distance_m: f64,
+        mode: DistanceMode,
+    ) -> impl Iterator<Item = (PlanetId, f64)> + use<> {
+        let mut hits: Vec<(PlanetId, f64)> = self
+            .distances_m(pos, mode)
+            .filter(|&(_, dist)| dist <= max_distance_m)
+            .collect();
+        hits.sort_by(|a, b| a.1.total_cmp(&b.1));
+        hits.into_iter()
+    }
+
+    /// Every planet's ID and distance from `pos` by `mode`, in meters.
+    ///
+    /// [`WorldPosition::distance_meters`] takes the offset exactly in i128,
+    /// so only the final distance is rounded.
+    fn distances_m(
+        &self,
+        pos: &WorldPosition,
+        mode: DistanceMode,
+    ) -> impl Iterator<Item = (PlanetId, f64)> {
+        self.planets.iter().enumerate().map(move |(idx, planet)| {
+            let center_m = planet.center.distance_meters(pos);
+            let dist_m = match mode {
+                DistanceMode::Center => center_m,
+                DistanceMode::Surface => center_m - planet.radius as f64 / UNITS_PER_METER as f64,
+            };
+            (PlanetId(idx), dist_m)
+        })
+    }
 }
 
 impl Default for PlanetRegistry {
@@ -115,103 +230,5 @@ impl std::fmt::Display for PlanetRegistryError {
 impl std::error::Error for PlanetRegistryError {}
 
 #[cfg(test)]
-mod tests {
-    use nebula_math::WorldPosition;
-
-    use super::*;
-
-    #[test]
-    fn test_registry_lookup_by_name() {
-        let mut registry = PlanetRegistry::new();
-        let terra = PlanetDef::earth_like("Terra", WorldPosition::default(), 42);
-        registry.register(terra).unwrap();
-
-        let found = registry.get_by_name("Terra");
-        assert!(found.is_some());
-        assert_eq!(found.unwrap().name, "Terra");
-        assert_eq!(found.unwrap().seed, 42);
-
-        assert!(registry.get_by_name("Mars").is_none());
-    }
-
-    #[test]
-    fn test_multiple_planets_distinct_centers() {
-        let mut registry = PlanetRegistry::new();
-
-        let terra = PlanetDef::earth_like("Terra", WorldPosition::new(0, 0, 0), 42);
-        let luna = PlanetDef::moon_like("Luna", WorldPosition::new(384_400_000_000, 0, 0), 43);
-        let mars = PlanetDef::mars_like("Mars", WorldPosition::new(225_000_000_000_000, 0, 0), 44);
-
-        registry.register(terra).unwrap();
-        registry.register(luna).unwrap();
-        registry.register(mars).unwrap();
-
-        assert_eq!(registry.len(), 3);
-
-        let t = registry.get_by_name("Terra").unwrap();
-        let l = registry.get_by_name("Luna").unwrap();
-        let m = registry.get_by_name("Mars").unwrap();
-
-        assert_ne!(t.center, l.center);
-        assert_ne!(t.center, m.center);
-        assert_ne!(l.center, m.center);
-    }
-
-    #[test]
-    fn test_duplicate_name_rejected() {
-        let mut registry = PlanetRegistry::new();
-        let p1 = PlanetDef::earth_like("Terra", WorldPosition::default(), 1);
-        let p2 = PlanetDef::earth_like("Terra", WorldPosition::new(999_999_999_999, 0, 0), 2);
-
-        registry.register(p1).unwrap();
-        let result = registry.register(p2);
-        assert!(result.is_err());
-        match result.unwrap_err() {
-            PlanetRegistryError::DuplicateName(name) => assert_eq!(name, "Terra"),
-            _ => panic!("Expected DuplicateName error"),
-        }
-    }
-
-    #[test]
-    fn test_overlapping_planets_rejected() {
-        let mut registry = PlanetRegistry::new();
-        let p1 = PlanetDef::earth_like("Terra", WorldPosition::default(), 1);
-        let p2 = PlanetDef::earth_like("TooClose", WorldPosition::new(1_000_000_000, 0, 0), 2);
-
-        registry.register(p1).unwrap();
-        let result = registry.register(p2);
-        assert!(result.is_err());
-        match result.unwrap_err() {
-            PlanetRegistryError::Overlap { new, existing } => {
-                assert_eq!(new, "TooClose");
-                assert_eq!(existing, "Terra");
-            }
-            _ => panic!("Expected Overlap error"),
-        }
-    }
-
-    #[test]
-    fn test_get_by_index() {
-        let mut registry = PlanetRegistry::new();
-        let terra = PlanetDef::earth_like("Terra", WorldPosition::default(), 42);
-        let idx = registry.register(terra).unwrap();
-
-        let found = registry.get_by_index(idx).unwrap();
-        assert_eq!(found.name, "Terra");
-        assert!(registry.get_by_index(999).is_none());
-    }
-
-    #[test]
-    fn test_empty_registry() {
-        let registry = PlanetRegistry::new();
-        assert!(registry.is_empty());
-        assert_eq!(registry.len(), 0);
-        assert_eq!(registry.iter().count(), 0);
-    }
-
-    #[test]
-    fn test_default_registry() {
-        let registry = PlanetRegistry::default();
-        assert!(registry.is_empty());
-    }
-}
+#[path = "planet_registry_tests.rs"]
+mod tests;
@@ -0,0 +1,338 @@
+//! Unit tests for planet registration, coordinate converters, and proximity queries.
+
+use super::*;
+use glam::UVec3;
+use nebula_coords::{
+    Invertible, PlanetSpace, PlanetToChunk, Pos, Transition, TransitionExt, UniverseSpace, Vec3I64,
+};
+use nebula_math::{Vec3I128, WorldPosition};
+
+#[test]
+fn test_registry_lookup_by_name() {
+    let mut registry = PlanetRegistry::new();
+    let terra = PlanetDef::earth_like("Terra", WorldPosition::default(), 42);
+    registry.register(terra).unwrap();
+
+    let found = registry.get_by_name("Terra");
+    assert!(found.is_some());
+    assert_eq!(found.unwrap().name, "Terra");
+    assert_eq!(found.unwrap().seed, 42);
+
+    assert!(registry.get_by_name("Mars").is_none());
+}
+
+#[test]
+fn test_multiple_planets_distinct_centers() {
+    let mut registry = PlanetRegistry::new();
+
+    let terra = PlanetDef::earth_like("Terra", WorldPosition::new(0, 0, 0), 42);
+    let luna = PlanetDef::moon_like("Luna", WorldPosition::new(384_400_000_000, 0, 0), 43);
+    let mars = PlanetDef::mars_like("Mars", WorldPosition::new(225_000_000_000_000, 0, 0), 44);
+
+    registry.register(terra).unwrap();
+    registry.register(luna).unwrap();
+    registry.register(mars).unwrap();
+
+    assert_eq!(registry.len(), 3);
+
+    let t = registry.get_by_name("Terra").unwrap();
+    let l = registry.get_by_name("Luna").unwrap();
+    let m = registry.get_by_name("Mars").unwrap();
+
+    assert_ne!(t.center, l.center);
+    assert_ne!(t.center, m.center);
+    assert_ne!(l.center, m.center);
+}
+
+#[test]
+fn test_duplicate_name_rejected() {
+    let mut registry = PlanetRegistry::new();
+    let p1 = PlanetDef::earth_like("Terra", WorldPosition::default(), 1);
+    let p2 = PlanetDef::earth_like("Terra", WorldPosition::new(999_999_999_999, 0, 0), 2);
+
+    registry.register(p1).unwrap();
+    let result = registry.register(p2);
+    assert!(result.is_err());
+    match result.unwrap_err() {
+        PlanetRegistryError::DuplicateName(name) => assert_eq!(name, "Terra"),
+        _ => panic!("Expected DuplicateName error"),
+    }
+}
+
+#[test]
+fn test_overlapping_planets_rejected() {
+    let mut registry = PlanetRegistry::new();
+    let p1 = PlanetDef::earth_like("Terra", WorldPosition::default(), 1);
+    let p2 = PlanetDef::earth_like("TooClose", WorldPosition::new(1_000_000_000, 0, 0), 2);
+
+    registry.register(p1).unwrap();
+    let result = registry.register(p2);
+    assert!(result.is_err());
+    match result.unwrap_err() {
+        PlanetRegistryError::Overlap { new, existing } => {
+            assert_eq!(new, "TooClose");
+            assert_eq!(existing, "Terra");
+        }
+        _ => panic!("Expected Overlap error"),
+    }
+}
+
+#[test]
+fn test_get_by_index() {
+    let mut registry = PlanetRegistry::new();
+    let terra = PlanetDef::earth_like("Terra", WorldPosition::default(), 42);
+    let idx = registry.register(terra).unwrap();
+
+    let found = registry.get_by_index(idx).unwrap();
+    assert_eq!(found.name, "Terra");
+    assert!(registry.get_by_index(999).is_none());
+}
+
+#[test]
+fn test_converter_round_trip_within_one_mm() {
+    let mut registry = PlanetRegistry::new();
+    let center = WorldPosition::new(384_400_000_000, -12_345_678, 9_876_543_210);
+    registry
+        .register(PlanetDef::moon_like("Luna", center, 43))
+        .unwrap();
+    let id = registry.id_of("Luna").unwrap();
+    let converter = registry.converter_for(id).unwrap();
+    assert_eq!(converter.planet_id, id);
+    assert_eq!(
+        converter.radius_mm as i128,
+        registry.get_by_name("Luna").unwrap().radius
+    );
+
+    let original = Pos::<UniverseSpace, Vec3I128>::new(Vec3I128::new(
+        center.x + 1_737_400_000,
+        center.y - 250_001,
+        center.z + 7,
+    ));
+    let planet: Pos<PlanetSpace, Vec3I64> = converter.apply(&original);
+    assert_eq!(planet.value, Vec3I64::new(1_737_400_000, -250_001, 7));
+
+    let back: Pos<UniverseSpace, Vec3I128> = converter.apply(&planet);
+    let delta = back.value - original.value;
+    assert!(delta.x.abs() <= 1 && delta.y.abs() <= 1 && delta.z.abs() <= 1);
+}
+
+#[test]
+fn test_converter_chains_with_then() {
+    let mut registry = PlanetRegistry::new();
+    let center = WorldPosition::new(5_000_000_000_000, 0, 0);
+    let idx = registry
+        .register(PlanetDef::earth_like("Terra", center, 1))
+        .unwrap();
+    let converter = *registry.converter_for(PlanetId(idx)).unwrap();
+
+    let chunk_origin = Vec3I64::new(6_371_000_000, 0, 0);
+    let to_chunk = converter.then(PlanetToChunk { chunk_origin });
+    let surface = Pos::<UniverseSpace, Vec3I128>::new(Vec3I128::new(
+        center.x + 6_371_000_250,
+        center.y + 40,
+        center.z + 3,
+    ));
+    assert_eq!(to_chunk.apply(&surface).value, UVec3::new(250, 40, 3));
+
+    let inverse: PlanetCoordinateConverter =
+        Invertible::<UniverseSpace, PlanetSpace>::inverse(&converter);
+    let planet = Transition::<UniverseSpace, PlanetSpace>::apply(&converter, &surface);
+    assert_eq!(inverse.apply(&planet).value, surface.value);
+}
+
+#[test]
+fn test_converter_for_unknown_id() {
+    let registry = PlanetRegistry::new();
+    assert!(registry.converter_for(PlanetId(0)).is_none());
+    assert!(registry.id_of("Terra").is_none());
+}
+
+/// Terra with a 100,000 km sphere of influence and Luna with 40,000 km,
+/// 120,000 km apart along +X.
+fn terra_and_luna() -> PlanetRegistry {
+    let mut registry = PlanetRegistry::new();
+    registry
+        .register(
+            PlanetDef::earth_like("Terra", WorldPosition::new(0, 0, 0), 1)
+                .with_influence_radius(100_000_000_000),
+        )
+        .unwrap();
+    registry
+        .register(
+            PlanetDef::moon_like("Luna", WorldPosition::new(120_000_000_000, 0, 0), 2)
+                .with_influence_radius(40_000_000_000),
+        )
+        .unwrap();
+    registry
+}
+
+#[test]
+fn test_halfway_point_resolves_to_stronger_influence() {
+    let registry = terra_and_luna();
+    let halfway = WorldPosition::new(60_000_000_000, 0, 0);
+    assert_eq!(registry.dominant_influence(&halfway), Some(PlanetId(0)));
+
+    // Close to Luna its own sphere dominates, though Terra's still reaches.
+    let near_luna = WorldPosition::new(95_000_000_000, 0, 0);
+    assert_eq!(registry.dominant_influence(&near_luna), Some(PlanetId(1)));
+    assert_eq!(
+        registry
+            .nearest(&near_luna, DistanceMode::Center)
+            .map(|(id, _)| id),
+        Some(PlanetId(1))
+    );
+}
+
+#[test]
+fn test_outside_every_influence_is_none() {
+    let registry = terra_and_luna();
+    let far = WorldPosition::new(0, 500_000_000_000, 0);
+    assert_eq!(registry.dominant_influence(&far), None);
+    assert_eq!(
+        registry
+            .nearest(&far, DistanceMode::Center)
+            .map(|(id, _)| id),
+        Some(PlanetId(0))
+    );
+    assert!(
+        PlanetRegistry::new()
+            .nearest(&far, DistanceMode::Center)
+            .is_none()
+    );
+}
+
+#[test]
+fn test_distances_exact_far_from_origin() {
+    // Centers ~1e24 mm from the origin, beyond f32 and f64 integer precision.
+    let base = 1_000_000_000_000_000_000_000_000_i128;
+    let mut registry = PlanetRegistry::new();
+    registry
+        .register(PlanetDef::earth_like(
+            "Far",
+            WorldPosition::new(base, -base, base),
+            1,
+        ))
+        .unwrap();
+    let pos = WorldPosition::new(base + 6_371_000_123, -base, base);
+    let (id, dist) = registry.nearest(&pos, DistanceMode::Center).unwrap();
+    assert_eq!(id, PlanetId(0));
+    assert_eq!(dist, 6_371_000.123);
+}
+
+#[test]
+fn test_within_sorted_ascending() {
+    let registry = terra_and_luna();
+    let pos = WorldPosition::new(100_000_000_000, 0, 0);
+    let hits: Vec<_> = registry
+        .within(&pos, 150_000_000.0, DistanceMode::Center)
+        .collect();
+    assert_eq!(
+        hits,
+        vec![(PlanetId(1), 20_000_000.0), (PlanetId(0), 100_000_000.0)]
+    );
+    assert_eq!(
+        registry
+            .within(&pos, 50_000_000.0, DistanceMode::Center)
+            .count(),
+        1
+    );
+    assert_eq!(registry.within(&pos, 1.0, DistanceMode::Center).count(), 0);
+}
+
+/// Terra, Luna 120,000 km along +X, and Mars 50,000 km along -X.
+fn three_planets() -> PlanetRegistry {
+    let mut registry = terra_and_luna();
+    registry
+        .register(PlanetDef::mars_like(
+            "Mars",
+            WorldPosition::new(-50_000_000_000, 0, 0),
+            3,
+        ))
+        .unwrap();
+    registry
+}
+
+#[test]
+fn test_nearest_by_surface_distance() {
+    let registry = three_planets();
+
+    let pos = WorldPosition::new(-30_000_000_000, 0, 0);
+    let (id, dist) = registry.nearest(&pos, DistanceMode::Surface).unwrap();
+    assert_eq!(registry.get_by_index(id.0).unwrap().name, "Mars");
+    assert_eq!(dist, 20_000_000.0 - 3_389_500.0);
+
+    // Luna's center is closer, but Terra's surface is.
+    let pos = WorldPosition::new(62_000_000_000, 0, 0);
+    let (id, dist) = registry.nearest(&pos, DistanceMode::Surface).unwrap();
+    assert_eq!(id, PlanetId(0));
+    assert_eq!(dist, 62_000_000.0 - 6_371_000.0);
+    assert_eq!(
+        registry
+            .nearest(&pos, DistanceMode::Center)
+            .map(|(id, _)| id),
+        Some(PlanetId(1))
+    );
+
+    assert!(
+        PlanetRegistry::new()
+            .nearest(&pos, DistanceMode::Surface)
+            .is_none()
+    );
+}
+
+#[test]
+fn test_within_surface_radius() {
+    let registry = three_planets();
+    let pos = WorldPosition::new(62_000_000_000, 0, 0);
+    let ids: Vec<_> = registry
+        .within(&pos, 150_000_000.0, DistanceMode::Surface)
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(ids, [PlanetId(0), PlanetId(1), PlanetId(2)]);
+    let surface = |max| registry.within(&pos, max, DistanceMode::Surface).count();
+    assert_eq!(surface(56_000_000.0), 1);
+    assert_eq!(surface(1_000.0), 0);
+}
+
+#[test]
+fn test_nearest_planet_returns_def_and_surface_distance() {
+    let registry = three_planets();
+    let pos = WorldPosition::new(-30_000_000_000, 0, 0);
+    let (planet, dist) = registry.nearest_planet(&pos).unwrap();
+    assert_eq!(planet.name, "Mars");
+    assert_eq!(dist, 20_000_000.0 - 3_389_500.0);
+
+    // Below Terra's surface the distance goes negative.
+    let (planet, dist) = registry
+        .nearest_planet(&WorldPosition::new(6_000_000_000, 0, 0))
+        .unwrap();
+    assert_eq!(planet.name, "Terra");
+    assert_eq!(dist, 6_000_000.0 - 6_371_000.0);
+    assert!(PlanetRegistry::new().nearest_planet(&pos).is_none());
+}
+
+#[test]
+fn test_planets_within_nearest_surface_first() {
+    let registry = three_planets();
+    let pos = WorldPosition::new(62_000_000_000, 0, 0);
+    let names: Vec<_> = registry
+        .planets_within(&pos, 150_000_000.0)
+        .map(|(planet, _)| planet.name.as_str())
+        .collect();
+    assert_eq!(names, ["Terra", "Luna", "Mars"]);
+    assert_eq!(registry.planets_within(&pos, 56_000_000.0).count(), 1);
+}
+
+#[test]
+fn test_empty_registry() {
+    let registry = PlanetRegistry::new();
+    assert!(registry.is_empty());
+    assert_eq!(registry.len(), 0);
+    assert_eq!(registry.iter().count(), 0);
+}
+
+#[test]
+fn test_default_registry() {
+    let registry = PlanetRegistry::default();
+    assert!(registry.is_empty());
+}
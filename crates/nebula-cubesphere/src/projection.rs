@@ -1,23 +1,37 @@
 //! Cube-to-sphere projection methods.
 //!
-//! Provides two projection approaches:
+//! Provides four projection approaches:
+//! - **Gnomonic**: Normalize the cube point directly; simplest, most stretched.
 //! - **Tangent warp**: Fast approximate equal-area via `tan(x * π/4)` remapping.
 //! - **Everitt**: Analytic mapping with better area uniformity for terrain generation.
+//! - **COBE**: The COBE quadrilateralized spherical cube, a polynomial fit with
+//!   the most uniform areas of the four.
+//!
+//! [`projection_distortion`] measures how much each method stretches the
+//! face grid, for comparing texel density.
 
-use std::f64::consts::FRAC_PI_4;
+use std::f64::consts::{FRAC_PI_4, PI};
 
 use glam::DVec3;
 
-use crate::FaceCoord;
+use crate::{CubeFace, FaceCoord};
 
 /// Selects which cube-to-sphere projection method to use.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum ProjectionMethod {
+    /// Gnomonic (identity) projection: the cube point normalized as is.
+    /// Cells near face corners cover about five times less area than at
+    /// the face center.
+    Gnomonic,
     /// Tangent-warp projection: faster, slightly less uniform.
     TangentWarp,
     /// Everitt/Mathworld analytic projection: better area uniformity.
     #[default]
     Everitt,
+    /// COBE quadrilateralized spherical cube (Chan & O'Neill, 1975): the
+    /// most uniform cell areas, at the cost of a degree-12 polynomial per
+    /// axis.
+    Cobe,
 }
 
 /// Convert a [`FaceCoord`] to a point on the surface of the `[-1, 1]` cube.
@@ -45,19 +59,15 @@ pub fn face_coord_to_cube_point(fc: &FaceCoord) -> DVec3 {
 #[inline]
 #[must_use]
 pub fn face_coord_to_sphere(fc: &FaceCoord) -> DVec3 {
-    let s = 2.0 * fc.u - 1.0;
-    let t = 2.0 * fc.v - 1.0;
-
-    // tan(π/4) = 1, so tan(x * π/4) is identity at x = ±1 and warps the interior.
-    let ws = (s * FRAC_PI_4).tan();
-    let wt = (t * FRAC_PI_4).tan();
-
-    let n = fc.face.normal();
-    let tang = fc.face.tangent();
-    let bitan = fc.face.bitangent();
+    project(fc, ProjectionMethod::TangentWarp)
+}
 
-    let cube_point = n + ws * tang + wt * bitan;
-    cube_point.normalize()
+/// Project a [`FaceCoord`] onto the unit sphere by normalizing its cube
+/// point (gnomonic projection).
+#[inline]
+#[must_use]
+pub fn face_coord_to_sphere_gnomonic(fc: &FaceCoord) -> DVec3 {
+    project(fc, ProjectionMethod::Gnomonic)
 }
 
 /// Analytic cube-to-sphere using the Everitt/Mathworld mapping.
@@ -84,24 +94,117 @@ pub fn cube_to_sphere_everitt(cube_point: DVec3) -> DVec3 {
     )
 }
 
+/// COBE quad-sphere warp of one face-plane coordinate.
+///
+/// Maps `x` (the axis being warped) to the gnomonic coordinate `χ` given
+/// the other axis `y`, using the deprojection polynomial of the COBE
+/// quadrilateralized spherical cube as tabulated in Calabretta & Greisen
+/// (2002), *Representations of celestial coordinates in FITS*, §5.6.2. The
+/// `x(1 - x²)` factor keeps face edges and centers fixed.
+fn cobe_warp(x: f64, y: f64) -> f64 {
+    const P: [&[f64]; 7] = [
+        &[
+            -0.27292696,
+            -0.07629969,
+            -0.22797056,
+            0.54852384,
+            -0.62930065,
+            0.25795794,
+            0.02584375,
+        ],
+        &[
+            -0.02819452,
+            -0.01471565,
+            0.48051509,
+            -1.74114454,
+            1.71547508,
+            -0.53022337,
+        ],
+        &[0.27058160, -0.56800938, 0.30803317, 0.98938102, -0.83180469],
+        &[-0.60441560, 1.50880086, -0.93678576, 0.08693841],
+        &[0.93412077, -1.41601920, 0.33887446],
+        &[-0.63915306, 0.52032238],
+        &[0.14381585],
+    ];
+    let (xx, yy) = (x * x, y * y);
+    // Horner in x² for each power of y², then in y².
+    let sum = P.iter().rev().fold(0.0, |acc, row| {
+        acc * yy + row.iter().rev().fold(0.0, |z, &p| z * xx + p)
+    });
+    x + x * (1.0 - xx) * sum
+}
+
+/// Project a [`FaceCoord`] onto the unit sphere using the COBE
+/// quadrilateralized spherical cube.
+#[inline]
+#[must_use]
+pub fn face_coord_to_sphere_cobe(fc: &FaceCoord) -> DVec3 {
+    project(fc, ProjectionMethod::Cobe)
+}
+
 /// Convenience: [`FaceCoord`] → unit sphere using the Everitt mapping.
 #[inline]
 #[must_use]
 pub fn face_coord_to_sphere_everitt(fc: &FaceCoord) -> DVec3 {
-    let cube_point = face_coord_to_cube_point(fc);
-    cube_to_sphere_everitt(cube_point)
+    project(fc, ProjectionMethod::Everitt)
 }
 
 /// Project a [`FaceCoord`] onto the unit sphere using the specified method.
 #[inline]
 #[must_use]
 pub fn project(fc: &FaceCoord, method: ProjectionMethod) -> DVec3 {
+    project_st(fc.face, 2.0 * fc.u - 1.0, 2.0 * fc.v - 1.0, method)
+}
+
+/// Project face-plane coordinates `s`, `t` (each `[-1, 1]` on the face,
+/// i.e. `2u - 1` and `2v - 1`) onto the unit sphere.
+///
+/// Unlike [`project`] this accepts coordinates slightly past the face edge,
+/// which numerical derivatives at the edges need.
+#[inline]
+pub(crate) fn project_st(face: CubeFace, s: f64, t: f64, method: ProjectionMethod) -> DVec3 {
+    let n = face.normal();
+    let tang = face.tangent();
+    let bitan = face.bitangent();
+
     match method {
-        ProjectionMethod::TangentWarp => face_coord_to_sphere(fc),
-        ProjectionMethod::Everitt => face_coord_to_sphere_everitt(fc),
+        ProjectionMethod::Gnomonic => (n + s * tang + t * bitan).normalize(),
+        ProjectionMethod::TangentWarp => {
+            // tan(π/4) = 1, so tan(x * π/4) is identity at x = ±1 and warps the interior.
+            let ws = (s * FRAC_PI_4).tan();
+            let wt = (t * FRAC_PI_4).tan();
+            (n + ws * tang + wt * bitan).normalize()
+        }
+        ProjectionMethod::Everitt => cube_to_sphere_everitt(n + s * tang + t * bitan),
+        ProjectionMethod::Cobe => {
+            let chi = cobe_warp(s, t);
+            let psi = cobe_warp(t, s);
+            (n + chi * tang + psi * bitan).normalize()
+        }
     }
 }
 
+/// Local area scale factor of `method` at `fc`.
+///
+/// The ratio of sphere area to face-UV area around `fc`, normalized so that
+/// a perfectly equal-area projection returns `1.0` everywhere. Values above
+/// one mean grid cells there cover more of the sphere (lower texel
+/// density); below one, less.
+#[must_use]
+pub fn projection_distortion(fc: &FaceCoord, method: ProjectionMethod) -> f64 {
+    const H: f64 = 1e-6;
+    let s = 2.0 * fc.u - 1.0;
+    let t = 2.0 * fc.v - 1.0;
+    let at = |s, t| project_st(fc.face, s, t, method);
+    let dp_ds = (at(s + H, t) - at(s - H, t)) / (2.0 * H);
+    let dp_dt = (at(s, t + H) - at(s, t - H)) / (2.0 * H);
+
+    // d(s, t) = 4 d(u, v), and each face covers 4π/6 of the unit sphere
+    // over one unit of UV area.
+    let area_per_uv = 4.0 * dp_ds.cross(dp_dt).length();
+    area_per_uv / (4.0 * PI / 6.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,6 +331,89 @@ mod tests {
         assert_eq!(project(&fc, ProjectionMethod::Everitt), everitt_result);
     }
 
+    #[test]
+    fn test_gnomonic_is_normalized_cube_point() {
+        let fc = FaceCoord::new(CubeFace::NegY, 0.15, 0.8);
+        let expected = face_coord_to_cube_point(&fc).normalize();
+        assert!((project(&fc, ProjectionMethod::Gnomonic) - expected).length() < EPSILON);
+        assert_eq!(face_coord_to_sphere_gnomonic(&fc), expected);
+    }
+
+    #[test]
+    fn test_all_methods_agree_at_face_center_and_corners() {
+        for face in CubeFace::ALL {
+            for (u, v) in [(0.5, 0.5), (0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)] {
+                let fc = FaceCoord::new(face, u, v);
+                let gnomonic = project(&fc, ProjectionMethod::Gnomonic);
+                for method in [
+                    ProjectionMethod::TangentWarp,
+                    ProjectionMethod::Everitt,
+                    ProjectionMethod::Cobe,
+                ] {
+                    assert!((project(&fc, method) - gnomonic).length() < EPSILON);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_distortion_averages_to_one() {
+        let n = 64;
+        for method in [
+            ProjectionMethod::Gnomonic,
+            ProjectionMethod::TangentWarp,
+            ProjectionMethod::Everitt,
+            ProjectionMethod::Cobe,
+        ] {
+            let mut sum = 0.0;
+            for i in 0..n {
+                for j in 0..n {
+                    let u = (i as f64 + 0.5) / n as f64;
+                    let v = (j as f64 + 0.5) / n as f64;
+                    sum += projection_distortion(&FaceCoord::new(CubeFace::PosZ, u, v), method);
+                }
+            }
+            let mean = sum / (n * n) as f64;
+            assert!((mean - 1.0).abs() < 1e-3, "{method:?} mean {mean}");
+        }
+    }
+
+    /// Ratio of the largest to the smallest area distortion over a 21×21
+    /// grid of one face, edges included.
+    fn area_spread(method: ProjectionMethod) -> f64 {
+        let (mut min, mut max) = (f64::MAX, 0.0f64);
+        for i in 0..=20 {
+            for j in 0..=20 {
+                let fc = FaceCoord::new(CubeFace::PosX, i as f64 / 20.0, j as f64 / 20.0);
+                let d = projection_distortion(&fc, method);
+                min = min.min(d);
+                max = max.max(d);
+            }
+        }
+        max / min
+    }
+
+    #[test]
+    fn test_everitt_stretches_less_than_gnomonic() {
+        let gnomonic = area_spread(ProjectionMethod::Gnomonic);
+        let tangent = area_spread(ProjectionMethod::TangentWarp);
+        let everitt = area_spread(ProjectionMethod::Everitt);
+        // Gnomonic: √3³ ≈ 5.2 between face center and corner.
+        assert!((gnomonic - 3f64.sqrt().powi(3)).abs() < 1e-3, "{gnomonic}");
+        assert!(
+            everitt < tangent && tangent < gnomonic,
+            "{everitt} {tangent} {gnomonic}"
+        );
+        assert!(everitt < 2.0, "{everitt}");
+    }
+
+    #[test]
+    fn test_cobe_areas_more_uniform_than_everitt() {
+        let cobe = area_spread(ProjectionMethod::Cobe);
+        let everitt = area_spread(ProjectionMethod::Everitt);
+        assert!(cobe < everitt, "cobe {cobe} everitt {everitt}");
+    }
+
     #[test]
     fn test_default_projection_method_is_everitt() {
         assert_eq!(ProjectionMethod::default(), ProjectionMethod::Everitt);
@@ -129,8 +129,76 @@ impl QuadNode {
             QuadNode::Leaf { address } | QuadNode::Branch { address, .. } => *address,
         }
     }
+
+    /// Total number of nodes in this subtree, including this one.
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        match self {
+            QuadNode::Leaf { .. } => 1,
+            QuadNode::Branch { children, .. } => {
+                1 + children.iter().map(QuadNode::node_count).sum::<usize>()
+            }
+        }
+    }
+
+    fn find_mut(&mut self, target: ChunkAddress) -> Option<&mut QuadNode> {
+        if self.address() == target {
+            return Some(self);
+        }
+        match self {
+            QuadNode::Leaf { .. } => None,
+            QuadNode::Branch { children, .. } => {
+                children.iter_mut().find_map(|child| child.find_mut(target))
+            }
+        }
+    }
+
+    /// Appends every branch whose children are all leaves, together with
+    /// the highest leaf priority under it.
+    fn collect_mergeable(
+        &self,
+        priority_fn: &impl Fn(&QuadNode) -> f32,
+        out: &mut Vec<(ChunkAddress, f32)>,
+    ) {
+        if let QuadNode::Branch { address, children } = self {
+            if children.iter().all(QuadNode::is_leaf) {
+                let priority = children
+                    .iter()
+                    .map(priority_fn)
+                    .fold(f32::NEG_INFINITY, f32::max);
+                out.push((*address, priority));
+            } else {
+                for child in children.iter() {
+                    child.collect_mergeable(priority_fn, out);
+                }
+            }
+        }
+    }
+}
+
+/// Errors from budgeted quadtree operations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QuadtreeBudgetError {
+    /// Subdividing would take the tree past its node budget.
+    BudgetExceeded,
+    /// No node with the requested address exists in the tree.
+    NodeNotFound(ChunkAddress),
+    /// The node is already a branch or sits at LOD 0.
+    CannotSubdivide(ChunkAddress),
+}
+
+impl std::fmt::Display for QuadtreeBudgetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BudgetExceeded => write!(f, "Quadtree node budget exceeded"),
+            Self::NodeNotFound(addr) => write!(f, "No quadtree node at {addr:?}"),
+            Self::CannotSubdivide(addr) => write!(f, "Quadtree node {addr:?} cannot subdivide"),
+        }
+    }
 }
 
+impl std::error::Error for QuadtreeBudgetError {}
+
 /// The quadtree for one cube face.
 ///
 /// The root node covers the entire face (LOD = `MAX_LOD`, x=0, y=0).
@@ -140,6 +208,9 @@ pub struct FaceQuadtree {
     pub face: CubeFace,
     /// The root node of the quadtree.
     pub root: QuadNode,
+    /// Upper bound on [`FaceQuadtree::node_count`] enforced by the budgeted
+    /// operations, if any.
+    max_nodes: Option<usize>,
 }
 
 impl FaceQuadtree {
@@ -151,8 +222,86 @@ impl FaceQuadtree {
             root: QuadNode::Leaf {
                 address: ChunkAddress::new(face, ChunkAddress::MAX_LOD, 0, 0),
             },
+            max_nodes: None,
+        }
+    }
+
+    /// Create a new quadtree that holds at most `max_nodes` nodes when grown
+    /// through [`FaceQuadtree::subdivide_with_budget`].
+    #[must_use]
+    pub fn new_with_budget(face: CubeFace, max_nodes: usize) -> Self {
+        Self {
+            max_nodes: Some(max_nodes),
+            ..Self::new(face)
         }
     }
+
+    /// The node budget, or `None` if the tree is unbounded.
+    #[must_use]
+    pub fn max_nodes(&self) -> Option<usize> {
+        self.max_nodes
+    }
+
+    /// Total number of nodes (branches and leaves) in the tree.
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.root.node_count()
+    }
+
+    /// Subdivide the leaf at `node`'s address, unless the four new children
+    /// would take the tree past its budget.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`QuadtreeBudgetError::BudgetExceeded`] if subdividing would
+    /// exceed the budget, [`QuadtreeBudgetError::NodeNotFound`] if the tree
+    /// has no node at that address, and
+    /// [`QuadtreeBudgetError::CannotSubdivide`] if the node is a branch or at
+    /// LOD 0.
+    pub fn subdivide_with_budget(&mut self, node: QuadNode) -> Result<(), QuadtreeBudgetError> {
+        let address = node.address();
+        if let Some(max) = self.max_nodes
+            && self.node_count() + 4 > max
+        {
+            return Err(QuadtreeBudgetError::BudgetExceeded);
+        }
+        let target = self
+            .root
+            .find_mut(address)
+            .ok_or(QuadtreeBudgetError::NodeNotFound(address))?;
+        if !target.is_leaf() || address.lod == 0 {
+            return Err(QuadtreeBudgetError::CannotSubdivide(address));
+        }
+        target.subdivide();
+        Ok(())
+    }
+
+    /// Merge the lowest-priority leaves back into their parents until the
+    /// tree is within budget. Returns the number of merges performed.
+    ///
+    /// Only branches whose children are all leaves are candidates; each is
+    /// ranked by the highest `priority_fn` value among its four leaves, so a
+    /// single important leaf keeps its siblings alive. No-op for unbounded
+    /// trees.
+    pub fn evict_lowest_priority(&mut self, priority_fn: impl Fn(&QuadNode) -> f32) -> usize {
+        let Some(max) = self.max_nodes else {
+            return 0;
+        };
+        let mut merges = 0;
+        while self.node_count() > max {
+            let mut candidates = Vec::new();
+            self.root.collect_mergeable(&priority_fn, &mut candidates);
+            let Some((address, _)) = candidates.into_iter().min_by(|a, b| a.1.total_cmp(&b.1))
+            else {
+                break;
+            };
+            if let Some(node) = self.root.find_mut(address) {
+                node.merge();
+                merges += 1;
+            }
+        }
+        merges
+    }
 }
 
 #[cfg(test)]
@@ -272,6 +421,65 @@ mod tests {
         assert_eq!(all_leaves.len(), 3 + 3 + 4);
     }
 
+    #[test]
+    fn test_budget_caps_node_count() {
+        let mut tree = FaceQuadtree::new_with_budget(CubeFace::PosX, 100);
+        let mut refused = false;
+        for _ in 0..64 {
+            // Always grow the coarsest leaf so the tree spreads out.
+            let leaves = tree.root.all_leaves();
+            let coarsest = leaves.iter().max_by_key(|a| a.lod).copied();
+            let leaf = QuadNode::Leaf {
+                address: coarsest.expect("tree has leaves"),
+            };
+            match tree.subdivide_with_budget(leaf) {
+                Ok(()) => {}
+                Err(QuadtreeBudgetError::BudgetExceeded) => refused = true,
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+            assert!(tree.node_count() <= 100, "{} nodes", tree.node_count());
+        }
+        assert!(refused, "budget was never hit");
+        assert_eq!(tree.node_count(), 97);
+    }
+
+    #[test]
+    fn test_eviction_merges_lowest_priority_first() {
+        let mut tree = FaceQuadtree::new_with_budget(CubeFace::PosZ, 9);
+        tree.root.subdivide();
+        let (low, high) = match &mut tree.root {
+            QuadNode::Branch { children, .. } => {
+                children[0].subdivide();
+                children[3].subdivide();
+                (children[0].address(), children[3].address())
+            }
+            QuadNode::Leaf { .. } => unreachable!(),
+        };
+        assert_eq!(tree.node_count(), 13);
+
+        // Leaves under `high` matter more than leaves under `low`.
+        let merges = tree.evict_lowest_priority(|node| {
+            let addr = node.address();
+            if addr.lod < high.lod && addr.x >= high.x * 2 {
+                10.0
+            } else {
+                1.0
+            }
+        });
+
+        assert_eq!(merges, 1);
+        assert_eq!(tree.node_count(), 9);
+        let leaves = tree.root.all_leaves();
+        assert!(
+            leaves.contains(&low),
+            "low-priority branch should be merged"
+        );
+        assert!(
+            !leaves.contains(&high),
+            "high-priority branch should survive"
+        );
+    }
+
     #[test]
     fn test_find_leaf_in_unsubdivided_tree() {
         let tree = FaceQuadtree::new(CubeFace::NegY);
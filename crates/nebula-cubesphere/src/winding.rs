@@ -7,7 +7,7 @@ use glam::DVec3;
 
 use crate::cube_face::CubeFace;
 use crate::face_coord::FaceCoord;
-use crate::projection::face_coord_to_sphere_everitt;
+use crate::projection::{ProjectionMethod, project};
 
 /// Check if a triangle has correct outward-facing winding order.
 ///
@@ -35,28 +35,29 @@ pub fn triangle_winds_outward(v0: DVec3, v1: DVec3, v2: DVec3) -> bool {
 /// This is determined by checking whether the cross product of the
 /// face's tangent and bitangent (in the order used for mesh generation)
 /// produces a normal that matches the face's outward normal, after
-/// projection onto the sphere.
-pub fn face_needs_winding_flip(face: CubeFace) -> bool {
+/// projection onto the sphere with `projection`.
+pub fn face_needs_winding_flip(face: CubeFace, projection: ProjectionMethod) -> bool {
     let fc0 = FaceCoord::new(face, 0.5, 0.5);
     let fc1 = FaceCoord::new(face, 0.501, 0.5);
     let fc2 = FaceCoord::new(face, 0.5, 0.501);
 
-    let v0 = face_coord_to_sphere_everitt(&fc0);
-    let v1 = face_coord_to_sphere_everitt(&fc1);
-    let v2 = face_coord_to_sphere_everitt(&fc2);
+    let v0 = project(&fc0, projection);
+    let v1 = project(&fc1, projection);
+    let v2 = project(&fc2, projection);
 
     !triangle_winds_outward(v0, v1, v2)
 }
 
-/// Precomputed winding flip table. Call once at startup.
+/// Precomputed winding flip table for a planet's `projection`. Call once
+/// per planet at startup.
 ///
 /// Returns an array indexed by `CubeFace as usize`. If the entry is `true`,
 /// all triangles on that face must have their vertex order reversed
 /// (swap v1 and v2) during mesh generation.
-pub fn compute_winding_flip_table() -> [bool; 6] {
+pub fn compute_winding_flip_table(projection: ProjectionMethod) -> [bool; 6] {
     let mut table = [false; 6];
     for face in CubeFace::ALL {
-        table[face as usize] = face_needs_winding_flip(face);
+        table[face as usize] = face_needs_winding_flip(face, projection);
     }
     table
 }
@@ -98,9 +99,14 @@ pub fn generate_chunk_indices(grid_size: u32, face: CubeFace, flip_table: &[bool
 
 /// Validate that winding order is consistent across a face edge.
 ///
-/// Generates triangles on both sides of the edge and verifies that
-/// both have outward-facing normals after applying the flip correction.
-pub fn validate_edge_winding(face_a: CubeFace, flip_table: &[bool; 6]) -> bool {
+/// Generates triangles on both sides of the edge, projected with
+/// `projection`, and verifies that both have outward-facing normals after
+/// applying the flip correction.
+pub fn validate_edge_winding(
+    face_a: CubeFace,
+    flip_table: &[bool; 6],
+    projection: ProjectionMethod,
+) -> bool {
     let samples = 10;
     for i in 0..samples {
         let t = (i as f64 + 0.5) / samples as f64;
@@ -109,9 +115,9 @@ pub fn validate_edge_winding(face_a: CubeFace, flip_table: &[bool; 6]) -> bool {
         let fc_a1 = FaceCoord::new(face_a, 0.998, t);
         let fc_a2 = FaceCoord::new(face_a, 0.999, (t + 0.001).min(1.0));
 
-        let v0 = face_coord_to_sphere_everitt(&fc_a);
-        let mut v1 = face_coord_to_sphere_everitt(&fc_a1);
-        let mut v2 = face_coord_to_sphere_everitt(&fc_a2);
+        let v0 = project(&fc_a, projection);
+        let mut v1 = project(&fc_a1, projection);
+        let mut v2 = project(&fc_a2, projection);
 
         if flip_table[face_a as usize] {
             std::mem::swap(&mut v1, &mut v2);
@@ -166,33 +172,42 @@ pub fn generate_lod_transition_strip(
 mod tests {
     use super::*;
 
+    const METHODS: [ProjectionMethod; 4] = [
+        ProjectionMethod::Gnomonic,
+        ProjectionMethod::TangentWarp,
+        ProjectionMethod::Everitt,
+        ProjectionMethod::Cobe,
+    ];
+
     #[test]
     fn test_triangle_normal_points_away_from_center_all_faces() {
-        let flip_table = compute_winding_flip_table();
+        for method in METHODS {
+            let flip_table = compute_winding_flip_table(method);
 
-        for face in CubeFace::ALL {
-            let fc0 = FaceCoord::new(face, 0.45, 0.45);
-            let fc1 = FaceCoord::new(face, 0.55, 0.45);
-            let fc2 = FaceCoord::new(face, 0.45, 0.55);
+            for face in CubeFace::ALL {
+                let fc0 = FaceCoord::new(face, 0.45, 0.45);
+                let fc1 = FaceCoord::new(face, 0.55, 0.45);
+                let fc2 = FaceCoord::new(face, 0.45, 0.55);
 
-            let v0 = face_coord_to_sphere_everitt(&fc0);
-            let mut v1 = face_coord_to_sphere_everitt(&fc1);
-            let mut v2 = face_coord_to_sphere_everitt(&fc2);
+                let v0 = project(&fc0, method);
+                let mut v1 = project(&fc1, method);
+                let mut v2 = project(&fc2, method);
 
-            if flip_table[face as usize] {
-                std::mem::swap(&mut v1, &mut v2);
-            }
+                if flip_table[face as usize] {
+                    std::mem::swap(&mut v1, &mut v2);
+                }
 
-            assert!(
-                triangle_winds_outward(v0, v1, v2),
-                "Triangle on face {face:?} does not point outward after correction"
-            );
+                assert!(
+                    triangle_winds_outward(v0, v1, v2),
+                    "Triangle on face {face:?} does not point outward after {method:?} correction"
+                );
+            }
         }
     }
 
     #[test]
     fn test_winding_ccw_from_outside_random_samples() {
-        let flip_table = compute_winding_flip_table();
+        let flip_table = compute_winding_flip_table(ProjectionMethod::default());
 
         let test_points = [
             (0.1, 0.1),
@@ -213,9 +228,9 @@ mod tests {
                 let fc1 = FaceCoord::new(face, (u + du).min(1.0), v);
                 let fc2 = FaceCoord::new(face, u, (v + dv).min(1.0));
 
-                let v0 = face_coord_to_sphere_everitt(&fc0);
-                let mut v1 = face_coord_to_sphere_everitt(&fc1);
-                let mut v2 = face_coord_to_sphere_everitt(&fc2);
+                let v0 = project_default(&fc0);
+                let mut v1 = project_default(&fc1);
+                let mut v2 = project_default(&fc2);
 
                 if flip_table[face as usize] {
                     std::mem::swap(&mut v1, &mut v2);
@@ -231,7 +246,7 @@ mod tests {
 
     #[test]
     fn test_no_flipped_triangles_at_face_edges() {
-        let flip_table = compute_winding_flip_table();
+        let flip_table = compute_winding_flip_table(ProjectionMethod::default());
 
         let edge_samples = [(0.5, 0.005), (0.5, 0.99), (0.005, 0.5), (0.99, 0.5)];
 
@@ -243,9 +258,9 @@ mod tests {
                 let fc1 = FaceCoord::new(face, u + du, v);
                 let fc2 = FaceCoord::new(face, u, v + dv);
 
-                let v0 = face_coord_to_sphere_everitt(&fc0);
-                let mut v1 = face_coord_to_sphere_everitt(&fc1);
-                let mut v2 = face_coord_to_sphere_everitt(&fc2);
+                let v0 = project_default(&fc0);
+                let mut v1 = project_default(&fc1);
+                let mut v2 = project_default(&fc2);
 
                 if flip_table[face as usize] {
                     std::mem::swap(&mut v1, &mut v2);
@@ -261,7 +276,7 @@ mod tests {
 
     #[test]
     fn test_lod_transition_triangles_maintain_winding() {
-        let flip_table = compute_winding_flip_table();
+        let flip_table = compute_winding_flip_table(ProjectionMethod::default());
 
         for face in CubeFace::ALL {
             let flip = flip_table[face as usize];
@@ -276,6 +291,10 @@ mod tests {
         }
     }
 
+    fn project_default(fc: &FaceCoord) -> DVec3 {
+        project(fc, ProjectionMethod::default())
+    }
+
     #[test]
     fn test_emit_triangle_flip() {
         let normal = emit_triangle(0, 1, 2, false);
@@ -287,13 +306,13 @@ mod tests {
 
     #[test]
     fn test_flip_table_has_6_entries() {
-        let table = compute_winding_flip_table();
+        let table = compute_winding_flip_table(ProjectionMethod::default());
         assert_eq!(table.len(), 6);
     }
 
     #[test]
     fn test_generate_chunk_indices_count() {
-        let flip_table = compute_winding_flip_table();
+        let flip_table = compute_winding_flip_table(ProjectionMethod::default());
         let grid_size = 17;
         let indices = generate_chunk_indices(grid_size, CubeFace::PosX, &flip_table);
         let expected_triangles = (grid_size - 1) * (grid_size - 1) * 2;
@@ -302,7 +321,7 @@ mod tests {
 
     #[test]
     fn test_opposite_faces_may_have_different_flip() {
-        let table = compute_winding_flip_table();
+        let table = compute_winding_flip_table(ProjectionMethod::default());
         // Verify the table was computed (non-trivial check).
         assert_eq!(table.len(), 6);
     }
@@ -2,13 +2,17 @@
 //!
 //! This module bridges the cubesphere abstraction and the engine's universal
 //! coordinate system ([`WorldPosition`] with i128 millimeter precision).
+//!
+//! Every conversion takes the planet's [`ProjectionMethod`] (see
+//! [`PlanetDef::projection`](crate::PlanetDef::projection)), so both
+//! directions use the mapping the planet was generated with.
 
 use glam::DVec3;
 use nebula_math::WorldPosition;
 
-use crate::inverse::sphere_to_face_coord_everitt;
-use crate::projection::face_coord_to_sphere_everitt;
-use crate::{ChunkAddress, FaceCoord};
+use crate::inverse::sphere_to_face_coord;
+use crate::projection::project;
+use crate::{ChunkAddress, FaceCoord, ProjectionMethod};
 
 /// Convert a face coordinate + planet parameters to a [`WorldPosition`].
 ///
@@ -16,6 +20,7 @@ use crate::{ChunkAddress, FaceCoord};
 /// - `planet_radius`: planet radius in mm (i128)
 /// - `terrain_height`: height above the sphere surface in mm (i64, signed for oceans)
 /// - `planet_center`: the planet's center position in world space
+/// - `projection`: the planet's cube-to-sphere mapping
 ///
 /// Returns the [`WorldPosition`] of the point on the planet surface.
 #[must_use]
@@ -24,9 +29,10 @@ pub fn face_uv_to_world_position(
     planet_radius: i128,
     terrain_height: i64,
     planet_center: &WorldPosition,
+    projection: ProjectionMethod,
 ) -> WorldPosition {
     // Step 1: FaceCoord -> unit sphere point
-    let unit_dir: DVec3 = face_coord_to_sphere_everitt(fc);
+    let unit_dir: DVec3 = project(fc, projection);
 
     // Step 2: Scale by (radius + height) to get planet-relative position
     let total_radius = planet_radius as f64 + terrain_height as f64;
@@ -53,6 +59,7 @@ pub fn world_position_to_face_uv(
     world_pos: &WorldPosition,
     planet_radius: i128,
     planet_center: &WorldPosition,
+    projection: ProjectionMethod,
 ) -> (FaceCoord, i64) {
     // Step 1: Compute planet-relative position
     let dx = (world_pos.x - planet_center.x) as f64;
@@ -66,7 +73,7 @@ pub fn world_position_to_face_uv(
     let terrain_height = (distance - planet_radius as f64).round() as i64;
 
     // Step 3: Convert direction to FaceCoord
-    let fc = sphere_to_face_coord_everitt(dir.normalize());
+    let fc = sphere_to_face_coord(dir.normalize(), projection);
 
     (fc, terrain_height)
 }
@@ -82,10 +89,38 @@ pub fn face_grid_to_world_positions(
     planet_center: &WorldPosition,
     grid_resolution: u32,
     heights: &[Vec<i64>],
+    projection: ProjectionMethod,
 ) -> Vec<WorldPosition> {
+    let mut positions = Vec::new();
+    face_grid_to_world_positions_into(
+        addr,
+        planet_radius,
+        planet_center,
+        grid_resolution,
+        heights,
+        projection,
+        &mut positions,
+    );
+    positions
+}
+
+/// Like [`face_grid_to_world_positions`], but writes into `out` so one
+/// buffer can be reused across chunks during generation.
+///
+/// `out` is cleared first and only grows when it is too small for the grid.
+pub fn face_grid_to_world_positions_into(
+    addr: &ChunkAddress,
+    planet_radius: i128,
+    planet_center: &WorldPosition,
+    grid_resolution: u32,
+    heights: &[Vec<i64>],
+    projection: ProjectionMethod,
+    out: &mut Vec<WorldPosition>,
+) {
     let (u_min, v_min, u_max, v_max) = addr.uv_bounds();
     let capacity = ((grid_resolution + 1) * (grid_resolution + 1)) as usize;
-    let mut positions = Vec::with_capacity(capacity);
+    out.clear();
+    out.reserve(capacity);
 
     for vi in 0..=grid_resolution {
         for ui in 0..=grid_resolution {
@@ -93,16 +128,15 @@ pub fn face_grid_to_world_positions(
             let v = v_min + (v_max - v_min) * (vi as f64 / grid_resolution as f64);
             let fc = FaceCoord::new(addr.face, u, v);
             let h = heights[ui as usize][vi as usize];
-            positions.push(face_uv_to_world_position(
+            out.push(face_uv_to_world_position(
                 &fc,
                 planet_radius,
                 h,
                 planet_center,
+                projection,
             ));
         }
     }
-
-    positions
 }
 
 #[cfg(test)]
@@ -112,12 +146,13 @@ mod tests {
 
     const EARTH_RADIUS: i128 = 6_371_000_000; // mm
     const ORIGIN: WorldPosition = WorldPosition { x: 0, y: 0, z: 0 };
+    const EVERITT: ProjectionMethod = ProjectionMethod::Everitt;
 
     #[test]
     fn test_face_center_at_radius_maps_to_correct_axis() {
         // PosX face center at height=0 should be at approximately (radius, 0, 0)
         let fc = FaceCoord::new(CubeFace::PosX, 0.5, 0.5);
-        let pos = face_uv_to_world_position(&fc, EARTH_RADIUS, 0, &ORIGIN);
+        let pos = face_uv_to_world_position(&fc, EARTH_RADIUS, 0, &ORIGIN, EVERITT);
         assert!(
             (pos.x - EARTH_RADIUS).abs() < 2,
             "PosX face center x: expected ~{EARTH_RADIUS}, got {}",
@@ -136,14 +171,14 @@ mod tests {
 
         // PosY face center should be at (0, radius, 0)
         let fc_y = FaceCoord::new(CubeFace::PosY, 0.5, 0.5);
-        let pos_y = face_uv_to_world_position(&fc_y, EARTH_RADIUS, 0, &ORIGIN);
+        let pos_y = face_uv_to_world_position(&fc_y, EARTH_RADIUS, 0, &ORIGIN, EVERITT);
         assert!(pos_y.x.abs() < 2);
         assert!((pos_y.y - EARTH_RADIUS).abs() < 2);
         assert!(pos_y.z.abs() < 2);
 
         // NegZ face center should be at (0, 0, -radius)
         let fc_nz = FaceCoord::new(CubeFace::NegZ, 0.5, 0.5);
-        let pos_nz = face_uv_to_world_position(&fc_nz, EARTH_RADIUS, 0, &ORIGIN);
+        let pos_nz = face_uv_to_world_position(&fc_nz, EARTH_RADIUS, 0, &ORIGIN, EVERITT);
         assert!(pos_nz.x.abs() < 2);
         assert!(pos_nz.y.abs() < 2);
         assert!((pos_nz.z + EARTH_RADIUS).abs() < 2);
@@ -153,8 +188,8 @@ mod tests {
     fn test_uv_corners_map_to_expected_directions() {
         let fc_00 = FaceCoord::new(CubeFace::PosX, 0.0, 0.0);
         let fc_11 = FaceCoord::new(CubeFace::PosX, 1.0, 1.0);
-        let pos_00 = face_uv_to_world_position(&fc_00, EARTH_RADIUS, 0, &ORIGIN);
-        let pos_11 = face_uv_to_world_position(&fc_11, EARTH_RADIUS, 0, &ORIGIN);
+        let pos_00 = face_uv_to_world_position(&fc_00, EARTH_RADIUS, 0, &ORIGIN, EVERITT);
+        let pos_11 = face_uv_to_world_position(&fc_11, EARTH_RADIUS, 0, &ORIGIN, EVERITT);
 
         let d_00 =
             ((pos_00.x as f64).powi(2) + (pos_00.y as f64).powi(2) + (pos_00.z as f64).powi(2))
@@ -176,7 +211,7 @@ mod tests {
     fn test_height_zero_puts_point_on_sphere_surface() {
         for face in CubeFace::ALL {
             let fc = FaceCoord::new(face, 0.3, 0.7);
-            let pos = face_uv_to_world_position(&fc, EARTH_RADIUS, 0, &ORIGIN);
+            let pos = face_uv_to_world_position(&fc, EARTH_RADIUS, 0, &ORIGIN, EVERITT);
             let dist =
                 ((pos.x as f64).powi(2) + (pos.y as f64).powi(2) + (pos.z as f64).powi(2)).sqrt();
             assert!(
@@ -190,7 +225,7 @@ mod tests {
     fn test_large_planet_radius_uses_full_i128_range() {
         let huge_radius: i128 = 1_000_000_000_000_000;
         let fc = FaceCoord::new(CubeFace::PosX, 0.5, 0.5);
-        let pos = face_uv_to_world_position(&fc, huge_radius, 0, &ORIGIN);
+        let pos = face_uv_to_world_position(&fc, huge_radius, 0, &ORIGIN, EVERITT);
         assert!(
             (pos.x - huge_radius).abs() < 1_000,
             "Large radius: expected x ~{huge_radius}, got {}",
@@ -206,7 +241,7 @@ mod tests {
             2_000_000_000_000_000,
         );
         let fc = FaceCoord::new(CubeFace::PosX, 0.5, 0.5);
-        let pos = face_uv_to_world_position(&fc, EARTH_RADIUS, 0, &center);
+        let pos = face_uv_to_world_position(&fc, EARTH_RADIUS, 0, &center, EVERITT);
         assert!((pos.x - center.x - EARTH_RADIUS).abs() < 2);
         assert!((pos.y - center.y).abs() < 2);
         assert!((pos.z - center.z).abs() < 2);
@@ -218,8 +253,9 @@ mod tests {
         let height: i64 = 5_000; // 5 meters
         let center = WorldPosition::new(0, 0, 0);
 
-        let world_pos = face_uv_to_world_position(&fc_orig, EARTH_RADIUS, height, &center);
-        let (fc_back, height_back) = world_position_to_face_uv(&world_pos, EARTH_RADIUS, &center);
+        let world_pos = face_uv_to_world_position(&fc_orig, EARTH_RADIUS, height, &center, EVERITT);
+        let (fc_back, height_back) =
+            world_position_to_face_uv(&world_pos, EARTH_RADIUS, &center, EVERITT);
 
         assert_eq!(fc_back.face, fc_orig.face);
         assert!(
@@ -242,11 +278,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_roundtrip_honors_projection_method() {
+        let center = WorldPosition::new(-5_000_000_000, 0, 77);
+        let fc = FaceCoord::new(CubeFace::NegX, 0.1, 0.85);
+        let mut positions = Vec::new();
+        for method in [
+            ProjectionMethod::Gnomonic,
+            ProjectionMethod::TangentWarp,
+            ProjectionMethod::Everitt,
+        ] {
+            let pos = face_uv_to_world_position(&fc, EARTH_RADIUS, 2_500, &center, method);
+            let (back, height) = world_position_to_face_uv(&pos, EARTH_RADIUS, &center, method);
+            assert_eq!(back.face, fc.face);
+            assert!((back.u - fc.u).abs() < 1e-9, "{method:?}");
+            assert!((back.v - fc.v).abs() < 1e-9, "{method:?}");
+            // Positions are rounded to whole millimeters.
+            assert!((height - 2_500).abs() <= 1, "{method:?} height {height}");
+            positions.push(pos);
+        }
+        // The same UV lands in different places under each mapping.
+        assert_ne!(positions[0], positions[1]);
+        assert_ne!(positions[1], positions[2]);
+    }
+
     #[test]
     fn test_terrain_height_displaces_outward() {
         let fc = FaceCoord::new(CubeFace::PosY, 0.5, 0.5);
-        let pos_flat = face_uv_to_world_position(&fc, EARTH_RADIUS, 0, &ORIGIN);
-        let pos_high = face_uv_to_world_position(&fc, EARTH_RADIUS, 1_000_000, &ORIGIN);
+        let pos_flat = face_uv_to_world_position(&fc, EARTH_RADIUS, 0, &ORIGIN, EVERITT);
+        let pos_high = face_uv_to_world_position(&fc, EARTH_RADIUS, 1_000_000, &ORIGIN, EVERITT);
 
         let dist_flat = ((pos_flat.x as f64).powi(2)
             + (pos_flat.y as f64).powi(2)
@@ -269,8 +329,14 @@ mod tests {
         let resolution = 4;
         let heights: Vec<Vec<i64>> =
             vec![vec![0; (resolution + 1) as usize]; (resolution + 1) as usize];
-        let positions =
-            face_grid_to_world_positions(&addr, EARTH_RADIUS, &ORIGIN, resolution, &heights);
+        let positions = face_grid_to_world_positions(
+            &addr,
+            EARTH_RADIUS,
+            &ORIGIN,
+            resolution,
+            &heights,
+            EVERITT,
+        );
         let expected = ((resolution + 1) * (resolution + 1)) as usize;
         assert_eq!(positions.len(), expected);
     }
@@ -281,8 +347,14 @@ mod tests {
         let resolution = 4;
         let heights: Vec<Vec<i64>> =
             vec![vec![0; (resolution + 1) as usize]; (resolution + 1) as usize];
-        let positions =
-            face_grid_to_world_positions(&addr, EARTH_RADIUS, &ORIGIN, resolution, &heights);
+        let positions = face_grid_to_world_positions(
+            &addr,
+            EARTH_RADIUS,
+            &ORIGIN,
+            resolution,
+            &heights,
+            EVERITT,
+        );
         for pos in &positions {
             let dist =
                 ((pos.x as f64).powi(2) + (pos.y as f64).powi(2) + (pos.z as f64).powi(2)).sqrt();
@@ -293,12 +365,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_buffered_grid_matches_allocating_version() {
+        let center = WorldPosition::new(384_400_000_000, -12_345, 987_654_321);
+        let mut buffer = Vec::new();
+        for (face, resolution) in [(CubeFace::NegY, 8), (CubeFace::PosX, 3)] {
+            let addr = ChunkAddress::new(face, 10, 50, 51);
+            let heights: Vec<Vec<i64>> = (0..=resolution)
+                .map(|ui| (0..=resolution).map(|vi| ui * 1_000 - vi * 700).collect())
+                .collect();
+            let expected = face_grid_to_world_positions(
+                &addr,
+                EARTH_RADIUS,
+                &center,
+                resolution as u32,
+                &heights,
+                EVERITT,
+            );
+            // The buffer still holds the previous (larger) grid here.
+            face_grid_to_world_positions_into(
+                &addr,
+                EARTH_RADIUS,
+                &center,
+                resolution as u32,
+                &heights,
+                EVERITT,
+                &mut buffer,
+            );
+            assert_eq!(buffer.len(), expected.len());
+            for (i, (got, want)) in buffer.iter().zip(&expected).enumerate() {
+                assert_eq!(got, want, "vertex {i} on {face:?}");
+            }
+        }
+    }
+
     #[test]
     fn test_negative_terrain_height() {
         // Ocean: negative height should place point below surface
         let fc = FaceCoord::new(CubeFace::PosX, 0.5, 0.5);
-        let pos_surface = face_uv_to_world_position(&fc, EARTH_RADIUS, 0, &ORIGIN);
-        let pos_ocean = face_uv_to_world_position(&fc, EARTH_RADIUS, -1_000_000, &ORIGIN);
+        let pos_surface = face_uv_to_world_position(&fc, EARTH_RADIUS, 0, &ORIGIN, EVERITT);
+        let pos_ocean = face_uv_to_world_position(&fc, EARTH_RADIUS, -1_000_000, &ORIGIN, EVERITT);
 
         let dist_surface = ((pos_surface.x as f64).powi(2)
             + (pos_surface.y as f64).powi(2)
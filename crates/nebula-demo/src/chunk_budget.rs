@@ -0,0 +1,81 @@
+//! Memory-budgeted chunk loading for the demo.
+//!
+//! [`BudgetedChunkLoader`] wraps a [`ChunkLoader`] with a
+//! [`MemoryBudgetTracker`]: every tick loads only as many chunks as the
+//! caller's per-frame cap and the tracker's
+//! [`BudgetPressure`](nebula_lod::BudgetPressure) allow, and the
+//! tracker sees each chunk the loader loads or unloads plus the idle buffers
+//! of an attached [`GpuBufferPool`].
+
+use std::collections::HashSet;
+
+use nebula_lod::{ChunkMemoryUsage, MemoryBudgetConfig, MemoryBudgetTracker};
+use nebula_render::GpuBufferPool;
+use nebula_voxel::{ChunkAddress, ChunkLoadTickResult, ChunkLoader, ChunkManager};
+
+/// A [`ChunkLoader`] throttled by memory budget pressure.
+pub(crate) struct BudgetedChunkLoader {
+    loader: ChunkLoader,
+    tracker: MemoryBudgetTracker<ChunkAddress>,
+}
+
+impl BudgetedChunkLoader {
+    /// Budget `loader` with `budget`, typically from
+    /// [`MemoryBudgetConfig::detect`].
+    pub(crate) fn new(loader: ChunkLoader, budget: MemoryBudgetConfig) -> Self {
+        Self {
+            loader,
+            tracker: MemoryBudgetTracker::new(budget),
+        }
+    }
+
+    /// Count the idle buffers `pool` keeps allocated toward the GPU budget.
+    pub(crate) fn attach_pool(&self, pool: &mut GpuBufferPool) {
+        let reporter = self.tracker.gpu_reporter();
+        pool.set_usage_callback(move |usage| {
+            let idle = usage.allocated.saturating_sub(usage.in_use);
+            reporter.report(usize::try_from(idle).unwrap_or(usize::MAX));
+        });
+    }
+
+    /// The budget the loader is throttled by.
+    pub(crate) fn tracker(&self) -> &MemoryBudgetTracker<ChunkAddress> {
+        &self.tracker
+    }
+
+    /// Run one loader tick loading at most `max_loads` chunks (such as the
+    /// frame budget's load rate), throttled further by the current pressure,
+    /// then account for the chunks it loaded and unloaded.
+    pub(crate) fn tick(
+        &mut self,
+        camera_chunk: ChunkAddress,
+        manager: &mut ChunkManager,
+        max_loads: u32,
+    ) -> ChunkLoadTickResult {
+        let max_loads = self
+            .tracker
+            .pressure()
+            .throttle_loads(max_loads.min(self.loader.config().loads_per_tick));
+        let result = self.loader.tick_throttled(camera_chunk, manager, max_loads);
+
+        let loaded: HashSet<ChunkAddress> = manager.loaded_addresses().copied().collect();
+        let unloaded: Vec<ChunkAddress> = self
+            .tracker
+            .chunk_usage()
+            .keys()
+            .filter(|address| !loaded.contains(address))
+            .copied()
+            .collect();
+        for address in &unloaded {
+            self.tracker.on_chunk_unloaded(address);
+        }
+        for address in loaded {
+            if !self.tracker.chunk_usage().contains_key(&address) {
+                // Freshly loaded chunks hold voxel data only until meshed.
+                self.tracker
+                    .on_chunk_loaded(address, ChunkMemoryUsage::estimate(0, 0));
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,287 @@
+//! Per-frame chunk streaming for the demo, paced by frame-time feedback.
+//!
+//! [`ChunkStreamer`] runs the chunk pipeline once per frame: the
+//! [`BudgetedChunkLoader`] picks chunks around the camera, an
+//! [`AsyncChunkGenerator`] fills them, a [`MeshingPipeline`] meshes them, and
+//! finished chunks are packed for upload and given colliders. The time spent
+//! on each stage is fed to a [`FrameBudgetScheduler`], whose rates cap the
+//! next frame's loads, mesh uploads and collider builds.
+//!
+//! The demo window owns the GPU device, so "upload" here packs meshes into
+//! the GPU vertex format and stages them for the renderer.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Instant;
+
+use nebula_coords::WorldPosition;
+use nebula_cubesphere::PlanetDef;
+use nebula_lod::{ChunkWorkRates, ChunkWorkTimings, FrameBudgetScheduler, MemoryBudgetConfig};
+use nebula_mesh::{
+    ChunkNeighborhood, MeshingPipeline, MeshingResult, MeshingTask, PackedChunkMesh,
+};
+use nebula_physics::{ChunkColliderMap, PhysicsWorld, create_chunk_collider, world_to_local};
+use nebula_terrain::{AsyncChunkGenerator, GenerationTask};
+use nebula_voxel::{
+    CHUNK_SIZE, ChunkAddress, ChunkData, ChunkLoadConfig, ChunkLoader, ChunkManager, VoxelTypeId,
+    VoxelTypeRegistry,
+};
+
+use crate::chunk_budget::BudgetedChunkLoader;
+
+/// Millimeters per chunk edge (one voxel per meter).
+const MM_PER_CHUNK: i128 = CHUNK_SIZE as i128 * 1000;
+
+/// Streams chunks around the camera within a per-frame chunk-work budget.
+pub(crate) struct ChunkStreamer {
+    loader: BudgetedChunkLoader,
+    manager: ChunkManager,
+    generator: AsyncChunkGenerator,
+    meshing: MeshingPipeline,
+    registry: Arc<VoxelTypeRegistry>,
+    scheduler: FrameBudgetScheduler,
+    planet: PlanetDef,
+    /// Loaded chunks whose generation has been submitted.
+    requested: HashSet<ChunkAddress>,
+    /// Generated chunks the meshing pipeline had no room for yet.
+    unmeshed: VecDeque<ChunkAddress>,
+    /// Finished meshes waiting for upload.
+    pending_uploads: VecDeque<MeshingResult>,
+    /// Generated chunks waiting for a collider.
+    pending_colliders: VecDeque<ChunkAddress>,
+    /// Packed meshes staged for the renderer.
+    meshes: HashMap<ChunkAddress, PackedChunkMesh>,
+    colliders: ChunkColliderMap,
+}
+
+impl ChunkStreamer {
+    /// Stream `planet`'s chunks with voxel types from `registry`, generating
+    /// each chunk's data with `generate`.
+    pub(crate) fn new(
+        planet: PlanetDef,
+        registry: Arc<VoxelTypeRegistry>,
+        scheduler: FrameBudgetScheduler,
+        generate: impl Fn(&GenerationTask) -> ChunkData + Send + Sync + 'static,
+    ) -> Self {
+        let max_loads = scheduler.config().max_rates.loads;
+        Self {
+            loader: BudgetedChunkLoader::new(
+                ChunkLoader::new(ChunkLoadConfig {
+                    load_radius: 3,
+                    unload_radius: 5,
+                    loads_per_tick: max_loads,
+                    unloads_per_tick: 64,
+                }),
+                MemoryBudgetConfig::default(),
+            ),
+            manager: ChunkManager::new(),
+            generator: AsyncChunkGenerator::with_generator(2, 128, 256, generate),
+            meshing: MeshingPipeline::new(2, 64, Arc::clone(&registry)),
+            registry,
+            scheduler,
+            planet,
+            requested: HashSet::new(),
+            unmeshed: VecDeque::new(),
+            pending_uploads: VecDeque::new(),
+            pending_colliders: VecDeque::new(),
+            meshes: HashMap::new(),
+            colliders: ChunkColliderMap::new(),
+        }
+    }
+
+    /// The chunk containing `position`.
+    pub(crate) fn chunk_at(position: &WorldPosition) -> ChunkAddress {
+        ChunkAddress::new(
+            position.x.div_euclid(MM_PER_CHUNK) as i64,
+            position.y.div_euclid(MM_PER_CHUNK) as i64,
+            position.z.div_euclid(MM_PER_CHUNK) as i64,
+            0,
+        )
+    }
+
+    /// Run one frame of chunk work around `camera_chunk`, building colliders
+    /// into `physics` relative to `origin`, and return the rates for the
+    /// next frame.
+    pub(crate) fn update(
+        &mut self,
+        camera_chunk: ChunkAddress,
+        dt: f32,
+        physics: &mut PhysicsWorld,
+        origin: &WorldPosition,
+    ) -> ChunkWorkRates {
+        let rates = self.scheduler.rates();
+        self.loader
+            .tick(camera_chunk, &mut self.manager, rates.loads);
+        self.forget_unloaded(physics);
+        self.request_generation(camera_chunk);
+
+        let mut timings = ChunkWorkTimings::default();
+
+        let start = Instant::now();
+        self.drain_generation();
+        timings.generation = start.elapsed();
+
+        let start = Instant::now();
+        self.pending_uploads.extend(self.meshing.drain_results());
+        timings.meshing = start.elapsed();
+
+        let start = Instant::now();
+        self.upload_meshes(rates.mesh_uploads);
+        timings.upload = start.elapsed();
+
+        let start = Instant::now();
+        self.build_colliders(rates.collider_builds, physics, origin);
+        timings.colliders = start.elapsed();
+
+        self.scheduler.record_frame(&timings, dt)
+    }
+
+    /// One line of chunk-work statistics for the debug overlay.
+    pub(crate) fn overlay_text(&self) -> String {
+        let rates = self.scheduler.rates();
+        format!(
+            "Chunks: {} loaded, {} meshed | work {:.2}/{:.1} ms | loads {} uploads {} colliders {}",
+            self.manager.loaded_count(),
+            self.meshes.len(),
+            self.scheduler.last_work_ms(),
+            self.scheduler.config().budget_ms,
+            rates.loads,
+            rates.mesh_uploads,
+            rates.collider_builds,
+        )
+    }
+
+    /// Drop every trace of chunks the loader unloaded.
+    fn forget_unloaded(&mut self, physics: &mut PhysicsWorld) {
+        let unloaded: Vec<ChunkAddress> = self
+            .requested
+            .iter()
+            .filter(|address| self.manager.get_chunk(address).is_none())
+            .copied()
+            .collect();
+        if unloaded.is_empty() {
+            return;
+        }
+        self.generator.cancel_batch(&unloaded);
+        nebula_physics::remove_chunk_colliders(physics, &unloaded, &mut self.colliders);
+        for address in &unloaded {
+            self.requested.remove(address);
+            self.meshes.remove(address);
+        }
+    }
+
+    /// Submit generation for loaded chunks that have not been requested yet,
+    /// prioritized by distance from the camera.
+    fn request_generation(&mut self, camera_chunk: ChunkAddress) {
+        for address in self.manager.loaded_addresses() {
+            if self.requested.contains(address) {
+                continue;
+            }
+            let (dx, dy, dz) = (
+                address.x - camera_chunk.x,
+                address.y - camera_chunk.y,
+                address.z - camera_chunk.z,
+            );
+            let task = GenerationTask {
+                address: *address,
+                seed: self.planet.seed,
+                planet: self.planet.clone(),
+                priority: (dx * dx + dy * dy + dz * dz) as u64,
+            };
+            if self.generator.submit(task).is_err() {
+                break;
+            }
+            self.requested.insert(*address);
+        }
+    }
+
+    /// Store finished chunk data and queue it for meshing and collision.
+    fn drain_generation(&mut self) {
+        for generated in self.generator.drain_results() {
+            if generated.evicted {
+                continue;
+            }
+            let Some(chunk) = self.manager.get_chunk_mut(&generated.address) else {
+                continue;
+            };
+            *chunk.data_mut() = generated.data;
+            self.unmeshed.push_back(generated.address);
+            self.pending_colliders.push_back(generated.address);
+        }
+        while let Some(address) = self.unmeshed.pop_front() {
+            let Some(chunk) = self.manager.get_chunk(&address) else {
+                continue;
+            };
+            let task = MeshingTask {
+                chunk_addr: address,
+                neighborhood: ChunkNeighborhood::from_center_only(chunk.data().clone()),
+                data_version: chunk.version(),
+            };
+            if !self.meshing.submit(task) {
+                self.unmeshed.push_front(address);
+                break;
+            }
+        }
+    }
+
+    /// Pack up to `max` finished meshes of still-loaded chunks.
+    fn upload_meshes(&mut self, max: u32) {
+        let mut uploaded = 0;
+        while uploaded < max {
+            let Some(result) = self.pending_uploads.pop_front() else {
+                break;
+            };
+            if self.manager.get_chunk(&result.chunk_addr).is_none() {
+                continue;
+            }
+            let packed = PackedChunkMesh::from_chunk_mesh(&result.mesh, &self.registry);
+            self.meshes.insert(result.chunk_addr, packed);
+            uploaded += 1;
+        }
+    }
+
+    /// Build colliders for up to `max` generated chunks that are still loaded.
+    fn build_colliders(&mut self, max: u32, physics: &mut PhysicsWorld, origin: &WorldPosition) {
+        let mut built = 0;
+        while built < max {
+            let Some(address) = self.pending_colliders.pop_front() else {
+                break;
+            };
+            let Some(chunk) = self.manager.get_chunk(&address) else {
+                continue;
+            };
+            let corner = WorldPosition::new(
+                i128::from(address.x) * MM_PER_CHUNK,
+                i128::from(address.y) * MM_PER_CHUNK,
+                i128::from(address.z) * MM_PER_CHUNK,
+            );
+            let local = world_to_local(&corner, origin);
+            if let Some(handle) = create_chunk_collider(physics, chunk, &self.registry, local, 1.0)
+            {
+                self.colliders.insert(address, handle);
+            }
+            built += 1;
+        }
+    }
+}
+
+/// Flat stone ground below world height `ground_y` (in voxels), with a
+/// gentle ripple so the meshes are not one giant quad.
+pub(crate) fn ground_chunk(task: &GenerationTask, ground_y: i64, stone: VoxelTypeId) -> ChunkData {
+    let mut data = ChunkData::new(VoxelTypeId(0));
+    let base = task.address.y * CHUNK_SIZE as i64;
+    for x in 0..CHUNK_SIZE {
+        let wx = task.address.x * CHUNK_SIZE as i64 + x as i64;
+        for z in 0..CHUNK_SIZE {
+            let wz = task.address.z * CHUNK_SIZE as i64 + z as i64;
+            let ripple = ((wx as f64 * 0.2).sin() + (wz as f64 * 0.15).cos()) * 2.0;
+            let height = ground_y + ripple.round() as i64;
+            for y in 0..CHUNK_SIZE {
+                if base + (y as i64) < height {
+                    data.set(x, y, z, stone);
+                }
+            }
+        }
+    }
+    data
+}
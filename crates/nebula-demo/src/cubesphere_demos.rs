@@ -2,10 +2,10 @@
 
 use nebula_coords::WorldPosition;
 use nebula_cubesphere::{
-    ChunkAddress, CubeCorner, CubeFace, FaceCoord, FaceDirection, FaceQuadtree, LodNeighbor,
-    PlanetDef, PlanetRegistry, SameFaceNeighbor, corner_lod_valid, direction_to_face,
-    face_coord_to_sphere_everitt, face_uv_to_world_position, sphere_to_face_coord_everitt,
-    world_position_to_face_uv,
+    ChunkAddress, CubeCorner, CubeFace, DistanceMode, FaceCoord, FaceDirection, FaceQuadtree,
+    LodNeighbor, PlanetDef, PlanetRegistry, ProjectionMethod, SameFaceNeighbor, corner_lod_valid,
+    direction_to_face, face_coord_to_sphere_everitt, face_uv_to_world_position,
+    sphere_to_face_coord_everitt, world_position_to_face_uv,
 };
 use rand::{Rng, SeedableRng};
 use rand_xoshiro::Xoshiro256StarStar;
@@ -199,11 +199,21 @@ pub(crate) fn demonstrate_face_uv_to_world() {
     let planet_center = WorldPosition::new(0, 0, 0);
 
     let fc = FaceCoord::new(CubeFace::PosX, 0.5, 0.5);
-    let world_pos = face_uv_to_world_position(&fc, earth_radius, 0, &planet_center);
+    let world_pos = face_uv_to_world_position(
+        &fc,
+        earth_radius,
+        0,
+        &planet_center,
+        ProjectionMethod::Everitt,
+    );
     info!("Clicked: {world_pos}");
 
-    let (fc_back, height_back) =
-        world_position_to_face_uv(&world_pos, earth_radius, &planet_center);
+    let (fc_back, height_back) = world_position_to_face_uv(
+        &world_pos,
+        earth_radius,
+        &planet_center,
+        ProjectionMethod::Everitt,
+    );
     info!(
         "Roundtrip: face={:?} u={:.6} v={:.6} height={} mm",
         fc_back.face, fc_back.u, fc_back.v, height_back
@@ -211,13 +221,24 @@ pub(crate) fn demonstrate_face_uv_to_world() {
 
     let mountain_height: i64 = 1_000_000;
     let fc_mountain = FaceCoord::new(CubeFace::PosY, 0.3, 0.7);
-    let pos_mountain =
-        face_uv_to_world_position(&fc_mountain, earth_radius, mountain_height, &planet_center);
+    let pos_mountain = face_uv_to_world_position(
+        &fc_mountain,
+        earth_radius,
+        mountain_height,
+        &planet_center,
+        ProjectionMethod::Everitt,
+    );
     info!("Mountain top: {pos_mountain}");
 
     for face in CubeFace::ALL {
         let fc_face = FaceCoord::new(face, 0.5, 0.5);
-        let pos = face_uv_to_world_position(&fc_face, earth_radius, 0, &planet_center);
+        let pos = face_uv_to_world_position(
+            &fc_face,
+            earth_radius,
+            0,
+            &planet_center,
+            ProjectionMethod::Everitt,
+        );
         let dist =
             ((pos.x as f64).powi(2) + (pos.y as f64).powi(2) + (pos.z as f64).powi(2)).sqrt();
         info!(
@@ -260,6 +281,21 @@ pub(crate) fn demonstrate_planet_definition() {
         );
     }
 
+    let probe = WorldPosition::new(380_000_000_000, 0, 0);
+    if let Some((id, dist_m)) = registry.nearest(&probe, DistanceMode::Center) {
+        info!("Nearest planet to probe: {id:?} at {dist_m:.0} m");
+    }
+    let anchor = registry.dominant_influence(&probe);
+    info!("Probe sphere of influence: {anchor:?}");
+    if let Some(planet) = anchor.and_then(|id| registry.get_by_index(id.0)) {
+        let source = nebula_physics::GravitySource::for_planet(planet, 1.62);
+        let gravity = nebula_physics::compute_gravity(&probe, &[(planet.center, &source)]);
+        info!(
+            "Probe gravity from {}: {:.4} m/s²",
+            planet.name, gravity.magnitude
+        );
+    }
+
     let too_close = PlanetDef::earth_like("TooClose", WorldPosition::new(1_000_000_000, 0, 0), 99);
     let result = registry.register(too_close);
     assert!(result.is_err(), "Overlapping planet should be rejected");
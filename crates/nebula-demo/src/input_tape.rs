@@ -0,0 +1,160 @@
+//! `--record` / `--replay` support for the demo's per-frame input loop.
+//!
+//! The demo resolves input once per rendered frame, so the tape keeps its
+//! own fixed 60 Hz tick counter: recording samples the resolved actions and
+//! look input on every tick boundary crossed during a frame, and replay
+//! advances the [`InputPlayer`] by exactly one tick per frame. Both hand the
+//! frame a whole number of ticks as its `dt`, so a replay does not depend
+//! on either session's frame rate.
+
+use std::path::PathBuf;
+
+use glam::Vec2;
+use nebula_app::game_loop::FIXED_DT;
+use nebula_input::{ActionState, InputPlayer, InputRecorder, InputRecording, LookInput};
+use tracing::{info, warn};
+
+/// Ticks between periodic saves while recording (10 s at 60 Hz).
+const FLUSH_INTERVAL_TICKS: u64 = 600;
+
+enum Mode {
+    Off,
+    Recording {
+        recorder: InputRecorder,
+        path: PathBuf,
+        /// Mouse motion since the last recorded tick.
+        pending_mouse: Vec2,
+    },
+    Replaying(InputPlayer),
+}
+
+/// Time step and look input a frame should simulate with.
+pub(crate) struct TapeFrame {
+    /// Seconds to simulate this frame.
+    pub(crate) dt: f64,
+    /// Mouse delta and right stick to look around with.
+    pub(crate) look: LookInput,
+}
+
+/// Records or replays resolved input on a fixed tick counter.
+pub(crate) struct InputTape {
+    mode: Mode,
+    tick: u64,
+    accumulator: f64,
+}
+
+impl InputTape {
+    /// Builds the tape from the `--record` / `--replay` CLI flags.
+    pub(crate) fn from_args(
+        record: Option<PathBuf>,
+        replay: Option<PathBuf>,
+        config_seed: u64,
+    ) -> Self {
+        let mode = if let Some(path) = replay {
+            match InputRecording::load(&path) {
+                Ok(recording) => {
+                    let header = &recording.header;
+                    info!(
+                        "Replaying {} (engine {}, seed {}, {} input ticks)",
+                        path.display(),
+                        header.engine_version,
+                        header.config_seed,
+                        recording.ticks.len()
+                    );
+                    if header.engine_version != env!("CARGO_PKG_VERSION") {
+                        warn!("Replay was recorded with a different engine version");
+                    }
+                    Mode::Replaying(InputPlayer::new(recording))
+                }
+                Err(e) => {
+                    warn!("Failed to load replay {}: {e}", path.display());
+                    Mode::Off
+                }
+            }
+        } else if let Some(path) = record {
+            info!("Recording input to {}", path.display());
+            Mode::Recording {
+                recorder: InputRecorder::new(env!("CARGO_PKG_VERSION"), config_seed, 0),
+                path,
+                pending_mouse: Vec2::ZERO,
+            }
+        } else {
+            Mode::Off
+        };
+        Self {
+            mode,
+            tick: 0,
+            accumulator: 0.0,
+        }
+    }
+
+    /// Whether live input is being replaced by a replay.
+    pub(crate) fn is_replaying(&self) -> bool {
+        matches!(self.mode, Mode::Replaying(_))
+    }
+
+    /// Advances the tick counter by `dt` and records or replays input.
+    ///
+    /// When replaying, `actions` is overwritten with the recorded values and
+    /// the frame advances one fixed tick whatever `dt` was. Without a tape
+    /// the frame keeps `dt` and the live `look` input.
+    pub(crate) fn update(
+        &mut self,
+        dt: f64,
+        look: LookInput,
+        actions: &mut ActionState,
+    ) -> TapeFrame {
+        let first_tick = self.tick;
+        match &mut self.mode {
+            Mode::Off => TapeFrame { dt, look },
+            Mode::Recording {
+                recorder,
+                path,
+                pending_mouse,
+            } => {
+                self.accumulator += dt;
+                while self.accumulator >= FIXED_DT {
+                    self.accumulator -= FIXED_DT;
+                    self.tick += 1;
+                }
+                *pending_mouse += look.mouse_delta;
+                for tick in first_tick..self.tick {
+                    let tick_look = LookInput {
+                        mouse_delta: std::mem::take(pending_mouse),
+                        right_stick: look.right_stick,
+                    };
+                    recorder.record_tick(tick, actions, tick_look);
+                    if tick > 0 && tick.is_multiple_of(FLUSH_INTERVAL_TICKS) {
+                        save_recording(recorder, path);
+                    }
+                }
+                TapeFrame {
+                    dt: (self.tick - first_tick) as f64 * FIXED_DT,
+                    look,
+                }
+            }
+            Mode::Replaying(player) => {
+                self.tick += 1;
+                TapeFrame {
+                    dt: FIXED_DT,
+                    look: player.play_tick(first_tick, actions),
+                }
+            }
+        }
+    }
+}
+
+impl Drop for InputTape {
+    fn drop(&mut self) {
+        if let Mode::Recording { recorder, path, .. } = &self.mode {
+            save_recording(recorder, path);
+        }
+    }
+}
+
+/// Writes everything recorded so far to `path`.
+fn save_recording(recorder: &InputRecorder, path: &std::path::Path) {
+    if let Err(e) = recorder.snapshot().save(path) {
+        warn!("Failed to save input recording to {}: {e}", path.display());
+    }
+}
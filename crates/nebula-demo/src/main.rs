@@ -3,8 +3,12 @@
 //! Configuration is loaded from `config.ron` and can be overridden via CLI flags.
 //! Run with `cargo run -p nebula-demo` to see the window.
 //! Run with `cargo run -p nebula-demo -- --width 1920 --height 1080` to override size.
+//! Run with `--record input.nvir` to capture input and `--replay input.nvir` to play it back.
 
+mod chunk_budget;
+mod chunk_streaming;
 mod cubesphere_demos;
+mod input_tape;
 
 use bevy_ecs::prelude::IntoSystemConfigs;
 use clap::Parser;
@@ -275,7 +279,7 @@ fn demonstrate_draw_call_batching() {
         let pipeline_id = rng.gen_range(0..3_u64); // 3 pipelines
         let material_id = rng.gen_range(0..4_u64); // 4 materials
         let mesh_id = 1; // all cubes share the same mesh
-        batch.push(DrawCall {
+        batch.push(DrawCall::Individual {
             pipeline_id,
             material_id,
             mesh_id,
@@ -309,21 +313,21 @@ fn demonstrate_voxel_registry() -> usize {
         solid: true,
         transparency: Transparency::Opaque,
         material_index: 1,
-        light_emission: 0,
+        light_emission: [0; 3],
     };
     let dirt = VoxelTypeDef {
         name: "dirt".to_string(),
         solid: true,
         transparency: Transparency::Opaque,
         material_index: 2,
-        light_emission: 0,
+        light_emission: [0; 3],
     };
     let grass = VoxelTypeDef {
         name: "grass".to_string(),
         solid: true,
         transparency: Transparency::Opaque,
         material_index: 3,
-        light_emission: 0,
+        light_emission: [0; 3],
     };
 
     registry.register(stone).expect("failed to register stone");
@@ -783,7 +787,7 @@ fn demonstrate_visible_face_detection() -> (u32, u32) {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 1,
-            light_emission: 0,
+            light_emission: [0; 3],
         })
         .expect("register stone");
     let glass_id = registry
@@ -792,7 +796,7 @@ fn demonstrate_visible_face_detection() -> (u32, u32) {
             solid: true,
             transparency: Transparency::SemiTransparent,
             material_index: 2,
-            light_emission: 0,
+            light_emission: [0; 3],
         })
         .expect("register glass");
 
@@ -835,7 +839,7 @@ fn demonstrate_greedy_meshing() -> (usize, usize) {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 3,
-            light_emission: 0,
+            light_emission: [0; 3],
         })
         .expect("register grass");
 
@@ -887,7 +891,7 @@ fn demonstrate_ambient_occlusion() -> (u8, u8, usize) {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 1,
-            light_emission: 0,
+            light_emission: [0; 3],
         })
         .expect("register stone");
 
@@ -933,7 +937,7 @@ fn demonstrate_adjacent_chunk_culling() -> (u32, u32) {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 1,
-            light_emission: 0,
+            light_emission: [0; 3],
         })
         .expect("register stone");
 
@@ -976,7 +980,15 @@ fn demonstrate_adjacent_chunk_culling() -> (u32, u32) {
 
 /// Demonstrates GPU mesh upload and buffer pool reuse.
 fn demonstrate_gpu_mesh_upload() -> (u64, u64, bool) {
-    use nebula_mesh::{ChunkVertex, FaceDirection, PackedChunkMesh};
+    use nebula_materials::MaterialId;
+    use nebula_mesh::{
+        ChunkVertex, DEFAULT_BIOME_BLEND_RADIUS, FaceDirection, PackedChunkMesh,
+        pack_blended_chunk_mesh,
+    };
+    use nebula_render::{BLENDED_CHUNK_SHADER_SOURCE, TexturedPipeline, TransparentDrawList};
+
+    /// Material of the glass pane standing behind the demo surface.
+    const GLASS_MATERIAL: u16 = 3;
 
     info!("Starting GPU mesh upload demonstration");
 
@@ -1026,12 +1038,55 @@ fn demonstrate_gpu_mesh_upload() -> (u64, u64, bool) {
         }
     }
 
-    // Upload to GPU
-    let mut gpu_mesh = GpuChunkMesh::upload(&device, &mesh);
+    // A glass pane at the far edge, four quads wide.
+    for i in 0..4u8 {
+        let x = i * 2;
+        mesh.push_quad(
+            [
+                ChunkVertex::new([x, 0, 10], FaceDirection::NegZ, 0, GLASS_MATERIAL, [0, 0]),
+                ChunkVertex::new(
+                    [x + 2, 0, 10],
+                    FaceDirection::NegZ,
+                    0,
+                    GLASS_MATERIAL,
+                    [1, 0],
+                ),
+                ChunkVertex::new(
+                    [x + 2, 3, 10],
+                    FaceDirection::NegZ,
+                    0,
+                    GLASS_MATERIAL,
+                    [1, 1],
+                ),
+                ChunkVertex::new([x, 3, 10], FaceDirection::NegZ, 0, GLASS_MATERIAL, [0, 1]),
+            ],
+            false,
+        );
+    }
+
+    // Upload to GPU, splitting the glass off into the translucent range.
+    let is_glass = |m: u16| m == GLASS_MATERIAL;
+    let mut gpu_mesh = GpuChunkMesh::upload(&device, &mesh, is_glass);
     let upload_bytes = gpu_mesh.total_gpu_bytes();
     info!(
-        "Uploaded mesh: {} vertices, {} indices, {} bytes on GPU",
-        gpu_mesh.vertex_count, gpu_mesh.index_count, upload_bytes
+        "Uploaded mesh: {} vertices, {} indices ({} translucent quads), {} bytes on GPU",
+        gpu_mesh.vertex_count,
+        gpu_mesh.index_count,
+        gpu_mesh.transparent_centers.len(),
+        upload_bytes
+    );
+
+    // A camera west of the chunk looking east sorts the pane's quads east to
+    // west and draws them as one batch.
+    let camera_z = glam::Vec3::X;
+    let resorted = gpu_mesh.sort_transparent(&queue, camera_z);
+    let mut translucent = TransparentDrawList::new();
+    translucent.push_chunk(0, &gpu_mesh, glam::Vec3::new(16.0, 0.0, 0.0));
+    translucent.sort_back_to_front(camera_z);
+    info!(
+        "Translucent pass: {} batch(es), glass re-sorted = {}",
+        translucent.len(),
+        resorted
     );
 
     // Re-upload a smaller mesh (simulating remesh after block edit)
@@ -1047,12 +1102,110 @@ fn demonstrate_gpu_mesh_upload() -> (u64, u64, bool) {
             false,
         );
     }
-    let reused = gpu_mesh.reupload(&device, &queue, &small_mesh);
+    let reused = gpu_mesh.reupload(&device, &queue, &small_mesh, is_glass);
     info!(
         "Reupload (smaller mesh): reused existing buffers = {}",
         reused
     );
 
+    // Mesh a floor straddling a desert/grass boundary and draw it through
+    // the blended chunk pipeline.
+    struct SplitBiomes;
+    impl nebula_materials::BiomeMap for SplitBiomes {
+        fn sample(&self, world_x: i128, _world_z: i128) -> (MaterialId, MaterialId, f32) {
+            let m = if world_x < 16 {
+                MaterialId(1)
+            } else {
+                MaterialId(2)
+            };
+            (m, m, 0.0)
+        }
+    }
+    let mut reg = VoxelTypeRegistry::new();
+    let sand = reg
+        .register(VoxelTypeDef {
+            name: "sand".to_string(),
+            solid: true,
+            transparency: Transparency::Opaque,
+            material_index: 1,
+            light_emission: [0; 3],
+        })
+        .expect("register sand");
+    let grass = reg
+        .register(VoxelTypeDef {
+            name: "grass".to_string(),
+            solid: true,
+            transparency: Transparency::Opaque,
+            material_index: 2,
+            light_emission: [0; 3],
+        })
+        .expect("register grass");
+    let mut floor = ChunkData::new_air();
+    for z in 0..32 {
+        for x in 0..32 {
+            floor.set(x, 0, z, if x < 16 { sand } else { grass });
+        }
+    }
+    let neighbors = ChunkNeighborhood::all_air();
+    let visible = compute_visible_faces(&floor, &neighbors, &reg);
+    let floor_mesh = pack_blended_chunk_mesh(
+        &greedy_mesh(&floor, &visible, &neighbors, &reg),
+        &reg,
+        &SplitBiomes,
+        [0, 0],
+        DEFAULT_BIOME_BLEND_RADIUS,
+    );
+    let blended = floor_mesh
+        .vertices
+        .iter()
+        .filter(|v| v.blend_weight > 0)
+        .count();
+    let translucent_materials: Vec<u16> = (0..reg.len())
+        .map(|id| reg.get(VoxelTypeId(id as u16)))
+        .filter(|def| def.transparency == Transparency::SemiTransparent)
+        .map(|def| def.material_index)
+        .collect();
+    let _floor_gpu =
+        GpuChunkMesh::upload(&device, &floor_mesh, |m| translucent_materials.contains(&m));
+    let chunk_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("blended-chunk-shader"),
+        source: wgpu::ShaderSource::Wgsl(BLENDED_CHUNK_SHADER_SOURCE.into()),
+    });
+    let material_layers_layout =
+        device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("chunk-material-layers-layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2Array,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+    let _chunk_pipeline = TexturedPipeline::new_blended_chunk(
+        &device,
+        &chunk_shader,
+        wgpu::TextureFormat::Bgra8UnormSrgb,
+        Some(wgpu::TextureFormat::Depth32Float),
+        &material_layers_layout,
+    );
+    info!(
+        "Blended chunk: {} of {} vertices mix a neighboring biome material",
+        blended,
+        floor_mesh.vertices.len()
+    );
+
     // Demonstrate buffer pool
     let mut pool = GpuBufferPool::new();
     let (vb, vc) = pool.acquire_vertex_buffer(&device, 1000);
@@ -1076,10 +1229,57 @@ fn demonstrate_gpu_mesh_upload() -> (u64, u64, bool) {
         pool_allocated == pool_allocated_after
     );
 
+    demonstrate_budgeted_chunk_loading(&adapter, &mut pool);
+
     info!("GPU mesh upload demonstration completed successfully");
     (upload_bytes, pool_allocated, reused)
 }
 
+/// Demonstrates chunk loading throttled by a memory budget detected from
+/// the adapter, with the buffer pool's idle buffers counted as GPU memory.
+fn demonstrate_budgeted_chunk_loading(adapter: &wgpu::Adapter, pool: &mut GpuBufferPool) {
+    use nebula_lod::{ChunkMemoryUsage, MemoryBudgetConfig};
+
+    let detected = MemoryBudgetConfig::detect(
+        adapter.limits().max_buffer_size,
+        adapter.get_info().device_type == wgpu::DeviceType::IntegratedGpu,
+    );
+    // A deliberately small voxel budget so pressure builds within a few ticks.
+    let budget = MemoryBudgetConfig {
+        voxel_budget: 200 * ChunkMemoryUsage::estimate(0, 0).voxel_bytes,
+        ..detected
+    };
+    let mut loader = chunk_budget::BudgetedChunkLoader::new(
+        ChunkLoader::new(ChunkLoadConfig {
+            load_radius: 4,
+            unload_radius: 6,
+            loads_per_tick: 32,
+            unloads_per_tick: 16,
+        }),
+        budget,
+    );
+    loader.attach_pool(pool);
+    info!(
+        "Budgeted chunk loading: GPU budget {} MB, idle pool buffers {} bytes",
+        loader.tracker().config().gpu_budget / (1024 * 1024),
+        loader.tracker().total_gpu_bytes()
+    );
+
+    let mut manager = ChunkManager::new();
+    let camera = ChunkAddress::new(0, 0, 0, 0);
+    for tick in 0..12 {
+        let pressure = loader.tracker().pressure();
+        let result = loader.tick(camera, &mut manager, 32);
+        info!(
+            "  Tick {}: {:?} pressure, loaded {} (total {})",
+            tick,
+            pressure,
+            result.loaded,
+            manager.loaded_count()
+        );
+    }
+}
+
 /// Demonstrates async mesh generation using the [`MeshingPipeline`].
 ///
 /// Submits multiple chunks to background threads and collects results,
@@ -1097,7 +1297,7 @@ fn demonstrate_async_meshing() -> (usize, usize) {
         transparency: nebula_voxel::Transparency::Opaque,
         solid: true,
         material_index: 0,
-        light_emission: 0,
+        light_emission: [0; 3],
     });
     let registry = Arc::new(reg);
 
@@ -1508,8 +1708,10 @@ fn demonstrate_heightmap() -> (f64, f64) {
 
 /// Demonstrates cubesphere terrain height sampling across all cube faces.
 fn demonstrate_cubesphere_terrain_height() {
-    use nebula_cubesphere::{CubeFace, FaceCoord, face_coord_to_sphere_everitt};
-    use nebula_terrain::{HeightmapParams, TerrainHeightConfig, TerrainHeightSampler};
+    use nebula_cubesphere::{CubeFace, FaceCoord, ProjectionMethod};
+    use nebula_terrain::{
+        HeightmapParams, TerrainHeightConfig, TerrainHeightSampler, column_surface_height,
+    };
 
     info!("Starting cubesphere terrain height demonstration");
 
@@ -1535,8 +1737,7 @@ fn demonstrate_cubesphere_terrain_height() {
                 let u = u_step as f64 / 20.0;
                 let v = v_step as f64 / 20.0;
                 let fc = FaceCoord::new(face, u, v);
-                let sphere_pt = face_coord_to_sphere_everitt(&fc);
-                let h = sampler.sample_height(sphere_pt);
+                let h = column_surface_height(&fc, &sampler, ProjectionMethod::default());
                 min_h = min_h.min(h);
                 max_h = max_h.max(h);
                 total_samples += 1;
@@ -1575,25 +1776,44 @@ fn demonstrate_ore_distribution() {
     let planet_radius = 6_371_000.0_f64;
     let surface_height = planet_radius + 100.0;
 
-    // Sample a grid of subsurface voxels and tally ore hits per type.
+    // Sample a block of subsurface voxels, tally ore hits per type, and
+    // count how often an ore voxel's +X neighbour is also ore. Veins show up
+    // as a neighbour rate far above the overall hit rate.
     let mut ore_hits: std::collections::HashMap<nebula_voxel::VoxelTypeId, u64> =
         std::collections::HashMap::new();
-    let total_samples = 100_000u64;
-
-    for i in 0..total_samples {
-        let depth = (i % 500) as f64 + 1.0;
-        let x = (i as f64) * 0.7;
-        let z = (i as f64) * 1.3;
-        let voxel_pos = DVec3::new(surface_height - depth, x, z);
-        if let Some(ore_id) = distributor.sample_ore(voxel_pos, surface_height) {
-            *ore_hits.entry(ore_id).or_insert(0) += 1;
+    let is_ore = |x: u32, depth: f64, z: u32| {
+        let voxel_pos = DVec3::new(surface_height - depth, f64::from(x), f64::from(z));
+        distributor.sample_ore(voxel_pos, surface_height)
+    };
+    let mut total_samples = 0u64;
+    let mut adjacent_hits = 0u64;
+    for layer in 0..50 {
+        let depth = f64::from(layer) * 10.0 + 1.0;
+        for x in 0..40 {
+            for z in 0..50 {
+                total_samples += 1;
+                if let Some(ore_id) = is_ore(x, depth, z) {
+                    *ore_hits.entry(ore_id).or_insert(0) += 1;
+                    if is_ore(x + 1, depth, z).is_some() {
+                        adjacent_hits += 1;
+                    }
+                }
+            }
         }
     }
 
     let total_ore: u64 = ore_hits.values().sum();
+    let hit_rate = total_ore as f64 / total_samples as f64;
+    let adjacent_rate = adjacent_hits as f64 / total_ore.max(1) as f64;
     info!(
         "Ore distribution: {total_samples} samples, {total_ore} ore hits ({:.2}%), {ore_count} ore types registered",
-        (total_ore as f64 / total_samples as f64) * 100.0
+        hit_rate * 100.0
+    );
+    info!(
+        "Ore clustering: {:.1}% of ore voxels have an ore neighbour vs {:.2}% overall ({:.1}x)",
+        adjacent_rate * 100.0,
+        hit_rate * 100.0,
+        adjacent_rate / hit_rate.max(f64::EPSILON)
     );
 
     for (id, count) in &ore_hits {
@@ -1604,6 +1824,10 @@ fn demonstrate_ore_distribution() {
         total_ore > 0,
         "Expected at least some ore hits in {total_samples} samples"
     );
+    assert!(
+        adjacent_rate > hit_rate,
+        "Expected ore to cluster into veins: neighbour rate {adjacent_rate:.3}, overall {hit_rate:.4}"
+    );
     assert_eq!(distributor.ore_count(), ore_count);
 
     info!("Ore resource distribution demonstration completed successfully");
@@ -1760,7 +1984,7 @@ fn demonstrate_feature_placement() -> usize {
 /// Demonstrates terrain debug visualization: generates heightmap, biome, cave, and ore debug images.
 fn demonstrate_terrain_debug_viz() {
     use glam::DVec3;
-    use nebula_cubesphere::CubeFace;
+    use nebula_cubesphere::{CubeFace, ProjectionMethod};
     use nebula_terrain::{
         BiomeDef, BiomeRegistry, BiomeSampler, CaveCarver, CaveConfig, HeightmapParams,
         HeightmapSampler, OreDistributor, SliceParams, TerrainDebugState, TerrainHeightConfig,
@@ -1792,6 +2016,7 @@ fn demonstrate_terrain_debug_viz() {
         128,
         CubeFace::PosX,
         (0.0, 0.0, 1.0, 1.0),
+        ProjectionMethod::default(),
     );
     info!(
         "Heightmap debug: {}x{}, {} unique colors",
@@ -1865,6 +2090,7 @@ fn demonstrate_terrain_debug_viz() {
         128,
         CubeFace::PosX,
         (0.0, 0.0, 1.0, 1.0),
+        ProjectionMethod::default(),
     );
     info!(
         "Biome debug: {}x{}, {} unique colors",
@@ -2091,7 +2317,8 @@ fn demonstrate_quadtree_lod_per_face() {
 
     info!("Starting quadtree LOD per-face demonstration");
 
-    let planet_radius: f64 = 6_371_000_000.0; // Earth-like, mm
+    let planet = PlanetDef::earth_like("Terra", WorldPosition::default(), 42);
+    let planet_radius = planet.radius as f64; // mm
 
     // Create a quadtree for the +Y face with max_depth 5
     let mut qt = FaceQuadtreeLod::new(
@@ -2099,7 +2326,8 @@ fn demonstrate_quadtree_lod_per_face() {
         5,
         LodThresholds::default_planet(),
         planet_radius,
-    );
+    )
+    .with_projection(planet.projection);
 
     // Camera far away in space — should produce minimal chunks
     let far_camera = nebula_coords::WorldPosition::new(0, 100_000_000_000_000, 0);
@@ -2146,9 +2374,30 @@ fn demonstrate_quadtree_lod_per_face() {
     let min_lod = near_chunks.iter().map(|c| c.lod).min().unwrap_or(0);
     info!("LOD range: {} (finest) to {} (coarsest)", min_lod, max_lod);
 
+    // Warm start: a tree restored from a snapshot at the same camera is
+    // already settled
+    let blob = nebula_lod::encode_quadtree_leaves([&qt]);
+    let mut warm = FaceQuadtreeLod::new(
+        CubeFace::PosY,
+        5,
+        LodThresholds::default_planet(),
+        planet_radius,
+    )
+    .with_projection(planet.projection);
+    let warm_chunks = warm
+        .restore(&blob, &near_camera)
+        .expect("snapshot of a live tree restores");
+    info!(
+        "Warm start: {} bytes restored {} chunks with {} LOD changes",
+        blob.len(),
+        warm_chunks.len(),
+        warm.last_actions().len()
+    );
+
     // Test all 6 faces
     for face in CubeFace::ALL {
-        let mut fqt = FaceQuadtreeLod::new(face, 5, LodThresholds::default_planet(), planet_radius);
+        let mut fqt = FaceQuadtreeLod::new(face, 5, LodThresholds::default_planet(), planet_radius)
+            .with_projection(planet.projection);
         let chunks = fqt.update(&near_camera);
         info!("  Face {:?}: {} active chunks", face, chunks.len());
     }
@@ -2411,8 +2660,8 @@ fn demonstrate_lod_memory_budget() {
             "  Over budget! Evicting {} lowest-priority chunks",
             evictions.len()
         );
-        for addr in &evictions {
-            tracker.on_chunk_unloaded(addr);
+        for eviction in &evictions {
+            tracker.apply_eviction(eviction);
         }
         let after_mb =
             (tracker.total_voxel_bytes() + tracker.total_mesh_bytes()) as f64 / (1024.0 * 1024.0);
@@ -2537,7 +2786,7 @@ fn demonstrate_voxel_collision() {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 1,
-            light_emission: 0,
+            light_emission: [0; 3],
         })
         .unwrap();
 
@@ -3393,7 +3642,9 @@ fn demonstrate_entity_replication() {
     server_sys.add_client(1);
 
     let mut server_world = World::new();
-    let net_id = server_sys.allocate_network_id();
+    let net_id = server_sys
+        .allocate_network_id()
+        .expect("fresh server has free network ids");
     let entity = server_world
         .spawn((net_id, DemoPos { x: 100, y: 200 }))
         .id();
@@ -3411,7 +3662,7 @@ fn demonstrate_entity_replication() {
     let mut client_world = World::new();
     let mut client_sys = ReplicationClientSystem::new();
     client_sys.apply(&mut client_world, &rep_set, client_msgs);
-    info!("Client received spawn for NetworkId({})", net_id.0);
+    info!("Client received spawn for {net_id:?}");
 
     // Tick 2: modify position → delta update.
     server_world.get_mut::<DemoPos>(entity).unwrap().x = 999;
@@ -3467,11 +3718,11 @@ fn demonstrate_spatial_interest() {
     // Tick 1: two entities, one inside (100m), one outside (800m).
     let entities = vec![
         TrackedEntity {
-            network_id: NetworkId(100),
+            network_id: NetworkId::new(100),
             position: InterestPosition::new(100.0, 0.0, 0.0),
         },
         TrackedEntity {
-            network_id: NetworkId(200),
+            network_id: NetworkId::new(200),
             position: InterestPosition::new(800.0, 0.0, 0.0),
         },
     ];
@@ -3488,11 +3739,11 @@ fn demonstrate_spatial_interest() {
     // Tick 2: outside entity moves inside (300m).
     let entities_moved = vec![
         TrackedEntity {
-            network_id: NetworkId(100),
+            network_id: NetworkId::new(100),
             position: InterestPosition::new(100.0, 0.0, 0.0),
         },
         TrackedEntity {
-            network_id: NetworkId(200),
+            network_id: NetworkId::new(200),
             position: InterestPosition::new(300.0, 0.0, 0.0),
         },
     ];
@@ -3509,11 +3760,11 @@ fn demonstrate_spatial_interest() {
     // Tick 3: first entity leaves (700m).
     let entities_leave = vec![
         TrackedEntity {
-            network_id: NetworkId(100),
+            network_id: NetworkId::new(100),
             position: InterestPosition::new(700.0, 0.0, 0.0),
         },
         TrackedEntity {
-            network_id: NetworkId(200),
+            network_id: NetworkId::new(200),
             position: InterestPosition::new(300.0, 0.0, 0.0),
         },
     ];
@@ -3745,10 +3996,10 @@ fn demonstrate_chunk_data_streaming() {
 
 /// Demonstrates voxel edit replication: validation, application, and broadcast.
 fn demonstrate_voxel_edit_replication() {
-    use nebula_multiplayer::replication::NetworkId;
+    use nebula_multiplayer::NetworkId;
     use nebula_multiplayer::{
-        ChunkId, EditRejection, PlayerPosition, ServerChunkStore, VoxelEditIntent, VoxelMaterial,
-        apply_voxel_edit, validate_voxel_edit,
+        ChunkId, EditRejection, PlayerPosition, ServerChunkStore, VoxelEditIntent,
+        VoxelEditRateLimiter, VoxelMaterial, apply_voxel_edit, validate_voxel_edit,
     };
 
     info!("Starting voxel edit replication demonstration");
@@ -3769,6 +4020,7 @@ fn demonstrate_voxel_edit_replication() {
         y: 1000,
         z: 1000,
     };
+    let mut limiter = VoxelEditRateLimiter::default();
 
     // Valid placement.
     let place = VoxelEditIntent::Place {
@@ -3780,9 +4032,9 @@ fn demonstrate_voxel_edit_replication() {
         source_inventory_slot: 0,
     };
 
-    match validate_voxel_edit(&player, &place, &store) {
+    match validate_voxel_edit(&player, &place, &store, &mut limiter, 1) {
         Ok(()) => {
-            let event = apply_voxel_edit(&place, &mut store, NetworkId(1), 1);
+            let event = apply_voxel_edit(&place, &mut store, NetworkId::new(1), 1);
             info!(
                 "Voxel placed: material={:?} at chunk {:?} pos ({},{},{}), broadcast tick={}",
                 event.new_material,
@@ -3797,7 +4049,7 @@ fn demonstrate_voxel_edit_replication() {
     }
 
     // Duplicate placement (should be rejected as obstructed).
-    match validate_voxel_edit(&player, &place, &store) {
+    match validate_voxel_edit(&player, &place, &store, &mut limiter, 1) {
         Ok(()) => info!("Unexpected success on duplicate placement"),
         Err(EditRejection::Obstructed) => {
             info!("Correctly rejected duplicate placement: obstructed");
@@ -3821,11 +4073,18 @@ fn demonstrate_voxel_edit_replication() {
         source_inventory_slot: 0,
     };
     // Chunk not loaded — will be rejected.
-    match validate_voxel_edit(&player, &far_place, &store) {
+    match validate_voxel_edit(&player, &far_place, &store, &mut limiter, 1) {
         Ok(()) => info!("Unexpected success on far placement"),
         Err(e) => info!("Correctly rejected far placement: {e}"),
     }
 
+    // A flood of edits in one tick runs out of allowance and burst tokens.
+    let accepted = (0..100)
+        .filter(|_| limiter.check_and_consume(2).is_ok())
+        .count();
+    info!("Flooding client: {accepted}/100 edits accepted this tick");
+    limiter.end_tick();
+
     info!("Voxel edit replication demonstration completed successfully");
 }
 
@@ -3835,15 +4094,16 @@ fn demonstrate_player_join_leave() {
     };
     use nebula_multiplayer::{
         AuthoritativeWorld, ConnectionRequest, DisconnectRequest, PROTOCOL_VERSION,
-        ReplicationServerSystem, ReplicationSet,
+        ReplicationServerSystem, ReplicationSet, VoxelEditRateLimiter,
     };
 
     info!("Starting player join/leave demonstration");
 
     let mut world = AuthoritativeWorld::new();
     let mut repl = ReplicationServerSystem::new();
+    let mut edit_limiter = VoxelEditRateLimiter::default();
     let mut rep_set = ReplicationSet::new();
-    rep_set.register::<nebula_multiplayer::replication::NetworkId>("NetworkId");
+    rep_set.register::<nebula_multiplayer::NetworkId>("NetworkId");
     rep_set.register::<nebula_multiplayer::PlayerState>("PlayerState");
 
     // Simulate connection request.
@@ -3858,12 +4118,12 @@ fn demonstrate_player_join_leave() {
     );
 
     // Join Alice.
-    let (entity_a, net_a) = spawn_player(&mut world, &mut repl, 1, None);
+    let (entity_a, net_a) = spawn_player(&mut world, &mut repl, 1, None).expect("join Alice");
     info!("  Alice joined: entity={entity_a:?}, net_id={net_a:?}");
     let _ = repl.replicate(world.world(), &rep_set, world.tick());
 
     // Join Bob.
-    let (_entity_b, net_b) = spawn_player(&mut world, &mut repl, 2, None);
+    let (_entity_b, net_b) = spawn_player(&mut world, &mut repl, 2, None).expect("join Bob");
     info!("  Bob joined: net_id={net_b:?}");
 
     // Replicate — Alice sees Bob spawn.
@@ -3888,11 +4148,12 @@ fn demonstrate_player_join_leave() {
         reason: DisconnectReason::Voluntary,
     };
     info!("  Alice disconnecting: reason={:?}", dc.reason);
-    remove_player(&mut world, &mut repl, 1, entity_a);
+    remove_player(&mut world, &mut repl, &mut edit_limiter, 1, entity_a);
     info!("  Alice removed, player_count={}", world.player_count());
 
     // Rejoin Alice with saved state.
-    let (_entity_a2, net_a2) = spawn_player(&mut world, &mut repl, 1, Some(&save));
+    let (_entity_a2, net_a2) =
+        spawn_player(&mut world, &mut repl, 1, Some(&save)).expect("rejoin Alice");
     let ps = world.find_player(1).expect("Alice rejoined");
     info!(
         "  Alice rejoined: net_id={net_a2:?} pos=({},{},{}) — state persisted ✓",
@@ -3906,8 +4167,8 @@ fn demonstrate_player_join_leave() {
 fn demonstrate_chat_system() {
     use nebula_multiplayer::interest::InterestPosition;
     use nebula_multiplayer::{
-        ChatConfig, ChatMessageIntent, ChatRejection, ChatScope, ConnectedClient, NetworkId,
-        RateTracker, broadcast_chat, validate_chat_message,
+        ChatChannel, ChatConfig, ChatMessageIntent, ChatRejection, ChatScope, ConnectedClient,
+        JoinChannel, NetworkId, RateTracker, broadcast_chat, validate_chat_message,
     };
 
     info!("Starting chat system demonstration");
@@ -3924,7 +4185,7 @@ fn demonstrate_chat_system() {
     info!("  Global message validated OK");
 
     let msg = nebula_multiplayer::ChatMessage {
-        sender_network_id: NetworkId(1),
+        sender_network_id: NetworkId::new(1),
         sender_name: "Alice".into(),
         scope: ChatScope::Global,
         content: intent.content.clone(),
@@ -3932,16 +4193,17 @@ fn demonstrate_chat_system() {
         timestamp: 1_700_000_000_000,
     };
 
-    let clients = vec![
-        ConnectedClient {
-            client_id: 2,
-            position: InterestPosition::new(0.0, 0.0, 0.0),
-        },
-        ConnectedClient {
-            client_id: 3,
-            position: InterestPosition::new(100.0, 0.0, 0.0),
-        },
-    ];
+    // Channels are opt-in; both clients join global and proximity chat.
+    let clients: Vec<ConnectedClient> = [(2, 0.0), (3, 100.0)]
+        .into_iter()
+        .map(|(id, x)| {
+            let mut client = ConnectedClient::new(id, InterestPosition::new(x, 0.0, 0.0));
+            for channel in [ChatChannel::Global, ChatChannel::Proximity] {
+                client.join_channel(&JoinChannel { channel });
+            }
+            client
+        })
+        .collect();
     let recipients = broadcast_chat(
         &msg,
         &InterestPosition::new(0.0, 0.0, 0.0),
@@ -3958,7 +4220,7 @@ fn demonstrate_chat_system() {
     };
     assert!(validate_chat_message(&config, &mut tracker, &prox).is_ok());
     let prox_msg = nebula_multiplayer::ChatMessage {
-        sender_network_id: NetworkId(1),
+        sender_network_id: NetworkId::new(1),
         sender_name: "Alice".into(),
         scope: prox.scope.clone(),
         content: prox.content.clone(),
@@ -4053,7 +4315,7 @@ fn demonstrate_world_state_snapshots() {
         },
         modified_chunks: chunks,
         entities: vec![EntitySnapshot {
-            network_id: NetworkId(1),
+            network_id: NetworkId::new(1),
             components: vec![("Pos".into(), vec![1, 2, 3])],
         }],
         world_time: 100.0,
@@ -4246,6 +4508,7 @@ fn demonstrate_bandwidth_budgeting() {
 
 fn main() {
     let args = CliArgs::parse();
+    let (record_path, replay_path) = (args.record.clone(), args.replay.clone());
 
     // Resolve config directory
     let config_dir = args.config.clone().unwrap_or_else(|| {
@@ -4728,10 +4991,24 @@ fn main() {
     };
     let mut ship_velocity = nebula_ecs::Velocity::default();
     let mut spaceship_mode = false;
+    // Hull mirrors the ship into the physics world so hits rumble the gamepad.
+    let ship_hull = nebula_player::ShipHull::spawn(
+        &mut ecs_world.resource_mut::<nebula_physics::PhysicsWorld>(),
+        2.0,
+    );
 
-    // Third-person camera: follows a placeholder "player" entity.
+    // Third-person camera: follows a placeholder "player" entity, whose
+    // capsule the camera collision probe must ignore.
     let mut tps_camera = nebula_player::ThirdPersonCamera::default();
     let player_target_pos = nebula_ecs::WorldPos::new(1_000_000, 2_000_000, 500_000);
+    let player_body = {
+        let origin = ecs_world
+            .resource::<nebula_physics::PhysicsOrigin>()
+            .world_origin;
+        let local = nebula_physics::world_to_local(&player_target_pos.0, &origin);
+        let mut physics = ecs_world.resource_mut::<nebula_physics::PhysicsWorld>();
+        nebula_physics::spawn_player_physics(&mut physics, local).body_handle
+    };
     let mut tps_cam_pos = nebula_ecs::WorldPos::new(1_000_000, 2_005_000, 505_000);
     let mut tps_cam_rotation = nebula_ecs::Rotation::default();
 
@@ -4744,6 +5021,28 @@ fn main() {
     let mut free_fly_cam = nebula_player::FreeFlyCam::default();
     let mut free_fly_overlay = nebula_player::DebugCameraOverlay::default();
 
+    // Chunk streaming around the player, paced by measured chunk work time.
+    let mut chunk_streamer = {
+        let mut registry = VoxelTypeRegistry::new();
+        let stone = registry
+            .register(VoxelTypeDef {
+                name: "stone".to_string(),
+                transparency: nebula_voxel::Transparency::Opaque,
+                solid: true,
+                material_index: 0,
+                light_emission: [0; 3],
+            })
+            .expect("registering stone in an empty registry");
+        // Ground level just below the starting position, in voxels.
+        let ground_y = (demo_state.position.y / 1000) as i64 - 2;
+        chunk_streaming::ChunkStreamer::new(
+            terra.clone(),
+            std::sync::Arc::new(registry),
+            nebula_lod::FrameBudgetScheduler::default(),
+            move |task| chunk_streaming::ground_chunk(task, ground_y, stone),
+        )
+    };
+
     // Gravity-oriented camera: up always away from planet center.
     let mut gravity_dir = nebula_player::GravityDirection::default();
     let mut grav_cam = nebula_player::GravityOrientedCamera::default();
@@ -4757,7 +5056,7 @@ fn main() {
     // Load keybindings from config or fall back to defaults.
     let input_map = if let Some(input_path) = nebula_input::InputMap::default_config_path() {
         info!("Loading keybindings from {}", input_path.display());
-        nebula_input::InputMap::load(&input_path)
+        nebula_input::InputMap::load_or_default(&input_path)
     } else {
         info!("No config directory found; using default keybindings");
         nebula_input::InputMap::default()
@@ -4835,6 +5134,8 @@ fn main() {
     };
     let mut context_stack = nebula_input::InputContextStack::new(gameplay_ctx);
     let mut action_state = nebula_input::ActionState::new();
+    let mut input_tape =
+        input_tape::InputTape::from_args(record_path, replay_path, config.server.world_seed);
 
     // Menu context: Escape to close, Free cursor, consumes input.
     let menu_input_map = {
@@ -4849,8 +5150,6 @@ fn main() {
     };
 
     nebula_app::window::run_with_config_and_input(config, move |dt, kb, ms, _camera| {
-        demo_state.update(dt);
-
         // Poll gamepad events.
         gamepad_mgr.update();
 
@@ -4859,7 +5158,18 @@ fn main() {
             .connected_gamepads()
             .next()
             .and_then(|id| gamepad_mgr.gamepad(id));
-        context_stack.resolve(kb, ms, gamepad, &mut action_state);
+        if !input_tape.is_replaying() {
+            context_stack.resolve(kb, ms, gamepad, None, &mut action_state);
+        }
+        // Record resolved input, or replace it with the replay's. Recording
+        // and replay run the frame on whole fixed ticks.
+        let live_look = nebula_input::LookInput {
+            mouse_delta: ms.delta(),
+            right_stick: gamepad.map_or(glam::Vec2::ZERO, |gp| gp.right_stick()),
+        };
+        let frame = input_tape.update(dt, live_look, &mut action_state);
+        let (dt, look_delta) = (frame.dt, frame.look.mouse_delta);
+        demo_state.update(dt);
 
         // Free-fly debug camera toggle (F1).
         nebula_player::free_fly_toggle_system(kb, &mut free_fly_cam);
@@ -4884,13 +5194,14 @@ fn main() {
         // First-person look: mouse delta → yaw/pitch → rotation quaternion.
         // (Skipped in spaceship mode, during camera transitions, and in free-fly mode.)
         if !spaceship_mode && cam_transition.is_none() && !free_fly_cam.active {
-            nebula_player::first_person_look_system(ms, &mut fps_camera, &mut cam_rotation);
+            fps_camera.apply_mouse_delta(look_delta.x, look_delta.y);
+            cam_rotation.0 = fps_camera.rotation();
         }
 
         // Gamepad right stick rotates view.
         let stick_sensitivity = 2.0_f32; // radians per second at full tilt
-        if let Some(gp) = gamepad {
-            let rs = gp.right_stick();
+        let rs = frame.look.right_stick;
+        if rs != glam::Vec2::ZERO {
             fps_camera.yaw += rs.x * stick_sensitivity * dt as f32;
             fps_camera.pitch -= rs.y * stick_sensitivity * dt as f32;
             fps_camera.pitch = fps_camera
@@ -5004,6 +5315,22 @@ fn main() {
                     &mut ship_velocity,
                 );
                 nebula_player::apply_velocity_system(&ship_velocity, &mut world_pos);
+                let origin = ecs_world
+                    .resource::<nebula_physics::PhysicsOrigin>()
+                    .world_origin;
+                let mut physics = ecs_world.resource_mut::<nebula_physics::PhysicsWorld>();
+                ship_hull.follow(
+                    &mut physics,
+                    nebula_physics::world_to_local(&world_pos.0, &origin),
+                    &ship_velocity,
+                    60.0,
+                );
+                physics.step();
+                nebula_player::spaceship_collision_rumble_system(
+                    ship_hull.impulses(&physics),
+                    &spaceship,
+                    &mut gamepad_mgr,
+                );
             } else {
                 nebula_player::first_person_move_system(
                     kb,
@@ -5027,6 +5354,21 @@ fn main() {
             demo_state.position = world_pos.0;
         }
 
+        // Chunk streaming: loads, mesh uploads and collider builds this frame
+        // follow the frame budget's rates from the last frame's chunk work.
+        {
+            let origin = ecs_world
+                .resource::<nebula_physics::PhysicsOrigin>()
+                .world_origin;
+            let mut physics = ecs_world.resource_mut::<nebula_physics::PhysicsWorld>();
+            chunk_streamer.update(
+                chunk_streaming::ChunkStreamer::chunk_at(&demo_state.position),
+                dt as f32,
+                &mut physics,
+                &origin,
+            );
+        }
+
         // Free-fly overlay (runs every frame, clears when inactive).
         {
             let world_pos = nebula_ecs::WorldPos(demo_state.position);
@@ -5035,14 +5377,32 @@ fn main() {
                 &world_pos,
                 &mut free_fly_overlay,
             );
+            if free_fly_cam.active {
+                free_fly_overlay.text.push('\n');
+                free_fly_overlay
+                    .text
+                    .push_str(&chunk_streamer.overlay_text());
+            }
             if !free_fly_overlay.text.is_empty() {
                 tracing::debug!("Free-fly: {}", free_fly_overlay.text.replace('\n', " | "));
             }
         }
 
-        // Third-person camera: orbit, zoom, follow.
+        // Third-person camera: orbit, zoom, pull in against colliders, follow.
         nebula_player::third_person_orbit_system(ms, &mut tps_camera);
         nebula_player::third_person_zoom_system(ms, &mut tps_camera);
+        {
+            let physics = ecs_world.resource::<nebula_physics::PhysicsWorld>();
+            let origin = ecs_world.resource::<nebula_physics::PhysicsOrigin>();
+            let probe = nebula_player::CameraProbe::physics(physics, origin.world_origin)
+                .excluding(player_body);
+            nebula_player::third_person_collision_system(
+                &mut tps_camera,
+                &player_target_pos,
+                &probe,
+                dt as f32,
+            );
+        }
         nebula_player::third_person_follow_system(
             &tps_camera,
             &player_target_pos,
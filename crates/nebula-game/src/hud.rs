@@ -1,10 +1,12 @@
-//! Basic HUD overlay displayed via the window title.
+//! Basic HUD drawn on the 2D screen overlay.
 //!
-//! Computes speed, altitude, throttle, heading, and FPS from the ship state
-//! and formats them as a compact string for the window title bar.
+//! Computes speed, altitude, throttle, heading, and FPS from the ship state,
+//! formats them as telemetry lines, and draws them with a throttle bar and a
+//! center reticle.
 
 use crate::ship::ShipState;
 use nebula_planet::TransitionConfig;
+use nebula_render::Overlay;
 use std::time::Instant;
 use tracing::info;
 
@@ -133,14 +135,28 @@ pub fn update_hud(
     }
 }
 
-/// Format HUD values as a compact string suitable for a window title.
+/// Text size of the telemetry lines, in pixels.
+const HUD_TEXT_SIZE: f32 = 20.0;
+/// Distance of the telemetry panel from the top-left corner, in pixels.
+const HUD_MARGIN: f32 = 16.0;
+/// Telemetry text color (linear RGBA).
+const HUD_TEXT_COLOR: [f32; 4] = [0.75, 0.95, 1.0, 1.0];
+/// Translucent backing behind the telemetry text.
+const HUD_PANEL_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 0.45];
+/// Throttle bar fill, and its color while boosting.
+const THROTTLE_COLOR: [f32; 4] = [0.2, 0.8, 1.0, 0.9];
+const BOOST_COLOR: [f32; 4] = [1.0, 0.55, 0.1, 0.9];
+/// Reticle arm length, gap from center, and thickness, in pixels.
+const RETICLE_ARM: f32 = 10.0;
+const RETICLE_GAP: f32 = 4.0;
+const RETICLE_THICKNESS: f32 = 2.0;
+const RETICLE_COLOR: [f32; 4] = [0.6, 1.0, 0.6, 0.8];
+
+/// Format HUD values as one telemetry field per line.
 ///
-/// Example: `SPD: 1,234 m/s | ALT: 402.3 km | THR: 75% | HDG: 045° | FPS: 144`
-pub fn format_hud(hud: &HudState) -> String {
-    let throttle = hud.throttle_pct;
-    let heading = hud.heading_deg;
-    let fps = hud.fps;
-
+/// Example: `SPD: 1.2 km/s`, `ALT: 402.3 km`, `THR: 75%`, `HDG: 045°`,
+/// `Orbital(100%)`, `FPS: 144`, with flight and landing indicators before FPS.
+pub fn hud_lines(hud: &HudState) -> Vec<String> {
     // Speed formatting: km/s when > 1000 m/s
     let speed_str = if hud.speed_mps > 1000.0 {
         format!("{:.1} km/s", hud.speed_mps / 1000.0)
@@ -155,30 +171,104 @@ pub fn format_hud(hud: &HudState) -> String {
         format!("{:.1} km", hud.altitude_m / 1000.0)
     };
 
-    let transition = hud.transition_mode;
-    let blend_pct = hud.transition_blend * 100.0;
+    let mut lines = vec![
+        format!("SPD: {speed_str}"),
+        format!("ALT: {alt_str}"),
+        format!("THR: {:.0}%", hud.throttle_pct),
+        format!("HDG: {:03.0}\u{00b0}", hud.heading_deg),
+        format!(
+            "{}({:.0}%)",
+            hud.transition_mode,
+            hud.transition_blend * 100.0
+        ),
+    ];
 
     // Supercruise indicator at high speed
-    let flight_mode = if hud.speed_mps > 2000.0 {
-        " | SUPERCRUISE"
-    } else {
-        ""
-    };
+    if hud.speed_mps > 2000.0 {
+        lines.push("SUPERCRUISE".to_string());
+    }
 
     // Landing/vertical speed indicators
-    let landing_str = if hud.landed {
-        " | SURFACE | Press SPACE to launch".to_string()
+    if hud.landed {
+        lines.push("SURFACE".to_string());
+        lines.push("Press SPACE to launch".to_string());
     } else if hud.landing_mode {
-        format!(" | LANDING MODE | VS: {:.1} m/s", hud.vertical_speed)
+        lines.push("LANDING MODE".to_string());
+        lines.push(format!("VS: {:.1} m/s", hud.vertical_speed));
     } else if hud.altitude_m < 10_000.0 {
-        format!(" | VS: {:.1} m/s", hud.vertical_speed)
+        lines.push(format!("VS: {:.1} m/s", hud.vertical_speed));
+    }
+
+    lines.push(format!("FPS: {:.0}", hud.fps));
+    lines
+}
+
+/// Draw the telemetry panel, throttle bar, and center reticle.
+pub fn draw_hud(hud: &HudState, overlay: &mut Overlay) {
+    let text = hud_lines(hud).join("\n");
+    let [text_w, text_h] = overlay.font_metrics().measure(HUD_TEXT_SIZE, &text);
+    let pad = HUD_TEXT_SIZE * 0.4;
+    let bar_h = HUD_TEXT_SIZE * 0.4;
+    overlay.rect(
+        HUD_MARGIN - pad,
+        HUD_MARGIN - pad,
+        text_w + 2.0 * pad,
+        text_h + bar_h + 3.0 * pad,
+        HUD_PANEL_COLOR,
+    );
+    overlay.text(HUD_MARGIN, HUD_MARGIN, HUD_TEXT_SIZE, HUD_TEXT_COLOR, &text);
+
+    // Throttle bar under the text: full width at 100%, boost in a warmer color.
+    let bar_y = HUD_MARGIN + text_h + pad;
+    let fill = (hud.throttle_pct / 100.0).clamp(0.0, 1.0) as f32;
+    let color = if hud.throttle_pct > 100.0 {
+        BOOST_COLOR
     } else {
-        String::new()
+        THROTTLE_COLOR
     };
-
-    format!(
-        "SPD: {speed_str} | ALT: {alt_str} | THR: {throttle:.0}% | HDG: {heading:03.0}\u{00b0} | {transition}({blend_pct:.0}%){flight_mode}{landing_str} | FPS: {fps:.0}",
-    )
+    overlay.rect(HUD_MARGIN, bar_y, text_w, bar_h, [1.0, 1.0, 1.0, 0.15]);
+    overlay.rect(HUD_MARGIN, bar_y, text_w * fill, bar_h, color);
+
+    // Center reticle: four arms around a gap, and a dot.
+    let (w, h) = overlay.screen_size();
+    let (cx, cy) = ((w * 0.5).round(), (h * 0.5).round());
+    let half = RETICLE_THICKNESS * 0.5;
+    let (near, far) = (RETICLE_GAP, RETICLE_GAP + RETICLE_ARM);
+    overlay.rect(
+        cx - far,
+        cy - half,
+        RETICLE_ARM,
+        RETICLE_THICKNESS,
+        RETICLE_COLOR,
+    );
+    overlay.rect(
+        cx + near,
+        cy - half,
+        RETICLE_ARM,
+        RETICLE_THICKNESS,
+        RETICLE_COLOR,
+    );
+    overlay.rect(
+        cx - half,
+        cy - far,
+        RETICLE_THICKNESS,
+        RETICLE_ARM,
+        RETICLE_COLOR,
+    );
+    overlay.rect(
+        cx - half,
+        cy + near,
+        RETICLE_THICKNESS,
+        RETICLE_ARM,
+        RETICLE_COLOR,
+    );
+    overlay.rect(
+        cx - half,
+        cy - half,
+        RETICLE_THICKNESS,
+        RETICLE_THICKNESS,
+        RETICLE_COLOR,
+    );
 }
 
 /// Format an integer with comma thousands separators.
@@ -208,6 +298,7 @@ mod tests {
             angular_velocity: DVec3::ZERO,
             landed: false,
             vertical_speed: 0.0,
+            thrust_accel: DVec3::ZERO,
         }
     }
 
@@ -269,7 +360,7 @@ mod tests {
     }
 
     #[test]
-    fn test_format_hud_output() {
+    fn test_hud_lines_output() {
         let hud = HudState {
             speed_mps: 1234.0,
             altitude_m: 402_300.0,
@@ -280,7 +371,7 @@ mod tests {
             transition_blend: 1.0,
             ..HudState::default()
         };
-        let s = format_hud(&hud);
+        let s = hud_lines(&hud).join(" | ");
         assert!(s.contains("SPD: 1.2 km/s"), "got: {s}");
         assert!(s.contains("ALT: 402.3 km"));
         assert!(s.contains("THR: 75%"));
@@ -290,7 +381,7 @@ mod tests {
     }
 
     #[test]
-    fn test_format_hud_megameters() {
+    fn test_hud_lines_megameters() {
         let hud = HudState {
             speed_mps: 500.0,
             altitude_m: 2_500_000.0,
@@ -301,29 +392,64 @@ mod tests {
             transition_blend: 1.0,
             ..HudState::default()
         };
-        let s = format_hud(&hud);
+        let s = hud_lines(&hud).join(" | ");
         assert!(s.contains("ALT: 2.50 Mm"), "got: {s}");
         assert!(s.contains("SPD: 500 m/s"), "got: {s}");
     }
 
     #[test]
-    fn test_format_hud_supercruise() {
+    fn test_hud_lines_supercruise() {
         let hud = HudState {
             speed_mps: 3000.0,
             altitude_m: 100_000.0,
             ..HudState::default()
         };
-        let s = format_hud(&hud);
+        let s = hud_lines(&hud).join(" | ");
         assert!(s.contains("SUPERCRUISE"), "got: {s}");
     }
 
     #[test]
-    fn test_format_hud_landed() {
+    fn test_hud_lines_one_field_per_line() {
+        let hud = HudState {
+            throttle_pct: 1000.0,
+            altitude_m: 500.0,
+            landing_mode: true,
+            vertical_speed: -2.5,
+            ..HudState::default()
+        };
+        let lines = hud_lines(&hud);
+        assert_eq!(lines[2], "THR: 1000%");
+        assert!(lines.iter().any(|l| l == "LANDING MODE"));
+        assert!(lines.iter().any(|l| l == "VS: -2.5 m/s"));
+        assert!(lines.last().is_some_and(|l| l.starts_with("FPS: ")));
+    }
+
+    #[test]
+    fn test_draw_hud_places_reticle_at_center() {
+        let mut overlay = Overlay::new(800.0, 600.0);
+        draw_hud(&HudState::default(), &mut overlay);
+        assert!(!overlay.text_vertices().is_empty());
+        // The reticle dot is the last rectangle and straddles the center.
+        let dot = &overlay.quad_vertices()[overlay.quad_vertices().len() - 6..];
+        let (min, max) = dot
+            .iter()
+            .fold(([f32::MAX; 2], [f32::MIN; 2]), |(min, max), v| {
+                (
+                    [min[0].min(v.position[0]), min[1].min(v.position[1])],
+                    [max[0].max(v.position[0]), max[1].max(v.position[1])],
+                )
+            });
+        assert!(min[0] < 400.0 && max[0] > 400.0);
+        assert!(min[1] < 300.0 && max[1] > 300.0);
+    }
+
+    #[test]
+    fn test_hud_lines_landed() {
         let hud = HudState {
             landed: true,
             ..HudState::default()
         };
-        let s = format_hud(&hud);
+        let s = hud_lines(&hud).join(" | ");
         assert!(s.contains("SURFACE | Press SPACE to launch"), "got: {s}");
     }
 }
@@ -0,0 +1,324 @@
+//! Landing gear, surface contact, and landing assist for the ship.
+//!
+//! Each tick after [`update_ship`](crate::ship::update_ship), [`update_landing`]
+//! sweeps a small sphere down from every gear point along local gravity and
+//! resolves contact with whatever [`SurfaceProbe`] reports: the ship is lifted
+//! out of the ground and loses its downward velocity instead of sinking in or
+//! bouncing. A soft, level touchdown on level ground settles into
+//! [`LandingState::Landed`], where the ship is held in place as if welded by a
+//! fixed joint until thrust exceeds [`LandingGear::breakaway_accel`]. A hard
+//! touchdown never settles: the ship has to lift off and touch down again.
+//!
+//! With [`Landing::assist`] enabled, descent rate is clamped and the ship is
+//! levelled automatically within [`LandingGear::assist_altitude`] of the
+//! surface.
+
+use glam::{DQuat, DVec3};
+
+use crate::ship::ShipState;
+
+/// A surface hit reported by a [`SurfaceProbe`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceHit {
+    /// Distance the sphere travelled before touching, in meters. Zero if it
+    /// started out touching or penetrating.
+    pub distance: f64,
+    /// Outward surface normal at the contact, in world space.
+    pub normal: DVec3,
+}
+
+/// Something the landing gear can touch down on.
+pub trait SurfaceProbe {
+    /// Sweep a sphere of `radius` from `origin` along the unit `direction`
+    /// and return the first surface it touches within `max_distance`.
+    fn cast_sphere(
+        &self,
+        origin: DVec3,
+        direction: DVec3,
+        radius: f64,
+        max_distance: f64,
+    ) -> Option<SurfaceHit>;
+}
+
+/// A smooth spherical planet surface.
+#[derive(Debug, Clone, Copy)]
+pub struct SphereSurface {
+    /// Planet center in world space.
+    pub center: DVec3,
+    /// Surface radius in meters.
+    pub radius: f64,
+}
+
+impl SurfaceProbe for SphereSurface {
+    fn cast_sphere(
+        &self,
+        origin: DVec3,
+        direction: DVec3,
+        radius: f64,
+        max_distance: f64,
+    ) -> Option<SurfaceHit> {
+        // Ray against the surface inflated by the probe radius.
+        let reach = self.radius + radius;
+        let offset = origin - self.center;
+        let c = offset.length_squared() - reach * reach;
+        let distance = if c <= 0.0 {
+            0.0
+        } else {
+            let b = offset.dot(direction);
+            let discriminant = b * b - c;
+            if b >= 0.0 || discriminant < 0.0 {
+                return None;
+            }
+            -b - discriminant.sqrt()
+        };
+        if distance > max_distance {
+            return None;
+        }
+        let normal = (offset + direction * distance).try_normalize()?;
+        Some(SurfaceHit { distance, normal })
+    }
+}
+
+/// Landing phase of the ship.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LandingState {
+    /// No gear touching the surface.
+    #[default]
+    Flying,
+    /// Gear in contact, waiting for the ship to settle.
+    Touchdown,
+    /// Settled and held in place until thrust breaks it away.
+    Landed,
+}
+
+/// Landing gear geometry and touchdown limits.
+#[derive(Debug, Clone)]
+pub struct LandingGear {
+    /// Gear foot positions in the ship's local frame, in meters.
+    pub gear_points: Vec<DVec3>,
+    /// Radius of each gear foot's contact sphere, in meters.
+    pub foot_radius: f64,
+    /// Clearance at or below which a foot counts as touching, in meters.
+    pub contact_distance: f64,
+    /// Height above each foot the downward sweep starts from, in meters.
+    /// Must exceed the distance the ship can sink in one tick.
+    pub probe_lift: f64,
+    /// Fastest vertical speed at contact that still counts as a soft
+    /// touchdown, in m/s.
+    pub max_touchdown_speed: f64,
+    /// Largest angle, in radians, between local up and either the ship's up
+    /// axis or the surface normal that still allows landing.
+    pub max_tilt: f64,
+    /// Time all feet must stay in soft, level contact before landing, in
+    /// seconds.
+    pub settle_time: f64,
+    /// Thrust acceleration, in m/s², that breaks a landed ship free.
+    pub breakaway_accel: f64,
+    /// Height above the surface within which landing assist engages, in
+    /// meters.
+    pub assist_altitude: f64,
+    /// Descent rate landing assist clamps to, in m/s. Kept below
+    /// [`max_touchdown_speed`](Self::max_touchdown_speed) so assisted
+    /// touchdowns are soft.
+    pub assist_max_descent: f64,
+    /// Rate at which landing assist levels the ship, in radians per second.
+    pub assist_level_rate: f64,
+}
+
+impl Default for LandingGear {
+    fn default() -> Self {
+        Self {
+            // Tripod: two feet forward, one aft, 1.5 m below the ship center.
+            gear_points: vec![
+                DVec3::new(-2.5, -1.5, -3.0),
+                DVec3::new(2.5, -1.5, -3.0),
+                DVec3::new(0.0, -1.5, 3.0),
+            ],
+            foot_radius: 0.25,
+            contact_distance: 0.1,
+            probe_lift: 2.0,
+            max_touchdown_speed: 3.0,
+            max_tilt: 15_f64.to_radians(),
+            settle_time: 0.5,
+            breakaway_accel: 15.0,
+            assist_altitude: 50.0,
+            assist_max_descent: 2.5,
+            assist_level_rate: 0.5,
+        }
+    }
+}
+
+/// Per-ship landing state.
+#[derive(Debug, Clone, Default)]
+pub struct Landing {
+    /// Current landing phase.
+    pub state: LandingState,
+    /// Whether landing assist is enabled.
+    pub assist: bool,
+    /// Seconds of continuous soft, level contact during touchdown.
+    settled_for: f64,
+    /// Downward speed when the gear first touched, in m/s. Latched until
+    /// lift-off, since contact cancels the descent on the first tick.
+    impact_speed: f64,
+    /// Position and orientation the ship is held at while landed.
+    weld: Option<(DVec3, DQuat)>,
+}
+
+/// Gear contact found by one probe pass.
+struct GearContact {
+    /// Smallest foot clearance above the surface, negative when sunk in.
+    clearance: f64,
+    /// Number of feet within contact distance.
+    touching: usize,
+    /// Average surface normal under the feet that hit.
+    normal: DVec3,
+}
+
+/// Local up: away from the planet center at the origin, like gravity in
+/// [`update_ship`](crate::ship::update_ship).
+fn local_up(ship: &ShipState) -> DVec3 {
+    ship.position.try_normalize().unwrap_or(DVec3::Y)
+}
+
+/// Sweep every gear foot down along `-up`.
+fn probe_gear(
+    ship: &ShipState,
+    gear: &LandingGear,
+    surface: &dyn SurfaceProbe,
+    up: DVec3,
+) -> Option<GearContact> {
+    let range = gear.probe_lift + gear.assist_altitude;
+    let mut contact: Option<GearContact> = None;
+    for point in &gear.gear_points {
+        let foot = ship.position + ship.orientation * *point;
+        let Some(hit) =
+            surface.cast_sphere(foot + up * gear.probe_lift, -up, gear.foot_radius, range)
+        else {
+            continue;
+        };
+        let clearance = hit.distance - gear.probe_lift;
+        let touching = usize::from(clearance <= gear.contact_distance);
+        contact = Some(match contact {
+            None => GearContact {
+                clearance,
+                touching,
+                normal: hit.normal,
+            },
+            Some(c) => GearContact {
+                clearance: c.clearance.min(clearance),
+                touching: c.touching + touching,
+                normal: c.normal + hit.normal,
+            },
+        });
+    }
+    contact.map(|c| GearContact {
+        normal: c.normal.try_normalize().unwrap_or(up),
+        ..c
+    })
+}
+
+/// Clamp descent and rotate the ship's up axis toward `up`.
+fn apply_assist(ship: &mut ShipState, gear: &LandingGear, up: DVec3, dt: f64) {
+    let vertical = ship.velocity.dot(up);
+    if vertical < -gear.assist_max_descent {
+        ship.velocity -= up * (vertical + gear.assist_max_descent);
+    }
+
+    let tilt = ship.up().angle_between(up);
+    if tilt > f64::EPSILON {
+        let t = (gear.assist_level_rate * dt / tilt).min(1.0);
+        let level = DQuat::from_rotation_arc(ship.up(), up);
+        ship.orientation = (DQuat::IDENTITY.slerp(level, t) * ship.orientation).normalize();
+    }
+}
+
+impl Landing {
+    /// Release the weld and return to flight.
+    fn lift_off(&mut self) {
+        self.state = LandingState::Flying;
+        self.settled_for = 0.0;
+        self.impact_speed = 0.0;
+        self.weld = None;
+    }
+}
+
+/// Resolve gear contact and advance the landing state for one tick.
+///
+/// Call after [`update_ship`](crate::ship::update_ship) has integrated the
+/// ship. Keeps [`ShipState::landed`] in sync with [`LandingState::Landed`].
+pub fn update_landing(
+    ship: &mut ShipState,
+    landing: &mut Landing,
+    gear: &LandingGear,
+    surface: &dyn SurfaceProbe,
+    dt: f64,
+) {
+    let up = local_up(ship);
+
+    if landing.state == LandingState::Landed {
+        match landing.weld {
+            Some(_) if ship.thrust_accel.length() > gear.breakaway_accel => landing.lift_off(),
+            Some((position, orientation)) => {
+                ship.position = position;
+                ship.orientation = orientation;
+                ship.velocity = DVec3::ZERO;
+                ship.angular_velocity = DVec3::ZERO;
+                ship.vertical_speed = 0.0;
+                ship.landed = true;
+                return;
+            }
+            None => landing.lift_off(),
+        }
+    }
+
+    let contact = probe_gear(ship, gear, surface, up);
+    if landing.assist
+        && let Some(contact) = &contact
+        && contact.clearance <= gear.assist_altitude
+    {
+        apply_assist(ship, gear, up, dt);
+    }
+
+    match contact {
+        Some(contact) if contact.touching > 0 => {
+            // Lift the gear out of the ground and absorb the impact.
+            if contact.clearance < 0.0 {
+                ship.position -= up * contact.clearance;
+            }
+            let vertical = ship.velocity.dot(up);
+            if vertical < 0.0 {
+                ship.velocity -= up * vertical;
+            }
+
+            if landing.state == LandingState::Flying {
+                landing.state = LandingState::Touchdown;
+                landing.settled_for = 0.0;
+                landing.impact_speed = (-vertical).max(0.0);
+            }
+            let soft = landing.impact_speed <= gear.max_touchdown_speed
+                && vertical.abs() <= gear.max_touchdown_speed;
+            let level = ship.up().angle_between(up) <= gear.max_tilt
+                && contact.normal.angle_between(up) <= gear.max_tilt;
+            let planted = contact.touching == gear.gear_points.len();
+            if soft && level && planted {
+                landing.settled_for += dt;
+            } else {
+                landing.settled_for = 0.0;
+            }
+            if landing.settled_for >= gear.settle_time {
+                landing.state = LandingState::Landed;
+                landing.weld = Some((ship.position, ship.orientation));
+                ship.velocity = DVec3::ZERO;
+                ship.angular_velocity = DVec3::ZERO;
+            }
+        }
+        _ => landing.lift_off(),
+    }
+
+    ship.landed = landing.state == LandingState::Landed;
+    ship.vertical_speed = ship.velocity.dot(up);
+}
+
+#[cfg(test)]
+#[path = "landing_tests.rs"]
+mod tests;
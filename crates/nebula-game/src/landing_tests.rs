@@ -0,0 +1,249 @@
+//! Unit tests for ship landing.
+
+use super::*;
+use crate::ship::{ShipConfig, update_ship};
+use nebula_input::{KeyboardState, MouseState};
+use std::f64::consts::FRAC_PI_6;
+use winit::event::ElementState;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+const DT: f64 = 1.0 / 60.0;
+
+/// Ground top 10 m above the default planet radius, straight up +Y.
+fn ground_origin() -> DVec3 {
+    DVec3::new(0.0, ShipConfig::default().planet_radius_m + 10.0, 0.0)
+}
+
+/// Flat ground through `point`, facing `normal`.
+struct PlaneSurface {
+    point: DVec3,
+    normal: DVec3,
+}
+
+impl SurfaceProbe for PlaneSurface {
+    fn cast_sphere(
+        &self,
+        origin: DVec3,
+        direction: DVec3,
+        radius: f64,
+        max_distance: f64,
+    ) -> Option<SurfaceHit> {
+        let gap = (origin - self.point).dot(self.normal) - radius;
+        let distance = if gap <= 0.0 {
+            0.0
+        } else {
+            let closing = -direction.dot(self.normal);
+            if closing <= 0.0 {
+                return None;
+            }
+            gap / closing
+        };
+        (distance <= max_distance).then_some(SurfaceHit {
+            distance,
+            normal: self.normal,
+        })
+    }
+}
+
+/// Ground through the ground origin, tilted by `slope` radians about Z.
+fn ground(slope: f64) -> PlaneSurface {
+    PlaneSurface {
+        point: ground_origin(),
+        normal: DQuat::from_rotation_z(slope) * DVec3::Y,
+    }
+}
+
+struct Descent {
+    ship: ShipState,
+    landing: Landing,
+    gear: LandingGear,
+    config: ShipConfig,
+}
+
+impl Descent {
+    fn new(height: f64) -> Self {
+        Self {
+            ship: ShipState::new(ground_origin() + DVec3::Y * height),
+            landing: Landing::default(),
+            gear: LandingGear::default(),
+            config: ShipConfig::default(),
+        }
+    }
+
+    /// One tick, optionally holding the ship at `hold_descent` m/s down
+    /// until it lands.
+    fn tick(&mut self, surface: &dyn SurfaceProbe, hold_descent: Option<f64>, kb: &KeyboardState) {
+        if let Some(speed) = hold_descent
+            && self.landing.state != LandingState::Landed
+        {
+            self.ship.velocity = DVec3::NEG_Y * speed;
+        }
+        update_ship(&mut self.ship, &self.config, DT, kb, &MouseState::new());
+        update_landing(&mut self.ship, &mut self.landing, &self.gear, surface, DT);
+    }
+
+    /// Lowest point of the gear feet above the ground origin.
+    fn foot_height(&self) -> f64 {
+        self.gear
+            .gear_points
+            .iter()
+            .map(|p| (self.ship.position + self.ship.orientation * *p).y)
+            .fold(f64::INFINITY, f64::min)
+            - self.gear.foot_radius
+            - ground_origin().y
+    }
+}
+
+#[test]
+fn test_slow_descent_lands_and_stays_put() {
+    let surface = ground(0.0);
+    let mut descent = Descent::new(20.0);
+    let idle = KeyboardState::new();
+    let mut states = Vec::new();
+    for _ in 0..(30.0 / DT) as usize {
+        descent.tick(&surface, Some(2.0), &idle);
+        states.push(descent.landing.state);
+        if descent.landing.state == LandingState::Landed {
+            break;
+        }
+    }
+    assert_eq!(descent.landing.state, LandingState::Landed);
+    assert!(states.contains(&LandingState::Touchdown));
+    assert!(descent.ship.landed);
+    assert!(
+        descent.foot_height().abs() < 0.15,
+        "{}",
+        descent.foot_height()
+    );
+
+    let parked = descent.ship.position;
+    for _ in 0..(10.0 / DT) as usize {
+        descent.tick(&surface, None, &idle);
+        assert_eq!(descent.landing.state, LandingState::Landed);
+    }
+    assert!((descent.ship.position - parked).length() < 1e-6);
+    assert_eq!(descent.ship.velocity, DVec3::ZERO);
+}
+
+#[test]
+fn test_hard_descent_never_lands() {
+    let surface = ground(0.0);
+    let mut descent = Descent::new(20.0);
+    descent.ship.velocity = DVec3::NEG_Y * 30.0;
+    let idle = KeyboardState::new();
+    let mut touched = false;
+    for _ in 0..(10.0 / DT) as usize {
+        descent.tick(&surface, None, &idle);
+        touched |= descent.landing.state == LandingState::Touchdown;
+        assert_ne!(descent.landing.state, LandingState::Landed);
+        assert!(descent.foot_height() > -0.05, "{}", descent.foot_height());
+    }
+    assert!(touched);
+    // The impact is absorbed and the ship rests on its gear, but the hard
+    // touchdown is remembered until it lifts off again.
+    assert_eq!(descent.landing.state, LandingState::Touchdown);
+    assert!(descent.ship.vertical_speed.abs() < 1e-9);
+}
+
+#[test]
+fn test_lift_off_clears_hard_impact() {
+    let surface = ground(0.0);
+    let mut descent = Descent::new(20.0);
+    descent.ship.velocity = DVec3::NEG_Y * 30.0;
+    let idle = KeyboardState::new();
+    for _ in 0..(2.0 / DT) as usize {
+        descent.tick(&surface, None, &idle);
+    }
+    assert_eq!(descent.landing.state, LandingState::Touchdown);
+
+    // Hop clear of the ground, then come back down gently.
+    descent.ship.position += DVec3::Y * 5.0;
+    descent.tick(&surface, None, &idle);
+    assert_eq!(descent.landing.state, LandingState::Flying);
+    for _ in 0..(10.0 / DT) as usize {
+        descent.tick(&surface, Some(2.0), &idle);
+        if descent.landing.state == LandingState::Landed {
+            break;
+        }
+    }
+    assert_eq!(descent.landing.state, LandingState::Landed);
+}
+
+#[test]
+fn test_steep_slope_refuses_landing() {
+    let surface = ground(FRAC_PI_6);
+    let mut descent = Descent::new(20.0);
+    let idle = KeyboardState::new();
+    for _ in 0..(10.0 / DT) as usize {
+        descent.tick(&surface, Some(2.0), &idle);
+        assert_ne!(descent.landing.state, LandingState::Landed);
+    }
+    assert_eq!(descent.landing.state, LandingState::Touchdown);
+}
+
+#[test]
+fn test_assist_clamps_descent_and_levels() {
+    let surface = SphereSurface {
+        center: DVec3::ZERO,
+        radius: ShipConfig::default().planet_radius_m,
+    };
+    let mut descent = Descent::new(30.0);
+    descent.ship.position = DVec3::Y * (surface.radius + 40.0);
+    descent.ship.velocity = DVec3::NEG_Y * 30.0;
+    descent.ship.orientation = DQuat::from_rotation_x(0.3);
+    descent.landing.assist = true;
+    let idle = KeyboardState::new();
+    for _ in 0..(30.0 / DT) as usize {
+        descent.tick(&surface, None, &idle);
+        if descent.landing.state == LandingState::Landed {
+            break;
+        }
+        assert!(descent.ship.vertical_speed >= -descent.gear.assist_max_descent - 0.2);
+    }
+    assert_eq!(descent.landing.state, LandingState::Landed);
+    assert!(descent.ship.up().angle_between(DVec3::Y) <= descent.gear.max_tilt);
+}
+
+#[test]
+fn test_thrust_breaks_weld() {
+    let surface = ground(0.0);
+    let mut descent = Descent::new(3.0);
+    let idle = KeyboardState::new();
+    for _ in 0..(10.0 / DT) as usize {
+        descent.tick(&surface, Some(1.0), &idle);
+    }
+    assert_eq!(descent.landing.state, LandingState::Landed);
+
+    let mut launch = KeyboardState::new();
+    launch.process_raw(nebula_input::keyboard::RawKeyEvent {
+        key: PhysicalKey::Code(KeyCode::Space),
+        state: ElementState::Pressed,
+        repeat: false,
+    });
+    descent.tick(&surface, None, &launch);
+    assert_eq!(descent.landing.state, LandingState::Flying);
+    assert!(!descent.ship.landed);
+    assert!(descent.ship.vertical_speed > 0.0);
+}
+
+#[test]
+fn test_sphere_surface_cast() {
+    let surface = SphereSurface {
+        center: DVec3::ZERO,
+        radius: 100.0,
+    };
+    let hit = surface
+        .cast_sphere(DVec3::new(0.0, 110.0, 0.0), DVec3::NEG_Y, 1.0, 20.0)
+        .unwrap();
+    assert!((hit.distance - 9.0).abs() < 1e-9);
+    assert!((hit.normal - DVec3::Y).length() < 1e-9);
+    assert!(
+        surface
+            .cast_sphere(DVec3::new(0.0, 110.0, 0.0), DVec3::Y, 1.0, 20.0)
+            .is_none()
+    );
+    let inside = surface
+        .cast_sphere(DVec3::new(0.0, 100.5, 0.0), DVec3::NEG_Y, 1.0, 20.0)
+        .unwrap();
+    assert_eq!(inside.distance, 0.0);
+}
@@ -3,10 +3,12 @@
 //! Opens a window with a real-scale Earth planet visible from orbit,
 //! initializes the wgpu renderer, runs the AI Debug API on port 9999,
 //! and provides a ship with 6DOF Newtonian flight for exploring the planet.
+//! Press L to toggle landing assist near the surface.
 //!
 //! Run with: `cargo run -p nebula-game`
 
 mod hud;
+mod landing;
 mod planet;
 mod ship;
 
@@ -79,19 +81,31 @@ fn main() {
     );
 
     let planet_radius_m = config.planet.radius_m;
-    let atmosphere_altitude_m = config.planet.atmosphere_altitude_m;
-
-    // Shared HUD state between the update and title callbacks.
+    let landing_gear = landing::LandingGear::default();
+    let mut ship_landing = landing::Landing::default();
+    let planet_surface = landing::SphereSurface {
+        center: glam::DVec3::ZERO,
+        radius: planet_radius_m,
+    };
+    // Sky fades to black at the configured atmosphere altitude (Kármán line).
+    let mut sky_params = nebula_planet::AtmosphereParams::earth_like(planet_radius_m as f32);
+    sky_params.atmosphere_radius = (planet_radius_m + config.planet.atmosphere_altitude_m) as f32;
+
+    // Shared HUD state between the update and overlay callbacks.
     let hud_state = Rc::new(RefCell::new(hud::HudState::default()));
-    let hud_for_title = Rc::clone(&hud_state);
+    let hud_for_overlay = Rc::clone(&hud_state);
 
     // Shared clear color between the update callback and the clear color callback.
     // Default to deep space black.
     let clear_color = Rc::new(Cell::new([0.02_f64, 0.02, 0.08]));
     let clear_color_for_render = Rc::clone(&clear_color);
 
-    // Run the engine with custom input, HUD title, and dynamic clear color.
-    nebula_app::window::run_with_config_input_title_and_clear(
+    // Sky tint follows the sun over the same day length the renderer uses.
+    let mut sky_clock = nebula_planet::DayNightClock::new(config.planet.day_length_seconds);
+    let sky_atmosphere = nebula_lighting::LightingAtmosphereConfig::default();
+
+    // Run the engine with custom input, on-screen HUD, and dynamic clear color.
+    nebula_app::window::run_with_config_input_overlay_and_clear(
         config,
         move |dt, keyboard, mouse, camera| {
             // Detect thrust and boost state for HUD throttle display.
@@ -103,22 +117,51 @@ fn main() {
                 || keyboard.is_pressed(PhysicalKey::Code(KeyCode::ShiftLeft));
             let is_boosting = keyboard.is_pressed(PhysicalKey::Code(KeyCode::ControlLeft));
 
+            if keyboard.just_pressed(PhysicalKey::Code(KeyCode::KeyL)) {
+                ship_landing.assist = !ship_landing.assist;
+                info!(
+                    "Landing assist {}",
+                    if ship_landing.assist { "on" } else { "off" }
+                );
+            }
+
             ship::update_ship(&mut ship_state, &ship_config, dt, keyboard, mouse);
+            landing::update_landing(
+                &mut ship_state,
+                &mut ship_landing,
+                &landing_gear,
+                &planet_surface,
+                dt,
+            );
             ship::sync_camera_to_ship(camera, &ship_state, is_boosting);
 
-            // Atmospheric clear color: lerp from space black to sky blue based on altitude.
-            let altitude = (ship_state.position.length() - planet_radius_m).max(0.0);
-            let atmo_t = if atmosphere_altitude_m > 0.0 {
-                (1.0 - altitude / atmosphere_altitude_m).clamp(0.0, 1.0)
-            } else {
-                0.0
-            };
-            // Space color -> sky blue (0.4, 0.6, 0.9)
-            let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+            // Atmospheric clear color: space black under the sky seen from this
+            // altitude, hued for the sun's elevation above the ship's horizon:
+            // blue at midday, orange/red at dawn and dusk.
+            let altitude = ship_state.position.length() - planet_radius_m;
+            let zenith = sky_params.scattering_at_altitude(altitude);
+            let atmo_t = f64::from(zenith.visibility);
+            sky_clock.tick(dt);
+            // The sun's path below the ship depends on its latitude and longitude.
+            let local_up = ship_state
+                .position
+                .try_normalize()
+                .unwrap_or(glam::DVec3::Y);
+            let sun_elevation = nebula_planet::sun_direction_from_time(
+                nebula_planet::local_time_of_day(sky_clock.time_of_day, local_up),
+                nebula_planet::latitude_from_up(local_up),
+            )
+            .y;
+            let brightness = zenith.rgb.into_iter().fold(0.0, f32::max);
+            let sky = nebula_lighting::modulate_ambient_by_sun(
+                nebula_lighting::atmospheric_tint(sun_elevation, &sky_atmosphere) * brightness,
+                sun_elevation,
+            );
+            let over_space = |space: f64, sky: f32| space * (1.0 - atmo_t) + f64::from(sky);
             clear_color.set([
-                lerp(0.02, 0.4, atmo_t),
-                lerp(0.02, 0.6, atmo_t),
-                lerp(0.08, 0.9, atmo_t),
+                over_space(0.02, sky.x),
+                over_space(0.02, sky.y),
+                over_space(0.08, sky.z),
             ]);
 
             // Reduce far plane in atmosphere for less deep-space visibility.
@@ -135,7 +178,7 @@ fn main() {
                 is_boosting,
             );
         },
-        move || hud::format_hud(&hud_for_title.borrow()),
+        move |overlay| hud::draw_hud(&hud_for_overlay.borrow(), overlay),
         move |_tick| {
             let c = clear_color_for_render.get();
             [c[0], c[1], c[2], 1.0]
@@ -21,6 +21,9 @@ const ORBITAL_CAMERA_SPEED: f64 = 8_000.0;
 /// Atmosphere altitude in meters (~100 km, Kármán line).
 const ATMOSPHERE_ALTITUDE_M: f64 = 100_000.0;
 
+/// Length of a game day in real-time seconds (20 minutes).
+const DAY_LENGTH_SECONDS: f64 = 1200.0;
+
 pub fn earth_config() -> PlanetConfig {
     PlanetConfig {
         radius_m: EARTH_RADIUS_M,
@@ -28,5 +31,7 @@ pub fn earth_config() -> PlanetConfig {
         free_fly_camera: true,
         camera_speed_m_s: ORBITAL_CAMERA_SPEED,
         atmosphere_altitude_m: ATMOSPHERE_ALTITUDE_M,
+        day_length_seconds: DAY_LENGTH_SECONDS,
+        ..PlanetConfig::default()
     }
 }
@@ -75,6 +75,8 @@ pub struct ShipState {
     pub landed: bool,
     /// Vertical speed relative to planet surface (positive = climbing, m/s).
     pub vertical_speed: f64,
+    /// Acceleration from thrust during the last tick, world space (m/s²).
+    pub thrust_accel: DVec3,
 }
 
 impl ShipState {
@@ -88,6 +90,7 @@ impl ShipState {
             angular_velocity: DVec3::ZERO,
             landed: false,
             vertical_speed: 0.0,
+            thrust_accel: DVec3::ZERO,
         }
     }
 
@@ -103,6 +106,7 @@ impl ShipState {
             angular_velocity: DVec3::ZERO,
             landed: false,
             vertical_speed: 0.0,
+            thrust_accel: DVec3::ZERO,
         }
     }
 
@@ -206,6 +210,7 @@ pub fn update_ship(
     // Transform thrust to world space and apply F = ma
     let thrust_world = ship.orientation * (thrust_local * thrust_magnitude);
     let acceleration = thrust_world / config.mass;
+    ship.thrust_accel = acceleration;
     ship.velocity += acceleration * dt;
 
     // --- Planetary gravity ---
@@ -1,92 +1,25 @@
 //! Action mapping system: maps abstract game actions to physical input bindings.
 //!
-//! [`InputMap`] defines which physical inputs (keys, mouse buttons, gamepad axes)
-//! trigger which [`Action`]s. [`ActionState`] is recomputed each frame by
-//! [`ActionResolver`], which reads the current keyboard, mouse, and gamepad state.
-
-use crate::gamepad::{GamepadState, UnifiedButton};
+//! [`InputMap`] defines which physical inputs (keys, mouse buttons, gamepad axes,
+//! touch axes) trigger which [`Action`]s. [`ActionState`] is recomputed each frame
+//! by [`ActionResolver`], which reads the current keyboard, mouse, gamepad, and
+//! touch state.
+
+use crate::activation::{ActivationMode, ActivationTracker};
+use crate::axis_response::AxisResponse;
+use crate::gamepad::UnifiedButton;
 use crate::keybindings::Modifiers;
-use crate::keyboard::KeyboardState;
-use crate::mouse::MouseState;
+use crate::touch::TouchAxisBinding;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use winit::event::MouseButton;
-use winit::keyboard::{KeyCode, PhysicalKey};
-
-/// Serde helper module for [`KeyCode`] which doesn't implement serde natively.
-mod keycode_serde {
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
-    use winit::keyboard::KeyCode;
-
-    /// Serialize a [`KeyCode`] as its debug string (e.g., `"KeyW"`).
-    pub fn serialize<S: Serializer>(code: &KeyCode, s: S) -> Result<S::Ok, S::Error> {
-        format!("{code:?}").serialize(s)
-    }
+use winit::keyboard::KeyCode;
 
-    /// Deserialize a [`KeyCode`] from its debug string.
-    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<KeyCode, D::Error> {
-        let name = String::deserialize(d)?;
-        string_to_keycode(&name)
-            .ok_or_else(|| serde::de::Error::custom(format!("unknown key: {name}")))
-    }
+mod keycode_serde;
+mod resolver;
 
-    fn string_to_keycode(s: &str) -> Option<KeyCode> {
-        // Match the Debug output of KeyCode variants
-        Some(match s {
-            "KeyA" => KeyCode::KeyA,
-            "KeyB" => KeyCode::KeyB,
-            "KeyC" => KeyCode::KeyC,
-            "KeyD" => KeyCode::KeyD,
-            "KeyE" => KeyCode::KeyE,
-            "KeyF" => KeyCode::KeyF,
-            "KeyG" => KeyCode::KeyG,
-            "KeyH" => KeyCode::KeyH,
-            "KeyI" => KeyCode::KeyI,
-            "KeyJ" => KeyCode::KeyJ,
-            "KeyK" => KeyCode::KeyK,
-            "KeyL" => KeyCode::KeyL,
-            "KeyM" => KeyCode::KeyM,
-            "KeyN" => KeyCode::KeyN,
-            "KeyO" => KeyCode::KeyO,
-            "KeyP" => KeyCode::KeyP,
-            "KeyQ" => KeyCode::KeyQ,
-            "KeyR" => KeyCode::KeyR,
-            "KeyS" => KeyCode::KeyS,
-            "KeyT" => KeyCode::KeyT,
-            "KeyU" => KeyCode::KeyU,
-            "KeyV" => KeyCode::KeyV,
-            "KeyW" => KeyCode::KeyW,
-            "KeyX" => KeyCode::KeyX,
-            "KeyY" => KeyCode::KeyY,
-            "KeyZ" => KeyCode::KeyZ,
-            "Digit0" => KeyCode::Digit0,
-            "Digit1" => KeyCode::Digit1,
-            "Digit2" => KeyCode::Digit2,
-            "Digit3" => KeyCode::Digit3,
-            "Digit4" => KeyCode::Digit4,
-            "Digit5" => KeyCode::Digit5,
-            "Digit6" => KeyCode::Digit6,
-            "Digit7" => KeyCode::Digit7,
-            "Digit8" => KeyCode::Digit8,
-            "Digit9" => KeyCode::Digit9,
-            "Space" => KeyCode::Space,
-            "Enter" => KeyCode::Enter,
-            "Escape" => KeyCode::Escape,
-            "Tab" => KeyCode::Tab,
-            "ShiftLeft" => KeyCode::ShiftLeft,
-            "ShiftRight" => KeyCode::ShiftRight,
-            "ControlLeft" => KeyCode::ControlLeft,
-            "ControlRight" => KeyCode::ControlRight,
-            "AltLeft" => KeyCode::AltLeft,
-            "AltRight" => KeyCode::AltRight,
-            "ArrowUp" => KeyCode::ArrowUp,
-            "ArrowDown" => KeyCode::ArrowDown,
-            "ArrowLeft" => KeyCode::ArrowLeft,
-            "ArrowRight" => KeyCode::ArrowRight,
-            _ => return None,
-        })
-    }
-}
+pub use resolver::ActionResolver;
 
 /// Semantic game actions that can be bound to physical inputs.
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Serialize, Deserialize)]
@@ -146,7 +79,7 @@ pub enum GamepadAxisBinding {
 }
 
 /// A physical input source that can be bound to an action.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum InputBinding {
     /// A keyboard key (physical scan code).
     Key(#[serde(with = "keycode_serde")] KeyCode),
@@ -173,6 +106,79 @@ pub enum InputBinding {
     GamepadButton(UnifiedButton),
     /// A gamepad axis (analog).
     GamepadAxis(GamepadAxisBinding),
+    /// A gamepad axis shaped by a radial dead zone and response curve.
+    ///
+    /// Axes are read unfiltered so only this response shapes them, and the
+    /// stick dead zone is applied to the whole stick vector rather than per
+    /// axis.
+    GamepadAxisWithResponse {
+        /// The axis to read.
+        axis: GamepadAxisBinding,
+        /// Dead zone and curve to apply.
+        response: AxisResponse,
+    },
+    /// Several inputs held simultaneously (e.g., `Ctrl + S`). Active only
+    /// while every member is active; an empty chord never activates.
+    ///
+    /// While a chord is held, bindings in the same map made of a strict
+    /// subset of its inputs (such as a plain `S`) are suppressed, so only
+    /// the chord's action fires. A [`Self::KeyWithModifiers`] member counts
+    /// its key and each of its modifiers as separate inputs.
+    Chord(Vec<InputBinding>),
+    /// A binding gated by tap, hold, or double-tap timing.
+    WithActivation {
+        /// The wrapped binding, read as digital.
+        binding: Box<InputBinding>,
+        /// When the wrapped binding activates the action.
+        mode: ActivationMode,
+    },
+    /// A virtual on-screen stick or look-area axis (analog).
+    TouchAxis(TouchAxisBinding),
+}
+
+impl InputBinding {
+    /// Wrap this binding with an [`ActivationMode`].
+    #[must_use]
+    pub fn with_activation(self, mode: ActivationMode) -> Self {
+        Self::WithActivation {
+            binding: Box::new(self),
+            mode,
+        }
+    }
+
+    /// Keyboard keys held together with exactly `modifiers`, e.g.
+    /// `key_chord(&[KeyCode::KeyS], Modifiers::CTRL)` for `Ctrl + S`.
+    ///
+    /// Built as a [`Self::Chord`] of [`Self::KeyWithModifiers`] members, so
+    /// it is resolved and shadows like any other chord.
+    #[must_use]
+    pub fn key_chord(keys: &[KeyCode], modifiers: Modifiers) -> Self {
+        Self::Chord(
+            keys.iter()
+                .map(|&key| Self::KeyWithModifiers { key, modifiers })
+                .collect(),
+        )
+    }
+
+    /// The plain inputs this binding is made of: chord members, flattened,
+    /// with activation wrappers removed.
+    pub(crate) fn leaves(&self) -> Vec<&InputBinding> {
+        match self {
+            Self::Chord(members) => members.iter().flat_map(Self::leaves).collect(),
+            Self::WithActivation { binding, .. } => binding.leaves(),
+            other => vec![other],
+        }
+    }
+
+    /// Whether reading this binding needs the keyboard.
+    fn reads_keyboard(&self) -> bool {
+        self.leaves().iter().any(|leaf| {
+            matches!(
+                leaf,
+                InputBinding::Key(_) | InputBinding::KeyWithModifiers { .. }
+            )
+        })
+    }
 }
 
 /// Wrapper for [`winit::event::MouseButton`] that supports serde.
@@ -316,6 +322,28 @@ impl InputMap {
         Self { bindings }
     }
 
+    /// [`Self::default_fps`] plus the virtual stick of a
+    /// [`TouchState`](crate::touch::TouchState), for builds without a keyboard.
+    ///
+    /// Stick axes are bound like the gamepad's left stick. On-screen buttons
+    /// drive their actions directly through the touch layout.
+    #[must_use]
+    pub fn default_handheld() -> Self {
+        let mut map = Self::default_fps();
+        for (action, axis) in [
+            (Action::MoveForward, TouchAxisBinding::StickY),
+            (Action::MoveBack, TouchAxisBinding::StickY),
+            (Action::MoveLeft, TouchAxisBinding::StickX),
+            (Action::MoveRight, TouchAxisBinding::StickX),
+        ] {
+            map.bindings
+                .entry(action)
+                .or_default()
+                .push(InputBinding::TouchAxis(axis));
+        }
+        map
+    }
+
     /// Set the bindings for an action, replacing any existing ones.
     pub fn set_bindings(&mut self, action: Action, bindings: Vec<InputBinding>) {
         self.bindings.insert(action, bindings);
@@ -354,6 +382,12 @@ pub struct ActionState {
     values: HashMap<Action, f32>,
     /// Previous frame values (for edge detection).
     prev_values: HashMap<Action, f32>,
+    /// Press history per binding wrapped in [`InputBinding::WithActivation`].
+    activations: HashMap<InputBinding, ActivationTracker>,
+    /// Frame time used by activation modes.
+    now: Duration,
+    /// Reference point for converting [`Instant`]s into frame times.
+    epoch: Option<Instant>,
 }
 
 impl Default for ActionState {
@@ -369,9 +403,27 @@ impl ActionState {
         Self {
             values: HashMap::new(),
             prev_values: HashMap::new(),
+            activations: HashMap::new(),
+            now: Duration::ZERO,
+            epoch: None,
         }
     }
 
+    /// Set the frame time that tap, hold, and double-tap bindings are timed
+    /// against, e.g. `TimeRes::elapsed` as a [`Duration`].
+    ///
+    /// Call once per frame before resolving bindings. Must not go backwards
+    /// and should not be mixed with [`Self::set_instant`].
+    pub fn set_time(&mut self, elapsed: Duration) {
+        self.now = elapsed;
+    }
+
+    /// Like [`Self::set_time`], measured from the first instant passed in.
+    pub fn set_instant(&mut self, now: Instant) {
+        let epoch = *self.epoch.get_or_insert(now);
+        self.now = now.saturating_duration_since(epoch);
+    }
+
     /// Shift current values to previous (call once at the start of each frame).
     ///
     /// Used by [`super::InputContextStack::resolve`] which may call
@@ -393,6 +445,14 @@ impl ActionState {
         self.values.get(&action).copied().unwrap_or(0.0)
     }
 
+    /// Overrides an action's value for the current frame, clamped to `[-1.0, 1.0]`.
+    ///
+    /// Used to inject already-resolved values, e.g. by
+    /// [`InputPlayer`](crate::InputPlayer) during replay.
+    pub fn set_action_value(&mut self, action: Action, value: f32) {
+        self.values.insert(action, value.clamp(-1.0, 1.0));
+    }
+
     /// True only on the frame the action transitioned from inactive to active.
     #[must_use]
     pub fn action_just_activated(&self, action: Action) -> bool {
@@ -412,361 +472,6 @@ impl ActionState {
     }
 }
 
-/// Reads input state resources and populates [`ActionState`] each frame.
-pub struct ActionResolver;
-
-impl ActionResolver {
-    /// Resolve all actions from the current input state.
-    ///
-    /// Call once per frame after input state has been updated.
-    pub fn resolve(
-        input_map: &InputMap,
-        keyboard: &KeyboardState,
-        mouse: &MouseState,
-        gamepad: Option<&GamepadState>,
-        state: &mut ActionState,
-    ) {
-        // Shift current values to previous.
-        state.prev_values.clone_from(&state.values);
-        state.values.clear();
-
-        for (action, bindings) in &input_map.bindings {
-            let mut value = 0.0_f32;
-
-            for binding in bindings {
-                let v = Self::read_binding(binding, keyboard, mouse, gamepad);
-                // Sum for analog, which also covers OR for digital (max via clamp).
-                value += v;
-            }
-
-            // Clamp to [-1, 1].
-            value = value.clamp(-1.0, 1.0);
-            state.values.insert(*action, value);
-        }
-    }
-
-    /// Resolve actions from a single context's input map, accumulating into `state`.
-    ///
-    /// Unlike [`Self::resolve`], this does **not** call `begin_frame` — the caller
-    /// is responsible for that. If `keyboard` is `None`, keyboard bindings are skipped
-    /// (used for text-input contexts where keys go to a text buffer instead).
-    pub fn resolve_partial(
-        input_map: &InputMap,
-        keyboard: Option<&KeyboardState>,
-        mouse: &MouseState,
-        gamepad: Option<&GamepadState>,
-        state: &mut ActionState,
-    ) {
-        let empty_kb = KeyboardState::new();
-        let kb = keyboard.unwrap_or(&empty_kb);
-
-        for (action, bindings) in &input_map.bindings {
-            let mut value = state.values.get(action).copied().unwrap_or(0.0);
-
-            for binding in bindings {
-                // Skip keyboard bindings if keyboard is None
-                if keyboard.is_none()
-                    && matches!(
-                        binding,
-                        InputBinding::Key(_) | InputBinding::KeyWithModifiers { .. }
-                    )
-                {
-                    continue;
-                }
-                let v = Self::read_binding(binding, kb, mouse, gamepad);
-                value += v;
-            }
-
-            value = value.clamp(-1.0, 1.0);
-            state.values.insert(*action, value);
-        }
-    }
-
-    /// Read the current value of a single binding.
-    fn read_binding(
-        binding: &InputBinding,
-        keyboard: &KeyboardState,
-        mouse: &MouseState,
-        gamepad: Option<&GamepadState>,
-    ) -> f32 {
-        match binding {
-            InputBinding::Key(code) => {
-                if keyboard.is_pressed(PhysicalKey::Code(*code)) {
-                    1.0
-                } else {
-                    0.0
-                }
-            }
-            InputBinding::KeyWithModifiers { key, modifiers } => {
-                let active = keyboard.active_modifiers();
-                if keyboard.is_pressed(PhysicalKey::Code(*key)) && active == *modifiers {
-                    1.0
-                } else {
-                    0.0
-                }
-            }
-            InputBinding::MouseButton(btn) => {
-                if mouse.is_button_pressed(btn.to_winit()) {
-                    1.0
-                } else {
-                    0.0
-                }
-            }
-            InputBinding::MouseButtonWithModifiers { button, modifiers } => {
-                let active = keyboard.active_modifiers();
-                if mouse.is_button_pressed(button.to_winit()) && active == *modifiers {
-                    1.0
-                } else {
-                    0.0
-                }
-            }
-            InputBinding::MouseAxis(axis) => {
-                let d = mouse.delta();
-                match axis {
-                    MouseAxisBinding::X => d.x,
-                    MouseAxisBinding::Y => d.y,
-                    MouseAxisBinding::Scroll => mouse.scroll(),
-                }
-            }
-            InputBinding::GamepadButton(btn) => {
-                if let Some(gp) = gamepad
-                    && gp.is_button_pressed(*btn)
-                {
-                    1.0
-                } else {
-                    0.0
-                }
-            }
-            InputBinding::GamepadAxis(axis) => {
-                if let Some(gp) = gamepad {
-                    match axis {
-                        GamepadAxisBinding::LeftStickX => gp.left_stick().x,
-                        GamepadAxisBinding::LeftStickY => gp.left_stick().y,
-                        GamepadAxisBinding::RightStickX => gp.right_stick().x,
-                        GamepadAxisBinding::RightStickY => gp.right_stick().y,
-                        GamepadAxisBinding::LeftTrigger => gp.left_trigger(),
-                        GamepadAxisBinding::RightTrigger => gp.right_trigger(),
-                    }
-                } else {
-                    0.0
-                }
-            }
-        }
-    }
-}
-
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use winit::event::ElementState;
-
-    /// Helper: press a key on a keyboard state.
-    fn press_key(kb: &mut KeyboardState, code: KeyCode) {
-        kb.process_raw(crate::keyboard::RawKeyEvent {
-            key: PhysicalKey::Code(code),
-            state: ElementState::Pressed,
-            repeat: false,
-        });
-    }
-
-    /// Helper: release a key on a keyboard state.
-    fn release_key(kb: &mut KeyboardState, code: KeyCode) {
-        kb.process_raw(crate::keyboard::RawKeyEvent {
-            key: PhysicalKey::Code(code),
-            state: ElementState::Released,
-            repeat: false,
-        });
-    }
-
-    #[test]
-    fn test_action_bound_to_key_activates_on_press() {
-        let mut map = InputMap::new();
-        map.set_bindings(Action::MoveForward, vec![InputBinding::Key(KeyCode::KeyW)]);
-
-        let mut kb = KeyboardState::new();
-        press_key(&mut kb, KeyCode::KeyW);
-
-        let mouse = MouseState::new();
-        let mut state = ActionState::new();
-        ActionResolver::resolve(&map, &kb, &mouse, None, &mut state);
-
-        assert!(state.is_action_active(Action::MoveForward));
-        assert!((state.action_value(Action::MoveForward) - 1.0).abs() < f32::EPSILON);
-    }
-
-    #[test]
-    fn test_action_bound_to_gamepad_axis_returns_analog() {
-        let mut map = InputMap::new();
-        map.set_bindings(
-            Action::MoveForward,
-            vec![InputBinding::GamepadAxis(GamepadAxisBinding::LeftStickY)],
-        );
-
-        let kb = KeyboardState::new();
-        let mouse = MouseState::new();
-
-        // Build a mock gamepad state with left_stick.y = 0.75
-        // We need to use the mock manager from gamepad module
-        use crate::gamepad::MockGamepadManager;
-        let mut mgr = MockGamepadManager::new();
-        mgr.set_deadzone(0.0);
-        let id = mgr.connect("TestPad");
-        mgr.set_axis(id, "left_stick_y", 0.75);
-        let gp = mgr.gamepad(id).unwrap();
-
-        let mut state = ActionState::new();
-        ActionResolver::resolve(&map, &kb, &mouse, Some(gp), &mut state);
-
-        assert!(
-            (state.action_value(Action::MoveForward) - 0.75).abs() < 0.01,
-            "got {}",
-            state.action_value(Action::MoveForward)
-        );
-    }
-
-    #[test]
-    fn test_unbound_action_returns_false_and_zero() {
-        let map = InputMap::new();
-        let kb = KeyboardState::new();
-        let mouse = MouseState::new();
-        let mut state = ActionState::new();
-        ActionResolver::resolve(&map, &kb, &mouse, None, &mut state);
-
-        assert!(!state.is_action_active(Action::OpenInventory));
-        assert!((state.action_value(Action::OpenInventory)).abs() < f32::EPSILON);
-    }
-
-    #[test]
-    fn test_multiple_bindings_or_logic() {
-        let mut map = InputMap::new();
-        map.set_bindings(
-            Action::Jump,
-            vec![
-                InputBinding::Key(KeyCode::Space),
-                InputBinding::GamepadButton(UnifiedButton::South),
-            ],
-        );
-
-        let mut kb = KeyboardState::new();
-        press_key(&mut kb, KeyCode::Space);
-
-        let mouse = MouseState::new();
-        let mut state = ActionState::new();
-        ActionResolver::resolve(&map, &kb, &mouse, None, &mut state);
-
-        assert!(state.is_action_active(Action::Jump));
-    }
-
-    #[test]
-    fn test_multiple_bindings_both_active() {
-        let mut map = InputMap::new();
-        map.set_bindings(
-            Action::Jump,
-            vec![
-                InputBinding::Key(KeyCode::Space),
-                InputBinding::GamepadButton(UnifiedButton::South),
-            ],
-        );
-
-        let mut kb = KeyboardState::new();
-        press_key(&mut kb, KeyCode::Space);
-
-        let mouse = MouseState::new();
-
-        use crate::gamepad::MockGamepadManager;
-        let mut mgr = MockGamepadManager::new();
-        let id = mgr.connect("TestPad");
-        mgr.press_button(id, UnifiedButton::South);
-        let gp = mgr.gamepad(id).unwrap();
-
-        let mut state = ActionState::new();
-        ActionResolver::resolve(&map, &kb, &mouse, Some(gp), &mut state);
-
-        assert!(
-            (state.action_value(Action::Jump) - 1.0).abs() < f32::EPSILON,
-            "should be clamped to 1.0, got {}",
-            state.action_value(Action::Jump)
-        );
-    }
-
-    #[test]
-    fn test_action_map_modified_at_runtime() {
-        let mut map = InputMap::new();
-        map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::Space)]);
-
-        let mut kb = KeyboardState::new();
-        let mouse = MouseState::new();
-        let mut state = ActionState::new();
-
-        // Rebind Jump to KeyJ
-        map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::KeyJ)]);
-
-        press_key(&mut kb, KeyCode::KeyJ);
-        ActionResolver::resolve(&map, &kb, &mouse, None, &mut state);
-        assert!(state.is_action_active(Action::Jump));
-
-        // Space should no longer activate Jump
-        release_key(&mut kb, KeyCode::KeyJ);
-        press_key(&mut kb, KeyCode::Space);
-        ActionResolver::resolve(&map, &kb, &mouse, None, &mut state);
-        assert!(!state.is_action_active(Action::Jump));
-    }
-
-    #[test]
-    fn test_action_just_activated_edge() {
-        let mut map = InputMap::new();
-        map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::Space)]);
-
-        let mut kb = KeyboardState::new();
-        let mouse = MouseState::new();
-        let mut state = ActionState::new();
-
-        // Frame 1: press Space
-        press_key(&mut kb, KeyCode::Space);
-        ActionResolver::resolve(&map, &kb, &mouse, None, &mut state);
-        assert!(
-            state.action_just_activated(Action::Jump),
-            "should be just activated on frame 1"
-        );
-
-        // Frame 2: still held
-        ActionResolver::resolve(&map, &kb, &mouse, None, &mut state);
-        assert!(
-            !state.action_just_activated(Action::Jump),
-            "should NOT be just activated on frame 2"
-        );
-        assert!(state.is_action_active(Action::Jump));
-    }
-
-    #[test]
-    fn test_analog_sum_clamped() {
-        let mut map = InputMap::new();
-        map.set_bindings(
-            Action::MoveForward,
-            vec![
-                InputBinding::Key(KeyCode::KeyW),
-                InputBinding::GamepadAxis(GamepadAxisBinding::LeftStickY),
-            ],
-        );
-
-        let mut kb = KeyboardState::new();
-        press_key(&mut kb, KeyCode::KeyW); // contributes 1.0
-        let mouse = MouseState::new();
-
-        use crate::gamepad::MockGamepadManager;
-        let mut mgr = MockGamepadManager::new();
-        mgr.set_deadzone(0.0);
-        let id = mgr.connect("TestPad");
-        mgr.set_axis(id, "left_stick_y", 0.8); // contributes 0.8
-        let gp = mgr.gamepad(id).unwrap();
-
-        let mut state = ActionState::new();
-        ActionResolver::resolve(&map, &kb, &mouse, Some(gp), &mut state);
-
-        assert!(
-            (state.action_value(Action::MoveForward) - 1.0).abs() < f32::EPSILON,
-            "should be clamped to 1.0, got {}",
-            state.action_value(Action::MoveForward)
-        );
-    }
-}
+#[path = "action_map_tests.rs"]
+mod tests;
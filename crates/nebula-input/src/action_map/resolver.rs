@@ -0,0 +1,379 @@
+//! [`ActionResolver`]: reads the device states into an [`ActionState`].
+
+use super::{
+    ACTIVATION_THRESHOLD, Action, ActionState, GamepadAxisBinding, InputBinding, InputMap,
+    MouseAxisBinding,
+};
+use crate::activation::ActivationTracker;
+use crate::gamepad::GamepadState;
+use crate::keybindings::Modifiers;
+use crate::keyboard::KeyboardState;
+use crate::mouse::MouseState;
+use crate::touch::TouchState;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+/// Reads input state resources and populates [`ActionState`] each frame.
+pub struct ActionResolver;
+
+impl ActionResolver {
+    /// Resolve all actions from the current input state.
+    ///
+    /// Call once per frame after input state has been updated.
+    pub fn resolve(
+        input_map: &InputMap,
+        keyboard: &KeyboardState,
+        mouse: &MouseState,
+        gamepad: Option<&GamepadState>,
+        touch: Option<&TouchState>,
+        state: &mut ActionState,
+    ) {
+        Self::resolve_at(
+            input_map,
+            keyboard,
+            mouse,
+            gamepad,
+            touch,
+            state,
+            Instant::now(),
+        );
+    }
+
+    /// Like [`Self::resolve`], but with an explicit frame timestamp used for
+    /// activation-mode timing.
+    pub fn resolve_at(
+        input_map: &InputMap,
+        keyboard: &KeyboardState,
+        mouse: &MouseState,
+        gamepad: Option<&GamepadState>,
+        touch: Option<&TouchState>,
+        state: &mut ActionState,
+        now: Instant,
+    ) {
+        state.begin_frame();
+        state.set_instant(now);
+        Self::resolve_partial(input_map, Some(keyboard), mouse, gamepad, touch, state);
+    }
+
+    /// Like [`Self::resolve`], but timed by the engine clock: pass the tick
+    /// time from `TimeRes::elapsed` so activation modes follow simulation
+    /// time rather than the wall clock.
+    pub fn resolve_timed(
+        input_map: &InputMap,
+        keyboard: &KeyboardState,
+        mouse: &MouseState,
+        gamepad: Option<&GamepadState>,
+        touch: Option<&TouchState>,
+        state: &mut ActionState,
+        elapsed: Duration,
+    ) {
+        state.begin_frame();
+        state.set_time(elapsed);
+        Self::resolve_partial(input_map, Some(keyboard), mouse, gamepad, touch, state);
+    }
+
+    /// Resolve actions from a single context's input map, accumulating into `state`.
+    ///
+    /// Unlike [`Self::resolve`], this does **not** call `begin_frame` — the caller
+    /// is responsible for that. If `keyboard` is `None`, keyboard bindings are skipped
+    /// (used for text-input contexts where keys go to a text buffer instead).
+    pub fn resolve_partial(
+        input_map: &InputMap,
+        keyboard: Option<&KeyboardState>,
+        mouse: &MouseState,
+        gamepad: Option<&GamepadState>,
+        touch: Option<&TouchState>,
+        state: &mut ActionState,
+    ) {
+        Self::resolve_layer(
+            input_map,
+            keyboard,
+            mouse,
+            gamepad,
+            touch,
+            state,
+            &mut HashSet::new(),
+        );
+    }
+
+    /// Like [`Self::resolve_partial`], but skips actions already in `handled`
+    /// and adds every action this map activates to it.
+    ///
+    /// Used by [`crate::InputContextStack::resolve`] so a context only sees
+    /// the actions the contexts above it left unhandled.
+    pub(crate) fn resolve_layer(
+        input_map: &InputMap,
+        keyboard: Option<&KeyboardState>,
+        mouse: &MouseState,
+        gamepad: Option<&GamepadState>,
+        touch: Option<&TouchState>,
+        state: &mut ActionState,
+        handled: &mut HashSet<Action>,
+    ) {
+        let empty_kb = KeyboardState::new();
+        let kb = keyboard.unwrap_or(&empty_kb);
+        let now = state.now;
+
+        // Chords currently held, as their flattened members. A single key
+        // with modifiers is a chord of the key and its modifiers.
+        let held_chords: Vec<Vec<&InputBinding>> = input_map
+            .bindings
+            .values()
+            .flatten()
+            .map(InputBinding::leaves)
+            .filter(|leaves| {
+                input_count(leaves) > 1
+                    && leaves.iter().all(|leaf| {
+                        Self::read_binding(
+                            leaf,
+                            kb,
+                            mouse,
+                            gamepad,
+                            touch,
+                            &mut state.activations,
+                            now,
+                        )
+                        .abs()
+                            > ACTIVATION_THRESHOLD
+                    })
+            })
+            .collect();
+        for (action, bindings) in &input_map.bindings {
+            if handled.contains(action) {
+                continue;
+            }
+            let mut value = state.values.get(action).copied().unwrap_or(0.0);
+
+            for binding in bindings {
+                // Skip keyboard bindings if keyboard is None
+                if keyboard.is_none() && binding.reads_keyboard() {
+                    continue;
+                }
+                if is_shadowed_by_chord(binding, &held_chords) {
+                    continue;
+                }
+                // Sum for analog, which also covers OR for digital (max via clamp).
+                value += Self::read_binding(
+                    binding,
+                    kb,
+                    mouse,
+                    gamepad,
+                    touch,
+                    &mut state.activations,
+                    now,
+                );
+            }
+            if touch.is_some_and(|t| t.is_action_pressed(*action)) {
+                value += 1.0;
+            }
+
+            value = value.clamp(-1.0, 1.0);
+            state.values.insert(*action, value);
+            if value.abs() > ACTIVATION_THRESHOLD {
+                handled.insert(*action);
+            }
+        }
+    }
+
+    /// Whether every key in `keys` is held along with exactly `modifiers`,
+    /// i.e. whether [`InputBinding::key_chord`] for them is active.
+    ///
+    /// The `modifiers` flags accept either side; an empty `keys` never
+    /// activates.
+    #[must_use]
+    pub fn resolve_chord(keyboard: &KeyboardState, keys: &[KeyCode], modifiers: Modifiers) -> bool {
+        let value = Self::read_binding(
+            &InputBinding::key_chord(keys, modifiers),
+            keyboard,
+            &MouseState::new(),
+            None,
+            None,
+            &mut HashMap::new(),
+            Duration::ZERO,
+        );
+        value.abs() > ACTIVATION_THRESHOLD
+    }
+
+    /// Read the current value of a single binding, advancing the press
+    /// history of any activation-mode wrappers to frame time `now`.
+    fn read_binding(
+        binding: &InputBinding,
+        keyboard: &KeyboardState,
+        mouse: &MouseState,
+        gamepad: Option<&GamepadState>,
+        touch: Option<&TouchState>,
+        activations: &mut HashMap<InputBinding, ActivationTracker>,
+        now: Duration,
+    ) -> f32 {
+        match binding {
+            InputBinding::Key(code) => {
+                if keyboard.is_pressed(PhysicalKey::Code(*code)) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            InputBinding::KeyWithModifiers { key, modifiers } => {
+                let active = keyboard.active_modifiers();
+                if keyboard.is_pressed(PhysicalKey::Code(*key)) && active == *modifiers {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            InputBinding::MouseButton(btn) => {
+                if mouse.is_button_pressed(btn.to_winit()) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            InputBinding::MouseButtonWithModifiers { button, modifiers } => {
+                let active = keyboard.active_modifiers();
+                if mouse.is_button_pressed(button.to_winit()) && active == *modifiers {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            InputBinding::MouseAxis(axis) => {
+                let d = mouse.delta();
+                match axis {
+                    MouseAxisBinding::X => d.x,
+                    MouseAxisBinding::Y => d.y,
+                    MouseAxisBinding::Scroll => mouse.scroll(),
+                }
+            }
+            InputBinding::GamepadButton(btn) => {
+                if let Some(gp) = gamepad
+                    && gp.is_button_pressed(*btn)
+                {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            InputBinding::GamepadAxis(axis) => {
+                if let Some(gp) = gamepad {
+                    match axis {
+                        GamepadAxisBinding::LeftStickX => gp.left_stick().x,
+                        GamepadAxisBinding::LeftStickY => gp.left_stick().y,
+                        GamepadAxisBinding::RightStickX => gp.right_stick().x,
+                        GamepadAxisBinding::RightStickY => gp.right_stick().y,
+                        GamepadAxisBinding::LeftTrigger => gp.left_trigger(),
+                        GamepadAxisBinding::RightTrigger => gp.right_trigger(),
+                    }
+                } else {
+                    0.0
+                }
+            }
+            InputBinding::GamepadAxisWithResponse { axis, response } => {
+                if let Some(gp) = gamepad {
+                    match axis {
+                        GamepadAxisBinding::LeftStickX => {
+                            response.apply_stick(gp.raw_left_stick()).x
+                        }
+                        GamepadAxisBinding::LeftStickY => {
+                            response.apply_stick(gp.raw_left_stick()).y
+                        }
+                        GamepadAxisBinding::RightStickX => {
+                            response.apply_stick(gp.raw_right_stick()).x
+                        }
+                        GamepadAxisBinding::RightStickY => {
+                            response.apply_stick(gp.raw_right_stick()).y
+                        }
+                        GamepadAxisBinding::LeftTrigger => {
+                            response.apply_trigger(gp.raw_left_trigger())
+                        }
+                        GamepadAxisBinding::RightTrigger => {
+                            response.apply_trigger(gp.raw_right_trigger())
+                        }
+                    }
+                } else {
+                    0.0
+                }
+            }
+            InputBinding::TouchAxis(axis) => touch.map_or(0.0, |t| t.axis(*axis)),
+            InputBinding::Chord(members) => {
+                // Read every member (no short-circuit) so wrapped members
+                // keep their press history current.
+                let held = members.iter().fold(!members.is_empty(), |held, member| {
+                    let v = Self::read_binding(
+                        member,
+                        keyboard,
+                        mouse,
+                        gamepad,
+                        touch,
+                        activations,
+                        now,
+                    );
+                    held & (v.abs() > ACTIVATION_THRESHOLD)
+                });
+                if held { 1.0 } else { 0.0 }
+            }
+            InputBinding::WithActivation { binding, mode } => {
+                let v =
+                    Self::read_binding(binding, keyboard, mouse, gamepad, touch, activations, now);
+                let tracker = activations.entry((**binding).clone()).or_default();
+                tracker.update(v.abs() > ACTIVATION_THRESHOLD, now);
+                if tracker.is_active(*mode, now) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// How many held inputs `leaves` need: each key or button, plus each
+/// modifier a leaf requires.
+fn input_count(leaves: &[&InputBinding]) -> u32 {
+    leaves
+        .iter()
+        .map(|leaf| match leaf {
+            InputBinding::KeyWithModifiers { modifiers, .. }
+            | InputBinding::MouseButtonWithModifiers { modifiers, .. } => {
+                1 + modifiers.0.count_ones()
+            }
+            _ => 1,
+        })
+        .sum()
+}
+
+/// Whether holding `chord` also holds `leaf`: it is one of the chord's
+/// members, the key of a keyed member, or a modifier key one of the
+/// members requires.
+fn chord_covers(chord: &[&InputBinding], leaf: &InputBinding) -> bool {
+    chord.iter().any(|&member| {
+        member == leaf
+            || match (member, leaf) {
+                (InputBinding::KeyWithModifiers { key, .. }, InputBinding::Key(k)) if key == k => {
+                    true
+                }
+                (
+                    InputBinding::KeyWithModifiers { modifiers, .. }
+                    | InputBinding::MouseButtonWithModifiers { modifiers, .. },
+                    InputBinding::Key(k),
+                ) => {
+                    let flag = Modifiers::from_key(*k);
+                    !flag.is_empty() && modifiers.contains(flag)
+                }
+                _ => false,
+            }
+    })
+}
+
+/// Whether `binding` is made of a strict subset of a held chord's inputs.
+fn is_shadowed_by_chord(binding: &InputBinding, held_chords: &[Vec<&InputBinding>]) -> bool {
+    let leaves = binding.leaves();
+    held_chords.iter().any(|chord| {
+        input_count(&leaves) < input_count(chord)
+            && leaves.iter().all(|leaf| chord_covers(chord, leaf))
+    })
+}
+
+#[cfg(test)]
+#[path = "resolver_tests.rs"]
+mod tests;
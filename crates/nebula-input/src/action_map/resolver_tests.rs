@@ -0,0 +1,364 @@
+//! Unit tests for chord and activation-mode resolution.
+
+use super::*;
+use crate::action_map::MouseButtonBinding;
+use crate::activation::ActivationMode;
+use crate::gamepad::UnifiedButton;
+use winit::event::{ElementState, MouseButton};
+
+/// Helper: press a key on a keyboard state.
+fn press_key(kb: &mut KeyboardState, code: KeyCode) {
+    kb.process_raw(crate::keyboard::RawKeyEvent {
+        key: PhysicalKey::Code(code),
+        state: ElementState::Pressed,
+        repeat: false,
+    });
+}
+
+/// Helper: release a key on a keyboard state.
+fn release_key(kb: &mut KeyboardState, code: KeyCode) {
+    kb.process_raw(crate::keyboard::RawKeyEvent {
+        key: PhysicalKey::Code(code),
+        state: ElementState::Released,
+        repeat: false,
+    });
+}
+
+#[test]
+fn test_chord_activates_only_when_all_keys_down() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::Interact,
+        vec![InputBinding::Chord(vec![
+            InputBinding::Key(KeyCode::KeyQ),
+            InputBinding::Key(KeyCode::KeyE),
+        ])],
+    );
+
+    let mut kb = KeyboardState::new();
+    let mouse = MouseState::new();
+    let mut state = ActionState::new();
+
+    press_key(&mut kb, KeyCode::KeyQ);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(!state.is_action_active(Action::Interact));
+
+    press_key(&mut kb, KeyCode::KeyE);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(state.action_just_activated(Action::Interact));
+
+    release_key(&mut kb, KeyCode::KeyQ);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(!state.is_action_active(Action::Interact));
+}
+
+/// Helper: tap `code` (press, resolve, release, resolve) starting at `at`.
+fn tap(
+    map: &InputMap,
+    kb: &mut KeyboardState,
+    state: &mut ActionState,
+    code: KeyCode,
+    at: Instant,
+) -> bool {
+    let mouse = MouseState::new();
+    press_key(kb, code);
+    ActionResolver::resolve_at(map, kb, &mouse, None, None, state, at);
+    let activated = state.action_just_activated(Action::Sprint);
+    kb.clear_transients();
+    release_key(kb, code);
+    ActionResolver::resolve_at(map, kb, &mouse, None, None, state, at);
+    kb.clear_transients();
+    activated
+}
+
+#[test]
+fn test_double_tap_within_window_activates_on_second_press() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::Sprint,
+        vec![InputBinding::Key(KeyCode::KeyW).with_activation(ActivationMode::DoubleTap(300))],
+    );
+
+    let mut kb = KeyboardState::new();
+    let mut state = ActionState::new();
+    let t0 = Instant::now();
+
+    assert!(!tap(&map, &mut kb, &mut state, KeyCode::KeyW, t0));
+    assert!(tap(
+        &map,
+        &mut kb,
+        &mut state,
+        KeyCode::KeyW,
+        t0 + Duration::from_millis(200),
+    ));
+}
+
+#[test]
+fn test_double_tap_outside_window_does_not_activate() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::Sprint,
+        vec![InputBinding::Key(KeyCode::KeyW).with_activation(ActivationMode::DoubleTap(300))],
+    );
+
+    let mut kb = KeyboardState::new();
+    let mut state = ActionState::new();
+    let t0 = Instant::now();
+
+    assert!(!tap(&map, &mut kb, &mut state, KeyCode::KeyW, t0));
+    assert!(!tap(
+        &map,
+        &mut kb,
+        &mut state,
+        KeyCode::KeyW,
+        t0 + Duration::from_millis(500),
+    ));
+}
+
+#[test]
+fn test_chord_and_activation_modes_ron_roundtrip() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::Interact,
+        vec![
+            InputBinding::Chord(vec![
+                InputBinding::Key(KeyCode::ControlLeft),
+                InputBinding::MouseButton(MouseButtonBinding::Left),
+            ]),
+            InputBinding::Key(KeyCode::KeyA).with_activation(ActivationMode::DoubleTap(250)),
+            InputBinding::Key(KeyCode::KeyF).with_activation(ActivationMode::Tap(200)),
+            InputBinding::GamepadButton(UnifiedButton::West)
+                .with_activation(ActivationMode::Hold(500)),
+        ],
+    );
+    let restored = InputMap::from_ron(&map.to_ron().unwrap()).unwrap();
+    assert_eq!(
+        restored.get_bindings(&Action::Interact),
+        map.get_bindings(&Action::Interact)
+    );
+}
+
+#[test]
+fn test_double_tap_180ms_gap_activates_400ms_does_not() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::Sprint,
+        vec![InputBinding::Key(KeyCode::KeyW).with_activation(ActivationMode::DoubleTap(300))],
+    );
+    let t0 = Instant::now();
+
+    let mut kb = KeyboardState::new();
+    let mut state = ActionState::new();
+    assert!(!tap(&map, &mut kb, &mut state, KeyCode::KeyW, t0));
+    assert!(tap(
+        &map,
+        &mut kb,
+        &mut state,
+        KeyCode::KeyW,
+        t0 + Duration::from_millis(180),
+    ));
+
+    let mut kb = KeyboardState::new();
+    let mut state = ActionState::new();
+    assert!(!tap(&map, &mut kb, &mut state, KeyCode::KeyW, t0));
+    assert!(!tap(
+        &map,
+        &mut kb,
+        &mut state,
+        KeyCode::KeyW,
+        t0 + Duration::from_millis(400),
+    ));
+}
+
+#[test]
+fn test_hold_250ms_fires_hold_but_not_tap() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::Interact,
+        vec![InputBinding::Key(KeyCode::KeyF).with_activation(ActivationMode::Hold(200))],
+    );
+    map.set_bindings(
+        Action::PrimaryAction,
+        vec![InputBinding::Key(KeyCode::KeyF).with_activation(ActivationMode::Tap(200))],
+    );
+    let mouse = MouseState::new();
+    let mut kb = KeyboardState::new();
+    let mut state = ActionState::new();
+    let mut tap_fired = false;
+    let mut resolve = |kb: &KeyboardState, state: &mut ActionState, ms: u64| {
+        ActionResolver::resolve_timed(
+            &map,
+            kb,
+            &mouse,
+            None,
+            None,
+            state,
+            Duration::from_millis(ms),
+        );
+        tap_fired |= state.is_action_active(Action::PrimaryAction);
+    };
+
+    press_key(&mut kb, KeyCode::KeyF);
+    resolve(&kb, &mut state, 1_000);
+    assert!(!state.is_action_active(Action::Interact));
+    resolve(&kb, &mut state, 1_150);
+    assert!(!state.is_action_active(Action::Interact));
+    resolve(&kb, &mut state, 1_200);
+    assert!(state.action_just_activated(Action::Interact));
+    release_key(&mut kb, KeyCode::KeyF);
+    resolve(&kb, &mut state, 1_250);
+    assert!(!state.is_action_active(Action::Interact));
+    resolve(&kb, &mut state, 1_300);
+    assert!(!tap_fired, "a 250 ms hold must not count as a tap");
+}
+
+#[test]
+fn test_short_press_fires_tap_but_not_hold() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::Interact,
+        vec![InputBinding::Key(KeyCode::KeyF).with_activation(ActivationMode::Hold(200))],
+    );
+    map.set_bindings(
+        Action::PrimaryAction,
+        vec![InputBinding::Key(KeyCode::KeyF).with_activation(ActivationMode::Tap(200))],
+    );
+    let mouse = MouseState::new();
+    let mut kb = KeyboardState::new();
+    let mut state = ActionState::new();
+
+    press_key(&mut kb, KeyCode::KeyF);
+    ActionResolver::resolve_timed(&map, &kb, &mouse, None, None, &mut state, Duration::ZERO);
+    release_key(&mut kb, KeyCode::KeyF);
+    let released = Duration::from_millis(120);
+    ActionResolver::resolve_timed(&map, &kb, &mouse, None, None, &mut state, released);
+    assert!(state.action_just_activated(Action::PrimaryAction));
+    assert!(!state.is_action_active(Action::Interact));
+
+    // The tap lasts a single frame.
+    let next = Duration::from_millis(136);
+    ActionResolver::resolve_timed(&map, &kb, &mouse, None, None, &mut state, next);
+    assert!(!state.is_action_active(Action::PrimaryAction));
+}
+
+#[test]
+fn test_ctrl_s_triggers_only_the_chord_action() {
+    let mut map = InputMap::new();
+    map.set_bindings(Action::MoveBack, vec![InputBinding::Key(KeyCode::KeyS)]);
+    map.set_bindings(
+        Action::Crouch,
+        vec![InputBinding::Key(KeyCode::ControlLeft)],
+    );
+    map.set_bindings(
+        Action::Pause,
+        vec![InputBinding::Chord(vec![
+            InputBinding::Key(KeyCode::ControlLeft),
+            InputBinding::Key(KeyCode::KeyS),
+        ])],
+    );
+    let mouse = MouseState::new();
+    let mut state = ActionState::new();
+
+    let mut kb = KeyboardState::new();
+    press_key(&mut kb, KeyCode::KeyS);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(state.is_action_active(Action::MoveBack));
+    assert!(!state.is_action_active(Action::Pause));
+
+    press_key(&mut kb, KeyCode::ControlLeft);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(state.is_action_active(Action::Pause));
+    assert!(!state.is_action_active(Action::MoveBack));
+    assert!(!state.is_action_active(Action::Crouch));
+}
+
+#[test]
+fn test_chord_members_can_mix_devices() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::SecondaryAction,
+        vec![InputBinding::Chord(vec![
+            InputBinding::Key(KeyCode::AltLeft),
+            InputBinding::MouseButton(MouseButtonBinding::Left),
+        ])],
+    );
+    let mut kb = KeyboardState::new();
+    let mut mouse = MouseState::new();
+    let mut state = ActionState::new();
+
+    press_key(&mut kb, KeyCode::AltLeft);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(!state.is_action_active(Action::SecondaryAction));
+
+    mouse.on_button(MouseButton::Left, ElementState::Pressed);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(state.is_action_active(Action::SecondaryAction));
+}
+
+#[test]
+fn test_key_chord_ctrl_s_needs_both_keys() {
+    let mut map = InputMap::new();
+    map.set_bindings(Action::MoveBack, vec![InputBinding::Key(KeyCode::KeyS)]);
+    map.set_bindings(
+        Action::Crouch,
+        vec![InputBinding::Key(KeyCode::ControlRight)],
+    );
+    map.set_bindings(
+        Action::Pause,
+        vec![InputBinding::key_chord(&[KeyCode::KeyS], Modifiers::CTRL)],
+    );
+    let mouse = MouseState::new();
+    let mut state = ActionState::new();
+
+    let mut kb = KeyboardState::new();
+    press_key(&mut kb, KeyCode::KeyS);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(state.is_action_active(Action::MoveBack));
+    assert!(!state.is_action_active(Action::Pause));
+
+    // Either Ctrl key satisfies the modifier and takes priority over `S`.
+    press_key(&mut kb, KeyCode::ControlRight);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(state.is_action_active(Action::Pause));
+    assert!(!state.is_action_active(Action::MoveBack));
+    assert!(!state.is_action_active(Action::Crouch));
+
+    release_key(&mut kb, KeyCode::KeyS);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(!state.is_action_active(Action::Pause));
+    assert!(state.is_action_active(Action::Crouch));
+}
+
+#[test]
+fn test_resolve_chord_rejects_extra_modifiers() {
+    let mut kb = KeyboardState::new();
+    press_key(&mut kb, KeyCode::ShiftLeft);
+    press_key(&mut kb, KeyCode::F5);
+    assert!(ActionResolver::resolve_chord(
+        &kb,
+        &[KeyCode::F5],
+        Modifiers::SHIFT
+    ));
+    assert_eq!(Modifiers::from_keyboard_state(&kb), Modifiers::SHIFT);
+
+    press_key(&mut kb, KeyCode::AltLeft);
+    assert!(!ActionResolver::resolve_chord(
+        &kb,
+        &[KeyCode::F5],
+        Modifiers::SHIFT
+    ));
+    assert!(!ActionResolver::resolve_chord(
+        &kb,
+        &[],
+        Modifiers::SHIFT | Modifiers::ALT
+    ));
+}
+
+#[test]
+fn test_key_chord_ron_roundtrip() {
+    let mut map = InputMap::new();
+    let chord = InputBinding::key_chord(&[KeyCode::Enter, KeyCode::KeyF], Modifiers::ALT);
+    map.set_bindings(Action::Pause, vec![chord.clone()]);
+    let restored = InputMap::from_ron(&map.to_ron().expect("serialize")).expect("deserialize");
+    assert_eq!(restored.get_bindings(&Action::Pause), [chord]);
+}
@@ -0,0 +1,266 @@
+//! Unit tests for action mapping and resolution.
+
+use super::*;
+use crate::keyboard::KeyboardState;
+use crate::mouse::MouseState;
+use winit::event::ElementState;
+use winit::keyboard::PhysicalKey;
+
+/// Helper: press a key on a keyboard state.
+fn press_key(kb: &mut KeyboardState, code: KeyCode) {
+    kb.process_raw(crate::keyboard::RawKeyEvent {
+        key: PhysicalKey::Code(code),
+        state: ElementState::Pressed,
+        repeat: false,
+    });
+}
+
+/// Helper: release a key on a keyboard state.
+fn release_key(kb: &mut KeyboardState, code: KeyCode) {
+    kb.process_raw(crate::keyboard::RawKeyEvent {
+        key: PhysicalKey::Code(code),
+        state: ElementState::Released,
+        repeat: false,
+    });
+}
+
+#[test]
+fn test_action_bound_to_key_activates_on_press() {
+    let mut map = InputMap::new();
+    map.set_bindings(Action::MoveForward, vec![InputBinding::Key(KeyCode::KeyW)]);
+
+    let mut kb = KeyboardState::new();
+    press_key(&mut kb, KeyCode::KeyW);
+
+    let mouse = MouseState::new();
+    let mut state = ActionState::new();
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+
+    assert!(state.is_action_active(Action::MoveForward));
+    assert!((state.action_value(Action::MoveForward) - 1.0).abs() < f32::EPSILON);
+}
+
+#[test]
+fn test_action_bound_to_gamepad_axis_returns_analog() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::MoveForward,
+        vec![InputBinding::GamepadAxis(GamepadAxisBinding::LeftStickY)],
+    );
+
+    let kb = KeyboardState::new();
+    let mouse = MouseState::new();
+
+    // Build a mock gamepad state with left_stick.y = 0.75
+    // We need to use the mock manager from gamepad module
+    use crate::gamepad::MockGamepadManager;
+    let mut mgr = MockGamepadManager::new();
+    mgr.set_deadzone(0.0);
+    let id = mgr.connect("TestPad");
+    mgr.set_axis(id, "left_stick_y", 0.75);
+    let gp = mgr.gamepad(id).unwrap();
+
+    let mut state = ActionState::new();
+    ActionResolver::resolve(&map, &kb, &mouse, Some(gp), None, &mut state);
+
+    assert!(
+        (state.action_value(Action::MoveForward) - 0.75).abs() < 0.01,
+        "got {}",
+        state.action_value(Action::MoveForward)
+    );
+}
+
+#[test]
+fn test_gamepad_axis_response_applies_radial_dead_zone_and_curve() {
+    use crate::axis_response::ResponseCurve;
+    use crate::gamepad::MockGamepadManager;
+
+    let binding = |axis, dead_zone, curve| InputBinding::GamepadAxisWithResponse {
+        axis,
+        response: AxisResponse::new(dead_zone, curve),
+    };
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::MoveForward,
+        vec![binding(
+            GamepadAxisBinding::LeftStickY,
+            0.2,
+            ResponseCurve::Linear,
+        )],
+    );
+    map.set_bindings(
+        Action::MoveRight,
+        vec![binding(
+            GamepadAxisBinding::RightStickX,
+            0.0,
+            ResponseCurve::Squared,
+        )],
+    );
+
+    let kb = KeyboardState::new();
+    let mouse = MouseState::new();
+    let mut mgr = MockGamepadManager::new();
+    let id = mgr.connect("TestPad");
+    // Drift inside the radial dead zone.
+    mgr.set_axis(id, "left_stick_x", 0.1);
+    mgr.set_axis(id, "left_stick_y", 0.1);
+    mgr.set_axis(id, "right_stick_x", 0.5);
+
+    let mut state = ActionState::new();
+    ActionResolver::resolve(&map, &kb, &mouse, mgr.gamepad(id), None, &mut state);
+    assert_eq!(state.action_value(Action::MoveForward), 0.0);
+    assert!((state.action_value(Action::MoveRight) - 0.25).abs() < 1e-6);
+
+    // A diagonal whose components are each inside the per-axis manager
+    // dead zone (0.15) still registers with the radial dead zone.
+    mgr.set_axis(id, "left_stick_x", 0.149);
+    mgr.set_axis(id, "left_stick_y", 0.149);
+    ActionResolver::resolve(&map, &kb, &mouse, mgr.gamepad(id), None, &mut state);
+    assert!(state.action_value(Action::MoveForward) > 0.0);
+}
+
+#[test]
+fn test_unbound_action_returns_false_and_zero() {
+    let map = InputMap::new();
+    let kb = KeyboardState::new();
+    let mouse = MouseState::new();
+    let mut state = ActionState::new();
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+
+    assert!(!state.is_action_active(Action::OpenInventory));
+    assert!((state.action_value(Action::OpenInventory)).abs() < f32::EPSILON);
+}
+
+#[test]
+fn test_multiple_bindings_or_logic() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::Jump,
+        vec![
+            InputBinding::Key(KeyCode::Space),
+            InputBinding::GamepadButton(UnifiedButton::South),
+        ],
+    );
+
+    let mut kb = KeyboardState::new();
+    press_key(&mut kb, KeyCode::Space);
+
+    let mouse = MouseState::new();
+    let mut state = ActionState::new();
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+
+    assert!(state.is_action_active(Action::Jump));
+}
+
+#[test]
+fn test_multiple_bindings_both_active() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::Jump,
+        vec![
+            InputBinding::Key(KeyCode::Space),
+            InputBinding::GamepadButton(UnifiedButton::South),
+        ],
+    );
+
+    let mut kb = KeyboardState::new();
+    press_key(&mut kb, KeyCode::Space);
+
+    let mouse = MouseState::new();
+
+    use crate::gamepad::MockGamepadManager;
+    let mut mgr = MockGamepadManager::new();
+    let id = mgr.connect("TestPad");
+    mgr.press_button(id, UnifiedButton::South);
+    let gp = mgr.gamepad(id).unwrap();
+
+    let mut state = ActionState::new();
+    ActionResolver::resolve(&map, &kb, &mouse, Some(gp), None, &mut state);
+
+    assert!(
+        (state.action_value(Action::Jump) - 1.0).abs() < f32::EPSILON,
+        "should be clamped to 1.0, got {}",
+        state.action_value(Action::Jump)
+    );
+}
+
+#[test]
+fn test_action_map_modified_at_runtime() {
+    let mut map = InputMap::new();
+    map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::Space)]);
+
+    let mut kb = KeyboardState::new();
+    let mouse = MouseState::new();
+    let mut state = ActionState::new();
+
+    // Rebind Jump to KeyJ
+    map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::KeyJ)]);
+
+    press_key(&mut kb, KeyCode::KeyJ);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(state.is_action_active(Action::Jump));
+
+    // Space should no longer activate Jump
+    release_key(&mut kb, KeyCode::KeyJ);
+    press_key(&mut kb, KeyCode::Space);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(!state.is_action_active(Action::Jump));
+}
+
+#[test]
+fn test_action_just_activated_edge() {
+    let mut map = InputMap::new();
+    map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::Space)]);
+
+    let mut kb = KeyboardState::new();
+    let mouse = MouseState::new();
+    let mut state = ActionState::new();
+
+    // Frame 1: press Space
+    press_key(&mut kb, KeyCode::Space);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(
+        state.action_just_activated(Action::Jump),
+        "should be just activated on frame 1"
+    );
+
+    // Frame 2: still held
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(
+        !state.action_just_activated(Action::Jump),
+        "should NOT be just activated on frame 2"
+    );
+    assert!(state.is_action_active(Action::Jump));
+}
+
+#[test]
+fn test_analog_sum_clamped() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::MoveForward,
+        vec![
+            InputBinding::Key(KeyCode::KeyW),
+            InputBinding::GamepadAxis(GamepadAxisBinding::LeftStickY),
+        ],
+    );
+
+    let mut kb = KeyboardState::new();
+    press_key(&mut kb, KeyCode::KeyW); // contributes 1.0
+    let mouse = MouseState::new();
+
+    use crate::gamepad::MockGamepadManager;
+    let mut mgr = MockGamepadManager::new();
+    mgr.set_deadzone(0.0);
+    let id = mgr.connect("TestPad");
+    mgr.set_axis(id, "left_stick_y", 0.8); // contributes 0.8
+    let gp = mgr.gamepad(id).unwrap();
+
+    let mut state = ActionState::new();
+    ActionResolver::resolve(&map, &kb, &mouse, Some(gp), None, &mut state);
+
+    assert!(
+        (state.action_value(Action::MoveForward) - 1.0).abs() < f32::EPSILON,
+        "should be clamped to 1.0, got {}",
+        state.action_value(Action::MoveForward)
+    );
+}
@@ -0,0 +1,132 @@
+//! Timing-based activation modes for digital bindings.
+//!
+//! An [`ActivationMode`] is attached to a binding through
+//! [`InputBinding::WithActivation`](crate::InputBinding::WithActivation).
+//! [`ActionResolver`](crate::ActionResolver) keeps one [`ActivationTracker`]
+//! per wrapped binding and advances it with the frame time every resolve,
+//! so tap, hold, and double-tap are judged on the same clock as the rest of
+//! the frame (see [`ActionState::set_time`](crate::ActionState::set_time)).
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How a digital binding's press turns into an action activation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ActivationMode {
+    /// Active while the input is held (the behavior of an unwrapped binding).
+    #[default]
+    Press,
+    /// Active for one frame on release, if the press lasted at most this many
+    /// milliseconds.
+    Tap(u32),
+    /// Active from the moment the input has been held for this many
+    /// milliseconds until it is released.
+    Hold(u32),
+    /// Active while a second press is held, if it started within this many
+    /// milliseconds of the previous press.
+    DoubleTap(u32),
+}
+
+/// Press history of one binding, advanced once per frame.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ActivationTracker {
+    /// Start of the current press, if the input is held.
+    pressed_at: Option<Duration>,
+    /// Start of the most recent press.
+    last_press: Option<Duration>,
+    /// Start of the press before `last_press`.
+    prev_press: Option<Duration>,
+    /// Frame time of the most recent release and how long that press lasted.
+    released: Option<(Duration, Duration)>,
+}
+
+impl ActivationTracker {
+    /// Feed the binding's raw held state at frame time `now`.
+    ///
+    /// Idempotent within a frame, so a binding evaluated by several context
+    /// layers advances only once.
+    pub(crate) fn update(&mut self, held: bool, now: Duration) {
+        match (held, self.pressed_at) {
+            (true, None) => {
+                self.prev_press = self.last_press;
+                self.last_press = Some(now);
+                self.pressed_at = Some(now);
+            }
+            (false, Some(start)) => {
+                self.released = Some((now, now.saturating_sub(start)));
+                self.pressed_at = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether `mode` is satisfied at frame time `now`.
+    pub(crate) fn is_active(&self, mode: ActivationMode, now: Duration) -> bool {
+        let ms = |ms: u32| Duration::from_millis(u64::from(ms));
+        match mode {
+            ActivationMode::Press => self.pressed_at.is_some(),
+            ActivationMode::Tap(max_ms) => self
+                .released
+                .is_some_and(|(at, held)| at == now && held <= ms(max_ms)),
+            ActivationMode::Hold(min_ms) => self
+                .pressed_at
+                .is_some_and(|start| now.saturating_sub(start) >= ms(min_ms)),
+            ActivationMode::DoubleTap(window_ms) => match (self.pressed_at, self.prev_press) {
+                (Some(start), Some(prev)) => start.saturating_sub(prev) <= ms(window_ms),
+                _ => false,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_tap_fires_only_on_release_frame() {
+        let mut t = ActivationTracker::default();
+        t.update(true, at(0));
+        assert!(!t.is_active(ActivationMode::Tap(200), at(0)));
+        t.update(false, at(150));
+        assert!(t.is_active(ActivationMode::Tap(200), at(150)));
+        t.update(false, at(160));
+        assert!(!t.is_active(ActivationMode::Tap(200), at(160)));
+    }
+
+    #[test]
+    fn test_hold_waits_for_threshold() {
+        let mut t = ActivationTracker::default();
+        t.update(true, at(1000));
+        assert!(!t.is_active(ActivationMode::Hold(200), at(1199)));
+        assert!(t.is_active(ActivationMode::Hold(200), at(1200)));
+        t.update(false, at(1300));
+        assert!(!t.is_active(ActivationMode::Hold(200), at(1300)));
+    }
+
+    #[test]
+    fn test_double_tap_measures_press_to_press() {
+        let mut t = ActivationTracker::default();
+        t.update(true, at(0));
+        t.update(false, at(50));
+        t.update(true, at(250));
+        assert!(t.is_active(ActivationMode::DoubleTap(300), at(250)));
+        assert!(!t.is_active(ActivationMode::DoubleTap(200), at(250)));
+    }
+
+    #[test]
+    fn test_repeated_update_in_same_frame_is_idempotent() {
+        let mut t = ActivationTracker::default();
+        t.update(true, at(0));
+        t.update(false, at(100));
+        t.update(false, at(100));
+        assert!(t.is_active(ActivationMode::Tap(200), at(100)));
+        t.update(true, at(200));
+        t.update(true, at(200));
+        assert!(t.is_active(ActivationMode::DoubleTap(300), at(200)));
+    }
+}
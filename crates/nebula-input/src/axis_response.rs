@@ -0,0 +1,302 @@
+//! Analog stick shaping: radial dead zones and response curves.
+//!
+//! A per-axis dead zone clips diagonals (a stick pushed to 45° reads less on
+//! each axis than the threshold long before the stick itself is near centre).
+//! [`radial_deadzone`] instead measures the length of the stick vector, zeroes
+//! it inside the dead zone, and rescales the rest so full deflection is still
+//! `1.0`, or already `1.0` at the saturation point for worn sticks that never
+//! reach the rim. A [`ResponseCurve`] is then applied to the rescaled magnitude
+//! to make small deflections finer for aiming.
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+
+/// Mapping from dead-zone-adjusted deflection to output magnitude.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum ResponseCurve {
+    /// Output equals input.
+    #[default]
+    Linear,
+    /// Output is input squared: finer control near centre.
+    Squared,
+    /// Output is input cubed: finest control near centre.
+    Cubic,
+    /// Output is input raised to this exponent. `Expo(2.0)` equals
+    /// [`Squared`](Self::Squared); exponents below `1.0` make the centre
+    /// more sensitive instead.
+    Expo(f32),
+    /// Piecewise-linear curve through `(input, output)` points, sorted by
+    /// input. Inputs outside the first and last point take their outputs; an
+    /// empty list behaves like [`Linear`](Self::Linear).
+    Custom(Vec<(f32, f32)>),
+}
+
+impl ResponseCurve {
+    /// Apply the curve to a magnitude in `[0.0, 1.0]`.
+    pub fn apply(&self, magnitude: f32) -> f32 {
+        match self {
+            Self::Linear => magnitude,
+            Self::Squared => magnitude * magnitude,
+            Self::Cubic => magnitude * magnitude * magnitude,
+            Self::Expo(exponent) => magnitude.powf(*exponent),
+            Self::Custom(points) => interpolate(points, magnitude),
+        }
+    }
+}
+
+/// Evaluate a piecewise-linear curve at `x`.
+fn interpolate(points: &[(f32, f32)], x: f32) -> f32 {
+    let (Some(&first), Some(&last)) = (points.first(), points.last()) else {
+        return x;
+    };
+    if x <= first.0 {
+        return first.1;
+    }
+    for pair in points.windows(2) {
+        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+        if x <= x1 {
+            let span = x1 - x0;
+            if span <= 0.0 {
+                return y1;
+            }
+            return y0 + (y1 - y0) * (x - x0) / span;
+        }
+    }
+    last.1
+}
+
+// `f32` is not `Eq`/`Hash`; compare bit patterns so bindings stay hashable.
+impl PartialEq for ResponseCurve {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Linear, Self::Linear)
+            | (Self::Squared, Self::Squared)
+            | (Self::Cubic, Self::Cubic) => true,
+            (Self::Expo(a), Self::Expo(b)) => a.to_bits() == b.to_bits(),
+            (Self::Custom(a), Self::Custom(b)) => {
+                a.len() == b.len()
+                    && a.iter().zip(b).all(|(p, q)| {
+                        p.0.to_bits() == q.0.to_bits() && p.1.to_bits() == q.1.to_bits()
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ResponseCurve {}
+
+impl Hash for ResponseCurve {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Linear | Self::Squared | Self::Cubic => {}
+            Self::Expo(exponent) => exponent.to_bits().hash(state),
+            Self::Custom(points) => {
+                for (x, y) in points {
+                    x.to_bits().hash(state);
+                    y.to_bits().hash(state);
+                }
+            }
+        }
+    }
+}
+
+/// Dead zone, saturation, and response curve applied to a gamepad axis.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AxisResponse {
+    /// Radial dead zone as a fraction of full deflection, `[0.0, 0.99]`.
+    pub dead_zone: f32,
+    /// Deflection that already counts as full output, `(dead_zone, 1.0]`.
+    pub saturation: f32,
+    /// Curve applied after the dead zone.
+    pub curve: ResponseCurve,
+}
+
+impl AxisResponse {
+    /// Create a response with the given dead zone (clamped to `[0.0, 0.99]`)
+    /// and no saturation.
+    pub fn new(dead_zone: f32, curve: ResponseCurve) -> Self {
+        Self {
+            dead_zone: dead_zone.clamp(0.0, 0.99),
+            saturation: 1.0,
+            curve,
+        }
+    }
+
+    /// Set the deflection at which output reaches `1.0`.
+    ///
+    /// Clamped to just above the dead zone so the rescaled range never
+    /// collapses.
+    pub fn with_saturation(mut self, saturation: f32) -> Self {
+        self.saturation = saturation.clamp(self.dead_zone + 0.01, 1.0);
+        self
+    }
+
+    /// Shape a full stick vector: radial dead zone and saturation, then curve
+    /// on magnitude.
+    pub fn apply_stick(&self, stick: Vec2) -> Vec2 {
+        let len = stick.length();
+        if len <= self.dead_zone || len == 0.0 {
+            return Vec2::ZERO;
+        }
+        let range = (self.saturation - self.dead_zone).max(f32::EPSILON);
+        let rescaled = ((len - self.dead_zone) / range).min(1.0);
+        let filtered = stick * (rescaled / len);
+        let len = filtered.length();
+        if len == 0.0 {
+            return Vec2::ZERO;
+        }
+        filtered * (self.curve.apply(len) / len)
+    }
+
+    /// Shape a single-axis value such as a trigger.
+    pub fn apply_scalar(&self, value: f32) -> f32 {
+        self.apply_stick(Vec2::new(value, 0.0)).x
+    }
+
+    /// Shape a raw trigger into `[0.0, 1.0]`.
+    ///
+    /// Both the gamepad's own axes and bindings with their own response go
+    /// through here, each starting from the raw value.
+    pub fn apply_trigger(&self, raw: f32) -> f32 {
+        self.apply_scalar(raw).max(0.0)
+    }
+}
+
+impl Default for AxisResponse {
+    fn default() -> Self {
+        Self {
+            dead_zone: 0.15,
+            saturation: 1.0,
+            curve: ResponseCurve::Linear,
+        }
+    }
+}
+
+// `f32` is not `Eq`/`Hash`; compare bit patterns so bindings stay hashable.
+impl PartialEq for AxisResponse {
+    fn eq(&self, other: &Self) -> bool {
+        self.dead_zone.to_bits() == other.dead_zone.to_bits()
+            && self.saturation.to_bits() == other.saturation.to_bits()
+            && self.curve == other.curve
+    }
+}
+
+impl Eq for AxisResponse {}
+
+impl Hash for AxisResponse {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dead_zone.to_bits().hash(state);
+        self.saturation.to_bits().hash(state);
+        self.curve.hash(state);
+    }
+}
+
+/// Apply a radial dead zone to a stick vector.
+///
+/// Vectors shorter than `dead_zone` map to zero. Longer vectors keep their
+/// direction and have their length rescaled from `[dead_zone, 1.0]` to
+/// `[0.0, 1.0]`.
+pub fn radial_deadzone(stick: Vec2, dead_zone: f32) -> Vec2 {
+    let len = stick.length();
+    if len <= dead_zone || len == 0.0 {
+        return Vec2::ZERO;
+    }
+    let rescaled = ((len - dead_zone) / (1.0 - dead_zone)).min(1.0);
+    stick * (rescaled / len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inside_dead_zone_maps_to_zero() {
+        let response = AxisResponse::new(0.2, ResponseCurve::Linear);
+        assert_eq!(response.apply_stick(Vec2::new(0.1, 0.1)), Vec2::ZERO);
+        assert_eq!(response.apply_stick(Vec2::new(0.0, -0.19)), Vec2::ZERO);
+        assert_eq!(response.apply_scalar(0.15), 0.0);
+    }
+
+    #[test]
+    fn test_trigger_never_goes_negative() {
+        let response = AxisResponse::new(0.2, ResponseCurve::Linear);
+        assert_eq!(response.apply_trigger(-0.8), 0.0);
+        assert!((response.apply_trigger(0.6) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_diagonal_not_clipped_by_radial_dead_zone() {
+        // Each component is below the dead zone, but the vector is not.
+        let out = radial_deadzone(Vec2::new(0.15, 0.15), 0.2);
+        assert!(out.x > 0.0 && out.y > 0.0);
+        assert!((out.x - out.y).abs() < 1e-6, "direction preserved");
+    }
+
+    #[test]
+    fn test_full_deflection_stays_full() {
+        let out = radial_deadzone(Vec2::new(0.0, 1.0), 0.25);
+        assert!((out.y - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_squared_curve_at_half_deflection() {
+        let response = AxisResponse::new(0.0, ResponseCurve::Squared);
+        let out = response.apply_stick(Vec2::new(0.5, 0.0));
+        assert!((out.x - 0.25).abs() < 1e-6, "got {}", out.x);
+
+        let cubic = AxisResponse::new(0.0, ResponseCurve::Cubic);
+        assert!((cubic.apply_scalar(-0.5) + 0.125).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_expo_curve_at_half_deflection() {
+        let response = AxisResponse::new(0.0, ResponseCurve::Expo(2.0));
+        assert!((response.apply_scalar(0.5) - 0.25).abs() < 1e-6);
+        assert!((response.apply_scalar(-0.5) + 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dead_zone_zeroes_small_values_and_rescales_rest() {
+        let response = AxisResponse::new(0.1, ResponseCurve::Linear);
+        assert_eq!(response.apply_scalar(0.05), 0.0);
+        assert_eq!(response.apply_scalar(-0.1), 0.0);
+        // (0.55 - 0.1) / 0.9 = 0.5
+        assert!((response.apply_scalar(0.55) - 0.5).abs() < 1e-6);
+        assert!((response.apply_scalar(1.0) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_saturation_reaches_full_output_early() {
+        let response = AxisResponse::new(0.1, ResponseCurve::Linear).with_saturation(0.9);
+        assert!((response.apply_scalar(0.9) - 1.0).abs() < 1e-6);
+        assert!((response.apply_scalar(0.5) - 0.5).abs() < 1e-6);
+        assert!((response.apply_scalar(0.95) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_custom_curve_interpolates_between_points() {
+        let curve = ResponseCurve::Custom(vec![(0.0, 0.0), (0.5, 0.2), (1.0, 1.0)]);
+        assert!((curve.apply(0.25) - 0.1).abs() < 1e-6);
+        assert!((curve.apply(0.75) - 0.6).abs() < 1e-6);
+        assert!((curve.apply(1.0) - 1.0).abs() < 1e-6);
+        assert_eq!(ResponseCurve::Custom(Vec::new()).apply(0.3), 0.3);
+    }
+
+    #[test]
+    fn test_ron_roundtrip() {
+        let response = AxisResponse::new(0.1, ResponseCurve::Cubic);
+        let text = ron::to_string(&response).unwrap();
+        let back: AxisResponse = ron::from_str(&text).unwrap();
+        assert_eq!(back, response);
+
+        let custom = AxisResponse::new(0.1, ResponseCurve::Custom(vec![(0.0, 0.0), (1.0, 0.8)]))
+            .with_saturation(0.95);
+        let text = ron::to_string(&custom).unwrap();
+        let back: AxisResponse = ron::from_str(&text).unwrap();
+        assert_eq!(back, custom);
+    }
+}
@@ -1,12 +1,16 @@
 //! Gamepad input abstraction wrapping [`gilrs`].
 //!
-//! [`GamepadManager`] polls gilrs each frame, normalises axes through a
-//! configurable deadzone, and tracks per-button press/release state.
-//! Hot-plug is handled transparently: gamepads appear in
+//! [`GamepadManager`] polls gilrs each frame, shapes axes through
+//! [`GamepadAxes`] (see [`gamepad_axes`](crate::gamepad_axes)), and tracks
+//! per-button press/release state. Hot-plug is handled transparently:
+//! gamepads appear in
 //! [`connected_gamepads`](GamepadManager::connected_gamepads) when plugged in
-//! and disappear when unplugged.
+//! and disappear when unplugged. Rumble goes through gilrs force feedback and
+//! is silently skipped on pads without it.
 
-use gilrs::{Axis, Button, EventType, GamepadId, Gilrs};
+use crate::gamepad_axes::{GamepadAxes, GamepadAxisResponses};
+use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Repeat, Replay, Ticks};
+use gilrs::{Button, EventType, GamepadId, Gilrs};
 use glam::Vec2;
 use std::collections::HashMap;
 
@@ -67,19 +71,6 @@ struct ButtonFrame {
     just_released: bool,
 }
 
-/// Axis values for a single gamepad.
-#[derive(Debug, Clone, Copy, Default)]
-pub struct GamepadAxes {
-    /// Left stick. x: left(-1)..right(+1), y: down(-1)..up(+1).
-    pub left_stick: Vec2,
-    /// Right stick.
-    pub right_stick: Vec2,
-    /// Left trigger 0.0..1.0.
-    pub left_trigger: f32,
-    /// Right trigger 0.0..1.0.
-    pub right_trigger: f32,
-}
-
 /// State snapshot for a single connected gamepad.
 pub struct GamepadState {
     _id: GamepadId,
@@ -120,6 +111,17 @@ impl GamepadState {
         self.axes.right_stick
     }
 
+    /// Left analog stick without deadzone filtering, for bindings that apply
+    /// their own [`AxisResponse`](crate::AxisResponse).
+    pub fn raw_left_stick(&self) -> Vec2 {
+        self.axes.raw_left_stick
+    }
+
+    /// Right analog stick without deadzone filtering.
+    pub fn raw_right_stick(&self) -> Vec2 {
+        self.axes.raw_right_stick
+    }
+
     /// Left trigger value `[0.0, 1.0]`.
     pub fn left_trigger(&self) -> f32 {
         self.axes.left_trigger
@@ -130,6 +132,17 @@ impl GamepadState {
         self.axes.right_trigger
     }
 
+    /// Left trigger before the manager's response, for bindings that apply
+    /// their own [`AxisResponse`](crate::AxisResponse).
+    pub fn raw_left_trigger(&self) -> f32 {
+        self.axes.raw_left_trigger
+    }
+
+    /// Right trigger before the manager's response.
+    pub fn raw_right_trigger(&self) -> f32 {
+        self.axes.raw_right_trigger
+    }
+
     /// Whether `button` is currently held.
     pub fn is_button_pressed(&self, button: UnifiedButton) -> bool {
         self.buttons.get(&button).is_some_and(|b| b.pressed)
@@ -150,8 +163,10 @@ impl GamepadState {
 pub struct GamepadManager {
     gilrs: Gilrs,
     gamepads: HashMap<GamepadId, GamepadState>,
-    /// Deadzone threshold for analog sticks (default 0.15).
-    deadzone: f32,
+    /// Per-axis dead zone, saturation, and curve.
+    responses: GamepadAxisResponses,
+    /// Rumble effects kept alive until replaced; dropping one stops it.
+    rumble: HashMap<GamepadId, Effect>,
 }
 
 impl GamepadManager {
@@ -164,7 +179,8 @@ impl GamepadManager {
         let mut manager = Self {
             gilrs,
             gamepads: HashMap::new(),
-            deadzone: 0.15,
+            responses: GamepadAxisResponses::default(),
+            rumble: HashMap::new(),
         };
         // Register already-connected gamepads.
         let ids: Vec<_> = manager
@@ -187,15 +203,86 @@ impl Default for GamepadManager {
 }
 
 impl GamepadManager {
-    /// Set the analog stick deadzone. Values below this threshold are clamped
-    /// to zero and the remaining range is rescaled to `[0.0, 1.0]`.
+    /// Set the deadzone on every axis. Values below this threshold are
+    /// clamped to zero and the remaining range is rescaled to `[0.0, 1.0]`.
     pub fn set_deadzone(&mut self, value: f32) {
-        self.deadzone = value.clamp(0.0, 0.99);
+        self.responses.set_dead_zone(value);
+        self.reapply_responses();
     }
 
-    /// Current deadzone threshold.
+    /// Current left stick deadzone threshold.
     pub fn deadzone(&self) -> f32 {
-        self.deadzone
+        self.responses.left_stick.dead_zone
+    }
+
+    /// Per-axis response configuration.
+    pub fn axis_responses(&self) -> &GamepadAxisResponses {
+        &self.responses
+    }
+
+    /// Replace the per-axis response configuration.
+    pub fn set_axis_responses(&mut self, responses: GamepadAxisResponses) {
+        self.responses = responses;
+        self.reapply_responses();
+    }
+
+    fn reapply_responses(&mut self) {
+        for state in self.gamepads.values_mut() {
+            state.axes.apply_responses(&self.responses);
+        }
+    }
+
+    /// Rumble gamepad `id` with motor strengths in `[0.0, 1.0]` for
+    /// `duration_ms` milliseconds, replacing any rumble already playing.
+    ///
+    /// `strong` drives the low-frequency motor and `weak` the high-frequency
+    /// one. Disconnected pads and pads without force feedback are skipped
+    /// and return `Ok(())`. Both strengths at zero stop the current rumble.
+    ///
+    /// # Errors
+    /// Returns the gilrs error if the force-feedback backend rejects the
+    /// effect.
+    pub fn rumble(
+        &mut self,
+        id: GamepadId,
+        strong: f32,
+        weak: f32,
+        duration_ms: u32,
+    ) -> Result<(), gilrs::ff::Error> {
+        let supported = self
+            .gilrs
+            .connected_gamepad(id)
+            .is_some_and(|pad| pad.is_ff_supported());
+        if !supported {
+            return Ok(());
+        }
+        // Dropping the previous handle stops it.
+        self.rumble.remove(&id);
+        let Some((strong, weak)) = rumble_magnitudes(strong, weak, duration_ms) else {
+            return Ok(());
+        };
+        let play_for = Ticks::from_ms(duration_ms);
+        let scheduling = Replay {
+            play_for,
+            ..Default::default()
+        };
+        let effect = EffectBuilder::new()
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Strong { magnitude: strong },
+                scheduling,
+                envelope: Default::default(),
+            })
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Weak { magnitude: weak },
+                scheduling,
+                envelope: Default::default(),
+            })
+            .repeat(Repeat::For(play_for))
+            .gamepads(&[id])
+            .finish(&mut self.gilrs)?;
+        effect.play()?;
+        self.rumble.insert(id, effect);
+        Ok(())
     }
 
     /// Iterate over IDs of currently connected gamepads.
@@ -238,23 +325,13 @@ impl GamepadManager {
                     if let Some(state) = self.gamepads.get_mut(&id) {
                         state.connected = false;
                     }
+                    self.rumble.remove(&id);
                 }
                 EventType::AxisChanged(axis, raw_value, _) => {
-                    if let Some(state) = self.gamepads.get_mut(&id) {
-                        let value = apply_deadzone(raw_value, self.deadzone);
-                        match axis {
-                            Axis::LeftStickX => state.axes.left_stick.x = value,
-                            Axis::LeftStickY => state.axes.left_stick.y = value,
-                            Axis::RightStickX => state.axes.right_stick.x = value,
-                            Axis::RightStickY => state.axes.right_stick.y = value,
-                            Axis::LeftZ => {
-                                state.axes.left_trigger = value.max(0.0);
-                            }
-                            Axis::RightZ => {
-                                state.axes.right_trigger = value.max(0.0);
-                            }
-                            _ => {}
-                        }
+                    if let Some(state) = self.gamepads.get_mut(&id)
+                        && state.axes.set_raw(axis, raw_value)
+                    {
+                        state.axes.apply_responses(&self.responses);
                     }
                 }
                 EventType::ButtonPressed(button, _) => {
@@ -281,18 +358,13 @@ impl GamepadManager {
     }
 }
 
-/// Apply deadzone filtering with rescaling.
+/// Convert rumble strengths to motor magnitudes.
 ///
-/// If `|raw| < deadzone`, returns `0.0`.
-/// Otherwise rescales from `[deadzone, 1.0]` to `[0.0, 1.0]`, preserving sign.
-pub(crate) fn apply_deadzone(raw: f32, deadzone: f32) -> f32 {
-    let abs = raw.abs();
-    if abs < deadzone {
-        return 0.0;
-    }
-    let scale = 1.0 / (1.0 - deadzone);
-    let rescaled = (abs - deadzone) * scale;
-    rescaled.min(1.0).copysign(raw)
+/// Returns `None` when the rumble would be silent or zero-length.
+pub(crate) fn rumble_magnitudes(strong: f32, weak: f32, duration_ms: u32) -> Option<(u16, u16)> {
+    let to_u16 = |v: f32| (v.clamp(0.0, 1.0) * f32::from(u16::MAX)).round() as u16;
+    let (strong, weak) = (to_u16(strong), to_u16(weak));
+    (duration_ms > 0 && (strong > 0 || weak > 0)).then_some((strong, weak))
 }
 
 // ── Mock-friendly test helpers ──────────────────────────────────────────────
@@ -301,7 +373,11 @@ pub(crate) fn apply_deadzone(raw: f32, deadzone: f32) -> f32 {
 #[cfg(test)]
 pub(crate) struct MockGamepadManager {
     pub gamepads: HashMap<u64, GamepadState>,
-    pub deadzone: f32,
+    pub responses: GamepadAxisResponses,
+    /// Pads that report force-feedback support.
+    pub ff_supported: std::collections::HashSet<u64>,
+    /// Last rumble played per pad as `(strong, weak, duration_ms)`.
+    pub rumbles: HashMap<u64, (u16, u16, u32)>,
     next_id: u64,
 }
 
@@ -310,13 +386,34 @@ impl MockGamepadManager {
     pub fn new() -> Self {
         Self {
             gamepads: HashMap::new(),
-            deadzone: 0.15,
+            responses: GamepadAxisResponses::default(),
+            ff_supported: std::collections::HashSet::new(),
+            rumbles: HashMap::new(),
             next_id: 0,
         }
     }
 
     pub fn set_deadzone(&mut self, value: f32) {
-        self.deadzone = value.clamp(0.0, 0.99);
+        self.responses.set_dead_zone(value);
+    }
+
+    /// Mirrors [`GamepadManager::rumble`] without a force-feedback backend.
+    pub fn rumble(
+        &mut self,
+        id: u64,
+        strong: f32,
+        weak: f32,
+        duration_ms: u32,
+    ) -> Result<(), gilrs::ff::Error> {
+        let connected = self.gamepads.get(&id).is_some_and(|s| s.connected);
+        if !connected || !self.ff_supported.contains(&id) {
+            return Ok(());
+        }
+        self.rumbles.remove(&id);
+        if let Some((strong, weak)) = rumble_magnitudes(strong, weak, duration_ms) {
+            self.rumbles.insert(id, (strong, weak, duration_ms));
+        }
+        Ok(())
     }
 
     /// Simulate a gamepad connection, returns an opaque id.
@@ -343,17 +440,20 @@ impl MockGamepadManager {
     }
 
     pub fn set_axis(&mut self, id: u64, axis: &str, raw_value: f32) {
-        if let Some(s) = self.gamepads.get_mut(&id) {
-            let value = apply_deadzone(raw_value, self.deadzone);
-            match axis {
-                "left_stick_x" => s.axes.left_stick.x = value,
-                "left_stick_y" => s.axes.left_stick.y = value,
-                "right_stick_x" => s.axes.right_stick.x = value,
-                "right_stick_y" => s.axes.right_stick.y = value,
-                "left_trigger" => s.axes.left_trigger = value.max(0.0),
-                "right_trigger" => s.axes.right_trigger = value.max(0.0),
-                _ => {}
-            }
+        use gilrs::Axis;
+        let axis = match axis {
+            "left_stick_x" => Axis::LeftStickX,
+            "left_stick_y" => Axis::LeftStickY,
+            "right_stick_x" => Axis::RightStickX,
+            "right_stick_y" => Axis::RightStickY,
+            "left_trigger" => Axis::LeftZ,
+            "right_trigger" => Axis::RightZ,
+            _ => return,
+        };
+        if let Some(s) = self.gamepads.get_mut(&id)
+            && s.axes.set_raw(axis, raw_value)
+        {
+            s.axes.apply_responses(&self.responses);
         }
     }
 
@@ -392,96 +492,5 @@ impl MockGamepadManager {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_gamepad_connection_detected() {
-        let mut mgr = MockGamepadManager::new();
-        let id = mgr.connect("Xbox Controller");
-        assert_eq!(mgr.connected_count(), 1);
-        assert!(mgr.gamepad(id).unwrap().connected());
-    }
-
-    #[test]
-    fn test_axis_values_in_range() {
-        let mut mgr = MockGamepadManager::new();
-        mgr.set_deadzone(0.0); // disable deadzone for this test
-        let id = mgr.connect("Pad");
-
-        for &val in &[-1.0_f32, 0.0, 1.0] {
-            mgr.set_axis(id, "left_stick_x", val);
-            mgr.set_axis(id, "left_stick_y", val);
-            let stick = mgr.gamepad(id).unwrap().left_stick();
-            assert!((-1.0..=1.0).contains(&stick.x));
-            assert!((-1.0..=1.0).contains(&stick.y));
-        }
-    }
-
-    #[test]
-    fn test_deadzone_filters_small_values() {
-        let mut mgr = MockGamepadManager::new();
-        mgr.set_deadzone(0.15);
-        let id = mgr.connect("Pad");
-        mgr.set_axis(id, "left_stick_x", 0.10);
-        assert_eq!(mgr.gamepad(id).unwrap().left_stick().x, 0.0);
-    }
-
-    #[test]
-    fn test_deadzone_rescales_above_threshold() {
-        let mut mgr = MockGamepadManager::new();
-        mgr.set_deadzone(0.15);
-        let id = mgr.connect("Pad");
-        mgr.set_axis(id, "left_stick_x", 0.575);
-        let rescaled = mgr.gamepad(id).unwrap().left_stick().x;
-        // (0.575 - 0.15) / (1.0 - 0.15) = 0.425 / 0.85 = 0.5
-        assert!((rescaled - 0.5).abs() < 0.01, "got {rescaled}");
-    }
-
-    #[test]
-    fn test_button_state_tracked() {
-        let mut mgr = MockGamepadManager::new();
-        let id = mgr.connect("Pad");
-
-        mgr.press_button(id, UnifiedButton::South);
-        let gs = mgr.gamepad(id).unwrap();
-        assert!(gs.is_button_pressed(UnifiedButton::South));
-        assert!(gs.just_button_pressed(UnifiedButton::South));
-
-        mgr.clear_frame();
-        mgr.release_button(id, UnifiedButton::South);
-        let gs = mgr.gamepad(id).unwrap();
-        assert!(!gs.is_button_pressed(UnifiedButton::South));
-        assert!(gs.just_button_released(UnifiedButton::South));
-    }
-
-    #[test]
-    fn test_disconnection_handled_gracefully() {
-        let mut mgr = MockGamepadManager::new();
-        let id = mgr.connect("Pad");
-        mgr.disconnect(id);
-        assert!(!mgr.gamepad(id).unwrap().connected());
-        assert_eq!(mgr.connected_count(), 0);
-    }
-
-    #[test]
-    fn test_multiple_gamepads_supported() {
-        let mut mgr = MockGamepadManager::new();
-        let _id1 = mgr.connect("Pad 1");
-        let _id2 = mgr.connect("Pad 2");
-        assert_eq!(mgr.connected_count(), 2);
-    }
-
-    #[test]
-    fn test_custom_deadzone() {
-        let mut mgr = MockGamepadManager::new();
-        mgr.set_deadzone(0.25);
-        let id = mgr.connect("Pad");
-
-        mgr.set_axis(id, "left_stick_x", 0.20);
-        assert_eq!(mgr.gamepad(id).unwrap().left_stick().x, 0.0);
-
-        mgr.set_axis(id, "left_stick_x", 0.30);
-        assert!(mgr.gamepad(id).unwrap().left_stick().x > 0.0);
-    }
-}
+#[path = "gamepad_tests.rs"]
+mod tests;
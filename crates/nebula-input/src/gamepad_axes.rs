@@ -0,0 +1,151 @@
+//! Per-axis shaping of raw gamepad input.
+//!
+//! [`GamepadAxes`] keeps each stick and trigger both raw and shaped by the
+//! [`GamepadAxisResponses`] last applied, so bindings that carry their own
+//! [`AxisResponse`] can read the raw value and shape it exactly once.
+
+use crate::axis_response::{AxisResponse, ResponseCurve};
+use gilrs::Axis;
+use glam::Vec2;
+
+/// Per-axis shaping applied to raw gamepad input before it reaches
+/// [`ActionResolver`](crate::ActionResolver).
+///
+/// Sticks are shaped as a whole vector so their dead zone stays radial.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GamepadAxisResponses {
+    /// Left stick response.
+    pub left_stick: AxisResponse,
+    /// Right stick response.
+    pub right_stick: AxisResponse,
+    /// Left trigger response.
+    pub left_trigger: AxisResponse,
+    /// Right trigger response.
+    pub right_trigger: AxisResponse,
+}
+
+impl GamepadAxisResponses {
+    /// Set the same dead zone on every axis.
+    pub fn set_dead_zone(&mut self, dead_zone: f32) {
+        let dead_zone = dead_zone.clamp(0.0, 0.99);
+        for response in [
+            &mut self.left_stick,
+            &mut self.right_stick,
+            &mut self.left_trigger,
+            &mut self.right_trigger,
+        ] {
+            response.dead_zone = dead_zone;
+            response.saturation = response.saturation.max(dead_zone + 0.01).min(1.0);
+        }
+    }
+}
+
+/// Stick shaping described by inner and outer dead zones, converted into
+/// the [`AxisResponse`] of both sticks in a [`GamepadAxisResponses`].
+///
+/// Quadratic response is [`ResponseCurve::Squared`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GamepadAxisConfig {
+    /// Stick deflection below which the output is zero.
+    pub dead_zone: f32,
+    /// Stick deflection at and beyond which the output is `1.0`.
+    pub outer_dead_zone: f32,
+    /// Curve applied to the deflection rescaled between the two zones.
+    pub response_curve: ResponseCurve,
+}
+
+impl Default for GamepadAxisConfig {
+    fn default() -> Self {
+        Self {
+            dead_zone: 0.15,
+            outer_dead_zone: 0.95,
+            response_curve: ResponseCurve::Linear,
+        }
+    }
+}
+
+impl From<&GamepadAxisConfig> for AxisResponse {
+    fn from(config: &GamepadAxisConfig) -> Self {
+        AxisResponse::new(config.dead_zone, config.response_curve.clone())
+            .with_saturation(config.outer_dead_zone)
+    }
+}
+
+impl From<&GamepadAxisConfig> for GamepadAxisResponses {
+    fn from(config: &GamepadAxisConfig) -> Self {
+        Self {
+            left_stick: config.into(),
+            right_stick: config.into(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Axis values for a single gamepad.
+#[derive(Debug, Clone, Default)]
+pub struct GamepadAxes {
+    /// Left stick. x: left(-1)..right(+1), y: down(-1)..up(+1).
+    pub left_stick: Vec2,
+    /// Right stick.
+    pub right_stick: Vec2,
+    /// Left trigger 0.0..1.0.
+    pub left_trigger: f32,
+    /// Right trigger 0.0..1.0.
+    pub right_trigger: f32,
+    /// Left stick before deadzone filtering.
+    pub raw_left_stick: Vec2,
+    /// Right stick before deadzone filtering.
+    pub raw_right_stick: Vec2,
+    /// Left trigger before shaping.
+    pub raw_left_trigger: f32,
+    /// Right trigger before shaping.
+    pub raw_right_trigger: f32,
+    /// Responses last applied by [`apply_responses`](Self::apply_responses).
+    responses: GamepadAxisResponses,
+}
+
+impl GamepadAxes {
+    /// Axes at rest whose sticks are shaped by `config`, as if a manager
+    /// had applied [`GamepadAxisResponses::from`] it.
+    pub fn with_config(config: GamepadAxisConfig) -> Self {
+        let mut axes = Self::default();
+        axes.apply_responses(&GamepadAxisResponses::from(&config));
+        axes
+    }
+
+    /// The current raw left stick shaped by the last applied responses.
+    ///
+    /// Matches [`left_stick`](Self::left_stick) once the raw value has gone
+    /// through [`apply_responses`](Self::apply_responses).
+    pub fn processed_left_stick(&self) -> Vec2 {
+        self.responses.left_stick.apply_stick(self.raw_left_stick)
+    }
+
+    /// Recompute the shaped values from the raw ones, and keep `responses`
+    /// for [`processed_left_stick`](Self::processed_left_stick).
+    pub fn apply_responses(&mut self, responses: &GamepadAxisResponses) {
+        if self.responses != *responses {
+            self.responses.clone_from(responses);
+        }
+        self.left_stick = responses.left_stick.apply_stick(self.raw_left_stick);
+        self.right_stick = responses.right_stick.apply_stick(self.raw_right_stick);
+        self.left_trigger = responses.left_trigger.apply_trigger(self.raw_left_trigger);
+        self.right_trigger = responses
+            .right_trigger
+            .apply_trigger(self.raw_right_trigger);
+    }
+
+    /// Store a raw gilrs axis value. Returns `false` for unmapped axes.
+    pub(crate) fn set_raw(&mut self, axis: Axis, value: f32) -> bool {
+        match axis {
+            Axis::LeftStickX => self.raw_left_stick.x = value,
+            Axis::LeftStickY => self.raw_left_stick.y = value,
+            Axis::RightStickX => self.raw_right_stick.x = value,
+            Axis::RightStickY => self.raw_right_stick.y = value,
+            Axis::LeftZ => self.raw_left_trigger = value,
+            Axis::RightZ => self.raw_right_trigger = value,
+            _ => return false,
+        }
+        true
+    }
+}
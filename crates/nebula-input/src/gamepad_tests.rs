@@ -0,0 +1,231 @@
+//! Unit tests for gamepad state, axis shaping, and rumble.
+
+use super::*;
+use crate::axis_response::{AxisResponse, ResponseCurve};
+use crate::gamepad_axes::{GamepadAxes, GamepadAxisConfig};
+
+#[test]
+fn test_gamepad_connection_detected() {
+    let mut mgr = MockGamepadManager::new();
+    let id = mgr.connect("Xbox Controller");
+    assert_eq!(mgr.connected_count(), 1);
+    assert!(mgr.gamepad(id).unwrap().connected());
+}
+
+#[test]
+fn test_axis_values_in_range() {
+    let mut mgr = MockGamepadManager::new();
+    mgr.set_deadzone(0.0); // disable deadzone for this test
+    let id = mgr.connect("Pad");
+
+    for &val in &[-1.0_f32, 0.0, 1.0] {
+        mgr.set_axis(id, "left_stick_x", val);
+        mgr.set_axis(id, "left_stick_y", val);
+        let stick = mgr.gamepad(id).unwrap().left_stick();
+        assert!((-1.0..=1.0).contains(&stick.x));
+        assert!((-1.0..=1.0).contains(&stick.y));
+    }
+}
+
+#[test]
+fn test_deadzone_filters_small_values() {
+    let mut mgr = MockGamepadManager::new();
+    mgr.set_deadzone(0.15);
+    let id = mgr.connect("Pad");
+    mgr.set_axis(id, "left_stick_x", 0.10);
+    assert_eq!(mgr.gamepad(id).unwrap().left_stick().x, 0.0);
+}
+
+#[test]
+fn test_deadzone_rescales_above_threshold() {
+    let mut mgr = MockGamepadManager::new();
+    mgr.set_deadzone(0.15);
+    let id = mgr.connect("Pad");
+    mgr.set_axis(id, "left_stick_x", 0.575);
+    let rescaled = mgr.gamepad(id).unwrap().left_stick().x;
+    // (0.575 - 0.15) / (1.0 - 0.15) = 0.425 / 0.85 = 0.5
+    assert!((rescaled - 0.5).abs() < 0.01, "got {rescaled}");
+}
+
+#[test]
+fn test_button_state_tracked() {
+    let mut mgr = MockGamepadManager::new();
+    let id = mgr.connect("Pad");
+
+    mgr.press_button(id, UnifiedButton::South);
+    let gs = mgr.gamepad(id).unwrap();
+    assert!(gs.is_button_pressed(UnifiedButton::South));
+    assert!(gs.just_button_pressed(UnifiedButton::South));
+
+    mgr.clear_frame();
+    mgr.release_button(id, UnifiedButton::South);
+    let gs = mgr.gamepad(id).unwrap();
+    assert!(!gs.is_button_pressed(UnifiedButton::South));
+    assert!(gs.just_button_released(UnifiedButton::South));
+}
+
+#[test]
+fn test_disconnection_handled_gracefully() {
+    let mut mgr = MockGamepadManager::new();
+    let id = mgr.connect("Pad");
+    mgr.disconnect(id);
+    assert!(!mgr.gamepad(id).unwrap().connected());
+    assert_eq!(mgr.connected_count(), 0);
+}
+
+#[test]
+fn test_multiple_gamepads_supported() {
+    let mut mgr = MockGamepadManager::new();
+    let _id1 = mgr.connect("Pad 1");
+    let _id2 = mgr.connect("Pad 2");
+    assert_eq!(mgr.connected_count(), 2);
+}
+
+#[test]
+fn test_custom_deadzone() {
+    let mut mgr = MockGamepadManager::new();
+    mgr.set_deadzone(0.25);
+    let id = mgr.connect("Pad");
+
+    mgr.set_axis(id, "left_stick_x", 0.20);
+    assert_eq!(mgr.gamepad(id).unwrap().left_stick().x, 0.0);
+
+    mgr.set_axis(id, "left_stick_x", 0.30);
+    assert!(mgr.gamepad(id).unwrap().left_stick().x > 0.0);
+}
+
+#[test]
+fn test_per_axis_expo_curve_reaches_state() {
+    let mut mgr = MockGamepadManager::new();
+    mgr.responses.right_stick = AxisResponse::new(0.0, ResponseCurve::Expo(2.0));
+    let id = mgr.connect("Pad");
+    mgr.set_axis(id, "right_stick_x", 0.5);
+    mgr.set_axis(id, "left_stick_x", 0.5);
+    let gs = mgr.gamepad(id).unwrap();
+    assert!((gs.right_stick().x - 0.25).abs() < 1e-6);
+    // The left stick keeps its default linear response.
+    assert!(gs.left_stick().x > 0.25);
+}
+
+#[test]
+fn test_per_axis_deadzone_and_saturation() {
+    let mut mgr = MockGamepadManager::new();
+    mgr.responses.left_trigger = AxisResponse::new(0.1, ResponseCurve::Linear).with_saturation(0.9);
+    let id = mgr.connect("Pad");
+
+    mgr.set_axis(id, "left_trigger", 0.05);
+    assert_eq!(mgr.gamepad(id).unwrap().left_trigger(), 0.0);
+    mgr.set_axis(id, "left_trigger", 0.5);
+    assert!((mgr.gamepad(id).unwrap().left_trigger() - 0.5).abs() < 1e-6);
+    mgr.set_axis(id, "left_trigger", 0.9);
+    assert!((mgr.gamepad(id).unwrap().left_trigger() - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_trigger_binding_with_response_shapes_raw_value_once() {
+    use crate::action_map::{Action, ActionResolver, ActionState, GamepadAxisBinding};
+    use crate::action_map::{InputBinding, InputMap};
+    use crate::{KeyboardState, MouseState};
+
+    let mut mgr = MockGamepadManager::new();
+    mgr.set_deadzone(0.2);
+    let id = mgr.connect("Pad");
+    mgr.set_axis(id, "right_trigger", 0.6);
+
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::PrimaryAction,
+        vec![InputBinding::GamepadAxisWithResponse {
+            axis: GamepadAxisBinding::RightTrigger,
+            response: AxisResponse::new(0.2, ResponseCurve::Linear),
+        }],
+    );
+    let mut state = ActionState::new();
+    ActionResolver::resolve(
+        &map,
+        &KeyboardState::new(),
+        &MouseState::new(),
+        mgr.gamepad(id),
+        None,
+        &mut state,
+    );
+    // (0.6 - 0.2) / 0.8, not the manager-shaped 0.5 shaped again.
+    assert!((state.action_value(Action::PrimaryAction) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_rumble_on_unsupported_pad_is_noop() {
+    let mut mgr = MockGamepadManager::new();
+    let id = mgr.connect("Pad");
+    assert!(mgr.rumble(id, 1.0, 0.5, 200).is_ok());
+    assert!(mgr.rumbles.is_empty());
+    assert!(mgr.rumble(99, 1.0, 0.5, 200).is_ok());
+}
+
+#[test]
+fn test_rumble_on_supported_pad_scales_magnitudes() {
+    let mut mgr = MockGamepadManager::new();
+    let id = mgr.connect("Pad");
+    mgr.ff_supported.insert(id);
+    mgr.rumble(id, 1.0, 0.5, 200).unwrap();
+    assert_eq!(mgr.rumbles[&id], (u16::MAX, 32768, 200));
+    mgr.rumble(id, 0.0, 0.0, 200).unwrap();
+    assert!(mgr.rumbles.is_empty());
+}
+
+#[test]
+fn test_rumble_magnitudes_clamp_and_skip_silence() {
+    assert_eq!(rumble_magnitudes(2.0, -1.0, 100), Some((u16::MAX, 0)));
+    assert_eq!(rumble_magnitudes(0.0, 0.0, 100), None);
+    assert_eq!(rumble_magnitudes(1.0, 1.0, 0), None);
+}
+
+#[test]
+fn test_axis_config_dead_zone_zeroes_small_deflection() {
+    let mut axes = GamepadAxes::with_config(GamepadAxisConfig::default());
+    axes.raw_left_stick = Vec2::new(0.1, 0.0);
+    assert_eq!(axes.processed_left_stick(), Vec2::ZERO);
+}
+
+#[test]
+fn test_axis_config_full_deflection_reaches_one() {
+    let mut axes = GamepadAxes::with_config(GamepadAxisConfig::default());
+    axes.raw_left_stick = Vec2::new(1.0, 0.0);
+    assert!((axes.processed_left_stick().x - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_axis_config_cubic_slope_crosses_linear() {
+    let slope_at = |response_curve: ResponseCurve, x: f32| {
+        let mut axes = GamepadAxes::with_config(GamepadAxisConfig {
+            dead_zone: 0.0,
+            outer_dead_zone: 1.0,
+            response_curve,
+        });
+        let h = 1e-3;
+        axes.raw_left_stick = Vec2::new(x + h, 0.0);
+        let hi = axes.processed_left_stick().x;
+        axes.raw_left_stick = Vec2::new(x - h, 0.0);
+        let lo = axes.processed_left_stick().x;
+        (hi - lo) / (2.0 * h)
+    };
+    // d/dx x³ = 3x²: 0.75 at 0.5, shallower than linear, and only steeper
+    // past 1/√3.
+    assert!((slope_at(ResponseCurve::Linear, 0.5) - 1.0).abs() < 1e-2);
+    assert!((slope_at(ResponseCurve::Cubic, 0.5) - 0.75).abs() < 1e-2);
+    assert!(slope_at(ResponseCurve::Cubic, 0.9) > slope_at(ResponseCurve::Linear, 0.9));
+}
+
+#[test]
+fn test_processed_left_stick_uses_manager_responses() {
+    let mut mgr = MockGamepadManager::new();
+    mgr.responses = GamepadAxisResponses::from(&GamepadAxisConfig {
+        response_curve: ResponseCurve::Cubic,
+        ..GamepadAxisConfig::default()
+    });
+    let id = mgr.connect("Pad");
+    mgr.set_axis(id, "left_stick_x", 0.6);
+    let axes = &mgr.gamepad(id).unwrap().axes;
+    assert!(axes.processed_left_stick().x > 0.0);
+    assert_eq!(axes.processed_left_stick(), axes.left_stick);
+}
@@ -9,7 +9,10 @@ use crate::action_map::{ActionResolver, ActionState, InputMap};
 use crate::gamepad::GamepadState;
 use crate::keyboard::KeyboardState;
 use crate::mouse::MouseState;
+use crate::touch::TouchState;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Instant;
 
 /// Whether the cursor is captured (FPS-style) or free (menu-style).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -83,40 +86,38 @@ impl InputContextStack {
 
     /// Resolve actions respecting the context stack.
     ///
-    /// If the top context has `consumes_input: true`, only its bindings are evaluated.
-    /// Otherwise, contexts are evaluated top-to-bottom until a consuming context is hit.
-    /// When the active context has `text_input: true`, keyboard bindings are skipped
+    /// Contexts are evaluated top-to-bottom. An action that a context binds
+    /// and activates is handled there and is not evaluated by the contexts
+    /// below; unhandled actions fall through. Evaluation stops after the
+    /// first context with `consumes_input: true`, so a non-consuming overlay
+    /// (e.g., a HUD) handles its own bindings and passes everything else on.
+    /// When a context has `text_input: true`, its keyboard bindings are skipped
     /// (only mouse/gamepad bindings are resolved).
     pub fn resolve(
         &self,
         keyboard: &KeyboardState,
         mouse: &MouseState,
         gamepad: Option<&GamepadState>,
+        touch: Option<&TouchState>,
         state: &mut ActionState,
     ) {
         // Shift previous values.
         state.begin_frame();
+        state.set_instant(Instant::now());
 
+        let mut handled = HashSet::new();
         for ctx in self.stack.iter().rev() {
-            if ctx.text_input {
-                // In text-input mode, skip keyboard-based action resolution entirely.
-                // Only resolve mouse/gamepad bindings from this context.
-                ActionResolver::resolve_partial(
-                    &ctx.input_map,
-                    None, // no keyboard
-                    mouse,
-                    gamepad,
-                    state,
-                );
-            } else {
-                ActionResolver::resolve_partial(
-                    &ctx.input_map,
-                    Some(keyboard),
-                    mouse,
-                    gamepad,
-                    state,
-                );
-            }
+            // In text-input mode, skip keyboard-based action resolution entirely.
+            let keyboard = (!ctx.text_input).then_some(keyboard);
+            ActionResolver::resolve_layer(
+                &ctx.input_map,
+                keyboard,
+                mouse,
+                gamepad,
+                touch,
+                state,
+                &mut handled,
+            );
 
             if ctx.consumes_input {
                 break;
@@ -161,8 +162,8 @@ impl TextInputBuffer {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::action_map::{Action, InputBinding};
-    use winit::event::ElementState;
+    use crate::action_map::{Action, InputBinding, MouseAxisBinding};
+    use winit::event::{ElementState, MouseScrollDelta};
     use winit::keyboard::{KeyCode, PhysicalKey};
 
     /// Helper: create a gameplay context with default FPS bindings and captured cursor.
@@ -257,7 +258,7 @@ mod tests {
         press_key(&mut kb, KeyCode::KeyW);
         let mouse = MouseState::new();
         let mut state = ActionState::new();
-        stack.resolve(&kb, &mouse, None, &mut state);
+        stack.resolve(&kb, &mouse, None, None, &mut state);
         assert!(
             !state.is_action_active(Action::MoveForward),
             "Keyboard action should be bypassed in text_input context"
@@ -295,7 +296,7 @@ mod tests {
         press_key(&mut kb, KeyCode::KeyW);
         let mouse = MouseState::new();
         let mut state = ActionState::new();
-        stack.resolve(&kb, &mouse, None, &mut state);
+        stack.resolve(&kb, &mouse, None, None, &mut state);
         assert!(
             !state.is_action_active(Action::MoveForward),
             "Gameplay action should be blocked by consuming menu context"
@@ -303,7 +304,7 @@ mod tests {
 
         // But Escape (bound in menu) should work.
         press_key(&mut kb, KeyCode::Escape);
-        stack.resolve(&kb, &mouse, None, &mut state);
+        stack.resolve(&kb, &mouse, None, None, &mut state);
         assert!(
             state.is_action_active(Action::Pause),
             "Menu's Pause action should be active"
@@ -320,7 +321,7 @@ mod tests {
         press_key(&mut kb, KeyCode::KeyW);
         let mouse = MouseState::new();
         let mut state = ActionState::new();
-        stack.resolve(&kb, &mouse, None, &mut state);
+        stack.resolve(&kb, &mouse, None, None, &mut state);
         assert!(
             state.is_action_active(Action::MoveForward),
             "Gameplay actions should pass through non-consuming overlay"
@@ -328,13 +329,61 @@ mod tests {
 
         // Press F — should activate Interact from overlay.
         press_key(&mut kb, KeyCode::KeyF);
-        stack.resolve(&kb, &mouse, None, &mut state);
+        stack.resolve(&kb, &mouse, None, None, &mut state);
         assert!(
             state.is_action_active(Action::Interact),
             "Overlay's Interact action should be active"
         );
     }
 
+    #[test]
+    fn test_overlay_passes_unbound_actions_to_gameplay() {
+        // HUD overlay binds only Interact; movement and jumping are gameplay's.
+        let mut stack = InputContextStack::new(gameplay_context());
+        stack.push_context(debug_overlay_context());
+
+        let mut kb = KeyboardState::new();
+        press_key(&mut kb, KeyCode::KeyD);
+        press_key(&mut kb, KeyCode::Space);
+        let mouse = MouseState::new();
+        let mut state = ActionState::new();
+        stack.resolve(&kb, &mouse, None, None, &mut state);
+        assert!(state.is_action_active(Action::MoveRight));
+        assert!(state.is_action_active(Action::Jump));
+        assert!(!state.is_action_active(Action::Interact));
+    }
+
+    #[test]
+    fn test_action_handled_by_overlay_is_not_resolved_below() {
+        let mut map = InputMap::new();
+        map.set_bindings(
+            Action::MoveRight,
+            vec![InputBinding::MouseAxis(MouseAxisBinding::Scroll)],
+        );
+        let mut stack = InputContextStack::new(gameplay_context());
+        stack.push_context(InputContext {
+            name: "hud",
+            input_map: map,
+            cursor_mode: CursorMode::Captured,
+            consumes_input: false,
+            text_input: false,
+        });
+
+        let mut kb = KeyboardState::new();
+        press_key(&mut kb, KeyCode::KeyD);
+        let mut mouse = MouseState::new();
+        let mut state = ActionState::new();
+
+        // Overlay idle: gameplay's D binding drives MoveRight.
+        stack.resolve(&kb, &mouse, None, None, &mut state);
+        assert_eq!(state.action_value(Action::MoveRight), 1.0);
+
+        // Overlay active: its value wins instead of summing with gameplay's.
+        mouse.on_scroll(MouseScrollDelta::LineDelta(0.0, -0.5));
+        stack.resolve(&kb, &mouse, None, None, &mut state);
+        assert_eq!(state.action_value(Action::MoveRight), -0.5);
+    }
+
     #[test]
     fn test_pop_on_single_context_is_noop() {
         let mut stack = InputContextStack::new(gameplay_context());
@@ -0,0 +1,83 @@
+//! [`Conflict`] detection: finds bindings shared by more than one action.
+
+use crate::action_map::{Action, InputBinding, InputMap};
+use crate::activation::ActivationMode;
+use std::collections::HashMap;
+
+/// A binding conflict: the same [`InputBinding`] is used by multiple actions.
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    /// The duplicated binding.
+    pub binding: InputBinding,
+    /// Actions that share this binding.
+    pub actions: Vec<Action>,
+}
+
+/// Normalize a binding so equivalent triggers compare equal: a key or mouse
+/// button with no modifiers is the same as the plain binding, a
+/// [`ActivationMode::Press`] wrapper is the same as the wrapped binding, and
+/// a chord is an unordered set of its (flattened) members.
+fn canonical(binding: &InputBinding) -> InputBinding {
+    match binding {
+        InputBinding::WithActivation {
+            binding,
+            mode: ActivationMode::Press,
+        } => canonical(binding),
+        InputBinding::WithActivation { binding, mode } => canonical(binding).with_activation(*mode),
+        InputBinding::Chord(members) => {
+            let mut members: Vec<InputBinding> = members
+                .iter()
+                .map(canonical)
+                .flat_map(|member| match member {
+                    InputBinding::Chord(inner) => inner,
+                    other => vec![other],
+                })
+                .collect();
+            members.sort_by_cached_key(|member| format!("{member:?}"));
+            members.dedup();
+            match members.as_slice() {
+                [only] => only.clone(),
+                _ => InputBinding::Chord(members),
+            }
+        }
+        InputBinding::KeyWithModifiers { key, modifiers } if modifiers.is_empty() => {
+            InputBinding::Key(*key)
+        }
+        InputBinding::MouseButtonWithModifiers { button, modifiers } if modifiers.is_empty() => {
+            InputBinding::MouseButton(*button)
+        }
+        other => other.clone(),
+    }
+}
+
+impl InputMap {
+    /// Detect all binding conflicts (same binding in multiple actions, or
+    /// duplicates within a single action).
+    ///
+    /// An [`InputMap`] belongs to one [`InputContext`](crate::InputContext),
+    /// so this reports conflicts within that context only. Bindings are
+    /// compared with their modifiers: `Ctrl+S` and `S` do not conflict, while
+    /// `S` and `S` with [`Modifiers::NONE`] do. Chords compare as sets, so a
+    /// `Ctrl + S` chord conflicts with `S + Ctrl` but not with `S` alone, and
+    /// bindings with different [`ActivationMode`]s (tap vs. hold) never
+    /// conflict.
+    #[must_use]
+    pub fn detect_conflicts(&self) -> Vec<Conflict> {
+        let mut seen: HashMap<InputBinding, Vec<Action>> = HashMap::new();
+
+        for (action, bindings) in &self.bindings {
+            for binding in bindings {
+                seen.entry(canonical(binding)).or_default().push(*action);
+            }
+        }
+
+        seen.into_iter()
+            .filter(|(_, actions)| actions.len() > 1)
+            .map(|(binding, actions)| Conflict { binding, actions })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[path = "conflict_tests.rs"]
+mod tests;
@@ -0,0 +1,140 @@
+//! Unit tests for binding conflict detection.
+
+use super::*;
+use crate::keybindings::Modifiers;
+use winit::keyboard::KeyCode;
+
+#[test]
+fn test_conflict_detection_flags_duplicates() {
+    let mut map = InputMap::new();
+    map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::Space)]);
+    map.set_bindings(Action::Sprint, vec![InputBinding::Key(KeyCode::Space)]);
+    let conflicts = map.detect_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].binding, InputBinding::Key(KeyCode::Space));
+    assert!(conflicts[0].actions.contains(&Action::Jump));
+    assert!(conflicts[0].actions.contains(&Action::Sprint));
+}
+
+#[test]
+fn test_plain_key_conflicts_with_explicit_no_modifiers() {
+    let mut map = InputMap::new();
+    map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::Space)]);
+    map.set_bindings(
+        Action::Crouch,
+        vec![InputBinding::KeyWithModifiers {
+            key: KeyCode::Space,
+            modifiers: Modifiers::NONE,
+        }],
+    );
+    let conflicts = map.detect_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].binding, InputBinding::Key(KeyCode::Space));
+}
+
+#[test]
+fn test_same_key_with_different_modifiers_is_not_a_conflict() {
+    let mut map = InputMap::new();
+    map.set_bindings(Action::Interact, vec![InputBinding::Key(KeyCode::KeyS)]);
+    map.set_bindings(
+        Action::OpenInventory,
+        vec![InputBinding::KeyWithModifiers {
+            key: KeyCode::KeyS,
+            modifiers: Modifiers::CTRL,
+        }],
+    );
+    map.set_bindings(
+        Action::Pause,
+        vec![InputBinding::KeyWithModifiers {
+            key: KeyCode::KeyS,
+            modifiers: Modifiers::CTRL | Modifiers::SHIFT,
+        }],
+    );
+    assert!(map.detect_conflicts().is_empty());
+}
+
+fn chord(keys: &[KeyCode]) -> InputBinding {
+    InputBinding::Chord(keys.iter().map(|k| InputBinding::Key(*k)).collect())
+}
+
+#[test]
+fn test_chord_does_not_conflict_with_its_members() {
+    let mut map = InputMap::new();
+    map.set_bindings(Action::MoveBack, vec![InputBinding::Key(KeyCode::KeyS)]);
+    map.set_bindings(
+        Action::Pause,
+        vec![chord(&[KeyCode::ControlLeft, KeyCode::KeyS])],
+    );
+    assert!(map.detect_conflicts().is_empty());
+}
+
+#[test]
+fn test_chords_conflict_regardless_of_member_order() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::Pause,
+        vec![chord(&[KeyCode::ControlLeft, KeyCode::KeyS])],
+    );
+    map.set_bindings(
+        Action::OpenInventory,
+        vec![chord(&[KeyCode::KeyS, KeyCode::ControlLeft])],
+    );
+    let conflicts = map.detect_conflicts();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].actions.len(), 2);
+}
+
+#[test]
+fn test_single_key_chord_conflicts_with_key_with_modifiers() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::Pause,
+        vec![InputBinding::key_chord(
+            &[KeyCode::KeyS, KeyCode::KeyS],
+            Modifiers::CTRL,
+        )],
+    );
+    map.set_bindings(
+        Action::Interact,
+        vec![InputBinding::KeyWithModifiers {
+            key: KeyCode::KeyS,
+            modifiers: Modifiers::CTRL,
+        }],
+    );
+    assert_eq!(map.detect_conflicts().len(), 1);
+}
+
+#[test]
+fn test_tap_and_hold_on_same_key_do_not_conflict() {
+    let mut map = InputMap::new();
+    let f = || InputBinding::Key(KeyCode::KeyF);
+    map.set_bindings(
+        Action::Interact,
+        vec![f().with_activation(ActivationMode::Hold(300))],
+    );
+    map.set_bindings(
+        Action::PrimaryAction,
+        vec![f().with_activation(ActivationMode::Tap(200))],
+    );
+    assert!(map.detect_conflicts().is_empty());
+
+    // A press-mode wrapper is just the key.
+    map.set_bindings(
+        Action::Jump,
+        vec![f().with_activation(ActivationMode::Press)],
+    );
+    map.set_bindings(Action::Sprint, vec![f()]);
+    assert_eq!(map.detect_conflicts().len(), 1);
+}
+
+#[test]
+fn test_no_conflicts_on_clean_map() {
+    let _default_map = InputMap::default();
+    // Use a known-clean map (default FPS map shares gamepad axes across actions).
+    let mut clean_map = InputMap::new();
+    clean_map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::Space)]);
+    clean_map.set_bindings(Action::Sprint, vec![InputBinding::Key(KeyCode::ShiftLeft)]);
+    clean_map.set_bindings(Action::Interact, vec![InputBinding::Key(KeyCode::KeyE)]);
+    let conflicts = clean_map.detect_conflicts();
+    assert!(conflicts.is_empty());
+}
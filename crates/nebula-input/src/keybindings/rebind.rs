@@ -0,0 +1,106 @@
+//! [`RebindState`]: the listen, stage and commit flow of a settings screen.
+
+use super::Conflict;
+use crate::action_map::{Action, InputBinding, InputMap};
+
+/// State machine for the rebind-listen flow.
+///
+/// A settings screen calls [`start_rebind`](Self::start_rebind), feeds the
+/// next input to [`stage`](Self::stage), then [`commit`](Self::commit)s it.
+/// [`capture`](Self::capture) applies immediately without the conflict check.
+#[derive(Debug, Clone, Default)]
+pub enum RebindState {
+    /// Not rebinding.
+    #[default]
+    Idle,
+    /// Listening for the next input to bind to `action`.
+    Listening { action: Action },
+    /// A binding was captured for `action` and awaits [`commit`](Self::commit).
+    Pending {
+        action: Action,
+        binding: InputBinding,
+    },
+}
+
+impl RebindState {
+    /// Begin listening for a new binding for `action`.
+    pub fn start_rebind(&mut self, action: Action) {
+        *self = Self::Listening { action };
+    }
+
+    /// Returns the action being rebound, if in listening mode.
+    #[must_use]
+    pub fn listening_action(&self) -> Option<Action> {
+        match self {
+            Self::Listening { action } => Some(*action),
+            Self::Idle | Self::Pending { .. } => None,
+        }
+    }
+
+    /// Record `binding` as the pending rebind. Returns `false` (and does
+    /// nothing) unless listening.
+    pub fn stage(&mut self, binding: InputBinding) -> bool {
+        let Some(action) = self.listening_action() else {
+            return false;
+        };
+        *self = Self::Pending { action, binding };
+        true
+    }
+
+    /// Apply the pending rebind if it introduces no conflict.
+    ///
+    /// Returns `None` if nothing is pending. On success the binding replaces
+    /// the action's bindings and the state returns to `Idle`. On conflict the
+    /// map is left untouched, the state stays `Pending`, and the conflicts
+    /// involving the rebound action are returned for the UI to resolve.
+    pub fn commit(&mut self, input_map: &mut InputMap) -> Option<Result<Action, Vec<Conflict>>> {
+        let Self::Pending { action, binding } = self else {
+            return None;
+        };
+        let action = *action;
+
+        let mut candidate = input_map.clone();
+        candidate.set_bindings(action, vec![binding.clone()]);
+        let conflicts: Vec<Conflict> = candidate
+            .detect_conflicts()
+            .into_iter()
+            .filter(|c| c.actions.contains(&action))
+            .collect();
+        if !conflicts.is_empty() {
+            return Some(Err(conflicts));
+        }
+
+        *input_map = candidate;
+        *self = Self::Idle;
+        Some(Ok(action))
+    }
+
+    /// Abandon any rebind in progress.
+    pub fn cancel(&mut self) {
+        *self = Self::Idle;
+    }
+
+    /// Capture a binding. Returns `Some((action, conflicts))` if a binding was
+    /// captured, allowing the caller to decide whether to accept or reject.
+    /// Resets to `Idle` regardless.
+    pub fn capture(
+        &mut self,
+        binding: InputBinding,
+        input_map: &mut InputMap,
+    ) -> Option<(Action, Vec<Conflict>)> {
+        let action = self.listening_action()?;
+
+        // Apply the binding.
+        input_map.set_bindings(action, vec![binding]);
+
+        // Detect conflicts after applying.
+        let conflicts = input_map.detect_conflicts();
+
+        *self = Self::Idle;
+        Some((action, conflicts))
+    }
+}
+
+#[cfg(test)]
+#[path = "rebind_tests.rs"]
+mod tests;
@@ -0,0 +1,47 @@
+//! Unit tests for the rebind commit flow.
+
+use super::*;
+use winit::keyboard::KeyCode;
+
+#[test]
+fn test_commit_applies_conflict_free_rebind() {
+    let mut map = InputMap::new();
+    map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::Space)]);
+
+    let mut rebind = RebindState::default();
+    assert!(rebind.commit(&mut map).is_none(), "nothing pending");
+    rebind.start_rebind(Action::Jump);
+    assert!(rebind.stage(InputBinding::Key(KeyCode::KeyJ)));
+
+    assert_eq!(rebind.commit(&mut map).unwrap().unwrap(), Action::Jump);
+    assert_eq!(
+        map.get_bindings(&Action::Jump),
+        &[InputBinding::Key(KeyCode::KeyJ)]
+    );
+    assert!(matches!(rebind, RebindState::Idle));
+}
+
+#[test]
+fn test_commit_rejects_conflicting_rebind() {
+    let mut map = InputMap::new();
+    map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::Space)]);
+    map.set_bindings(Action::Crouch, vec![InputBinding::Key(KeyCode::KeyC)]);
+
+    let mut rebind = RebindState::default();
+    rebind.start_rebind(Action::Crouch);
+    rebind.stage(InputBinding::Key(KeyCode::Space));
+
+    let conflicts = rebind.commit(&mut map).unwrap().unwrap_err();
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].binding, InputBinding::Key(KeyCode::Space));
+    assert!(conflicts[0].actions.contains(&Action::Jump));
+    // Map untouched; rebind still pending for the UI to resolve.
+    assert_eq!(
+        map.get_bindings(&Action::Crouch),
+        &[InputBinding::Key(KeyCode::KeyC)]
+    );
+    assert!(matches!(rebind, RebindState::Pending { .. }));
+
+    rebind.cancel();
+    assert!(rebind.commit(&mut map).is_none());
+}
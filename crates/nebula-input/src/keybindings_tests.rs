@@ -0,0 +1,136 @@
+//! Unit tests for keybinding serialization, modifiers and persistence.
+
+use super::*;
+use crate::action_map::{Action, ActionResolver, ActionState, InputBinding};
+use crate::mouse::MouseState;
+use winit::event::ElementState as WinitElementState;
+use winit::keyboard::PhysicalKey;
+
+fn press_key(kb: &mut KeyboardState, code: KeyCode) {
+    kb.process_raw(crate::keyboard::RawKeyEvent {
+        key: PhysicalKey::Code(code),
+        state: WinitElementState::Pressed,
+        repeat: false,
+    });
+}
+
+#[test]
+fn test_default_bindings_serialize_to_ron() {
+    let original = InputMap::default();
+    let ron_str = original.to_ron().expect("serialize");
+    let restored = InputMap::from_ron(&ron_str).expect("deserialize");
+    // Every action in original should be present with same binding count.
+    for (action, bindings) in &original.bindings {
+        let restored_bindings = restored.get_bindings(action);
+        assert_eq!(
+            bindings.len(),
+            restored_bindings.len(),
+            "action {action:?} binding count mismatch"
+        );
+    }
+}
+
+#[test]
+fn test_custom_bindings_deserialize_correctly() {
+    let mut map = InputMap::new();
+    map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::KeyJ)]);
+    let ron_str = map.to_ron().expect("serialize");
+    let restored = InputMap::from_ron(&ron_str).expect("deserialize");
+    let bindings = restored.get_bindings(&Action::Jump);
+    assert_eq!(bindings.len(), 1);
+    assert_eq!(bindings[0], InputBinding::Key(KeyCode::KeyJ));
+}
+
+#[test]
+fn test_modifier_combinations_work() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::OpenInventory,
+        vec![InputBinding::KeyWithModifiers {
+            key: KeyCode::KeyI,
+            modifiers: Modifiers::CTRL,
+        }],
+    );
+
+    let mouse = MouseState::new();
+    let mut state = ActionState::new();
+
+    // Press I alone — should NOT activate.
+    let mut kb = KeyboardState::new();
+    press_key(&mut kb, KeyCode::KeyI);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(!state.is_action_active(Action::OpenInventory));
+
+    // Press Ctrl+I — should activate.
+    let mut kb2 = KeyboardState::new();
+    press_key(&mut kb2, KeyCode::ControlLeft);
+    press_key(&mut kb2, KeyCode::KeyI);
+    ActionResolver::resolve(&map, &kb2, &mouse, None, None, &mut state);
+    assert!(state.is_action_active(Action::OpenInventory));
+}
+
+#[test]
+fn test_modifier_subset_does_not_match() {
+    let mut map = InputMap::new();
+    map.set_bindings(
+        Action::OpenInventory,
+        vec![InputBinding::KeyWithModifiers {
+            key: KeyCode::KeyS,
+            modifiers: Modifiers::CTRL | Modifiers::SHIFT,
+        }],
+    );
+
+    let mouse = MouseState::new();
+    let mut state = ActionState::new();
+
+    // Press only Ctrl+S (missing Shift) — should NOT activate.
+    let mut kb = KeyboardState::new();
+    press_key(&mut kb, KeyCode::ControlLeft);
+    press_key(&mut kb, KeyCode::KeyS);
+    ActionResolver::resolve(&map, &kb, &mouse, None, None, &mut state);
+    assert!(!state.is_action_active(Action::OpenInventory));
+}
+
+#[test]
+fn test_rebinding_persists_across_save_load() {
+    let dir = std::env::temp_dir().join("nebula_keybind_test");
+    let path = dir.join("input.ron");
+
+    let mut map = InputMap::default();
+    map.set_bindings(Action::Jump, vec![InputBinding::Key(KeyCode::KeyK)]);
+    map.save(&path).expect("save");
+
+    let loaded = InputMap::load(&path).expect("load");
+    let bindings = loaded.get_bindings(&Action::Jump);
+    assert_eq!(bindings.len(), 1);
+    assert_eq!(bindings[0], InputBinding::Key(KeyCode::KeyK));
+
+    // Cleanup.
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_malformed_ron_falls_back_to_defaults() {
+    let dir = std::env::temp_dir().join("nebula_keybind_malformed");
+    let path = dir.join("input.ron");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(&path, "not valid ron {{{").unwrap();
+
+    assert!(matches!(
+        InputMap::load(&path),
+        Err(InputError::ParseError(_))
+    ));
+    let loaded = InputMap::load_or_default(&path);
+    // Should be the default map, not panic.
+    assert!(!loaded.bindings.is_empty());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_missing_file_falls_back_to_defaults() {
+    let path = std::path::PathBuf::from("/tmp/nebula_nonexistent_12345/input.ron");
+    assert!(matches!(InputMap::load(&path), Err(InputError::IoError(_))));
+    let loaded = InputMap::load_or_default(&path);
+    assert!(!loaded.bindings.is_empty());
+}
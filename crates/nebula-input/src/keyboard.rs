@@ -103,6 +103,22 @@ impl KeyboardState {
         self.just_released.contains(&key)
     }
 
+    /// Returns `true` while every key in `keys` is held down at once.
+    ///
+    /// An empty slice is never active.
+    #[must_use]
+    pub fn chord_active(&self, keys: &[KeyCode]) -> bool {
+        !keys.is_empty()
+            && keys
+                .iter()
+                .all(|&key| self.is_pressed(PhysicalKey::Code(key)))
+    }
+
+    /// Iterates over the keys that transitioned to pressed this frame.
+    pub fn iter_just_pressed(&self) -> impl Iterator<Item = PhysicalKey> + '_ {
+        self.just_pressed.iter().copied()
+    }
+
     /// Returns the currently active modifier keys as [`Modifiers`] bitflags.
     #[must_use]
     pub fn active_modifiers(&self) -> Modifiers {
@@ -235,4 +251,15 @@ mod tests {
         assert!(kb.just_pressed(pk));
         assert!(kb.is_pressed(pk));
     }
+
+    #[test]
+    fn test_chord_active_requires_every_key() {
+        let mut kb = KeyboardState::new();
+        let chord = [KeyCode::ControlLeft, KeyCode::KeyS];
+        kb.process_raw(raw(KeyCode::KeyS, ElementState::Pressed, false));
+        assert!(!kb.chord_active(&chord));
+        kb.process_raw(raw(KeyCode::ControlLeft, ElementState::Pressed, false));
+        assert!(kb.chord_active(&chord));
+        assert!(!kb.chord_active(&[]));
+    }
 }
@@ -1,18 +1,31 @@
 //! Input abstraction: keyboard, mouse, and gamepad mapped through configurable action-based keybindings.
 
 pub mod action_map;
+pub mod activation;
+pub mod axis_response;
 pub mod gamepad;
+pub mod gamepad_axes;
 pub mod input_context;
 pub mod keybindings;
 pub mod keyboard;
 pub mod mouse;
+pub mod replay;
+pub mod touch;
 
 pub use action_map::{
     Action, ActionResolver, ActionState, GamepadAxisBinding, InputBinding, InputMap,
     MouseAxisBinding, MouseButtonBinding,
 };
-pub use gamepad::{GamepadAxes, GamepadManager, GamepadState, UnifiedButton};
+pub use activation::ActivationMode;
+pub use axis_response::{AxisResponse, ResponseCurve, radial_deadzone};
+pub use gamepad::{GamepadManager, GamepadState, UnifiedButton};
+pub use gamepad_axes::{GamepadAxes, GamepadAxisConfig, GamepadAxisResponses};
 pub use input_context::{CursorMode, InputContext, InputContextStack, TextInputBuffer};
-pub use keybindings::{Conflict, Modifiers, RebindState};
+pub use keybindings::{Conflict, InputError, Modifiers, RebindState};
 pub use keyboard::{KeyboardState, RawKeyEvent};
 pub use mouse::MouseState;
+pub use replay::{
+    InputPlayer, InputRecorder, InputRecording, LookInput, REPLAY_FORMAT_VERSION, RecordedTick,
+    RecordingHeader, ReplayError,
+};
+pub use touch::{TouchAxisBinding, TouchRegion, TouchState, VirtualControl, VirtualControlLayout};
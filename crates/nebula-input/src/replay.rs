@@ -0,0 +1,249 @@
+//! Input recording and deterministic playback for replays and bug repros.
+//!
+//! [`InputRecorder`] captures, once per fixed simulation tick, every
+//! [`Action`] whose resolved value changed plus the [`LookInput`] that
+//! bypasses the action map: the raw mouse delta and the gamepad right stick.
+//! Recording happens *after* [`ActionResolver`](crate::ActionResolver), so
+//! dead zones, response curves, and analog stick noise are already baked
+//! into the stored values and playback reproduces them exactly.
+//!
+//! [`InputPlayer`] feeds the stream back into an [`ActionState`] keyed by
+//! the same tick counter, so playback does not depend on the frame rate as
+//! long as the caller also simulates each tick with the fixed tick length.
+//!
+//! The binary encoding lives in the `format` submodule.
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::action_map::{Action, ActionState};
+
+mod format;
+
+pub use format::REPLAY_FORMAT_VERSION;
+
+/// Every action, indexed by its on-disk code.
+const ACTIONS: [Action; 12] = [
+    Action::MoveForward,
+    Action::MoveBack,
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::Jump,
+    Action::Crouch,
+    Action::Sprint,
+    Action::PrimaryAction,
+    Action::SecondaryAction,
+    Action::Interact,
+    Action::OpenInventory,
+    Action::Pause,
+];
+
+/// Errors that can occur while loading an input recording.
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    /// The data does not start with the expected magic bytes.
+    #[error("invalid magic bytes")]
+    InvalidMagic,
+    /// The format version is not supported by this build.
+    #[error("unsupported replay format version: {0}")]
+    UnsupportedVersion(u8),
+    /// The data ended before the recording was complete.
+    #[error("replay data truncated at byte {0}")]
+    Truncated(usize),
+    /// An action code does not name a known [`Action`].
+    #[error("unknown action code: {0}")]
+    UnknownAction(u8),
+    /// The engine version string is not valid UTF-8.
+    #[error("engine version is not valid UTF-8")]
+    InvalidEngineVersion,
+    /// Reading or writing the recording file failed.
+    #[error("replay I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Metadata identifying the session a recording was made in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingHeader {
+    /// Engine version that produced the recording.
+    pub engine_version: String,
+    /// World seed from the config, needed to rebuild the same world.
+    pub config_seed: u64,
+    /// Fixed tick at which recording started.
+    pub start_tick: u64,
+}
+
+/// Input captured during one fixed tick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedTick {
+    /// Fixed simulation tick.
+    pub tick: u64,
+    /// Actions whose resolved value changed this tick, with the new value.
+    pub changes: Vec<(Action, f32)>,
+    /// Raw mouse delta accumulated during the tick.
+    pub mouse_delta: Vec2,
+    /// New right stick value, if it changed this tick.
+    pub right_stick: Option<Vec2>,
+}
+
+/// Look input that bypasses the action map, for one tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LookInput {
+    /// Raw mouse delta accumulated during the tick.
+    pub mouse_delta: Vec2,
+    /// Shaped gamepad right stick at the end of the tick.
+    pub right_stick: Vec2,
+}
+
+/// A complete input recording: header plus sparse per-tick input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputRecording {
+    /// Session metadata.
+    pub header: RecordingHeader,
+    /// Ticks with input changes or mouse motion, in tick order.
+    pub ticks: Vec<RecordedTick>,
+}
+
+/// Captures resolved action values and look input once per fixed tick.
+pub struct InputRecorder {
+    header: RecordingHeader,
+    /// Values as of the last recorded tick, indexed like [`ACTIONS`].
+    last: [f32; ACTIONS.len()],
+    /// Right stick as of the last recorded tick.
+    last_right_stick: Vec2,
+    ticks: Vec<RecordedTick>,
+}
+
+impl InputRecorder {
+    /// Starts a recording at `start_tick`.
+    pub fn new(engine_version: impl Into<String>, config_seed: u64, start_tick: u64) -> Self {
+        Self {
+            header: RecordingHeader {
+                engine_version: engine_version.into(),
+                config_seed,
+                start_tick,
+            },
+            last: [0.0; ACTIONS.len()],
+            last_right_stick: Vec2::ZERO,
+            ticks: Vec::new(),
+        }
+    }
+
+    /// Records the resolved `state` and the `look` input for `tick`.
+    ///
+    /// Call once per fixed tick, after actions have been resolved. Values
+    /// are compared bit-for-bit, so any change is kept.
+    pub fn record_tick(&mut self, tick: u64, state: &ActionState, look: LookInput) {
+        let mut changes = Vec::new();
+        for (last, &action) in self.last.iter_mut().zip(&ACTIONS) {
+            let value = state.action_value(action);
+            if value.to_bits() != last.to_bits() {
+                *last = value;
+                changes.push((action, value));
+            }
+        }
+        let right_stick = (look.right_stick != self.last_right_stick).then_some(look.right_stick);
+        if let Some(stick) = right_stick {
+            self.last_right_stick = stick;
+        }
+        if changes.is_empty() && look.mouse_delta == Vec2::ZERO && right_stick.is_none() {
+            return;
+        }
+        self.ticks.push(RecordedTick {
+            tick,
+            changes,
+            mouse_delta: look.mouse_delta,
+            right_stick,
+        });
+    }
+
+    /// Number of ticks stored so far (ticks without input are skipped).
+    pub fn recorded_ticks(&self) -> usize {
+        self.ticks.len()
+    }
+
+    /// Copies everything recorded so far, e.g. for periodic saves.
+    pub fn snapshot(&self) -> InputRecording {
+        InputRecording {
+            header: self.header.clone(),
+            ticks: self.ticks.clone(),
+        }
+    }
+
+    /// Ends the recording.
+    pub fn finish(self) -> InputRecording {
+        InputRecording {
+            header: self.header,
+            ticks: self.ticks,
+        }
+    }
+}
+
+/// Replays an [`InputRecording`] into an [`ActionState`], tick by tick.
+pub struct InputPlayer {
+    recording: InputRecording,
+    /// Index of the next recorded tick to apply.
+    cursor: usize,
+    /// Current value of every action that has been set non-zero.
+    current: HashMap<Action, f32>,
+    /// Right stick as of the last played tick.
+    right_stick: Vec2,
+}
+
+impl InputPlayer {
+    /// Creates a player positioned at the start of `recording`.
+    pub fn new(recording: InputRecording) -> Self {
+        Self {
+            recording,
+            cursor: 0,
+            current: HashMap::new(),
+            right_stick: Vec2::ZERO,
+        }
+    }
+
+    /// Header of the recording being played.
+    pub fn header(&self) -> &RecordingHeader {
+        &self.recording.header
+    }
+
+    /// Writes the recorded action values for `tick` into `state` and returns
+    /// the recorded look input.
+    ///
+    /// Replaces the resolver for this tick: starts a new frame on `state`
+    /// so edge queries behave as when recording. If ticks were skipped,
+    /// their changes are applied and their mouse deltas summed.
+    pub fn play_tick(&mut self, tick: u64, state: &mut ActionState) -> LookInput {
+        let mut mouse_delta = Vec2::ZERO;
+        while let Some(recorded) = self.recording.ticks.get(self.cursor) {
+            if recorded.tick > tick {
+                break;
+            }
+            for &(action, value) in &recorded.changes {
+                self.current.insert(action, value);
+            }
+            mouse_delta += recorded.mouse_delta;
+            if let Some(stick) = recorded.right_stick {
+                self.right_stick = stick;
+            }
+            self.cursor += 1;
+        }
+
+        state.begin_frame();
+        for (&action, &value) in &self.current {
+            state.set_action_value(action, value);
+        }
+        LookInput {
+            mouse_delta,
+            right_stick: self.right_stick,
+        }
+    }
+
+    /// Whether every recorded tick has been played.
+    pub fn is_finished(&self) -> bool {
+        self.cursor >= self.recording.ticks.len()
+    }
+}
+
+#[cfg(test)]
+#[path = "replay_tests.rs"]
+mod tests;
@@ -0,0 +1,239 @@
+//! Binary encoding of [`InputRecording`]s.
+//!
+//! ## Binary Layout (v2)
+//!
+//! | Size | Field |
+//! |------|-------|
+//! | 4 | Magic bytes `"NVIR"` |
+//! | 1 | Format version (`u8`, currently 2) |
+//! | 8 | Config seed (`u64` LE) |
+//! | 8 | Start tick (`u64` LE) |
+//! | 2 + N | Engine version (`u16` LE length + UTF-8) |
+//! | 4 | Recorded tick count (`u32` LE) |
+//! | … | Recorded ticks |
+//!
+//! Each recorded tick is: tick delta from the previous entry (LEB128),
+//! flags (`u8`, bit 0: mouse delta present, bit 1: right stick present),
+//! change count (`u8`), changes (action `u8`, value `f32` LE), then the
+//! mouse delta and the right stick (2 × `f32` LE each) if flagged. Ticks
+//! without changes, mouse motion, or stick movement are not stored.
+//!
+//! Version 1 recordings have the same layout without the right stick and
+//! still load.
+
+use std::path::Path;
+
+use glam::Vec2;
+
+use super::{ACTIONS, InputRecording, RecordedTick, RecordingHeader, ReplayError};
+use crate::action_map::Action;
+
+/// Magic bytes identifying an input recording.
+const MAGIC: [u8; 4] = *b"NVIR";
+
+/// Current input recording format version.
+pub const REPLAY_FORMAT_VERSION: u8 = 2;
+
+/// Oldest format version that still loads.
+const MIN_FORMAT_VERSION: u8 = 1;
+
+/// Tick flag: bit 0 indicates a mouse delta follows the changes.
+const FLAG_MOUSE: u8 = 0x01;
+
+/// Tick flag: bit 1 indicates a right stick value follows the mouse delta.
+const FLAG_RIGHT_STICK: u8 = 0x02;
+
+impl InputRecording {
+    /// Encodes the recording in the binary replay format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let version = self.header.engine_version.as_bytes();
+        let version_len = version.len().min(usize::from(u16::MAX));
+        let mut buf = Vec::with_capacity(27 + version_len + self.ticks.len() * 8);
+        buf.extend_from_slice(&MAGIC);
+        buf.push(REPLAY_FORMAT_VERSION);
+        buf.extend_from_slice(&self.header.config_seed.to_le_bytes());
+        buf.extend_from_slice(&self.header.start_tick.to_le_bytes());
+        buf.extend_from_slice(&(version_len as u16).to_le_bytes());
+        buf.extend_from_slice(&version[..version_len]);
+        buf.extend_from_slice(&(self.ticks.len() as u32).to_le_bytes());
+
+        let mut prev_tick = self.header.start_tick;
+        for tick in &self.ticks {
+            write_varint(&mut buf, tick.tick.saturating_sub(prev_tick));
+            prev_tick = tick.tick;
+            let has_mouse = tick.mouse_delta != Vec2::ZERO;
+            let mut flags = if has_mouse { FLAG_MOUSE } else { 0 };
+            if tick.right_stick.is_some() {
+                flags |= FLAG_RIGHT_STICK;
+            }
+            buf.push(flags);
+            buf.push(tick.changes.len() as u8);
+            for &(action, value) in &tick.changes {
+                buf.push(action_code(action));
+                buf.extend_from_slice(&value.to_le_bytes());
+            }
+            for v in [has_mouse.then_some(tick.mouse_delta), tick.right_stick]
+                .into_iter()
+                .flatten()
+            {
+                buf.extend_from_slice(&v.x.to_le_bytes());
+                buf.extend_from_slice(&v.y.to_le_bytes());
+            }
+        }
+        buf
+    }
+
+    /// Decodes a recording produced by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ReplayError> {
+        let mut r = Reader { data, pos: 0 };
+        if r.take(4).map_err(|_| ReplayError::InvalidMagic)? != MAGIC {
+            return Err(ReplayError::InvalidMagic);
+        }
+        let version = r.u8()?;
+        if !(MIN_FORMAT_VERSION..=REPLAY_FORMAT_VERSION).contains(&version) {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+        let config_seed = r.u64()?;
+        let start_tick = r.u64()?;
+        let version_len = usize::from(u16::from_le_bytes(r.array()?));
+        let engine_version = std::str::from_utf8(r.take(version_len)?)
+            .map_err(|_| ReplayError::InvalidEngineVersion)?
+            .to_owned();
+        let count = u32::from_le_bytes(r.array()?) as usize;
+
+        let mut ticks = Vec::with_capacity(count.min(data.len()));
+        let mut tick = start_tick;
+        for _ in 0..count {
+            tick = tick.wrapping_add(r.varint()?);
+            let flags = r.u8()?;
+            let change_count = r.u8()?;
+            let mut changes = Vec::with_capacity(usize::from(change_count));
+            for _ in 0..change_count {
+                let code = r.u8()?;
+                let action = ACTIONS
+                    .get(usize::from(code))
+                    .copied()
+                    .ok_or(ReplayError::UnknownAction(code))?;
+                changes.push((action, r.f32()?));
+            }
+            let mouse_delta = if flags & FLAG_MOUSE != 0 {
+                r.vec2()?
+            } else {
+                Vec2::ZERO
+            };
+            let right_stick = if flags & FLAG_RIGHT_STICK != 0 {
+                Some(r.vec2()?)
+            } else {
+                None
+            };
+            ticks.push(RecordedTick {
+                tick,
+                changes,
+                mouse_delta,
+                right_stick,
+            });
+        }
+
+        Ok(Self {
+            header: RecordingHeader {
+                engine_version,
+                config_seed,
+                start_tick,
+            },
+            ticks,
+        })
+    }
+
+    /// Writes the recording to `path`.
+    pub fn save(&self, path: &Path) -> Result<(), ReplayError> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    /// Reads a recording from `path`.
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
+
+/// On-disk code of `action`: its index in [`ACTIONS`].
+///
+/// Exhaustive so adding an [`Action`] forces a code to be assigned.
+pub(super) fn action_code(action: Action) -> u8 {
+    match action {
+        Action::MoveForward => 0,
+        Action::MoveBack => 1,
+        Action::MoveLeft => 2,
+        Action::MoveRight => 3,
+        Action::Jump => 4,
+        Action::Crouch => 5,
+        Action::Sprint => 6,
+        Action::PrimaryAction => 7,
+        Action::SecondaryAction => 8,
+        Action::Interact => 9,
+        Action::OpenInventory => 10,
+        Action::Pause => 11,
+    }
+}
+
+/// Appends `value` as an unsigned LEB128 varint.
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Bounds-checked little-endian cursor over recording bytes.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ReplayError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or(ReplayError::Truncated(self.data.len()))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ReplayError> {
+        let mut out = [0; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8, ReplayError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u64(&mut self) -> Result<u64, ReplayError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, ReplayError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+
+    fn vec2(&mut self) -> Result<Vec2, ReplayError> {
+        Ok(Vec2::new(self.f32()?, self.f32()?))
+    }
+
+    fn varint(&mut self) -> Result<u64, ReplayError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(ReplayError::Truncated(self.pos))
+    }
+}
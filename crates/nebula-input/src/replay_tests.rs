@@ -0,0 +1,140 @@
+//! Unit tests for input recording, encoding, and playback.
+
+use super::format::action_code;
+use super::*;
+
+fn state_with(values: &[(Action, f32)]) -> ActionState {
+    let mut state = ActionState::new();
+    state.begin_frame();
+    for &(action, value) in values {
+        state.set_action_value(action, value);
+    }
+    state
+}
+
+fn mouse(mouse_delta: Vec2) -> LookInput {
+    LookInput {
+        mouse_delta,
+        right_stick: Vec2::ZERO,
+    }
+}
+
+#[test]
+fn test_recorder_stores_only_transitions() {
+    let mut recorder = InputRecorder::new("0.1.0", 7, 100);
+    let held = state_with(&[(Action::MoveForward, 1.0)]);
+    recorder.record_tick(100, &held, mouse(Vec2::ZERO));
+    recorder.record_tick(101, &held, mouse(Vec2::ZERO));
+    recorder.record_tick(102, &state_with(&[]), mouse(Vec2::new(3.0, -1.0)));
+    let recording = recorder.finish();
+
+    assert_eq!(recording.ticks.len(), 2);
+    assert_eq!(recording.ticks[0].changes, vec![(Action::MoveForward, 1.0)]);
+    assert_eq!(recording.ticks[1].tick, 102);
+    assert_eq!(recording.ticks[1].changes, vec![(Action::MoveForward, 0.0)]);
+}
+
+#[test]
+fn test_bytes_roundtrip() {
+    let mut recorder = InputRecorder::new("1.2.3", u64::MAX, 5);
+    recorder.record_tick(
+        5,
+        &state_with(&[(Action::MoveLeft, 0.37)]),
+        mouse(Vec2::ZERO),
+    );
+    recorder.record_tick(
+        900,
+        &state_with(&[(Action::Pause, 1.0)]),
+        LookInput {
+            mouse_delta: Vec2::new(0.5, 2.0),
+            right_stick: Vec2::new(-0.25, 1.0),
+        },
+    );
+    let recording = recorder.finish();
+
+    let restored = InputRecording::from_bytes(&recording.to_bytes()).unwrap();
+    assert_eq!(restored, recording);
+    assert_eq!(restored.header.engine_version, "1.2.3");
+    assert_eq!(restored.header.config_seed, u64::MAX);
+}
+
+#[test]
+fn test_corrupted_data_returns_error() {
+    assert!(matches!(
+        InputRecording::from_bytes(b"NOPE"),
+        Err(ReplayError::InvalidMagic)
+    ));
+    let bytes = InputRecorder::new("v", 0, 0).finish().to_bytes();
+    let mut bad_version = bytes.clone();
+    bad_version[4] = 99;
+    assert!(matches!(
+        InputRecording::from_bytes(&bad_version),
+        Err(ReplayError::UnsupportedVersion(99))
+    ));
+    assert!(matches!(
+        InputRecording::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(ReplayError::Truncated(_))
+    ));
+}
+
+#[test]
+fn test_player_reproduces_values_and_edges() {
+    let mut recorder = InputRecorder::new("v", 0, 0);
+    recorder.record_tick(0, &state_with(&[]), mouse(Vec2::ZERO));
+    recorder.record_tick(1, &state_with(&[(Action::Jump, 1.0)]), mouse(Vec2::X));
+    recorder.record_tick(2, &state_with(&[(Action::Jump, 1.0)]), mouse(Vec2::ZERO));
+    let mut player = InputPlayer::new(recorder.finish());
+
+    let mut state = ActionState::new();
+    assert_eq!(player.play_tick(0, &mut state).mouse_delta, Vec2::ZERO);
+    assert!(!state.is_action_active(Action::Jump));
+    assert_eq!(player.play_tick(1, &mut state).mouse_delta, Vec2::X);
+    assert!(state.action_just_activated(Action::Jump));
+    player.play_tick(2, &mut state);
+    assert!(state.is_action_active(Action::Jump));
+    assert!(!state.action_just_activated(Action::Jump));
+    assert!(player.is_finished());
+}
+
+#[test]
+fn test_right_stick_recorded_on_change_and_held_on_playback() {
+    let stick = |right_stick| LookInput {
+        mouse_delta: Vec2::ZERO,
+        right_stick,
+    };
+    let mut recorder = InputRecorder::new("v", 0, 0);
+    recorder.record_tick(0, &state_with(&[]), stick(Vec2::new(0.5, 0.0)));
+    recorder.record_tick(1, &state_with(&[]), stick(Vec2::new(0.5, 0.0)));
+    recorder.record_tick(2, &state_with(&[]), stick(Vec2::ZERO));
+    let recording = InputRecording::from_bytes(&recorder.finish().to_bytes()).unwrap();
+    assert_eq!(recording.ticks.len(), 2, "a held stick is stored once");
+
+    let mut player = InputPlayer::new(recording);
+    let mut state = ActionState::new();
+    assert_eq!(
+        player.play_tick(0, &mut state).right_stick,
+        Vec2::new(0.5, 0.0)
+    );
+    assert_eq!(
+        player.play_tick(1, &mut state).right_stick,
+        Vec2::new(0.5, 0.0)
+    );
+    assert_eq!(player.play_tick(2, &mut state).right_stick, Vec2::ZERO);
+}
+
+#[test]
+fn test_version_1_recording_still_loads() {
+    let mut recorder = InputRecorder::new("v", 0, 0);
+    recorder.record_tick(3, &state_with(&[(Action::Jump, 1.0)]), mouse(Vec2::X));
+    let recording = recorder.finish();
+    let mut bytes = recording.to_bytes();
+    bytes[4] = 1;
+    assert_eq!(InputRecording::from_bytes(&bytes).unwrap(), recording);
+}
+
+#[test]
+fn test_action_codes_match_table() {
+    for (code, &action) in ACTIONS.iter().enumerate() {
+        assert_eq!(usize::from(action_code(action)), code);
+    }
+}
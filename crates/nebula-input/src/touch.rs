@@ -0,0 +1,446 @@
+//! Multi-touch state and virtual on-screen controls.
+//!
+//! [`TouchState`] tracks every finger reported by winit and routes each one
+//! to the [`VirtualControl`] it landed on: a floating stick for movement, a
+//! look area that reports drag motion like a mouse, or buttons bound
+//! directly to [`Action`]s. A finger keeps its control until it lifts, so a
+//! thumb can drag the stick while another finger taps jump. The layout is a
+//! [`VirtualControlLayout`] loaded from RON, with regions in normalized
+//! screen coordinates so it is independent of resolution.
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+use winit::event::{Touch, TouchPhase};
+
+use crate::action_map::Action;
+
+/// Axis-aligned rectangle in normalized screen coordinates: `(0, 0)` is the
+/// top-left corner and `(1, 1)` the bottom-right.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TouchRegion {
+    /// Left edge.
+    pub x: f32,
+    /// Top edge.
+    pub y: f32,
+    /// Width.
+    pub width: f32,
+    /// Height.
+    pub height: f32,
+}
+
+impl TouchRegion {
+    /// Create a region from its top-left corner and size.
+    #[must_use]
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Whether a normalized point lies inside the region.
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.x >= self.x
+            && point.x < self.x + self.width
+            && point.y >= self.y
+            && point.y < self.y + self.height
+    }
+}
+
+/// Which touch axis to read for an analog binding.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TouchAxisBinding {
+    /// Virtual stick horizontal, `-1.0` (left) to `1.0` (right).
+    StickX,
+    /// Virtual stick vertical, `-1.0` (down) to `1.0` (up).
+    StickY,
+    /// Horizontal drag in the look area this frame, in pixels.
+    LookX,
+    /// Vertical drag in the look area this frame, in pixels.
+    LookY,
+}
+
+/// One on-screen control.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum VirtualControl {
+    /// Floating stick: centred where the finger lands inside `region`.
+    Stick {
+        /// Where a finger must land to grab the stick.
+        region: TouchRegion,
+        /// Drag distance for full deflection, as a fraction of screen height.
+        radius: f32,
+    },
+    /// Drag area whose finger motion is reported like mouse motion.
+    Look {
+        /// Where a finger must land to start looking.
+        region: TouchRegion,
+    },
+    /// Keeps `action` active while a finger that landed inside is down.
+    Button {
+        /// Tappable area.
+        region: TouchRegion,
+        /// Action the button drives.
+        action: Action,
+    },
+}
+
+impl VirtualControl {
+    fn region(&self) -> &TouchRegion {
+        match self {
+            Self::Stick { region, .. } | Self::Look { region } | Self::Button { region, .. } => {
+                region
+            }
+        }
+    }
+}
+
+/// Ordered list of on-screen controls.
+///
+/// When regions overlap, the earlier control wins, so list buttons before
+/// the stick and look areas they sit on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VirtualControlLayout {
+    /// The controls, highest priority first.
+    pub controls: Vec<VirtualControl>,
+}
+
+impl Default for VirtualControlLayout {
+    fn default() -> Self {
+        Self::default_handheld()
+    }
+}
+
+impl VirtualControlLayout {
+    /// Twin-thumb handheld layout: jump and interact buttons at the bottom
+    /// right, a movement stick on the lower left, and the right half of the
+    /// screen for looking.
+    #[must_use]
+    pub fn default_handheld() -> Self {
+        Self {
+            controls: vec![
+                VirtualControl::Button {
+                    region: TouchRegion::new(0.84, 0.72, 0.12, 0.2),
+                    action: Action::Jump,
+                },
+                VirtualControl::Button {
+                    region: TouchRegion::new(0.7, 0.78, 0.12, 0.16),
+                    action: Action::Interact,
+                },
+                VirtualControl::Stick {
+                    region: TouchRegion::new(0.0, 0.35, 0.5, 0.65),
+                    radius: 0.12,
+                },
+                VirtualControl::Look {
+                    region: TouchRegion::new(0.5, 0.0, 0.5, 1.0),
+                },
+            ],
+        }
+    }
+
+    /// Index of the first control whose region contains `point`.
+    fn hit(&self, point: Vec2) -> Option<usize> {
+        self.controls
+            .iter()
+            .position(|c| c.region().contains(point))
+    }
+
+    /// Serialize to RON string.
+    ///
+    /// # Errors
+    /// Returns an error if serialization fails.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    /// Deserialize from RON string.
+    ///
+    /// # Errors
+    /// Returns an error if the RON string is malformed.
+    pub fn from_ron(s: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(s)
+    }
+}
+
+/// A finger currently on the screen.
+#[derive(Debug, Clone, Copy)]
+struct Finger {
+    /// Control the finger landed on, if any.
+    control: Option<usize>,
+    /// Where the finger landed, in pixels.
+    origin: Vec2,
+    /// Latest position, in pixels.
+    position: Vec2,
+}
+
+/// Frame-coherent multi-touch state routed through a [`VirtualControlLayout`].
+///
+/// # Usage
+///
+/// 1. Call [`set_screen_size`](Self::set_screen_size) on startup and resize.
+/// 2. Forward winit touch events via [`process_event`](Self::process_event).
+/// 3. Pass the state to [`ActionResolver`](crate::ActionResolver) or query it.
+/// 4. Call [`clear_transients`](Self::clear_transients) at end of frame.
+#[derive(Debug, Clone)]
+pub struct TouchState {
+    layout: VirtualControlLayout,
+    screen_size: Vec2,
+    fingers: HashMap<u64, Finger>,
+    look_delta: Vec2,
+}
+
+impl Default for TouchState {
+    fn default() -> Self {
+        Self::new(VirtualControlLayout::default())
+    }
+}
+
+impl TouchState {
+    /// Create a touch state using `layout`, with a 1×1 screen until
+    /// [`set_screen_size`](Self::set_screen_size) is called.
+    #[must_use]
+    pub fn new(layout: VirtualControlLayout) -> Self {
+        Self {
+            layout,
+            screen_size: Vec2::ONE,
+            fingers: HashMap::new(),
+            look_delta: Vec2::ZERO,
+        }
+    }
+
+    /// The active control layout.
+    #[must_use]
+    pub fn layout(&self) -> &VirtualControlLayout {
+        &self.layout
+    }
+
+    /// Replace the layout. Fingers already down are released from their
+    /// controls until they lift.
+    pub fn set_layout(&mut self, layout: VirtualControlLayout) {
+        self.layout = layout;
+        for finger in self.fingers.values_mut() {
+            finger.control = None;
+        }
+    }
+
+    /// Set the window size in pixels that touch positions are relative to.
+    pub fn set_screen_size(&mut self, width: f32, height: f32) {
+        self.screen_size = Vec2::new(width.max(1.0), height.max(1.0));
+    }
+
+    // ── Event handlers ──────────────────────────────────────────────
+
+    /// Handle a winit touch event.
+    pub fn process_event(&mut self, touch: &Touch) {
+        let position = Vec2::new(touch.location.x as f32, touch.location.y as f32);
+        self.process_touch(touch.id, touch.phase, position);
+    }
+
+    /// Handle a touch by finger `id` at `position` in pixels.
+    pub fn process_touch(&mut self, id: u64, phase: TouchPhase, position: Vec2) {
+        match phase {
+            TouchPhase::Started => {
+                let control = self.layout.hit(position / self.screen_size);
+                self.fingers.insert(
+                    id,
+                    Finger {
+                        control,
+                        origin: position,
+                        position,
+                    },
+                );
+            }
+            TouchPhase::Moved => {
+                if let Some(finger) = self.fingers.get_mut(&id) {
+                    if let Some(i) = finger.control
+                        && matches!(self.layout.controls[i], VirtualControl::Look { .. })
+                    {
+                        self.look_delta += position - finger.position;
+                    }
+                    finger.position = position;
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.fingers.remove(&id);
+            }
+        }
+    }
+
+    /// Reset per-frame values (call at end of frame).
+    pub fn clear_transients(&mut self) {
+        self.look_delta = Vec2::ZERO;
+    }
+
+    // ── Queries ─────────────────────────────────────────────────────
+
+    /// Number of fingers on the screen.
+    #[must_use]
+    pub fn touch_count(&self) -> usize {
+        self.fingers.len()
+    }
+
+    /// Virtual stick deflection, each axis in `[-1.0, 1.0]` with `+y` up.
+    ///
+    /// Zero when no finger holds a stick. With several sticks in the layout,
+    /// the first one being dragged is reported.
+    #[must_use]
+    pub fn stick(&self) -> Vec2 {
+        self.fingers
+            .values()
+            .filter_map(|finger| match self.layout.controls.get(finger.control?)? {
+                VirtualControl::Stick { radius, .. } => Some((finger, *radius)),
+                _ => None,
+            })
+            .min_by_key(|(finger, _)| finger.control)
+            .map_or(Vec2::ZERO, |(finger, radius)| {
+                let reach = (radius * self.screen_size.y).max(f32::EPSILON);
+                let offset = (finger.position - finger.origin) / reach;
+                Vec2::new(offset.x, -offset.y).clamp_length_max(1.0)
+            })
+    }
+
+    /// Drag motion in the look area since the last
+    /// [`clear_transients`](Self::clear_transients), in pixels.
+    #[must_use]
+    pub fn look_delta(&self) -> Vec2 {
+        self.look_delta
+    }
+
+    /// Whether a finger is holding a button bound to `action`.
+    #[must_use]
+    pub fn is_action_pressed(&self, action: Action) -> bool {
+        self.fingers.values().any(|finger| {
+            finger.control.is_some_and(|i| {
+                matches!(
+                    self.layout.controls[i],
+                    VirtualControl::Button { action: a, .. } if a == action
+                )
+            })
+        })
+    }
+
+    /// Current value of a touch axis.
+    #[must_use]
+    pub fn axis(&self, axis: TouchAxisBinding) -> f32 {
+        match axis {
+            TouchAxisBinding::StickX => self.stick().x,
+            TouchAxisBinding::StickY => self.stick().y,
+            TouchAxisBinding::LookX => self.look_delta.x,
+            TouchAxisBinding::LookY => self.look_delta.y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action_map::{ActionResolver, ActionState, InputMap};
+    use crate::keyboard::KeyboardState;
+    use crate::mouse::MouseState;
+
+    const W: f32 = 1000.0;
+    const H: f32 = 500.0;
+
+    fn touch_state() -> TouchState {
+        let mut touch = TouchState::default();
+        touch.set_screen_size(W, H);
+        touch
+    }
+
+    fn resolve(touch: &TouchState, state: &mut ActionState) {
+        let map = InputMap::default_handheld();
+        let kb = KeyboardState::new();
+        let mouse = MouseState::new();
+        ActionResolver::resolve(&map, &kb, &mouse, None, Some(touch), state);
+    }
+
+    #[test]
+    fn test_stick_drag_moves_player() {
+        let mut touch = touch_state();
+        let mut state = ActionState::new();
+        // Land at (200, 400): inside the left stick region.
+        touch.process_touch(1, TouchPhase::Started, Vec2::new(200.0, 400.0));
+        // Radius is 0.12 * 500 = 60 px; drag 30 px up, 60 px right.
+        touch.process_touch(1, TouchPhase::Moved, Vec2::new(260.0, 370.0));
+        resolve(&touch, &mut state);
+
+        let stick = touch.stick();
+        assert!(stick.length() <= 1.0 + 1e-6);
+        assert!(stick.x > 0.8 && stick.y > 0.4, "got {stick}");
+        assert!((state.action_value(Action::MoveForward) - stick.y).abs() < 1e-6);
+        assert!((state.action_value(Action::MoveRight) - stick.x).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_drag_stick_while_tapping_jump() {
+        let mut touch = touch_state();
+        let mut state = ActionState::new();
+        touch.process_touch(1, TouchPhase::Started, Vec2::new(200.0, 400.0));
+        touch.process_touch(1, TouchPhase::Moved, Vec2::new(200.0, 340.0));
+        // Second finger lands on the jump button while the stick is held.
+        touch.process_touch(2, TouchPhase::Started, Vec2::new(900.0, 420.0));
+        resolve(&touch, &mut state);
+        assert!(state.action_just_activated(Action::Jump));
+        assert!((state.action_value(Action::MoveForward) - 1.0).abs() < 1e-6);
+
+        touch.process_touch(2, TouchPhase::Ended, Vec2::new(900.0, 420.0));
+        resolve(&touch, &mut state);
+        assert!(state.action_just_deactivated(Action::Jump));
+        assert!(
+            state.is_action_active(Action::MoveForward),
+            "stick still held"
+        );
+        assert_eq!(touch.touch_count(), 1);
+    }
+
+    #[test]
+    fn test_lifting_stick_finger_recenters_in_one_frame() {
+        let mut touch = touch_state();
+        let mut state = ActionState::new();
+        touch.process_touch(7, TouchPhase::Started, Vec2::new(100.0, 300.0));
+        touch.process_touch(7, TouchPhase::Moved, Vec2::new(130.0, 300.0));
+        resolve(&touch, &mut state);
+        assert!(state.is_action_active(Action::MoveRight));
+
+        // Finger lifts inside the stick region.
+        touch.process_touch(7, TouchPhase::Ended, Vec2::new(130.0, 300.0));
+        resolve(&touch, &mut state);
+        assert_eq!(touch.stick(), Vec2::ZERO);
+        assert_eq!(state.action_value(Action::MoveRight), 0.0);
+    }
+
+    #[test]
+    fn test_look_drag_reports_delta_until_cleared() {
+        let mut touch = touch_state();
+        touch.process_touch(3, TouchPhase::Started, Vec2::new(700.0, 100.0));
+        touch.process_touch(3, TouchPhase::Moved, Vec2::new(710.0, 95.0));
+        touch.process_touch(3, TouchPhase::Moved, Vec2::new(725.0, 95.0));
+        assert_eq!(touch.look_delta(), Vec2::new(25.0, -5.0));
+        assert_eq!(touch.axis(TouchAxisBinding::LookX), 25.0);
+        assert_eq!(touch.stick(), Vec2::ZERO, "look finger is not a stick");
+
+        touch.clear_transients();
+        assert_eq!(touch.look_delta(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_finger_keeps_control_after_leaving_region() {
+        let mut touch = touch_state();
+        touch.process_touch(1, TouchPhase::Started, Vec2::new(400.0, 400.0));
+        // Drag far into the look half: still the stick, and no look motion.
+        touch.process_touch(1, TouchPhase::Moved, Vec2::new(800.0, 400.0));
+        assert_eq!(touch.stick(), Vec2::X);
+        assert_eq!(touch.look_delta(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_layout_ron_roundtrip() {
+        let layout = VirtualControlLayout::default_handheld();
+        let text = layout.to_ron().unwrap();
+        assert_eq!(VirtualControlLayout::from_ron(&text).unwrap(), layout);
+    }
+}
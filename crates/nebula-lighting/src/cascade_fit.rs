@@ -0,0 +1,458 @@
+//! Tight fitting of shadow cascades to the visible scene.
+//!
+//! [`compute_cascade_matrix`](crate::compute_cascade_matrix) encloses a whole
+//! frustum slice, most of which is usually empty sky or lies below the
+//! ground. Here the slice is first intersected with the scene bounds, and
+//! the light's orthographic projection is fitted to that intersection. The
+//! depth range still extends to the far side of the scene towards the light,
+//! so casters outside the slice keep casting into it.
+
+use glam::{Mat4, Vec3, Vec4};
+
+/// Tolerance (in world units) for inside tests on clipped vertices.
+const FIT_EPSILON: f32 = 1e-3;
+
+/// Default weight of the logarithmic split in [`practical_split_distances`];
+/// the rest is uniform. 0.75 keeps near cascades small without starving far
+/// ones.
+pub const PRACTICAL_SPLIT_LAMBDA: f32 = 0.75;
+
+/// Corner indices of the six frustum faces (near, far, left, right, bottom, top).
+const FRUSTUM_FACES: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [4, 5, 6],
+    [0, 3, 7],
+    [1, 2, 6],
+    [0, 1, 5],
+    [3, 2, 6],
+];
+
+/// Corner index pairs of the twelve frustum edges.
+const FRUSTUM_EDGES: [(usize, usize); 12] = [
+    (0, 1),
+    (1, 2),
+    (2, 3),
+    (3, 0),
+    (4, 5),
+    (5, 6),
+    (6, 7),
+    (7, 4),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+/// Corner indices (see [`WorldAabb::corners`]) of the six box faces.
+const AABB_FACES: [[usize; 4]; 6] = [
+    [0, 2, 6, 4],
+    [1, 3, 7, 5],
+    [0, 1, 5, 4],
+    [2, 3, 7, 6],
+    [0, 1, 3, 2],
+    [4, 5, 7, 6],
+];
+
+/// An axis-aligned box in the same f32 world space as the camera.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WorldAabb {
+    /// Minimum corner.
+    pub min: Vec3,
+    /// Maximum corner.
+    pub max: Vec3,
+}
+
+impl WorldAabb {
+    /// Create a box from its minimum and maximum corners.
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    /// The eight corners; bit 0/1/2 of the index selects max x/y/z.
+    pub fn corners(&self) -> [Vec3; 8] {
+        std::array::from_fn(|i| {
+            Vec3::new(
+                if i & 1 == 0 { self.min.x } else { self.max.x },
+                if i & 2 == 0 { self.min.y } else { self.max.y },
+                if i & 4 == 0 { self.min.z } else { self.max.z },
+            )
+        })
+    }
+
+    /// Whether `p` lies inside the box, within `eps`.
+    fn contains(&self, p: Vec3, eps: f32) -> bool {
+        p.cmpge(self.min - eps).all() && p.cmple(self.max + eps).all()
+    }
+}
+
+/// View-space depth range `(near, far)` of cascade `cascade_index` out of
+/// `num_cascades` between the camera's `near` and `far` planes.
+///
+/// The slice boundaries are those of [`practical_split_distances`] with the
+/// same `lambda`, so a cascade's range matches its entry in
+/// [`CascadedShadowConfig::cascade_far`](crate::CascadedShadowConfig::cascade_far).
+pub fn cascade_split_range(
+    near: f32,
+    far: f32,
+    cascade_index: usize,
+    num_cascades: usize,
+    lambda: f32,
+) -> (f32, f32) {
+    let splits = practical_split_distances(near, far, num_cascades, lambda);
+    let index = cascade_index.min(num_cascades.clamp(1, 4) - 1);
+    let slice_near = if index == 0 { near } else { splits[index - 1] };
+    (slice_near, splits[index])
+}
+
+/// Far distance of each of `num_cascades` cascades between the camera's
+/// `near` and `far` planes, blending logarithmic (`lambda = 1`) and uniform
+/// (`lambda = 0`) splits.
+///
+/// The last active cascade always ends exactly at `far`. Entries past
+/// `num_cascades` (at most 4) repeat `far`.
+pub fn practical_split_distances(
+    near: f32,
+    far: f32,
+    num_cascades: usize,
+    lambda: f32,
+) -> [f32; 4] {
+    let count = num_cascades.clamp(1, 4);
+    std::array::from_fn(|i| {
+        if i + 1 >= count {
+            far
+        } else {
+            practical_split(near, far, (i + 1) as f32 / count as f32, lambda)
+        }
+    })
+}
+
+/// Split depth at fraction `t` of the way from `near` to `far`.
+fn practical_split(near: f32, far: f32, t: f32, lambda: f32) -> f32 {
+    let lambda = lambda.clamp(0.0, 1.0);
+    let log = near * (far / near).powf(t);
+    let uniform = near + (far - near) * t;
+    lambda * log + (1.0 - lambda) * uniform
+}
+
+/// Fit an orthographic light-space matrix to the part of a frustum slice
+/// that overlaps `scene_aabb`.
+///
+/// `slice_inv_view_proj` is the inverse reverse-Z view-projection of the
+/// cascade's frustum slice. The light-space X/Y extent covers the convex
+/// intersection of the slice and the scene box; the depth range runs from
+/// the far side of that intersection back to the scene box's extent towards
+/// the light. If the slice misses the scene entirely, the whole slice is
+/// enclosed instead. Depth maps to `[0, 1]` with reverse-Z (1 = nearest the
+/// light).
+pub fn fit_cascade_to_scene(
+    light_dir: Vec3,
+    slice_inv_view_proj: Mat4,
+    scene_aabb: &WorldAabb,
+) -> Mat4 {
+    let frustum = slice_corners(slice_inv_view_proj);
+    let mut receivers = clip_frustum_to_aabb(&frustum, scene_aabb);
+    if receivers.is_empty() {
+        receivers = frustum.to_vec();
+    }
+
+    let center = receivers.iter().copied().sum::<Vec3>() / receivers.len() as f32;
+    let light_up = if light_dir.y.abs() > 0.99 {
+        Vec3::Z
+    } else {
+        Vec3::Y
+    };
+    let light_view = Mat4::look_to_rh(center, light_dir, light_up);
+
+    let mut min = Vec3::splat(f32::MAX);
+    let mut max = Vec3::splat(f32::MIN);
+    for p in &receivers {
+        let ls = light_view.transform_point3(*p);
+        min = min.min(ls);
+        max = max.max(ls);
+    }
+    // Casters between the light and the receivers sit at larger light-space
+    // z, anywhere in the column of scene above the receivers' footprint.
+    let column = [
+        Vec4::new(1.0, 0.0, 0.0, -min.x),
+        Vec4::new(-1.0, 0.0, 0.0, max.x),
+        Vec4::new(0.0, 1.0, 0.0, -min.y),
+        Vec4::new(0.0, -1.0, 0.0, max.y),
+    ];
+    let corners = scene_aabb.corners().map(|c| light_view.transform_point3(c));
+    for face in AABB_FACES {
+        let mut polygon: Vec<Vec3> = face.iter().map(|&i| corners[i]).collect();
+        for plane in &column {
+            polygon = clip_polygon(&polygon, *plane);
+        }
+        for p in polygon {
+            max.z = max.z.max(p.z);
+        }
+    }
+
+    // Reverse-Z: light-space z = max.z maps to depth 1, z = min.z to 0.
+    let ortho = Mat4::orthographic_rh(min.x, max.x, min.y, max.y, -min.z, -max.z);
+    ortho * light_view
+}
+
+/// Tight-fitting counterpart of
+/// [`compute_cascade_matrix_from_camera`](crate::compute_cascade_matrix_from_camera).
+pub fn compute_cascade_matrix_tight_from_camera(
+    light_dir: Vec3,
+    camera_view: Mat4,
+    fov_y: f32,
+    aspect: f32,
+    cascade_near: f32,
+    cascade_far: f32,
+    scene_aabb: &WorldAabb,
+) -> Mat4 {
+    let proj = Mat4::perspective_rh(fov_y, aspect, cascade_far, cascade_near);
+    fit_cascade_to_scene(light_dir, (proj * camera_view).inverse(), scene_aabb)
+}
+
+/// World-space corners of a reverse-Z frustum: near plane (NDC z = 1) first,
+/// then far plane (z = 0), each counter-clockwise from bottom-left.
+fn slice_corners(inv_view_proj: Mat4) -> [Vec3; 8] {
+    let ndc = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)];
+    std::array::from_fn(|i| {
+        let (x, y) = ndc[i % 4];
+        let z = if i < 4 { 1.0 } else { 0.0 };
+        let p = inv_view_proj * Vec4::new(x, y, z, 1.0);
+        p.truncate() / p.w
+    })
+}
+
+/// Clips a convex polygon to the half-space `plane · p >= 0`
+/// (Sutherland–Hodgman).
+fn clip_polygon(polygon: &[Vec3], plane: Vec4) -> Vec<Vec3> {
+    let mut out = Vec::with_capacity(polygon.len() + 1);
+    for (i, &current) in polygon.iter().enumerate() {
+        let next = polygon[(i + 1) % polygon.len()];
+        let d0 = plane.dot(current.extend(1.0));
+        let d1 = plane.dot(next.extend(1.0));
+        if d0 >= 0.0 {
+            out.push(current);
+        }
+        if (d0 >= 0.0) != (d1 >= 0.0) {
+            out.push(current + (next - current) * (d0 / (d0 - d1)));
+        }
+    }
+    out
+}
+
+/// Vertices of the convex intersection of a frustum and a box.
+///
+/// Every vertex of the intersection is a frustum corner inside the box, a
+/// box corner inside the frustum, or an edge of one crossing a face of the
+/// other, so the bounds of these points are the bounds of the hull.
+fn clip_frustum_to_aabb(frustum: &[Vec3; 8], aabb: &WorldAabb) -> Vec<Vec3> {
+    let centroid = frustum.iter().copied().sum::<Vec3>() / 8.0;
+    let planes: Vec<Vec4> = FRUSTUM_FACES
+        .iter()
+        .map(|&[a, b, c]| {
+            let n = (frustum[b] - frustum[a])
+                .cross(frustum[c] - frustum[a])
+                .normalize_or_zero();
+            let plane = n.extend(-n.dot(frustum[a]));
+            // Orient so the inside has positive distance.
+            if plane.dot(centroid.extend(1.0)) < 0.0 {
+                -plane
+            } else {
+                plane
+            }
+        })
+        .collect();
+    let in_frustum = |p: Vec3| {
+        planes
+            .iter()
+            .all(|plane| plane.dot(p.extend(1.0)) >= -FIT_EPSILON)
+    };
+
+    let mut points: Vec<Vec3> = frustum
+        .iter()
+        .copied()
+        .filter(|&p| aabb.contains(p, FIT_EPSILON))
+        .collect();
+    let box_corners = aabb.corners();
+    points.extend(box_corners.iter().copied().filter(|&p| in_frustum(p)));
+
+    // Frustum edges against the box's axis-aligned faces.
+    for &(a, b) in &FRUSTUM_EDGES {
+        let (p0, p1) = (frustum[a], frustum[b]);
+        let d = p1 - p0;
+        for axis in 0..3 {
+            if d[axis].abs() < f32::EPSILON {
+                continue;
+            }
+            for bound in [aabb.min[axis], aabb.max[axis]] {
+                let t = (bound - p0[axis]) / d[axis];
+                let p = p0 + d * t;
+                if (0.0..=1.0).contains(&t) && aabb.contains(p, FIT_EPSILON) {
+                    points.push(p);
+                }
+            }
+        }
+    }
+
+    // Box edges against the frustum planes.
+    for a in 0..8 {
+        for bit in [1, 2, 4] {
+            if a & bit != 0 {
+                continue;
+            }
+            let (p0, p1) = (box_corners[a], box_corners[a | bit]);
+            for plane in &planes {
+                let d0 = plane.dot(p0.extend(1.0));
+                let d1 = plane.dot(p1.extend(1.0));
+                if (d0 < 0.0) == (d1 < 0.0) {
+                    continue;
+                }
+                let p = p0 + (p1 - p0) * (d0 / (d0 - d1));
+                if in_frustum(p) {
+                    points.push(p);
+                }
+            }
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute_cascade_matrix_from_camera;
+
+    const FOV_Y: f32 = std::f32::consts::FRAC_PI_4;
+    const ASPECT: f32 = 16.0 / 9.0;
+
+    /// Camera 10 m above flat terrain, looking slightly down the -Z axis.
+    fn camera_view() -> Mat4 {
+        Mat4::look_to_rh(
+            Vec3::new(0.0, 10.0, 0.0),
+            Vec3::new(0.0, -0.2, -1.0).normalize(),
+            Vec3::Y,
+        )
+    }
+
+    /// A 2 km × 2 km terrain slab, 6 m thick.
+    fn terrain() -> WorldAabb {
+        WorldAabb::new(
+            Vec3::new(-1000.0, -2.0, -1000.0),
+            Vec3::new(1000.0, 4.0, 1000.0),
+        )
+    }
+
+    fn sun() -> Vec3 {
+        Vec3::new(0.3, -1.0, 0.2).normalize()
+    }
+
+    /// World-space volume of the box a light matrix maps to NDC
+    /// `[-1, 1]² × [0, 1]`.
+    fn light_volume(m: Mat4) -> f32 {
+        4.0 / m.determinant().abs()
+    }
+
+    #[test]
+    fn test_tight_fit_has_less_volume_than_fixed() {
+        let fixed = compute_cascade_matrix_from_camera(
+            sun(),
+            camera_view(),
+            FOV_Y,
+            ASPECT,
+            0.1,
+            32.0,
+            2048,
+        );
+        let tight = compute_cascade_matrix_tight_from_camera(
+            sun(),
+            camera_view(),
+            FOV_Y,
+            ASPECT,
+            0.1,
+            32.0,
+            &terrain(),
+        );
+        let (fixed_volume, tight_volume) = (light_volume(fixed), light_volume(tight));
+        assert!(tight_volume.is_finite() && tight_volume > 0.0);
+        assert!(
+            tight_volume < fixed_volume,
+            "tight {tight_volume} should be smaller than fixed {fixed_volume}"
+        );
+    }
+
+    #[test]
+    fn test_point_inside_cascade_maps_into_ndc() {
+        let tight = compute_cascade_matrix_tight_from_camera(
+            sun(),
+            camera_view(),
+            FOV_Y,
+            ASPECT,
+            0.1,
+            32.0,
+            &terrain(),
+        );
+        // On the ground, 20 m in front of the camera: inside both the slice
+        // and the scene.
+        for p in [Vec3::new(0.0, 0.0, -20.0), Vec3::new(2.0, 3.5, -15.0)] {
+            let ndc = tight.project_point3(p);
+            assert!(
+                (-1.0..=1.0).contains(&ndc.x) && (-1.0..=1.0).contains(&ndc.y),
+                "{p} mapped outside the cascade: {ndc}"
+            );
+            assert!(
+                (0.0..=1.0).contains(&ndc.z),
+                "{p} depth out of range: {ndc}"
+            );
+        }
+        // Far outside the slice laterally.
+        let outside = tight.project_point3(Vec3::new(500.0, 0.0, -20.0));
+        assert!(outside.x.abs() > 1.0 || outside.y.abs() > 1.0);
+    }
+
+    #[test]
+    fn test_casters_above_slice_stay_in_depth_range() {
+        let scene = WorldAabb::new(Vec3::new(-50.0, -2.0, -50.0), Vec3::new(50.0, 60.0, 50.0));
+        let tight = compute_cascade_matrix_tight_from_camera(
+            Vec3::NEG_Y,
+            camera_view(),
+            FOV_Y,
+            ASPECT,
+            0.1,
+            32.0,
+            &scene,
+        );
+        // A caster at the top of the scene, directly above a receiver.
+        let ndc = tight.project_point3(Vec3::new(0.0, 59.0, -20.0));
+        assert!((0.0..=1.0).contains(&ndc.z), "caster depth {}", ndc.z);
+    }
+
+    #[test]
+    fn test_slice_missing_scene_encloses_whole_slice() {
+        let far_away = WorldAabb::new(Vec3::splat(5000.0), Vec3::splat(5010.0));
+        let view = camera_view();
+        let tight = compute_cascade_matrix_tight_from_camera(
+            sun(),
+            view,
+            FOV_Y,
+            ASPECT,
+            0.1,
+            32.0,
+            &far_away,
+        );
+        let ndc = tight.project_point3(Vec3::new(0.0, 8.0, -20.0));
+        assert!(ndc.x.abs() <= 1.0 && ndc.y.abs() <= 1.0);
+    }
+
+    #[test]
+    fn test_cascade_split_range_covers_near_to_far() {
+        let (near, far) = (0.1, 2048.0);
+        let mut previous_far = near;
+        for i in 0..4 {
+            let (slice_near, slice_far) =
+                cascade_split_range(near, far, i, 4, PRACTICAL_SPLIT_LAMBDA);
+            assert_eq!(slice_near, previous_far);
+            assert!(slice_far > slice_near);
+            previous_far = slice_far;
+        }
+        assert_eq!(previous_far, far);
+    }
+}
@@ -9,7 +9,7 @@ use std::collections::VecDeque;
 
 use nebula_voxel::{CHUNK_SIZE, ChunkData, Transparency, VoxelTypeRegistry};
 
-use crate::voxel_light::{ChunkLightMap, VoxelLight};
+use crate::voxel_light::{ChunkLightMap, VoxelLight, raise_block_light};
 
 /// Number of voxels per face edge.
 const S: u32 = CHUNK_SIZE as u32;
@@ -30,6 +30,13 @@ impl ChunkBorderLights {
             faces: std::array::from_fn(|_| Box::new([VoxelLight(0); CHUNK_SIZE * CHUNK_SIZE])),
         }
     }
+
+    /// Border cache holding the current edge layers of `light`.
+    pub fn from_light_map(light: &ChunkLightMap) -> Self {
+        Self {
+            faces: std::array::from_fn(|i| light.extract_border(Face::ALL[i])),
+        }
+    }
 }
 
 /// The six axis-aligned faces of a chunk.
@@ -50,6 +57,16 @@ pub enum Face {
 }
 
 impl Face {
+    /// All faces, in discriminant order.
+    pub const ALL: [Face; 6] = [
+        Face::PosX,
+        Face::NegX,
+        Face::PosY,
+        Face::NegY,
+        Face::PosZ,
+        Face::NegZ,
+    ];
+
     /// Returns the opposite face.
     pub fn opposite(self) -> Face {
         match self {
@@ -179,12 +196,11 @@ pub fn propagate_cross_chunk(
             let mut current = chunk.get(x, y, z);
             let mut changed = false;
 
-            // Block light channel
-            let incoming_bl = neighbor_light.block_light().saturating_sub(1 + decay_extra);
-            if incoming_bl > current.block_light() {
-                current.set_block_light(incoming_bl);
-                changed = true;
-            }
+            // Block light channels
+            let incoming_bl = neighbor_light
+                .block_light_rgb()
+                .map(|level| level.saturating_sub(1 + decay_extra));
+            changed |= raise_block_light(&mut current, incoming_bl);
 
             // Sunlight channel: vertical (NegY) has no decay
             let sl_decay = if face == Face::NegY {
@@ -209,7 +225,8 @@ pub fn propagate_cross_chunk(
     propagate_bfs_from_queue(&mut queue, chunk, voxels, registry);
 }
 
-/// Continues BFS propagation from an existing queue (both channels).
+/// Continues BFS propagation from an existing queue (sunlight and every
+/// block light channel).
 fn propagate_bfs_from_queue(
     queue: &mut VecDeque<(u32, u32, u32)>,
     chunk: &mut ChunkLightMap,
@@ -218,7 +235,7 @@ fn propagate_bfs_from_queue(
 ) {
     while let Some((x, y, z)) = queue.pop_front() {
         let current = chunk.get(x, y, z);
-        let bl = current.block_light();
+        let bl = current.block_light_rgb();
         let sl = current.sunlight();
 
         for (dx, dy, dz) in NEIGHBORS_6 {
@@ -233,16 +250,10 @@ fn propagate_bfs_from_queue(
 
             let decay = 1 + extra_decay(voxels, registry, nx, ny, nz);
             let mut neighbor = chunk.get(nx, ny, nz);
-            let mut push = false;
 
             // Block light
-            if bl > decay {
-                let new_bl = bl - decay;
-                if new_bl > neighbor.block_light() {
-                    neighbor.set_block_light(new_bl);
-                    push = true;
-                }
-            }
+            let mut push =
+                raise_block_light(&mut neighbor, bl.map(|level| level.saturating_sub(decay)));
 
             // Sunlight
             if sl > decay {
@@ -262,160 +273,38 @@ fn propagate_bfs_from_queue(
 }
 
 // ---------------------------------------------------------------------------
-// Tests
+// Neighbor loading
 // ---------------------------------------------------------------------------
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::voxel_light::{propagate_block_light, remove_block_light};
-    use nebula_voxel::{Transparency, VoxelTypeDef, VoxelTypeRegistry};
-
-    fn test_registry() -> VoxelTypeRegistry {
-        let mut reg = VoxelTypeRegistry::new();
-        reg.register(VoxelTypeDef {
-            name: "stone".to_string(),
-            solid: true,
-            transparency: Transparency::Opaque,
-            material_index: 1,
-            light_emission: 0,
-        })
-        .unwrap();
-        reg.register(VoxelTypeDef {
-            name: "glass".to_string(),
-            solid: true,
-            transparency: Transparency::SemiTransparent,
-            material_index: 2,
-            light_emission: 0,
-        })
-        .unwrap();
-        reg
-    }
-
-    fn make_adjacent_pair() -> (ChunkLightMap, ChunkData, ChunkLightMap, ChunkData) {
-        (
-            ChunkLightMap::new_dark(),
-            ChunkData::new_air(),
-            ChunkLightMap::new_dark(),
-            ChunkData::new_air(),
-        )
-    }
-
-    #[test]
-    fn test_light_crosses_chunk_boundary() {
-        let reg = test_registry();
-        let (mut light_a, voxels_a, mut light_b, voxels_b) = make_adjacent_pair();
-        propagate_block_light(&mut light_a, &voxels_a, &reg, &[(31, 16, 16, 15)]);
-
-        let border = light_a.extract_border(Face::PosX);
-        propagate_cross_chunk(&mut light_b, &voxels_b, &reg, Face::NegX, &border);
-
-        let bl = light_b.get(0, 16, 16).block_light();
-        assert!(bl > 0, "light should cross into chunk B, got {bl}");
-        assert!(
-            bl <= 14,
-            "light should decay when crossing boundary, got {bl}"
-        );
-    }
-
-    #[test]
-    fn test_border_cache_matches_neighbor_edge() {
-        let reg = test_registry();
-        let (mut light_a, voxels_a, _, _) = make_adjacent_pair();
-        propagate_block_light(&mut light_a, &voxels_a, &reg, &[(30, 16, 16, 10)]);
-        let border = light_a.extract_border(Face::PosX);
-
-        let expected = light_a.get(31, 16, 16);
-        let actual = border[(16 * S + 16) as usize];
-        assert_eq!(actual, expected, "border cache must match chunk edge voxel");
-    }
-
-    #[test]
-    fn test_removing_light_depropagates_across_boundary() {
-        let reg = test_registry();
-        let (mut light_a, voxels_a, mut light_b, voxels_b) = make_adjacent_pair();
-        propagate_block_light(&mut light_a, &voxels_a, &reg, &[(31, 16, 16, 15)]);
-        let border = light_a.extract_border(Face::PosX);
-        propagate_cross_chunk(&mut light_b, &voxels_b, &reg, Face::NegX, &border);
-        assert!(light_b.get(0, 16, 16).block_light() > 0);
-
-        remove_block_light(&mut light_a, &voxels_a, &reg, 31, 16, 16);
-        let new_border = light_a.extract_border(Face::PosX);
-        assert!(border_changed(&border, &new_border));
-
-        let mut light_b_clean = ChunkLightMap::new_dark();
-        propagate_cross_chunk(&mut light_b_clean, &voxels_b, &reg, Face::NegX, &new_border);
-        assert_eq!(
-            light_b_clean.get(0, 16, 16).block_light(),
-            0,
-            "light should be removed after source deletion"
-        );
-    }
-
-    #[test]
-    fn test_two_lights_from_different_chunks_combine() {
-        let reg = test_registry();
-        let (mut light_a, voxels_a, mut light_b, voxels_b) = make_adjacent_pair();
-        propagate_block_light(&mut light_a, &voxels_a, &reg, &[(31, 16, 16, 10)]);
-        propagate_block_light(&mut light_b, &voxels_b, &reg, &[(5, 16, 16, 10)]);
-
-        let border_a = light_a.extract_border(Face::PosX);
-        propagate_cross_chunk(&mut light_b, &voxels_b, &reg, Face::NegX, &border_a);
-
-        let bl = light_b.get(0, 16, 16).block_light();
-        let from_b_alone = 10u8.saturating_sub(5);
-        assert!(
-            bl >= from_b_alone,
-            "combined light ({bl}) should be >= single source contribution ({from_b_alone})"
-        );
-    }
-
-    #[test]
-    fn test_propagation_settles_in_bounded_steps() {
-        let reg = test_registry();
-        let (mut light_a, voxels_a, mut light_b, voxels_b) = make_adjacent_pair();
-        propagate_block_light(&mut light_a, &voxels_a, &reg, &[(31, 16, 16, 15)]);
-        let border = light_a.extract_border(Face::PosX);
-        propagate_cross_chunk(&mut light_b, &voxels_b, &reg, Face::NegX, &border);
-
-        assert_eq!(
-            light_b.get(31, 16, 16).block_light(),
-            0,
-            "light should not reach the far end of a neighboring chunk from level 15"
-        );
-    }
-
-    #[test]
-    fn test_border_changed_detects_difference() {
-        let a: BorderLightFace = Box::new([VoxelLight(0); CHUNK_SIZE * CHUNK_SIZE]);
-        let mut b: BorderLightFace = Box::new([VoxelLight(0); CHUNK_SIZE * CHUNK_SIZE]);
-        assert!(!border_changed(&a, &b));
-        b[500] = VoxelLight(5);
-        assert!(border_changed(&a, &b));
-    }
-
-    #[test]
-    fn test_extract_border_all_faces() {
-        let reg = test_registry();
-        let mut light = ChunkLightMap::new_dark();
-        let voxels = ChunkData::new_air();
-        propagate_block_light(&mut light, &voxels, &reg, &[(16, 16, 16, 15)]);
-
-        // All faces should be extractable without panic
-        for face in [
-            Face::PosX,
-            Face::NegX,
-            Face::PosY,
-            Face::NegY,
-            Face::PosZ,
-            Face::NegZ,
-        ] {
-            let border = light.extract_border(face);
-            // At least some values should be non-zero (light reaches edges from center)
-            let has_light = border.iter().any(|v| v.block_light() > 0);
-            // Light level 15 from center (16,16,16) reaches edge at distance 15-16 = might not
-            // For faces at distance 16 from center, light doesn't reach. That's fine.
-            let _ = has_light;
-        }
+/// Lets light from an already-lit chunk flow into a neighbor that just
+/// loaded on its `newly_loaded_face`.
+///
+/// `center` is the lit chunk's border cache; its face toward the neighbor
+/// seeds the neighbor's opposite face. A freshly loaded chunk has seen only
+/// darkness across that seam, so propagation runs only if the cached border
+/// differs from a dark face. Returns whether the neighbor was relit.
+pub fn relight_on_neighbor_load(
+    center: &ChunkBorderLights,
+    newly_loaded_face: Face,
+    neighbor_light: &mut ChunkLightMap,
+    neighbor_voxels: &ChunkData,
+    registry: &VoxelTypeRegistry,
+) -> bool {
+    let border = &center.faces[newly_loaded_face as usize];
+    let dark: BorderLightFace = Box::new([VoxelLight(0); CHUNK_SIZE * CHUNK_SIZE]);
+    if !border_changed(&dark, border) {
+        return false;
     }
+    propagate_cross_chunk(
+        neighbor_light,
+        neighbor_voxels,
+        registry,
+        newly_loaded_face.opposite(),
+        border,
+    );
+    true
 }
+
+#[cfg(test)]
+#[path = "cross_chunk_tests.rs"]
+mod tests;
@@ -1,18 +1,28 @@
 //! Light types, shadow mapping, PBR shading calculations, and ambient occlusion integration.
 
+mod cascade_fit;
 pub mod cross_chunk;
 mod directional;
+pub mod light_serial;
 pub mod pbr;
 mod point;
 mod shadow;
 pub mod space_surface;
 pub mod voxel_light;
 
+pub use cascade_fit::{
+    PRACTICAL_SPLIT_LAMBDA, WorldAabb, cascade_split_range,
+    compute_cascade_matrix_tight_from_camera, fit_cascade_to_scene, practical_split_distances,
+};
 pub use cross_chunk::{
     BorderLightFace, ChunkBorderLights, Face, border_changed, propagate_cross_chunk,
+    relight_on_neighbor_load,
 };
 pub use directional::{DirectionalLight, DirectionalLightUniform, sun_direction_at_time};
-pub use pbr::{PbrMaterial, PbrMaterialUniform};
+pub use light_serial::{
+    LightSerError, LoadedChunk, deserialize_chunk_with_light, serialize_chunk_with_light,
+};
+pub use pbr::{PbrMaterial, PbrMaterialUniform, collect_emissive_materials};
 pub use point::{
     Frustum as PointLightFrustum, PointLight, PointLightGpu, PointLightHeader, PointLightManager,
     attenuation,
@@ -23,9 +33,9 @@ pub use shadow::{
 };
 pub use space_surface::{
     AtmosphereConfig as LightingAtmosphereConfig, LightingContext, LightingContextUniform,
-    lighting_context_at_altitude, modulate_ambient_by_sun,
+    atmospheric_tint, lighting_context_at_altitude, modulate_ambient_by_sun, tint_ambient_by_sun,
 };
 pub use voxel_light::{
-    ChunkLightMap, VoxelLight, collect_emissive_sources, propagate_block_light, propagate_sunlight,
-    remove_block_light,
+    ChunkLightMap, LightColor, VoxelLight, collect_emissive_sources, propagate_block_light,
+    propagate_sunlight, remove_block_light,
 };
@@ -0,0 +1,248 @@
+//! Persistence of [`ChunkLightMap`]s alongside serialized chunk voxels.
+//!
+//! Light maps are stored as one little-endian [`VoxelLight`] `u16` per voxel
+//! in the light section of the NVCK chunk format (see
+//! [`ChunkData::serialize_with_light`]). Maps saved before colored light, with
+//! one byte per voxel, load with their block light as white. Chunks saved
+//! without light are relit on load with the same sunlight and block light
+//! passes used for freshly generated chunks.
+
+use nebula_voxel::{CHUNK_SIZE, ChunkData, ChunkSerError, VoxelTypeRegistry};
+
+use crate::voxel_light::{
+    ChunkLightMap, VoxelLight, collect_emissive_sources, propagate_block_light, propagate_sunlight,
+};
+
+/// Number of voxels in one chunk.
+const LIGHT_MAP_LEN: usize = CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE;
+
+/// Serialized light map size: two bytes per voxel.
+const SERIALIZED_LEN: usize = LIGHT_MAP_LEN * 2;
+
+/// Errors that can occur when decoding a serialized [`ChunkLightMap`].
+#[derive(Debug, thiserror::Error)]
+pub enum LightSerError {
+    /// The byte slice does not hold one packed value per voxel.
+    #[error("invalid light map length: expected {expected} bytes, got {actual}")]
+    InvalidLength {
+        /// Required byte count.
+        expected: usize,
+        /// Actual byte count received.
+        actual: usize,
+    },
+}
+
+impl ChunkLightMap {
+    /// Serializes the light map to one little-endian packed [`VoxelLight`]
+    /// per voxel, in `y`-major, then `z`, then `x` order.
+    pub fn serialize(&self) -> Vec<u8> {
+        let s = CHUNK_SIZE as u32;
+        let mut bytes = Vec::with_capacity(SERIALIZED_LEN);
+        for y in 0..s {
+            for z in 0..s {
+                for x in 0..s {
+                    bytes.extend_from_slice(&self.get(x, y, z).0.to_le_bytes());
+                }
+            }
+        }
+        bytes
+    }
+
+    /// Rebuilds a light map from bytes produced by [`serialize`](Self::serialize).
+    ///
+    /// Also accepts the older one-byte-per-voxel layout (sunlight in the
+    /// high nibble, block light in the low nibble), whose block light becomes
+    /// white.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, LightSerError> {
+        let values: Vec<VoxelLight> = match bytes.len() {
+            SERIALIZED_LEN => bytes
+                .chunks_exact(2)
+                .map(|pair| VoxelLight(u16::from_le_bytes([pair[0], pair[1]])))
+                .collect(),
+            LIGHT_MAP_LEN => bytes
+                .iter()
+                .map(|&byte| {
+                    let mut light = VoxelLight::default();
+                    light.set_sunlight(byte >> 4);
+                    light.set_block_light(byte & 0xF);
+                    light
+                })
+                .collect(),
+            actual => {
+                return Err(LightSerError::InvalidLength {
+                    expected: SERIALIZED_LEN,
+                    actual,
+                });
+            }
+        };
+        let s = CHUNK_SIZE as u32;
+        let mut map = Self::new_dark();
+        let mut values = values.into_iter();
+        for y in 0..s {
+            for z in 0..s {
+                for x in 0..s {
+                    if let Some(value) = values.next() {
+                        map.set(x, y, z, value);
+                    }
+                }
+            }
+        }
+        Ok(map)
+    }
+
+    /// Computes sunlight and emissive block light for `voxels` from scratch.
+    pub fn compute(voxels: &ChunkData, registry: &VoxelTypeRegistry) -> Self {
+        let mut map = Self::new_dark();
+        propagate_sunlight(&mut map, voxels, registry);
+        let sources = collect_emissive_sources(voxels, registry);
+        propagate_block_light(&mut map, voxels, registry, &sources);
+        map
+    }
+}
+
+/// A chunk and its light map, as restored by [`deserialize_chunk_with_light`].
+pub struct LoadedChunk {
+    /// The chunk's voxels.
+    pub voxels: ChunkData,
+    /// The chunk's light map.
+    pub light: ChunkLightMap,
+    /// `true` if no light was stored and [`light`](Self::light) was recomputed.
+    pub light_recomputed: bool,
+}
+
+/// Serializes `voxels` with `light` stored in the chunk's light section.
+pub fn serialize_chunk_with_light(voxels: &ChunkData, light: &ChunkLightMap) -> Vec<u8> {
+    // Always `SERIALIZED_LEN` bytes, so the light section cannot be rejected.
+    voxels
+        .serialize_with_light(Some(&light.serialize()))
+        .unwrap_or_else(|_| voxels.serialize())
+}
+
+/// Deserializes a chunk and its light map, recomputing the light map with
+/// [`ChunkLightMap::compute`] if the chunk was saved without one.
+pub fn deserialize_chunk_with_light(
+    data: &[u8],
+    registry: &VoxelTypeRegistry,
+) -> Result<LoadedChunk, ChunkSerError> {
+    let (voxels, stored) = ChunkData::deserialize_with_light(data)?;
+    let Some(bytes) = stored else {
+        let light = ChunkLightMap::compute(&voxels, registry);
+        return Ok(LoadedChunk {
+            voxels,
+            light,
+            light_recomputed: true,
+        });
+    };
+    let light = ChunkLightMap::deserialize(&bytes).map_err(|e| {
+        let LightSerError::InvalidLength { expected, actual } = e;
+        ChunkSerError::LightMapMismatch { expected, actual }
+    })?;
+    Ok(LoadedChunk {
+        voxels,
+        light,
+        light_recomputed: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use nebula_voxel::{Transparency, VoxelTypeDef, VoxelTypeId};
+
+    use super::*;
+
+    /// Creates a registry with air(0), stone(1), lamp(2).
+    fn test_registry() -> VoxelTypeRegistry {
+        let mut reg = VoxelTypeRegistry::new();
+        reg.register(VoxelTypeDef {
+            name: "stone".to_string(),
+            solid: true,
+            transparency: Transparency::Opaque,
+            material_index: 1,
+            light_emission: [0; 3],
+        })
+        .unwrap();
+        reg.register(VoxelTypeDef {
+            name: "lamp".to_string(),
+            solid: true,
+            transparency: Transparency::Opaque,
+            material_index: 2,
+            light_emission: [14; 3],
+        })
+        .unwrap();
+        reg
+    }
+
+    /// A stone floor with a covered cave containing a lamp.
+    fn lit_chunk(reg: &VoxelTypeRegistry) -> (ChunkData, ChunkLightMap) {
+        let mut voxels = ChunkData::new_air();
+        for z in 0..CHUNK_SIZE {
+            for x in 0..CHUNK_SIZE {
+                voxels.set(x, 10, z, VoxelTypeId(1));
+            }
+        }
+        voxels.set(8, 4, 8, VoxelTypeId(2));
+        let light = ChunkLightMap::compute(&voxels, reg);
+        (voxels, light)
+    }
+
+    fn assert_same_light(a: &ChunkLightMap, b: &ChunkLightMap) {
+        for y in 0..32 {
+            for z in 0..32 {
+                for x in 0..32 {
+                    assert_eq!(a.get(x, y, z), b.get(x, y, z), "at ({x},{y},{z})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_light_map_roundtrip() {
+        let reg = test_registry();
+        let (_, light) = lit_chunk(&reg);
+        let bytes = light.serialize();
+        assert_eq!(bytes.len(), SERIALIZED_LEN);
+        let restored = ChunkLightMap::deserialize(&bytes).unwrap();
+        assert_same_light(&light, &restored);
+        assert!(matches!(
+            ChunkLightMap::deserialize(&bytes[1..]),
+            Err(LightSerError::InvalidLength { .. })
+        ));
+    }
+
+    #[test]
+    fn test_one_byte_light_map_loads_as_white() {
+        let mut bytes = vec![0u8; LIGHT_MAP_LEN];
+        bytes[0] = 0xF9;
+        let map = ChunkLightMap::deserialize(&bytes).unwrap();
+        assert_eq!(map.get(0, 0, 0).sunlight(), 15);
+        assert_eq!(map.get(0, 0, 0).block_light_rgb(), [9; 3]);
+        assert_eq!(map.get(1, 0, 0), VoxelLight(0));
+    }
+
+    #[test]
+    fn test_chunk_reload_restores_stored_light() {
+        let reg = test_registry();
+        let (voxels, mut light) = lit_chunk(&reg);
+        // A value no propagation pass would produce proves the map is read
+        // back rather than recomputed.
+        light.set(0, 0, 0, VoxelLight(0x7A));
+
+        let bytes = serialize_chunk_with_light(&voxels, &light);
+        let loaded = deserialize_chunk_with_light(&bytes, &reg).unwrap();
+        assert!(!loaded.light_recomputed);
+        assert_same_light(&light, &loaded.light);
+        assert_eq!(loaded.voxels.get(8, 4, 8), VoxelTypeId(2));
+    }
+
+    #[test]
+    fn test_chunk_saved_without_light_is_relit() {
+        let reg = test_registry();
+        let (voxels, light) = lit_chunk(&reg);
+
+        let loaded = deserialize_chunk_with_light(&voxels.serialize(), &reg).unwrap();
+        assert!(loaded.light_recomputed);
+        assert_same_light(&light, &loaded.light);
+        assert_eq!(loaded.light.get(8, 5, 8).block_light(), 13);
+        assert_eq!(loaded.light.get(0, 20, 0).sunlight(), VoxelLight::MAX_LEVEL);
+    }
+}
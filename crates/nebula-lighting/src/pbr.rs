@@ -5,6 +5,7 @@
 //! reference functions used for unit testing shader correctness.
 
 use bytemuck::{Pod, Zeroable};
+use nebula_materials::{MaterialId, MaterialRegistry};
 
 /// PBR material parameters (CPU-side, per voxel type or per texture).
 #[derive(Clone, Debug)]
@@ -21,6 +22,11 @@ pub struct PbrMaterial {
     pub emissive_color: glam::Vec3,
     /// Emissive intensity multiplier. Values > 1.0 produce HDR output for bloom.
     pub emissive_intensity: f32,
+    /// Strength of a glossy clearcoat layer over the base \[0.0, 1.0\].
+    /// 0 disables the layer.
+    pub clearcoat: f32,
+    /// Roughness of the clearcoat layer \[0.0, 1.0\].
+    pub clearcoat_roughness: f32,
 }
 
 impl Default for PbrMaterial {
@@ -32,6 +38,8 @@ impl Default for PbrMaterial {
             ao: 1.0,
             emissive_color: glam::Vec3::ZERO,
             emissive_intensity: 0.0,
+            clearcoat: 0.0,
+            clearcoat_roughness: 0.0,
         }
     }
 }
@@ -46,6 +54,8 @@ impl PbrMaterial {
             ao: 1.0,
             emissive_color: glam::Vec3::ZERO,
             emissive_intensity: 0.0,
+            clearcoat: 0.0,
+            clearcoat_roughness: 0.0,
         }
     }
 
@@ -58,6 +68,8 @@ impl PbrMaterial {
             ao: 1.0,
             emissive_color: glam::Vec3::ZERO,
             emissive_intensity: 0.0,
+            clearcoat: 0.0,
+            clearcoat_roughness: 0.0,
         }
     }
 
@@ -70,6 +82,8 @@ impl PbrMaterial {
             ao: 1.0,
             emissive_color: glam::Vec3::new(1.0, 0.3, 0.0),
             emissive_intensity: 5.0,
+            clearcoat: 0.0,
+            clearcoat_roughness: 0.0,
         }
     }
 
@@ -82,6 +96,22 @@ impl PbrMaterial {
             ao: 1.0,
             emissive_color: glam::Vec3::new(1.0, 0.9, 0.6),
             emissive_intensity: 3.0,
+            clearcoat: 0.0,
+            clearcoat_roughness: 0.0,
+        }
+    }
+
+    /// Painted ship hull: light grey metal under a smooth clearcoat.
+    pub fn ship_hull() -> Self {
+        Self {
+            albedo: glam::Vec3::new(0.75, 0.76, 0.78),
+            metallic: 0.9,
+            roughness: 0.45,
+            ao: 1.0,
+            emissive_color: glam::Vec3::ZERO,
+            emissive_intensity: 0.0,
+            clearcoat: 1.0,
+            clearcoat_roughness: 0.08,
         }
     }
 
@@ -100,26 +130,51 @@ impl PbrMaterial {
         let emissive = self.emissive_output();
         PbrMaterialUniform {
             albedo_metallic: [self.albedo.x, self.albedo.y, self.albedo.z, self.metallic],
-            roughness_ao_pad: [self.roughness, self.ao, 0.0, 0.0],
-            emissive: [emissive.x, emissive.y, emissive.z, 0.0],
+            roughness_ao_clearcoat: [
+                self.roughness,
+                self.ao,
+                self.clearcoat.clamp(0.0, 1.0),
+                self.clearcoat_roughness.clamp(0.0, 1.0),
+            ],
+            emissive: [emissive.x, emissive.y, emissive.z, self.emissive_intensity],
         }
     }
 }
 
-/// GPU-side PBR material uniform, 48 bytes, std140-compatible.
+/// GPU-side PBR material uniform, 48 bytes, std140- and std430-compatible.
 ///
 /// Bound at `@group(3) @binding(0)` visible to `ShaderStages::FRAGMENT`.
+/// Every field is a `vec4<f32>`, so the layout is identical under both
+/// rules and needs no implicit padding:
+///
+/// | Offset | WGSL field                           | Contents                                        |
+/// |--------|--------------------------------------|-------------------------------------------------|
+/// | 0      | `albedo_metallic: vec4<f32>`         | albedo rgb, metallic                            |
+/// | 16     | `roughness_ao_clearcoat: vec4<f32>`  | roughness, ao, clearcoat, clearcoat roughness   |
+/// | 32     | `emissive: vec4<f32>`                | emissive rgb × intensity, intensity             |
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct PbrMaterialUniform {
     /// xyz = albedo (linear RGB), w = metallic.
     pub albedo_metallic: [f32; 4],
-    /// x = roughness, y = ao, zw = padding.
-    pub roughness_ao_pad: [f32; 4],
-    /// xyz = emissive color × intensity (pre-multiplied), w = padding.
+    /// x = roughness, y = ao, z = clearcoat strength, w = clearcoat roughness.
+    pub roughness_ao_clearcoat: [f32; 4],
+    /// xyz = emissive color × intensity (pre-multiplied), w = emissive
+    /// intensity alone.
     pub emissive: [f32; 4],
 }
 
+/// IDs of every registered material that emits light, in ID order.
+///
+/// The bloom pipeline uses this to find surfaces whose HDR output may
+/// exceed the bloom threshold.
+pub fn collect_emissive_materials(registry: &MaterialRegistry) -> Vec<MaterialId> {
+    (0..registry.len())
+        .filter_map(|i| u16::try_from(i).ok().map(MaterialId))
+        .filter(|&id| registry.get(id).is_emissive())
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // CPU-side BRDF reference implementation (for testing)
 // ---------------------------------------------------------------------------
@@ -178,211 +233,66 @@ pub fn evaluate_brdf_cpu(
     (diffuse + specular) * n_dot_l
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_pbr_material_uniform_size() {
-        assert_eq!(std::mem::size_of::<PbrMaterialUniform>(), 48);
-    }
-
-    #[test]
-    fn test_pbr_material_default() {
-        let mat = PbrMaterial::default();
-        assert!((mat.metallic - 0.0).abs() < 1e-6);
-        assert!((mat.roughness - 0.5).abs() < 1e-6);
-        assert!((mat.ao - 1.0).abs() < 1e-6);
-    }
-
-    #[test]
-    fn test_to_uniform_packs_correctly() {
-        let mat = PbrMaterial {
-            albedo: glam::Vec3::new(1.0, 0.5, 0.25),
-            metallic: 0.8,
-            roughness: 0.3,
-            ao: 0.9,
-            emissive_color: glam::Vec3::ZERO,
-            emissive_intensity: 0.0,
-        };
-        let u = mat.to_uniform();
-        assert!((u.albedo_metallic[0] - 1.0).abs() < 1e-6);
-        assert!((u.albedo_metallic[1] - 0.5).abs() < 1e-6);
-        assert!((u.albedo_metallic[2] - 0.25).abs() < 1e-6);
-        assert!((u.albedo_metallic[3] - 0.8).abs() < 1e-6);
-        assert!((u.roughness_ao_pad[0] - 0.3).abs() < 1e-6);
-        assert!((u.roughness_ao_pad[1] - 0.9).abs() < 1e-6);
-    }
-
-    #[test]
-    fn test_pure_metal_has_no_diffuse() {
-        let result = evaluate_brdf_cpu(
-            glam::Vec3::Y,
-            glam::Vec3::new(0.0, 1.0, 1.0).normalize(),
-            glam::Vec3::Y,
-            glam::Vec3::new(1.0, 0.8, 0.2),
-            1.0,
-            0.5,
-        );
-        let dielectric_result = evaluate_brdf_cpu(
-            glam::Vec3::Y,
-            glam::Vec3::new(0.0, 1.0, 1.0).normalize(),
-            glam::Vec3::Y,
-            glam::Vec3::new(1.0, 0.8, 0.2),
-            0.0,
-            0.5,
-        );
-        assert!(
-            dielectric_result.length() > result.length(),
-            "dielectric ({dielectric_result:?}) should have more total light than metal ({result:?}) due to diffuse",
-        );
-    }
-
-    #[test]
-    fn test_pure_dielectric_has_full_diffuse() {
-        let result = evaluate_brdf_cpu(
-            glam::Vec3::Y,
-            glam::Vec3::Y,
-            glam::Vec3::Y,
-            glam::Vec3::ONE,
-            0.0,
-            1.0,
-        );
-        assert!(
-            result.length() > 0.0,
-            "dielectric should have non-zero output"
-        );
-        assert!(
-            result.x > 0.2,
-            "diffuse contribution should be significant for dielectric"
-        );
-    }
-
-    #[test]
-    fn test_roughness_zero_gives_sharp_specular() {
-        let smooth = evaluate_brdf_cpu(
-            glam::Vec3::Y,
-            glam::Vec3::Y,
-            glam::Vec3::Y,
-            glam::Vec3::ONE,
-            0.5,
-            0.01,
-        );
-        let rough = evaluate_brdf_cpu(
-            glam::Vec3::Y,
-            glam::Vec3::Y,
-            glam::Vec3::Y,
-            glam::Vec3::ONE,
-            0.5,
-            0.99,
-        );
-        assert!(
-            smooth.length() > rough.length(),
-            "smooth ({smooth:?}) should have stronger specular peak than rough ({rough:?})",
-        );
-    }
-
-    #[test]
-    fn test_roughness_one_gives_broad_specular() {
-        let off_angle_light = glam::Vec3::new(0.5, 0.5, 0.0).normalize();
-        let rough = evaluate_brdf_cpu(
-            off_angle_light,
-            glam::Vec3::Y,
-            glam::Vec3::Y,
-            glam::Vec3::ONE,
-            0.5,
-            1.0,
-        );
-        assert!(
-            rough.length() > 0.0,
-            "rough material should scatter light broadly"
-        );
-    }
-
-    #[test]
-    fn test_energy_conservation() {
-        let result = evaluate_brdf_cpu(
-            glam::Vec3::Y,
-            glam::Vec3::Y,
-            glam::Vec3::Y,
-            glam::Vec3::ONE,
-            0.5,
-            0.5,
-        );
-        assert!(
-            result.x <= 1.0 + 1e-6,
-            "R channel ({}) exceeds incoming",
-            result.x
-        );
-        assert!(
-            result.y <= 1.0 + 1e-6,
-            "G channel ({}) exceeds incoming",
-            result.y
-        );
-        assert!(
-            result.z <= 1.0 + 1e-6,
-            "B channel ({}) exceeds incoming",
-            result.z
-        );
-    }
-
-    #[test]
-    fn test_emissive_material_outputs_hdr_values() {
-        let mat = PbrMaterial::lava();
-        let output = mat.emissive_output();
-        assert!(
-            output.x > 1.0,
-            "emissive R ({}) should exceed 1.0 for HDR",
-            output.x
-        );
-        assert!(
-            output.y > 1.0,
-            "emissive G ({}) should exceed 1.0 for HDR",
-            output.y
-        );
-        assert!((output.x - 5.0).abs() < 1e-6);
-        assert!((output.y - 1.5).abs() < 1e-6);
-    }
-
-    #[test]
-    fn test_non_emissive_material_has_zero_emissive() {
-        let mat = PbrMaterial::stone();
-        let output = mat.emissive_output();
-        assert_eq!(output, glam::Vec3::ZERO);
-        assert!(!mat.is_emissive());
-    }
-
-    #[test]
-    fn test_emissive_color_matches_gpu_uniform() {
-        let mat = PbrMaterial::lava();
-        let gpu = mat.to_uniform();
-        let expected = mat.emissive_output();
-        assert!((gpu.emissive[0] - expected.x).abs() < 1e-6);
-        assert!((gpu.emissive[1] - expected.y).abs() < 1e-6);
-        assert!((gpu.emissive[2] - expected.z).abs() < 1e-6);
+/// Clearcoat lobe for a single light (CPU reference).
+///
+/// Returns the coat's specular contribution (× N·L) and the factor
+/// `1 − Fc` by which the coat attenuates the base layer. The coat is a
+/// dielectric (F0 = 0.04) GGX lobe with a Kelemen visibility term.
+pub fn clearcoat_cpu(
+    light_dir: glam::Vec3,
+    view_dir: glam::Vec3,
+    normal: glam::Vec3,
+    clearcoat: f32,
+    clearcoat_roughness: f32,
+) -> (f32, f32) {
+    if clearcoat <= 0.0 {
+        return (0.0, 1.0);
     }
+    let half_vec = (view_dir + light_dir).normalize();
+    let n_dot_l = normal.dot(light_dir).max(0.0);
+    let n_dot_h = normal.dot(half_vec).max(0.0);
+    let l_dot_h = light_dir.dot(half_vec).max(0.0);
 
-    #[test]
-    fn test_bloom_responds_to_emissive_surfaces() {
-        let mat = PbrMaterial::lava();
-        let output = mat.emissive_output();
-        let bloom_threshold = 1.0;
-        let max_channel = output.x.max(output.y).max(output.z);
-        assert!(max_channel > bloom_threshold);
-
-        let stone_output = PbrMaterial::stone().emissive_output();
-        let stone_max = stone_output.x.max(stone_output.y).max(stone_output.z);
-        assert!(stone_max <= bloom_threshold);
-    }
+    let d = distribution_ggx_cpu(n_dot_h, clearcoat_roughness.max(0.01));
+    let v = 0.25 / (l_dot_h * l_dot_h).max(1e-4);
+    let f = (0.04 + 0.96 * (1.0 - l_dot_h).powf(5.0)) * clearcoat;
+    (d * v * f * n_dot_l, 1.0 - f)
+}
 
-    #[test]
-    fn test_ao_reduces_ambient_contribution() {
-        let albedo = glam::Vec3::ONE;
-        let ambient_full = glam::Vec3::splat(0.03) * albedo * 1.0;
-        let ambient_half = glam::Vec3::splat(0.03) * albedo * 0.5;
-        let ambient_zero = glam::Vec3::splat(0.03) * albedo * 0.0;
-        assert!(ambient_full.x > ambient_half.x);
-        assert!(ambient_half.x > ambient_zero.x);
-        assert_eq!(ambient_zero.x, 0.0);
-    }
+/// HDR fragment color for one directional light (CPU reference).
+///
+/// Mirrors `fs_main` in the lit shader without shadows or point lights:
+/// clearcoat-layered BRDF × light radiance, plus ambient × albedo × AO, plus
+/// the material's emissive output. The result is unclamped.
+pub fn shade_hdr_cpu(
+    material: &PbrMaterial,
+    light_dir: glam::Vec3,
+    view_dir: glam::Vec3,
+    normal: glam::Vec3,
+    light_radiance: glam::Vec3,
+    ambient: glam::Vec3,
+) -> glam::Vec3 {
+    let uniform = material.to_uniform();
+    let base = evaluate_brdf_cpu(
+        light_dir,
+        view_dir,
+        normal,
+        material.albedo,
+        material.metallic,
+        material.roughness,
+    );
+    let (coat, base_weight) = clearcoat_cpu(
+        light_dir,
+        view_dir,
+        normal,
+        uniform.roughness_ao_clearcoat[2],
+        uniform.roughness_ao_clearcoat[3],
+    );
+    let direct = (base * base_weight + glam::Vec3::splat(coat)) * light_radiance;
+    let emissive = glam::Vec3::from_slice(&uniform.emissive[..3]);
+    direct + ambient * material.albedo * material.ao + emissive
 }
+
+#[cfg(test)]
+#[path = "pbr_tests.rs"]
+mod tests;
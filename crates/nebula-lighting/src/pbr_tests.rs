@@ -0,0 +1,327 @@
+//! Unit tests for the PBR material model and CPU-side BRDF reference.
+
+use super::*;
+
+#[test]
+fn test_pbr_material_uniform_size() {
+    assert_eq!(std::mem::size_of::<PbrMaterialUniform>(), 48);
+}
+
+#[test]
+fn test_pbr_material_uniform_layout_is_stable() {
+    use std::mem::{align_of, offset_of, size_of};
+    assert_eq!(size_of::<PbrMaterialUniform>() % 16, 0);
+    assert_eq!(align_of::<PbrMaterialUniform>(), 4);
+    let offsets = [
+        offset_of!(PbrMaterialUniform, albedo_metallic),
+        offset_of!(PbrMaterialUniform, roughness_ao_clearcoat),
+        offset_of!(PbrMaterialUniform, emissive),
+    ];
+    assert_eq!(offsets, [0, 16, 32]);
+    assert!(offsets.iter().all(|offset| offset % 16 == 0));
+}
+
+#[test]
+fn test_emissive_intensity_packed_in_w() {
+    let u = PbrMaterial::lava().to_uniform();
+    assert_eq!(u.emissive[3], 5.0);
+}
+
+#[test]
+fn test_clearcoat_adds_glossy_highlight() {
+    let coated = PbrMaterial::ship_hull();
+    let bare = PbrMaterial {
+        clearcoat: 0.0,
+        ..PbrMaterial::ship_hull()
+    };
+    // Mirror configuration: the coat's sharp lobe dominates.
+    let light = glam::Vec3::new(0.0, 1.0, 1.0).normalize();
+    let view = glam::Vec3::new(0.0, 1.0, -1.0).normalize();
+    let shade_one = |mat: &PbrMaterial| {
+        shade_hdr_cpu(
+            mat,
+            light,
+            view,
+            glam::Vec3::Y,
+            glam::Vec3::ONE,
+            glam::Vec3::ZERO,
+        )
+    };
+    assert!(shade_one(&coated).x > shade_one(&bare).x);
+    assert_eq!(
+        clearcoat_cpu(light, view, glam::Vec3::Y, 0.0, 0.1),
+        (0.0, 1.0)
+    );
+}
+
+#[test]
+fn test_pbr_material_default() {
+    let mat = PbrMaterial::default();
+    assert!((mat.metallic - 0.0).abs() < 1e-6);
+    assert!((mat.roughness - 0.5).abs() < 1e-6);
+    assert!((mat.ao - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_to_uniform_packs_correctly() {
+    let mat = PbrMaterial {
+        albedo: glam::Vec3::new(1.0, 0.5, 0.25),
+        metallic: 0.8,
+        roughness: 0.3,
+        ao: 0.9,
+        emissive_color: glam::Vec3::ZERO,
+        emissive_intensity: 0.0,
+        clearcoat: 0.6,
+        clearcoat_roughness: 0.1,
+    };
+    let u = mat.to_uniform();
+    assert!((u.albedo_metallic[0] - 1.0).abs() < 1e-6);
+    assert!((u.albedo_metallic[1] - 0.5).abs() < 1e-6);
+    assert!((u.albedo_metallic[2] - 0.25).abs() < 1e-6);
+    assert!((u.albedo_metallic[3] - 0.8).abs() < 1e-6);
+    assert!((u.roughness_ao_clearcoat[0] - 0.3).abs() < 1e-6);
+    assert!((u.roughness_ao_clearcoat[1] - 0.9).abs() < 1e-6);
+    assert_eq!(u.roughness_ao_clearcoat[2], 0.6);
+    assert_eq!(u.roughness_ao_clearcoat[3], 0.1);
+}
+
+#[test]
+fn test_pure_metal_has_no_diffuse() {
+    let result = evaluate_brdf_cpu(
+        glam::Vec3::Y,
+        glam::Vec3::new(0.0, 1.0, 1.0).normalize(),
+        glam::Vec3::Y,
+        glam::Vec3::new(1.0, 0.8, 0.2),
+        1.0,
+        0.5,
+    );
+    let dielectric_result = evaluate_brdf_cpu(
+        glam::Vec3::Y,
+        glam::Vec3::new(0.0, 1.0, 1.0).normalize(),
+        glam::Vec3::Y,
+        glam::Vec3::new(1.0, 0.8, 0.2),
+        0.0,
+        0.5,
+    );
+    assert!(
+        dielectric_result.length() > result.length(),
+        "dielectric ({dielectric_result:?}) should have more total light than metal ({result:?}) due to diffuse",
+    );
+}
+
+#[test]
+fn test_pure_dielectric_has_full_diffuse() {
+    let result = evaluate_brdf_cpu(
+        glam::Vec3::Y,
+        glam::Vec3::Y,
+        glam::Vec3::Y,
+        glam::Vec3::ONE,
+        0.0,
+        1.0,
+    );
+    assert!(
+        result.length() > 0.0,
+        "dielectric should have non-zero output"
+    );
+    assert!(
+        result.x > 0.2,
+        "diffuse contribution should be significant for dielectric"
+    );
+}
+
+#[test]
+fn test_roughness_zero_gives_sharp_specular() {
+    let smooth = evaluate_brdf_cpu(
+        glam::Vec3::Y,
+        glam::Vec3::Y,
+        glam::Vec3::Y,
+        glam::Vec3::ONE,
+        0.5,
+        0.01,
+    );
+    let rough = evaluate_brdf_cpu(
+        glam::Vec3::Y,
+        glam::Vec3::Y,
+        glam::Vec3::Y,
+        glam::Vec3::ONE,
+        0.5,
+        0.99,
+    );
+    assert!(
+        smooth.length() > rough.length(),
+        "smooth ({smooth:?}) should have stronger specular peak than rough ({rough:?})",
+    );
+}
+
+#[test]
+fn test_roughness_one_gives_broad_specular() {
+    let off_angle_light = glam::Vec3::new(0.5, 0.5, 0.0).normalize();
+    let rough = evaluate_brdf_cpu(
+        off_angle_light,
+        glam::Vec3::Y,
+        glam::Vec3::Y,
+        glam::Vec3::ONE,
+        0.5,
+        1.0,
+    );
+    assert!(
+        rough.length() > 0.0,
+        "rough material should scatter light broadly"
+    );
+}
+
+#[test]
+fn test_energy_conservation() {
+    let result = evaluate_brdf_cpu(
+        glam::Vec3::Y,
+        glam::Vec3::Y,
+        glam::Vec3::Y,
+        glam::Vec3::ONE,
+        0.5,
+        0.5,
+    );
+    assert!(
+        result.x <= 1.0 + 1e-6,
+        "R channel ({}) exceeds incoming",
+        result.x
+    );
+    assert!(
+        result.y <= 1.0 + 1e-6,
+        "G channel ({}) exceeds incoming",
+        result.y
+    );
+    assert!(
+        result.z <= 1.0 + 1e-6,
+        "B channel ({}) exceeds incoming",
+        result.z
+    );
+}
+
+#[test]
+fn test_emissive_material_outputs_hdr_values() {
+    let mat = PbrMaterial::lava();
+    let output = mat.emissive_output();
+    assert!(
+        output.x > 1.0,
+        "emissive R ({}) should exceed 1.0 for HDR",
+        output.x
+    );
+    assert!(
+        output.y > 1.0,
+        "emissive G ({}) should exceed 1.0 for HDR",
+        output.y
+    );
+    assert!((output.x - 5.0).abs() < 1e-6);
+    assert!((output.y - 1.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_non_emissive_material_has_zero_emissive() {
+    let mat = PbrMaterial::stone();
+    let output = mat.emissive_output();
+    assert_eq!(output, glam::Vec3::ZERO);
+    assert!(!mat.is_emissive());
+}
+
+#[test]
+fn test_emissive_color_matches_gpu_uniform() {
+    let mat = PbrMaterial::lava();
+    let gpu = mat.to_uniform();
+    let expected = mat.emissive_output();
+    assert!((gpu.emissive[0] - expected.x).abs() < 1e-6);
+    assert!((gpu.emissive[1] - expected.y).abs() < 1e-6);
+    assert!((gpu.emissive[2] - expected.z).abs() < 1e-6);
+}
+
+#[test]
+fn test_bloom_responds_to_emissive_surfaces() {
+    let mat = PbrMaterial::lava();
+    let output = mat.emissive_output();
+    let bloom_threshold = 1.0;
+    let max_channel = output.x.max(output.y).max(output.z);
+    assert!(max_channel > bloom_threshold);
+
+    let stone_output = PbrMaterial::stone().emissive_output();
+    let stone_max = stone_output.x.max(stone_output.y).max(stone_output.z);
+    assert!(stone_max <= bloom_threshold);
+}
+
+fn shade(mat: &PbrMaterial) -> glam::Vec3 {
+    shade_hdr_cpu(
+        mat,
+        glam::Vec3::new(0.3, 1.0, 0.2).normalize(),
+        glam::Vec3::new(0.0, 1.0, 1.0).normalize(),
+        glam::Vec3::Y,
+        glam::Vec3::splat(1.0),
+        glam::Vec3::splat(0.03),
+    )
+}
+
+#[test]
+fn test_zero_intensity_renders_like_non_emissive() {
+    let plain = PbrMaterial::stone();
+    let dark = PbrMaterial {
+        emissive_color: glam::Vec3::new(1.0, 0.5, 0.0),
+        emissive_intensity: 0.0,
+        ..PbrMaterial::stone()
+    };
+    assert!(!dark.is_emissive());
+    assert_eq!(shade(&dark), shade(&plain));
+}
+
+#[test]
+fn test_intensity_ten_exceeds_one_in_hdr_output() {
+    let mat = PbrMaterial {
+        emissive_color: glam::Vec3::new(0.5, 0.2, 0.1),
+        emissive_intensity: 10.0,
+        ..PbrMaterial::stone()
+    };
+    assert!(mat.is_emissive());
+    let hdr = shade(&mat);
+    assert!(hdr.x > 1.0 && hdr.y > 1.0 && hdr.z > 1.0, "{hdr:?}");
+    assert!(shade(&PbrMaterial::stone()).max_element() < 1.0);
+}
+
+#[test]
+fn test_collect_emissive_materials_finds_glowing_entries() {
+    let dir = tempfile::TempDir::new().unwrap();
+    image::RgbaImage::from_pixel(16, 16, image::Rgba([128, 128, 128, 255]))
+        .save(dir.path().join("t.png"))
+        .unwrap();
+    let entry = |name: &str, intensity: f32| {
+        format!(
+            r#"(name: "{name}", albedo: (0.5, 0.5, 0.5, 1.0), metallic: 0.0,
+            roughness: 0.8, emissive_color: (1.0, 0.4, 0.0),
+            emissive_intensity: {intensity:?}, normal_strength: 1.0,
+            opacity: 1.0, textures: Uniform(texture: "t.png"))"#
+        )
+    };
+    let ron = format!(
+        "MaterialManifest(atlas: AtlasConfig(atlas_size: 256, tile_size: 16), \
+         materials: [{}, {}, {}])",
+        entry("stone", 0.0),
+        entry("lava", 8.0),
+        entry("glowstone", 2.0),
+    );
+    let registry = MaterialRegistry::from_ron_str(&ron, dir.path()).unwrap();
+
+    let emissive = collect_emissive_materials(&registry);
+    assert_eq!(
+        emissive,
+        vec![
+            registry.lookup_by_name("lava").unwrap(),
+            registry.lookup_by_name("glowstone").unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn test_ao_reduces_ambient_contribution() {
+    let albedo = glam::Vec3::ONE;
+    let ambient_full = glam::Vec3::splat(0.03) * albedo * 1.0;
+    let ambient_half = glam::Vec3::splat(0.03) * albedo * 0.5;
+    let ambient_zero = glam::Vec3::splat(0.03) * albedo * 0.0;
+    assert!(ambient_full.x > ambient_half.x);
+    assert!(ambient_half.x > ambient_zero.x);
+    assert_eq!(ambient_zero.x, 0.0);
+}
@@ -3,9 +3,19 @@
 //! Splits the view frustum into multiple depth slices (cascades), each with its
 //! own shadow map covering a progressively larger area at lower resolution.
 //! This provides sharp shadows near the camera and acceptable quality far away.
+//!
+//! With [`CascadedShadowConfig::use_tight_fitting`], each cascade is fitted to
+//! the part of its frustum slice that actually contains scene geometry (see
+//! [`fit_cascade_to_scene`](crate::fit_cascade_to_scene)) instead of the whole slice, so fewer shadow map
+//! texels are spent on empty space.
 
 use bytemuck::{Pod, Zeroable};
 
+use crate::cascade_fit::{
+    PRACTICAL_SPLIT_LAMBDA, WorldAabb, cascade_split_range,
+    compute_cascade_matrix_tight_from_camera, practical_split_distances,
+};
+
 /// Configuration for cascaded shadow mapping.
 #[derive(Clone, Debug)]
 pub struct CascadedShadowConfig {
@@ -13,7 +23,17 @@ pub struct CascadedShadowConfig {
     pub cascade_count: u32,
     /// Far distance of each cascade in meters.
     /// `cascade_far[0] < cascade_far[1] < ... < cascade_far[cascade_count-1]`.
+    ///
+    /// Recomputed from the camera by [`fit_splits`](Self::fit_splits).
     pub cascade_far: [f32; 4],
+    /// Blend between logarithmic (1.0) and uniform (0.0) cascade splits used
+    /// by [`fit_splits`](Self::fit_splits). Default: [`PRACTICAL_SPLIT_LAMBDA`].
+    pub split_lambda: f32,
+    /// Farthest distance in meters covered by the last cascade.
+    /// [`fit_splits`](Self::fit_splits) clamps the camera's far plane to it,
+    /// so a distant far plane does not stretch the cascades over terrain
+    /// too far away to shadow. Default: unlimited.
+    pub max_distance: f32,
     /// Shadow map resolution (width = height) per cascade. Default: 2048.
     pub resolution: u32,
     /// Depth bias to mitigate shadow acne (constant term).
@@ -22,6 +42,9 @@ pub struct CascadedShadowConfig {
     pub depth_bias_slope: f32,
     /// Normal offset bias in texels.
     pub normal_bias_texels: f32,
+    /// Fit each cascade to the scene bounds inside its frustum slice rather
+    /// than to the whole slice. Default: `false`.
+    pub use_tight_fitting: bool,
 }
 
 impl Default for CascadedShadowConfig {
@@ -29,14 +52,51 @@ impl Default for CascadedShadowConfig {
         Self {
             cascade_count: 4,
             cascade_far: [32.0, 128.0, 512.0, 2048.0],
+            split_lambda: PRACTICAL_SPLIT_LAMBDA,
+            max_distance: f32::INFINITY,
             resolution: 2048,
             depth_bias_constant: 1.25,
             depth_bias_slope: 1.75,
             normal_bias_texels: 0.5,
+            use_tight_fitting: false,
         }
     }
 }
 
+impl CascadedShadowConfig {
+    /// Cascade far distances for a camera with the given `near` and `far`
+    /// planes, using the practical split scheme weighted by
+    /// [`split_lambda`](Self::split_lambda).
+    pub fn compute_splits(&self, near: f32, far: f32) -> [f32; 4] {
+        practical_split_distances(near, far, self.cascade_count as usize, self.split_lambda)
+    }
+
+    /// Recompute [`cascade_far`](Self::cascade_far) for the camera's current
+    /// near and far planes, so the cascades follow far-plane changes.
+    ///
+    /// `far` is clamped to [`max_distance`](Self::max_distance).
+    pub fn fit_splits(&mut self, near: f32, far: f32) {
+        self.cascade_far = self.compute_splits(near, self.clamp_far(near, far));
+    }
+
+    /// View-space depth range of cascade `index` for a camera with the
+    /// given `near` and `far` planes: the slice
+    /// [`fit_splits`](Self::fit_splits) assigns to it.
+    pub fn split_range(&self, near: f32, far: f32, index: usize) -> (f32, f32) {
+        cascade_split_range(
+            near,
+            self.clamp_far(near, far),
+            index,
+            self.cascade_count as usize,
+            self.split_lambda,
+        )
+    }
+
+    fn clamp_far(&self, near: f32, far: f32) -> f32 {
+        far.min(self.max_distance).max(near)
+    }
+}
+
 /// GPU-side shadow uniform data (bound in fragment shader).
 ///
 /// Contains 4 light-space matrices, cascade far distances, and cascade count.
@@ -167,6 +227,53 @@ impl CascadedShadowMaps {
         }
     }
 
+    /// Update light matrices from explicit camera parameters, fitting each
+    /// cascade to `scene_aabb` when [`CascadedShadowConfig::use_tight_fitting`]
+    /// is set.
+    pub fn update_matrices_fitted(
+        &mut self,
+        light_dir: glam::Vec3,
+        camera_view: glam::Mat4,
+        fov_y: f32,
+        aspect: f32,
+        camera_near: f32,
+        scene_aabb: &WorldAabb,
+    ) {
+        let count = self.config.cascade_count as usize;
+        for i in 0..count {
+            let near = if i == 0 {
+                camera_near
+            } else {
+                self.config.cascade_far[i - 1]
+            };
+            let far = self.config.cascade_far[i];
+            self.light_matrices[i] = if self.config.use_tight_fitting {
+                compute_cascade_matrix_tight_from_camera(
+                    light_dir,
+                    camera_view,
+                    fov_y,
+                    aspect,
+                    near,
+                    far,
+                    scene_aabb,
+                )
+            } else {
+                compute_cascade_matrix_from_camera(
+                    light_dir,
+                    camera_view,
+                    fov_y,
+                    aspect,
+                    near,
+                    far,
+                    self.config.resolution,
+                )
+            };
+        }
+        for i in count..4 {
+            self.light_matrices[i] = glam::Mat4::IDENTITY;
+        }
+    }
+
     /// Build the GPU uniform from current state.
     pub fn to_uniform(&self) -> ShadowUniform {
         ShadowUniform::from_matrices(&self.config, &self.light_matrices)
@@ -299,112 +406,5 @@ pub fn compute_cascade_matrix_from_camera(
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_shadow_uniform_size() {
-        // 4 mat4 (4×64=256) + vec4 (16) + uvec4 (16) = 288 bytes
-        assert_eq!(std::mem::size_of::<ShadowUniform>(), 288);
-    }
-
-    #[test]
-    fn test_cascade_boundaries_cover_view_frustum() {
-        let config = CascadedShadowConfig::default();
-        assert!(
-            config.cascade_far[0] > 0.0,
-            "first cascade must cover near range"
-        );
-        for i in 1..config.cascade_count as usize {
-            assert!(
-                config.cascade_far[i] > config.cascade_far[i - 1],
-                "cascade {i} must be farther than cascade {}",
-                i - 1
-            );
-        }
-        assert!(
-            config.cascade_far[config.cascade_count as usize - 1] >= 1000.0,
-            "last cascade should cover at least 1000m"
-        );
-    }
-
-    #[test]
-    fn test_shadow_acne_bias_is_positive() {
-        let config = CascadedShadowConfig::default();
-        assert!(config.depth_bias_constant > 0.0);
-        assert!(config.depth_bias_slope > 0.0);
-    }
-
-    #[test]
-    fn test_peter_panning_bias_is_bounded() {
-        let config = CascadedShadowConfig::default();
-        assert!(config.depth_bias_constant < 10.0);
-        assert!(config.normal_bias_texels < 4.0);
-    }
-
-    #[test]
-    fn test_cascade_blending_overlap() {
-        let config = CascadedShadowConfig::default();
-        let blend_fraction = 0.05;
-        for i in 0..config.cascade_count as usize - 1 {
-            let blend_start = config.cascade_far[i] * (1.0 - blend_fraction);
-            let blend_end = config.cascade_far[i];
-            assert!(blend_end > blend_start);
-            assert!(
-                (blend_end - blend_start) >= 1.0,
-                "blend zone for cascade {i} is too narrow: {}m",
-                blend_end - blend_start
-            );
-        }
-    }
-
-    #[test]
-    fn test_light_matrix_is_valid() {
-        let light_dir = glam::Vec3::new(0.3, -1.0, 0.2).normalize();
-        let camera_inv = glam::Mat4::IDENTITY;
-        let matrix = compute_cascade_matrix(light_dir, camera_inv, 0.1, 32.0, 2048);
-        for col in 0..4 {
-            for row in 0..4 {
-                let val = matrix.col(col)[row];
-                assert!(
-                    val.is_finite(),
-                    "light matrix element [{col}][{row}] is not finite: {val}"
-                );
-            }
-        }
-        assert_ne!(matrix, glam::Mat4::IDENTITY);
-    }
-
-    #[test]
-    fn test_shadow_uniform_from_matrices() {
-        let config = CascadedShadowConfig::default();
-        let matrices = [glam::Mat4::IDENTITY; 4];
-        let uniform = ShadowUniform::from_matrices(&config, &matrices);
-        assert_eq!(uniform.cascade_count_pad[0], 4);
-        assert!((uniform.cascade_far[0] - 32.0).abs() < 1e-6);
-    }
-
-    #[test]
-    fn test_cascade_matrix_from_camera() {
-        let light_dir = glam::Vec3::new(0.0, -1.0, 0.3).normalize();
-        let view = glam::Mat4::look_to_rh(
-            glam::Vec3::new(0.0, 10.0, 0.0),
-            glam::Vec3::NEG_Z,
-            glam::Vec3::Y,
-        );
-        let mat = compute_cascade_matrix_from_camera(
-            light_dir,
-            view,
-            std::f32::consts::FRAC_PI_4,
-            16.0 / 9.0,
-            0.1,
-            32.0,
-            2048,
-        );
-        for col in 0..4 {
-            for row in 0..4 {
-                assert!(mat.col(col)[row].is_finite());
-            }
-        }
-    }
-}
+#[path = "shadow_tests.rs"]
+mod tests;
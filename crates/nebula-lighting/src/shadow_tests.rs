@@ -0,0 +1,192 @@
+//! Unit tests for cascaded shadow map configuration and light matrices.
+
+use super::*;
+
+#[test]
+fn test_shadow_uniform_size() {
+    // 4 mat4 (4×64=256) + vec4 (16) + uvec4 (16) = 288 bytes
+    assert_eq!(std::mem::size_of::<ShadowUniform>(), 288);
+}
+
+#[test]
+fn test_cascade_boundaries_cover_view_frustum() {
+    let config = CascadedShadowConfig::default();
+    assert!(
+        config.cascade_far[0] > 0.0,
+        "first cascade must cover near range"
+    );
+    for i in 1..config.cascade_count as usize {
+        assert!(
+            config.cascade_far[i] > config.cascade_far[i - 1],
+            "cascade {i} must be farther than cascade {}",
+            i - 1
+        );
+    }
+    assert!(
+        config.cascade_far[config.cascade_count as usize - 1] >= 1000.0,
+        "last cascade should cover at least 1000m"
+    );
+}
+
+#[test]
+fn test_shadow_acne_bias_is_positive() {
+    let config = CascadedShadowConfig::default();
+    assert!(config.depth_bias_constant > 0.0);
+    assert!(config.depth_bias_slope > 0.0);
+}
+
+#[test]
+fn test_peter_panning_bias_is_bounded() {
+    let config = CascadedShadowConfig::default();
+    assert!(config.depth_bias_constant < 10.0);
+    assert!(config.normal_bias_texels < 4.0);
+}
+
+#[test]
+fn test_cascade_blending_overlap() {
+    let config = CascadedShadowConfig::default();
+    let blend_fraction = 0.05;
+    for i in 0..config.cascade_count as usize - 1 {
+        let blend_start = config.cascade_far[i] * (1.0 - blend_fraction);
+        let blend_end = config.cascade_far[i];
+        assert!(blend_end > blend_start);
+        assert!(
+            (blend_end - blend_start) >= 1.0,
+            "blend zone for cascade {i} is too narrow: {}m",
+            blend_end - blend_start
+        );
+    }
+}
+
+#[test]
+fn test_light_matrix_is_valid() {
+    let light_dir = glam::Vec3::new(0.3, -1.0, 0.2).normalize();
+    let camera_inv = glam::Mat4::IDENTITY;
+    let matrix = compute_cascade_matrix(light_dir, camera_inv, 0.1, 32.0, 2048);
+    for col in 0..4 {
+        for row in 0..4 {
+            let val = matrix.col(col)[row];
+            assert!(
+                val.is_finite(),
+                "light matrix element [{col}][{row}] is not finite: {val}"
+            );
+        }
+    }
+    assert_ne!(matrix, glam::Mat4::IDENTITY);
+}
+
+#[test]
+fn test_shadow_uniform_from_matrices() {
+    let config = CascadedShadowConfig::default();
+    let matrices = [glam::Mat4::IDENTITY; 4];
+    let uniform = ShadowUniform::from_matrices(&config, &matrices);
+    assert_eq!(uniform.cascade_count_pad[0], 4);
+    assert!((uniform.cascade_far[0] - 32.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_cascade_matrix_from_camera() {
+    let light_dir = glam::Vec3::new(0.0, -1.0, 0.3).normalize();
+    let view = glam::Mat4::look_to_rh(
+        glam::Vec3::new(0.0, 10.0, 0.0),
+        glam::Vec3::NEG_Z,
+        glam::Vec3::Y,
+    );
+    let mat = compute_cascade_matrix_from_camera(
+        light_dir,
+        view,
+        std::f32::consts::FRAC_PI_4,
+        16.0 / 9.0,
+        0.1,
+        32.0,
+        2048,
+    );
+    for col in 0..4 {
+        for row in 0..4 {
+            assert!(mat.col(col)[row].is_finite());
+        }
+    }
+}
+
+#[test]
+fn test_computed_splits_increase_and_bracket_camera_range() {
+    let (near, far) = (0.5, 5000.0);
+    for lambda in [0.0, 0.5, PRACTICAL_SPLIT_LAMBDA, 1.0] {
+        let config = CascadedShadowConfig {
+            split_lambda: lambda,
+            ..Default::default()
+        };
+        let splits = config.compute_splits(near, far);
+        assert!(splits[0] > near, "lambda {lambda}: {splits:?}");
+        for i in 1..splits.len() {
+            assert!(splits[i] > splits[i - 1], "lambda {lambda}: {splits:?}");
+        }
+        assert_eq!(splits[3], far);
+    }
+}
+
+#[test]
+fn test_split_range_follows_fitted_splits() {
+    let mut config = CascadedShadowConfig {
+        split_lambda: 0.3,
+        max_distance: 900.0,
+        ..Default::default()
+    };
+    config.fit_splits(0.5, 5000.0);
+    let mut previous_far = 0.5;
+    for i in 0..4 {
+        let (near, far) = config.split_range(0.5, 5000.0, i);
+        assert_eq!(near, previous_far, "cascade {i}");
+        assert_eq!(far, config.cascade_far[i], "cascade {i}");
+        previous_far = far;
+    }
+}
+
+#[test]
+fn test_split_lambda_blends_uniform_and_log() {
+    let mut config = CascadedShadowConfig {
+        split_lambda: 0.0,
+        ..Default::default()
+    };
+    config.fit_splits(1.0, 401.0);
+    assert_eq!(config.cascade_far, [101.0, 201.0, 301.0, 401.0]);
+
+    config.split_lambda = 1.0;
+    config.fit_splits(1.0, 10_000.0);
+    for (split, expected) in config
+        .cascade_far
+        .iter()
+        .zip([10.0, 100.0, 1000.0, 10_000.0])
+    {
+        assert!(
+            (split / expected - 1.0).abs() < 1e-4,
+            "{split} vs {expected}"
+        );
+    }
+}
+
+#[test]
+fn test_fewer_cascades_end_at_far_plane() {
+    let config = CascadedShadowConfig {
+        cascade_count: 2,
+        ..Default::default()
+    };
+    let splits = config.compute_splits(0.1, 1000.0);
+    assert!(splits[0] < 1000.0);
+    assert_eq!(&splits[1..], &[1000.0; 3]);
+}
+
+#[test]
+fn test_fit_splits_clamps_far_plane_to_max_distance() {
+    let mut config = CascadedShadowConfig {
+        max_distance: 256.0,
+        ..Default::default()
+    };
+    config.fit_splits(0.1, 1.0e7);
+    assert_eq!(config.cascade_far[3], 256.0);
+    assert!(config.cascade_far.windows(2).all(|w| w[0] < w[1]));
+
+    // A far plane inside the limit is used as is.
+    config.fit_splits(0.1, 100.0);
+    assert_eq!(config.cascade_far[3], 100.0);
+}
@@ -5,6 +5,10 @@
 //! provides fill light that softens shadows. This module provides
 //! [`LightingContext`] to interpolate between these two regimes based on
 //! altitude, and [`LightingContextUniform`] for GPU upload.
+//!
+//! [`atmospheric_tint`] adds the hue of Rayleigh scattering: blue sky with the
+//! sun high, orange and red toward sunrise and sunset when sunlight crosses
+//! more air.
 
 use bytemuck::{Pod, Zeroable};
 
@@ -69,6 +73,10 @@ pub struct AtmosphereConfig {
     pub atmosphere_start: f64,
     /// Altitude (meters) above which full vacuum applies.
     pub atmosphere_end: f64,
+    /// Rayleigh scattering coefficients per RGB channel at sea level (m⁻¹).
+    pub rayleigh_scattering: glam::Vec3,
+    /// Height (meters) over which air density falls by a factor of e.
+    pub rayleigh_scale_height: f32,
 }
 
 impl Default for AtmosphereConfig {
@@ -76,6 +84,9 @@ impl Default for AtmosphereConfig {
         Self {
             atmosphere_start: 10_000.0,
             atmosphere_end: 100_000.0,
+            // Earth's sea-level coefficients at 680, 550 and 440 nm.
+            rayleigh_scattering: glam::Vec3::new(5.8e-6, 13.5e-6, 33.1e-6),
+            rayleigh_scale_height: 8_000.0,
         }
     }
 }
@@ -123,6 +134,49 @@ pub fn modulate_ambient_by_sun(base_ambient: glam::Vec3, sun_elevation: f32) ->
     base_ambient * factor
 }
 
+/// Relative air mass along a path at `sun_elevation` (sine of the elevation
+/// angle), using the Kasten–Young approximation. 1.0 at zenith, about 38 at
+/// the horizon; the sun below the horizon is treated as on it.
+fn air_mass(sun_elevation: f32) -> f32 {
+    let sin_elevation = sun_elevation.clamp(0.0, 1.0);
+    let degrees = sin_elevation.asin().to_degrees();
+    1.0 / (sin_elevation + 0.50572 * (degrees + 6.07995).powf(-1.6364))
+}
+
+/// Hue of scattered skylight for a sun at `sun_elevation` (the sine of its
+/// elevation, e.g. the `y` of
+/// `nebula_planet::sun_direction_from_time`), normalized so the brightest
+/// channel is 1.0.
+///
+/// Sunlight is attenuated along its slanted path through the atmosphere and
+/// then scattered toward the viewer through one vertical air mass. Short
+/// wavelengths scatter most, so the sky is blue with the sun high; near the
+/// horizon the blue is scattered out of the sunlight before it arrives and the
+/// tint turns orange and red. Brightness is left to
+/// [`modulate_ambient_by_sun`].
+pub fn atmospheric_tint(sun_elevation: f32, config: &AtmosphereConfig) -> glam::Vec3 {
+    let optical_depth = config.rayleigh_scattering * config.rayleigh_scale_height;
+    let sunlight = (-optical_depth * air_mass(sun_elevation)).exp();
+    let scattered = glam::Vec3::ONE - (-optical_depth).exp();
+    let tint = sunlight * scattered;
+    let peak = tint.max_element();
+    if peak > 0.0 && peak.is_finite() {
+        tint / peak
+    } else {
+        glam::Vec3::ONE
+    }
+}
+
+/// Recolor `base_ambient` with the [`atmospheric_tint`] for `sun_elevation`,
+/// keeping its brightest channel.
+pub fn tint_ambient_by_sun(
+    base_ambient: glam::Vec3,
+    sun_elevation: f32,
+    config: &AtmosphereConfig,
+) -> glam::Vec3 {
+    atmospheric_tint(sun_elevation, config) * base_ambient.max_element()
+}
+
 /// GPU-side lighting context uniform, 32 bytes, std140-compatible.
 ///
 /// Uploaded each frame alongside the directional light and shadow uniforms.
@@ -172,10 +226,12 @@ mod tests {
         let config_a = AtmosphereConfig {
             atmosphere_start: 5_000.0,
             atmosphere_end: 50_000.0,
+            ..Default::default()
         };
         let config_b = AtmosphereConfig {
             atmosphere_start: 20_000.0,
             atmosphere_end: 200_000.0,
+            ..Default::default()
         };
         let surface = LightingContext::earth_like_surface();
 
@@ -246,6 +302,50 @@ mod tests {
         assert!((result - expected).length() < 1e-6);
     }
 
+    /// Hue angle in degrees: 0 = red, 30 = orange, 240 = blue.
+    fn hue(c: glam::Vec3) -> f32 {
+        let (r, g, b) = (c.x, c.y, c.z);
+        (3.0_f32.sqrt() * (g - b))
+            .atan2(2.0 * r - g - b)
+            .to_degrees()
+            .rem_euclid(360.0)
+    }
+
+    #[test]
+    fn test_tint_shifts_from_blue_at_zenith_to_red_at_horizon() {
+        let config = AtmosphereConfig::default();
+        // Sun directions as sun_direction_from_time gives them: y is elevation.
+        let zenith = atmospheric_tint(glam::Vec3::Y.y, &config);
+        let horizon = atmospheric_tint(glam::Vec3::X.y, &config);
+
+        assert!(zenith.z > zenith.y && zenith.y > zenith.x, "{zenith}");
+        assert!(horizon.x > horizon.y && horizon.y > horizon.z, "{horizon}");
+        assert!((200.0..260.0).contains(&hue(zenith)), "{}", hue(zenith));
+        assert!(hue(horizon) < 45.0, "{}", hue(horizon));
+        assert_eq!(zenith.max_element(), 1.0);
+        assert_eq!(horizon.max_element(), 1.0);
+
+        // Warming is monotonic as the sun sets.
+        let mut previous = zenith.x / zenith.z;
+        for step in (0..10).rev() {
+            let tint = atmospheric_tint(step as f32 / 10.0, &config);
+            let warmth = tint.x / tint.z;
+            assert!(warmth >= previous, "step {step}");
+            previous = warmth;
+        }
+    }
+
+    #[test]
+    fn test_tint_below_horizon_matches_horizon() {
+        let config = AtmosphereConfig::default();
+        assert_eq!(
+            atmospheric_tint(-0.5, &config),
+            atmospheric_tint(0.0, &config)
+        );
+        let ambient = tint_ambient_by_sun(glam::Vec3::new(0.4, 0.5, 0.7), 1.0, &config);
+        assert!((ambient.max_element() - 0.7).abs() < 1e-6);
+    }
+
     #[test]
     fn test_uniform_packs_correctly() {
         let ctx = LightingContext::earth_like_surface();
@@ -1,47 +1,87 @@
 //! Per-voxel light storage and flood-fill propagation for sunlight and block light.
 //!
-//! Each voxel stores two 4-bit light levels packed into a single byte:
-//! the high nibble for sunlight and the low nibble for block (emissive) light.
+//! Each voxel stores four 4-bit light levels packed into a `u16`: sunlight in
+//! the high nibble, then red, green, and blue block (emissive) light. Sunlight
+//! is always white; block light propagates each color channel independently,
+//! so emitters of different colors blend where their light overlaps.
 //! Light propagates via BFS with -1 decay per step, blocked by opaque voxels.
 
 use std::collections::VecDeque;
 
 use nebula_voxel::{CHUNK_SIZE, ChunkData, Transparency, VoxelTypeRegistry};
 
-/// Packed light value: high nibble = sunlight, low nibble = block light.
+/// Block light color as red, green, and blue levels (0–15 each).
+pub type LightColor = [u8; 3];
+
+/// Packed light value, from the high nibble down: sunlight, red, green, and
+/// blue block light.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-pub struct VoxelLight(pub u8);
+pub struct VoxelLight(pub u16);
 
 impl VoxelLight {
-    /// Maximum light level for either channel.
+    /// Maximum light level for any channel.
     pub const MAX_LEVEL: u8 = 15;
 
+    /// Number of block light color channels.
+    pub const BLOCK_CHANNELS: usize = 3;
+
+    /// Bit offset of the sunlight nibble.
+    const SUN_SHIFT: u32 = 12;
+
     /// Returns the sunlight level (0–15).
     pub fn sunlight(self) -> u8 {
-        (self.0 >> 4) & 0xF
+        ((self.0 >> Self::SUN_SHIFT) & 0xF) as u8
     }
 
-    /// Returns the block light level (0–15).
+    /// Returns the brightest block light channel (0–15).
     pub fn block_light(self) -> u8 {
-        self.0 & 0xF
+        self.block_light_rgb().into_iter().max().unwrap_or(0)
+    }
+
+    /// Returns the block light color.
+    pub fn block_light_rgb(self) -> LightColor {
+        std::array::from_fn(|channel| self.block_channel(channel))
     }
 
     /// Sets the sunlight level (0–15).
     pub fn set_sunlight(&mut self, level: u8) {
         debug_assert!(level <= 15);
-        self.0 = (self.0 & 0x0F) | (level << 4);
+        self.0 = (self.0 & !(0xF << Self::SUN_SHIFT)) | (u16::from(level & 0xF) << Self::SUN_SHIFT);
     }
 
-    /// Sets the block light level (0–15).
+    /// Sets every block light channel to `level` (white light, 0–15).
     pub fn set_block_light(&mut self, level: u8) {
+        self.set_block_light_rgb([level; 3]);
+    }
+
+    /// Sets the block light color.
+    pub fn set_block_light_rgb(&mut self, color: LightColor) {
+        for (channel, level) in color.into_iter().enumerate() {
+            self.set_block_channel(channel, level);
+        }
+    }
+
+    /// Returns one block light channel (0 = red, 1 = green, 2 = blue).
+    pub(crate) fn block_channel(self, channel: usize) -> u8 {
+        ((self.0 >> Self::channel_shift(channel)) & 0xF) as u8
+    }
+
+    /// Sets one block light channel (0 = red, 1 = green, 2 = blue).
+    pub(crate) fn set_block_channel(&mut self, channel: usize, level: u8) {
         debug_assert!(level <= 15);
-        self.0 = (self.0 & 0xF0) | (level & 0x0F);
+        let shift = Self::channel_shift(channel);
+        self.0 = (self.0 & !(0xF << shift)) | (u16::from(level & 0xF) << shift);
+    }
+
+    fn channel_shift(channel: usize) -> u32 {
+        debug_assert!(channel < Self::BLOCK_CHANNELS);
+        8 - 4 * channel as u32
     }
 }
 
 /// Per-voxel light data for a 32×32×32 chunk.
 pub struct ChunkLightMap {
-    /// One byte per voxel: 32×32×32 = 32 768 entries.
+    /// One packed value per voxel: 32×32×32 = 32 768 entries.
     data: Box<[VoxelLight; CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE]>,
 }
 
@@ -185,31 +225,45 @@ pub fn propagate_sunlight(
     propagate_sunlight_bfs(chunk, voxels, registry);
 }
 
+/// Raises each block light channel of `light` to at least `color`, returning
+/// whether any channel changed.
+pub(crate) fn raise_block_light(light: &mut VoxelLight, color: LightColor) -> bool {
+    let mut raised = false;
+    for (channel, level) in color.into_iter().enumerate() {
+        if level > light.block_channel(channel) {
+            light.set_block_channel(channel, level);
+            raised = true;
+        }
+    }
+    raised
+}
+
 /// Propagates block light from the given sources via BFS flood-fill.
 ///
-/// Each source is `(x, y, z, level)`. Light decays by 1 per step through
-/// fully transparent blocks, and by 2 through semi-transparent blocks.
-/// Opaque blocks stop propagation entirely.
+/// Each source is `(x, y, z, color)`. Every color channel spreads
+/// independently: it decays by 1 per step through fully transparent blocks,
+/// and by 2 through semi-transparent blocks. Opaque blocks stop propagation
+/// entirely. Sources never darken a channel that is already brighter.
 pub fn propagate_block_light(
     chunk: &mut ChunkLightMap,
     voxels: &ChunkData,
     registry: &VoxelTypeRegistry,
-    sources: &[(u32, u32, u32, u8)],
+    sources: &[(u32, u32, u32, LightColor)],
 ) {
     let mut queue = VecDeque::new();
 
     // Seed sources.
-    for &(x, y, z, level) in sources {
+    for &(x, y, z, color) in sources {
         let mut l = chunk.get(x, y, z);
-        l.set_block_light(level);
+        raise_block_light(&mut l, color);
         chunk.set(x, y, z, l);
         queue.push_back((x, y, z));
     }
 
     // BFS flood-fill.
     while let Some((x, y, z)) = queue.pop_front() {
-        let current_level = chunk.get(x, y, z).block_light();
-        if current_level <= 1 {
+        let current = chunk.get(x, y, z).block_light_rgb();
+        if current.iter().all(|&level| level <= 1) {
             continue;
         }
 
@@ -223,23 +277,20 @@ pub fn propagate_block_light(
                 continue;
             }
             let decay = 1 + extra_decay(voxels, registry, nx, ny, nz);
-            if current_level <= decay {
-                continue;
-            }
-            let new_level = current_level - decay;
-            if chunk.get(nx, ny, nz).block_light() >= new_level {
-                continue;
-            }
             let mut l = chunk.get(nx, ny, nz);
-            l.set_block_light(new_level);
-            chunk.set(nx, ny, nz, l);
-            queue.push_back((nx, ny, nz));
+            if raise_block_light(&mut l, current.map(|level| level.saturating_sub(decay))) {
+                chunk.set(nx, ny, nz, l);
+                queue.push_back((nx, ny, nz));
+            }
         }
     }
 }
 
 /// Removes block light originating from `(x, y, z)` via reverse BFS, then
 /// re-propagates from any remaining sources in the affected region.
+///
+/// Each color channel is cleared independently, so removing a red emitter
+/// leaves overlapping blue light intact.
 pub fn remove_block_light(
     chunk: &mut ChunkLightMap,
     voxels: &ChunkData,
@@ -248,18 +299,36 @@ pub fn remove_block_light(
     y: u32,
     z: u32,
 ) {
-    let old_level = chunk.get(x, y, z).block_light();
+    let mut relight_queue: Vec<(u32, u32, u32, LightColor)> = Vec::new();
+    for channel in 0..VoxelLight::BLOCK_CHANNELS {
+        remove_block_channel(chunk, channel, (x, y, z), &mut relight_queue);
+    }
+
+    // Phase 2: re-propagate from boundary sources.
+    if !relight_queue.is_empty() {
+        propagate_block_light(chunk, voxels, registry, &relight_queue);
+    }
+}
+
+/// Phase 1 of [`remove_block_light`] for one channel: reverse BFS to clear
+/// light that was propagated from the source, collecting brighter boundary
+/// voxels from other sources into `relight_queue`.
+fn remove_block_channel(
+    chunk: &mut ChunkLightMap,
+    channel: usize,
+    (x, y, z): (u32, u32, u32),
+    relight_queue: &mut Vec<(u32, u32, u32, LightColor)>,
+) {
+    let old_level = chunk.get(x, y, z).block_channel(channel);
     if old_level == 0 {
         return;
     }
 
-    // Phase 1: reverse BFS to clear light that was propagated from this source.
     let mut remove_queue: VecDeque<(u32, u32, u32, u8)> = VecDeque::new();
-    let mut relight_queue: Vec<(u32, u32, u32, u8)> = Vec::new();
 
     // Zero the source.
     let mut l = chunk.get(x, y, z);
-    l.set_block_light(0);
+    l.set_block_channel(channel, 0);
     chunk.set(x, y, z, l);
     remove_queue.push_back((x, y, z, old_level));
 
@@ -270,37 +339,34 @@ pub fn remove_block_light(
                 continue;
             }
             let (nx, ny, nz) = (nx as u32, ny as u32, nz as u32);
-            let neighbor_level = chunk.get(nx, ny, nz).block_light();
+            let mut nl = chunk.get(nx, ny, nz);
+            let neighbor_level = nl.block_channel(channel);
             if neighbor_level == 0 {
                 continue;
             }
             if neighbor_level < level {
                 // This was propagated from the removed source — clear it.
-                let mut nl = chunk.get(nx, ny, nz);
-                nl.set_block_light(0);
+                nl.set_block_channel(channel, 0);
                 chunk.set(nx, ny, nz, nl);
                 remove_queue.push_back((nx, ny, nz, neighbor_level));
             } else {
                 // This is from another source — re-propagate from here.
-                relight_queue.push((nx, ny, nz, neighbor_level));
+                let mut color = [0; 3];
+                color[channel] = neighbor_level;
+                relight_queue.push((nx, ny, nz, color));
             }
         }
     }
-
-    // Phase 2: re-propagate from boundary sources.
-    if !relight_queue.is_empty() {
-        propagate_block_light(chunk, voxels, registry, &relight_queue);
-    }
 }
 
-/// Scans a chunk for emissive voxels and returns their positions and light levels.
+/// Scans a chunk for emissive voxels and returns their positions and light colors.
 ///
 /// Each entry is `(x, y, z, light_emission)` suitable for passing to
 /// [`propagate_block_light`].
 pub fn collect_emissive_sources(
     voxels: &ChunkData,
     registry: &VoxelTypeRegistry,
-) -> Vec<(u32, u32, u32, u8)> {
+) -> Vec<(u32, u32, u32, LightColor)> {
     let mut sources = Vec::new();
     let s = CHUNK_SIZE as u32;
     for y in 0..s {
@@ -308,7 +374,7 @@ pub fn collect_emissive_sources(
             for x in 0..s {
                 let id = voxels.get(x as usize, y as usize, z as usize);
                 let def = registry.get(id);
-                if def.light_emission > 0 {
+                if def.light_emission.iter().any(|&level| level > 0) {
                     sources.push((x, y, z, def.light_emission));
                 }
             }
@@ -317,168 +383,6 @@ pub fn collect_emissive_sources(
     sources
 }
 
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
-
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use nebula_voxel::{Transparency, VoxelTypeDef, VoxelTypeId, VoxelTypeRegistry};
-
-    /// Creates a registry with air(0), stone(1), glass(2).
-    fn test_registry() -> VoxelTypeRegistry {
-        let mut reg = VoxelTypeRegistry::new();
-        reg.register(VoxelTypeDef {
-            name: "stone".to_string(),
-            solid: true,
-            transparency: Transparency::Opaque,
-            material_index: 1,
-            light_emission: 0,
-        })
-        .unwrap();
-        reg.register(VoxelTypeDef {
-            name: "glass".to_string(),
-            solid: true,
-            transparency: Transparency::SemiTransparent,
-            material_index: 2,
-            light_emission: 0,
-        })
-        .unwrap();
-        reg
-    }
-
-    fn make_empty_chunk() -> (ChunkLightMap, ChunkData) {
-        (ChunkLightMap::new_dark(), ChunkData::new_air())
-    }
-
-    #[test]
-    fn test_sunlight_fills_open_area_to_max() {
-        let reg = test_registry();
-        let (mut light, voxels) = make_empty_chunk();
-        propagate_sunlight(&mut light, &voxels, &reg);
-        for y in 0..32 {
-            assert_eq!(
-                light.get(16, y, 16).sunlight(),
-                15,
-                "open area at y={y} should have max sunlight"
-            );
-        }
-    }
-
-    #[test]
-    fn test_block_light_decays_with_distance() {
-        let reg = test_registry();
-        let (mut light, voxels) = make_empty_chunk();
-        propagate_block_light(&mut light, &voxels, &reg, &[(16, 16, 16, 15)]);
-        assert_eq!(light.get(17, 16, 16).block_light(), 14);
-        assert_eq!(light.get(21, 16, 16).block_light(), 10);
-        assert_eq!(light.get(31, 16, 16).block_light(), 0);
-    }
-
-    #[test]
-    fn test_opaque_block_creates_shadow() {
-        let reg = test_registry();
-        let (mut light, mut voxels) = make_empty_chunk();
-        // stone = VoxelTypeId(1)
-        voxels.set(18, 16, 16, VoxelTypeId(1));
-        propagate_block_light(&mut light, &voxels, &reg, &[(16, 16, 16, 15)]);
-        let behind_wall = light.get(19, 16, 16).block_light();
-        let without_wall_equivalent = 15 - 3; // distance 3 = 12
-        assert!(
-            behind_wall < without_wall_equivalent,
-            "block behind wall should have less light ({behind_wall}) than open path ({without_wall_equivalent})"
-        );
-    }
-
-    #[test]
-    fn test_transparent_block_transmits_light() {
-        let reg = test_registry();
-        let (mut light, mut voxels) = make_empty_chunk();
-        // glass = VoxelTypeId(2)
-        voxels.set(17, 16, 16, VoxelTypeId(2));
-        propagate_block_light(&mut light, &voxels, &reg, &[(16, 16, 16, 15)]);
-        let through_glass = light.get(18, 16, 16).block_light();
-        assert!(
-            through_glass > 0,
-            "light should pass through transparent block"
-        );
-    }
-
-    #[test]
-    fn test_light_level_in_valid_range() {
-        let reg = test_registry();
-        let (mut light, voxels) = make_empty_chunk();
-        propagate_block_light(&mut light, &voxels, &reg, &[(16, 16, 16, 15)]);
-        for x in 0..32 {
-            for y in 0..32 {
-                for z in 0..32 {
-                    let bl = light.get(x, y, z).block_light();
-                    let sl = light.get(x, y, z).sunlight();
-                    assert!(bl <= VoxelLight::MAX_LEVEL, "block light {bl} exceeds max");
-                    assert!(sl <= VoxelLight::MAX_LEVEL, "sunlight {sl} exceeds max");
-                }
-            }
-        }
-    }
-
-    #[test]
-    fn test_propagation_handles_chunk_boundaries() {
-        let reg = test_registry();
-        let (mut light, voxels) = make_empty_chunk();
-        propagate_block_light(&mut light, &voxels, &reg, &[(0, 0, 0, 15)]);
-        assert_eq!(light.get(0, 0, 0).block_light(), 15);
-        assert_eq!(light.get(1, 0, 0).block_light(), 14);
-    }
-
-    #[test]
-    fn test_remove_block_light_clears_and_relights() {
-        let reg = test_registry();
-        let (mut light, voxels) = make_empty_chunk();
-        propagate_block_light(&mut light, &voxels, &reg, &[(16, 16, 16, 15)]);
-        assert_eq!(light.get(17, 16, 16).block_light(), 14);
-
-        remove_block_light(&mut light, &voxels, &reg, 16, 16, 16);
-        // After removal the source and neighbours should be dark.
-        assert_eq!(light.get(16, 16, 16).block_light(), 0);
-        assert_eq!(light.get(17, 16, 16).block_light(), 0);
-    }
-
-    #[test]
-    fn test_collect_emissive_sources() {
-        let mut reg = VoxelTypeRegistry::new();
-        reg.register(VoxelTypeDef {
-            name: "glowstone".to_string(),
-            solid: true,
-            transparency: Transparency::Opaque,
-            material_index: 1,
-            light_emission: 14,
-        })
-        .unwrap();
-
-        let glow_id = reg.lookup_by_name("glowstone").unwrap();
-        let mut voxels = ChunkData::new_air();
-        voxels.set(16, 16, 16, glow_id);
-
-        let sources = collect_emissive_sources(&voxels, &reg);
-        assert_eq!(sources.len(), 1);
-        assert_eq!(sources[0], (16, 16, 16, 14));
-    }
-
-    #[test]
-    fn test_sunlight_blocked_by_floor() {
-        let reg = test_registry();
-        let (mut light, mut voxels) = make_empty_chunk();
-        // Place a stone floor at y=16.
-        for x in 0..32u32 {
-            for z in 0..32u32 {
-                voxels.set(x as usize, 16, z as usize, VoxelTypeId(1));
-            }
-        }
-        propagate_sunlight(&mut light, &voxels, &reg);
-        // Above the floor: sunlight = 15.
-        assert_eq!(light.get(16, 17, 16).sunlight(), 15);
-        // Below the floor: sunlight = 0 (no horizontal source nearby).
-        assert_eq!(light.get(16, 15, 16).sunlight(), 0);
-    }
-}
+#[path = "voxel_light_tests.rs"]
+mod tests;
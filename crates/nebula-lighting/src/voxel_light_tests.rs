@@ -0,0 +1,235 @@
+//! Unit tests for voxel light storage and propagation.
+
+use super::*;
+use nebula_voxel::{Transparency, VoxelTypeDef, VoxelTypeId, VoxelTypeRegistry};
+
+/// Creates a registry with air(0), stone(1), glass(2).
+fn test_registry() -> VoxelTypeRegistry {
+    let mut reg = VoxelTypeRegistry::new();
+    reg.register(VoxelTypeDef {
+        name: "stone".to_string(),
+        solid: true,
+        transparency: Transparency::Opaque,
+        material_index: 1,
+        light_emission: [0; 3],
+    })
+    .unwrap();
+    reg.register(VoxelTypeDef {
+        name: "glass".to_string(),
+        solid: true,
+        transparency: Transparency::SemiTransparent,
+        material_index: 2,
+        light_emission: [0; 3],
+    })
+    .unwrap();
+    reg
+}
+
+fn make_empty_chunk() -> (ChunkLightMap, ChunkData) {
+    (ChunkLightMap::new_dark(), ChunkData::new_air())
+}
+
+#[test]
+fn test_sunlight_fills_open_area_to_max() {
+    let reg = test_registry();
+    let (mut light, voxels) = make_empty_chunk();
+    propagate_sunlight(&mut light, &voxels, &reg);
+    for y in 0..32 {
+        assert_eq!(
+            light.get(16, y, 16).sunlight(),
+            15,
+            "open area at y={y} should have max sunlight"
+        );
+    }
+}
+
+#[test]
+fn test_block_light_decays_with_distance() {
+    let reg = test_registry();
+    let (mut light, voxels) = make_empty_chunk();
+    propagate_block_light(&mut light, &voxels, &reg, &[(16, 16, 16, [15; 3])]);
+    assert_eq!(light.get(17, 16, 16).block_light(), 14);
+    assert_eq!(light.get(21, 16, 16).block_light(), 10);
+    assert_eq!(light.get(31, 16, 16).block_light(), 0);
+}
+
+#[test]
+fn test_opaque_block_creates_shadow() {
+    let reg = test_registry();
+    let (mut light, mut voxels) = make_empty_chunk();
+    // stone = VoxelTypeId(1)
+    voxels.set(18, 16, 16, VoxelTypeId(1));
+    propagate_block_light(&mut light, &voxels, &reg, &[(16, 16, 16, [15; 3])]);
+    let behind_wall = light.get(19, 16, 16).block_light();
+    let without_wall_equivalent = 15 - 3; // distance 3 = 12
+    assert!(
+        behind_wall < without_wall_equivalent,
+        "block behind wall should have less light ({behind_wall}) than open path ({without_wall_equivalent})"
+    );
+}
+
+#[test]
+fn test_transparent_block_transmits_light() {
+    let reg = test_registry();
+    let (mut light, mut voxels) = make_empty_chunk();
+    // glass = VoxelTypeId(2)
+    voxels.set(17, 16, 16, VoxelTypeId(2));
+    propagate_block_light(&mut light, &voxels, &reg, &[(16, 16, 16, [15; 3])]);
+    let through_glass = light.get(18, 16, 16).block_light();
+    assert!(
+        through_glass > 0,
+        "light should pass through transparent block"
+    );
+}
+
+#[test]
+fn test_light_level_in_valid_range() {
+    let reg = test_registry();
+    let (mut light, voxels) = make_empty_chunk();
+    propagate_block_light(&mut light, &voxels, &reg, &[(16, 16, 16, [15; 3])]);
+    for x in 0..32 {
+        for y in 0..32 {
+            for z in 0..32 {
+                let bl = light.get(x, y, z).block_light();
+                let sl = light.get(x, y, z).sunlight();
+                assert!(bl <= VoxelLight::MAX_LEVEL, "block light {bl} exceeds max");
+                assert!(sl <= VoxelLight::MAX_LEVEL, "sunlight {sl} exceeds max");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_propagation_handles_chunk_boundaries() {
+    let reg = test_registry();
+    let (mut light, voxels) = make_empty_chunk();
+    propagate_block_light(&mut light, &voxels, &reg, &[(0, 0, 0, [15; 3])]);
+    assert_eq!(light.get(0, 0, 0).block_light(), 15);
+    assert_eq!(light.get(1, 0, 0).block_light(), 14);
+}
+
+#[test]
+fn test_remove_block_light_clears_and_relights() {
+    let reg = test_registry();
+    let (mut light, voxels) = make_empty_chunk();
+    propagate_block_light(&mut light, &voxels, &reg, &[(16, 16, 16, [15; 3])]);
+    assert_eq!(light.get(17, 16, 16).block_light(), 14);
+
+    remove_block_light(&mut light, &voxels, &reg, 16, 16, 16);
+    // After removal the source and neighbours should be dark.
+    assert_eq!(light.get(16, 16, 16).block_light(), 0);
+    assert_eq!(light.get(17, 16, 16).block_light(), 0);
+}
+
+#[test]
+fn test_collect_emissive_sources() {
+    let mut reg = VoxelTypeRegistry::new();
+    reg.register(VoxelTypeDef {
+        name: "glowstone".to_string(),
+        solid: true,
+        transparency: Transparency::Opaque,
+        material_index: 1,
+        light_emission: [14; 3],
+    })
+    .unwrap();
+
+    let glow_id = reg.lookup_by_name("glowstone").unwrap();
+    let mut voxels = ChunkData::new_air();
+    voxels.set(16, 16, 16, glow_id);
+
+    let sources = collect_emissive_sources(&voxels, &reg);
+    assert_eq!(sources.len(), 1);
+    assert_eq!(sources[0], (16, 16, 16, [14; 3]));
+}
+
+#[test]
+fn test_sunlight_blocked_by_floor() {
+    let reg = test_registry();
+    let (mut light, mut voxels) = make_empty_chunk();
+    // Place a stone floor at y=16.
+    for x in 0..32u32 {
+        for z in 0..32u32 {
+            voxels.set(x as usize, 16, z as usize, VoxelTypeId(1));
+        }
+    }
+    propagate_sunlight(&mut light, &voxels, &reg);
+    // Above the floor: sunlight = 15.
+    assert_eq!(light.get(16, 17, 16).sunlight(), 15);
+    // Below the floor: sunlight = 0 (no horizontal source nearby).
+    assert_eq!(light.get(16, 15, 16).sunlight(), 0);
+}
+
+const RED: LightColor = [15, 0, 0];
+const BLUE: LightColor = [0, 0, 15];
+
+#[test]
+fn test_voxel_light_packs_channels_independently() {
+    let mut light = VoxelLight::default();
+    light.set_sunlight(15);
+    light.set_block_light_rgb([3, 7, 11]);
+    assert_eq!(light.sunlight(), 15);
+    assert_eq!(light.block_light_rgb(), [3, 7, 11]);
+    assert_eq!(light.block_light(), 11);
+
+    light.set_block_light(4);
+    assert_eq!(light.block_light_rgb(), [4; 3]);
+    assert_eq!(light.sunlight(), 15);
+}
+
+#[test]
+fn test_red_and_blue_emitters_blend_at_midpoint() {
+    let reg = test_registry();
+    let (mut light, voxels) = make_empty_chunk();
+    propagate_block_light(
+        &mut light,
+        &voxels,
+        &reg,
+        &[(10, 16, 16, RED), (20, 16, 16, BLUE)],
+    );
+
+    // Midpoint: five steps from each emitter.
+    assert_eq!(light.get(15, 16, 16).block_light_rgb(), [10, 0, 10]);
+    // Each channel falls off with its own emitter's distance.
+    assert_eq!(light.get(12, 16, 16).block_light_rgb(), [13, 0, 7]);
+    assert_eq!(light.get(19, 16, 16).block_light_rgb(), [6, 0, 14]);
+    // Off-axis, the Manhattan distance applies per channel.
+    assert_eq!(light.get(15, 18, 16).block_light_rgb(), [8, 0, 8]);
+}
+
+#[test]
+fn test_removing_one_color_keeps_the_other() {
+    let reg = test_registry();
+    let (mut light, voxels) = make_empty_chunk();
+    propagate_block_light(
+        &mut light,
+        &voxels,
+        &reg,
+        &[(10, 16, 16, RED), (20, 16, 16, BLUE)],
+    );
+
+    remove_block_light(&mut light, &voxels, &reg, 10, 16, 16);
+    assert_eq!(light.get(15, 16, 16).block_light_rgb(), [0, 0, 10]);
+    assert_eq!(light.get(10, 16, 16).block_light_rgb(), [0, 0, 5]);
+}
+
+#[test]
+fn test_collect_colored_emissive_sources() {
+    let mut reg = VoxelTypeRegistry::new();
+    reg.register(VoxelTypeDef {
+        name: "lava".to_string(),
+        solid: false,
+        transparency: Transparency::Opaque,
+        material_index: 1,
+        light_emission: [15, 6, 0],
+    })
+    .unwrap();
+
+    let lava = reg.lookup_by_name("lava").unwrap();
+    let mut voxels = ChunkData::new_air();
+    voxels.set(4, 5, 6, lava);
+
+    assert_eq!(
+        collect_emissive_sources(&voxels, &reg),
+        vec![(4, 5, 6, [15, 6, 0])]
+    );
+}
@@ -1,10 +1,31 @@
 //! Per-face quadtree LOD controller that splits/merges nodes based on camera distance.
 
+use std::collections::HashMap;
+
 use glam::DVec3;
-use nebula_cubesphere::{BoundingSphere, ChunkAddress, CubeFace, FaceQuadtree, QuadNode};
+use nebula_cubesphere::{
+    BoundingSphere, ChunkAddress, CubeFace, FaceQuadtree, ProjectionMethod, QuadNode,
+};
 use nebula_math::WorldPosition;
 
 use crate::LodThresholds;
+use crate::face_quadtree_update::{ChangeRecord, UpdatePass, prune_changes};
+use crate::frustum::Frustum;
+use crate::quadtree_snapshot::{QuadtreeSnapshotError, decode_face_leaves};
+
+/// Why a node was split or merged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LodChangeReason {
+    /// The camera crossed the node's promote or demote distance.
+    Distance,
+    /// The node left the view frustum.
+    Frustum,
+    /// A neighbor more than one level finer forced a split.
+    Balance,
+    /// A change held back by the node's cooldown went through once the
+    /// cooldown expired.
+    CooldownExpired,
+}
 
 /// Result of evaluating a node during quadtree traversal.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -12,9 +33,24 @@ pub enum LodAction {
     /// Keep the node as-is.
     Keep,
     /// Split the node into four children (increase detail).
-    Split,
+    Split(LodChangeReason),
     /// Merge the node's children back into a single leaf (decrease detail).
-    Merge,
+    Merge(LodChangeReason),
+}
+
+/// Suggested minimum time between two LOD changes of one node, in seconds,
+/// for callers that advance the clock with
+/// [`FaceQuadtreeLod::tick`].
+pub const RECOMMENDED_LOD_COOLDOWN_SECONDS: f64 = 0.5;
+
+/// How the renderer should draw an active quadtree node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LodChunkKind {
+    /// A regular voxel chunk, streamed and meshed individually.
+    Chunk,
+    /// A far-terrain impostor: one low-resolution heightfield mesh baked
+    /// for the whole node in place of the chunks it covers.
+    MergedPatch,
 }
 
 /// Describes an active (leaf) chunk produced by the quadtree LOD update.
@@ -22,6 +58,8 @@ pub enum LodAction {
 pub struct LodChunkDescriptor {
     /// The chunk address on the cubesphere.
     pub address: ChunkAddress,
+    /// Whether the node is drawn as a chunk or as a merged patch.
+    pub kind: LodChunkKind,
     /// LOD level (0 = finest detail).
     pub lod: u8,
     /// Bounding sphere for this chunk.
@@ -33,7 +71,11 @@ pub struct LodChunkDescriptor {
 /// Per-face quadtree LOD controller.
 ///
 /// Manages a quadtree for one cube face, splitting and merging nodes
-/// based on camera distance each frame.
+/// based on camera distance each frame. With a cooldown enabled (see
+/// [`with_cooldown`](Self::with_cooldown)), a node changes LOD at most once
+/// per cooldown period unless the camera has moved more than the node's
+/// width since its last change, so jitter around a threshold cannot remesh
+/// it every frame.
 pub struct FaceQuadtreeLod {
     /// The underlying quadtree.
     tree: FaceQuadtree,
@@ -43,6 +85,21 @@ pub struct FaceQuadtreeLod {
     thresholds: LodThresholds,
     /// Planet radius in mm (for bounding sphere computation).
     planet_radius: f64,
+    /// Cube-to-sphere mapping of the planet.
+    projection: ProjectionMethod,
+    /// Minimum seconds between two LOD changes of one node.
+    cooldown_seconds: f64,
+    /// Cooldown clock advanced by [`tick`](Self::tick), in seconds.
+    time: f64,
+    /// Number of update passes run so far.
+    pass: u64,
+    /// Last change of each node whose cooldown is still relevant.
+    changes: HashMap<ChunkAddress, ChangeRecord>,
+    /// Splits and merges applied by the last update.
+    actions: Vec<(ChunkAddress, LodAction)>,
+    /// Coarsest address LOD still drawn as chunks; leaves above it are
+    /// emitted as merged patches. `None` disables patches.
+    merged_patch_lod: Option<u8>,
 }
 
 impl FaceQuadtreeLod {
@@ -63,9 +120,63 @@ impl FaceQuadtreeLod {
             max_depth,
             thresholds,
             planet_radius,
+            projection: ProjectionMethod::default(),
+            cooldown_seconds: 0.0,
+            time: 0.0,
+            pass: 0,
+            changes: HashMap::new(),
+            actions: Vec::new(),
+            merged_patch_lod: None,
         }
     }
 
+    /// Emit leaves whose address LOD is above `max_chunk_lod` as
+    /// [`LodChunkKind::MergedPatch`] instead of individual chunks.
+    ///
+    /// A patch lives exactly as long as its node is a leaf: once the node
+    /// splits, its children are emitted instead and the patch can be
+    /// released.
+    pub fn with_merged_patches(mut self, max_chunk_lod: u8) -> Self {
+        self.merged_patch_lod = Some(max_chunk_lod);
+        self
+    }
+
+    /// How a leaf at address LOD `lod` is drawn.
+    fn kind_for(&self, lod: u8) -> LodChunkKind {
+        match self.merged_patch_lod {
+            Some(max_chunk_lod) if lod > max_chunk_lod => LodChunkKind::MergedPatch,
+            _ => LodChunkKind::Chunk,
+        }
+    }
+
+    /// Place node bounds with the planet's cube-to-sphere `projection`
+    /// instead of the default mapping.
+    pub fn with_projection(mut self, projection: ProjectionMethod) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    /// Set the per-node cooldown in seconds. Zero, the default, disables it.
+    ///
+    /// The cooldown runs on the clock advanced by [`tick`](Self::tick); a
+    /// caller that never ticks holds every node after its first change until
+    /// the camera moves more than the node's width.
+    pub fn with_cooldown(mut self, seconds: f64) -> Self {
+        self.cooldown_seconds = seconds.max(0.0);
+        self
+    }
+
+    /// Advance the cooldown clock by `dt` seconds.
+    pub fn tick(&mut self, dt: f64) {
+        self.time += dt.max(0.0);
+    }
+
+    /// Splits and merges applied by the last update, in order, keyed by the
+    /// address of the node that changed.
+    pub fn last_actions(&self) -> &[(ChunkAddress, LodAction)] {
+        &self.actions
+    }
+
     /// Which face this quadtree covers.
     pub fn face(&self) -> CubeFace {
         self.tree.face
@@ -81,104 +192,73 @@ impl FaceQuadtreeLod {
     /// Returns a list of active chunk descriptors (leaf nodes) after
     /// splitting/merging. The camera position is in world coordinates (mm).
     pub fn update(&mut self, camera_pos: &WorldPosition) -> Vec<LodChunkDescriptor> {
+        self.update_inner(camera_pos, None)
+    }
+
+    /// Like [`update`](Self::update), but nodes whose bounding sphere lies
+    /// entirely outside `frustum` are kept at (or merged back to) their
+    /// coarsest level instead of being subdivided by distance.
+    ///
+    /// The balance constraint is still enforced afterwards, so off-screen
+    /// nodes bordering visible ones are split just enough to stay within one
+    /// LOD level of them. `frustum` must be in the same space as the camera
+    /// position (planet-centered mm).
+    pub fn update_with_frustum(
+        &mut self,
+        camera_pos: &WorldPosition,
+        frustum: &Frustum,
+    ) -> Vec<LodChunkDescriptor> {
+        self.update_inner(camera_pos, Some(frustum))
+    }
+
+    fn update_inner(
+        &mut self,
+        camera_pos: &WorldPosition,
+        frustum: Option<&Frustum>,
+    ) -> Vec<LodChunkDescriptor> {
         let cam_dvec3 = DVec3::new(
             camera_pos.x as f64,
             camera_pos.y as f64,
             camera_pos.z as f64,
         );
 
-        // Phase 1: recursive split/merge
-        let max_depth = self.max_depth;
-        let root_lod = self.tree.root.address().lod;
-        Self::update_node(
-            &mut self.tree.root,
-            &cam_dvec3,
-            &self.thresholds,
-            self.planet_radius,
-            max_depth,
-            root_lod,
-        );
+        self.pass += 1;
+        self.actions.clear();
 
-        // Phase 2: balance constraint (max 1 LOD diff between neighbors)
+        // Phase 1: recursive split/merge
+        let mut pass = UpdatePass {
+            cam: cam_dvec3,
+            frustum,
+            thresholds: &self.thresholds,
+            planet_radius: self.planet_radius,
+            projection: self.projection,
+            max_depth: self.max_depth,
+            root_lod: self.tree.root.address().lod,
+            time: self.time,
+            cooldown_seconds: self.cooldown_seconds,
+            pass: self.pass,
+            changes: &mut self.changes,
+            actions: &mut self.actions,
+        };
+        pass.update_node(&mut self.tree.root);
+
+        // Phase 2: balance constraint (max 1 LOD diff between neighbors),
+        // which overrides the cooldown
         self.enforce_balance(&cam_dvec3);
 
+        prune_changes(
+            &mut self.changes,
+            self.time,
+            self.cooldown_seconds,
+            self.pass,
+        );
+
         // Phase 3: collect active leaves
         let mut chunks = Vec::new();
-        Self::collect_leaves(&self.tree.root, &cam_dvec3, self.planet_radius, &mut chunks);
+        self.collect_leaves(&self.tree.root, &cam_dvec3, &mut chunks);
         chunks
     }
 
-    /// Recursively decide whether to split or merge each node.
-    ///
-    /// The quadtree maps `max_depth` levels of subdivision onto the threshold LOD range.
-    /// - Depth 0 (root) → quadtree LOD = `max_depth` (coarsest)
-    /// - Depth `max_depth` → quadtree LOD = 0 (finest)
-    fn update_node(
-        node: &mut QuadNode,
-        cam: &DVec3,
-        thresholds: &LodThresholds,
-        planet_radius: f64,
-        max_depth: u8,
-        root_lod: u8,
-    ) {
-        let addr = node.address();
-        let bs = BoundingSphere::from_chunk(&addr, planet_radius, 0.0, 0.0);
-        let distance = (bs.center - *cam).length();
-
-        // Convert distance to threshold units (thresholds are in meters, distance in mm)
-        let distance_meters = distance / 1000.0;
-
-        // Desired LOD from thresholds (0 = finest, max_lod = coarsest)
-        let desired_lod = select_lod(thresholds, distance_meters);
-
-        // Map node address LOD to quadtree-relative LOD:
-        // depth_from_root = root_lod - addr.lod
-        // quadtree_lod = max_depth - depth_from_root
-        let depth_from_root = root_lod.saturating_sub(addr.lod);
-        let quadtree_lod = max_depth.saturating_sub(depth_from_root);
-
-        match node {
-            QuadNode::Leaf { .. } => {
-                // Split if node is too coarse (quadtree_lod > desired_lod)
-                // and we haven't reached max depth
-                if quadtree_lod > desired_lod && depth_from_root < max_depth && addr.lod > 0 {
-                    node.subdivide();
-                    // Recurse into new children
-                    if let QuadNode::Branch { children, .. } = node {
-                        for child in children.iter_mut() {
-                            Self::update_node(
-                                child,
-                                cam,
-                                thresholds,
-                                planet_radius,
-                                max_depth,
-                                root_lod,
-                            );
-                        }
-                    }
-                }
-            }
-            QuadNode::Branch { children, .. } => {
-                // Merge if node is already fine enough (quadtree_lod <= desired_lod)
-                if quadtree_lod <= desired_lod {
-                    node.merge();
-                } else {
-                    // Recurse into children
-                    for child in children.iter_mut() {
-                        Self::update_node(
-                            child,
-                            cam,
-                            thresholds,
-                            planet_radius,
-                            max_depth,
-                            root_lod,
-                        );
-                    }
-                }
-            }
-        }
-    }
-
     /// Enforce the balance constraint: no two adjacent leaves differ by more than 1 LOD level.
     fn enforce_balance(&mut self, cam: &DVec3) {
         // Iterate until stable
@@ -186,22 +266,33 @@ impl FaceQuadtreeLod {
             let leaves = self.tree.root.all_leaves();
             let mut changed = false;
 
-            // For each leaf, check if any neighbor is >1 LOD coarser, and force-split it
+            // For each leaf, check if any neighbor differs by >1 LOD, and force-split the coarser
             for leaf_addr in &leaves {
                 let neighbors = self.same_face_neighbors(leaf_addr);
                 for neighbor_addr in neighbors {
-                    // If neighbor is >1 LOD coarser than this leaf, force-split
-                    if neighbor_addr.lod > leaf_addr.lod + 1 {
-                        // Find and split the neighbor node
-                        if Self::force_split_at(
-                            &mut self.tree.root,
-                            &neighbor_addr,
-                            cam,
-                            &self.thresholds,
-                            self.planet_radius,
-                        ) {
-                            changed = true;
-                        }
+                    // Split whichever side is >1 LOD coarser. Neighbors are
+                    // sampled at this leaf's resolution, so a coarse leaf can
+                    // see a much finer one that never sees it back; this
+                    // matters where off-screen nodes stay coarse.
+                    let coarser = if neighbor_addr.lod > leaf_addr.lod + 1 {
+                        neighbor_addr
+                    } else if leaf_addr.lod > neighbor_addr.lod + 1 {
+                        *leaf_addr
+                    } else {
+                        continue;
+                    };
+                    if Self::force_split_at(
+                        &mut self.tree.root,
+                        &coarser,
+                        cam,
+                        &self.thresholds,
+                        self.planet_radius,
+                    ) {
+                        self.changes
+                            .insert(coarser, ChangeRecord::new(self.time, *cam));
+                        self.actions
+                            .push((coarser, LodAction::Split(LodChangeReason::Balance)));
+                        changed = true;
                     }
                 }
             }
@@ -265,18 +356,20 @@ impl FaceQuadtreeLod {
     }
 
     /// Collect all leaf nodes as `LodChunkDescriptor`s.
-    fn collect_leaves(
-        node: &QuadNode,
-        cam: &DVec3,
-        planet_radius: f64,
-        out: &mut Vec<LodChunkDescriptor>,
-    ) {
+    fn collect_leaves(&self, node: &QuadNode, cam: &DVec3, out: &mut Vec<LodChunkDescriptor>) {
         match node {
             QuadNode::Leaf { address } => {
-                let bs = BoundingSphere::from_chunk(address, planet_radius, 0.0, 0.0);
+                let bs = BoundingSphere::from_chunk(
+                    address,
+                    self.planet_radius,
+                    0.0,
+                    0.0,
+                    self.projection,
+                );
                 let distance = (bs.center - *cam).length();
                 out.push(LodChunkDescriptor {
                     address: *address,
+                    kind: self.kind_for(address.lod),
                     lod: address.lod,
                     bounding_sphere: bs,
                     distance,
@@ -284,7 +377,7 @@ impl FaceQuadtreeLod {
             }
             QuadNode::Branch { children, .. } => {
                 for child in children.iter() {
-                    Self::collect_leaves(child, cam, planet_radius, out);
+                    self.collect_leaves(child, cam, out);
                 }
             }
         }
@@ -297,10 +390,12 @@ impl FaceQuadtreeLod {
         neighbor_addrs
             .iter()
             .map(|addr| {
-                let bs = BoundingSphere::from_chunk(addr, self.planet_radius, 0.0, 0.0);
+                let bs =
+                    BoundingSphere::from_chunk(addr, self.planet_radius, 0.0, 0.0, self.projection);
                 let distance = (bs.center - cam).length();
                 LodChunkDescriptor {
                     address: *addr,
+                    kind: self.kind_for(addr.lod),
                     lod: addr.lod,
                     bounding_sphere: bs,
                     distance,
@@ -309,160 +404,81 @@ impl FaceQuadtreeLod {
             .collect()
     }
 
-    /// Reset the quadtree to a single root leaf.
-    pub fn reset(&mut self) {
-        self.tree = FaceQuadtree::new(self.tree.face);
-    }
-}
-
-/// Select LOD level from thresholds (mirrors `LodSelector::select_lod`).
-fn select_lod(thresholds: &LodThresholds, distance: f64) -> u8 {
-    for (i, &threshold) in thresholds.thresholds().iter().enumerate() {
-        if distance < threshold {
-            return i as u8;
+    /// Rebuild the tree from a snapshot written by
+    /// [`encode_quadtree_leaves`](crate::encode_quadtree_leaves), then
+    /// reconcile it against `camera_pos` with a regular update.
+    ///
+    /// Leaves that are still valid for the camera are kept, so restoring at
+    /// the camera position the snapshot was taken from changes nothing.
+    /// Returns the active chunks after reconciling; the splits and merges it
+    /// needed are in [`last_actions`](Self::last_actions).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving the tree untouched, if the blob is
+    /// malformed, has no section for this face, or its leaves lie deeper
+    /// than this tree's `max_depth` or do not tile the face exactly.
+    pub fn restore(
+        &mut self,
+        blob: &[u8],
+        camera_pos: &WorldPosition,
+    ) -> Result<Vec<LodChunkDescriptor>, QuadtreeSnapshotError> {
+        let face = self.tree.face;
+        let mut leaves = decode_face_leaves(blob, face)?;
+        let mut tree = FaceQuadtree::new(face);
+        let root_lod = tree.root.address().lod;
+        for leaf in &leaves {
+            if root_lod - leaf.lod > self.max_depth {
+                return Err(QuadtreeSnapshotError::InvalidLeaf {
+                    face,
+                    lod: leaf.lod,
+                    x: leaf.x,
+                    y: leaf.y,
+                });
+            }
+            seed_leaf(&mut tree.root, leaf);
+        }
+        let mut built = tree.root.all_leaves();
+        leaves.sort_unstable();
+        built.sort_unstable();
+        if leaves != built {
+            return Err(QuadtreeSnapshotError::Inconsistent(face));
         }
-    }
-    thresholds.max_lod()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const PLANET_RADIUS: f64 = 6_371_000_000.0; // Earth-like, mm
-
-    fn make_test_quadtree(max_depth: u8) -> FaceQuadtreeLod {
-        FaceQuadtreeLod::new(
-            CubeFace::PosY,
-            max_depth,
-            LodThresholds::default_planet(),
-            PLANET_RADIUS,
-        )
-    }
-
-    /// Camera at the center of a face should subdivide nodes near it deeply.
-    #[test]
-    fn test_camera_at_face_center_subdivides_deeply() {
-        let mut qt = make_test_quadtree(5);
-        // Camera on surface of +Y face (planet_radius mm up)
-        let camera = WorldPosition::new(0, PLANET_RADIUS as i128, 0);
-        let chunks = qt.update(&camera);
-
-        // Should produce multiple chunks (root was split)
-        assert!(
-            chunks.len() > 1,
-            "expected subdivision, got {} chunks",
-            chunks.len()
-        );
 
-        // Closest chunk should have a low LOD (fine detail)
-        let closest = chunks
-            .iter()
-            .min_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap())
-            .unwrap();
-        // The closest chunk should have been subdivided to a finer LOD than the root
-        assert!(
-            closest.lod < ChunkAddress::MAX_LOD,
-            "closest chunk should be subdivided, got lod={}",
-            closest.lod
-        );
+        self.tree = tree;
+        self.changes.clear();
+        Ok(self.update(camera_pos))
     }
 
-    /// Camera very far away should keep the root node unsplit (single coarse chunk).
-    #[test]
-    fn test_camera_far_away_keeps_root_coarse() {
-        let mut qt = make_test_quadtree(5);
-        // Camera very far in space
-        let camera = WorldPosition::new(0, 100_000_000_000_000, 0);
-        let chunks = qt.update(&camera);
-
-        // Should produce very few chunks (root-level leaf)
-        assert!(
-            chunks.len() <= 4,
-            "expected at most 4 coarse chunks, got {}",
-            chunks.len()
-        );
+    /// Reset the quadtree to a single root leaf.
+    pub fn reset(&mut self) {
+        self.tree = FaceQuadtree::new(self.tree.face);
+        self.changes.clear();
+        self.actions.clear();
     }
+}
 
-    /// Moving the camera toward a coarse node should trigger a split.
-    #[test]
-    fn test_moving_camera_triggers_split() {
-        let mut qt = make_test_quadtree(5);
-
-        // Start far away
-        let far_camera = WorldPosition::new(0, 100_000_000_000_000, 0);
-        let chunks_far = qt.update(&far_camera);
-        let count_far = chunks_far.len();
-
-        // Move close to surface
-        let near_camera = WorldPosition::new(0, PLANET_RADIUS as i128, 0);
-        let chunks_near = qt.update(&near_camera);
-        let count_near = chunks_near.len();
-
-        assert!(
-            count_near > count_far,
-            "closer camera should produce more chunks: near={count_near}, far={count_far}"
-        );
+/// Subdivide the nodes above `leaf` until it exists in the tree.
+fn seed_leaf(node: &mut QuadNode, leaf: &ChunkAddress) {
+    let addr = node.address();
+    if addr.lod <= leaf.lod {
+        return;
     }
-
-    /// Neighboring leaf nodes should never differ by more than 1 LOD level.
-    #[test]
-    fn test_quadtree_balance_max_one_lod_difference() {
-        let mut qt = make_test_quadtree(5);
-        // Camera offset on the surface to create LOD variation
-        let camera = WorldPosition::new((PLANET_RADIUS * 0.1) as i128, PLANET_RADIUS as i128, 0);
-        let chunks = qt.update(&camera);
-
-        for chunk in &chunks {
-            let neighbors = qt.leaf_neighbors(chunk);
-            for neighbor in &neighbors {
-                let lod_diff = (chunk.lod as i8 - neighbor.lod as i8).abs();
-                assert!(
-                    lod_diff <= 1,
-                    "LOD difference between neighbors must be <= 1, got {} (lods: {}, {})",
-                    lod_diff,
-                    chunk.lod,
-                    neighbor.lod
-                );
-            }
-        }
+    if node.is_leaf() {
+        node.subdivide();
     }
-
-    /// The quadtree should produce valid chunk addresses.
-    #[test]
-    fn test_chunks_have_valid_addresses() {
-        let mut qt = make_test_quadtree(4);
-        let camera = WorldPosition::new(0, PLANET_RADIUS as i128, 0);
-        let chunks = qt.update(&camera);
-
-        for chunk in &chunks {
-            assert_eq!(chunk.address.face, CubeFace::PosY);
-            assert!(chunk.address.lod <= ChunkAddress::MAX_LOD);
-            let grid = ChunkAddress::grid_size(chunk.address.lod);
-            assert!(chunk.address.x < grid);
-            assert!(chunk.address.y < grid);
+    if let QuadNode::Branch { children, .. } = node {
+        let shift = addr.lod - 1 - leaf.lod;
+        let (x, y) = (leaf.x >> shift, leaf.y >> shift);
+        if let Some(child) = children.iter_mut().find(|child| {
+            let child_addr = child.address();
+            child_addr.x == x && child_addr.y == y
+        }) {
+            seed_leaf(child, leaf);
         }
     }
-
-    /// Reset should return to a single root leaf.
-    #[test]
-    fn test_reset() {
-        let mut qt = make_test_quadtree(5);
-        let camera = WorldPosition::new(0, PLANET_RADIUS as i128, 0);
-        qt.update(&camera);
-        qt.reset();
-        let chunks = qt.update(&WorldPosition::new(0, 100_000_000_000_000, 0));
-        assert_eq!(chunks.len(), 1);
-    }
-
-    /// LodAction enum should be constructable.
-    #[test]
-    fn test_lod_action_variants() {
-        let keep = LodAction::Keep;
-        let split = LodAction::Split;
-        let merge = LodAction::Merge;
-        assert_eq!(keep, LodAction::Keep);
-        assert_eq!(split, LodAction::Split);
-        assert_eq!(merge, LodAction::Merge);
-    }
 }
+
+#[cfg(test)]
+#[path = "face_quadtree_lod_tests.rs"]
+mod tests;
@@ -0,0 +1,396 @@
+//! Unit tests for the per-face quadtree LOD controller.
+
+use super::*;
+
+#[path = "face_quadtree_restore_tests.rs"]
+mod restore;
+
+const PLANET_RADIUS: f64 = 6_371_000_000.0; // Earth-like, mm
+
+fn make_test_quadtree(max_depth: u8) -> FaceQuadtreeLod {
+    FaceQuadtreeLod::new(
+        CubeFace::PosY,
+        max_depth,
+        LodThresholds::default_planet(),
+        PLANET_RADIUS,
+    )
+}
+
+/// Camera at the center of a face should subdivide nodes near it deeply.
+#[test]
+fn test_camera_at_face_center_subdivides_deeply() {
+    let mut qt = make_test_quadtree(5);
+    // Camera on surface of +Y face (planet_radius mm up)
+    let camera = WorldPosition::new(0, PLANET_RADIUS as i128, 0);
+    let chunks = qt.update(&camera);
+
+    // Should produce multiple chunks (root was split)
+    assert!(
+        chunks.len() > 1,
+        "expected subdivision, got {} chunks",
+        chunks.len()
+    );
+
+    // Closest chunk should have a low LOD (fine detail)
+    let closest = chunks
+        .iter()
+        .min_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap())
+        .unwrap();
+    // The closest chunk should have been subdivided to a finer LOD than the root
+    assert!(
+        closest.lod < ChunkAddress::MAX_LOD,
+        "closest chunk should be subdivided, got lod={}",
+        closest.lod
+    );
+}
+
+/// Camera very far away should keep the root node unsplit (single coarse chunk).
+#[test]
+fn test_camera_far_away_keeps_root_coarse() {
+    let mut qt = make_test_quadtree(5);
+    // Camera very far in space
+    let camera = WorldPosition::new(0, 100_000_000_000_000, 0);
+    let chunks = qt.update(&camera);
+
+    // Should produce very few chunks (root-level leaf)
+    assert!(
+        chunks.len() <= 4,
+        "expected at most 4 coarse chunks, got {}",
+        chunks.len()
+    );
+}
+
+/// Moving the camera toward a coarse node should trigger a split.
+#[test]
+fn test_moving_camera_triggers_split() {
+    let mut qt = make_test_quadtree(5);
+
+    // Start far away
+    let far_camera = WorldPosition::new(0, 100_000_000_000_000, 0);
+    let chunks_far = qt.update(&far_camera);
+    let count_far = chunks_far.len();
+
+    // Move close to surface
+    let near_camera = WorldPosition::new(0, PLANET_RADIUS as i128, 0);
+    let chunks_near = qt.update(&near_camera);
+    let count_near = chunks_near.len();
+
+    assert!(
+        count_near > count_far,
+        "closer camera should produce more chunks: near={count_near}, far={count_far}"
+    );
+}
+
+/// Neighboring leaf nodes should never differ by more than 1 LOD level.
+#[test]
+fn test_quadtree_balance_max_one_lod_difference() {
+    let mut qt = make_test_quadtree(5);
+    // Camera offset on the surface to create LOD variation
+    let camera = WorldPosition::new((PLANET_RADIUS * 0.1) as i128, PLANET_RADIUS as i128, 0);
+    let chunks = qt.update(&camera);
+
+    for chunk in &chunks {
+        let neighbors = qt.leaf_neighbors(chunk);
+        for neighbor in &neighbors {
+            let lod_diff = (chunk.lod as i8 - neighbor.lod as i8).abs();
+            assert!(
+                lod_diff <= 1,
+                "LOD difference between neighbors must be <= 1, got {} (lods: {}, {})",
+                lod_diff,
+                chunk.lod,
+                neighbor.lod
+            );
+        }
+    }
+}
+
+/// The quadtree should produce valid chunk addresses.
+#[test]
+fn test_chunks_have_valid_addresses() {
+    let mut qt = make_test_quadtree(4);
+    let camera = WorldPosition::new(0, PLANET_RADIUS as i128, 0);
+    let chunks = qt.update(&camera);
+
+    for chunk in &chunks {
+        assert_eq!(chunk.address.face, CubeFace::PosY);
+        assert!(chunk.address.lod <= ChunkAddress::MAX_LOD);
+        let grid = ChunkAddress::grid_size(chunk.address.lod);
+        assert!(chunk.address.x < grid);
+        assert!(chunk.address.y < grid);
+    }
+}
+
+/// Reset should return to a single root leaf.
+#[test]
+fn test_reset() {
+    let mut qt = make_test_quadtree(5);
+    let camera = WorldPosition::new(0, PLANET_RADIUS as i128, 0);
+    qt.update(&camera);
+    qt.reset();
+    let chunks = qt.update(&WorldPosition::new(0, 100_000_000_000_000, 0));
+    assert_eq!(chunks.len(), 1);
+}
+
+/// LodAction enum should be constructable.
+#[test]
+fn test_lod_action_variants() {
+    let keep = LodAction::Keep;
+    let split = LodAction::Split(LodChangeReason::Distance);
+    let merge = LodAction::Merge(LodChangeReason::Balance);
+    assert_eq!(keep, LodAction::Keep);
+    assert_eq!(split, LodAction::Split(LodChangeReason::Distance));
+    assert_ne!(merge, LodAction::Merge(LodChangeReason::Distance));
+}
+
+/// Small planet (10 km radius) with thresholds that subdivide its whole +Y
+/// face from orbit. Hysteresis is off so updates depend only on the camera.
+fn make_small_planet_quadtree() -> FaceQuadtreeLod {
+    small_planet_face(CubeFace::PosY, 6)
+}
+
+/// Any face of the small planet, subdividing at most `max_depth` levels.
+fn small_planet_face(face: CubeFace, max_depth: u8) -> FaceQuadtreeLod {
+    FaceQuadtreeLod::new(
+        face,
+        max_depth,
+        LodThresholds::custom(vec![
+            12_000.0, 13_000.0, 14_000.0, 15_000.0, 16_000.0, 17_000.0,
+        ])
+        .with_promote_ratio(1.0),
+        10_000_000.0,
+    )
+}
+
+/// Camera in orbit 10 km above the face center.
+const ORBIT_CAMERA_Y: i128 = 20_000_000;
+
+/// Narrow frustum from the orbiting camera aimed at a spot on the face
+/// away from the sub-camera point.
+fn narrow_orbit_frustum() -> Frustum {
+    let eye = DVec3::new(0.0, ORBIT_CAMERA_Y as f64, 0.0);
+    let target = DVec3::new(5_000_000.0, 10_000_000.0, 0.0).normalize() * 10_000_000.0;
+    let view = glam::DMat4::look_at_rh(eye, target, DVec3::Y);
+    let proj = glam::DMat4::perspective_rh(0.1, 1.0, 1_000.0, 100_000_000.0);
+    Frustum::from_view_projection(&(proj * view))
+}
+
+fn assert_balanced(qt: &FaceQuadtreeLod, chunks: &[LodChunkDescriptor]) {
+    for chunk in chunks {
+        for neighbor in qt.leaf_neighbors(chunk) {
+            let lod_diff = (chunk.lod as i8 - neighbor.lod as i8).abs();
+            assert!(
+                lod_diff <= 1,
+                "unbalanced neighbors: lods {} and {}",
+                chunk.lod,
+                neighbor.lod
+            );
+        }
+    }
+}
+
+/// A narrow frustum should leave off-screen nodes coarse, producing far
+/// fewer chunks than the frustum-agnostic update while staying balanced.
+#[test]
+fn test_frustum_update_skips_offscreen_subdivision() {
+    let camera = WorldPosition::new(0, ORBIT_CAMERA_Y, 0);
+
+    let mut full = make_small_planet_quadtree();
+    let full_chunks = full.update(&camera);
+
+    let mut culled = make_small_planet_quadtree();
+    let culled_chunks = culled.update_with_frustum(&camera, &narrow_orbit_frustum());
+
+    assert!(
+        culled_chunks.len() * 5 < full_chunks.len(),
+        "frustum update should cut the chunk count: {} vs {}",
+        culled_chunks.len(),
+        full_chunks.len()
+    );
+    // Visible chunks still reach full detail.
+    assert_eq!(
+        culled_chunks.iter().map(|c| c.lod).min(),
+        full_chunks.iter().map(|c| c.lod).min()
+    );
+    assert_balanced(&culled, &culled_chunks);
+}
+
+/// Balance holds in the frustum-agnostic update too.
+#[test]
+fn test_full_update_from_orbit_is_balanced() {
+    let mut qt = make_small_planet_quadtree();
+    let chunks = qt.update(&WorldPosition::new(0, ORBIT_CAMERA_Y, 0));
+    assert_balanced(&qt, &chunks);
+}
+
+/// Switching to a frustum update merges nodes that are no longer visible.
+#[test]
+fn test_frustum_update_merges_nodes_leaving_view() {
+    let camera = WorldPosition::new(0, ORBIT_CAMERA_Y, 0);
+    let mut qt = make_small_planet_quadtree();
+    let full_count = qt.update(&camera).len();
+    let culled_count = qt
+        .update_with_frustum(&camera, &narrow_orbit_frustum())
+        .len();
+    assert!(culled_count < full_count);
+
+    let mut fresh = make_small_planet_quadtree();
+    let fresh_count = fresh
+        .update_with_frustum(&camera, &narrow_orbit_frustum())
+        .len();
+    assert_eq!(culled_count, fresh_count);
+}
+
+/// Camera altitude at which a node of the small planet sits on a threshold.
+const THRESHOLD_CAMERA_Y: i128 = 19_120_000;
+
+/// Run `frames` 60 Hz updates with the camera oscillating ±5 m around
+/// [`THRESHOLD_CAMERA_Y`], returning the number of LOD changes per frame.
+fn oscillate(qt: &mut FaceQuadtreeLod, frames: usize) -> Vec<usize> {
+    (0..frames)
+        .map(|frame| {
+            let offset = 5_000.0 * (frame as f64 * 0.9).sin();
+            qt.tick(1.0 / 60.0);
+            qt.update(&WorldPosition::new(
+                0,
+                THRESHOLD_CAMERA_Y + offset as i128,
+                0,
+            ));
+            qt.last_actions().len()
+        })
+        .collect()
+}
+
+/// Without hysteresis or cooldown the oscillation flips LODs constantly;
+/// with them, nothing changes once the tree has settled.
+#[test]
+fn test_oscillating_camera_settles_with_hysteresis() {
+    let mut raw = make_small_planet_quadtree();
+    let raw_changes: usize = oscillate(&mut raw, 120)[10..].iter().sum();
+    assert!(raw_changes > 0, "camera should sit on a threshold");
+
+    let mut qt = FaceQuadtreeLod::new(
+        CubeFace::PosY,
+        6,
+        LodThresholds::custom(vec![
+            12_000.0, 13_000.0, 14_000.0, 15_000.0, 16_000.0, 17_000.0,
+        ]),
+        10_000_000.0,
+    )
+    .with_cooldown(RECOMMENDED_LOD_COOLDOWN_SECONDS);
+    let changes = oscillate(&mut qt, 300);
+    assert!(changes[0] > 0, "first update builds the tree");
+    assert_eq!(changes[60..].iter().sum::<usize>(), 0, "{changes:?}");
+}
+
+/// A node held back by its cooldown changes with `CooldownExpired` once the
+/// cooldown has run out.
+#[test]
+fn test_cooldown_delays_merge_until_expiry() {
+    let mut qt = make_small_planet_quadtree().with_cooldown(1.0);
+    qt.update(&WorldPosition::new(0, ORBIT_CAMERA_Y, 0));
+    // Backing off a little wants merges, but the camera moved less than a
+    // root-level chunk width.
+    let camera = WorldPosition::new(0, ORBIT_CAMERA_Y + 3_000_000, 0);
+    let before = qt.update(&camera).len();
+    let root = qt.tree().root.address();
+    assert!(qt.last_actions().iter().all(|(addr, _)| *addr != root));
+
+    qt.tick(1.5);
+    let after = qt.update(&camera).len();
+    assert!(after < before);
+    assert!(
+        qt.last_actions()
+            .iter()
+            .any(|(_, a)| *a == LodAction::Merge(LodChangeReason::CooldownExpired)),
+        "{:?}",
+        qt.last_actions()
+    );
+}
+
+/// Balance splits go through even for nodes whose cooldown is running.
+#[test]
+fn test_balance_overrides_cooldown() {
+    let mut qt = make_small_planet_quadtree().with_cooldown(1_000.0);
+    qt.update(&WorldPosition::new(0, ORBIT_CAMERA_Y, 0));
+    qt.tick(2_000.0);
+    // Backing off merges nodes, starting their cooldown.
+    qt.update(&WorldPosition::new(0, ORBIT_CAMERA_Y + 1_000_000, 0));
+    let cooling: Vec<ChunkAddress> = qt.changes.keys().copied().collect();
+    assert!(!cooling.is_empty());
+
+    // Swinging over the face edge refines nodes there, and balance must
+    // split cooling-down neighbors to keep up.
+    let chunks = qt.update(&WorldPosition::new(6_000_000, 17_000_000, 0));
+    let forced = qt
+        .last_actions()
+        .iter()
+        .filter(|(addr, action)| {
+            *action == LodAction::Split(LodChangeReason::Balance) && cooling.contains(addr)
+        })
+        .count();
+    assert!(forced > 0, "{:?}", qt.last_actions());
+    assert_balanced(&qt, &chunks);
+}
+
+/// Whether the node at `outer` contains the node at `inner` (or is it).
+fn covers(outer: &ChunkAddress, inner: &ChunkAddress) -> bool {
+    let shift = u32::from(outer.lod.saturating_sub(inner.lod));
+    outer.face == inner.face
+        && outer.lod >= inner.lod
+        && inner.x >> shift == outer.x
+        && inner.y >> shift == outer.y
+}
+
+/// Coarse leaves become merged patches, and a patch never shares the
+/// descriptor stream with chunks it covers while the camera dives in and
+/// climbs back out.
+#[test]
+fn test_merged_patches_never_overlap_their_chunks() {
+    let mut qt = make_small_planet_quadtree().with_merged_patches(16);
+    let altitudes = (0..40).map(|i| 100_000_000 - i * 2_250_000);
+    let mut saw_both = false;
+    for y in altitudes.clone().chain(altitudes.rev()) {
+        let chunks = qt.update(&WorldPosition::new(0, y, 0));
+        for patch in chunks
+            .iter()
+            .filter(|c| c.kind == LodChunkKind::MergedPatch)
+        {
+            assert!(patch.lod > 16);
+            let overlapping = chunks
+                .iter()
+                .filter(|c| covers(&patch.address, &c.address))
+                .count();
+            assert_eq!(overlapping, 1, "patch {:?} overlaps chunks", patch.address);
+        }
+        assert!(
+            chunks
+                .iter()
+                .filter(|c| c.kind == LodChunkKind::Chunk)
+                .all(|c| c.lod <= 16)
+        );
+        saw_both |= chunks.iter().any(|c| c.kind == LodChunkKind::Chunk)
+            && chunks.iter().any(|c| c.kind == LodChunkKind::MergedPatch);
+    }
+    assert!(saw_both, "sweep should mix patches and chunks");
+}
+
+/// Splitting a patch's node replaces the patch with its children.
+#[test]
+fn test_split_releases_merged_patch() {
+    let mut qt = make_small_planet_quadtree().with_merged_patches(16);
+    let far = qt.update(&WorldPosition::new(0, 100_000_000, 0));
+    assert!(far.iter().all(|c| c.kind == LodChunkKind::MergedPatch));
+
+    let near = qt.update(&WorldPosition::new(0, ORBIT_CAMERA_Y, 0));
+    for patch in &far {
+        if near.iter().any(|c| c.address == patch.address) {
+            continue;
+        }
+        assert!(
+            near.iter().any(|c| covers(&patch.address, &c.address)),
+            "split patch {:?} left a hole",
+            patch.address
+        );
+    }
+    assert!(near.iter().any(|c| c.kind == LodChunkKind::Chunk));
+}
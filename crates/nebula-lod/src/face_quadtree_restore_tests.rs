@@ -0,0 +1,147 @@
+//! Unit tests for restoring a [`FaceQuadtreeLod`] from a leaf snapshot.
+
+use super::*;
+
+/// Update at `camera` until the leaf set stops changing, returning it
+/// sorted. Balance can re-split nodes that distance merged in the same pass,
+/// so a settled tree may still report actions.
+fn converge(qt: &mut FaceQuadtreeLod, camera: &WorldPosition) -> Vec<ChunkAddress> {
+    let mut previous = sorted_leaves(qt);
+    for _ in 0..16 {
+        qt.update(camera);
+        let leaves = sorted_leaves(qt);
+        if leaves == previous {
+            return leaves;
+        }
+        previous = leaves;
+    }
+    panic!("quadtree did not converge");
+}
+
+fn sorted_leaves(qt: &FaceQuadtreeLod) -> Vec<ChunkAddress> {
+    let mut leaves = qt.tree().root.all_leaves();
+    leaves.sort_unstable();
+    leaves
+}
+
+/// Restoring at the camera the snapshot was taken from changes nothing.
+#[test]
+fn test_restore_at_same_camera_is_a_no_op() {
+    let camera = WorldPosition::new(4_000_000, 12_000_000, 0);
+    let mut cold = make_small_planet_quadtree();
+    let leaves = converge(&mut cold, &camera);
+    assert!(leaves.len() > 1);
+    let blob = crate::encode_quadtree_leaves([&cold]);
+
+    let mut warm = make_small_planet_quadtree();
+    let chunks = warm.restore(&blob, &camera).unwrap();
+    assert!(warm.last_actions().is_empty());
+    assert_eq!(chunks.len(), leaves.len());
+    assert_eq!(sorted_leaves(&warm), leaves);
+
+    warm.update(&camera);
+    assert!(warm.last_actions().is_empty());
+}
+
+/// A snapshot restored far from where it was taken converges to the same
+/// leaves as a cold build at the new camera.
+#[test]
+fn test_restore_at_far_camera_converges_to_cold_build() {
+    let near = WorldPosition::new(4_000_000, 12_000_000, 0);
+    let far = WorldPosition::new(-9_000_000, 20_000_000, 0);
+    let mut source = make_small_planet_quadtree();
+    converge(&mut source, &near);
+    let blob = crate::encode_quadtree_leaves([&source]);
+
+    let mut warm = make_small_planet_quadtree();
+    warm.restore(&blob, &far).unwrap();
+    let mut cold = make_small_planet_quadtree();
+    assert_eq!(converge(&mut warm, &far), converge(&mut cold, &far));
+}
+
+/// Restoring picks this face's section out of a multi-face snapshot.
+#[test]
+fn test_restore_selects_own_face() {
+    let camera = WorldPosition::new(4_000_000, 12_000_000, 0);
+    let mut faces: Vec<_> = CubeFace::ALL
+        .into_iter()
+        .map(|face| small_planet_face(face, 6))
+        .collect();
+    for qt in &mut faces {
+        converge(qt, &camera);
+    }
+    let blob = crate::encode_quadtree_leaves(&faces);
+
+    let mut warm = small_planet_face(CubeFace::NegX, 6);
+    warm.restore(&blob, &camera).unwrap();
+    assert_eq!(sorted_leaves(&warm), sorted_leaves(&faces[1]));
+}
+
+#[test]
+fn test_restore_rejects_bad_snapshots() {
+    let camera = WorldPosition::new(4_000_000, 12_000_000, 0);
+    let mut source = make_small_planet_quadtree();
+    converge(&mut source, &camera);
+    let blob = crate::encode_quadtree_leaves([&source]);
+    let mut qt = make_small_planet_quadtree();
+
+    assert_eq!(
+        qt.restore(b"nope", &camera).unwrap_err(),
+        QuadtreeSnapshotError::BadMagic
+    );
+    assert_eq!(
+        qt.restore(&blob[..blob.len() - 3], &camera).unwrap_err(),
+        QuadtreeSnapshotError::Truncated
+    );
+
+    let other_face = crate::encode_quadtree_leaves([&small_planet_face(CubeFace::PosX, 6)]);
+    assert_eq!(
+        qt.restore(&other_face, &camera).unwrap_err(),
+        QuadtreeSnapshotError::MissingFace(CubeFace::PosY)
+    );
+
+    // Dropping the last leaf leaves a hole in the face.
+    let mut holed = blob[..blob.len() - 9].to_vec();
+    let count = u32::from_le_bytes(holed[5..9].try_into().unwrap()) - 1;
+    holed[5..9].copy_from_slice(&count.to_le_bytes());
+    assert_eq!(
+        qt.restore(&holed, &camera).unwrap_err(),
+        QuadtreeSnapshotError::Inconsistent(CubeFace::PosY)
+    );
+
+    // A shallower tree cannot hold the saved leaves.
+    let mut shallow = small_planet_face(CubeFace::PosY, 2);
+    assert!(matches!(
+        shallow.restore(&blob, &camera),
+        Err(QuadtreeSnapshotError::InvalidLeaf { .. })
+    ));
+    assert!(
+        shallow.tree().root.is_leaf(),
+        "failed restore changed the tree"
+    );
+}
+
+#[test]
+fn test_snapshot_store_round_trip() {
+    use crate::QuadtreeSnapshotStore;
+    use nebula_cubesphere::PlanetId;
+
+    let dir = std::env::temp_dir().join(format!("nebula-lod-snapshots-{}", std::process::id()));
+    let store = QuadtreeSnapshotStore::new(&dir);
+    let camera = WorldPosition::new(4_000_000, 12_000_000, 0);
+    let mut source = make_small_planet_quadtree();
+    converge(&mut source, &camera);
+
+    assert!(store.load(PlanetId(3), 42).is_none());
+    store.save(PlanetId(3), 42, [&source]).unwrap();
+    assert!(
+        store.load(PlanetId(3), 43).is_none(),
+        "seed is part of the key"
+    );
+
+    let blob = store.load(PlanetId(3), 42).unwrap();
+    let mut warm = make_small_planet_quadtree();
+    warm.restore(&blob, &camera).unwrap();
+    assert_eq!(sorted_leaves(&warm), sorted_leaves(&source));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
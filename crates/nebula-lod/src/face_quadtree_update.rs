@@ -0,0 +1,170 @@
+//! Split/merge pass of [`FaceQuadtreeLod`](crate::FaceQuadtreeLod) and the
+//! per-node cooldown that keeps a node from changing LOD every frame.
+
+use std::collections::HashMap;
+use std::f64::consts::FRAC_PI_2;
+
+use glam::DVec3;
+use nebula_cubesphere::{BoundingSphere, ChunkAddress, ProjectionMethod, QuadNode};
+
+use crate::LodThresholds;
+use crate::face_quadtree_lod::{LodAction, LodChangeReason};
+use crate::frustum::Frustum;
+
+/// The last LOD change of a node, kept while its cooldown runs.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ChangeRecord {
+    /// Clock time of the change, in seconds.
+    time: f64,
+    /// Camera position at the change, in mm.
+    camera: DVec3,
+    /// Last update pass in which the cooldown held back a change.
+    blocked_pass: Option<u64>,
+}
+
+impl ChangeRecord {
+    pub(crate) fn new(time: f64, camera: DVec3) -> Self {
+        Self {
+            time,
+            camera,
+            blocked_pass: None,
+        }
+    }
+}
+
+/// Drop change records whose cooldown has run out, keeping those that held
+/// back a change in update pass `pass` so the change is reported as
+/// [`LodChangeReason::CooldownExpired`] when it goes through.
+pub(crate) fn prune_changes(
+    changes: &mut HashMap<ChunkAddress, ChangeRecord>,
+    time: f64,
+    cooldown_seconds: f64,
+    pass: u64,
+) {
+    changes.retain(|_, c| time - c.time < cooldown_seconds || c.blocked_pass == Some(pass));
+}
+
+/// State shared by the recursive split/merge phase of one update.
+pub(crate) struct UpdatePass<'a> {
+    pub(crate) cam: DVec3,
+    pub(crate) frustum: Option<&'a Frustum>,
+    pub(crate) thresholds: &'a LodThresholds,
+    pub(crate) planet_radius: f64,
+    pub(crate) projection: ProjectionMethod,
+    pub(crate) max_depth: u8,
+    pub(crate) root_lod: u8,
+    pub(crate) time: f64,
+    pub(crate) cooldown_seconds: f64,
+    pub(crate) pass: u64,
+    pub(crate) changes: &'a mut HashMap<ChunkAddress, ChangeRecord>,
+    pub(crate) actions: &'a mut Vec<(ChunkAddress, LodAction)>,
+}
+
+impl UpdatePass<'_> {
+    /// Recursively decide whether to split or merge each node.
+    ///
+    /// The quadtree maps `max_depth` levels of subdivision onto the threshold LOD range.
+    /// - Depth 0 (root) → quadtree LOD = `max_depth` (coarsest)
+    /// - Depth `max_depth` → quadtree LOD = 0 (finest)
+    ///
+    /// A leaf splits once closer than its level's promote distance, and a
+    /// branch merges once its children reach their demote distance, so a
+    /// camera between the two leaves the node alone. Nodes outside
+    /// `frustum` (when given) want the coarsest LOD.
+    pub(crate) fn update_node(&mut self, node: &mut QuadNode) {
+        let addr = node.address();
+        let bs = BoundingSphere::from_chunk(&addr, self.planet_radius, 0.0, 0.0, self.projection);
+        let distance = (bs.center - self.cam).length();
+
+        // Convert distance to threshold units (thresholds are in meters, distance in mm)
+        let distance_meters = distance / 1000.0;
+        let in_view = self
+            .frustum
+            .is_none_or(|f| f.intersects_sphere(bs.center, bs.radius));
+
+        // Map node address LOD to quadtree-relative LOD:
+        // depth_from_root = root_lod - addr.lod
+        // quadtree_lod = max_depth - depth_from_root
+        let depth_from_root = self.root_lod.saturating_sub(addr.lod);
+        let quadtree_lod = self.max_depth.saturating_sub(depth_from_root);
+
+        match node {
+            QuadNode::Leaf { .. } => {
+                // Levels coarser than the thresholds cover always split.
+                let too_coarse = quadtree_lod > self.thresholds.max_lod()
+                    || self
+                        .thresholds
+                        .promote_distance(quadtree_lod)
+                        .is_some_and(|t| distance_meters < t);
+                if !(in_view && too_coarse && depth_from_root < self.max_depth && addr.lod > 0) {
+                    return;
+                }
+                let Some(reason) = self.permit(&addr, LodChangeReason::Distance) else {
+                    return;
+                };
+                node.subdivide();
+                self.record(addr, LodAction::Split(reason));
+                // Recurse into new children
+                if let QuadNode::Branch { children, .. } = node {
+                    for child in children.iter_mut() {
+                        self.update_node(child);
+                    }
+                }
+            }
+            QuadNode::Branch { children, .. } => {
+                let fine_enough = quadtree_lod.checked_sub(1).is_none_or(|child_lod| {
+                    self.thresholds
+                        .demote_distance(child_lod)
+                        .is_some_and(|t| distance_meters >= t)
+                });
+                let wanted = if !in_view {
+                    Some(LodChangeReason::Frustum)
+                } else if fine_enough {
+                    Some(LodChangeReason::Distance)
+                } else {
+                    None
+                };
+                if let Some(wanted) = wanted
+                    && let Some(reason) = self.permit(&addr, wanted)
+                {
+                    node.merge();
+                    self.record(addr, LodAction::Merge(reason));
+                } else {
+                    // Recurse into children
+                    for child in children.iter_mut() {
+                        self.update_node(child);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reason to apply a change to `addr` wanted for `wanted`, or `None`
+    /// while the node's cooldown holds the change back.
+    fn permit(&mut self, addr: &ChunkAddress, wanted: LodChangeReason) -> Option<LodChangeReason> {
+        let Some(change) = self.changes.get_mut(addr) else {
+            return Some(wanted);
+        };
+        // A face spans a quarter of a great circle.
+        let width = self.planet_radius * FRAC_PI_2 / f64::from(ChunkAddress::grid_size(addr.lod));
+        if change.camera.distance(self.cam) > width {
+            return Some(wanted);
+        }
+        if self.time - change.time < self.cooldown_seconds {
+            change.blocked_pass = Some(self.pass);
+            return None;
+        }
+        if change.blocked_pass == Some(self.pass - 1) {
+            Some(LodChangeReason::CooldownExpired)
+        } else {
+            Some(wanted)
+        }
+    }
+
+    /// Start the cooldown of `addr` and report the change.
+    fn record(&mut self, addr: ChunkAddress, action: LodAction) {
+        self.changes
+            .insert(addr, ChangeRecord::new(self.time, self.cam));
+        self.actions.push((addr, action));
+    }
+}
@@ -0,0 +1,230 @@
+//! Frame-time feedback for per-frame chunk work.
+//!
+//! A static chunk budget (such as one picked from camera altitude) is tuned
+//! for a typical machine; on a slow one, draining generation results,
+//! meshing, GPU uploads and collider builds can still overrun the frame. The
+//! [`FrameBudgetScheduler`] is fed the time actually spent on that work each
+//! frame and steers a single throttle with a PI controller so the work
+//! converges on [`FrameBudgetConfig::budget_ms`]. The throttle interpolates
+//! each per-frame count between its configured minimum and maximum, so a
+//! small amount of work always proceeds and loading cannot stall.
+
+use std::time::Duration;
+
+/// Per-frame counts of chunk work.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChunkWorkRates {
+    /// Chunk loads (generation results drained) per frame.
+    pub loads: u32,
+    /// Chunk meshes uploaded to the GPU per frame.
+    pub mesh_uploads: u32,
+    /// Chunk colliders built per frame.
+    pub collider_builds: u32,
+}
+
+/// Time spent on chunk work during one frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChunkWorkTimings {
+    /// Draining finished generation jobs.
+    pub generation: Duration,
+    /// Draining finished meshing jobs.
+    pub meshing: Duration,
+    /// Uploading meshes to the GPU.
+    pub upload: Duration,
+    /// Building chunk colliders.
+    pub colliders: Duration,
+}
+
+impl ChunkWorkTimings {
+    /// Total chunk work this frame.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.generation + self.meshing + self.upload + self.colliders
+    }
+}
+
+/// Tuning for [`FrameBudgetScheduler`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameBudgetConfig {
+    /// Chunk work allowed per frame, in milliseconds.
+    pub budget_ms: f32,
+    /// Counts used when the throttle is fully closed.
+    pub min_rates: ChunkWorkRates,
+    /// Counts used when the throttle is fully open.
+    pub max_rates: ChunkWorkRates,
+    /// Proportional gain on the budget error, as a fraction of the budget.
+    pub kp: f32,
+    /// Integral gain per second on the budget error.
+    pub ki: f32,
+}
+
+impl Default for FrameBudgetConfig {
+    /// 4 ms of chunk work per frame, 1–16 loads, 1–32 mesh uploads and 1–8
+    /// collider builds, with gains that settle within about a second.
+    fn default() -> Self {
+        Self {
+            budget_ms: 4.0,
+            min_rates: ChunkWorkRates {
+                loads: 1,
+                mesh_uploads: 1,
+                collider_builds: 1,
+            },
+            max_rates: ChunkWorkRates {
+                loads: 16,
+                mesh_uploads: 32,
+                collider_builds: 8,
+            },
+            kp: 0.3,
+            ki: 1.5,
+        }
+    }
+}
+
+/// Adapts per-frame chunk work counts to measured chunk work time.
+#[derive(Clone, Debug)]
+pub struct FrameBudgetScheduler {
+    config: FrameBudgetConfig,
+    /// Integral term of the controller, kept within the throttle range so it
+    /// cannot wind up while the output is saturated.
+    integral: f32,
+    throttle: f32,
+    last_work_ms: f32,
+}
+
+impl FrameBudgetScheduler {
+    /// Create a scheduler that starts fully open.
+    #[must_use]
+    pub fn new(config: FrameBudgetConfig) -> Self {
+        Self {
+            config,
+            integral: 1.0,
+            throttle: 1.0,
+            last_work_ms: 0.0,
+        }
+    }
+
+    /// The scheduler's configuration.
+    #[must_use]
+    pub fn config(&self) -> &FrameBudgetConfig {
+        &self.config
+    }
+
+    /// Feed the chunk work measured during a frame of length `dt` seconds
+    /// and return the counts to use next frame.
+    ///
+    /// The error is normalized by the budget and clamped to `[-1, 1]`, so a
+    /// single frame far over budget (a hitch unrelated to chunk work, say)
+    /// pulls the throttle down no harder than one at twice the budget.
+    pub fn record_frame(&mut self, timings: &ChunkWorkTimings, dt: f32) -> ChunkWorkRates {
+        let work_ms = timings.total().as_secs_f32() * 1000.0;
+        self.last_work_ms = work_ms;
+
+        let budget = self.config.budget_ms.max(f32::EPSILON);
+        let error = ((budget - work_ms) / budget).clamp(-1.0, 1.0);
+        self.integral = (self.integral + self.config.ki * error * dt.max(0.0)).clamp(0.0, 1.0);
+        self.throttle = (self.integral + self.config.kp * error).clamp(0.0, 1.0);
+        self.rates()
+    }
+
+    /// Current per-frame counts, for the chunk pipeline and debug overlay.
+    #[must_use]
+    pub fn rates(&self) -> ChunkWorkRates {
+        let lerp = |min: u32, max: u32| {
+            let max = max.max(min);
+            min + ((max - min) as f32 * self.throttle).round() as u32
+        };
+        let (min, max) = (self.config.min_rates, self.config.max_rates);
+        ChunkWorkRates {
+            loads: lerp(min.loads, max.loads),
+            mesh_uploads: lerp(min.mesh_uploads, max.mesh_uploads),
+            collider_builds: lerp(min.collider_builds, max.collider_builds),
+        }
+    }
+
+    /// How far open the throttle is, from `0.0` (minimum rates) to `1.0`
+    /// (maximum rates).
+    #[must_use]
+    pub fn throttle(&self) -> f32 {
+        self.throttle
+    }
+
+    /// Chunk work measured in the most recent frame, in milliseconds.
+    #[must_use]
+    pub fn last_work_ms(&self) -> f32 {
+        self.last_work_ms
+    }
+}
+
+impl Default for FrameBudgetScheduler {
+    fn default() -> Self {
+        Self::new(FrameBudgetConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    /// Simulated chunk work: each counted item costs `cost_ms`.
+    fn simulate(rates: ChunkWorkRates, cost_ms: f32) -> ChunkWorkTimings {
+        let items = rates.loads + rates.mesh_uploads + rates.collider_builds;
+        ChunkWorkTimings {
+            meshing: Duration::from_secs_f32(items as f32 * cost_ms / 1000.0),
+            ..Default::default()
+        }
+    }
+
+    /// Run `frames` frames at `cost_ms` per item, returning the work time of
+    /// each frame.
+    fn run(scheduler: &mut FrameBudgetScheduler, frames: usize, cost_ms: f32) -> Vec<f32> {
+        (0..frames)
+            .map(|_| {
+                let timings = simulate(scheduler.rates(), cost_ms);
+                scheduler.record_frame(&timings, DT);
+                scheduler.last_work_ms()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_over_budget_work_reduces_rates() {
+        let mut scheduler = FrameBudgetScheduler::default();
+        let open = scheduler.rates();
+        // Full rates cost 56 × 0.2 = 11.2 ms against a 4 ms budget.
+        let work = run(&mut scheduler, 60, 0.2);
+        let reduced = scheduler.rates();
+        assert!(reduced.loads < open.loads);
+        assert!(reduced.mesh_uploads < open.mesh_uploads);
+        let last = work[work.len() - 1];
+        assert!(last < 4.0 * 1.2, "still {last} ms after one second");
+    }
+
+    #[test]
+    fn test_under_budget_work_recovers_rates() {
+        let mut scheduler = FrameBudgetScheduler::default();
+        run(&mut scheduler, 120, 0.2);
+        assert!(scheduler.throttle() < 0.5);
+
+        run(&mut scheduler, 60, 0.01);
+        assert_eq!(scheduler.rates(), scheduler.config().max_rates);
+    }
+
+    #[test]
+    fn test_steady_state_stays_within_twenty_percent() {
+        let mut scheduler = FrameBudgetScheduler::default();
+        let work = run(&mut scheduler, 600, 0.2);
+        for &ms in &work[300..] {
+            assert!((ms - 4.0).abs() <= 4.0 * 0.2, "{ms} ms at steady state");
+        }
+    }
+
+    #[test]
+    fn test_rates_never_drop_below_minimum() {
+        let mut scheduler = FrameBudgetScheduler::default();
+        run(&mut scheduler, 300, 50.0);
+        assert_eq!(scheduler.throttle(), 0.0);
+        assert_eq!(scheduler.rates(), scheduler.config().min_rates);
+    }
+}
@@ -0,0 +1,76 @@
+//! Double-precision view frustum for LOD decisions.
+//!
+//! The render crate culls in camera-relative f32 space; quadtree LOD works
+//! with planet-scale bounding spheres in millimeters, so it keeps its own
+//! f64 frustum in the same space as [`BoundingSphere`](nebula_cubesphere::BoundingSphere).
+
+use glam::{DMat4, DVec3, DVec4};
+
+/// A view frustum defined by six inward-pointing, normalized planes.
+#[derive(Clone, Debug)]
+pub struct Frustum {
+    /// Left, right, bottom, top, and the two depth planes.
+    planes: [DVec4; 6],
+}
+
+impl Frustum {
+    /// Extract frustum planes from a view-projection matrix with depth in
+    /// `[0, 1]`.
+    ///
+    /// Works for both standard and reverse-Z projections: the two depth
+    /// planes only swap roles.
+    pub fn from_view_projection(vp: &DMat4) -> Self {
+        Self {
+            planes: nebula_math::frustum_planes(vp),
+        }
+    }
+
+    /// Returns `true` unless the sphere lies entirely behind one plane.
+    ///
+    /// Conservative: spheres near frustum corners may be reported as
+    /// intersecting even when they are outside.
+    pub fn intersects_sphere(&self, center: DVec3, radius: f64) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.truncate().dot(center) + plane.w >= -radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn looking_down_neg_z(reverse_z: bool) -> Frustum {
+        let view = DMat4::look_to_rh(DVec3::ZERO, DVec3::NEG_Z, DVec3::Y);
+        let proj = if reverse_z {
+            DMat4::perspective_rh(1.0, 1.0, 1000.0, 1.0)
+        } else {
+            DMat4::perspective_rh(1.0, 1.0, 1.0, 1000.0)
+        };
+        Frustum::from_view_projection(&(proj * view))
+    }
+
+    #[test]
+    fn test_sphere_in_front_intersects() {
+        for reverse_z in [false, true] {
+            let f = looking_down_neg_z(reverse_z);
+            assert!(f.intersects_sphere(DVec3::new(0.0, 0.0, -100.0), 1.0));
+        }
+    }
+
+    #[test]
+    fn test_sphere_behind_or_beyond_far_is_outside() {
+        for reverse_z in [false, true] {
+            let f = looking_down_neg_z(reverse_z);
+            assert!(!f.intersects_sphere(DVec3::new(0.0, 0.0, 100.0), 1.0));
+            assert!(!f.intersects_sphere(DVec3::new(0.0, 0.0, -2000.0), 1.0));
+            assert!(!f.intersects_sphere(DVec3::new(500.0, 0.0, -100.0), 1.0));
+        }
+    }
+
+    #[test]
+    fn test_large_sphere_straddling_plane_intersects() {
+        let f = looking_down_neg_z(false);
+        assert!(f.intersects_sphere(DVec3::new(0.0, 0.0, 50.0), 60.0));
+    }
+}
@@ -0,0 +1,119 @@
+//! Keeps background generation in step with memory-budget eviction.
+//!
+//! [`select_evictions`] picks loaded LOD chunks to drop when over budget, but
+//! the terrain generator may still be generating voxel chunks under those
+//! same columns. [`GenerationBudgetController::evict_and_cancel`] applies the
+//! evictions to the [`MemoryBudgetTracker`] and hands the fully unloaded
+//! chunks to a cancellation hook in one step. The terrain crate sits below
+//! this one, so the generator is reached only through that hook, typically
+//! `|columns| generator.cancel_under_columns(columns)`.
+
+use std::collections::HashMap;
+
+use nebula_cubesphere::ChunkAddress;
+
+use crate::memory_budget::{ChunkEviction, MemoryBudgetTracker, select_evictions};
+use crate::memory_budget_config::EvictionScope;
+
+/// Result of one [`GenerationBudgetController::evict_and_cancel`] call.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EvictionOutcome {
+    /// LOD chunk evictions applied to the tracker, lowest priority first.
+    pub evicted: Vec<ChunkEviction>,
+    /// Generation tasks the hook reported as cancelled.
+    pub cancelled_tasks: usize,
+}
+
+/// Couples [`MemoryBudgetTracker`] eviction with generation cancellation.
+pub struct GenerationBudgetController;
+
+impl GenerationBudgetController {
+    /// Evict chunks until the tracker is within budget and pass every chunk
+    /// evicted with [`EvictionScope::All`] to `cancel`, which cancels the
+    /// pending generation tasks under those columns and returns how many it
+    /// cancelled. `cancel` is not called when nothing is fully unloaded.
+    ///
+    /// Evictions are applied to `tracker`; the caller is still responsible
+    /// for dropping the evicted voxel data and meshes.
+    pub fn evict_and_cancel(
+        tracker: &mut MemoryBudgetTracker,
+        priorities: &HashMap<ChunkAddress, f64>,
+        cancel: impl FnOnce(&[ChunkAddress]) -> usize,
+    ) -> EvictionOutcome {
+        let evicted = select_evictions(tracker, priorities);
+        for eviction in &evicted {
+            tracker.apply_eviction(eviction);
+        }
+
+        let unloaded: Vec<ChunkAddress> = evicted
+            .iter()
+            .filter(|e| e.scope == EvictionScope::All)
+            .map(|e| e.address)
+            .collect();
+        let cancelled_tasks = if unloaded.is_empty() {
+            0
+        } else {
+            cancel(&unloaded)
+        };
+
+        EvictionOutcome {
+            evicted,
+            cancelled_tasks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_budget::ChunkMemoryUsage;
+    use crate::memory_budget_config::MemoryBudgetConfig;
+    use nebula_cubesphere::CubeFace;
+
+    #[test]
+    fn test_evict_and_cancel_hands_unloaded_chunks_to_hook() {
+        let face = CubeFace::PosY;
+        let low = ChunkAddress::new(face, 0, 10, 10);
+        let high = ChunkAddress::new(face, 0, 0, 0);
+        let usage = ChunkMemoryUsage {
+            voxel_bytes: 1024,
+            mesh_bytes: 0,
+            gpu_bytes: 0,
+        };
+        let mut tracker = MemoryBudgetTracker::new(MemoryBudgetConfig {
+            voxel_budget: 1024,
+            mesh_budget: 1024,
+            ..MemoryBudgetConfig::default()
+        });
+        tracker.on_chunk_loaded(low, usage);
+        tracker.on_chunk_loaded(high, usage);
+        let priorities = HashMap::from([(low, 1.0), (high, 100.0)]);
+
+        let mut seen = Vec::new();
+        let outcome =
+            GenerationBudgetController::evict_and_cancel(&mut tracker, &priorities, |columns| {
+                seen = columns.to_vec();
+                2
+            });
+        assert_eq!(
+            outcome.evicted,
+            vec![ChunkEviction {
+                address: low,
+                scope: EvictionScope::All,
+            }]
+        );
+        assert_eq!(seen, vec![low]);
+        assert_eq!(outcome.cancelled_tasks, 2);
+        assert!(!tracker.is_over_budget());
+    }
+
+    #[test]
+    fn test_under_budget_evicts_nothing() {
+        let mut tracker = MemoryBudgetTracker::new(MemoryBudgetConfig::default());
+        let outcome =
+            GenerationBudgetController::evict_and_cancel(&mut tracker, &HashMap::new(), |_| {
+                panic!("nothing was unloaded")
+            });
+        assert_eq!(outcome, EvictionOutcome::default());
+    }
+}
@@ -1,21 +1,39 @@
 //! Level-of-detail management: distance-based LOD selection, transition blending, and LOD quadtree.
 
 mod face_quadtree_lod;
+mod face_quadtree_update;
+mod frame_budget;
+mod frustum;
+mod generation_eviction;
 mod horizon_culling;
 mod memory_budget;
+mod memory_budget_config;
 mod planet_lod;
 mod priority_queue;
+mod quadtree_snapshot;
 mod selector;
 mod transition;
 
-pub use face_quadtree_lod::{FaceQuadtreeLod, LodAction, LodChunkDescriptor};
+pub use face_quadtree_lod::{
+    FaceQuadtreeLod, LodAction, LodChangeReason, LodChunkDescriptor, LodChunkKind,
+    RECOMMENDED_LOD_COOLDOWN_SECONDS,
+};
+pub use frame_budget::{ChunkWorkRates, ChunkWorkTimings, FrameBudgetConfig, FrameBudgetScheduler};
+pub use frustum::Frustum;
+pub use generation_eviction::{EvictionOutcome, GenerationBudgetController};
 pub use horizon_culling::HorizonCuller;
 pub use memory_budget::{
-    ChunkMemoryUsage, MemoryBudgetConfig, MemoryBudgetTracker, select_evictions,
+    BudgetPressure, ChunkEviction, ChunkMemoryUsage, GpuMemoryReporter, MemoryBudgetTracker,
+    MemoryCategory, select_evictions,
+};
+pub use memory_budget_config::{
+    DEFAULT_DROP_ALL_BELOW_PRIORITY, DEFAULT_SOFT_EVICTION_BATCH, DEFAULT_SOFT_FRACTION,
+    EvictionScope, MemoryBudgetConfig,
 };
 pub use planet_lod::{PlanetLodConfig, PlanetLodSelector, PlanetRenderMode};
 pub use priority_queue::{ChunkPriorityFactors, LodPriorityQueue, compute_priority};
-pub use selector::{LodSelector, LodThresholds, chunk_distance_to_camera};
+pub use quadtree_snapshot::{QuadtreeSnapshotError, QuadtreeSnapshotStore, encode_quadtree_leaves};
+pub use selector::{DEFAULT_PROMOTE_RATIO, LodSelector, LodThresholds, chunk_distance_to_camera};
 pub use transition::{
     LodTransitionConfig, LodTransitionManager, LodTransitionState, MorphVertex, smooth_step,
 };
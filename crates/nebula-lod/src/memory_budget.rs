@@ -3,18 +3,48 @@
 //! Provides [`MemoryBudgetTracker`] to monitor approximate memory usage of loaded
 //! chunks and their GPU meshes, and [`select_evictions`] to determine which chunks
 //! to evict when the budget is exceeded.
+//!
+//! Memory is tracked in three [`MemoryCategory`]s: voxel data, CPU mesh data,
+//! and GPU buffers. Each budget has a hard cap and a soft budget at
+//! [`MemoryBudgetConfig::soft_fraction`] of it. Between the two, eviction
+//! trickles out a small batch per tick so memory is reclaimed gradually
+//! instead of in one large spike when the hard cap is reached. Over-budget
+//! mesh and GPU memory is reclaimed by dropping meshes only, keeping voxel
+//! data loaded, unless a chunk is far enough to drop entirely. The tracker's
+//! [`BudgetPressure`] lets chunk loading slow down before that happens.
+//!
+//! Chunks are keyed by cube-sphere [`ChunkAddress`] by default, but any
+//! copyable address works, so the voxel chunk loader can be budgeted too.
 
 use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use nebula_cubesphere::ChunkAddress;
 
+use crate::memory_budget_config::{EvictionScope, MemoryBudgetConfig};
+
+/// A kind of memory with its own budget.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MemoryCategory {
+    /// Chunk voxel data in system memory.
+    Voxel,
+    /// CPU-side chunk mesh data.
+    Mesh,
+    /// GPU buffers: uploaded chunk meshes plus shared pools and textures.
+    Gpu,
+}
+
 /// Approximate memory usage of a loaded chunk.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct ChunkMemoryUsage {
     /// Bytes used by voxel data (palette + bit-packed array).
     pub voxel_bytes: usize,
-    /// Bytes used by the GPU mesh (vertex buffer + index buffer).
+    /// Bytes used by the CPU mesh (vertex buffer + index buffer).
     pub mesh_bytes: usize,
+    /// Bytes of GPU buffers holding the chunk's uploaded mesh.
+    pub gpu_bytes: usize,
 }
 
 impl ChunkMemoryUsage {
@@ -41,66 +71,105 @@ impl ChunkMemoryUsage {
         Self {
             voxel_bytes,
             mesh_bytes,
+            // The uploaded buffers mirror the CPU mesh.
+            gpu_bytes: mesh_bytes,
         }
     }
 
-    /// Total bytes used by this chunk (voxel + mesh).
+    /// Total bytes used by this chunk (voxel + mesh + GPU).
     #[must_use]
     pub fn total(&self) -> usize {
-        self.voxel_bytes + self.mesh_bytes
+        self.voxel_bytes + self.mesh_bytes + self.gpu_bytes
+    }
+
+    /// Bytes used in `category`.
+    #[must_use]
+    pub fn bytes(&self, category: MemoryCategory) -> usize {
+        match category {
+            MemoryCategory::Voxel => self.voxel_bytes,
+            MemoryCategory::Mesh => self.mesh_bytes,
+            MemoryCategory::Gpu => self.gpu_bytes,
+        }
     }
 }
 
-/// Memory budget configuration.
-#[derive(Clone, Debug)]
-pub struct MemoryBudgetConfig {
-    /// Maximum bytes for chunk voxel data. Default: 2 GB.
-    pub voxel_budget: usize,
-    /// Maximum bytes for chunk mesh data. Default: 1 GB.
-    pub mesh_budget: usize,
+/// Memory categories in the order [`select_evictions`] considers them.
+const CATEGORIES: [MemoryCategory; 3] = [
+    MemoryCategory::Voxel,
+    MemoryCategory::Mesh,
+    MemoryCategory::Gpu,
+];
+
+/// How close usage is to the budgets, for throttling new chunk loads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BudgetPressure {
+    /// Every category is under its soft budget.
+    Green,
+    /// Some category is over its soft budget but none over its hard cap.
+    Yellow,
+    /// Some category is over its hard cap.
+    Red,
 }
 
-impl Default for MemoryBudgetConfig {
-    fn default() -> Self {
-        Self {
-            voxel_budget: 2 * 1024 * 1024 * 1024, // 2 GB
-            mesh_budget: 1024 * 1024 * 1024,      // 1 GB
-        }
+impl BudgetPressure {
+    /// Loads to start this tick given the configured `loads_per_tick`:
+    /// all of them at green, three quarters at yellow, half at red. A
+    /// non-zero budget never throttles to zero, so loading cannot stall.
+    #[must_use]
+    pub fn throttle_loads(self, loads_per_tick: u32) -> u32 {
+        let throttled = match self {
+            Self::Green => loads_per_tick,
+            Self::Yellow => loads_per_tick * 3 / 4,
+            Self::Red => loads_per_tick / 2,
+        };
+        throttled.max(loads_per_tick.min(1))
     }
 }
 
-impl MemoryBudgetConfig {
-    /// Create a budget for low-memory systems (e.g., integrated GPU).
-    #[must_use]
-    pub fn low() -> Self {
-        Self {
-            voxel_budget: 512 * 1024 * 1024, // 512 MB
-            mesh_budget: 256 * 1024 * 1024,  // 256 MB
-        }
+/// Shared counter for GPU bytes not attributed to any chunk, such as idle
+/// pooled buffers and textures.
+///
+/// Obtained from [`MemoryBudgetTracker::gpu_reporter`] and cheap to clone, so
+/// it can be moved into a GPU allocator's usage callback:
+///
+/// ```ignore
+/// let reporter = tracker.gpu_reporter();
+/// pool.set_usage_callback(move |usage| {
+///     reporter.report((usage.allocated - usage.in_use) as usize)
+/// });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GpuMemoryReporter(Arc<AtomicUsize>);
+
+impl GpuMemoryReporter {
+    /// Replace the reported byte count.
+    pub fn report(&self, bytes: usize) {
+        self.0.store(bytes, Ordering::Relaxed);
     }
 
-    /// Create a budget for high-end systems.
+    /// Last reported byte count.
     #[must_use]
-    pub fn high() -> Self {
-        Self {
-            voxel_budget: 4 * 1024 * 1024 * 1024, // 4 GB
-            mesh_budget: 2 * 1024 * 1024 * 1024,  // 2 GB
-        }
+    pub fn bytes(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
     }
 }
 
 /// Tracks memory usage across all loaded chunks and enforces budget limits.
-pub struct MemoryBudgetTracker {
+pub struct MemoryBudgetTracker<A = ChunkAddress> {
     config: MemoryBudgetConfig,
     /// Per-chunk memory usage.
-    chunk_usage: HashMap<ChunkAddress, ChunkMemoryUsage>,
+    chunk_usage: HashMap<A, ChunkMemoryUsage>,
     /// Running total of voxel data bytes.
     total_voxel_bytes: usize,
     /// Running total of mesh data bytes.
     total_mesh_bytes: usize,
+    /// Running total of per-chunk GPU bytes.
+    chunk_gpu_bytes: usize,
+    /// GPU bytes outside any chunk, fed by allocator callbacks.
+    shared_gpu: GpuMemoryReporter,
 }
 
-impl MemoryBudgetTracker {
+impl<A: Copy + Eq + Hash> MemoryBudgetTracker<A> {
     /// Create a new tracker with the given budget configuration.
     #[must_use]
     pub fn new(config: MemoryBudgetConfig) -> Self {
@@ -109,33 +178,118 @@ impl MemoryBudgetTracker {
             chunk_usage: HashMap::new(),
             total_voxel_bytes: 0,
             total_mesh_bytes: 0,
+            chunk_gpu_bytes: 0,
+            shared_gpu: GpuMemoryReporter::default(),
         }
     }
 
     /// Record that a chunk has been loaded with the given memory usage.
-    pub fn on_chunk_loaded(&mut self, address: ChunkAddress, usage: ChunkMemoryUsage) {
+    pub fn on_chunk_loaded(&mut self, address: A, usage: ChunkMemoryUsage) {
         if let Some(old) = self.chunk_usage.insert(address, usage) {
             // Replacing an existing entry — subtract old usage first
-            self.total_voxel_bytes -= old.voxel_bytes;
-            self.total_mesh_bytes -= old.mesh_bytes;
+            self.subtract(&old);
         }
         self.total_voxel_bytes += usage.voxel_bytes;
         self.total_mesh_bytes += usage.mesh_bytes;
+        self.chunk_gpu_bytes += usage.gpu_bytes;
     }
 
     /// Record that a chunk has been unloaded.
-    pub fn on_chunk_unloaded(&mut self, address: &ChunkAddress) {
+    pub fn on_chunk_unloaded(&mut self, address: &A) {
         if let Some(usage) = self.chunk_usage.remove(address) {
-            self.total_voxel_bytes -= usage.voxel_bytes;
+            self.subtract(&usage);
+        }
+    }
+
+    /// Record that a chunk's CPU mesh and GPU buffers were dropped while its
+    /// voxel data stays loaded.
+    pub fn on_mesh_dropped(&mut self, address: &A) {
+        if let Some(usage) = self.chunk_usage.get_mut(address) {
             self.total_mesh_bytes -= usage.mesh_bytes;
+            self.chunk_gpu_bytes -= usage.gpu_bytes;
+            usage.mesh_bytes = 0;
+            usage.gpu_bytes = 0;
+        }
+    }
+
+    /// Record that `eviction` was carried out.
+    pub fn apply_eviction(&mut self, eviction: &ChunkEviction<A>) {
+        match eviction.scope {
+            EvictionScope::Mesh => self.on_mesh_dropped(&eviction.address),
+            EvictionScope::All => self.on_chunk_unloaded(&eviction.address),
         }
     }
 
-    /// Check whether either budget is exceeded.
+    fn subtract(&mut self, usage: &ChunkMemoryUsage) {
+        self.total_voxel_bytes -= usage.voxel_bytes;
+        self.total_mesh_bytes -= usage.mesh_bytes;
+        self.chunk_gpu_bytes -= usage.gpu_bytes;
+    }
+
+    /// Handle for reporting GPU bytes that belong to no chunk; they count
+    /// toward the GPU budget but no eviction can free them.
+    #[must_use]
+    pub fn gpu_reporter(&self) -> GpuMemoryReporter {
+        self.shared_gpu.clone()
+    }
+
+    /// Bytes currently used in `category`.
+    #[must_use]
+    pub fn total_bytes(&self, category: MemoryCategory) -> usize {
+        match category {
+            MemoryCategory::Voxel => self.total_voxel_bytes,
+            MemoryCategory::Mesh => self.total_mesh_bytes,
+            MemoryCategory::Gpu => self.total_gpu_bytes(),
+        }
+    }
+
+    /// Bytes in `category` held by chunks, excluding shared GPU memory
+    /// reported through [`gpu_reporter`](Self::gpu_reporter).
+    fn chunk_bytes(&self, category: MemoryCategory) -> usize {
+        match category {
+            MemoryCategory::Gpu => self.chunk_gpu_bytes,
+            _ => self.total_bytes(category),
+        }
+    }
+
+    /// Bytes over the hard budget of `category` (0 if under budget).
+    #[must_use]
+    pub fn overage(&self, category: MemoryCategory) -> usize {
+        self.total_bytes(category)
+            .saturating_sub(self.config.budget(category))
+    }
+
+    /// Bytes over the soft budget of `category` (0 if under it).
+    #[must_use]
+    pub fn soft_overage(&self, category: MemoryCategory) -> usize {
+        self.total_bytes(category)
+            .saturating_sub(self.config.soft_budget(category))
+    }
+
+    /// Check whether any budget is exceeded.
     #[must_use]
     pub fn is_over_budget(&self) -> bool {
-        self.total_voxel_bytes > self.config.voxel_budget
-            || self.total_mesh_bytes > self.config.mesh_budget
+        CATEGORIES.iter().any(|&c| self.overage(c) > 0)
+    }
+
+    /// Check whether any soft budget is exceeded.
+    ///
+    /// Always true when [`is_over_budget`](Self::is_over_budget) is.
+    #[must_use]
+    pub fn is_over_soft_budget(&self) -> bool {
+        CATEGORIES.iter().any(|&c| self.soft_overage(c) > 0)
+    }
+
+    /// Current pressure on the budgets.
+    #[must_use]
+    pub fn pressure(&self) -> BudgetPressure {
+        if self.is_over_budget() {
+            BudgetPressure::Red
+        } else if self.is_over_soft_budget() {
+            BudgetPressure::Yellow
+        } else {
+            BudgetPressure::Green
+        }
     }
 
     /// Return how many bytes over the voxel budget we are (0 if under budget).
@@ -164,6 +318,12 @@ impl MemoryBudgetTracker {
         self.total_mesh_bytes
     }
 
+    /// Total GPU bytes: per-chunk buffers plus reported shared memory.
+    #[must_use]
+    pub fn total_gpu_bytes(&self) -> usize {
+        self.chunk_gpu_bytes + self.shared_gpu.bytes()
+    }
+
     /// Number of chunks currently tracked.
     #[must_use]
     pub fn loaded_chunk_count(&self) -> usize {
@@ -172,7 +332,7 @@ impl MemoryBudgetTracker {
 
     /// Read-only access to per-chunk usage data (used by [`select_evictions`]).
     #[must_use]
-    pub fn chunk_usage(&self) -> &HashMap<ChunkAddress, ChunkMemoryUsage> {
+    pub fn chunk_usage(&self) -> &HashMap<A, ChunkMemoryUsage> {
         &self.chunk_usage
     }
 
@@ -183,18 +343,57 @@ impl MemoryBudgetTracker {
     }
 }
 
-/// Determine which chunks to evict to bring memory usage within budget.
+/// One chunk picked by [`select_evictions`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChunkEviction<A = ChunkAddress> {
+    /// The chunk to evict.
+    pub address: A,
+    /// What to drop.
+    pub scope: EvictionScope,
+}
+
+/// Determine which chunks to evict this tick.
+///
+/// Above a hard cap, evicts as many chunks as needed to get that category
+/// back under it in one go. Between the soft and hard budgets, evicts at
+/// most [`MemoryBudgetConfig::soft_eviction_batch`] chunks, stopping early
+/// once usage would drop below the soft budget. Below every soft budget,
+/// evicts nothing.
+///
+/// Each chunk's [`EvictionScope`] follows the categories still over target
+/// when it is picked: voxel memory can only be reclaimed by dropping the
+/// whole chunk, mesh and GPU memory by
+/// [`mesh_eviction`](MemoryBudgetConfig::mesh_eviction) and
+/// [`gpu_eviction`](MemoryBudgetConfig::gpu_eviction). Chunks below
+/// [`drop_all_below_priority`](MemoryBudgetConfig::drop_all_below_priority)
+/// are always dropped entirely, and chunks whose mesh is already gone are
+/// only picked to reclaim voxel memory.
 ///
-/// Returns chunk addresses in eviction order (lowest priority first).
+/// Targets count chunk-held bytes only: shared GPU memory reported through
+/// [`MemoryBudgetTracker::gpu_reporter`] raises pressure but no eviction can
+/// free it, so it never makes chunks evict.
+///
+/// Returns evictions in order (lowest priority first).
 /// `priorities` maps each loaded chunk to its priority score — higher means
 /// more important (keep loaded). Chunks without an entry default to priority 0.
-pub fn select_evictions(
-    tracker: &MemoryBudgetTracker,
-    priorities: &HashMap<ChunkAddress, f64>,
-) -> Vec<ChunkAddress> {
-    if !tracker.is_over_budget() {
+pub fn select_evictions<A: Copy + Eq + Hash>(
+    tracker: &MemoryBudgetTracker<A>,
+    priorities: &HashMap<A, f64>,
+) -> Vec<ChunkEviction<A>> {
+    let config = tracker.config();
+    let over = |budget: fn(&MemoryBudgetConfig, MemoryCategory) -> usize| {
+        CATEGORIES.map(|c| tracker.chunk_bytes(c).saturating_sub(budget(config, c)))
+    };
+    let (targets, max_chunks) = if tracker.is_over_budget() {
+        (over(MemoryBudgetConfig::budget), usize::MAX)
+    } else if tracker.is_over_soft_budget() {
+        (
+            over(MemoryBudgetConfig::soft_budget),
+            config.soft_eviction_batch,
+        )
+    } else {
         return Vec::new();
-    }
+    };
 
     // Sort loaded chunks by priority (ascending — lowest priority = evicted first)
     let mut candidates: Vec<_> = tracker
@@ -208,188 +407,43 @@ pub fn select_evictions(
     candidates.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
 
     let mut evictions = Vec::new();
-    let mut freed_voxel = 0usize;
-    let mut freed_mesh = 0usize;
-    let voxel_target = tracker.voxel_overage();
-    let mesh_target = tracker.mesh_overage();
+    let mut freed = [0usize; CATEGORIES.len()];
 
-    for (addr, _priority) in candidates {
-        if freed_voxel >= voxel_target && freed_mesh >= mesh_target {
+    for (addr, priority) in candidates {
+        let pending = |i: usize| freed[i] < targets[i];
+        if evictions.len() >= max_chunks || !(0..CATEGORIES.len()).any(pending) {
             break;
         }
-        if let Some(usage) = tracker.chunk_usage().get(&addr) {
-            freed_voxel += usage.voxel_bytes;
-            freed_mesh += usage.mesh_bytes;
-            evictions.push(addr);
-        }
-    }
-
-    evictions
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use nebula_cubesphere::CubeFace;
-
-    fn make_config(voxel_mb: usize, mesh_mb: usize) -> MemoryBudgetConfig {
-        MemoryBudgetConfig {
-            voxel_budget: voxel_mb * 1024 * 1024,
-            mesh_budget: mesh_mb * 1024 * 1024,
-        }
-    }
-
-    fn make_address(id: u32) -> ChunkAddress {
-        // LOD 10 grid is 1024x1024; use (x, y) = (id % 1024, id / 1024)
-        let grid = ChunkAddress::grid_size(10);
-        ChunkAddress::new(CubeFace::PosY, 10, id % grid, id / grid)
-    }
-
-    /// Loading a chunk should increase the tracked memory usage.
-    #[test]
-    fn test_memory_increases_on_load() {
-        let mut tracker = MemoryBudgetTracker::new(MemoryBudgetConfig::default());
-        assert_eq!(tracker.total_voxel_bytes(), 0);
-        assert_eq!(tracker.total_mesh_bytes(), 0);
-
-        let usage = ChunkMemoryUsage {
-            voxel_bytes: 1024,
-            mesh_bytes: 2048,
+        let Some(usage) = tracker.chunk_usage().get(&addr) else {
+            continue;
         };
-        tracker.on_chunk_loaded(make_address(1), usage);
-
-        assert_eq!(tracker.total_voxel_bytes(), 1024);
-        assert_eq!(tracker.total_mesh_bytes(), 2048);
-        assert_eq!(tracker.loaded_chunk_count(), 1);
-    }
-
-    /// Unloading a chunk should decrease the tracked memory usage.
-    #[test]
-    fn test_memory_decreases_on_unload() {
-        let mut tracker = MemoryBudgetTracker::new(MemoryBudgetConfig::default());
-        let addr = make_address(1);
-        let usage = ChunkMemoryUsage {
-            voxel_bytes: 1024,
-            mesh_bytes: 2048,
+        let [voxel, mesh, gpu] = [0, 1, 2].map(pending);
+        let scope = if voxel
+            || priority < config.drop_all_below_priority
+            || (mesh && config.mesh_eviction == EvictionScope::All)
+            || (gpu && config.gpu_eviction == EvictionScope::All)
+        {
+            EvictionScope::All
+        } else if usage.mesh_bytes + usage.gpu_bytes > 0 {
+            EvictionScope::Mesh
+        } else {
+            continue;
         };
 
-        tracker.on_chunk_loaded(addr, usage);
-        tracker.on_chunk_unloaded(&addr);
-
-        assert_eq!(tracker.total_voxel_bytes(), 0);
-        assert_eq!(tracker.total_mesh_bytes(), 0);
-        assert_eq!(tracker.loaded_chunk_count(), 0);
-    }
-
-    /// Exceeding the budget should be detected by `is_over_budget()`.
-    #[test]
-    fn test_budget_exceeded_triggers_detection() {
-        let mut tracker = MemoryBudgetTracker::new(make_config(1, 1)); // 1 MB each
-
-        // Load chunks until over budget
-        for i in 0..2000 {
-            tracker.on_chunk_loaded(
-                make_address(i),
-                ChunkMemoryUsage {
-                    voxel_bytes: 1024,
-                    mesh_bytes: 512,
-                },
-            );
+        for (i, &category) in CATEGORIES.iter().enumerate() {
+            if scope == EvictionScope::All || category != MemoryCategory::Voxel {
+                freed[i] += usage.bytes(category);
+            }
         }
-
-        // 2000 * 1024 = ~2 MB voxels, exceeding 1 MB budget
-        assert!(tracker.is_over_budget());
-    }
-
-    /// Eviction should remove the lowest-priority chunks first.
-    #[test]
-    fn test_eviction_removes_lowest_priority_first() {
-        let mut tracker = MemoryBudgetTracker::new(make_config(1, 1));
-        let mut priorities = HashMap::new();
-
-        // Load 3 chunks with different priorities
-        let low = make_address(1);
-        let mid = make_address(2);
-        let high = make_address(3);
-
-        for addr in [low, mid, high] {
-            tracker.on_chunk_loaded(
-                addr,
-                ChunkMemoryUsage {
-                    voxel_bytes: 500 * 1024, // 500 KB each -> 1.5 MB total, over 1 MB budget
-                    mesh_bytes: 100 * 1024,
-                },
-            );
-        }
-
-        priorities.insert(low, 10.0);
-        priorities.insert(mid, 50.0);
-        priorities.insert(high, 100.0);
-
-        let evictions = select_evictions(&tracker, &priorities);
-
-        // Lowest priority should be evicted first
-        assert!(!evictions.is_empty());
-        assert_eq!(
-            evictions[0], low,
-            "lowest priority chunk should be evicted first"
-        );
-    }
-
-    /// The budget should be configurable with custom values.
-    #[test]
-    fn test_budget_can_be_configured() {
-        let config = make_config(4096, 2048); // 4 GB voxels, 2 GB meshes
-        let tracker = MemoryBudgetTracker::new(config);
-
-        assert!(!tracker.is_over_budget()); // empty tracker is never over budget
-
-        // Verify the config values are stored correctly
-        let config_low = MemoryBudgetConfig::low();
-        assert_eq!(config_low.voxel_budget, 512 * 1024 * 1024);
-        assert_eq!(config_low.mesh_budget, 256 * 1024 * 1024);
-
-        let config_high = MemoryBudgetConfig::high();
-        assert_eq!(config_high.voxel_budget, 4 * 1024 * 1024 * 1024);
-        assert_eq!(config_high.mesh_budget, 2 * 1024 * 1024 * 1024);
+        evictions.push(ChunkEviction {
+            address: addr,
+            scope,
+        });
     }
 
-    /// `ChunkMemoryUsage::estimate` should produce reasonable values.
-    #[test]
-    fn test_estimate_produces_reasonable_values() {
-        let usage = ChunkMemoryUsage::estimate(0, 1000);
-        assert!(usage.voxel_bytes > 0);
-        assert!(usage.mesh_bytes > 0);
-        assert_eq!(usage.total(), usage.voxel_bytes + usage.mesh_bytes);
-
-        // Higher LOD (coarser) should use less voxel memory
-        let usage_coarse = ChunkMemoryUsage::estimate(3, 100);
-        assert!(usage_coarse.voxel_bytes < usage.voxel_bytes);
-    }
-
-    /// Replacing a chunk should update totals correctly.
-    #[test]
-    fn test_replace_chunk_updates_totals() {
-        let mut tracker = MemoryBudgetTracker::new(MemoryBudgetConfig::default());
-        let addr = make_address(1);
-
-        tracker.on_chunk_loaded(
-            addr,
-            ChunkMemoryUsage {
-                voxel_bytes: 1000,
-                mesh_bytes: 2000,
-            },
-        );
-        tracker.on_chunk_loaded(
-            addr,
-            ChunkMemoryUsage {
-                voxel_bytes: 500,
-                mesh_bytes: 800,
-            },
-        );
-
-        assert_eq!(tracker.total_voxel_bytes(), 500);
-        assert_eq!(tracker.total_mesh_bytes(), 800);
-        assert_eq!(tracker.loaded_chunk_count(), 1);
-    }
+    evictions
 }
+
+#[cfg(test)]
+#[path = "memory_budget_tests.rs"]
+mod tests;
@@ -0,0 +1,150 @@
+//! Budget limits and eviction policy for [`MemoryBudgetTracker`].
+//!
+//! [`MemoryBudgetConfig`] holds a hard cap per [`MemoryCategory`], the soft
+//! fraction below it where gradual eviction starts, and what eviction drops
+//! for each category. [`MemoryBudgetConfig::detect`] picks a profile from the
+//! graphics adapter's limits.
+//!
+//! [`MemoryBudgetTracker`]: crate::MemoryBudgetTracker
+
+use crate::memory_budget::MemoryCategory;
+
+/// Default soft budget as a fraction of the hard cap.
+pub const DEFAULT_SOFT_FRACTION: f64 = 0.8;
+
+/// Default number of chunks evicted per tick between the soft and hard budgets.
+pub const DEFAULT_SOFT_EVICTION_BATCH: usize = 4;
+
+/// What evicting a chunk drops.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvictionScope {
+    /// Drop the CPU mesh and GPU buffers; keep the voxel data so the chunk
+    /// can be remeshed without regenerating it.
+    Mesh,
+    /// Unload the chunk entirely.
+    All,
+}
+
+/// Default priority below which evicted chunks are dropped entirely.
+pub const DEFAULT_DROP_ALL_BELOW_PRIORITY: f64 = 0.0;
+
+/// Memory budget configuration.
+#[derive(Clone, Debug)]
+pub struct MemoryBudgetConfig {
+    /// Maximum bytes for chunk voxel data. Default: 2 GB.
+    pub voxel_budget: usize,
+    /// Maximum bytes for chunk mesh data. Default: 1 GB.
+    pub mesh_budget: usize,
+    /// Maximum bytes of GPU buffer memory. Default: 1 GB.
+    pub gpu_budget: usize,
+    /// Soft budget as a fraction of each hard cap, in `[0, 1]`. Default: 0.8.
+    pub soft_fraction: f64,
+    /// Maximum chunks evicted per call while between the soft and hard
+    /// budgets. Default: 4.
+    pub soft_eviction_batch: usize,
+    /// How chunks are evicted to reclaim mesh memory. Default:
+    /// [`EvictionScope::Mesh`].
+    pub mesh_eviction: EvictionScope,
+    /// How chunks are evicted to reclaim GPU memory. Default:
+    /// [`EvictionScope::Mesh`].
+    pub gpu_eviction: EvictionScope,
+    /// Evicted chunks with a priority below this are dropped entirely
+    /// whatever the category. Default: 0.0, which [`compute_priority`]
+    /// only goes below for chunks behind the camera.
+    ///
+    /// [`compute_priority`]: crate::compute_priority
+    pub drop_all_below_priority: f64,
+}
+
+impl Default for MemoryBudgetConfig {
+    fn default() -> Self {
+        Self {
+            voxel_budget: 2 * 1024 * 1024 * 1024, // 2 GB
+            mesh_budget: 1024 * 1024 * 1024,      // 1 GB
+            gpu_budget: 1024 * 1024 * 1024,       // 1 GB
+            soft_fraction: DEFAULT_SOFT_FRACTION,
+            soft_eviction_batch: DEFAULT_SOFT_EVICTION_BATCH,
+            mesh_eviction: EvictionScope::Mesh,
+            gpu_eviction: EvictionScope::Mesh,
+            drop_all_below_priority: DEFAULT_DROP_ALL_BELOW_PRIORITY,
+        }
+    }
+}
+
+impl MemoryBudgetConfig {
+    /// Create a budget for low-memory systems (e.g., integrated GPU).
+    #[must_use]
+    pub fn low() -> Self {
+        Self {
+            voxel_budget: 512 * 1024 * 1024, // 512 MB
+            mesh_budget: 256 * 1024 * 1024,  // 256 MB
+            gpu_budget: 256 * 1024 * 1024,   // 256 MB
+            ..Self::default()
+        }
+    }
+
+    /// Create a budget for high-end systems.
+    #[must_use]
+    pub fn high() -> Self {
+        Self {
+            voxel_budget: 4 * 1024 * 1024 * 1024, // 4 GB
+            mesh_budget: 2 * 1024 * 1024 * 1024,  // 2 GB
+            gpu_budget: 3 * 1024 * 1024 * 1024,   // 3 GB
+            ..Self::default()
+        }
+    }
+
+    /// Pick a profile from what the graphics adapter reports.
+    ///
+    /// Integrated GPUs share system memory and get [`low`](Self::low).
+    /// Otherwise `max_buffer_size` (wgpu's `Limits::max_buffer_size`) stands
+    /// in for the card's class: 4 GiB or more selects [`high`](Self::high),
+    /// anything else the default profile. The GPU budget is capped at
+    /// eight maximum-size buffers so small-limit adapters stay conservative.
+    #[must_use]
+    pub fn detect(max_buffer_size: u64, integrated_gpu: bool) -> Self {
+        let mut config = if integrated_gpu {
+            Self::low()
+        } else if max_buffer_size >= 4 * 1024 * 1024 * 1024 {
+            Self::high()
+        } else {
+            Self::default()
+        };
+        let cap = usize::try_from(max_buffer_size.saturating_mul(8)).unwrap_or(usize::MAX);
+        config.gpu_budget = config.gpu_budget.min(cap);
+        config
+    }
+
+    /// Hard budget for `category`.
+    #[must_use]
+    pub fn budget(&self, category: MemoryCategory) -> usize {
+        match category {
+            MemoryCategory::Voxel => self.voxel_budget,
+            MemoryCategory::Mesh => self.mesh_budget,
+            MemoryCategory::Gpu => self.gpu_budget,
+        }
+    }
+
+    /// Soft budget for `category`: `soft_fraction × budget`.
+    #[must_use]
+    pub fn soft_budget(&self, category: MemoryCategory) -> usize {
+        soft_limit(self.budget(category), self.soft_fraction)
+    }
+
+    /// Soft voxel budget: `soft_fraction × voxel_budget`.
+    #[must_use]
+    pub fn soft_voxel_budget(&self) -> usize {
+        soft_limit(self.voxel_budget, self.soft_fraction)
+    }
+
+    /// Soft mesh budget: `soft_fraction × mesh_budget`.
+    #[must_use]
+    pub fn soft_mesh_budget(&self) -> usize {
+        soft_limit(self.mesh_budget, self.soft_fraction)
+    }
+}
+
+/// Scales `budget` by `fraction` clamped to `[0, 1]`.
+fn soft_limit(budget: usize, fraction: f64) -> usize {
+    (budget as f64 * fraction.clamp(0.0, 1.0)) as usize
+}
@@ -0,0 +1,405 @@
+//! Unit tests for memory budget tracking and eviction.
+
+use super::*;
+use nebula_cubesphere::CubeFace;
+
+fn make_config(voxel_mb: usize, mesh_mb: usize) -> MemoryBudgetConfig {
+    MemoryBudgetConfig {
+        voxel_budget: voxel_mb * 1024 * 1024,
+        mesh_budget: mesh_mb * 1024 * 1024,
+        ..MemoryBudgetConfig::default()
+    }
+}
+
+fn addresses(evictions: &[ChunkEviction]) -> Vec<ChunkAddress> {
+    evictions.iter().map(|e| e.address).collect()
+}
+
+fn make_address(id: u32) -> ChunkAddress {
+    // LOD 10 grid is 1024x1024; use (x, y) = (id % 1024, id / 1024)
+    let grid = ChunkAddress::grid_size(10);
+    ChunkAddress::new(CubeFace::PosY, 10, id % grid, id / grid)
+}
+
+/// Loading a chunk should increase the tracked memory usage.
+#[test]
+fn test_memory_increases_on_load() {
+    let mut tracker = MemoryBudgetTracker::new(MemoryBudgetConfig::default());
+    assert_eq!(tracker.total_voxel_bytes(), 0);
+    assert_eq!(tracker.total_mesh_bytes(), 0);
+
+    let usage = ChunkMemoryUsage {
+        voxel_bytes: 1024,
+        mesh_bytes: 2048,
+        gpu_bytes: 0,
+    };
+    tracker.on_chunk_loaded(make_address(1), usage);
+
+    assert_eq!(tracker.total_voxel_bytes(), 1024);
+    assert_eq!(tracker.total_mesh_bytes(), 2048);
+    assert_eq!(tracker.loaded_chunk_count(), 1);
+}
+
+/// Unloading a chunk should decrease the tracked memory usage.
+#[test]
+fn test_memory_decreases_on_unload() {
+    let mut tracker = MemoryBudgetTracker::new(MemoryBudgetConfig::default());
+    let addr = make_address(1);
+    let usage = ChunkMemoryUsage {
+        voxel_bytes: 1024,
+        mesh_bytes: 2048,
+        gpu_bytes: 0,
+    };
+
+    tracker.on_chunk_loaded(addr, usage);
+    tracker.on_chunk_unloaded(&addr);
+
+    assert_eq!(tracker.total_voxel_bytes(), 0);
+    assert_eq!(tracker.total_mesh_bytes(), 0);
+    assert_eq!(tracker.loaded_chunk_count(), 0);
+}
+
+/// Exceeding the budget should be detected by `is_over_budget()`.
+#[test]
+fn test_budget_exceeded_triggers_detection() {
+    let mut tracker = MemoryBudgetTracker::new(make_config(1, 1)); // 1 MB each
+
+    // Load chunks until over budget
+    for i in 0..2000 {
+        tracker.on_chunk_loaded(
+            make_address(i),
+            ChunkMemoryUsage {
+                voxel_bytes: 1024,
+                mesh_bytes: 512,
+                gpu_bytes: 0,
+            },
+        );
+    }
+
+    // 2000 * 1024 = ~2 MB voxels, exceeding 1 MB budget
+    assert!(tracker.is_over_budget());
+}
+
+/// Eviction should remove the lowest-priority chunks first.
+#[test]
+fn test_eviction_removes_lowest_priority_first() {
+    let mut tracker = MemoryBudgetTracker::new(make_config(1, 1));
+    let mut priorities = HashMap::new();
+
+    // Load 3 chunks with different priorities
+    let low = make_address(1);
+    let mid = make_address(2);
+    let high = make_address(3);
+
+    for addr in [low, mid, high] {
+        tracker.on_chunk_loaded(
+            addr,
+            ChunkMemoryUsage {
+                voxel_bytes: 500 * 1024, // 500 KB each -> 1.5 MB total, over 1 MB budget
+                mesh_bytes: 100 * 1024,
+                gpu_bytes: 0,
+            },
+        );
+    }
+
+    priorities.insert(low, 10.0);
+    priorities.insert(mid, 50.0);
+    priorities.insert(high, 100.0);
+
+    let evictions = select_evictions(&tracker, &priorities);
+
+    // Lowest priority should be evicted first
+    assert!(!evictions.is_empty());
+    assert_eq!(
+        evictions[0].address, low,
+        "lowest priority chunk should be evicted first"
+    );
+    assert_eq!(evictions[0].scope, EvictionScope::All);
+}
+
+/// The budget should be configurable with custom values.
+#[test]
+fn test_budget_can_be_configured() {
+    let config = make_config(4096, 2048); // 4 GB voxels, 2 GB meshes
+    let tracker: MemoryBudgetTracker = MemoryBudgetTracker::new(config);
+
+    assert!(!tracker.is_over_budget()); // empty tracker is never over budget
+
+    // Verify the config values are stored correctly
+    let config_low = MemoryBudgetConfig::low();
+    assert_eq!(config_low.voxel_budget, 512 * 1024 * 1024);
+    assert_eq!(config_low.mesh_budget, 256 * 1024 * 1024);
+
+    let config_high = MemoryBudgetConfig::high();
+    assert_eq!(config_high.voxel_budget, 4 * 1024 * 1024 * 1024);
+    assert_eq!(config_high.mesh_budget, 2 * 1024 * 1024 * 1024);
+}
+
+/// `ChunkMemoryUsage::estimate` should produce reasonable values.
+#[test]
+fn test_estimate_produces_reasonable_values() {
+    let usage = ChunkMemoryUsage::estimate(0, 1000);
+    assert!(usage.voxel_bytes > 0);
+    assert!(usage.mesh_bytes > 0);
+    assert_eq!(usage.gpu_bytes, usage.mesh_bytes);
+    assert_eq!(
+        usage.total(),
+        usage.voxel_bytes + usage.mesh_bytes + usage.gpu_bytes
+    );
+
+    // Higher LOD (coarser) should use less voxel memory
+    let usage_coarse = ChunkMemoryUsage::estimate(3, 100);
+    assert!(usage_coarse.voxel_bytes < usage.voxel_bytes);
+}
+
+/// Replacing a chunk should update totals correctly.
+#[test]
+fn test_replace_chunk_updates_totals() {
+    let mut tracker = MemoryBudgetTracker::new(MemoryBudgetConfig::default());
+    let addr = make_address(1);
+
+    tracker.on_chunk_loaded(
+        addr,
+        ChunkMemoryUsage {
+            voxel_bytes: 1000,
+            mesh_bytes: 2000,
+            gpu_bytes: 0,
+        },
+    );
+    tracker.on_chunk_loaded(
+        addr,
+        ChunkMemoryUsage {
+            voxel_bytes: 500,
+            mesh_bytes: 800,
+            gpu_bytes: 0,
+        },
+    );
+
+    assert_eq!(tracker.total_voxel_bytes(), 500);
+    assert_eq!(tracker.total_mesh_bytes(), 800);
+    assert_eq!(tracker.loaded_chunk_count(), 1);
+}
+
+/// 1 MB voxel / 1 MB mesh budget, soft at 80%, batches of 2; loads `count`
+/// 100 KB voxel chunks with priority equal to their id.
+fn soft_tracker(count: u32) -> (MemoryBudgetTracker, HashMap<ChunkAddress, f64>) {
+    let mut tracker = MemoryBudgetTracker::new(MemoryBudgetConfig {
+        soft_fraction: 0.8,
+        soft_eviction_batch: 2,
+        ..make_config(1, 1)
+    });
+    let mut priorities = HashMap::new();
+    for i in 0..count {
+        tracker.on_chunk_loaded(
+            make_address(i),
+            ChunkMemoryUsage {
+                voxel_bytes: 100 * 1024,
+                mesh_bytes: 0,
+                gpu_bytes: 0,
+            },
+        );
+        priorities.insert(make_address(i), f64::from(i));
+    }
+    (tracker, priorities)
+}
+
+/// Below the soft budget nothing is evicted.
+#[test]
+fn test_below_soft_budget_evicts_nothing() {
+    // 800 KB < 819.2 KB soft budget.
+    let (tracker, priorities) = soft_tracker(8);
+    assert!(!tracker.is_over_soft_budget());
+    assert!(select_evictions(&tracker, &priorities).is_empty());
+}
+
+/// Between soft and hard, eviction is capped at the batch size per tick.
+#[test]
+fn test_between_soft_and_hard_evicts_gradually() {
+    // 1000 KB: over the 819.2 KB soft budget, under the 1 MB hard cap.
+    let (mut tracker, priorities) = soft_tracker(10);
+    assert!(tracker.is_over_soft_budget());
+    assert!(!tracker.is_over_budget());
+
+    let first = addresses(&select_evictions(&tracker, &priorities));
+    assert_eq!(first, vec![make_address(0), make_address(1)]);
+    for addr in &first {
+        tracker.on_chunk_unloaded(addr);
+    }
+
+    // 800 KB left: back under the soft budget, so the next tick is idle.
+    assert!(!tracker.is_over_soft_budget());
+    assert!(select_evictions(&tracker, &priorities).is_empty());
+}
+
+/// Just over the soft budget, a tick stops as soon as usage drops below it.
+#[test]
+fn test_soft_eviction_stops_at_soft_budget() {
+    // 900 KB: a single 100 KB chunk brings usage under the soft budget.
+    let (tracker, priorities) = soft_tracker(9);
+    let evictions = addresses(&select_evictions(&tracker, &priorities));
+    assert_eq!(evictions, vec![make_address(0)]);
+}
+
+/// Above the hard cap, eviction ignores the batch limit.
+#[test]
+fn test_above_hard_budget_evicts_aggressively() {
+    // 1500 KB: 476 KB over the hard cap needs 5 chunks in one tick.
+    let (mut tracker, priorities) = soft_tracker(15);
+    assert!(tracker.is_over_budget());
+
+    let evictions = select_evictions(&tracker, &priorities);
+    assert_eq!(evictions.len(), 5);
+    assert_eq!(evictions[0].address, make_address(0));
+    for eviction in &evictions {
+        tracker.apply_eviction(eviction);
+    }
+    assert!(!tracker.is_over_budget());
+}
+
+/// Loads `count` chunks of 100 KB voxels, 100 KB mesh and 100 KB GPU
+/// buffers, priority `10 + id`, under roomy voxel and mesh budgets and a
+/// 1 MB GPU budget.
+fn gpu_tracker(count: u32) -> (MemoryBudgetTracker, HashMap<ChunkAddress, f64>) {
+    let mut tracker = MemoryBudgetTracker::new(MemoryBudgetConfig {
+        gpu_budget: 1024 * 1024,
+        ..make_config(64, 64)
+    });
+    let mut priorities = HashMap::new();
+    for i in 0..count {
+        tracker.on_chunk_loaded(
+            make_address(i),
+            ChunkMemoryUsage {
+                voxel_bytes: 100 * 1024,
+                mesh_bytes: 100 * 1024,
+                gpu_bytes: 100 * 1024,
+            },
+        );
+        priorities.insert(make_address(i), 10.0 + f64::from(i));
+    }
+    (tracker, priorities)
+}
+
+/// Exceeding only the GPU budget drops meshes but keeps voxel data loaded.
+#[test]
+fn test_gpu_overage_evicts_meshes_only() {
+    let (mut tracker, priorities) = gpu_tracker(15);
+    assert_eq!(tracker.pressure(), BudgetPressure::Red);
+    assert_eq!(tracker.overage(MemoryCategory::Voxel), 0);
+
+    let evictions = select_evictions(&tracker, &priorities);
+    assert_eq!(evictions.len(), 5);
+    assert!(evictions.iter().all(|e| e.scope == EvictionScope::Mesh));
+    for eviction in &evictions {
+        tracker.apply_eviction(eviction);
+    }
+
+    assert!(!tracker.is_over_budget());
+    assert_eq!(tracker.loaded_chunk_count(), 15);
+    assert_eq!(tracker.total_voxel_bytes(), 15 * 100 * 1024);
+    assert_eq!(tracker.total_gpu_bytes(), 10 * 100 * 1024);
+    let dropped = tracker.chunk_usage()[&make_address(0)];
+    assert_eq!((dropped.mesh_bytes, dropped.gpu_bytes), (0, 0));
+}
+
+/// Chunks below the drop-all priority are unloaded even for GPU overage.
+#[test]
+fn test_far_chunks_drop_everything() {
+    let (tracker, mut priorities) = gpu_tracker(15);
+    priorities.insert(make_address(0), -40.0);
+    let evictions = select_evictions(&tracker, &priorities);
+    assert_eq!(evictions[0].address, make_address(0));
+    assert_eq!(evictions[0].scope, EvictionScope::All);
+    assert!(
+        evictions[1..]
+            .iter()
+            .all(|e| e.scope == EvictionScope::Mesh)
+    );
+}
+
+/// A GPU eviction strategy of `All` unloads whole chunks.
+#[test]
+fn test_gpu_eviction_strategy_all() {
+    let (mut tracker, priorities) = gpu_tracker(15);
+    tracker.config.gpu_eviction = EvictionScope::All;
+    let evictions = select_evictions(&tracker, &priorities);
+    assert!(evictions.iter().all(|e| e.scope == EvictionScope::All));
+}
+
+/// Shared GPU memory reported through the callback handle counts toward
+/// the GPU budget and pressure.
+#[test]
+fn test_reported_gpu_memory_raises_pressure() {
+    let (tracker, _) = gpu_tracker(5);
+    assert_eq!(tracker.pressure(), BudgetPressure::Green);
+
+    let reporter = tracker.gpu_reporter();
+    reporter.report(400 * 1024);
+    assert_eq!(tracker.total_gpu_bytes(), 900 * 1024);
+    assert_eq!(tracker.pressure(), BudgetPressure::Yellow);
+    reporter.report(600 * 1024);
+    assert_eq!(tracker.pressure(), BudgetPressure::Red);
+}
+
+/// Shared GPU memory over the hard cap throttles loading but evicts no
+/// chunks, since dropping their meshes cannot free it.
+#[test]
+fn test_shared_gpu_overage_evicts_nothing() {
+    let (tracker, priorities) = gpu_tracker(5);
+    tracker.gpu_reporter().report(2 * 1024 * 1024);
+    assert_eq!(tracker.pressure(), BudgetPressure::Red);
+    assert!(select_evictions(&tracker, &priorities).is_empty());
+
+    // Chunk GPU bytes over the budget are still reclaimed, as many as
+    // without the shared bytes.
+    let (tracker, priorities) = gpu_tracker(15);
+    tracker.gpu_reporter().report(2 * 1024 * 1024);
+    assert_eq!(select_evictions(&tracker, &priorities).len(), 5);
+}
+
+/// Red pressure halves the configured loads per tick.
+#[test]
+fn test_pressure_throttles_loads() {
+    assert_eq!(BudgetPressure::Green.throttle_loads(8), 8);
+    assert_eq!(BudgetPressure::Yellow.throttle_loads(8), 6);
+    assert_eq!(BudgetPressure::Red.throttle_loads(8), 4);
+    assert_eq!(BudgetPressure::Red.throttle_loads(1), 1);
+    assert_eq!(BudgetPressure::Red.throttle_loads(0), 0);
+}
+
+/// Adapter-based detection picks a profile and caps the GPU budget.
+#[test]
+fn test_detect_profiles() {
+    let gib = 1024 * 1024 * 1024;
+    assert_eq!(
+        MemoryBudgetConfig::detect(8 * gib, true).voxel_budget,
+        512 * 1024 * 1024
+    );
+    let high = MemoryBudgetConfig::detect(8 * gib, false);
+    assert_eq!(high.voxel_budget, MemoryBudgetConfig::high().voxel_budget);
+    assert_eq!(high.gpu_budget, MemoryBudgetConfig::high().gpu_budget);
+    // wgpu's default 256 MiB buffer limit: default profile, 1 GB GPU budget.
+    let default = MemoryBudgetConfig::detect(256 * 1024 * 1024, false);
+    assert_eq!(default.gpu_budget, 1024 * 1024 * 1024);
+    assert_eq!(
+        MemoryBudgetConfig::detect(16 * 1024 * 1024, false).gpu_budget,
+        128 * 1024 * 1024
+    );
+}
+
+#[test]
+fn test_tracker_accepts_other_address_types() {
+    let mut tracker = MemoryBudgetTracker::new(make_config(1, 1));
+    let usage = ChunkMemoryUsage {
+        voxel_bytes: 600 * 1024,
+        ..ChunkMemoryUsage::default()
+    };
+    tracker.on_chunk_loaded((0_i64, 0_i64, 0_i64), usage);
+    tracker.on_chunk_loaded((9, 0, 0), usage);
+    assert_eq!(tracker.pressure(), BudgetPressure::Red);
+
+    let priorities = HashMap::from([((0, 0, 0), 10.0), ((9, 0, 0), 1.0)]);
+    let evictions = select_evictions(&tracker, &priorities);
+    assert_eq!(evictions.len(), 1);
+    assert_eq!(evictions[0].address, (9, 0, 0));
+    assert_eq!(evictions[0].scope, EvictionScope::All);
+}
@@ -74,6 +74,9 @@ impl Ord for PriorityEntry {
     }
 }
 
+/// Stale heap entries tolerated before [`LodPriorityQueue`] compacts.
+const STALE_SLACK: usize = 64;
+
 /// Priority queue for chunk generation and meshing tasks.
 ///
 /// Orders work items by visual importance so the most impactful chunks
@@ -107,14 +110,34 @@ impl LodPriorityQueue {
     /// Insert or update a chunk's priority.
     /// If the chunk is already in the queue, its priority is updated.
     pub fn push(&mut self, address: ChunkAddress, priority: f64) {
+        self.update_priority(address, priority);
+    }
+
+    /// Reposition a queued chunk at `new_priority`, or insert it if absent.
+    ///
+    /// The old heap entry is left in place and skipped by [`pop`](Self::pop)
+    /// once its generation no longer matches. When stale entries outnumber
+    /// live ones the heap is rebuilt, so re-prioritizing every frame under
+    /// continuous camera motion does not grow it without bound.
+    pub fn update_priority(&mut self, address: ChunkAddress, new_priority: f64) {
         let generation = self.next_generation;
         self.next_generation += 1;
         self.generations.insert(address, generation);
         self.heap.push(PriorityEntry {
             address,
-            priority,
+            priority: new_priority,
             generation,
         });
+        if self.heap.len() > 2 * self.generations.len() + STALE_SLACK {
+            self.compact();
+        }
+    }
+
+    /// Drop heap entries superseded by a later update or already popped.
+    fn compact(&mut self) {
+        let generations = &self.generations;
+        self.heap
+            .retain(|entry| generations.get(&entry.address) == Some(&entry.generation));
     }
 
     /// Remove and return the highest-priority chunk address.
@@ -243,6 +266,46 @@ mod tests {
         assert_eq!(queue.pop(), Some(addr_a));
     }
 
+    /// A chunk re-prioritized from low to high should pop first, exactly once.
+    #[test]
+    fn test_update_priority_moves_existing_entry() {
+        let mut queue = LodPriorityQueue::new();
+        let moved = make_address(0, 10, 1, 0);
+        let other = make_address(0, 10, 2, 0);
+        queue.push(moved, 1.0);
+        queue.push(other, 50.0);
+        queue.update_priority(moved, 100.0);
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.pop(), Some(moved));
+        assert_eq!(queue.pop(), Some(other));
+        assert_eq!(queue.pop(), None);
+    }
+
+    /// Updating an absent chunk inserts it.
+    #[test]
+    fn test_update_priority_inserts_when_absent() {
+        let mut queue = LodPriorityQueue::new();
+        let addr = make_address(0, 10, 3, 0);
+        queue.update_priority(addr, 5.0);
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.pop(), Some(addr));
+    }
+
+    /// Repeated updates compact stale entries instead of growing the heap.
+    #[test]
+    fn test_repeated_updates_keep_heap_bounded() {
+        let mut queue = LodPriorityQueue::new();
+        let addrs: Vec<_> = (0..4).map(|x| make_address(0, 10, x, 0)).collect();
+        for frame in 0..1_000 {
+            for (i, &addr) in addrs.iter().enumerate() {
+                queue.update_priority(addr, f64::from(frame * 4 + i as u32));
+            }
+        }
+        assert!(queue.heap.len() <= 2 * addrs.len() + STALE_SLACK + 1);
+        assert_eq!(queue.pop(), Some(addrs[3]));
+        assert_eq!(queue.len(), 3);
+    }
+
     /// Queue length should reflect the number of valid entries.
     #[test]
     fn test_queue_length() {
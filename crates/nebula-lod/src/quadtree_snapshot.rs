@@ -0,0 +1,191 @@
+//! Compact snapshots of quadtree leaf sets for warm starts.
+//!
+//! Rebuilding every face quadtree from its root on launch re-subdivides and
+//! regenerates everything around the camera. Instead, the leaf set of each
+//! [`FaceQuadtreeLod`] is saved on shutdown and fed to
+//! [`FaceQuadtreeLod::restore`] on start, which rebuilds the tree and then
+//! reconciles it against the current camera, so only leaves that are no
+//! longer valid change.
+//!
+//! A snapshot is the magic bytes `NQT1` followed by one section per face:
+//! the face index (`u8`), the leaf count (`u32`), then each leaf as its LOD
+//! (`u8`) and grid `x`, `y` (`u32` each). Integers are little-endian.
+
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use nebula_cubesphere::{ChunkAddress, CubeFace, PlanetId};
+
+use crate::FaceQuadtreeLod;
+
+const MAGIC: &[u8; 4] = b"NQT1";
+
+/// Bytes per encoded leaf: LOD, x, y.
+const LEAF_BYTES: usize = 1 + 4 + 4;
+
+/// Errors that can occur when restoring a quadtree snapshot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QuadtreeSnapshotError {
+    /// The blob does not start with the snapshot magic bytes.
+    BadMagic,
+    /// The blob ends in the middle of a section.
+    Truncated,
+    /// A section names a face index outside `0..6`.
+    InvalidFace(u8),
+    /// The blob has no section for the face being restored.
+    MissingFace(CubeFace),
+    /// A leaf lies outside its face grid or beyond the tree's depth.
+    InvalidLeaf {
+        /// Face of the offending section.
+        face: CubeFace,
+        /// LOD of the leaf.
+        lod: u8,
+        /// Grid x of the leaf.
+        x: u32,
+        /// Grid y of the leaf.
+        y: u32,
+    },
+    /// The leaves overlap or do not cover the whole face.
+    Inconsistent(CubeFace),
+}
+
+impl fmt::Display for QuadtreeSnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a quadtree snapshot"),
+            Self::Truncated => write!(f, "quadtree snapshot is truncated"),
+            Self::InvalidFace(index) => write!(f, "invalid cube face index {index}"),
+            Self::MissingFace(face) => write!(f, "snapshot has no leaves for face {face:?}"),
+            Self::InvalidLeaf { face, lod, x, y } => {
+                write!(f, "invalid leaf {face:?} lod {lod} ({x}, {y})")
+            }
+            Self::Inconsistent(face) => {
+                write!(f, "leaves for face {face:?} do not tile the face")
+            }
+        }
+    }
+}
+
+impl std::error::Error for QuadtreeSnapshotError {}
+
+/// Encode the leaf sets of `trees` into one snapshot blob.
+pub fn encode_quadtree_leaves<'a>(trees: impl IntoIterator<Item = &'a FaceQuadtreeLod>) -> Vec<u8> {
+    let mut blob = MAGIC.to_vec();
+    for tree in trees {
+        let leaves = tree.tree().root.all_leaves();
+        blob.push(tree.face() as u8);
+        blob.extend_from_slice(&(leaves.len() as u32).to_le_bytes());
+        for leaf in leaves {
+            blob.push(leaf.lod);
+            blob.extend_from_slice(&leaf.x.to_le_bytes());
+            blob.extend_from_slice(&leaf.y.to_le_bytes());
+        }
+    }
+    blob
+}
+
+/// Decode the leaves saved for `face`, checking each lies on its face grid.
+pub(crate) fn decode_face_leaves(
+    blob: &[u8],
+    face: CubeFace,
+) -> Result<Vec<ChunkAddress>, QuadtreeSnapshotError> {
+    let mut rest = blob
+        .strip_prefix(MAGIC)
+        .ok_or(QuadtreeSnapshotError::BadMagic)?;
+    while let Some((&index, tail)) = rest.split_first() {
+        let section_face = *CubeFace::ALL
+            .get(usize::from(index))
+            .ok_or(QuadtreeSnapshotError::InvalidFace(index))?;
+        let (count, tail) = split_u32(tail)?;
+        let len = (count as usize)
+            .checked_mul(LEAF_BYTES)
+            .filter(|&len| len <= tail.len())
+            .ok_or(QuadtreeSnapshotError::Truncated)?;
+        let (leaves, tail) = tail.split_at(len);
+        rest = tail;
+        if section_face != face {
+            continue;
+        }
+
+        return leaves
+            .chunks_exact(LEAF_BYTES)
+            .map(|leaf| {
+                let lod = leaf[0];
+                let x = u32::from_le_bytes([leaf[1], leaf[2], leaf[3], leaf[4]]);
+                let y = u32::from_le_bytes([leaf[5], leaf[6], leaf[7], leaf[8]]);
+                let on_grid = lod <= ChunkAddress::MAX_LOD
+                    && x < ChunkAddress::grid_size(lod)
+                    && y < ChunkAddress::grid_size(lod);
+                if on_grid {
+                    Ok(ChunkAddress::new(face, lod, x, y))
+                } else {
+                    Err(QuadtreeSnapshotError::InvalidLeaf { face, lod, x, y })
+                }
+            })
+            .collect();
+    }
+    Err(QuadtreeSnapshotError::MissingFace(face))
+}
+
+fn split_u32(bytes: &[u8]) -> Result<(u32, &[u8]), QuadtreeSnapshotError> {
+    let (head, tail) = bytes
+        .split_first_chunk::<4>()
+        .ok_or(QuadtreeSnapshotError::Truncated)?;
+    Ok((u32::from_le_bytes(*head), tail))
+}
+
+/// On-disk store of quadtree snapshots, one file per planet.
+///
+/// Files are keyed by planet ID and terrain seed, so a planet regenerated
+/// with a new seed starts cold instead of restoring leaves for different
+/// terrain. Save the planet's face trees on shutdown with
+/// [`save`](Self::save) and pass [`load`](Self::load)'s blob to
+/// [`FaceQuadtreeLod::restore`] on start.
+#[derive(Clone, Debug)]
+pub struct QuadtreeSnapshotStore {
+    dir: PathBuf,
+}
+
+impl QuadtreeSnapshotStore {
+    /// Store snapshots under `dir`, which is created on first save.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Path of the snapshot for `planet` generated with `seed`.
+    pub fn path_for(&self, planet: PlanetId, seed: u64) -> PathBuf {
+        self.dir
+            .join(format!("quadtree-{}-{seed:016x}.bin", planet.0))
+    }
+
+    /// Save the leaf sets of a planet's face trees.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created or the file
+    /// cannot be written.
+    pub fn save<'a>(
+        &self,
+        planet: PlanetId,
+        seed: u64,
+        trees: impl IntoIterator<Item = &'a FaceQuadtreeLod>,
+    ) -> io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        write_atomically(&self.path_for(planet, seed), &encode_quadtree_leaves(trees))
+    }
+
+    /// The saved snapshot for `planet` and `seed`, or `None` if there is
+    /// none (or it cannot be read), in which case the trees start cold.
+    pub fn load(&self, planet: PlanetId, seed: u64) -> Option<Vec<u8>> {
+        std::fs::read(self.path_for(planet, seed)).ok()
+    }
+}
+
+/// Write through a temporary file so a crash mid-save cannot leave a
+/// truncated snapshot behind.
+fn write_atomically(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let tmp = path.with_extension("bin.tmp");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path)
+}
@@ -2,6 +2,9 @@
 
 use nebula_math::WorldPosition;
 
+/// Default ratio of a threshold's promote distance to its demote distance.
+pub const DEFAULT_PROMOTE_RATIO: f64 = 0.8;
+
 /// Configuration for distance-based LOD selection.
 #[derive(Clone, Debug)]
 pub struct LodThresholds {
@@ -9,6 +12,9 @@ pub struct LodThresholds {
     /// `thresholds[i]` is the maximum distance for LOD level `i`.
     /// Length determines the number of LOD levels minus one (the last level extends to infinity).
     thresholds: Vec<f64>,
+    /// A chunk demotes past `thresholds[i]` at that distance, but promotes
+    /// back only below `thresholds[i] * promote_ratio`.
+    promote_ratio: f64,
 }
 
 impl LodThresholds {
@@ -16,6 +22,7 @@ impl LodThresholds {
     pub fn default_planet() -> Self {
         Self {
             thresholds: vec![256.0, 512.0, 1024.0, 2048.0, 4096.0],
+            promote_ratio: DEFAULT_PROMOTE_RATIO,
         }
     }
 
@@ -35,7 +42,48 @@ impl LodThresholds {
                 );
             }
         }
-        Self { thresholds }
+        Self {
+            thresholds,
+            promote_ratio: DEFAULT_PROMOTE_RATIO,
+        }
+    }
+
+    /// Set the ratio between promote and demote distances.
+    ///
+    /// The band is shared by [`LodSelector::select_lod_with_current`] and
+    /// [`FaceQuadtreeLod`](crate::FaceQuadtreeLod) splits and merges.
+    ///
+    /// A ratio of 1.0 disables the hysteresis band: chunks promote and
+    /// demote at the same distance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is not in `(0, 1]`.
+    pub fn with_promote_ratio(mut self, ratio: f64) -> Self {
+        assert!(
+            ratio > 0.0 && ratio <= 1.0,
+            "promote ratio must be in (0, 1]"
+        );
+        self.promote_ratio = ratio;
+        self
+    }
+
+    /// Ratio between promote and demote distances.
+    pub fn promote_ratio(&self) -> f64 {
+        self.promote_ratio
+    }
+
+    /// Distance at or beyond which a chunk at `lod` demotes to `lod + 1`,
+    /// or `None` at the coarsest level.
+    pub fn demote_distance(&self, lod: u8) -> Option<f64> {
+        self.thresholds.get(lod as usize).copied()
+    }
+
+    /// Distance below which a chunk at `lod` promotes to `lod - 1`,
+    /// or `None` at LOD 0 and beyond the coarsest level.
+    pub fn promote_distance(&self, lod: u8) -> Option<f64> {
+        let below = lod.checked_sub(1)?;
+        self.demote_distance(below).map(|t| t * self.promote_ratio)
     }
 
     /// Return the maximum LOD level (the coarsest level of detail).
@@ -75,6 +123,35 @@ impl LodSelector {
         self.thresholds.max_lod()
     }
 
+    /// Determine the LOD level for a chunk currently displayed at `current_lod`.
+    ///
+    /// Like [`select_lod`](Self::select_lod), but a chunk only coarsens once
+    /// `distance` reaches its [`demote_distance`](LodThresholds::demote_distance)
+    /// and only refines back below its
+    /// [`promote_distance`](LodThresholds::promote_distance). A chunk sitting
+    /// on a threshold therefore keeps its LOD while the camera jitters
+    /// instead of remeshing every frame.
+    pub fn select_lod_with_current(&self, distance: f64, current_lod: u8) -> u8 {
+        debug_assert!(distance >= 0.0, "distance must be non-negative");
+        let mut lod = current_lod.min(self.thresholds.max_lod());
+
+        while self
+            .thresholds
+            .demote_distance(lod)
+            .is_some_and(|demote| distance >= demote)
+        {
+            lod += 1;
+        }
+        while self
+            .thresholds
+            .promote_distance(lod)
+            .is_some_and(|promote| distance < promote)
+        {
+            lod -= 1;
+        }
+        lod
+    }
+
     /// Return the voxel resolution for a given LOD level.
     /// LOD 0 = 32, LOD 1 = 16, LOD 2 = 8, etc.
     pub fn resolution_for_lod(lod: u8) -> u32 {
@@ -170,6 +247,75 @@ mod tests {
         assert_eq!(selector.select_lod(500.0), 3);
     }
 
+    /// Jitter inside the band below a threshold never flips the LOD.
+    #[test]
+    fn test_hysteresis_keeps_lod_stable_at_threshold() {
+        let selector = default_selector();
+
+        for start in [1u8, 2] {
+            let mut lod = start;
+            for frame in 0..200 {
+                // Oscillate ±45 m inside the 409.6–512 m band below 512 m.
+                let distance = 461.0 + 45.0 * (frame as f64 * 0.7).sin();
+                lod = selector.select_lod_with_current(distance, lod);
+                assert_eq!(lod, start, "LOD flipped at distance {distance}");
+            }
+        }
+    }
+
+    /// Leaving the band does switch LOD, in both directions.
+    #[test]
+    fn test_hysteresis_switches_outside_band() {
+        let selector = default_selector();
+        assert_eq!(selector.select_lod_with_current(511.9, 1), 1);
+        assert_eq!(selector.select_lod_with_current(512.0, 1), 2);
+        assert_eq!(selector.select_lod_with_current(409.7, 2), 2);
+        assert_eq!(selector.select_lod_with_current(409.5, 2), 1);
+
+        // Large camera moves cross several thresholds at once.
+        assert_eq!(selector.select_lod_with_current(10.0, 4), 0);
+        assert_eq!(selector.select_lod_with_current(3000.0, 0), 4);
+        assert_eq!(selector.select_lod_with_current(100_000.0, 0), 5);
+    }
+
+    /// With a promote ratio of 1.0 the result matches `select_lod`.
+    #[test]
+    fn test_unit_promote_ratio_matches_select_lod() {
+        let selector = LodSelector::new(LodThresholds::default_planet().with_promote_ratio(1.0));
+        for d in [0.0, 255.9, 256.0, 700.0, 1024.0, 5000.0] {
+            for current in 0..=5 {
+                assert_eq!(
+                    selector.select_lod_with_current(d, current),
+                    selector.select_lod(d)
+                );
+            }
+        }
+    }
+
+    /// Demotion happens at the threshold, promotion only below the ratio.
+    #[test]
+    fn test_promote_and_demote_distances() {
+        let thresholds = LodThresholds::custom(vec![100.0, 200.0]);
+        assert_eq!(thresholds.demote_distance(0), Some(100.0));
+        assert_eq!(thresholds.demote_distance(2), None);
+        assert_eq!(thresholds.promote_distance(0), None);
+        assert_eq!(thresholds.promote_distance(1), Some(80.0));
+        assert_eq!(thresholds.promote_distance(2), Some(160.0));
+
+        // The selector promotes and demotes at the same distances.
+        let selector = LodSelector::new(thresholds);
+        assert_eq!(selector.select_lod_with_current(99.9, 1), 1);
+        assert_eq!(selector.select_lod_with_current(79.9, 1), 0);
+        assert_eq!(selector.select_lod_with_current(100.0, 0), 1);
+    }
+
+    /// Promote ratios outside (0, 1] are rejected.
+    #[test]
+    #[should_panic(expected = "promote ratio")]
+    fn test_invalid_promote_ratio_panics() {
+        let _ = LodThresholds::default_planet().with_promote_ratio(1.5);
+    }
+
     /// Resolution halves with each LOD level.
     #[test]
     fn test_resolution_for_lod() {
@@ -4,12 +4,14 @@
 mod animator;
 mod atlas;
 pub mod blending;
+mod manifest_validation;
 mod material;
 mod registry;
 
 pub use animator::{AnimationGpuData, MaterialAnimation, MaterialAnimator};
 pub use atlas::{AtlasBuilder, AtlasConfig, AtlasError, TextureAtlas, VoxelTextures};
 pub use blending::{BiomeMap, blend_colors, compute_blend_weight, triplanar_weights};
+pub use manifest_validation::ManifestReport;
 pub use material::{MaterialDef, MaterialError, MaterialGpuData, MaterialId};
 pub use registry::{
     Face, MaterialEntry, MaterialManifest, MaterialRegistry, MaterialUVs, RegistryError,
@@ -0,0 +1,135 @@
+//! Manifest validation: checks a [`MaterialManifest`] against the texture
+//! directory without building the atlas, collecting every problem at once.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::atlas::VoxelTextures;
+use crate::material::MaterialError;
+use crate::registry::{FALLBACK_NAME, MaterialManifest};
+
+/// File extensions treated as textures when looking for unreferenced files.
+const TEXTURE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "tga", "bmp"];
+
+/// Outcome of [`MaterialManifest::validate`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ManifestReport {
+    /// Problems that prevent the registry from loading: empty, reserved or
+    /// duplicate names, and textures that do not fit in the atlas.
+    pub errors: Vec<MaterialError>,
+    /// Problems the registry works around: missing textures, which resolve
+    /// to the placeholder tile, and unreferenced files in the texture
+    /// directory.
+    pub warnings: Vec<MaterialError>,
+}
+
+impl MaterialManifest {
+    /// Check the manifest against the textures under `texture_base_dir`
+    /// without loading anything, collecting every problem found.
+    pub fn validate(&self, texture_base_dir: &Path) -> ManifestReport {
+        let mut report = ManifestReport::default();
+
+        let mut entries_by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (index, entry) in self.materials.iter().enumerate() {
+            if entry.name.is_empty() {
+                report.errors.push(MaterialError::EmptyName);
+            } else if entry.name == FALLBACK_NAME {
+                report.errors.push(MaterialError::ReservedName {
+                    name: entry.name.clone(),
+                    entry: index,
+                });
+            } else {
+                entries_by_name.entry(&entry.name).or_default().push(index);
+            }
+        }
+        for (index, entry) in self.materials.iter().enumerate() {
+            if let Some(entries) = entries_by_name.get(entry.name.as_str())
+                && entries.len() > 1
+                && entries[0] == index
+            {
+                report.errors.push(MaterialError::DuplicateName {
+                    name: entry.name.clone(),
+                    entries: entries.clone(),
+                });
+            }
+        }
+
+        // Walk textures in packing order; tile 0 holds the placeholder.
+        let max_tiles = self.atlas.max_tiles();
+        let mut referenced = HashSet::new();
+        let mut next_tile = 1;
+        for entry in &self.materials {
+            for name in texture_names(&entry.textures) {
+                let path = texture_base_dir.join(name);
+                if !path.is_file() {
+                    report.warnings.push(MaterialError::MissingTexture {
+                        material: entry.name.clone(),
+                        path,
+                    });
+                    continue;
+                }
+                if !referenced.insert(path.clone()) {
+                    continue;
+                }
+                if next_tile >= max_tiles {
+                    report.errors.push(MaterialError::TileOutOfBounds {
+                        material: entry.name.clone(),
+                        path,
+                        tile: next_tile,
+                        max_tiles,
+                    });
+                }
+                next_tile += 1;
+            }
+        }
+
+        let mut files = Vec::new();
+        collect_texture_files(texture_base_dir, &mut files);
+        files.sort();
+        for path in files {
+            if !referenced.contains(&path) {
+                report
+                    .warnings
+                    .push(MaterialError::UnreferencedTexture { path });
+            }
+        }
+
+        report
+    }
+}
+
+/// The distinct texture names a material uses.
+fn texture_names(textures: &VoxelTextures) -> Vec<&str> {
+    match textures {
+        VoxelTextures::Uniform { texture } => vec![texture],
+        VoxelTextures::TopSideBottom { top, side, bottom } => {
+            let mut names: Vec<&str> = vec![top];
+            for name in [side, bottom] {
+                if !names.contains(&name.as_str()) {
+                    names.push(name);
+                }
+            }
+            names
+        }
+    }
+}
+
+/// Recursively collects image files under `dir`. Unreadable directories
+/// are skipped.
+fn collect_texture_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_texture_files(&path, files);
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| TEXTURE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        {
+            files.push(path);
+        }
+    }
+}
@@ -1,5 +1,7 @@
 //! Core material types: [`MaterialId`], [`MaterialDef`], and [`MaterialGpuData`].
 
+use std::path::PathBuf;
+
 use bytemuck::{Pod, Zeroable};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -10,21 +12,82 @@ use thiserror::Error;
 
 /// Compact identifier stored per voxel type to reference a material.
 ///
-/// `MaterialId(0)` is reserved for the default material (neutral grey dielectric).
-/// User-defined materials start at 1, up to a maximum of 65 535.
+/// [`MaterialId::FALLBACK`] (0) is reserved for the fallback material that
+/// unknown IDs resolve to. User-defined materials start at 1, up to a maximum
+/// of 65 535.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct MaterialId(pub u16);
 
+impl MaterialId {
+    /// The fallback material (magenta checkerboard), always registered.
+    pub const FALLBACK: Self = Self(0);
+}
+
 // ---------------------------------------------------------------------------
 // MaterialError
 // ---------------------------------------------------------------------------
 
-/// Errors returned during material validation.
-#[derive(Debug, Error)]
+/// Problems found while validating materials and material manifests.
+///
+/// Manifest entries are identified by their index in the manifest's
+/// `materials` list and texture paths are resolved against the texture
+/// base directory, so each problem points at exactly what to fix.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
 pub enum MaterialError {
     /// The material name must not be empty.
     #[error("material name must not be empty")]
     EmptyName,
+
+    /// Several manifest entries share a name.
+    #[error("material name {name:?} is used by entries {entries:?}")]
+    DuplicateName {
+        /// The shared name.
+        name: String,
+        /// Indices of every entry using the name, in manifest order.
+        entries: Vec<usize>,
+    },
+
+    /// A manifest entry uses a name reserved for a built-in material.
+    #[error("entry {entry} uses the reserved material name {name:?}")]
+    ReservedName {
+        /// The reserved name.
+        name: String,
+        /// Index of the entry.
+        entry: usize,
+    },
+
+    /// A texture referenced by a material does not exist on disk.
+    #[error("material {material:?} references missing texture {}", path.display())]
+    MissingTexture {
+        /// Name of the referencing material.
+        material: String,
+        /// Resolved path of the missing file.
+        path: PathBuf,
+    },
+
+    /// A texture would be packed into a tile slot past the end of the
+    /// atlas, so its UVs would fall outside `[0, 1]`.
+    #[error(
+        "texture {} for material {material:?} needs atlas tile {tile}, but the atlas holds {max_tiles}",
+        path.display()
+    )]
+    TileOutOfBounds {
+        /// Name of the first material referencing the texture.
+        material: String,
+        /// Resolved path of the texture.
+        path: PathBuf,
+        /// Tile slot the texture would occupy.
+        tile: u32,
+        /// Number of tiles the atlas holds.
+        max_tiles: u32,
+    },
+
+    /// An image in the texture directory is not used by any material.
+    #[error("texture {} is not referenced by any material", path.display())]
+    UnreferencedTexture {
+        /// Path of the unused file.
+        path: PathBuf,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -61,6 +124,20 @@ pub struct MaterialDef {
 
     /// Opacity: 1.0 = fully opaque, 0.0 = fully transparent. Clamped to `[0.0, 1.0]`.
     pub opacity: f32,
+
+    /// Voxels covered by one texture tile on liquid surfaces, which tile in
+    /// world space (1.0 = one tile per voxel). Must be > 0.0.
+    #[serde(default = "default_tile_scale")]
+    pub tile_scale: f32,
+
+    /// Texture tiles scrolled per second along u and v, driven by the
+    /// shader's `time_offset`. Zero for static surfaces.
+    #[serde(default)]
+    pub uv_scroll: [f32; 2],
+}
+
+pub(crate) fn default_tile_scale() -> f32 {
+    1.0
 }
 
 impl Default for MaterialDef {
@@ -74,6 +151,8 @@ impl Default for MaterialDef {
             emissive_intensity: 0.0,
             normal_strength: 1.0,
             opacity: 1.0,
+            tile_scale: default_tile_scale(),
+            uv_scroll: [0.0, 0.0],
         }
     }
 }
@@ -98,6 +177,9 @@ impl MaterialDef {
         self.normal_strength = self.normal_strength.clamp(0.0, 1.0);
         self.opacity = self.opacity.clamp(0.0, 1.0);
         self.emissive_intensity = self.emissive_intensity.max(0.0);
+        if self.tile_scale.is_nan() || self.tile_scale <= 0.0 {
+            self.tile_scale = default_tile_scale();
+        }
 
         for c in &mut self.emissive_color {
             *c = c.clamp(0.0, 1.0);
@@ -125,7 +207,7 @@ impl MaterialDef {
 // MaterialGpuData
 // ---------------------------------------------------------------------------
 
-/// GPU-friendly packed PBR material data, 48 bytes, std140-compatible.
+/// GPU-friendly packed PBR material data, 64 bytes, std140-compatible.
 ///
 /// Suitable for upload as a storage buffer element.
 #[repr(C)]
@@ -143,23 +225,15 @@ pub struct MaterialGpuData {
     pub normal_strength: f32,
     /// Opacity.
     pub opacity: f32,
+    /// Texture tiles scrolled per second along u and v.
+    pub uv_scroll: [f32; 2],
+    /// Padding to a 16-byte multiple.
+    pub _padding: [f32; 2],
 }
 
 impl From<MaterialDef> for MaterialGpuData {
     fn from(m: MaterialDef) -> Self {
-        Self {
-            albedo: m.albedo,
-            emissive_rgb_intensity: [
-                m.emissive_color[0],
-                m.emissive_color[1],
-                m.emissive_color[2],
-                m.emissive_intensity,
-            ],
-            metallic: m.metallic,
-            roughness: m.roughness,
-            normal_strength: m.normal_strength,
-            opacity: m.opacity,
-        }
+        Self::from(&m)
     }
 }
 
@@ -177,6 +251,8 @@ impl From<&MaterialDef> for MaterialGpuData {
             roughness: m.roughness,
             normal_strength: m.normal_strength,
             opacity: m.opacity,
+            uv_scroll: m.uv_scroll,
+            _padding: [0.0; 2],
         }
     }
 }
@@ -296,7 +372,7 @@ mod tests {
 
     #[test]
     fn test_material_gpu_data_size_and_alignment() {
-        assert_eq!(std::mem::size_of::<MaterialGpuData>(), 48);
+        assert_eq!(std::mem::size_of::<MaterialGpuData>(), 64);
         assert!(std::mem::align_of::<MaterialGpuData>() >= 4);
     }
 
@@ -310,6 +386,7 @@ mod tests {
             emissive_intensity: 3.0,
             normal_strength: 0.8,
             opacity: 1.0,
+            uv_scroll: [0.25, -0.5],
             ..Default::default()
         };
         let gpu: MaterialGpuData = mat.into();
@@ -319,6 +396,20 @@ mod tests {
         assert_eq!(gpu.emissive_rgb_intensity, [1.0, 0.5, 0.0, 3.0]);
         assert_eq!(gpu.normal_strength, 0.8);
         assert_eq!(gpu.opacity, 1.0);
+        assert_eq!(gpu.uv_scroll, [0.25, -0.5]);
+    }
+
+    #[test]
+    fn test_non_positive_tile_scale_reset_to_default() {
+        for bad in [0.0, -2.0, f32::NAN] {
+            let mat = MaterialDef {
+                tile_scale: bad,
+                ..Default::default()
+            }
+            .validated()
+            .unwrap();
+            assert_eq!(mat.tile_scale, 1.0);
+        }
     }
 
     #[test]
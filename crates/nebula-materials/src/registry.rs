@@ -2,6 +2,11 @@
 //!
 //! Loads material definitions from a RON manifest file, builds the texture atlas,
 //! and provides O(1) lookups.
+//!
+//! Manifests are validated before anything is built, and every problem is
+//! reported at once. Duplicate names and textures that would not fit in the
+//! atlas fail the load; missing texture files only warn, and the materials
+//! using them are bound to the fallback's magenta/black checkerboard tile.
 
 use std::collections::HashMap;
 use std::path::Path;
@@ -10,7 +15,14 @@ use glam::Vec2;
 use thiserror::Error;
 
 use crate::atlas::{AtlasBuilder, AtlasConfig, AtlasError, TextureAtlas, VoxelTextures};
-use crate::material::{MaterialDef, MaterialId};
+use crate::material::{MaterialDef, MaterialError, MaterialId};
+
+/// Name of the built-in fallback material.
+pub(crate) const FALLBACK_NAME: &str = "fallback";
+
+/// Atlas tile name of the magenta/black checkerboard, used by the fallback
+/// material and by every missing texture.
+const PLACEHOLDER_TILE: &str = "__fallback__";
 
 // ---------------------------------------------------------------------------
 // Errors
@@ -33,15 +45,19 @@ pub enum RegistryError {
 
     /// Material validation error.
     #[error("material error: {0}")]
-    Material(#[from] crate::material::MaterialError),
+    Material(#[from] MaterialError),
 
-    /// Duplicate material name.
-    #[error("duplicate material name: {0}")]
-    DuplicateName(String),
+    /// The manifest failed validation; every blocking problem is listed.
+    #[error("invalid material manifest: {}", join_problems(.0))]
+    InvalidManifest(Vec<MaterialError>),
+}
 
-    /// Texture file not found.
-    #[error("texture not found: {0}")]
-    TextureNotFound(String),
+fn join_problems(problems: &[MaterialError]) -> String {
+    problems
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
 }
 
 // ---------------------------------------------------------------------------
@@ -76,6 +92,12 @@ pub struct MaterialEntry {
     pub normal_strength: f32,
     /// Opacity.
     pub opacity: f32,
+    /// Voxels covered by one texture tile on liquid surfaces.
+    #[serde(default = "crate::material::default_tile_scale")]
+    pub tile_scale: f32,
+    /// Texture tiles scrolled per second along u and v.
+    #[serde(default)]
+    pub uv_scroll: (f32, f32),
     /// Texture assignment for faces.
     pub textures: VoxelTextures,
 }
@@ -122,7 +144,7 @@ pub struct MaterialUVs {
 
 /// Unified registry mapping [`MaterialId`] to PBR properties and atlas UVs.
 ///
-/// `MaterialId(0)` is always the fallback (magenta checkerboard).
+/// [`MaterialId::FALLBACK`] is always the fallback (magenta checkerboard).
 /// The registry is immutable after construction.
 pub struct MaterialRegistry {
     /// Dense array: index == `MaterialId.0`.
@@ -133,6 +155,8 @@ pub struct MaterialRegistry {
     name_to_id: HashMap<String, MaterialId>,
     /// The built texture atlas.
     atlas: TextureAtlas,
+    /// Non-fatal problems found while validating the manifest.
+    warnings: Vec<MaterialError>,
 }
 
 impl MaterialRegistry {
@@ -149,12 +173,21 @@ impl MaterialRegistry {
     /// Load the registry from a RON string. Texture paths are resolved
     /// relative to `texture_base_dir`.
     ///
+    /// Missing textures resolve to the placeholder tile and are listed in
+    /// [`warnings`](Self::warnings).
+    ///
     /// # Errors
     ///
-    /// Returns [`RegistryError`] on parse or validation failures.
+    /// Returns [`RegistryError`] on parse failures, or
+    /// [`RegistryError::InvalidManifest`] with every blocking problem found
+    /// by [`MaterialManifest::validate`].
     pub fn from_ron_str(ron_str: &str, texture_base_dir: &Path) -> Result<Self, RegistryError> {
         let manifest: MaterialManifest = ron::from_str(ron_str)?;
         manifest.atlas.validate()?;
+        let report = manifest.validate(texture_base_dir);
+        if !report.errors.is_empty() {
+            return Err(RegistryError::InvalidManifest(report.errors));
+        }
 
         let mut builder = AtlasBuilder::new(manifest.atlas);
         let mut materials = Vec::with_capacity(manifest.materials.len() + 1);
@@ -162,17 +195,13 @@ impl MaterialRegistry {
         let mut name_to_id = HashMap::new();
 
         // Register fallback at ID 0
-        let (fallback_def, fallback_uvs) = register_fallback(&mut builder)?;
+        let (fallback_def, fallback_uvs, placeholder) = register_fallback(&mut builder)?;
         materials.push(fallback_def);
         uvs.push(fallback_uvs);
-        name_to_id.insert("fallback".to_string(), MaterialId(0));
+        name_to_id.insert(FALLBACK_NAME.to_string(), MaterialId::FALLBACK);
 
         // Register user materials
         for entry in manifest.materials {
-            if name_to_id.contains_key(&entry.name) {
-                return Err(RegistryError::DuplicateName(entry.name));
-            }
-
             let id = MaterialId(materials.len() as u16);
 
             let mat_def = MaterialDef {
@@ -193,10 +222,17 @@ impl MaterialRegistry {
                 emissive_intensity: entry.emissive_intensity,
                 normal_strength: entry.normal_strength,
                 opacity: entry.opacity,
+                tile_scale: entry.tile_scale,
+                uv_scroll: [entry.uv_scroll.0, entry.uv_scroll.1],
             }
             .validated()?;
 
-            let mat_uvs = add_textures_to_atlas(&mut builder, &entry.textures, texture_base_dir)?;
+            let mat_uvs = add_textures_to_atlas(
+                &mut builder,
+                &entry.textures,
+                texture_base_dir,
+                placeholder,
+            )?;
 
             name_to_id.insert(entry.name, id);
             materials.push(mat_def);
@@ -209,6 +245,7 @@ impl MaterialRegistry {
             uvs,
             name_to_id,
             atlas,
+            warnings: report.warnings,
         })
     }
 
@@ -218,14 +255,17 @@ impl MaterialRegistry {
     pub fn get(&self, id: MaterialId) -> &MaterialDef {
         self.materials
             .get(id.0 as usize)
-            .unwrap_or(&self.materials[0])
+            .unwrap_or(&self.materials[MaterialId::FALLBACK.0 as usize])
     }
 
     /// Returns the atlas UV rectangle for a specific face of a material.
     ///
     /// If the ID is out of range, returns the fallback UVs.
     pub fn atlas_uvs(&self, id: MaterialId, face: Face) -> (Vec2, Vec2) {
-        let mat_uvs = self.uvs.get(id.0 as usize).unwrap_or(&self.uvs[0]);
+        let mat_uvs = self
+            .uvs
+            .get(id.0 as usize)
+            .unwrap_or(&self.uvs[MaterialId::FALLBACK.0 as usize]);
         match face {
             Face::Top => mat_uvs.top,
             Face::Bottom => mat_uvs.bottom,
@@ -252,6 +292,12 @@ impl MaterialRegistry {
     pub fn atlas(&self) -> &TextureAtlas {
         &self.atlas
     }
+
+    /// Non-fatal problems found while loading: missing textures (bound to
+    /// the placeholder tile) and unreferenced texture files.
+    pub fn warnings(&self) -> &[MaterialError] {
+        &self.warnings
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -271,12 +317,13 @@ fn generate_checkerboard(size: u32, color_a: [u8; 4], color_b: [u8; 4]) -> image
     })
 }
 
-/// Registers the fallback material (magenta checkerboard) at tile slot 0.
+/// Registers the fallback material (magenta checkerboard) at tile slot 0,
+/// returning its definition, UVs, and the placeholder tile index.
 fn register_fallback(
     builder: &mut AtlasBuilder,
-) -> Result<(MaterialDef, MaterialUVs), RegistryError> {
+) -> Result<(MaterialDef, MaterialUVs, u32), RegistryError> {
     let fallback_def = MaterialDef {
-        name: "fallback".to_string(),
+        name: FALLBACK_NAME.to_string(),
         albedo: [1.0, 0.0, 1.0, 1.0],
         metallic: 0.0,
         roughness: 1.0,
@@ -284,10 +331,11 @@ fn register_fallback(
         emissive_intensity: 0.0,
         normal_strength: 0.0,
         opacity: 1.0,
+        ..MaterialDef::default()
     };
 
     let checkerboard = generate_checkerboard(32, [255, 0, 255, 255], [0, 0, 0, 255]);
-    let tile_idx = builder.add_texture_from_image("__fallback__", &checkerboard)?;
+    let tile_idx = builder.add_texture_from_image(PLACEHOLDER_TILE, &checkerboard)?;
     let uv = builder.tile_uvs(tile_idx);
 
     Ok((
@@ -297,18 +345,21 @@ fn register_fallback(
             side: uv,
             bottom: uv,
         },
+        tile_idx,
     ))
 }
 
 /// Resolves texture paths and adds them to the atlas, returning [`MaterialUVs`].
+/// Missing files use the `placeholder` tile.
 fn add_textures_to_atlas(
     builder: &mut AtlasBuilder,
     textures: &VoxelTextures,
     base_dir: &Path,
+    placeholder: u32,
 ) -> Result<MaterialUVs, RegistryError> {
     match textures {
         VoxelTextures::Uniform { texture } => {
-            let idx = load_or_generate_tile(builder, texture, base_dir)?;
+            let idx = load_or_placeholder_tile(builder, texture, base_dir, placeholder)?;
             let uv = builder.tile_uvs(idx);
             Ok(MaterialUVs {
                 top: uv,
@@ -317,9 +368,9 @@ fn add_textures_to_atlas(
             })
         }
         VoxelTextures::TopSideBottom { top, side, bottom } => {
-            let top_idx = load_or_generate_tile(builder, top, base_dir)?;
-            let side_idx = load_or_generate_tile(builder, side, base_dir)?;
-            let bottom_idx = load_or_generate_tile(builder, bottom, base_dir)?;
+            let top_idx = load_or_placeholder_tile(builder, top, base_dir, placeholder)?;
+            let side_idx = load_or_placeholder_tile(builder, side, base_dir, placeholder)?;
+            let bottom_idx = load_or_placeholder_tile(builder, bottom, base_dir, placeholder)?;
             Ok(MaterialUVs {
                 top: builder.tile_uvs(top_idx),
                 side: builder.tile_uvs(side_idx),
@@ -329,51 +380,33 @@ fn add_textures_to_atlas(
     }
 }
 
-/// Loads a texture file from disk, or generates a solid-color placeholder if not found.
-fn load_or_generate_tile(
+/// Loads a texture file from disk, or returns the `placeholder` tile if the
+/// file is missing.
+fn load_or_placeholder_tile(
     builder: &mut AtlasBuilder,
     name: &str,
     base_dir: &Path,
+    placeholder: u32,
 ) -> Result<u32, RegistryError> {
-    // Check if already added (deduplication in AtlasBuilder)
-    if let Some(idx) = builder.tile_index(name) {
-        return Ok(idx);
-    }
-
     let path = base_dir.join(name);
-    if path.exists() {
+    if path.is_file() {
         Ok(builder.add_texture(&path.to_string_lossy(), &path)?)
     } else {
-        // Generate a solid-color placeholder so the engine doesn't crash
-        // on missing textures during development.
-        let hash = name
-            .bytes()
-            .fold(0u32, |acc, b| acc.wrapping_mul(31).wrapping_add(b as u32));
-        let r = ((hash >> 16) & 0xFF) as u8;
-        let g = ((hash >> 8) & 0xFF) as u8;
-        let b = (hash & 0xFF) as u8;
-        let placeholder = image::RgbaImage::from_pixel(32, 32, image::Rgba([r, g, b, 255]));
-        Ok(builder.add_texture_from_image(name, &placeholder)?)
+        Ok(placeholder)
     }
 }
 
 // ---------------------------------------------------------------------------
-// AtlasBuilder extension — tile_index and tile_uvs helpers
+// AtlasBuilder extension — tile_uvs helper
 // ---------------------------------------------------------------------------
 
 /// Extension trait giving `AtlasBuilder` UV lookup before `build()`.
 trait AtlasBuilderExt {
-    /// Look up a tile index by name.
-    fn tile_index(&self, name: &str) -> Option<u32>;
     /// Get UV coords for a tile index.
     fn tile_uvs(&self, tile_index: u32) -> (Vec2, Vec2);
 }
 
 impl AtlasBuilderExt for AtlasBuilder {
-    fn tile_index(&self, name: &str) -> Option<u32> {
-        self.tile_map().get(name).copied()
-    }
-
     fn tile_uvs(&self, tile_index: u32) -> (Vec2, Vec2) {
         let tiles_per_row = self.config().tiles_per_row();
         let col = tile_index % tiles_per_row;
@@ -393,171 +426,5 @@ impl AtlasBuilderExt for AtlasBuilder {
 // ---------------------------------------------------------------------------
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::TempDir;
-
-    fn sample_ron() -> String {
-        r#"MaterialManifest(
-            atlas: AtlasConfig(atlas_size: 256, tile_size: 16),
-            materials: [
-                (
-                    name: "stone",
-                    albedo: (0.5, 0.5, 0.5, 1.0),
-                    metallic: 0.0,
-                    roughness: 0.85,
-                    emissive_color: (0.0, 0.0, 0.0),
-                    emissive_intensity: 0.0,
-                    normal_strength: 1.0,
-                    opacity: 1.0,
-                    textures: Uniform(texture: "stone.png"),
-                ),
-                (
-                    name: "dirt",
-                    albedo: (0.6, 0.4, 0.2, 1.0),
-                    metallic: 0.0,
-                    roughness: 0.95,
-                    emissive_color: (0.0, 0.0, 0.0),
-                    emissive_intensity: 0.0,
-                    normal_strength: 1.0,
-                    opacity: 1.0,
-                    textures: Uniform(texture: "dirt.png"),
-                ),
-            ],
-        )"#
-        .to_string()
-    }
-
-    fn create_test_textures() -> TempDir {
-        let dir = TempDir::new().unwrap();
-        // Create small PNG textures
-        let stone = image::RgbaImage::from_pixel(16, 16, image::Rgba([128, 128, 128, 255]));
-        stone.save(dir.path().join("stone.png")).unwrap();
-        let dirt = image::RgbaImage::from_pixel(16, 16, image::Rgba([139, 90, 43, 255]));
-        dirt.save(dir.path().join("dirt.png")).unwrap();
-        dir
-    }
-
-    fn create_test_registry() -> MaterialRegistry {
-        let dir = create_test_textures();
-        MaterialRegistry::from_ron_str(&sample_ron(), dir.path()).unwrap()
-    }
-
-    #[test]
-    fn test_registry_loads_from_ron() {
-        let registry = create_test_registry();
-        // Fallback (ID 0) + stone (ID 1) + dirt (ID 2) = 3 materials
-        assert_eq!(registry.len(), 3);
-    }
-
-    #[test]
-    fn test_all_material_ids_are_sequential() {
-        let registry = create_test_registry();
-        assert_eq!(registry.get(MaterialId(0)).name, "fallback");
-        assert_eq!(registry.get(MaterialId(1)).name, "stone");
-        assert_eq!(registry.get(MaterialId(2)).name, "dirt");
-    }
-
-    #[test]
-    fn test_atlas_uvs_are_valid() {
-        let registry = create_test_registry();
-        for id in 0..registry.len() as u16 {
-            for face in [
-                Face::Top,
-                Face::Bottom,
-                Face::North,
-                Face::South,
-                Face::East,
-                Face::West,
-            ] {
-                let (uv_min, uv_max) = registry.atlas_uvs(MaterialId(id), face);
-                assert!(
-                    uv_min.x >= 0.0 && uv_min.x <= 1.0,
-                    "UV min x out of range for material {id}, face {face:?}"
-                );
-                assert!(
-                    uv_min.y >= 0.0 && uv_min.y <= 1.0,
-                    "UV min y out of range for material {id}, face {face:?}"
-                );
-                assert!(
-                    uv_max.x >= 0.0 && uv_max.x <= 1.0,
-                    "UV max x out of range for material {id}, face {face:?}"
-                );
-                assert!(
-                    uv_max.y >= 0.0 && uv_max.y <= 1.0,
-                    "UV max y out of range for material {id}, face {face:?}"
-                );
-                assert!(uv_min.x < uv_max.x);
-                assert!(uv_min.y < uv_max.y);
-            }
-        }
-    }
-
-    #[test]
-    fn test_missing_material_returns_fallback() {
-        let registry = create_test_registry();
-        let mat = registry.get(MaterialId(9999));
-        assert_eq!(mat.name, "fallback");
-        assert_eq!(mat.albedo, [1.0, 0.0, 1.0, 1.0]);
-    }
-
-    #[test]
-    fn test_registry_is_read_only_at_runtime() {
-        let registry = create_test_registry();
-        let _ref: &MaterialDef = registry.get(MaterialId(1));
-        let _uvs: (Vec2, Vec2) = registry.atlas_uvs(MaterialId(1), Face::Top);
-        let _name: Option<MaterialId> = registry.lookup_by_name("stone");
-    }
-
-    #[test]
-    fn test_lookup_by_name() {
-        let registry = create_test_registry();
-        assert_eq!(registry.lookup_by_name("stone"), Some(MaterialId(1)));
-        assert_eq!(registry.lookup_by_name("dirt"), Some(MaterialId(2)));
-        assert_eq!(registry.lookup_by_name("nonexistent"), None);
-    }
-
-    #[test]
-    fn test_fallback_is_always_id_zero() {
-        let registry = create_test_registry();
-        let fallback = registry.get(MaterialId(0));
-        assert_eq!(fallback.name, "fallback");
-        assert_eq!(fallback.albedo[0], 1.0);
-        assert_eq!(fallback.albedo[1], 0.0);
-        assert_eq!(fallback.albedo[2], 1.0);
-    }
-
-    #[test]
-    fn test_is_empty() {
-        let registry = create_test_registry();
-        assert!(!registry.is_empty());
-    }
-
-    #[test]
-    fn test_duplicate_name_rejected() {
-        let ron = r#"MaterialManifest(
-            atlas: AtlasConfig(atlas_size: 256, tile_size: 16),
-            materials: [
-                (
-                    name: "stone",
-                    albedo: (0.5, 0.5, 0.5, 1.0),
-                    metallic: 0.0, roughness: 0.85,
-                    emissive_color: (0.0, 0.0, 0.0), emissive_intensity: 0.0,
-                    normal_strength: 1.0, opacity: 1.0,
-                    textures: Uniform(texture: "stone.png"),
-                ),
-                (
-                    name: "stone",
-                    albedo: (0.5, 0.5, 0.5, 1.0),
-                    metallic: 0.0, roughness: 0.85,
-                    emissive_color: (0.0, 0.0, 0.0), emissive_intensity: 0.0,
-                    normal_strength: 1.0, opacity: 1.0,
-                    textures: Uniform(texture: "stone.png"),
-                ),
-            ],
-        )"#;
-        let dir = create_test_textures();
-        let result = MaterialRegistry::from_ron_str(ron, dir.path());
-        assert!(matches!(result, Err(RegistryError::DuplicateName(_))));
-    }
-}
+#[path = "registry_tests.rs"]
+mod tests;
@@ -0,0 +1,289 @@
+//! Unit tests for manifest loading, validation, and material lookups.
+
+use super::*;
+use tempfile::TempDir;
+
+fn sample_ron() -> String {
+    r#"MaterialManifest(
+        atlas: AtlasConfig(atlas_size: 256, tile_size: 16),
+        materials: [
+            (
+                name: "stone",
+                albedo: (0.5, 0.5, 0.5, 1.0),
+                metallic: 0.0,
+                roughness: 0.85,
+                emissive_color: (0.0, 0.0, 0.0),
+                emissive_intensity: 0.0,
+                normal_strength: 1.0,
+                opacity: 1.0,
+                textures: Uniform(texture: "stone.png"),
+            ),
+            (
+                name: "dirt",
+                albedo: (0.6, 0.4, 0.2, 1.0),
+                metallic: 0.0,
+                roughness: 0.95,
+                emissive_color: (0.0, 0.0, 0.0),
+                emissive_intensity: 0.0,
+                normal_strength: 1.0,
+                opacity: 1.0,
+                textures: Uniform(texture: "dirt.png"),
+            ),
+        ],
+    )"#
+    .to_string()
+}
+
+/// A manifest with one uniformly textured material per `(name, texture)`.
+fn manifest_ron(atlas_size: u32, materials: &[(&str, &str)]) -> String {
+    let entries: String = materials
+        .iter()
+        .map(|(name, texture)| {
+            format!(
+                r#"(
+                name: "{name}",
+                albedo: (0.5, 0.5, 0.5, 1.0),
+                metallic: 0.0, roughness: 0.85,
+                emissive_color: (0.0, 0.0, 0.0), emissive_intensity: 0.0,
+                normal_strength: 1.0, opacity: 1.0,
+                textures: Uniform(texture: "{texture}"),
+            ),"#
+            )
+        })
+        .collect();
+    format!(
+        "MaterialManifest(atlas: AtlasConfig(atlas_size: {atlas_size}, tile_size: 16), \
+         materials: [{entries}])"
+    )
+}
+
+fn create_test_textures() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    // Create small PNG textures
+    let stone = image::RgbaImage::from_pixel(16, 16, image::Rgba([128, 128, 128, 255]));
+    stone.save(dir.path().join("stone.png")).unwrap();
+    let dirt = image::RgbaImage::from_pixel(16, 16, image::Rgba([139, 90, 43, 255]));
+    dirt.save(dir.path().join("dirt.png")).unwrap();
+    dir
+}
+
+fn create_test_registry() -> MaterialRegistry {
+    let dir = create_test_textures();
+    MaterialRegistry::from_ron_str(&sample_ron(), dir.path()).unwrap()
+}
+
+#[test]
+fn test_registry_loads_from_ron() {
+    let registry = create_test_registry();
+    // Fallback (ID 0) + stone (ID 1) + dirt (ID 2) = 3 materials
+    assert_eq!(registry.len(), 3);
+}
+
+#[test]
+fn test_all_material_ids_are_sequential() {
+    let registry = create_test_registry();
+    assert_eq!(registry.get(MaterialId(0)).name, "fallback");
+    assert_eq!(registry.get(MaterialId(1)).name, "stone");
+    assert_eq!(registry.get(MaterialId(2)).name, "dirt");
+}
+
+#[test]
+fn test_atlas_uvs_are_valid() {
+    let registry = create_test_registry();
+    for id in 0..registry.len() as u16 {
+        for face in [
+            Face::Top,
+            Face::Bottom,
+            Face::North,
+            Face::South,
+            Face::East,
+            Face::West,
+        ] {
+            let (uv_min, uv_max) = registry.atlas_uvs(MaterialId(id), face);
+            assert!(
+                uv_min.x >= 0.0 && uv_min.x <= 1.0,
+                "UV min x out of range for material {id}, face {face:?}"
+            );
+            assert!(
+                uv_min.y >= 0.0 && uv_min.y <= 1.0,
+                "UV min y out of range for material {id}, face {face:?}"
+            );
+            assert!(
+                uv_max.x >= 0.0 && uv_max.x <= 1.0,
+                "UV max x out of range for material {id}, face {face:?}"
+            );
+            assert!(
+                uv_max.y >= 0.0 && uv_max.y <= 1.0,
+                "UV max y out of range for material {id}, face {face:?}"
+            );
+            assert!(uv_min.x < uv_max.x);
+            assert!(uv_min.y < uv_max.y);
+        }
+    }
+}
+
+#[test]
+fn test_missing_material_returns_fallback() {
+    let registry = create_test_registry();
+    let mat = registry.get(MaterialId(9999));
+    assert_eq!(mat.name, "fallback");
+    assert_eq!(mat.albedo, [1.0, 0.0, 1.0, 1.0]);
+}
+
+#[test]
+fn test_registry_is_read_only_at_runtime() {
+    let registry = create_test_registry();
+    let _ref: &MaterialDef = registry.get(MaterialId(1));
+    let _uvs: (Vec2, Vec2) = registry.atlas_uvs(MaterialId(1), Face::Top);
+    let _name: Option<MaterialId> = registry.lookup_by_name("stone");
+}
+
+#[test]
+fn test_lookup_by_name() {
+    let registry = create_test_registry();
+    assert_eq!(registry.lookup_by_name("stone"), Some(MaterialId(1)));
+    assert_eq!(registry.lookup_by_name("dirt"), Some(MaterialId(2)));
+    assert_eq!(registry.lookup_by_name("nonexistent"), None);
+}
+
+#[test]
+fn test_fallback_is_always_id_zero() {
+    let registry = create_test_registry();
+    let fallback = registry.get(MaterialId(0));
+    assert_eq!(fallback.name, "fallback");
+    assert_eq!(fallback.albedo[0], 1.0);
+    assert_eq!(fallback.albedo[1], 0.0);
+    assert_eq!(fallback.albedo[2], 1.0);
+}
+
+#[test]
+fn test_is_empty() {
+    let registry = create_test_registry();
+    assert!(!registry.is_empty());
+}
+
+#[test]
+fn test_duplicate_name_rejected() {
+    let ron = manifest_ron(
+        256,
+        &[
+            ("stone", "stone.png"),
+            ("dirt", "dirt.png"),
+            ("stone", "stone.png"),
+        ],
+    );
+    let dir = create_test_textures();
+    let Err(RegistryError::InvalidManifest(problems)) =
+        MaterialRegistry::from_ron_str(&ron, dir.path())
+    else {
+        panic!("duplicate names should fail to load");
+    };
+    assert_eq!(
+        problems,
+        vec![MaterialError::DuplicateName {
+            name: "stone".to_string(),
+            entries: vec![0, 2],
+        }]
+    );
+}
+
+#[test]
+fn test_missing_texture_binds_placeholder_and_warns() {
+    let ron = manifest_ron(256, &[("stone", "stone.png"), ("ore", "ore.png")]);
+    let dir = create_test_textures();
+    let registry = MaterialRegistry::from_ron_str(&ron, dir.path()).unwrap();
+
+    let ore = registry.lookup_by_name("ore").unwrap();
+    assert_eq!(registry.get(ore).name, "ore");
+    for face in [Face::Top, Face::Bottom, Face::North] {
+        assert_eq!(
+            registry.atlas_uvs(ore, face),
+            registry.atlas_uvs(MaterialId::FALLBACK, face)
+        );
+    }
+    let stone = registry.lookup_by_name("stone").unwrap();
+    assert_ne!(
+        registry.atlas_uvs(stone, Face::Top),
+        registry.atlas_uvs(MaterialId::FALLBACK, Face::Top)
+    );
+
+    // dirt.png exists but nothing uses it.
+    assert_eq!(
+        registry.warnings(),
+        [
+            MaterialError::MissingTexture {
+                material: "ore".to_string(),
+                path: dir.path().join("ore.png"),
+            },
+            MaterialError::UnreferencedTexture {
+                path: dir.path().join("dirt.png"),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_validation_reports_every_problem_at_once() {
+    // A 32×32 atlas of 16-pixel tiles holds the placeholder and three
+    // textures, so the fourth distinct texture does not fit; reusing a
+    // packed texture needs no new tile.
+    let dir = create_test_textures();
+    for name in ["a.png", "b.png", "c.png"] {
+        image::RgbaImage::new(16, 16)
+            .save(dir.path().join(name))
+            .unwrap();
+    }
+    let ron = manifest_ron(
+        32,
+        &[
+            ("fallback", "stone.png"),
+            ("a", "a.png"),
+            ("b", "b.png"),
+            ("a", "c.png"),
+            ("d", "b.png"),
+        ],
+    );
+    let manifest: MaterialManifest = ron::from_str(&ron).unwrap();
+    let report = manifest.validate(dir.path());
+    assert_eq!(
+        report.errors,
+        vec![
+            MaterialError::ReservedName {
+                name: "fallback".to_string(),
+                entry: 0,
+            },
+            MaterialError::DuplicateName {
+                name: "a".to_string(),
+                entries: vec![1, 3],
+            },
+            MaterialError::TileOutOfBounds {
+                material: "a".to_string(),
+                path: dir.path().join("c.png"),
+                tile: 4,
+                max_tiles: 4,
+            },
+        ]
+    );
+    assert_eq!(
+        report.warnings,
+        vec![MaterialError::UnreferencedTexture {
+            path: dir.path().join("dirt.png"),
+        }]
+    );
+}
+
+#[test]
+fn test_invalid_manifest_error_lists_problems() {
+    let err = RegistryError::InvalidManifest(vec![
+        MaterialError::EmptyName,
+        MaterialError::DuplicateName {
+            name: "stone".to_string(),
+            entries: vec![1, 4],
+        },
+    ]);
+    assert_eq!(
+        err.to_string(),
+        "invalid material manifest: material name must not be empty; \
+         material name \"stone\" is used by entries [1, 4]"
+    );
+}
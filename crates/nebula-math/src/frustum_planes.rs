@@ -0,0 +1,53 @@
+use glam::{DMat4, DVec4};
+
+/// Extract the six normalized, inward-pointing frustum planes from a
+/// view-projection matrix with clip-space depth in `[0, 1]`
+/// (Griggs-Hartmann).
+///
+/// Planes are `(a, b, c, d)` with `(a, b, c)` the unit inward normal, in
+/// the order left, right, bottom, top, `z = 0`, `z = w`. The last two are
+/// the near and far planes of a standard projection; under reverse-Z they
+/// swap roles, so the set is the same for both.
+pub fn frustum_planes(vp: &DMat4) -> [DVec4; 6] {
+    let rows = [vp.row(0), vp.row(1), vp.row(2), vp.row(3)];
+    let mut planes = [
+        rows[3] + rows[0],
+        rows[3] - rows[0],
+        rows[3] + rows[1],
+        rows[3] - rows[1],
+        rows[2],
+        rows[3] - rows[2],
+    ];
+    for plane in &mut planes {
+        let len = plane.truncate().length();
+        if len > 0.0 {
+            *plane /= len;
+        }
+    }
+    planes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::DVec3;
+
+    fn inside(planes: &[DVec4; 6], p: DVec3) -> bool {
+        planes
+            .iter()
+            .all(|plane| plane.truncate().dot(p) + plane.w >= 0.0)
+    }
+
+    #[test]
+    fn test_standard_and_reverse_z_give_the_same_volume() {
+        let view = DMat4::look_to_rh(DVec3::ZERO, DVec3::NEG_Z, DVec3::Y);
+        let standard = frustum_planes(&(DMat4::perspective_rh(1.0, 1.0, 1.0, 100.0) * view));
+        let reverse = frustum_planes(&(DMat4::perspective_rh(1.0, 1.0, 100.0, 1.0) * view));
+        for planes in [standard, reverse] {
+            assert!(inside(&planes, DVec3::new(0.0, 0.0, -50.0)));
+            assert!(!inside(&planes, DVec3::new(0.0, 0.0, -0.5)), "before near");
+            assert!(!inside(&planes, DVec3::new(0.0, 0.0, -150.0)), "beyond far");
+            assert!(!inside(&planes, DVec3::new(0.0, 0.0, 10.0)), "behind");
+        }
+    }
+}
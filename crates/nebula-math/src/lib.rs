@@ -3,6 +3,7 @@
 mod aabb;
 mod conversion;
 mod fixed_point;
+mod frustum_planes;
 mod local_position;
 mod units;
 mod vector;
@@ -11,6 +12,7 @@ mod world_position;
 pub use aabb::Aabb128;
 pub use conversion::{MAX_SAFE_LOCAL_DELTA, to_local, to_local_batch, to_local_checked, to_world};
 pub use fixed_point::FixedI128;
+pub use frustum_planes::frustum_planes;
 pub use local_position::LocalPosition;
 pub use units::{
     EARTH_RADIUS_UNITS, SOLAR_RADIUS_UNITS, UNITS_PER_AU, UNITS_PER_CENTIMETER, UNITS_PER_INCH,
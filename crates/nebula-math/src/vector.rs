@@ -2,6 +2,7 @@ use std::fmt;
 use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use crate::WorldPosition;
+use glam::DVec3;
 
 /// 3D displacement / direction vector in i128 space.
 #[repr(C)]
@@ -84,7 +85,9 @@ impl Vec3I128 {
     /// stays within range.
     ///
     /// For components up to ~2⁴¹ (~2.2×10¹²), the result fits
-    /// comfortably with no risk.
+    /// comfortably with no risk. Overflow panics in debug builds and
+    /// wraps in release; use [`checked_dot`](Self::checked_dot) when the
+    /// inputs are untrusted.
     pub fn dot(self, rhs: Vec3I128) -> i128 {
         self.x * rhs.x + self.y * rhs.y + self.z * rhs.z
     }
@@ -171,6 +174,33 @@ impl Vec3I128 {
         (x * x + y * y + z * z).sqrt()
     }
 
+    /// Returns x² + y² + z² as a `u128`.
+    ///
+    /// Squaring the unsigned magnitudes doubles the headroom of
+    /// [`magnitude_squared`](Self::magnitude_squared): the sum stays in
+    /// range for components below ~1.06×10¹⁹ (~2⁶³·¹). Overflow panics in
+    /// debug builds and wraps in release, like the other operators.
+    pub fn length_squared(self) -> u128 {
+        let x = self.x.unsigned_abs();
+        let y = self.y.unsigned_abs();
+        let z = self.z.unsigned_abs();
+        x * x + y * y + z * z
+    }
+
+    /// Euclidean length as `f64`. Same precision notes as
+    /// [`magnitude_f64`](Self::magnitude_f64).
+    pub fn length_f64(self) -> f64 {
+        self.magnitude_f64()
+    }
+
+    /// Unit-length `f64` direction of this vector, or zero for the zero vector.
+    ///
+    /// Components are converted to `f64` before normalizing, so this never
+    /// overflows even for galaxy-scale vectors.
+    pub fn normalize_f64(self) -> DVec3 {
+        DVec3::new(self.x as f64, self.y as f64, self.z as f64).normalize_or_zero()
+    }
+
     /// Manhattan (L1) magnitude: |x| + |y| + |z|.
     ///
     /// Cannot overflow unless the sum of three i128::MAX-magnitude
@@ -438,262 +468,5 @@ pub fn manhattan_distance(a: WorldPosition, b: WorldPosition) -> i128 {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_add() {
-        let a = Vec3I128::new(1, 2, 3);
-        let b = Vec3I128::new(10, 20, 30);
-        assert_eq!(a + b, Vec3I128::new(11, 22, 33));
-    }
-
-    #[test]
-    fn test_sub() {
-        let a = Vec3I128::new(10, 20, 30);
-        let b = Vec3I128::new(1, 2, 3);
-        assert_eq!(a - b, Vec3I128::new(9, 18, 27));
-    }
-
-    #[test]
-    fn test_neg() {
-        let v = Vec3I128::new(1, -2, 3);
-        assert_eq!(-v, Vec3I128::new(-1, 2, -3));
-    }
-
-    #[test]
-    fn test_scalar_mul() {
-        let v = Vec3I128::new(2, 3, 4);
-        assert_eq!(v * 10, Vec3I128::new(20, 30, 40));
-    }
-
-    #[test]
-    fn test_scalar_div() {
-        let v = Vec3I128::new(20, 30, 40);
-        assert_eq!(v / 10, Vec3I128::new(2, 3, 4));
-    }
-
-    #[test]
-    fn test_scalar_div_truncates() {
-        let v = Vec3I128::new(7, 7, 7);
-        assert_eq!(v / 2, Vec3I128::new(3, 3, 3)); // truncation toward zero
-    }
-
-    #[test]
-    fn test_zero_vector() {
-        let z = Vec3I128::zero();
-        assert_eq!(z, Vec3I128::new(0, 0, 0));
-        assert_eq!(z, Vec3I128::default());
-    }
-
-    #[test]
-    fn test_basis_vectors() {
-        assert_eq!(Vec3I128::unit_x(), Vec3I128::new(1, 0, 0));
-        assert_eq!(Vec3I128::unit_y(), Vec3I128::new(0, 1, 0));
-        assert_eq!(Vec3I128::unit_z(), Vec3I128::new(0, 0, 1));
-    }
-
-    #[test]
-    #[cfg(debug_assertions)]
-    #[should_panic]
-    fn test_overflow_panics_in_debug() {
-        let v = Vec3I128::new(i128::MAX, 0, 0);
-        let _ = v + Vec3I128::new(1, 0, 0); // overflow
-    }
-
-    #[test]
-    fn test_checked_add_overflow() {
-        let v = Vec3I128::new(i128::MAX, 0, 0);
-        assert!(v.checked_add(Vec3I128::new(1, 0, 0)).is_none());
-    }
-
-    #[test]
-    fn test_saturating_add() {
-        let v = Vec3I128::new(i128::MAX, 0, 0);
-        let result = v.saturating_add(Vec3I128::new(1, 0, 0));
-        assert_eq!(result.x, i128::MAX);
-    }
-
-    #[test]
-    fn test_vec2_add() {
-        let a = Vec2I128::new(1, 2);
-        let b = Vec2I128::new(10, 20);
-        assert_eq!(a + b, Vec2I128::new(11, 22));
-    }
-
-    #[test]
-    fn test_dot_orthogonal_is_zero() {
-        let x = Vec3I128::unit_x();
-        let y = Vec3I128::unit_y();
-        assert_eq!(x.dot(y), 0);
-    }
-
-    #[test]
-    fn test_dot_parallel() {
-        let v = Vec3I128::new(3, 0, 0);
-        let w = Vec3I128::new(5, 0, 0);
-        assert_eq!(v.dot(w), 15);
-    }
-
-    #[test]
-    fn test_dot_antiparallel() {
-        let v = Vec3I128::new(3, 0, 0);
-        let w = Vec3I128::new(-5, 0, 0);
-        assert_eq!(v.dot(w), -15);
-    }
-
-    #[test]
-    fn test_dot_general() {
-        let a = Vec3I128::new(1, 2, 3);
-        let b = Vec3I128::new(4, 5, 6);
-        // 1*4 + 2*5 + 3*6 = 4 + 10 + 18 = 32
-        assert_eq!(a.dot(b), 32);
-    }
-
-    #[test]
-    fn test_cross_basis_vectors() {
-        let x = Vec3I128::unit_x();
-        let y = Vec3I128::unit_y();
-        let z = Vec3I128::unit_z();
-        assert_eq!(x.cross(y), z); // x × y = z
-        assert_eq!(y.cross(z), x); // y × z = x
-        assert_eq!(z.cross(x), y); // z × x = y
-    }
-
-    #[test]
-    fn test_cross_anti_commutativity() {
-        let a = Vec3I128::new(1, 2, 3);
-        let b = Vec3I128::new(4, 5, 6);
-        assert_eq!(a.cross(b), -b.cross(a));
-    }
-
-    #[test]
-    fn test_cross_self_is_zero() {
-        let v = Vec3I128::new(7, 11, 13);
-        assert_eq!(v.cross(v), Vec3I128::zero());
-    }
-
-    #[test]
-    fn test_component_min() {
-        let a = Vec3I128::new(1, 5, 3);
-        let b = Vec3I128::new(4, 2, 6);
-        assert_eq!(a.component_min(b), Vec3I128::new(1, 2, 3));
-    }
-
-    #[test]
-    fn test_component_max() {
-        let a = Vec3I128::new(1, 5, 3);
-        let b = Vec3I128::new(4, 2, 6);
-        assert_eq!(a.component_max(b), Vec3I128::new(4, 5, 6));
-    }
-
-    #[test]
-    fn test_checked_dot_overflow_returns_none() {
-        let v = Vec3I128::new(i128::MAX, 0, 0);
-        let w = Vec3I128::new(2, 0, 0);
-        assert!(v.checked_dot(w).is_none());
-    }
-
-    #[test]
-    fn test_checked_cross_overflow_returns_none() {
-        let v = Vec3I128::new(0, i128::MAX, 0);
-        let w = Vec3I128::new(0, 0, 2);
-        assert!(v.checked_cross(w).is_none());
-    }
-
-    #[test]
-    fn test_vec2_dot() {
-        let a = Vec2I128::new(3, 4);
-        let b = Vec2I128::new(1, 2);
-        assert_eq!(a.dot(b), 11); // 3*1 + 4*2
-    }
-
-    #[test]
-    fn test_vec2_perp_dot() {
-        let a = Vec2I128::new(1, 0);
-        let b = Vec2I128::new(0, 1);
-        assert_eq!(a.perp_dot(b), 1); // 1*1 - 0*0
-    }
-
-    #[test]
-    fn test_distance_to_self_is_zero() {
-        let p = WorldPosition::new(1000, 2000, 3000);
-        assert_eq!(distance_squared(p, p), 0);
-        assert_eq!(distance_f64(p, p), 0.0);
-    }
-
-    #[test]
-    fn test_distance_3_4_5_triangle() {
-        // Displacement of (3000, 4000, 0) mm -> distance = 5000 mm
-        let a = WorldPosition::new(0, 0, 0);
-        let b = WorldPosition::new(3000, 4000, 0);
-        assert_eq!(distance_squared(a, b), 25_000_000);
-        assert!((distance_f64(a, b) - 5000.0).abs() < 1e-10);
-    }
-
-    #[test]
-    fn test_distance_symmetric() {
-        let a = WorldPosition::new(10, 20, 30);
-        let b = WorldPosition::new(40, 50, 60);
-        assert_eq!(distance_squared(a, b), distance_squared(b, a));
-        assert_eq!(distance_f64(a, b), distance_f64(b, a));
-    }
-
-    #[test]
-    fn test_manhattan_distance() {
-        let a = WorldPosition::new(0, 0, 0);
-        let b = WorldPosition::new(3, 4, 5);
-        assert_eq!(manhattan_distance(a, b), 12);
-    }
-
-    #[test]
-    fn test_manhattan_distance_with_negatives() {
-        let a = WorldPosition::new(10, 10, 10);
-        let b = WorldPosition::new(7, 14, 10);
-        // |3| + |-4| + |0| = 7
-        assert_eq!(manhattan_distance(a, b), 7);
-    }
-
-    #[test]
-    fn test_squared_distance_manual_calc() {
-        let a = WorldPosition::new(1, 2, 3);
-        let b = WorldPosition::new(4, 6, 8);
-        // (3² + 4² + 5²) = 9 + 16 + 25 = 50
-        assert_eq!(distance_squared(a, b), 50);
-    }
-
-    #[test]
-    fn test_magnitude_squared() {
-        let v = Vec3I128::new(3, 4, 0);
-        assert_eq!(v.magnitude_squared(), 25);
-    }
-
-    #[test]
-    fn test_magnitude_f64() {
-        let v = Vec3I128::new(3, 4, 0);
-        assert!((v.magnitude_f64() - 5.0).abs() < 1e-10);
-    }
-
-    #[test]
-    fn test_checked_magnitude_squared_safe() {
-        let v = Vec3I128::new(1000, 2000, 3000);
-        assert_eq!(v.checked_magnitude_squared(), Some(14_000_000));
-    }
-
-    #[test]
-    fn test_checked_magnitude_squared_overflow() {
-        let v = Vec3I128::new(i128::MAX, 0, 0);
-        assert!(v.checked_magnitude_squared().is_none());
-    }
-
-    #[test]
-    fn test_large_coordinates_f64_distance() {
-        // Two points 1 AU apart along x-axis
-        // 1 AU ≈ 1.496×10¹¹ m = 1.496×10¹⁴ mm
-        let au_mm: i128 = 149_597_870_700_000;
-        let a = WorldPosition::new(0, 0, 0);
-        let b = WorldPosition::new(au_mm, 0, 0);
-        let d = distance_f64(a, b);
-        assert!((d - au_mm as f64).abs() / (au_mm as f64) < 1e-10);
-    }
-}
+#[path = "vector_tests.rs"]
+mod tests;
@@ -0,0 +1,305 @@
+//! Unit tests for the i128 vector type.
+
+use super::*;
+
+#[test]
+fn test_add() {
+    let a = Vec3I128::new(1, 2, 3);
+    let b = Vec3I128::new(10, 20, 30);
+    assert_eq!(a + b, Vec3I128::new(11, 22, 33));
+}
+
+#[test]
+fn test_sub() {
+    let a = Vec3I128::new(10, 20, 30);
+    let b = Vec3I128::new(1, 2, 3);
+    assert_eq!(a - b, Vec3I128::new(9, 18, 27));
+}
+
+#[test]
+fn test_neg() {
+    let v = Vec3I128::new(1, -2, 3);
+    assert_eq!(-v, Vec3I128::new(-1, 2, -3));
+}
+
+#[test]
+fn test_scalar_mul() {
+    let v = Vec3I128::new(2, 3, 4);
+    assert_eq!(v * 10, Vec3I128::new(20, 30, 40));
+}
+
+#[test]
+fn test_scalar_div() {
+    let v = Vec3I128::new(20, 30, 40);
+    assert_eq!(v / 10, Vec3I128::new(2, 3, 4));
+}
+
+#[test]
+fn test_scalar_div_truncates() {
+    let v = Vec3I128::new(7, 7, 7);
+    assert_eq!(v / 2, Vec3I128::new(3, 3, 3)); // truncation toward zero
+}
+
+#[test]
+fn test_zero_vector() {
+    let z = Vec3I128::zero();
+    assert_eq!(z, Vec3I128::new(0, 0, 0));
+    assert_eq!(z, Vec3I128::default());
+}
+
+#[test]
+fn test_basis_vectors() {
+    assert_eq!(Vec3I128::unit_x(), Vec3I128::new(1, 0, 0));
+    assert_eq!(Vec3I128::unit_y(), Vec3I128::new(0, 1, 0));
+    assert_eq!(Vec3I128::unit_z(), Vec3I128::new(0, 0, 1));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic]
+fn test_overflow_panics_in_debug() {
+    let v = Vec3I128::new(i128::MAX, 0, 0);
+    let _ = v + Vec3I128::new(1, 0, 0); // overflow
+}
+
+#[test]
+fn test_checked_add_overflow() {
+    let v = Vec3I128::new(i128::MAX, 0, 0);
+    assert!(v.checked_add(Vec3I128::new(1, 0, 0)).is_none());
+}
+
+#[test]
+fn test_saturating_add() {
+    let v = Vec3I128::new(i128::MAX, 0, 0);
+    let result = v.saturating_add(Vec3I128::new(1, 0, 0));
+    assert_eq!(result.x, i128::MAX);
+}
+
+#[test]
+fn test_vec2_add() {
+    let a = Vec2I128::new(1, 2);
+    let b = Vec2I128::new(10, 20);
+    assert_eq!(a + b, Vec2I128::new(11, 22));
+}
+
+#[test]
+fn test_dot_orthogonal_is_zero() {
+    let x = Vec3I128::unit_x();
+    let y = Vec3I128::unit_y();
+    assert_eq!(x.dot(y), 0);
+}
+
+#[test]
+fn test_dot_parallel() {
+    let v = Vec3I128::new(3, 0, 0);
+    let w = Vec3I128::new(5, 0, 0);
+    assert_eq!(v.dot(w), 15);
+}
+
+#[test]
+fn test_dot_antiparallel() {
+    let v = Vec3I128::new(3, 0, 0);
+    let w = Vec3I128::new(-5, 0, 0);
+    assert_eq!(v.dot(w), -15);
+}
+
+#[test]
+fn test_dot_general() {
+    let a = Vec3I128::new(1, 2, 3);
+    let b = Vec3I128::new(4, 5, 6);
+    // 1*4 + 2*5 + 3*6 = 4 + 10 + 18 = 32
+    assert_eq!(a.dot(b), 32);
+}
+
+#[test]
+fn test_cross_basis_vectors() {
+    let x = Vec3I128::unit_x();
+    let y = Vec3I128::unit_y();
+    let z = Vec3I128::unit_z();
+    assert_eq!(x.cross(y), z); // x × y = z
+    assert_eq!(y.cross(z), x); // y × z = x
+    assert_eq!(z.cross(x), y); // z × x = y
+}
+
+#[test]
+fn test_cross_anti_commutativity() {
+    let a = Vec3I128::new(1, 2, 3);
+    let b = Vec3I128::new(4, 5, 6);
+    assert_eq!(a.cross(b), -b.cross(a));
+}
+
+#[test]
+fn test_cross_self_is_zero() {
+    let v = Vec3I128::new(7, 11, 13);
+    assert_eq!(v.cross(v), Vec3I128::zero());
+}
+
+#[test]
+fn test_component_min() {
+    let a = Vec3I128::new(1, 5, 3);
+    let b = Vec3I128::new(4, 2, 6);
+    assert_eq!(a.component_min(b), Vec3I128::new(1, 2, 3));
+}
+
+#[test]
+fn test_component_max() {
+    let a = Vec3I128::new(1, 5, 3);
+    let b = Vec3I128::new(4, 2, 6);
+    assert_eq!(a.component_max(b), Vec3I128::new(4, 5, 6));
+}
+
+#[test]
+fn test_checked_dot_overflow_returns_none() {
+    let v = Vec3I128::new(i128::MAX, 0, 0);
+    let w = Vec3I128::new(2, 0, 0);
+    assert!(v.checked_dot(w).is_none());
+}
+
+#[test]
+fn test_checked_cross_overflow_returns_none() {
+    let v = Vec3I128::new(0, i128::MAX, 0);
+    let w = Vec3I128::new(0, 0, 2);
+    assert!(v.checked_cross(w).is_none());
+}
+
+#[test]
+fn test_vec2_dot() {
+    let a = Vec2I128::new(3, 4);
+    let b = Vec2I128::new(1, 2);
+    assert_eq!(a.dot(b), 11); // 3*1 + 4*2
+}
+
+#[test]
+fn test_vec2_perp_dot() {
+    let a = Vec2I128::new(1, 0);
+    let b = Vec2I128::new(0, 1);
+    assert_eq!(a.perp_dot(b), 1); // 1*1 - 0*0
+}
+
+#[test]
+fn test_distance_to_self_is_zero() {
+    let p = WorldPosition::new(1000, 2000, 3000);
+    assert_eq!(distance_squared(p, p), 0);
+    assert_eq!(distance_f64(p, p), 0.0);
+}
+
+#[test]
+fn test_distance_3_4_5_triangle() {
+    // Displacement of (3000, 4000, 0) mm -> distance = 5000 mm
+    let a = WorldPosition::new(0, 0, 0);
+    let b = WorldPosition::new(3000, 4000, 0);
+    assert_eq!(distance_squared(a, b), 25_000_000);
+    assert!((distance_f64(a, b) - 5000.0).abs() < 1e-10);
+}
+
+#[test]
+fn test_distance_symmetric() {
+    let a = WorldPosition::new(10, 20, 30);
+    let b = WorldPosition::new(40, 50, 60);
+    assert_eq!(distance_squared(a, b), distance_squared(b, a));
+    assert_eq!(distance_f64(a, b), distance_f64(b, a));
+}
+
+#[test]
+fn test_manhattan_distance() {
+    let a = WorldPosition::new(0, 0, 0);
+    let b = WorldPosition::new(3, 4, 5);
+    assert_eq!(manhattan_distance(a, b), 12);
+}
+
+#[test]
+fn test_manhattan_distance_with_negatives() {
+    let a = WorldPosition::new(10, 10, 10);
+    let b = WorldPosition::new(7, 14, 10);
+    // |3| + |-4| + |0| = 7
+    assert_eq!(manhattan_distance(a, b), 7);
+}
+
+#[test]
+fn test_squared_distance_manual_calc() {
+    let a = WorldPosition::new(1, 2, 3);
+    let b = WorldPosition::new(4, 6, 8);
+    // (3² + 4² + 5²) = 9 + 16 + 25 = 50
+    assert_eq!(distance_squared(a, b), 50);
+}
+
+#[test]
+fn test_magnitude_squared() {
+    let v = Vec3I128::new(3, 4, 0);
+    assert_eq!(v.magnitude_squared(), 25);
+}
+
+#[test]
+fn test_magnitude_f64() {
+    let v = Vec3I128::new(3, 4, 0);
+    assert!((v.magnitude_f64() - 5.0).abs() < 1e-10);
+}
+
+#[test]
+fn test_checked_magnitude_squared_safe() {
+    let v = Vec3I128::new(1000, 2000, 3000);
+    assert_eq!(v.checked_magnitude_squared(), Some(14_000_000));
+}
+
+#[test]
+fn test_checked_magnitude_squared_overflow() {
+    let v = Vec3I128::new(i128::MAX, 0, 0);
+    assert!(v.checked_magnitude_squared().is_none());
+}
+
+#[test]
+fn test_large_coordinates_f64_distance() {
+    // Two points 1 AU apart along x-axis
+    // 1 AU ≈ 1.496×10¹¹ m = 1.496×10¹⁴ mm
+    let au_mm: i128 = 149_597_870_700_000;
+    let a = WorldPosition::new(0, 0, 0);
+    let b = WorldPosition::new(au_mm, 0, 0);
+    let d = distance_f64(a, b);
+    assert!((d - au_mm as f64).abs() / (au_mm as f64) < 1e-10);
+}
+
+#[test]
+fn test_length_squared_3_4_0() {
+    assert_eq!(Vec3I128::new(3, 4, 0).length_squared(), 25u128);
+}
+
+#[test]
+fn test_length_squared_exceeds_i128_range() {
+    // Each square is ~1.7×10³⁸; the i128 sum would overflow, the u128 one does not.
+    let c = 1i128 << 63;
+    let v = Vec3I128::new(c, -c, 0);
+    assert_eq!(v.length_squared(), 2 * (1u128 << 126));
+    assert!(v.checked_magnitude_squared().is_none());
+}
+
+#[test]
+fn test_length_f64_matches_magnitude() {
+    let v = Vec3I128::new(3, 4, 12);
+    assert_eq!(v.length_f64(), 13.0);
+}
+
+#[test]
+fn test_normalize_f64() {
+    let n = Vec3I128::new(0, -5_000_000, 0).normalize_f64();
+    assert_eq!(n, DVec3::new(0.0, -1.0, 0.0));
+    assert_eq!(Vec3I128::zero().normalize_f64(), DVec3::ZERO);
+
+    let big = Vec3I128::new(i128::MAX, i128::MAX, 0).normalize_f64();
+    assert!((big.length() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_cross_product_is_orthogonal_to_inputs() {
+    let a = Vec3I128::new(3, -7, 11);
+    let b = Vec3I128::new(-2, 5, 13);
+    let c = a.cross(b);
+    assert_eq!(c.dot(a), 0);
+    assert_eq!(c.dot(b), 0);
+}
+
+#[test]
+fn test_dot_commutative() {
+    let a = Vec3I128::new(1_000_000, -42, 7);
+    let b = Vec3I128::new(-3, 99, 123_456);
+    assert_eq!(a.dot(b), b.dot(a));
+}
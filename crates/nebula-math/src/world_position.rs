@@ -1,7 +1,11 @@
 use std::fmt;
-use std::ops::{Add, Sub};
+use std::ops::{Add, Mul, Sub};
 
 use crate::Vec3I128;
+use crate::units::{
+    UNITS_PER_AU, UNITS_PER_KILOMETER, UNITS_PER_LIGHT_YEAR, UNITS_PER_METER, UNITS_PER_PARSEC,
+    light_years_to_units,
+};
 use glam::Vec3;
 
 /// Canonical position in the universe. Each unit equals 1 millimeter.
@@ -33,6 +37,118 @@ impl WorldPosition {
             (self.z - camera.z) as f32,
         )
     }
+
+    /// Displacement from `self` to `other` (equivalent to `*other - *self`).
+    pub fn vector_to(&self, other: &WorldPosition) -> Vec3I128 {
+        *other - *self
+    }
+
+    /// Straight-line distance to `other` in meters.
+    ///
+    /// The difference is taken exactly in i128 before converting to f64, so
+    /// the result keeps millimeter precision for nearby points anywhere in
+    /// the universe.
+    pub fn distance_meters(&self, other: &WorldPosition) -> f64 {
+        (*other - *self).magnitude_f64() / UNITS_PER_METER as f64
+    }
+
+    /// Create a position from light-year coordinates, rounded to the
+    /// nearest millimeter.
+    pub fn from_light_years(lx: f64, ly: f64, lz: f64) -> Self {
+        Self::new(
+            light_years_to_units(lx),
+            light_years_to_units(ly),
+            light_years_to_units(lz),
+        )
+    }
+
+    /// Component-wise sum, or `None` if any component overflows.
+    pub fn add_checked(self, other: WorldPosition) -> Option<WorldPosition> {
+        Some(Self::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+            self.z.checked_add(other.z)?,
+        ))
+    }
+
+    /// Displacement `self - other` (as the `-` operator), or `None` if any
+    /// component overflows.
+    pub fn sub_checked(self, other: WorldPosition) -> Option<Vec3I128> {
+        Some(Vec3I128::new(
+            self.x.checked_sub(other.x)?,
+            self.y.checked_sub(other.y)?,
+            self.z.checked_sub(other.z)?,
+        ))
+    }
+
+    /// Every component multiplied by `scalar`, or `None` on overflow.
+    pub fn mul_checked(self, scalar: i128) -> Option<WorldPosition> {
+        Some(Self::new(
+            self.x.checked_mul(scalar)?,
+            self.y.checked_mul(scalar)?,
+            self.z.checked_mul(scalar)?,
+        ))
+    }
+
+    /// Component-wise sum clamped to `i128::MIN..=i128::MAX`.
+    ///
+    /// A clamped component equal to `i128::MAX` or `i128::MIN` marks a
+    /// position at the edge of the universe.
+    pub fn saturating_add(self, other: WorldPosition) -> WorldPosition {
+        Self::new(
+            self.x.saturating_add(other.x),
+            self.y.saturating_add(other.y),
+            self.z.saturating_add(other.z),
+        )
+    }
+
+    /// Distance from the origin in millimeters, as f64.
+    fn distance_from_origin(&self) -> f64 {
+        Vec3I128::new(self.x, self.y, self.z).magnitude_f64()
+    }
+
+    /// Distance from the origin in astronomical units.
+    pub fn to_astronomical_units(&self) -> f64 {
+        self.distance_from_origin() / UNITS_PER_AU as f64
+    }
+
+    /// Distance from the origin in light-years.
+    pub fn to_light_years(&self) -> f64 {
+        self.distance_from_origin() / UNITS_PER_LIGHT_YEAR as f64
+    }
+
+    /// Distance from the origin in parsecs.
+    pub fn to_parsecs(&self) -> f64 {
+        self.distance_from_origin() / UNITS_PER_PARSEC as f64
+    }
+
+    /// Distance from the origin formatted in the most readable unit:
+    /// mm below 1 km, km below 1 AU, AU below 1 ly, ly below 1 kpc, and
+    /// pc beyond.
+    pub fn display_human(&self) -> impl fmt::Display {
+        HumanDistance(self.distance_from_origin())
+    }
+}
+
+/// [`fmt::Display`] adapter returned by [`WorldPosition::display_human`].
+struct HumanDistance(f64);
+
+impl fmt::Display for HumanDistance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mm = self.0;
+        let (value, unit) = if mm < UNITS_PER_KILOMETER as f64 {
+            return write!(f, "{} mm", mm.round());
+        } else if mm < UNITS_PER_AU as f64 {
+            (mm / UNITS_PER_KILOMETER as f64, "km")
+        } else if mm < UNITS_PER_LIGHT_YEAR as f64 {
+            (mm / UNITS_PER_AU as f64, "AU")
+        } else if mm < 1_000.0 * UNITS_PER_PARSEC as f64 {
+            (mm / UNITS_PER_LIGHT_YEAR as f64, "ly")
+        } else {
+            (mm / UNITS_PER_PARSEC as f64, "pc")
+        };
+        write!(f, "{value:.3} {unit}")
+    }
 }
 
 impl fmt::Display for WorldPosition {
@@ -47,12 +163,76 @@ impl From<(i128, i128, i128)> for WorldPosition {
     }
 }
 
+// Operator arithmetic panics on overflow in debug builds and wraps in
+// release builds. Use the `*_checked` methods to handle overflow explicitly.
+
+#[cfg(debug_assertions)]
+fn op_add(a: i128, b: i128) -> i128 {
+    a.checked_add(b)
+        .unwrap_or_else(|| panic!("WorldPosition overflow: {a} + {b}"))
+}
+
+#[cfg(not(debug_assertions))]
+fn op_add(a: i128, b: i128) -> i128 {
+    a.wrapping_add(b)
+}
+
+#[cfg(debug_assertions)]
+fn op_sub(a: i128, b: i128) -> i128 {
+    a.checked_sub(b)
+        .unwrap_or_else(|| panic!("WorldPosition overflow: {a} - {b}"))
+}
+
+#[cfg(not(debug_assertions))]
+fn op_sub(a: i128, b: i128) -> i128 {
+    a.wrapping_sub(b)
+}
+
+#[cfg(debug_assertions)]
+fn op_mul(a: i128, b: i128) -> i128 {
+    a.checked_mul(b)
+        .unwrap_or_else(|| panic!("WorldPosition overflow: {a} * {b}"))
+}
+
+#[cfg(not(debug_assertions))]
+fn op_mul(a: i128, b: i128) -> i128 {
+    a.wrapping_mul(b)
+}
+
+impl Add for WorldPosition {
+    type Output = WorldPosition;
+
+    fn add(self, rhs: WorldPosition) -> Self::Output {
+        WorldPosition::new(
+            op_add(self.x, rhs.x),
+            op_add(self.y, rhs.y),
+            op_add(self.z, rhs.z),
+        )
+    }
+}
+
+impl Mul<i128> for WorldPosition {
+    type Output = WorldPosition;
+
+    fn mul(self, rhs: i128) -> Self::Output {
+        WorldPosition::new(
+            op_mul(self.x, rhs),
+            op_mul(self.y, rhs),
+            op_mul(self.z, rhs),
+        )
+    }
+}
+
 // WorldPosition interop with Vec3I128
 impl Sub for WorldPosition {
     type Output = Vec3I128;
 
     fn sub(self, rhs: WorldPosition) -> Self::Output {
-        Vec3I128::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+        Vec3I128::new(
+            op_sub(self.x, rhs.x),
+            op_sub(self.y, rhs.y),
+            op_sub(self.z, rhs.z),
+        )
     }
 }
 
@@ -60,7 +240,11 @@ impl Add<Vec3I128> for WorldPosition {
     type Output = WorldPosition;
 
     fn add(self, rhs: Vec3I128) -> Self::Output {
-        WorldPosition::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+        WorldPosition::new(
+            op_add(self.x, rhs.x),
+            op_add(self.y, rhs.y),
+            op_add(self.z, rhs.z),
+        )
     }
 }
 
@@ -68,7 +252,11 @@ impl Sub<Vec3I128> for WorldPosition {
     type Output = WorldPosition;
 
     fn sub(self, rhs: Vec3I128) -> Self::Output {
-        WorldPosition::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+        WorldPosition::new(
+            op_sub(self.x, rhs.x),
+            op_sub(self.y, rhs.y),
+            op_sub(self.z, rhs.z),
+        )
     }
 }
 
@@ -100,6 +288,15 @@ mod tests {
         assert_eq!(s, "WorldPosition(1, -2, 3)");
     }
 
+    #[test]
+    fn test_distance_meters_far_from_origin() {
+        let base = 1_000_000_000_000_000_000_000_000_i128;
+        let a = WorldPosition::new(base, -base, base);
+        let b = WorldPosition::new(base + 3_000, -base + 4_000, base);
+        assert_eq!(a.distance_meters(&b), 5.0);
+        assert_eq!(b.distance_meters(&a), 5.0);
+    }
+
     #[test]
     fn test_equality() {
         let a = WorldPosition::new(5, 5, 5);
@@ -160,4 +357,101 @@ mod tests {
         let offset = Vec3I128::new(5, 5, 5);
         assert_eq!(pos + offset, WorldPosition::new(15, 25, 35));
     }
+
+    #[test]
+    fn test_vector_to_is_other_minus_self() {
+        let a = WorldPosition::new(10, 20, 30);
+        let b = WorldPosition::new(-5, 25, 100);
+        assert_eq!(a.vector_to(&b), b - a);
+        assert_eq!(a + a.vector_to(&b), b);
+    }
+
+    #[test]
+    fn test_from_light_years_roundtrip() {
+        let pos = WorldPosition::from_light_years(1.0, 0.0, 0.0);
+        assert!((pos.to_light_years() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_astronomical_conversions() {
+        let one_au = WorldPosition::new(0, UNITS_PER_AU, 0);
+        assert!((one_au.to_astronomical_units() - 1.0).abs() < 1e-12);
+
+        let one_pc = WorldPosition::new(0, 0, -UNITS_PER_PARSEC);
+        assert!((one_pc.to_parsecs() - 1.0).abs() < 1e-12);
+        assert!((one_pc.to_light_years() - 3.26156).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_display_human_origin_is_mm() {
+        assert_eq!(WorldPosition::default().display_human().to_string(), "0 mm");
+    }
+
+    #[test]
+    fn test_display_human_selects_unit() {
+        let cases = [
+            (
+                WorldPosition::new(5 * UNITS_PER_KILOMETER, 0, 0),
+                "5.000 km",
+            ),
+            (WorldPosition::new(2 * UNITS_PER_AU, 0, 0), "2.000 AU"),
+            (WorldPosition::from_light_years(0.0, -4.2, 0.0), "4.200 ly"),
+            (
+                WorldPosition::new(0, 0, 8_000 * UNITS_PER_PARSEC),
+                "8000.000 pc",
+            ),
+        ];
+        for (pos, expected) in cases {
+            assert_eq!(pos.display_human().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let max = WorldPosition::new(i128::MAX, 0, 0);
+        let one = WorldPosition::new(1, 0, 0);
+        assert_eq!(max.add_checked(one), None);
+        assert_eq!(one.add_checked(one), Some(WorldPosition::new(2, 0, 0)));
+        assert_eq!(WorldPosition::new(i128::MIN, 0, 0).sub_checked(one), None);
+        assert_eq!(
+            max.sub_checked(one),
+            Some(Vec3I128::new(i128::MAX - 1, 0, 0))
+        );
+        assert_eq!(max.mul_checked(2), None);
+        assert_eq!(
+            WorldPosition::new(1, -2, 3).mul_checked(-3),
+            Some(WorldPosition::new(-3, 6, -9))
+        );
+    }
+
+    #[test]
+    fn test_saturating_add_clamps_per_component() {
+        let edge = WorldPosition::new(i128::MAX - 1, i128::MIN + 1, 5);
+        let push = WorldPosition::new(10, -10, 5);
+        assert_eq!(
+            edge.saturating_add(push),
+            WorldPosition::new(i128::MAX, i128::MIN, 10)
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "WorldPosition overflow")]
+    fn test_add_overflow_panics_in_debug() {
+        let _ = WorldPosition::new(i128::MAX, 0, 0) + WorldPosition::new(1, 0, 0);
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn test_add_overflow_wraps_in_release() {
+        let sum = WorldPosition::new(i128::MAX, 0, 0) + WorldPosition::new(1, 0, 0);
+        assert_eq!(sum, WorldPosition::new(i128::MIN, 0, 0));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "WorldPosition overflow")]
+    fn test_mul_overflow_panics_in_debug() {
+        let _ = WorldPosition::new(0, i128::MAX, 0) * 2;
+    }
 }
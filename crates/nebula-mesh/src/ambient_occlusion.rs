@@ -237,7 +237,7 @@ mod tests {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 1,
-            light_emission: 0,
+            light_emission: [0; 3],
         })
         .expect("register stone");
         reg
@@ -5,10 +5,10 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread::JoinHandle;
 
-use nebula_voxel::{ChunkAddress, VoxelTypeRegistry};
+use nebula_voxel::{CHUNK_SIZE, ChunkAddress, VoxelTypeRegistry};
 
 use crate::chunk_mesh::ChunkMesh;
-use crate::greedy::greedy_mesh;
+use crate::liquid::{LiquidTileScales, greedy_mesh_liquid};
 use crate::neighborhood::ChunkNeighborhood;
 use crate::visibility::compute_visible_faces;
 
@@ -61,7 +61,27 @@ impl MeshingPipeline {
     /// `worker_count` — number of OS threads to spawn for meshing.
     /// `budget` — maximum number of in-flight tasks (limits memory usage from snapshots).
     /// `registry` — immutable voxel type registry shared by all workers.
+    ///
+    /// Liquid surfaces tile every voxel; use
+    /// [`with_liquid_tile_scales`](Self::with_liquid_tile_scales) to tile
+    /// them by material.
     pub fn new(worker_count: usize, budget: usize, registry: Arc<VoxelTypeRegistry>) -> Self {
+        Self::with_liquid_tile_scales(
+            worker_count,
+            budget,
+            registry,
+            Arc::new(LiquidTileScales::uniform(1.0)),
+        )
+    }
+
+    /// Creates a meshing pipeline whose liquid surfaces tile their textures
+    /// in world space with the given per-voxel-type `tile_scales`.
+    pub fn with_liquid_tile_scales(
+        worker_count: usize,
+        budget: usize,
+        registry: Arc<VoxelTypeRegistry>,
+        tile_scales: Arc<LiquidTileScales>,
+    ) -> Self {
         let (task_tx, task_rx) = crossbeam_channel::bounded(budget);
         let (result_tx, result_rx) = crossbeam_channel::unbounded();
         let in_flight = Arc::new(AtomicUsize::new(0));
@@ -71,6 +91,7 @@ impl MeshingPipeline {
             let rx: crossbeam_channel::Receiver<MeshingTask> = task_rx.clone();
             let tx = result_tx.clone();
             let reg = Arc::clone(&registry);
+            let scales = Arc::clone(&tile_scales);
             let flight = Arc::clone(&in_flight);
 
             handles.push(std::thread::spawn(move || {
@@ -78,7 +99,16 @@ impl MeshingPipeline {
                     let mesh = match task.neighborhood.center() {
                         Some(center) => {
                             let visible = compute_visible_faces(center, &task.neighborhood, &reg);
-                            greedy_mesh(center, &visible, &task.neighborhood, &reg)
+                            let addr = task.chunk_addr;
+                            let size = CHUNK_SIZE as i64;
+                            greedy_mesh_liquid(
+                                center,
+                                &visible,
+                                &task.neighborhood,
+                                &reg,
+                                [addr.x * size, addr.y * size, addr.z * size],
+                                &scales,
+                            )
                         }
                         None => ChunkMesh::new(),
                     };
@@ -166,7 +196,7 @@ mod tests {
             transparency: Transparency::Opaque,
             solid: true,
             material_index: 0,
-            light_emission: 0,
+            light_emission: [0; 3],
         });
         Arc::new(reg)
     }
@@ -302,6 +332,54 @@ mod tests {
         }
     }
 
+    /// Liquid quads from the pipeline tile with their type's scale.
+    #[test]
+    fn test_pipeline_meshes_liquid_surfaces() {
+        let mut reg = VoxelTypeRegistry::new();
+        let water = reg
+            .register(VoxelTypeDef {
+                name: "water".to_string(),
+                transparency: Transparency::SemiTransparent,
+                solid: false,
+                material_index: 0,
+                light_emission: [0; 3],
+            })
+            .expect("register water");
+        let pipeline = MeshingPipeline::with_liquid_tile_scales(
+            1,
+            4,
+            Arc::new(reg),
+            Arc::new(LiquidTileScales::uniform(4.0)),
+        );
+
+        let mut chunk = ChunkData::new(VoxelTypeId(0));
+        chunk.set(1, 1, 1, water);
+        let task = MeshingTask {
+            chunk_addr: ChunkAddress::new(3, 0, -2, 0),
+            neighborhood: ChunkNeighborhood::from_center_only(chunk),
+            data_version: 1,
+        };
+        assert!(pipeline.submit(task));
+
+        let start = std::time::Instant::now();
+        loop {
+            let results = pipeline.drain_results();
+            if let Some(result) = results.first() {
+                assert!(result.mesh.quad_count() > 0);
+                assert!(
+                    result
+                        .mesh
+                        .quads
+                        .iter()
+                        .all(|q| q.is_liquid && q.tile_scale == 4.0)
+                );
+                break;
+            }
+            assert!(start.elapsed().as_secs() < 5, "Timed out");
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+    }
+
     /// The budget should prevent submitting more tasks than allowed.
     #[test]
     fn test_budget_limits_active_tasks() {
@@ -0,0 +1,269 @@
+//! Material blending at biome boundaries.
+//!
+//! Each surface vertex of a packed chunk mesh looks at the biome columns
+//! within a blend radius around it and records the most common material
+//! other than its own in [`ChunkVertex::secondary_material`], weighted by
+//! the share of columns that material covers. The columns come from the
+//! [`BiomeMap`] in world space, so windows near a chunk edge reach into the
+//! neighboring chunks and both sides of a boundary agree on the mix there.
+//!
+//! The window is `2r × 2r` columns centered on the vertex (vertices sit on
+//! column corners), so across a straight boundary the other biome's share
+//! ramps linearly from 0 to 1 over `r` voxels on either side, and a radius
+//! of 0 leaves the mesh untouched.
+//!
+//! [`pack_blended_chunk_mesh`] is the usual entry point: it packs a meshed
+//! chunk and blends it in one step.
+//!
+//! [`ChunkVertex::secondary_material`]: crate::ChunkVertex::secondary_material
+
+use nebula_materials::{BiomeMap, MaterialId, compute_blend_weight};
+use nebula_voxel::{CHUNK_SIZE, VoxelTypeRegistry};
+
+use crate::chunk_mesh::ChunkMesh;
+use crate::packed::PackedChunkMesh;
+
+/// Blend radius, in voxels, used by default for biome boundaries.
+pub const DEFAULT_BIOME_BLEND_RADIUS: u32 = 4;
+
+/// Pack `mesh` for the GPU and blend neighboring biome materials into it.
+///
+/// See [`PackedChunkMesh::from_chunk_mesh`] and [`blend_biome_materials`].
+pub fn pack_blended_chunk_mesh(
+    mesh: &ChunkMesh,
+    registry: &VoxelTypeRegistry,
+    biome_map: &dyn BiomeMap,
+    column_origin: [i128; 2],
+    blend_radius: u32,
+) -> PackedChunkMesh {
+    let mut packed = PackedChunkMesh::from_chunk_mesh(mesh, registry);
+    blend_biome_materials(&mut packed, biome_map, column_origin, blend_radius);
+    packed
+}
+
+/// Blend neighboring biome materials into the vertices of `mesh`.
+///
+/// `column_origin` is the world `(x, z)` column of the chunk's local
+/// `(0, 0)`, matching the coordinates `biome_map` is sampled in. Vertex
+/// material IDs are compared against the biome map's primary materials;
+/// vertices whose material does not appear around them (stone under a
+/// grass biome, say) are left unblended.
+pub fn blend_biome_materials(
+    mesh: &mut PackedChunkMesh,
+    biome_map: &dyn BiomeMap,
+    column_origin: [i128; 2],
+    blend_radius: u32,
+) {
+    if blend_radius == 0 {
+        return;
+    }
+
+    // Primary biome material of every column any window can reach.
+    let r = blend_radius as usize;
+    let side = CHUNK_SIZE + 2 * r;
+    let mut columns = Vec::with_capacity(side * side);
+    for z in 0..side {
+        for x in 0..side {
+            let world_x = column_origin[0] + x as i128 - r as i128;
+            let world_z = column_origin[1] + z as i128 - r as i128;
+            columns.push(compute_blend_weight(biome_map, world_x, world_z).0);
+        }
+    }
+
+    let window_area = (2 * r * 2 * r) as f32;
+    let mut counts: Vec<(MaterialId, u32)> = Vec::new();
+    for vertex in &mut mesh.vertices {
+        // The window for the corner at local `p` spans local columns
+        // `p - r .. p + r`, which start at `p` in the padded grid.
+        let [px, _, pz] = vertex.position.map(usize::from);
+        counts.clear();
+        for z in pz..pz + 2 * r {
+            for &material in &columns[z * side + px..z * side + px + 2 * r] {
+                match counts.iter_mut().find(|(m, _)| *m == material) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((material, 1)),
+                }
+            }
+        }
+
+        let own = MaterialId(vertex.material_id);
+        if !counts.iter().any(|&(m, _)| m == own) {
+            continue;
+        }
+        let secondary = counts.iter().filter(|&&(m, _)| m != own).fold(
+            None,
+            |best: Option<(MaterialId, u32)>, &(m, count)| match best {
+                Some((_, best_count)) if best_count >= count => best,
+                _ => Some((m, count)),
+            },
+        );
+        if let Some((material, count)) = secondary {
+            *vertex = vertex.with_blend(material.0, count as f32 / window_area);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::face_direction::FaceDirection;
+    use crate::greedy::greedy_mesh;
+    use crate::neighborhood::ChunkNeighborhood;
+    use crate::packed::ChunkVertex;
+    use crate::visibility::compute_visible_faces;
+    use nebula_voxel::{ChunkData, Transparency, VoxelTypeDef};
+
+    const DESERT: MaterialId = MaterialId(3);
+    const GRASS: MaterialId = MaterialId(7);
+    const STONE: MaterialId = MaterialId(1);
+
+    /// Desert for world `x < boundary`, grass from `boundary` on.
+    struct StraightBoundary {
+        boundary: i128,
+    }
+
+    impl BiomeMap for StraightBoundary {
+        fn sample(&self, world_x: i128, _world_z: i128) -> (MaterialId, MaterialId, f32) {
+            let m = if world_x < self.boundary {
+                DESERT
+            } else {
+                GRASS
+            };
+            (m, m, 0.0)
+        }
+    }
+
+    /// A row of one-voxel top faces along x, each with its column's biome.
+    fn top_strip(map: &StraightBoundary, origin_x: i128) -> PackedChunkMesh {
+        let mut mesh = PackedChunkMesh::new();
+        for x in 0..CHUNK_SIZE as u8 {
+            let material = map.sample(origin_x + i128::from(x), 0).0.0;
+            let vertex = |dx: u8, z: u8| {
+                ChunkVertex::new([x + dx, 8, z], FaceDirection::PosY, 0, material, [dx, z])
+            };
+            mesh.push_quad(
+                [vertex(0, 5), vertex(1, 5), vertex(1, 6), vertex(0, 6)],
+                false,
+            );
+        }
+        mesh
+    }
+
+    #[test]
+    fn test_straight_boundary_ramps_over_blend_radius() {
+        let map = StraightBoundary { boundary: 16 };
+        for radius in [1, 4, 8] {
+            let mut mesh = top_strip(&map, 0);
+            blend_biome_materials(&mut mesh, &map, [0, 0], radius);
+
+            for vertex in &mesh.vertices {
+                let w = vertex.blend_weight_f32();
+                assert!((0.0..=1.0).contains(&w));
+                let grass_share = if MaterialId(vertex.material_id) == GRASS {
+                    1.0 - w
+                } else {
+                    w
+                };
+                if vertex.blend_weight > 0 {
+                    let expected = if vertex.material_id == GRASS.0 {
+                        DESERT
+                    } else {
+                        GRASS
+                    };
+                    assert_eq!({ vertex.secondary_material }, expected.0);
+                }
+
+                let x = f32::from(vertex.position[0]);
+                let r = radius as f32;
+                let expected = ((x - 16.0 + r) / (2.0 * r)).clamp(0.0, 1.0);
+                assert!(
+                    (grass_share - expected).abs() <= 1.0 / 255.0,
+                    "radius {radius}, x {x}: grass share {grass_share}, expected {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_boundary_in_neighbor_chunk_blends_across_chunk_edge() {
+        // The boundary sits 2 voxels past this chunk's +X edge.
+        let map = StraightBoundary { boundary: 34 };
+        let mut mesh = top_strip(&map, 0);
+        blend_biome_materials(&mut mesh, &map, [0, 0], 4);
+        let edge = mesh
+            .vertices
+            .iter()
+            .find(|v| v.position[0] == 32)
+            .expect("edge vertex");
+        assert_eq!({ edge.secondary_material }, GRASS.0);
+        assert!((edge.blend_weight_f32() - 2.0 / 8.0).abs() <= 1.0 / 255.0);
+    }
+
+    #[test]
+    fn test_zero_radius_leaves_mesh_byte_identical() {
+        let map = StraightBoundary { boundary: 16 };
+        let mut mesh = top_strip(&map, 0);
+        let before = mesh.vertex_bytes().to_vec();
+        blend_biome_materials(&mut mesh, &map, [0, 0], 0);
+        assert_eq!(mesh.vertex_bytes(), before.as_slice());
+    }
+
+    #[test]
+    fn test_foreign_material_is_not_blended() {
+        let map = StraightBoundary { boundary: 16 };
+        let mut mesh = PackedChunkMesh::new();
+        let v = |x, z| ChunkVertex::new([x, 4, z], FaceDirection::PosX, 0, STONE.0, [0, 0]);
+        mesh.push_quad([v(16, 0), v(16, 1), v(16, 2), v(16, 3)], false);
+        let before = mesh.vertex_bytes().to_vec();
+        blend_biome_materials(&mut mesh, &map, [0, 0], 4);
+        assert_eq!(mesh.vertex_bytes(), before.as_slice());
+    }
+
+    #[test]
+    fn test_meshed_chunk_across_biomes_gets_blend_weights() {
+        let mut registry = VoxelTypeRegistry::new();
+        let mut register = |name: &str, material: MaterialId| {
+            registry
+                .register(VoxelTypeDef {
+                    name: name.to_string(),
+                    solid: true,
+                    transparency: Transparency::Opaque,
+                    material_index: material.0,
+                    light_emission: [0; 3],
+                })
+                .expect("register voxel type")
+        };
+        let sand = register("sand", DESERT);
+        let grass = register("grass", GRASS);
+
+        // A flat floor whose surface follows the biome map.
+        let map = StraightBoundary { boundary: 16 };
+        let mut chunk = ChunkData::new_air();
+        for z in 0..CHUNK_SIZE {
+            for x in 0..CHUNK_SIZE {
+                let voxel = if x < 16 { sand } else { grass };
+                chunk.set(x, 0, z, voxel);
+            }
+        }
+        let neighbors = ChunkNeighborhood::all_air();
+        let visible = compute_visible_faces(&chunk, &neighbors, &registry);
+        let mesh = greedy_mesh(&chunk, &visible, &neighbors, &registry);
+
+        let packed = pack_blended_chunk_mesh(&mesh, &registry, &map, [0, 0], 4);
+        let boundary_tops: Vec<_> = packed
+            .vertices
+            .iter()
+            .filter(|v| v.normal == FaceDirection::PosY as u8 && v.position[0] == 16)
+            .collect();
+        assert!(!boundary_tops.is_empty());
+        for vertex in boundary_tops {
+            assert!(vertex.blend_weight > 0, "unblended vertex {vertex:?}");
+            let expected = if vertex.material_id == GRASS.0 {
+                DESERT
+            } else {
+                GRASS
+            };
+            assert_eq!({ vertex.secondary_material }, expected.0);
+        }
+    }
+}
@@ -23,6 +23,14 @@ pub struct MeshVertex {
 pub struct QuadInfo {
     /// Which face direction this quad belongs to.
     pub direction: FaceDirection,
+    /// Chunk-local position of the quad's first corner `(u, v)`.
+    pub origin: [f32; 3],
+    /// Quad extent along the face's u and v sweep axes, in voxels.
+    pub size: [f32; 2],
+    /// Whether the quad is a liquid surface with world-space tiled UVs.
+    pub is_liquid: bool,
+    /// Voxels covered by one texture tile (1.0 = one tile per voxel).
+    pub tile_scale: f32,
 }
 
 /// The mesh output of a chunk meshing pass.
@@ -103,10 +111,13 @@ impl ChunkMesh {
         ];
 
         let base = self.vertices.len() as u32;
+        let mut origin = [0.0_f32; 3];
+        origin[layer_axis] = layer_pos;
+        origin[u_axis] = u as f32;
+        origin[v_axis] = v as f32;
 
         for (i, &(cu, cv)) in corners.iter().enumerate() {
-            let mut pos = [0.0_f32; 3];
-            pos[layer_axis] = layer_pos;
+            let mut pos = origin;
             pos[u_axis] = cu;
             pos[v_axis] = cv;
 
@@ -169,7 +180,13 @@ impl ChunkMesh {
             }
         }
 
-        self.quads.push(QuadInfo { direction });
+        self.quads.push(QuadInfo {
+            direction,
+            origin,
+            size: [w as f32, h as f32],
+            is_liquid: false,
+            tile_scale: 1.0,
+        });
     }
 
     /// Counts the number of quads emitted for a specific face direction.
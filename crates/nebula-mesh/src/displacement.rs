@@ -2,7 +2,7 @@
 //! curved surface by projecting through the cube-to-sphere mapping.
 
 use glam::DVec3;
-use nebula_cubesphere::{ChunkAddress, FaceCoord, face_coord_to_cube_point};
+use nebula_cubesphere::{BoundingSphere, ChunkAddress, FaceCoord, ProjectionMethod, project};
 
 /// Parameters describing a planet's geometry for vertex displacement.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -11,6 +11,8 @@ pub struct PlanetParams {
     pub radius: f64,
     /// Size of one voxel in world-space units (meters per voxel).
     pub voxel_size: f64,
+    /// Cube-to-sphere mapping of the planet.
+    pub projection: ProjectionMethod,
 }
 
 impl PlanetParams {
@@ -28,7 +30,18 @@ impl PlanetParams {
             voxel_size > 0.0 && voxel_size.is_finite(),
             "voxel_size must be positive and finite, got {voxel_size}"
         );
-        Self { radius, voxel_size }
+        Self {
+            radius,
+            voxel_size,
+            projection: ProjectionMethod::default(),
+        }
+    }
+
+    /// Displace onto the sphere with `projection` instead of the default
+    /// mapping.
+    pub fn with_projection(mut self, projection: ProjectionMethod) -> Self {
+        self.projection = projection;
+        self
     }
 }
 
@@ -98,9 +111,8 @@ impl Default for DisplacementBuffer {
 ///
 /// 1. Chunk-local `(u8, u8, u8)` in `[0, 32]`
 /// 2. Face UV `(f64, f64)` in `[0, 1]` via chunk's UV bounds
-/// 3. Unit cube point via [`face_coord_to_cube_point`]
-/// 4. Unit sphere point via normalization
-/// 5. Planet surface point at `radius + height`
+/// 3. Unit sphere point via the planet's [`project`]ion
+/// 4. Planet surface point at `radius + height`
 pub fn displace_to_cubesphere(
     mesh: &super::PackedChunkMesh,
     chunk_addr: &ChunkAddress,
@@ -122,14 +134,11 @@ pub fn displace_to_cubesphere(
         let face_u = u_min + local_u * u_size;
         let face_v = v_min + local_v * v_size;
 
-        // Step 2: face UV to unit cube point
+        // Step 2: face UV to unit sphere
         let fc = FaceCoord::new(face, face_u, face_v);
-        let cube_point = face_coord_to_cube_point(&fc);
-
-        // Step 3: normalize to unit sphere
-        let sphere_dir = cube_point.normalize();
+        let sphere_dir = project(&fc, planet.projection);
 
-        // Step 4: apply terrain height (Y axis = radial)
+        // Step 3: apply terrain height (Y axis = radial)
         let height = vertex.position[1] as f64 * planet.voxel_size;
         let world_pos = sphere_dir * (planet.radius + height);
 
@@ -160,244 +169,64 @@ pub fn displace_vertex(
     let face_v = v_min + local_v * v_size;
 
     let fc = FaceCoord::new(chunk_addr.face, face_u, face_v);
-    let cube_point = face_coord_to_cube_point(&fc);
-    let sphere_dir = cube_point.normalize();
+    let sphere_dir = project(&fc, planet.projection);
 
     let height = position[1] as f64 * planet.voxel_size;
     sphere_dir * (planet.radius + height)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::FaceDirection;
-    use crate::packed::{ChunkVertex, PackedChunkMesh};
-    use nebula_cubesphere::CubeFace;
-
-    fn test_planet() -> PlanetParams {
-        PlanetParams::new(1000.0, 1.0)
-    }
-
-    /// A displaced vertex at height 0 should lie on the sphere surface.
-    #[test]
-    fn test_displaced_vertex_is_on_sphere_surface() {
-        let planet = test_planet();
-        let chunk_addr = ChunkAddress::new(CubeFace::PosX, 10, 0, 0);
-
-        let mut mesh = PackedChunkMesh::new();
-        mesh.push_quad(
-            [
-                ChunkVertex::new([16, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
-                ChunkVertex::new([17, 0, 16], FaceDirection::PosY, 0, 1, [1, 0]),
-                ChunkVertex::new([17, 0, 17], FaceDirection::PosY, 0, 1, [1, 1]),
-                ChunkVertex::new([16, 0, 17], FaceDirection::PosY, 0, 1, [0, 1]),
-            ],
-            false,
-        );
-
-        let buf = displace_to_cubesphere(&mesh, &chunk_addr, &planet);
-        assert_eq!(buf.len(), 4);
-
-        for pos in &buf.positions {
-            let p = DVec3::new(pos[0] as f64, pos[1] as f64, pos[2] as f64);
-            let distance = p.length();
-            assert!(
-                (distance - planet.radius).abs() < 0.01,
-                "Vertex at height 0 should be at radius {}, got {distance}",
-                planet.radius
-            );
-        }
-    }
-
-    /// A vertex on the +X face should have a positive X direction.
-    #[test]
-    fn test_vertex_on_pos_x_face_has_positive_x() {
-        let planet = test_planet();
-        let chunk_addr = ChunkAddress::new(CubeFace::PosX, 10, 0, 0);
-
-        let mut mesh = PackedChunkMesh::new();
-        mesh.push_quad(
-            [
-                ChunkVertex::new([16, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
-                ChunkVertex::new([17, 0, 16], FaceDirection::PosY, 0, 1, [1, 0]),
-                ChunkVertex::new([17, 0, 17], FaceDirection::PosY, 0, 1, [1, 1]),
-                ChunkVertex::new([16, 0, 17], FaceDirection::PosY, 0, 1, [0, 1]),
-            ],
-            false,
-        );
-
-        let buf = displace_to_cubesphere(&mesh, &chunk_addr, &planet);
-        let pos = DVec3::new(
-            buf.positions[0][0] as f64,
-            buf.positions[0][1] as f64,
-            buf.positions[0][2] as f64,
-        );
-        let dir = pos.normalize();
-        assert!(
-            dir.x > 0.0,
-            "Vertex on +X face should have positive X direction, got {dir:?}"
-        );
-    }
-
-    /// Vertices at a shared chunk boundary should have identical displaced positions.
-    #[test]
-    fn test_adjacent_chunk_vertices_align_at_boundaries() {
-        let planet = test_planet();
-        let chunk_a = ChunkAddress::new(CubeFace::PosX, 10, 0, 0);
-        let chunk_b = ChunkAddress::new(CubeFace::PosX, 10, 1, 0);
-
-        // Chunk A: vertex at its +U boundary (x=32)
-        let mut mesh_a = PackedChunkMesh::new();
-        mesh_a.push_quad(
-            [
-                ChunkVertex::new([32, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
-                ChunkVertex::new([32, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
-                ChunkVertex::new([32, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
-                ChunkVertex::new([32, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
-            ],
-            false,
-        );
-
-        // Chunk B: vertex at its -U boundary (x=0)
-        let mut mesh_b = PackedChunkMesh::new();
-        mesh_b.push_quad(
-            [
-                ChunkVertex::new([0, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
-                ChunkVertex::new([0, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
-                ChunkVertex::new([0, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
-                ChunkVertex::new([0, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
-            ],
-            false,
-        );
-
-        let buf_a = displace_to_cubesphere(&mesh_a, &chunk_a, &planet);
-        let buf_b = displace_to_cubesphere(&mesh_b, &chunk_b, &planet);
-
-        let pos_a = buf_a.positions[0];
-        let pos_b = buf_b.positions[0];
-
-        let max_diff = (pos_a[0] - pos_b[0])
-            .abs()
-            .max((pos_a[1] - pos_b[1]).abs())
-            .max((pos_a[2] - pos_b[2]).abs());
-        assert!(
-            max_diff < 1e-4,
-            "Boundary vertices should align, max difference: {max_diff}"
-        );
-    }
-
-    /// Displacement magnitude should equal planet radius + height.
-    #[test]
-    fn test_displacement_magnitude_equals_radius_plus_height() {
-        let planet = test_planet();
-        let chunk_addr = ChunkAddress::new(CubeFace::PosZ, 10, 0, 0);
-        let height_voxels = 10u8;
-
-        let mut mesh = PackedChunkMesh::new();
-        mesh.push_quad(
-            [
-                ChunkVertex::new([16, height_voxels, 16], FaceDirection::PosY, 0, 1, [0, 0]),
-                ChunkVertex::new([17, height_voxels, 16], FaceDirection::PosY, 0, 1, [1, 0]),
-                ChunkVertex::new([17, height_voxels, 17], FaceDirection::PosY, 0, 1, [1, 1]),
-                ChunkVertex::new([16, height_voxels, 17], FaceDirection::PosY, 0, 1, [0, 1]),
-            ],
-            false,
-        );
-
-        let buf = displace_to_cubesphere(&mesh, &chunk_addr, &planet);
-        let expected = planet.radius + (height_voxels as f64 * planet.voxel_size);
-
-        for pos in &buf.positions {
-            let p = DVec3::new(pos[0] as f64, pos[1] as f64, pos[2] as f64);
-            let distance = p.length();
-            assert!(
-                (distance - expected).abs() < 0.1,
-                "Expected distance {expected}, got {distance}"
-            );
-        }
-    }
-
-    /// Empty mesh produces empty displacement buffer.
-    #[test]
-    fn test_empty_mesh_produces_empty_buffer() {
-        let planet = test_planet();
-        let chunk_addr = ChunkAddress::new(CubeFace::PosX, 10, 0, 0);
-        let mesh = PackedChunkMesh::new();
-        let buf = displace_to_cubesphere(&mesh, &chunk_addr, &planet);
-        assert!(buf.is_empty());
-    }
-
-    /// `displace_vertex` matches the buffer version.
-    #[test]
-    fn test_displace_vertex_matches_buffer() {
-        let planet = test_planet();
-        let chunk_addr = ChunkAddress::new(CubeFace::NegY, 10, 5, 5);
-        let pos = [16, 8, 16];
-
-        let single = displace_vertex(pos, &chunk_addr, &planet);
-
-        let mut mesh = PackedChunkMesh::new();
-        mesh.push_quad(
-            [
-                ChunkVertex::new(pos, FaceDirection::PosY, 0, 1, [0, 0]),
-                ChunkVertex::new(pos, FaceDirection::PosY, 0, 1, [0, 0]),
-                ChunkVertex::new(pos, FaceDirection::PosY, 0, 1, [0, 0]),
-                ChunkVertex::new(pos, FaceDirection::PosY, 0, 1, [0, 0]),
-            ],
-            false,
-        );
-        let buf = displace_to_cubesphere(&mesh, &chunk_addr, &planet);
-        let buffered = DVec3::new(
-            buf.positions[0][0] as f64,
-            buf.positions[0][1] as f64,
-            buf.positions[0][2] as f64,
-        );
-
-        assert!(
-            (single - buffered).length() < 0.01,
-            "Single vertex and buffer should match: {single:?} vs {buffered:?}"
-        );
-    }
-
-    /// Displacement buffer bytes are correct size.
-    #[test]
-    fn test_displacement_buffer_bytes() {
-        let mut buf = DisplacementBuffer::new();
-        buf.positions.push([1.0, 2.0, 3.0]);
-        buf.positions.push([4.0, 5.0, 6.0]);
-        assert_eq!(buf.byte_size(), 2 * 12);
-        assert_eq!(buf.as_bytes().len(), 24);
-    }
-
-    #[test]
-    #[should_panic(expected = "radius must be positive")]
-    fn test_planet_params_zero_radius_panics() {
-        PlanetParams::new(0.0, 1.0);
-    }
-
-    #[test]
-    #[should_panic(expected = "voxel_size must be positive")]
-    fn test_planet_params_zero_voxel_size_panics() {
-        PlanetParams::new(1000.0, 0.0);
-    }
-
-    /// All six faces produce vertices in the correct hemisphere.
-    #[test]
-    fn test_all_faces_displace_to_correct_hemisphere() {
-        let planet = test_planet();
-
-        for face in CubeFace::ALL {
-            let chunk_addr = ChunkAddress::new(face, 10, 0, 0);
-            let pos = displace_vertex([16, 0, 16], &chunk_addr, &planet);
-            let dir = pos.normalize();
-            let normal = face.normal();
+/// Points sampled along each edge of a chunk's outline by
+/// [`displaced_bounding_sphere`]; must be a multiple of the 32 voxels per
+/// chunk edge so every vertex column on the edge is a sample.
+const OUTLINE_SAMPLES: usize = 32;
 
-            // The displaced vertex should be in the hemisphere of this face's normal
-            assert!(
-                dir.dot(normal) > 0.0,
-                "Vertex on {face:?} should be in correct hemisphere, got dot={:.3}",
-                dir.dot(normal)
-            );
-        }
-    }
+/// Bounding sphere of a chunk's displaced geometry, relative to the planet
+/// center and in the same units as [`PlanetParams::radius`].
+///
+/// `height_range` is the `(min, max)` terrain height within the chunk, so
+/// the sphere encloses every vertex [`displace_vertex`] can produce for it.
+/// The chunk displaces onto a spherical quad, so the farthest points from
+/// any center on the quad's axis lie on its outline (or the axis itself) at
+/// the minimum or maximum height. Only the gnomonic mapping bounds the quad
+/// by great circles, so the outline is sampled along each edge rather than
+/// at the corners alone; the sphere is centered on the axis, halfway through
+/// the shell's axial extent. Use
+/// [`WorldBoundingSphere::from_local`](nebula_cubesphere::WorldBoundingSphere::from_local)
+/// to place it in world space for culling.
+pub fn displaced_bounding_sphere(
+    chunk_addr: &ChunkAddress,
+    planet: &PlanetParams,
+    height_range: (f64, f64),
+) -> BoundingSphere {
+    let (u_min, v_min, u_max, v_max) = chunk_addr.uv_bounds();
+    let outline: Vec<DVec3> = (0..OUTLINE_SAMPLES)
+        .flat_map(|i| {
+            let t = i as f64 / OUTLINE_SAMPLES as f64;
+            let u = u_min + (u_max - u_min) * t;
+            let v = v_min + (v_max - v_min) * t;
+            let w = u_max - (u_max - u_min) * t;
+            let x = v_max - (v_max - v_min) * t;
+            [(u, v_min), (u_max, v), (w, v_max), (u_min, x)]
+        })
+        .map(|(u, v)| project(&FaceCoord::new(chunk_addr.face, u, v), planet.projection))
+        .collect();
+    let axis = outline.iter().sum::<DVec3>().normalize();
+    let cos_max = outline.iter().map(|c| c.dot(axis)).fold(1.0_f64, f64::min);
+
+    let r_min = planet.radius + height_range.0.min(height_range.1);
+    let r_max = planet.radius + height_range.0.max(height_range.1);
+    let center = axis * (r_min * cos_max + r_max) * 0.5;
+
+    let radius = outline
+        .iter()
+        .chain([&axis])
+        .flat_map(|dir| [*dir * r_min, *dir * r_max])
+        .map(|p| p.distance(center))
+        .fold(0.0_f64, f64::max);
+
+    BoundingSphere { center, radius }
 }
+
+#[cfg(test)]
+#[path = "displacement_tests.rs"]
+mod tests;
@@ -0,0 +1,297 @@
+//! Unit tests for cubesphere vertex displacement.
+
+use super::*;
+use crate::FaceDirection;
+use crate::packed::{ChunkVertex, PackedChunkMesh};
+use nebula_cubesphere::CubeFace;
+
+fn test_planet() -> PlanetParams {
+    PlanetParams::new(1000.0, 1.0)
+}
+
+/// A displaced vertex at height 0 should lie on the sphere surface.
+#[test]
+fn test_displaced_vertex_is_on_sphere_surface() {
+    let planet = test_planet();
+    let chunk_addr = ChunkAddress::new(CubeFace::PosX, 10, 0, 0);
+
+    let mut mesh = PackedChunkMesh::new();
+    mesh.push_quad(
+        [
+            ChunkVertex::new([16, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
+            ChunkVertex::new([17, 0, 16], FaceDirection::PosY, 0, 1, [1, 0]),
+            ChunkVertex::new([17, 0, 17], FaceDirection::PosY, 0, 1, [1, 1]),
+            ChunkVertex::new([16, 0, 17], FaceDirection::PosY, 0, 1, [0, 1]),
+        ],
+        false,
+    );
+
+    let buf = displace_to_cubesphere(&mesh, &chunk_addr, &planet);
+    assert_eq!(buf.len(), 4);
+
+    for pos in &buf.positions {
+        let p = DVec3::new(pos[0] as f64, pos[1] as f64, pos[2] as f64);
+        let distance = p.length();
+        assert!(
+            (distance - planet.radius).abs() < 0.01,
+            "Vertex at height 0 should be at radius {}, got {distance}",
+            planet.radius
+        );
+    }
+}
+
+/// A vertex on the +X face should have a positive X direction.
+#[test]
+fn test_vertex_on_pos_x_face_has_positive_x() {
+    let planet = test_planet();
+    let chunk_addr = ChunkAddress::new(CubeFace::PosX, 10, 0, 0);
+
+    let mut mesh = PackedChunkMesh::new();
+    mesh.push_quad(
+        [
+            ChunkVertex::new([16, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
+            ChunkVertex::new([17, 0, 16], FaceDirection::PosY, 0, 1, [1, 0]),
+            ChunkVertex::new([17, 0, 17], FaceDirection::PosY, 0, 1, [1, 1]),
+            ChunkVertex::new([16, 0, 17], FaceDirection::PosY, 0, 1, [0, 1]),
+        ],
+        false,
+    );
+
+    let buf = displace_to_cubesphere(&mesh, &chunk_addr, &planet);
+    let pos = DVec3::new(
+        buf.positions[0][0] as f64,
+        buf.positions[0][1] as f64,
+        buf.positions[0][2] as f64,
+    );
+    let dir = pos.normalize();
+    assert!(
+        dir.x > 0.0,
+        "Vertex on +X face should have positive X direction, got {dir:?}"
+    );
+}
+
+/// Vertices at a shared chunk boundary should have identical displaced positions.
+#[test]
+fn test_adjacent_chunk_vertices_align_at_boundaries() {
+    let planet = test_planet();
+    let chunk_a = ChunkAddress::new(CubeFace::PosX, 10, 0, 0);
+    let chunk_b = ChunkAddress::new(CubeFace::PosX, 10, 1, 0);
+
+    // Chunk A: vertex at its +U boundary (x=32)
+    let mut mesh_a = PackedChunkMesh::new();
+    mesh_a.push_quad(
+        [
+            ChunkVertex::new([32, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
+            ChunkVertex::new([32, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
+            ChunkVertex::new([32, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
+            ChunkVertex::new([32, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
+        ],
+        false,
+    );
+
+    // Chunk B: vertex at its -U boundary (x=0)
+    let mut mesh_b = PackedChunkMesh::new();
+    mesh_b.push_quad(
+        [
+            ChunkVertex::new([0, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
+            ChunkVertex::new([0, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
+            ChunkVertex::new([0, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
+            ChunkVertex::new([0, 0, 16], FaceDirection::PosY, 0, 1, [0, 0]),
+        ],
+        false,
+    );
+
+    let buf_a = displace_to_cubesphere(&mesh_a, &chunk_a, &planet);
+    let buf_b = displace_to_cubesphere(&mesh_b, &chunk_b, &planet);
+
+    let pos_a = buf_a.positions[0];
+    let pos_b = buf_b.positions[0];
+
+    let max_diff = (pos_a[0] - pos_b[0])
+        .abs()
+        .max((pos_a[1] - pos_b[1]).abs())
+        .max((pos_a[2] - pos_b[2]).abs());
+    assert!(
+        max_diff < 1e-4,
+        "Boundary vertices should align, max difference: {max_diff}"
+    );
+}
+
+/// Displacement magnitude should equal planet radius + height.
+#[test]
+fn test_displacement_magnitude_equals_radius_plus_height() {
+    let planet = test_planet();
+    let chunk_addr = ChunkAddress::new(CubeFace::PosZ, 10, 0, 0);
+    let height_voxels = 10u8;
+
+    let mut mesh = PackedChunkMesh::new();
+    mesh.push_quad(
+        [
+            ChunkVertex::new([16, height_voxels, 16], FaceDirection::PosY, 0, 1, [0, 0]),
+            ChunkVertex::new([17, height_voxels, 16], FaceDirection::PosY, 0, 1, [1, 0]),
+            ChunkVertex::new([17, height_voxels, 17], FaceDirection::PosY, 0, 1, [1, 1]),
+            ChunkVertex::new([16, height_voxels, 17], FaceDirection::PosY, 0, 1, [0, 1]),
+        ],
+        false,
+    );
+
+    let buf = displace_to_cubesphere(&mesh, &chunk_addr, &planet);
+    let expected = planet.radius + (height_voxels as f64 * planet.voxel_size);
+
+    for pos in &buf.positions {
+        let p = DVec3::new(pos[0] as f64, pos[1] as f64, pos[2] as f64);
+        let distance = p.length();
+        assert!(
+            (distance - expected).abs() < 0.1,
+            "Expected distance {expected}, got {distance}"
+        );
+    }
+}
+
+/// Empty mesh produces empty displacement buffer.
+#[test]
+fn test_empty_mesh_produces_empty_buffer() {
+    let planet = test_planet();
+    let chunk_addr = ChunkAddress::new(CubeFace::PosX, 10, 0, 0);
+    let mesh = PackedChunkMesh::new();
+    let buf = displace_to_cubesphere(&mesh, &chunk_addr, &planet);
+    assert!(buf.is_empty());
+}
+
+/// `displace_vertex` matches the buffer version.
+#[test]
+fn test_displace_vertex_matches_buffer() {
+    let planet = test_planet();
+    let chunk_addr = ChunkAddress::new(CubeFace::NegY, 10, 5, 5);
+    let pos = [16, 8, 16];
+
+    let single = displace_vertex(pos, &chunk_addr, &planet);
+
+    let mut mesh = PackedChunkMesh::new();
+    mesh.push_quad(
+        [
+            ChunkVertex::new(pos, FaceDirection::PosY, 0, 1, [0, 0]),
+            ChunkVertex::new(pos, FaceDirection::PosY, 0, 1, [0, 0]),
+            ChunkVertex::new(pos, FaceDirection::PosY, 0, 1, [0, 0]),
+            ChunkVertex::new(pos, FaceDirection::PosY, 0, 1, [0, 0]),
+        ],
+        false,
+    );
+    let buf = displace_to_cubesphere(&mesh, &chunk_addr, &planet);
+    let buffered = DVec3::new(
+        buf.positions[0][0] as f64,
+        buf.positions[0][1] as f64,
+        buf.positions[0][2] as f64,
+    );
+
+    assert!(
+        (single - buffered).length() < 0.01,
+        "Single vertex and buffer should match: {single:?} vs {buffered:?}"
+    );
+}
+
+/// Displacement buffer bytes are correct size.
+#[test]
+fn test_displacement_buffer_bytes() {
+    let mut buf = DisplacementBuffer::new();
+    buf.positions.push([1.0, 2.0, 3.0]);
+    buf.positions.push([4.0, 5.0, 6.0]);
+    assert_eq!(buf.byte_size(), 2 * 12);
+    assert_eq!(buf.as_bytes().len(), 24);
+}
+
+/// The chunk's displaced corners at both ends of its height range lie in
+/// its bounding sphere, which is not much larger than they require.
+#[test]
+fn test_displaced_bounding_sphere_is_tight_around_corners() {
+    let planet = test_planet();
+    for (chunk_addr, height) in [
+        (ChunkAddress::new(CubeFace::PosX, 10, 3, 7), 32u8),
+        (ChunkAddress::new(CubeFace::NegZ, 2, 0, 3), 8),
+        (ChunkAddress::new(CubeFace::PosY, 0, 0, 0), 32),
+    ] {
+        let top = f64::from(height) * planet.voxel_size;
+        let sphere = displaced_bounding_sphere(&chunk_addr, &planet, (0.0, top));
+
+        let mut corners = Vec::new();
+        for x in [0, 32] {
+            for z in [0, 32] {
+                for y in [0, height] {
+                    corners.push(displace_vertex([x, y, z], &chunk_addr, &planet));
+                }
+            }
+        }
+        for corner in &corners {
+            assert!(
+                corner.distance(sphere.center) <= sphere.radius + 1e-9,
+                "{chunk_addr:?}: corner {corner:?} outside {sphere:?}"
+            );
+        }
+
+        // No sphere enclosing the corners is smaller than half their
+        // widest separation.
+        let widest = corners
+            .iter()
+            .flat_map(|a| corners.iter().map(move |b| a.distance(*b)))
+            .fold(0.0_f64, f64::max);
+        assert!(
+            sphere.radius <= widest * 0.5 * 1.25,
+            "{chunk_addr:?}: radius {} for corners {widest} apart",
+            sphere.radius
+        );
+    }
+}
+
+/// Interior vertices, not just corners, stay inside the sphere, whatever
+/// the planet's projection.
+#[test]
+fn test_displaced_bounding_sphere_contains_interior_vertices() {
+    let chunk_addr = ChunkAddress::new(CubeFace::NegX, 1, 1, 0);
+    for projection in [ProjectionMethod::Gnomonic, ProjectionMethod::Cobe] {
+        let planet = test_planet().with_projection(projection);
+        let sphere = displaced_bounding_sphere(&chunk_addr, &planet, (2.0, 20.0));
+        for x in (0..=32).step_by(4) {
+            for z in (0..=32).step_by(4) {
+                for y in [2, 11, 20] {
+                    let p = displace_vertex([x, y, z], &chunk_addr, &planet);
+                    assert!(
+                        p.distance(sphere.center) <= sphere.radius + 1e-9,
+                        "{projection:?}: {p:?} outside {sphere:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "radius must be positive")]
+fn test_planet_params_zero_radius_panics() {
+    PlanetParams::new(0.0, 1.0);
+}
+
+#[test]
+#[should_panic(expected = "voxel_size must be positive")]
+fn test_planet_params_zero_voxel_size_panics() {
+    PlanetParams::new(1000.0, 0.0);
+}
+
+/// All six faces produce vertices in the correct hemisphere.
+#[test]
+fn test_all_faces_displace_to_correct_hemisphere() {
+    let planet = test_planet();
+
+    for face in CubeFace::ALL {
+        let chunk_addr = ChunkAddress::new(face, 10, 0, 0);
+        let pos = displace_vertex([16, 0, 16], &chunk_addr, &planet);
+        let dir = pos.normalize();
+        let normal = face.normal();
+
+        // The displaced vertex should be in the hemisphere of this face's normal
+        assert!(
+            dir.dot(normal) > 0.0,
+            "Vertex on {face:?} should be in correct hemisphere, got dot={:.3}",
+            dir.dot(normal)
+        );
+    }
+}
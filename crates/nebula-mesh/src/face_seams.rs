@@ -0,0 +1,248 @@
+//! Chunk neighborhoods that reach across cube-face seams.
+//!
+//! Planet chunks sit in a per-face grid: a chunk address's `x` and `z`
+//! index the face's U and V directions and `y` the radial layer, matching
+//! the chunk-local axes used by [`displace_to_cubesphere`](crate::displace_to_cubesphere)
+//! (x → U, z → V, y → radial). Inside a face, neighbors are plain grid
+//! offsets. Past a face edge the neighbor lies on the adjacent face given by
+//! [`face_adjacency`], so [`assemble_neighborhood`] maps each boundary voxel
+//! across the seam: the along-edge coordinate is reversed where the edge is
+//! flipped, the across-edge coordinate counts inward from the neighbor's
+//! edge, and the radial axis is kept. Keeping the radial axis means outward
+//! stays outward on both sides, so the per-face winding from
+//! [`compute_winding_flip_table`](nebula_cubesphere::compute_winding_flip_table)
+//! applies unchanged to meshes built from these neighborhoods.
+
+use nebula_cubesphere::{CubeFace, FaceDirection as EdgeSide, face_adjacency};
+use nebula_voxel::{CHUNK_SIZE, ChunkAddress, ChunkManager};
+
+use crate::neighborhood::ChunkNeighborhood;
+
+/// Chunks along each side of a cube face at full resolution, matching the
+/// LOD 0 grid of [`nebula_cubesphere::ChunkAddress`].
+pub const FACE_CHUNKS: i64 = nebula_cubesphere::ChunkAddress::MAX_LOD0_AXIS as i64;
+
+/// Build the neighborhood of the chunk at `addr` from `manager`, fetching
+/// neighbors across cube-face edges from the adjacent face.
+///
+/// Missing chunks read as air. Where two face edges meet at a cube corner
+/// only three faces touch, so the diagonal neighbors there are air too.
+/// Addresses whose `face` is not a cube face (free-space chunks) use plain
+/// grid offsets.
+pub fn assemble_neighborhood(manager: &ChunkManager, addr: &ChunkAddress) -> ChunkNeighborhood {
+    let center = manager.get_chunk(addr).map(|chunk| chunk.data().clone());
+    ChunkNeighborhood::from_lookup(center, |x, y, z| {
+        let (chunk_addr, [lx, ly, lz]) = locate_voxel(addr, x, y, z)?;
+        let chunk = manager.get_chunk(&chunk_addr)?;
+        Some(chunk.data().get(lx, ly, lz))
+    })
+}
+
+/// The chunk and local voxel for center-relative coordinates `(x, y, z)`
+/// around `addr`, crossing onto the adjacent face past a face edge. `None`
+/// beyond a cube corner, where no voxel exists.
+fn locate_voxel(addr: &ChunkAddress, x: i32, y: i32, z: i32) -> Option<(ChunkAddress, [usize; 3])> {
+    let s = CHUNK_SIZE as i64;
+    let u = addr.x * s + i64::from(x);
+    let r = addr.y * s + i64::from(y);
+    let v = addr.z * s + i64::from(z);
+
+    let (face, u, v) = match CubeFace::ALL.get(usize::from(addr.face)) {
+        Some(&face) => cross_seam(face, u, v)?,
+        None => return Some(split(addr.face, u, r, v)),
+    };
+    Some(split(face as u8, u, r, v))
+}
+
+/// Map face-voxel coordinates `(u, v)` that may lie past one edge of `face`
+/// onto the face they actually fall on.
+fn cross_seam(face: CubeFace, u: i64, v: i64) -> Option<(CubeFace, i64, i64)> {
+    let extent = FACE_CHUNKS * CHUNK_SIZE as i64;
+    let outside = |c: i64| !(0..extent).contains(&c);
+
+    // `depth` counts voxels past the edge (1 = first voxel beyond it).
+    let (side, depth, along) = match (outside(u), outside(v)) {
+        (false, false) => return Some((face, u, v)),
+        (true, true) => return None,
+        (true, false) if u >= extent => (EdgeSide::East, u - extent + 1, v),
+        (true, false) => (EdgeSide::West, -u, v),
+        (false, true) if v >= extent => (EdgeSide::North, v - extent + 1, u),
+        (false, true) => (EdgeSide::South, -v, u),
+    };
+
+    let adj = face_adjacency(face, side);
+    let along = if adj.flipped {
+        extent - 1 - along
+    } else {
+        along
+    };
+    let inward = depth - 1;
+    let (nu, nv) = match adj.neighbor_edge {
+        EdgeSide::East => (extent - 1 - inward, along),
+        EdgeSide::West => (inward, along),
+        EdgeSide::North => (along, extent - 1 - inward),
+        EdgeSide::South => (along, inward),
+    };
+    Some((adj.neighbor_face, nu, nv))
+}
+
+/// Split face-voxel coordinates into a chunk address and local voxel.
+fn split(face: u8, u: i64, r: i64, v: i64) -> (ChunkAddress, [usize; 3]) {
+    let s = CHUNK_SIZE as i64;
+    let addr = ChunkAddress::new(u.div_euclid(s), r.div_euclid(s), v.div_euclid(s), face);
+    let local = [u, r, v].map(|c| c.rem_euclid(s) as usize);
+    (addr, local)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::face_direction::FaceDirection;
+    use crate::visibility::compute_visible_faces;
+    use nebula_cubesphere::{FaceCoord, transform_uv_across_edge};
+    use nebula_voxel::{
+        Chunk, ChunkData, Transparency, VoxelTypeDef, VoxelTypeId, VoxelTypeRegistry,
+    };
+
+    const LAST: i64 = FACE_CHUNKS - 1;
+
+    fn registry() -> (VoxelTypeRegistry, VoxelTypeId) {
+        let mut reg = VoxelTypeRegistry::new();
+        let stone = reg
+            .register(VoxelTypeDef {
+                name: "stone".to_string(),
+                solid: true,
+                transparency: Transparency::Opaque,
+                material_index: 1,
+                light_emission: [0; 3],
+            })
+            .expect("register stone");
+        (reg, stone)
+    }
+
+    /// One edge per cube edge: the `(face, side)` with the lower face index.
+    fn cube_edges() -> Vec<(CubeFace, EdgeSide)> {
+        let mut edges = Vec::new();
+        for face in CubeFace::ALL {
+            for side in EdgeSide::ALL {
+                if (face as u8) < (face_adjacency(face, side).neighbor_face as u8) {
+                    edges.push((face, side));
+                }
+            }
+        }
+        edges
+    }
+
+    /// A chunk on `face` touching its `side` edge, and the mesh direction
+    /// that points across that edge.
+    fn seam_chunk(face: CubeFace, side: EdgeSide) -> (ChunkAddress, FaceDirection) {
+        let f = face as u8;
+        match side {
+            EdgeSide::East => (ChunkAddress::new(LAST, 3, 7, f), FaceDirection::PosX),
+            EdgeSide::West => (ChunkAddress::new(0, 3, 7, f), FaceDirection::NegX),
+            EdgeSide::North => (ChunkAddress::new(7, 3, LAST, f), FaceDirection::PosZ),
+            EdgeSide::South => (ChunkAddress::new(7, 3, 0, f), FaceDirection::NegZ),
+        }
+    }
+
+    /// Center-relative `(x, z)` of the voxel just past the chunk boundary in
+    /// `dir`, at position `i` along it.
+    fn beyond_edge(dir: FaceDirection, i: i32) -> (i32, i32) {
+        let s = CHUNK_SIZE as i32;
+        match dir {
+            FaceDirection::PosX => (s, i),
+            FaceDirection::NegX => (-1, i),
+            FaceDirection::PosZ => (i, s),
+            _ => (i, -1),
+        }
+    }
+
+    #[test]
+    fn test_cube_has_twelve_edges() {
+        assert_eq!(cube_edges().len(), 12);
+    }
+
+    #[test]
+    fn test_solid_chunks_cull_seam_faces_on_every_cube_edge() {
+        let (reg, stone) = registry();
+        for (face, side) in cube_edges() {
+            let (addr, dir) = seam_chunk(face, side);
+            let (x, z) = beyond_edge(dir, 0);
+            let (across, _) = locate_voxel(&addr, x, 0, z).expect("seam neighbor");
+            assert_ne!(across.face, addr.face, "{face:?} {side:?} should cross");
+
+            let mut manager = ChunkManager::new();
+            manager.load_chunk(addr, Chunk::new_filled(stone));
+            manager.load_chunk(across, Chunk::new_filled(stone));
+            let neighborhood = assemble_neighborhood(&manager, &addr);
+
+            let center = ChunkData::new(stone);
+            let mut same_face = ChunkNeighborhood::from_center_only(center.clone());
+            same_face.set_face_neighbor(dir, &center);
+
+            let seam = compute_visible_faces(&center, &neighborhood, &reg);
+            let reference = compute_visible_faces(&center, &same_face, &reg);
+            assert!(
+                seam.iter().all(|faces| !faces.is_visible(dir)),
+                "{face:?} {side:?}: seam faces left visible"
+            );
+            assert_eq!(
+                seam.iter().map(|f| f.count()).sum::<u32>(),
+                reference.iter().map(|f| f.count()).sum::<u32>(),
+                "{face:?} {side:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_seam_voxels_land_where_the_cube_table_puts_them() {
+        let s = CHUNK_SIZE as i32;
+        let s64 = CHUNK_SIZE as i64;
+        let extent = (FACE_CHUNKS * s64) as f64;
+        for face in CubeFace::ALL {
+            for side in EdgeSide::ALL {
+                let (addr, dir) = seam_chunk(face, side);
+                for (i, y) in [(0, 0), (5, 17), (31, 31)] {
+                    let (x, z) = beyond_edge(dir, i);
+                    let (n_addr, [nx, ny, nz]) = locate_voxel(&addr, x, y, z).expect("neighbor");
+                    assert_eq!(ny as i32, y, "radial axis preserved");
+
+                    // The neighbor voxel sits where the cube table puts the
+                    // edge point we stepped across.
+                    let edge_u = (addr.x * s64 + i64::from(x.clamp(0, s - 1))) as f64 + 0.5;
+                    let edge_v = (addr.z * s64 + i64::from(z.clamp(0, s - 1))) as f64 + 0.5;
+                    let (eu, ev) = match side {
+                        EdgeSide::East => (extent, edge_v),
+                        EdgeSide::West => (0.0, edge_v),
+                        EdgeSide::North => (edge_u, extent),
+                        EdgeSide::South => (edge_u, 0.0),
+                    };
+                    let expected = transform_uv_across_edge(face, side, eu / extent, ev / extent);
+                    let got = FaceCoord::new(
+                        CubeFace::ALL[n_addr.face as usize],
+                        ((n_addr.x * s64 + nx as i64) as f64 + 0.5) / extent,
+                        ((n_addr.z * s64 + nz as i64) as f64 + 0.5) / extent,
+                    );
+                    assert_eq!(got.face, expected.face);
+                    assert!((got.u - expected.u).abs() <= 1.0 / extent);
+                    assert!((got.v - expected.v).abs() <= 1.0 / extent);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cube_corner_diagonal_is_air() {
+        let addr = ChunkAddress::new(LAST, 0, LAST, CubeFace::PosY as u8);
+        assert!(locate_voxel(&addr, 32, 0, 32).is_none());
+        assert!(locate_voxel(&addr, 31, 0, 31).is_some());
+    }
+
+    #[test]
+    fn test_interior_chunk_matches_same_face_offsets() {
+        let addr = ChunkAddress::new(10, 2, 10, CubeFace::NegZ as u8);
+        let (n, local) = locate_voxel(&addr, 32, -1, 5).expect("neighbor");
+        assert_eq!(n, addr.offset(1, -1, 0));
+        assert_eq!(local, [0, 31, 5]);
+    }
+}
@@ -151,7 +151,7 @@ mod tests {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 1,
-            light_emission: 0,
+            light_emission: [0; 3],
         })
         .expect("register stone");
         reg.register(VoxelTypeDef {
@@ -159,7 +159,7 @@ mod tests {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 2,
-            light_emission: 0,
+            light_emission: [0; 3],
         })
         .expect("register dirt");
         reg
@@ -2,11 +2,14 @@
 
 pub mod ambient_occlusion;
 pub mod async_mesh;
+pub mod biome_blend;
 pub mod chunk_mesh;
 pub mod displacement;
 pub mod face_direction;
+pub mod face_seams;
 pub mod greedy;
 pub mod invalidation;
+pub mod liquid;
 pub mod lod_meshing;
 pub mod lod_stitching;
 pub mod neighborhood;
@@ -17,8 +20,10 @@ pub mod visibility;
 pub mod visible_faces;
 
 pub use ambient_occlusion::{compute_face_ao, should_flip_ao_diagonal, vertex_ao};
+pub use biome_blend::{DEFAULT_BIOME_BLEND_RADIUS, blend_biome_materials, pack_blended_chunk_mesh};
 pub use chunk_mesh::{ChunkMesh, MeshVertex, QuadInfo};
 pub use face_direction::{CornerDirection, EdgeDirection, FaceDirection};
+pub use face_seams::{FACE_CHUNKS, assemble_neighborhood};
 pub use greedy::greedy_mesh;
 pub use neighborhood::{
     ChunkBoundaryEdge, ChunkBoundarySlice, ChunkNeighborhood, extract_boundary_slice,
@@ -31,8 +36,12 @@ pub use visible_faces::VisibleFaces;
 pub use lod_meshing::{compute_visible_faces_lod, default_registry, mesh_lod_chunk};
 
 pub use async_mesh::{MeshingPipeline, MeshingResult, MeshingTask};
-pub use displacement::{DisplacementBuffer, PlanetParams, displace_to_cubesphere, displace_vertex};
+pub use displacement::{
+    DisplacementBuffer, PlanetParams, displace_to_cubesphere, displace_vertex,
+    displaced_bounding_sphere,
+};
 pub use invalidation::{ChunkMeshState, MeshInvalidator};
+pub use liquid::{LiquidTileScales, compute_liquid_uvs, greedy_mesh_liquid, is_liquid};
 pub use lod_stitching::{
     LodContext, apply_lod_stitching, generate_transition_strip, snap_edge_vertices,
 };
@@ -0,0 +1,267 @@
+//! Liquid surface meshing: world-space tiled UVs for scrolling water textures.
+//!
+//! Regular greedy quads restart their UVs at `(0, 0)` on every quad, which is
+//! fine for static block textures but makes a scrolling water texture jump at
+//! every quad and chunk seam. Liquid quads instead derive their UVs from the
+//! world-space position, so neighbouring quads continue the same texture
+//! tile. Each liquid's tile size comes from its material's `tile_scale`;
+//! the shader scrolls it by the material's `uv_scroll` and the animator's
+//! `uv_offset` (see `pbr_voxel.wgsl`), so every surface of one liquid moves
+//! in unison.
+
+use nebula_materials::{MaterialId, MaterialRegistry};
+use nebula_voxel::{ChunkData, Transparency, VoxelTypeId, VoxelTypeRegistry};
+
+use crate::chunk_mesh::{ChunkMesh, QuadInfo};
+use crate::face_direction::FaceDirection;
+use crate::greedy::greedy_mesh;
+use crate::neighborhood::ChunkNeighborhood;
+use crate::visible_faces::VisibleFaces;
+
+/// Returns `true` for voxel types meshed as liquids: non-solid and
+/// semi-transparent (water, lava).
+pub fn is_liquid(registry: &VoxelTypeRegistry, voxel_type: VoxelTypeId) -> bool {
+    let def = registry.get(voxel_type);
+    !def.solid && def.transparency == Transparency::SemiTransparent
+}
+
+/// Texture tile size, in voxels, for each voxel type's liquid surfaces.
+///
+/// Types without an entry tile every voxel (scale 1.0).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LiquidTileScales(Vec<f32>);
+
+impl LiquidTileScales {
+    /// The same tile size for every voxel type.
+    pub fn uniform(tile_scale: f32) -> Self {
+        Self(vec![tile_scale; u16::MAX as usize + 1])
+    }
+
+    /// Tile sizes from the `tile_scale` of each voxel type's material.
+    pub fn from_materials(voxels: &VoxelTypeRegistry, materials: &MaterialRegistry) -> Self {
+        Self(
+            (0..voxels.len())
+                .map(|id| {
+                    let def = voxels.get(VoxelTypeId(id as u16));
+                    materials.get(MaterialId(def.material_index)).tile_scale
+                })
+                .collect(),
+        )
+    }
+
+    /// Tile size for `voxel_type`.
+    pub fn get(&self, voxel_type: VoxelTypeId) -> f32 {
+        self.0.get(voxel_type.0 as usize).copied().unwrap_or(1.0)
+    }
+}
+
+/// Computes world-space tiled UVs for the four corners of `quad`.
+///
+/// `quad.origin` must already be in world space (or offset by any multiple
+/// of `tile_scale`). `tile_scale` is the number of voxels covered by one
+/// texture tile. Only the first corner is wrapped into `[0, 1)`; the other
+/// corners extend past 1.0 by the quad's size in tiles, so a quad spanning
+/// several tiles still interpolates linearly and relies on a repeating
+/// sampler. Corners are returned in the order emitted by
+/// [`ChunkMesh::push_quad_ao`].
+pub fn compute_liquid_uvs(quad: &QuadInfo, face: FaceDirection, tile_scale: f32) -> [[f32; 2]; 4] {
+    let tile = tile_scale.max(f32::EPSILON);
+    let (_, u_axis, v_axis) = face.sweep_axes();
+    let u0 = (quad.origin[u_axis] / tile).rem_euclid(1.0);
+    let v0 = (quad.origin[v_axis] / tile).rem_euclid(1.0);
+    let u1 = u0 + quad.size[0] / tile;
+    let v1 = v0 + quad.size[1] / tile;
+    [[u0, v0], [u1, v0], [u1, v1], [u0, v1]]
+}
+
+/// Greedy-meshes a chunk and rewrites the UVs of its liquid quads to tile
+/// in world space.
+///
+/// `chunk_origin` is the chunk's minimum corner in world voxel coordinates.
+/// It is reduced modulo each liquid's tile size in `f64` before use, so UVs
+/// stay precise far from the world origin.
+pub fn greedy_mesh_liquid(
+    chunk: &ChunkData,
+    visible_faces: &[VisibleFaces],
+    neighbors: &ChunkNeighborhood,
+    registry: &VoxelTypeRegistry,
+    chunk_origin: [i64; 3],
+    tile_scales: &LiquidTileScales,
+) -> ChunkMesh {
+    let mut mesh = greedy_mesh(chunk, visible_faces, neighbors, registry);
+
+    // `push_quad_ao` emits exactly four vertices per quad.
+    for (quad, vertices) in mesh.quads.iter_mut().zip(mesh.vertices.chunks_mut(4)) {
+        let voxel_type = vertices[0].voxel_type;
+        if !is_liquid(registry, voxel_type) {
+            continue;
+        }
+        let tile_scale = tile_scales.get(voxel_type);
+        let tile = f64::from(tile_scale.max(f32::EPSILON));
+        let offset = chunk_origin.map(|c| (c as f64).rem_euclid(tile) as f32);
+        quad.is_liquid = true;
+        quad.tile_scale = tile_scale;
+
+        let world = QuadInfo {
+            origin: [
+                quad.origin[0] + offset[0],
+                quad.origin[1] + offset[1],
+                quad.origin[2] + offset[2],
+            ],
+            ..*quad
+        };
+        let uvs = compute_liquid_uvs(&world, quad.direction, tile_scale);
+        for (vertex, uv) in vertices.iter_mut().zip(uvs) {
+            vertex.uv = uv;
+        }
+    }
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use nebula_voxel::{CHUNK_SIZE, VoxelTypeDef};
+
+    use super::*;
+    use crate::visibility::compute_visible_faces;
+
+    /// Stone = VoxelTypeId(1), Water = VoxelTypeId(2).
+    const STONE: VoxelTypeId = VoxelTypeId(1);
+    const WATER: VoxelTypeId = VoxelTypeId(2);
+
+    fn test_registry() -> VoxelTypeRegistry {
+        let mut reg = VoxelTypeRegistry::new();
+        reg.register(VoxelTypeDef {
+            name: "stone".to_string(),
+            solid: true,
+            transparency: Transparency::Opaque,
+            material_index: 1,
+            light_emission: [0; 3],
+        })
+        .expect("register stone");
+        reg.register(VoxelTypeDef {
+            name: "water".to_string(),
+            solid: false,
+            transparency: Transparency::SemiTransparent,
+            material_index: 2,
+            light_emission: [0; 3],
+        })
+        .expect("register water");
+        reg
+    }
+
+    /// Asserts two UVs sample the same texel under a repeating sampler.
+    fn assert_same_phase(a: [f32; 2], b: [f32; 2]) {
+        for axis in 0..2 {
+            let phase = (a[axis] - b[axis]).rem_euclid(1.0);
+            let distance = phase.min(1.0 - phase);
+            assert!(
+                distance < 1e-4,
+                "UVs {a:?} and {b:?} do not tile seamlessly"
+            );
+        }
+    }
+
+    fn top_quad(origin_x: f32, width: f32) -> QuadInfo {
+        QuadInfo {
+            direction: FaceDirection::PosY,
+            origin: [origin_x, 1.0, 0.0],
+            size: [width, 2.0],
+            is_liquid: true,
+            tile_scale: 4.0,
+        }
+    }
+
+    #[test]
+    fn test_adjacent_quads_tile_seamlessly() {
+        // Quad A covers x in [0, 5), quad B continues over [5, 7).
+        let a = compute_liquid_uvs(&top_quad(0.0, 5.0), FaceDirection::PosY, 4.0);
+        let b = compute_liquid_uvs(&top_quad(5.0, 2.0), FaceDirection::PosY, 4.0);
+
+        // A's far edge (corners 1, 2) meets B's near edge (corners 0, 3).
+        assert_same_phase(a[1], b[0]);
+        assert_same_phase(a[2], b[3]);
+        assert_eq!(b[0], [0.25, 0.0]);
+    }
+
+    #[test]
+    fn test_uvs_scale_with_tile_size() {
+        let uvs = compute_liquid_uvs(&top_quad(2.0, 8.0), FaceDirection::PosY, 4.0);
+        assert_eq!(uvs, [[0.5, 0.0], [2.5, 0.0], [2.5, 0.5], [0.5, 0.5]]);
+    }
+
+    #[test]
+    fn test_water_surface_tiles_across_chunk_seam() {
+        let mut chunk = ChunkData::new_air();
+        for z in 0..CHUNK_SIZE {
+            for x in 0..CHUNK_SIZE {
+                chunk.set(x, 0, z, WATER);
+            }
+        }
+        let neighbors = ChunkNeighborhood::all_air();
+        let reg = test_registry();
+        let visible = compute_visible_faces(&chunk, &neighbors, &reg);
+
+        let scales = LiquidTileScales::uniform(5.0);
+        let west = greedy_mesh_liquid(&chunk, &visible, &neighbors, &reg, [0, 0, 0], &scales);
+        let east = greedy_mesh_liquid(&chunk, &visible, &neighbors, &reg, [32, 0, 0], &scales);
+
+        let top_uvs = |mesh: &ChunkMesh| {
+            let q = mesh
+                .quads
+                .iter()
+                .position(|q| q.direction == FaceDirection::PosY)
+                .expect("water surface quad");
+            assert!(mesh.quads[q].is_liquid);
+            assert_eq!(mesh.quads[q].tile_scale, 5.0);
+            let uvs: Vec<[f32; 2]> = mesh.vertices[q * 4..q * 4 + 4]
+                .iter()
+                .map(|v| v.uv)
+                .collect();
+            uvs
+        };
+        let west_uvs = top_uvs(&west);
+        let east_uvs = top_uvs(&east);
+        assert_same_phase(west_uvs[1], east_uvs[0]);
+        assert_same_phase(west_uvs[2], east_uvs[3]);
+    }
+
+    #[test]
+    fn test_solid_quads_keep_per_quad_uvs() {
+        let mut chunk = ChunkData::new_air();
+        chunk.set(3, 0, 3, STONE);
+        let neighbors = ChunkNeighborhood::all_air();
+        let reg = test_registry();
+        let visible = compute_visible_faces(&chunk, &neighbors, &reg);
+
+        let scales = LiquidTileScales::uniform(5.0);
+        let mesh = greedy_mesh_liquid(&chunk, &visible, &neighbors, &reg, [7, 0, 7], &scales);
+        assert!(mesh.quads.iter().all(|q| !q.is_liquid));
+        assert_eq!(mesh.vertices[0].uv, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_tile_scales_follow_material() {
+        let entry = |name: &str, extra: &str| {
+            format!(
+                r#"(name: "{name}", albedo: (1.0, 1.0, 1.0, 1.0), metallic: 0.0,
+                roughness: 0.5, emissive_color: (0.0, 0.0, 0.0), emissive_intensity: 0.0,
+                normal_strength: 1.0, opacity: 1.0, {extra}
+                textures: Uniform(texture: "{name}.png")),"#
+            )
+        };
+        let ron = format!(
+            "MaterialManifest(atlas: AtlasConfig(atlas_size: 64, tile_size: 16), \
+             materials: [{}{}])",
+            entry("stone", ""),
+            entry("water", "tile_scale: 8.0,"),
+        );
+        let materials =
+            MaterialRegistry::from_ron_str(&ron, std::path::Path::new("")).expect("materials");
+        let scales = LiquidTileScales::from_materials(&test_registry(), &materials);
+
+        assert_eq!(scales.get(WATER), 8.0);
+        assert_eq!(scales.get(STONE), 1.0);
+        assert_eq!(scales.get(VoxelTypeId(999)), 1.0);
+    }
+}
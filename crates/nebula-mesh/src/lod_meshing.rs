@@ -214,7 +214,7 @@ pub fn default_registry() -> VoxelTypeRegistry {
         solid: true,
         transparency: Transparency::Opaque,
         material_index: 1,
-        light_emission: 0,
+        light_emission: [0; 3],
     })
     .expect("register stone");
     reg
@@ -91,19 +91,18 @@ pub fn snap_edge_vertices(
             continue;
         }
 
-        let u = vertex.position[u_idx] as usize;
-        let v = vertex.position[v_idx] as usize;
-
-        // Round to nearest multiple of step (ties go down via integer division).
-        let snapped_u = ((u + step / 2) / step) * step;
-        let snapped_v = ((v + step / 2) / step) * step;
-
-        // Clamp to chunk bounds.
-        vertex.position[u_idx] = snapped_u.min(chunk_size) as u8;
-        vertex.position[v_idx] = snapped_v.min(chunk_size) as u8;
+        vertex.position[u_idx] = snap_to_step(vertex.position[u_idx], step, chunk_size);
+        vertex.position[v_idx] = snap_to_step(vertex.position[v_idx], step, chunk_size);
     }
 }
 
+/// Round a coordinate to the nearest multiple of `step`, clamped to the chunk.
+fn snap_to_step(coord: u8, step: usize, chunk_size: usize) -> u8 {
+    // Ties round up: (coord + step / 2) / step truncates toward zero.
+    let snapped = ((coord as usize + step / 2) / step) * step;
+    snapped.min(chunk_size) as u8
+}
+
 /// Generate a transition mesh strip along a LOD boundary.
 ///
 /// The strip connects the high-LOD edge vertices to the low-LOD edge vertices
@@ -223,11 +222,29 @@ pub fn generate_transition_strip(
 ///
 /// For each face with a positive LOD difference (neighbor is coarser),
 /// snaps boundary vertices to the coarser grid.
+///
+/// A vertex on an edge or corner shared by several boundary faces is snapped
+/// once, to the grid of the coarsest neighbor among those faces. Snapping
+/// face by face would round it twice (e.g. 29 → 30 → 32 instead of 28 for
+/// LOD 1 then LOD 2), leaving it off the position the coarsest neighbor
+/// meshes and cracking the corner where three LODs meet.
 pub fn apply_lod_stitching(mesh: &mut PackedChunkMesh, context: &LodContext, chunk_size: usize) {
-    for dir in FaceDirection::ALL {
-        let diff = context.lod_difference(dir);
-        if diff > 0 {
-            snap_edge_vertices(mesh, dir, diff as u8, chunk_size);
+    for vertex in &mut mesh.vertices {
+        let coarsest = FaceDirection::ALL
+            .into_iter()
+            .filter(|&dir| is_on_face_boundary(vertex, dir, chunk_size))
+            .map(|dir| context.lod_difference(dir))
+            .max()
+            .unwrap_or(0);
+        if coarsest <= 0 {
+            continue;
+        }
+
+        // Coordinates on a boundary plane (0 or chunk_size) are fixed points
+        // of the snap, so all three axes can be snapped uniformly.
+        let step = 1usize << coarsest;
+        for coord in &mut vertex.position {
+            *coord = snap_to_step(*coord, step, chunk_size);
         }
     }
 }
@@ -383,6 +400,39 @@ mod tests {
         }
     }
 
+    /// Where the +X (LOD 1), +Y (LOD 2) and +Z (LOD 0) neighbors meet, the
+    /// shared edge and corner vertices land on the LOD 2 grid.
+    #[test]
+    fn test_corner_snaps_to_coarsest_neighbor() {
+        let chunk_size = 32usize;
+        let edge = chunk_size as u8;
+        let mut mesh = PackedChunkMesh::new();
+        for z in [29, 30, 31, edge] {
+            mesh.vertices.push(ChunkVertex::new(
+                [edge, edge, z],
+                FaceDirection::PosY,
+                0,
+                1,
+                [edge, z],
+            ));
+        }
+
+        let ctx = LodContext {
+            center_lod: 0,
+            neighbor_lods: [Some(1), Some(0), Some(2), Some(0), Some(0), Some(0)],
+        };
+        apply_lod_stitching(&mut mesh, &ctx, chunk_size);
+
+        let step = 4u8;
+        for vertex in &mesh.vertices {
+            for coord in vertex.position {
+                assert_eq!(coord % step, 0, "{:?} off the LOD 2 grid", vertex.position);
+            }
+        }
+        assert_eq!(mesh.vertices[0].position, [edge, edge, 28]);
+        assert_eq!(mesh.vertices[3].position, [edge, edge, edge]);
+    }
+
     /// Uniform LOD context has no transitions.
     #[test]
     fn test_uniform_lod_context() {
@@ -0,0 +1,136 @@
+//! Unit tests for chunk neighborhood boundary extraction and lookup.
+
+use super::*;
+
+fn make_chunk(fill: VoxelTypeId) -> ChunkData {
+    ChunkData::new(fill)
+}
+
+#[test]
+fn test_interior_voxel_does_not_need_neighbors() {
+    let center = make_chunk(VoxelTypeId(1));
+    let neighborhood = ChunkNeighborhood::from_center_only(center);
+    assert_eq!(neighborhood.get(16, 16, 16), VoxelTypeId(1));
+}
+
+#[test]
+fn test_boundary_voxel_queries_correct_neighbor() {
+    let center = ChunkData::new_air();
+    let mut neg_x_neighbor = ChunkData::new_air();
+    neg_x_neighbor.set(31, 10, 10, VoxelTypeId(1));
+
+    let mut neighborhood = ChunkNeighborhood::from_center_only(center);
+    neighborhood.set_face_neighbor(FaceDirection::NegX, &neg_x_neighbor);
+
+    assert_eq!(neighborhood.get(-1, 10, 10), VoxelTypeId(1));
+}
+
+#[test]
+fn test_missing_neighbor_treats_boundary_as_air() {
+    let center = make_chunk(VoxelTypeId(1));
+    let neighborhood = ChunkNeighborhood::from_center_only(center);
+    assert_eq!(neighborhood.get(32, 10, 10), VoxelTypeId(0));
+}
+
+#[test]
+fn test_neighborhood_covers_all_26_directions() {
+    let center = ChunkData::new_air();
+    let mut neighborhood = ChunkNeighborhood::from_center_only(center);
+
+    let stone_chunk = make_chunk(VoxelTypeId(1));
+    for dir in FaceDirection::ALL {
+        neighborhood.set_face_neighbor(dir, &stone_chunk);
+    }
+    for edge in EdgeDirection::ALL {
+        neighborhood.set_edge_neighbor(edge, &stone_chunk);
+    }
+    for corner in CornerDirection::ALL {
+        neighborhood.set_corner_neighbor(corner, VoxelTypeId(1));
+    }
+
+    // Face neighbors
+    assert_eq!(neighborhood.get(32, 16, 16), VoxelTypeId(1)); // +X
+    assert_eq!(neighborhood.get(-1, 16, 16), VoxelTypeId(1)); // -X
+    assert_eq!(neighborhood.get(16, 32, 16), VoxelTypeId(1)); // +Y
+    assert_eq!(neighborhood.get(16, -1, 16), VoxelTypeId(1)); // -Y
+    assert_eq!(neighborhood.get(16, 16, 32), VoxelTypeId(1)); // +Z
+    assert_eq!(neighborhood.get(16, 16, -1), VoxelTypeId(1)); // -Z
+
+    // Corner neighbors
+    assert_eq!(neighborhood.get(-1, -1, -1), VoxelTypeId(1));
+    assert_eq!(neighborhood.get(32, -1, -1), VoxelTypeId(1));
+    assert_eq!(neighborhood.get(-1, 32, -1), VoxelTypeId(1));
+    assert_eq!(neighborhood.get(32, 32, -1), VoxelTypeId(1));
+    assert_eq!(neighborhood.get(-1, -1, 32), VoxelTypeId(1));
+    assert_eq!(neighborhood.get(32, -1, 32), VoxelTypeId(1));
+    assert_eq!(neighborhood.get(-1, 32, 32), VoxelTypeId(1));
+    assert_eq!(neighborhood.get(32, 32, 32), VoxelTypeId(1));
+}
+
+#[test]
+fn test_boundary_slice_is_minimal() {
+    let neighbor = make_chunk(VoxelTypeId(1));
+    let slice = extract_boundary_slice(&neighbor, FaceDirection::PosX, 32);
+    assert_eq!(slice.len(), 32 * 32);
+}
+
+#[test]
+fn test_all_air_returns_air() {
+    let n = ChunkNeighborhood::all_air();
+    assert_eq!(n.get(-1, 0, 0), VoxelTypeId(0));
+    assert_eq!(n.get(32, 0, 0), VoxelTypeId(0));
+    assert_eq!(n.get(0, -1, 0), VoxelTypeId(0));
+}
+
+#[test]
+fn test_legacy_with_neg_x() {
+    let mut chunk = ChunkData::new_air();
+    chunk.set(31, 10, 10, VoxelTypeId(5));
+    let n = ChunkNeighborhood::with_neg_x(chunk);
+    assert_eq!(n.get(-1, 10, 10), VoxelTypeId(5));
+    assert_eq!(n.get(-1, 0, 0), VoxelTypeId(0));
+}
+
+#[test]
+fn test_legacy_set_pos_y_neighbor() {
+    let mut n = ChunkNeighborhood::all_air();
+    let mut chunk = ChunkData::new_air();
+    chunk.set(5, 0, 5, VoxelTypeId(9));
+    n.set(2, chunk); // +Y direction index
+    assert_eq!(n.get(5, 32, 5), VoxelTypeId(9));
+}
+
+#[test]
+fn test_edge_neighbor_lookup() {
+    let center = ChunkData::new_air();
+    let mut neighborhood = ChunkNeighborhood::from_center_only(center);
+
+    // Create an edge neighbor chunk with a specific voxel
+    let mut edge_chunk = ChunkData::new_air();
+    // PosXPosY edge: opposite is NegXNegY, which extracts at (0, 0, i)
+    edge_chunk.set(0, 0, 15, VoxelTypeId(7));
+    neighborhood.set_edge_neighbor(EdgeDirection::PosXPosY, &edge_chunk);
+
+    assert_eq!(neighborhood.get(32, 32, 15), VoxelTypeId(7));
+    assert_eq!(neighborhood.get(32, 32, 0), VoxelTypeId(0));
+}
+
+#[test]
+fn test_corner_neighbor_from_chunk() {
+    let center = ChunkData::new_air();
+    let mut neighborhood = ChunkNeighborhood::from_center_only(center);
+
+    let mut corner_chunk = ChunkData::new_air();
+    // PosXPosYPosZ corner: opposite is NegXNegYNegZ, extracts (0,0,0)
+    corner_chunk.set(0, 0, 0, VoxelTypeId(42));
+    neighborhood.set_corner_neighbor_from_chunk(CornerDirection::PosXPosYPosZ, &corner_chunk);
+
+    assert_eq!(neighborhood.get(32, 32, 32), VoxelTypeId(42));
+}
+
+#[test]
+fn test_boundary_edge_is_minimal() {
+    let chunk = make_chunk(VoxelTypeId(1));
+    let edge = extract_boundary_edge(&chunk, EdgeDirection::PosXPosY, 32);
+    assert_eq!(edge.len(), 32);
+}
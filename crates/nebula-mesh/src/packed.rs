@@ -3,6 +3,9 @@
 //! [`ChunkVertex`] is a 12-byte packed vertex format that reduces GPU memory
 //! usage by 3x compared to the unpacked [`super::MeshVertex`] format.
 
+use nebula_voxel::VoxelTypeRegistry;
+
+use crate::chunk_mesh::ChunkMesh;
 use crate::face_direction::FaceDirection;
 
 /// A single vertex in a chunk mesh, packed to 12 bytes for efficient GPU upload.
@@ -11,10 +14,10 @@ use crate::face_direction::FaceDirection;
 ///   - `[0..3]`  position `[u8; 3]` — XYZ in chunk-local coords (0..=32)
 ///   - `[3]`     normal `u8` — face direction index (0..=5)
 ///   - `[4]`     ao `u8` — ambient occlusion level (0..=3)
-///   - `[5]`     padding
+///   - `[5]`     blend_weight `u8` — weight of the secondary material (0..=255 → 0.0..=1.0)
 ///   - `[6..8]`  material_id `u16` — voxel type / material index (little-endian)
 ///   - `[8..10]` uv `[u8; 2]` — texture coordinates (0..=32)
-///   - `[10..12]` padding
+///   - `[10..12]` secondary_material `u16` — material blended in at biome boundaries
 #[repr(C, packed)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct ChunkVertex {
@@ -24,14 +27,15 @@ pub struct ChunkVertex {
     pub normal: u8,
     /// Ambient occlusion level (0..=3).
     pub ao: u8,
-    /// Reserved, set to 0.
-    pub _pad0: u8,
+    /// Weight of [`secondary_material`](Self::secondary_material), where 255
+    /// is fully secondary. 0 outside biome boundaries.
+    pub blend_weight: u8,
     /// Voxel type / material index.
     pub material_id: u16,
     /// Texture coordinates (each component 0..=32, tiles across merged quads).
     pub uv: [u8; 2],
-    /// Reserved, aligns struct to 12 bytes.
-    pub _pad1: u16,
+    /// Material blended in at biome boundaries; 0 when unblended.
+    pub secondary_material: u16,
 }
 
 static_assertions::assert_eq_size!(ChunkVertex, [u8; 12]);
@@ -45,13 +49,26 @@ impl ChunkVertex {
             position: pos,
             normal: direction as u8,
             ao,
-            _pad0: 0,
+            blend_weight: 0,
             material_id: material,
             uv,
-            _pad1: 0,
+            secondary_material: 0,
         }
     }
 
+    /// Blend `secondary` into this vertex with weight `weight` (clamped to
+    /// `[0, 1]` and quantized to 8 bits).
+    pub fn with_blend(mut self, secondary: u16, weight: f32) -> Self {
+        self.secondary_material = secondary;
+        self.blend_weight = (weight.clamp(0.0, 1.0) * 255.0).round() as u8;
+        self
+    }
+
+    /// The secondary material's weight as `0.0..=1.0`.
+    pub fn blend_weight_f32(&self) -> f32 {
+        f32::from(self.blend_weight) / 255.0
+    }
+
     /// Decode the face direction from the packed normal byte.
     ///
     /// Returns `None` if the stored value is out of range.
@@ -88,6 +105,35 @@ impl PackedChunkMesh {
         }
     }
 
+    /// Pack a chunk-local [`ChunkMesh`] (e.g. from [`crate::greedy_mesh`]),
+    /// mapping each voxel type to its registered material index.
+    ///
+    /// Positions and UVs must lie in `0..=32`, i.e. the mesh must not have
+    /// been rescaled. Indices are kept as-is, so AO diagonal flips survive.
+    pub fn from_chunk_mesh(mesh: &ChunkMesh, registry: &VoxelTypeRegistry) -> Self {
+        let vertices = mesh
+            .vertices
+            .iter()
+            .map(|v| {
+                let direction = FaceDirection::ALL
+                    .into_iter()
+                    .find(|d| d.normal() == v.normal)
+                    .unwrap_or(FaceDirection::PosY);
+                ChunkVertex::new(
+                    v.position.map(|c| c as u8),
+                    direction,
+                    v.ao,
+                    registry.get(v.voxel_type).material_index,
+                    v.uv.map(|c| c as u8),
+                )
+            })
+            .collect();
+        Self {
+            vertices,
+            indices: mesh.indices.clone(),
+        }
+    }
+
     /// Add a quad (4 vertices, 6 indices).
     ///
     /// If `flip` is true, the triangulation diagonal is flipped for
@@ -234,6 +280,19 @@ mod tests {
         assert_eq!(bytes.len(), 12);
     }
 
+    #[test]
+    fn test_blend_packs_into_spare_bytes() {
+        let plain = ChunkVertex::new([1, 2, 3], FaceDirection::PosY, 1, 7, [4, 5]);
+        let blended = plain.with_blend(0x0302, 0.5);
+        let bytes: &[u8] = bytemuck::bytes_of(&blended);
+        assert_eq!(bytes[5], 128);
+        assert_eq!(&bytes[10..12], &[0x02, 0x03]);
+        assert_eq!(bytemuck::bytes_of(&plain)[..5], bytes[..5]);
+        assert_eq!(bytemuck::bytes_of(&plain)[6..10], bytes[6..10]);
+        assert_eq!(plain.with_blend(1, 2.0).blend_weight_f32(), 1.0);
+        assert_eq!(plain.with_blend(1, -1.0).blend_weight_f32(), 0.0);
+    }
+
     #[test]
     fn test_empty_mesh_stats() {
         let mesh = PackedChunkMesh::new();
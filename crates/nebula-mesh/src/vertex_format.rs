@@ -8,8 +8,8 @@
 //! | Location | Offset | Format   | Fields                          |
 //! |----------|--------|----------|---------------------------------|
 //! | 0        | 0      | Uint8x4  | position xyz + normal index     |
-//! | 1        | 4      | Uint8x4  | ao + pad + material_id (2 bytes)|
-//! | 2        | 8      | Uint8x4  | uv xy + pad                    |
+//! | 1        | 4      | Uint8x4  | ao + blend weight + material_id (2 bytes) |
+//! | 2        | 8      | Uint8x4  | uv xy + secondary material (2 bytes)      |
 
 use std::mem;
 
@@ -27,13 +27,13 @@ pub const CHUNK_VERTEX_ATTRIBUTES: [VertexAttribute; 3] = [
         offset: 0,
         shader_location: 0,
     },
-    // Attribute 1: ao + padding + material_id, packed as 4× u8
+    // Attribute 1: ao + blend weight + material_id, packed as 4× u8
     VertexAttribute {
         format: VertexFormat::Uint8x4,
         offset: 4,
         shader_location: 1,
     },
-    // Attribute 2: uv + padding, packed as 4× u8
+    // Attribute 2: uv + secondary material, packed as 4× u8
     VertexAttribute {
         format: VertexFormat::Uint8x4,
         offset: 8,
@@ -109,7 +109,7 @@ mod tests {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 1,
-            light_emission: 0,
+            light_emission: [0; 3],
         }
     }
 
@@ -119,7 +119,7 @@ mod tests {
             solid: true,
             transparency: Transparency::SemiTransparent,
             material_index: 2,
-            light_emission: 0,
+            light_emission: [0; 3],
         }
     }
 
@@ -129,7 +129,7 @@ mod tests {
             solid: false,
             transparency: Transparency::SemiTransparent,
             material_index: 3,
-            light_emission: 0,
+            light_emission: [0; 3],
         }
     }
 
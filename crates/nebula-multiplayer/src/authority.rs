@@ -2,11 +2,18 @@
 //!
 //! The server owns the canonical world state. Clients submit [`ClientIntent`]
 //! messages describing *what they want to do*, and the server validates and
-//! applies them each tick via [`IntentValidator`] and [`AuthoritativeWorld`].
+//! applies them each tick via
+//! [`IntentValidator`](crate::authority_validation::IntentValidator) and
+//! [`AuthoritativeWorld`].
+//!
+//! Hot-standby failover between servers lives in
+//! [`authority_transfer`](crate::authority_transfer).
 
 use bevy_ecs::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::lag_compensation::{EntityPose, HistoryBuffer, HitRay};
+
 // ---------------------------------------------------------------------------
 // Constants
 // ---------------------------------------------------------------------------
@@ -17,13 +24,6 @@ pub const SERVER_TICK_RATE: u32 = 60;
 /// Duration of a single server tick in seconds.
 pub const TICK_DURATION_SECS: f64 = 1.0 / SERVER_TICK_RATE as f64;
 
-/// Maximum movement distance per tick in millimeters (prevents teleport hacks).
-/// ~10 m/s at 60 Hz ≈ 167 mm/tick.
-const MAX_MOVE_DISTANCE_MM: i128 = 200;
-
-/// Maximum interaction range in millimeters (5 meters).
-const MAX_INTERACT_RANGE_MM: i128 = 5_000;
-
 // ---------------------------------------------------------------------------
 // ClientIntent
 // ---------------------------------------------------------------------------
@@ -71,11 +71,18 @@ pub enum ClientIntent {
     },
 
     /// Interact with an entity (e.g. open a container, talk to NPC).
+    ///
+    /// The server rewinds to `client_tick` and checks that `ray` hits
+    /// `target_entity` as the client saw it.
     Interact {
         /// Player identifier.
         player_id: u64,
         /// Target entity identifier.
         target_entity: u64,
+        /// Server tick the client was displaying when it aimed.
+        client_tick: u64,
+        /// The client's aim ray.
+        ray: HitRay,
     },
 
     /// Rotate the player's view (yaw/pitch in milliradians).
@@ -134,6 +141,28 @@ pub enum IntentValidationError {
     /// Invalid voxel type ID.
     #[error("invalid voxel type {0}")]
     InvalidVoxelType(u16),
+
+    /// Requested rewind is further back than the client's latency allows.
+    #[error("rewind too far: {rewind_ticks} ticks > max {max_ticks}")]
+    RewindExceedsLatency {
+        /// Ticks between the server tick and the requested tick.
+        rewind_ticks: u64,
+        /// Maximum rewind allowed by the measured RTT.
+        max_ticks: u64,
+    },
+
+    /// Requested tick is no longer in the history buffer.
+    #[error("tick {tick} is outside history (oldest {oldest:?})")]
+    RewindOutOfHistory {
+        /// Requested tick.
+        tick: u64,
+        /// Oldest tick still recorded, if any.
+        oldest: Option<u64>,
+    },
+
+    /// The rewound ray did not hit the claimed target.
+    #[error("ray missed target {0}")]
+    TargetMissed(u64),
 }
 
 // ---------------------------------------------------------------------------
@@ -169,14 +198,23 @@ pub struct AuthoritativeWorld {
     world: World,
     /// Monotonically increasing tick counter.
     tick: u64,
+    /// Recent player poses for lag-compensated hit validation.
+    history: HistoryBuffer,
 }
 
 impl AuthoritativeWorld {
-    /// Creates a new empty authoritative world.
+    /// Creates a new empty authoritative world with one second of history.
     pub fn new() -> Self {
+        Self::with_history(HistoryBuffer::default())
+    }
+
+    /// Creates a new empty authoritative world using the given history
+    /// buffer for lag compensation.
+    pub fn with_history(history: HistoryBuffer) -> Self {
         Self {
             world: World::new(),
             tick: 0,
+            history,
         }
     }
 
@@ -185,9 +223,33 @@ impl AuthoritativeWorld {
         self.tick
     }
 
-    /// Advances the tick counter by one.
+    /// Advances the tick counter by one and records every player's pose
+    /// into the history buffer under the new tick.
     pub fn advance_tick(&mut self) {
         self.tick += 1;
+        self.record_history();
+    }
+
+    /// Records every player's pose into the history buffer under the
+    /// current tick.
+    fn record_history(&mut self) {
+        let mut query = self.world.query::<&PlayerState>();
+        let poses = query.iter(&self.world).map(|ps| {
+            let pose = EntityPose {
+                x: ps.x,
+                y: ps.y,
+                z: ps.z,
+                yaw_mrad: ps.yaw_mrad,
+                pitch_mrad: ps.pitch_mrad,
+            };
+            (ps.player_id, pose)
+        });
+        self.history.record(self.tick, poses);
+    }
+
+    /// Returns the pose history used for lag compensation.
+    pub fn history(&self) -> &HistoryBuffer {
+        &self.history
     }
 
     /// Spawns a player entity with the given initial state. Returns the
@@ -196,6 +258,24 @@ impl AuthoritativeWorld {
         self.world.spawn(state).id()
     }
 
+    /// Despawns the player with the given ID. Returns `false` if no such
+    /// player exists.
+    pub fn despawn_player(&mut self, player_id: u64) -> bool {
+        let mut query = self.world.query::<(Entity, &PlayerState)>();
+        let entity = query
+            .iter(&self.world)
+            .find(|(_, ps)| ps.player_id == player_id)
+            .map(|(entity, _)| entity);
+        entity.is_some_and(|entity| self.world.despawn(entity))
+    }
+
+    /// Iterates over the state of every player.
+    pub fn players(&self) -> impl Iterator<Item = &PlayerState> {
+        self.world
+            .iter_entities()
+            .filter_map(|entity| entity.get::<PlayerState>())
+    }
+
     /// Looks up a player's [`PlayerState`] by player ID.
     pub fn find_player(&self, player_id: u64) -> Option<&PlayerState> {
         // SAFETY: query requires &mut World in bevy 0.15 but we only read.
@@ -231,6 +311,19 @@ impl AuthoritativeWorld {
         &mut self.world
     }
 
+    /// Replaces every player with `players` and jumps to `tick`, recording
+    /// the new poses into the history buffer under that tick.
+    pub(crate) fn restore_players(&mut self, tick: u64, players: Vec<PlayerState>) {
+        let mut query = self.world.query_filtered::<Entity, With<PlayerState>>();
+        let stale: Vec<Entity> = query.iter(&self.world).collect();
+        for entity in stale {
+            self.world.despawn(entity);
+        }
+        self.world.spawn_batch(players);
+        self.tick = tick;
+        self.record_history();
+    }
+
     /// Returns the number of player entities.
     pub fn player_count(&self) -> usize {
         let world_ptr = &self.world as *const World as *mut World;
@@ -247,158 +340,6 @@ impl Default for AuthoritativeWorld {
     }
 }
 
-// ---------------------------------------------------------------------------
-// IntentValidator
-// ---------------------------------------------------------------------------
-
-/// Validates [`ClientIntent`] messages against the [`AuthoritativeWorld`].
-///
-/// Each validation method checks constraints (speed limits, range, etc.)
-/// and returns `Ok(())` if the intent is legal.
-pub struct IntentValidator;
-
-impl IntentValidator {
-    /// Validates a single intent. Returns `Ok(())` if it should be applied.
-    pub fn validate(
-        intent: &ClientIntent,
-        world: &AuthoritativeWorld,
-    ) -> Result<(), IntentValidationError> {
-        match intent {
-            ClientIntent::Move {
-                player_id,
-                dx,
-                dy,
-                dz,
-            } => {
-                // Player must exist.
-                if world.find_player(*player_id).is_none() {
-                    return Err(IntentValidationError::UnknownPlayer(*player_id));
-                }
-                // Speed check: Euclidean distance of delta.
-                let dist_sq = (*dx as i128).pow(2) + (*dy as i128).pow(2) + (*dz as i128).pow(2);
-                let max_sq = MAX_MOVE_DISTANCE_MM.pow(2);
-                if dist_sq > max_sq {
-                    let dist = (dist_sq as f64).sqrt() as i128;
-                    return Err(IntentValidationError::MoveTooFast {
-                        distance: dist,
-                        max: MAX_MOVE_DISTANCE_MM,
-                    });
-                }
-                Ok(())
-            }
-
-            ClientIntent::PlaceVoxel {
-                player_id,
-                voxel_type,
-                x,
-                y,
-                z,
-            } => {
-                let ps = world
-                    .find_player(*player_id)
-                    .ok_or(IntentValidationError::UnknownPlayer(*player_id))?;
-                // Voxel type 0 (air) is invalid for placement.
-                if *voxel_type == 0 {
-                    return Err(IntentValidationError::InvalidVoxelType(0));
-                }
-                // Range check.
-                Self::check_range(ps, *x, *y, *z)?;
-                Ok(())
-            }
-
-            ClientIntent::BreakVoxel { player_id, x, y, z } => {
-                let ps = world
-                    .find_player(*player_id)
-                    .ok_or(IntentValidationError::UnknownPlayer(*player_id))?;
-                Self::check_range(ps, *x, *y, *z)?;
-                Ok(())
-            }
-
-            ClientIntent::Interact {
-                player_id,
-                target_entity: _,
-            } => {
-                // Just verify the player exists; target entity checks would
-                // require more world state.
-                if world.find_player(*player_id).is_none() {
-                    return Err(IntentValidationError::UnknownPlayer(*player_id));
-                }
-                Ok(())
-            }
-
-            ClientIntent::Rotate { player_id, .. } => {
-                if world.find_player(*player_id).is_none() {
-                    return Err(IntentValidationError::UnknownPlayer(*player_id));
-                }
-                Ok(())
-            }
-        }
-    }
-
-    /// Validates and applies the intent to the world. Returns `Ok(())` on
-    /// success.
-    pub fn validate_and_apply(
-        intent: &ClientIntent,
-        world: &mut AuthoritativeWorld,
-    ) -> Result<(), IntentValidationError> {
-        Self::validate(intent, world)?;
-        Self::apply(intent, world);
-        Ok(())
-    }
-
-    /// Applies a (pre-validated) intent to the authoritative world.
-    fn apply(intent: &ClientIntent, world: &mut AuthoritativeWorld) {
-        match intent {
-            ClientIntent::Move {
-                player_id,
-                dx,
-                dy,
-                dz,
-            } => {
-                if let Some(ps) = world.find_player_mut(*player_id) {
-                    ps.x = ps.x.saturating_add(*dx);
-                    ps.y = ps.y.saturating_add(*dy);
-                    ps.z = ps.z.saturating_add(*dz);
-                }
-            }
-            ClientIntent::Rotate {
-                player_id,
-                yaw_mrad,
-                pitch_mrad,
-            } => {
-                if let Some(ps) = world.find_player_mut(*player_id) {
-                    ps.yaw_mrad = ps.yaw_mrad.wrapping_add(*yaw_mrad);
-                    ps.pitch_mrad = ps.pitch_mrad.wrapping_add(*pitch_mrad);
-                }
-            }
-            // PlaceVoxel / BreakVoxel / Interact would modify voxel or
-            // entity state; placeholder for now (logged in demo).
-            ClientIntent::PlaceVoxel { .. }
-            | ClientIntent::BreakVoxel { .. }
-            | ClientIntent::Interact { .. } => {
-                tracing::debug!("Applied intent: {intent:?}");
-            }
-        }
-    }
-
-    /// Checks that a target position is within interaction range of the player.
-    fn check_range(ps: &PlayerState, x: i64, y: i64, z: i64) -> Result<(), IntentValidationError> {
-        let dx = (x as i128) - (ps.x as i128);
-        let dy = (y as i128) - (ps.y as i128);
-        let dz = (z as i128) - (ps.z as i128);
-        let dist_sq = dx.pow(2) + dy.pow(2) + dz.pow(2);
-        let max_sq = MAX_INTERACT_RANGE_MM.pow(2);
-        if dist_sq > max_sq {
-            let dist = (dist_sq as f64).sqrt() as i128;
-            return Err(IntentValidationError::OutOfRange {
-                distance: dist,
-                max: MAX_INTERACT_RANGE_MM,
-            });
-        }
-        Ok(())
-    }
-}
-
 // ---------------------------------------------------------------------------
 // ServerTickSchedule
 // ---------------------------------------------------------------------------
@@ -461,195 +402,6 @@ impl Default for ServerTickSchedule {
     }
 }
 
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
-
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_client_intent_serialization_roundtrip() {
-        let intents = vec![
-            ClientIntent::Move {
-                player_id: 1,
-                dx: 100,
-                dy: -50,
-                dz: 0,
-            },
-            ClientIntent::PlaceVoxel {
-                player_id: 2,
-                voxel_type: 5,
-                x: 1000,
-                y: 2000,
-                z: 3000,
-            },
-            ClientIntent::BreakVoxel {
-                player_id: 3,
-                x: -500,
-                y: 100,
-                z: 200,
-            },
-            ClientIntent::Interact {
-                player_id: 4,
-                target_entity: 99,
-            },
-            ClientIntent::Rotate {
-                player_id: 5,
-                yaw_mrad: 314,
-                pitch_mrad: -157,
-            },
-        ];
-
-        for intent in &intents {
-            // postcard round-trip
-            let bytes = postcard::to_allocvec(intent).expect("serialize");
-            let decoded: ClientIntent = postcard::from_bytes(&bytes).expect("deserialize");
-            assert_eq!(*intent, decoded);
-
-            // serde_json round-trip (proves Serialize+Deserialize work generically)
-            let json = serde_json::to_string(intent).expect("json serialize");
-            let from_json: ClientIntent = serde_json::from_str(&json).expect("json deserialize");
-            assert_eq!(*intent, from_json);
-        }
-    }
-
-    #[test]
-    fn test_authoritative_world_spawn_and_find() {
-        let mut world = AuthoritativeWorld::new();
-        assert_eq!(world.tick(), 0);
-        assert_eq!(world.player_count(), 0);
-
-        let entity = world.spawn_player(PlayerState {
-            player_id: 42,
-            x: 1000,
-            y: 2000,
-            z: 3000,
-            yaw_mrad: 0,
-            pitch_mrad: 0,
-        });
-        assert_eq!(world.player_count(), 1);
-
-        let ps = world.find_player(42).expect("player 42 should exist");
-        assert_eq!(ps.x, 1000);
-        assert_eq!(ps.y, 2000);
-        assert_eq!(ps.z, 3000);
-
-        // Unknown player returns None.
-        assert!(world.find_player(999).is_none());
-
-        // Tick advances.
-        world.advance_tick();
-        assert_eq!(world.tick(), 1);
-
-        // Entity handle is valid.
-        assert!(world.world().get_entity(entity).is_ok());
-    }
-
-    #[test]
-    fn test_intent_validator_rejects_speed_hack() {
-        let mut world = AuthoritativeWorld::new();
-        world.spawn_player(PlayerState {
-            player_id: 1,
-            x: 0,
-            y: 0,
-            z: 0,
-            yaw_mrad: 0,
-            pitch_mrad: 0,
-        });
-
-        // Legal move (within MAX_MOVE_DISTANCE_MM).
-        let legal_move = ClientIntent::Move {
-            player_id: 1,
-            dx: 100,
-            dy: 0,
-            dz: 0,
-        };
-        assert!(IntentValidator::validate(&legal_move, &world).is_ok());
-
-        // Illegal move (way too fast).
-        let speed_hack = ClientIntent::Move {
-            player_id: 1,
-            dx: 10_000,
-            dy: 10_000,
-            dz: 10_000,
-        };
-        let err = IntentValidator::validate(&speed_hack, &world).unwrap_err();
-        assert!(matches!(err, IntentValidationError::MoveTooFast { .. }));
-    }
-
-    #[test]
-    fn test_intent_validator_rejects_out_of_range() {
-        let mut world = AuthoritativeWorld::new();
-        world.spawn_player(PlayerState {
-            player_id: 1,
-            x: 0,
-            y: 0,
-            z: 0,
-            yaw_mrad: 0,
-            pitch_mrad: 0,
-        });
-
-        // Place voxel within range.
-        let near_place = ClientIntent::PlaceVoxel {
-            player_id: 1,
-            voxel_type: 1,
-            x: 1000,
-            y: 0,
-            z: 0,
-        };
-        assert!(IntentValidator::validate(&near_place, &world).is_ok());
-
-        // Place voxel far away.
-        let far_place = ClientIntent::PlaceVoxel {
-            player_id: 1,
-            voxel_type: 1,
-            x: 100_000,
-            y: 0,
-            z: 0,
-        };
-        let err = IntentValidator::validate(&far_place, &world).unwrap_err();
-        assert!(matches!(err, IntentValidationError::OutOfRange { .. }));
-
-        // Place air (voxel_type 0) is invalid.
-        let air_place = ClientIntent::PlaceVoxel {
-            player_id: 1,
-            voxel_type: 0,
-            x: 0,
-            y: 0,
-            z: 0,
-        };
-        let err = IntentValidator::validate(&air_place, &world).unwrap_err();
-        assert!(matches!(err, IntentValidationError::InvalidVoxelType(0)));
-    }
-
-    #[test]
-    fn test_server_tick_schedule_60hz() {
-        // Fresh schedule: accumulate 60 individual ticks worth of time.
-        let mut schedule = ServerTickSchedule::new();
-        assert_eq!(schedule.total_ticks(), 0);
-
-        // Feed exactly one tick duration 60 times → must yield 60 ticks total.
-        for _ in 0..60 {
-            let t = schedule.accumulate(TICK_DURATION_SECS);
-            assert_eq!(t, 1, "each tick-duration step should yield exactly 1 tick");
-        }
-        assert_eq!(schedule.total_ticks(), 60);
-
-        // Half-tick accumulations: two halves = one tick.
-        let mut schedule2 = ServerTickSchedule::new();
-        let t = schedule2.accumulate(TICK_DURATION_SECS * 0.4);
-        assert_eq!(t, 0, "0.4 of a tick should not fire");
-        let t = schedule2.accumulate(TICK_DURATION_SECS * 0.7);
-        assert_eq!(t, 1, "0.4 + 0.7 = 1.1 ticks should fire once");
-        assert_eq!(schedule2.total_ticks(), 1);
-
-        // Custom tick rate.
-        let mut schedule_30 = ServerTickSchedule::with_tick_rate(30);
-        for _ in 0..30 {
-            schedule_30.accumulate(1.0 / 30.0);
-        }
-        assert_eq!(schedule_30.total_ticks(), 30);
-    }
-}
+#[path = "authority_tests.rs"]
+mod tests;
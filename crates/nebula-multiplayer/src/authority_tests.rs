@@ -0,0 +1,123 @@
+//! Unit tests for the authoritative world, client intents and tick
+//! scheduling.
+
+use super::*;
+
+#[test]
+fn test_client_intent_serialization_roundtrip() {
+    let intents = vec![
+        ClientIntent::Move {
+            player_id: 1,
+            dx: 100,
+            dy: -50,
+            dz: 0,
+        },
+        ClientIntent::PlaceVoxel {
+            player_id: 2,
+            voxel_type: 5,
+            x: 1000,
+            y: 2000,
+            z: 3000,
+        },
+        ClientIntent::BreakVoxel {
+            player_id: 3,
+            x: -500,
+            y: 100,
+            z: 200,
+        },
+        ClientIntent::Interact {
+            player_id: 4,
+            target_entity: 99,
+            client_tick: 1234,
+            ray: HitRay {
+                origin: [10, 1_600, -20],
+                direction: [0.0, 0.0, 1.0],
+                max_distance_mm: 5_000,
+            },
+        },
+        ClientIntent::Rotate {
+            player_id: 5,
+            yaw_mrad: 314,
+            pitch_mrad: -157,
+        },
+    ];
+
+    for intent in &intents {
+        // postcard round-trip
+        let bytes = postcard::to_allocvec(intent).expect("serialize");
+        let decoded: ClientIntent = postcard::from_bytes(&bytes).expect("deserialize");
+        assert_eq!(*intent, decoded);
+
+        // serde_json round-trip (proves Serialize+Deserialize work generically)
+        let json = serde_json::to_string(intent).expect("json serialize");
+        let from_json: ClientIntent = serde_json::from_str(&json).expect("json deserialize");
+        assert_eq!(*intent, from_json);
+    }
+}
+
+#[test]
+fn test_authoritative_world_spawn_and_find() {
+    let mut world = AuthoritativeWorld::new();
+    assert_eq!(world.tick(), 0);
+    assert_eq!(world.player_count(), 0);
+
+    let entity = world.spawn_player(PlayerState {
+        player_id: 42,
+        x: 1000,
+        y: 2000,
+        z: 3000,
+        yaw_mrad: 0,
+        pitch_mrad: 0,
+    });
+    assert_eq!(world.player_count(), 1);
+
+    let ps = world.find_player(42).expect("player 42 should exist");
+    assert_eq!(ps.x, 1000);
+    assert_eq!(ps.y, 2000);
+    assert_eq!(ps.z, 3000);
+
+    // Unknown player returns None.
+    assert!(world.find_player(999).is_none());
+
+    // Tick advances.
+    world.advance_tick();
+    assert_eq!(world.tick(), 1);
+
+    // Entity handle is valid.
+    assert!(world.world().get_entity(entity).is_ok());
+
+    assert_eq!(world.players().count(), 1);
+    assert!(world.despawn_player(42));
+    assert!(!world.despawn_player(42));
+    assert_eq!(world.player_count(), 0);
+    assert!(world.world().get_entity(entity).is_err());
+}
+
+#[test]
+fn test_server_tick_schedule_60hz() {
+    // Fresh schedule: accumulate 60 individual ticks worth of time.
+    let mut schedule = ServerTickSchedule::new();
+    assert_eq!(schedule.total_ticks(), 0);
+
+    // Feed exactly one tick duration 60 times → must yield 60 ticks total.
+    for _ in 0..60 {
+        let t = schedule.accumulate(TICK_DURATION_SECS);
+        assert_eq!(t, 1, "each tick-duration step should yield exactly 1 tick");
+    }
+    assert_eq!(schedule.total_ticks(), 60);
+
+    // Half-tick accumulations: two halves = one tick.
+    let mut schedule2 = ServerTickSchedule::new();
+    let t = schedule2.accumulate(TICK_DURATION_SECS * 0.4);
+    assert_eq!(t, 0, "0.4 of a tick should not fire");
+    let t = schedule2.accumulate(TICK_DURATION_SECS * 0.7);
+    assert_eq!(t, 1, "0.4 + 0.7 = 1.1 ticks should fire once");
+    assert_eq!(schedule2.total_ticks(), 1);
+
+    // Custom tick rate.
+    let mut schedule_30 = ServerTickSchedule::with_tick_rate(30);
+    for _ in 0..30 {
+        schedule_30.accumulate(1.0 / 30.0);
+    }
+    assert_eq!(schedule_30.total_ticks(), 30);
+}
@@ -0,0 +1,204 @@
+//! Authority transfer for hot-standby failover.
+//!
+//! The primary periodically sends an [`AuthorityTransferPacket`] from
+//! [`AuthoritativeWorld::export_state`] to a backup, which applies it with
+//! [`AuthoritativeWorld::import_state`] and can take over at the exported
+//! tick without disconnecting clients.
+
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+
+use crate::authority::{AuthoritativeWorld, PlayerState};
+
+/// Reasons an [`AuthorityTransferPacket`] may be rejected by the backup.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AuthorityTransferError {
+    /// The MAC does not match the packet contents under the shared secret.
+    #[error("authority transfer MAC verification failed")]
+    InvalidMac,
+
+    /// The packet is older than the state the backup already holds.
+    #[error("stale authority transfer: tick {received_tick} < current {current_tick}")]
+    StaleState {
+        /// Tick carried by the packet.
+        received_tick: u64,
+        /// Tick of the importing world.
+        current_tick: u64,
+    },
+}
+
+/// Snapshot of the authoritative player state handed from a primary server
+/// to a hot-standby backup.
+///
+/// The MAC is HMAC-SHA256 over the tick and every player's state, keyed by
+/// a secret shared between the two servers, so a backup never assumes
+/// authority over state it did not receive from the primary.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuthorityTransferPacket {
+    /// Server tick at which the state was exported.
+    pub tick: u64,
+    /// State of every player at `tick`.
+    pub players: Vec<PlayerState>,
+    /// HMAC-SHA256 of `tick` and `players`.
+    pub mac: Vec<u8>,
+}
+
+impl AuthorityTransferPacket {
+    /// Bytes covered by the MAC: the tick, then each player's fields, all
+    /// little-endian.
+    fn signed_bytes(tick: u64, players: &[PlayerState]) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + players.len() * 40);
+        bytes.extend_from_slice(&tick.to_le_bytes());
+        for ps in players {
+            bytes.extend_from_slice(&ps.player_id.to_le_bytes());
+            bytes.extend_from_slice(&ps.x.to_le_bytes());
+            bytes.extend_from_slice(&ps.y.to_le_bytes());
+            bytes.extend_from_slice(&ps.z.to_le_bytes());
+            bytes.extend_from_slice(&ps.yaw_mrad.to_le_bytes());
+            bytes.extend_from_slice(&ps.pitch_mrad.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+impl AuthoritativeWorld {
+    /// Exports every player's state and the current tick, authenticated
+    /// with `secret`.
+    ///
+    /// The secret is a parameter rather than world state so the primary
+    /// and backup can be configured with it the same way.
+    pub fn export_state(&self, secret: &[u8]) -> AuthorityTransferPacket {
+        let mut players: Vec<PlayerState> = self.players().cloned().collect();
+        players.sort_by_key(|ps| ps.player_id);
+        let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+        let mac = hmac::sign(
+            &key,
+            &AuthorityTransferPacket::signed_bytes(self.tick(), &players),
+        );
+        AuthorityTransferPacket {
+            tick: self.tick(),
+            players,
+            mac: mac.as_ref().to_vec(),
+        }
+    }
+
+    /// Verifies `packet` against `secret` and replaces this world's players
+    /// and tick with its contents.
+    ///
+    /// The imported poses are recorded into the history buffer under the
+    /// packet's tick, so lag-compensated hits keep working after failover.
+    /// On error the world is left unchanged.
+    pub fn import_state(
+        &mut self,
+        packet: AuthorityTransferPacket,
+        secret: &[u8],
+    ) -> Result<(), AuthorityTransferError> {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, secret);
+        let signed = AuthorityTransferPacket::signed_bytes(packet.tick, &packet.players);
+        hmac::verify(&key, &signed, &packet.mac).map_err(|_| AuthorityTransferError::InvalidMac)?;
+        if packet.tick < self.tick() {
+            return Err(AuthorityTransferError::StaleState {
+                received_tick: packet.tick,
+                current_tick: self.tick(),
+            });
+        }
+
+        self.restore_players(packet.tick, packet.players);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer_world() -> AuthoritativeWorld {
+        let mut world = AuthoritativeWorld::new();
+        for (id, x) in [(1, 0), (2, -4_500), (3, 12_000)] {
+            world.spawn_player(PlayerState {
+                player_id: id,
+                x,
+                y: 1_600,
+                z: id as i64 * 250,
+                yaw_mrad: 100 * id as i32,
+                pitch_mrad: -20,
+            });
+        }
+        for _ in 0..42 {
+            world.advance_tick();
+        }
+        world
+    }
+
+    #[test]
+    fn test_exported_state_restores_on_fresh_world() {
+        let primary = transfer_world();
+        let packet = primary.export_state(b"failover-secret");
+
+        // The packet survives the wire.
+        let bytes = postcard::to_allocvec(&packet).expect("serialize");
+        let packet: AuthorityTransferPacket = postcard::from_bytes(&bytes).expect("deserialize");
+
+        let mut backup = AuthoritativeWorld::new();
+        backup
+            .import_state(packet, b"failover-secret")
+            .expect("import");
+        assert_eq!(backup.player_count(), primary.player_count());
+        assert_eq!(backup.tick(), primary.tick());
+        for ps in primary.players() {
+            assert_eq!(backup.find_player(ps.player_id), Some(ps));
+        }
+        assert_eq!(backup.history().newest_tick(), Some(42));
+    }
+
+    #[test]
+    fn test_import_replaces_existing_players() {
+        let packet = transfer_world().export_state(b"k");
+        let mut backup = AuthoritativeWorld::new();
+        backup.spawn_player(PlayerState {
+            player_id: 99,
+            x: 0,
+            y: 0,
+            z: 0,
+            yaw_mrad: 0,
+            pitch_mrad: 0,
+        });
+        backup.import_state(packet, b"k").expect("import");
+        assert_eq!(backup.player_count(), 3);
+        assert!(backup.find_player(99).is_none());
+    }
+
+    #[test]
+    fn test_modified_packet_fails_mac() {
+        let primary = transfer_world();
+        let mut packet = primary.export_state(b"failover-secret");
+        packet.players[1].x += 1;
+
+        let mut backup = AuthoritativeWorld::new();
+        assert_eq!(
+            backup.import_state(packet, b"failover-secret"),
+            Err(AuthorityTransferError::InvalidMac)
+        );
+        assert_eq!(backup.player_count(), 0);
+
+        let packet = primary.export_state(b"failover-secret");
+        assert_eq!(
+            backup.import_state(packet, b"wrong-secret"),
+            Err(AuthorityTransferError::InvalidMac)
+        );
+    }
+
+    #[test]
+    fn test_older_packet_is_stale() {
+        let packet = transfer_world().export_state(b"k");
+        let mut backup = transfer_world();
+        backup.advance_tick();
+        assert_eq!(
+            backup.import_state(packet, b"k"),
+            Err(AuthorityTransferError::StaleState {
+                received_tick: 42,
+                current_tick: 43,
+            })
+        );
+    }
+}
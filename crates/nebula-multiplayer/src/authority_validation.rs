@@ -0,0 +1,219 @@
+//! Validation and application of client intents against the authoritative
+//! world.
+//!
+//! [`IntentValidator`] checks each [`ClientIntent`] (speed limits, reach,
+//! lag-compensated hits) before the server applies it to the
+//! [`AuthoritativeWorld`].
+
+use crate::authority::{AuthoritativeWorld, ClientIntent, IntentValidationError, PlayerState};
+use crate::lag_compensation::HitRay;
+
+/// Maximum movement distance per tick in millimeters (prevents teleport hacks).
+/// ~10 m/s at 60 Hz ≈ 167 mm/tick.
+const MAX_MOVE_DISTANCE_MM: i128 = 200;
+
+/// Maximum interaction range in millimeters (5 meters).
+const MAX_INTERACT_RANGE_MM: i128 = 5_000;
+
+/// Validates [`ClientIntent`] messages against the [`AuthoritativeWorld`].
+///
+/// Each validation method checks constraints (speed limits, range, etc.)
+/// and returns `Ok(())` if the intent is legal.
+pub struct IntentValidator;
+
+impl IntentValidator {
+    /// Validates a single intent. Returns `Ok(())` if it should be applied.
+    ///
+    /// No rewind is granted, so [`ClientIntent::Interact`] only succeeds
+    /// against the current tick. Use
+    /// [`IntentValidator::validate_with_rewind`] for lag compensation.
+    pub fn validate(
+        intent: &ClientIntent,
+        world: &AuthoritativeWorld,
+    ) -> Result<(), IntentValidationError> {
+        Self::validate_with_rewind(intent, world, 0)
+    }
+
+    /// Validates a single intent, allowing [`ClientIntent::Interact`] to
+    /// rewind up to `max_rewind_ticks`, e.g. the client's
+    /// [`ClockSync::max_rewind_ticks`](crate::clock::ClockSync::max_rewind_ticks).
+    pub fn validate_with_rewind(
+        intent: &ClientIntent,
+        world: &AuthoritativeWorld,
+        max_rewind_ticks: u64,
+    ) -> Result<(), IntentValidationError> {
+        match intent {
+            ClientIntent::Move {
+                player_id,
+                dx,
+                dy,
+                dz,
+            } => {
+                // Player must exist.
+                if world.find_player(*player_id).is_none() {
+                    return Err(IntentValidationError::UnknownPlayer(*player_id));
+                }
+                // Speed check: Euclidean distance of delta.
+                let dist_sq = (*dx as i128).pow(2) + (*dy as i128).pow(2) + (*dz as i128).pow(2);
+                let max_sq = MAX_MOVE_DISTANCE_MM.pow(2);
+                if dist_sq > max_sq {
+                    let dist = (dist_sq as f64).sqrt() as i128;
+                    return Err(IntentValidationError::MoveTooFast {
+                        distance: dist,
+                        max: MAX_MOVE_DISTANCE_MM,
+                    });
+                }
+                Ok(())
+            }
+
+            ClientIntent::PlaceVoxel {
+                player_id,
+                voxel_type,
+                x,
+                y,
+                z,
+            } => {
+                let ps = world
+                    .find_player(*player_id)
+                    .ok_or(IntentValidationError::UnknownPlayer(*player_id))?;
+                // Voxel type 0 (air) is invalid for placement.
+                if *voxel_type == 0 {
+                    return Err(IntentValidationError::InvalidVoxelType(0));
+                }
+                // Range check.
+                Self::check_range(ps, *x, *y, *z)?;
+                Ok(())
+            }
+
+            ClientIntent::BreakVoxel { player_id, x, y, z } => {
+                let ps = world
+                    .find_player(*player_id)
+                    .ok_or(IntentValidationError::UnknownPlayer(*player_id))?;
+                Self::check_range(ps, *x, *y, *z)?;
+                Ok(())
+            }
+
+            ClientIntent::Interact {
+                player_id,
+                target_entity,
+                client_tick,
+                ray,
+            } => {
+                let ps = world
+                    .find_player(*player_id)
+                    .ok_or(IntentValidationError::UnknownPlayer(*player_id))?;
+                Self::check_range(ps, ray.origin[0], ray.origin[1], ray.origin[2])?;
+                Self::check_rewind_hit(world, *target_entity, *client_tick, ray, max_rewind_ticks)
+            }
+
+            ClientIntent::Rotate { player_id, .. } => {
+                if world.find_player(*player_id).is_none() {
+                    return Err(IntentValidationError::UnknownPlayer(*player_id));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Validates and applies the intent to the world. Returns `Ok(())` on
+    /// success.
+    pub fn validate_and_apply(
+        intent: &ClientIntent,
+        world: &mut AuthoritativeWorld,
+    ) -> Result<(), IntentValidationError> {
+        Self::validate(intent, world)?;
+        Self::apply(intent, world);
+        Ok(())
+    }
+
+    /// Applies a (pre-validated) intent to the authoritative world.
+    fn apply(intent: &ClientIntent, world: &mut AuthoritativeWorld) {
+        match intent {
+            ClientIntent::Move {
+                player_id,
+                dx,
+                dy,
+                dz,
+            } => {
+                if let Some(ps) = world.find_player_mut(*player_id) {
+                    ps.x = ps.x.saturating_add(*dx);
+                    ps.y = ps.y.saturating_add(*dy);
+                    ps.z = ps.z.saturating_add(*dz);
+                }
+            }
+            ClientIntent::Rotate {
+                player_id,
+                yaw_mrad,
+                pitch_mrad,
+            } => {
+                if let Some(ps) = world.find_player_mut(*player_id) {
+                    ps.yaw_mrad = ps.yaw_mrad.wrapping_add(*yaw_mrad);
+                    ps.pitch_mrad = ps.pitch_mrad.wrapping_add(*pitch_mrad);
+                }
+            }
+            // PlaceVoxel / BreakVoxel / Interact would modify voxel or
+            // entity state; placeholder for now (logged in demo).
+            ClientIntent::PlaceVoxel { .. }
+            | ClientIntent::BreakVoxel { .. }
+            | ClientIntent::Interact { .. } => {
+                tracing::debug!("Applied intent: {intent:?}");
+            }
+        }
+    }
+
+    /// Rewinds the history to `client_tick` and checks that `ray` hits
+    /// `target` within interaction range.
+    fn check_rewind_hit(
+        world: &AuthoritativeWorld,
+        target: u64,
+        client_tick: u64,
+        ray: &HitRay,
+        max_rewind_ticks: u64,
+    ) -> Result<(), IntentValidationError> {
+        // A client can't have seen the future; treat it as the present.
+        let tick = client_tick.min(world.tick());
+        let rewind_ticks = world.tick() - tick;
+        if rewind_ticks > max_rewind_ticks {
+            return Err(IntentValidationError::RewindExceedsLatency {
+                rewind_ticks,
+                max_ticks: max_rewind_ticks,
+            });
+        }
+        let history = world.history();
+        if history.oldest_tick().is_none_or(|oldest| tick < oldest) {
+            return Err(IntentValidationError::RewindOutOfHistory {
+                tick,
+                oldest: history.oldest_tick(),
+            });
+        }
+        let ray = HitRay {
+            max_distance_mm: ray.max_distance_mm.min(MAX_INTERACT_RANGE_MM as i64),
+            ..*ray
+        };
+        match history.rewind_and_query(tick as f64, &ray) {
+            Some(hit) if hit.entity == target => Ok(()),
+            _ => Err(IntentValidationError::TargetMissed(target)),
+        }
+    }
+
+    /// Checks that a target position is within interaction range of the player.
+    fn check_range(ps: &PlayerState, x: i64, y: i64, z: i64) -> Result<(), IntentValidationError> {
+        let dx = (x as i128) - (ps.x as i128);
+        let dy = (y as i128) - (ps.y as i128);
+        let dz = (z as i128) - (ps.z as i128);
+        let dist_sq = dx.pow(2) + dy.pow(2) + dz.pow(2);
+        let max_sq = MAX_INTERACT_RANGE_MM.pow(2);
+        if dist_sq > max_sq {
+            let dist = (dist_sq as f64).sqrt() as i128;
+            return Err(IntentValidationError::OutOfRange {
+                distance: dist,
+                max: MAX_INTERACT_RANGE_MM,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "authority_validation_tests.rs"]
+mod tests;
@@ -0,0 +1,80 @@
+//! Unit tests for client intent validation.
+
+use super::*;
+
+#[test]
+fn test_intent_validator_rejects_speed_hack() {
+    let mut world = AuthoritativeWorld::new();
+    world.spawn_player(PlayerState {
+        player_id: 1,
+        x: 0,
+        y: 0,
+        z: 0,
+        yaw_mrad: 0,
+        pitch_mrad: 0,
+    });
+
+    // Legal move (within MAX_MOVE_DISTANCE_MM).
+    let legal_move = ClientIntent::Move {
+        player_id: 1,
+        dx: 100,
+        dy: 0,
+        dz: 0,
+    };
+    assert!(IntentValidator::validate(&legal_move, &world).is_ok());
+
+    // Illegal move (way too fast).
+    let speed_hack = ClientIntent::Move {
+        player_id: 1,
+        dx: 10_000,
+        dy: 10_000,
+        dz: 10_000,
+    };
+    let err = IntentValidator::validate(&speed_hack, &world).unwrap_err();
+    assert!(matches!(err, IntentValidationError::MoveTooFast { .. }));
+}
+
+#[test]
+fn test_intent_validator_rejects_out_of_range() {
+    let mut world = AuthoritativeWorld::new();
+    world.spawn_player(PlayerState {
+        player_id: 1,
+        x: 0,
+        y: 0,
+        z: 0,
+        yaw_mrad: 0,
+        pitch_mrad: 0,
+    });
+
+    // Place voxel within range.
+    let near_place = ClientIntent::PlaceVoxel {
+        player_id: 1,
+        voxel_type: 1,
+        x: 1000,
+        y: 0,
+        z: 0,
+    };
+    assert!(IntentValidator::validate(&near_place, &world).is_ok());
+
+    // Place voxel far away.
+    let far_place = ClientIntent::PlaceVoxel {
+        player_id: 1,
+        voxel_type: 1,
+        x: 100_000,
+        y: 0,
+        z: 0,
+    };
+    let err = IntentValidator::validate(&far_place, &world).unwrap_err();
+    assert!(matches!(err, IntentValidationError::OutOfRange { .. }));
+
+    // Place air (voxel_type 0) is invalid.
+    let air_place = ClientIntent::PlaceVoxel {
+        player_id: 1,
+        voxel_type: 0,
+        x: 0,
+        y: 0,
+        z: 0,
+    };
+    let err = IntentValidator::validate(&air_place, &world).unwrap_err();
+    assert!(matches!(err, IntentValidationError::InvalidVoxelType(0)));
+}
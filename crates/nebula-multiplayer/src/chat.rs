@@ -6,14 +6,19 @@
 //! server-authoritative tick and wall-clock timestamp, then broadcasts the
 //! resulting [`ChatMessage`] to the appropriate recipients via
 //! [`broadcast_chat`].
+//!
+//! Channels are opt-in: a client only receives messages for the
+//! [`ChatChannel`]s it has joined via [`JoinChannel`], and never receives
+//! messages from senders on its [`MuteList`]. Recent messages are retained in
+//! [`ChatHistory`](crate::chat_history::ChatHistory) for late joiners.
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 
 use crate::interest::{InterestPosition, within_interest};
-use crate::replication::NetworkId;
+use crate::network_id::NetworkId;
 
 // ---------------------------------------------------------------------------
 // ChatScope
@@ -31,6 +36,72 @@ pub enum ChatScope {
     },
 }
 
+impl ChatScope {
+    /// The channel this scope is delivered on.
+    pub fn channel(&self) -> ChatChannel {
+        match self {
+            ChatScope::Global => ChatChannel::Global,
+            ChatScope::Proximity { .. } => ChatChannel::Proximity,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Channels
+// ---------------------------------------------------------------------------
+
+/// A chat channel clients subscribe to. Each [`ChatScope`] maps to one
+/// channel regardless of its parameters.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChatChannel {
+    /// Messages sent with [`ChatScope::Global`].
+    Global,
+    /// Messages sent with [`ChatScope::Proximity`].
+    Proximity,
+}
+
+/// Client → server request to start receiving a channel.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JoinChannel {
+    /// Channel to subscribe to.
+    pub channel: ChatChannel,
+}
+
+/// Client → server request to stop receiving a channel.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LeaveChannel {
+    /// Channel to unsubscribe from.
+    pub channel: ChatChannel,
+}
+
+// ---------------------------------------------------------------------------
+// MuteList
+// ---------------------------------------------------------------------------
+
+/// Senders a player has muted. Their messages are not delivered to that
+/// player.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MuteList {
+    muted: HashSet<NetworkId>,
+}
+
+impl MuteList {
+    /// Mutes `sender`. Returns `false` if it was already muted.
+    pub fn mute(&mut self, sender: NetworkId) -> bool {
+        self.muted.insert(sender)
+    }
+
+    /// Unmutes `sender`. Returns `false` if it was not muted.
+    pub fn unmute(&mut self, sender: NetworkId) -> bool {
+        self.muted.remove(&sender)
+    }
+
+    /// Returns `true` if messages from `sender` are suppressed.
+    pub fn is_muted(&self, sender: NetworkId) -> bool {
+        self.muted.contains(&sender)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // ChatMessageIntent (client → server)
 // ---------------------------------------------------------------------------
@@ -80,6 +151,8 @@ pub struct ChatConfig {
     pub rate_limit_window: Duration,
     /// Default proximity radius in meters.
     pub proximity_radius: f64,
+    /// Messages retained per channel for late joiners.
+    pub history_retention: usize,
 }
 
 impl Default for ChatConfig {
@@ -89,6 +162,7 @@ impl Default for ChatConfig {
             rate_limit_messages: 5,
             rate_limit_window: Duration::from_secs(10),
             proximity_radius: 50.0,
+            history_retention: 50,
         }
     }
 }
@@ -138,6 +212,34 @@ impl RateTracker {
     }
 }
 
+/// Per-client rate trackers, one per [`ChatChannel`], so spamming one
+/// channel does not silence the others.
+#[derive(Debug, Clone)]
+pub struct ChannelRateTrackers {
+    trackers: HashMap<ChatChannel, RateTracker>,
+    max_count: u32,
+    window: Duration,
+}
+
+impl ChannelRateTrackers {
+    /// Creates trackers using the limits from `config`.
+    pub fn new(config: &ChatConfig) -> Self {
+        Self {
+            trackers: HashMap::new(),
+            max_count: config.rate_limit_messages,
+            window: config.rate_limit_window,
+        }
+    }
+
+    /// The tracker for `channel`, created on first use.
+    pub fn tracker_mut(&mut self, channel: ChatChannel) -> &mut RateTracker {
+        let (max_count, window) = (self.max_count, self.window);
+        self.trackers
+            .entry(channel)
+            .or_insert_with(|| RateTracker::new(max_count, window))
+    }
+}
+
 // ---------------------------------------------------------------------------
 // ChatRejection
 // ---------------------------------------------------------------------------
@@ -151,6 +253,8 @@ pub enum ChatRejection {
     Empty,
     /// Client exceeded the per-window rate limit.
     RateLimited,
+    /// Client has not joined the channel it is sending to.
+    NotSubscribed,
 }
 
 // ---------------------------------------------------------------------------
@@ -177,6 +281,21 @@ pub fn validate_chat_message(
     Ok(())
 }
 
+/// Validates a [`ChatMessageIntent`] from `sender`, who must have joined the
+/// message's channel. Rate limits apply per channel.
+pub fn validate_channel_message(
+    config: &ChatConfig,
+    sender: &ConnectedClient,
+    rate_trackers: &mut ChannelRateTrackers,
+    message: &ChatMessageIntent,
+) -> Result<(), ChatRejection> {
+    let channel = message.scope.channel();
+    if !sender.is_subscribed(channel) {
+        return Err(ChatRejection::NotSubscribed);
+    }
+    validate_chat_message(config, rate_trackers.tracker_mut(channel), message)
+}
+
 // ---------------------------------------------------------------------------
 // ConnectedClient (lightweight descriptor for broadcast)
 // ---------------------------------------------------------------------------
@@ -188,6 +307,44 @@ pub struct ConnectedClient {
     pub client_id: u64,
     /// Current position of the client's player entity.
     pub position: InterestPosition,
+    /// Channels the client has joined.
+    pub channels: HashSet<ChatChannel>,
+    /// Senders the client has muted.
+    pub mute_list: MuteList,
+}
+
+impl ConnectedClient {
+    /// Creates a client with no channel subscriptions and nobody muted.
+    pub fn new(client_id: u64, position: InterestPosition) -> Self {
+        Self {
+            client_id,
+            position,
+            channels: HashSet::new(),
+            mute_list: MuteList::default(),
+        }
+    }
+
+    /// Applies a [`JoinChannel`] request.
+    pub fn join_channel(&mut self, request: &JoinChannel) {
+        self.channels.insert(request.channel);
+    }
+
+    /// Applies a [`LeaveChannel`] request.
+    pub fn leave_channel(&mut self, request: &LeaveChannel) {
+        self.channels.remove(&request.channel);
+    }
+
+    /// Returns `true` if the client receives messages on `channel`.
+    pub fn is_subscribed(&self, channel: ChatChannel) -> bool {
+        self.channels.contains(&channel)
+    }
+
+    /// Whether `message` should be delivered to this client, ignoring
+    /// distance.
+    pub fn accepts(&self, message: &ChatMessage) -> bool {
+        self.is_subscribed(message.scope.channel())
+            && !self.mute_list.is_muted(message.sender_network_id)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -199,6 +356,9 @@ pub struct ConnectedClient {
 /// - **Global**: all clients receive the message.
 /// - **Proximity**: only clients within `radius` of `sender_pos`.
 ///
+/// Clients that have not joined the message's channel, or that muted the
+/// sender, are skipped.
+///
 /// Returns the list of [`client_id`](ConnectedClient::client_id) values that
 /// should receive the message.
 pub fn broadcast_chat(
@@ -207,170 +367,17 @@ pub fn broadcast_chat(
     clients: &[ConnectedClient],
     _config: &ChatConfig,
 ) -> Vec<u64> {
-    match &message.scope {
-        ChatScope::Global => clients.iter().map(|c| c.client_id).collect(),
-        ChatScope::Proximity { radius } => clients
-            .iter()
-            .filter(|c| within_interest(sender_pos, &c.position, *radius))
-            .map(|c| c.client_id)
-            .collect(),
-    }
+    let in_range = |c: &ConnectedClient| match &message.scope {
+        ChatScope::Global => true,
+        ChatScope::Proximity { radius } => within_interest(sender_pos, &c.position, *radius),
+    };
+    clients
+        .iter()
+        .filter(|c| c.accepts(message) && in_range(c))
+        .map(|c| c.client_id)
+        .collect()
 }
 
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
-
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn default_config() -> ChatConfig {
-        ChatConfig::default()
-    }
-
-    fn make_tracker(config: &ChatConfig) -> RateTracker {
-        RateTracker::new(config.rate_limit_messages, config.rate_limit_window)
-    }
-
-    fn stamp_message(
-        intent: &ChatMessageIntent,
-        sender_id: NetworkId,
-        sender_name: &str,
-        tick: u64,
-        timestamp: u64,
-    ) -> ChatMessage {
-        ChatMessage {
-            sender_network_id: sender_id,
-            sender_name: sender_name.to_string(),
-            scope: intent.scope.clone(),
-            content: intent.content.clone(),
-            server_tick: tick,
-            timestamp,
-        }
-    }
-
-    #[test]
-    fn test_message_sent_and_received_by_all() {
-        let config = default_config();
-        let mut tracker = make_tracker(&config);
-
-        let intent = ChatMessageIntent {
-            scope: ChatScope::Global,
-            content: "Hello".to_string(),
-        };
-        assert!(validate_chat_message(&config, &mut tracker, &intent).is_ok());
-
-        let msg = stamp_message(&intent, NetworkId(1), "Alice", 42, 1_700_000_000_000);
-
-        let clients = vec![
-            ConnectedClient {
-                client_id: 2,
-                position: InterestPosition::new(0.0, 0.0, 0.0),
-            },
-            ConnectedClient {
-                client_id: 3,
-                position: InterestPosition::new(100.0, 0.0, 0.0),
-            },
-            ConnectedClient {
-                client_id: 4,
-                position: InterestPosition::new(999.0, 0.0, 0.0),
-            },
-        ];
-
-        let recipients = broadcast_chat(
-            &msg,
-            &InterestPosition::new(0.0, 0.0, 0.0),
-            &clients,
-            &config,
-        );
-        assert_eq!(recipients, vec![2, 3, 4]);
-        assert_eq!(msg.sender_network_id, NetworkId(1));
-        assert_eq!(msg.content, "Hello");
-        assert!(msg.timestamp > 0);
-    }
-
-    #[test]
-    fn test_proximity_chat_limited_by_distance() {
-        let config = default_config();
-        let mut tracker = make_tracker(&config);
-
-        let intent = ChatMessageIntent {
-            scope: ChatScope::Proximity { radius: 50.0 },
-            content: "Psst".to_string(),
-        };
-        assert!(validate_chat_message(&config, &mut tracker, &intent).is_ok());
-
-        let msg = stamp_message(&intent, NetworkId(1), "Alice", 10, 1_700_000_000_000);
-
-        let sender_pos = InterestPosition::new(0.0, 0.0, 0.0);
-        let clients = vec![
-            ConnectedClient {
-                client_id: 2,
-                position: InterestPosition::new(30.0, 0.0, 0.0),
-            },
-            ConnectedClient {
-                client_id: 3,
-                position: InterestPosition::new(100.0, 0.0, 0.0),
-            },
-        ];
-
-        let recipients = broadcast_chat(&msg, &sender_pos, &clients, &config);
-        assert_eq!(recipients, vec![2]);
-    }
-
-    #[test]
-    fn test_message_length_limit_enforced() {
-        let config = default_config();
-        let mut tracker = make_tracker(&config);
-
-        let intent = ChatMessageIntent {
-            scope: ChatScope::Global,
-            content: "x".repeat(600),
-        };
-        assert_eq!(
-            validate_chat_message(&config, &mut tracker, &intent),
-            Err(ChatRejection::TooLong)
-        );
-    }
-
-    #[test]
-    fn test_rate_limiting_prevents_spam() {
-        let config = default_config();
-        let mut tracker = make_tracker(&config);
-
-        let intent = ChatMessageIntent {
-            scope: ChatScope::Global,
-            content: "msg".to_string(),
-        };
-
-        for _ in 0..5 {
-            assert!(validate_chat_message(&config, &mut tracker, &intent).is_ok());
-        }
-        assert_eq!(
-            validate_chat_message(&config, &mut tracker, &intent),
-            Err(ChatRejection::RateLimited)
-        );
-    }
-
-    #[test]
-    fn test_timestamp_is_server_authoritative() {
-        let config = default_config();
-        let mut tracker = make_tracker(&config);
-
-        let intent = ChatMessageIntent {
-            scope: ChatScope::Global,
-            content: "Hello".to_string(),
-        };
-        assert!(validate_chat_message(&config, &mut tracker, &intent).is_ok());
-
-        let server_tick: u64 = 77;
-        let server_time: u64 = 1_700_000_042_000;
-        let msg = stamp_message(&intent, NetworkId(5), "Bob", server_tick, server_time);
-
-        // The timestamp and tick come from the server, not the client.
-        assert_eq!(msg.server_tick, 77);
-        assert_eq!(msg.timestamp, 1_700_000_042_000);
-        assert_eq!(msg.sender_network_id, NetworkId(5));
-    }
-}
+#[path = "chat_tests.rs"]
+mod tests;
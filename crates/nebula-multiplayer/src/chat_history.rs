@@ -0,0 +1,130 @@
+//! Recent chat retained per channel so late joiners see the conversation.
+//!
+//! The server records every delivered [`ChatMessage`] in [`ChatHistory`].
+//! When a client connects, [`ChatHistory::backlog_for`] returns what it
+//! missed on the channels it has joined, oldest first.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::chat::{ChatChannel, ChatConfig, ChatMessage, ConnectedClient};
+
+/// Ring buffer of the most recent messages per [`ChatChannel`].
+#[derive(Debug, Clone)]
+pub struct ChatHistory {
+    retention: usize,
+    channels: HashMap<ChatChannel, VecDeque<ChatMessage>>,
+}
+
+impl ChatHistory {
+    /// Creates a history keeping at most `retention` messages per channel.
+    pub fn new(retention: usize) -> Self {
+        Self {
+            retention,
+            channels: HashMap::new(),
+        }
+    }
+
+    /// Creates a history using [`ChatConfig::history_retention`].
+    pub fn from_config(config: &ChatConfig) -> Self {
+        Self::new(config.history_retention)
+    }
+
+    /// Maximum messages kept per channel.
+    pub fn retention(&self) -> usize {
+        self.retention
+    }
+
+    /// Records an accepted message, evicting the oldest on its channel when
+    /// full.
+    pub fn record(&mut self, message: &ChatMessage) {
+        if self.retention == 0 {
+            return;
+        }
+        let buffer = self.channels.entry(message.scope.channel()).or_default();
+        if buffer.len() >= self.retention {
+            buffer.pop_front();
+        }
+        buffer.push_back(message.clone());
+    }
+
+    /// Retained messages on `channel`, oldest first.
+    pub fn recent(&self, channel: ChatChannel) -> impl Iterator<Item = &ChatMessage> {
+        self.channels.get(&channel).into_iter().flatten()
+    }
+
+    /// Messages to send to `client` when it joins: everything retained on
+    /// its subscribed channels from senders it has not muted, ordered by
+    /// server tick.
+    ///
+    /// Proximity chat is not replayed, since whether the joiner was in range
+    /// depends on positions at the time the message was sent.
+    pub fn backlog_for(&self, client: &ConnectedClient) -> Vec<ChatMessage> {
+        let mut backlog: Vec<ChatMessage> = self
+            .channels
+            .iter()
+            .filter(|(channel, _)| **channel != ChatChannel::Proximity)
+            .flat_map(|(_, messages)| messages)
+            .filter(|m| client.accepts(m))
+            .cloned()
+            .collect();
+        backlog.sort_by_key(|m| m.server_tick);
+        backlog
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::{ChatScope, JoinChannel};
+    use crate::interest::InterestPosition;
+    use crate::network_id::NetworkId;
+
+    fn message(scope: ChatScope, tick: u64) -> ChatMessage {
+        ChatMessage {
+            sender_network_id: NetworkId::new(1),
+            sender_name: "Alice".to_string(),
+            scope,
+            content: format!("message {tick}"),
+            server_tick: tick,
+            timestamp: 1_700_000_000_000 + tick,
+        }
+    }
+
+    fn joiner() -> ConnectedClient {
+        let mut client = ConnectedClient::new(9, InterestPosition::new(0.0, 0.0, 0.0));
+        client.join_channel(&JoinChannel {
+            channel: ChatChannel::Global,
+        });
+        client
+    }
+
+    #[test]
+    fn test_joining_client_receives_last_n_global_messages_in_order() {
+        let mut history = ChatHistory::new(3);
+        for tick in 1..=5 {
+            history.record(&message(ChatScope::Global, tick));
+        }
+        history.record(&message(ChatScope::Proximity { radius: 10.0 }, 6));
+
+        let ticks: Vec<u64> = history
+            .backlog_for(&joiner())
+            .iter()
+            .map(|m| m.server_tick)
+            .collect();
+        assert_eq!(ticks, vec![3, 4, 5]);
+        assert_eq!(history.recent(ChatChannel::Proximity).count(), 1);
+    }
+
+    #[test]
+    fn test_backlog_respects_mutes_and_subscriptions() {
+        let mut history = ChatHistory::from_config(&ChatConfig::default());
+        history.record(&message(ChatScope::Global, 1));
+
+        let mut muter = joiner();
+        muter.mute_list.mute(NetworkId::new(1));
+        assert!(history.backlog_for(&muter).is_empty());
+
+        let unsubscribed = ConnectedClient::new(10, InterestPosition::new(0.0, 0.0, 0.0));
+        assert!(history.backlog_for(&unsubscribed).is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+//! Unit tests for chat validation, channels, and broadcast.
+
+use super::*;
+
+fn default_config() -> ChatConfig {
+    ChatConfig::default()
+}
+
+/// A client subscribed to both channels.
+fn client(client_id: u64, position: InterestPosition) -> ConnectedClient {
+    let mut client = ConnectedClient::new(client_id, position);
+    client.join_channel(&JoinChannel {
+        channel: ChatChannel::Global,
+    });
+    client.join_channel(&JoinChannel {
+        channel: ChatChannel::Proximity,
+    });
+    client
+}
+
+fn make_tracker(config: &ChatConfig) -> RateTracker {
+    RateTracker::new(config.rate_limit_messages, config.rate_limit_window)
+}
+
+fn stamp_message(
+    intent: &ChatMessageIntent,
+    sender_id: NetworkId,
+    sender_name: &str,
+    tick: u64,
+    timestamp: u64,
+) -> ChatMessage {
+    ChatMessage {
+        sender_network_id: sender_id,
+        sender_name: sender_name.to_string(),
+        scope: intent.scope.clone(),
+        content: intent.content.clone(),
+        server_tick: tick,
+        timestamp,
+    }
+}
+
+#[test]
+fn test_message_sent_and_received_by_all() {
+    let config = default_config();
+    let mut tracker = make_tracker(&config);
+
+    let intent = ChatMessageIntent {
+        scope: ChatScope::Global,
+        content: "Hello".to_string(),
+    };
+    assert!(validate_chat_message(&config, &mut tracker, &intent).is_ok());
+
+    let msg = stamp_message(&intent, NetworkId::new(1), "Alice", 42, 1_700_000_000_000);
+
+    let clients = vec![
+        client(2, InterestPosition::new(0.0, 0.0, 0.0)),
+        client(3, InterestPosition::new(100.0, 0.0, 0.0)),
+        client(4, InterestPosition::new(999.0, 0.0, 0.0)),
+    ];
+
+    let recipients = broadcast_chat(
+        &msg,
+        &InterestPosition::new(0.0, 0.0, 0.0),
+        &clients,
+        &config,
+    );
+    assert_eq!(recipients, vec![2, 3, 4]);
+    assert_eq!(msg.sender_network_id, NetworkId::new(1));
+    assert_eq!(msg.content, "Hello");
+    assert!(msg.timestamp > 0);
+}
+
+#[test]
+fn test_proximity_chat_limited_by_distance() {
+    let config = default_config();
+    let mut tracker = make_tracker(&config);
+
+    let intent = ChatMessageIntent {
+        scope: ChatScope::Proximity { radius: 50.0 },
+        content: "Psst".to_string(),
+    };
+    assert!(validate_chat_message(&config, &mut tracker, &intent).is_ok());
+
+    let msg = stamp_message(&intent, NetworkId::new(1), "Alice", 10, 1_700_000_000_000);
+
+    let sender_pos = InterestPosition::new(0.0, 0.0, 0.0);
+    let clients = vec![
+        client(2, InterestPosition::new(30.0, 0.0, 0.0)),
+        client(3, InterestPosition::new(100.0, 0.0, 0.0)),
+    ];
+
+    let recipients = broadcast_chat(&msg, &sender_pos, &clients, &config);
+    assert_eq!(recipients, vec![2]);
+}
+
+#[test]
+fn test_message_length_limit_enforced() {
+    let config = default_config();
+    let mut tracker = make_tracker(&config);
+
+    let intent = ChatMessageIntent {
+        scope: ChatScope::Global,
+        content: "x".repeat(600),
+    };
+    assert_eq!(
+        validate_chat_message(&config, &mut tracker, &intent),
+        Err(ChatRejection::TooLong)
+    );
+}
+
+#[test]
+fn test_rate_limiting_prevents_spam() {
+    let config = default_config();
+    let mut tracker = make_tracker(&config);
+
+    let intent = ChatMessageIntent {
+        scope: ChatScope::Global,
+        content: "msg".to_string(),
+    };
+
+    for _ in 0..5 {
+        assert!(validate_chat_message(&config, &mut tracker, &intent).is_ok());
+    }
+    assert_eq!(
+        validate_chat_message(&config, &mut tracker, &intent),
+        Err(ChatRejection::RateLimited)
+    );
+}
+
+#[test]
+fn test_timestamp_is_server_authoritative() {
+    let config = default_config();
+    let mut tracker = make_tracker(&config);
+
+    let intent = ChatMessageIntent {
+        scope: ChatScope::Global,
+        content: "Hello".to_string(),
+    };
+    assert!(validate_chat_message(&config, &mut tracker, &intent).is_ok());
+
+    let server_tick: u64 = 77;
+    let server_time: u64 = 1_700_000_042_000;
+    let msg = stamp_message(&intent, NetworkId::new(5), "Bob", server_tick, server_time);
+
+    // The timestamp and tick come from the server, not the client.
+    assert_eq!(msg.server_tick, 77);
+    assert_eq!(msg.timestamp, 1_700_000_042_000);
+    assert_eq!(msg.sender_network_id, NetworkId::new(5));
+}
+
+#[test]
+fn test_muted_sender_reaches_everyone_but_the_muter() {
+    let config = default_config();
+    let intent = ChatMessageIntent {
+        scope: ChatScope::Global,
+        content: "spam".to_string(),
+    };
+    let msg = stamp_message(&intent, NetworkId::new(1), "Alice", 1, 1_700_000_000_000);
+
+    let origin = InterestPosition::new(0.0, 0.0, 0.0);
+    let mut muter = client(3, origin);
+    assert!(muter.mute_list.mute(NetworkId::new(1)));
+    let clients = vec![client(2, origin), muter, client(4, origin)];
+
+    assert_eq!(broadcast_chat(&msg, &origin, &clients, &config), vec![2, 4]);
+}
+
+#[test]
+fn test_unsubscribed_channel_is_not_delivered() {
+    let config = default_config();
+    let origin = InterestPosition::new(0.0, 0.0, 0.0);
+    let mut listener = client(2, origin);
+    listener.leave_channel(&LeaveChannel {
+        channel: ChatChannel::Global,
+    });
+    let clients = vec![listener, client(3, origin)];
+
+    let global = ChatMessageIntent {
+        scope: ChatScope::Global,
+        content: "hi all".to_string(),
+    };
+    let msg = stamp_message(&global, NetworkId::new(1), "Alice", 1, 0);
+    assert_eq!(broadcast_chat(&msg, &origin, &clients, &config), vec![3]);
+
+    // Proximity is still joined, so nearby chat gets through.
+    let near = ChatMessageIntent {
+        scope: ChatScope::Proximity { radius: 10.0 },
+        content: "hi you".to_string(),
+    };
+    let msg = stamp_message(&near, NetworkId::new(1), "Alice", 2, 0);
+    assert_eq!(broadcast_chat(&msg, &origin, &clients, &config), vec![2, 3]);
+}
+
+#[test]
+fn test_sending_requires_subscription_and_limits_per_channel() {
+    let config = default_config();
+    let mut sender = ConnectedClient::new(1, InterestPosition::new(0.0, 0.0, 0.0));
+    let mut limits = ChannelRateTrackers::new(&config);
+    let global = ChatMessageIntent {
+        scope: ChatScope::Global,
+        content: "hello".to_string(),
+    };
+    assert_eq!(
+        validate_channel_message(&config, &sender, &mut limits, &global),
+        Err(ChatRejection::NotSubscribed)
+    );
+
+    sender = client(1, sender.position);
+    for _ in 0..config.rate_limit_messages {
+        assert!(validate_channel_message(&config, &sender, &mut limits, &global).is_ok());
+    }
+    assert_eq!(
+        validate_channel_message(&config, &sender, &mut limits, &global),
+        Err(ChatRejection::RateLimited)
+    );
+
+    // The proximity channel has its own budget.
+    let near = ChatMessageIntent {
+        scope: ChatScope::Proximity { radius: 10.0 },
+        content: "still here".to_string(),
+    };
+    assert!(validate_channel_message(&config, &sender, &mut limits, &near).is_ok());
+}
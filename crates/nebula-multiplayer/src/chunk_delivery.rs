@@ -0,0 +1,468 @@
+//! Acknowledged, bandwidth-budgeted chunk delivery.
+//!
+//! [`ChunkSendQueue::flush_tick`] sends a fixed byte count per tick and
+//! forgets about a chunk once it is sent. [`ClientChunkStream`] wraps a queue
+//! with a per-client [`ClientBandwidthTracker`] budget and a sliding window of
+//! unacknowledged chunks: a client that stops sending [`ChunkAck`]s stalls at
+//! [`ChunkDeliveryConfig::max_in_flight`] chunks, and chunks that are not
+//! acknowledged within [`ChunkDeliveryConfig::ack_timeout_ticks`] are re-queued
+//! behind fresh chunks of the same distance.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::budget::{BandwidthConfig, ClientBandwidthTracker, ClientId};
+use crate::chunk_streaming::{
+    ChunkDataMessage, ChunkId, ChunkSendEntry, ChunkSendQueue, ChunkStreamConfig,
+};
+
+/// Client-to-server acknowledgement that a chunk was received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkAck {
+    /// The chunk that arrived.
+    pub chunk_id: ChunkId,
+}
+
+/// Flow-control settings for acknowledged chunk delivery.
+#[derive(Debug, Clone)]
+pub struct ChunkDeliveryConfig {
+    /// Maximum chunks sent but not yet acknowledged. Default: 8.
+    pub max_in_flight: usize,
+    /// Ticks to wait for an ack before re-queueing a chunk. Default: 120.
+    pub ack_timeout_ticks: u64,
+    /// Added to a timed-out chunk's priority (distance) when it is re-queued,
+    /// so it goes out after fresh chunks nearby. Default: 256.0.
+    pub resend_priority_penalty: f64,
+}
+
+impl Default for ChunkDeliveryConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight: 8,
+            ack_timeout_ticks: 120,
+            resend_priority_penalty: 256.0,
+        }
+    }
+}
+
+/// Snapshot of a client's chunk delivery state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkDeliveryStats {
+    /// Chunks waiting to be sent.
+    pub queued: usize,
+    /// Chunks sent but not yet acknowledged.
+    pub in_flight: usize,
+    /// Chunks re-queued after an ack timeout, since creation.
+    pub resent: u64,
+    /// Average chunk bytes sent per second over the tracker history.
+    pub bytes_per_second: f64,
+}
+
+/// A sent chunk awaiting acknowledgement.
+#[derive(Debug, Clone)]
+struct InFlightChunk {
+    priority: f64,
+    sent_tick: u64,
+}
+
+/// Chunk queue, bandwidth budget and ack window for a single client.
+#[derive(Debug)]
+pub struct ClientChunkStream {
+    /// Chunks waiting to be sent, nearest first.
+    pub queue: ChunkSendQueue,
+    /// Per-tick byte budget for this client.
+    pub bandwidth: ClientBandwidthTracker,
+    in_flight: HashMap<ChunkId, InFlightChunk>,
+    resent: u64,
+}
+
+impl ClientChunkStream {
+    /// Create an empty stream for `client_id` with the given bandwidth budget.
+    pub fn new(client_id: ClientId, bandwidth: BandwidthConfig) -> Self {
+        Self {
+            queue: ChunkSendQueue::new(),
+            bandwidth: ClientBandwidthTracker::new(client_id, bandwidth),
+            in_flight: HashMap::new(),
+            resent: 0,
+        }
+    }
+
+    /// Queue a chunk for this client (see [`ChunkSendQueue::enqueue`]).
+    pub fn enqueue(&mut self, entry: ChunkSendEntry, config: &ChunkStreamConfig) {
+        self.queue.enqueue(entry, config);
+    }
+
+    /// Record an acknowledgement, opening a slot in the window.
+    ///
+    /// An ack for a chunk that already timed out and was re-queued removes
+    /// it from the queue. Returns `false` if the chunk was not awaiting an
+    /// ack.
+    pub fn on_ack(&mut self, ack: &ChunkAck) -> bool {
+        if self.in_flight.remove(&ack.chunk_id).is_some() {
+            return true;
+        }
+        let before = self.queue.queue.len();
+        self.queue.queue.retain(|e| e.chunk_id != ack.chunk_id);
+        if self.queue.queue.len() == before {
+            return false;
+        }
+        self.queue.sent.insert(ack.chunk_id);
+        true
+    }
+
+    /// Run one tick: re-queue timed-out chunks, then send the nearest queued
+    /// chunks until the window is full or the byte budget is spent.
+    ///
+    /// A tick can exceed the budget by at most one chunk.
+    pub fn flush_tick(
+        &mut self,
+        tick: u64,
+        config: &ChunkDeliveryConfig,
+        chunk_data_fn: impl Fn(&ChunkId) -> Option<Vec<u8>>,
+    ) -> Vec<ChunkDataMessage> {
+        self.requeue_timed_out(tick, config);
+
+        let window = config.max_in_flight.saturating_sub(self.in_flight.len());
+        let budget = self.bandwidth.remaining_budget();
+        let mut messages = Vec::new();
+        if window > 0 && budget > 0 {
+            // Look up priorities before the entries leave the heap.
+            let priorities: HashMap<ChunkId, f64> = self
+                .queue
+                .queue
+                .iter()
+                .map(|e| (e.chunk_id, e.priority))
+                .collect();
+            messages = self.queue.flush_budgeted(budget, window, chunk_data_fn);
+            for msg in &messages {
+                self.bandwidth.consume(msg.compressed_data.len());
+                let priority = priorities.get(&msg.chunk_id).copied().unwrap_or(0.0);
+                self.in_flight.insert(
+                    msg.chunk_id,
+                    InFlightChunk {
+                        priority,
+                        sent_tick: tick,
+                    },
+                );
+            }
+        }
+
+        self.bandwidth.end_tick();
+        messages
+    }
+
+    /// Everything not yet acknowledged: the queue plus the in-flight chunks,
+    /// which are put back at their original priority.
+    pub fn into_pending(mut self) -> ChunkSendQueue {
+        for (chunk_id, chunk) in self.in_flight.drain() {
+            self.queue.requeue(ChunkSendEntry {
+                chunk_id,
+                priority: chunk.priority,
+            });
+        }
+        self.queue
+    }
+
+    /// Current queue, window and throughput figures.
+    pub fn stats(&self) -> ChunkDeliveryStats {
+        ChunkDeliveryStats {
+            queued: self.queue.queue.len(),
+            in_flight: self.in_flight.len(),
+            resent: self.resent,
+            bytes_per_second: self.bandwidth.average_usage()
+                * f64::from(self.bandwidth.config.tick_rate),
+        }
+    }
+
+    fn requeue_timed_out(&mut self, tick: u64, config: &ChunkDeliveryConfig) {
+        let expired: Vec<ChunkId> = self
+            .in_flight
+            .iter()
+            .filter(|(_, c)| tick.saturating_sub(c.sent_tick) >= config.ack_timeout_ticks)
+            .map(|(id, _)| *id)
+            .collect();
+        for chunk_id in expired {
+            if let Some(chunk) = self.in_flight.remove(&chunk_id) {
+                self.queue.requeue(ChunkSendEntry {
+                    chunk_id,
+                    priority: chunk.priority + config.resend_priority_penalty,
+                });
+                self.resent += 1;
+            }
+        }
+    }
+}
+
+/// Server-side chunk delivery for all connected clients.
+#[derive(Debug)]
+pub struct ChunkStreamer {
+    /// Queue limits applied to every client.
+    pub stream_config: ChunkStreamConfig,
+    /// Ack window and timeout settings.
+    pub delivery_config: ChunkDeliveryConfig,
+    /// Bandwidth budget given to newly added clients.
+    pub bandwidth_config: BandwidthConfig,
+    clients: HashMap<ClientId, ClientChunkStream>,
+}
+
+impl ChunkStreamer {
+    /// Create a streamer with no clients.
+    pub fn new(
+        stream_config: ChunkStreamConfig,
+        delivery_config: ChunkDeliveryConfig,
+        bandwidth_config: BandwidthConfig,
+    ) -> Self {
+        Self {
+            stream_config,
+            delivery_config,
+            bandwidth_config,
+            clients: HashMap::new(),
+        }
+    }
+
+    /// Start streaming to a client. Does nothing if it is already known.
+    pub fn add_client(&mut self, client_id: ClientId) {
+        let bandwidth = self.bandwidth_config.clone();
+        self.clients
+            .entry(client_id)
+            .or_insert_with(|| ClientChunkStream::new(client_id, bandwidth));
+    }
+
+    /// Stop streaming to a client, dropping its queue and window.
+    pub fn remove_client(&mut self, client_id: ClientId) -> Option<ClientChunkStream> {
+        self.clients.remove(&client_id)
+    }
+
+    /// Start streaming to a client from a queue kept while it was away, e.g.
+    /// [`ResumedSession::pending_chunks`](crate::ResumedSession::pending_chunks).
+    /// Replaces any stream the client already has.
+    pub fn resume_client(&mut self, client_id: ClientId, pending: ChunkSendQueue) {
+        let mut stream = ClientChunkStream::new(client_id, self.bandwidth_config.clone());
+        stream.queue = pending;
+        self.clients.insert(client_id, stream);
+    }
+
+    /// Queue a chunk for a client. Ignored for unknown clients.
+    pub fn enqueue(&mut self, client_id: ClientId, entry: ChunkSendEntry) {
+        if let Some(stream) = self.clients.get_mut(&client_id) {
+            stream.enqueue(entry, &self.stream_config);
+        }
+    }
+
+    /// Route a [`ChunkAck`] from a client. Returns `false` if the client is
+    /// unknown or the chunk was not awaiting an ack.
+    pub fn handle_ack(&mut self, client_id: ClientId, ack: &ChunkAck) -> bool {
+        self.clients
+            .get_mut(&client_id)
+            .is_some_and(|stream| stream.on_ack(ack))
+    }
+
+    /// Run one tick for every client, returning the messages to send.
+    pub fn flush_tick(
+        &mut self,
+        tick: u64,
+        chunk_data_fn: impl Fn(&ChunkId) -> Option<Vec<u8>>,
+    ) -> Vec<(ClientId, ChunkDataMessage)> {
+        let mut out = Vec::new();
+        for (&client_id, stream) in &mut self.clients {
+            for msg in stream.flush_tick(tick, &self.delivery_config, &chunk_data_fn) {
+                out.push((client_id, msg));
+            }
+        }
+        out
+    }
+
+    /// Delivery figures for a client.
+    pub fn stats(&self, client_id: ClientId) -> Option<ChunkDeliveryStats> {
+        self.clients.get(&client_id).map(ClientChunkStream::stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(x: i32) -> ChunkId {
+        ChunkId {
+            face: 0,
+            lod: 0,
+            x,
+            y: 0,
+            z: 0,
+        }
+    }
+
+    /// Pseudo-random bytes that LZ4 cannot shrink much.
+    fn noisy(len: usize, seed: u32) -> Vec<u8> {
+        let mut state = seed.wrapping_mul(2_654_435_761) | 1;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect()
+    }
+
+    fn data_fn(id: &ChunkId) -> Option<Vec<u8>> {
+        Some(noisy(300, id.x as u32))
+    }
+
+    fn streamer(max_bytes_per_second: usize, ack_timeout_ticks: u64) -> ChunkStreamer {
+        ChunkStreamer::new(
+            ChunkStreamConfig::default(),
+            ChunkDeliveryConfig {
+                ack_timeout_ticks,
+                ..ChunkDeliveryConfig::default()
+            },
+            BandwidthConfig {
+                max_bytes_per_second,
+                tick_rate: 60,
+            },
+        )
+    }
+
+    #[test]
+    fn test_tick_never_exceeds_budget_by_more_than_one_chunk() {
+        let mut streamer = streamer(64 * 1024, 1_000);
+        streamer.add_client(1);
+        for x in 0..200 {
+            streamer.enqueue(
+                1,
+                ChunkSendEntry {
+                    chunk_id: chunk(x),
+                    priority: f64::from(x),
+                },
+            );
+        }
+
+        let budget = streamer.bandwidth_config.bytes_per_tick();
+        let mut largest_chunk = 0;
+        for tick in 0..50 {
+            let sent = streamer.flush_tick(tick, data_fn);
+            let mut tick_bytes = 0;
+            for (client, msg) in &sent {
+                tick_bytes += msg.compressed_data.len();
+                largest_chunk = largest_chunk.max(msg.compressed_data.len());
+                assert!(streamer.handle_ack(
+                    *client,
+                    &ChunkAck {
+                        chunk_id: msg.chunk_id
+                    }
+                ));
+            }
+            assert!(
+                tick_bytes <= budget + largest_chunk,
+                "tick {tick} sent {tick_bytes} bytes, budget {budget}"
+            );
+        }
+
+        let stats = streamer.stats(1).unwrap();
+        assert!(stats.queued < 200, "chunks were delivered");
+        assert!(stats.bytes_per_second <= (budget + largest_chunk) as f64 * 60.0);
+    }
+
+    #[test]
+    fn test_stalled_client_stops_at_window_others_continue() {
+        let mut streamer = streamer(1_000_000, 10_000);
+        streamer.add_client(1);
+        streamer.add_client(2);
+        for x in 0..100 {
+            for client in [1, 2] {
+                streamer.enqueue(
+                    client,
+                    ChunkSendEntry {
+                        chunk_id: chunk(x),
+                        priority: f64::from(x),
+                    },
+                );
+            }
+        }
+
+        let mut received = [0usize; 3];
+        for tick in 0..20 {
+            for (client, msg) in streamer.flush_tick(tick, data_fn) {
+                received[client as usize] += 1;
+                // Client 1 never acks.
+                if client == 2 {
+                    streamer.handle_ack(
+                        2,
+                        &ChunkAck {
+                            chunk_id: msg.chunk_id,
+                        },
+                    );
+                }
+            }
+        }
+
+        assert_eq!(received[1], 8);
+        assert_eq!(streamer.stats(1).unwrap().in_flight, 8);
+        assert_eq!(received[2], 100);
+        assert_eq!(streamer.stats(2).unwrap().in_flight, 0);
+    }
+
+    #[test]
+    fn test_timed_out_chunk_is_resent_after_fresh_chunks() {
+        let config = ChunkDeliveryConfig {
+            max_in_flight: 1,
+            ack_timeout_ticks: 5,
+            resend_priority_penalty: 100.0,
+        };
+        let mut stream = ClientChunkStream::new(7, BandwidthConfig::default());
+        let stream_config = ChunkStreamConfig::default();
+        stream.enqueue(
+            ChunkSendEntry {
+                chunk_id: chunk(0),
+                priority: 10.0,
+            },
+            &stream_config,
+        );
+
+        let first = stream.flush_tick(0, &config, data_fn);
+        assert_eq!(first[0].chunk_id, chunk(0));
+
+        // A nearer-than-penalised chunk arrives while the first is unacked.
+        stream.enqueue(
+            ChunkSendEntry {
+                chunk_id: chunk(1),
+                priority: 50.0,
+            },
+            &stream_config,
+        );
+        assert!(stream.flush_tick(1, &config, data_fn).is_empty());
+
+        let after_timeout = stream.flush_tick(5, &config, data_fn);
+        assert_eq!(after_timeout[0].chunk_id, chunk(1));
+        assert_eq!(stream.stats().resent, 1);
+        assert_eq!(stream.stats().queued, 1);
+
+        assert!(stream.on_ack(&ChunkAck { chunk_id: chunk(1) }));
+        let resend = stream.flush_tick(6, &config, data_fn);
+        assert_eq!(resend[0].chunk_id, chunk(0));
+    }
+
+    #[test]
+    fn test_late_ack_removes_requeued_chunk() {
+        let config = ChunkDeliveryConfig {
+            ack_timeout_ticks: 1,
+            ..ChunkDeliveryConfig::default()
+        };
+        let mut stream = ClientChunkStream::new(7, BandwidthConfig::default());
+        stream.enqueue(
+            ChunkSendEntry {
+                chunk_id: chunk(3),
+                priority: 1.0,
+            },
+            &ChunkStreamConfig::default(),
+        );
+        stream.flush_tick(0, &config, |_| Some(vec![1; 64]));
+        stream.requeue_timed_out(1, &config);
+        assert_eq!(stream.stats().queued, 1);
+
+        assert!(stream.on_ack(&ChunkAck { chunk_id: chunk(3) }));
+        assert_eq!(stream.stats().queued, 0);
+        assert!(!stream.on_ack(&ChunkAck { chunk_id: chunk(3) }));
+    }
+}
@@ -157,6 +157,14 @@ impl ChunkSendQueue {
         self.queue.push(entry);
     }
 
+    /// Put a previously sent chunk back in the queue, e.g. after its
+    /// acknowledgement timed out.  Bypasses the `max_queued_chunks` limit
+    /// since the chunk was already admitted once.
+    pub fn requeue(&mut self, entry: ChunkSendEntry) {
+        self.sent.remove(&entry.chunk_id);
+        self.queue.push(entry);
+    }
+
     /// Drain up to `bytes_per_tick` worth of compressed chunk data from the
     /// queue.  Returns the produced messages and the number of bytes consumed.
     pub fn flush_tick(
@@ -164,10 +172,26 @@ impl ChunkSendQueue {
         config: &ChunkStreamConfig,
         chunk_data_fn: impl Fn(&ChunkId) -> Option<Vec<u8>>,
     ) -> Vec<ChunkDataMessage> {
-        let mut budget = config.bytes_per_tick;
+        self.flush_budgeted(config.bytes_per_tick, usize::MAX, chunk_data_fn)
+    }
+
+    /// Drain at most `max_chunks` chunks and roughly `budget` compressed
+    /// bytes from the queue, highest priority first.
+    ///
+    /// The first chunk is always sent even if it alone exceeds `budget`, so
+    /// a tick overshoots by at most one chunk and large chunks cannot stall
+    /// the queue.
+    pub fn flush_budgeted(
+        &mut self,
+        mut budget: usize,
+        max_chunks: usize,
+        chunk_data_fn: impl Fn(&ChunkId) -> Option<Vec<u8>>,
+    ) -> Vec<ChunkDataMessage> {
         let mut messages = Vec::new();
 
-        while let Some(entry) = self.queue.peek() {
+        while messages.len() < max_chunks
+            && let Some(entry) = self.queue.peek()
+        {
             let id = entry.chunk_id;
             let raw = match chunk_data_fn(&id) {
                 Some(d) => d,
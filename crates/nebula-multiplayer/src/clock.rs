@@ -2,18 +2,49 @@
 //!
 //! Provides NTP-like clock sync so clients can estimate the server's current
 //! tick and run slightly ahead (by half-RTT) for timely input delivery.
+//!
+//! [`ClockSync`] also keeps a smoothed estimate of the offset between the
+//! local monotonic clock and server time. Corrections are *smeared*: the
+//! clock runs at most [`MAX_SMEAR_RATE`] fast or slow until the error is
+//! absorbed, so remote entities never visibly hitch. Only a divergence
+//! beyond [`HARD_RESYNC_THRESHOLD`] jumps the clock outright.
 
 use std::collections::VecDeque;
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
+use crate::lag_compensation::REWIND_SLACK_TICKS;
+
 /// Fixed tick rate shared by client and server.
 pub const TICK_RATE: u32 = 60;
 
 /// Duration of a single tick at [`TICK_RATE`].
 pub const TICK_DURATION: Duration = Duration::from_nanos(1_000_000_000 / TICK_RATE as u64);
 
+/// Server time in seconds at the start of `tick`, on the same timeline as
+/// [`ClockSync::estimated_server_time`].
+pub fn server_time_at_tick(tick: u64) -> f64 {
+    tick as f64 * TICK_DURATION.as_secs_f64()
+}
+
+/// Largest fraction by which smearing speeds up or slows down the clock.
+pub const MAX_SMEAR_RATE: f64 = 0.02;
+
+/// Offset error beyond which the clock is resynchronized by jumping.
+pub const HARD_RESYNC_THRESHOLD: Duration = Duration::from_secs(1);
+
+/// Pongs whose RTT exceeds this multiple of the median RTT are ignored for
+/// offset estimation.
+pub const OUTLIER_RTT_FACTOR: f64 = 3.0;
+
+/// RTT samples needed before outlier rejection kicks in.
+const MIN_SAMPLES_FOR_OUTLIER_REJECTION: usize = 4;
+
+/// Consecutive divergent samples required before a hard resync, so a single
+/// bogus pong cannot make the clock jump.
+const DIVERGENT_SAMPLES_FOR_RESYNC: u32 = 2;
+
 /// Ping message sent by the client.
 ///
 /// `client_send_time_ns` is a monotonic timestamp in nanoseconds (not
@@ -103,6 +134,23 @@ impl RttEstimator {
         self.ewma_rtt = Duration::from_secs_f64(new_ewma);
     }
 
+    /// Mean absolute difference between consecutive stored samples.
+    ///
+    /// Approximates network jitter in the spirit of RFC 3550; zero with
+    /// fewer than two samples.
+    pub fn jitter(&self) -> Duration {
+        if self.samples.len() < 2 {
+            return Duration::ZERO;
+        }
+        let total: f64 = self
+            .samples
+            .iter()
+            .zip(self.samples.iter().skip(1))
+            .map(|(a, b)| (b.as_secs_f64() - a.as_secs_f64()).abs())
+            .sum();
+        Duration::from_secs_f64(total / (self.samples.len() - 1) as f64)
+    }
+
     /// Compute the median of stored samples.
     pub fn median_rtt(&self) -> Duration {
         if self.samples.is_empty() {
@@ -148,6 +196,22 @@ pub struct ClockSync {
     pub converged: bool,
     /// Minimum samples before declaring convergence.
     pub min_samples_for_convergence: usize,
+    /// EWMA smoothing factor for the server time offset (default 0.1).
+    pub offset_alpha: f64,
+    /// Largest clock rate adjustment while smearing (default [`MAX_SMEAR_RATE`]).
+    pub max_smear_rate: f64,
+    /// Offset error that forces a hard resync (default [`HARD_RESYNC_THRESHOLD`]).
+    pub hard_resync_threshold: Duration,
+    /// Smoothed `server_time - local_time` in nanoseconds.
+    offset_estimate_ns: Option<f64>,
+    /// Offset currently applied by [`Self::estimated_server_time`], smeared
+    /// toward `offset_estimate_ns`.
+    applied_offset_ns: f64,
+    /// Rate adjustment applied by the last [`Self::advance`].
+    smear_rate: f64,
+    last_advance_ns: Option<u64>,
+    divergent_samples: u32,
+    hard_resyncs: u64,
 }
 
 impl Default for ClockSync {
@@ -158,12 +222,24 @@ impl Default for ClockSync {
             target_lead: 0.0,
             converged: false,
             min_samples_for_convergence: 8,
+            offset_alpha: 0.1,
+            max_smear_rate: MAX_SMEAR_RATE,
+            hard_resync_threshold: HARD_RESYNC_THRESHOLD,
+            offset_estimate_ns: None,
+            applied_offset_ns: 0.0,
+            smear_rate: 0.0,
+            last_advance_ns: None,
+            divergent_samples: 0,
+            hard_resyncs: 0,
         }
     }
 }
 
 impl ClockSync {
-    /// Process a pong response.
+    /// Process a pong response and return how the clock is being corrected.
+    ///
+    /// Pongs whose RTT exceeds [`OUTLIER_RTT_FACTOR`] times the median are
+    /// recorded for RTT statistics but otherwise ignored.
     ///
     /// All times are monotonic nanosecond timestamps (not `Instant`).
     pub fn on_pong_received(
@@ -172,10 +248,15 @@ impl ClockSync {
         local_receive_time_ns: u64,
         local_send_time_ns: u64,
         client_tick_at_send: u64,
-    ) {
+    ) -> TickAdjustment {
         let rtt_ns = local_receive_time_ns.saturating_sub(local_send_time_ns);
         let rtt = Duration::from_nanos(rtt_ns);
+        let outlier = self.rtt.samples.len() >= MIN_SAMPLES_FOR_OUTLIER_REJECTION
+            && rtt.as_secs_f64() > OUTLIER_RTT_FACTOR * self.rtt.median_rtt().as_secs_f64();
         self.rtt.record_sample(rtt);
+        if outlier {
+            return TickAdjustment::None;
+        }
 
         let half_rtt_ticks = (rtt.as_secs_f64() * f64::from(TICK_RATE)) / 2.0;
         let estimated_server_tick = pong.server_tick as f64 + half_rtt_ticks;
@@ -190,133 +271,137 @@ impl ClockSync {
         if self.rtt.samples.len() >= self.min_samples_for_convergence {
             self.converged = true;
         }
-    }
 
-    /// Return the adjusted client tick that accounts for the target lead.
-    pub fn adjusted_client_tick(&self, raw_tick: u64) -> u64 {
-        (raw_tick as i64 + self.tick_offset) as u64
+        // NTP-style offset: the server stamped the pong half an RTT after
+        // the ping left. Asymmetric routes bias this by half the asymmetry,
+        // which no timestamp exchange can observe, but the bias is stable.
+        let server_ns = pong.server_tick as f64 * TICK_DURATION.as_nanos() as f64;
+        let midpoint_ns = local_send_time_ns as f64 + rtt_ns as f64 / 2.0;
+        self.update_offset(server_ns - midpoint_ns)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_tick_offset_converges_after_multiple_samples() {
-        let mut sync = ClockSync::default();
-        let rtt_ns: u64 = 50_000_000; // 50 ms
-
-        for i in 0..16u32 {
-            let send_time = (i as u64) * 500_000_000; // every 500ms
-            let recv_time = send_time + rtt_ns;
-            let client_tick = (i as u64) * 30; // ~30 ticks per 500ms
-            let server_tick = client_tick; // server at same tick
-
-            let pong = Pong {
-                sequence: i,
-                server_tick,
-            };
-            sync.on_pong_received(&pong, recv_time, send_time, client_tick);
-        }
+    /// Folds one offset sample into the estimate.
+    fn update_offset(&mut self, sample_ns: f64) -> TickAdjustment {
+        let Some(estimate) = self.offset_estimate_ns else {
+            self.hard_resync(sample_ns);
+            return TickAdjustment::HardReset;
+        };
 
-        assert!(sync.converged, "should converge after 16 samples");
-        // With 50ms RTT, half_rtt_ticks ≈ 1.5 ticks. Offset should be stable.
-        // The exact offset depends on the math; just check it's within 5 ticks.
-        assert!(
-            (sync.tick_offset as f64).abs() < 5.0,
-            "tick_offset should be stable, got {}",
-            sync.tick_offset
-        );
+        if (sample_ns - estimate).abs() > self.hard_resync_threshold.as_nanos() as f64 {
+            self.divergent_samples += 1;
+            if self.divergent_samples < DIVERGENT_SAMPLES_FOR_RESYNC {
+                return TickAdjustment::None;
+            }
+            tracing::warn!(
+                "clock diverged by {:.3} s, resynchronizing",
+                (sample_ns - estimate) / 1e9
+            );
+            self.hard_resync(sample_ns);
+            self.hard_resyncs += 1;
+            return TickAdjustment::HardReset;
+        }
+        self.divergent_samples = 0;
+
+        let estimate = estimate + self.offset_alpha * (sample_ns - estimate);
+        self.offset_estimate_ns = Some(estimate);
+
+        // Positive error: the applied clock runs ahead of the estimate.
+        let error_ticks = (self.applied_offset_ns - estimate) / TICK_DURATION.as_nanos() as f64;
+        if error_ticks > 0.1 {
+            TickAdjustment::SlowDown
+        } else if error_ticks < -0.1 {
+            TickAdjustment::SpeedUp
+        } else {
+            TickAdjustment::None
+        }
     }
 
-    #[test]
-    fn test_rtt_measurement_is_accurate() {
-        let mut rtt_est = RttEstimator::default();
-        let rtt = Duration::from_millis(40);
-        rtt_est.record_sample(rtt);
-
-        assert_eq!(rtt_est.samples.len(), 1);
-        let recorded = rtt_est.samples[0];
-        let diff = recorded.abs_diff(rtt);
-        assert!(
-            diff < Duration::from_millis(1),
-            "recorded RTT should be ~40ms, got {:?}",
-            recorded
-        );
+    /// Jumps the applied offset straight to `offset_ns`.
+    fn hard_resync(&mut self, offset_ns: f64) {
+        self.offset_estimate_ns = Some(offset_ns);
+        self.applied_offset_ns = offset_ns;
+        self.smear_rate = 0.0;
+        self.divergent_samples = 0;
     }
 
-    #[test]
-    fn test_client_tick_leads_server_by_half_rtt() {
-        let mut sync = ClockSync {
-            min_samples_for_convergence: 1,
-            ..ClockSync::default()
+    /// Smears the applied offset toward the current estimate. Call once per
+    /// frame with the local monotonic time.
+    ///
+    /// The applied offset moves by at most `max_smear_rate` of the local
+    /// time elapsed since the previous call, so server time never advances
+    /// more than that fraction faster or slower than the local clock.
+    pub fn advance(&mut self, local_now_ns: u64) {
+        let elapsed_ns = self
+            .last_advance_ns
+            .map_or(0.0, |last| local_now_ns.saturating_sub(last) as f64);
+        self.last_advance_ns = Some(local_now_ns);
+        let Some(estimate) = self.offset_estimate_ns else {
+            return;
         };
 
-        let rtt_ns: u64 = 60_000_000; // 60 ms
-        let server_tick = 1000u64;
-        let client_tick = 1000u64;
-
-        let pong = Pong {
-            sequence: 0,
-            server_tick,
+        let max_step = elapsed_ns * self.max_smear_rate;
+        let step = (estimate - self.applied_offset_ns).clamp(-max_step, max_step);
+        self.applied_offset_ns += step;
+        self.smear_rate = if elapsed_ns > 0.0 {
+            step / elapsed_ns
+        } else {
+            0.0
         };
-        sync.on_pong_received(&pong, rtt_ns, 0, client_tick);
-
-        // half RTT = 30ms = 1.8 ticks at 60Hz
-        let expected_lead = 1.8;
-        assert!(
-            (sync.target_lead - expected_lead).abs() < 0.1,
-            "target_lead should be ~1.8, got {}",
-            sync.target_lead
-        );
     }
 
-    #[test]
-    fn test_clock_sync_handles_jitter() {
-        let mut rtt_est = RttEstimator::default();
+    /// Estimated server time in seconds at local time `local_now_ns`, with
+    /// sub-tick precision, or `None` before the first pong.
+    /// [`InterpolationClock::sync_to`](crate::InterpolationClock::sync_to)
+    /// renders remote entities against it.
+    pub fn estimated_server_time(&self, local_now_ns: u64) -> Option<f64> {
+        self.offset_estimate_ns?;
+        Some((local_now_ns as f64 + self.applied_offset_ns) / 1e9)
+    }
 
-        // 14 normal samples at ~50ms
-        for _ in 0..14 {
-            rtt_est.record_sample(Duration::from_millis(50));
-        }
-        // 2 outlier samples at 200ms
-        rtt_est.record_sample(Duration::from_millis(200));
-        rtt_est.record_sample(Duration::from_millis(200));
-
-        let median = rtt_est.median_rtt();
-        // Median of 14×50ms + 2×200ms → 50ms (since 14/16 are 50ms)
-        assert!(
-            median <= Duration::from_millis(55),
-            "median should be ~50ms despite outliers, got {:?}",
-            median
-        );
+    /// Estimated fractional server tick at local time `local_now_ns`.
+    pub fn estimated_server_tick(&self, local_now_ns: u64) -> Option<f64> {
+        self.estimated_server_time(local_now_ns)
+            .map(|secs| secs * f64::from(TICK_RATE))
     }
 
-    #[test]
-    fn test_tick_numbers_are_monotonic() {
-        let mut counter = TickCounter::new(false);
-        let mut prev = counter.tick;
-
-        for i in 0..1000u64 {
-            counter.advance();
-
-            // Simulate clock adjustments at ticks 100, 300, 500 by computing
-            // adjustment but never decreasing the counter.
-            if i == 100 || i == 300 || i == 500 {
-                let adj = compute_tick_adjustment(0.5);
-                assert_eq!(adj, TickAdjustment::SlowDown);
-                // SlowDown means we might skip an advance next iteration,
-                // but tick never decreases.
-            }
+    /// Local duration of one server tick while the current correction is
+    /// smeared in: shorter while catching up, longer while backing off.
+    pub fn effective_tick_duration(&self) -> Duration {
+        Duration::from_secs_f64(TICK_DURATION.as_secs_f64() / (1.0 + self.smear_rate))
+    }
 
-            assert!(
-                counter.tick >= prev,
-                "tick must be monotonic: {} < {}",
-                counter.tick,
-                prev
-            );
-            prev = counter.tick;
-        }
+    /// Rate adjustment applied by the last [`Self::advance`], within
+    /// `±max_smear_rate`.
+    pub fn smear_rate(&self) -> f64 {
+        self.smear_rate
+    }
+
+    /// Offset error still to be smeared in, in seconds (positive: the
+    /// applied clock is behind the estimate).
+    pub fn pending_correction_secs(&self) -> f64 {
+        self.offset_estimate_ns
+            .map_or(0.0, |estimate| (estimate - self.applied_offset_ns) / 1e9)
+    }
+
+    /// Number of hard resyncs caused by clock divergence.
+    pub fn hard_resyncs(&self) -> u64 {
+        self.hard_resyncs
+    }
+
+    /// Maximum number of ticks a lag-compensated request from this client
+    /// may rewind: the median RTT in ticks (rounded up) plus
+    /// [`REWIND_SLACK_TICKS`].
+    pub fn max_rewind_ticks(&self) -> u64 {
+        let rtt_ticks = self.rtt.median_rtt().as_secs_f64() * f64::from(TICK_RATE);
+        rtt_ticks.ceil() as u64 + REWIND_SLACK_TICKS
+    }
+
+    /// Return the adjusted client tick that accounts for the target lead.
+    pub fn adjusted_client_tick(&self, raw_tick: u64) -> u64 {
+        (raw_tick as i64 + self.tick_offset) as u64
     }
 }
+
+#[cfg(test)]
+#[path = "clock_tests.rs"]
+mod tests;
@@ -0,0 +1,292 @@
+//! Unit tests for RTT estimation, tick adjustment and clock sync.
+
+use super::*;
+
+#[test]
+fn test_tick_offset_converges_after_multiple_samples() {
+    let mut sync = ClockSync::default();
+    let rtt_ns: u64 = 50_000_000; // 50 ms
+
+    for i in 0..16u32 {
+        let send_time = (i as u64) * 500_000_000; // every 500ms
+        let recv_time = send_time + rtt_ns;
+        let client_tick = (i as u64) * 30; // ~30 ticks per 500ms
+        let server_tick = client_tick; // server at same tick
+
+        let pong = Pong {
+            sequence: i,
+            server_tick,
+        };
+        sync.on_pong_received(&pong, recv_time, send_time, client_tick);
+    }
+
+    assert!(sync.converged, "should converge after 16 samples");
+    // With 50ms RTT, half_rtt_ticks ≈ 1.5 ticks. Offset should be stable.
+    // The exact offset depends on the math; just check it's within 5 ticks.
+    assert!(
+        (sync.tick_offset as f64).abs() < 5.0,
+        "tick_offset should be stable, got {}",
+        sync.tick_offset
+    );
+}
+
+#[test]
+fn test_rtt_measurement_is_accurate() {
+    let mut rtt_est = RttEstimator::default();
+    let rtt = Duration::from_millis(40);
+    rtt_est.record_sample(rtt);
+
+    assert_eq!(rtt_est.samples.len(), 1);
+    let recorded = rtt_est.samples[0];
+    let diff = recorded.abs_diff(rtt);
+    assert!(
+        diff < Duration::from_millis(1),
+        "recorded RTT should be ~40ms, got {:?}",
+        recorded
+    );
+}
+
+#[test]
+fn test_client_tick_leads_server_by_half_rtt() {
+    let mut sync = ClockSync {
+        min_samples_for_convergence: 1,
+        ..ClockSync::default()
+    };
+
+    let rtt_ns: u64 = 60_000_000; // 60 ms
+    let server_tick = 1000u64;
+    let client_tick = 1000u64;
+
+    let pong = Pong {
+        sequence: 0,
+        server_tick,
+    };
+    sync.on_pong_received(&pong, rtt_ns, 0, client_tick);
+
+    // half RTT = 30ms = 1.8 ticks at 60Hz
+    let expected_lead = 1.8;
+    assert!(
+        (sync.target_lead - expected_lead).abs() < 0.1,
+        "target_lead should be ~1.8, got {}",
+        sync.target_lead
+    );
+}
+
+#[test]
+fn test_clock_sync_handles_jitter() {
+    let mut rtt_est = RttEstimator::default();
+
+    // 14 normal samples at ~50ms
+    for _ in 0..14 {
+        rtt_est.record_sample(Duration::from_millis(50));
+    }
+    // 2 outlier samples at 200ms
+    rtt_est.record_sample(Duration::from_millis(200));
+    rtt_est.record_sample(Duration::from_millis(200));
+
+    let median = rtt_est.median_rtt();
+    // Median of 14×50ms + 2×200ms → 50ms (since 14/16 are 50ms)
+    assert!(
+        median <= Duration::from_millis(55),
+        "median should be ~50ms despite outliers, got {:?}",
+        median
+    );
+}
+
+#[test]
+fn test_tick_numbers_are_monotonic() {
+    let mut counter = TickCounter::new(false);
+    let mut prev = counter.tick;
+
+    for i in 0..1000u64 {
+        counter.advance();
+
+        // Simulate clock adjustments at ticks 100, 300, 500 by computing
+        // adjustment but never decreasing the counter.
+        if i == 100 || i == 300 || i == 500 {
+            let adj = compute_tick_adjustment(0.5);
+            assert_eq!(adj, TickAdjustment::SlowDown);
+            // SlowDown means we might skip an advance next iteration,
+            // but tick never decreases.
+        }
+
+        assert!(
+            counter.tick >= prev,
+            "tick must be monotonic: {} < {}",
+            counter.tick,
+            prev
+        );
+        prev = counter.tick;
+    }
+}
+
+#[test]
+fn test_max_rewind_ticks_tracks_median_rtt() {
+    let mut sync = ClockSync::default();
+    assert_eq!(sync.max_rewind_ticks(), REWIND_SLACK_TICKS);
+
+    for _ in 0..5 {
+        sync.rtt.record_sample(Duration::from_millis(100));
+    }
+    // 100 ms at 60 Hz is 6 ticks.
+    assert_eq!(sync.max_rewind_ticks(), 6 + REWIND_SLACK_TICKS);
+}
+
+// ---------------------------------------------------------------------------
+// Offset estimation and smearing
+// ---------------------------------------------------------------------------
+
+const TICK_NS: u64 = 1_000_000_000 / TICK_RATE as u64;
+
+/// Simulated route to a server whose clock is `offset_ns` ahead of ours.
+struct Link {
+    offset_ns: u64,
+    upstream_ns: u64,
+    downstream_ns: u64,
+}
+
+impl Link {
+    /// Pings at local time `send_ns` and delivers the pong to `sync`.
+    fn ping(&self, sync: &mut ClockSync, send_ns: u64, sequence: u32) -> TickAdjustment {
+        let server_ns = send_ns + self.upstream_ns + self.offset_ns;
+        let pong = Pong {
+            sequence,
+            server_tick: server_ns / TICK_NS,
+        };
+        let receive_ns = send_ns + self.upstream_ns + self.downstream_ns;
+        sync.on_pong_received(&pong, receive_ns, send_ns, send_ns / TICK_NS)
+    }
+}
+
+/// Runs 60 Hz frames for `frames` from `*now`, pinging every 6th frame, and
+/// returns the estimated server time after each frame.
+fn run_frames(sync: &mut ClockSync, link: &Link, now: &mut u64, frames: u32) -> Vec<f64> {
+    (0..frames)
+        .map(|frame| {
+            *now += TICK_NS;
+            if frame % 6 == 0 {
+                link.ping(sync, *now, frame);
+            }
+            sync.advance(*now);
+            sync.estimated_server_time(*now).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_asymmetric_route_converges_to_stable_offset() {
+    let mut sync = ClockSync::default();
+    // 50 ms more delay on the way out than on the way back.
+    let link = Link {
+        offset_ns: 10_000_000_000,
+        upstream_ns: 75_000_000,
+        downstream_ns: 25_000_000,
+    };
+    let mut now = 1_000_000_000;
+    let times = run_frames(&mut sync, &link, &mut now, 3_600);
+
+    // The estimate settles on the true offset biased by half the asymmetry
+    // (and up to one tick of server tick quantization).
+    let expected = (link.offset_ns + 25_000_000) as f64 / 1e9;
+    let errors: Vec<f64> = times[times.len() - 600..]
+        .iter()
+        .enumerate()
+        .map(|(i, &t)| t - ((now - (599 - i as u64) * TICK_NS) as f64 / 1e9) - expected)
+        .collect();
+    let spread = errors.iter().cloned().fold(f64::MIN, f64::max)
+        - errors.iter().cloned().fold(f64::MAX, f64::min);
+    assert!(spread < 0.005, "offset not stable: spread {spread} s");
+    assert!(
+        errors.iter().all(|e| e.abs() < 0.020),
+        "offset {:?} s off the expected bias",
+        errors.last()
+    );
+    assert_eq!(sync.hard_resyncs(), 0);
+}
+
+#[test]
+fn test_step_change_is_smeared_without_jumps() {
+    let mut sync = ClockSync::default();
+    let mut link = Link {
+        offset_ns: 2_000_000_000,
+        upstream_ns: 30_000_000,
+        downstream_ns: 30_000_000,
+    };
+    let mut now = 1_000_000_000;
+    run_frames(&mut sync, &link, &mut now, 600);
+
+    link.offset_ns += 500_000_000;
+    let mut previous = sync.estimated_server_time(now).unwrap();
+    let times = run_frames(&mut sync, &link, &mut now, 3_600);
+
+    let tick = TICK_NS as f64 / 1e9;
+    for &t in &times {
+        let advance = t - previous;
+        assert!(
+            (advance - tick).abs() <= tick * MAX_SMEAR_RATE + 1e-9,
+            "server time advanced {advance} s in one {tick} s tick"
+        );
+        previous = t;
+    }
+    assert!(
+        sync.pending_correction_secs().abs() < 0.005,
+        "step not absorbed: {} s left",
+        sync.pending_correction_secs()
+    );
+    assert!(sync.effective_tick_duration() > TICK_DURATION.mul_f64(0.97));
+    assert_eq!(sync.hard_resyncs(), 0);
+}
+
+#[test]
+fn test_large_discontinuity_forces_hard_resync() {
+    let mut sync = ClockSync::default();
+    let mut link = Link {
+        offset_ns: 2_000_000_000,
+        upstream_ns: 20_000_000,
+        downstream_ns: 20_000_000,
+    };
+    let mut now = 1_000_000_000;
+    run_frames(&mut sync, &link, &mut now, 600);
+
+    link.offset_ns += 5_000_000_000;
+    now += TICK_NS;
+    // A single divergent pong is held back; the second confirms it.
+    assert_eq!(link.ping(&mut sync, now, 1), TickAdjustment::None);
+    assert_eq!(link.ping(&mut sync, now, 2), TickAdjustment::HardReset);
+    assert_eq!(sync.hard_resyncs(), 1);
+
+    let truth = (now + link.offset_ns) as f64 / 1e9;
+    let estimate = sync.estimated_server_time(now).unwrap();
+    assert!((estimate - truth).abs() < 0.020);
+}
+
+#[test]
+fn test_high_rtt_outliers_are_ignored() {
+    let mut sync = ClockSync::default();
+    let link = Link {
+        offset_ns: 1_000_000_000,
+        upstream_ns: 20_000_000,
+        downstream_ns: 20_000_000,
+    };
+    let mut now = 1_000_000_000;
+    run_frames(&mut sync, &link, &mut now, 120);
+    let before = sync.pending_correction_secs();
+    let estimate = sync.estimated_server_time(now).unwrap();
+
+    // A pong stuck 600 ms in a queue on the way back would skew the offset.
+    let congested = Link {
+        downstream_ns: 600_000_000,
+        ..link
+    };
+    assert_eq!(congested.ping(&mut sync, now, 99), TickAdjustment::None);
+    assert_eq!(sync.pending_correction_secs(), before);
+    assert_eq!(sync.estimated_server_time(now).unwrap(), estimate);
+}
+
+#[test]
+fn test_no_server_time_before_first_pong() {
+    let mut sync = ClockSync::default();
+    sync.advance(1_000);
+    assert_eq!(sync.estimated_server_time(1_000), None);
+    assert_eq!(sync.effective_tick_duration(), TICK_DURATION);
+}
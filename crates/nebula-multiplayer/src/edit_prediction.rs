@@ -0,0 +1,327 @@
+//! Client-side prediction for voxel edits with rollback.
+//!
+//! [`apply_voxel_edit`] is server-authoritative, so waiting for the
+//! broadcast [`VoxelEditEvent`] makes block placement lag by a full round
+//! trip. The client instead applies each edit to its local chunks
+//! immediately and records it in a [`PendingEditBuffer`] keyed by an edit
+//! sequence number. When the server's [`EditResponse`] arrives the edit is
+//! either confirmed or dropped, and the voxel is re-derived from the last
+//! server-confirmed value plus any edits still in flight — so a rejection
+//! rolls the voxel back unless a later accepted edit already overwrote it.
+//!
+//! All local writes go through [`nebula_voxel::set_voxel`], which marks the
+//! chunk [`MESH_DIRTY`](nebula_voxel::MESH_DIRTY) and emits a
+//! [`VoxelModifiedEvent`](nebula_voxel::VoxelModifiedEvent), so predictions
+//! and rollbacks remesh through the same path as any other voxel change.
+
+use std::collections::{HashMap, VecDeque};
+
+use nebula_voxel::{ChunkAddress, ChunkManager, VoxelEventBuffer, VoxelTypeId, set_voxel};
+use serde::{Deserialize, Serialize};
+
+use crate::budget::ClientId;
+use crate::chunk_streaming::ChunkId;
+use crate::network_id::NetworkId;
+use crate::voxel_edit::{
+    CHUNK_SIZE, EditRejection, PlayerPosition, ServerChunkStore, VoxelEditEvent, VoxelEditIntent,
+    VoxelEditRateLimiter, VoxelMaterial, apply_voxel_edit, validate_voxel_edit,
+};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Default maximum number of unconfirmed edits (~2 s of edits at 60 Hz).
+pub const DEFAULT_MAX_PENDING_EDITS: usize = 128;
+
+// ---------------------------------------------------------------------------
+// Wire messages
+// ---------------------------------------------------------------------------
+
+/// A [`VoxelEditIntent`] tagged with the client's edit sequence number.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SequencedEditIntent {
+    /// Client-assigned sequence number, echoed in the [`EditResponse`].
+    pub sequence: u32,
+    /// The requested edit.
+    pub intent: VoxelEditIntent,
+}
+
+/// Server reply to a [`SequencedEditIntent`], sent to the editing client.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EditResponse {
+    /// Sequence number of the edit this responds to.
+    pub sequence: u32,
+    /// The applied edit, or the reason it was rejected.
+    pub outcome: Result<VoxelEditEvent, EditRejection>,
+}
+
+impl EditResponse {
+    /// Validates and, if valid, applies `edit` from `client_id` to the
+    /// server's chunk store.
+    pub fn resolve(
+        player_pos: &PlayerPosition,
+        edit: &SequencedEditIntent,
+        store: &mut ServerChunkStore,
+        limiter: &mut VoxelEditRateLimiter,
+        client_id: ClientId,
+        editor_network_id: NetworkId,
+        tick: u64,
+    ) -> Self {
+        let outcome = validate_voxel_edit(player_pos, &edit.intent, store, limiter, client_id)
+            .map(|()| apply_voxel_edit(&edit.intent, store, editor_network_id, tick));
+        Self {
+            sequence: edit.sequence,
+            outcome,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// PendingEditBuffer
+// ---------------------------------------------------------------------------
+
+/// A single voxel, addressed by chunk and local position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct VoxelKey {
+    chunk_id: ChunkId,
+    local: (u8, u8, u8),
+}
+
+impl VoxelKey {
+    fn address(&self) -> ChunkAddress {
+        let id = self.chunk_id;
+        ChunkAddress::new(i64::from(id.x), i64::from(id.y), i64::from(id.z), id.face)
+    }
+}
+
+/// An edit applied locally but not yet confirmed by the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingEdit {
+    /// Edit sequence number.
+    pub sequence: u32,
+    /// The edit sent to the server.
+    pub intent: VoxelEditIntent,
+    /// Material the edit predicted for its voxel.
+    pub predicted: VoxelMaterial,
+}
+
+/// Unconfirmed voxel edits and the server-confirmed values they overlay.
+pub struct PendingEditBuffer {
+    next_sequence: u32,
+    pending: VecDeque<(VoxelKey, PendingEdit)>,
+    /// Last server-confirmed material of every voxel with edits in flight.
+    confirmed: HashMap<VoxelKey, VoxelMaterial>,
+    max_pending: usize,
+}
+
+impl PendingEditBuffer {
+    /// Creates a buffer holding at most `max_pending` unconfirmed edits.
+    pub fn new(max_pending: usize) -> Self {
+        Self {
+            next_sequence: 0,
+            pending: VecDeque::new(),
+            confirmed: HashMap::new(),
+            max_pending: max_pending.max(1),
+        }
+    }
+
+    /// Number of unconfirmed edits.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if no edits are awaiting confirmation.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Iterates over unconfirmed edits, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &PendingEdit> {
+        self.pending.iter().map(|(_, edit)| edit)
+    }
+
+    /// Applies `intent` to the local chunks and records it as pending.
+    ///
+    /// Returns the message to send to the server, or `None` if the edit
+    /// cannot succeed locally (target chunk not loaded, position out of
+    /// bounds, placing into a solid voxel, removing air) or too many edits
+    /// are already unconfirmed.
+    pub fn predict(
+        &mut self,
+        intent: VoxelEditIntent,
+        chunks: &mut ChunkManager,
+        events: &mut VoxelEventBuffer,
+    ) -> Option<SequencedEditIntent> {
+        if self.pending.len() >= self.max_pending {
+            return None;
+        }
+        let (key, predicted) = edit_target(&intent)?;
+        let (x, y, z) = key.local;
+        let current = VoxelMaterial(chunks.get_chunk(&key.address())?.get(x, y, z).0);
+        let allowed = match intent {
+            VoxelEditIntent::Place { .. } => current.is_air(),
+            VoxelEditIntent::Remove { .. } => !current.is_air(),
+        };
+        if !allowed {
+            return None;
+        }
+
+        self.confirmed.entry(key).or_insert(current);
+        set_voxel(
+            chunks,
+            &key.address(),
+            x,
+            y,
+            z,
+            VoxelTypeId(predicted.0),
+            events,
+        );
+
+        let sequence = self.next_sequence;
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        self.pending.push_back((
+            key,
+            PendingEdit {
+                sequence,
+                intent: intent.clone(),
+                predicted,
+            },
+        ));
+        Some(SequencedEditIntent { sequence, intent })
+    }
+
+    /// Reconciles the local chunks with the server's verdict on one edit.
+    ///
+    /// An accepted edit becomes the voxel's confirmed value and supersedes
+    /// older pending edits on the same voxel, since the server applies edits
+    /// in order; a rejected one is dropped. Either way the voxel is rewritten
+    /// to the confirmed value overlaid with the newest edit still pending on
+    /// it. Responses for edits no longer pending are ignored.
+    pub fn reconcile(
+        &mut self,
+        response: &EditResponse,
+        chunks: &mut ChunkManager,
+        events: &mut VoxelEventBuffer,
+    ) {
+        let Some(index) = self
+            .pending
+            .iter()
+            .position(|(_, edit)| edit.sequence == response.sequence)
+        else {
+            return;
+        };
+        let Some((key, _)) = self.pending.remove(index) else {
+            return;
+        };
+
+        match &response.outcome {
+            Ok(event) => {
+                self.confirmed.insert(key, event.new_material);
+                let mut position = 0;
+                self.pending.retain(|(k, _)| {
+                    let keep = position >= index || *k != key;
+                    position += 1;
+                    keep
+                });
+            }
+            Err(reason) => {
+                tracing::debug!("voxel edit {} rejected: {reason}", response.sequence);
+            }
+        }
+        self.resync(key, chunks, events);
+    }
+
+    /// Applies a broadcast [`VoxelEditEvent`] from another player.
+    ///
+    /// Voxels with edits still in flight only have their confirmed value
+    /// updated, so the local prediction stays visible until it resolves.
+    pub fn apply_server_edit(
+        &mut self,
+        event: &VoxelEditEvent,
+        chunks: &mut ChunkManager,
+        events: &mut VoxelEventBuffer,
+    ) {
+        if event.local_x >= CHUNK_SIZE || event.local_y >= CHUNK_SIZE || event.local_z >= CHUNK_SIZE
+        {
+            return;
+        }
+        let key = VoxelKey {
+            chunk_id: event.chunk_id,
+            local: (
+                event.local_x as u8,
+                event.local_y as u8,
+                event.local_z as u8,
+            ),
+        };
+        self.confirmed.insert(key, event.new_material);
+        self.resync(key, chunks, events);
+    }
+
+    /// Rewrites `key` to its confirmed value overlaid with its newest
+    /// pending edit, forgetting the confirmed value once nothing is pending.
+    fn resync(&mut self, key: VoxelKey, chunks: &mut ChunkManager, events: &mut VoxelEventBuffer) {
+        let latest = self
+            .pending
+            .iter()
+            .rev()
+            .find(|(k, _)| *k == key)
+            .map(|(_, edit)| edit.predicted);
+        let target = match latest {
+            Some(material) => material,
+            None => match self.confirmed.remove(&key) {
+                Some(material) => material,
+                None => return,
+            },
+        };
+        let (x, y, z) = key.local;
+        set_voxel(
+            chunks,
+            &key.address(),
+            x,
+            y,
+            z,
+            VoxelTypeId(target.0),
+            events,
+        );
+    }
+}
+
+impl Default for PendingEditBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_PENDING_EDITS)
+    }
+}
+
+/// Voxel targeted by `intent` and the material it would leave there, or
+/// `None` if the local position is outside the chunk.
+fn edit_target(intent: &VoxelEditIntent) -> Option<(VoxelKey, VoxelMaterial)> {
+    let (chunk_id, x, y, z, material) = match *intent {
+        VoxelEditIntent::Place {
+            chunk_id,
+            local_x,
+            local_y,
+            local_z,
+            material,
+            ..
+        } => (chunk_id, local_x, local_y, local_z, material),
+        VoxelEditIntent::Remove {
+            chunk_id,
+            local_x,
+            local_y,
+            local_z,
+        } => (chunk_id, local_x, local_y, local_z, VoxelMaterial::AIR),
+    };
+    if x >= CHUNK_SIZE || y >= CHUNK_SIZE || z >= CHUNK_SIZE {
+        return None;
+    }
+    let key = VoxelKey {
+        chunk_id,
+        local: (x as u8, y as u8, z as u8),
+    };
+    Some((key, material))
+}
+
+#[cfg(test)]
+#[path = "edit_prediction_tests.rs"]
+mod tests;
@@ -0,0 +1,246 @@
+//! Unit tests for predicted voxel edits and their reconciliation.
+
+use nebula_voxel::{Chunk, MESH_DIRTY};
+
+use super::*;
+
+const CHUNK: ChunkId = ChunkId {
+    face: 0,
+    lod: 0,
+    x: 0,
+    y: 0,
+    z: 0,
+};
+
+const NEAR: PlayerPosition = PlayerPosition {
+    x: 500,
+    y: 500,
+    z: 500,
+};
+
+/// Client and server views of the same empty chunk.
+fn worlds() -> (ChunkManager, ServerChunkStore) {
+    let mut chunks = ChunkManager::new();
+    chunks.load_chunk(address(), Chunk::new());
+    let mut store = ServerChunkStore::new();
+    store.load_chunk(CHUNK, VoxelMaterial::AIR);
+    (chunks, store)
+}
+
+fn address() -> ChunkAddress {
+    ChunkAddress::new(0, 0, 0, 0)
+}
+
+fn place(material: VoxelMaterial) -> VoxelEditIntent {
+    VoxelEditIntent::Place {
+        chunk_id: CHUNK,
+        local_x: 1,
+        local_y: 1,
+        local_z: 1,
+        material,
+        source_inventory_slot: 0,
+    }
+}
+
+fn remove() -> VoxelEditIntent {
+    VoxelEditIntent::Remove {
+        chunk_id: CHUNK,
+        local_x: 1,
+        local_y: 1,
+        local_z: 1,
+    }
+}
+
+fn client_voxel(chunks: &ChunkManager) -> VoxelMaterial {
+    VoxelMaterial(chunks.get_chunk(&address()).unwrap().get(1, 1, 1).0)
+}
+
+#[test]
+fn test_accepted_placement_stays_and_empties_buffer() {
+    let (mut chunks, mut store) = worlds();
+    let mut limiter = VoxelEditRateLimiter::default();
+    let mut events = VoxelEventBuffer::new();
+    let mut buffer = PendingEditBuffer::default();
+
+    let sent = buffer
+        .predict(place(VoxelMaterial::STONE), &mut chunks, &mut events)
+        .unwrap();
+    // Applied immediately, before any server round trip.
+    assert_eq!(client_voxel(&chunks), VoxelMaterial::STONE);
+    assert_eq!(buffer.len(), 1);
+
+    let response = EditResponse::resolve(
+        &NEAR,
+        &sent,
+        &mut store,
+        &mut limiter,
+        1,
+        NetworkId::new(1),
+        3,
+    );
+    assert!(response.outcome.is_ok());
+    buffer.reconcile(&response, &mut chunks, &mut events);
+
+    assert_eq!(client_voxel(&chunks), VoxelMaterial::STONE);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_rejection_rolls_back_and_remeshes() {
+    let (mut chunks, mut store) = worlds();
+    let mut limiter = VoxelEditRateLimiter::default();
+    let mut events = VoxelEventBuffer::new();
+    let mut buffer = PendingEditBuffer::default();
+
+    let sent = buffer
+        .predict(place(VoxelMaterial::STONE), &mut chunks, &mut events)
+        .unwrap();
+    chunks
+        .get_chunk_mut(&address())
+        .unwrap()
+        .clear_dirty(MESH_DIRTY);
+    events.clear();
+
+    let far = PlayerPosition {
+        x: 1_000_000,
+        y: 0,
+        z: 0,
+    };
+    let response = EditResponse::resolve(
+        &far,
+        &sent,
+        &mut store,
+        &mut limiter,
+        1,
+        NetworkId::new(1),
+        3,
+    );
+    assert_eq!(response.outcome, Err(EditRejection::OutOfRange));
+    buffer.reconcile(&response, &mut chunks, &mut events);
+
+    assert_eq!(client_voxel(&chunks), VoxelMaterial::AIR);
+    assert!(buffer.is_empty());
+    assert!(chunks.get_chunk(&address()).unwrap().is_dirty(MESH_DIRTY));
+    let rollback = events.read().next().unwrap();
+    assert_eq!(rollback.old_type, VoxelTypeId(VoxelMaterial::STONE.0));
+    assert_eq!(rollback.new_type, VoxelTypeId(0));
+}
+
+#[test]
+fn test_overlapping_edits_converge_to_server_state() {
+    // Delivery order of the two responses: in order, then swapped.
+    for swap in [false, true] {
+        let (mut chunks, mut store) = worlds();
+        let mut limiter = VoxelEditRateLimiter::default();
+        let mut events = VoxelEventBuffer::new();
+        let mut buffer = PendingEditBuffer::default();
+
+        // Place stone, then change our mind and break it again.
+        let first = buffer
+            .predict(place(VoxelMaterial::STONE), &mut chunks, &mut events)
+            .unwrap();
+        let second = buffer.predict(remove(), &mut chunks, &mut events).unwrap();
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(client_voxel(&chunks), VoxelMaterial::AIR);
+
+        // Another player's dirt reaches the server first, so our placement
+        // is obstructed but our removal breaks their dirt.
+        let theirs = apply_voxel_edit(
+            &place(VoxelMaterial::DIRT),
+            &mut store,
+            NetworkId::new(2),
+            1,
+        );
+        let rejected = EditResponse::resolve(
+            &NEAR,
+            &first,
+            &mut store,
+            &mut limiter,
+            1,
+            NetworkId::new(1),
+            2,
+        );
+        let accepted = EditResponse::resolve(
+            &NEAR,
+            &second,
+            &mut store,
+            &mut limiter,
+            1,
+            NetworkId::new(1),
+            2,
+        );
+        assert_eq!(rejected.outcome, Err(EditRejection::Obstructed));
+        assert!(accepted.outcome.is_ok());
+
+        buffer.apply_server_edit(&theirs, &mut chunks, &mut events);
+        // Still showing our own prediction while edits are in flight.
+        assert_eq!(client_voxel(&chunks), VoxelMaterial::AIR);
+        let responses = if swap {
+            [&accepted, &rejected]
+        } else {
+            [&rejected, &accepted]
+        };
+        for response in responses {
+            buffer.reconcile(response, &mut chunks, &mut events);
+        }
+
+        let server = store.get_voxel(&CHUNK, 1, 1, 1).unwrap();
+        assert_eq!(server, VoxelMaterial::AIR);
+        assert_eq!(client_voxel(&chunks), server, "swap = {swap}");
+        assert!(buffer.is_empty());
+    }
+}
+
+#[test]
+fn test_remote_edit_on_idle_voxel_is_applied() {
+    let (mut chunks, mut store) = worlds();
+    let mut events = VoxelEventBuffer::new();
+    let mut buffer = PendingEditBuffer::default();
+
+    let theirs = apply_voxel_edit(
+        &place(VoxelMaterial::DIRT),
+        &mut store,
+        NetworkId::new(2),
+        1,
+    );
+    buffer.apply_server_edit(&theirs, &mut chunks, &mut events);
+    assert_eq!(client_voxel(&chunks), VoxelMaterial::DIRT);
+}
+
+#[test]
+fn test_locally_impossible_edits_are_not_predicted() {
+    let (mut chunks, _) = worlds();
+    let mut events = VoxelEventBuffer::new();
+    let mut buffer = PendingEditBuffer::new(1);
+
+    // Removing air and editing an unloaded chunk are refused.
+    assert!(buffer.predict(remove(), &mut chunks, &mut events).is_none());
+    let elsewhere = VoxelEditIntent::Remove {
+        chunk_id: ChunkId { x: 9, ..CHUNK },
+        local_x: 0,
+        local_y: 0,
+        local_z: 0,
+    };
+    assert!(
+        buffer
+            .predict(elsewhere, &mut chunks, &mut events)
+            .is_none()
+    );
+
+    // The buffer is bounded.
+    assert!(
+        buffer
+            .predict(place(VoxelMaterial::STONE), &mut chunks, &mut events)
+            .is_some()
+    );
+    let next = VoxelEditIntent::Place {
+        chunk_id: CHUNK,
+        local_x: 2,
+        local_y: 1,
+        local_z: 1,
+        material: VoxelMaterial::STONE,
+        source_inventory_slot: 0,
+    };
+    assert!(buffer.predict(next, &mut chunks, &mut events).is_none());
+    assert_eq!(events.len(), 1);
+}
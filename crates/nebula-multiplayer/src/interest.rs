@@ -1,12 +1,22 @@
 //! Spatial interest management: determines which entities are relevant to
 //! each connected client based on proximity, and produces spawn/despawn
 //! transitions when entities enter or leave a client's interest area.
-
-use std::collections::HashSet;
-
+//!
+//! A client's [`InterestShape`] is either a Euclidean radius or a cube of
+//! chunks around the client's chunk, matching how the voxel world streams.
+//! The chunk shape has a hysteresis margin so an entity hovering on the
+//! boundary does not spawn and despawn every tick.
+//!
+//! Entities are bucketed into coarse interest sectors each tick, so a client
+//! only tests entities in the sectors its shape can reach instead of every
+//! entity in the world.
+
+use std::collections::{HashMap, HashSet};
+
+use nebula_voxel::{CHUNK_SIZE, ChunkAddress};
 use serde::{Deserialize, Serialize};
 
-use crate::replication::NetworkId;
+use crate::network_id::NetworkId;
 
 // ---------------------------------------------------------------------------
 // InterestArea
@@ -26,6 +36,75 @@ impl Default for InterestArea {
     }
 }
 
+// ---------------------------------------------------------------------------
+// InterestShape
+// ---------------------------------------------------------------------------
+
+/// Largest interest radius in meters. Larger radii are clamped to it.
+pub const MAX_INTEREST_RADIUS: f64 = 8_192.0;
+
+/// How a client's interest region is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum InterestShape {
+    /// Entities within `radius` meters (see [`InterestArea`]).
+    Radius {
+        /// Radius of the interest sphere in meters.
+        radius: f64,
+    },
+    /// Entities whose chunk is within `radius_chunks` of the client's chunk
+    /// on every axis.
+    ///
+    /// An entity already in the set only leaves once it is more than
+    /// `radius_chunks + unload_margin` chunks away.
+    Chunks {
+        /// Chunks around the client's chunk that bring an entity into interest.
+        radius_chunks: u8,
+        /// Extra chunks an entity may drift past `radius_chunks` before it
+        /// leaves interest.
+        unload_margin: u8,
+    },
+}
+
+impl Default for InterestShape {
+    fn default() -> Self {
+        InterestArea::default().into()
+    }
+}
+
+impl From<InterestArea> for InterestShape {
+    fn from(area: InterestArea) -> Self {
+        Self::Radius {
+            radius: area.radius,
+        }
+    }
+}
+
+impl InterestShape {
+    /// This shape with its radius clamped to [`MAX_INTEREST_RADIUS`], or
+    /// `None` if the radius is negative or not finite.
+    pub fn validated(self) -> Option<Self> {
+        match self {
+            Self::Radius { radius } if radius.is_finite() && radius >= 0.0 => Some(Self::Radius {
+                radius: radius.min(MAX_INTEREST_RADIUS),
+            }),
+            Self::Radius { .. } => None,
+            Self::Chunks { .. } => Some(self),
+        }
+    }
+
+    /// Farthest distance in meters at which an entity can still be in
+    /// interest, used to pick candidate sectors.
+    fn reach_meters(&self) -> f64 {
+        match *self {
+            Self::Radius { radius } => radius,
+            Self::Chunks {
+                radius_chunks,
+                unload_margin,
+            } => (f64::from(radius_chunks) + f64::from(unload_margin) + 1.0) * CHUNK_EXTENT,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // ClientInterestSet
 // ---------------------------------------------------------------------------
@@ -104,6 +183,42 @@ impl InterestPosition {
     pub fn new(x: f64, y: f64, z: f64) -> Self {
         Self { x, y, z }
     }
+
+    /// Whether every coordinate is finite.
+    pub fn is_finite(&self) -> bool {
+        self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
+    }
+}
+
+/// Edge length of a chunk in meters (one voxel per meter).
+const CHUNK_EXTENT: f64 = CHUNK_SIZE as f64;
+
+/// Edge length of an interest sector in chunks. Coarse enough that a client
+/// visits few sectors, fine enough that each holds few entities.
+const SECTOR_CHUNKS: i64 = 8;
+
+/// Returns the chunk containing `position`.
+///
+/// Interest uses a single flat chunk grid, so `face` is always 0.
+pub fn chunk_address(position: &InterestPosition) -> ChunkAddress {
+    let cell = |v: f64| (v / CHUNK_EXTENT).floor() as i64;
+    ChunkAddress::new(cell(position.x), cell(position.y), cell(position.z), 0)
+}
+
+/// Largest per-axis distance between two chunks, in chunks.
+fn chunk_distance(a: &ChunkAddress, b: &ChunkAddress) -> u64 {
+    a.x.abs_diff(b.x)
+        .max(a.y.abs_diff(b.y))
+        .max(a.z.abs_diff(b.z))
+}
+
+/// Interest sector containing `chunk`.
+fn sector_of(chunk: &ChunkAddress) -> (i64, i64, i64) {
+    (
+        chunk.x.div_euclid(SECTOR_CHUNKS),
+        chunk.y.div_euclid(SECTOR_CHUNKS),
+        chunk.z.div_euclid(SECTOR_CHUNKS),
+    )
 }
 
 /// Returns `true` if positions `a` and `b` are within `radius` meters of
@@ -129,205 +244,228 @@ pub struct TrackedEntity {
     pub position: InterestPosition,
 }
 
+/// Work done by the most recent [`SpatialInterestSystem::evaluate`] call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InterestEvaluationStats {
+    /// Occupied sectors visited across all clients.
+    pub sectors_visited: usize,
+    /// Entities tested against a client's shape across all clients.
+    pub entities_tested: usize,
+}
+
+/// Interest state for a single registered client.
+#[derive(Debug, Clone)]
+struct ClientInterest {
+    set: ClientInterestSet,
+    shape: InterestShape,
+    position: InterestPosition,
+}
+
+impl ClientInterest {
+    /// Whether `entity` belongs in this client's interest set this tick.
+    fn contains(&self, entity: &TrackedEntity, entity_chunk: &ChunkAddress) -> bool {
+        match self.shape {
+            InterestShape::Radius { radius } => {
+                within_interest(&self.position, &entity.position, radius)
+            }
+            InterestShape::Chunks {
+                radius_chunks,
+                unload_margin,
+            } => {
+                let distance = chunk_distance(&chunk_address(&self.position), entity_chunk);
+                let limit = if self.set.previous.contains(&entity.network_id) {
+                    u64::from(radius_chunks) + u64::from(unload_margin)
+                } else {
+                    u64::from(radius_chunks)
+                };
+                distance <= limit
+            }
+        }
+    }
+}
+
 /// Server-side system that evaluates spatial interest for all connected
 /// clients each tick. For each client it determines which entities fall
-/// within the client's [`InterestArea`] and computes [`InterestTransitions`].
+/// within the client's [`InterestShape`] and computes [`InterestTransitions`].
 #[derive(Debug, Default)]
 pub struct SpatialInterestSystem {
     /// Per-client interest tracking, keyed by client ID.
-    interest_sets: Vec<(u64, ClientInterestSet, InterestArea, InterestPosition)>,
+    clients: Vec<(u64, ClientInterest)>,
+    /// Work done by the last evaluation.
+    last_stats: InterestEvaluationStats,
 }
 
 impl SpatialInterestSystem {
     /// Creates an empty spatial interest system.
     pub fn new() -> Self {
-        Self {
-            interest_sets: Vec::new(),
-        }
+        Self::default()
     }
 
-    /// Registers a client with a given interest area and initial position.
-    pub fn add_client(&mut self, client_id: u64, area: InterestArea, position: InterestPosition) {
-        self.interest_sets
-            .push((client_id, ClientInterestSet::new(client_id), area, position));
+    /// Registers a client with a given interest shape and initial position.
+    ///
+    /// Accepts an [`InterestArea`] for radius-based interest. Returns `false`
+    /// and leaves the client unregistered if the shape is rejected by
+    /// [`InterestShape::validated`] or the position is not finite.
+    pub fn add_client(
+        &mut self,
+        client_id: u64,
+        shape: impl Into<InterestShape>,
+        position: InterestPosition,
+    ) -> bool {
+        let Some(shape) = shape.into().validated() else {
+            return false;
+        };
+        if !position.is_finite() {
+            return false;
+        }
+        self.clients.push((
+            client_id,
+            ClientInterest {
+                set: ClientInterestSet::new(client_id),
+                shape,
+                position,
+            },
+        ));
+        true
     }
 
     /// Removes a client from interest tracking.
     pub fn remove_client(&mut self, client_id: u64) {
-        self.interest_sets.retain(|(id, _, _, _)| *id != client_id);
+        self.clients.retain(|(id, _)| *id != client_id);
     }
 
-    /// Updates a client's position.
-    pub fn set_client_position(&mut self, client_id: u64, position: InterestPosition) {
-        if let Some(entry) = self
-            .interest_sets
+    fn client_mut(&mut self, client_id: u64) -> Option<&mut ClientInterest> {
+        self.clients
             .iter_mut()
-            .find(|(id, _, _, _)| *id == client_id)
-        {
-            entry.3 = position;
+            .find(|(id, _)| *id == client_id)
+            .map(|(_, client)| client)
+    }
+
+    /// Updates a client's position. Non-finite positions are ignored.
+    pub fn set_client_position(&mut self, client_id: u64, position: InterestPosition) {
+        if !position.is_finite() {
+            return;
+        }
+        if let Some(client) = self.client_mut(client_id) {
+            client.position = position;
         }
     }
 
-    /// Updates a client's interest area radius.
-    pub fn set_client_radius(&mut self, client_id: u64, radius: f64) {
-        if let Some(entry) = self
-            .interest_sets
-            .iter_mut()
-            .find(|(id, _, _, _)| *id == client_id)
-        {
-            entry.2.radius = radius;
+    /// Updates a client's interest area radius, switching it to
+    /// [`InterestShape::Radius`] if it used chunk interest.
+    ///
+    /// Returns `false` if the client is unknown or the radius is rejected
+    /// (see [`set_client_shape`](Self::set_client_shape)).
+    pub fn set_client_radius(&mut self, client_id: u64, radius: f64) -> bool {
+        self.set_client_shape(client_id, InterestShape::Radius { radius })
+    }
+
+    /// Replaces a client's interest shape.
+    ///
+    /// The shape goes through [`InterestShape::validated`]: large radii are
+    /// clamped, and a negative or non-finite radius leaves the old shape in
+    /// place and returns `false`. Also returns `false` for unknown clients.
+    pub fn set_client_shape(&mut self, client_id: u64, shape: InterestShape) -> bool {
+        let Some(shape) = shape.validated() else {
+            return false;
+        };
+        match self.client_mut(client_id) {
+            Some(client) => {
+                client.shape = shape;
+                true
+            }
+            None => false,
         }
     }
 
     /// Runs one interest evaluation tick. For each client, determines which
     /// of the given `entities` are within range and returns per-client
     /// [`InterestTransitions`].
+    ///
+    /// Entities are bucketed by sector once, then each client only tests the
+    /// entities in sectors its shape can reach. When the reachable cube holds
+    /// more sectors than are occupied, the occupied sectors are scanned
+    /// instead, so a wide shape never walks empty space.
     pub fn evaluate(&mut self, entities: &[TrackedEntity]) -> Vec<(u64, InterestTransitions)> {
+        let chunks: Vec<ChunkAddress> = entities
+            .iter()
+            .map(|e| chunk_address(&e.position))
+            .collect();
+        let mut sectors: HashMap<(i64, i64, i64), Vec<usize>> = HashMap::new();
+        for (index, chunk) in chunks.iter().enumerate() {
+            sectors.entry(sector_of(chunk)).or_default().push(index);
+        }
+
+        let sector_extent = SECTOR_CHUNKS as f64 * CHUNK_EXTENT;
+        let mut stats = InterestEvaluationStats::default();
         let mut results = Vec::new();
 
-        for (client_id, interest_set, area, client_pos) in &mut self.interest_sets {
+        for (client_id, client) in &mut self.clients {
             // Advance: move current → previous, clear current.
-            interest_set.advance();
-
-            // Populate current set with entities within range.
-            for entity in entities {
-                if within_interest(client_pos, &entity.position, area.radius) {
-                    interest_set.current.insert(entity.network_id);
+            client.set.advance();
+
+            let center = sector_of(&chunk_address(&client.position));
+            let reach = (client.shape.reach_meters() / sector_extent).ceil() as u64;
+            let in_reach = |key: &(i64, i64, i64)| {
+                key.0.abs_diff(center.0) <= reach
+                    && key.1.abs_diff(center.1) <= reach
+                    && key.2.abs_diff(center.2) <= reach
+            };
+            let side = 2 * reach + 1;
+            let candidates: Vec<&Vec<usize>> = if side.saturating_pow(3) > sectors.len() as u64 {
+                sectors
+                    .iter()
+                    .filter(|(key, _)| in_reach(key))
+                    .map(|(_, bucket)| bucket)
+                    .collect()
+            } else {
+                let reach = reach as i64;
+                let mut found = Vec::new();
+                for dx in -reach..=reach {
+                    for dy in -reach..=reach {
+                        for dz in -reach..=reach {
+                            let key = (center.0 + dx, center.1 + dy, center.2 + dz);
+                            if let Some(bucket) = sectors.get(&key) {
+                                found.push(bucket);
+                            }
+                        }
+                    }
+                }
+                found
+            };
+            for bucket in candidates {
+                stats.sectors_visited += 1;
+                for &index in bucket {
+                    stats.entities_tested += 1;
+                    let entity = &entities[index];
+                    if client.contains(entity, &chunks[index]) {
+                        client.set.current.insert(entity.network_id);
+                    }
                 }
             }
 
-            let transitions = interest_set.compute_transitions();
+            let transitions = client.set.compute_transitions();
             results.push((*client_id, transitions));
         }
 
+        self.last_stats = stats;
         results
     }
 
+    /// Work done by the most recent [`evaluate`](Self::evaluate) call.
+    pub fn last_evaluation_stats(&self) -> InterestEvaluationStats {
+        self.last_stats
+    }
+
     /// Returns the current interest set for a client, if registered.
     pub fn interest_set(&self, client_id: u64) -> Option<&ClientInterestSet> {
-        self.interest_sets
+        self.clients
             .iter()
-            .find(|(id, _, _, _)| *id == client_id)
-            .map(|(_, set, _, _)| set)
+            .find(|(id, _)| *id == client_id)
+            .map(|(_, client)| &client.set)
     }
 }
 
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
-
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn origin() -> InterestPosition {
-        InterestPosition::new(0.0, 0.0, 0.0)
-    }
-
-    #[test]
-    fn test_entity_inside_area_is_replicated() {
-        let mut sys = SpatialInterestSystem::new();
-        sys.add_client(1, InterestArea { radius: 500.0 }, origin());
-
-        let entities = vec![TrackedEntity {
-            network_id: NetworkId(10),
-            position: InterestPosition::new(100.0, 0.0, 0.0),
-        }];
-
-        let results = sys.evaluate(&entities);
-        assert_eq!(results.len(), 1);
-
-        let (client_id, transitions) = &results[0];
-        assert_eq!(*client_id, 1);
-        assert!(transitions.entered.contains(&NetworkId(10)));
-        assert!(transitions.exited.is_empty());
-
-        // Verify interest set contains the entity.
-        let set = sys.interest_set(1).unwrap();
-        assert!(set.current.contains(&NetworkId(10)));
-    }
-
-    #[test]
-    fn test_entity_outside_area_is_not_replicated() {
-        let mut sys = SpatialInterestSystem::new();
-        sys.add_client(1, InterestArea { radius: 500.0 }, origin());
-
-        let entities = vec![TrackedEntity {
-            network_id: NetworkId(20),
-            position: InterestPosition::new(1000.0, 0.0, 0.0),
-        }];
-
-        let results = sys.evaluate(&entities);
-        let (_, transitions) = &results[0];
-        assert!(transitions.entered.is_empty());
-        assert!(transitions.exited.is_empty());
-
-        let set = sys.interest_set(1).unwrap();
-        assert!(!set.current.contains(&NetworkId(20)));
-    }
-
-    #[test]
-    fn test_entity_entering_area_triggers_full_state_send() {
-        let mut sys = SpatialInterestSystem::new();
-        sys.add_client(1, InterestArea { radius: 500.0 }, origin());
-
-        // Tick 1: entity at 600m (outside).
-        let entities_far = vec![TrackedEntity {
-            network_id: NetworkId(30),
-            position: InterestPosition::new(600.0, 0.0, 0.0),
-        }];
-        let r1 = sys.evaluate(&entities_far);
-        assert!(r1[0].1.entered.is_empty());
-
-        // Tick 2: entity moves to 400m (inside).
-        let entities_near = vec![TrackedEntity {
-            network_id: NetworkId(30),
-            position: InterestPosition::new(400.0, 0.0, 0.0),
-        }];
-        let r2 = sys.evaluate(&entities_near);
-        assert!(r2[0].1.entered.contains(&NetworkId(30)));
-    }
-
-    #[test]
-    fn test_entity_leaving_area_triggers_despawn() {
-        let mut sys = SpatialInterestSystem::new();
-        sys.add_client(1, InterestArea { radius: 500.0 }, origin());
-
-        // Tick 1: entity at 400m (inside).
-        let entities_near = vec![TrackedEntity {
-            network_id: NetworkId(40),
-            position: InterestPosition::new(400.0, 0.0, 0.0),
-        }];
-        let r1 = sys.evaluate(&entities_near);
-        assert!(r1[0].1.entered.contains(&NetworkId(40)));
-
-        // Tick 2: entity moves to 600m (outside).
-        let entities_far = vec![TrackedEntity {
-            network_id: NetworkId(40),
-            position: InterestPosition::new(600.0, 0.0, 0.0),
-        }];
-        let r2 = sys.evaluate(&entities_far);
-        assert!(r2[0].1.exited.contains(&NetworkId(40)));
-    }
-
-    #[test]
-    fn test_interest_radius_is_configurable() {
-        let mut sys = SpatialInterestSystem::new();
-        sys.add_client(1, InterestArea { radius: 200.0 }, origin());
-
-        let entities = vec![TrackedEntity {
-            network_id: NetworkId(50),
-            position: InterestPosition::new(300.0, 0.0, 0.0),
-        }];
-
-        // With radius 200, entity at 300m is outside.
-        let r1 = sys.evaluate(&entities);
-        assert!(r1[0].1.entered.is_empty());
-
-        // Change radius to 400m.
-        sys.set_client_radius(1, 400.0);
-
-        let r2 = sys.evaluate(&entities);
-        assert!(r2[0].1.entered.contains(&NetworkId(50)));
-    }
-}
+#[path = "interest_tests.rs"]
+mod tests;
@@ -0,0 +1,284 @@
+//! Unit tests for spatial interest management.
+
+use super::*;
+
+fn origin() -> InterestPosition {
+    InterestPosition::new(0.0, 0.0, 0.0)
+}
+
+#[test]
+fn test_entity_inside_area_is_replicated() {
+    let mut sys = SpatialInterestSystem::new();
+    sys.add_client(1, InterestArea { radius: 500.0 }, origin());
+
+    let entities = vec![TrackedEntity {
+        network_id: NetworkId::new(10),
+        position: InterestPosition::new(100.0, 0.0, 0.0),
+    }];
+
+    let results = sys.evaluate(&entities);
+    assert_eq!(results.len(), 1);
+
+    let (client_id, transitions) = &results[0];
+    assert_eq!(*client_id, 1);
+    assert!(transitions.entered.contains(&NetworkId::new(10)));
+    assert!(transitions.exited.is_empty());
+
+    // Verify interest set contains the entity.
+    let set = sys.interest_set(1).unwrap();
+    assert!(set.current.contains(&NetworkId::new(10)));
+}
+
+#[test]
+fn test_entity_outside_area_is_not_replicated() {
+    let mut sys = SpatialInterestSystem::new();
+    sys.add_client(1, InterestArea { radius: 500.0 }, origin());
+
+    let entities = vec![TrackedEntity {
+        network_id: NetworkId::new(20),
+        position: InterestPosition::new(1000.0, 0.0, 0.0),
+    }];
+
+    let results = sys.evaluate(&entities);
+    let (_, transitions) = &results[0];
+    assert!(transitions.entered.is_empty());
+    assert!(transitions.exited.is_empty());
+
+    let set = sys.interest_set(1).unwrap();
+    assert!(!set.current.contains(&NetworkId::new(20)));
+}
+
+#[test]
+fn test_entity_entering_area_triggers_full_state_send() {
+    let mut sys = SpatialInterestSystem::new();
+    sys.add_client(1, InterestArea { radius: 500.0 }, origin());
+
+    // Tick 1: entity at 600m (outside).
+    let entities_far = vec![TrackedEntity {
+        network_id: NetworkId::new(30),
+        position: InterestPosition::new(600.0, 0.0, 0.0),
+    }];
+    let r1 = sys.evaluate(&entities_far);
+    assert!(r1[0].1.entered.is_empty());
+
+    // Tick 2: entity moves to 400m (inside).
+    let entities_near = vec![TrackedEntity {
+        network_id: NetworkId::new(30),
+        position: InterestPosition::new(400.0, 0.0, 0.0),
+    }];
+    let r2 = sys.evaluate(&entities_near);
+    assert!(r2[0].1.entered.contains(&NetworkId::new(30)));
+}
+
+#[test]
+fn test_entity_leaving_area_triggers_despawn() {
+    let mut sys = SpatialInterestSystem::new();
+    sys.add_client(1, InterestArea { radius: 500.0 }, origin());
+
+    // Tick 1: entity at 400m (inside).
+    let entities_near = vec![TrackedEntity {
+        network_id: NetworkId::new(40),
+        position: InterestPosition::new(400.0, 0.0, 0.0),
+    }];
+    let r1 = sys.evaluate(&entities_near);
+    assert!(r1[0].1.entered.contains(&NetworkId::new(40)));
+
+    // Tick 2: entity moves to 600m (outside).
+    let entities_far = vec![TrackedEntity {
+        network_id: NetworkId::new(40),
+        position: InterestPosition::new(600.0, 0.0, 0.0),
+    }];
+    let r2 = sys.evaluate(&entities_far);
+    assert!(r2[0].1.exited.contains(&NetworkId::new(40)));
+}
+
+#[test]
+fn test_interest_radius_is_configurable() {
+    let mut sys = SpatialInterestSystem::new();
+    sys.add_client(1, InterestArea { radius: 200.0 }, origin());
+
+    let entities = vec![TrackedEntity {
+        network_id: NetworkId::new(50),
+        position: InterestPosition::new(300.0, 0.0, 0.0),
+    }];
+
+    // With radius 200, entity at 300m is outside.
+    let r1 = sys.evaluate(&entities);
+    assert!(r1[0].1.entered.is_empty());
+
+    // Change radius to 400m.
+    sys.set_client_radius(1, 400.0);
+
+    let r2 = sys.evaluate(&entities);
+    assert!(r2[0].1.entered.contains(&NetworkId::new(50)));
+}
+
+fn chunk_shape(radius_chunks: u8, unload_margin: u8) -> InterestShape {
+    InterestShape::Chunks {
+        radius_chunks,
+        unload_margin,
+    }
+}
+
+fn entity_at(id: u32, x: f64) -> Vec<TrackedEntity> {
+    vec![TrackedEntity {
+        network_id: NetworkId::new(id),
+        position: InterestPosition::new(x, 0.0, 0.0),
+    }]
+}
+
+#[test]
+fn test_chunk_interest_uses_chunk_distance() {
+    let mut sys = SpatialInterestSystem::new();
+    // Client in chunk 0; radius 2 covers chunks -2..=2.
+    sys.add_client(1, chunk_shape(2, 0), InterestPosition::new(5.0, 5.0, 5.0));
+
+    let edge = 3.0 * CHUNK_EXTENT - 0.5; // last metre of chunk 2
+    let r = sys.evaluate(&entity_at(60, edge));
+    assert!(r[0].1.entered.contains(&NetworkId::new(60)));
+
+    let r = sys.evaluate(&entity_at(60, edge + 1.0)); // chunk 3
+    assert!(r[0].1.exited.contains(&NetworkId::new(60)));
+}
+
+#[test]
+fn test_oscillation_across_edge_with_margin_produces_no_transitions() {
+    let mut sys = SpatialInterestSystem::new();
+    sys.add_client(1, chunk_shape(2, 1), origin());
+
+    // Boundary between chunk 2 (inside) and chunk 3 (margin).
+    let boundary = 3.0 * CHUNK_EXTENT;
+    let r = sys.evaluate(&entity_at(70, boundary - 1.0));
+    assert!(r[0].1.entered.contains(&NetworkId::new(70)));
+
+    for tick in 0..20 {
+        let x = if tick % 2 == 0 {
+            boundary + 1.0
+        } else {
+            boundary - 1.0
+        };
+        let r = sys.evaluate(&entity_at(70, x));
+        assert!(r[0].1.entered.is_empty(), "tick {tick}: spurious enter");
+        assert!(r[0].1.exited.is_empty(), "tick {tick}: spurious exit");
+    }
+
+    // Leaving the margin does exit.
+    let r = sys.evaluate(&entity_at(70, boundary + CHUNK_EXTENT + 1.0));
+    assert!(r[0].1.exited.contains(&NetworkId::new(70)));
+}
+
+#[test]
+fn test_margin_does_not_extend_entry() {
+    let mut sys = SpatialInterestSystem::new();
+    sys.add_client(1, chunk_shape(2, 1), origin());
+
+    // Chunk 3 is in the margin, but the entity was never inside.
+    let r = sys.evaluate(&entity_at(80, 3.0 * CHUNK_EXTENT + 1.0));
+    assert!(r[0].1.entered.is_empty());
+}
+
+#[test]
+fn test_evaluate_touches_only_candidate_sectors() {
+    let mut sys = SpatialInterestSystem::new();
+    sys.add_client(1, chunk_shape(2, 1), origin());
+
+    // 5000 entities spread along a 1000-sector line, 5 per sector.
+    let sector_extent = SECTOR_CHUNKS as f64 * CHUNK_EXTENT;
+    let entities: Vec<TrackedEntity> = (0..5000)
+        .map(|i| TrackedEntity {
+            network_id: NetworkId::new(i),
+            position: InterestPosition::new(
+                (i / 5) as f64 * sector_extent + (i % 5) as f64,
+                0.0,
+                0.0,
+            ),
+        })
+        .collect();
+
+    sys.evaluate(&entities);
+    let stats = sys.last_evaluation_stats();
+    // Reach of 4 chunks stays within the neighbouring sectors.
+    assert!(stats.sectors_visited <= 2, "{stats:?}");
+    assert!(stats.entities_tested <= 10, "{stats:?}");
+    assert_eq!(sys.interest_set(1).unwrap().current.len(), 5);
+}
+
+#[test]
+fn test_radius_mode_scales_with_sectors() {
+    let mut sys = SpatialInterestSystem::new();
+    for client in 0..10 {
+        sys.add_client(
+            client,
+            InterestArea { radius: 100.0 },
+            InterestPosition::new(client as f64 * 10_000.0, 0.0, 0.0),
+        );
+    }
+    let entities: Vec<TrackedEntity> = (0..2000)
+        .map(|i| TrackedEntity {
+            network_id: NetworkId::new(i),
+            position: InterestPosition::new(i as f64 * 50.0, 0.0, 0.0),
+        })
+        .collect();
+
+    sys.evaluate(&entities);
+    let stats = sys.last_evaluation_stats();
+    assert!(
+        stats.entities_tested < entities.len(),
+        "tested {} of {} entities for 10 clients",
+        stats.entities_tested,
+        entities.len()
+    );
+}
+
+#[test]
+fn test_huge_radius_is_clamped_and_evaluates_quickly() {
+    let mut sys = SpatialInterestSystem::new();
+    assert!(sys.add_client(1, InterestArea { radius: 1.0e12 }, origin()));
+    let entities = vec![
+        TrackedEntity {
+            network_id: NetworkId::new(1),
+            position: InterestPosition::new(MAX_INTEREST_RADIUS - 1.0, 0.0, 0.0),
+        },
+        TrackedEntity {
+            network_id: NetworkId::new(2),
+            position: InterestPosition::new(MAX_INTEREST_RADIUS * 2.0, 0.0, 0.0),
+        },
+    ];
+
+    let start = std::time::Instant::now();
+    let r = sys.evaluate(&entities);
+    assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    assert_eq!(r[0].1.entered, HashSet::from([NetworkId::new(1)]));
+    // Only the occupied sector in reach is visited, not the whole reach cube.
+    assert_eq!(sys.last_evaluation_stats().sectors_visited, 1);
+}
+
+#[test]
+fn test_non_finite_radius_and_position_are_rejected() {
+    let mut sys = SpatialInterestSystem::new();
+    assert!(!sys.add_client(
+        1,
+        InterestArea {
+            radius: f64::INFINITY
+        },
+        origin()
+    ));
+    assert!(!sys.add_client(1, InterestArea { radius: f64::NAN }, origin()));
+    assert!(!sys.add_client(
+        1,
+        InterestArea { radius: 10.0 },
+        InterestPosition::new(f64::NAN, 0.0, 0.0)
+    ));
+    assert!(sys.interest_set(1).is_none());
+
+    assert!(sys.add_client(1, InterestArea { radius: 100.0 }, origin()));
+    assert!(!sys.set_client_radius(1, f64::INFINITY));
+    assert!(!sys.set_client_radius(1, -5.0));
+    sys.set_client_position(1, InterestPosition::new(0.0, f64::INFINITY, 0.0));
+
+    // The old radius and position still apply.
+    let r = sys.evaluate(&entity_at(3, 50.0));
+    assert_eq!(r[0].1.entered, HashSet::from([NetworkId::new(3)]));
+    let r = sys.evaluate(&entity_at(3, 150.0));
+    assert_eq!(r[0].1.exited, HashSet::from([NetworkId::new(3)]));
+}
@@ -0,0 +1,296 @@
+//! Snapshot interpolation for remote entities on the client.
+//!
+//! Replicated updates arrive at 10–20 Hz with network jitter, so applying
+//! them directly makes remote players stutter. Instead, pose components
+//! registered with
+//! [`ReplicationSet::register_interpolated`](crate::ReplicationSet::register_interpolated)
+//! are pushed by the [`ReplicationClientSystem`](crate::ReplicationClientSystem)
+//! into each remote entity's [`InterpolationBuffer`], stamped with the
+//! server time of their tick, and [`interpolate_remote_entities_system`]
+//! renders it slightly in the past (at `server_time - delay`) by blending
+//! the two snapshots that bracket that moment. The delay adapts to the
+//! jitter measured by [`RttEstimator`].
+
+use std::collections::VecDeque;
+
+use bevy_ecs::prelude::*;
+use glam::{DVec3, Quat};
+
+use crate::clock::{ClockSync, RttEstimator};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Default number of snapshots retained per entity.
+pub const DEFAULT_SNAPSHOT_CAPACITY: usize = 16;
+
+// ---------------------------------------------------------------------------
+// RemoteSnapshot / InterpolationBuffer
+// ---------------------------------------------------------------------------
+
+/// A replicated component carrying an entity's pose, so its updates can be
+/// interpolated rather than applied on arrival.
+pub trait InterpolatedPose {
+    /// Position in millimeters and orientation.
+    fn pose(&self) -> (DVec3, Quat);
+}
+
+/// Replicated state of a remote entity at one server time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RemoteSnapshot {
+    /// Server time the state was sampled at, in seconds.
+    pub server_time: f64,
+    /// Position in millimeters.
+    pub position_mm: DVec3,
+    /// Orientation.
+    pub rotation: Quat,
+}
+
+/// Timestamped snapshots for one remote entity, ordered by server time.
+#[derive(Component, Debug, Clone)]
+pub struct InterpolationBuffer {
+    snapshots: VecDeque<RemoteSnapshot>,
+    capacity: usize,
+}
+
+impl InterpolationBuffer {
+    /// Creates an empty buffer retaining up to `capacity` snapshots (at
+    /// least two, so there is always a pair to interpolate between).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(2);
+        Self {
+            snapshots: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Number of buffered snapshots.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Whether no snapshot has been received yet.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// The most recent snapshot, if any.
+    pub fn latest(&self) -> Option<&RemoteSnapshot> {
+        self.snapshots.back()
+    }
+
+    /// Inserts a snapshot in server-time order.
+    ///
+    /// Out-of-order arrivals are slotted into place; a snapshot with the
+    /// same time as a buffered one replaces it, and one older than
+    /// everything in a full buffer is dropped.
+    pub fn push(&mut self, snapshot: RemoteSnapshot) {
+        let idx = self
+            .snapshots
+            .partition_point(|s| s.server_time < snapshot.server_time);
+        if self
+            .snapshots
+            .get(idx)
+            .is_some_and(|s| s.server_time == snapshot.server_time)
+        {
+            self.snapshots[idx] = snapshot;
+            return;
+        }
+        if self.snapshots.len() == self.capacity {
+            if idx == 0 {
+                return;
+            }
+            self.snapshots.pop_front();
+            self.snapshots.insert(idx - 1, snapshot);
+        } else {
+            self.snapshots.insert(idx, snapshot);
+        }
+    }
+
+    /// Drops snapshots that can no longer bracket `render_time`, keeping
+    /// the newest one at or before it.
+    pub fn discard_before(&mut self, render_time: f64) {
+        while self
+            .snapshots
+            .get(1)
+            .is_some_and(|s| s.server_time <= render_time)
+        {
+            self.snapshots.pop_front();
+        }
+    }
+
+    /// Position and rotation at `render_time`.
+    ///
+    /// Interpolates between the bracketing snapshots, extrapolates from the
+    /// last two when data is late (up to `settings.max_extrapolation_secs`),
+    /// and holds the earlier snapshot instead of sweeping across a gap
+    /// wider than `settings.teleport_distance_mm`.
+    pub fn sample(
+        &self,
+        render_time: f64,
+        settings: &InterpolationSettings,
+    ) -> Option<(DVec3, Quat)> {
+        let first = self.snapshots.front()?;
+        let idx = self
+            .snapshots
+            .partition_point(|s| s.server_time <= render_time);
+        if idx == 0 {
+            return Some((first.position_mm, first.rotation));
+        }
+        let a = &self.snapshots[idx - 1];
+        let teleported = |a: &RemoteSnapshot, b: &RemoteSnapshot| {
+            a.position_mm.distance(b.position_mm) > settings.teleport_distance_mm
+        };
+
+        let Some(b) = self.snapshots.get(idx) else {
+            // Late data: extrapolate along the last known velocity.
+            let Some(prev) = idx.checked_sub(2).map(|i| &self.snapshots[i]) else {
+                return Some((a.position_mm, a.rotation));
+            };
+            if teleported(prev, a) {
+                return Some((a.position_mm, a.rotation));
+            }
+            let velocity = (a.position_mm - prev.position_mm) / (a.server_time - prev.server_time);
+            let ahead = (render_time - a.server_time).min(settings.max_extrapolation_secs);
+            return Some((a.position_mm + velocity * ahead, a.rotation));
+        };
+
+        if teleported(a, b) {
+            return Some((a.position_mm, a.rotation));
+        }
+        let t = (render_time - a.server_time) / (b.server_time - a.server_time);
+        Some((
+            a.position_mm.lerp(b.position_mm, t),
+            a.rotation.slerp(b.rotation, t as f32),
+        ))
+    }
+}
+
+impl Default for InterpolationBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_SNAPSHOT_CAPACITY)
+    }
+}
+
+/// Smoothed render state written by [`interpolate_remote_entities_system`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct InterpolatedTransform {
+    /// Position in millimeters.
+    pub position_mm: DVec3,
+    /// Orientation.
+    pub rotation: Quat,
+}
+
+// ---------------------------------------------------------------------------
+// Settings / clock resources
+// ---------------------------------------------------------------------------
+
+/// Tuning for remote entity interpolation.
+#[derive(Resource, Debug, Clone)]
+pub struct InterpolationSettings {
+    /// Delay with zero jitter, in seconds; about one replication interval.
+    pub base_delay_secs: f64,
+    /// Lower bound on the adaptive delay, in seconds.
+    pub min_delay_secs: f64,
+    /// Upper bound on the adaptive delay, in seconds.
+    pub max_delay_secs: f64,
+    /// Seconds of extra delay per second of measured jitter.
+    pub jitter_multiplier: f64,
+    /// Longest time to extrapolate past the newest snapshot, in seconds.
+    pub max_extrapolation_secs: f64,
+    /// Snapshot-to-snapshot distance treated as a teleport, in millimeters.
+    pub teleport_distance_mm: f64,
+}
+
+impl Default for InterpolationSettings {
+    fn default() -> Self {
+        Self {
+            base_delay_secs: 0.1,
+            min_delay_secs: 0.05,
+            max_delay_secs: 0.5,
+            jitter_multiplier: 2.0,
+            max_extrapolation_secs: 0.25,
+            teleport_distance_mm: 10_000.0,
+        }
+    }
+}
+
+/// The client's view of server time and the current interpolation delay.
+///
+/// Call [`Self::sync_to`] once per frame so the render clock follows
+/// [`ClockSync::estimated_server_time`], whose corrections are smeared in
+/// rather than jumping.
+#[derive(Resource, Debug, Clone)]
+pub struct InterpolationClock {
+    /// Current server time as estimated by the client, in seconds.
+    pub server_time: f64,
+    /// Current interpolation delay in seconds.
+    pub delay_secs: f64,
+}
+
+impl InterpolationClock {
+    /// Creates a clock at `server_time` using the settings' base delay.
+    pub fn new(server_time: f64, settings: &InterpolationSettings) -> Self {
+        Self {
+            server_time,
+            delay_secs: settings.base_delay_secs,
+        }
+    }
+
+    /// Recomputes the delay from the jitter measured by `rtt`.
+    pub fn adapt_delay(&mut self, rtt: &RttEstimator, settings: &InterpolationSettings) {
+        let delay =
+            settings.base_delay_secs + settings.jitter_multiplier * rtt.jitter().as_secs_f64();
+        self.delay_secs = delay.clamp(settings.min_delay_secs, settings.max_delay_secs);
+    }
+
+    /// Takes the server time from `clock`'s estimate at local monotonic time
+    /// `local_now_ns` and adapts the delay to its measured jitter. The
+    /// server time is left as is until `clock` has received a pong.
+    pub fn sync_to(
+        &mut self,
+        clock: &ClockSync,
+        local_now_ns: u64,
+        settings: &InterpolationSettings,
+    ) {
+        if let Some(server_time) = clock.estimated_server_time(local_now_ns) {
+            self.server_time = server_time;
+        }
+        self.adapt_delay(&clock.rtt, settings);
+    }
+
+    /// Server time remote entities are rendered at.
+    pub fn render_time(&self) -> f64 {
+        self.server_time - self.delay_secs
+    }
+}
+
+// ---------------------------------------------------------------------------
+// System
+// ---------------------------------------------------------------------------
+
+/// Writes each remote entity's [`InterpolatedTransform`] for the current
+/// render time and discards snapshots it no longer needs.
+pub fn interpolate_remote_entities_system(
+    clock: Res<InterpolationClock>,
+    settings: Res<InterpolationSettings>,
+    mut query: Query<(&mut InterpolationBuffer, &mut InterpolatedTransform)>,
+) {
+    let render_time = clock.render_time();
+    for (mut buffer, mut transform) in query.iter_mut() {
+        buffer.discard_before(render_time);
+        if let Some((position_mm, rotation)) = buffer.sample(render_time, &settings) {
+            transform.position_mm = position_mm;
+            transform.rotation = rotation;
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[path = "interpolation_tests.rs"]
+mod tests;
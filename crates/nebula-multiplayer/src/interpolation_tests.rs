@@ -0,0 +1,212 @@
+//! Unit tests for remote snapshot interpolation.
+
+use super::*;
+use std::time::Duration;
+
+const FRAME: f64 = 1.0 / 60.0;
+const SNAPSHOT_INTERVAL: f64 = 0.1;
+const ONE_WAY_LATENCY: f64 = 0.05;
+
+/// Deterministic jitter in `0.0..0.04` seconds.
+fn jitter(i: u64) -> f64 {
+    let h = i.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 40;
+    (h % 1000) as f64 / 1000.0 * 0.04
+}
+
+/// 10 m radius circle at 0.5 rad/s (5 m/s), facing along the path.
+fn circle(t: f64) -> RemoteSnapshot {
+    let angle = 0.5 * t;
+    RemoteSnapshot {
+        server_time: t,
+        position_mm: DVec3::new(angle.cos(), 0.0, angle.sin()) * 10_000.0,
+        rotation: Quat::from_rotation_y(-angle as f32),
+    }
+}
+
+/// Drives the system at 60 fps, delivering 10 Hz snapshots from `path`
+/// with latency and jitter. Returns `(render_time, position)` per frame.
+fn run(path: impl Fn(f64) -> RemoteSnapshot, duration: f64) -> Vec<(f64, DVec3)> {
+    let settings = InterpolationSettings::default();
+    let mut rtt = RttEstimator::default();
+    for i in 0..16 {
+        rtt.record_sample(Duration::from_secs_f64(2.0 * ONE_WAY_LATENCY + jitter(i)));
+    }
+    let mut clock = InterpolationClock::new(0.0, &settings);
+    clock.adapt_delay(&rtt, &settings);
+
+    let mut world = World::new();
+    world.insert_resource(settings);
+    world.insert_resource(clock);
+    let entity = world
+        .spawn((
+            InterpolationBuffer::default(),
+            InterpolatedTransform::default(),
+        ))
+        .id();
+    let mut schedule = Schedule::default();
+    schedule.add_systems(interpolate_remote_entities_system);
+
+    let mut pending: Vec<u64> = (0..(duration / SNAPSHOT_INTERVAL) as u64).collect();
+    let mut frames = Vec::new();
+    let mut now = 0.0;
+    while now < duration {
+        // Deliver whatever has arrived by now; jitter can reorder them.
+        pending.retain(|&i| {
+            let sent = i as f64 * SNAPSHOT_INTERVAL;
+            if sent + ONE_WAY_LATENCY + jitter(i) > now {
+                return true;
+            }
+            let mut buffer = world.get_mut::<InterpolationBuffer>(entity).unwrap();
+            buffer.push(path(sent));
+            false
+        });
+        world.resource_mut::<InterpolationClock>().server_time = now - ONE_WAY_LATENCY;
+        schedule.run(&mut world);
+
+        let render_time = world.resource::<InterpolationClock>().render_time();
+        let transform = world.get::<InterpolatedTransform>(entity).unwrap();
+        frames.push((render_time, transform.position_mm));
+        now += FRAME;
+    }
+    frames
+}
+
+#[test]
+fn test_jittered_10hz_updates_track_true_path() {
+    let frames = run(circle, 5.0);
+    let mut prev: Option<DVec3> = None;
+    for &(render_time, pos) in frames.iter().filter(|(t, _)| *t > 0.5) {
+        let error = pos.distance(circle(render_time).position_mm);
+        // Late packets occasionally force a short extrapolation.
+        assert!(error < 50.0, "error {error:.1} mm at t={render_time:.3}");
+        if let Some(prev) = prev {
+            // 5 m/s at 60 fps is ~83 mm per frame.
+            let step = pos.distance(prev);
+            assert!(
+                step < 125.0,
+                "frame step {step:.1} mm at t={render_time:.3}"
+            );
+        }
+        prev = Some(pos);
+    }
+}
+
+#[test]
+fn test_teleport_snaps_instead_of_sweeping() {
+    let teleport_at = 2.0;
+    let path = |t: f64| {
+        let mut x = 5_000.0 * t;
+        if t >= teleport_at {
+            x += 100_000.0;
+        }
+        RemoteSnapshot {
+            server_time: t,
+            position_mm: DVec3::new(x, 0.0, 0.0),
+            rotation: Quat::IDENTITY,
+        }
+    };
+    let frames = run(path, 4.0);
+
+    let before = 5_000.0 * teleport_at;
+    let after = before + 100_000.0;
+    for &(t, pos) in &frames {
+        assert!(
+            pos.x <= before + 1.0 || pos.x >= after - 1_000.0,
+            "swept through {:.0} mm at t={t:.3}",
+            pos.x
+        );
+    }
+    let jumps = frames
+        .windows(2)
+        .filter(|w| w[1].1.x - w[0].1.x > 50_000.0)
+        .count();
+    assert_eq!(jumps, 1);
+}
+
+#[test]
+fn test_extrapolation_is_capped() {
+    let settings = InterpolationSettings::default();
+    let mut buffer = InterpolationBuffer::default();
+    buffer.push(circle(0.0));
+    buffer.push(circle(0.1));
+
+    let (near, _) = buffer.sample(0.15, &settings).unwrap();
+    assert!(near.distance(circle(0.15).position_mm) < 20.0);
+
+    let (capped, _) = buffer.sample(10.0, &settings).unwrap();
+    let (at_cap, _) = buffer
+        .sample(0.1 + settings.max_extrapolation_secs, &settings)
+        .unwrap();
+    assert!(capped.distance(at_cap) < 1e-6);
+}
+
+#[test]
+fn test_push_orders_and_bounds_snapshots() {
+    let mut buffer = InterpolationBuffer::new(3);
+    for t in [0.2, 0.0, 0.1, 0.3] {
+        buffer.push(circle(t));
+    }
+    assert_eq!(buffer.len(), 3);
+    buffer.push(circle(0.05)); // older than everything in a full buffer
+    let times: Vec<f64> = buffer.snapshots.iter().map(|s| s.server_time).collect();
+    assert_eq!(times, vec![0.1, 0.2, 0.3]);
+
+    buffer.discard_before(0.25);
+    assert_eq!(buffer.len(), 2);
+    assert_eq!(buffer.snapshots[0].server_time, 0.2);
+}
+
+#[test]
+fn test_render_clock_follows_clock_sync_estimate() {
+    use crate::clock::{Pong, TICK_DURATION};
+
+    let settings = InterpolationSettings::default();
+    let mut clock = InterpolationClock::new(0.0, &settings);
+    let mut sync = ClockSync::default();
+    clock.sync_to(&sync, 1_000_000_000, &settings);
+    assert_eq!(clock.server_time, 0.0, "no estimate before the first pong");
+
+    // Server stamps tick 600 (10 s) at the midpoint of a 100 ms ping
+    // sent at local time 1 s.
+    let send_ns = 1_000_000_000;
+    let pong = Pong {
+        sequence: 0,
+        server_tick: 600,
+    };
+    sync.on_pong_received(&pong, send_ns + 100_000_000, send_ns, 60);
+    let now_ns = send_ns + 500_000_000;
+    sync.advance(now_ns);
+    clock.sync_to(&sync, now_ns, &settings);
+
+    let expected = 600.0 * TICK_DURATION.as_secs_f64() + 0.45;
+    assert!((clock.server_time - expected).abs() < 1e-6);
+    assert_eq!(Some(clock.server_time), sync.estimated_server_time(now_ns));
+    assert!((clock.render_time() - (expected - clock.delay_secs)).abs() < 1e-9);
+}
+
+#[test]
+fn test_delay_adapts_to_jitter() {
+    let settings = InterpolationSettings::default();
+    let mut clock = InterpolationClock::new(0.0, &settings);
+
+    let mut steady = RttEstimator::default();
+    for _ in 0..8 {
+        steady.record_sample(Duration::from_millis(100));
+    }
+    clock.adapt_delay(&steady, &settings);
+    assert!((clock.delay_secs - settings.base_delay_secs).abs() < 1e-9);
+
+    let mut jittery = RttEstimator::default();
+    for i in 0..8 {
+        jittery.record_sample(Duration::from_millis(if i % 2 == 0 { 60 } else { 140 }));
+    }
+    clock.adapt_delay(&jittery, &settings);
+    assert!((clock.delay_secs - 0.26).abs() < 1e-6);
+
+    let mut awful = RttEstimator::default();
+    for i in 0..8 {
+        awful.record_sample(Duration::from_millis(if i % 2 == 0 { 50 } else { 900 }));
+    }
+    clock.adapt_delay(&awful, &settings);
+    assert_eq!(clock.delay_secs, settings.max_delay_secs);
+}
@@ -0,0 +1,321 @@
+//! Lag compensation: historical hitbox rewind for server-side validation.
+//!
+//! A client aims at where it *saw* a target, which is where the target was
+//! roughly one round trip ago. The server keeps a short [`HistoryBuffer`] of
+//! per-entity poses recorded every tick and answers hit queries against the
+//! interpolated past state via [`HistoryBuffer::rewind_and_query`].
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::authority::SERVER_TICK_RATE;
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Default history length in ticks (one second at [`SERVER_TICK_RATE`]).
+pub const DEFAULT_HISTORY_TICKS: usize = SERVER_TICK_RATE as usize;
+
+/// Extra ticks of rewind granted on top of the measured RTT to absorb
+/// jitter and client-side interpolation delay.
+pub const REWIND_SLACK_TICKS: u64 = 2;
+
+/// Rays with a direction shorter than this are rejected as degenerate.
+const MIN_DIRECTION_LENGTH: f64 = 1e-6;
+
+// ---------------------------------------------------------------------------
+// Hitbox / EntityPose
+// ---------------------------------------------------------------------------
+
+/// Vertical capsule used for rewind hit tests, centered on the entity
+/// position. A `half_height_mm` of zero degenerates to a sphere.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Hitbox {
+    /// Capsule radius in millimeters.
+    pub radius_mm: f64,
+    /// Half the length of the capsule's inner segment in millimeters.
+    pub half_height_mm: f64,
+}
+
+impl Default for Hitbox {
+    /// A 1.8 m tall, 0.8 m wide player capsule.
+    fn default() -> Self {
+        Self {
+            radius_mm: 400.0,
+            half_height_mm: 500.0,
+        }
+    }
+}
+
+/// Position and orientation of one entity at one tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntityPose {
+    /// X position in millimeters.
+    pub x: i64,
+    /// Y position in millimeters.
+    pub y: i64,
+    /// Z position in millimeters.
+    pub z: i64,
+    /// Yaw in milliradians.
+    pub yaw_mrad: i32,
+    /// Pitch in milliradians.
+    pub pitch_mrad: i32,
+}
+
+// ---------------------------------------------------------------------------
+// HitRay / HitResult
+// ---------------------------------------------------------------------------
+
+/// A client-supplied ray in world millimeters.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HitRay {
+    /// Ray origin in millimeters.
+    pub origin: [i64; 3],
+    /// Ray direction; need not be normalized.
+    pub direction: [f32; 3],
+    /// Maximum hit distance along the ray in millimeters.
+    pub max_distance_mm: i64,
+}
+
+/// The closest entity hit by a rewound ray query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HitResult {
+    /// Identifier of the entity that was hit.
+    pub entity: u64,
+    /// Tick the query was evaluated at (may be fractional).
+    pub tick: f64,
+    /// Distance from the ray origin to the hit point in millimeters.
+    pub distance_mm: f64,
+    /// Hit point in world millimeters.
+    pub point_mm: [f64; 3],
+}
+
+// ---------------------------------------------------------------------------
+// HistoryBuffer
+// ---------------------------------------------------------------------------
+
+/// All entity poses recorded for a single tick.
+#[derive(Debug, Clone)]
+struct HistoryFrame {
+    tick: u64,
+    poses: HashMap<u64, EntityPose>,
+}
+
+/// Ring buffer of the last N ticks of entity poses.
+#[derive(Debug, Clone)]
+pub struct HistoryBuffer {
+    capacity_ticks: usize,
+    hitbox: Hitbox,
+    frames: VecDeque<HistoryFrame>,
+}
+
+impl HistoryBuffer {
+    /// Creates a buffer retaining `capacity_ticks` ticks (at least one).
+    pub fn new(capacity_ticks: usize) -> Self {
+        let capacity_ticks = capacity_ticks.max(1);
+        Self {
+            capacity_ticks,
+            hitbox: Hitbox::default(),
+            frames: VecDeque::with_capacity(capacity_ticks),
+        }
+    }
+
+    /// Replaces the hitbox used for every entity.
+    pub fn with_hitbox(mut self, hitbox: Hitbox) -> Self {
+        self.hitbox = hitbox;
+        self
+    }
+
+    /// Maximum number of ticks retained.
+    pub fn capacity_ticks(&self) -> usize {
+        self.capacity_ticks
+    }
+
+    /// Number of ticks currently stored.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether no tick has been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Oldest recorded tick, if any.
+    pub fn oldest_tick(&self) -> Option<u64> {
+        self.frames.front().map(|f| f.tick)
+    }
+
+    /// Newest recorded tick, if any.
+    pub fn newest_tick(&self) -> Option<u64> {
+        self.frames.back().map(|f| f.tick)
+    }
+
+    /// Records the poses for `tick`, evicting the oldest tick when full.
+    ///
+    /// Ticks must be recorded in increasing order; re-recording the newest
+    /// tick replaces it, and older ticks are ignored.
+    pub fn record(&mut self, tick: u64, poses: impl IntoIterator<Item = (u64, EntityPose)>) {
+        let frame = HistoryFrame {
+            tick,
+            poses: poses.into_iter().collect(),
+        };
+        match self.newest_tick() {
+            Some(newest) if tick < newest => return,
+            Some(newest) if tick == newest => {
+                self.frames.pop_back();
+            }
+            _ => {}
+        }
+        if self.frames.len() == self.capacity_ticks {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Interpolated position of `entity` at a possibly fractional `tick`.
+    ///
+    /// Returns `None` if `tick` lies outside the recorded range or the
+    /// entity is missing from either surrounding snapshot.
+    pub fn position_at(&self, entity: u64, tick: f64) -> Option<[f64; 3]> {
+        let (before, after, t) = self.surrounding(tick)?;
+        let a = before.poses.get(&entity)?;
+        let b = after.poses.get(&entity)?;
+        Some(lerp_pose(a, b, t))
+    }
+
+    /// Rewinds every entity to `tick` and returns the closest capsule hit
+    /// along `ray`.
+    ///
+    /// Positions are linearly interpolated between the two snapshots that
+    /// surround `tick`. Capsules containing the ray origin (typically the
+    /// querying entity itself) are ignored. Returns `None` if `tick` is
+    /// outside the buffer or nothing is hit.
+    pub fn rewind_and_query(&self, tick: f64, ray: &HitRay) -> Option<HitResult> {
+        let (before, after, t) = self.surrounding(tick)?;
+        let origin = ray.origin.map(|c| c as f64);
+        let dir = normalize(ray.direction.map(f64::from))?;
+        let max_distance = ray.max_distance_mm as f64;
+
+        let mut best: Option<HitResult> = None;
+        for (&entity, a) in &before.poses {
+            let Some(b) = after.poses.get(&entity) else {
+                continue;
+            };
+            let center = lerp_pose(a, b, t);
+            let Some(distance) = ray_capsule(origin, dir, center, &self.hitbox) else {
+                continue;
+            };
+            if distance > max_distance || best.is_some_and(|h| h.distance_mm <= distance) {
+                continue;
+            }
+            best = Some(HitResult {
+                entity,
+                tick,
+                distance_mm: distance,
+                point_mm: [0, 1, 2].map(|i| origin[i] + dir[i] * distance),
+            });
+        }
+        best
+    }
+
+    /// Finds the snapshots bracketing `tick` and the blend factor between
+    /// them.
+    fn surrounding(&self, tick: f64) -> Option<(&HistoryFrame, &HistoryFrame, f64)> {
+        let oldest = self.oldest_tick()? as f64;
+        let newest = self.newest_tick()? as f64;
+        if !(oldest..=newest).contains(&tick) {
+            return None;
+        }
+        let idx = self.frames.partition_point(|f| (f.tick as f64) <= tick);
+        let before = &self.frames[idx - 1];
+        let Some(after) = self.frames.get(idx) else {
+            return Some((before, before, 0.0));
+        };
+        let span = (after.tick - before.tick) as f64;
+        Some((before, after, (tick - before.tick as f64) / span))
+    }
+}
+
+impl Default for HistoryBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_TICKS)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Geometry helpers
+// ---------------------------------------------------------------------------
+
+fn lerp_pose(a: &EntityPose, b: &EntityPose, t: f64) -> [f64; 3] {
+    let lerp = |a: i64, b: i64| a as f64 + (b as f64 - a as f64) * t;
+    [lerp(a.x, b.x), lerp(a.y, b.y), lerp(a.z, b.z)]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn normalize(v: [f64; 3]) -> Option<[f64; 3]> {
+    let len = dot(v, v).sqrt();
+    (len > MIN_DIRECTION_LENGTH).then(|| v.map(|c| c / len))
+}
+
+/// Entry distance of a normalized ray into a sphere, if in front of the origin.
+fn ray_sphere(origin: [f64; 3], dir: [f64; 3], center: [f64; 3], radius: f64) -> Option<f64> {
+    let oc = sub(origin, center);
+    let b = dot(oc, dir);
+    let c = dot(oc, oc) - radius * radius;
+    let disc = b * b - c;
+    if disc < 0.0 {
+        return None;
+    }
+    let t = -b - disc.sqrt();
+    (t >= 0.0).then_some(t)
+}
+
+/// Entry distance of a normalized ray into a Y-aligned capsule: the
+/// cylinder body plus the two end spheres.
+fn ray_capsule(origin: [f64; 3], dir: [f64; 3], center: [f64; 3], hitbox: &Hitbox) -> Option<f64> {
+    let r = hitbox.radius_mm;
+    let h = hitbox.half_height_mm;
+    let mut best: Option<f64> = None;
+
+    // Infinite cylinder in XZ, clipped to the segment's Y extent.
+    let (ox, oz) = (origin[0] - center[0], origin[2] - center[2]);
+    let a = dir[0] * dir[0] + dir[2] * dir[2];
+    if a > MIN_DIRECTION_LENGTH {
+        let b = ox * dir[0] + oz * dir[2];
+        let c = ox * ox + oz * oz - r * r;
+        let disc = b * b - a * c;
+        if disc >= 0.0 {
+            let t = (-b - disc.sqrt()) / a;
+            let y = origin[1] + dir[1] * t;
+            if t >= 0.0 && (y - center[1]).abs() <= h {
+                best = Some(t);
+            }
+        }
+    }
+
+    for cap_y in [center[1] - h, center[1] + h] {
+        let cap = [center[0], cap_y, center[2]];
+        if let Some(t) = ray_sphere(origin, dir, cap, r) {
+            best = Some(best.map_or(t, |b| b.min(t)));
+        }
+    }
+    best
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+#[path = "lag_compensation_tests.rs"]
+mod tests;
@@ -0,0 +1,212 @@
+//! Unit tests for pose history, rewind hit queries and lag-compensated
+//! intent validation.
+
+use super::*;
+use crate::authority::{AuthoritativeWorld, ClientIntent, IntentValidationError, PlayerState};
+use crate::authority_validation::IntentValidator;
+use crate::clock::ClockSync;
+use std::time::Duration;
+
+const TARGET: u64 = 7;
+
+fn pose(x: i64, z: i64) -> EntityPose {
+    EntityPose {
+        x,
+        y: 0,
+        z,
+        yaw_mrad: 0,
+        pitch_mrad: 0,
+    }
+}
+
+/// Target strafes along +Z at 100 mm/tick, 3 m in front of the origin.
+fn moving_target_history(ticks: u64) -> HistoryBuffer {
+    let mut history = HistoryBuffer::default();
+    for tick in 0..ticks {
+        history.record(tick, [(TARGET, pose(3_000, -3_000 + 100 * tick as i64))]);
+    }
+    history
+}
+
+fn ray_towards(x: f32, z: f32) -> HitRay {
+    HitRay {
+        origin: [0, 0, 0],
+        direction: [x, 0.0, z],
+        max_distance_mm: 10_000,
+    }
+}
+
+#[test]
+fn test_hit_at_lagged_tick_missed_at_current_tick() {
+    let history = moving_target_history(60);
+    // Client saw the target at tick 45: z = 1500.
+    let ray = ray_towards(3_000.0, 1_500.0);
+
+    let hit = history.rewind_and_query(45.0, &ray).expect("lagged hit");
+    assert_eq!(hit.entity, TARGET);
+    assert!(hit.distance_mm < 3_354.0, "hit before the capsule center");
+
+    // By tick 59 the target is at z = 2900, well outside the capsule.
+    assert!(history.rewind_and_query(59.0, &ray).is_none());
+}
+
+#[test]
+fn test_interpolates_between_snapshots() {
+    let history = moving_target_history(60);
+    let pos = history.position_at(TARGET, 10.5).expect("pose");
+    assert_eq!(pos, [3_000.0, 0.0, -3_000.0 + 1_050.0]);
+
+    // A narrow sphere only lines up with the half-tick position.
+    let history = history.with_hitbox(Hitbox {
+        radius_mm: 20.0,
+        half_height_mm: 0.0,
+    });
+    let ray = ray_towards(3_000.0, -1_950.0);
+    assert!(history.rewind_and_query(10.5, &ray).is_some());
+    assert!(history.rewind_and_query(10.0, &ray).is_none());
+    assert!(history.rewind_and_query(11.0, &ray).is_none());
+}
+
+#[test]
+fn test_rewind_older_than_buffer_is_rejected() {
+    let mut history = HistoryBuffer::new(30);
+    for tick in 0..100 {
+        history.record(tick, [(TARGET, pose(3_000, 0))]);
+    }
+    assert_eq!(history.len(), 30);
+    assert_eq!(history.oldest_tick(), Some(70));
+
+    let ray = ray_towards(1.0, 0.0);
+    assert!(history.rewind_and_query(80.0, &ray).is_some());
+    assert!(history.rewind_and_query(69.0, &ray).is_none());
+    assert!(history.rewind_and_query(100.0, &ray).is_none());
+}
+
+#[test]
+fn test_ignores_capsule_containing_origin_and_respects_max_distance() {
+    let mut history = HistoryBuffer::default();
+    history.record(0, [(1, pose(0, 0)), (TARGET, pose(3_000, 0))]);
+
+    let mut ray = ray_towards(1.0, 0.0);
+    let hit = history.rewind_and_query(0.0, &ray).expect("hit");
+    assert_eq!(hit.entity, TARGET);
+    assert!((hit.distance_mm - 2_600.0).abs() < 1e-6);
+
+    ray.max_distance_mm = 2_000;
+    assert!(history.rewind_and_query(0.0, &ray).is_none());
+}
+
+#[test]
+fn test_capsule_end_caps_and_degenerate_ray() {
+    let mut history = HistoryBuffer::default();
+    history.record(0, [(TARGET, pose(0, 0))]);
+
+    // Straight down from above hits the top cap at y = 500 + 400.
+    let down = HitRay {
+        origin: [0, 5_000, 0],
+        direction: [0.0, -1.0, 0.0],
+        max_distance_mm: 10_000,
+    };
+    let hit = history.rewind_and_query(0.0, &down).expect("cap hit");
+    assert!((hit.distance_mm - 4_100.0).abs() < 1e-6);
+
+    let zero = HitRay {
+        direction: [0.0; 3],
+        ..down
+    };
+    assert!(history.rewind_and_query(0.0, &zero).is_none());
+}
+
+// -- IntentValidator integration ----------------------------------------
+
+const SHOOTER: u64 = 1;
+
+fn player(player_id: u64, x: i64, z: i64) -> PlayerState {
+    PlayerState {
+        player_id,
+        x,
+        y: 0,
+        z,
+        yaw_mrad: 0,
+        pitch_mrad: 0,
+    }
+}
+
+/// Runs 60 ticks with the target strafing +100 mm/tick along Z.
+fn strafing_world(history_ticks: usize) -> AuthoritativeWorld {
+    let mut world = AuthoritativeWorld::with_history(HistoryBuffer::new(history_ticks));
+    world.spawn_player(player(SHOOTER, 0, 0));
+    world.spawn_player(player(TARGET, 3_000, -3_000));
+    let step = ClientIntent::Move {
+        player_id: TARGET,
+        dx: 0,
+        dy: 0,
+        dz: 100,
+    };
+    for _ in 0..60 {
+        IntentValidator::validate_and_apply(&step, &mut world).unwrap();
+        world.advance_tick();
+    }
+    world
+}
+
+fn clock_with_rtt(rtt: Duration) -> ClockSync {
+    let mut clock = ClockSync::default();
+    clock.rtt.record_sample(rtt);
+    clock
+}
+
+fn interact(client_tick: u64, ray: HitRay) -> ClientIntent {
+    ClientIntent::Interact {
+        player_id: SHOOTER,
+        target_entity: TARGET,
+        client_tick,
+        ray,
+    }
+}
+
+#[test]
+fn test_validator_accepts_lagged_interact_within_rtt() {
+    let world = strafing_world(DEFAULT_HISTORY_TICKS);
+    assert_eq!(world.tick(), 60);
+    let clock = clock_with_rtt(Duration::from_millis(200));
+
+    // At tick 50 the target stood at z = 2000.
+    let lagged = interact(50, ray_towards(3_000.0, 2_000.0));
+    assert_eq!(
+        IntentValidator::validate_with_rewind(&lagged, &world, clock.max_rewind_ticks()),
+        Ok(())
+    );
+
+    // The same ray against the present misses.
+    let present = interact(60, ray_towards(3_000.0, 2_000.0));
+    assert_eq!(
+        IntentValidator::validate_with_rewind(&present, &world, clock.max_rewind_ticks()),
+        Err(IntentValidationError::TargetMissed(TARGET))
+    );
+}
+
+#[test]
+fn test_validator_bounds_rewind_by_rtt_and_history() {
+    let world = strafing_world(30);
+    let ray = ray_towards(3_000.0, 2_000.0);
+
+    // Without a clock no rewind is granted.
+    assert_eq!(
+        IntentValidator::validate(&interact(50, ray), &world),
+        Err(IntentValidationError::RewindExceedsLatency {
+            rewind_ticks: 10,
+            max_ticks: 0,
+        })
+    );
+
+    // A 2 s RTT would allow it, but tick 20 has left the 30-tick buffer.
+    let slow = clock_with_rtt(Duration::from_secs(2));
+    assert_eq!(
+        IntentValidator::validate_with_rewind(&interact(20, ray), &world, slow.max_rewind_ticks()),
+        Err(IntentValidationError::RewindOutOfHistory {
+            tick: 20,
+            oldest: Some(31),
+        })
+    );
+}
@@ -2,29 +2,47 @@
 //! client-side prediction, and session management.
 
 pub mod authority;
+pub mod authority_transfer;
+pub mod authority_validation;
 pub mod budget;
 pub mod chat;
+pub mod chat_history;
+pub mod chunk_delivery;
 pub mod chunk_streaming;
 pub mod clock;
+pub mod edit_prediction;
 pub mod interest;
+pub mod interpolation;
+pub mod lag_compensation;
+pub mod movement_envelope;
+pub mod network_id;
 pub mod player_session;
 pub mod prediction;
 pub mod reconciliation;
 pub mod replication;
+pub mod replication_client;
+pub mod session_resume;
 pub mod snapshot;
+pub mod snapshot_delta;
 pub mod voxel_edit;
 
 pub use authority::{
-    AuthoritativeWorld, ClientIntent, IntentValidationError, IntentValidator, PlayerState,
-    ServerTickSchedule,
+    AuthoritativeWorld, ClientIntent, IntentValidationError, PlayerState, ServerTickSchedule,
 };
+pub use authority_transfer::{AuthorityTransferError, AuthorityTransferPacket};
+pub use authority_validation::IntentValidator;
 pub use budget::{
     AdaptiveRate, BandwidthConfig, BandwidthStats, ClientBandwidthTracker, ClientId,
     MessagePriority, MessageSender, PrioritizedMessage, send_tick_messages,
 };
 pub use chat::{
-    ChatConfig, ChatMessage, ChatMessageIntent, ChatRejection, ChatScope, ConnectedClient,
-    RateTracker, broadcast_chat, validate_chat_message,
+    ChannelRateTrackers, ChatChannel, ChatConfig, ChatMessage, ChatMessageIntent, ChatRejection,
+    ChatScope, ConnectedClient, JoinChannel, LeaveChannel, MuteList, RateTracker, broadcast_chat,
+    validate_channel_message, validate_chat_message,
+};
+pub use chat_history::ChatHistory;
+pub use chunk_delivery::{
+    ChunkAck, ChunkDeliveryConfig, ChunkDeliveryStats, ChunkStreamer, ClientChunkStream,
 };
 pub use chunk_streaming::{
     ChunkDataMessage, ChunkDecompressError, ChunkId, ChunkSendEntry, ChunkSendQueue,
@@ -32,12 +50,25 @@ pub use chunk_streaming::{
 };
 pub use clock::{
     ClockSync, Ping, Pong, RttEstimator, TICK_DURATION, TICK_RATE, TickAdjustment, TickCounter,
-    compute_tick_adjustment,
+    compute_tick_adjustment, server_time_at_tick,
+};
+pub use edit_prediction::{
+    DEFAULT_MAX_PENDING_EDITS, EditResponse, PendingEdit, PendingEditBuffer, SequencedEditIntent,
 };
 pub use interest::{
-    ClientInterestSet, InterestArea, InterestPosition, InterestTransitions, SpatialInterestSystem,
-    TrackedEntity, within_interest,
+    ClientInterestSet, InterestArea, InterestEvaluationStats, InterestPosition, InterestShape,
+    InterestTransitions, MAX_INTEREST_RADIUS, SpatialInterestSystem, TrackedEntity, chunk_address,
+    within_interest,
+};
+pub use interpolation::{
+    DEFAULT_SNAPSHOT_CAPACITY, InterpolatedPose, InterpolatedTransform, InterpolationBuffer,
+    InterpolationClock, InterpolationSettings, RemoteSnapshot, interpolate_remote_entities_system,
 };
+pub use lag_compensation::{
+    DEFAULT_HISTORY_TICKS, EntityPose, HistoryBuffer, HitRay, HitResult, Hitbox, REWIND_SLACK_TICKS,
+};
+pub use movement_envelope::{EnvelopeConfig, MovementEnvelope, MovementVerdict};
+pub use network_id::{NetworkId, NetworkIdAllocator};
 pub use player_session::{
     AuthResult, ConnectionRequest, ConnectionState, DisconnectReason, DisconnectRequest,
     InitialWorldState, PROTOCOL_VERSION, PlayerSaveData,
@@ -50,16 +81,24 @@ pub use reconciliation::{
     AuthoritativePlayerState, CorrectionSmoothing, ReconciliationResult, positions_match, reconcile,
 };
 pub use replication::{
-    ComponentDescriptor, ComponentTypeTag, DespawnEntity, EntityUpdate, NetworkId,
-    ReplicationClientSystem, ReplicationMessages, ReplicationServerSystem, ReplicationSet,
-    SpawnEntity,
+    ComponentDescriptor, ComponentTypeTag, DespawnEntity, EntityUpdate, PoseDecoder,
+    ReplicationMessages, ReplicationServerSystem, ReplicationSet, SpawnEntity,
+};
+pub use replication_client::ReplicationClientSystem;
+pub use session_resume::{
+    ResumeAccepted, ResumeOutcome, ResumeToken, ResumedSession, SessionResumeRegistry,
+    SessionResumeState, resume_session,
 };
 pub use snapshot::{
     CURRENT_SNAPSHOT_VERSION, ChunkSnapshot, DirtyChunkTracker, EntitySnapshot, SnapshotConfig,
-    SnapshotError, SnapshotHeader, SnapshotTimer, WorldSnapshot, check_version, load_snapshot,
-    write_snapshot,
+    SnapshotError, SnapshotHeader, SnapshotKind, SnapshotTimer, WorldSnapshot, check_version,
+    load_snapshot, write_snapshot,
+};
+pub use snapshot_delta::{
+    SnapshotDelta, SnapshotSource, load_delta, load_snapshot_chain, write_delta, write_incremental,
 };
 pub use voxel_edit::{
-    EditRejection, PlayerPosition, ServerChunkStore, VoxelEditEvent, VoxelEditIntent,
-    VoxelMaterial, apply_voxel_edit, validate_voxel_edit,
+    DEFAULT_MAX_EDIT_BURST, DEFAULT_MAX_EDITS_PER_TICK, EditRejection, PlayerPosition,
+    ServerChunkStore, VoxelEditEvent, VoxelEditIntent, VoxelEditRateLimiter, VoxelMaterial,
+    apply_voxel_edit, validate_voxel_edit,
 };
@@ -0,0 +1,390 @@
+//! Server-side movement envelope with gravity and terrain awareness.
+//!
+//! [`IntentValidator`] caps the total distance of a move, which rejects
+//! legitimate falls. [`MovementEnvelope`] replaces that cap for moves: it
+//! re-simulates every claimed move, tracks each player's ballistic vertical
+//! velocity under the local gravity up to terminal velocity, bounds the
+//! horizontal speed, and sweeps the player's bounding box against the
+//! [`ServerChunkStore`]. A move whose claimed end position is within
+//! tolerance of the server's result is accepted; anything else is snapped
+//! to the server's result and answered with an [`AuthoritativePlayerState`]
+//! correction, the same message reconciliation already consumes.
+//!
+//! The store's +Y axis is treated as local "up"; only the Y component of
+//! the gravity vector drives the ballistic model.
+
+use std::collections::HashMap;
+
+use bevy_ecs::prelude::*;
+use nebula_math::WorldPosition;
+use nebula_physics::{GravitySource, compute_gravity};
+
+use crate::authority::{AuthoritativeWorld, ClientIntent, IntentValidationError, SERVER_TICK_RATE};
+use crate::authority_validation::IntentValidator;
+use crate::chunk_streaming::ChunkId;
+use crate::prediction::simulate_movement;
+use crate::reconciliation::AuthoritativePlayerState;
+use crate::voxel_edit::{CHUNK_SIZE, ServerChunkStore};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+/// Voxel edge length in millimeters.
+const VOXEL_SIZE_MM: i64 = 1_000;
+
+// ---------------------------------------------------------------------------
+// EnvelopeConfig
+// ---------------------------------------------------------------------------
+
+/// Tuning for [`MovementEnvelope`]. All distances are millimeters and all
+/// speeds are millimeters per server tick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvelopeConfig {
+    /// Maximum horizontal speed (~12 m/s at 60 Hz).
+    pub max_horizontal_mm: i64,
+    /// Maximum upward speed when leaving the ground (~5.4 m/s at 60 Hz).
+    pub jump_velocity_mm: i64,
+    /// Maximum falling speed (~90 m/s at 60 Hz). Faster vertical claims
+    /// are rejected outright.
+    pub terminal_velocity_mm: i64,
+    /// Per-axis distance by which the claim may differ from the server's
+    /// result and still be accepted.
+    pub tolerance_mm: i64,
+    /// Half of the player's bounding box width on X and Z.
+    pub half_width_mm: i64,
+    /// Player bounding box height, measured up from the feet.
+    pub height_mm: i64,
+}
+
+impl Default for EnvelopeConfig {
+    fn default() -> Self {
+        Self {
+            max_horizontal_mm: 200,
+            jump_velocity_mm: 90,
+            terminal_velocity_mm: 1_500,
+            tolerance_mm: 10,
+            half_width_mm: 300,
+            height_mm: 1_800,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// MovementVerdict
+// ---------------------------------------------------------------------------
+
+/// Outcome of [`MovementEnvelope::process_move`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MovementVerdict {
+    /// The claimed position was applied as-is.
+    Accepted,
+    /// The claim was replaced by the server's result; send this state to
+    /// the client so it can reconcile.
+    Corrected(AuthoritativePlayerState),
+}
+
+// ---------------------------------------------------------------------------
+// MovementEnvelope
+// ---------------------------------------------------------------------------
+
+/// Per-player movement re-simulation and violation tracking.
+///
+/// A violation is counted whenever a claim falls outside the envelope
+/// (too fast, hovering, flying). Collisions alone only produce a
+/// correction, since client prediction routinely overshoots into walls.
+#[derive(Debug, Default, Resource)]
+pub struct MovementEnvelope {
+    config: EnvelopeConfig,
+    /// Ballistic vertical velocity per player in mm/tick (positive up).
+    vertical_velocity: HashMap<u64, f64>,
+    violations: HashMap<u64, u32>,
+}
+
+impl MovementEnvelope {
+    /// Creates an envelope with the given tuning.
+    pub fn new(config: EnvelopeConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the active tuning.
+    pub fn config(&self) -> &EnvelopeConfig {
+        &self.config
+    }
+
+    /// Number of envelope violations recorded for `player_id`.
+    pub fn violations(&self, player_id: u64) -> u32 {
+        self.violations.get(&player_id).copied().unwrap_or(0)
+    }
+
+    /// Violation counts for every player with at least one violation.
+    pub fn violation_counts(&self) -> &HashMap<u64, u32> {
+        &self.violations
+    }
+
+    /// Players whose violation count has reached `threshold`, sorted by id.
+    /// Intended for kick policies.
+    pub fn players_at_or_above(&self, threshold: u32) -> Vec<u64> {
+        let mut ids: Vec<u64> = self
+            .violations
+            .iter()
+            .filter(|&(_, &count)| count >= threshold)
+            .map(|(&id, _)| id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Clears the violation count for `player_id`.
+    pub fn reset_violations(&mut self, player_id: u64) {
+        self.violations.remove(&player_id);
+    }
+
+    /// Forgets all state for a disconnected player.
+    pub fn remove_player(&mut self, player_id: u64) {
+        self.vertical_velocity.remove(&player_id);
+        self.violations.remove(&player_id);
+    }
+
+    /// Validates a client move against the envelope and applies the result
+    /// to `world`.
+    ///
+    /// Non-movement intents are validated and applied unchanged by
+    /// [`IntentValidator`]. Moves faster than the horizontal cap or terminal
+    /// velocity return [`IntentValidationError::MoveTooFast`] without
+    /// touching the world and count as a violation.
+    pub fn process_move(
+        &mut self,
+        intent: &ClientIntent,
+        world: &mut AuthoritativeWorld,
+        store: &ServerChunkStore,
+        gravity_sources: &[(WorldPosition, &GravitySource)],
+    ) -> Result<MovementVerdict, IntentValidationError> {
+        let ClientIntent::Move { player_id, .. } = intent else {
+            IntentValidator::validate_and_apply(intent, world)?;
+            return Ok(MovementVerdict::Accepted);
+        };
+        let player_id = *player_id;
+        let ps = world
+            .find_player(player_id)
+            .ok_or(IntentValidationError::UnknownPlayer(player_id))?;
+        let start = [ps.x, ps.y, ps.z];
+
+        let claimed = simulate_movement(start[0], start[1], start[2], 0, 0, 0, intent);
+        let claimed_delta = [claimed.vx, claimed.vy, claimed.vz];
+        if let Err(err) = self.check_speed(claimed_delta) {
+            self.record_violation(player_id);
+            return Err(err);
+        }
+
+        let prev_vy = self
+            .vertical_velocity
+            .get(&player_id)
+            .copied()
+            .unwrap_or(0.0);
+        let gravity = gravity_per_tick(start, gravity_sources);
+        let grounded = self.sweep_axis(store, start, 1, -1) == 0;
+        let candidate = self.clamp_to_envelope(claimed_delta, prev_vy, gravity, grounded);
+        let in_envelope = candidate == claimed_delta;
+
+        // Resolve horizontal movement first so a wall never lifts the player.
+        let mut resolved = start;
+        for axis in [0, 2, 1] {
+            resolved[axis] += self.sweep_axis(store, resolved, axis, candidate[axis]);
+        }
+        let vertical_blocked = resolved[1] - start[1] != candidate[1];
+
+        let tol = self.config.tolerance_mm;
+        let claimed_pos = [claimed.x, claimed.y, claimed.z];
+        let matches = (0..3).all(|i| (claimed_pos[i] - resolved[i]).abs() <= tol);
+        let end = if matches { claimed_pos } else { resolved };
+
+        if let Some(ps) = world.find_player_mut(player_id) {
+            ps.x = end[0];
+            ps.y = end[1];
+            ps.z = end[2];
+        }
+        // In the air the server's own arc is authoritative, so small
+        // per-tick deviations inside the tolerance cannot accumulate into a
+        // hover.
+        let vy = if vertical_blocked {
+            0.0
+        } else if grounded {
+            (end[1] - start[1]) as f64
+        } else {
+            self.fall(prev_vy, gravity)
+        };
+        self.vertical_velocity.insert(player_id, vy);
+
+        if matches {
+            return Ok(MovementVerdict::Accepted);
+        }
+        if !in_envelope {
+            self.record_violation(player_id);
+        }
+        Ok(MovementVerdict::Corrected(AuthoritativePlayerState {
+            tick: world.tick(),
+            x: end[0],
+            y: end[1],
+            z: end[2],
+            vx: end[0] - start[0],
+            vy: end[1] - start[1],
+            vz: end[2] - start[2],
+        }))
+    }
+
+    /// Rejects moves no envelope could produce: faster than the horizontal
+    /// cap, or faster vertically than terminal velocity.
+    fn check_speed(&self, delta: [i64; 3]) -> Result<(), IntentValidationError> {
+        let cfg = &self.config;
+        let horizontal_sq = i128::from(delta[0]).pow(2) + i128::from(delta[2]).pow(2);
+        if horizontal_sq > i128::from(cfg.max_horizontal_mm).pow(2) {
+            return Err(IntentValidationError::MoveTooFast {
+                distance: (horizontal_sq as f64).sqrt() as i128,
+                max: cfg.max_horizontal_mm.into(),
+            });
+        }
+        let vertical = i128::from(delta[1]).abs();
+        if vertical > i128::from(cfg.terminal_velocity_mm) {
+            return Err(IntentValidationError::MoveTooFast {
+                distance: vertical,
+                max: cfg.terminal_velocity_mm.into(),
+            });
+        }
+        Ok(())
+    }
+
+    fn record_violation(&mut self, player_id: u64) {
+        let count = self.violations.entry(player_id).or_insert(0);
+        *count += 1;
+        tracing::debug!("Player {player_id} left movement envelope ({count} violations)");
+    }
+
+    /// Clamps a claimed per-tick delta into the envelope: horizontal speed
+    /// is capped, and vertical speed must follow the ballistic arc unless
+    /// the player is standing on ground (where a jump may start). The
+    /// tolerance is applied later, when comparing end positions.
+    fn clamp_to_envelope(
+        &self,
+        delta: [i64; 3],
+        prev_vy: f64,
+        gravity: f64,
+        grounded: bool,
+    ) -> [i64; 3] {
+        let cfg = &self.config;
+        let (mut dx, mut dz) = (delta[0], delta[2]);
+        let horizontal = ((dx as f64).powi(2) + (dz as f64).powi(2)).sqrt();
+        if horizontal > cfg.max_horizontal_mm as f64 {
+            let scale = cfg.max_horizontal_mm as f64 / horizontal;
+            dx = (dx as f64 * scale).round() as i64;
+            dz = (dz as f64 * scale).round() as i64;
+        }
+
+        let (lo, hi) = if grounded {
+            (-gravity, cfg.jump_velocity_mm as f64)
+        } else {
+            let expected = self.fall(prev_vy, gravity);
+            (expected, expected)
+        };
+        let dy = delta[1].clamp(lo.floor() as i64, hi.ceil() as i64);
+        [dx, dy, dz]
+    }
+
+    /// Vertical velocity one airborne tick after `vy`, limited to terminal
+    /// velocity.
+    fn fall(&self, vy: f64, gravity: f64) -> f64 {
+        (vy - gravity).max(-(self.config.terminal_velocity_mm as f64))
+    }
+
+    /// How far the player's box at `pos` can move by `delta` along `axis`
+    /// before touching a solid voxel. Returns the allowed delta, which is
+    /// flush with the blocking voxel face when movement is cut short.
+    fn sweep_axis(&self, store: &ServerChunkStore, pos: [i64; 3], axis: usize, delta: i64) -> i64 {
+        if delta == 0 {
+            return 0;
+        }
+        let (lo, hi) = self.body_bounds(pos);
+        let blocked = |layer: i64| {
+            let (a, b) = match axis {
+                0 => (1, 2),
+                1 => (0, 2),
+                _ => (0, 1),
+            };
+            let range =
+                |i: usize| lo[i].div_euclid(VOXEL_SIZE_MM)..=(hi[i] - 1).div_euclid(VOXEL_SIZE_MM);
+            range(a).any(|va| {
+                range(b).any(|vb| {
+                    let mut voxel = [0; 3];
+                    voxel[axis] = layer;
+                    voxel[a] = va;
+                    voxel[b] = vb;
+                    is_solid(store, voxel)
+                })
+            })
+        };
+
+        if delta > 0 {
+            let face = hi[axis];
+            let target = face + delta;
+            let mut layer = -(-face).div_euclid(VOXEL_SIZE_MM);
+            while layer * VOXEL_SIZE_MM < target {
+                if blocked(layer) {
+                    return layer * VOXEL_SIZE_MM - face;
+                }
+                layer += 1;
+            }
+        } else {
+            let face = lo[axis];
+            let target = face + delta;
+            let mut layer = face.div_euclid(VOXEL_SIZE_MM) - 1;
+            while (layer + 1) * VOXEL_SIZE_MM > target {
+                if blocked(layer) {
+                    return (layer + 1) * VOXEL_SIZE_MM - face;
+                }
+                layer -= 1;
+            }
+        }
+        delta
+    }
+
+    /// Half-open bounding box `[lo, hi)` of a player whose feet are at `pos`.
+    fn body_bounds(&self, pos: [i64; 3]) -> ([i64; 3], [i64; 3]) {
+        let w = self.config.half_width_mm;
+        (
+            [pos[0] - w, pos[1], pos[2] - w],
+            [pos[0] + w, pos[1] + self.config.height_mm, pos[2] + w],
+        )
+    }
+}
+
+/// Downward gravitational acceleration along -Y at `pos`, in mm/tick².
+fn gravity_per_tick(pos: [i64; 3], sources: &[(WorldPosition, &GravitySource)]) -> f64 {
+    let at = WorldPosition::new(pos[0] as i128, pos[1] as i128, pos[2] as i128);
+    let gravity = compute_gravity(&at, sources);
+    let down = -(gravity.direction.y as f64) * gravity.magnitude as f64;
+    down * 1_000.0 / f64::from(SERVER_TICK_RATE).powi(2)
+}
+
+/// Whether the world voxel at integer coordinates `voxel` is solid.
+/// Voxels in unloaded chunks count as air.
+fn is_solid(store: &ServerChunkStore, voxel: [i64; 3]) -> bool {
+    let size = i64::from(CHUNK_SIZE);
+    let chunk = ChunkId {
+        face: 0,
+        lod: 0,
+        x: voxel[0].div_euclid(size) as i32,
+        y: voxel[1].div_euclid(size) as i32,
+        z: voxel[2].div_euclid(size) as i32,
+    };
+    let [lx, ly, lz] = voxel.map(|v| v.rem_euclid(size) as u32);
+    store
+        .get_voxel(&chunk, lx, ly, lz)
+        .is_some_and(|m| !m.is_air())
+}
+
+#[cfg(test)]
+#[path = "movement_envelope_tests.rs"]
+mod tests;
@@ -0,0 +1,189 @@
+//! Unit tests for the server movement envelope.
+
+use super::*;
+use crate::authority::PlayerState;
+use crate::voxel_edit::VoxelMaterial;
+
+const STONE: VoxelMaterial = VoxelMaterial(1);
+
+fn chunk(x: i32, y: i32, z: i32) -> ChunkId {
+    ChunkId {
+        face: 0,
+        lod: 0,
+        x,
+        y,
+        z,
+    }
+}
+
+/// Solid ground below y = 0 and open air above.
+fn flat_ground() -> ServerChunkStore {
+    let mut store = ServerChunkStore::new();
+    store.load_chunk(chunk(0, -1, 0), STONE);
+    store.load_chunk(chunk(0, 0, 0), VoxelMaterial::AIR);
+    store
+}
+
+/// Constant Earth gravity pointing along -Y near the origin.
+fn earth() -> GravitySource {
+    GravitySource {
+        mass: 1.0,
+        surface_gravity: 9.81,
+        surface_radius: 1.0e9,
+        influence_radius: 1.0e12,
+        constant_near_surface: true,
+        atmosphere_height: 1.0e9,
+    }
+}
+
+fn world_with_player(x: i64, y: i64, z: i64) -> AuthoritativeWorld {
+    let mut world = AuthoritativeWorld::new();
+    world.spawn_player(PlayerState {
+        player_id: 1,
+        x,
+        y,
+        z,
+        yaw_mrad: 0,
+        pitch_mrad: 0,
+    });
+    world
+}
+
+fn step(dx: i64, dy: i64, dz: i64) -> ClientIntent {
+    ClientIntent::Move {
+        player_id: 1,
+        dx,
+        dy,
+        dz,
+    }
+}
+
+#[test]
+fn test_legitimate_fall_is_accepted() {
+    let store = flat_ground();
+    let source = earth();
+    let sources = [(WorldPosition::new(16_000, -1_000_000_000, 16_000), &source)];
+    let mut world = world_with_player(16_000, 20_000, 16_000);
+    let mut envelope = MovementEnvelope::default();
+    let g = 9.81 * 1_000.0 / 3_600.0;
+
+    // The client integrates the same arc and stops on the floor.
+    let mut vy = 0.0_f64;
+    let mut ticks = 0;
+    while world.find_player(1).map(|p| p.y) != Some(0) {
+        let y = world.find_player(1).map_or(0, |p| p.y);
+        let dy = ((vy - g).round() as i64).max(-y);
+        let verdict = envelope
+            .process_move(&step(0, dy, 0), &mut world, &store, &sources)
+            .unwrap();
+        assert_eq!(verdict, MovementVerdict::Accepted, "tick {ticks}");
+        vy -= g;
+        ticks += 1;
+        assert!(ticks < 200, "fall never landed");
+    }
+    // 20 m takes ~2 s; the final tick exceeds the flat 200 mm cap.
+    assert!(ticks > 100);
+    assert!(vy.abs() > 200.0);
+    assert_eq!(envelope.violations(1), 0);
+}
+
+#[test]
+fn test_fall_speed_is_capped_at_terminal_velocity() {
+    let store = ServerChunkStore::new();
+    let source = earth();
+    let sources = [(WorldPosition::new(16_000, -1_000_000_000, 16_000), &source)];
+    let mut world = world_with_player(16_000, 1_000_000, 16_000);
+    let mut envelope = MovementEnvelope::new(EnvelopeConfig {
+        terminal_velocity_mm: 50,
+        ..EnvelopeConfig::default()
+    });
+    let g = 9.81 * 1_000.0 / 3_600.0;
+
+    // The client's fall levels off at terminal velocity, as does the server's.
+    let mut vy = 0.0_f64;
+    for tick in 0..40 {
+        vy = (vy - g).max(-50.0);
+        let verdict = envelope
+            .process_move(&step(0, vy.round() as i64, 0), &mut world, &store, &sources)
+            .unwrap();
+        assert_eq!(verdict, MovementVerdict::Accepted, "tick {tick}");
+    }
+
+    // Falling any faster is rejected without moving the player.
+    let y = world.find_player(1).unwrap().y;
+    let err = envelope
+        .process_move(&step(0, -51, 0), &mut world, &store, &sources)
+        .unwrap_err();
+    assert!(matches!(err, IntentValidationError::MoveTooFast { .. }));
+    assert_eq!(world.find_player(1).unwrap().y, y);
+    assert_eq!(envelope.violations(1), 1);
+}
+
+#[test]
+fn test_horizontal_glide_is_rejected() {
+    let store = flat_ground();
+    let source = earth();
+    let sources = [(WorldPosition::new(16_000, -1_000_000_000, 16_000), &source)];
+    let mut world = world_with_player(0, 20_000, 16_000);
+    let mut envelope = MovementEnvelope::default();
+
+    // 50 m/s ≈ 833 mm/tick at constant altitude.
+    for _ in 0..5 {
+        let err = envelope
+            .process_move(&step(833, 0, 0), &mut world, &store, &sources)
+            .unwrap_err();
+        assert!(matches!(err, IntentValidationError::MoveTooFast { .. }));
+    }
+    assert_eq!(envelope.violations(1), 5);
+
+    // Hovering at walking speed passes the flat cap, but once the
+    // expected fall outgrows the tolerance every tick is corrected.
+    let mut corrected = 0;
+    for _ in 0..20 {
+        let verdict = envelope
+            .process_move(&step(150, 0, 0), &mut world, &store, &sources)
+            .unwrap();
+        if matches!(verdict, MovementVerdict::Corrected(_)) {
+            corrected += 1;
+        }
+    }
+    assert!(corrected >= 15, "only {corrected} corrections");
+    assert_eq!(envelope.violations(1), 5 + corrected);
+    assert!(world.find_player(1).unwrap().y < 20_000);
+    assert_eq!(envelope.players_at_or_above(20), vec![1]);
+}
+
+#[test]
+fn test_walking_into_wall_is_corrected_flush() {
+    let mut store = flat_ground();
+    let wall = chunk(0, 0, 0);
+    for y in 0..3 {
+        for z in 0..CHUNK_SIZE {
+            store.set_voxel(&wall, 3, y, z, STONE);
+        }
+    }
+    let source = earth();
+    let sources = [(WorldPosition::new(16_000, -1_000_000_000, 16_000), &source)];
+    let mut world = world_with_player(1_000, 0, 16_500);
+    let mut envelope = MovementEnvelope::default();
+
+    let mut corrections = 0;
+    for _ in 0..20 {
+        match envelope
+            .process_move(&step(150, 0, 0), &mut world, &store, &sources)
+            .unwrap()
+        {
+            MovementVerdict::Accepted => {}
+            MovementVerdict::Corrected(state) => {
+                assert_eq!(state.x + envelope.config().half_width_mm, 3_000);
+                corrections += 1;
+            }
+        }
+    }
+    let ps = world.find_player(1).unwrap();
+    assert_eq!(ps.x + envelope.config().half_width_mm, 3_000);
+    assert_eq!(ps.y, 0);
+    assert!(corrections > 0);
+    // Bumping into a wall is not cheating.
+    assert_eq!(envelope.violations(1), 0);
+}
@@ -0,0 +1,194 @@
+//! Network identifiers for replicated entities and the server-side
+//! allocator that hands them out.
+
+use std::collections::{BTreeSet, HashMap};
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Unique network identifier for a replicated entity. Allocated by the server
+/// with a [`NetworkIdAllocator`]. Clients reference entities exclusively by
+/// `NetworkId`.
+///
+/// Freed `id`s are handed out again with a new `generation`, so a recycled
+/// ID never compares equal to the entity that held it before.
+#[derive(Component, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NetworkId {
+    /// Numeric identifier.
+    pub id: u32,
+    /// Incarnation of `id`, bumped each time the ID is recycled.
+    pub generation: u8,
+}
+
+impl NetworkId {
+    /// A first-generation `NetworkId`.
+    pub const fn new(id: u32) -> Self {
+        Self { id, generation: 0 }
+    }
+}
+
+/// Allocates [`NetworkId`]s from a monotonically increasing counter starting
+/// at 1.
+///
+/// IDs returned with [`free`](Self::free) are reused first, each with its
+/// generation incremented, so the set of tracked IDs stays bounded by the
+/// number of live entities. The counter only advances when nothing is free,
+/// and once it wraps past `u32::MAX` allocation fails until an ID is freed.
+#[derive(Debug, Clone)]
+pub struct NetworkIdAllocator {
+    /// First ID handed out; IDs below it are never allocated.
+    start: u32,
+    /// Next never-allocated ID, until the counter wraps.
+    next_id: u32,
+    /// Whether every `u32` from `start` up has been handed out once.
+    wrapped: bool,
+    /// IDs available for reuse.
+    freed: BTreeSet<u32>,
+    /// Current generation of each recycled ID; absent means 0.
+    generation: HashMap<u32, u8>,
+}
+
+impl NetworkIdAllocator {
+    /// Creates an allocator whose first ID is 1.
+    pub fn new() -> Self {
+        Self::starting_at(1)
+    }
+
+    /// Creates an allocator whose first ID is `start`; IDs below it are
+    /// never handed out.
+    pub(crate) fn starting_at(start: u32) -> Self {
+        Self {
+            start,
+            next_id: start,
+            wrapped: false,
+            freed: BTreeSet::new(),
+            generation: HashMap::new(),
+        }
+    }
+
+    /// Allocates a `NetworkId` that differs from every live one.
+    ///
+    /// Returns `None` if the counter has wrapped and no freed ID is
+    /// available, i.e. every ID is live.
+    pub fn allocate(&mut self) -> Option<NetworkId> {
+        if let Some(id) = self.freed.pop_first() {
+            let generation = self.generation.entry(id).or_insert(0);
+            *generation = generation.wrapping_add(1);
+            return Some(NetworkId {
+                id,
+                generation: *generation,
+            });
+        }
+        if self.wrapped {
+            return None;
+        }
+
+        let id = self.next_id;
+        match self.next_id.checked_add(1) {
+            Some(next) => self.next_id = next,
+            None => self.wrapped = true,
+        }
+        Some(NetworkId::new(id))
+    }
+
+    /// Returns `net_id` to the allocator. Returns `false`, and does nothing,
+    /// if it is not live: never allocated, already freed, or from an older
+    /// generation.
+    pub fn free(&mut self, net_id: NetworkId) -> bool {
+        let allocated = net_id.id >= self.start && (self.wrapped || net_id.id < self.next_id);
+        let current = self.generation.get(&net_id.id).copied().unwrap_or(0);
+        if !allocated || net_id.generation != current || self.freed.contains(&net_id.id) {
+            return false;
+        }
+        self.freed.insert(net_id.id)
+    }
+}
+
+impl Default for NetworkIdAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_allocator_never_duplicates_live_ids_across_wraparound() {
+        // Start close to the end of the ID space so the counter wraps halfway.
+        let mut allocator = NetworkIdAllocator::starting_at(u32::MAX - (1 << 15));
+        let mut live_order = std::collections::VecDeque::new();
+        let mut live = HashSet::new();
+        let mut recycled = 0;
+
+        for _ in 0..1 << 16 {
+            if live_order.len() >= 1000 {
+                let oldest = live_order.pop_front().unwrap();
+                assert!(allocator.free(oldest));
+                live.remove(&oldest);
+            }
+            let net_id = allocator.allocate().unwrap();
+            assert!(!live.contains(&net_id), "{net_id:?} is already live");
+            if net_id.generation > 0 {
+                recycled += 1;
+            }
+            live.insert(net_id);
+            live_order.push_back(net_id);
+        }
+        assert!(recycled > 1 << 14, "only {recycled} recycled IDs");
+    }
+
+    #[test]
+    fn test_recycled_id_differs_from_stale_reference() {
+        let mut allocator = NetworkIdAllocator::starting_at(u32::MAX);
+        let first = allocator.allocate().unwrap();
+        assert_eq!(first, NetworkId::new(u32::MAX));
+        assert!(allocator.free(first));
+
+        let second = allocator.allocate().unwrap();
+        assert_eq!(second.id, first.id);
+        assert_ne!(second, first);
+        assert!(!allocator.free(first), "stale generation must not free");
+        assert!(allocator.free(second));
+        assert!(!allocator.free(second), "double free");
+    }
+
+    #[test]
+    fn test_freed_ids_are_reused_before_fresh_ones() {
+        let mut allocator = NetworkIdAllocator::new();
+        let a = allocator.allocate().unwrap();
+        let b = allocator.allocate().unwrap();
+        assert!(allocator.free(a));
+        let reused = allocator.allocate().unwrap();
+        assert_eq!(reused.id, a.id);
+        assert_eq!(reused.generation, 1);
+        // With nothing free, the counter continues where it left off.
+        assert_eq!(allocator.allocate(), Some(NetworkId::new(b.id + 1)));
+        assert!(!allocator.free(NetworkId::new(1000)), "never allocated");
+    }
+
+    #[test]
+    fn test_despawn_churn_keeps_free_list_bounded() {
+        let mut allocator = NetworkIdAllocator::new();
+        for _ in 0..10_000 {
+            let id = allocator.allocate().unwrap();
+            assert!(allocator.free(id));
+        }
+        assert_eq!(allocator.freed.len(), 1);
+        assert_eq!(allocator.allocate().map(|id| id.id), Some(1));
+    }
+
+    #[test]
+    fn test_free_rejects_ids_below_start_after_wrap() {
+        let mut allocator = NetworkIdAllocator::starting_at(u32::MAX - 1);
+        let a = allocator.allocate().unwrap();
+        let b = allocator.allocate().unwrap();
+        assert_eq!(allocator.allocate(), None, "every ID is live");
+        assert!(!allocator.free(NetworkId::new(0)), "below the starting ID");
+        assert!(!allocator.free(NetworkId::new(1)), "below the starting ID");
+        assert!(allocator.free(a));
+        assert!(allocator.free(b));
+    }
+}
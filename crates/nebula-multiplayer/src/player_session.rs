@@ -11,7 +11,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::authority::{AuthoritativeWorld, PlayerState};
 use crate::chunk_streaming::ChunkDataMessage;
-use crate::replication::{NetworkId, ReplicationServerSystem, SpawnEntity};
+use crate::network_id::NetworkId;
+use crate::replication::{ReplicationServerSystem, SpawnEntity};
+use crate::voxel_edit::VoxelEditRateLimiter;
 
 // ---------------------------------------------------------------------------
 // Protocol version
@@ -151,14 +153,15 @@ pub struct PlayerSaveData {
 /// If `saved_state` is provided the player resumes at their last position;
 /// otherwise they start at the default spawn (origin).
 ///
-/// Returns the ECS [`Entity`] and assigned [`NetworkId`].
+/// Returns the ECS [`Entity`] and assigned [`NetworkId`], or `None` if no
+/// `NetworkId` is available, in which case nothing is spawned.
 pub fn spawn_player(
     world: &mut AuthoritativeWorld,
     replication: &mut ReplicationServerSystem,
     client_id: u64,
     saved_state: Option<&PlayerSaveData>,
-) -> (Entity, NetworkId) {
-    let network_id = replication.allocate_network_id();
+) -> Option<(Entity, NetworkId)> {
+    let network_id = replication.allocate_network_id()?;
 
     let (x, y, z) = match saved_state {
         Some(save) => (save.x, save.y, save.z),
@@ -178,7 +181,7 @@ pub fn spawn_player(
 
     replication.add_client(client_id);
 
-    (entity, network_id)
+    Some((entity, network_id))
 }
 
 /// Saves the current state of a player before disconnect.
@@ -200,16 +203,22 @@ pub fn save_player_state(
 }
 
 /// Removes a player entity from the authoritative world and cleans up
-/// replication state. Other clients are notified via the normal replication
+/// replication and voxel edit state, returning the entity's [`NetworkId`]
+/// to the allocator. Other clients are notified via the normal replication
 /// despawn path.
 pub fn remove_player(
     world: &mut AuthoritativeWorld,
     replication: &mut ReplicationServerSystem,
+    edit_limiter: &mut VoxelEditRateLimiter,
     client_id: u64,
     entity: Entity,
 ) {
+    if let Some(&network_id) = world.world().get::<NetworkId>(entity) {
+        replication.free_network_id(network_id);
+    }
     world.world_mut().despawn(entity);
     replication.remove_client(client_id);
+    edit_limiter.remove_client(client_id);
 }
 
 // ---------------------------------------------------------------------------
@@ -220,6 +229,7 @@ pub fn remove_player(
 mod tests {
     use super::*;
     use crate::replication::{ReplicationServerSystem, ReplicationSet};
+    use crate::voxel_edit::EditRejection;
 
     /// Helper: set up a world + replication and join a player, returning
     /// the pieces needed for further assertions.
@@ -229,7 +239,7 @@ mod tests {
         client_id: u64,
         saved: Option<&PlayerSaveData>,
     ) -> (Entity, NetworkId) {
-        spawn_player(world, repl, client_id, saved)
+        spawn_player(world, repl, client_id, saved).expect("network id available")
     }
 
     // 1. test_join_spawns_entity_visible_to_others
@@ -271,6 +281,7 @@ mod tests {
     fn test_leave_despawns_entity() {
         let mut world = AuthoritativeWorld::new();
         let mut repl = ReplicationServerSystem::new();
+        let mut limiter = VoxelEditRateLimiter::new(1, 1);
         let mut rep_set = ReplicationSet::new();
         rep_set.register::<NetworkId>("NetworkId");
         rep_set.register::<PlayerState>("PlayerState");
@@ -281,8 +292,15 @@ mod tests {
         // Baseline replication.
         let _ = repl.replicate(world.world(), &rep_set, world.tick());
 
-        // Client B leaves.
-        remove_player(&mut world, &mut repl, 2, eb);
+        // Client B spends its edit for the tick, then leaves.
+        limiter.check_and_consume(2).expect("per-tick edit");
+        remove_player(&mut world, &mut repl, &mut limiter, 2, eb);
+        assert_eq!(limiter.burst_tokens(2), 0, "limiter forgets the bank");
+        assert_eq!(
+            limiter.check_and_consume(2),
+            Err(EditRejection::RateLimited),
+            "rejoining does not restore the allowance"
+        );
 
         // Replicate — A should receive DespawnEntity for B.
         world.advance_tick();
@@ -373,6 +391,7 @@ mod tests {
     fn test_state_persists_across_rejoin() {
         let mut world = AuthoritativeWorld::new();
         let mut repl = ReplicationServerSystem::new();
+        let mut limiter = VoxelEditRateLimiter::default();
 
         // Client A joins at default position.
         let (entity_a, _na) = join_player(&mut world, &mut repl, 1, None);
@@ -387,7 +406,7 @@ mod tests {
 
         // Save state and disconnect.
         let save = save_player_state(&world, "Alice", 1).expect("save");
-        remove_player(&mut world, &mut repl, 1, entity_a);
+        remove_player(&mut world, &mut repl, &mut limiter, 1, entity_a);
         assert!(world.find_player(1).is_none());
 
         // Rejoin with saved state.
@@ -400,4 +419,30 @@ mod tests {
         assert_eq!(ps.z, 1000);
         assert_eq!(save.player_name, "Alice");
     }
+
+    // 6. test_reconnect_loop_recycles_network_ids
+    #[test]
+    fn test_reconnect_loop_recycles_network_ids() {
+        use crate::network_id::NetworkIdAllocator;
+
+        // Only a handful of IDs exist before the counter wraps, so the loop
+        // runs out unless disconnects return their IDs.
+        let mut world = AuthoritativeWorld::new();
+        let mut repl = ReplicationServerSystem::with_network_ids(NetworkIdAllocator::starting_at(
+            u32::MAX - 3,
+        ));
+        let mut limiter = VoxelEditRateLimiter::default();
+        let (_host, host_id) = join_player(&mut world, &mut repl, 1, None);
+
+        let mut previous = None;
+        for _ in 0..1000 {
+            let (entity, net_id) = spawn_player(&mut world, &mut repl, 2, None)
+                .expect("disconnects must free their network ids");
+            assert_ne!(net_id, host_id);
+            assert_ne!(Some(net_id), previous, "recycled id must change generation");
+            previous = Some(net_id);
+            remove_player(&mut world, &mut repl, &mut limiter, 2, entity);
+        }
+        assert!(previous.is_some_and(|id| id.generation > 0));
+    }
 }
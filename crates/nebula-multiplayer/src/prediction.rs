@@ -282,7 +282,8 @@ mod tests {
 
     #[test]
     fn test_prediction_matches_server_for_simple_movement() {
-        use crate::authority::{AuthoritativeWorld, IntentValidator, PlayerState};
+        use crate::authority::{AuthoritativeWorld, PlayerState};
+        use crate::authority_validation::IntentValidator;
 
         let mut world = AuthoritativeWorld::new();
         world.spawn_player(PlayerState {
@@ -4,29 +4,29 @@
 //! The server assigns a [`NetworkId`] to every replicated entity. Each tick,
 //! the [`ReplicationServerSystem`] diffs component state against per-client
 //! shadow state and produces [`EntityUpdate`], [`SpawnEntity`], or
-//! [`DespawnEntity`] messages as needed. The [`ReplicationClientSystem`]
-//! applies those messages to the local ECS world.
+//! [`DespawnEntity`] messages as needed. The
+//! [`ReplicationClientSystem`](crate::ReplicationClientSystem) applies those
+//! messages to the local ECS world.
 
 use std::any::TypeId;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use bevy_ecs::prelude::*;
+use glam::{DVec3, Quat};
 use serde::{Deserialize, Serialize};
 
-// ---------------------------------------------------------------------------
-// NetworkId
-// ---------------------------------------------------------------------------
-
-/// Unique network identifier for a replicated entity. Allocated by the server
-/// from a monotonically increasing counter. Clients reference entities
-/// exclusively by `NetworkId`.
-#[derive(Component, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct NetworkId(pub u64);
+use crate::clock::server_time_at_tick;
+use crate::interpolation::InterpolatedPose;
+use crate::network_id::{NetworkId, NetworkIdAllocator};
 
 // ---------------------------------------------------------------------------
 // ComponentDescriptor
 // ---------------------------------------------------------------------------
 
+/// Decodes a serialized pose component into a position in millimeters and
+/// an orientation.
+pub type PoseDecoder = fn(&[u8]) -> Option<(DVec3, Quat)>;
+
 /// A type-erased descriptor for a replicated component. Contains the
 /// [`TypeId`] plus function pointers for serializing from / deserializing
 /// into a Bevy [`World`].
@@ -40,6 +40,10 @@ pub struct ComponentDescriptor {
     pub serializer: fn(&World, Entity) -> Option<Vec<u8>>,
     /// Deserialize and insert the component onto `entity` in `world`.
     pub deserializer: fn(&mut World, Entity, &[u8]),
+    /// For components registered with
+    /// [`ReplicationSet::register_interpolated`], decodes the pose the
+    /// client buffers instead of applying updates directly.
+    pub pose: Option<PoseDecoder>,
 }
 
 // ---------------------------------------------------------------------------
@@ -82,10 +86,27 @@ impl ReplicationSet {
                     }
                 }
             },
+            pose: None,
         };
         self.descriptors.push(descriptor);
     }
 
+    /// Registers a pose component for replication. The client applies its
+    /// spawn state directly but feeds later updates into the entity's
+    /// [`InterpolationBuffer`](crate::InterpolationBuffer), so remote
+    /// entities render through
+    /// [`InterpolatedTransform`](crate::InterpolatedTransform) instead of
+    /// snapping to each update.
+    pub fn register_interpolated<T>(&mut self, tag: &'static str)
+    where
+        T: Component + Serialize + serde::de::DeserializeOwned + Clone + InterpolatedPose,
+    {
+        self.register::<T>(tag);
+        if let Some(descriptor) = self.descriptors.last_mut() {
+            descriptor.pose = Some(|bytes| postcard::from_bytes::<T>(bytes).ok().map(|c| c.pose()));
+        }
+    }
+
     /// Returns the registered descriptors.
     pub fn descriptors(&self) -> &[ComponentDescriptor] {
         &self.descriptors
@@ -125,6 +146,13 @@ pub struct SpawnEntity {
     pub components: Vec<(ComponentTypeTag, Vec<u8>)>,
 }
 
+impl EntityUpdate {
+    /// Server time of the update in seconds.
+    pub fn server_time(&self) -> f64 {
+        server_time_at_tick(self.tick)
+    }
+}
+
 /// Despawn message: entity no longer exists or left interest area.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct DespawnEntity {
@@ -135,6 +163,8 @@ pub struct DespawnEntity {
 /// Aggregated output of one replication tick for one client.
 #[derive(Debug, Clone, Default)]
 pub struct ReplicationMessages {
+    /// Server tick the messages were produced at.
+    pub tick: u64,
     /// New entities the client should spawn.
     pub spawns: Vec<SpawnEntity>,
     /// Component updates for entities the client already knows about.
@@ -143,16 +173,76 @@ pub struct ReplicationMessages {
     pub despawns: Vec<DespawnEntity>,
 }
 
+impl ReplicationMessages {
+    /// Server time the messages were produced at, in seconds.
+    pub fn server_time(&self) -> f64 {
+        server_time_at_tick(self.tick)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Per-client shadow state
 // ---------------------------------------------------------------------------
 
+/// Map from `NetworkId` → (tag → serialized component bytes).
+type EntityShadow = HashMap<NetworkId, HashMap<String, Vec<u8>>>;
+
+/// Most replicated ticks kept per client while waiting for an ack. Acks for
+/// older ticks are ignored.
+const MAX_UNACKED_TICKS: usize = 64;
+
 /// Shadow state for one client: tracks which entities the client knows about
 /// and the last-serialized component bytes for delta comparison.
+///
+/// Besides the state as of the last tick sent, the shadow keeps the state as
+/// of the last tick the client acknowledged, so a resumed session can be
+/// diffed against what the client actually received.
 #[derive(Debug, Clone, Default)]
 pub(crate) struct ClientShadow {
-    /// Map from `NetworkId` → (tag → last-sent bytes).
-    pub(crate) entities: HashMap<u64, HashMap<String, Vec<u8>>>,
+    /// State as of the last tick sent.
+    pub(crate) entities: EntityShadow,
+    /// State after each sent tick that changed something and is not yet
+    /// acknowledged, oldest first.
+    unacked: VecDeque<(u64, EntityShadow)>,
+    /// Last tick the client acknowledged.
+    acked_tick: u64,
+    /// State as of `acked_tick`.
+    acked: EntityShadow,
+}
+
+impl ClientShadow {
+    /// Record that the client applied every message up to `tick`. Returns
+    /// `false` for a stale ack or one older than the kept history.
+    fn acknowledge(&mut self, tick: u64) -> bool {
+        if tick <= self.acked_tick {
+            return false;
+        }
+        if self.unacked.len() == MAX_UNACKED_TICKS
+            && self
+                .unacked
+                .front()
+                .is_some_and(|(oldest, _)| tick < *oldest)
+        {
+            return false;
+        }
+        while let Some((sent, _)) = self.unacked.front()
+            && *sent <= tick
+        {
+            if let Some((_, state)) = self.unacked.pop_front() {
+                self.acked = state;
+            }
+        }
+        self.acked_tick = tick;
+        true
+    }
+
+    /// Drop everything the client has not acknowledged, so replication
+    /// resumes from the last acked tick. Returns that tick.
+    pub(crate) fn rewind_to_ack(&mut self) -> u64 {
+        self.entities = self.acked.clone();
+        self.unacked.clear();
+        self.acked_tick
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -162,8 +252,8 @@ pub(crate) struct ClientShadow {
 /// Server-side replication system. Each tick, compares authoritative component
 /// state against per-client shadow state and emits minimal messages.
 pub struct ReplicationServerSystem {
-    /// Next `NetworkId` to allocate.
-    next_network_id: u64,
+    /// Allocator for entity `NetworkId`s.
+    network_ids: NetworkIdAllocator,
     /// Per-client shadow state, keyed by client ID.
     shadows: HashMap<u64, ClientShadow>,
 }
@@ -171,17 +261,27 @@ pub struct ReplicationServerSystem {
 impl ReplicationServerSystem {
     /// Creates a new server replication system.
     pub fn new() -> Self {
+        Self::with_network_ids(NetworkIdAllocator::new())
+    }
+
+    /// Creates a server replication system that draws IDs from `network_ids`.
+    pub(crate) fn with_network_ids(network_ids: NetworkIdAllocator) -> Self {
         Self {
-            next_network_id: 1,
+            network_ids,
             shadows: HashMap::new(),
         }
     }
 
-    /// Allocates the next [`NetworkId`].
-    pub fn allocate_network_id(&mut self) -> NetworkId {
-        let id = NetworkId(self.next_network_id);
-        self.next_network_id += 1;
-        id
+    /// Allocates the next [`NetworkId`], or `None` if every ID is live.
+    /// See [`NetworkIdAllocator::allocate`].
+    pub fn allocate_network_id(&mut self) -> Option<NetworkId> {
+        self.network_ids.allocate()
+    }
+
+    /// Returns the [`NetworkId`] of a despawned entity for reuse. See
+    /// [`NetworkIdAllocator::free`].
+    pub fn free_network_id(&mut self, net_id: NetworkId) -> bool {
+        self.network_ids.free(net_id)
     }
 
     /// Registers a client so we track shadow state for them.
@@ -194,6 +294,27 @@ impl ReplicationServerSystem {
         self.shadows.remove(&client_id);
     }
 
+    /// Removes a client but hands back its shadow state so replication can
+    /// later continue from where the client left off.
+    pub(crate) fn detach_client(&mut self, client_id: u64) -> Option<ClientShadow> {
+        self.shadows.remove(&client_id)
+    }
+
+    /// Records that `client_id` applied every replication message up to
+    /// `tick`. Returns `false` for unknown clients and stale acks.
+    pub fn acknowledge(&mut self, client_id: u64, tick: u64) -> bool {
+        self.shadows
+            .get_mut(&client_id)
+            .is_some_and(|shadow| shadow.acknowledge(tick))
+    }
+
+    /// Registers a client with previously detached shadow state. The next
+    /// [`replicate`](Self::replicate) then emits only what changed since the
+    /// shadow was captured.
+    pub(crate) fn attach_client(&mut self, client_id: u64, shadow: ClientShadow) {
+        self.shadows.insert(client_id, shadow);
+    }
+
     /// Runs one replication tick. Scans all entities with [`NetworkId`] in
     /// `world`, serializes their replicated components via `rep_set`, and
     /// produces per-client [`ReplicationMessages`].
@@ -204,8 +325,8 @@ impl ReplicationServerSystem {
         tick: u64,
     ) -> HashMap<u64, ReplicationMessages> {
         // Collect current replicated entities: NetworkId → Entity + serialized components.
-        let mut current_entities: HashMap<u64, Vec<(String, Vec<u8>)>> = HashMap::new();
-        let mut current_entity_set: HashSet<u64> = HashSet::new();
+        let mut current_entities: HashMap<NetworkId, Vec<(String, Vec<u8>)>> = HashMap::new();
+        let mut current_entity_set: HashSet<NetworkId> = HashSet::new();
 
         // We need a mutable reference for query, but only read. Use unsafe same pattern as authority.
         let world_ptr = world as *const World as *mut World;
@@ -215,29 +336,30 @@ impl ReplicationServerSystem {
         };
 
         for (entity, net_id) in &entities_with_net_id {
-            current_entity_set.insert(net_id.0);
+            current_entity_set.insert(*net_id);
             let mut components = Vec::new();
             for desc in rep_set.descriptors() {
                 if let Some(bytes) = (desc.serializer)(world, *entity) {
                     components.push((desc.tag.to_string(), bytes));
                 }
             }
-            current_entities.insert(net_id.0, components);
+            current_entities.insert(*net_id, components);
         }
 
         // For each client, diff against shadow.
         let mut result: HashMap<u64, ReplicationMessages> = HashMap::new();
 
         for (client_id, shadow) in &mut self.shadows {
-            let mut msgs = ReplicationMessages::default();
+            let mut msgs = ReplicationMessages {
+                tick,
+                ..ReplicationMessages::default()
+            };
 
             // Detect despawns: entities in shadow but not in current.
-            let shadow_ids: Vec<u64> = shadow.entities.keys().copied().collect();
+            let shadow_ids: Vec<NetworkId> = shadow.entities.keys().copied().collect();
             for nid in shadow_ids {
                 if !current_entity_set.contains(&nid) {
-                    msgs.despawns.push(DespawnEntity {
-                        network_id: NetworkId(nid),
-                    });
+                    msgs.despawns.push(DespawnEntity { network_id: nid });
                     shadow.entities.remove(&nid);
                 }
             }
@@ -249,7 +371,7 @@ impl ReplicationServerSystem {
                     Entry::Vacant(vacant) => {
                         // New entity → spawn.
                         msgs.spawns.push(SpawnEntity {
-                            network_id: NetworkId(nid),
+                            network_id: nid,
                             components: components.clone(),
                         });
                         let comp_map: HashMap<String, Vec<u8>> =
@@ -272,7 +394,7 @@ impl ReplicationServerSystem {
                         }
                         if !changed.is_empty() {
                             msgs.updates.push(EntityUpdate {
-                                network_id: NetworkId(nid),
+                                network_id: nid,
                                 tick,
                                 changed_components: changed,
                             });
@@ -281,6 +403,12 @@ impl ReplicationServerSystem {
                 }
             }
 
+            if !(msgs.spawns.is_empty() && msgs.updates.is_empty() && msgs.despawns.is_empty()) {
+                if shadow.unacked.len() == MAX_UNACKED_TICKS {
+                    shadow.unacked.pop_front();
+                }
+                shadow.unacked.push_back((tick, shadow.entities.clone()));
+            }
             result.insert(*client_id, msgs);
         }
 
@@ -294,77 +422,6 @@ impl Default for ReplicationServerSystem {
     }
 }
 
-// ---------------------------------------------------------------------------
-// ReplicationClientSystem
-// ---------------------------------------------------------------------------
-
-/// Client-side replication system. Applies [`ReplicationMessages`] from the
-/// server to the local ECS [`World`].
-pub struct ReplicationClientSystem {
-    /// Maps `NetworkId` → local ECS [`Entity`].
-    net_to_local: HashMap<u64, Entity>,
-}
-
-impl ReplicationClientSystem {
-    /// Creates a new client replication system.
-    pub fn new() -> Self {
-        Self {
-            net_to_local: HashMap::new(),
-        }
-    }
-
-    /// Applies a batch of replication messages to the local `world`.
-    pub fn apply(
-        &mut self,
-        world: &mut World,
-        rep_set: &ReplicationSet,
-        msgs: &ReplicationMessages,
-    ) {
-        // Process spawns.
-        for spawn in &msgs.spawns {
-            let entity = world.spawn(spawn.network_id).id();
-            self.net_to_local.insert(spawn.network_id.0, entity);
-            for (tag, bytes) in &spawn.components {
-                if let Some(desc) = rep_set.descriptors().iter().find(|d| d.tag == tag.as_str()) {
-                    (desc.deserializer)(world, entity, bytes);
-                }
-            }
-        }
-
-        // Process updates.
-        for update in &msgs.updates {
-            if let Some(&entity) = self.net_to_local.get(&update.network_id.0) {
-                for (tag, bytes) in &update.changed_components {
-                    if let Some(desc) = rep_set.descriptors().iter().find(|d| d.tag == tag.as_str())
-                    {
-                        (desc.deserializer)(world, entity, bytes);
-                    }
-                }
-            }
-        }
-
-        // Process despawns.
-        for despawn in &msgs.despawns {
-            if let Some(entity) = self.net_to_local.remove(&despawn.network_id.0)
-                && world.get_entity(entity).is_ok()
-            {
-                world.despawn(entity);
-            }
-        }
-    }
-
-    /// Returns the local [`Entity`] for a given [`NetworkId`], if known.
-    pub fn local_entity(&self, net_id: NetworkId) -> Option<Entity> {
-        self.net_to_local.get(&net_id.0).copied()
-    }
-}
-
-impl Default for ReplicationClientSystem {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
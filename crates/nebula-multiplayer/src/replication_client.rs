@@ -0,0 +1,179 @@
+//! Client side of entity replication: applies the server's
+//! [`ReplicationMessages`] to the local ECS world.
+
+use std::collections::HashMap;
+
+use bevy_ecs::prelude::*;
+use glam::{DVec3, Quat};
+
+use crate::interpolation::{InterpolatedTransform, InterpolationBuffer, RemoteSnapshot};
+use crate::network_id::NetworkId;
+use crate::replication::{ReplicationMessages, ReplicationSet};
+
+/// Client-side replication system. Applies [`ReplicationMessages`] from the
+/// server to the local ECS [`World`].
+///
+/// Updates to components registered with
+/// [`ReplicationSet::register_interpolated`] are not applied on arrival;
+/// they are pushed into the entity's [`InterpolationBuffer`] at the server
+/// time of their tick, for
+/// [`interpolate_remote_entities_system`](crate::interpolate_remote_entities_system)
+/// to sample at render time.
+///
+/// An optional interest filter lets the client drop updates for entities it
+/// no longer cares about (outside its interest area, say) without waiting
+/// for the server to stop sending them.
+pub struct ReplicationClientSystem {
+    /// Maps `NetworkId` → local ECS [`Entity`].
+    net_to_local: HashMap<NetworkId, Entity>,
+    /// Entities for which updates are applied; `None` applies all.
+    interest_filter: Option<Box<dyn Fn(NetworkId) -> bool + Send + Sync>>,
+    /// Updates dropped by the interest filter since creation.
+    skipped_updates: u64,
+}
+
+impl ReplicationClientSystem {
+    /// Creates a new client replication system.
+    pub fn new() -> Self {
+        Self {
+            net_to_local: HashMap::new(),
+            interest_filter: None,
+            skipped_updates: 0,
+        }
+    }
+
+    /// Only apply updates for entities for which `filter` returns `true`.
+    ///
+    /// Spawns and despawns are still applied, so the local entity mapping
+    /// stays consistent with the server; filtered-out entities simply keep
+    /// their last applied state.
+    pub fn set_interest_filter(
+        &mut self,
+        filter: impl Fn(NetworkId) -> bool + Send + Sync + 'static,
+    ) {
+        self.interest_filter = Some(Box::new(filter));
+    }
+
+    /// Removes the interest filter, so every update is applied again.
+    pub fn clear_interest_filter(&mut self) {
+        self.interest_filter = None;
+    }
+
+    /// Number of updates skipped by the interest filter.
+    pub fn skipped_update_count(&self) -> u64 {
+        self.skipped_updates
+    }
+
+    /// Applies a batch of replication messages to the local `world`.
+    pub fn apply(
+        &mut self,
+        world: &mut World,
+        rep_set: &ReplicationSet,
+        msgs: &ReplicationMessages,
+    ) {
+        // Process spawns.
+        for spawn in &msgs.spawns {
+            let entity = world.spawn(spawn.network_id).id();
+            self.net_to_local.insert(spawn.network_id, entity);
+            for (tag, bytes) in &spawn.components {
+                if let Some(desc) = rep_set.descriptors().iter().find(|d| d.tag == tag.as_str()) {
+                    (desc.deserializer)(world, entity, bytes);
+                    if let Some(pose) = desc.pose.and_then(|pose| pose(bytes)) {
+                        push_snapshot(world, entity, msgs.server_time(), pose);
+                    }
+                }
+            }
+        }
+
+        // Process updates.
+        for update in &msgs.updates {
+            if let Some(filter) = &self.interest_filter
+                && !filter(update.network_id)
+            {
+                self.skipped_updates += 1;
+                continue;
+            }
+            if let Some(&entity) = self.net_to_local.get(&update.network_id) {
+                for (tag, bytes) in &update.changed_components {
+                    let Some(desc) = rep_set.descriptors().iter().find(|d| d.tag == tag.as_str())
+                    else {
+                        continue;
+                    };
+                    match desc.pose {
+                        Some(pose) => {
+                            if let Some(pose) = pose(bytes) {
+                                push_snapshot(world, entity, update.server_time(), pose);
+                            }
+                        }
+                        None => (desc.deserializer)(world, entity, bytes),
+                    }
+                }
+            }
+        }
+
+        // Process despawns.
+        for despawn in &msgs.despawns {
+            if let Some(entity) = self.net_to_local.remove(&despawn.network_id)
+                && world.get_entity(entity).is_ok()
+            {
+                world.despawn(entity);
+            }
+        }
+    }
+
+    /// Returns the local [`Entity`] for a given [`NetworkId`], if known.
+    pub fn local_entity(&self, net_id: NetworkId) -> Option<Entity> {
+        self.net_to_local.get(&net_id).copied()
+    }
+}
+
+/// Buffers `pose` for `entity` at `server_time`, adding the interpolation
+/// components on the first snapshot.
+fn push_snapshot(world: &mut World, entity: Entity, server_time: f64, pose: (DVec3, Quat)) {
+    let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    let (position_mm, rotation) = pose;
+    let snapshot = RemoteSnapshot {
+        server_time,
+        position_mm,
+        rotation,
+    };
+    match entity_mut.get_mut::<InterpolationBuffer>() {
+        Some(mut buffer) => buffer.push(snapshot),
+        None => {
+            let mut buffer = InterpolationBuffer::default();
+            buffer.push(snapshot);
+            entity_mut.insert((
+                buffer,
+                InterpolatedTransform {
+                    position_mm,
+                    rotation,
+                },
+            ));
+        }
+    }
+}
+
+impl Default for ReplicationClientSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_system_stays_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ReplicationClientSystem>();
+
+        let mut client_sys = ReplicationClientSystem::new();
+        client_sys.set_interest_filter(|id| id.id % 2 == 0);
+        std::thread::spawn(move || client_sys.skipped_update_count())
+            .join()
+            .unwrap();
+    }
+}
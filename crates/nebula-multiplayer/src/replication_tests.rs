@@ -1,6 +1,7 @@
 //! Unit tests for entity replication.
 
 use super::*;
+use crate::replication_client::ReplicationClientSystem;
 
 // Test component types.
 #[derive(Component, Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -29,7 +30,7 @@ fn test_entity_spawned_on_server_appears_on_client() {
 
     // Server world with one replicated entity.
     let mut server_world = World::new();
-    let net_id = server.allocate_network_id();
+    let net_id = server.allocate_network_id().unwrap();
     server_world.spawn((
         net_id,
         Position128 {
@@ -77,7 +78,7 @@ fn test_component_change_replicates() {
     server.add_client(1);
 
     let mut server_world = World::new();
-    let net_id = server.allocate_network_id();
+    let net_id = server.allocate_network_id().unwrap();
     let entity = server_world
         .spawn((net_id, Position128 { x: 0, y: 0, z: 0 }, MeshHandle(1)))
         .id();
@@ -106,7 +107,7 @@ fn test_unchanged_components_not_sent() {
     server.add_client(1);
 
     let mut server_world = World::new();
-    let net_id = server.allocate_network_id();
+    let net_id = server.allocate_network_id().unwrap();
     server_world.spawn((net_id, Position128 { x: 1, y: 2, z: 3 }, MeshHandle(5)));
 
     // First tick: spawn.
@@ -127,7 +128,7 @@ fn test_network_id_is_consistent() {
     server.add_client(1);
 
     let mut server_world = World::new();
-    let net_id = NetworkId(42);
+    let net_id = NetworkId::new(42);
     server_world.spawn((
         net_id,
         Position128 {
@@ -145,10 +146,10 @@ fn test_network_id_is_consistent() {
     let mut client_sys = ReplicationClientSystem::new();
     client_sys.apply(&mut client_world, &rep_set, client_msgs);
 
-    // Client entity should have NetworkId(42).
-    let local_entity = client_sys.local_entity(NetworkId(42)).unwrap();
+    // Client entity should have NetworkId::new(42).
+    let local_entity = client_sys.local_entity(NetworkId::new(42)).unwrap();
     let client_net_id = client_world.get::<NetworkId>(local_entity).unwrap();
-    assert_eq!(*client_net_id, NetworkId(42));
+    assert_eq!(*client_net_id, NetworkId::new(42));
 
     // Modify on server, replicate again.
     // Find the server entity by querying.
@@ -156,7 +157,7 @@ fn test_network_id_is_consistent() {
         let world_ptr = &server_world as *const World as *mut World;
         unsafe {
             let mut q = (*world_ptr).query::<(Entity, &NetworkId)>();
-            q.iter(&*world_ptr).find(|(_, n)| n.0 == 42).unwrap().0
+            q.iter(&*world_ptr).find(|(_, n)| n.id == 42).unwrap().0
         }
     };
     server_world
@@ -168,7 +169,7 @@ fn test_network_id_is_consistent() {
     let client_msgs = &msgs[&1];
     // Update should reference the same NetworkId.
     assert_eq!(client_msgs.updates.len(), 1);
-    assert_eq!(client_msgs.updates[0].network_id, NetworkId(42));
+    assert_eq!(client_msgs.updates[0].network_id, NetworkId::new(42));
 }
 
 #[test]
@@ -178,7 +179,7 @@ fn test_despawn_replicates() {
     server.add_client(1);
 
     let mut server_world = World::new();
-    let net_id = server.allocate_network_id();
+    let net_id = server.allocate_network_id().unwrap();
     let entity = server_world
         .spawn((net_id, Position128 { x: 0, y: 0, z: 0 }))
         .id();
@@ -203,3 +204,126 @@ fn test_despawn_replicates() {
     client_sys.apply(&mut client_world, &rep_set, client_msgs);
     assert!(client_sys.local_entity(net_id).is_none());
 }
+
+/// Helper: replicate one spawned entity to a fresh client, then move it on
+/// the server and return the resulting update messages.
+fn spawn_then_move(
+    rep_set: &ReplicationSet,
+    client_world: &mut World,
+    client_sys: &mut ReplicationClientSystem,
+) -> (NetworkId, ReplicationMessages) {
+    let mut server = ReplicationServerSystem::new();
+    server.add_client(1);
+    let mut server_world = World::new();
+    let net_id = server.allocate_network_id().unwrap();
+    let entity = server_world
+        .spawn((net_id, Position128 { x: 1, y: 2, z: 3 }))
+        .id();
+
+    let mut msgs = server.replicate(&server_world, rep_set, 1);
+    client_sys.apply(client_world, rep_set, &msgs.remove(&1).unwrap());
+
+    server_world.get_mut::<Position128>(entity).unwrap().x = 500;
+    let mut msgs = server.replicate(&server_world, rep_set, 2);
+    (net_id, msgs.remove(&1).unwrap())
+}
+
+#[test]
+fn test_interest_filter_skips_updates_for_filtered_entities() {
+    let rep_set = test_rep_set();
+    let mut client_world = World::new();
+    let mut client_sys = ReplicationClientSystem::new();
+    let (net_id, update) = spawn_then_move(&rep_set, &mut client_world, &mut client_sys);
+    assert_eq!(update.updates.len(), 1);
+
+    client_sys.set_interest_filter(move |id| id != net_id);
+    client_sys.apply(&mut client_world, &rep_set, &update);
+
+    let local = client_sys.local_entity(net_id).unwrap();
+    assert_eq!(
+        *client_world.get::<Position128>(local).unwrap(),
+        Position128 { x: 1, y: 2, z: 3 }
+    );
+    assert_eq!(client_sys.skipped_update_count(), 1);
+}
+
+#[test]
+fn test_clearing_interest_filter_applies_updates_again() {
+    let rep_set = test_rep_set();
+    let mut client_world = World::new();
+    let mut client_sys = ReplicationClientSystem::new();
+    let (net_id, update) = spawn_then_move(&rep_set, &mut client_world, &mut client_sys);
+
+    client_sys.set_interest_filter(|_| false);
+    client_sys.apply(&mut client_world, &rep_set, &update);
+    client_sys.clear_interest_filter();
+    client_sys.apply(&mut client_world, &rep_set, &update);
+
+    let local = client_sys.local_entity(net_id).unwrap();
+    assert_eq!(client_world.get::<Position128>(local).unwrap().x, 500);
+    assert_eq!(client_sys.skipped_update_count(), 1);
+}
+
+#[derive(Component, Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct RemotePose {
+    x_mm: f64,
+}
+
+impl crate::interpolation::InterpolatedPose for RemotePose {
+    fn pose(&self) -> (glam::DVec3, glam::Quat) {
+        (glam::DVec3::new(self.x_mm, 0.0, 0.0), glam::Quat::IDENTITY)
+    }
+}
+
+#[test]
+fn test_interpolated_updates_are_buffered_and_sampled_at_render_time() {
+    use crate::clock::server_time_at_tick;
+    use crate::interpolation::{
+        InterpolatedTransform, InterpolationBuffer, InterpolationClock, InterpolationSettings,
+        interpolate_remote_entities_system,
+    };
+
+    let mut rep_set = ReplicationSet::new();
+    rep_set.register_interpolated::<RemotePose>("RemotePose");
+    let mut server = ReplicationServerSystem::new();
+    server.add_client(1);
+    let mut server_world = World::new();
+    let net_id = server.allocate_network_id().unwrap();
+    let entity = server_world.spawn((net_id, RemotePose { x_mm: 0.0 })).id();
+
+    let mut client_world = World::new();
+    let mut client_sys = ReplicationClientSystem::new();
+    // 10 Hz replication: one message every six 60 Hz ticks, 1 m per message.
+    for step in 0..4_u64 {
+        server_world.get_mut::<RemotePose>(entity).unwrap().x_mm = step as f64 * 1_000.0;
+        let mut msgs = server.replicate(&server_world, &rep_set, step * 6);
+        client_sys.apply(&mut client_world, &rep_set, &msgs.remove(&1).unwrap());
+    }
+
+    let local = client_sys.local_entity(net_id).unwrap();
+    assert_eq!(
+        client_world.get::<RemotePose>(local).unwrap().x_mm,
+        0.0,
+        "updates must not snap the replicated component"
+    );
+    assert_eq!(
+        client_world
+            .get::<InterpolationBuffer>(local)
+            .unwrap()
+            .len(),
+        4
+    );
+
+    // Render halfway between the second and third messages.
+    let settings = InterpolationSettings::default();
+    let mut clock = InterpolationClock::new(0.0, &settings);
+    clock.server_time = (server_time_at_tick(6) + server_time_at_tick(12)) / 2.0 + clock.delay_secs;
+    client_world.insert_resource(settings);
+    client_world.insert_resource(clock);
+    let mut schedule = Schedule::default();
+    schedule.add_systems(interpolate_remote_entities_system);
+    schedule.run(&mut client_world);
+
+    let transform = client_world.get::<InterpolatedTransform>(local).unwrap();
+    assert!((transform.position_mm.x - 1_500.0).abs() < 1e-6);
+}
@@ -0,0 +1,252 @@
+//! Session resume: reconnecting within the grace period without a full
+//! re-join.
+//!
+//! When a client drops, the server captures a [`SessionResumeState`] — the
+//! player's position, interest set, pending chunk queue and the client's
+//! replication shadow as of the last acknowledged tick — and files it in a
+//! [`SessionResumeRegistry`] under a single-use [`ResumeToken`]. The player
+//! entity stays in the world while the session is suspended.
+//!
+//! If the client reconnects (see `nebula_net::reconnect_loop`) and presents
+//! the token before the grace period ends, [`resume_session`] restores the
+//! shadow so the next replication pass yields only what changed since the
+//! suspension, delivered as [`ResumeAccepted`]. Otherwise the client falls
+//! back to the full join flow and [`InitialWorldState`](crate::InitialWorldState).
+
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::authority::AuthoritativeWorld;
+use crate::chunk_delivery::{ChunkStreamer, ClientChunkStream};
+use crate::chunk_streaming::ChunkSendQueue;
+use crate::interest::SpatialInterestSystem;
+use crate::network_id::NetworkId;
+use crate::player_session::{PlayerSaveData, save_player_state};
+use crate::replication::{
+    ClientShadow, DespawnEntity, EntityUpdate, ReplicationMessages, ReplicationServerSystem,
+    SpawnEntity,
+};
+
+/// Opaque single-use token a client presents to resume a suspended session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ResumeToken(pub u64);
+
+/// Server-side state preserved while a session is suspended.
+#[derive(Debug)]
+pub struct SessionResumeState {
+    /// Client identifier the session belonged to.
+    pub client_id: u64,
+    /// The player's network entity id.
+    pub network_id: NetworkId,
+    /// Player position and name at suspension.
+    pub player: PlayerSaveData,
+    /// Inventory contents (placeholder until inventories are replicated).
+    pub inventory: Vec<u8>,
+    /// Entities that were inside the client's interest area.
+    pub interest: HashSet<NetworkId>,
+    /// Last replication tick the client acknowledged.
+    pub last_acked_tick: u64,
+    /// Chunks still waiting to be streamed to the client.
+    pub pending_chunks: ChunkSendQueue,
+    /// What the client is known to hold, for delta replication on resume.
+    replication: ClientShadow,
+}
+
+impl SessionResumeState {
+    /// Capture the session of `client_id` and detach it from replication
+    /// and chunk streaming.
+    ///
+    /// Replication is rewound to the last tick the client acknowledged, so
+    /// anything sent after it is sent again on resume. Chunks that were sent
+    /// but not yet acknowledged go back into the pending queue.
+    ///
+    /// Returns `None` if the player entity cannot be found.
+    pub fn capture(
+        world: &AuthoritativeWorld,
+        replication: &mut ReplicationServerSystem,
+        interest: &SpatialInterestSystem,
+        chunks: &mut ChunkStreamer,
+        client_id: u64,
+        player_name: &str,
+        network_id: NetworkId,
+    ) -> Option<Self> {
+        let player = save_player_state(world, player_name, client_id)?;
+        let mut shadow = replication.detach_client(client_id).unwrap_or_default();
+        let last_acked_tick = shadow.rewind_to_ack();
+        let interest = interest
+            .interest_set(client_id)
+            .map(|set| set.current.clone())
+            .unwrap_or_default();
+        let pending_chunks = chunks
+            .remove_client(client_id)
+            .map(ClientChunkStream::into_pending)
+            .unwrap_or_default();
+        Some(Self {
+            client_id,
+            network_id,
+            player,
+            inventory: Vec::new(),
+            interest,
+            last_acked_tick,
+            pending_chunks,
+            replication: shadow,
+        })
+    }
+}
+
+/// Server-to-client reply to a successful resume: everything that changed
+/// since the client's last acknowledged tick.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResumeAccepted {
+    /// The player's own network entity id.
+    pub your_network_id: NetworkId,
+    /// Current server tick.
+    pub server_tick: u64,
+    /// Last tick the client acknowledged before suspension.
+    pub resumed_from_tick: u64,
+    /// Entities that appeared since suspension.
+    pub spawns: Vec<SpawnEntity>,
+    /// Component changes on entities the client already knows.
+    pub updates: Vec<EntityUpdate>,
+    /// Entities that disappeared since suspension.
+    pub despawns: Vec<DespawnEntity>,
+}
+
+impl ResumeAccepted {
+    /// Build the reply from the first replication pass after resuming.
+    pub fn new(resumed: &ResumedSession, server_tick: u64, delta: ReplicationMessages) -> Self {
+        Self {
+            your_network_id: resumed.network_id,
+            server_tick,
+            resumed_from_tick: resumed.last_acked_tick,
+            spawns: delta.spawns,
+            updates: delta.updates,
+            despawns: delta.despawns,
+        }
+    }
+}
+
+/// A suspended session that was successfully resumed.
+#[derive(Debug)]
+pub struct ResumedSession {
+    /// The player's network entity id.
+    pub network_id: NetworkId,
+    /// Last tick the client acknowledged before suspension.
+    pub last_acked_tick: u64,
+    /// Entities that were inside the client's interest area.
+    pub interest: HashSet<NetworkId>,
+    /// Chunks still waiting to be streamed.
+    pub pending_chunks: ChunkSendQueue,
+    /// Inventory contents (placeholder).
+    pub inventory: Vec<u8>,
+}
+
+/// Result of a reconnecting client presenting a resume token.
+#[derive(Debug)]
+pub enum ResumeOutcome {
+    /// The session was restored; replicate and send [`ResumeAccepted`].
+    Resumed(ResumedSession),
+    /// The token was unknown, already used, or expired; run the full join
+    /// flow and send an [`InitialWorldState`](crate::InitialWorldState).
+    FullJoin,
+}
+
+/// Suspended sessions keyed by resume token, expiring with the grace period.
+pub struct SessionResumeRegistry {
+    grace_period: Duration,
+    suspended: HashMap<ResumeToken, (Instant, SessionResumeState)>,
+    hasher: RandomState,
+    counter: u64,
+}
+
+impl SessionResumeRegistry {
+    /// Create a registry whose entries expire after `grace_period`.
+    pub fn new(grace_period: Duration) -> Self {
+        Self {
+            grace_period,
+            suspended: HashMap::new(),
+            hasher: RandomState::new(),
+            counter: 0,
+        }
+    }
+
+    /// File a suspended session and return the token that resumes it.
+    pub fn suspend(&mut self, state: SessionResumeState, now: Instant) -> ResumeToken {
+        let token = loop {
+            self.counter += 1;
+            let token = ResumeToken(self.hasher.hash_one(self.counter));
+            if !self.suspended.contains_key(&token) {
+                break token;
+            }
+        };
+        self.suspended.insert(token, (now, state));
+        token
+    }
+
+    /// Consume `token`. Returns the state if it exists and has not expired;
+    /// a token can never be redeemed twice. Expired entries are left for
+    /// [`expire`](Self::expire) so the player still gets cleaned up.
+    pub fn take(&mut self, token: ResumeToken, now: Instant) -> Option<SessionResumeState> {
+        let (since, _) = self.suspended.get(&token)?;
+        if now.duration_since(*since) > self.grace_period {
+            return None;
+        }
+        self.suspended.remove(&token).map(|(_, state)| state)
+    }
+
+    /// Remove and return every session whose grace period has elapsed, so
+    /// the caller can persist and despawn the players.
+    pub fn expire(&mut self, now: Instant) -> Vec<SessionResumeState> {
+        let expired: Vec<ResumeToken> = self
+            .suspended
+            .iter()
+            .filter(|(_, (since, _))| now.duration_since(*since) > self.grace_period)
+            .map(|(token, _)| *token)
+            .collect();
+        expired
+            .into_iter()
+            .filter_map(|token| self.suspended.remove(&token).map(|(_, state)| state))
+            .collect()
+    }
+
+    /// Number of sessions currently suspended.
+    pub fn len(&self) -> usize {
+        self.suspended.len()
+    }
+
+    /// Whether no sessions are suspended.
+    pub fn is_empty(&self) -> bool {
+        self.suspended.is_empty()
+    }
+}
+
+/// Redeem `token` for `client_id`. On success the client's replication
+/// shadow is re-attached, so the next [`ReplicationServerSystem::replicate`]
+/// produces only the delta since suspension.
+pub fn resume_session(
+    registry: &mut SessionResumeRegistry,
+    replication: &mut ReplicationServerSystem,
+    client_id: u64,
+    token: ResumeToken,
+    now: Instant,
+) -> ResumeOutcome {
+    let Some(state) = registry.take(token, now) else {
+        return ResumeOutcome::FullJoin;
+    };
+    replication.attach_client(client_id, state.replication);
+    ResumeOutcome::Resumed(ResumedSession {
+        network_id: state.network_id,
+        last_acked_tick: state.last_acked_tick,
+        interest: state.interest,
+        pending_chunks: state.pending_chunks,
+        inventory: state.inventory,
+    })
+}
+
+#[cfg(test)]
+#[path = "session_resume_tests.rs"]
+mod tests;
@@ -0,0 +1,279 @@
+//! Unit tests for session resume.
+
+use super::*;
+use crate::authority::PlayerState;
+use crate::budget::BandwidthConfig;
+use crate::chunk_delivery::ChunkDeliveryConfig;
+use crate::chunk_streaming::{ChunkId, ChunkSendEntry, ChunkStreamConfig};
+use crate::interest::{InterestArea, InterestPosition, TrackedEntity};
+use crate::player_session::spawn_player;
+use crate::replication::ReplicationSet;
+
+struct Server {
+    world: AuthoritativeWorld,
+    repl: ReplicationServerSystem,
+    rep_set: ReplicationSet,
+    interest: SpatialInterestSystem,
+    chunks: ChunkStreamer,
+    registry: SessionResumeRegistry,
+}
+
+fn chunk(x: i32) -> ChunkId {
+    ChunkId {
+        face: 0,
+        lod: 0,
+        x,
+        y: 0,
+        z: 0,
+    }
+}
+
+impl Server {
+    fn new(grace: Duration) -> Self {
+        let mut rep_set = ReplicationSet::new();
+        rep_set.register::<NetworkId>("NetworkId");
+        rep_set.register::<PlayerState>("PlayerState");
+        Self {
+            world: AuthoritativeWorld::new(),
+            repl: ReplicationServerSystem::new(),
+            rep_set,
+            interest: SpatialInterestSystem::new(),
+            chunks: ChunkStreamer::new(
+                ChunkStreamConfig::default(),
+                ChunkDeliveryConfig::default(),
+                BandwidthConfig::default(),
+            ),
+            registry: SessionResumeRegistry::new(grace),
+        }
+    }
+
+    fn join(&mut self, client_id: u64) -> NetworkId {
+        self.interest.add_client(
+            client_id,
+            InterestArea::default(),
+            InterestPosition::new(0.0, 0.0, 0.0),
+        );
+        self.chunks.add_client(client_id);
+        spawn_player(&mut self.world, &mut self.repl, client_id, None)
+            .expect("network id available")
+            .1
+    }
+
+    fn replicate(&mut self) -> HashMap<u64, ReplicationMessages> {
+        self.world.advance_tick();
+        self.repl
+            .replicate(self.world.world(), &self.rep_set, self.world.tick())
+    }
+
+    /// Replicate and have every client acknowledge the tick.
+    fn replicate_acked(&mut self) -> HashMap<u64, ReplicationMessages> {
+        let msgs = self.replicate();
+        for &client_id in msgs.keys() {
+            self.repl.acknowledge(client_id, self.world.tick());
+        }
+        msgs
+    }
+
+    /// Simulate the connection of `client_id` dying.
+    fn kill(&mut self, client_id: u64, network_id: NetworkId, now: Instant) -> ResumeToken {
+        let state = SessionResumeState::capture(
+            &self.world,
+            &mut self.repl,
+            &self.interest,
+            &mut self.chunks,
+            client_id,
+            "Alice",
+            network_id,
+        )
+        .expect("player exists");
+        self.registry.suspend(state, now)
+    }
+}
+
+#[test]
+fn test_resume_within_grace_receives_only_changes() {
+    let mut server = Server::new(Duration::from_secs(60));
+    let net_a = server.join(1);
+    let net_b = server.join(2);
+    let net_c = server.join(3);
+    server.replicate_acked();
+
+    let t0 = Instant::now();
+    let token = server.kill(1, net_a, t0);
+
+    // While client 1 is away: B moves, C stays idle, D joins.
+    server.world.find_player_mut(2).expect("player 2").x = 9_000;
+    let net_d = server.join(4);
+    server.replicate();
+
+    let outcome = resume_session(
+        &mut server.registry,
+        &mut server.repl,
+        1,
+        token,
+        t0 + Duration::from_secs(5),
+    );
+    let ResumeOutcome::Resumed(resumed) = outcome else {
+        panic!("expected resume within grace period");
+    };
+    let mut msgs = server.replicate();
+    let delta = msgs.remove(&1).expect("client 1");
+    let accepted = ResumeAccepted::new(&resumed, server.world.tick(), delta);
+
+    assert_eq!(accepted.your_network_id, net_a);
+    let spawned: Vec<_> = accepted.spawns.iter().map(|s| s.network_id).collect();
+    let updated: Vec<_> = accepted.updates.iter().map(|u| u.network_id).collect();
+    assert_eq!(spawned, vec![net_d]);
+    assert_eq!(updated, vec![net_b]);
+    assert!(!updated.contains(&net_c) && !spawned.contains(&net_c));
+    assert!(accepted.despawns.is_empty());
+    assert!(server.world.find_player(1).is_some(), "entity kept alive");
+}
+
+#[test]
+fn test_token_is_single_use() {
+    let mut server = Server::new(Duration::from_secs(60));
+    let net_a = server.join(1);
+    server.replicate();
+
+    let now = Instant::now();
+    let token = server.kill(1, net_a, now);
+    assert!(matches!(
+        resume_session(&mut server.registry, &mut server.repl, 1, token, now),
+        ResumeOutcome::Resumed(_)
+    ));
+    assert!(matches!(
+        resume_session(&mut server.registry, &mut server.repl, 1, token, now),
+        ResumeOutcome::FullJoin
+    ));
+}
+
+#[test]
+fn test_resume_after_expiry_falls_back_to_full_join() {
+    let mut server = Server::new(Duration::from_secs(10));
+    let net_a = server.join(1);
+    server.join(2);
+    server.replicate();
+
+    let t0 = Instant::now();
+    let token = server.kill(1, net_a, t0);
+    let late = t0 + Duration::from_secs(11);
+
+    let expired = server.registry.expire(late);
+    assert_eq!(expired.len(), 1);
+    assert!(server.registry.is_empty());
+
+    let outcome = resume_session(&mut server.registry, &mut server.repl, 1, token, late);
+    assert!(matches!(outcome, ResumeOutcome::FullJoin));
+
+    // Full join: the client has no shadow, so it is sent every entity.
+    server.repl.add_client(1);
+    let msgs = server.replicate();
+    assert_eq!(msgs[&1].spawns.len(), 2);
+}
+
+#[test]
+fn test_take_rejects_expired_token_before_sweep() {
+    let mut server = Server::new(Duration::from_millis(100));
+    let net_a = server.join(1);
+    let t0 = Instant::now();
+    let token = server.kill(1, net_a, t0);
+
+    assert!(
+        server
+            .registry
+            .take(token, t0 + Duration::from_secs(1))
+            .is_none()
+    );
+    // Still handed to the sweep so the player can be persisted.
+    assert_eq!(server.registry.expire(t0 + Duration::from_secs(1)).len(), 1);
+}
+
+#[test]
+fn test_capture_records_acked_tick_not_last_sent() {
+    let mut server = Server::new(Duration::from_secs(60));
+    let net_a = server.join(1);
+    server.join(2);
+    server.replicate_acked();
+    let acked = server.world.tick();
+
+    // Sent but never acknowledged before the connection died.
+    server.world.find_player_mut(2).expect("player 2").x = 4_000;
+    server.replicate();
+
+    let now = Instant::now();
+    let token = server.kill(1, net_a, now);
+    let ResumeOutcome::Resumed(resumed) =
+        resume_session(&mut server.registry, &mut server.repl, 1, token, now)
+    else {
+        panic!("expected resume");
+    };
+    assert_eq!(resumed.last_acked_tick, acked);
+    assert!(resumed.last_acked_tick < server.world.tick());
+
+    // The unacknowledged move is sent again.
+    let delta = server.replicate().remove(&1).expect("client 1");
+    assert_eq!(delta.updates.len(), 1);
+    assert!(delta.spawns.is_empty());
+}
+
+#[test]
+fn test_resume_with_chunks_in_flight_keeps_interest_and_pending_chunks() {
+    let mut server = Server::new(Duration::from_secs(60));
+    let net_a = server.join(1);
+    let net_b = server.join(2);
+    server.replicate_acked();
+    server.interest.evaluate(&[
+        TrackedEntity {
+            network_id: net_b,
+            position: InterestPosition::new(10.0, 0.0, 0.0),
+        },
+        TrackedEntity {
+            network_id: NetworkId::new(99),
+            position: InterestPosition::new(10_000.0, 0.0, 0.0),
+        },
+    ]);
+
+    for x in 0..12 {
+        server.chunks.enqueue(
+            1,
+            ChunkSendEntry {
+                chunk_id: chunk(x),
+                priority: f64::from(x),
+            },
+        );
+    }
+    let sent = server.chunks.flush_tick(1, |_| Some(vec![7; 64]));
+    let in_flight = server.chunks.stats(1).expect("client 1").in_flight;
+    assert!(in_flight > 0 && !sent.is_empty());
+    // One chunk made it; the rest are still in flight or queued.
+    server.chunks.handle_ack(
+        1,
+        &crate::chunk_delivery::ChunkAck {
+            chunk_id: sent[0].1.chunk_id,
+        },
+    );
+
+    let now = Instant::now();
+    let token = server.kill(1, net_a, now);
+    assert!(server.chunks.stats(1).is_none(), "stream detached");
+
+    let ResumeOutcome::Resumed(resumed) =
+        resume_session(&mut server.registry, &mut server.repl, 1, token, now)
+    else {
+        panic!("expected resume");
+    };
+    assert_eq!(resumed.interest, HashSet::from([net_b]));
+    let pending: HashSet<ChunkId> = resumed
+        .pending_chunks
+        .queue
+        .iter()
+        .map(|e| e.chunk_id)
+        .collect();
+    let expected: HashSet<ChunkId> = (1..12).map(chunk).collect();
+    assert_eq!(pending, expected, "in-flight chunks are pending again");
+
+    server.chunks.resume_client(1, resumed.pending_chunks);
+    let resent = server.chunks.flush_tick(2, |_| Some(vec![7; 64]));
+    assert_eq!(resent[0].1.chunk_id, chunk(1), "nearest unacked first");
+}
@@ -2,7 +2,8 @@
 //! state for crash recovery and orderly restarts.
 //!
 //! Supports full and incremental snapshots. Incremental snapshots only capture
-//! chunks modified since the last snapshot, minimizing I/O cost.
+//! chunks modified since the last snapshot, minimizing I/O cost; see
+//! [`crate::snapshot_delta`] for writing and replaying them.
 
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
@@ -11,14 +12,15 @@ use std::time::{Duration, Instant};
 use serde::{Deserialize, Serialize};
 
 use crate::chunk_streaming::ChunkId;
-use crate::replication::{ComponentTypeTag, NetworkId};
+use crate::network_id::NetworkId;
+use crate::replication::ComponentTypeTag;
 
 // ---------------------------------------------------------------------------
 // Snapshot version
 // ---------------------------------------------------------------------------
 
-/// Current snapshot format version.
-pub const CURRENT_SNAPSHOT_VERSION: u32 = 1;
+/// Current snapshot format version. Version 2 added [`NetworkId`] generations.
+pub const CURRENT_SNAPSHOT_VERSION: u32 = 2;
 
 // ---------------------------------------------------------------------------
 // Snapshot structures
@@ -87,6 +89,10 @@ pub struct SnapshotConfig {
     pub snapshot_dir: PathBuf,
     /// Maximum number of snapshot files to retain. Default: 24.
     pub max_snapshots_retained: usize,
+    /// With `incremental` enabled, every `full_every`-th snapshot is a full
+    /// one and the rest are deltas. Default: 6 (one full snapshot every
+    /// 30 minutes at the default interval).
+    pub full_every: u32,
 }
 
 impl Default for SnapshotConfig {
@@ -96,10 +102,21 @@ impl Default for SnapshotConfig {
             incremental: true,
             snapshot_dir: PathBuf::from("./snapshots/"),
             max_snapshots_retained: 24,
+            full_every: 6,
         }
     }
 }
 
+/// Whether a snapshot captures the whole world or only recent changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotKind {
+    /// Complete world state, written with [`write_snapshot`].
+    Full,
+    /// Changes since the previous snapshot, written with
+    /// [`write_incremental`](crate::snapshot_delta::write_incremental).
+    Incremental,
+}
+
 /// Timer that tracks when the next snapshot should be taken.
 #[derive(Debug)]
 pub struct SnapshotTimer {
@@ -107,6 +124,9 @@ pub struct SnapshotTimer {
     pub last_snapshot: Instant,
     /// Snapshot configuration.
     pub config: SnapshotConfig,
+    /// Incremental snapshots taken since the last full one, or `None` if no
+    /// full snapshot has been taken yet.
+    pub incrementals_since_full: Option<u32>,
 }
 
 impl SnapshotTimer {
@@ -115,9 +135,30 @@ impl SnapshotTimer {
         Self {
             last_snapshot: Instant::now(),
             config,
+            incrementals_since_full: None,
+        }
+    }
+
+    /// The kind of snapshot to take next. The first snapshot is always
+    /// full, as is every one when incremental snapshots are disabled.
+    pub fn next_kind(&self) -> SnapshotKind {
+        match self.incrementals_since_full {
+            Some(n) if self.config.incremental && n + 1 < self.config.full_every => {
+                SnapshotKind::Incremental
+            }
+            _ => SnapshotKind::Full,
         }
     }
 
+    /// Records that a snapshot of `kind` was just taken and resets the timer.
+    pub fn record(&mut self, kind: SnapshotKind) {
+        self.incrementals_since_full = Some(match kind {
+            SnapshotKind::Full => 0,
+            SnapshotKind::Incremental => self.incrementals_since_full.map_or(1, |n| n + 1),
+        });
+        self.reset();
+    }
+
     /// Returns `true` if enough time has elapsed for a new snapshot.
     pub fn should_snapshot(&self) -> bool {
         self.last_snapshot.elapsed() >= self.config.interval
@@ -133,10 +174,12 @@ impl SnapshotTimer {
 // Dirty chunk tracker
 // ---------------------------------------------------------------------------
 
-/// Tracks chunks modified since the last snapshot for incremental writes.
+/// Tracks chunks and entities modified since the last snapshot for
+/// incremental writes.
 #[derive(Debug, Default)]
 pub struct DirtyChunkTracker {
     dirty: HashSet<ChunkId>,
+    dirty_entities: HashSet<NetworkId>,
 }
 
 impl DirtyChunkTracker {
@@ -155,6 +198,28 @@ impl DirtyChunkTracker {
         std::mem::take(&mut self.dirty)
     }
 
+    /// Marks an entity as changed, spawned, or despawned since the last
+    /// snapshot.
+    pub fn mark_entity_dirty(&mut self, network_id: NetworkId) {
+        self.dirty_entities.insert(network_id);
+    }
+
+    /// Currently dirty chunk IDs.
+    pub fn chunks(&self) -> impl Iterator<Item = &ChunkId> {
+        self.dirty.iter()
+    }
+
+    /// Currently dirty entity IDs.
+    pub fn entities(&self) -> impl Iterator<Item = &NetworkId> {
+        self.dirty_entities.iter()
+    }
+
+    /// Forgets all dirty chunks and entities (call after a snapshot).
+    pub fn clear(&mut self) {
+        self.dirty.clear();
+        self.dirty_entities.clear();
+    }
+
     /// Returns the number of currently dirty chunks.
     pub fn len(&self) -> usize {
         self.dirty.len()
@@ -187,6 +252,27 @@ pub enum SnapshotError {
     /// Serialization / deserialization error.
     #[error("serialization error: {0}")]
     Serialization(String),
+    /// A snapshot chain must start from a full snapshot.
+    #[error("snapshot {0} is incremental and cannot start a chain")]
+    NotFullSnapshot(u64),
+    /// A delta does not extend the snapshot it is applied to.
+    #[error("delta {delta} extends snapshot {found:?}, expected {expected}")]
+    BrokenChain {
+        /// Identifier of the offending delta.
+        delta: u64,
+        /// Snapshot the chain has reached so far.
+        expected: u64,
+        /// Parent recorded in the delta.
+        found: Option<u64>,
+    },
+    /// A delta was taken before the snapshot it is applied to.
+    #[error("delta tick {delta_tick} precedes base tick {base_tick}")]
+    TickRegression {
+        /// Tick the chain has reached so far.
+        base_tick: u64,
+        /// Tick recorded in the delta.
+        delta_tick: u64,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -254,178 +340,6 @@ pub fn check_version(header: &SnapshotHeader) -> Result<(), SnapshotError> {
     Ok(())
 }
 
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
-
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    fn make_chunk_id(face: u8, x: i32, y: i32, z: i32) -> ChunkId {
-        ChunkId {
-            face,
-            lod: 0,
-            x,
-            y,
-            z,
-        }
-    }
-
-    fn make_chunk_snapshot(face: u8, x: i32, y: i32, z: i32, data: &[u8]) -> ChunkSnapshot {
-        ChunkSnapshot {
-            chunk_id: make_chunk_id(face, x, y, z),
-            voxel_data: data.to_vec(),
-        }
-    }
-
-    fn make_entity(id: u64) -> EntitySnapshot {
-        EntitySnapshot {
-            network_id: NetworkId(id),
-            components: vec![("Position".to_string(), vec![1, 2, 3])],
-        }
-    }
-
-    fn make_full_snapshot(id: u64, chunks: Vec<ChunkSnapshot>) -> WorldSnapshot {
-        WorldSnapshot {
-            header: SnapshotHeader {
-                version: CURRENT_SNAPSHOT_VERSION,
-                snapshot_id: id,
-                server_tick: id * 100,
-                timestamp: 1_000_000 + id,
-                is_incremental: false,
-                parent_snapshot_id: None,
-            },
-            modified_chunks: chunks,
-            entities: vec![make_entity(1), make_entity(2)],
-            world_time: 42.0,
-        }
-    }
-
-    #[test]
-    fn test_snapshot_contains_all_modified_chunks() {
-        let chunks: Vec<ChunkSnapshot> = (0..10)
-            .map(|i| make_chunk_snapshot(0, i, 0, 0, &[i as u8; 64]))
-            .collect();
-        let snapshot = make_full_snapshot(1, chunks.clone());
-
-        assert_eq!(snapshot.modified_chunks.len(), 10);
-        for (i, cs) in snapshot.modified_chunks.iter().enumerate() {
-            assert_eq!(cs.chunk_id, make_chunk_id(0, i as i32, 0, 0));
-            assert_eq!(cs.voxel_data, vec![i as u8; 64]);
-        }
-    }
-
-    #[test]
-    fn test_incremental_snapshot_is_smaller_than_full() {
-        // Full snapshot with 100 chunks.
-        let full_chunks: Vec<ChunkSnapshot> = (0..100)
-            .map(|i| make_chunk_snapshot(0, i, 0, 0, &[i as u8; 256]))
-            .collect();
-        let full = make_full_snapshot(1, full_chunks);
-        let full_bytes = postcard::to_allocvec(&full).unwrap();
-
-        // Incremental with only 5 chunks.
-        let inc_chunks: Vec<ChunkSnapshot> = (100..105)
-            .map(|i| make_chunk_snapshot(0, i, 0, 0, &[i as u8; 256]))
-            .collect();
-        let inc = WorldSnapshot {
-            header: SnapshotHeader {
-                version: CURRENT_SNAPSHOT_VERSION,
-                snapshot_id: 2,
-                server_tick: 200,
-                timestamp: 1_000_002,
-                is_incremental: true,
-                parent_snapshot_id: Some(1),
-            },
-            modified_chunks: inc_chunks,
-            entities: vec![make_entity(1), make_entity(2)],
-            world_time: 43.0,
-        };
-        let inc_bytes = postcard::to_allocvec(&inc).unwrap();
-
-        assert_eq!(inc.modified_chunks.len(), 5);
-        assert!(
-            inc_bytes.len() < full_bytes.len(),
-            "incremental {} should be smaller than full {}",
-            inc_bytes.len(),
-            full_bytes.len()
-        );
-    }
-
-    #[test]
-    fn test_snapshot_loads_correctly() {
-        let dir = std::env::temp_dir().join("nebula_snap_test_load");
-        let _ = std::fs::remove_dir_all(&dir);
-
-        let config = SnapshotConfig {
-            snapshot_dir: dir.clone(),
-            ..Default::default()
-        };
-
-        let chunks = vec![
-            make_chunk_snapshot(0, 1, 2, 3, &[0xAB; 128]),
-            make_chunk_snapshot(1, 4, 5, 6, &[0xCD; 128]),
-        ];
-        let original = make_full_snapshot(1, chunks);
-
-        let path = write_snapshot(&original, &config).unwrap();
-        let loaded = load_snapshot(&path).unwrap();
-
-        assert_eq!(loaded.header.snapshot_id, original.header.snapshot_id);
-        assert_eq!(loaded.modified_chunks.len(), 2);
-        assert_eq!(loaded.modified_chunks[0].voxel_data, vec![0xAB; 128]);
-        assert_eq!(loaded.modified_chunks[1].voxel_data, vec![0xCD; 128]);
-        assert_eq!(loaded.entities.len(), 2);
-        assert!((loaded.world_time - 42.0).abs() < f64::EPSILON);
-
-        let _ = std::fs::remove_dir_all(&dir);
-    }
-
-    #[test]
-    fn test_version_mismatch_handled() {
-        let header = SnapshotHeader {
-            version: CURRENT_SNAPSHOT_VERSION + 1,
-            snapshot_id: 1,
-            server_tick: 100,
-            timestamp: 1_000_000,
-            is_incremental: false,
-            parent_snapshot_id: None,
-        };
-        let result = check_version(&header);
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(
-            matches!(err, SnapshotError::VersionTooNew { found, max_supported }
-                if found == CURRENT_SNAPSHOT_VERSION + 1
-                && max_supported == CURRENT_SNAPSHOT_VERSION
-            ),
-            "expected VersionTooNew, got: {err:?}"
-        );
-    }
-
-    #[test]
-    fn test_snapshot_interval_is_configurable() {
-        let config = SnapshotConfig {
-            interval: Duration::from_millis(1000),
-            ..Default::default()
-        };
-        let timer = SnapshotTimer {
-            last_snapshot: Instant::now(),
-            config,
-        };
-
-        // Just created — should not trigger yet.
-        assert!(!timer.should_snapshot());
-
-        // Simulate elapsed time by creating a timer with a past instant.
-        let timer_old = SnapshotTimer {
-            last_snapshot: Instant::now() - Duration::from_millis(1100),
-            config: SnapshotConfig {
-                interval: Duration::from_millis(1000),
-                ..Default::default()
-            },
-        };
-        assert!(timer_old.should_snapshot());
-    }
-}
+#[path = "snapshot_tests.rs"]
+mod tests;
@@ -0,0 +1,500 @@
+//! Incremental world snapshots: deltas of dirty chunks and entities that are
+//! replayed onto a full snapshot on load.
+//!
+//! A chain is one full [`WorldSnapshot`] followed by [`SnapshotDelta`]s, each
+//! naming the snapshot it extends in its header. [`write_incremental`] reads
+//! only the chunks and entities flagged in a [`DirtyChunkTracker`], so the
+//! file size scales with the amount of change rather than the world size.
+//! [`load_snapshot_chain`] validates the chain's order before applying it.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::chunk_streaming::ChunkId;
+use crate::network_id::NetworkId;
+use crate::snapshot::{
+    CURRENT_SNAPSHOT_VERSION, ChunkSnapshot, DirtyChunkTracker, EntitySnapshot, SnapshotConfig,
+    SnapshotError, SnapshotHeader, WorldSnapshot, check_version, load_snapshot,
+};
+
+// ---------------------------------------------------------------------------
+// Source
+// ---------------------------------------------------------------------------
+
+/// Read access to the live world state, used to capture deltas without
+/// building a full [`WorldSnapshot`].
+pub trait SnapshotSource {
+    /// Current server tick.
+    fn server_tick(&self) -> u64;
+    /// Current in-game world time.
+    fn world_time(&self) -> f64;
+    /// Snapshot of a chunk, or `None` if it no longer exists.
+    fn chunk_snapshot(&self, chunk_id: &ChunkId) -> Option<ChunkSnapshot>;
+    /// Snapshot of an entity, or `None` if it was despawned.
+    fn entity_snapshot(&self, network_id: NetworkId) -> Option<EntitySnapshot>;
+}
+
+// ---------------------------------------------------------------------------
+// SnapshotDelta
+// ---------------------------------------------------------------------------
+
+/// Changes to the world between two snapshots.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SnapshotDelta {
+    /// Metadata; `is_incremental` is set and `parent_snapshot_id` names the
+    /// snapshot this delta extends.
+    pub header: SnapshotHeader,
+    /// Chunks modified since the parent, with their new contents.
+    pub changed_chunks: Vec<ChunkSnapshot>,
+    /// Chunks that existed in the parent and have since been removed.
+    pub removed_chunks: Vec<ChunkId>,
+    /// Entities changed or spawned since the parent.
+    pub changed_entities: Vec<EntitySnapshot>,
+    /// Entities despawned since the parent.
+    pub removed_entities: Vec<NetworkId>,
+    /// In-game world time.
+    pub world_time: f64,
+}
+
+impl SnapshotDelta {
+    /// Captures everything flagged in `dirty` from `source` as a delta on
+    /// top of `base`.
+    pub fn capture(
+        base: &SnapshotHeader,
+        dirty: &DirtyChunkTracker,
+        source: &impl SnapshotSource,
+    ) -> Self {
+        let mut changed_chunks = Vec::new();
+        let mut removed_chunks = Vec::new();
+        for id in dirty.chunks() {
+            match source.chunk_snapshot(id) {
+                Some(chunk) => changed_chunks.push(chunk),
+                None => removed_chunks.push(*id),
+            }
+        }
+        let mut changed_entities = Vec::new();
+        let mut removed_entities = Vec::new();
+        for &id in dirty.entities() {
+            match source.entity_snapshot(id) {
+                Some(entity) => changed_entities.push(entity),
+                None => removed_entities.push(id),
+            }
+        }
+
+        Self {
+            header: SnapshotHeader {
+                version: CURRENT_SNAPSHOT_VERSION,
+                snapshot_id: base.snapshot_id + 1,
+                server_tick: source.server_tick(),
+                timestamp: unix_millis(),
+                is_incremental: true,
+                parent_snapshot_id: Some(base.snapshot_id),
+            },
+            changed_chunks,
+            removed_chunks,
+            changed_entities,
+            removed_entities,
+            world_time: source.world_time(),
+        }
+    }
+
+    /// Applies this delta to `snapshot`, which must be the state it extends.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SnapshotError::BrokenChain`] if the delta's parent is not
+    /// `snapshot`, or [`SnapshotError::TickRegression`] if the delta is
+    /// older than it.
+    pub fn apply_to(&self, snapshot: &mut WorldSnapshot) -> Result<(), SnapshotError> {
+        check_version(&self.header)?;
+        if self.header.parent_snapshot_id != Some(snapshot.header.snapshot_id) {
+            return Err(SnapshotError::BrokenChain {
+                delta: self.header.snapshot_id,
+                expected: snapshot.header.snapshot_id,
+                found: self.header.parent_snapshot_id,
+            });
+        }
+        if self.header.server_tick < snapshot.header.server_tick {
+            return Err(SnapshotError::TickRegression {
+                base_tick: snapshot.header.server_tick,
+                delta_tick: self.header.server_tick,
+            });
+        }
+
+        let mut chunks: HashMap<ChunkId, usize> = snapshot
+            .modified_chunks
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.chunk_id, i))
+            .collect();
+        for chunk in &self.changed_chunks {
+            match chunks.get(&chunk.chunk_id) {
+                Some(&i) => snapshot.modified_chunks[i] = chunk.clone(),
+                None => {
+                    chunks.insert(chunk.chunk_id, snapshot.modified_chunks.len());
+                    snapshot.modified_chunks.push(chunk.clone());
+                }
+            }
+        }
+        snapshot
+            .modified_chunks
+            .retain(|c| !self.removed_chunks.contains(&c.chunk_id));
+
+        let mut entities: HashMap<NetworkId, usize> = snapshot
+            .entities
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (e.network_id, i))
+            .collect();
+        for entity in &self.changed_entities {
+            match entities.get(&entity.network_id) {
+                Some(&i) => snapshot.entities[i] = entity.clone(),
+                None => {
+                    entities.insert(entity.network_id, snapshot.entities.len());
+                    snapshot.entities.push(entity.clone());
+                }
+            }
+        }
+        snapshot
+            .entities
+            .retain(|e| !self.removed_entities.contains(&e.network_id));
+
+        snapshot.world_time = self.world_time;
+        snapshot.header = SnapshotHeader {
+            is_incremental: false,
+            parent_snapshot_id: None,
+            ..self.header.clone()
+        };
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Write / Load
+// ---------------------------------------------------------------------------
+
+/// Captures a delta on top of `base` from the chunks and entities flagged in
+/// `dirty` and writes it to disk. Returns the path written.
+///
+/// The tracker is left untouched; clear it once the write succeeded.
+///
+/// # Errors
+///
+/// Returns [`SnapshotError`] on I/O or serialization failure.
+pub fn write_incremental(
+    base: &SnapshotHeader,
+    dirty: &DirtyChunkTracker,
+    source: &impl SnapshotSource,
+    config: &SnapshotConfig,
+) -> Result<PathBuf, SnapshotError> {
+    write_delta(&SnapshotDelta::capture(base, dirty, source), config)
+}
+
+/// Writes an already captured delta to disk. Returns the path written.
+///
+/// # Errors
+///
+/// Returns [`SnapshotError`] on I/O or serialization failure.
+pub fn write_delta(
+    delta: &SnapshotDelta,
+    config: &SnapshotConfig,
+) -> Result<PathBuf, SnapshotError> {
+    let bytes =
+        postcard::to_allocvec(delta).map_err(|e| SnapshotError::Serialization(e.to_string()))?;
+    let compressed = lz4_flex::compress_prepend_size(&bytes);
+
+    let filename = format!("snapshot_{:06}_delta.nbsnap", delta.header.snapshot_id);
+    let path = config.snapshot_dir.join(&filename);
+
+    std::fs::create_dir_all(&config.snapshot_dir)?;
+    std::fs::write(&path, &compressed)?;
+    Ok(path)
+}
+
+/// Loads a delta from a file on disk.
+///
+/// # Errors
+///
+/// Returns [`SnapshotError`] on I/O, decompression, deserialization, or
+/// version mismatch.
+pub fn load_delta(path: &Path) -> Result<SnapshotDelta, SnapshotError> {
+    let compressed = std::fs::read(path)?;
+    let bytes = lz4_flex::decompress_size_prepended(&compressed)
+        .map_err(|e| SnapshotError::Serialization(e.to_string()))?;
+    let delta: SnapshotDelta =
+        postcard::from_bytes(&bytes).map_err(|e| SnapshotError::Serialization(e.to_string()))?;
+    check_version(&delta.header)?;
+    Ok(delta)
+}
+
+/// Loads the full snapshot at `base` and replays `deltas` onto it in order.
+///
+/// # Errors
+///
+/// Returns [`SnapshotError::NotFullSnapshot`] if `base` is incremental, and
+/// [`SnapshotError::BrokenChain`] or [`SnapshotError::TickRegression`] if a
+/// delta is out of order, plus any error from loading the files.
+pub fn load_snapshot_chain(
+    base: &Path,
+    deltas: &[impl AsRef<Path>],
+) -> Result<WorldSnapshot, SnapshotError> {
+    let mut snapshot = load_snapshot(base)?;
+    if snapshot.header.is_incremental {
+        return Err(SnapshotError::NotFullSnapshot(snapshot.header.snapshot_id));
+    }
+    for path in deltas {
+        load_delta(path.as_ref())?.apply_to(&mut snapshot)?;
+    }
+    Ok(snapshot)
+}
+
+/// Wall-clock Unix milliseconds, or 0 if the clock is before the epoch.
+fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::write_snapshot;
+
+    const CHUNK_BYTES: usize = 2_048;
+
+    /// In-memory world used as a [`SnapshotSource`].
+    struct TestWorld {
+        tick: u64,
+        chunks: HashMap<ChunkId, Vec<u8>>,
+        entities: HashMap<NetworkId, Vec<u8>>,
+    }
+
+    impl SnapshotSource for TestWorld {
+        fn server_tick(&self) -> u64 {
+            self.tick
+        }
+
+        fn world_time(&self) -> f64 {
+            self.tick as f64 / 60.0
+        }
+
+        fn chunk_snapshot(&self, chunk_id: &ChunkId) -> Option<ChunkSnapshot> {
+            self.chunks.get(chunk_id).map(|data| ChunkSnapshot {
+                chunk_id: *chunk_id,
+                voxel_data: data.clone(),
+            })
+        }
+
+        fn entity_snapshot(&self, network_id: NetworkId) -> Option<EntitySnapshot> {
+            self.entities.get(&network_id).map(|pos| EntitySnapshot {
+                network_id,
+                components: vec![("Position".to_string(), pos.clone())],
+            })
+        }
+    }
+
+    impl TestWorld {
+        fn new(chunk_count: i32) -> Self {
+            let chunks = (0..chunk_count)
+                .map(|i| (chunk_id(i), noise_bytes(i as u64)))
+                .collect();
+            let entities = (0..10)
+                .map(|i| (NetworkId::new(i), vec![i as u8; 12]))
+                .collect();
+            Self {
+                tick: 100,
+                chunks,
+                entities,
+            }
+        }
+
+        fn full_snapshot(&self, snapshot_id: u64) -> WorldSnapshot {
+            let mut chunk_ids: Vec<_> = self.chunks.keys().copied().collect();
+            chunk_ids.sort_by_key(|c| c.x);
+            let mut entity_ids: Vec<_> = self.entities.keys().copied().collect();
+            entity_ids.sort_by_key(|e| e.id);
+            WorldSnapshot {
+                header: SnapshotHeader {
+                    version: CURRENT_SNAPSHOT_VERSION,
+                    snapshot_id,
+                    server_tick: self.tick,
+                    timestamp: 1_000_000,
+                    is_incremental: false,
+                    parent_snapshot_id: None,
+                },
+                modified_chunks: chunk_ids
+                    .iter()
+                    .filter_map(|id| self.chunk_snapshot(id))
+                    .collect(),
+                entities: entity_ids
+                    .into_iter()
+                    .filter_map(|id| self.entity_snapshot(id))
+                    .collect(),
+                world_time: self.world_time(),
+            }
+        }
+    }
+
+    fn chunk_id(x: i32) -> ChunkId {
+        ChunkId {
+            face: 0,
+            lod: 0,
+            x,
+            y: 0,
+            z: 0,
+        }
+    }
+
+    /// Incompressible bytes, so file sizes reflect the chunk count.
+    fn noise_bytes(seed: u64) -> Vec<u8> {
+        let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+        (0..CHUNK_BYTES)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    }
+
+    fn temp_config(name: &str) -> SnapshotConfig {
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        SnapshotConfig {
+            snapshot_dir: dir,
+            ..Default::default()
+        }
+    }
+
+    /// Contents of a snapshot in a canonical order for comparison.
+    type Canonical = (u64, Vec<(i32, Vec<u8>)>, Vec<(u32, Vec<u8>)>);
+
+    fn canonical(snapshot: &WorldSnapshot) -> Canonical {
+        let mut chunks: Vec<_> = snapshot
+            .modified_chunks
+            .iter()
+            .map(|c| (c.chunk_id.x, c.voxel_data.clone()))
+            .collect();
+        chunks.sort();
+        let mut entities: Vec<_> = snapshot
+            .entities
+            .iter()
+            .map(|e| (e.network_id.id, e.components[0].1.clone()))
+            .collect();
+        entities.sort();
+        (snapshot.header.server_tick, chunks, entities)
+    }
+
+    /// Advances `world` by one tick, rewriting `changed` chunks and entity 1.
+    fn mutate(world: &mut TestWorld, dirty: &mut DirtyChunkTracker, changed: &[i32]) {
+        world.tick += 60;
+        for &x in changed {
+            world
+                .chunks
+                .insert(chunk_id(x), noise_bytes(world.tick + x as u64));
+            dirty.mark_dirty(chunk_id(x));
+        }
+        world
+            .entities
+            .insert(NetworkId::new(1), vec![world.tick as u8; 12]);
+        dirty.mark_entity_dirty(NetworkId::new(1));
+    }
+
+    #[test]
+    fn test_delta_of_three_chunks_is_tiny_next_to_full() {
+        let config = temp_config("nebula_snap_delta_size");
+        let mut world = TestWorld::new(1_000);
+        let base = world.full_snapshot(1);
+        let full_path = write_snapshot(&base, &config).unwrap();
+
+        let mut dirty = DirtyChunkTracker::new();
+        mutate(&mut world, &mut dirty, &[10, 500, 999]);
+        let delta_path = write_incremental(&base.header, &dirty, &world, &config).unwrap();
+
+        let full_len = std::fs::metadata(&full_path).unwrap().len();
+        let delta_len = std::fs::metadata(&delta_path).unwrap().len();
+        // 3 of 1000 chunks: the delta is bounded by ~1/333 of the full size.
+        assert!(
+            delta_len * 300 < full_len,
+            "delta {delta_len} B vs full {full_len} B"
+        );
+        let _ = std::fs::remove_dir_all(&config.snapshot_dir);
+    }
+
+    #[test]
+    fn test_base_plus_deltas_equals_full_snapshot_at_same_tick() {
+        let config = temp_config("nebula_snap_delta_chain");
+        let mut world = TestWorld::new(50);
+        let base = world.full_snapshot(1);
+        let base_path = write_snapshot(&base, &config).unwrap();
+
+        let mut dirty = DirtyChunkTracker::new();
+        mutate(&mut world, &mut dirty, &[3, 7]);
+        world.chunks.remove(&chunk_id(20));
+        dirty.mark_dirty(chunk_id(20));
+        let first = SnapshotDelta::capture(&base.header, &dirty, &world);
+        let first_path = write_delta(&first, &config).unwrap();
+        dirty.clear();
+
+        mutate(&mut world, &mut dirty, &[7, 60]);
+        world.entities.remove(&NetworkId::new(4));
+        dirty.mark_entity_dirty(NetworkId::new(4));
+        let second_path = write_incremental(&first.header, &dirty, &world, &config).unwrap();
+
+        let chained = load_snapshot_chain(&base_path, &[first_path, second_path]).unwrap();
+        let expected = world.full_snapshot(99);
+        assert_eq!(canonical(&chained), canonical(&expected));
+        assert!(!chained.header.is_incremental);
+        assert!((chained.world_time - expected.world_time).abs() < f64::EPSILON);
+        let _ = std::fs::remove_dir_all(&config.snapshot_dir);
+    }
+
+    #[test]
+    fn test_out_of_order_deltas_are_rejected() {
+        let config = temp_config("nebula_snap_delta_order");
+        let mut world = TestWorld::new(10);
+        let base = world.full_snapshot(1);
+        let base_path = write_snapshot(&base, &config).unwrap();
+
+        let mut dirty = DirtyChunkTracker::new();
+        mutate(&mut world, &mut dirty, &[1]);
+        let first = SnapshotDelta::capture(&base.header, &dirty, &world);
+        let first_path = write_delta(&first, &config).unwrap();
+        mutate(&mut world, &mut dirty, &[2]);
+        let second_path = write_incremental(&first.header, &dirty, &world, &config).unwrap();
+
+        let err = load_snapshot_chain(&base_path, &[&second_path, &first_path]).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                SnapshotError::BrokenChain {
+                    delta: 3,
+                    expected: 1,
+                    found: Some(2)
+                }
+            ),
+            "got {err:?}"
+        );
+
+        // A delta cannot start a chain either.
+        let err = load_snapshot_chain(&first_path, &[&second_path]);
+        assert!(err.is_err());
+
+        // A delta claiming an earlier tick than its parent is rejected.
+        let mut stale = SnapshotDelta::capture(&base.header, &dirty, &world);
+        stale.header.server_tick = base.header.server_tick - 1;
+        let mut state = base.clone();
+        assert!(matches!(
+            stale.apply_to(&mut state),
+            Err(SnapshotError::TickRegression { .. })
+        ));
+        let _ = std::fs::remove_dir_all(&config.snapshot_dir);
+    }
+}
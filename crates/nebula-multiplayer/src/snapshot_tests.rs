@@ -0,0 +1,203 @@
+//! Unit tests for world snapshots, the snapshot timer and dirty tracking.
+
+use super::*;
+
+fn make_chunk_id(face: u8, x: i32, y: i32, z: i32) -> ChunkId {
+    ChunkId {
+        face,
+        lod: 0,
+        x,
+        y,
+        z,
+    }
+}
+
+fn make_chunk_snapshot(face: u8, x: i32, y: i32, z: i32, data: &[u8]) -> ChunkSnapshot {
+    ChunkSnapshot {
+        chunk_id: make_chunk_id(face, x, y, z),
+        voxel_data: data.to_vec(),
+    }
+}
+
+fn make_entity(id: u32) -> EntitySnapshot {
+    EntitySnapshot {
+        network_id: NetworkId::new(id),
+        components: vec![("Position".to_string(), vec![1, 2, 3])],
+    }
+}
+
+fn make_full_snapshot(id: u64, chunks: Vec<ChunkSnapshot>) -> WorldSnapshot {
+    WorldSnapshot {
+        header: SnapshotHeader {
+            version: CURRENT_SNAPSHOT_VERSION,
+            snapshot_id: id,
+            server_tick: id * 100,
+            timestamp: 1_000_000 + id,
+            is_incremental: false,
+            parent_snapshot_id: None,
+        },
+        modified_chunks: chunks,
+        entities: vec![make_entity(1), make_entity(2)],
+        world_time: 42.0,
+    }
+}
+
+#[test]
+fn test_snapshot_contains_all_modified_chunks() {
+    let chunks: Vec<ChunkSnapshot> = (0..10)
+        .map(|i| make_chunk_snapshot(0, i, 0, 0, &[i as u8; 64]))
+        .collect();
+    let snapshot = make_full_snapshot(1, chunks.clone());
+
+    assert_eq!(snapshot.modified_chunks.len(), 10);
+    for (i, cs) in snapshot.modified_chunks.iter().enumerate() {
+        assert_eq!(cs.chunk_id, make_chunk_id(0, i as i32, 0, 0));
+        assert_eq!(cs.voxel_data, vec![i as u8; 64]);
+    }
+}
+
+#[test]
+fn test_incremental_snapshot_is_smaller_than_full() {
+    // Full snapshot with 100 chunks.
+    let full_chunks: Vec<ChunkSnapshot> = (0..100)
+        .map(|i| make_chunk_snapshot(0, i, 0, 0, &[i as u8; 256]))
+        .collect();
+    let full = make_full_snapshot(1, full_chunks);
+    let full_bytes = postcard::to_allocvec(&full).unwrap();
+
+    // Incremental with only 5 chunks.
+    let inc_chunks: Vec<ChunkSnapshot> = (100..105)
+        .map(|i| make_chunk_snapshot(0, i, 0, 0, &[i as u8; 256]))
+        .collect();
+    let inc = WorldSnapshot {
+        header: SnapshotHeader {
+            version: CURRENT_SNAPSHOT_VERSION,
+            snapshot_id: 2,
+            server_tick: 200,
+            timestamp: 1_000_002,
+            is_incremental: true,
+            parent_snapshot_id: Some(1),
+        },
+        modified_chunks: inc_chunks,
+        entities: vec![make_entity(1), make_entity(2)],
+        world_time: 43.0,
+    };
+    let inc_bytes = postcard::to_allocvec(&inc).unwrap();
+
+    assert_eq!(inc.modified_chunks.len(), 5);
+    assert!(
+        inc_bytes.len() < full_bytes.len(),
+        "incremental {} should be smaller than full {}",
+        inc_bytes.len(),
+        full_bytes.len()
+    );
+}
+
+#[test]
+fn test_snapshot_loads_correctly() {
+    let dir = std::env::temp_dir().join("nebula_snap_test_load");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let config = SnapshotConfig {
+        snapshot_dir: dir.clone(),
+        ..Default::default()
+    };
+
+    let chunks = vec![
+        make_chunk_snapshot(0, 1, 2, 3, &[0xAB; 128]),
+        make_chunk_snapshot(1, 4, 5, 6, &[0xCD; 128]),
+    ];
+    let original = make_full_snapshot(1, chunks);
+
+    let path = write_snapshot(&original, &config).unwrap();
+    let loaded = load_snapshot(&path).unwrap();
+
+    assert_eq!(loaded.header.snapshot_id, original.header.snapshot_id);
+    assert_eq!(loaded.modified_chunks.len(), 2);
+    assert_eq!(loaded.modified_chunks[0].voxel_data, vec![0xAB; 128]);
+    assert_eq!(loaded.modified_chunks[1].voxel_data, vec![0xCD; 128]);
+    assert_eq!(loaded.entities.len(), 2);
+    assert!((loaded.world_time - 42.0).abs() < f64::EPSILON);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_version_mismatch_handled() {
+    let header = SnapshotHeader {
+        version: CURRENT_SNAPSHOT_VERSION + 1,
+        snapshot_id: 1,
+        server_tick: 100,
+        timestamp: 1_000_000,
+        is_incremental: false,
+        parent_snapshot_id: None,
+    };
+    let result = check_version(&header);
+    assert!(result.is_err());
+    let err = result.unwrap_err();
+    assert!(
+        matches!(err, SnapshotError::VersionTooNew { found, max_supported }
+            if found == CURRENT_SNAPSHOT_VERSION + 1
+            && max_supported == CURRENT_SNAPSHOT_VERSION
+        ),
+        "expected VersionTooNew, got: {err:?}"
+    );
+}
+
+#[test]
+fn test_snapshot_interval_is_configurable() {
+    let config = SnapshotConfig {
+        interval: Duration::from_millis(1000),
+        ..Default::default()
+    };
+    let timer = SnapshotTimer {
+        last_snapshot: Instant::now(),
+        config,
+        incrementals_since_full: None,
+    };
+
+    // Just created — should not trigger yet.
+    assert!(!timer.should_snapshot());
+
+    // Simulate elapsed time by creating a timer with a past instant.
+    let timer_old = SnapshotTimer {
+        last_snapshot: Instant::now() - Duration::from_millis(1100),
+        config: SnapshotConfig {
+            interval: Duration::from_millis(1000),
+            ..Default::default()
+        },
+        incrementals_since_full: None,
+    };
+    assert!(timer_old.should_snapshot());
+}
+
+#[test]
+fn test_timer_alternates_full_and_incremental() {
+    let mut timer = SnapshotTimer::new(SnapshotConfig {
+        full_every: 3,
+        ..Default::default()
+    });
+    let kinds: Vec<SnapshotKind> = (0..7)
+        .map(|_| {
+            let kind = timer.next_kind();
+            timer.record(kind);
+            kind
+        })
+        .collect();
+    use SnapshotKind::{Full, Incremental};
+    assert_eq!(
+        kinds,
+        vec![
+            Full,
+            Incremental,
+            Incremental,
+            Full,
+            Incremental,
+            Incremental,
+            Full
+        ]
+    );
+
+    timer.config.incremental = false;
+    assert_eq!(timer.next_kind(), Full);
+}
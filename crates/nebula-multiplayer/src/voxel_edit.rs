@@ -2,12 +2,17 @@
 //!
 //! Clients submit [`VoxelEditIntent`] messages to the server, which validates
 //! them against the [`AuthoritativeWorld`] state, applies valid edits, and
-//! broadcasts [`VoxelEditEvent`] messages to interested clients.
+//! broadcasts [`VoxelEditEvent`] messages to interested clients. A
+//! [`VoxelEditRateLimiter`] caps how many edits each client may submit per
+//! tick, so a malicious client cannot flood the server with block changes.
+
+use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::budget::ClientId;
 use crate::chunk_streaming::ChunkId;
-use crate::replication::NetworkId;
+use crate::network_id::NetworkId;
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -17,7 +22,13 @@ use crate::replication::NetworkId;
 const INTERACTION_RADIUS_MM: i64 = 6_000;
 
 /// Chunk size in voxels per axis.
-const CHUNK_SIZE: u32 = 32;
+pub(crate) const CHUNK_SIZE: u32 = 32;
+
+/// Default number of voxel edits a client may submit per tick.
+pub const DEFAULT_MAX_EDITS_PER_TICK: u32 = 2;
+
+/// Default number of burst tokens a client can bank while idle.
+pub const DEFAULT_MAX_EDIT_BURST: u32 = 16;
 
 // ---------------------------------------------------------------------------
 // VoxelMaterial
@@ -115,7 +126,7 @@ pub struct VoxelEditEvent {
 // ---------------------------------------------------------------------------
 
 /// Reason a voxel edit intent was rejected by the server.
-#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
 pub enum EditRejection {
     /// Target voxel is outside the player's interaction radius.
     #[error("target out of range")]
@@ -142,6 +153,96 @@ pub enum EditRejection {
     /// Player entity not found.
     #[error("unknown player")]
     UnknownPlayer,
+    /// The client has used up its edits for this tick and its burst tokens.
+    #[error("too many voxel edits this tick")]
+    RateLimited,
+}
+
+// ---------------------------------------------------------------------------
+// VoxelEditRateLimiter
+// ---------------------------------------------------------------------------
+
+/// Per-client limit on voxel edits per server tick.
+///
+/// Each client may make [`max_edits_per_tick`](Self::max_edits_per_tick)
+/// edits every tick. Allowance a client leaves unused is banked as burst
+/// tokens, up to [`max_burst`](Self::max_burst), which are spent on edits
+/// past the per-tick limit — so a player who pauses can briefly build
+/// faster, but sustained throughput never exceeds the per-tick rate.
+/// Clients start with an empty bank from their first edit on, so
+/// reconnecting cannot refill it.
+#[derive(Debug, Clone)]
+pub struct VoxelEditRateLimiter {
+    /// Edits each client may make per tick before spending burst tokens.
+    pub max_edits_per_tick: u32,
+    /// Maximum burst tokens a client can bank.
+    pub max_burst: u32,
+    /// Edits made by each client during the current tick.
+    per_client: HashMap<ClientId, u32>,
+    /// Banked burst tokens of every client that has edited.
+    burst: HashMap<ClientId, u32>,
+}
+
+impl VoxelEditRateLimiter {
+    /// Creates a limiter with the given per-tick allowance and burst bank.
+    pub fn new(max_edits_per_tick: u32, max_burst: u32) -> Self {
+        Self {
+            max_edits_per_tick,
+            max_burst,
+            per_client: HashMap::new(),
+            burst: HashMap::new(),
+        }
+    }
+
+    /// Counts one edit by `client_id` against its allowance.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EditRejection::RateLimited`] if the client has used its
+    /// per-tick allowance and has no burst tokens left.
+    pub fn check_and_consume(&mut self, client_id: ClientId) -> Result<(), EditRejection> {
+        let used = self.per_client.entry(client_id).or_insert(0);
+        let tokens = self.burst.entry(client_id).or_insert(0);
+        if *used >= self.max_edits_per_tick {
+            if *tokens == 0 {
+                return Err(EditRejection::RateLimited);
+            }
+            *tokens -= 1;
+        }
+        *used += 1;
+        Ok(())
+    }
+
+    /// Burst tokens `client_id` currently has banked.
+    pub fn burst_tokens(&self, client_id: ClientId) -> u32 {
+        self.burst.get(&client_id).copied().unwrap_or(0)
+    }
+
+    /// Ends the tick: banks each client's unused allowance as burst tokens
+    /// and resets all per-tick counters.
+    pub fn end_tick(&mut self) {
+        let (max_edits, max_burst) = (self.max_edits_per_tick, self.max_burst);
+        for (client_id, tokens) in &mut self.burst {
+            let used = self.per_client.get(client_id).copied().unwrap_or(0);
+            *tokens = tokens
+                .saturating_add(max_edits.saturating_sub(used))
+                .min(max_burst);
+        }
+        self.per_client.clear();
+    }
+
+    /// Forgets `client_id`'s burst bank, e.g. when it disconnects. Its edits
+    /// this tick still count until [`end_tick`](Self::end_tick), so
+    /// rejoining within the tick does not restore the allowance either.
+    pub fn remove_client(&mut self, client_id: ClientId) {
+        self.burst.remove(&client_id);
+    }
+}
+
+impl Default for VoxelEditRateLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_EDITS_PER_TICK, DEFAULT_MAX_EDIT_BURST)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -157,11 +258,35 @@ pub struct ServerChunkStore {
 }
 
 impl ServerChunkStore {
+    /// Edge length of a chunk in voxels.
+    pub const CHUNK_SIZE: u32 = CHUNK_SIZE;
+
     /// Create an empty chunk store.
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Load (or replace) a chunk with the given voxels, laid out as for
+    /// [`Self::get_voxel`]. Returns `false` and leaves the store unchanged
+    /// unless `voxels` holds exactly `CHUNK_SIZE³` entries.
+    pub fn insert_chunk(&mut self, id: ChunkId, voxels: Vec<VoxelMaterial>) -> bool {
+        if voxels.len() != (CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE) as usize {
+            return false;
+        }
+        self.chunks.insert(id, voxels);
+        true
+    }
+
+    /// Iterates over every loaded chunk and its voxels.
+    pub fn chunks(&self) -> impl Iterator<Item = (&ChunkId, &[VoxelMaterial])> {
+        self.chunks.iter().map(|(id, data)| (id, data.as_slice()))
+    }
+
+    /// Voxels of a loaded chunk.
+    pub fn chunk(&self, id: &ChunkId) -> Option<&[VoxelMaterial]> {
+        self.chunks.get(id).map(Vec::as_slice)
+    }
+
     /// Load (or create) a chunk filled with the given material.
     pub fn load_chunk(&mut self, id: ChunkId, fill: VoxelMaterial) {
         let size = (CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE) as usize;
@@ -210,16 +335,26 @@ pub struct PlayerPosition {
 // Validation
 // ---------------------------------------------------------------------------
 
-/// Validates a [`VoxelEditIntent`] against the server state.
+/// Validates a [`VoxelEditIntent`] from `client_id` against the server
+/// state.
+///
+/// The edit is counted against the client's allowance in `limiter` before
+/// anything else is checked, so rejected edits still cost a flooding client
+/// its budget.
 ///
 /// # Errors
 ///
-/// Returns [`EditRejection`] if the edit is invalid.
+/// Returns [`EditRejection`] if the edit is invalid or the client is rate
+/// limited.
 pub fn validate_voxel_edit(
     player_pos: &PlayerPosition,
     edit: &VoxelEditIntent,
     store: &ServerChunkStore,
+    limiter: &mut VoxelEditRateLimiter,
+    client_id: ClientId,
 ) -> Result<(), EditRejection> {
+    limiter.check_and_consume(client_id)?;
+
     let (chunk_id, lx, ly, lz) = match edit {
         VoxelEditIntent::Place {
             chunk_id,
@@ -337,198 +472,6 @@ pub fn apply_voxel_edit(
     }
 }
 
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
-
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::interest::{InterestPosition, within_interest};
-    use crate::replication::NetworkId;
-
-    fn test_chunk_id() -> ChunkId {
-        ChunkId {
-            face: 0,
-            lod: 0,
-            x: 0,
-            y: 0,
-            z: 0,
-        }
-    }
-
-    fn near_player() -> PlayerPosition {
-        PlayerPosition {
-            x: 500,
-            y: 500,
-            z: 500,
-        }
-    }
-
-    fn setup_store(chunk_id: ChunkId, fill: VoxelMaterial) -> ServerChunkStore {
-        let mut store = ServerChunkStore::new();
-        store.load_chunk(chunk_id, fill);
-        store
-    }
-
-    #[test]
-    fn test_valid_edit_is_applied_and_broadcast() {
-        let cid = test_chunk_id();
-        let mut store = setup_store(cid, VoxelMaterial::AIR);
-        let player = near_player();
-        let net_id = NetworkId(1);
-
-        let intent = VoxelEditIntent::Place {
-            chunk_id: cid,
-            local_x: 0,
-            local_y: 0,
-            local_z: 0,
-            material: VoxelMaterial::STONE,
-            source_inventory_slot: 0,
-        };
-
-        assert!(validate_voxel_edit(&player, &intent, &store).is_ok());
-
-        let event = apply_voxel_edit(&intent, &mut store, net_id, 10);
-        assert_eq!(event.new_material, VoxelMaterial::STONE);
-        assert_eq!(event.tick, 10);
-        assert_eq!(event.editor_network_id, net_id);
-
-        // Voxel is now stone in the store.
-        assert_eq!(store.get_voxel(&cid, 0, 0, 0), Some(VoxelMaterial::STONE));
-    }
-
-    #[test]
-    fn test_invalid_edit_is_rejected() {
-        let cid = ChunkId {
-            face: 0,
-            lod: 0,
-            x: 100,
-            y: 100,
-            z: 100,
-        };
-        let store = setup_store(cid, VoxelMaterial::AIR);
-        // Player at origin — chunk at (100,100,100) is far away.
-        let player = PlayerPosition { x: 0, y: 0, z: 0 };
-
-        let intent = VoxelEditIntent::Place {
-            chunk_id: cid,
-            local_x: 5,
-            local_y: 5,
-            local_z: 5,
-            material: VoxelMaterial::STONE,
-            source_inventory_slot: 0,
-        };
-
-        let err = validate_voxel_edit(&player, &intent, &store).unwrap_err();
-        assert_eq!(err, EditRejection::OutOfRange);
-    }
-
-    #[test]
-    fn test_edit_reaches_all_interested_clients() {
-        let cid = test_chunk_id();
-        let mut store = setup_store(cid, VoxelMaterial::AIR);
-        let player = near_player();
-
-        let intent = VoxelEditIntent::Place {
-            chunk_id: cid,
-            local_x: 1,
-            local_y: 1,
-            local_z: 1,
-            material: VoxelMaterial::DIRT,
-            source_inventory_slot: 0,
-        };
-
-        validate_voxel_edit(&player, &intent, &store).unwrap();
-        let event = apply_voxel_edit(&intent, &mut store, NetworkId(1), 5);
-
-        // Chunk world position at origin.
-        let chunk_pos = InterestPosition::new(0.0, 0.0, 0.0);
-
-        // Two nearby clients, one far away.
-        let client_a = InterestPosition::new(100.0, 100.0, 100.0);
-        let client_b = InterestPosition::new(200.0, 200.0, 200.0);
-        let client_c = InterestPosition::new(999_999.0, 999_999.0, 999_999.0);
-
-        let interest_radius = 5000.0;
-
-        let a_receives = within_interest(&client_a, &chunk_pos, interest_radius);
-        let b_receives = within_interest(&client_b, &chunk_pos, interest_radius);
-        let c_receives = within_interest(&client_c, &chunk_pos, interest_radius);
-
-        assert!(a_receives, "client A should receive the edit");
-        assert!(b_receives, "client B should receive the edit");
-        assert!(!c_receives, "client C should NOT receive the edit");
-
-        // Event data is correct.
-        assert_eq!(event.new_material, VoxelMaterial::DIRT);
-    }
-
-    #[test]
-    fn test_edit_modifies_correct_voxel() {
-        let cid = test_chunk_id();
-        let mut store = setup_store(cid, VoxelMaterial::AIR);
-        // Player close to target voxel world pos (5000, 10000, 3000) mm.
-        let player = PlayerPosition {
-            x: 5000,
-            y: 10000,
-            z: 3000,
-        };
-
-        let intent = VoxelEditIntent::Place {
-            chunk_id: cid,
-            local_x: 5,
-            local_y: 10,
-            local_z: 3,
-            material: VoxelMaterial::STONE,
-            source_inventory_slot: 0,
-        };
-
-        validate_voxel_edit(&player, &intent, &store).unwrap();
-        apply_voxel_edit(&intent, &mut store, NetworkId(1), 1);
-
-        // Target voxel is now stone.
-        assert_eq!(store.get_voxel(&cid, 5, 10, 3), Some(VoxelMaterial::STONE));
-
-        // Adjacent voxels are still air.
-        assert_eq!(store.get_voxel(&cid, 4, 10, 3), Some(VoxelMaterial::AIR));
-        assert_eq!(store.get_voxel(&cid, 5, 11, 3), Some(VoxelMaterial::AIR));
-        assert_eq!(store.get_voxel(&cid, 5, 10, 4), Some(VoxelMaterial::AIR));
-    }
-
-    #[test]
-    fn test_concurrent_edits_dont_conflict() {
-        let cid = test_chunk_id();
-        let mut store = setup_store(cid, VoxelMaterial::AIR);
-        let player = near_player();
-
-        let intent_a = VoxelEditIntent::Place {
-            chunk_id: cid,
-            local_x: 2,
-            local_y: 2,
-            local_z: 2,
-            material: VoxelMaterial::STONE,
-            source_inventory_slot: 0,
-        };
-
-        let intent_b = VoxelEditIntent::Place {
-            chunk_id: cid,
-            local_x: 2,
-            local_y: 2,
-            local_z: 2,
-            material: VoxelMaterial::DIRT,
-            source_inventory_slot: 1,
-        };
-
-        // First edit succeeds.
-        assert!(validate_voxel_edit(&player, &intent_a, &store).is_ok());
-        apply_voxel_edit(&intent_a, &mut store, NetworkId(1), 1);
-
-        // Second edit to same position is rejected (obstructed).
-        let err = validate_voxel_edit(&player, &intent_b, &store).unwrap_err();
-        assert_eq!(err, EditRejection::Obstructed);
-
-        // Final state is from first edit.
-        assert_eq!(store.get_voxel(&cid, 2, 2, 2), Some(VoxelMaterial::STONE));
-    }
-}
+#[path = "voxel_edit_tests.rs"]
+mod tests;
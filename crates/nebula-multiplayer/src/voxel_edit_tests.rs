@@ -0,0 +1,305 @@
+//! Unit tests for voxel edit validation, rate limiting, and application.
+
+use super::*;
+use crate::interest::{InterestPosition, within_interest};
+use crate::network_id::NetworkId;
+
+fn test_chunk_id() -> ChunkId {
+    ChunkId {
+        face: 0,
+        lod: 0,
+        x: 0,
+        y: 0,
+        z: 0,
+    }
+}
+
+fn near_player() -> PlayerPosition {
+    PlayerPosition {
+        x: 500,
+        y: 500,
+        z: 500,
+    }
+}
+
+fn setup_store(chunk_id: ChunkId, fill: VoxelMaterial) -> ServerChunkStore {
+    let mut store = ServerChunkStore::new();
+    store.load_chunk(chunk_id, fill);
+    store
+}
+
+#[test]
+fn test_valid_edit_is_applied_and_broadcast() {
+    let cid = test_chunk_id();
+    let mut store = setup_store(cid, VoxelMaterial::AIR);
+    let player = near_player();
+    let mut limiter = VoxelEditRateLimiter::default();
+    let net_id = NetworkId::new(1);
+
+    let intent = VoxelEditIntent::Place {
+        chunk_id: cid,
+        local_x: 0,
+        local_y: 0,
+        local_z: 0,
+        material: VoxelMaterial::STONE,
+        source_inventory_slot: 0,
+    };
+
+    assert!(validate_voxel_edit(&player, &intent, &store, &mut limiter, 1).is_ok());
+
+    let event = apply_voxel_edit(&intent, &mut store, net_id, 10);
+    assert_eq!(event.new_material, VoxelMaterial::STONE);
+    assert_eq!(event.tick, 10);
+    assert_eq!(event.editor_network_id, net_id);
+
+    // Voxel is now stone in the store.
+    assert_eq!(store.get_voxel(&cid, 0, 0, 0), Some(VoxelMaterial::STONE));
+}
+
+#[test]
+fn test_invalid_edit_is_rejected() {
+    let cid = ChunkId {
+        face: 0,
+        lod: 0,
+        x: 100,
+        y: 100,
+        z: 100,
+    };
+    let store = setup_store(cid, VoxelMaterial::AIR);
+    // Player at origin — chunk at (100,100,100) is far away.
+    let player = PlayerPosition { x: 0, y: 0, z: 0 };
+    let mut limiter = VoxelEditRateLimiter::default();
+
+    let intent = VoxelEditIntent::Place {
+        chunk_id: cid,
+        local_x: 5,
+        local_y: 5,
+        local_z: 5,
+        material: VoxelMaterial::STONE,
+        source_inventory_slot: 0,
+    };
+
+    let err = validate_voxel_edit(&player, &intent, &store, &mut limiter, 1).unwrap_err();
+    assert_eq!(err, EditRejection::OutOfRange);
+}
+
+#[test]
+fn test_edit_reaches_all_interested_clients() {
+    let cid = test_chunk_id();
+    let mut store = setup_store(cid, VoxelMaterial::AIR);
+    let player = near_player();
+    let mut limiter = VoxelEditRateLimiter::default();
+
+    let intent = VoxelEditIntent::Place {
+        chunk_id: cid,
+        local_x: 1,
+        local_y: 1,
+        local_z: 1,
+        material: VoxelMaterial::DIRT,
+        source_inventory_slot: 0,
+    };
+
+    validate_voxel_edit(&player, &intent, &store, &mut limiter, 1).unwrap();
+    let event = apply_voxel_edit(&intent, &mut store, NetworkId::new(1), 5);
+
+    // Chunk world position at origin.
+    let chunk_pos = InterestPosition::new(0.0, 0.0, 0.0);
+
+    // Two nearby clients, one far away.
+    let client_a = InterestPosition::new(100.0, 100.0, 100.0);
+    let client_b = InterestPosition::new(200.0, 200.0, 200.0);
+    let client_c = InterestPosition::new(999_999.0, 999_999.0, 999_999.0);
+
+    let interest_radius = 5000.0;
+
+    let a_receives = within_interest(&client_a, &chunk_pos, interest_radius);
+    let b_receives = within_interest(&client_b, &chunk_pos, interest_radius);
+    let c_receives = within_interest(&client_c, &chunk_pos, interest_radius);
+
+    assert!(a_receives, "client A should receive the edit");
+    assert!(b_receives, "client B should receive the edit");
+    assert!(!c_receives, "client C should NOT receive the edit");
+
+    // Event data is correct.
+    assert_eq!(event.new_material, VoxelMaterial::DIRT);
+}
+
+#[test]
+fn test_edit_modifies_correct_voxel() {
+    let cid = test_chunk_id();
+    let mut store = setup_store(cid, VoxelMaterial::AIR);
+    // Player close to target voxel world pos (5000, 10000, 3000) mm.
+    let player = PlayerPosition {
+        x: 5000,
+        y: 10000,
+        z: 3000,
+    };
+    let mut limiter = VoxelEditRateLimiter::default();
+
+    let intent = VoxelEditIntent::Place {
+        chunk_id: cid,
+        local_x: 5,
+        local_y: 10,
+        local_z: 3,
+        material: VoxelMaterial::STONE,
+        source_inventory_slot: 0,
+    };
+
+    validate_voxel_edit(&player, &intent, &store, &mut limiter, 1).unwrap();
+    apply_voxel_edit(&intent, &mut store, NetworkId::new(1), 1);
+
+    // Target voxel is now stone.
+    assert_eq!(store.get_voxel(&cid, 5, 10, 3), Some(VoxelMaterial::STONE));
+
+    // Adjacent voxels are still air.
+    assert_eq!(store.get_voxel(&cid, 4, 10, 3), Some(VoxelMaterial::AIR));
+    assert_eq!(store.get_voxel(&cid, 5, 11, 3), Some(VoxelMaterial::AIR));
+    assert_eq!(store.get_voxel(&cid, 5, 10, 4), Some(VoxelMaterial::AIR));
+}
+
+#[test]
+fn test_concurrent_edits_dont_conflict() {
+    let cid = test_chunk_id();
+    let mut store = setup_store(cid, VoxelMaterial::AIR);
+    let player = near_player();
+    let mut limiter = VoxelEditRateLimiter::default();
+
+    let intent_a = VoxelEditIntent::Place {
+        chunk_id: cid,
+        local_x: 2,
+        local_y: 2,
+        local_z: 2,
+        material: VoxelMaterial::STONE,
+        source_inventory_slot: 0,
+    };
+
+    let intent_b = VoxelEditIntent::Place {
+        chunk_id: cid,
+        local_x: 2,
+        local_y: 2,
+        local_z: 2,
+        material: VoxelMaterial::DIRT,
+        source_inventory_slot: 1,
+    };
+
+    // First edit succeeds.
+    assert!(validate_voxel_edit(&player, &intent_a, &store, &mut limiter, 1).is_ok());
+    apply_voxel_edit(&intent_a, &mut store, NetworkId::new(1), 1);
+
+    // Second edit to same position is rejected (obstructed).
+    let err = validate_voxel_edit(&player, &intent_b, &store, &mut limiter, 1).unwrap_err();
+    assert_eq!(err, EditRejection::Obstructed);
+
+    // Final state is from first edit.
+    assert_eq!(store.get_voxel(&cid, 2, 2, 2), Some(VoxelMaterial::STONE));
+}
+
+#[test]
+fn test_rate_limit_caps_edits_per_tick() {
+    let mut limiter = VoxelEditRateLimiter::new(4, 0);
+    let accepted = (0..100)
+        .filter(|_| limiter.check_and_consume(7).is_ok())
+        .count();
+    assert_eq!(accepted, 4);
+    assert_eq!(
+        limiter.check_and_consume(7),
+        Err(EditRejection::RateLimited)
+    );
+    // Other clients have their own allowance.
+    assert!(limiter.check_and_consume(8).is_ok());
+
+    limiter.end_tick();
+    assert!(limiter.check_and_consume(7).is_ok());
+}
+
+#[test]
+fn test_rate_limited_edit_is_rejected_by_validation() {
+    let cid = test_chunk_id();
+    let store = setup_store(cid, VoxelMaterial::AIR);
+    let player = near_player();
+    let mut limiter = VoxelEditRateLimiter::new(1, 0);
+    let intent = VoxelEditIntent::Remove {
+        chunk_id: cid,
+        local_x: 0,
+        local_y: 0,
+        local_z: 0,
+    };
+
+    // The first (invalid) edit still uses up the allowance.
+    let err = validate_voxel_edit(&player, &intent, &store, &mut limiter, 1).unwrap_err();
+    assert_eq!(err, EditRejection::AlreadyEmpty);
+    let err = validate_voxel_edit(&player, &intent, &store, &mut limiter, 1).unwrap_err();
+    assert_eq!(err, EditRejection::RateLimited);
+}
+
+#[test]
+fn test_burst_tokens_accumulate_over_idle_ticks() {
+    let mut limiter = VoxelEditRateLimiter::new(2, 5);
+    // A fresh client has no burst bank, only the per-tick allowance.
+    let accepted = (0..100)
+        .filter(|_| limiter.check_and_consume(1).is_ok())
+        .count();
+    assert_eq!(accepted, 2);
+    assert_eq!(limiter.burst_tokens(1), 0);
+
+    // Editing at the full rate banks nothing.
+    limiter.end_tick();
+    limiter.check_and_consume(1).unwrap();
+    limiter.check_and_consume(1).unwrap();
+    limiter.end_tick();
+    assert_eq!(limiter.burst_tokens(1), 0);
+
+    // Each idle tick banks the unused allowance, up to the cap.
+    limiter.end_tick();
+    assert_eq!(limiter.burst_tokens(1), 2);
+    limiter.end_tick();
+    assert_eq!(limiter.burst_tokens(1), 4);
+    limiter.end_tick();
+    assert_eq!(limiter.burst_tokens(1), 5);
+
+    let accepted = (0..100)
+        .filter(|_| limiter.check_and_consume(1).is_ok())
+        .count();
+    assert_eq!(accepted, 7);
+}
+
+#[test]
+fn test_reconnecting_does_not_refill_burst() {
+    let mut limiter = VoxelEditRateLimiter::new(2, 5);
+    limiter.check_and_consume(1).unwrap();
+    for _ in 0..3 {
+        limiter.end_tick();
+    }
+    assert_eq!(limiter.burst_tokens(1), 5);
+    let spent = (0..100)
+        .filter(|_| limiter.check_and_consume(1).is_ok())
+        .count();
+    assert_eq!(spent, 7);
+
+    // Dropping and rejoining within the tick leaves the allowance spent.
+    limiter.remove_client(1);
+    assert_eq!(limiter.burst_tokens(1), 0);
+    limiter.end_tick();
+    let accepted = (0..100)
+        .filter(|_| limiter.check_and_consume(1).is_ok())
+        .count();
+    assert_eq!(accepted, 2);
+}
+
+#[test]
+fn test_insert_chunk_requires_full_chunk() {
+    let cid = test_chunk_id();
+    let mut store = ServerChunkStore::new();
+    assert!(!store.insert_chunk(cid, vec![VoxelMaterial::STONE; 8]));
+    assert!(!store.is_loaded(&cid));
+
+    let mut voxels = vec![VoxelMaterial::AIR; (CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE) as usize];
+    voxels[0] = VoxelMaterial::DIRT;
+    assert!(store.insert_chunk(cid, voxels));
+    assert_eq!(store.get_voxel(&cid, 0, 0, 0), Some(VoxelMaterial::DIRT));
+    assert_eq!(store.chunks().count(), 1);
+    assert_eq!(
+        store.chunk(&cid).map(<[VoxelMaterial]>::len),
+        Some((CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE) as usize)
+    );
+}
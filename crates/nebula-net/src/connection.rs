@@ -0,0 +1,181 @@
+//! Connection identities, the registry of live connections, and the
+//! per-connection task that dispatches received messages.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tokio::sync::{RwLock, mpsc, watch};
+
+use crate::messages::{Message, Pong, deserialize_message, serialize_message};
+use crate::routing::IncomingMessage;
+use crate::transport::{Channel, Transport};
+
+/// Unique identifier for a client connection within a server session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConnectionId(pub u64);
+
+/// Atomic generator for monotonically increasing [`ConnectionId`]s.
+pub struct IdGenerator {
+    next: AtomicU64,
+}
+
+impl IdGenerator {
+    /// Create a new generator starting at 1.
+    pub fn new() -> Self {
+        Self {
+            next: AtomicU64::new(1),
+        }
+    }
+
+    /// Return the next unique [`ConnectionId`].
+    pub fn next_id(&self) -> ConnectionId {
+        ConnectionId(self.next.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Default for IdGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Error returned when the connection map is at capacity.
+#[derive(Debug)]
+pub struct ConnectionLimitReached;
+
+/// Thread-safe map of active connections (peer addresses) keyed by [`ConnectionId`].
+pub struct ConnectionMap {
+    inner: RwLock<HashMap<ConnectionId, SocketAddr>>,
+    max_connections: usize,
+}
+
+impl ConnectionMap {
+    /// Create a new map with the given capacity limit.
+    pub fn new(max_connections: usize) -> Self {
+        Self {
+            inner: RwLock::new(HashMap::new()),
+            max_connections,
+        }
+    }
+
+    /// Insert a connection. Returns `Err` if the map is at capacity.
+    pub async fn insert(
+        &self,
+        id: ConnectionId,
+        peer_addr: SocketAddr,
+    ) -> Result<(), ConnectionLimitReached> {
+        let mut map = self.inner.write().await;
+        if map.len() >= self.max_connections {
+            return Err(ConnectionLimitReached);
+        }
+        map.insert(id, peer_addr);
+        Ok(())
+    }
+
+    /// Remove a connection by ID.
+    pub async fn remove(&self, id: &ConnectionId) -> Option<SocketAddr> {
+        self.inner.write().await.remove(id)
+    }
+
+    /// Return the number of active connections.
+    pub async fn len(&self) -> usize {
+        self.inner.read().await.len()
+    }
+
+    /// Return whether the map is empty.
+    pub async fn is_empty(&self) -> bool {
+        self.inner.read().await.is_empty()
+    }
+
+    /// Return the IDs of all active connections.
+    pub async fn ids(&self) -> Vec<ConnectionId> {
+        self.inner.read().await.keys().copied().collect()
+    }
+
+    /// Return the peer address of a connection.
+    pub async fn peer_addr(&self, id: &ConnectionId) -> Option<SocketAddr> {
+        self.inner.read().await.get(id).copied()
+    }
+}
+
+/// Per-connection loop: forwards received messages to the sink and
+/// writes queued outgoing payloads. Exits when the peer disconnects, the
+/// connection is kicked or the server shuts down; dropping the transport
+/// on exit closes it.
+pub(crate) async fn run_connection<T: Transport>(
+    id: ConnectionId,
+    mut transport: T,
+    mut outbound_rx: mpsc::Receiver<(Channel, Vec<u8>)>,
+    sink: Option<mpsc::Sender<IncomingMessage>>,
+    shutdown_rx: &mut watch::Receiver<bool>,
+) {
+    loop {
+        tokio::select! {
+            result = transport.recv() => {
+                let Ok((channel, bytes)) = result else {
+                    break;
+                };
+                tracing::trace!(
+                    "Connection {id:?} received {} bytes on {channel:?}",
+                    bytes.len()
+                );
+                let message = match deserialize_message(&bytes) {
+                    Ok(message) => message,
+                    Err(e) => {
+                        tracing::debug!("Connection {id:?} sent undecodable message: {e}");
+                        continue;
+                    }
+                };
+                if let Message::Ping(ping) = message {
+                    let pong = Message::Pong(Pong {
+                        timestamp_ms: ping.timestamp_ms,
+                        sequence: ping.sequence,
+                    });
+                    if let Ok(bytes) = serialize_message(&pong)
+                        && transport.send(Channel::Unreliable, &bytes).await.is_err()
+                    {
+                        break;
+                    }
+                    continue;
+                }
+                if let Some(sink) = &sink {
+                    let incoming = IncomingMessage { connection_id: id, message };
+                    if sink.send(incoming).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            queued = outbound_rx.recv() => {
+                let Some((channel, bytes)) = queued else {
+                    break;
+                };
+                if transport.send(channel, &bytes).await.is_err() {
+                    break;
+                }
+            }
+            _ = shutdown_rx.changed() => {
+                if *shutdown_rx.borrow() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_id_uniqueness() {
+        let id_gen = IdGenerator::new();
+        let id1 = id_gen.next_id();
+        let id2 = id_gen.next_id();
+        let id3 = id_gen.next_id();
+        assert_ne!(id1, id2);
+        assert_ne!(id2, id3);
+        assert_eq!(id1.0 + 1, id2.0);
+        assert_eq!(id2.0 + 1, id3.0);
+    }
+}
@@ -0,0 +1,220 @@
+//! Protocol v1 frames and the v1 shapes of messages that changed since.
+//!
+//! A protocol v1 frame is `[1] [postcard-encoded Message]`, with no message
+//! tag or schema version byte; the postcard enum discriminant identifies the
+//! message. [`decode_v1_frame`] reads such a frame with the v1 payload shapes
+//! and upgrades it, so peers built before schema versioning can still log in.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::messages::{
+    ChunkData, EntityUpdate, LoginRequest, LoginResponse, Logout, Message, Ping, PlayerAction,
+    PlayerPosition, Pong, TimeSync,
+};
+use crate::schema::MessageSchema;
+
+/// Protocol version of frames without a per-message header.
+pub const LEGACY_PROTOCOL_VERSION: u8 = 1;
+
+/// The [`Message`] enum as a protocol v1 peer encodes it. Variant order must
+/// match v1's [`Message`], since postcard writes the variant index.
+#[derive(Serialize, Deserialize)]
+enum MessageV1 {
+    LoginRequest(LoginRequestV1),
+    LoginResponse(LoginResponseV1),
+    Logout(Logout),
+    ChunkData(ChunkData),
+    EntityUpdate(EntityUpdate),
+    PlayerPosition(PlayerPositionV1),
+    PlayerAction(PlayerAction),
+    Ping(Ping),
+    Pong(Pong),
+    TimeSync(TimeSync),
+}
+
+impl From<MessageV1> for Message {
+    fn from(m: MessageV1) -> Self {
+        match m {
+            MessageV1::LoginRequest(m) => Message::LoginRequest(m.into()),
+            MessageV1::LoginResponse(m) => Message::LoginResponse(m.into()),
+            MessageV1::Logout(m) => Message::Logout(m),
+            MessageV1::ChunkData(m) => Message::ChunkData(m),
+            MessageV1::EntityUpdate(m) => Message::EntityUpdate(m),
+            MessageV1::PlayerPosition(m) => Message::PlayerPosition(m.into()),
+            MessageV1::PlayerAction(m) => Message::PlayerAction(m),
+            MessageV1::Ping(m) => Message::Ping(m),
+            MessageV1::Pong(m) => Message::Pong(m),
+            MessageV1::TimeSync(m) => Message::TimeSync(m),
+        }
+    }
+}
+
+/// Decode the body of a protocol v1 frame (everything after the version
+/// byte), filling fields added since v1 with defaults.
+pub(crate) fn decode_v1_frame(body: &[u8]) -> Result<Message, postcard::Error> {
+    postcard::from_bytes::<MessageV1>(body).map(Message::from)
+}
+
+/// Decode a legacy payload into `L` and upgrade it to the current struct.
+pub(crate) fn upgrade<L: DeserializeOwned, T: From<L>>(body: &[u8]) -> Result<T, postcard::Error> {
+    postcard::from_bytes::<L>(body).map(T::from)
+}
+
+/// `LoginRequest` v1: no schema exchange.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct LoginRequestV1 {
+    player_name: String,
+}
+
+/// `LoginResponse` v1: no schema exchange.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct LoginResponseV1 {
+    player_id: u64,
+    success: bool,
+    message: String,
+}
+
+/// `PlayerPosition` v1: no input sequence number.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct PlayerPositionV1 {
+    player_id: u64,
+    pos_x_high: i64,
+    pos_x_low: i64,
+    pos_y_high: i64,
+    pos_y_low: i64,
+    pos_z_high: i64,
+    pos_z_low: i64,
+}
+
+impl From<&LoginRequest> for LoginRequestV1 {
+    fn from(m: &LoginRequest) -> Self {
+        Self {
+            player_name: m.player_name.clone(),
+        }
+    }
+}
+
+impl From<LoginRequestV1> for LoginRequest {
+    fn from(m: LoginRequestV1) -> Self {
+        Self {
+            player_name: m.player_name,
+            schema: MessageSchema::legacy_v1(),
+        }
+    }
+}
+
+impl From<&LoginResponse> for LoginResponseV1 {
+    fn from(m: &LoginResponse) -> Self {
+        Self {
+            player_id: m.player_id,
+            success: m.success,
+            message: m.message.clone(),
+        }
+    }
+}
+
+impl From<LoginResponseV1> for LoginResponse {
+    fn from(m: LoginResponseV1) -> Self {
+        Self {
+            player_id: m.player_id,
+            success: m.success,
+            message: m.message,
+            schema: MessageSchema::legacy_v1(),
+        }
+    }
+}
+
+impl From<&PlayerPosition> for PlayerPositionV1 {
+    fn from(m: &PlayerPosition) -> Self {
+        Self {
+            player_id: m.player_id,
+            pos_x_high: m.pos_x_high,
+            pos_x_low: m.pos_x_low,
+            pos_y_high: m.pos_y_high,
+            pos_y_low: m.pos_y_low,
+            pos_z_high: m.pos_z_high,
+            pos_z_low: m.pos_z_low,
+        }
+    }
+}
+
+impl From<PlayerPositionV1> for PlayerPosition {
+    fn from(m: PlayerPositionV1) -> Self {
+        Self {
+            player_id: m.player_id,
+            pos_x_high: m.pos_x_high,
+            pos_x_low: m.pos_x_low,
+            pos_y_high: m.pos_y_high,
+            pos_y_low: m.pos_y_low,
+            pos_z_high: m.pos_z_high,
+            pos_z_low: m.pos_z_low,
+            input_sequence: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{MessageError, deserialize_message};
+
+    /// Encode `msg` the way a protocol v1 peer's `serialize_message` did.
+    fn v1_frame(msg: &MessageV1) -> Vec<u8> {
+        let mut out = vec![LEGACY_PROTOCOL_VERSION];
+        out.extend(postcard::to_allocvec(msg).unwrap());
+        out
+    }
+
+    #[test]
+    fn test_v1_ping_frame_decodes() {
+        // [protocol 1] [variant 7 = Ping] [timestamp_ms = 5] [sequence = 3]
+        let decoded = deserialize_message(&[1, 7, 5, 3]).unwrap();
+        assert_eq!(
+            decoded,
+            Message::Ping(Ping {
+                timestamp_ms: 5,
+                sequence: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_v1_player_position_frame_decodes_with_defaults() {
+        let frame = v1_frame(&MessageV1::PlayerPosition(PlayerPositionV1 {
+            player_id: 4,
+            pos_x_high: 1,
+            pos_x_low: -1,
+            pos_y_high: 2,
+            pos_y_low: -2,
+            pos_z_high: 3,
+            pos_z_low: -3,
+        }));
+        let Message::PlayerPosition(decoded) = deserialize_message(&frame).unwrap() else {
+            panic!("expected PlayerPosition");
+        };
+        assert_eq!(decoded.player_id, 4);
+        assert_eq!(decoded.pos_z_low, -3);
+        assert_eq!(decoded.input_sequence, 0, "field added in v2 defaulted");
+    }
+
+    #[test]
+    fn test_v1_login_request_frame_implies_legacy_schema() {
+        let frame = v1_frame(&MessageV1::LoginRequest(LoginRequestV1 {
+            player_name: "Old".to_string(),
+        }));
+        let Message::LoginRequest(decoded) = deserialize_message(&frame).unwrap() else {
+            panic!("expected LoginRequest");
+        };
+        assert_eq!(decoded.player_name, "Old");
+        assert_eq!(decoded.schema, MessageSchema::legacy_v1());
+    }
+
+    #[test]
+    fn test_truncated_v1_frame_rejected() {
+        assert!(matches!(
+            deserialize_message(&[LEGACY_PROTOCOL_VERSION, 7]),
+            Err(MessageError::Postcard(_))
+        ));
+    }
+}
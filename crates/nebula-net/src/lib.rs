@@ -1,16 +1,21 @@
-//! TCP networking: connection management, message framing, serialization, and connection lifecycle.
+//! Networking over TCP or UDP: transports, connection management, message framing, serialization, and connection lifecycle.
 
 pub mod bandwidth;
 pub mod compression;
+pub mod connection;
 pub mod diagnostics;
 pub mod framing;
+pub mod legacy;
 pub mod messages;
 pub mod platform;
 pub mod reconnection;
 pub mod routing;
+pub mod schema;
 pub mod session;
 pub mod tcp_client;
 pub mod tcp_server;
+pub mod transport;
+pub mod udp_transport;
 
 pub use bandwidth::{
     MessageTypeStats, NetworkCounters, NetworkStats, PerMessageCounters, StatsSnapshot,
@@ -20,12 +25,14 @@ pub use compression::{
     COMPRESSION_FLAG_LZ4, COMPRESSION_FLAG_NONE, CompressionConfig, CompressionError,
     compress_payload, decompress_payload,
 };
+pub use connection::{ConnectionId, ConnectionLimitReached, ConnectionMap, IdGenerator};
 pub use diagnostics::{DiagnosticsConfig, DiagnosticsTracker, NetworkDiagnostics};
 pub use framing::{FrameConfig, FrameError, read_frame, write_frame};
+pub use legacy::LEGACY_PROTOCOL_VERSION;
 pub use messages::{
     ChunkData, EntityUpdate, LoginRequest, LoginResponse, Logout, Message, MessageError,
-    PROTOCOL_VERSION, Ping, PlayerAction, PlayerPosition, Pong, TimeSync, deserialize_message,
-    serialize_message,
+    PROTOCOL_VERSION, Ping, PlayerAction, PlayerCorrection, PlayerPosition, Pong, TimeSync,
+    deserialize_message, serialize_message,
 };
 pub use platform::{
     SocketConfig, configure_stream, create_listener, default_bind_address, ipv4_bind_address,
@@ -38,8 +45,12 @@ pub use routing::{
     HandlerContext, IncomingMessage, MessageHandler, MessageRouter, MessageTag, message_channel,
     process_incoming_messages,
 };
+pub use schema::{
+    MessageSchema, NegotiatedVersions, NegotiationError, VersionRange, serialize_message_at,
+    serialize_message_for,
+};
 pub use session::{AuthError, PlayerSession, SessionManager, SessionState, timeout_check};
 pub use tcp_client::{ConnectionState, ConnectionStateWatch, GameClient};
-pub use tcp_server::{
-    ConnectionId, ConnectionLimitReached, ConnectionMap, GameServer, IdGenerator, ServerConfig,
-};
+pub use tcp_server::{GameServer, ServerConfig};
+pub use transport::{Channel, TcpTransport, Transport, TransportError, TransportKind};
+pub use udp_transport::{MAX_UDP_PAYLOAD, UdpConfig, UdpListener, UdpTransport};
@@ -1,13 +1,18 @@
 //! Network message types and serialization.
 //!
 //! All messages are serialized with [`postcard`] and prefixed with a protocol
-//! version byte. Use [`serialize_message`] and [`deserialize_message`] for
-//! encoding/decoding.
+//! version byte, the message tag and the per-message schema version (see
+//! [`crate::schema`]). Use [`serialize_message`] and [`deserialize_message`]
+//! for encoding/decoding.
 
 use serde::{Deserialize, Serialize};
 
+use crate::legacy::{self, LEGACY_PROTOCOL_VERSION};
+use crate::routing::MessageTag;
+use crate::schema::{self, MessageSchema};
+
 /// Current wire-protocol version. Prepended to every serialized message.
-pub const PROTOCOL_VERSION: u8 = 1;
+pub const PROTOCOL_VERSION: u8 = 2;
 
 // ---------------------------------------------------------------------------
 // Top-level enum
@@ -43,6 +48,10 @@ pub enum Message {
     Pong(Pong),
     /// Time synchronization message for clock alignment.
     TimeSync(TimeSync),
+
+    // --- Movement ---
+    /// Server corrects the receiving client's own movement.
+    PlayerCorrection(PlayerCorrection),
 }
 
 // ---------------------------------------------------------------------------
@@ -54,6 +63,18 @@ pub enum Message {
 pub struct LoginRequest {
     /// Desired player name.
     pub player_name: String,
+    /// Message schema versions the client supports (added in v2).
+    pub schema: MessageSchema,
+}
+
+impl LoginRequest {
+    /// Login request advertising this build's [`MessageSchema`].
+    pub fn new(player_name: impl Into<String>) -> Self {
+        Self {
+            player_name: player_name.into(),
+            schema: MessageSchema::current(),
+        }
+    }
 }
 
 /// Server login response.
@@ -65,6 +86,8 @@ pub struct LoginResponse {
     pub success: bool,
     /// Human-readable status message.
     pub message: String,
+    /// Message schema versions the server supports (added in v2).
+    pub schema: MessageSchema,
 }
 
 /// Logout notification.
@@ -135,6 +158,31 @@ pub struct PlayerPosition {
     pub pos_z_high: i64,
     /// 128-bit Z position, low 64 bits.
     pub pos_z_low: i64,
+    /// Last client input sequence applied to this position (added in v2;
+    /// `0` when decoded from v1).
+    pub input_sequence: u32,
+}
+
+/// Authoritative state sent to a client whose claimed movement the server
+/// corrected. Mirrors the reconciliation state the client rewinds to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlayerCorrection {
+    /// Player identifier.
+    pub player_id: u64,
+    /// Server tick the state corresponds to.
+    pub tick: u64,
+    /// Authoritative X position in millimeters.
+    pub x: i64,
+    /// Authoritative Y position in millimeters.
+    pub y: i64,
+    /// Authoritative Z position in millimeters.
+    pub z: i64,
+    /// Authoritative X velocity in mm/tick.
+    pub vx: i64,
+    /// Authoritative Y velocity in mm/tick.
+    pub vy: i64,
+    /// Authoritative Z velocity in mm/tick.
+    pub vz: i64,
 }
 
 /// Player action (place/break voxel, interact, etc.).
@@ -194,11 +242,33 @@ pub enum MessageError {
     #[error("empty payload — no version byte")]
     EmptyPayload,
 
-    /// The version byte does not match [`PROTOCOL_VERSION`].
+    /// The version byte is neither [`PROTOCOL_VERSION`] nor
+    /// [`LEGACY_PROTOCOL_VERSION`].
     #[error("unsupported protocol version: {0}")]
     UnsupportedVersion(u8),
 
-    /// Postcard deserialization failed.
+    /// The payload ended before the message tag and schema version.
+    #[error("payload truncated — missing message header")]
+    Truncated,
+
+    /// The message tag byte is not a known [`MessageTag`].
+    #[error("unknown message tag: {0}")]
+    UnknownTag(u8),
+
+    /// The schema version is outside the range supported for this message.
+    #[error("unsupported {tag:?} schema version: {version}")]
+    UnsupportedMessageVersion {
+        /// The message type.
+        tag: MessageTag,
+        /// The schema version found on the wire.
+        version: u8,
+    },
+
+    /// No schema version was negotiated with the peer for this message.
+    #[error("no negotiated schema version for {0:?}")]
+    NotNegotiated(MessageTag),
+
+    /// Postcard serialization or deserialization failed.
     #[error("deserialization error: {0}")]
     Postcard(#[from] postcard::Error),
 }
@@ -207,206 +277,45 @@ pub enum MessageError {
 // Serialization helpers
 // ---------------------------------------------------------------------------
 
-/// Serialize a [`Message`] into a versioned binary payload.
+/// Serialize a [`Message`] into a versioned binary payload at the current
+/// schema version of its type.
 ///
-/// Wire format: `[version: u8] [postcard-encoded Message]`
+/// Wire format: `[version: u8] [tag: u8] [schema version: u8] [postcard-encoded payload]`
+///
+/// Use [`schema::serialize_message_for`] when talking to a peer that
+/// negotiated older schema versions.
 pub fn serialize_message(msg: &Message) -> Result<Vec<u8>, postcard::Error> {
-    let body = postcard::to_allocvec(msg)?;
-    let mut out = Vec::with_capacity(1 + body.len());
-    out.push(PROTOCOL_VERSION);
-    out.extend_from_slice(&body);
-    Ok(out)
+    let tag = msg.tag();
+    let body = schema::encode_current(msg)?;
+    Ok(schema::with_header(
+        tag,
+        tag.supported_versions().max,
+        &body,
+    ))
 }
 
 /// Deserialize a versioned binary payload into a [`Message`].
 ///
-/// Returns an error if the version is unsupported or the payload is malformed.
+/// Older schema versions, and frames from protocol v1 peers, are upgraded
+/// with fields they lack set to defaults. Returns an error if the protocol or
+/// schema version is unknown or the payload is malformed.
 pub fn deserialize_message(data: &[u8]) -> Result<Message, MessageError> {
-    if data.is_empty() {
+    let Some((&version, rest)) = data.split_first() else {
         return Err(MessageError::EmptyPayload);
+    };
+    match version {
+        PROTOCOL_VERSION => {}
+        LEGACY_PROTOCOL_VERSION => return Ok(legacy::decode_v1_frame(rest)?),
+        _ => return Err(MessageError::UnsupportedVersion(version)),
     }
 
-    let version = data[0];
-    if version != PROTOCOL_VERSION {
-        return Err(MessageError::UnsupportedVersion(version));
-    }
-
-    let msg = postcard::from_bytes(&data[1..])?;
-    Ok(msg)
+    let [tag, schema_version, body @ ..] = rest else {
+        return Err(MessageError::Truncated);
+    };
+    let tag = MessageTag::from_wire_id(*tag).ok_or(MessageError::UnknownTag(*tag))?;
+    schema::decode_versioned(tag, *schema_version, body)
 }
 
-// ---------------------------------------------------------------------------
-// Tests
-// ---------------------------------------------------------------------------
-
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_login_request_roundtrip() {
-        let msg = Message::LoginRequest(LoginRequest {
-            player_name: "Alice".to_string(),
-        });
-        let bytes = serialize_message(&msg).unwrap();
-        let decoded = deserialize_message(&bytes).unwrap();
-        assert_eq!(msg, decoded);
-    }
-
-    #[test]
-    fn test_login_response_roundtrip() {
-        let msg = Message::LoginResponse(LoginResponse {
-            player_id: 42,
-            success: true,
-            message: "Welcome".to_string(),
-        });
-        let bytes = serialize_message(&msg).unwrap();
-        let decoded = deserialize_message(&bytes).unwrap();
-        assert_eq!(msg, decoded);
-    }
-
-    #[test]
-    fn test_chunk_data_roundtrip() {
-        let msg = Message::ChunkData(ChunkData {
-            chunk_x: -100,
-            chunk_y: 50,
-            chunk_z: 200,
-            face: 3,
-            voxel_data: vec![1, 2, 3, 4, 5, 0, 255],
-        });
-        let bytes = serialize_message(&msg).unwrap();
-        let decoded = deserialize_message(&bytes).unwrap();
-        assert_eq!(msg, decoded);
-    }
-
-    #[test]
-    fn test_entity_update_128bit_coords_roundtrip() {
-        let msg = Message::EntityUpdate(EntityUpdate {
-            entity_id: 999,
-            pos_x_high: i64::MAX,
-            pos_x_low: i64::MIN,
-            pos_y_high: 0,
-            pos_y_low: 1,
-            pos_z_high: -1,
-            pos_z_low: 0,
-            rot_x: 0.0,
-            rot_y: 0.707,
-            rot_z: 0.0,
-            rot_w: 0.707,
-        });
-        let bytes = serialize_message(&msg).unwrap();
-        let decoded = deserialize_message(&bytes).unwrap();
-        assert_eq!(msg, decoded);
-    }
-
-    #[test]
-    fn test_ping_pong_roundtrip() {
-        let ping = Message::Ping(Ping {
-            timestamp_ms: 1234567890,
-            sequence: 42,
-        });
-        let pong = Message::Pong(Pong {
-            timestamp_ms: 1234567891,
-            sequence: 42,
-        });
-        for msg in [ping, pong] {
-            let bytes = serialize_message(&msg).unwrap();
-            let decoded = deserialize_message(&bytes).unwrap();
-            assert_eq!(msg, decoded);
-        }
-    }
-
-    #[test]
-    fn test_time_sync_roundtrip() {
-        let msg = Message::TimeSync(TimeSync {
-            client_send_ms: 1000,
-            server_recv_ms: 1005,
-            server_send_ms: 1006,
-        });
-        let bytes = serialize_message(&msg).unwrap();
-        let decoded = deserialize_message(&bytes).unwrap();
-        assert_eq!(msg, decoded);
-    }
-
-    #[test]
-    fn test_player_action_roundtrip() {
-        let msg = Message::PlayerAction(PlayerAction {
-            player_id: 7,
-            action_type: 1,
-            target_x: -500,
-            target_y: 100,
-            target_z: 300,
-            payload: vec![0xDE, 0xAD, 0xBE, 0xEF],
-        });
-        let bytes = serialize_message(&msg).unwrap();
-        let decoded = deserialize_message(&bytes).unwrap();
-        assert_eq!(msg, decoded);
-    }
-
-    #[test]
-    fn test_postcard_output_is_compact() {
-        let msg = Message::Ping(Ping {
-            timestamp_ms: 100,
-            sequence: 1,
-        });
-        let bytes = serialize_message(&msg).unwrap();
-        assert!(
-            bytes.len() < 20,
-            "Ping should be compact, got {} bytes",
-            bytes.len()
-        );
-    }
-
-    #[test]
-    fn test_unsupported_version_rejected() {
-        let msg = Message::Ping(Ping {
-            timestamp_ms: 0,
-            sequence: 0,
-        });
-        let mut bytes = serialize_message(&msg).unwrap();
-        bytes[0] = 255;
-        let result = deserialize_message(&bytes);
-        assert!(matches!(result, Err(MessageError::UnsupportedVersion(255))));
-    }
-
-    #[test]
-    fn test_empty_payload_rejected() {
-        let result = deserialize_message(&[]);
-        assert!(matches!(result, Err(MessageError::EmptyPayload)));
-    }
-
-    #[test]
-    fn test_corrupted_payload_rejected() {
-        let result = deserialize_message(&[PROTOCOL_VERSION, 0xFF, 0xFF, 0xFF]);
-        assert!(
-            result.is_err(),
-            "Corrupted payload should fail deserialization"
-        );
-    }
-
-    #[test]
-    fn test_all_fields_survive_roundtrip_player_position() {
-        let msg = Message::PlayerPosition(PlayerPosition {
-            player_id: u64::MAX,
-            pos_x_high: i64::MAX,
-            pos_x_low: i64::MIN,
-            pos_y_high: 0,
-            pos_y_low: 0,
-            pos_z_high: -1,
-            pos_z_low: -1,
-        });
-        let bytes = serialize_message(&msg).unwrap();
-        let decoded = deserialize_message(&bytes).unwrap();
-        assert_eq!(msg, decoded);
-    }
-
-    #[test]
-    fn test_version_byte_is_first_byte() {
-        let msg = Message::Logout(Logout {
-            player_id: 1,
-            reason: "quit".to_string(),
-        });
-        let bytes = serialize_message(&msg).unwrap();
-        assert_eq!(bytes[0], PROTOCOL_VERSION);
-    }
-}
+#[path = "messages_tests.rs"]
+mod tests;
@@ -0,0 +1,187 @@
+//! Unit tests for network message types and serialization.
+
+use super::*;
+
+#[test]
+fn test_login_request_roundtrip() {
+    let msg = Message::LoginRequest(LoginRequest::new("Alice"));
+    let bytes = serialize_message(&msg).unwrap();
+    let decoded = deserialize_message(&bytes).unwrap();
+    assert_eq!(msg, decoded);
+}
+
+#[test]
+fn test_login_response_roundtrip() {
+    let msg = Message::LoginResponse(LoginResponse {
+        player_id: 42,
+        success: true,
+        message: "Welcome".to_string(),
+        schema: MessageSchema::current(),
+    });
+    let bytes = serialize_message(&msg).unwrap();
+    let decoded = deserialize_message(&bytes).unwrap();
+    assert_eq!(msg, decoded);
+}
+
+#[test]
+fn test_chunk_data_roundtrip() {
+    let msg = Message::ChunkData(ChunkData {
+        chunk_x: -100,
+        chunk_y: 50,
+        chunk_z: 200,
+        face: 3,
+        voxel_data: vec![1, 2, 3, 4, 5, 0, 255],
+    });
+    let bytes = serialize_message(&msg).unwrap();
+    let decoded = deserialize_message(&bytes).unwrap();
+    assert_eq!(msg, decoded);
+}
+
+#[test]
+fn test_entity_update_128bit_coords_roundtrip() {
+    let msg = Message::EntityUpdate(EntityUpdate {
+        entity_id: 999,
+        pos_x_high: i64::MAX,
+        pos_x_low: i64::MIN,
+        pos_y_high: 0,
+        pos_y_low: 1,
+        pos_z_high: -1,
+        pos_z_low: 0,
+        rot_x: 0.0,
+        rot_y: 0.707,
+        rot_z: 0.0,
+        rot_w: 0.707,
+    });
+    let bytes = serialize_message(&msg).unwrap();
+    let decoded = deserialize_message(&bytes).unwrap();
+    assert_eq!(msg, decoded);
+}
+
+#[test]
+fn test_ping_pong_roundtrip() {
+    let ping = Message::Ping(Ping {
+        timestamp_ms: 1234567890,
+        sequence: 42,
+    });
+    let pong = Message::Pong(Pong {
+        timestamp_ms: 1234567891,
+        sequence: 42,
+    });
+    for msg in [ping, pong] {
+        let bytes = serialize_message(&msg).unwrap();
+        let decoded = deserialize_message(&bytes).unwrap();
+        assert_eq!(msg, decoded);
+    }
+}
+
+#[test]
+fn test_time_sync_roundtrip() {
+    let msg = Message::TimeSync(TimeSync {
+        client_send_ms: 1000,
+        server_recv_ms: 1005,
+        server_send_ms: 1006,
+    });
+    let bytes = serialize_message(&msg).unwrap();
+    let decoded = deserialize_message(&bytes).unwrap();
+    assert_eq!(msg, decoded);
+}
+
+#[test]
+fn test_player_correction_roundtrip() {
+    let msg = Message::PlayerCorrection(PlayerCorrection {
+        player_id: 3,
+        tick: 120,
+        x: -1_500,
+        y: 64_000,
+        z: 7,
+        vx: 0,
+        vy: -98,
+        vz: 12,
+    });
+    let bytes = serialize_message(&msg).unwrap();
+    let decoded = deserialize_message(&bytes).unwrap();
+    assert_eq!(msg, decoded);
+}
+
+#[test]
+fn test_player_action_roundtrip() {
+    let msg = Message::PlayerAction(PlayerAction {
+        player_id: 7,
+        action_type: 1,
+        target_x: -500,
+        target_y: 100,
+        target_z: 300,
+        payload: vec![0xDE, 0xAD, 0xBE, 0xEF],
+    });
+    let bytes = serialize_message(&msg).unwrap();
+    let decoded = deserialize_message(&bytes).unwrap();
+    assert_eq!(msg, decoded);
+}
+
+#[test]
+fn test_postcard_output_is_compact() {
+    let msg = Message::Ping(Ping {
+        timestamp_ms: 100,
+        sequence: 1,
+    });
+    let bytes = serialize_message(&msg).unwrap();
+    assert!(
+        bytes.len() < 20,
+        "Ping should be compact, got {} bytes",
+        bytes.len()
+    );
+}
+
+#[test]
+fn test_unsupported_version_rejected() {
+    let msg = Message::Ping(Ping {
+        timestamp_ms: 0,
+        sequence: 0,
+    });
+    let mut bytes = serialize_message(&msg).unwrap();
+    bytes[0] = 255;
+    let result = deserialize_message(&bytes);
+    assert!(matches!(result, Err(MessageError::UnsupportedVersion(255))));
+}
+
+#[test]
+fn test_empty_payload_rejected() {
+    let result = deserialize_message(&[]);
+    assert!(matches!(result, Err(MessageError::EmptyPayload)));
+}
+
+#[test]
+fn test_corrupted_payload_rejected() {
+    let result = deserialize_message(&[PROTOCOL_VERSION, 0xFF, 0xFF, 0xFF]);
+    assert!(
+        result.is_err(),
+        "Corrupted payload should fail deserialization"
+    );
+}
+
+#[test]
+fn test_all_fields_survive_roundtrip_player_position() {
+    let msg = Message::PlayerPosition(PlayerPosition {
+        player_id: u64::MAX,
+        pos_x_high: i64::MAX,
+        pos_x_low: i64::MIN,
+        pos_y_high: 0,
+        pos_y_low: 0,
+        pos_z_high: -1,
+        pos_z_low: -1,
+        input_sequence: u32::MAX,
+    });
+    let bytes = serialize_message(&msg).unwrap();
+    let decoded = deserialize_message(&bytes).unwrap();
+    assert_eq!(msg, decoded);
+}
+
+#[test]
+fn test_version_byte_is_first_byte() {
+    let msg = Message::Logout(Logout {
+        player_id: 1,
+        reason: "quit".to_string(),
+    });
+    let bytes = serialize_message(&msg).unwrap();
+    assert_eq!(bytes[0], PROTOCOL_VERSION);
+}
@@ -8,17 +8,18 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
+use crate::connection::{ConnectionId, ConnectionMap};
 use crate::messages::Message;
-use crate::tcp_server::{ConnectionId, ConnectionMap};
 
 // ---------------------------------------------------------------------------
 // MessageTag
 // ---------------------------------------------------------------------------
 
 /// Unique tag identifying a message type, used as the key for routing.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum MessageTag {
     /// Client login request.
     LoginRequest,
@@ -40,6 +41,8 @@ pub enum MessageTag {
     Pong,
     /// Time synchronization.
     TimeSync,
+    /// Server movement correction.
+    PlayerCorrection,
 }
 
 impl Message {
@@ -56,6 +59,7 @@ impl Message {
             Message::Ping(_) => MessageTag::Ping,
             Message::Pong(_) => MessageTag::Pong,
             Message::TimeSync(_) => MessageTag::TimeSync,
+            Message::PlayerCorrection(_) => MessageTag::PlayerCorrection,
         }
     }
 }
@@ -245,9 +249,7 @@ mod tests {
             },
         );
 
-        let msg = Message::LoginRequest(LoginRequest {
-            player_name: "TestPlayer".to_string(),
-        });
+        let msg = Message::LoginRequest(LoginRequest::new("TestPlayer"));
         let ctx = dummy_ctx();
         router.route(msg, &ctx);
 
@@ -314,12 +316,7 @@ mod tests {
 
         let ctx = dummy_ctx();
 
-        router.route(
-            Message::LoginRequest(LoginRequest {
-                player_name: "A".into(),
-            }),
-            &ctx,
-        );
+        router.route(Message::LoginRequest(LoginRequest::new("A")), &ctx);
         router.route(
             Message::Ping(Ping {
                 timestamp_ms: 0,
@@ -336,6 +333,7 @@ mod tests {
                 pos_y_low: 0,
                 pos_z_high: 0,
                 pos_z_low: 0,
+                input_sequence: 0,
             }),
             &ctx,
         );
@@ -0,0 +1,436 @@
+//! Per-message schema versioning and version negotiation.
+//!
+//! Every [`MessageTag`] has a range of schema versions this build can encode
+//! and decode. Serialized messages carry their schema version, so a payload
+//! written by an older peer is decoded by a version-specific decoder that
+//! fills defaults for fields added later.
+//!
+//! During login both sides exchange their [`MessageSchema`] and agree on the
+//! highest common version per message type ([`MessageSchema::negotiate`]).
+//! Outgoing messages are then encoded with [`serialize_message_for`].
+//!
+//! Version history:
+//!
+//! | Message          | v1                 | v2                          |
+//! |------------------|--------------------|-----------------------------|
+//! | `LoginRequest`   | `player_name`      | + `schema`                  |
+//! | `LoginResponse`  | id/success/message | + `schema`                  |
+//! | `PlayerPosition` | id + position      | + `input_sequence`          |
+//!
+//! All other messages are at v1.
+//!
+//! Frames from protocol v1 peers, which predate the per-message header, are
+//! decoded by [`crate::legacy`].
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::legacy::{LoginRequestV1, LoginResponseV1, PlayerPositionV1, upgrade};
+use crate::messages::{Message, MessageError, PROTOCOL_VERSION};
+use crate::routing::MessageTag;
+
+/// Inclusive range of schema versions supported for one message type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionRange {
+    /// Oldest supported version.
+    pub min: u8,
+    /// Newest supported version (the one written by default).
+    pub max: u8,
+}
+
+impl VersionRange {
+    /// Create a range covering `min..=max`.
+    pub const fn new(min: u8, max: u8) -> Self {
+        Self { min, max }
+    }
+
+    /// Whether `version` falls inside this range.
+    pub fn contains(self, version: u8) -> bool {
+        (self.min..=self.max).contains(&version)
+    }
+
+    /// The highest version supported by both ranges, if they overlap.
+    pub fn highest_common(self, other: VersionRange) -> Option<u8> {
+        let lo = self.min.max(other.min);
+        let hi = self.max.min(other.max);
+        (lo <= hi).then_some(hi)
+    }
+}
+
+impl MessageTag {
+    /// Every message tag, indexed by [`wire_id`](Self::wire_id).
+    pub const ALL: [MessageTag; 11] = [
+        MessageTag::LoginRequest,
+        MessageTag::LoginResponse,
+        MessageTag::Logout,
+        MessageTag::ChunkData,
+        MessageTag::EntityUpdate,
+        MessageTag::PlayerPosition,
+        MessageTag::PlayerAction,
+        MessageTag::Ping,
+        MessageTag::Pong,
+        MessageTag::TimeSync,
+        MessageTag::PlayerCorrection,
+    ];
+
+    /// Byte identifying this message type on the wire.
+    pub fn wire_id(self) -> u8 {
+        match self {
+            MessageTag::LoginRequest => 0,
+            MessageTag::LoginResponse => 1,
+            MessageTag::Logout => 2,
+            MessageTag::ChunkData => 3,
+            MessageTag::EntityUpdate => 4,
+            MessageTag::PlayerPosition => 5,
+            MessageTag::PlayerAction => 6,
+            MessageTag::Ping => 7,
+            MessageTag::Pong => 8,
+            MessageTag::TimeSync => 9,
+            MessageTag::PlayerCorrection => 10,
+        }
+    }
+
+    /// Parse a wire byte back into a [`MessageTag`].
+    pub fn from_wire_id(id: u8) -> Option<Self> {
+        Self::ALL.get(id as usize).copied()
+    }
+
+    /// Schema versions this build supports for the message type.
+    pub fn supported_versions(self) -> VersionRange {
+        match self {
+            MessageTag::LoginRequest | MessageTag::LoginResponse | MessageTag::PlayerPosition => {
+                VersionRange::new(1, 2)
+            }
+            _ => VersionRange::new(1, 1),
+        }
+    }
+}
+
+/// Registry of supported schema versions per message type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageSchema {
+    versions: BTreeMap<MessageTag, VersionRange>,
+}
+
+impl MessageSchema {
+    /// The schema supported by this build.
+    pub fn current() -> Self {
+        Self {
+            versions: MessageTag::ALL
+                .iter()
+                .map(|&tag| (tag, tag.supported_versions()))
+                .collect(),
+        }
+    }
+
+    /// The schema implied by a peer that predates schema exchange: version 1
+    /// of every message.
+    pub fn legacy_v1() -> Self {
+        Self {
+            versions: MessageTag::ALL
+                .iter()
+                .map(|&tag| (tag, VersionRange::new(1, 1)))
+                .collect(),
+        }
+    }
+
+    /// Builder: override the supported range for `tag`.
+    pub fn with_range(mut self, tag: MessageTag, range: VersionRange) -> Self {
+        self.versions.insert(tag, range);
+        self
+    }
+
+    /// The supported range for `tag`, if the message type is known.
+    pub fn range(&self, tag: MessageTag) -> Option<VersionRange> {
+        self.versions.get(&tag).copied()
+    }
+
+    /// Agree on the highest common version for every message type both
+    /// sides know. Types only one side knows are left out.
+    pub fn negotiate(&self, peer: &MessageSchema) -> Result<NegotiatedVersions, NegotiationError> {
+        let mut versions = BTreeMap::new();
+        for (&tag, &local) in &self.versions {
+            let Some(&remote) = peer.versions.get(&tag) else {
+                continue;
+            };
+            let version =
+                local
+                    .highest_common(remote)
+                    .ok_or(NegotiationError::NoCommonVersion {
+                        tag,
+                        local,
+                        peer: remote,
+                    })?;
+            versions.insert(tag, version);
+        }
+        Ok(NegotiatedVersions { versions })
+    }
+}
+
+impl Default for MessageSchema {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+/// The per-message versions agreed with one peer.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NegotiatedVersions {
+    versions: BTreeMap<MessageTag, u8>,
+}
+
+impl NegotiatedVersions {
+    /// The version to encode `tag` with, or `None` if it was not negotiated.
+    pub fn version(&self, tag: MessageTag) -> Option<u8> {
+        self.versions.get(&tag).copied()
+    }
+}
+
+/// Errors from [`MessageSchema::negotiate`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NegotiationError {
+    /// Both sides know the message type but their version ranges are disjoint.
+    #[error(
+        "no common version for {tag:?}: local supports {}..={}, peer supports {}..={}",
+        local.min, local.max, peer.min, peer.max
+    )]
+    NoCommonVersion {
+        /// The message type.
+        tag: MessageTag,
+        /// Versions supported locally.
+        local: VersionRange,
+        /// Versions supported by the peer.
+        peer: VersionRange,
+    },
+}
+
+// ---------------------------------------------------------------------------
+// Encoding / decoding
+// ---------------------------------------------------------------------------
+
+/// Encode the payload of `msg` at the current schema version.
+pub(crate) fn encode_current(msg: &Message) -> Result<Vec<u8>, postcard::Error> {
+    match msg {
+        Message::LoginRequest(m) => postcard::to_allocvec(m),
+        Message::LoginResponse(m) => postcard::to_allocvec(m),
+        Message::Logout(m) => postcard::to_allocvec(m),
+        Message::ChunkData(m) => postcard::to_allocvec(m),
+        Message::EntityUpdate(m) => postcard::to_allocvec(m),
+        Message::PlayerPosition(m) => postcard::to_allocvec(m),
+        Message::PlayerAction(m) => postcard::to_allocvec(m),
+        Message::Ping(m) => postcard::to_allocvec(m),
+        Message::Pong(m) => postcard::to_allocvec(m),
+        Message::TimeSync(m) => postcard::to_allocvec(m),
+        Message::PlayerCorrection(m) => postcard::to_allocvec(m),
+    }
+}
+
+/// Decode a payload written at the current schema version.
+fn decode_current(tag: MessageTag, body: &[u8]) -> Result<Message, postcard::Error> {
+    Ok(match tag {
+        MessageTag::LoginRequest => Message::LoginRequest(postcard::from_bytes(body)?),
+        MessageTag::LoginResponse => Message::LoginResponse(postcard::from_bytes(body)?),
+        MessageTag::Logout => Message::Logout(postcard::from_bytes(body)?),
+        MessageTag::ChunkData => Message::ChunkData(postcard::from_bytes(body)?),
+        MessageTag::EntityUpdate => Message::EntityUpdate(postcard::from_bytes(body)?),
+        MessageTag::PlayerPosition => Message::PlayerPosition(postcard::from_bytes(body)?),
+        MessageTag::PlayerAction => Message::PlayerAction(postcard::from_bytes(body)?),
+        MessageTag::Ping => Message::Ping(postcard::from_bytes(body)?),
+        MessageTag::Pong => Message::Pong(postcard::from_bytes(body)?),
+        MessageTag::TimeSync => Message::TimeSync(postcard::from_bytes(body)?),
+        MessageTag::PlayerCorrection => Message::PlayerCorrection(postcard::from_bytes(body)?),
+    })
+}
+
+/// Prepend the protocol header to an encoded payload.
+pub(crate) fn with_header(tag: MessageTag, version: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(3 + body.len());
+    out.push(PROTOCOL_VERSION);
+    out.push(tag.wire_id());
+    out.push(version);
+    out.extend_from_slice(body);
+    out
+}
+
+/// Serialize `msg` at a specific schema version.
+pub fn serialize_message_at(msg: &Message, version: u8) -> Result<Vec<u8>, MessageError> {
+    let tag = msg.tag();
+    let supported = tag.supported_versions();
+    if !supported.contains(version) {
+        return Err(MessageError::UnsupportedMessageVersion { tag, version });
+    }
+    let body = if version == supported.max {
+        encode_current(msg)?
+    } else {
+        match msg {
+            Message::LoginRequest(m) => postcard::to_allocvec(&LoginRequestV1::from(m))?,
+            Message::LoginResponse(m) => postcard::to_allocvec(&LoginResponseV1::from(m))?,
+            Message::PlayerPosition(m) => postcard::to_allocvec(&PlayerPositionV1::from(m))?,
+            _ => return Err(MessageError::UnsupportedMessageVersion { tag, version }),
+        }
+    };
+    Ok(with_header(tag, version, &body))
+}
+
+/// Serialize `msg` at the version negotiated with a peer.
+pub fn serialize_message_for(
+    msg: &Message,
+    negotiated: &NegotiatedVersions,
+) -> Result<Vec<u8>, MessageError> {
+    let tag = msg.tag();
+    let version = negotiated
+        .version(tag)
+        .ok_or(MessageError::NotNegotiated(tag))?;
+    serialize_message_at(msg, version)
+}
+
+/// Decode a message body of type `tag` written at schema `version`.
+pub(crate) fn decode_versioned(
+    tag: MessageTag,
+    version: u8,
+    body: &[u8],
+) -> Result<Message, MessageError> {
+    let supported = tag.supported_versions();
+    if !supported.contains(version) {
+        return Err(MessageError::UnsupportedMessageVersion { tag, version });
+    }
+    if version == supported.max {
+        return Ok(decode_current(tag, body)?);
+    }
+    Ok(match tag {
+        MessageTag::LoginRequest => Message::LoginRequest(upgrade::<LoginRequestV1, _>(body)?),
+        MessageTag::LoginResponse => Message::LoginResponse(upgrade::<LoginResponseV1, _>(body)?),
+        MessageTag::PlayerPosition => {
+            Message::PlayerPosition(upgrade::<PlayerPositionV1, _>(body)?)
+        }
+        _ => return Err(MessageError::UnsupportedMessageVersion { tag, version }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{
+        LoginRequest, Ping, PlayerPosition, deserialize_message, serialize_message,
+    };
+
+    fn position() -> PlayerPosition {
+        PlayerPosition {
+            player_id: 9,
+            pos_x_high: 1,
+            pos_x_low: -2,
+            pos_y_high: 3,
+            pos_y_low: -4,
+            pos_z_high: 5,
+            pos_z_low: -6,
+            input_sequence: 77,
+        }
+    }
+
+    #[test]
+    fn test_wire_ids_roundtrip() {
+        for tag in MessageTag::ALL {
+            assert_eq!(MessageTag::from_wire_id(tag.wire_id()), Some(tag));
+        }
+        assert_eq!(MessageTag::from_wire_id(200), None);
+    }
+
+    #[test]
+    fn test_v1_player_position_decodes_on_v2_with_default() {
+        let msg = Message::PlayerPosition(position());
+        let v1_bytes = serialize_message_at(&msg, 1).unwrap();
+        assert_eq!(v1_bytes[2], 1, "schema version byte");
+
+        let Message::PlayerPosition(decoded) = deserialize_message(&v1_bytes).unwrap() else {
+            panic!("expected PlayerPosition");
+        };
+        assert_eq!(decoded.input_sequence, 0, "new field defaulted");
+        assert_eq!(
+            decoded,
+            PlayerPosition {
+                input_sequence: 0,
+                ..position()
+            }
+        );
+    }
+
+    #[test]
+    fn test_current_version_roundtrip_keeps_new_field() {
+        let msg = Message::PlayerPosition(position());
+        let bytes = serialize_message(&msg).unwrap();
+        assert_eq!(bytes[2], 2);
+        assert_eq!(deserialize_message(&bytes).unwrap(), msg);
+    }
+
+    #[test]
+    fn test_v1_login_request_implies_legacy_schema() {
+        let msg = Message::LoginRequest(LoginRequest::new("Old"));
+        let bytes = serialize_message_at(&msg, 1).unwrap();
+        let Message::LoginRequest(decoded) = deserialize_message(&bytes).unwrap() else {
+            panic!("expected LoginRequest");
+        };
+        assert_eq!(decoded.player_name, "Old");
+        assert_eq!(decoded.schema, MessageSchema::legacy_v1());
+    }
+
+    #[test]
+    fn test_unsupported_message_version_rejected() {
+        let msg = Message::Ping(Ping {
+            timestamp_ms: 0,
+            sequence: 0,
+        });
+        assert!(matches!(
+            serialize_message_at(&msg, 2),
+            Err(MessageError::UnsupportedMessageVersion { .. })
+        ));
+
+        let mut bytes = serialize_message(&msg).unwrap();
+        bytes[2] = 9;
+        assert!(matches!(
+            deserialize_message(&bytes),
+            Err(MessageError::UnsupportedMessageVersion {
+                tag: MessageTag::Ping,
+                version: 9
+            })
+        ));
+    }
+
+    #[test]
+    fn test_negotiate_picks_highest_common_version() {
+        let server = MessageSchema::current();
+        let negotiated = server.negotiate(&MessageSchema::legacy_v1()).unwrap();
+        assert_eq!(negotiated.version(MessageTag::PlayerPosition), Some(1));
+
+        let negotiated = server.negotiate(&MessageSchema::current()).unwrap();
+        assert_eq!(negotiated.version(MessageTag::PlayerPosition), Some(2));
+        assert_eq!(negotiated.version(MessageTag::Ping), Some(1));
+    }
+
+    #[test]
+    fn test_negotiate_fails_without_overlap() {
+        let future_client = MessageSchema::current()
+            .with_range(MessageTag::PlayerPosition, VersionRange::new(3, 4));
+        let err = MessageSchema::current()
+            .negotiate(&future_client)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            NegotiationError::NoCommonVersion {
+                tag: MessageTag::PlayerPosition,
+                local: VersionRange::new(1, 2),
+                peer: VersionRange::new(3, 4),
+            }
+        );
+    }
+
+    #[test]
+    fn test_serialize_for_negotiated_peer_uses_old_version() {
+        let negotiated = MessageSchema::current()
+            .negotiate(&MessageSchema::legacy_v1())
+            .unwrap();
+        let bytes =
+            serialize_message_for(&Message::PlayerPosition(position()), &negotiated).unwrap();
+        assert_eq!(bytes[2], 1);
+    }
+}
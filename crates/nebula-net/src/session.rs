@@ -10,6 +10,8 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
 use crate::ConnectionId;
+use crate::messages::{LoginRequest, LoginResponse};
+use crate::schema::{MessageSchema, NegotiatedVersions, NegotiationError};
 
 /// State machine for a client connection's lifecycle.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -40,6 +42,8 @@ pub struct PlayerSession {
     pub position: [i128; 3],
     /// Timestamp when disconnect began, used for reconnection grace period.
     pub disconnect_time: Option<Instant>,
+    /// Per-message schema versions negotiated at login.
+    pub versions: NegotiatedVersions,
 }
 
 /// Errors that can occur during authentication.
@@ -54,6 +58,9 @@ pub enum AuthError {
     /// The player name was empty.
     #[error("player name cannot be empty")]
     EmptyName,
+    /// The client's message schema has no overlap with the server's.
+    #[error("incompatible protocol: {0}")]
+    IncompatibleProtocol(#[from] NegotiationError),
 }
 
 /// Manages all active player sessions and provides lifecycle operations.
@@ -87,6 +94,7 @@ impl SessionManager {
             last_activity: Instant::now(),
             position: [0; 3],
             disconnect_time: None,
+            versions: NegotiatedVersions::default(),
         };
         self.sessions.write().await.insert(connection_id, session);
     }
@@ -127,6 +135,44 @@ impl SessionManager {
         Ok(player_id)
     }
 
+    /// Negotiate message versions with the client's advertised schema, then
+    /// authenticate. Failures are reported in the returned [`LoginResponse`]
+    /// rather than as an error so they can be sent to the client as-is.
+    pub async fn handle_login(
+        &self,
+        connection_id: ConnectionId,
+        request: &LoginRequest,
+        server_schema: &MessageSchema,
+    ) -> LoginResponse {
+        let result = match server_schema.negotiate(&request.schema) {
+            Ok(versions) => self
+                .authenticate(connection_id, &request.player_name)
+                .await
+                .map(|player_id| (player_id, versions)),
+            Err(e) => Err(e.into()),
+        };
+
+        match result {
+            Ok((player_id, versions)) => {
+                if let Some(session) = self.sessions.write().await.get_mut(&connection_id) {
+                    session.versions = versions;
+                }
+                LoginResponse {
+                    player_id,
+                    success: true,
+                    message: "Welcome".to_string(),
+                    schema: server_schema.clone(),
+                }
+            }
+            Err(e) => LoginResponse {
+                player_id: 0,
+                success: false,
+                message: e.to_string(),
+                schema: server_schema.clone(),
+            },
+        }
+    }
+
     /// Initiate disconnect for a connection. Persists player state and
     /// transitions through Disconnecting → Removed.
     pub async fn on_disconnect(&self, connection_id: ConnectionId) -> Option<u64> {
@@ -176,6 +222,15 @@ impl SessionManager {
             .map(|s| s.state)
     }
 
+    /// Get the message versions negotiated for a session.
+    pub async fn versions(&self, connection_id: &ConnectionId) -> Option<NegotiatedVersions> {
+        self.sessions
+            .read()
+            .await
+            .get(connection_id)
+            .map(|s| s.versions.clone())
+    }
+
     /// Get the connection ID for a player ID (for reconnection).
     pub async fn connection_for_player(&self, player_id: u64) -> Option<ConnectionId> {
         self.player_index.read().await.get(&player_id).copied()
@@ -207,6 +262,8 @@ pub async fn timeout_check(session_manager: &SessionManager, timeout: Duration)
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::routing::MessageTag;
+    use crate::schema::VersionRange;
 
     #[tokio::test]
     async fn test_new_connection_starts_in_auth_state() {
@@ -333,4 +390,50 @@ mod tests {
         let found_cid = sm.connection_for_player(pid).await;
         assert_eq!(found_cid, None);
     }
+
+    #[tokio::test]
+    async fn test_login_negotiates_versions_with_legacy_client() {
+        let sm = SessionManager::new();
+        let cid = ConnectionId(1);
+        sm.on_connect(cid).await;
+
+        let request = LoginRequest {
+            player_name: "Ivy".to_string(),
+            schema: MessageSchema::legacy_v1(),
+        };
+        let response = sm
+            .handle_login(cid, &request, &MessageSchema::current())
+            .await;
+
+        assert!(response.success);
+        let versions = sm.versions(&cid).await.unwrap();
+        assert_eq!(versions.version(MessageTag::PlayerPosition), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_login_rejects_non_overlapping_client() {
+        let sm = SessionManager::new();
+        let cid = ConnectionId(1);
+        sm.on_connect(cid).await;
+
+        let request = LoginRequest {
+            player_name: "Jack".to_string(),
+            schema: MessageSchema::current()
+                .with_range(MessageTag::PlayerPosition, VersionRange::new(5, 6)),
+        };
+        let response = sm
+            .handle_login(cid, &request, &MessageSchema::current())
+            .await;
+
+        assert!(!response.success);
+        assert_eq!(response.player_id, 0);
+        assert!(
+            response.message.contains("incompatible protocol")
+                && response.message.contains("PlayerPosition")
+                && response.message.contains("5..=6"),
+            "unclear rejection message: {}",
+            response.message
+        );
+        assert_eq!(sm.state(&cid).await, Some(SessionState::Authenticating));
+    }
 }
@@ -1,23 +1,30 @@
-//! TCP client for connecting to a Nebula Engine game server.
+//! Client for connecting to a Nebula Engine game server.
 //!
 //! Manages the full connection lifecycle: connecting, heartbeat keepalive,
 //! and clean disconnect. State changes are broadcast via a [`watch`] channel
-//! so any number of consumers can react without polling.
+//! so any number of consumers can react without polling. Game messages are
+//! exchanged with [`GameClient::send`] and [`GameClient::recv`].
 
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::TcpStream;
-use tokio::sync::{Mutex, watch};
+use tokio::sync::{mpsc, watch};
+
+use crate::framing::FrameConfig;
+use crate::messages::{Message, Ping, deserialize_message, serialize_message};
+use crate::transport::{Channel, TcpTransport, Transport, TransportError, TransportKind};
+use crate::udp_transport::{UdpConfig, UdpTransport};
+
+/// Capacity of the outgoing queue and of the received-message inbox.
+const CLIENT_QUEUE_CAPACITY: usize = 256;
 
 /// Connection lifecycle state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConnectionState {
-    /// Attempting to establish a TCP connection.
+    /// Attempting to establish a connection.
     Connecting,
-    /// TCP connection established, ready for communication.
+    /// Connection established, ready for communication.
     Connected,
     /// Connection lost or intentionally closed.
     Disconnected,
@@ -62,60 +69,107 @@ impl ConnectionStateWatch {
 
 /// Handle to a connected game server session.
 ///
-/// Created via [`GameClient::connect`]. Owns the writer half of the TCP
-/// stream (behind a mutex for shared access), the connection state watch,
-/// and a shutdown signal for background tasks.
+/// Created via [`GameClient::connect`] or [`GameClient::connect_with`]. A
+/// background task owns the [`Transport`] and runs the heartbeat; the handle
+/// keeps the connection state watch, a shutdown signal for that task and the
+/// queues it exchanges messages through.
 pub struct GameClient {
-    /// Writer half, shared with the heartbeat task.
-    /// Will be used by the framing/send layer in story 03.
-    #[allow(dead_code)]
-    writer: Arc<Mutex<tokio::net::tcp::OwnedWriteHalf>>,
     /// Observable connection state.
     state: Arc<ConnectionStateWatch>,
-    /// Sending `true` causes reader and heartbeat tasks to exit.
+    /// Sending `true` causes the connection task to exit.
     shutdown_tx: watch::Sender<bool>,
+    /// Serialized payloads waiting to be written by the connection task.
+    outbound: mpsc::Sender<(Channel, Vec<u8>)>,
+    /// Messages received from the server, heartbeats excluded.
+    inbox: mpsc::Receiver<Message>,
 }
 
 impl GameClient {
-    /// Connect to the server at `addr`.
+    /// Connect to the server at `addr` over TCP.
     ///
-    /// Sets `TCP_NODELAY`, splits the stream, and spawns reader + heartbeat
-    /// background tasks. Returns immediately after the TCP handshake.
+    /// Sets `TCP_NODELAY` and spawns the connection task. Returns immediately
+    /// after the TCP handshake.
     pub async fn connect(addr: SocketAddr) -> std::io::Result<Self> {
+        Self::connect_with(addr, TransportKind::Tcp).await
+    }
+
+    /// Connect to the server at `addr` using the given transport.
+    ///
+    /// For [`TransportKind::Udp`] there is no handshake; the first heartbeat
+    /// introduces the client to the server.
+    pub async fn connect_with(addr: SocketAddr, kind: TransportKind) -> std::io::Result<Self> {
         let state = Arc::new(ConnectionStateWatch::new());
         state.set(ConnectionState::Connecting);
 
-        let stream = TcpStream::connect(addr).await?;
-        stream.set_nodelay(true)?;
-
-        state.set(ConnectionState::Connected);
-
-        let (reader, writer) = stream.into_split();
-        let writer = Arc::new(Mutex::new(writer));
         let (shutdown_tx, shutdown_rx) = watch::channel(false);
-
-        // Spawn reader task
-        let reader_state = Arc::clone(&state);
-        let mut reader_shutdown = shutdown_rx.clone();
-        tokio::spawn(async move {
-            Self::read_loop(reader, &reader_state, &mut reader_shutdown).await;
-        });
-
-        // Spawn heartbeat task
-        let hb_writer = Arc::clone(&writer);
-        let hb_state = Arc::clone(&state);
-        let mut hb_shutdown = shutdown_rx.clone();
-        tokio::spawn(async move {
-            Self::heartbeat_loop(&hb_writer, &hb_state, &mut hb_shutdown).await;
-        });
+        let (outbound, outbound_rx) = mpsc::channel(CLIENT_QUEUE_CAPACITY);
+        let (inbox_tx, inbox) = mpsc::channel(CLIENT_QUEUE_CAPACITY);
+        let queues = ConnectionQueues {
+            outbound: outbound_rx,
+            inbox: inbox_tx,
+        };
+        match kind {
+            TransportKind::Tcp => {
+                let transport = TcpTransport::connect(addr, FrameConfig::default()).await?;
+                Self::spawn_connection(transport, &state, shutdown_rx, queues);
+            }
+            TransportKind::Udp => {
+                let transport = UdpTransport::connect(addr, UdpConfig::default()).await?;
+                Self::spawn_connection(transport, &state, shutdown_rx, queues);
+            }
+        }
 
         Ok(Self {
-            writer,
             state,
             shutdown_tx,
+            outbound,
+            inbox,
         })
     }
 
+    /// Mark the client connected and spawn the connection task.
+    fn spawn_connection<T: Transport>(
+        transport: T,
+        state: &Arc<ConnectionStateWatch>,
+        mut shutdown_rx: watch::Receiver<bool>,
+        mut queues: ConnectionQueues,
+    ) {
+        state.set(ConnectionState::Connected);
+        let task_state = Arc::clone(state);
+        tokio::spawn(async move {
+            Self::connection_loop(transport, &task_state, &mut shutdown_rx, &mut queues).await;
+        });
+    }
+
+    /// Queue `msg` to be sent to the server on `channel`.
+    ///
+    /// Waits while the outgoing queue is full.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TransportError::Closed`] if the connection task has exited,
+    /// or an I/O error if the message cannot be serialized.
+    pub async fn send(&self, channel: Channel, msg: &Message) -> Result<(), TransportError> {
+        let bytes = serialize_message(msg).map_err(std::io::Error::other)?;
+        self.outbound
+            .send((channel, bytes))
+            .await
+            .map_err(|_| TransportError::Closed)
+    }
+
+    /// Wait for the next message from the server.
+    ///
+    /// Returns `None` once the connection has closed and every received
+    /// message has been taken.
+    pub async fn recv(&mut self) -> Option<Message> {
+        self.inbox.recv().await
+    }
+
+    /// Take the next received message without waiting.
+    pub fn try_recv(&mut self) -> Option<Message> {
+        self.inbox.try_recv().ok()
+    }
+
     /// Return the connection state watch.
     pub fn state(&self) -> &Arc<ConnectionStateWatch> {
         &self.state
@@ -123,64 +177,62 @@ impl GameClient {
 
     /// Disconnect from the server.
     ///
-    /// Signals background tasks to exit and transitions state to
+    /// Signals the connection task to exit and transitions state to
     /// [`ConnectionState::Disconnected`] immediately.
     pub fn disconnect(&self) {
         let _ = self.shutdown_tx.send(true);
         self.state.set(ConnectionState::Disconnected);
     }
 
-    /// Read incoming bytes until the connection closes or shutdown is signalled.
-    async fn read_loop(
-        mut reader: tokio::net::tcp::OwnedReadHalf,
+    /// Receive payloads, write queued messages and send a ping every 5
+    /// seconds. If nothing is received within 15 seconds, transition to
+    /// [`ConnectionState::Disconnected`].
+    ///
+    /// Received messages other than heartbeats go to the inbox; if the
+    /// application does not drain it, further messages are dropped.
+    async fn connection_loop<T: Transport>(
+        mut transport: T,
         state: &ConnectionStateWatch,
         shutdown_rx: &mut watch::Receiver<bool>,
+        queues: &mut ConnectionQueues,
     ) {
-        let mut buf = [0u8; 4096];
+        let ping_interval = Duration::from_secs(5);
+        let timeout_duration = Duration::from_secs(15);
+        let mut last_heard = tokio::time::Instant::now();
+        let mut interval = tokio::time::interval(ping_interval);
+        let mut sequence = 0u32;
+
         loop {
             tokio::select! {
-                result = reader.read(&mut buf) => {
-                    match result {
-                        Ok(0) | Err(_) => {
-                            state.set(ConnectionState::Disconnected);
-                            break;
-                        }
-                        Ok(n) => {
-                            // Forward bytes to framing layer (story 03).
-                            let _ = &buf[..n];
+                result = transport.recv() => {
+                    let Ok((_, bytes)) = result else {
+                        state.set(ConnectionState::Disconnected);
+                        break;
+                    };
+                    last_heard = tokio::time::Instant::now();
+                    match deserialize_message(&bytes) {
+                        Ok(Message::Ping(_) | Message::Pong(_)) => {}
+                        Ok(message) => {
+                            if queues.inbox.try_send(message).is_err() {
+                                tracing::warn!("Client inbox full, dropping message");
+                            }
                         }
+                        Err(e) => tracing::debug!("Undecodable message from server: {e}"),
                     }
                 }
-                _ = shutdown_rx.changed() => {
-                    if *shutdown_rx.borrow() {
+                Some((channel, bytes)) = queues.outbound.recv() => {
+                    if transport.send(channel, &bytes).await.is_err() {
+                        state.set(ConnectionState::Disconnected);
                         break;
                     }
                 }
-            }
-        }
-    }
-
-    /// Send a ping every 5 seconds. If no pong is received within 15 seconds,
-    /// transition to [`ConnectionState::Disconnected`].
-    async fn heartbeat_loop(
-        writer: &Mutex<tokio::net::tcp::OwnedWriteHalf>,
-        state: &ConnectionStateWatch,
-        shutdown_rx: &mut watch::Receiver<bool>,
-    ) {
-        let ping_interval = Duration::from_secs(5);
-        let timeout_duration = Duration::from_secs(15);
-        let last_pong = tokio::time::Instant::now();
-        let mut interval = tokio::time::interval(ping_interval);
-
-        loop {
-            tokio::select! {
                 _ = interval.tick() => {
                     if state.current() != ConnectionState::Connected {
                         break;
                     }
 
                     // Check for timeout
-                    if last_pong.elapsed() > timeout_duration {
+                    if last_heard.elapsed() > timeout_duration {
                         tracing::warn!(
                             "Heartbeat timeout — no response in {timeout_duration:?}"
                         );
@@ -188,10 +240,18 @@ impl GameClient {
                         break;
                     }
 
-                    // Send ping (placeholder opcode; real message defined in story 04).
-                    let ping_bytes = [0x00];
-                    let mut w = writer.lock().await;
-                    if w.write_all(&ping_bytes).await.is_err() {
+                    let timestamp_ms = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map_or(0, |d| d.as_millis() as u64);
+                    let ping = Message::Ping(Ping {
+                        timestamp_ms,
+                        sequence,
+                    });
+                    sequence = sequence.wrapping_add(1);
+                    let Ok(bytes) = serialize_message(&ping) else {
+                        continue;
+                    };
+                    if transport.send(Channel::Unreliable, &bytes).await.is_err() {
                         state.set(ConnectionState::Disconnected);
                         break;
                     }
@@ -206,6 +266,12 @@ impl GameClient {
     }
 }
 
+/// Queues shared between a [`GameClient`] handle and its connection task.
+struct ConnectionQueues {
+    outbound: mpsc::Receiver<(Channel, Vec<u8>)>,
+    inbox: mpsc::Sender<Message>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
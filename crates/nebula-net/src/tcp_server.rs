@@ -1,92 +1,24 @@
-//! TCP server for accepting and managing client connections.
+//! Game server for accepting and managing client connections over TCP or UDP.
 
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
 
-use tokio::io::AsyncReadExt;
 use tokio::net::TcpListener;
-use tokio::net::tcp::OwnedWriteHalf;
-use tokio::sync::{RwLock, watch};
+use tokio::sync::{RwLock, mpsc, watch};
 
-/// Unique identifier for a TCP connection within a server session.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct ConnectionId(pub u64);
+use crate::connection::{ConnectionId, ConnectionMap, IdGenerator, run_connection};
+use crate::framing::FrameConfig;
+use crate::messages::{Message, serialize_message};
+use crate::routing::IncomingMessage;
+use crate::transport::{Channel, TcpTransport, Transport, TransportKind};
+use crate::udp_transport::{UdpConfig, UdpListener};
 
-/// Atomic generator for monotonically increasing [`ConnectionId`]s.
-pub struct IdGenerator {
-    next: AtomicU64,
-}
-
-impl IdGenerator {
-    /// Create a new generator starting at 1.
-    pub fn new() -> Self {
-        Self {
-            next: AtomicU64::new(1),
-        }
-    }
-
-    /// Return the next unique [`ConnectionId`].
-    pub fn next_id(&self) -> ConnectionId {
-        ConnectionId(self.next.fetch_add(1, Ordering::Relaxed))
-    }
-}
-
-impl Default for IdGenerator {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-/// Error returned when the connection map is at capacity.
-#[derive(Debug)]
-pub struct ConnectionLimitReached;
-
-/// Thread-safe map of active connections keyed by [`ConnectionId`].
-pub struct ConnectionMap {
-    inner: RwLock<HashMap<ConnectionId, OwnedWriteHalf>>,
-    max_connections: usize,
-}
-
-impl ConnectionMap {
-    /// Create a new map with the given capacity limit.
-    pub fn new(max_connections: usize) -> Self {
-        Self {
-            inner: RwLock::new(HashMap::new()),
-            max_connections,
-        }
-    }
-
-    /// Insert a connection. Returns `Err` if the map is at capacity.
-    pub async fn insert(
-        &self,
-        id: ConnectionId,
-        writer: OwnedWriteHalf,
-    ) -> Result<(), ConnectionLimitReached> {
-        let mut map = self.inner.write().await;
-        if map.len() >= self.max_connections {
-            return Err(ConnectionLimitReached);
-        }
-        map.insert(id, writer);
-        Ok(())
-    }
-
-    /// Remove a connection by ID.
-    pub async fn remove(&self, id: &ConnectionId) -> Option<OwnedWriteHalf> {
-        self.inner.write().await.remove(id)
-    }
+/// Capacity of each connection's outgoing payload queue.
+const OUTBOUND_CAPACITY: usize = 256;
 
-    /// Return the number of active connections.
-    pub async fn len(&self) -> usize {
-        self.inner.read().await.len()
-    }
-
-    /// Return whether the map is empty.
-    pub async fn is_empty(&self) -> bool {
-        self.inner.read().await.is_empty()
-    }
-}
+/// Sender half of a connection's outgoing payload queue.
+type OutboundSender = mpsc::Sender<(Channel, Vec<u8>)>;
 
 /// Configuration for [`GameServer`].
 pub struct ServerConfig {
@@ -94,6 +26,12 @@ pub struct ServerConfig {
     pub bind_addr: SocketAddr,
     /// Maximum concurrent connections. Default: 256.
     pub max_connections: usize,
+    /// Which transport to listen on. Default: [`TransportKind::Tcp`].
+    pub transport: TransportKind,
+    /// Framing limits for the TCP transport.
+    pub frame: FrameConfig,
+    /// Reliability settings for the UDP transport.
+    pub udp: UdpConfig,
 }
 
 impl Default for ServerConfig {
@@ -101,11 +39,19 @@ impl Default for ServerConfig {
         Self {
             bind_addr: "0.0.0.0:7777".parse().unwrap(),
             max_connections: 256,
+            transport: TransportKind::Tcp,
+            frame: FrameConfig::default(),
+            udp: UdpConfig::default(),
         }
     }
 }
 
-/// TCP game server that accepts connections and manages their lifecycle.
+/// Game server that accepts connections and manages their lifecycle.
+///
+/// Received payloads are decoded and forwarded to the message sink set with
+/// [`GameServer::with_message_sink`]; pings are answered directly by the
+/// connection task. Outgoing messages are queued per connection with
+/// [`GameServer::send_to`].
 pub struct GameServer {
     config: ServerConfig,
     /// Active connection map (public for test inspection).
@@ -113,6 +59,8 @@ pub struct GameServer {
     id_gen: Arc<IdGenerator>,
     shutdown_tx: watch::Sender<bool>,
     shutdown_rx: watch::Receiver<bool>,
+    sink: Option<mpsc::Sender<IncomingMessage>>,
+    outbound: Arc<RwLock<HashMap<ConnectionId, OutboundSender>>>,
 }
 
 impl GameServer {
@@ -125,17 +73,65 @@ impl GameServer {
             config,
             shutdown_tx,
             shutdown_rx,
+            sink: None,
+            outbound: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Forward every decoded message received from clients to `sink`
+    /// (see [`crate::routing::message_channel`]).
+    pub fn with_message_sink(mut self, sink: mpsc::Sender<IncomingMessage>) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// Queue `msg` for delivery to connection `id` on `channel`.
+    ///
+    /// Never blocks: returns `false` if the connection is unknown, closed or
+    /// its outgoing queue is full, in which case the message is dropped.
+    pub async fn send_to(&self, id: ConnectionId, channel: Channel, msg: &Message) -> bool {
+        let Ok(bytes) = serialize_message(msg) else {
+            return false;
+        };
+        let outbound = self.outbound.read().await;
+        let Some(tx) = outbound.get(&id) else {
+            return false;
+        };
+        match tx.try_send((channel, bytes)) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!("Dropping outgoing message for {id:?}: {e}");
+                false
+            }
         }
     }
 
-    /// Bind to the configured address and run the accept loop.
+    /// Close connection `id` after flushing already queued messages.
+    ///
+    /// Returns `false` if no such connection exists.
+    pub async fn kick(&self, id: ConnectionId) -> bool {
+        self.outbound.write().await.remove(&id).is_some()
+    }
+
+    /// Bind to the configured address with the configured transport and run
+    /// the accept loop.
     pub async fn run(&self) -> std::io::Result<()> {
-        let listener = TcpListener::bind(self.config.bind_addr).await?;
-        tracing::info!("Server listening on {}", self.config.bind_addr);
-        self.run_with_listener(listener).await
+        match self.config.transport {
+            TransportKind::Tcp => {
+                let listener = TcpListener::bind(self.config.bind_addr).await?;
+                tracing::info!("Server listening on {} (TCP)", self.config.bind_addr);
+                self.run_with_listener(listener).await
+            }
+            TransportKind::Udp => {
+                let listener =
+                    UdpListener::bind(self.config.bind_addr, self.config.udp.clone()).await?;
+                tracing::info!("Server listening on {} (UDP)", self.config.bind_addr);
+                self.run_with_udp_listener(listener).await
+            }
+        }
     }
 
-    /// Run the accept loop with a pre-bound listener (useful for tests).
+    /// Run the accept loop with a pre-bound TCP listener (useful for tests).
     pub async fn run_with_listener(&self, listener: TcpListener) -> std::io::Result<()> {
         let mut shutdown_rx = self.shutdown_rx.clone();
 
@@ -144,25 +140,8 @@ impl GameServer {
                 result = listener.accept() => {
                     let (stream, peer_addr) = result?;
                     stream.set_nodelay(true)?;
-
-                    let id = self.id_gen.next_id();
-                    let (reader, writer) = stream.into_split();
-
-                    if self.connections.insert(id, writer).await.is_err() {
-                        tracing::warn!("Connection limit reached, rejecting {peer_addr}");
-                        continue;
-                    }
-
-                    tracing::info!("Accepted connection {id:?} from {peer_addr}");
-
-                    let connections = Arc::clone(&self.connections);
-                    let mut task_shutdown = self.shutdown_rx.clone();
-
-                    tokio::spawn(async move {
-                        Self::handle_connection(id, reader, &mut task_shutdown).await;
-                        connections.remove(&id).await;
-                        tracing::info!("Connection {id:?} closed");
-                    });
+                    let transport = TcpTransport::new(stream, self.config.frame.clone());
+                    self.spawn_connection(peer_addr, transport).await;
                 }
                 _ = shutdown_rx.changed() => {
                     if *shutdown_rx.borrow() {
@@ -176,128 +155,64 @@ impl GameServer {
         Ok(())
     }
 
-    /// Signal the server to shut down gracefully.
-    pub fn shutdown(&self) {
-        let _ = self.shutdown_tx.send(true);
-    }
+    /// Run the accept loop with a pre-bound UDP listener.
+    pub async fn run_with_udp_listener(&self, mut listener: UdpListener) -> std::io::Result<()> {
+        let mut shutdown_rx = self.shutdown_rx.clone();
 
-    /// Per-connection reader loop.
-    async fn handle_connection(
-        id: ConnectionId,
-        mut reader: tokio::net::tcp::OwnedReadHalf,
-        shutdown_rx: &mut watch::Receiver<bool>,
-    ) {
-        let mut buf = [0u8; 4096];
         loop {
             tokio::select! {
-                result = reader.read(&mut buf) => {
-                    match result {
-                        Ok(0) | Err(_) => break,
-                        Ok(n) => {
-                            // Future: pass bytes to framing layer
-                            let _ = &buf[..n];
-                            tracing::trace!("Connection {id:?} received {n} bytes");
-                        }
-                    }
+                result = listener.accept() => {
+                    let Ok(transport) = result else {
+                        break;
+                    };
+                    let peer_addr = transport.peer_addr();
+                    self.spawn_connection(peer_addr, transport).await;
                 }
                 _ = shutdown_rx.changed() => {
                     if *shutdown_rx.borrow() {
+                        tracing::info!("Server shutting down");
                         break;
                     }
                 }
             }
         }
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::time::Duration;
-    use tokio::io::AsyncReadExt;
-    use tokio::net::TcpStream;
-
-    /// Helper: start a server on an ephemeral port and return the bound address.
-    async fn start_test_server(max_connections: usize) -> (SocketAddr, Arc<GameServer>) {
-        let config = ServerConfig {
-            bind_addr: "127.0.0.1:0".parse().unwrap(),
-            max_connections,
-        };
-        let server = Arc::new(GameServer::new(config));
-        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
-        let addr = listener.local_addr().unwrap();
-        let srv = Arc::clone(&server);
-        tokio::spawn(async move {
-            srv.run_with_listener(listener).await.unwrap();
-        });
-        tokio::time::sleep(Duration::from_millis(10)).await;
-        (addr, server)
-    }
-
-    #[tokio::test]
-    async fn test_server_binds_to_port() {
-        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
-        let addr = listener.local_addr().unwrap();
-        assert_ne!(addr.port(), 0);
+        Ok(())
     }
 
-    #[tokio::test]
-    async fn test_server_accepts_connection() {
-        let (addr, _server) = start_test_server(16).await;
-        let stream = TcpStream::connect(addr).await;
-        assert!(stream.is_ok(), "Client should connect to the server");
-    }
+    /// Register a new connection and spawn its handler task.
+    async fn spawn_connection<T: Transport>(&self, peer_addr: SocketAddr, transport: T) {
+        let id = self.id_gen.next_id();
 
-    #[tokio::test]
-    async fn test_multiple_clients_connect() {
-        let (addr, server) = start_test_server(16).await;
-        let mut streams = Vec::new();
-        for _ in 0..5 {
-            let stream = TcpStream::connect(addr).await.unwrap();
-            streams.push(stream);
+        if self.connections.insert(id, peer_addr).await.is_err() {
+            tracing::warn!("Connection limit reached, rejecting {peer_addr}");
+            return;
         }
-        tokio::time::sleep(Duration::from_millis(50)).await;
-        assert_eq!(server.connections.len().await, 5);
-    }
 
-    #[tokio::test]
-    async fn test_max_connections_enforced() {
-        let max = 2;
-        let (addr, server) = start_test_server(max).await;
+        tracing::info!("Accepted connection {id:?} from {peer_addr}");
 
-        let _c1 = TcpStream::connect(addr).await.unwrap();
-        let _c2 = TcpStream::connect(addr).await.unwrap();
-        tokio::time::sleep(Duration::from_millis(50)).await;
-        assert_eq!(server.connections.len().await, 2);
+        let (outbound_tx, outbound_rx) = mpsc::channel(OUTBOUND_CAPACITY);
+        self.outbound.write().await.insert(id, outbound_tx);
 
-        let _c3 = TcpStream::connect(addr).await.unwrap();
-        tokio::time::sleep(Duration::from_millis(50)).await;
-        assert!(server.connections.len().await <= max);
-    }
+        let connections = Arc::clone(&self.connections);
+        let outbound = Arc::clone(&self.outbound);
+        let sink = self.sink.clone();
+        let mut task_shutdown = self.shutdown_rx.clone();
 
-    #[tokio::test]
-    async fn test_graceful_shutdown_closes_connections() {
-        let (addr, server) = start_test_server(16).await;
-        let mut stream = TcpStream::connect(addr).await.unwrap();
-        tokio::time::sleep(Duration::from_millis(50)).await;
-
-        server.shutdown();
-        tokio::time::sleep(Duration::from_millis(100)).await;
-
-        let mut buf = [0u8; 64];
-        let n = stream.read(&mut buf).await.unwrap();
-        assert_eq!(n, 0, "Client should receive EOF after server shutdown");
+        tokio::spawn(async move {
+            run_connection(id, transport, outbound_rx, sink, &mut task_shutdown).await;
+            outbound.write().await.remove(&id);
+            connections.remove(&id).await;
+            tracing::info!("Connection {id:?} closed");
+        });
     }
 
-    #[tokio::test]
-    async fn test_connection_id_uniqueness() {
-        let id_gen = IdGenerator::new();
-        let id1 = id_gen.next_id();
-        let id2 = id_gen.next_id();
-        let id3 = id_gen.next_id();
-        assert_ne!(id1, id2);
-        assert_ne!(id2, id3);
-        assert_eq!(id1.0 + 1, id2.0);
-        assert_eq!(id2.0 + 1, id3.0);
+    /// Signal the server to shut down gracefully.
+    pub fn shutdown(&self) {
+        let _ = self.shutdown_tx.send(true);
     }
 }
+
+#[cfg(test)]
+#[path = "tcp_server_tests.rs"]
+mod tests;
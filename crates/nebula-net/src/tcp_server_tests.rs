@@ -0,0 +1,154 @@
+//! Unit tests for the game server's accept loop, message forwarding and
+//! outgoing queues.
+
+use super::*;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpStream;
+
+/// Helper: start a server on an ephemeral port and return the bound address.
+async fn start_test_server(max_connections: usize) -> (SocketAddr, Arc<GameServer>) {
+    let config = ServerConfig {
+        bind_addr: "127.0.0.1:0".parse().unwrap(),
+        max_connections,
+        ..ServerConfig::default()
+    };
+    let server = Arc::new(GameServer::new(config));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let srv = Arc::clone(&server);
+    tokio::spawn(async move {
+        srv.run_with_listener(listener).await.unwrap();
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    (addr, server)
+}
+
+#[tokio::test]
+async fn test_server_binds_to_port() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    assert_ne!(addr.port(), 0);
+}
+
+#[tokio::test]
+async fn test_server_accepts_connection() {
+    let (addr, _server) = start_test_server(16).await;
+    let stream = TcpStream::connect(addr).await;
+    assert!(stream.is_ok(), "Client should connect to the server");
+}
+
+#[tokio::test]
+async fn test_multiple_clients_connect() {
+    let (addr, server) = start_test_server(16).await;
+    let mut streams = Vec::new();
+    for _ in 0..5 {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        streams.push(stream);
+    }
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(server.connections.len().await, 5);
+}
+
+#[tokio::test]
+async fn test_max_connections_enforced() {
+    let max = 2;
+    let (addr, server) = start_test_server(max).await;
+
+    let _c1 = TcpStream::connect(addr).await.unwrap();
+    let _c2 = TcpStream::connect(addr).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(server.connections.len().await, 2);
+
+    let _c3 = TcpStream::connect(addr).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(server.connections.len().await <= max);
+}
+
+#[tokio::test]
+async fn test_graceful_shutdown_closes_connections() {
+    let (addr, server) = start_test_server(16).await;
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    server.shutdown();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut buf = [0u8; 64];
+    let n = stream.read(&mut buf).await.unwrap();
+    assert_eq!(n, 0, "Client should receive EOF after server shutdown");
+}
+
+#[tokio::test]
+async fn test_messages_forwarded_and_queued_per_connection() {
+    use crate::messages::{LoginRequest, LoginResponse};
+    use crate::routing::message_channel;
+    use crate::schema::MessageSchema;
+
+    let (sink, mut incoming) = message_channel(16);
+    let server = Arc::new(GameServer::new(ServerConfig::default()).with_message_sink(sink));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let srv = Arc::clone(&server);
+    tokio::spawn(async move {
+        srv.run_with_listener(listener).await.unwrap();
+    });
+
+    let mut client = crate::tcp_client::GameClient::connect(addr).await.unwrap();
+    let login = Message::LoginRequest(LoginRequest::new("Alice"));
+    client.send(Channel::Reliable, &login).await.unwrap();
+
+    let received = tokio::time::timeout(Duration::from_secs(2), incoming.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(received.message, login);
+
+    let response = Message::LoginResponse(LoginResponse {
+        player_id: 1,
+        success: true,
+        message: "welcome".into(),
+        schema: MessageSchema::current(),
+    });
+    assert!(
+        server
+            .send_to(received.connection_id, Channel::Reliable, &response)
+            .await
+    );
+    let reply = tokio::time::timeout(Duration::from_secs(2), client.recv())
+        .await
+        .unwrap();
+    assert_eq!(reply, Some(response));
+
+    assert!(server.kick(received.connection_id).await);
+    let closed = tokio::time::timeout(Duration::from_secs(2), client.recv())
+        .await
+        .unwrap();
+    assert_eq!(closed, None, "kicked client should see the stream end");
+    assert!(!server.kick(received.connection_id).await);
+}
+
+#[tokio::test]
+async fn test_udp_transport_selected_from_config() {
+    let config = ServerConfig {
+        bind_addr: "127.0.0.1:0".parse().unwrap(),
+        transport: TransportKind::Udp,
+        ..ServerConfig::default()
+    };
+    let server = Arc::new(GameServer::new(config));
+    let listener = UdpListener::bind("127.0.0.1:0".parse().unwrap(), UdpConfig::default())
+        .await
+        .unwrap();
+    let addr = listener.local_addr();
+    let srv = Arc::clone(&server);
+    tokio::spawn(async move {
+        srv.run_with_udp_listener(listener).await.unwrap();
+    });
+
+    let client = crate::tcp_client::GameClient::connect_with(addr, TransportKind::Udp)
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(server.connections.len().await, 1);
+    client.disconnect();
+}
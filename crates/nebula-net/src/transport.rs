@@ -0,0 +1,330 @@
+//! Transport abstraction over TCP and UDP.
+//!
+//! A [`Transport`] carries opaque byte payloads on one of two logical
+//! [`Channel`]s. The reliable channel guarantees ordered delivery (chunk data,
+//! logins); the unreliable channel may drop or reorder payloads and is meant
+//! for high-frequency state such as player positions.
+//!
+//! [`TcpTransport`] carries both channels over a single length-prefixed stream,
+//! so "unreliable" payloads are in fact delivered reliably. The UDP
+//! implementation lives in [`crate::udp_transport`].
+
+use std::future::Future;
+use std::net::SocketAddr;
+
+use tokio::net::TcpStream;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::framing::{FrameConfig, FrameError, read_frame, write_frame};
+
+pub use nebula_config::TransportKind;
+
+/// Logical delivery channel for a payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Channel {
+    /// Ordered, guaranteed delivery.
+    Reliable,
+    /// Best-effort delivery; payloads may be lost or arrive out of order.
+    Unreliable,
+}
+
+impl Channel {
+    /// Wire tag identifying this channel.
+    pub fn tag(self) -> u8 {
+        match self {
+            Self::Reliable => 0,
+            Self::Unreliable => 1,
+        }
+    }
+
+    /// Parse a wire tag back into a [`Channel`].
+    pub fn from_tag(tag: u8) -> Result<Self, TransportError> {
+        match tag {
+            0 => Ok(Self::Reliable),
+            1 => Ok(Self::Unreliable),
+            other => Err(TransportError::UnknownChannel(other)),
+        }
+    }
+}
+
+/// Errors that can occur while sending or receiving over a [`Transport`].
+#[derive(Debug, thiserror::Error)]
+pub enum TransportError {
+    /// A framing error on a stream transport.
+    #[error("frame error: {0}")]
+    Frame(#[from] FrameError),
+
+    /// An I/O error occurred.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The payload does not fit in a single datagram.
+    #[error("payload size {size} exceeds datagram maximum {max}")]
+    PayloadTooLarge {
+        /// The actual payload size.
+        size: usize,
+        /// The maximum payload size.
+        max: usize,
+    },
+
+    /// The received payload carried an unknown channel tag.
+    #[error("unknown channel tag: {0}")]
+    UnknownChannel(u8),
+
+    /// The transport has been closed by the peer or timed out.
+    #[error("transport closed")]
+    Closed,
+}
+
+/// A bidirectional, channelled message transport.
+///
+/// Both methods are cancel-safe: dropping a pending [`recv`](Self::recv)
+/// future inside `tokio::select!` never loses a payload.
+pub trait Transport: Send + 'static {
+    /// Send `bytes` on `channel`.
+    fn send(
+        &mut self,
+        channel: Channel,
+        bytes: &[u8],
+    ) -> impl Future<Output = Result<(), TransportError>> + Send;
+
+    /// Receive the next payload and the channel it arrived on.
+    fn recv(&mut self) -> impl Future<Output = Result<(Channel, Vec<u8>), TransportError>> + Send;
+}
+
+/// Capacity of the reader-task → transport queue.
+const TCP_INCOMING_CAPACITY: usize = 256;
+
+/// [`Transport`] over a TCP stream using [`write_frame`]/[`read_frame`].
+///
+/// Each frame payload is `[channel tag: u8] [bytes]`. A background task reads
+/// frames so that [`Transport::recv`] stays cancel-safe.
+pub struct TcpTransport {
+    writer: OwnedWriteHalf,
+    incoming: mpsc::Receiver<Result<(Channel, Vec<u8>), TransportError>>,
+    reader_task: JoinHandle<()>,
+    config: FrameConfig,
+}
+
+impl TcpTransport {
+    /// Wrap an established stream.
+    pub fn new(stream: TcpStream, config: FrameConfig) -> Self {
+        let (mut reader, writer) = stream.into_split();
+        let (tx, incoming) = mpsc::channel(TCP_INCOMING_CAPACITY);
+        let read_config = config.clone();
+
+        let reader_task = tokio::spawn(async move {
+            loop {
+                let item = match read_frame(&mut reader, &read_config).await {
+                    Ok(frame) => match frame.split_first() {
+                        Some((&tag, body)) => Channel::from_tag(tag).map(|ch| (ch, body.to_vec())),
+                        // Zero-length frames are keepalive padding.
+                        None => continue,
+                    },
+                    Err(FrameError::ConnectionClosed) => Err(TransportError::Closed),
+                    Err(e) => Err(e.into()),
+                };
+                let failed = item.is_err();
+                if tx.send(item).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            writer,
+            incoming,
+            reader_task,
+            config,
+        }
+    }
+
+    /// Connect to `addr` and enable `TCP_NODELAY`.
+    pub async fn connect(addr: SocketAddr, config: FrameConfig) -> std::io::Result<Self> {
+        let stream = TcpStream::connect(addr).await?;
+        stream.set_nodelay(true)?;
+        Ok(Self::new(stream, config))
+    }
+}
+
+impl Drop for TcpTransport {
+    fn drop(&mut self) {
+        self.reader_task.abort();
+    }
+}
+
+impl Transport for TcpTransport {
+    async fn send(&mut self, channel: Channel, bytes: &[u8]) -> Result<(), TransportError> {
+        let mut frame = Vec::with_capacity(1 + bytes.len());
+        frame.push(channel.tag());
+        frame.extend_from_slice(bytes);
+        write_frame(&mut self.writer, &frame, &self.config).await?;
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Result<(Channel, Vec<u8>), TransportError> {
+        self.incoming
+            .recv()
+            .await
+            .unwrap_or(Err(TransportError::Closed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::{
+        ChunkData, EntityUpdate, LoginRequest, LoginResponse, Logout, Message, Ping, PlayerAction,
+        PlayerPosition, Pong, TimeSync, deserialize_message, serialize_message,
+    };
+    use crate::schema::MessageSchema;
+    use crate::udp_transport::{UdpConfig, UdpListener, UdpTransport};
+    use tokio::net::TcpListener;
+
+    /// One instance of every [`Message`] variant.
+    fn all_messages() -> Vec<Message> {
+        vec![
+            Message::LoginRequest(LoginRequest::new("Alice")),
+            Message::LoginResponse(LoginResponse {
+                player_id: 7,
+                success: true,
+                message: "welcome".to_string(),
+                schema: MessageSchema::current(),
+            }),
+            Message::Logout(Logout {
+                player_id: 7,
+                reason: "bye".to_string(),
+            }),
+            Message::ChunkData(ChunkData {
+                chunk_x: -3,
+                chunk_y: 4,
+                chunk_z: 5,
+                face: 2,
+                voxel_data: vec![9; 2048],
+            }),
+            Message::EntityUpdate(EntityUpdate {
+                entity_id: 1,
+                pos_x_high: 1,
+                pos_x_low: 2,
+                pos_y_high: 3,
+                pos_y_low: 4,
+                pos_z_high: 5,
+                pos_z_low: 6,
+                rot_x: 0.0,
+                rot_y: 0.0,
+                rot_z: 0.0,
+                rot_w: 1.0,
+            }),
+            Message::PlayerPosition(PlayerPosition {
+                player_id: 7,
+                pos_x_high: 0,
+                pos_x_low: 10,
+                pos_y_high: 0,
+                pos_y_low: 20,
+                pos_z_high: 0,
+                pos_z_low: 30,
+                input_sequence: 3,
+            }),
+            Message::PlayerAction(PlayerAction {
+                player_id: 7,
+                action_type: 1,
+                target_x: 1,
+                target_y: 2,
+                target_z: 3,
+                payload: vec![1, 2, 3],
+            }),
+            Message::Ping(Ping {
+                timestamp_ms: 100,
+                sequence: 1,
+            }),
+            Message::Pong(Pong {
+                timestamp_ms: 100,
+                sequence: 1,
+            }),
+            Message::TimeSync(TimeSync {
+                client_send_ms: 1,
+                server_recv_ms: 2,
+                server_send_ms: 3,
+            }),
+        ]
+    }
+
+    async fn tcp_pair() -> (TcpTransport, TcpTransport) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = TcpTransport::connect(addr, FrameConfig::default())
+            .await
+            .unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        (client, TcpTransport::new(stream, FrameConfig::default()))
+    }
+
+    #[test]
+    fn test_channel_tag_roundtrip() {
+        for ch in [Channel::Reliable, Channel::Unreliable] {
+            assert_eq!(Channel::from_tag(ch.tag()).unwrap(), ch);
+        }
+        assert!(matches!(
+            Channel::from_tag(9),
+            Err(TransportError::UnknownChannel(9))
+        ));
+    }
+
+    /// Send every message type from `client`, echo it back from `server`,
+    /// and check the round-tripped message and channel.
+    async fn echo_all_messages<T: Transport>(client: &mut T, server: &mut T) {
+        for (i, msg) in all_messages().iter().enumerate() {
+            let channel = if i % 2 == 0 {
+                Channel::Reliable
+            } else {
+                Channel::Unreliable
+            };
+            client
+                .send(channel, &serialize_message(msg).unwrap())
+                .await
+                .unwrap();
+
+            let (ch, bytes) = server.recv().await.unwrap();
+            server.send(ch, &bytes).await.unwrap();
+
+            let (echo_ch, echo) = client.recv().await.unwrap();
+            assert_eq!(echo_ch, channel);
+            assert_eq!(&deserialize_message(&echo).unwrap(), msg);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tcp_loopback_echo_all_message_types() {
+        let (mut client, mut server) = tcp_pair().await;
+        echo_all_messages(&mut client, &mut server).await;
+    }
+
+    #[tokio::test]
+    async fn test_udp_loopback_echo_all_message_types() {
+        let mut listener = UdpListener::bind("127.0.0.1:0".parse().unwrap(), UdpConfig::default())
+            .await
+            .unwrap();
+        let mut client = UdpTransport::connect(listener.local_addr(), UdpConfig::default())
+            .await
+            .unwrap();
+
+        // The server learns about a UDP peer from its first datagram.
+        client.send(Channel::Reliable, b"hello").await.unwrap();
+        let mut server = listener.accept().await.unwrap();
+        assert_eq!(
+            server.recv().await.unwrap(),
+            (Channel::Reliable, b"hello".to_vec())
+        );
+
+        echo_all_messages(&mut client, &mut server).await;
+    }
+
+    #[tokio::test]
+    async fn test_tcp_recv_reports_closed_after_peer_drop() {
+        let (client, mut server) = tcp_pair().await;
+        drop(client);
+        assert!(matches!(server.recv().await, Err(TransportError::Closed)));
+    }
+}
@@ -0,0 +1,401 @@
+//! UDP [`Transport`] with a minimal reliability layer.
+//!
+//! Every datagram starts with a one-byte packet kind:
+//!
+//! ```text
+//! UNRELIABLE: [0x00] [payload]
+//! RELIABLE:   [0x01] [seq: u32 LE] [payload]
+//! ACK:        [0x02] [base: u32 LE] [bits: u32 LE]
+//! ```
+//!
+//! An ACK says "every reliable seq below `base` arrived, and bit `i` of `bits`
+//! is set if `base + 1 + i` arrived too". Sequence numbers wrap, so "below"
+//! means within half the sequence space behind. Unacknowledged reliable datagrams
+//! are resent every [`UdpConfig::resend_interval`], and the receiver buffers
+//! out-of-order reliable payloads so they are delivered in sequence.
+//!
+//! A single driver task owns the socket and all per-peer state; client and
+//! server transports talk to it through channels.
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::transport::{Channel, Transport, TransportError};
+
+/// Largest UDP payload that fits in a single IPv4 datagram.
+const MAX_DATAGRAM_SIZE: usize = 65_507;
+/// Header size of a reliable datagram (kind + sequence number).
+const RELIABLE_HEADER: usize = 5;
+/// Largest payload [`UdpTransport::send`] accepts on either channel.
+pub const MAX_UDP_PAYLOAD: usize = MAX_DATAGRAM_SIZE - RELIABLE_HEADER;
+
+const KIND_UNRELIABLE: u8 = 0x00;
+const KIND_RELIABLE: u8 = 0x01;
+const KIND_ACK: u8 = 0x02;
+
+/// Capacity of the transport → driver queue.
+const OUTGOING_CAPACITY: usize = 256;
+/// How far past the next expected sequence number a reliable datagram may
+/// be and still get buffered: the span the ACK bitfield can describe.
+/// Anything further ahead is dropped unacknowledged and resent later.
+const REORDER_WINDOW: u32 = 32;
+
+/// Configuration for the UDP transport.
+#[derive(Debug, Clone)]
+pub struct UdpConfig {
+    /// How long to wait for an ACK before resending a reliable datagram. Default: 100 ms.
+    pub resend_interval: Duration,
+    /// Drop a peer that has sent nothing for this long. Default: 15 s.
+    pub idle_timeout: Duration,
+    /// Fraction of outgoing datagrams to drop on purpose, for testing under
+    /// packet loss. Default: 0.0.
+    pub simulated_loss: f32,
+}
+
+impl Default for UdpConfig {
+    fn default() -> Self {
+        Self {
+            resend_interval: Duration::from_millis(100),
+            idle_timeout: Duration::from_secs(15),
+            simulated_loss: 0.0,
+        }
+    }
+}
+
+/// Whether reliable sequence number `a` comes before `b`, allowing for
+/// wrap-around: anything up to half the sequence space behind `b` is older.
+fn seq_before(a: u32, b: u32) -> bool {
+    (a.wrapping_sub(b) as i32) < 0
+}
+
+/// A payload queued by a transport for the driver to send.
+struct Outgoing {
+    peer: SocketAddr,
+    channel: Channel,
+    bytes: Vec<u8>,
+}
+
+/// [`Transport`] to a single UDP peer.
+pub struct UdpTransport {
+    peer: SocketAddr,
+    outgoing: mpsc::Sender<Outgoing>,
+    incoming: mpsc::UnboundedReceiver<(Channel, Vec<u8>)>,
+}
+
+impl UdpTransport {
+    /// Bind an ephemeral local socket and start talking to `addr`.
+    ///
+    /// UDP is connectionless: the server only learns about this client once
+    /// the first datagram arrives.
+    pub async fn connect(addr: SocketAddr, config: UdpConfig) -> std::io::Result<Self> {
+        let local = if addr.is_ipv6() {
+            SocketAddr::from(([0u16; 8], 0))
+        } else {
+            SocketAddr::from(([0, 0, 0, 0], 0))
+        };
+        let socket = UdpSocket::bind(local).await?;
+        let (out_tx, out_rx) = mpsc::channel(OUTGOING_CAPACITY);
+        let mut driver = Driver::new(socket, config, None, out_tx.downgrade());
+        let transport = driver.add_peer(addr, out_tx);
+        tokio::spawn(driver.run(out_rx));
+        Ok(transport)
+    }
+
+    /// The remote address of this transport.
+    pub fn peer_addr(&self) -> SocketAddr {
+        self.peer
+    }
+}
+
+impl Transport for UdpTransport {
+    async fn send(&mut self, channel: Channel, bytes: &[u8]) -> Result<(), TransportError> {
+        if bytes.len() > MAX_UDP_PAYLOAD {
+            return Err(TransportError::PayloadTooLarge {
+                size: bytes.len(),
+                max: MAX_UDP_PAYLOAD,
+            });
+        }
+        self.outgoing
+            .send(Outgoing {
+                peer: self.peer,
+                channel,
+                bytes: bytes.to_vec(),
+            })
+            .await
+            .map_err(|_| TransportError::Closed)
+    }
+
+    async fn recv(&mut self) -> Result<(Channel, Vec<u8>), TransportError> {
+        self.incoming.recv().await.ok_or(TransportError::Closed)
+    }
+}
+
+/// Server-side UDP socket that yields a [`UdpTransport`] per new peer.
+pub struct UdpListener {
+    local_addr: SocketAddr,
+    accept_rx: mpsc::Receiver<UdpTransport>,
+    /// Keeps the driver alive while the listener exists, even with no peers.
+    _outgoing: mpsc::Sender<Outgoing>,
+}
+
+impl UdpListener {
+    /// Bind to `addr` and start the driver task.
+    pub async fn bind(addr: SocketAddr, config: UdpConfig) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(addr).await?;
+        let local_addr = socket.local_addr()?;
+        let (out_tx, out_rx) = mpsc::channel(OUTGOING_CAPACITY);
+        let (accept_tx, accept_rx) = mpsc::channel(OUTGOING_CAPACITY);
+        let driver = Driver::new(socket, config, Some(accept_tx), out_tx.downgrade());
+        tokio::spawn(driver.run(out_rx));
+        Ok(Self {
+            local_addr,
+            accept_rx,
+            _outgoing: out_tx,
+        })
+    }
+
+    /// The bound local address.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Wait for a datagram from a new peer and return its transport.
+    pub async fn accept(&mut self) -> Result<UdpTransport, TransportError> {
+        self.accept_rx.recv().await.ok_or(TransportError::Closed)
+    }
+}
+
+/// A reliable datagram awaiting acknowledgement.
+struct PendingPacket {
+    datagram: Vec<u8>,
+    last_sent: Instant,
+}
+
+/// Reliability state for one remote peer.
+struct PeerState {
+    incoming: mpsc::UnboundedSender<(Channel, Vec<u8>)>,
+    next_send_seq: u32,
+    pending: BTreeMap<u32, PendingPacket>,
+    next_expected: u32,
+    buffered: BTreeMap<u32, Vec<u8>>,
+    last_heard: Instant,
+}
+
+impl PeerState {
+    fn new(incoming: mpsc::UnboundedSender<(Channel, Vec<u8>)>) -> Self {
+        Self {
+            incoming,
+            next_send_seq: 0,
+            pending: BTreeMap::new(),
+            next_expected: 0,
+            buffered: BTreeMap::new(),
+            last_heard: Instant::now(),
+        }
+    }
+
+    /// Accept a reliable payload and deliver everything now in sequence.
+    fn receive_reliable(&mut self, seq: u32, payload: &[u8]) {
+        if seq.wrapping_sub(self.next_expected) <= REORDER_WINDOW {
+            self.buffered.entry(seq).or_insert_with(|| payload.to_vec());
+        }
+        while let Some(bytes) = self.buffered.remove(&self.next_expected) {
+            let _ = self.incoming.send((Channel::Reliable, bytes));
+            self.next_expected = self.next_expected.wrapping_add(1);
+        }
+    }
+
+    /// Build the ACK datagram describing what has been received.
+    fn ack_datagram(&self) -> Vec<u8> {
+        let base = self.next_expected;
+        let bits = self
+            .buffered
+            .keys()
+            .filter_map(|&seq| seq.wrapping_sub(base).checked_sub(1))
+            .filter(|&offset| offset < 32)
+            .fold(0u32, |bits, offset| bits | (1 << offset));
+        [&[KIND_ACK][..], &base.to_le_bytes(), &bits.to_le_bytes()].concat()
+    }
+
+    /// Drop pending datagrams covered by an ACK.
+    fn apply_ack(&mut self, base: u32, bits: u32) {
+        self.pending.retain(|&seq, _| {
+            if seq_before(seq, base) {
+                return false;
+            }
+            let offset = seq.wrapping_sub(base);
+            !(1..=32).contains(&offset) || bits & (1 << (offset - 1)) == 0
+        });
+    }
+}
+
+/// Owns the socket and all per-peer reliability state.
+struct Driver {
+    socket: UdpSocket,
+    config: UdpConfig,
+    peers: HashMap<SocketAddr, PeerState>,
+    /// `Some` for listeners: unknown peers are announced here.
+    accept_tx: Option<mpsc::Sender<UdpTransport>>,
+    /// Used to hand new transports a sender without keeping the driver alive.
+    outgoing: mpsc::WeakSender<Outgoing>,
+}
+
+impl Driver {
+    fn new(
+        socket: UdpSocket,
+        config: UdpConfig,
+        accept_tx: Option<mpsc::Sender<UdpTransport>>,
+        outgoing: mpsc::WeakSender<Outgoing>,
+    ) -> Self {
+        Self {
+            socket,
+            config,
+            peers: HashMap::new(),
+            accept_tx,
+            outgoing,
+        }
+    }
+
+    /// Register `peer` and return the transport that talks to it.
+    fn add_peer(&mut self, peer: SocketAddr, outgoing: mpsc::Sender<Outgoing>) -> UdpTransport {
+        let (in_tx, incoming) = mpsc::unbounded_channel();
+        self.peers.insert(peer, PeerState::new(in_tx));
+        UdpTransport {
+            peer,
+            outgoing,
+            incoming,
+        }
+    }
+
+    /// Run until every transport (and the listener, if any) has been dropped.
+    async fn run(mut self, mut out_rx: mpsc::Receiver<Outgoing>) {
+        let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+        let mut tick = tokio::time::interval(self.config.resend_interval);
+
+        loop {
+            tokio::select! {
+                cmd = out_rx.recv() => match cmd {
+                    Some(out) => self.send_payload(out).await,
+                    None => break,
+                },
+                result = self.socket.recv_from(&mut buf) => match result {
+                    Ok((n, from)) => self.handle_datagram(from, &buf[..n]).await,
+                    // ICMP errors (e.g., port unreachable) surface here; keep going.
+                    Err(e) => tracing::trace!("UDP recv error: {e}"),
+                },
+                _ = tick.tick() => self.on_tick().await,
+            }
+        }
+    }
+
+    /// Send a datagram, honouring [`UdpConfig::simulated_loss`].
+    async fn transmit(&self, peer: SocketAddr, datagram: &[u8]) {
+        if self.config.simulated_loss > 0.0 && rand::random::<f32>() < self.config.simulated_loss {
+            return;
+        }
+        if let Err(e) = self.socket.send_to(datagram, peer).await {
+            tracing::trace!("UDP send to {peer} failed: {e}");
+        }
+    }
+
+    async fn send_payload(&mut self, out: Outgoing) {
+        let Some(state) = self.peers.get_mut(&out.peer) else {
+            return;
+        };
+        let datagram = match out.channel {
+            Channel::Unreliable => [&[KIND_UNRELIABLE][..], &out.bytes].concat(),
+            Channel::Reliable => {
+                let seq = state.next_send_seq;
+                state.next_send_seq = seq.wrapping_add(1);
+                let d = [&[KIND_RELIABLE][..], &seq.to_le_bytes(), &out.bytes].concat();
+                state.pending.insert(
+                    seq,
+                    PendingPacket {
+                        datagram: d.clone(),
+                        last_sent: Instant::now(),
+                    },
+                );
+                d
+            }
+        };
+        self.transmit(out.peer, &datagram).await;
+    }
+
+    async fn handle_datagram(&mut self, from: SocketAddr, data: &[u8]) {
+        let Some((&kind, body)) = data.split_first() else {
+            return;
+        };
+
+        if !self.peers.contains_key(&from) {
+            let Some(accept_tx) = &self.accept_tx else {
+                return;
+            };
+            let Some(outgoing) = self.outgoing.upgrade() else {
+                return;
+            };
+            let accept_tx = accept_tx.clone();
+            let transport = self.add_peer(from, outgoing);
+            if accept_tx.send(transport).await.is_err() {
+                self.peers.remove(&from);
+                return;
+            }
+        }
+        let Some(state) = self.peers.get_mut(&from) else {
+            return;
+        };
+        state.last_heard = Instant::now();
+
+        match kind {
+            KIND_UNRELIABLE => {
+                let _ = state.incoming.send((Channel::Unreliable, body.to_vec()));
+            }
+            KIND_RELIABLE if body.len() >= 4 => {
+                let seq = u32::from_le_bytes([body[0], body[1], body[2], body[3]]);
+                state.receive_reliable(seq, &body[4..]);
+                let ack = state.ack_datagram();
+                self.transmit(from, &ack).await;
+            }
+            KIND_ACK if body.len() >= 8 => {
+                let base = u32::from_le_bytes([body[0], body[1], body[2], body[3]]);
+                let bits = u32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+                state.apply_ack(base, bits);
+            }
+            _ => tracing::trace!("Malformed UDP datagram from {from}"),
+        }
+    }
+
+    /// Resend overdue reliable datagrams and drop idle or abandoned peers.
+    async fn on_tick(&mut self) {
+        let now = Instant::now();
+        let idle_timeout = self.config.idle_timeout;
+        self.peers.retain(|peer, state| {
+            let keep =
+                !state.incoming.is_closed() && now.duration_since(state.last_heard) < idle_timeout;
+            if !keep {
+                tracing::debug!("Dropping UDP peer {peer}");
+            }
+            keep
+        });
+
+        let mut resend = Vec::new();
+        for (&peer, state) in &mut self.peers {
+            for packet in state.pending.values_mut() {
+                if now.duration_since(packet.last_sent) >= self.config.resend_interval {
+                    packet.last_sent = now;
+                    resend.push((peer, packet.datagram.clone()));
+                }
+            }
+        }
+        for (peer, datagram) in resend {
+            self.transmit(peer, &datagram).await;
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "udp_transport_tests.rs"]
+mod tests;
@@ -0,0 +1,177 @@
+//! Unit tests for the UDP transport and its reliability layer.
+
+use super::*;
+
+/// Connect a client to a fresh listener and return both ends.
+async fn udp_pair(config: UdpConfig) -> (UdpTransport, UdpTransport) {
+    let mut listener = UdpListener::bind("127.0.0.1:0".parse().unwrap(), config.clone())
+        .await
+        .unwrap();
+    let mut client = UdpTransport::connect(listener.local_addr(), config)
+        .await
+        .unwrap();
+    client.send(Channel::Reliable, b"hello").await.unwrap();
+    let mut server = listener.accept().await.unwrap();
+    assert_eq!(
+        server.recv().await.unwrap(),
+        (Channel::Reliable, b"hello".to_vec())
+    );
+    (client, server)
+}
+
+#[test]
+fn test_ack_covers_buffered_out_of_order_packets() {
+    let (tx, _rx) = mpsc::unbounded_channel();
+    let mut state = PeerState::new(tx);
+    state.receive_reliable(0, b"a");
+    state.receive_reliable(2, b"c");
+    state.receive_reliable(4, b"e");
+
+    let ack = state.ack_datagram();
+    let base = u32::from_le_bytes([ack[1], ack[2], ack[3], ack[4]]);
+    let bits = u32::from_le_bytes([ack[5], ack[6], ack[7], ack[8]]);
+    assert_eq!(base, 1);
+    assert_eq!(bits, 0b101, "seqs 2 and 4 are buffered");
+}
+
+#[test]
+fn test_apply_ack_removes_acknowledged_packets() {
+    let (tx, _rx) = mpsc::unbounded_channel();
+    let mut state = PeerState::new(tx);
+    for seq in 0..6 {
+        state.pending.insert(
+            seq,
+            PendingPacket {
+                datagram: Vec::new(),
+                last_sent: Instant::now(),
+            },
+        );
+    }
+    state.apply_ack(2, 0b10);
+    let remaining: Vec<u32> = state.pending.keys().copied().collect();
+    assert_eq!(remaining, vec![2, 3, 5]);
+}
+
+#[test]
+fn test_sequence_numbers_wrap_around() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut state = PeerState::new(tx);
+    state.next_expected = u32::MAX;
+    state.receive_reliable(0, b"b");
+    assert!(rx.try_recv().is_err(), "seq 0 follows u32::MAX");
+    let ack = state.ack_datagram();
+    let bits = u32::from_le_bytes([ack[5], ack[6], ack[7], ack[8]]);
+    assert_eq!(bits, 0b1, "seq 0 is buffered one past the base");
+
+    state.receive_reliable(u32::MAX, b"a");
+    assert_eq!(rx.try_recv().unwrap().1, b"a");
+    assert_eq!(rx.try_recv().unwrap().1, b"b");
+    state.receive_reliable(u32::MAX, b"a");
+    assert!(
+        rx.try_recv().is_err(),
+        "stale seq before the wrap is dropped"
+    );
+
+    for seq in [u32::MAX - 1, u32::MAX, 0, 1] {
+        state.pending.insert(
+            seq,
+            PendingPacket {
+                datagram: Vec::new(),
+                last_sent: Instant::now(),
+            },
+        );
+    }
+    state.apply_ack(0, 0);
+    let remaining: Vec<u32> = state.pending.keys().copied().collect();
+    assert_eq!(
+        remaining,
+        vec![0, 1],
+        "seqs before the wrapped base are acked"
+    );
+}
+
+#[test]
+fn test_reliable_delivered_in_order_despite_reordering() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut state = PeerState::new(tx);
+    state.receive_reliable(1, b"b");
+    assert!(rx.try_recv().is_err(), "seq 1 must wait for seq 0");
+    state.receive_reliable(0, b"a");
+    state.receive_reliable(0, b"a");
+    assert_eq!(rx.try_recv().unwrap().1, b"a");
+    assert_eq!(rx.try_recv().unwrap().1, b"b");
+    assert!(rx.try_recv().is_err(), "duplicates are not redelivered");
+}
+
+#[test]
+fn test_far_ahead_packets_are_discarded() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut state = PeerState::new(tx);
+    state.receive_reliable(REORDER_WINDOW, b"edge");
+    state.receive_reliable(REORDER_WINDOW + 1, b"beyond");
+    state.receive_reliable(1 << 31, b"far");
+    assert_eq!(
+        state.buffered.keys().copied().collect::<Vec<_>>(),
+        vec![REORDER_WINDOW]
+    );
+
+    let ack = state.ack_datagram();
+    let bits = u32::from_le_bytes([ack[5], ack[6], ack[7], ack[8]]);
+    assert_eq!(
+        bits,
+        1 << (REORDER_WINDOW - 1),
+        "only the in-window seq is acked"
+    );
+
+    for seq in 0..REORDER_WINDOW {
+        state.receive_reliable(seq, b"x");
+    }
+    let delivered: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+    assert_eq!(delivered.len() as u32, REORDER_WINDOW + 1);
+    assert_eq!(delivered.last().unwrap().1, b"edge");
+    assert!(state.buffered.is_empty());
+}
+
+#[tokio::test]
+async fn test_oversized_payload_rejected() {
+    let (mut client, _server) = udp_pair(UdpConfig::default()).await;
+    let big = vec![0u8; MAX_UDP_PAYLOAD + 1];
+    assert!(matches!(
+        client.send(Channel::Unreliable, &big).await,
+        Err(TransportError::PayloadTooLarge { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_packet_loss_does_not_stall_reliable_channel() {
+    // Both ends drop 20% of everything they send: data and ACKs alike.
+    let (mut sender, mut receiver) = udp_pair(UdpConfig {
+        resend_interval: Duration::from_millis(20),
+        simulated_loss: 0.2,
+        ..UdpConfig::default()
+    })
+    .await;
+
+    for i in 0..50u8 {
+        sender.send(Channel::Unreliable, &[i]).await.unwrap();
+        sender.send(Channel::Reliable, &[i]).await.unwrap();
+    }
+
+    let mut reliable = Vec::new();
+    let mut unreliable = 0;
+    let deadline = tokio::time::sleep(Duration::from_secs(10));
+    tokio::pin!(deadline);
+    while reliable.len() < 50 {
+        tokio::select! {
+            item = receiver.recv() => match item.unwrap() {
+                (Channel::Reliable, bytes) => reliable.push(bytes[0]),
+                (Channel::Unreliable, _) => unreliable += 1,
+            },
+            _ = &mut deadline => panic!("reliable channel stalled at {}", reliable.len()),
+        }
+    }
+
+    let expected: Vec<u8> = (0..50).collect();
+    assert_eq!(reliable, expected, "reliable payloads arrive in order");
+    assert!(unreliable <= 50);
+}
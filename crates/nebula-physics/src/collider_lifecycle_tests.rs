@@ -20,7 +20,7 @@ fn test_registry() -> VoxelTypeRegistry {
         solid: true,
         transparency: Transparency::Opaque,
         material_index: 1,
-        light_emission: 0,
+        light_emission: [0; 3],
     })
     .unwrap();
     reg
@@ -0,0 +1,124 @@
+//! Collision impulse events reported by the physics step.
+//!
+//! Rapier reports contact forces through an [`EventHandler`] that is only
+//! borrowed immutably during the step, so [`ImpulseCollector`] buffers them
+//! behind a mutex. [`PhysicsWorld::step`](crate::PhysicsWorld::step) drains the
+//! buffer into [`PhysicsWorld::collision_impulses`](crate::PhysicsWorld::collision_impulses)
+//! for gameplay systems such as camera shake or gamepad rumble.
+//!
+//! Only colliders with [`ActiveEvents::CONTACT_FORCE_EVENTS`] whose contact
+//! force exceeds their `contact_force_event_threshold` produce events.
+
+use std::sync::Mutex;
+
+use rapier3d::prelude::*;
+
+/// A contact between two colliders during one physics step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CollisionImpulse {
+    /// First collider of the contact pair.
+    pub collider1: ColliderHandle,
+    /// Second collider of the contact pair.
+    pub collider2: ColliderHandle,
+    /// Impulse transferred during the step, in N·s (total force × dt).
+    pub impulse: f32,
+}
+
+impl crate::PhysicsWorld {
+    /// Impulses of the last step's contacts that involve `collider`.
+    pub fn impulses_on(&self, collider: ColliderHandle) -> impl Iterator<Item = f32> + '_ {
+        self.collision_impulses
+            .iter()
+            .filter(move |event| event.collider1 == collider || event.collider2 == collider)
+            .map(|event| event.impulse)
+    }
+}
+
+/// Event handler that records contact force events as impulses.
+#[derive(Default)]
+pub(crate) struct ImpulseCollector {
+    events: Mutex<Vec<CollisionImpulse>>,
+}
+
+impl ImpulseCollector {
+    /// Take all impulses recorded since the last drain.
+    pub(crate) fn drain(&self) -> Vec<CollisionImpulse> {
+        match self.events.lock() {
+            Ok(mut events) => std::mem::take(&mut *events),
+            Err(poisoned) => std::mem::take(&mut *poisoned.into_inner()),
+        }
+    }
+}
+
+impl EventHandler for ImpulseCollector {
+    fn handle_collision_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _event: CollisionEvent,
+        _contact_pair: Option<&ContactPair>,
+    ) {
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        dt: Real,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        contact_pair: &ContactPair,
+        total_force_magnitude: Real,
+    ) {
+        let event = CollisionImpulse {
+            collider1: contact_pair.collider1,
+            collider2: contact_pair.collider2,
+            impulse: total_force_magnitude * dt,
+        };
+        match self.events.lock() {
+            Ok(mut events) => events.push(event),
+            Err(poisoned) => poisoned.into_inner().push(event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PhysicsWorld;
+    use rapier3d::prelude::*;
+
+    #[test]
+    fn test_falling_body_reports_impact_impulse() {
+        let mut world = PhysicsWorld::new();
+        let ground = ColliderBuilder::cuboid(10.0, 0.5, 10.0).build();
+        world.collider_set.insert(ground);
+
+        let body = RigidBodyBuilder::dynamic()
+            .translation(Vector::new(0.0, 2.0, 0.0))
+            .build();
+        let handle = world.rigid_body_set.insert(body);
+        let ball = ColliderBuilder::ball(0.5)
+            .active_events(ActiveEvents::CONTACT_FORCE_EVENTS)
+            .build();
+        let ball = world
+            .collider_set
+            .insert_with_parent(ball, handle, &mut world.rigid_body_set);
+
+        let mut peak: f32 = 0.0;
+        let mut ball_peak: f32 = 0.0;
+        for _ in 0..120 {
+            world.step();
+            for event in &world.collision_impulses {
+                peak = peak.max(event.impulse);
+            }
+            ball_peak = world.impulses_on(ball).fold(ball_peak, f32::max);
+        }
+        assert!(peak > 0.0, "landing should report an impulse");
+        assert_eq!(ball_peak, peak, "the only contact involves the ball");
+    }
+
+    #[test]
+    fn test_impulses_cleared_each_step() {
+        let mut world = PhysicsWorld::new();
+        world.step();
+        assert!(world.collision_impulses.is_empty());
+    }
+}
@@ -2,10 +2,13 @@
 //!
 //! Supports inverse-square falloff, constant near-surface gravity,
 //! influence radius cutoff, and smooth blending between multiple sources.
+//! Registered planets pull through [`PlanetGravityField`]: each entity is
+//! attracted by the planet whose sphere of influence it is in.
 
 use bevy_ecs::prelude::*;
 use glam::{DVec3, Vec3};
-use nebula_math::WorldPosition;
+use nebula_cubesphere::{PlanetDef, PlanetId, PlanetRegistry};
+use nebula_math::{UNITS_PER_METER, WorldPosition};
 
 use rapier3d::prelude::nalgebra;
 
@@ -36,6 +39,66 @@ pub struct GravitySource {
     pub atmosphere_height: f64,
 }
 
+/// Newtonian gravitational constant in m³·kg⁻¹·s⁻².
+const GRAVITATIONAL_CONSTANT: f64 = 6.674_30e-11;
+
+impl GravitySource {
+    /// Gravity source for a registered planet with the given surface
+    /// gravity, reaching out to the planet's sphere of influence.
+    ///
+    /// [`compute_gravity`] compares the radii against raw [`WorldPosition`]
+    /// offsets, so they are taken from the planet as-is (mm). The mass is
+    /// the one that produces `surface_gravity` at the planet's radius.
+    pub fn for_planet(planet: &PlanetDef, surface_gravity: f32) -> Self {
+        let radius_m = planet.radius as f64 / UNITS_PER_METER as f64;
+        Self {
+            mass: f64::from(surface_gravity) * radius_m * radius_m / GRAVITATIONAL_CONSTANT,
+            surface_gravity,
+            surface_radius: planet.radius as f64,
+            influence_radius: planet.influence_radius as f64,
+            constant_near_surface: false,
+            atmosphere_height: 0.0,
+        }
+    }
+}
+
+/// Registered planets as a gravity resource for [`gravity_update_system`].
+#[derive(Resource)]
+pub struct PlanetGravityField {
+    /// The planets that pull on entities.
+    pub registry: PlanetRegistry,
+    /// Surface gravity in m/s², indexed by [`PlanetId`]. Planets without an
+    /// entry use [`Self::DEFAULT_SURFACE_GRAVITY`].
+    pub surface_gravity: Vec<f32>,
+}
+
+impl PlanetGravityField {
+    /// Earth surface gravity in m/s², used for planets without an entry.
+    pub const DEFAULT_SURFACE_GRAVITY: f32 = 9.81;
+
+    /// The gravity source of the planet whose sphere of influence holds
+    /// `pos`, as picked by [`PlanetRegistry::dominant_influence`], or `None`
+    /// in open space.
+    ///
+    /// Only the dominant planet pulls, so crossing from one sphere of
+    /// influence into another hands gravity over instead of summing distant
+    /// pulls.
+    pub fn dominant_source(&self, pos: &WorldPosition) -> Option<(WorldPosition, GravitySource)> {
+        let id = self.registry.dominant_influence(pos)?;
+        let planet = self.registry.get_by_index(id.0)?;
+        let source = GravitySource::for_planet(planet, self.surface_gravity_of(id));
+        Some((planet.center, source))
+    }
+
+    /// Surface gravity of `planet` in m/s².
+    pub fn surface_gravity_of(&self, planet: PlanetId) -> f32 {
+        self.surface_gravity
+            .get(planet.0)
+            .copied()
+            .unwrap_or(Self::DEFAULT_SURFACE_GRAVITY)
+    }
+}
+
 /// Cached per-entity gravity direction and magnitude, updated each fixed tick.
 ///
 /// Other systems (character controller, dynamic body forces) read this to know
@@ -122,16 +185,29 @@ pub fn compute_gravity(
 /// System that computes and caches gravity for each physics entity.
 ///
 /// Runs early in `FixedUpdate` (ForceApplication set) so that downstream systems
-/// can read the cached `LocalGravity` component.
+/// can read the cached `LocalGravity` component. With a [`PlanetGravityField`]
+/// resource, each entity is also pulled by the planet whose sphere of
+/// influence it is in, re-resolved every tick.
 pub fn gravity_update_system(
     sources: Query<(&crate::IslandWorldPos, &GravitySource)>,
+    planets: Option<Res<PlanetGravityField>>,
     mut entities: Query<(&crate::IslandWorldPos, &mut LocalGravity), With<RigidBodyHandle>>,
 ) {
     let source_list: Vec<(WorldPosition, &GravitySource)> =
         sources.iter().map(|(pos, src)| (pos.0, src)).collect();
 
     for (entity_pos, mut gravity) in entities.iter_mut() {
-        let result = compute_gravity(&entity_pos.0, &source_list);
+        let planet = planets
+            .as_ref()
+            .and_then(|field| field.dominant_source(&entity_pos.0));
+        let result = match &planet {
+            Some((center, source)) => {
+                let mut with_planet = source_list.clone();
+                with_planet.push((*center, source));
+                compute_gravity(&entity_pos.0, &with_planet)
+            }
+            None => compute_gravity(&entity_pos.0, &source_list),
+        };
         gravity.direction = result.direction;
         gravity.magnitude = result.magnitude;
     }
@@ -313,4 +389,66 @@ mod tests {
             result.magnitude
         );
     }
+
+    /// Terra at the origin and a small moon far out along +X, each with its
+    /// default sphere of influence.
+    fn two_planet_field() -> PlanetGravityField {
+        let mut registry = PlanetRegistry::new();
+        registry
+            .register(PlanetDef::earth_like("Terra", WorldPosition::default(), 1))
+            .unwrap();
+        registry
+            .register(PlanetDef::moon_like(
+                "Luna",
+                WorldPosition::new(384_400_000_000, 0, 0),
+                2,
+            ))
+            .unwrap();
+        PlanetGravityField {
+            registry,
+            surface_gravity: vec![9.81, 1.62],
+        }
+    }
+
+    #[test]
+    fn test_dominant_planet_source_follows_sphere_of_influence() {
+        let field = two_planet_field();
+
+        let terra_surface = WorldPosition::new(0, 6_371_000_000, 0);
+        let (center, source) = field.dominant_source(&terra_surface).unwrap();
+        assert_eq!(center, WorldPosition::default());
+        let result = compute_gravity(&terra_surface, &[(center, &source)]);
+        assert!((result.magnitude - 9.81).abs() < 1e-3);
+        assert!((result.direction.y + 1.0).abs() < 1e-6);
+
+        let luna_surface = WorldPosition::new(384_400_000_000 - 1_737_400_000, 0, 0);
+        let (center, source) = field.dominant_source(&luna_surface).unwrap();
+        let result = compute_gravity(&luna_surface, &[(center, &source)]);
+        assert!((result.magnitude - 1.62).abs() < 1e-3);
+        assert!((result.direction.x - 1.0).abs() < 1e-6);
+
+        let deep_space = WorldPosition::new(0, 0, 10_000_000_000_000);
+        assert!(field.dominant_source(&deep_space).is_none());
+    }
+
+    #[test]
+    fn test_gravity_system_pulls_toward_dominant_planet() {
+        let mut world = World::new();
+        world.insert_resource(two_planet_field());
+        let body = world
+            .spawn((
+                crate::IslandWorldPos(WorldPosition::new(6_371_000_000, 0, 0)),
+                LocalGravity::default(),
+                RigidBodyHandle(rapier3d::prelude::RigidBodyHandle::invalid()),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(gravity_update_system);
+        schedule.run(&mut world);
+
+        let gravity = world.get::<LocalGravity>(body).unwrap();
+        assert!((gravity.magnitude - 9.81).abs() < 1e-3);
+        assert!((gravity.direction.x + 1.0).abs() < 1e-6);
+    }
 }
@@ -0,0 +1,350 @@
+//! Multiple physics islands: one per cluster of players.
+//!
+//! A single [`PhysicsIsland`] follows one player. When several players are
+//! far apart each needs its own simulated neighbourhood, so the
+//! [`PhysicsIslandManager`] clusters players and splits or merges islands as
+//! they move. Merging and splitting use different distances so a pair of
+//! players hovering near one threshold doesn't churn islands every tick.
+
+use std::collections::HashMap;
+
+use bevy_ecs::prelude::*;
+use nebula_math::WorldPosition;
+
+use crate::physics_island::{IslandId, PhysicsIsland};
+
+/// An island topology change produced by [`PhysicsIslandManager::update`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IslandEvent {
+    /// The second island was absorbed into the first.
+    Merged(IslandId, IslandId),
+    /// A new island (second id) split off the first, centered at the given
+    /// position.
+    Split(IslandId, IslandId, WorldPosition),
+}
+
+/// Players currently assigned to one island during an update.
+struct Cluster {
+    id: IslandId,
+    /// Island whose settings a newly created island inherits.
+    parent: Option<IslandId>,
+    players: Vec<(Entity, WorldPosition)>,
+}
+
+/// Owns every physics island and keeps them in step with player clusters.
+#[derive(Resource)]
+pub struct PhysicsIslandManager {
+    /// Active islands, oldest first.
+    pub islands: Vec<PhysicsIsland>,
+    /// Islands merge when every player in one is within this distance of
+    /// every player in the other, in millimeters.
+    pub merge_distance_mm: i128,
+    /// An island splits when its players form groups separated by more
+    /// than this distance, in millimeters. Should exceed
+    /// `merge_distance_mm`.
+    pub split_distance_mm: i128,
+    members: HashMap<Entity, IslandId>,
+    next_id: u32,
+}
+
+impl Default for PhysicsIslandManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PhysicsIslandManager {
+    /// Creates a manager with no islands, merging within 512 m and
+    /// splitting beyond 1024 m.
+    pub fn new() -> Self {
+        Self {
+            islands: Vec::new(),
+            merge_distance_mm: 512_000,
+            split_distance_mm: 1_024_000,
+            members: HashMap::new(),
+            next_id: 1,
+        }
+    }
+
+    /// The island a player was assigned to by the last update.
+    pub fn island_of(&self, player: Entity) -> Option<&PhysicsIsland> {
+        let id = self.members.get(&player)?;
+        self.islands.iter().find(|i| i.id == *id)
+    }
+
+    /// Reassigns `players` to islands, splitting and merging as needed.
+    ///
+    /// Players not seen before get an island of their own (which may merge
+    /// immediately); islands left without players are dropped. Island
+    /// centers move to the centroid of their players.
+    pub fn update(&mut self, players: &[(Entity, WorldPosition)]) -> Vec<IslandEvent> {
+        let mut clusters = self.assign(players);
+        let mut events = Vec::new();
+        self.split(&mut clusters, &mut events);
+        self.merge(&mut clusters, &mut events);
+        clusters.retain(|c| !c.players.is_empty());
+        self.rebuild(clusters, &events);
+        events
+    }
+
+    /// Groups players by their previous island; newcomers get new clusters.
+    fn assign(&mut self, players: &[(Entity, WorldPosition)]) -> Vec<Cluster> {
+        let mut clusters: Vec<Cluster> = self
+            .islands
+            .iter()
+            .map(|island| Cluster {
+                id: island.id,
+                parent: None,
+                players: Vec::new(),
+            })
+            .collect();
+        for &(entity, pos) in players {
+            let known = self
+                .members
+                .get(&entity)
+                .and_then(|id| clusters.iter_mut().find(|c| c.id == *id));
+            match known {
+                Some(cluster) => cluster.players.push((entity, pos)),
+                None => {
+                    let id = self.allocate_id();
+                    clusters.push(Cluster {
+                        id,
+                        parent: None,
+                        players: vec![(entity, pos)],
+                    });
+                }
+            }
+        }
+        clusters
+    }
+
+    /// Splits clusters whose players form groups further apart than the
+    /// split distance. The largest group keeps the original island.
+    fn split(&mut self, clusters: &mut Vec<Cluster>, events: &mut Vec<IslandEvent>) {
+        let limit = self.split_distance_mm as f64;
+        let mut spawned = Vec::new();
+        for cluster in clusters.iter_mut() {
+            let mut groups = connected_groups(&cluster.players, limit);
+            if groups.len() < 2 {
+                continue;
+            }
+            // Stable sort keeps the group containing the first player
+            // ahead of equally sized ones.
+            groups.sort_by_key(|g| std::cmp::Reverse(g.len()));
+            let mut groups = groups.into_iter();
+            cluster.players = groups.next().unwrap_or_default();
+            for group in groups {
+                let id = self.allocate_id();
+                events.push(IslandEvent::Split(cluster.id, id, centroid(&group)));
+                spawned.push(Cluster {
+                    id,
+                    parent: Some(cluster.id),
+                    players: group,
+                });
+            }
+        }
+        clusters.extend(spawned);
+    }
+
+    /// Merges pairs of clusters whose players are all within the merge
+    /// distance of each other. The older island survives.
+    fn merge(&self, clusters: &mut Vec<Cluster>, events: &mut Vec<IslandEvent>) {
+        let limit = self.merge_distance_mm as f64;
+        'restart: loop {
+            for i in 0..clusters.len() {
+                for j in (i + 1)..clusters.len() {
+                    let (a, b) = (&clusters[i], &clusters[j]);
+                    if a.players.is_empty() || b.players.is_empty() {
+                        continue;
+                    }
+                    let close = a.players.iter().all(|(_, pa)| {
+                        b.players.iter().all(|(_, pb)| distance_mm(pa, pb) <= limit)
+                    });
+                    if close {
+                        let absorbed = clusters.remove(j);
+                        events.push(IslandEvent::Merged(clusters[i].id, absorbed.id));
+                        clusters[i].players.extend(absorbed.players);
+                        continue 'restart;
+                    }
+                }
+            }
+            break;
+        }
+    }
+
+    /// Replaces `islands` with one island per cluster, carrying over state
+    /// from the previous islands where ids survive.
+    fn rebuild(&mut self, clusters: Vec<Cluster>, events: &[IslandEvent]) {
+        let mut previous: HashMap<IslandId, PhysicsIsland> =
+            self.islands.drain(..).map(|i| (i.id, i)).collect();
+
+        // Absorbed islands hand their active entities to the survivor.
+        for event in events {
+            if let IslandEvent::Merged(survivor, absorbed) = event
+                && let Some(gone) = previous.remove(absorbed)
+                && let Some(keep) = previous.get_mut(survivor)
+            {
+                keep.active_entities.extend(gone.active_entities);
+                keep.active_chunks.extend(gone.active_chunks);
+            }
+        }
+
+        self.members.clear();
+        for cluster in clusters {
+            let mut island = previous.remove(&cluster.id).unwrap_or_else(|| {
+                let mut island = PhysicsIsland::new();
+                if let Some(parent) = cluster.parent.and_then(|p| previous.get(&p)) {
+                    island.radius = parent.radius;
+                    island.hysteresis = parent.hysteresis;
+                }
+                island.id = cluster.id;
+                island
+            });
+            island.center = centroid(&cluster.players);
+            for (entity, _) in &cluster.players {
+                self.members.insert(*entity, cluster.id);
+            }
+            self.islands.push(island);
+        }
+    }
+
+    fn allocate_id(&mut self) -> IslandId {
+        let id = IslandId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+}
+
+/// Splits players into groups where each player is within `limit` mm of
+/// at least one other player in its group (single-linkage clustering).
+fn connected_groups(
+    players: &[(Entity, WorldPosition)],
+    limit: f64,
+) -> Vec<Vec<(Entity, WorldPosition)>> {
+    let mut group_of: Vec<Option<usize>> = vec![None; players.len()];
+    let mut groups = Vec::new();
+    for start in 0..players.len() {
+        if group_of[start].is_some() {
+            continue;
+        }
+        let group = groups.len();
+        group_of[start] = Some(group);
+        let mut stack = vec![start];
+        let mut members = Vec::new();
+        while let Some(i) = stack.pop() {
+            members.push(players[i]);
+            for j in 0..players.len() {
+                if group_of[j].is_none() && distance_mm(&players[i].1, &players[j].1) <= limit {
+                    group_of[j] = Some(group);
+                    stack.push(j);
+                }
+            }
+        }
+        groups.push(members);
+    }
+    groups
+}
+
+/// Mean position of `players` (origin if empty).
+fn centroid(players: &[(Entity, WorldPosition)]) -> WorldPosition {
+    let n = players.len().max(1) as i128;
+    let (x, y, z) = players
+        .iter()
+        .fold((0, 0, 0), |(x, y, z), (_, p)| (x + p.x, y + p.y, z + p.z));
+    WorldPosition::new(x / n, y / n, z / n)
+}
+
+fn distance_mm(a: &WorldPosition, b: &WorldPosition) -> f64 {
+    PhysicsIsland::distance_meters(a, b) * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KM: i128 = 1_000_000;
+
+    fn players(world: &mut World, n: usize) -> Vec<Entity> {
+        (0..n).map(|_| world.spawn_empty().id()).collect()
+    }
+
+    #[test]
+    fn test_distant_players_get_separate_islands_and_merge_on_teleport() {
+        let mut world = World::new();
+        let p = players(&mut world, 2);
+        let mut manager = PhysicsIslandManager::new();
+
+        let a = WorldPosition::new(0, 0, 0);
+        let far = WorldPosition::new(2_000 * KM, 0, 0);
+        let events = manager.update(&[(p[0], a), (p[1], far)]);
+        assert!(events.is_empty());
+        assert_eq!(manager.islands.len(), 2);
+        let id_a = manager.island_of(p[0]).unwrap().id;
+        let id_b = manager.island_of(p[1]).unwrap().id;
+        assert_ne!(id_a, id_b);
+        assert_eq!(manager.island_of(p[1]).unwrap().center, far);
+
+        // Player B teleports 100 m from player A.
+        let near = WorldPosition::new(100_000, 0, 0);
+        let events = manager.update(&[(p[0], a), (p[1], near)]);
+        assert_eq!(events, vec![IslandEvent::Merged(id_a, id_b)]);
+        assert_eq!(manager.islands.len(), 1);
+        assert_eq!(manager.islands[0].center, WorldPosition::new(50_000, 0, 0));
+        assert_eq!(manager.island_of(p[1]).unwrap().id, id_a);
+    }
+
+    #[test]
+    fn test_diverging_players_split_island() {
+        let mut world = World::new();
+        let p = players(&mut world, 3);
+        let mut manager = PhysicsIslandManager::new();
+        let origin = WorldPosition::new(0, 0, 0);
+        let close = WorldPosition::new(10_000, 0, 0);
+        manager.update(&[(p[0], origin), (p[1], close), (p[2], close)]);
+        assert_eq!(manager.islands.len(), 1);
+        let id = manager.islands[0].id;
+
+        let far = WorldPosition::new(0, 0, 2_000 * KM);
+        let events = manager.update(&[(p[0], origin), (p[1], close), (p[2], far)]);
+        let new_id = manager.island_of(p[2]).unwrap().id;
+        assert_eq!(events, vec![IslandEvent::Split(id, new_id, far)]);
+        assert_eq!(manager.island_of(p[0]).unwrap().id, id);
+        assert_eq!(manager.island_of(p[1]).unwrap().id, id);
+    }
+
+    #[test]
+    fn test_hysteresis_between_merge_and_split_distance() {
+        let mut world = World::new();
+        let p = players(&mut world, 2);
+        let mut manager = PhysicsIslandManager::new();
+        let a = WorldPosition::new(0, 0, 0);
+        let mid = WorldPosition::new(700_000, 0, 0);
+
+        // Together, then 700 m apart: still one island.
+        manager.update(&[(p[0], a), (p[1], WorldPosition::new(1_000, 0, 0))]);
+        assert!(manager.update(&[(p[0], a), (p[1], mid)]).is_empty());
+        assert_eq!(manager.islands.len(), 1);
+
+        // Apart, then back to 700 m: still two islands.
+        manager.update(&[(p[0], a), (p[1], WorldPosition::new(5 * KM, 0, 0))]);
+        assert_eq!(manager.islands.len(), 2);
+        assert!(manager.update(&[(p[0], a), (p[1], mid)]).is_empty());
+        assert_eq!(manager.islands.len(), 2);
+    }
+
+    #[test]
+    fn test_islands_without_players_are_dropped() {
+        let mut world = World::new();
+        let p = players(&mut world, 2);
+        let mut manager = PhysicsIslandManager::new();
+        manager.update(&[
+            (p[0], WorldPosition::new(0, 0, 0)),
+            (p[1], WorldPosition::new(10 * KM, 0, 0)),
+        ]);
+        assert_eq!(manager.islands.len(), 2);
+
+        manager.update(&[(p[0], WorldPosition::new(0, 0, 0))]);
+        assert_eq!(manager.islands.len(), 1);
+        assert!(manager.island_of(p[1]).is_none());
+    }
+}
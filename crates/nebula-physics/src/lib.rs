@@ -4,7 +4,9 @@
 //! that owns all simulation state and exposes a minimal, engine-friendly API.
 
 pub mod collider_lifecycle;
+pub mod contact_events;
 pub mod gravity;
+pub mod island_manager;
 pub mod physics_bridge;
 pub mod physics_debug;
 pub mod physics_island;
@@ -23,13 +25,15 @@ pub use collider_lifecycle::{
     PhysicsShape, deduplicate_voxel_changes, despawn_physics_bodies, on_chunk_loaded,
     on_chunk_unloaded, on_voxel_changed, spawn_physics_bodies,
 };
+pub use contact_events::CollisionImpulse;
 pub use gravity::{
-    GravityResult, GravitySource, LocalGravity, apply_gravity_forces_system, compute_gravity,
-    gravity_update_system,
+    GravityResult, GravitySource, LocalGravity, PlanetGravityField, apply_gravity_forces_system,
+    compute_gravity, gravity_update_system,
 };
+pub use island_manager::{IslandEvent, PhysicsIslandManager};
 pub use physics_bridge::{
-    PhysicsOrigin, bridge_read_from_rapier, bridge_write_to_rapier, local_to_world,
-    recenter_physics_origin, world_to_local,
+    OriginPolicy, OriginRebased, PhysicsOrigin, bridge_read_from_rapier, bridge_write_to_rapier,
+    local_to_world, recenter_physics_origin, world_to_local,
 };
 pub use physics_debug::{
     COLORS as PHYSICS_DEBUG_COLORS, DebugLine, DebugLineBuffer, DebugRay, DebugRaycastBuffer,
@@ -38,8 +42,8 @@ pub use physics_debug::{
     physics_debug_toggle_system,
 };
 pub use physics_island::{
-    ChunkCoord, FrozenPhysicsState, IslandPlayer, IslandWorldPos, PhysicsEligible, PhysicsIsland,
-    RigidBodyHandle, physics_island_update_system,
+    ChunkCoord, FrozenPhysicsState, IslandId, IslandPlayer, IslandWorldPos, PhysicsEligible,
+    PhysicsIsland, RigidBodyHandle, physics_island_update_system,
 };
 pub use physics_region::{
     CurrentPhysicsRegion, GravityConfig, PhysicsRegion, PhysicsRegionType, RegionBounds,
@@ -47,8 +51,8 @@ pub use physics_region::{
     create_planet_surface_region, region_detection_system,
 };
 pub use player_physics::{
-    JUMP_IMPULSE, PlayerPhysics, WALK_SPEED, ground_raycast, player_movement_step,
-    spawn_player_physics,
+    GROUND_CACHE_TOLERANCE, JUMP_IMPULSE, PlayerPhysics, WALK_SPEED, ground_raycast,
+    player_movement_step, spawn_player_physics, teleport_player,
 };
 pub use voxel_collision::{
     ChunkColliderMap, chunk_to_voxel_collider, create_chunk_collider, remove_chunk_colliders,
@@ -59,7 +63,7 @@ pub use voxel_raycast::{
 };
 pub use zero_gravity::{
     SpaceObject, ThrustInput, ZERO_G_THRESHOLD, apply_thrust_system,
-    configure_space_damping_system, get_angular_velocity, is_zero_gravity,
+    configure_space_damping_system, get_angular_velocity, is_zero_gravity, ship_hull_collider,
 };
 
 use bevy_ecs::prelude::*;
@@ -93,6 +97,13 @@ pub struct PhysicsWorld {
     pub multibody_joint_set: MultibodyJointSet,
     /// Continuous collision detection solver.
     pub ccd_solver: CCDSolver,
+    /// Contact impulses reported during the most recent [`step`](Self::step).
+    pub collision_impulses: Vec<CollisionImpulse>,
+    /// Buffers contact force events while the pipeline is stepping.
+    impulse_collector: contact_events::ImpulseCollector,
+    /// Bumped whenever chunk colliders are rebuilt or removed, so cached
+    /// ground checks know the terrain under them may have changed.
+    pub(crate) terrain_revision: u64,
 }
 
 impl PhysicsWorld {
@@ -116,10 +127,16 @@ impl PhysicsWorld {
             impulse_joint_set: ImpulseJointSet::new(),
             multibody_joint_set: MultibodyJointSet::new(),
             ccd_solver: CCDSolver::new(),
+            collision_impulses: Vec::new(),
+            impulse_collector: contact_events::ImpulseCollector::default(),
+            terrain_revision: 0,
         }
     }
 
     /// Advances the simulation by one fixed timestep.
+    ///
+    /// Replaces [`collision_impulses`](Self::collision_impulses) with the
+    /// contacts reported during this step.
     pub fn step(&mut self) {
         self.physics_pipeline.step(
             self.gravity,
@@ -133,8 +150,9 @@ impl PhysicsWorld {
             &mut self.multibody_joint_set,
             &mut self.ccd_solver,
             &(),
-            &(),
+            &self.impulse_collector,
         );
+        self.collision_impulses = self.impulse_collector.drain();
     }
 
     /// Sets the world gravity vector.
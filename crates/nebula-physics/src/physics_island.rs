@@ -12,10 +12,17 @@ use nebula_math::WorldPosition;
 /// Chunk coordinate (face, x, y, z). Simple tuple type for island tracking.
 pub type ChunkCoord = (i64, i64, i64, u8);
 
+/// Identifier of a physics island. Standalone islands use `IslandId(0)`;
+/// [`crate::PhysicsIslandManager`] assigns unique ids.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IslandId(pub u32);
+
 /// Central physics island resource. Only entities within `radius` of `center`
 /// get active Rapier rigid bodies. The island moves with the player.
 #[derive(Resource)]
 pub struct PhysicsIsland {
+    /// Identifier of this island.
+    pub id: IslandId,
     /// Center of the island in world coordinates (i128 millimeters).
     pub center: WorldPosition,
     /// Radius in meters. Objects within this distance gain physics bodies.
@@ -38,6 +45,7 @@ impl PhysicsIsland {
     /// Creates a new physics island with default radius (512m) and hysteresis (16m).
     pub fn new() -> Self {
         Self {
+            id: IslandId::default(),
             center: WorldPosition::default(),
             radius: 512.0,
             hysteresis: 16.0,
@@ -5,6 +5,7 @@
 //! [`KinematicCharacterController`].
 
 use rapier3d::control::{CharacterAutostep, CharacterLength, KinematicCharacterController};
+use rapier3d::parry::query::ShapeCastOptions;
 use rapier3d::prelude::*;
 
 use crate::PhysicsWorld;
@@ -25,6 +26,28 @@ pub struct PlayerPhysics {
     pub grounded: bool,
     /// Current vertical velocity in m/s (positive = up).
     pub vertical_velocity: f32,
+    /// Body translation and result of the last [`ground_raycast`] query.
+    /// Reused while the body stays within [`GROUND_CACHE_TOLERANCE`] and
+    /// no chunk collider has changed.
+    pub last_ground_check: Option<(Vector, bool)>,
+    /// Surface normal under the player from the last ground probe, or
+    /// `None` if it missed. Used for slope-based movement adjustment.
+    pub ground_normal: Option<Vector>,
+    /// Terrain revision of the physics world when the cached ground check
+    /// was made.
+    ground_check_revision: u64,
+}
+
+impl PlayerPhysics {
+    /// Forces the next [`ground_raycast`] to query Rapier.
+    ///
+    /// [`teleport_player`] calls this, and [`ground_raycast`] calls it
+    /// itself once [`update_chunk_colliders`](crate::update_chunk_colliders)
+    /// or [`remove_chunk_colliders`](crate::remove_chunk_colliders) has
+    /// changed the terrain. Call it after moving the body any other way.
+    pub fn invalidate_ground_cache(&mut self) {
+        self.last_ground_check = None;
+    }
 }
 
 /// Default walk speed in m/s.
@@ -35,6 +58,13 @@ pub const JUMP_IMPULSE: f32 = 7.0;
 const CAPSULE_HALF_HEIGHT: f32 = 0.6;
 /// Capsule radius (meters).
 const CAPSULE_RADIUS: f32 = 0.3;
+/// Movement (meters) below which a cached ground check is reused.
+pub const GROUND_CACHE_TOLERANCE: f32 = 0.005;
+/// How far below the capsule (meters) [`ground_raycast`] looks for ground.
+const GROUND_PROBE_DISTANCE: f32 = 0.1;
+/// Height (meters) above the body the ground sweep starts from, so a
+/// capsule resting slightly inside the floor still registers a hit.
+const GROUND_PROBE_SKIN: f32 = 0.05;
 
 /// Spawns a player physics entity: kinematic body + capsule collider + controller.
 ///
@@ -74,9 +104,26 @@ pub fn spawn_player_physics(physics: &mut PhysicsWorld, local_pos: glam::Vec3) -
         controller,
         grounded: false,
         vertical_velocity: 0.0,
+        last_ground_check: None,
+        ground_normal: None,
+        ground_check_revision: physics.terrain_revision,
     }
 }
 
+/// Moves the player body to `local_pos` instantly, clearing its vertical
+/// velocity and ground cache.
+pub fn teleport_player(
+    physics: &mut PhysicsWorld,
+    player: &mut PlayerPhysics,
+    local_pos: glam::Vec3,
+) {
+    let body = &mut physics.rigid_body_set[player.body_handle];
+    body.set_translation(Vector::new(local_pos.x, local_pos.y, local_pos.z), true);
+    player.vertical_velocity = 0.0;
+    player.grounded = false;
+    player.invalidate_ground_cache();
+}
+
 /// Applies one tick of player movement: horizontal walk + vertical gravity/jump.
 ///
 /// `horizontal` is the desired XZ movement direction (normalized or zero),
@@ -84,7 +131,10 @@ pub fn spawn_player_physics(physics: &mut PhysicsWorld, local_pos: glam::Vec3) -
 /// `dt` is the fixed timestep in seconds.
 ///
 /// Internally calls `KinematicCharacterController::move_shape` to resolve
-/// collisions, then updates the body position and grounded state.
+/// collisions, then updates the body position and grounded state. The
+/// grounded state is the controller's own ground contact after the
+/// corrected move; [`ground_raycast`] then refreshes
+/// [`PlayerPhysics::ground_normal`] from the corrected position.
 pub fn player_movement_step(
     player: &mut PlayerPhysics,
     physics: &mut PhysicsWorld,
@@ -134,17 +184,30 @@ pub fn player_movement_step(
     body.set_next_kinematic_translation(new_translation);
 
     player.grounded = corrected.grounded;
+    ground_raycast(physics, player);
 }
 
-/// Performs a downward raycast to detect ground beneath the player.
+/// Sweeps the player's capsule downward to detect ground beneath it.
 ///
-/// Returns `true` if a surface is found within `max_distance` below the body origin.
-/// This supplements the controller's built-in `grounded` flag.
-pub fn ground_raycast(physics: &PhysicsWorld, player: &PlayerPhysics) -> bool {
-    let body = &physics.rigid_body_set[player.body_handle];
-    let origin = body.translation();
-    let ray = Ray::new(origin, Vector::new(0.0, -1.0, 0.0));
-    let max_distance = CAPSULE_HALF_HEIGHT + CAPSULE_RADIUS + 0.1;
+/// Returns `true` if a surface is found within a short distance below the
+/// capsule. The sweep starts from the body's next kinematic position, so
+/// it sees the translation [`player_movement_step`] just applied, and the
+/// full capsule catches ledges and slopes a single center ray would miss.
+/// The result is cached and reused while the body moves less than
+/// [`GROUND_CACHE_TOLERANCE`] and the chunk colliders stay unchanged. Each
+/// query also refreshes [`PlayerPhysics::ground_normal`].
+pub fn ground_raycast(physics: &PhysicsWorld, player: &mut PlayerPhysics) -> bool {
+    if player.ground_check_revision != physics.terrain_revision {
+        player.invalidate_ground_cache();
+        player.ground_check_revision = physics.terrain_revision;
+    }
+    let pose = *physics.rigid_body_set[player.body_handle].next_position();
+    let origin = pose.translation;
+    if let Some((last, grounded)) = player.last_ground_check
+        && (origin - last).length() <= GROUND_CACHE_TOLERANCE
+    {
+        return grounded;
+    }
 
     let filter = QueryFilter::new().exclude_rigid_body(player.body_handle);
     let query_pipeline = physics.broad_phase.as_query_pipeline(
@@ -154,270 +217,25 @@ pub fn ground_raycast(physics: &PhysicsWorld, player: &PlayerPhysics) -> bool {
         filter,
     );
 
-    query_pipeline.cast_ray(&ray, max_distance, true).is_some()
+    let character_shape = Capsule::new_y(CAPSULE_HALF_HEIGHT, CAPSULE_RADIUS);
+    let start = Pose::from_translation(Vector::new(0.0, GROUND_PROBE_SKIN, 0.0)) * pose;
+    let hit = query_pipeline.cast_shape(
+        &start,
+        Vector::new(0.0, -1.0, 0.0),
+        &character_shape,
+        ShapeCastOptions {
+            max_time_of_impact: GROUND_PROBE_SKIN + GROUND_PROBE_DISTANCE,
+            target_distance: 0.0,
+            stop_at_penetration: false,
+            compute_impact_geometry_on_penetration: true,
+        },
+    );
+    let grounded = hit.is_some();
+    player.ground_normal = hit.map(|(_, hit)| hit.normal1);
+    player.last_ground_check = Some((origin, grounded));
+    grounded
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::PhysicsWorld;
-
-    /// Helper: create a flat floor collider at y=0 (a thin cuboid spanning 100x1x100).
-    fn add_floor(physics: &mut PhysicsWorld) -> rapier3d::geometry::ColliderHandle {
-        let floor_body = RigidBodyBuilder::fixed()
-            .translation(Vector::new(0.0, -0.5, 0.0))
-            .build();
-        let floor_handle = physics.rigid_body_set.insert(floor_body);
-        let floor_collider = ColliderBuilder::cuboid(50.0, 0.5, 50.0).build();
-        physics.collider_set.insert_with_parent(
-            floor_collider,
-            floor_handle,
-            &mut physics.rigid_body_set,
-        )
-    }
-
-    /// Helper: step physics + player movement for N ticks.
-    fn step_n(
-        player: &mut PlayerPhysics,
-        physics: &mut PhysicsWorld,
-        n: usize,
-        horizontal: glam::Vec3,
-        jump: bool,
-    ) {
-        let dt = 1.0 / 60.0;
-        for i in 0..n {
-            // Only jump on first tick
-            let j = jump && i == 0;
-            physics.step();
-            player_movement_step(player, physics, horizontal, j, dt);
-            physics.step();
-        }
-    }
-
-    #[test]
-    fn test_player_stands_on_solid_ground() {
-        let mut physics = PhysicsWorld::new();
-        add_floor(&mut physics);
-
-        // Spawn player 2m above floor
-        let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 2.0, 0.0));
-
-        step_n(&mut player, &mut physics, 120, glam::Vec3::ZERO, false);
-
-        let y = physics.rigid_body_set[player.body_handle].translation().y;
-        // Capsule center should be at ~0.9m (half-height above floor)
-        assert!(
-            (y - 0.9).abs() < 0.3,
-            "Player should stabilize near y=0.9, got y={y}"
-        );
-        assert!(player.grounded, "Player should be grounded on flat floor");
-    }
-
-    #[test]
-    fn test_player_cannot_walk_through_walls() {
-        let mut physics = PhysicsWorld::new();
-        add_floor(&mut physics);
-
-        // Wall at x=5, spanning y=0..3, z=-50..50
-        let wall_body = RigidBodyBuilder::fixed()
-            .translation(Vector::new(5.0, 1.5, 0.0))
-            .build();
-        let wall_handle = physics.rigid_body_set.insert(wall_body);
-        let wall_collider = ColliderBuilder::cuboid(0.5, 1.5, 50.0).build();
-        physics.collider_set.insert_with_parent(
-            wall_collider,
-            wall_handle,
-            &mut physics.rigid_body_set,
-        );
-
-        // Spawn player at x=2, on the floor
-        let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(2.0, 0.9, 0.0));
-
-        // Let player settle, then walk toward wall (+X)
-        step_n(&mut player, &mut physics, 30, glam::Vec3::ZERO, false);
-        step_n(
-            &mut player,
-            &mut physics,
-            60,
-            glam::Vec3::new(1.0, 0.0, 0.0),
-            false,
-        );
-
-        let x = physics.rigid_body_set[player.body_handle].translation().x;
-        // Wall face is at x=4.5, player capsule radius is 0.3, skin is 0.01
-        assert!(
-            x < 4.5,
-            "Player should not cross wall plane at x=4.5, got x={x}"
-        );
-    }
-
-    #[test]
-    fn test_jump_applies_upward_velocity() {
-        let mut physics = PhysicsWorld::new();
-        add_floor(&mut physics);
-
-        let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 0.9, 0.0));
-
-        // Settle on ground
-        step_n(&mut player, &mut physics, 60, glam::Vec3::ZERO, false);
-        assert!(player.grounded, "Player should be grounded before jump");
-
-        let rest_y = physics.rigid_body_set[player.body_handle].translation().y;
-
-        // Jump
-        step_n(&mut player, &mut physics, 1, glam::Vec3::ZERO, true);
-        assert!(
-            player.vertical_velocity > 0.0,
-            "Vertical velocity should be positive after jump"
-        );
-
-        // Step forward to reach peak
-        step_n(&mut player, &mut physics, 30, glam::Vec3::ZERO, false);
-        let peak_y = physics.rigid_body_set[player.body_handle].translation().y;
-        assert!(
-            peak_y > rest_y + 0.1,
-            "Player should rise above rest position: peak_y={peak_y}, rest_y={rest_y}"
-        );
-
-        // Step more to return to ground
-        step_n(&mut player, &mut physics, 90, glam::Vec3::ZERO, false);
-        let final_y = physics.rigid_body_set[player.body_handle].translation().y;
-        assert!(
-            (final_y - rest_y).abs() < 0.5,
-            "Player should return near rest: final_y={final_y}, rest_y={rest_y}"
-        );
-    }
-
-    #[test]
-    fn test_ground_detection_on_flat_surface() {
-        let mut physics = PhysicsWorld::new();
-        let floor_collider_handle = add_floor(&mut physics);
-
-        let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 0.9, 0.0));
-        step_n(&mut player, &mut physics, 60, glam::Vec3::ZERO, false);
-        assert!(player.grounded, "Player should be grounded on floor");
-
-        // Remove the floor
-        let parent = physics
-            .collider_set
-            .get(floor_collider_handle)
-            .and_then(|c| c.parent());
-        if let Some(parent_handle) = parent {
-            physics.rigid_body_set.remove(
-                parent_handle,
-                &mut physics.island_manager,
-                &mut physics.collider_set,
-                &mut physics.impulse_joint_set,
-                &mut physics.multibody_joint_set,
-                true,
-            );
-        }
-
-        step_n(&mut player, &mut physics, 10, glam::Vec3::ZERO, false);
-        assert!(
-            !player.grounded,
-            "Player should not be grounded after floor removal"
-        );
-    }
-
-    #[test]
-    fn test_stair_stepping_climbs_small_steps() {
-        let mut physics = PhysicsWorld::new();
-
-        // Lower floor: y=-0.5..0 for x < 5
-        let lower_body = RigidBodyBuilder::fixed()
-            .translation(Vector::new(0.0, -0.5, 0.0))
-            .build();
-        let lower_handle = physics.rigid_body_set.insert(lower_body);
-        physics.collider_set.insert_with_parent(
-            ColliderBuilder::cuboid(50.0, 0.5, 50.0).build(),
-            lower_handle,
-            &mut physics.rigid_body_set,
-        );
-
-        // Step: a block at y=0..0.5 for x >= 4.5
-        let step_body = RigidBodyBuilder::fixed()
-            .translation(Vector::new(7.0, 0.25, 0.0))
-            .build();
-        let step_handle = physics.rigid_body_set.insert(step_body);
-        physics.collider_set.insert_with_parent(
-            ColliderBuilder::cuboid(3.0, 0.25, 50.0).build(),
-            step_handle,
-            &mut physics.rigid_body_set,
-        );
-
-        // Spawn player on lower floor, walk toward step
-        let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 0.9, 0.0));
-        step_n(&mut player, &mut physics, 30, glam::Vec3::ZERO, false);
-
-        let start_y = physics.rigid_body_set[player.body_handle].translation().y;
-
-        // Walk toward and over the step
-        step_n(
-            &mut player,
-            &mut physics,
-            120,
-            glam::Vec3::new(1.0, 0.0, 0.0),
-            false,
-        );
-
-        let end_y = physics.rigid_body_set[player.body_handle].translation().y;
-        assert!(
-            end_y > start_y + 0.3,
-            "Player should climb the step: start_y={start_y}, end_y={end_y}"
-        );
-    }
-
-    #[test]
-    fn test_slope_slide_above_max_angle() {
-        let mut physics = PhysicsWorld::new();
-
-        // Create a steep slope (60°) using a rotated cuboid.
-        // We approximate with a wedge: a thin tilted surface.
-        // Simpler approach: place a floor, then a steep ramp as a rotated body.
-        let angle_rad = 60.0_f32.to_radians();
-        let ramp_body = RigidBodyBuilder::fixed()
-            .translation(Vector::new(5.0, 2.0, 0.0))
-            .rotation(Vector::new(0.0, 0.0, angle_rad))
-            .build();
-        let ramp_handle = physics.rigid_body_set.insert(ramp_body);
-        physics.collider_set.insert_with_parent(
-            ColliderBuilder::cuboid(5.0, 0.1, 50.0).build(),
-            ramp_handle,
-            &mut physics.rigid_body_set,
-        );
-
-        // Flat floor below so player doesn't fall forever
-        let floor_body = RigidBodyBuilder::fixed()
-            .translation(Vector::new(0.0, -0.5, 0.0))
-            .build();
-        let floor_handle = physics.rigid_body_set.insert(floor_body);
-        physics.collider_set.insert_with_parent(
-            ColliderBuilder::cuboid(50.0, 0.5, 50.0).build(),
-            floor_handle,
-            &mut physics.rigid_body_set,
-        );
-
-        // Spawn player at base of ramp
-        let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(2.0, 0.9, 0.0));
-        step_n(&mut player, &mut physics, 30, glam::Vec3::ZERO, false);
-
-        let start_y = physics.rigid_body_set[player.body_handle].translation().y;
-
-        // Try to walk up the steep slope
-        step_n(
-            &mut player,
-            &mut physics,
-            60,
-            glam::Vec3::new(1.0, 0.0, 0.0),
-            false,
-        );
-
-        let end_y = physics.rigid_body_set[player.body_handle].translation().y;
-        // Player should NOT have climbed significantly (slope > 45° limit)
-        assert!(
-            end_y < start_y + 1.0,
-            "Player should not climb steep slope: start_y={start_y}, end_y={end_y}"
-        );
-    }
-}
+#[path = "player_physics_tests.rs"]
+mod tests;
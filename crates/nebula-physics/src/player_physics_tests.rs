@@ -0,0 +1,370 @@
+//! Unit tests for the player character controller.
+
+use super::*;
+use crate::PhysicsWorld;
+
+/// Helper: create a flat floor collider at y=0 (a thin cuboid spanning 100x1x100).
+fn add_floor(physics: &mut PhysicsWorld) -> rapier3d::geometry::ColliderHandle {
+    let floor_body = RigidBodyBuilder::fixed()
+        .translation(Vector::new(0.0, -0.5, 0.0))
+        .build();
+    let floor_handle = physics.rigid_body_set.insert(floor_body);
+    let floor_collider = ColliderBuilder::cuboid(50.0, 0.5, 50.0).build();
+    physics.collider_set.insert_with_parent(
+        floor_collider,
+        floor_handle,
+        &mut physics.rigid_body_set,
+    )
+}
+
+/// Helper: step physics + player movement for N ticks.
+fn step_n(
+    player: &mut PlayerPhysics,
+    physics: &mut PhysicsWorld,
+    n: usize,
+    horizontal: glam::Vec3,
+    jump: bool,
+) {
+    let dt = 1.0 / 60.0;
+    for i in 0..n {
+        // Only jump on first tick
+        let j = jump && i == 0;
+        physics.step();
+        player_movement_step(player, physics, horizontal, j, dt);
+        physics.step();
+    }
+}
+
+#[test]
+fn test_player_stands_on_solid_ground() {
+    let mut physics = PhysicsWorld::new();
+    add_floor(&mut physics);
+
+    // Spawn player 2m above floor
+    let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 2.0, 0.0));
+
+    step_n(&mut player, &mut physics, 120, glam::Vec3::ZERO, false);
+
+    let y = physics.rigid_body_set[player.body_handle].translation().y;
+    // Capsule center should be at ~0.9m (half-height above floor)
+    assert!(
+        (y - 0.9).abs() < 0.3,
+        "Player should stabilize near y=0.9, got y={y}"
+    );
+    assert!(player.grounded, "Player should be grounded on flat floor");
+}
+
+#[test]
+fn test_player_cannot_walk_through_walls() {
+    let mut physics = PhysicsWorld::new();
+    add_floor(&mut physics);
+
+    // Wall at x=5, spanning y=0..3, z=-50..50
+    let wall_body = RigidBodyBuilder::fixed()
+        .translation(Vector::new(5.0, 1.5, 0.0))
+        .build();
+    let wall_handle = physics.rigid_body_set.insert(wall_body);
+    let wall_collider = ColliderBuilder::cuboid(0.5, 1.5, 50.0).build();
+    physics.collider_set.insert_with_parent(
+        wall_collider,
+        wall_handle,
+        &mut physics.rigid_body_set,
+    );
+
+    // Spawn player at x=2, on the floor
+    let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(2.0, 0.9, 0.0));
+
+    // Let player settle, then walk toward wall (+X)
+    step_n(&mut player, &mut physics, 30, glam::Vec3::ZERO, false);
+    step_n(
+        &mut player,
+        &mut physics,
+        60,
+        glam::Vec3::new(1.0, 0.0, 0.0),
+        false,
+    );
+
+    let x = physics.rigid_body_set[player.body_handle].translation().x;
+    // Wall face is at x=4.5, player capsule radius is 0.3, skin is 0.01
+    assert!(
+        x < 4.5,
+        "Player should not cross wall plane at x=4.5, got x={x}"
+    );
+}
+
+#[test]
+fn test_jump_applies_upward_velocity() {
+    let mut physics = PhysicsWorld::new();
+    add_floor(&mut physics);
+
+    let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 0.9, 0.0));
+
+    // Settle on ground
+    step_n(&mut player, &mut physics, 60, glam::Vec3::ZERO, false);
+    assert!(player.grounded, "Player should be grounded before jump");
+
+    let rest_y = physics.rigid_body_set[player.body_handle].translation().y;
+
+    // Jump
+    step_n(&mut player, &mut physics, 1, glam::Vec3::ZERO, true);
+    assert!(
+        player.vertical_velocity > 0.0,
+        "Vertical velocity should be positive after jump"
+    );
+
+    // Step forward to reach peak
+    step_n(&mut player, &mut physics, 30, glam::Vec3::ZERO, false);
+    let peak_y = physics.rigid_body_set[player.body_handle].translation().y;
+    assert!(
+        peak_y > rest_y + 0.1,
+        "Player should rise above rest position: peak_y={peak_y}, rest_y={rest_y}"
+    );
+
+    // Step more to return to ground
+    step_n(&mut player, &mut physics, 90, glam::Vec3::ZERO, false);
+    let final_y = physics.rigid_body_set[player.body_handle].translation().y;
+    assert!(
+        (final_y - rest_y).abs() < 0.5,
+        "Player should return near rest: final_y={final_y}, rest_y={rest_y}"
+    );
+}
+
+#[test]
+fn test_ground_detection_on_flat_surface() {
+    let mut physics = PhysicsWorld::new();
+    let floor_collider_handle = add_floor(&mut physics);
+
+    let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 0.9, 0.0));
+    step_n(&mut player, &mut physics, 60, glam::Vec3::ZERO, false);
+    assert!(player.grounded, "Player should be grounded on floor");
+
+    // Remove the floor
+    let parent = physics
+        .collider_set
+        .get(floor_collider_handle)
+        .and_then(|c| c.parent());
+    if let Some(parent_handle) = parent {
+        physics.rigid_body_set.remove(
+            parent_handle,
+            &mut physics.island_manager,
+            &mut physics.collider_set,
+            &mut physics.impulse_joint_set,
+            &mut physics.multibody_joint_set,
+            true,
+        );
+    }
+
+    step_n(&mut player, &mut physics, 10, glam::Vec3::ZERO, false);
+    assert!(
+        !player.grounded,
+        "Player should not be grounded after floor removal"
+    );
+}
+
+#[test]
+fn test_stair_stepping_climbs_small_steps() {
+    let mut physics = PhysicsWorld::new();
+
+    // Lower floor: y=-0.5..0 for x < 5
+    let lower_body = RigidBodyBuilder::fixed()
+        .translation(Vector::new(0.0, -0.5, 0.0))
+        .build();
+    let lower_handle = physics.rigid_body_set.insert(lower_body);
+    physics.collider_set.insert_with_parent(
+        ColliderBuilder::cuboid(50.0, 0.5, 50.0).build(),
+        lower_handle,
+        &mut physics.rigid_body_set,
+    );
+
+    // Step: a block at y=0..0.5 for x >= 4.5
+    let step_body = RigidBodyBuilder::fixed()
+        .translation(Vector::new(7.0, 0.25, 0.0))
+        .build();
+    let step_handle = physics.rigid_body_set.insert(step_body);
+    physics.collider_set.insert_with_parent(
+        ColliderBuilder::cuboid(3.0, 0.25, 50.0).build(),
+        step_handle,
+        &mut physics.rigid_body_set,
+    );
+
+    // Spawn player on lower floor, walk toward step
+    let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 0.9, 0.0));
+    step_n(&mut player, &mut physics, 30, glam::Vec3::ZERO, false);
+
+    let start_y = physics.rigid_body_set[player.body_handle].translation().y;
+
+    // Walk toward and over the step
+    step_n(
+        &mut player,
+        &mut physics,
+        120,
+        glam::Vec3::new(1.0, 0.0, 0.0),
+        false,
+    );
+
+    let end_y = physics.rigid_body_set[player.body_handle].translation().y;
+    assert!(
+        end_y > start_y + 0.3,
+        "Player should climb the step: start_y={start_y}, end_y={end_y}"
+    );
+}
+
+/// Removes the floor's body directly, without bumping the terrain revision
+/// the way the chunk collider helpers do.
+fn remove_floor(physics: &mut PhysicsWorld, floor: rapier3d::geometry::ColliderHandle) {
+    let parent = physics.collider_set[floor].parent().expect("floor body");
+    physics.rigid_body_set.remove(
+        parent,
+        &mut physics.island_manager,
+        &mut physics.collider_set,
+        &mut physics.impulse_joint_set,
+        &mut physics.multibody_joint_set,
+        true,
+    );
+    physics.step();
+}
+
+#[test]
+fn test_ground_raycast_cached_while_standing_still() {
+    let mut physics = PhysicsWorld::new();
+    let floor = add_floor(&mut physics);
+    let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 0.9, 0.0));
+    step_n(&mut player, &mut physics, 60, glam::Vec3::ZERO, false);
+    assert!(ground_raycast(&physics, &mut player));
+    let normal = player.ground_normal.expect("ground normal recorded");
+    assert!(normal.y > 0.99, "flat floor normal points up: {normal:?}");
+
+    // Standing still reuses the cached hit, even though the floor is gone.
+    remove_floor(&mut physics, floor);
+    assert!(
+        ground_raycast(&physics, &mut player),
+        "standing still must hit the cache"
+    );
+    player.invalidate_ground_cache();
+    assert!(!ground_raycast(&physics, &mut player));
+}
+
+#[test]
+fn test_teleport_invalidates_ground_cache() {
+    let mut physics = PhysicsWorld::new();
+    add_floor(&mut physics);
+    let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 0.9, 0.0));
+    step_n(&mut player, &mut physics, 60, glam::Vec3::ZERO, false);
+    assert!(ground_raycast(&physics, &mut player));
+
+    teleport_player(&mut physics, &mut player, glam::Vec3::new(0.0, 20.0, 0.0));
+    assert!(player.last_ground_check.is_none());
+    assert!(
+        !ground_raycast(&physics, &mut player),
+        "mid-air after teleport"
+    );
+}
+
+#[test]
+fn test_ground_probe_catches_ledge_off_center() {
+    let mut physics = PhysicsWorld::new();
+    // A ledge whose edge sits 0.2 m off the player's center: a center ray
+    // misses it, but the capsule still rests on it.
+    let ledge = RigidBodyBuilder::fixed()
+        .translation(Vector::new(-5.2, -0.5, 0.0))
+        .build();
+    let ledge = physics.rigid_body_set.insert(ledge);
+    physics.collider_set.insert_with_parent(
+        ColliderBuilder::cuboid(5.0, 0.5, 50.0).build(),
+        ledge,
+        &mut physics.rigid_body_set,
+    );
+    let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 0.92, 0.0));
+    physics.step();
+    assert!(ground_raycast(&physics, &mut player));
+    assert!(player.ground_normal.is_some());
+}
+
+#[test]
+fn test_slope_slide_above_max_angle() {
+    let mut physics = PhysicsWorld::new();
+
+    // Create a steep slope (60°) using a rotated cuboid.
+    // We approximate with a wedge: a thin tilted surface.
+    // Simpler approach: place a floor, then a steep ramp as a rotated body.
+    let angle_rad = 60.0_f32.to_radians();
+    let ramp_body = RigidBodyBuilder::fixed()
+        .translation(Vector::new(5.0, 2.0, 0.0))
+        .rotation(Vector::new(0.0, 0.0, angle_rad))
+        .build();
+    let ramp_handle = physics.rigid_body_set.insert(ramp_body);
+    physics.collider_set.insert_with_parent(
+        ColliderBuilder::cuboid(5.0, 0.1, 50.0).build(),
+        ramp_handle,
+        &mut physics.rigid_body_set,
+    );
+
+    // Flat floor below so player doesn't fall forever
+    let floor_body = RigidBodyBuilder::fixed()
+        .translation(Vector::new(0.0, -0.5, 0.0))
+        .build();
+    let floor_handle = physics.rigid_body_set.insert(floor_body);
+    physics.collider_set.insert_with_parent(
+        ColliderBuilder::cuboid(50.0, 0.5, 50.0).build(),
+        floor_handle,
+        &mut physics.rigid_body_set,
+    );
+
+    // Spawn player at base of ramp
+    let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(2.0, 0.9, 0.0));
+    step_n(&mut player, &mut physics, 30, glam::Vec3::ZERO, false);
+
+    let start_y = physics.rigid_body_set[player.body_handle].translation().y;
+
+    // Try to walk up the steep slope
+    step_n(
+        &mut player,
+        &mut physics,
+        60,
+        glam::Vec3::new(1.0, 0.0, 0.0),
+        false,
+    );
+
+    let end_y = physics.rigid_body_set[player.body_handle].translation().y;
+    // Player should NOT have climbed significantly (slope > 45° limit)
+    assert!(
+        end_y < start_y + 1.0,
+        "Player should not climb steep slope: start_y={start_y}, end_y={end_y}"
+    );
+}
+
+#[test]
+fn test_removing_chunk_colliders_invalidates_ground_cache() {
+    let mut physics = PhysicsWorld::new();
+    let floor = add_floor(&mut physics);
+    let addr = nebula_voxel::ChunkAddress::new(0, 0, 0, 0);
+    let mut colliders = crate::ChunkColliderMap::new();
+    colliders.insert(addr, floor);
+    let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 0.9, 0.0));
+    step_n(&mut player, &mut physics, 60, glam::Vec3::ZERO, false);
+    assert!(ground_raycast(&physics, &mut player));
+    assert!(player.ground_normal.is_some());
+
+    // The chunk unloads under a stationary player.
+    crate::remove_chunk_colliders(&mut physics, &[addr], &mut colliders);
+    physics.step();
+    assert!(!ground_raycast(&physics, &mut player), "the floor is gone");
+    assert!(player.ground_normal.is_none(), "a miss clears the normal");
+}
+
+#[test]
+fn test_movement_step_uses_ground_raycast() {
+    let mut physics = PhysicsWorld::new();
+    add_floor(&mut physics);
+    let mut player = spawn_player_physics(&mut physics, glam::Vec3::new(0.0, 0.9, 0.0));
+    step_n(&mut player, &mut physics, 60, glam::Vec3::ZERO, false);
+    assert!(player.grounded);
+    assert!(player.ground_normal.is_some_and(|n| n.y > 0.99));
+
+    // Walking off the floor's edge: the controller loses contact and the
+    // probe misses, so the player is airborne and the stale normal is
+    // dropped.
+    step_n(&mut player, &mut physics, 660, glam::Vec3::X, false);
+    assert!(!player.grounded);
+    assert!(player.ground_normal.is_none());
+}
@@ -132,6 +132,9 @@ pub fn update_chunk_colliders(
         dirty_chunks.insert(event.chunk);
     }
 
+    if !dirty_chunks.is_empty() {
+        physics.terrain_revision += 1;
+    }
     for coord in dirty_chunks {
         // Remove old collider.
         if let Some(old_handle) = collider_map.remove(&coord) {
@@ -163,6 +166,7 @@ pub fn remove_chunk_colliders(
 ) {
     for addr in unloaded {
         if let Some(handle) = collider_map.remove(addr) {
+            physics.terrain_revision += 1;
             physics.collider_set.remove(
                 handle,
                 &mut physics.island_manager,
@@ -189,7 +193,7 @@ mod tests {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 1,
-            light_emission: 0,
+            light_emission: [0; 3],
         })
         .unwrap();
         reg
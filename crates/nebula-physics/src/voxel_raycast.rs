@@ -2,13 +2,16 @@
 //!
 //! Steps through an i128 voxel grid with f32 parametric values,
 //! returning the first solid voxel hit along with face normal, distance,
-//! voxel type, and hit UV coordinates.
+//! voxel type, and the exact sub-voxel hit point.
 
 use bevy_ecs::prelude::*;
 use glam::{IVec3, Vec2, Vec3};
 use nebula_math::WorldPosition;
 use nebula_voxel::VoxelTypeId;
 
+/// Millimeters per voxel edge (voxels are 1 m cubes).
+const MM_PER_VOXEL: f64 = 1_000.0;
+
 /// Trait for looking up voxel data by world position.
 ///
 /// Implementors map a [`WorldPosition`] to an optional [`VoxelData`] describing
@@ -58,6 +61,14 @@ pub struct VoxelRaycastHit {
     pub voxel_type: VoxelTypeId,
     /// Exact hit point within the voxel face (0.0..1.0 UV coordinates).
     pub hit_uv: Vec2,
+    /// Hit point in meters relative to the voxel's minimum corner
+    /// (each component in `0.0..=1.0`).
+    pub hit_position_local: Vec3,
+    /// Distance from the ray origin to the hit point, in millimeters.
+    pub distance_mm: f64,
+    /// The neighbouring voxel across the hit face, i.e. where a new block
+    /// would be placed. Equals `voxel_pos` if the ray started inside it.
+    pub adjacent_voxel_pos: WorldPosition,
 }
 
 /// Current crosshair target: the voxel the player is aiming at.
@@ -97,7 +108,6 @@ pub fn voxel_raycast(ray: &VoxelRay, world: &dyn VoxelWorldAccess) -> Option<Vox
     );
 
     let mut last_normal = IVec3::ZERO;
-    let mut t = 0.0_f32;
     let mut is_origin = true;
 
     loop {
@@ -106,17 +116,27 @@ pub fn voxel_raycast(ray: &VoxelRay, world: &dyn VoxelWorldAccess) -> Option<Vox
             && data.solid
             && !(is_origin && ray.skip_origin)
         {
+            let (t_hit, local) = intersect_voxel(ray, &voxel);
+            let normal = last_normal;
             return Some(VoxelRaycastHit {
                 voxel_pos: voxel,
-                face_normal: last_normal,
-                distance: t,
+                face_normal: normal,
+                distance: t_hit,
                 voxel_type: data.id,
-                hit_uv: compute_hit_uv(ray, t, &last_normal),
+                hit_uv: face_uv(local, &normal),
+                hit_position_local: local,
+                distance_mm: f64::from(t_hit) * MM_PER_VOXEL,
+                adjacent_voxel_pos: WorldPosition::new(
+                    voxel.x + i128::from(normal.x),
+                    voxel.y + i128::from(normal.y),
+                    voxel.z + i128::from(normal.z),
+                ),
             });
         }
         is_origin = false;
 
         // Advance along the axis with the smallest t_max.
+        let t;
         if t_max.x < t_max.y && t_max.x < t_max.z {
             t = t_max.x;
             t_max.x += t_delta.x;
@@ -160,273 +180,45 @@ fn initial_t_max(sub: f32, dir_component: f32, t_delta: f32) -> f32 {
     }
 }
 
-/// Compute UV coordinates of the hit point on the entry face.
-fn compute_hit_uv(ray: &VoxelRay, t: f32, normal: &IVec3) -> Vec2 {
-    // Hit point in local space relative to origin voxel.
-    let hit_point = ray.sub_offset + ray.direction * t;
+/// Solve the ray against the unit AABB of `voxel` with the slab method.
+///
+/// Returns the entry parameter (clamped to 0 when the ray starts inside)
+/// and the entry point relative to the voxel's minimum corner. Working
+/// relative to the origin voxel keeps the f32 math small regardless of
+/// where in the i128 world the ray is.
+fn intersect_voxel(ray: &VoxelRay, voxel: &WorldPosition) -> (f32, Vec3) {
+    let offset = Vec3::new(
+        (voxel.x - ray.origin.x) as f32,
+        (voxel.y - ray.origin.y) as f32,
+        (voxel.z - ray.origin.z) as f32,
+    );
+    let start = ray.sub_offset - offset;
+    let dir = ray.direction;
+
+    let mut t_enter = 0.0_f32;
+    for axis in 0..3 {
+        if dir[axis].abs() < f32::EPSILON {
+            continue;
+        }
+        let t0 = -start[axis] / dir[axis];
+        let t1 = (1.0 - start[axis]) / dir[axis];
+        t_enter = t_enter.max(t0.min(t1));
+    }
+    let local = (start + dir * t_enter).clamp(Vec3::ZERO, Vec3::ONE);
+    (t_enter, local)
+}
 
-    // Project onto the face plane based on the normal axis.
+/// Project a voxel-local hit point onto the face plane as UV coordinates.
+fn face_uv(local: Vec3, normal: &IVec3) -> Vec2 {
     if normal.x != 0 {
-        Vec2::new(hit_point.z.fract().abs(), hit_point.y.fract().abs())
+        Vec2::new(local.z, local.y)
     } else if normal.y != 0 {
-        Vec2::new(hit_point.x.fract().abs(), hit_point.z.fract().abs())
+        Vec2::new(local.x, local.z)
     } else {
-        Vec2::new(hit_point.x.fract().abs(), hit_point.y.fract().abs())
+        Vec2::new(local.x, local.y)
     }
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use std::collections::HashMap;
-
-    /// Simple test world backed by a hash map.
-    struct TestWorld {
-        voxels: HashMap<(i128, i128, i128), VoxelData>,
-    }
-
-    impl TestWorld {
-        fn new() -> Self {
-            Self {
-                voxels: HashMap::new(),
-            }
-        }
-
-        fn set_solid(&mut self, x: i128, y: i128, z: i128, id: u16) {
-            self.voxels.insert(
-                (x, y, z),
-                VoxelData {
-                    id: VoxelTypeId(id),
-                    solid: true,
-                },
-            );
-        }
-    }
-
-    impl VoxelWorldAccess for TestWorld {
-        fn get_voxel(&self, pos: &WorldPosition) -> Option<VoxelData> {
-            self.voxels.get(&(pos.x, pos.y, pos.z)).copied()
-        }
-    }
-
-    fn ray_along(dx: f32, dy: f32, dz: f32, max_dist: f32) -> VoxelRay {
-        let dir = Vec3::new(dx, dy, dz).normalize();
-        VoxelRay {
-            origin: WorldPosition::new(0, 0, 0),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: dir,
-            max_distance: max_dist,
-            skip_origin: false,
-        }
-    }
-
-    #[test]
-    fn test_ray_hits_solid_voxel() {
-        let mut world = TestWorld::new();
-        world.set_solid(5, 0, 0, 1);
-
-        let ray = VoxelRay {
-            origin: WorldPosition::new(0, 0, 0),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: Vec3::X,
-            max_distance: 10.0,
-            skip_origin: false,
-        };
-
-        let hit = voxel_raycast(&ray, &world).expect("should hit");
-        assert_eq!(hit.voxel_pos, WorldPosition::new(5, 0, 0));
-        assert!((hit.distance - 4.5).abs() < 0.1); // 5 - 0.5 sub_offset
-    }
-
-    #[test]
-    fn test_ray_misses_empty_space() {
-        let world = TestWorld::new();
-        let ray = ray_along(1.0, 0.0, 0.0, 100.0);
-        assert!(voxel_raycast(&ray, &world).is_none());
-    }
-
-    #[test]
-    fn test_hit_face_normal_correct() {
-        let mut world = TestWorld::new();
-        world.set_solid(5, 0, 0, 1);
-
-        // Ray from -X side
-        let ray = VoxelRay {
-            origin: WorldPosition::new(0, 0, 0),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: Vec3::X,
-            max_distance: 10.0,
-            skip_origin: false,
-        };
-        let hit = voxel_raycast(&ray, &world).unwrap();
-        assert_eq!(hit.face_normal, IVec3::new(-1, 0, 0));
-
-        // Ray from +X side
-        let mut world2 = TestWorld::new();
-        world2.set_solid(0, 0, 0, 1);
-        let ray_neg = VoxelRay {
-            origin: WorldPosition::new(5, 0, 0),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: Vec3::NEG_X,
-            max_distance: 10.0,
-            skip_origin: false,
-        };
-        let hit_neg = voxel_raycast(&ray_neg, &world2).unwrap();
-        assert_eq!(hit_neg.face_normal, IVec3::new(1, 0, 0));
-
-        // Ray from -Y side
-        let mut world3 = TestWorld::new();
-        world3.set_solid(0, 5, 0, 1);
-        let ray_y = VoxelRay {
-            origin: WorldPosition::new(0, 0, 0),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: Vec3::Y,
-            max_distance: 10.0,
-            skip_origin: false,
-        };
-        let hit_y = voxel_raycast(&ray_y, &world3).unwrap();
-        assert_eq!(hit_y.face_normal, IVec3::new(0, -1, 0));
-
-        // Ray from +Y side
-        let ray_neg_y = VoxelRay {
-            origin: WorldPosition::new(0, 10, 0),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: Vec3::NEG_Y,
-            max_distance: 15.0,
-            skip_origin: false,
-        };
-        let hit_neg_y = voxel_raycast(&ray_neg_y, &world3).unwrap();
-        assert_eq!(hit_neg_y.face_normal, IVec3::new(0, 1, 0));
-
-        // Ray from -Z side
-        let mut world4 = TestWorld::new();
-        world4.set_solid(0, 0, 5, 1);
-        let ray_z = VoxelRay {
-            origin: WorldPosition::new(0, 0, 0),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: Vec3::Z,
-            max_distance: 10.0,
-            skip_origin: false,
-        };
-        let hit_z = voxel_raycast(&ray_z, &world4).unwrap();
-        assert_eq!(hit_z.face_normal, IVec3::new(0, 0, -1));
-
-        // Ray from +Z side
-        let ray_neg_z = VoxelRay {
-            origin: WorldPosition::new(0, 0, 10),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: Vec3::NEG_Z,
-            max_distance: 15.0,
-            skip_origin: false,
-        };
-        let hit_neg_z = voxel_raycast(&ray_neg_z, &world4).unwrap();
-        assert_eq!(hit_neg_z.face_normal, IVec3::new(0, 0, 1));
-    }
-
-    #[test]
-    fn test_max_distance_limits_search() {
-        let mut world = TestWorld::new();
-        world.set_solid(20, 0, 0, 1);
-
-        let ray_short = VoxelRay {
-            origin: WorldPosition::new(0, 0, 0),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: Vec3::X,
-            max_distance: 10.0,
-            skip_origin: false,
-        };
-        assert!(voxel_raycast(&ray_short, &world).is_none());
-
-        let ray_long = VoxelRay {
-            origin: WorldPosition::new(0, 0, 0),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: Vec3::X,
-            max_distance: 25.0,
-            skip_origin: false,
-        };
-        assert!(voxel_raycast(&ray_long, &world).is_some());
-    }
-
-    #[test]
-    fn test_ray_from_inside_solid_escapes() {
-        let mut world = TestWorld::new();
-        // 3x3x3 solid cube centered at (1,1,1): positions 0..=2 on each axis.
-        for x in 0..=2_i128 {
-            for y in 0..=2_i128 {
-                for z in 0..=2_i128 {
-                    world.set_solid(x, y, z, 1);
-                }
-            }
-        }
-        // Place another solid voxel outside the cube to catch.
-        world.set_solid(5, 1, 1, 2);
-
-        let ray = VoxelRay {
-            origin: WorldPosition::new(1, 1, 1),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: Vec3::X,
-            max_distance: 20.0,
-            skip_origin: true,
-        };
-
-        let hit = voxel_raycast(&ray, &world).expect("should hit after escaping");
-        // The first solid voxel after origin (1,1,1) in +X is (2,1,1) which is
-        // still in the cube. skip_origin only skips the origin voxel itself.
-        assert_eq!(hit.voxel_pos, WorldPosition::new(2, 1, 1));
-    }
-
-    #[test]
-    fn test_diagonal_ray_crosses_voxels_correctly() {
-        let mut world = TestWorld::new();
-        world.set_solid(3, 3, 0, 1);
-
-        let dir = Vec3::new(1.0, 1.0, 0.0).normalize();
-        let ray = VoxelRay {
-            origin: WorldPosition::new(0, 0, 0),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: dir,
-            max_distance: 20.0,
-            skip_origin: false,
-        };
-
-        let hit = voxel_raycast(&ray, &world).expect("should hit diagonal voxel");
-        assert_eq!(hit.voxel_pos, WorldPosition::new(3, 3, 0));
-        // Distance should be approximately 3 * sqrt(2) - some offset for sub_offset
-        let expected = (3.0_f32 - 0.5) * 2.0_f32.sqrt();
-        assert!(
-            (hit.distance - expected).abs() < 0.5,
-            "distance {} expected ~{}",
-            hit.distance,
-            expected,
-        );
-    }
-
-    #[test]
-    fn test_ray_returns_correct_voxel_type() {
-        let mut world = TestWorld::new();
-        world.set_solid(5, 0, 0, 10); // stone
-        world.set_solid(10, 0, 0, 20); // dirt
-
-        // First ray hits stone
-        let ray1 = VoxelRay {
-            origin: WorldPosition::new(0, 0, 0),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: Vec3::X,
-            max_distance: 20.0,
-            skip_origin: false,
-        };
-        let hit1 = voxel_raycast(&ray1, &world).unwrap();
-        assert_eq!(hit1.voxel_type, VoxelTypeId(10));
-
-        // Second ray starts past stone, hits dirt
-        let ray2 = VoxelRay {
-            origin: WorldPosition::new(6, 0, 0),
-            sub_offset: Vec3::new(0.5, 0.5, 0.5),
-            direction: Vec3::X,
-            max_distance: 20.0,
-            skip_origin: false,
-        };
-        let hit2 = voxel_raycast(&ray2, &world).unwrap();
-        assert_eq!(hit2.voxel_type, VoxelTypeId(20));
-    }
-}
+#[path = "voxel_raycast_tests.rs"]
+mod tests;
@@ -0,0 +1,315 @@
+//! Unit tests for voxel DDA raycasting.
+
+use super::*;
+use std::collections::HashMap;
+
+/// Simple test world backed by a hash map.
+struct TestWorld {
+    voxels: HashMap<(i128, i128, i128), VoxelData>,
+}
+
+impl TestWorld {
+    fn new() -> Self {
+        Self {
+            voxels: HashMap::new(),
+        }
+    }
+
+    fn set_solid(&mut self, x: i128, y: i128, z: i128, id: u16) {
+        self.voxels.insert(
+            (x, y, z),
+            VoxelData {
+                id: VoxelTypeId(id),
+                solid: true,
+            },
+        );
+    }
+}
+
+impl VoxelWorldAccess for TestWorld {
+    fn get_voxel(&self, pos: &WorldPosition) -> Option<VoxelData> {
+        self.voxels.get(&(pos.x, pos.y, pos.z)).copied()
+    }
+}
+
+fn ray_along(dx: f32, dy: f32, dz: f32, max_dist: f32) -> VoxelRay {
+    let dir = Vec3::new(dx, dy, dz).normalize();
+    VoxelRay {
+        origin: WorldPosition::new(0, 0, 0),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: dir,
+        max_distance: max_dist,
+        skip_origin: false,
+    }
+}
+
+#[test]
+fn test_ray_hits_solid_voxel() {
+    let mut world = TestWorld::new();
+    world.set_solid(5, 0, 0, 1);
+
+    let ray = VoxelRay {
+        origin: WorldPosition::new(0, 0, 0),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::X,
+        max_distance: 10.0,
+        skip_origin: false,
+    };
+
+    let hit = voxel_raycast(&ray, &world).expect("should hit");
+    assert_eq!(hit.voxel_pos, WorldPosition::new(5, 0, 0));
+    assert!((hit.distance - 4.5).abs() < 0.1); // 5 - 0.5 sub_offset
+}
+
+#[test]
+fn test_ray_misses_empty_space() {
+    let world = TestWorld::new();
+    let ray = ray_along(1.0, 0.0, 0.0, 100.0);
+    assert!(voxel_raycast(&ray, &world).is_none());
+}
+
+#[test]
+fn test_hit_face_normal_correct() {
+    let mut world = TestWorld::new();
+    world.set_solid(5, 0, 0, 1);
+
+    // Ray from -X side
+    let ray = VoxelRay {
+        origin: WorldPosition::new(0, 0, 0),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::X,
+        max_distance: 10.0,
+        skip_origin: false,
+    };
+    let hit = voxel_raycast(&ray, &world).unwrap();
+    assert_eq!(hit.face_normal, IVec3::new(-1, 0, 0));
+
+    // Ray from +X side
+    let mut world2 = TestWorld::new();
+    world2.set_solid(0, 0, 0, 1);
+    let ray_neg = VoxelRay {
+        origin: WorldPosition::new(5, 0, 0),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::NEG_X,
+        max_distance: 10.0,
+        skip_origin: false,
+    };
+    let hit_neg = voxel_raycast(&ray_neg, &world2).unwrap();
+    assert_eq!(hit_neg.face_normal, IVec3::new(1, 0, 0));
+
+    // Ray from -Y side
+    let mut world3 = TestWorld::new();
+    world3.set_solid(0, 5, 0, 1);
+    let ray_y = VoxelRay {
+        origin: WorldPosition::new(0, 0, 0),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::Y,
+        max_distance: 10.0,
+        skip_origin: false,
+    };
+    let hit_y = voxel_raycast(&ray_y, &world3).unwrap();
+    assert_eq!(hit_y.face_normal, IVec3::new(0, -1, 0));
+
+    // Ray from +Y side
+    let ray_neg_y = VoxelRay {
+        origin: WorldPosition::new(0, 10, 0),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::NEG_Y,
+        max_distance: 15.0,
+        skip_origin: false,
+    };
+    let hit_neg_y = voxel_raycast(&ray_neg_y, &world3).unwrap();
+    assert_eq!(hit_neg_y.face_normal, IVec3::new(0, 1, 0));
+
+    // Ray from -Z side
+    let mut world4 = TestWorld::new();
+    world4.set_solid(0, 0, 5, 1);
+    let ray_z = VoxelRay {
+        origin: WorldPosition::new(0, 0, 0),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::Z,
+        max_distance: 10.0,
+        skip_origin: false,
+    };
+    let hit_z = voxel_raycast(&ray_z, &world4).unwrap();
+    assert_eq!(hit_z.face_normal, IVec3::new(0, 0, -1));
+
+    // Ray from +Z side
+    let ray_neg_z = VoxelRay {
+        origin: WorldPosition::new(0, 0, 10),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::NEG_Z,
+        max_distance: 15.0,
+        skip_origin: false,
+    };
+    let hit_neg_z = voxel_raycast(&ray_neg_z, &world4).unwrap();
+    assert_eq!(hit_neg_z.face_normal, IVec3::new(0, 0, 1));
+}
+
+#[test]
+fn test_max_distance_limits_search() {
+    let mut world = TestWorld::new();
+    world.set_solid(20, 0, 0, 1);
+
+    let ray_short = VoxelRay {
+        origin: WorldPosition::new(0, 0, 0),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::X,
+        max_distance: 10.0,
+        skip_origin: false,
+    };
+    assert!(voxel_raycast(&ray_short, &world).is_none());
+
+    let ray_long = VoxelRay {
+        origin: WorldPosition::new(0, 0, 0),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::X,
+        max_distance: 25.0,
+        skip_origin: false,
+    };
+    assert!(voxel_raycast(&ray_long, &world).is_some());
+}
+
+#[test]
+fn test_ray_from_inside_solid_escapes() {
+    let mut world = TestWorld::new();
+    // 3x3x3 solid cube centered at (1,1,1): positions 0..=2 on each axis.
+    for x in 0..=2_i128 {
+        for y in 0..=2_i128 {
+            for z in 0..=2_i128 {
+                world.set_solid(x, y, z, 1);
+            }
+        }
+    }
+    // Place another solid voxel outside the cube to catch.
+    world.set_solid(5, 1, 1, 2);
+
+    let ray = VoxelRay {
+        origin: WorldPosition::new(1, 1, 1),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::X,
+        max_distance: 20.0,
+        skip_origin: true,
+    };
+
+    let hit = voxel_raycast(&ray, &world).expect("should hit after escaping");
+    // The first solid voxel after origin (1,1,1) in +X is (2,1,1) which is
+    // still in the cube. skip_origin only skips the origin voxel itself.
+    assert_eq!(hit.voxel_pos, WorldPosition::new(2, 1, 1));
+}
+
+#[test]
+fn test_diagonal_ray_crosses_voxels_correctly() {
+    let mut world = TestWorld::new();
+    world.set_solid(3, 3, 0, 1);
+
+    let dir = Vec3::new(1.0, 1.0, 0.0).normalize();
+    let ray = VoxelRay {
+        origin: WorldPosition::new(0, 0, 0),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: dir,
+        max_distance: 20.0,
+        skip_origin: false,
+    };
+
+    let hit = voxel_raycast(&ray, &world).expect("should hit diagonal voxel");
+    assert_eq!(hit.voxel_pos, WorldPosition::new(3, 3, 0));
+    // Distance should be approximately 3 * sqrt(2) - some offset for sub_offset
+    let expected = (3.0_f32 - 0.5) * 2.0_f32.sqrt();
+    assert!(
+        (hit.distance - expected).abs() < 0.5,
+        "distance {} expected ~{}",
+        hit.distance,
+        expected,
+    );
+}
+
+#[test]
+fn test_ray_returns_correct_voxel_type() {
+    let mut world = TestWorld::new();
+    world.set_solid(5, 0, 0, 10); // stone
+    world.set_solid(10, 0, 0, 20); // dirt
+
+    // First ray hits stone
+    let ray1 = VoxelRay {
+        origin: WorldPosition::new(0, 0, 0),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::X,
+        max_distance: 20.0,
+        skip_origin: false,
+    };
+    let hit1 = voxel_raycast(&ray1, &world).unwrap();
+    assert_eq!(hit1.voxel_type, VoxelTypeId(10));
+
+    // Second ray starts past stone, hits dirt
+    let ray2 = VoxelRay {
+        origin: WorldPosition::new(6, 0, 0),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::X,
+        max_distance: 20.0,
+        skip_origin: false,
+    };
+    let hit2 = voxel_raycast(&ray2, &world).unwrap();
+    assert_eq!(hit2.voxel_type, VoxelTypeId(20));
+}
+
+#[test]
+fn test_top_face_center_hit_position_and_adjacent_voxel() {
+    let mut world = TestWorld::new();
+    world.set_solid(3, -2, 7, 1);
+
+    // Straight down onto the +Y face center from four voxels above.
+    let ray = VoxelRay {
+        origin: WorldPosition::new(3, 2, 7),
+        sub_offset: Vec3::new(0.5, 0.5, 0.5),
+        direction: Vec3::NEG_Y,
+        max_distance: 10.0,
+        skip_origin: false,
+    };
+    let hit = voxel_raycast(&ray, &world).expect("should hit");
+    assert_eq!(hit.face_normal, IVec3::Y);
+    assert!(
+        hit.hit_position_local
+            .abs_diff_eq(Vec3::new(0.5, 1.0, 0.5), 1e-5)
+    );
+    assert!(hit.hit_uv.abs_diff_eq(Vec2::new(0.5, 0.5), 1e-5));
+    assert!((hit.distance_mm - 3_500.0).abs() < 1e-3);
+    assert_eq!(hit.adjacent_voxel_pos.y, hit.voxel_pos.y + 1);
+    assert_eq!(hit.adjacent_voxel_pos.x, hit.voxel_pos.x);
+    assert_eq!(hit.adjacent_voxel_pos.z, hit.voxel_pos.z);
+}
+
+#[test]
+fn test_diagonal_hit_position_lies_on_entry_face() {
+    let mut world = TestWorld::new();
+    world.set_solid(4, 0, 0, 1);
+
+    let ray = VoxelRay {
+        origin: WorldPosition::new(0, 0, 0),
+        sub_offset: Vec3::new(0.5, 0.25, 0.5),
+        direction: Vec3::new(1.0, 0.1, 0.0).normalize(),
+        max_distance: 10.0,
+        skip_origin: false,
+    };
+    let hit = voxel_raycast(&ray, &world).expect("should hit");
+    assert_eq!(hit.face_normal, IVec3::NEG_X);
+    // Entry on the -X face at x = 4: 3.5 voxels along X, 0.35 up in Y.
+    assert!(
+        hit.hit_position_local
+            .abs_diff_eq(Vec3::new(0.0, 0.6, 0.5), 1e-4)
+    );
+    assert_eq!(hit.adjacent_voxel_pos, WorldPosition::new(3, 0, 0));
+    let expected_mm = f64::from(3.5_f32.hypot(0.35)) * 1_000.0;
+    assert!((hit.distance_mm - expected_mm).abs() < 1.0);
+}
+
+#[test]
+fn test_origin_hit_reports_sub_offset() {
+    let mut world = TestWorld::new();
+    world.set_solid(0, 0, 0, 1);
+    let ray = ray_along(1.0, 0.0, 0.0, 5.0);
+    let hit = voxel_raycast(&ray, &world).expect("origin is solid");
+    assert_eq!(hit.distance_mm, 0.0);
+    assert_eq!(hit.hit_position_local, Vec3::new(0.5, 0.5, 0.5));
+    assert_eq!(hit.adjacent_voxel_pos, hit.voxel_pos);
+}
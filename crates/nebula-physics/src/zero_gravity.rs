@@ -71,6 +71,16 @@ impl Default for ThrustInput {
     }
 }
 
+/// Spherical collider for a ship hull of `radius` meters.
+///
+/// Reports contact forces so every hit shows up in
+/// [`PhysicsWorld::collision_impulses`] for rumble and camera shake.
+pub fn ship_hull_collider(radius: f32) -> Collider {
+    ColliderBuilder::ball(radius)
+        .active_events(ActiveEvents::CONTACT_FORCE_EVENTS)
+        .build()
+}
+
 /// System that configures damping on space objects based on their gravity environment.
 ///
 /// In zero-g with `newtonian = true`, linear damping is zero and angular damping
@@ -17,7 +17,8 @@ struct AtmosphereParams {
     inv_view_proj: mat4x4<f32>,
     near_clip: f32,
     far_clip: f32,
-    _padding1: vec2<f32>,
+    view_altitude: f32,
+    _padding1: f32,
 };
 
 @group(0) @binding(0) var<uniform> atmo: AtmosphereParams;
@@ -76,8 +77,13 @@ fn fs_atmosphere(in: VertexOutput) -> @location(0) vec4<f32> {
     let world_far3 = world_far.xyz / world_far.w;
     let ray_dir = normalize(world_far3 - atmo.camera_position);
 
+    // Rebuild the ray origin from the precise view altitude: at planet scale
+    // the f32 camera position is too coarse near the ground.
+    let up = normalize(atmo.camera_position - atmo.planet_center);
+    let origin = atmo.planet_center + up * (atmo.planet_radius + max(atmo.view_altitude, 0.0));
+
     let atmo_hit = ray_sphere_intersect(
-        atmo.camera_position, ray_dir, atmo.planet_center, atmo.atmosphere_radius
+        origin, ray_dir, atmo.planet_center, atmo.atmosphere_radius
     );
 
     if atmo_hit.x > atmo_hit.y || atmo_hit.y < 0.0 {
@@ -105,7 +111,7 @@ fn fs_atmosphere(in: VertexOutput) -> @location(0) vec4<f32> {
 
     // Check planet surface intersection
     let planet_hit = ray_sphere_intersect(
-        atmo.camera_position, ray_dir, atmo.planet_center, atmo.planet_radius
+        origin, ray_dir, atmo.planet_center, atmo.planet_radius
     );
     if planet_hit.x > 0.0 {
         t_end = min(t_end, planet_hit.x);
@@ -123,7 +129,7 @@ fn fs_atmosphere(in: VertexOutput) -> @location(0) vec4<f32> {
 
     for (var i = 0; i < NUM_SAMPLES; i++) {
         let t = t_start + (f32(i) + 0.5) * step_size;
-        let sample_pos = atmo.camera_position + ray_dir * t;
+        let sample_pos = origin + ray_dir * t;
         let height = length(sample_pos - atmo.planet_center) - atmo.planet_radius;
 
         let density_r = exp(-height / atmo.rayleigh_scale_height) * step_size;
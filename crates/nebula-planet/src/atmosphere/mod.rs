@@ -9,5 +9,5 @@ mod scatter;
 
 pub use renderer::{ATMOSPHERE_SHADER_SOURCE, AtmosphereRenderer};
 pub use scatter::{
-    AtmosphereParams, AtmosphereUniform, compute_single_scatter, ray_sphere_intersect_f32,
+    AtmosphereParams, AtmosphereUniform, SkyColor, compute_single_scatter, ray_sphere_intersect_f32,
 };
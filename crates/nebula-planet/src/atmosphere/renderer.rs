@@ -163,6 +163,7 @@ impl AtmosphereRenderer {
         planet_center: Vec3,
         sun_direction: Vec3,
         camera_position: Vec3,
+        view_altitude: f32,
         inv_view_proj: glam::Mat4,
         near_clip: f32,
         far_clip: f32,
@@ -172,6 +173,7 @@ impl AtmosphereRenderer {
             planet_center,
             sun_direction,
             camera_position,
+            view_altitude,
             inv_view_proj,
             near_clip,
             far_clip,
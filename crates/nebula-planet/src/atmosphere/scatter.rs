@@ -4,6 +4,19 @@ use bytemuck::{Pod, Zeroable};
 use glam::Vec3;
 use std::f32::consts::PI;
 
+/// Brightest channel of the clear-day zenith sky at sea level.
+const ZENITH_SKY_BRIGHTNESS: f32 = 0.9;
+
+/// Background sky color seen looking straight up from some altitude.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SkyColor {
+    /// Linear RGB sky color with the sun high.
+    pub rgb: [f32; 3],
+    /// Fraction of the sea-level sky remaining: 1.0 on the surface, 0.0 at
+    /// the top of the atmosphere and above.
+    pub visibility: f32,
+}
+
 /// Physical parameters defining a planet's atmosphere.
 #[derive(Clone, Debug)]
 pub struct AtmosphereParams {
@@ -39,6 +52,38 @@ impl AtmosphereParams {
             sun_intensity: 22.0,
         }
     }
+
+    /// Height of the atmosphere's top above the surface, in meters.
+    pub fn atmosphere_altitude(&self) -> f32 {
+        self.atmosphere_radius - self.planet_radius
+    }
+
+    /// Zenith sky color at `altitude_m` above the surface, for clear colors
+    /// and other background fills.
+    ///
+    /// Sea level shows the Rayleigh hue at full strength. Above it the sky
+    /// dims with the fraction of the air column still overhead, which falls
+    /// off exponentially with the Rayleigh scale height and is rescaled to
+    /// reach exactly zero at [`atmosphere_altitude`](Self::atmosphere_altitude):
+    /// the sky darkens quickly in the first scale heights, then eases into
+    /// black.
+    pub fn scattering_at_altitude(&self, altitude_m: f64) -> SkyColor {
+        let top = f64::from(self.atmosphere_altitude());
+        let visibility = if top <= 0.0 {
+            0.0
+        } else {
+            let h = altitude_m.clamp(0.0, top);
+            let scale = f64::from(self.rayleigh_scale_height.max(f32::EPSILON));
+            let column = |h: f64| (-h / scale).exp();
+            ((column(h) - column(top)) / (1.0 - column(top))) as f32
+        };
+        let rc = Vec3::from(self.rayleigh_coefficients);
+        let hue = rc / rc.max_element().max(f32::EPSILON);
+        SkyColor {
+            rgb: (hue * ZENITH_SKY_BRIGHTNESS * visibility).to_array(),
+            visibility,
+        }
+    }
 }
 
 /// GPU-side atmosphere uniform buffer. Matches the WGSL struct layout.
@@ -82,17 +127,22 @@ pub struct AtmosphereUniform {
     pub near_clip: f32,
     /// Far clip plane distance. (offset 164)
     pub far_clip: f32,
-    /// Padding. (offset 168)
-    pub _padding1: [f32; 2],
+    /// Camera altitude above the surface in meters, computed in double
+    /// precision so the sky is correct near the ground. (offset 168)
+    pub view_altitude: f32,
+    /// Padding. (offset 172)
+    pub _padding1: f32,
 }
 
 impl AtmosphereUniform {
     /// Create a uniform from parameters and per-frame state.
+    #[allow(clippy::too_many_arguments)]
     pub fn from_params(
         params: &AtmosphereParams,
         planet_center: Vec3,
         sun_direction: Vec3,
         camera_position: Vec3,
+        view_altitude: f32,
         inv_view_proj: glam::Mat4,
         near_clip: f32,
         far_clip: f32,
@@ -115,7 +165,8 @@ impl AtmosphereUniform {
             inv_view_proj: inv_view_proj.to_cols_array_2d(),
             near_clip,
             far_clip,
-            _padding1: [0.0; 2],
+            view_altitude,
+            _padding1: 0.0,
         }
     }
 }
@@ -353,6 +404,45 @@ mod tests {
         assert_eq!(std::mem::size_of::<AtmosphereUniform>() % 16, 0);
     }
 
+    #[test]
+    fn test_uniform_view_altitude_offset() {
+        assert_eq!(std::mem::size_of::<AtmosphereUniform>(), 176);
+        assert_eq!(std::mem::offset_of!(AtmosphereUniform, view_altitude), 168);
+    }
+
+    #[test]
+    fn test_sky_fades_from_blue_to_black() {
+        let mut params = AtmosphereParams::earth_like(6_371_000.0);
+        params.atmosphere_radius = params.planet_radius + 100_000.0;
+        let top = f64::from(params.atmosphere_altitude());
+
+        let ground = params.scattering_at_altitude(0.0);
+        assert_eq!(ground.visibility, 1.0);
+        let [r, g, b] = ground.rgb;
+        assert!(b > g && g > r, "sea-level sky is blue: {:?}", ground.rgb);
+
+        let space = params.scattering_at_altitude(top);
+        assert_eq!(space.rgb, [0.0; 3]);
+        assert_eq!(space.visibility, 0.0);
+        assert_eq!(params.scattering_at_altitude(top * 4.0), space);
+        assert_eq!(params.scattering_at_altitude(-50.0), ground);
+
+        let mut previous = ground;
+        for step in 1..=100 {
+            let sky = params.scattering_at_altitude(top * f64::from(step) / 100.0);
+            assert!(sky.visibility < previous.visibility, "step {step}");
+            for c in 0..3 {
+                assert!(sky.rgb[c] <= previous.rgb[c], "step {step} channel {c}");
+            }
+            previous = sky;
+        }
+
+        // Not a linear ramp: most of the sky is gone within a few scale heights.
+        let scale_height = f64::from(params.rayleigh_scale_height);
+        assert!(params.scattering_at_altitude(top * 0.5).visibility < 0.05);
+        assert!(params.scattering_at_altitude(scale_height).visibility < 0.5);
+    }
+
     #[test]
     fn test_ray_sphere_miss() {
         let (t_near, t_far) =
@@ -4,10 +4,22 @@
 //!   against the i128 [`Frustum128`] — skip all chunks if the planet is off-screen.
 //! - **Level 2 (fine):** Test individual chunk bounding volumes against a
 //!   camera-relative f32 [`LocalFrustum`] for per-chunk culling.
+//!
+//! For planets with many chunks, [`QuadtreeCuller`] replaces the per-chunk
+//! pass: it walks a face quadtree testing each node's cached bounding sphere
+//! (see [`NodeBoundsCache`]) in f64, skips whole subtrees that are outside,
+//! and only converts surviving leaves to f32 AABBs for the fine test.
+
+use std::collections::HashMap;
 
-use glam::{Mat4, Vec3, Vec4};
+use glam::{DVec3, Mat4, Vec3, Vec4};
 use nebula_coords::{Frustum128, Intersection};
+use nebula_cubesphere::{
+    BoundingSphere, ChunkAABB, ChunkAddress, CubeFace, ProjectionMethod, QuadNode, WorldAABB,
+    WorldBoundingSphere,
+};
 use nebula_math::{Aabb128, WorldPosition};
+use nebula_render::{Aabb, FrustumCuller};
 
 /// A planet's bounding volume for coarse frustum culling.
 #[derive(Debug, Clone)]
@@ -126,162 +138,243 @@ pub struct CullResult {
     pub chunks_visible: u32,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use nebula_coords::{Frustum128, Intersection};
-    use nebula_math::WorldPosition;
-
-    fn earth_like_planet() -> PlanetBounds {
-        PlanetBounds {
-            center: WorldPosition::new(0, 0, 0),
-            radius: 6_371_000_000, // 6371 km in mm
+/// A world-space bounding sphere relative to `camera`, in f64 millimeters.
+///
+/// The subtraction happens in i128, so the result stays finite and exact to
+/// f64 precision however far the sphere is from the world origin.
+pub fn camera_relative_sphere(
+    sphere: &WorldBoundingSphere,
+    camera: &WorldPosition,
+) -> (DVec3, f64) {
+    let center = DVec3::new(
+        (sphere.center_x - camera.x) as f64,
+        (sphere.center_y - camera.y) as f64,
+        (sphere.center_z - camera.z) as f64,
+    );
+    (center, sphere.radius as f64)
+}
+
+/// A world-space AABB relative to `camera`, in f32 millimeters, matching
+/// [`WorldPosition::to_local_f32`].
+pub fn camera_relative_aabb(aabb: &WorldAABB, camera: &WorldPosition) -> Aabb {
+    Aabb::new(
+        Vec3::new(
+            (aabb.min_x - camera.x) as f32,
+            (aabb.min_y - camera.y) as f32,
+            (aabb.min_z - camera.z) as f32,
+        ),
+        Vec3::new(
+            (aabb.max_x - camera.x) as f32,
+            (aabb.max_y - camera.y) as f32,
+            (aabb.max_z - camera.z) as f32,
+        ),
+    )
+}
+
+/// Counters from one [`QuadtreeCuller::cull`] pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuadtreeCullStats {
+    /// Quadtree nodes whose bounding sphere was tested.
+    pub nodes_visited: u32,
+    /// Nodes rejected by the sphere test, along with their whole subtree.
+    pub subtrees_culled: u32,
+    /// Leaves that passed the sphere test but failed the f32 AABB test.
+    pub leaves_culled: u32,
+}
+
+/// A node's world-space bounding sphere and the cull pass that last used it.
+#[derive(Debug, Clone, Copy)]
+struct CachedSphere {
+    sphere: WorldBoundingSphere,
+    pass: u64,
+}
+
+/// Bounding volumes of a planet's quadtree nodes, kept across frames.
+///
+/// Each node's [`WorldBoundingSphere`] is computed the first time the node is
+/// tested and reused by every later cull, keyed by its [`ChunkAddress`]. A
+/// [`QuadtreeCuller::cull`] pass drops the spheres of nodes on that face it
+/// did not visit, so the cache tracks the live quadtree instead of growing
+/// with every node ever split.
+#[derive(Debug, Clone)]
+pub struct NodeBoundsCache {
+    planet_center: WorldPosition,
+    planet_radius: f64,
+    min_height: f64,
+    max_height: f64,
+    projection: ProjectionMethod,
+    spheres: HashMap<ChunkAddress, CachedSphere>,
+    pass: u64,
+}
+
+impl NodeBoundsCache {
+    /// Bounds of the planet centered at `planet_center` with radius
+    /// `planet_radius` (mm), whose terrain lies between `min_height` and
+    /// `max_height` (mm, relative to the radius).
+    pub fn new(
+        planet_center: WorldPosition,
+        planet_radius: f64,
+        min_height: f64,
+        max_height: f64,
+    ) -> Self {
+        Self {
+            planet_center,
+            planet_radius,
+            min_height,
+            max_height,
+            projection: ProjectionMethod::default(),
+            spheres: HashMap::new(),
+            pass: 0,
         }
     }
 
-    /// Build a Frustum128 looking from `position` in `forward` direction.
-    fn build_test_frustum_128(
-        position: WorldPosition,
-        forward: nebula_coords::Vec3I64,
-    ) -> Frustum128 {
-        use nebula_coords::Vec3I64;
-
-        // Derive right/up from forward
-        let (right, up) = if forward.x.abs() > forward.z.abs() {
-            (
-                Vec3I64::new(-forward.y, forward.x, 0),
-                Vec3I64::new(0, 0, 1_000_000),
-            )
-        } else {
-            (Vec3I64::new(1_000_000, 0, 0), Vec3I64::new(0, 1_000_000, 0))
-        };
-
-        Frustum128::from_camera(
-            &position,
-            &forward,
-            &right,
-            &up,
-            1_000,               // near: 1mm
-            100_000_000_000_000, // far: 100 billion km
-            (1, 1),              // 90° FOV
-        )
+    /// Place node bounds with the planet's cube-to-sphere `projection`
+    /// instead of the default mapping.
+    pub fn with_projection(mut self, projection: ProjectionMethod) -> Self {
+        self.projection = projection;
+        self.spheres.clear();
+        self
     }
 
-    #[test]
-    fn test_planet_behind_camera_culled_entirely() {
-        let frustum = build_test_frustum_128(
-            WorldPosition::new(0, 0, 20_000_000_000_000),
-            nebula_coords::Vec3I64::new(0, 0, 1_000_000), // looking +Z, away from origin
-        );
-        let planet = earth_like_planet();
-        let result = planet.test_frustum(&frustum);
-        assert_eq!(
-            result,
-            Intersection::Outside,
-            "Planet at origin should be culled when camera faces away from it"
-        );
+    /// Number of cached node spheres.
+    pub fn len(&self) -> usize {
+        self.spheres.len()
     }
 
-    #[test]
-    fn test_planet_in_view_passes_coarse_cull() {
-        let frustum = build_test_frustum_128(
-            WorldPosition::new(0, 0, -20_000_000_000_000),
-            nebula_coords::Vec3I64::new(0, 0, 1_000_000), // looking +Z, toward planet
-        );
-        let planet = earth_like_planet();
-        let result = planet.test_frustum(&frustum);
-        assert_ne!(
-            result,
-            Intersection::Outside,
-            "Planet should be visible when camera looks directly at it"
-        );
+    /// Whether no node sphere is cached.
+    pub fn is_empty(&self) -> bool {
+        self.spheres.is_empty()
     }
 
-    #[test]
-    fn test_offscreen_chunks_not_rendered() {
-        let frustum_local = LocalFrustum::from_view_proj(
-            &(Mat4::perspective_rh(1.0, 1.0, 0.1, 10_000.0)
-                * Mat4::look_at_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y)),
+    /// The world-space bounding sphere of the node at `addr`, computed on
+    /// first use.
+    pub fn sphere(&mut self, addr: &ChunkAddress) -> WorldBoundingSphere {
+        let pass = self.pass;
+        if let Some(cached) = self.spheres.get_mut(addr) {
+            cached.pass = pass;
+            return cached.sphere;
+        }
+        let local = BoundingSphere::from_chunk(
+            addr,
+            self.planet_radius,
+            self.min_height,
+            self.max_height,
+            self.projection,
         );
+        let c = &self.planet_center;
+        let sphere = WorldBoundingSphere::from_local(&local, c.x, c.y, c.z);
+        self.spheres.insert(*addr, CachedSphere { sphere, pass });
+        sphere
+    }
 
-        // Chunk far to the right, outside the FOV.
-        let offscreen_center = Vec3::new(50_000.0, 0.0, -100.0);
-        let half_extents = Vec3::splat(16.0);
-        let result = frustum_local.test_aabb(offscreen_center, half_extents);
-        assert_eq!(
-            result,
-            Intersection::Outside,
-            "Chunk far to the right should be culled"
+    /// The world-space AABB of the chunk at `addr`. Only surviving leaves
+    /// need it, so it is not cached.
+    pub fn aabb(&self, addr: &ChunkAddress) -> WorldAABB {
+        let local = ChunkAABB::from_chunk(
+            addr,
+            self.planet_radius,
+            self.min_height,
+            self.max_height,
+            self.projection,
         );
+        let c = &self.planet_center;
+        WorldAABB::from_local(&local, c.x, c.y, c.z)
     }
 
-    #[test]
-    fn test_onscreen_chunks_always_rendered() {
-        let frustum_local = LocalFrustum::from_view_proj(
-            &(Mat4::perspective_rh(1.0, 1.0, 0.1, 10_000.0)
-                * Mat4::look_at_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y)),
-        );
+    /// Start a cull pass; spheres used from now on are stamped with it.
+    fn begin_pass(&mut self) -> u64 {
+        self.pass += 1;
+        self.pass
+    }
 
-        let onscreen_center = Vec3::new(0.0, 0.0, -500.0);
-        let half_extents = Vec3::splat(16.0);
-        let result = frustum_local.test_aabb(onscreen_center, half_extents);
-        assert_ne!(
-            result,
-            Intersection::Outside,
-            "Chunk directly ahead should NOT be culled"
-        );
+    /// Drop the spheres of `face` nodes not used since `pass` began.
+    fn retain_pass(&mut self, face: CubeFace, pass: u64) {
+        self.spheres
+            .retain(|addr, cached| addr.face != face || cached.pass == pass);
     }
+}
 
-    #[test]
-    fn test_culling_reduces_draw_calls_by_50_percent() {
-        let planet_radius = 1000.0_f32;
-        let camera_pos = Vec3::new(0.0, planet_radius + 10.0, 0.0);
-        let look_dir = Vec3::new(1.0, 0.0, 0.0).normalize();
-        let vp = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 1.0, 5000.0)
-            * Mat4::look_at_rh(camera_pos, camera_pos + look_dir, Vec3::Y);
-        let frustum = LocalFrustum::from_view_proj(&vp);
-
-        let total_chunks = 1000;
-        let mut visible = 0;
-        for i in 0..total_chunks {
-            let theta = (i as f32 / total_chunks as f32) * std::f32::consts::TAU;
-            let phi = ((i * 7 + 3) as f32 / total_chunks as f32) * std::f32::consts::PI;
-            let pos = Vec3::new(
-                planet_radius * phi.sin() * theta.cos(),
-                planet_radius * phi.cos(),
-                planet_radius * phi.sin() * theta.sin(),
-            );
-            let center = pos - camera_pos;
-            let result = frustum.test_aabb(center, Vec3::splat(8.0));
-            if result != Intersection::Outside {
-                visible += 1;
-            }
+/// Hierarchical frustum culling of a planet's face quadtrees.
+///
+/// Each node's [`WorldBoundingSphere`] comes from a [`NodeBoundsCache`],
+/// is made camera-relative in i128 and tested in f64 with
+/// [`FrustumCuller::is_sphere_visible_f64`]; a node outside the frustum
+/// rejects its subtree without visiting the children. Leaves that survive
+/// get the fine f32 AABB test with [`FrustumCuller::is_visible`]. The culler
+/// must be built from a camera-relative view-projection in millimeters,
+/// e.g. with [`FrustumCuller::from_camera_f64`].
+pub struct QuadtreeCuller<'a> {
+    culler: &'a FrustumCuller,
+    camera: WorldPosition,
+    bounds: &'a mut NodeBoundsCache,
+}
+
+impl<'a> QuadtreeCuller<'a> {
+    /// Cull the nodes described by `bounds` for a camera at `camera`.
+    pub fn new(
+        culler: &'a FrustumCuller,
+        camera: WorldPosition,
+        bounds: &'a mut NodeBoundsCache,
+    ) -> Self {
+        Self {
+            culler,
+            camera,
+            bounds,
         }
+    }
 
-        let cull_ratio = 1.0 - (visible as f32 / total_chunks as f32);
-        assert!(
-            cull_ratio > 0.5,
-            "Expected >50% culled, got {:.1}% ({visible}/{total_chunks} visible)",
-            cull_ratio * 100.0
-        );
+    /// Whether the chunk at `addr` passes the coarse f64 sphere test.
+    pub fn is_node_visible(&mut self, addr: &ChunkAddress) -> bool {
+        let world = self.bounds.sphere(addr);
+        let (center, radius) = camera_relative_sphere(&world, &self.camera);
+        self.culler.is_sphere_visible_f64(center, radius)
     }
 
-    #[test]
-    fn test_planet_bounds_to_aabb() {
-        let planet = earth_like_planet();
-        let aabb = planet.to_aabb();
-        assert_eq!(aabb.min.x, -6_371_000_000);
-        assert_eq!(aabb.max.x, 6_371_000_000);
+    /// Whether the chunk at `addr` passes the fine f32 AABB test.
+    pub fn is_leaf_visible(&self, addr: &ChunkAddress) -> bool {
+        let world = self.bounds.aabb(addr);
+        self.culler
+            .is_visible(&camera_relative_aabb(&world, &self.camera))
     }
 
-    #[test]
-    fn test_local_frustum_behind_camera() {
-        let frustum = LocalFrustum::from_view_proj(
-            &(Mat4::perspective_rh(1.0, 1.0, 0.1, 1000.0)
-                * Mat4::look_at_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y)),
-        );
-        // Chunk behind camera (positive Z in RH looking -Z)
-        let result = frustum.test_aabb(Vec3::new(0.0, 0.0, 100.0), Vec3::splat(5.0));
-        assert_eq!(result, Intersection::Outside);
+    /// Append the visible leaves under `root` to `visible`.
+    pub fn cull(&mut self, root: &QuadNode, visible: &mut Vec<ChunkAddress>) -> QuadtreeCullStats {
+        let pass = self.bounds.begin_pass();
+        let mut stats = QuadtreeCullStats::default();
+        self.cull_node(root, visible, &mut stats);
+        self.bounds.retain_pass(root.address().face, pass);
+        stats
+    }
+
+    fn cull_node(
+        &mut self,
+        node: &QuadNode,
+        visible: &mut Vec<ChunkAddress>,
+        stats: &mut QuadtreeCullStats,
+    ) {
+        stats.nodes_visited += 1;
+        let addr = node.address();
+        if !self.is_node_visible(&addr) {
+            stats.subtrees_culled += 1;
+            return;
+        }
+        match node {
+            QuadNode::Leaf { .. } => {
+                if self.is_leaf_visible(&addr) {
+                    visible.push(addr);
+                } else {
+                    stats.leaves_culled += 1;
+                }
+            }
+            QuadNode::Branch { children, .. } => {
+                for child in children.iter() {
+                    self.cull_node(child, visible, stats);
+                }
+            }
+        }
     }
 }
+
+#[cfg(test)]
+#[path = "culling_tests.rs"]
+mod tests;
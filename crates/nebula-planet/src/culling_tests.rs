@@ -0,0 +1,330 @@
+//! Unit tests for the planet culling pipeline.
+
+use super::*;
+use nebula_coords::{Frustum128, Intersection};
+use nebula_math::WorldPosition;
+
+fn earth_like_planet() -> PlanetBounds {
+    PlanetBounds {
+        center: WorldPosition::new(0, 0, 0),
+        radius: 6_371_000_000, // 6371 km in mm
+    }
+}
+
+/// Build a Frustum128 looking from `position` in `forward` direction.
+fn build_test_frustum_128(position: WorldPosition, forward: nebula_coords::Vec3I64) -> Frustum128 {
+    use nebula_coords::Vec3I64;
+
+    // Derive right/up from forward
+    let (right, up) = if forward.x.abs() > forward.z.abs() {
+        (
+            Vec3I64::new(-forward.y, forward.x, 0),
+            Vec3I64::new(0, 0, 1_000_000),
+        )
+    } else {
+        (Vec3I64::new(1_000_000, 0, 0), Vec3I64::new(0, 1_000_000, 0))
+    };
+
+    Frustum128::from_camera(
+        &position,
+        &forward,
+        &right,
+        &up,
+        1_000,               // near: 1mm
+        100_000_000_000_000, // far: 100 billion km
+        (1, 1),              // 90° FOV
+    )
+}
+
+#[test]
+fn test_planet_behind_camera_culled_entirely() {
+    let frustum = build_test_frustum_128(
+        WorldPosition::new(0, 0, 20_000_000_000_000),
+        nebula_coords::Vec3I64::new(0, 0, 1_000_000), // looking +Z, away from origin
+    );
+    let planet = earth_like_planet();
+    let result = planet.test_frustum(&frustum);
+    assert_eq!(
+        result,
+        Intersection::Outside,
+        "Planet at origin should be culled when camera faces away from it"
+    );
+}
+
+#[test]
+fn test_planet_in_view_passes_coarse_cull() {
+    let frustum = build_test_frustum_128(
+        WorldPosition::new(0, 0, -20_000_000_000_000),
+        nebula_coords::Vec3I64::new(0, 0, 1_000_000), // looking +Z, toward planet
+    );
+    let planet = earth_like_planet();
+    let result = planet.test_frustum(&frustum);
+    assert_ne!(
+        result,
+        Intersection::Outside,
+        "Planet should be visible when camera looks directly at it"
+    );
+}
+
+#[test]
+fn test_offscreen_chunks_not_rendered() {
+    let frustum_local = LocalFrustum::from_view_proj(
+        &(Mat4::perspective_rh(1.0, 1.0, 0.1, 10_000.0)
+            * Mat4::look_at_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y)),
+    );
+
+    // Chunk far to the right, outside the FOV.
+    let offscreen_center = Vec3::new(50_000.0, 0.0, -100.0);
+    let half_extents = Vec3::splat(16.0);
+    let result = frustum_local.test_aabb(offscreen_center, half_extents);
+    assert_eq!(
+        result,
+        Intersection::Outside,
+        "Chunk far to the right should be culled"
+    );
+}
+
+#[test]
+fn test_onscreen_chunks_always_rendered() {
+    let frustum_local = LocalFrustum::from_view_proj(
+        &(Mat4::perspective_rh(1.0, 1.0, 0.1, 10_000.0)
+            * Mat4::look_at_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y)),
+    );
+
+    let onscreen_center = Vec3::new(0.0, 0.0, -500.0);
+    let half_extents = Vec3::splat(16.0);
+    let result = frustum_local.test_aabb(onscreen_center, half_extents);
+    assert_ne!(
+        result,
+        Intersection::Outside,
+        "Chunk directly ahead should NOT be culled"
+    );
+}
+
+#[test]
+fn test_culling_reduces_draw_calls_by_50_percent() {
+    let planet_radius = 1000.0_f32;
+    let camera_pos = Vec3::new(0.0, planet_radius + 10.0, 0.0);
+    let look_dir = Vec3::new(1.0, 0.0, 0.0).normalize();
+    let vp = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 1.0, 5000.0)
+        * Mat4::look_at_rh(camera_pos, camera_pos + look_dir, Vec3::Y);
+    let frustum = LocalFrustum::from_view_proj(&vp);
+
+    let total_chunks = 1000;
+    let mut visible = 0;
+    for i in 0..total_chunks {
+        let theta = (i as f32 / total_chunks as f32) * std::f32::consts::TAU;
+        let phi = ((i * 7 + 3) as f32 / total_chunks as f32) * std::f32::consts::PI;
+        let pos = Vec3::new(
+            planet_radius * phi.sin() * theta.cos(),
+            planet_radius * phi.cos(),
+            planet_radius * phi.sin() * theta.sin(),
+        );
+        let center = pos - camera_pos;
+        let result = frustum.test_aabb(center, Vec3::splat(8.0));
+        if result != Intersection::Outside {
+            visible += 1;
+        }
+    }
+
+    let cull_ratio = 1.0 - (visible as f32 / total_chunks as f32);
+    assert!(
+        cull_ratio > 0.5,
+        "Expected >50% culled, got {:.1}% ({visible}/{total_chunks} visible)",
+        cull_ratio * 100.0
+    );
+}
+
+#[test]
+fn test_planet_bounds_to_aabb() {
+    let planet = earth_like_planet();
+    let aabb = planet.to_aabb();
+    assert_eq!(aabb.min.x, -6_371_000_000);
+    assert_eq!(aabb.max.x, 6_371_000_000);
+}
+
+const PLANET_RADIUS: f64 = 6_371_000_000.0;
+
+/// A culler for a camera `altitude` mm above the +Y pole of a planet
+/// centered at `planet_center`, looking along `look`.
+fn planet_camera(
+    planet_center: WorldPosition,
+    altitude: i128,
+    look: DVec3,
+) -> (FrustumCuller, WorldPosition) {
+    let camera = WorldPosition::new(
+        planet_center.x,
+        planet_center.y + PLANET_RADIUS as i128 + altitude,
+        planet_center.z,
+    );
+    let up = if look.cross(DVec3::Y).length_squared() > 0.0 {
+        DVec3::Y
+    } else {
+        DVec3::Z
+    };
+    let view = glam::DMat4::look_to_rh(DVec3::ZERO, look, up);
+    let proj = glam::DMat4::perspective_infinite_reverse_rh(1.2, 16.0 / 9.0, 100.0);
+    (FrustumCuller::from_camera_f64(&(proj * view)), camera)
+}
+
+/// Refine `node` toward the unit direction `target`, `depth` levels deep.
+fn refine(node: &mut QuadNode, target: DVec3, depth: u8) {
+    let center = nebula_cubesphere::project(
+        &node.address().center_face_coord(),
+        ProjectionMethod::default(),
+    );
+    if depth == 0 || center.dot(target) < 0.2 {
+        return;
+    }
+    node.subdivide();
+    if let QuadNode::Branch { children, .. } = node {
+        for child in children.iter_mut() {
+            refine(child, target, depth - 1);
+        }
+    }
+}
+
+/// Six face quadtrees refined toward the +Y pole, as the LOD system
+/// would leave them for a camera above it.
+fn planet_scene() -> Vec<nebula_cubesphere::FaceQuadtree> {
+    nebula_cubesphere::CubeFace::ALL
+        .iter()
+        .map(|&face| {
+            let mut tree = nebula_cubesphere::FaceQuadtree::new(face);
+            refine(&mut tree.root, DVec3::Y, 5);
+            tree
+        })
+        .collect()
+}
+
+#[test]
+fn test_hierarchical_cull_matches_brute_force() {
+    let center = WorldPosition::new(0, 0, 0);
+    let scene = planet_scene();
+    for look in [
+        DVec3::new(1.0, -0.3, 0.0).normalize(),
+        DVec3::NEG_Y,
+        DVec3::new(0.0, -0.1, -1.0).normalize(),
+        DVec3::Y,
+    ] {
+        let (frustum, camera) = planet_camera(center, 2_000_000_000, look);
+        let mut bounds = NodeBoundsCache::new(center, PLANET_RADIUS, -50_000.0, 8_000_000.0);
+        let mut culler = QuadtreeCuller::new(&frustum, camera, &mut bounds);
+
+        let mut hierarchical = Vec::new();
+        let mut brute_force = Vec::new();
+        let (mut visited, mut total) = (0, 0);
+        for tree in &scene {
+            visited += culler.cull(&tree.root, &mut hierarchical).nodes_visited as usize;
+            total += tree.root.node_count();
+            brute_force.extend(
+                tree.root
+                    .all_leaves()
+                    .into_iter()
+                    .filter(|addr| culler.is_leaf_visible(addr)),
+            );
+        }
+        hierarchical.sort();
+        brute_force.sort();
+        assert_eq!(hierarchical, brute_force, "looking along {look}");
+        assert!(visited < total, "looking along {look}: no subtree culled");
+    }
+}
+
+#[test]
+fn test_outside_node_rejects_subtree_without_visiting_children() {
+    let center = WorldPosition::new(0, 0, 0);
+    // Looking straight up, away from the planet.
+    let (frustum, camera) = planet_camera(center, 2_000_000_000, DVec3::Y);
+    let mut bounds = NodeBoundsCache::new(center, PLANET_RADIUS, 0.0, 8_000_000.0);
+    let mut culler = QuadtreeCuller::new(&frustum, camera, &mut bounds);
+
+    // The far side of the planet, refined as if another camera were there.
+    let mut far_side = nebula_cubesphere::FaceQuadtree::new(nebula_cubesphere::CubeFace::NegY);
+    refine(&mut far_side.root, DVec3::NEG_Y, 4);
+    assert!(far_side.root.node_count() > 1);
+
+    let mut visible = Vec::new();
+    let stats = culler.cull(&far_side.root, &mut visible);
+    assert!(visible.is_empty());
+    assert_eq!(stats.nodes_visited, 1);
+    assert_eq!(stats.subtrees_culled, 1);
+}
+
+#[test]
+fn test_node_spheres_are_cached_across_culls_and_pruned_with_the_tree() {
+    let center = WorldPosition::new(0, 0, 0);
+    let (frustum, camera) = planet_camera(center, 2_000_000_000, DVec3::NEG_Y);
+    let mut bounds = NodeBoundsCache::new(center, PLANET_RADIUS, 0.0, 8_000_000.0);
+    let mut tree = nebula_cubesphere::FaceQuadtree::new(nebula_cubesphere::CubeFace::PosY);
+    refine(&mut tree.root, DVec3::Y, 4);
+
+    let mut visible = Vec::new();
+    let first = QuadtreeCuller::new(&frustum, camera, &mut bounds).cull(&tree.root, &mut visible);
+    assert_eq!(bounds.len(), first.nodes_visited as usize);
+    let root = tree.root.address();
+    let cached = bounds.sphere(&root);
+
+    let mut again = Vec::new();
+    QuadtreeCuller::new(&frustum, camera, &mut bounds).cull(&tree.root, &mut again);
+    assert_eq!(again, visible);
+    assert_eq!(bounds.len(), first.nodes_visited as usize);
+    assert_eq!(bounds.sphere(&root).center_x, cached.center_x);
+
+    // Merging the tree back to its root releases the children's spheres.
+    let coarse = nebula_cubesphere::FaceQuadtree::new(nebula_cubesphere::CubeFace::PosY);
+    QuadtreeCuller::new(&frustum, camera, &mut bounds).cull(&coarse.root, &mut Vec::new());
+    assert_eq!(bounds.len(), 1);
+}
+
+#[test]
+fn test_chunk_ten_million_km_away_stays_finite() {
+    // 10 million km = 1e13 mm along -Z from the camera.
+    let camera = WorldPosition::new(0, 0, 0);
+    let center = WorldPosition::new(0, 0, -10_000_000_000_000);
+    let view = glam::DMat4::look_to_rh(DVec3::ZERO, DVec3::NEG_Z, DVec3::Y);
+    let proj = glam::DMat4::perspective_infinite_reverse_rh(1.2, 1.0, 100.0);
+    let frustum = FrustumCuller::from_camera_f64(&(proj * view));
+    let mut bounds = NodeBoundsCache::new(center, PLANET_RADIUS, 0.0, 8_000_000.0);
+    let mut culler = QuadtreeCuller::new(&frustum, camera, &mut bounds);
+
+    let addr = ChunkAddress::new(nebula_cubesphere::CubeFace::PosZ, 10, 512, 512);
+    let local = BoundingSphere::from_chunk(
+        &addr,
+        PLANET_RADIUS,
+        0.0,
+        8_000_000.0,
+        ProjectionMethod::default(),
+    );
+    let world = WorldBoundingSphere::from_local(&local, center.x, center.y, center.z);
+    let (rel, radius) = camera_relative_sphere(&world, &camera);
+    assert!(rel.is_finite() && radius.is_finite());
+
+    let local = ChunkAABB::from_chunk(
+        &addr,
+        PLANET_RADIUS,
+        0.0,
+        8_000_000.0,
+        ProjectionMethod::default(),
+    );
+    let aabb = camera_relative_aabb(
+        &WorldAABB::from_local(&local, center.x, center.y, center.z),
+        &camera,
+    );
+    assert!(aabb.min.is_finite() && aabb.max.is_finite());
+
+    // The chunk faces the camera across the gap, so it is kept.
+    assert!(culler.is_node_visible(&addr));
+    assert!(culler.is_leaf_visible(&addr));
+}
+
+#[test]
+fn test_local_frustum_behind_camera() {
+    let frustum = LocalFrustum::from_view_proj(
+        &(Mat4::perspective_rh(1.0, 1.0, 0.1, 1000.0)
+            * Mat4::look_at_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y)),
+    );
+    // Chunk behind camera (positive Z in RH looking -Z)
+    let result = frustum.test_aabb(Vec3::new(0.0, 0.0, 100.0), Vec3::splat(5.0));
+    assert_eq!(result, Intersection::Outside);
+}
@@ -4,8 +4,14 @@
 //! in `[0.0, 1.0)` where 0.0 is midnight, 0.25 is dawn, 0.5 is noon,
 //! and 0.75 is dusk. All derived lighting values (intensity, color,
 //! ambient, star visibility) update smoothly each frame.
+//!
+//! The sun stays in the planet's equatorial plane, so its path depends on
+//! the observer's latitude: it passes overhead at the equator, peaks at
+//! `90° − |latitude|` elsewhere, and grazes the horizon at the poles.
+//! Directions are given in the observer's horizon frame, where the lighting
+//! curves read elevation from `y`.
 
-use glam::Vec3;
+use glam::{DVec3, Vec3};
 
 /// In-game time tracking for the day/night cycle.
 #[derive(Clone, Debug)]
@@ -13,17 +19,17 @@ pub struct DayNightClock {
     /// Current time of day, normalized `[0.0, 1.0)`. 0.0 = midnight, 0.5 = noon.
     pub time_of_day: f64,
     /// Duration of one full day in real-time seconds.
-    pub day_duration_seconds: f64,
+    pub day_length_seconds: f64,
     /// Whether the cycle is paused (e.g., in editor mode).
     pub paused: bool,
 }
 
 impl DayNightClock {
     /// Create a new clock starting at noon.
-    pub fn new(day_duration_seconds: f64) -> Self {
+    pub fn new(day_length_seconds: f64) -> Self {
         Self {
             time_of_day: 0.5,
-            day_duration_seconds,
+            day_length_seconds,
             paused: false,
         }
     }
@@ -33,7 +39,7 @@ impl DayNightClock {
         if self.paused {
             return;
         }
-        let day_fraction = dt / self.day_duration_seconds;
+        let day_fraction = dt / self.day_length_seconds;
         self.time_of_day = (self.time_of_day + day_fraction) % 1.0;
     }
 
@@ -48,17 +54,47 @@ impl DayNightClock {
     }
 }
 
-/// Compute the sun's direction vector from the time of day.
+/// Compute the sun's direction from the local time of day and the
+/// observer's latitude in degrees.
 ///
-/// The sun orbits in the XZ plane with Y as "up".
-/// At `time_of_day = 0.5` (noon) the sun is directly overhead (+Y).
-/// At `time_of_day = 0.0` (midnight) the sun is directly below (−Y).
-pub fn sun_direction_from_time(time_of_day: f64) -> Vec3 {
-    let angle = (time_of_day as f32) * std::f32::consts::TAU;
-    Vec3::new(angle.sin(), -angle.cos(), 0.0).normalize()
+/// The result is in the observer's horizon frame: +X east, +Y up, +Z south
+/// (right-handed). The sun rises in the east at 0.25 and sets in the west
+/// at 0.75; at noon its elevation is `90° − |latitude|`. At the equator it
+/// passes directly overhead (+Y) at noon and directly below (−Y) at
+/// midnight; at the poles it circles along the horizon.
+pub fn sun_direction_from_time(time_of_day: f64, latitude_deg: f64) -> Vec3 {
+    // Hour angle: 0 at local noon, positive in the afternoon.
+    let hour_angle = (time_of_day - 0.5) * std::f64::consts::TAU;
+    let (sin_lat, cos_lat) = latitude_deg.to_radians().sin_cos();
+    let (sin_h, cos_h) = hour_angle.sin_cos();
+    DVec3::new(-sin_h, cos_lat * cos_h, sin_lat * cos_h)
+        .normalize()
+        .as_vec3()
+}
+
+/// Latitude in degrees of the point on a planet in direction `up` from its
+/// center, using the cubesphere convention of
+/// [`PlanetBody`](crate::PlanetBody): +Y is the north pole.
+pub fn latitude_from_up(up: DVec3) -> f64 {
+    up.normalize_or_zero()
+        .y
+        .clamp(-1.0, 1.0)
+        .asin()
+        .to_degrees()
 }
 
-/// Compute the sun intensity multiplier from its elevation.
+/// Local time of day at the point in direction `up` from the planet center,
+/// given the time of day at longitude 0.
+///
+/// Each 360° of longitude eastward adds one day; longitude follows
+/// [`PlanetBody`](crate::PlanetBody), `atan2(z, x)`.
+pub fn local_time_of_day(time_of_day: f64, up: DVec3) -> f64 {
+    let longitude_deg = up.z.atan2(up.x).to_degrees();
+    (time_of_day + longitude_deg / 360.0).rem_euclid(1.0)
+}
+
+/// Compute the sun intensity multiplier from its elevation (`y` of a
+/// horizon-frame direction from [`sun_direction_from_time`]).
 ///
 /// Returns a value in `[0.0, 1.0]`:
 /// - 1.0 when sun is well above the horizon (elevation > 15°)
@@ -97,7 +133,8 @@ pub fn sun_color(sun_direction: Vec3) -> Vec3 {
 
 /// Compute the opacity of the starfield based on sun intensity.
 ///
-/// Stars are fully visible at night, fully invisible during the day.
+/// Stars are fully visible at night, fully invisible during the day; near
+/// the poles, where the sun stays low, they stay faintly visible.
 pub fn star_visibility(sun_direction: Vec3) -> f32 {
     let sun_factor = sun_intensity_curve(sun_direction);
     (1.0 - sun_factor * 2.0).clamp(0.0, 1.0)
@@ -118,27 +155,41 @@ pub struct DayNightState {
     pub ambient_intensity: f32,
     /// Star visibility `[0.0, 1.0]`.
     pub star_visibility: f32,
+    /// Observer latitude in degrees (0 = equator).
+    pub latitude_deg: f64,
 }
 
 impl DayNightState {
-    /// Create a new state with the given day duration (real-time seconds).
-    pub fn new(day_duration_seconds: f64) -> Self {
-        let clock = DayNightClock::new(day_duration_seconds);
-        let sun_dir = sun_direction_from_time(clock.time_of_day);
-        Self {
-            clock,
-            sun_direction: sun_dir,
-            sun_color: sun_color(sun_dir),
-            sun_intensity: sun_intensity_curve(sun_dir),
-            ambient_intensity: ambient_intensity(sun_dir),
-            star_visibility: star_visibility(sun_dir),
-        }
+    /// Create a new state at the equator with the given day length
+    /// (real-time seconds).
+    pub fn new(day_length_seconds: f64) -> Self {
+        let mut state = Self {
+            clock: DayNightClock::new(day_length_seconds),
+            sun_direction: Vec3::Y,
+            sun_color: Vec3::ZERO,
+            sun_intensity: 0.0,
+            ambient_intensity: 0.0,
+            star_visibility: 0.0,
+            latitude_deg: 0.0,
+        };
+        state.refresh();
+        state
+    }
+
+    /// Move the observer to `latitude_deg` and recompute derived values.
+    pub fn set_latitude(&mut self, latitude_deg: f64) {
+        self.latitude_deg = latitude_deg;
+        self.refresh();
     }
 
     /// Advance the clock by `dt` seconds and recompute all derived values.
     pub fn tick(&mut self, dt: f64) {
         self.clock.tick(dt);
-        self.sun_direction = sun_direction_from_time(self.clock.time_of_day);
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        self.sun_direction = sun_direction_from_time(self.clock.time_of_day, self.latitude_deg);
         self.sun_intensity = sun_intensity_curve(self.sun_direction);
         self.sun_color = sun_color(self.sun_direction);
         self.ambient_intensity = ambient_intensity(self.sun_direction);
@@ -161,7 +212,7 @@ mod tests {
 
     #[test]
     fn test_noon_has_maximum_light() {
-        let sun_dir = sun_direction_from_time(0.5);
+        let sun_dir = sun_direction_from_time(0.5, 0.0);
         let intensity = sun_intensity_curve(sun_dir);
         assert!(
             intensity > 0.95,
@@ -176,7 +227,7 @@ mod tests {
 
     #[test]
     fn test_midnight_has_minimum_light() {
-        let sun_dir = sun_direction_from_time(0.0);
+        let sun_dir = sun_direction_from_time(0.0, 0.0);
         let intensity = sun_intensity_curve(sun_dir);
         assert!(
             intensity < 0.05,
@@ -192,7 +243,7 @@ mod tests {
     #[test]
     fn test_dawn_dusk_have_warm_colors() {
         for &time in &[0.25, 0.75] {
-            let sun_dir = sun_direction_from_time(time);
+            let sun_dir = sun_direction_from_time(time, 0.0);
             let color = sun_color(sun_dir);
             let brightness = color.x + color.y + color.z;
             if brightness > 0.01 {
@@ -232,11 +283,12 @@ mod tests {
         let mut clock = DayNightClock::new(1200.0);
         let dt = 1.0 / 60.0;
 
-        let mut prev_intensity = sun_intensity_curve(sun_direction_from_time(clock.time_of_day));
+        let mut prev_intensity =
+            sun_intensity_curve(sun_direction_from_time(clock.time_of_day, 0.0));
 
         for frame in 0..600 {
             clock.tick(dt);
-            let sun_dir = sun_direction_from_time(clock.time_of_day);
+            let sun_dir = sun_direction_from_time(clock.time_of_day, 0.0);
             let intensity = sun_intensity_curve(sun_dir);
 
             let delta = (intensity - prev_intensity).abs();
@@ -250,7 +302,7 @@ mod tests {
 
     #[test]
     fn test_star_visibility_at_night() {
-        let sun_dir = sun_direction_from_time(0.0);
+        let sun_dir = sun_direction_from_time(0.0, 0.0);
         let stars = star_visibility(sun_dir);
         assert!(
             stars > 0.9,
@@ -260,7 +312,7 @@ mod tests {
 
     #[test]
     fn test_star_visibility_at_noon() {
-        let sun_dir = sun_direction_from_time(0.5);
+        let sun_dir = sun_direction_from_time(0.5, 0.0);
         let stars = star_visibility(sun_dir);
         assert!(
             stars < 0.1,
@@ -291,6 +343,68 @@ mod tests {
         );
     }
 
+    /// Sun elevation in degrees for a horizon-frame direction.
+    fn elevation_deg(sun_dir: Vec3) -> f32 {
+        sun_dir.y.asin().to_degrees()
+    }
+
+    #[test]
+    fn test_noon_elevation_by_latitude() {
+        let equator = elevation_deg(sun_direction_from_time(0.5, 0.0));
+        let temperate = elevation_deg(sun_direction_from_time(0.5, 45.0));
+        let polar = elevation_deg(sun_direction_from_time(0.5, -85.0));
+        assert!((equator - 90.0).abs() < 1e-3, "equator noon: {equator}");
+        assert!((temperate - 45.0).abs() < 1e-3, "45° noon: {temperate}");
+        assert!((polar - 5.0).abs() < 1e-3, "85° S noon: {polar}");
+
+        // The grazing polar sun is dimmer, redder, and lets stars through.
+        let (eq_dir, pole_dir) = (
+            sun_direction_from_time(0.5, 0.0),
+            sun_direction_from_time(0.5, 89.0),
+        );
+        assert!(sun_intensity_curve(pole_dir) < sun_intensity_curve(eq_dir));
+        let pole_color = sun_color(pole_dir);
+        assert!(pole_color.z / pole_color.x < 0.5, "polar sun {pole_color}");
+        assert!(star_visibility(pole_dir) > star_visibility(eq_dir));
+    }
+
+    #[test]
+    fn test_sun_path_east_to_west() {
+        // Northern observer: rises in the east, crosses south, sets west.
+        let dawn = sun_direction_from_time(0.25, 40.0);
+        let noon = sun_direction_from_time(0.5, 40.0);
+        let dusk = sun_direction_from_time(0.75, 40.0);
+        assert!(dawn.x > 0.99 && dawn.y.abs() < 1e-6);
+        assert!(noon.z > 0.0, "noon sun is to the south: {noon}");
+        assert!(dusk.x < -0.99);
+        // The south pole at its midnight sees the sun on the horizon.
+        assert!(sun_direction_from_time(0.0, -90.0).y.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_latitude_and_local_time_from_position() {
+        assert!((latitude_from_up(DVec3::Y) - 90.0).abs() < 1e-9);
+        assert!(latitude_from_up(DVec3::X).abs() < 1e-9);
+        let up = DVec3::new(1.0, 1.0, 0.0);
+        assert!((latitude_from_up(up) - 45.0).abs() < 1e-9);
+
+        assert!((local_time_of_day(0.5, DVec3::X) - 0.5).abs() < 1e-12);
+        // 90° east is six hours later.
+        assert!((local_time_of_day(0.9, DVec3::Z) - 0.15).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_state_follows_latitude() {
+        let mut state = DayNightState::new(600.0);
+        assert_eq!(state.clock.day_length_seconds, 600.0);
+        let equator_intensity = state.sun_intensity;
+        state.set_latitude(88.0);
+        assert!(state.sun_direction.y < 0.1);
+        assert!(state.sun_intensity < equator_intensity);
+        state.tick(1.0);
+        assert_eq!(state.latitude_deg, 88.0);
+    }
+
     #[test]
     fn test_hours_conversion() {
         let clock = DayNightClock::new(1200.0);
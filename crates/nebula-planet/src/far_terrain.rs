@@ -0,0 +1,395 @@
+//! Far-terrain impostor patches.
+//!
+//! Distant quadtree nodes are drawn as a single low-resolution heightfield
+//! mesh per node instead of thousands of tiny chunk meshes. A
+//! [`FarTerrainPatch`] samples [`TerrainHeightSampler`] on a
+//! [`FAR_PATCH_RESOLUTION`]² grid over the node and displaces it onto the
+//! cubesphere. [`FarTerrainPatchCache`] keeps one patch per node emitted as
+//! [`LodChunkKind::MergedPatch`] and releases it once the node splits.
+//!
+//! A patch bordering a finer one shares every other edge vertex with it; the
+//! finer patch's in-between vertices leave T-junction cracks against the
+//! coarse edge. Each patch hangs a skirt below its outline, deep enough to
+//! close those cracks.
+
+use std::collections::{HashMap, HashSet};
+
+use glam::{DVec3, Vec3};
+use nebula_cubesphere::{ChunkAddress, FaceCoord, ProjectionMethod, project};
+use nebula_lod::{LodChunkDescriptor, LodChunkKind};
+use nebula_terrain::TerrainHeightSampler;
+
+/// Vertices along each side of a far-terrain patch.
+///
+/// One more than a power of two, so the samples of a node's edge are every
+/// other sample of its children's edges and neighboring patches one level
+/// apart share vertices.
+pub const FAR_PATCH_RESOLUTION: usize = 17;
+
+/// Depth of a patch's skirt below its outline, in grid cells of the patch.
+///
+/// A T-junction crack is as deep as the terrain rises or falls across half
+/// a cell of the coarser patch, which is one cell of the finer one; the
+/// shallower skirt of the two, the finer patch's, still covers slopes up to
+/// 60 degrees.
+const SKIRT_DEPTH_CELLS: f64 = 2.0;
+
+/// A baked heightfield mesh covering one quadtree node.
+pub struct FarTerrainPatch {
+    /// The node this patch covers.
+    pub address: ChunkAddress,
+    /// Planet-centered vertex positions in the sampler's units: the
+    /// [`FAR_PATCH_RESOLUTION`]² grid, row-major with `u` varying fastest,
+    /// followed by the skirt vertices below the grid's outline.
+    pub positions: Vec<DVec3>,
+    /// Unit outward surface normals per vertex.
+    pub normals: Vec<Vec3>,
+    /// Triangle indices: the grid's, counter-clockwise seen from above the
+    /// surface, followed by the skirt's, facing away from the patch.
+    pub indices: Vec<u32>,
+    /// How far the skirt hangs below the outline, in the sampler's units.
+    pub skirt_depth: f64,
+}
+
+impl FarTerrainPatch {
+    /// Bake the patch for `address` by sampling `sampler` on the node's grid,
+    /// placed on the sphere with the planet's `projection`.
+    pub fn bake(
+        address: &ChunkAddress,
+        sampler: &TerrainHeightSampler,
+        projection: ProjectionMethod,
+    ) -> Self {
+        let n = FAR_PATCH_RESOLUTION;
+        let (u_min, v_min, u_max, v_max) = address.uv_bounds();
+        let step = 1.0 / (n - 1) as f64;
+
+        let mut positions = Vec::with_capacity(n * n);
+        for j in 0..n {
+            let v = v_min + (v_max - v_min) * (j as f64 * step);
+            for i in 0..n {
+                let u = u_min + (u_max - u_min) * (i as f64 * step);
+                let fc = FaceCoord::new(address.face, u.clamp(0.0, 1.0), v.clamp(0.0, 1.0));
+                let sphere_pt = project(&fc, projection);
+                positions.push(sampler.sample_world_position(sphere_pt));
+            }
+        }
+
+        let mut normals: Vec<Vec3> = (0..n * n)
+            .map(|k| {
+                let (i, j) = (k % n, k / n);
+                let du =
+                    positions[j * n + (i + 1).min(n - 1)] - positions[j * n + i.saturating_sub(1)];
+                let dv =
+                    positions[(j + 1).min(n - 1) * n + i] - positions[j.saturating_sub(1) * n + i];
+                let normal = du.cross(dv).normalize_or_zero();
+                if normal.dot(positions[k]) < 0.0 {
+                    (-normal).as_vec3()
+                } else {
+                    normal.as_vec3()
+                }
+            })
+            .collect();
+
+        // Face (u, v) axes are left- or right-handed depending on the face,
+        // so pick the winding that faces away from the planet center.
+        let outward = (positions[1] - positions[0])
+            .cross(positions[n] - positions[0])
+            .dot(positions[0])
+            > 0.0;
+        let outline = outline_indices();
+        let mut indices = Vec::with_capacity(((n - 1) * (n - 1) + outline.len()) * 6);
+        for j in 0..n - 1 {
+            for i in 0..n - 1 {
+                let a = (j * n + i) as u32;
+                let b = a + 1;
+                let c = a + n as u32;
+                let d = c + 1;
+                if outward {
+                    indices.extend_from_slice(&[a, b, c, b, d, c]);
+                } else {
+                    indices.extend_from_slice(&[a, c, b, b, c, d]);
+                }
+            }
+        }
+
+        // Hang one skirt vertex below each outline vertex and join
+        // neighboring pairs with a quad facing away from the patch center.
+        let cell = positions[0]
+            .distance(positions[1])
+            .max(positions[0].distance(positions[n]));
+        let skirt_depth = cell * SKIRT_DEPTH_CELLS;
+        let center = positions[n * n / 2];
+        let skirt = positions.len() as u32;
+        for &k in &outline {
+            let top = positions[k];
+            positions.push(top - top.normalize_or_zero() * skirt_depth);
+            normals.push(normals[k]);
+        }
+        for s in 0..outline.len() {
+            let next = (s + 1) % outline.len();
+            let (a, b) = (outline[s] as u32, outline[next] as u32);
+            let (c, d) = (skirt + s as u32, skirt + next as u32);
+            let facing = (positions[b as usize] - positions[a as usize])
+                .cross(positions[c as usize] - positions[a as usize])
+                .dot(positions[a as usize] - center);
+            if facing > 0.0 {
+                indices.extend_from_slice(&[a, b, c, b, d, c]);
+            } else {
+                indices.extend_from_slice(&[a, c, b, b, c, d]);
+            }
+        }
+
+        Self {
+            address: *address,
+            positions,
+            normals,
+            indices,
+            skirt_depth,
+        }
+    }
+
+    /// Position of grid vertex `(i, j)`, with `i` along `u` and `j` along `v`.
+    pub fn vertex(&self, i: usize, j: usize) -> DVec3 {
+        self.positions[j * FAR_PATCH_RESOLUTION + i]
+    }
+
+    /// Positions of the grid vertices on the patch's outline, in order
+    /// around it.
+    pub fn boundary_vertices(&self) -> Vec<DVec3> {
+        outline_indices()
+            .into_iter()
+            .map(|k| self.positions[k])
+            .collect()
+    }
+}
+
+/// Grid indices of the outline vertices, walking around the grid once.
+fn outline_indices() -> Vec<usize> {
+    let n = FAR_PATCH_RESOLUTION;
+    let last = n - 1;
+    let bottom = 0..last;
+    let right = (0..last).map(|j| j * n + last);
+    let top = (1..n).rev().map(|i| last * n + i);
+    let left = (1..n).rev().map(|j| j * n);
+    bottom.chain(right).chain(top).chain(left).collect()
+}
+
+/// Patches baked and released by one [`FarTerrainPatchCache::sync`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FarPatchSync {
+    /// Patches baked for nodes that newly became merged patches.
+    pub baked: usize,
+    /// Patches dropped because their node split or left the stream.
+    pub released: usize,
+}
+
+/// Far-terrain patches keyed by quadtree node.
+#[derive(Default)]
+pub struct FarTerrainPatchCache {
+    patches: HashMap<ChunkAddress, FarTerrainPatch>,
+    projection: ProjectionMethod,
+}
+
+impl FarTerrainPatchCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bake patches with the planet's cube-to-sphere `projection` instead of
+    /// the default mapping.
+    pub fn with_projection(mut self, projection: ProjectionMethod) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    /// Match the cache to a descriptor stream from
+    /// [`FaceQuadtreeLod`](nebula_lod::FaceQuadtreeLod): bake a patch for
+    /// every new [`LodChunkKind::MergedPatch`] entry and release the patches
+    /// of nodes that are no longer merged patches.
+    pub fn sync(
+        &mut self,
+        descriptors: &[LodChunkDescriptor],
+        sampler: &TerrainHeightSampler,
+    ) -> FarPatchSync {
+        let wanted: HashSet<ChunkAddress> = descriptors
+            .iter()
+            .filter(|d| d.kind == LodChunkKind::MergedPatch)
+            .map(|d| d.address)
+            .collect();
+
+        let before = self.patches.len();
+        self.patches.retain(|addr, _| wanted.contains(addr));
+        let released = before - self.patches.len();
+
+        let mut baked = 0;
+        let projection = self.projection;
+        for addr in wanted {
+            self.patches.entry(addr).or_insert_with(|| {
+                baked += 1;
+                FarTerrainPatch::bake(&addr, sampler, projection)
+            });
+        }
+        FarPatchSync { baked, released }
+    }
+
+    /// The patch baked for `address`, if it is cached.
+    pub fn get(&self, address: &ChunkAddress) -> Option<&FarTerrainPatch> {
+        self.patches.get(address)
+    }
+
+    /// All cached patches, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &FarTerrainPatch> {
+        self.patches.values()
+    }
+
+    /// Number of cached patches.
+    pub fn len(&self) -> usize {
+        self.patches.len()
+    }
+
+    /// Whether no patches are cached.
+    pub fn is_empty(&self) -> bool {
+        self.patches.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nebula_cubesphere::CubeFace;
+    use nebula_lod::{FaceQuadtreeLod, LodThresholds};
+    use nebula_math::WorldPosition;
+    use nebula_terrain::{HeightmapParams, TerrainHeightConfig};
+
+    /// Voxel size of the test terrain, in sampler units.
+    const VOXEL_SIZE: f64 = 1.0;
+
+    /// The small demo planet used by the face loaders.
+    fn demo_sampler() -> TerrainHeightSampler {
+        TerrainHeightSampler::new(
+            HeightmapParams {
+                seed: 42,
+                octaves: 4,
+                amplitude: 8.0,
+                base_frequency: 0.05,
+                ..Default::default()
+            },
+            TerrainHeightConfig {
+                min_height: -4.0,
+                max_height: 12.0,
+                sea_level: 0.0,
+                beach_width: 1.0,
+                planet_radius: 200.0,
+            },
+        )
+    }
+
+    #[test]
+    fn test_patch_grid_is_17_by_17_and_faces_outward() {
+        let sampler = demo_sampler();
+        let grid_indices = 16 * 16 * 6;
+        for face in CubeFace::ALL {
+            let patch = FarTerrainPatch::bake(
+                &ChunkAddress::new(face, 18, 1, 2),
+                &sampler,
+                ProjectionMethod::default(),
+            );
+            assert_eq!(patch.positions.len(), 17 * 17 + 4 * 16);
+            assert_eq!(patch.indices.len(), grid_indices + 4 * 16 * 6);
+            for tri in patch.indices[..grid_indices].chunks(3) {
+                let [a, b, c] = [0, 1, 2].map(|k| patch.positions[tri[k] as usize]);
+                let normal = (b - a).cross(c - a);
+                assert!(normal.dot(a) > 0.0, "{face:?} triangle faces inward");
+            }
+            let center = patch.vertex(8, 8);
+            for tri in patch.indices[grid_indices..].chunks(3) {
+                let [a, b, c] = [0, 1, 2].map(|k| patch.positions[tri[k] as usize]);
+                let normal = (b - a).cross(c - a);
+                assert!(
+                    normal.dot(a - center) > 0.0,
+                    "{face:?} skirt faces the patch"
+                );
+            }
+            for (pos, normal) in patch.positions.iter().zip(&patch.normals) {
+                assert!(normal.as_dvec3().dot(*pos) > 0.0);
+            }
+        }
+    }
+
+    /// Across the edge between a coarse patch and the finer patches of its
+    /// split neighbor, every coarse edge vertex is a fine edge vertex, and
+    /// the skirts are deeper than the T-junction cracks left by the fine
+    /// vertices in between.
+    #[test]
+    fn test_coarse_and_fine_patches_share_edge_vertices() {
+        let sampler = demo_sampler();
+        let n = FAR_PATCH_RESOLUTION;
+        let half = (n - 1) / 2;
+        let projection = ProjectionMethod::default();
+        let coarse_addr = ChunkAddress::new(CubeFace::PosY, 18, 1, 1);
+        let coarse = FarTerrainPatch::bake(&coarse_addr, &sampler, projection);
+        let Some(children) = ChunkAddress::new(CubeFace::PosY, 18, 2, 1).children() else {
+            panic!("lod 18 node has children");
+        };
+
+        let mut deepest_crack = 0.0_f64;
+        for child in children.iter().filter(|c| c.x == 4) {
+            let fine = FarTerrainPatch::bake(child, &sampler, projection);
+            let offset = (child.y - 2 * coarse_addr.y) as usize * half;
+            for j in 0..n {
+                let on_fine = fine.vertex(0, j);
+                let k = offset + j / 2;
+                if j % 2 == 0 {
+                    let on_coarse = coarse.vertex(n - 1, k);
+                    assert!(
+                        on_fine.distance(on_coarse) < 1e-6 * VOXEL_SIZE,
+                        "edge vertex {j} of {child:?} is not shared"
+                    );
+                } else {
+                    let edge = (coarse.vertex(n - 1, k) + coarse.vertex(n - 1, k + 1)) * 0.5;
+                    let crack = on_fine.distance(edge);
+                    assert!(
+                        crack < fine.skirt_depth.min(coarse.skirt_depth),
+                        "crack of {crack} at edge vertex {j} of {child:?} is deeper \
+                         than the skirts"
+                    );
+                    deepest_crack = deepest_crack.max(crack);
+                }
+            }
+        }
+        assert!(deepest_crack > 0.0, "fine edge vertices leave T-junctions");
+    }
+
+    #[test]
+    fn test_cache_bakes_patches_and_releases_them_on_split() {
+        let sampler = demo_sampler();
+        let mut lod = FaceQuadtreeLod::new(
+            CubeFace::PosY,
+            6,
+            LodThresholds::custom(vec![
+                12_000.0, 13_000.0, 14_000.0, 15_000.0, 16_000.0, 17_000.0,
+            ])
+            .with_promote_ratio(1.0),
+            10_000_000.0,
+        )
+        .with_merged_patches(16);
+        let mut cache = FarTerrainPatchCache::new();
+
+        let far = lod.update(&WorldPosition::new(0, 100_000_000, 0));
+        let sync = cache.sync(&far, &sampler);
+        assert_eq!(sync.baked, far.len());
+        assert_eq!(sync.released, 0);
+        assert_eq!(cache.sync(&far, &sampler), FarPatchSync::default());
+
+        let near = lod.update(&WorldPosition::new(0, 20_000_000, 0));
+        let sync = cache.sync(&near, &sampler);
+        assert!(sync.released > 0, "splitting nodes should release patches");
+        for patch in cache.iter() {
+            assert!(
+                near.iter()
+                    .any(|d| d.address == patch.address && d.kind == LodChunkKind::MergedPatch)
+            );
+        }
+    }
+}
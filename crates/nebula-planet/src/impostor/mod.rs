@@ -6,7 +6,12 @@
 
 mod pipeline;
 
-pub use pipeline::{IMPOSTOR_SHADER_SOURCE, ImpostorPipeline, ImpostorRenderer, ImpostorVertex};
+pub use pipeline::{
+    IMPOSTOR_SHADER_SOURCE, ImpostorPipeline, ImpostorRenderer, ImpostorVertex,
+    generate_placeholder_texture,
+};
+
+use std::collections::HashMap;
 
 use glam::Vec3;
 
@@ -153,6 +158,48 @@ impl ImpostorState {
     }
 }
 
+/// Per-planet capture state deciding when impostor snapshots go stale.
+///
+/// Each planet remembers the view and sun directions of its last capture,
+/// and is due for a new one once either has turned by more than
+/// [`ImpostorConfig::angle_threshold`] since then.
+#[derive(Clone, Debug)]
+pub struct ImpostorCaptureTracker {
+    angle_threshold: f32,
+    planets: HashMap<u64, ImpostorState>,
+}
+
+impl ImpostorCaptureTracker {
+    /// Create a tracker using `config.angle_threshold`.
+    pub fn new(config: &ImpostorConfig) -> Self {
+        Self {
+            angle_threshold: config.angle_threshold,
+            planets: HashMap::new(),
+        }
+    }
+
+    /// Whether `planet_id` needs a new snapshot for the given directions.
+    /// Planets never captured always do.
+    pub fn needs_refresh(&self, planet_id: u64, view_dir: Vec3, sun_dir: Vec3) -> bool {
+        self.planets
+            .get(&planet_id)
+            .is_none_or(|state| state.needs_update(view_dir, sun_dir))
+    }
+
+    /// Record that `planet_id` was just captured with the given directions.
+    pub fn mark_captured(&mut self, planet_id: u64, view_dir: Vec3, sun_dir: Vec3) {
+        self.planets
+            .entry(planet_id)
+            .or_insert_with(|| ImpostorState::new(self.angle_threshold))
+            .mark_captured(view_dir, sun_dir);
+    }
+
+    /// Forget `planet_id`, so its next snapshot is captured unconditionally.
+    pub fn remove(&mut self, planet_id: u64) {
+        self.planets.remove(&planet_id);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,4 +337,40 @@ mod tests {
         assert!(!state.dirty);
         assert!(!state.needs_update(Vec3::X, Vec3::Y));
     }
+
+    /// Small view steps only trigger a refresh once their sum since the
+    /// last capture exceeds the threshold.
+    #[test]
+    fn test_capture_tracker_refreshes_after_accumulated_angle() {
+        let config = ImpostorConfig::default();
+        let mut tracker = ImpostorCaptureTracker::new(&config);
+        let step = config.angle_threshold * 0.3;
+        let view_at = |angle: f32| Vec3::new(angle.sin(), 0.0, angle.cos());
+
+        assert!(tracker.needs_refresh(7, view_at(0.0), Vec3::Y));
+        tracker.mark_captured(7, view_at(0.0), Vec3::Y);
+
+        let mut refreshes = Vec::new();
+        for i in 1..=10 {
+            let angle = step * i as f32;
+            if tracker.needs_refresh(7, view_at(angle), Vec3::Y) {
+                refreshes.push(i);
+                tracker.mark_captured(7, view_at(angle), Vec3::Y);
+            }
+        }
+        // 0.3 per step: the fourth step crosses the threshold, then the
+        // eighth step is four steps past that capture.
+        assert_eq!(refreshes, [4, 8]);
+    }
+
+    #[test]
+    fn test_capture_tracker_tracks_planets_separately() {
+        let mut tracker = ImpostorCaptureTracker::new(&ImpostorConfig::default());
+        tracker.mark_captured(1, Vec3::Z, Vec3::Y);
+        assert!(!tracker.needs_refresh(1, Vec3::Z, Vec3::Y));
+        assert!(tracker.needs_refresh(2, Vec3::Z, Vec3::Y));
+
+        tracker.remove(1);
+        assert!(tracker.needs_refresh(1, Vec3::Z, Vec3::Y));
+    }
 }
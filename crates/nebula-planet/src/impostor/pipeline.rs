@@ -4,6 +4,8 @@ use bytemuck::{Pod, Zeroable};
 use glam::{Mat4, Vec3};
 use wgpu::util::DeviceExt;
 
+use super::{ImpostorCaptureTracker, ImpostorConfig};
+
 /// WGSL source for the impostor billboard shader.
 pub const IMPOSTOR_SHADER_SOURCE: &str = include_str!("impostor.wgsl");
 
@@ -161,10 +163,18 @@ pub struct ImpostorRenderer {
     pub camera_bind_group: wgpu::BindGroup,
     /// Texture bind group (impostor snapshot).
     pub texture_bind_group: wgpu::BindGroup,
+    /// The snapshot texture sampled by the billboard.
+    texture: wgpu::Texture,
+    /// Snapshot width and height in pixels.
+    resolution: u32,
+    /// When each planet's snapshot was last captured.
+    captures: ImpostorCaptureTracker,
 }
 
 impl ImpostorRenderer {
-    /// Create a new impostor renderer with a placeholder texture.
+    /// Create a new impostor renderer with a placeholder texture of
+    /// `config.texture_resolution` pixels, re-captured per
+    /// `config.angle_threshold` (see [`refresh_snapshot`](Self::refresh_snapshot)).
     ///
     /// The texture is a small colored circle on transparent background,
     /// suitable for initial display before a proper snapshot is captured.
@@ -172,8 +182,9 @@ impl ImpostorRenderer {
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         surface_format: wgpu::TextureFormat,
-        resolution: u32,
+        config: &ImpostorConfig,
     ) -> Self {
+        let resolution = config.texture_resolution;
         let pipeline = ImpostorPipeline::new(device, surface_format);
 
         // Create a placeholder impostor texture (colored circle)
@@ -260,7 +271,47 @@ impl ImpostorRenderer {
             camera_buffer,
             camera_bind_group,
             texture_bind_group,
+            texture,
+            resolution,
+            captures: ImpostorCaptureTracker::new(config),
+        }
+    }
+
+    /// Re-capture the snapshot of `planet_id` if the view or sun direction
+    /// turned past the angle threshold since its last capture.
+    ///
+    /// `capture` renders the snapshot at the given resolution as tightly
+    /// packed RGBA8 rows; it is only called when a refresh is due. Returns
+    /// whether the texture was updated.
+    pub fn refresh_snapshot(
+        &mut self,
+        queue: &wgpu::Queue,
+        planet_id: u64,
+        view_dir: Vec3,
+        sun_dir: Vec3,
+        capture: impl FnOnce(u32) -> Vec<u8>,
+    ) -> bool {
+        if !self.captures.needs_refresh(planet_id, view_dir, sun_dir) {
+            return false;
         }
+        let pixels = capture(self.resolution);
+        queue.write_texture(
+            self.texture.as_image_copy(),
+            &pixels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * self.resolution),
+                rows_per_image: Some(self.resolution),
+            },
+            self.texture.size(),
+        );
+        self.captures.mark_captured(planet_id, view_dir, sun_dir);
+        true
+    }
+
+    /// Per-planet capture state used by [`refresh_snapshot`](Self::refresh_snapshot).
+    pub fn captures(&self) -> &ImpostorCaptureTracker {
+        &self.captures
     }
 
     /// Update the billboard vertices and camera for the current frame.
@@ -301,7 +352,9 @@ impl ImpostorRenderer {
 }
 
 /// Generate a placeholder impostor texture: a colored circle with atmosphere glow.
-fn generate_placeholder_texture(resolution: u32) -> Vec<u8> {
+///
+/// Returns `resolution`² tightly packed RGBA8 pixels.
+pub fn generate_placeholder_texture(resolution: u32) -> Vec<u8> {
     let mut pixels = Vec::with_capacity((resolution * resolution * 4) as usize);
     let center = resolution as f32 / 2.0;
     let radius = center * 0.8;
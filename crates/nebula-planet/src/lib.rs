@@ -3,39 +3,48 @@
 pub mod atmosphere;
 mod culling;
 pub mod day_night;
+mod far_terrain;
 pub mod impostor;
 pub mod ocean;
 pub mod orbital;
 mod origin;
+mod planet_quadtrees;
 pub mod planetary_coord;
 mod single_face;
 mod six_face;
 mod transition;
 
 pub use atmosphere::{AtmosphereParams, AtmosphereRenderer, AtmosphereUniform};
-pub use culling::{CullResult, LocalFrustum, PlanetBounds};
+pub use culling::{
+    CullResult, LocalFrustum, NodeBoundsCache, PlanetBounds, QuadtreeCullStats, QuadtreeCuller,
+    camera_relative_aabb, camera_relative_sphere,
+};
 pub use day_night::{
-    DayNightClock, DayNightState, ambient_intensity, star_visibility, sun_color,
-    sun_direction_from_time, sun_intensity_curve,
+    DayNightClock, DayNightState, ambient_intensity, latitude_from_up, local_time_of_day,
+    star_visibility, sun_color, sun_direction_from_time, sun_intensity_curve,
 };
+pub use far_terrain::{FAR_PATCH_RESOLUTION, FarPatchSync, FarTerrainPatch, FarTerrainPatchCache};
 pub use impostor::{
-    IMPOSTOR_INDICES, ImpostorConfig, ImpostorPipeline, ImpostorRenderer, ImpostorState,
-    ImpostorVertex, PlanetRepresentation, billboard_vertices, impostor_quad_size,
-    select_planet_representation,
+    IMPOSTOR_INDICES, ImpostorCaptureTracker, ImpostorConfig, ImpostorPipeline, ImpostorRenderer,
+    ImpostorState, ImpostorVertex, PlanetRepresentation, billboard_vertices,
+    generate_placeholder_texture, impostor_quad_size, select_planet_representation,
 };
 pub use ocean::{
     OceanParams, OceanRenderer, OceanUniform, compute_water_color, compute_wave_displacement,
     ray_sphere_intersect_f32,
 };
 pub use orbital::{
-    OrbitalMesh, OrbitalPipeline, OrbitalRenderer, PlanetUniform, generate_orbital_sphere,
-    generate_terrain_color_texture, orbital_model_matrix,
+    MAX_ORBITAL_SUBDIVISION, MIN_ORBITAL_SUBDIVISION, OrbitalMesh, OrbitalPipeline,
+    OrbitalRenderer, PlanetUniform, RING_SEGMENTS, RingMeshError, RingSystemConfig,
+    generate_orbital_sphere, generate_ring_mesh, generate_terrain_color_texture,
+    orbital_model_matrix, ring_model_matrix, select_orbital_lod,
 };
 pub use origin::OriginManager;
+pub use planet_quadtrees::PlanetQuadtrees;
 pub use planetary_coord::{PlanetBody, PlanetaryCoord};
 pub use single_face::{
     FaceChunkMesh, SingleFaceLoader, SingleFaceRenderData, build_face_render_data,
     create_face_camera,
 };
-pub use six_face::{FaceState, PlanetFaces, create_orbit_camera};
+pub use six_face::{FaceState, PlanetFaces, create_orbit_camera, orbit_camera_eye};
 pub use transition::{TransitionConfig, TransitionUniform, chunk_budget_for_altitude};
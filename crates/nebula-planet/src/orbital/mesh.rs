@@ -1,5 +1,6 @@
-//! Icosphere mesh generation for orbital planet rendering.
+//! Icosphere mesh generation and upload for orbital planet rendering.
 
+use bytemuck::{Pod, Zeroable};
 use glam::Vec3;
 
 /// A mesh suitable for orbital-distance planet rendering.
@@ -73,6 +74,50 @@ pub fn generate_orbital_sphere(subdivisions: u32) -> OrbitalMesh {
     }
 }
 
+/// Coarsest subdivision [`select_orbital_lod`] returns, used for a planet
+/// that is a speck on screen.
+pub const MIN_ORBITAL_SUBDIVISION: u32 = 1;
+
+/// Finest subdivision [`select_orbital_lod`] returns, used when the planet
+/// fills the screen just before the voxel terrain takes over.
+pub const MAX_ORBITAL_SUBDIVISION: u32 = 6;
+
+/// Vertical field of view the LOD selection assumes (matches the orbit camera).
+const REFERENCE_FOV_Y: f32 = 70.0 * std::f32::consts::PI / 180.0;
+
+/// Screen height in pixels the LOD selection assumes.
+const REFERENCE_SCREEN_HEIGHT: f32 = 1080.0;
+
+/// Largest allowed gap, in pixels, between the true silhouette and the
+/// faceted one.
+const MAX_SILHOUETTE_ERROR_PX: f32 = 0.25;
+
+/// Angle subtended by an icosahedron edge at the sphere's center (radians).
+const ICOSAHEDRON_EDGE_ANGLE: f32 = 1.107_148_7;
+
+/// Pick the icosphere subdivision for a planet of `planet_radius` seen from
+/// `distance` away from its center.
+///
+/// The planet's on-screen radius is estimated for a reference camera, and
+/// the coarsest level whose facets bulge the silhouette by no more than a
+/// fraction of a pixel is chosen, clamped to
+/// [`MIN_ORBITAL_SUBDIVISION`]..=[`MAX_ORBITAL_SUBDIVISION`].
+pub fn select_orbital_lod(distance: f32, planet_radius: f32) -> u32 {
+    if distance <= planet_radius {
+        return MAX_ORBITAL_SUBDIVISION;
+    }
+    let angular_radius = (planet_radius / distance).asin();
+    let screen_radius =
+        angular_radius.tan() / (REFERENCE_FOV_Y * 0.5).tan() * (REFERENCE_SCREEN_HEIGHT * 0.5);
+
+    (MIN_ORBITAL_SUBDIVISION..MAX_ORBITAL_SUBDIVISION)
+        .find(|&level| {
+            let edge_angle = ICOSAHEDRON_EDGE_ANGLE / (1u32 << level) as f32;
+            screen_radius * (1.0 - (edge_angle * 0.5).cos()) <= MAX_SILHOUETTE_ERROR_PX
+        })
+        .unwrap_or(MAX_ORBITAL_SUBDIVISION)
+}
+
 /// Subdivide each triangle into 4 by splitting edges at midpoints.
 fn subdivide(positions: &mut Vec<Vec3>, indices: &mut Vec<u32>) {
     use std::collections::HashMap;
@@ -108,6 +153,84 @@ fn subdivide(positions: &mut Vec<Vec3>, indices: &mut Vec<u32>) {
     *indices = new_indices;
 }
 
+/// Vertex layout for orbital mesh: position (vec3), normal (vec3), uv (vec2).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct OrbitalVertex {
+    /// Position on unit sphere.
+    pub position: [f32; 3],
+    /// Normal (same as position for unit sphere).
+    pub normal: [f32; 3],
+    /// Equirectangular UV.
+    pub uv: [f32; 2],
+}
+
+impl OrbitalVertex {
+    /// Vertex buffer layout for the orbital shader.
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<OrbitalVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: 12,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                wgpu::VertexAttribute {
+                    offset: 24,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32x2,
+                },
+            ],
+        }
+    }
+}
+
+/// GPU buffers for one orbital mesh: an icosphere level or a ring.
+pub(super) struct OrbitalMeshBuffers {
+    pub(super) vertex_buffer: wgpu::Buffer,
+    pub(super) index_buffer: wgpu::Buffer,
+    pub(super) index_count: u32,
+}
+
+impl OrbitalMeshBuffers {
+    pub(super) fn upload(device: &wgpu::Device, mesh: &OrbitalMesh) -> Self {
+        use wgpu::util::DeviceExt;
+
+        let vertices: Vec<OrbitalVertex> = (0..mesh.positions.len())
+            .map(|i| OrbitalVertex {
+                position: mesh.positions[i].to_array(),
+                normal: mesh.normals[i].to_array(),
+                uv: mesh.uvs[i],
+            })
+            .collect();
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("orbital-vertices"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("orbital-indices"),
+            contents: bytemuck::cast_slice(&mesh.indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        Self {
+            vertex_buffer,
+            index_buffer,
+            index_count: mesh.indices.len() as u32,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,4 +283,39 @@ mod tests {
             assert!(diff < 1e-6, "Normal should equal position on unit sphere");
         }
     }
+
+    #[test]
+    fn test_vertex_count_increases_with_subdivision() {
+        let counts: Vec<usize> = (0..=MAX_ORBITAL_SUBDIVISION)
+            .map(|level| generate_orbital_sphere(level).positions.len())
+            .collect();
+        assert!(
+            counts.windows(2).all(|w| w[0] < w[1]),
+            "vertex counts not increasing: {counts:?}"
+        );
+        // An icosphere at level n has 10 * 4^n + 2 vertices.
+        assert_eq!(counts[0], 12);
+        assert_eq!(counts[3], 642);
+    }
+
+    #[test]
+    fn test_select_orbital_lod_decreases_with_distance() {
+        let radius = 6_371_000.0;
+        let levels: Vec<u32> = [1.01, 1.1, 2.0, 4.0, 20.0, 200.0, 10_000.0]
+            .iter()
+            .map(|&k| select_orbital_lod(radius * k, radius))
+            .collect();
+        assert!(
+            levels.windows(2).all(|w| w[0] >= w[1]),
+            "LOD should not increase with distance: {levels:?}"
+        );
+        assert_eq!(levels[0], MAX_ORBITAL_SUBDIVISION);
+        assert_eq!(*levels.last().unwrap(), MIN_ORBITAL_SUBDIVISION);
+    }
+
+    #[test]
+    fn test_select_orbital_lod_inside_planet_is_finest() {
+        assert_eq!(select_orbital_lod(0.0, 100.0), MAX_ORBITAL_SUBDIVISION);
+        assert_eq!(select_orbital_lod(50.0, 100.0), MAX_ORBITAL_SUBDIVISION);
+    }
 }
@@ -1,4 +1,5 @@
-//! Orbital-distance planet rendering: icosphere mesh, terrain color texture, and GPU pipeline.
+//! Orbital-distance planet rendering: icosphere mesh, ring systems, terrain color
+//! texture, and GPU pipeline.
 //!
 //! When the camera is far from the planet (orbital distance), individual voxels
 //! are sub-pixel. This module renders the planet as a smooth textured sphere
@@ -6,11 +7,18 @@
 
 mod mesh;
 mod pipeline;
+mod rings;
 pub mod texture;
 
-pub use mesh::{OrbitalMesh, generate_orbital_sphere};
+pub use mesh::{
+    MAX_ORBITAL_SUBDIVISION, MIN_ORBITAL_SUBDIVISION, OrbitalMesh, OrbitalVertex,
+    generate_orbital_sphere, select_orbital_lod,
+};
 pub use pipeline::{
-    ORBITAL_SHADER_SOURCE, OrbitalPipeline, OrbitalRenderer, OrbitalVertex, PlanetUniform,
-    orbital_model_matrix,
+    ORBITAL_SHADER_SOURCE, OrbitalPipeline, OrbitalRenderer, PlanetUniform, orbital_model_matrix,
+};
+pub use rings::{
+    RING_SEGMENTS, RING_SHADER_SOURCE, RingMeshError, RingSystemConfig, generate_ring_mesh,
+    ring_model_matrix,
 };
 pub use texture::generate_terrain_color_texture;
@@ -4,7 +4,10 @@ use bytemuck::{Pod, Zeroable};
 use glam::{Mat4, Vec3};
 use nebula_render::DepthBuffer;
 
-use super::mesh::OrbitalMesh;
+use std::collections::HashMap;
+
+use super::mesh::{OrbitalMeshBuffers, OrbitalVertex, generate_orbital_sphere, select_orbital_lod};
+use super::rings::{RingBuffers, RingMeshError, RingSystemConfig, create_ring_pipeline};
 
 /// WGSL source for the orbital planet shader.
 pub const ORBITAL_SHADER_SOURCE: &str = include_str!("orbital.wgsl");
@@ -35,49 +38,13 @@ pub fn orbital_model_matrix(planet_center: Vec3, planet_radius: f32, rotation_an
         * Mat4::from_scale(Vec3::splat(planet_radius))
 }
 
-/// Vertex layout for orbital mesh: position (vec3), normal (vec3), uv (vec2).
-#[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
-pub struct OrbitalVertex {
-    /// Position on unit sphere.
-    pub position: [f32; 3],
-    /// Normal (same as position for unit sphere).
-    pub normal: [f32; 3],
-    /// Equirectangular UV.
-    pub uv: [f32; 2],
-}
-
-impl OrbitalVertex {
-    /// Vertex buffer layout for the orbital shader.
-    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
-        wgpu::VertexBufferLayout {
-            array_stride: std::mem::size_of::<OrbitalVertex>() as wgpu::BufferAddress,
-            step_mode: wgpu::VertexStepMode::Vertex,
-            attributes: &[
-                wgpu::VertexAttribute {
-                    offset: 0,
-                    shader_location: 0,
-                    format: wgpu::VertexFormat::Float32x3,
-                },
-                wgpu::VertexAttribute {
-                    offset: 12,
-                    shader_location: 1,
-                    format: wgpu::VertexFormat::Float32x3,
-                },
-                wgpu::VertexAttribute {
-                    offset: 24,
-                    shader_location: 2,
-                    format: wgpu::VertexFormat::Float32x2,
-                },
-            ],
-        }
-    }
-}
-
-/// Render pipeline for orbital planet sphere.
+/// Render pipelines for the orbital planet sphere and its rings.
 pub struct OrbitalPipeline {
     /// The wgpu render pipeline.
     pub pipeline: wgpu::RenderPipeline,
+    /// Alpha-blended, double-sided pipeline for ring systems. Shares both
+    /// bind group layouts with the sphere pipeline.
+    pub ring_pipeline: wgpu::RenderPipeline,
     /// Camera bind group layout (group 0).
     pub camera_bind_group_layout: wgpu::BindGroupLayout,
     /// Planet bind group layout (group 1): texture + sampler + uniform.
@@ -187,8 +154,11 @@ impl OrbitalPipeline {
             cache: None,
         });
 
+        let ring_pipeline = create_ring_pipeline(device, &pipeline_layout, surface_format);
+
         Self {
             pipeline,
+            ring_pipeline,
             camera_bind_group_layout,
             planet_bind_group_layout,
         }
@@ -197,15 +167,16 @@ impl OrbitalPipeline {
 
 /// High-level orbital planet renderer. Owns GPU resources for rendering
 /// the planet as a textured sphere from orbit.
+///
+/// Icosphere buffers are generated lazily and cached per subdivision level,
+/// so switching LOD back and forth only uploads each level once.
 pub struct OrbitalRenderer {
     /// The render pipeline.
     pub pipeline: OrbitalPipeline,
-    /// Vertex buffer for the icosphere.
-    pub vertex_buffer: wgpu::Buffer,
-    /// Index buffer for the icosphere.
-    pub index_buffer: wgpu::Buffer,
-    /// Number of indices.
-    pub index_count: u32,
+    /// Uploaded icosphere buffers keyed by subdivision level.
+    meshes: HashMap<u32, OrbitalMeshBuffers>,
+    /// Subdivision level drawn by [`render`](Self::render).
+    subdivision: u32,
     /// Planet uniform buffer.
     pub planet_uniform_buffer: wgpu::Buffer,
     /// Camera uniform buffer.
@@ -216,16 +187,28 @@ pub struct OrbitalRenderer {
     pub planet_bind_group: wgpu::BindGroup,
     /// Planet radius.
     pub planet_radius: f32,
+    /// Meters per renderer unit, used to convert ring radii when a ring
+    /// system is uploaded. Defaults to 1 (renderer units are meters).
+    pub meters_per_unit: f64,
+    /// Device the renderer's resources live on, kept to upload ring meshes.
+    device: wgpu::Device,
+    /// Sampler shared by the terrain and ring textures.
+    sampler: wgpu::Sampler,
+    /// 1×1 opaque white texture, the ring profile until one is bound.
+    white_view: wgpu::TextureView,
+    /// Ring system drawn around the planet, if any.
+    rings: Option<RingBuffers>,
 }
 
 impl OrbitalRenderer {
-    /// Create a new orbital renderer with the given mesh and terrain texture.
+    /// Create a new orbital renderer drawing an icosphere of `subdivision`
+    /// levels with the given terrain texture.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         surface_format: wgpu::TextureFormat,
-        mesh: &OrbitalMesh,
+        subdivision: u32,
         terrain_pixels: &[[u8; 4]],
         tex_width: u32,
         tex_height: u32,
@@ -235,26 +218,11 @@ impl OrbitalRenderer {
 
         let pipeline = OrbitalPipeline::new(device, surface_format);
 
-        // Build vertex data
-        let vertices: Vec<OrbitalVertex> = (0..mesh.positions.len())
-            .map(|i| OrbitalVertex {
-                position: mesh.positions[i].to_array(),
-                normal: mesh.normals[i].to_array(),
-                uv: mesh.uvs[i],
-            })
-            .collect();
-
-        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("orbital-vertices"),
-            contents: bytemuck::cast_slice(&vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-
-        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("orbital-indices"),
-            contents: bytemuck::cast_slice(&mesh.indices),
-            usage: wgpu::BufferUsages::INDEX,
-        });
+        let mut meshes = HashMap::new();
+        meshes.insert(
+            subdivision,
+            OrbitalMeshBuffers::upload(device, &generate_orbital_sphere(subdivision)),
+        );
 
         // Terrain texture
         let tex_data: Vec<u8> = terrain_pixels
@@ -288,6 +256,28 @@ impl OrbitalRenderer {
             ..Default::default()
         });
 
+        let white_view = device
+            .create_texture_with_data(
+                queue,
+                &wgpu::TextureDescriptor {
+                    label: Some("orbital-ring-default-texture"),
+                    size: wgpu::Extent3d {
+                        width: 1,
+                        height: 1,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
+                },
+                wgpu::util::TextureDataOrder::LayerMajor,
+                &[255; 4],
+            )
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
         // Planet uniform buffer
         let planet_uniform = PlanetUniform {
             model: Mat4::IDENTITY.to_cols_array_2d(),
@@ -339,14 +329,98 @@ impl OrbitalRenderer {
 
         Self {
             pipeline,
-            vertex_buffer,
-            index_buffer,
-            index_count: mesh.indices.len() as u32,
+            meshes,
+            subdivision,
             planet_uniform_buffer,
             camera_buffer,
             camera_bind_group,
             planet_bind_group,
             planet_radius,
+            meters_per_unit: 1.0,
+            device: device.clone(),
+            sampler,
+            white_view,
+            rings: None,
+        }
+    }
+
+    /// Subdivision level currently drawn.
+    pub fn subdivision(&self) -> u32 {
+        self.subdivision
+    }
+
+    /// Number of indices drawn at the current subdivision level.
+    pub fn index_count(&self) -> u32 {
+        self.meshes
+            .get(&self.subdivision)
+            .map_or(0, |mesh| mesh.index_count)
+    }
+
+    /// Draw the icosphere at `subdivision` levels from now on, generating
+    /// and uploading it the first time the level is used.
+    pub fn set_subdivision(&mut self, device: &wgpu::Device, subdivision: u32) {
+        self.meshes.entry(subdivision).or_insert_with(|| {
+            OrbitalMeshBuffers::upload(device, &generate_orbital_sphere(subdivision))
+        });
+        self.subdivision = subdivision;
+    }
+
+    /// Pick the subdivision for a camera `distance` from the planet center
+    /// with [`select_orbital_lod`] and switch to it. Returns the chosen level.
+    pub fn select_lod(&mut self, device: &wgpu::Device, distance: f32) -> u32 {
+        let level = select_orbital_lod(distance, self.planet_radius);
+        self.set_subdivision(device, level);
+        level
+    }
+
+    /// Give the planet a ring system, or remove it with `None`.
+    ///
+    /// The ring mesh is converted to renderer units with
+    /// [`meters_per_unit`](Self::meters_per_unit), uploaded immediately and
+    /// drawn with a uniform profile; if the config names an
+    /// [`opacity_texture`](RingSystemConfig::opacity_texture), resolve it
+    /// through the texture manager and pass its view to
+    /// [`bind_ring_texture`](Self::bind_ring_texture).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, keeping the current rings, if the config fails
+    /// [`RingSystemConfig::validate`].
+    pub fn set_ring_system(
+        &mut self,
+        config: Option<RingSystemConfig>,
+    ) -> Result<(), RingMeshError> {
+        self.rings = match config {
+            Some(config) => Some(RingBuffers::new(
+                &self.device,
+                config,
+                &self.pipeline.planet_bind_group_layout,
+                &self.sampler,
+                &self.white_view,
+                self.planet_radius,
+                self.meters_per_unit,
+            )?),
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// The current ring system, if any.
+    pub fn ring_system(&self) -> Option<&RingSystemConfig> {
+        self.rings.as_ref().map(|rings| &rings.config)
+    }
+
+    /// Sample the ring profile from `view` (the resolved
+    /// [`opacity_texture`](RingSystemConfig::opacity_texture)). Does nothing
+    /// without a ring system.
+    pub fn bind_ring_texture(&mut self, view: &wgpu::TextureView) {
+        if let Some(rings) = &mut self.rings {
+            rings.bind_texture(
+                &self.device,
+                &self.pipeline.planet_bind_group_layout,
+                &self.sampler,
+                view,
+            );
         }
     }
 
@@ -388,64 +462,31 @@ impl OrbitalRenderer {
             0,
             bytemuck::cast_slice(&[planet_uniform]),
         );
+
+        if let Some(rings) = &self.rings {
+            rings.write_uniform(queue, planet_uniform, planet_center);
+        }
     }
 
-    /// Render the orbital planet sphere.
+    /// Render the orbital planet sphere, then its rings (if any) blended
+    /// over it.
     pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        let Some(mesh) = self.meshes.get(&self.subdivision) else {
+            return;
+        };
         render_pass.set_pipeline(&self.pipeline.pipeline);
         render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
         render_pass.set_bind_group(1, &self.planet_bind_group, &[]);
-        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-        render_pass.draw_indexed(0..self.index_count, 0, 0..1);
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_orbital_model_matrix_rotation() {
-        let t0 = orbital_model_matrix(Vec3::ZERO, 1.0, 0.0);
-        let t1 = orbital_model_matrix(Vec3::ZERO, 1.0, 0.5);
-        let t2 = orbital_model_matrix(Vec3::ZERO, 1.0, 1.0);
-
-        assert_ne!(t0, t1, "Model matrix should change with rotation");
-        assert_ne!(t1, t2, "Model matrix should change with rotation");
-
-        let equator_point = glam::Vec4::new(1.0, 0.0, 0.0, 1.0);
-        let p0 = t0 * equator_point;
-        let p1 = t1 * equator_point;
-        let diff = (p0 - p1).length();
-        assert!(
-            diff > 0.01,
-            "Equator point should move with rotation, diff = {diff}"
-        );
-    }
-
-    #[test]
-    fn test_planet_uniform_size_alignment() {
-        assert_eq!(std::mem::size_of::<PlanetUniform>() % 16, 0);
-    }
+        render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
 
-    #[test]
-    fn test_planet_size_decreases_with_distance() {
-        let planet_radius = 200.0_f32;
-        let distances = [
-            planet_radius * 2.0,
-            planet_radius * 5.0,
-            planet_radius * 20.0,
-        ];
-        let mut prev_angular_size = f32::MAX;
-
-        for &dist in &distances {
-            let angular_size = 2.0 * (planet_radius / dist).asin();
-            assert!(
-                angular_size < prev_angular_size,
-                "Planet should appear smaller at distance {dist}"
-            );
-            prev_angular_size = angular_size;
+        if let Some(rings) = &self.rings {
+            rings.draw(render_pass, &self.pipeline.ring_pipeline);
         }
     }
 }
+
+#[cfg(test)]
+#[path = "pipeline_tests.rs"]
+mod tests;
@@ -0,0 +1,47 @@
+//! Unit tests for the orbital planet pipeline.
+
+use super::*;
+
+#[test]
+fn test_orbital_model_matrix_rotation() {
+    let t0 = orbital_model_matrix(Vec3::ZERO, 1.0, 0.0);
+    let t1 = orbital_model_matrix(Vec3::ZERO, 1.0, 0.5);
+    let t2 = orbital_model_matrix(Vec3::ZERO, 1.0, 1.0);
+
+    assert_ne!(t0, t1, "Model matrix should change with rotation");
+    assert_ne!(t1, t2, "Model matrix should change with rotation");
+
+    let equator_point = glam::Vec4::new(1.0, 0.0, 0.0, 1.0);
+    let p0 = t0 * equator_point;
+    let p1 = t1 * equator_point;
+    let diff = (p0 - p1).length();
+    assert!(
+        diff > 0.01,
+        "Equator point should move with rotation, diff = {diff}"
+    );
+}
+
+#[test]
+fn test_planet_uniform_size_alignment() {
+    assert_eq!(std::mem::size_of::<PlanetUniform>() % 16, 0);
+}
+
+#[test]
+fn test_planet_size_decreases_with_distance() {
+    let planet_radius = 200.0_f32;
+    let distances = [
+        planet_radius * 2.0,
+        planet_radius * 5.0,
+        planet_radius * 20.0,
+    ];
+    let mut prev_angular_size = f32::MAX;
+
+    for &dist in &distances {
+        let angular_size = 2.0 * (planet_radius / dist).asin();
+        assert!(
+            angular_size < prev_angular_size,
+            "Planet should appear smaller at distance {dist}"
+        );
+        prev_angular_size = angular_size;
+    }
+}
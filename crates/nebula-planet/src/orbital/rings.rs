@@ -0,0 +1,403 @@
+//! Planetary ring systems for gas giants: annulus geometry, the blended
+//! ring pipeline and the GPU resources the orbital renderer draws them with.
+
+use glam::{Mat4, Vec3};
+use nebula_render::{DepthBuffer, TextureId};
+
+use super::mesh::{OrbitalMesh, OrbitalMeshBuffers, OrbitalVertex};
+use super::pipeline::PlanetUniform;
+
+/// WGSL source for the planetary ring shader.
+pub const RING_SHADER_SOURCE: &str = include_str!("rings.wgsl");
+
+/// Arc slices used for ring meshes drawn by the orbital renderer.
+pub const RING_SEGMENTS: u32 = 128;
+
+/// Fewest arc slices that still enclose the planet.
+const MIN_RING_SEGMENTS: u32 = 3;
+
+/// Shape and appearance of a planet's ring system.
+///
+/// Radii are measured in meters from the planet center; the orbital
+/// renderer converts them to its own units with
+/// [`OrbitalRenderer::meters_per_unit`](super::OrbitalRenderer::meters_per_unit)
+/// when it uploads the ring mesh.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RingSystemConfig {
+    /// Radius of the inner edge of the rings, in meters.
+    pub inner_radius_m: f64,
+    /// Radius of the outer edge of the rings, in meters.
+    pub outer_radius_m: f64,
+    /// Radial opacity/color profile, sampled with `u` running from the
+    /// inner edge (0) to the outer edge (1). `None` draws uniform rings.
+    pub opacity_texture: Option<TextureId>,
+    /// Tilt of the ring plane away from the planet's equatorial (XZ)
+    /// plane, in degrees about the X axis.
+    pub tilt_deg: f32,
+    /// How densely the rings are packed, from 0 (invisible) to 1 (opaque);
+    /// scales the ring opacity.
+    pub particle_density: f32,
+}
+
+impl RingSystemConfig {
+    /// Check that the radii describe an annulus: finite, with
+    /// `0 <= inner < outer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RingMeshError::InvalidRadii`] otherwise.
+    pub fn validate(&self) -> Result<(), RingMeshError> {
+        let (inner, outer) = (self.inner_radius_m, self.outer_radius_m);
+        if inner.is_finite() && outer.is_finite() && 0.0 <= inner && inner < outer {
+            Ok(())
+        } else {
+            Err(RingMeshError::InvalidRadii { inner, outer })
+        }
+    }
+}
+
+/// Why a ring mesh could not be generated.
+#[derive(Debug, Clone, Copy, PartialEq, thiserror::Error)]
+pub enum RingMeshError {
+    /// Fewer than three arc slices were requested.
+    #[error("a ring needs at least {MIN_RING_SEGMENTS} segments, got {0}")]
+    TooFewSegments(u32),
+    /// The radii do not satisfy `0 <= inner < outer`, or are not finite.
+    #[error("ring radii must satisfy 0 <= inner < outer, got inner {inner} and outer {outer}")]
+    InvalidRadii {
+        /// Requested inner radius.
+        inner: f64,
+        /// Requested outer radius.
+        outer: f64,
+    },
+}
+
+/// Generate a flat annulus in the XZ plane for a ring system.
+///
+/// The disc is split into `segments` arc slices, each a quad of two
+/// triangles from the inner to the outer radius. UV `u` runs radially from
+/// the inner edge (0) to the outer edge (1) and `v` around the ring; the
+/// first column is repeated at `v = 1` so the texture wraps without a seam.
+/// Normals point along +Y and positions are in meters.
+///
+/// # Errors
+///
+/// Returns an error if `segments` is less than 3 or the config fails
+/// [`RingSystemConfig::validate`].
+pub fn generate_ring_mesh(
+    config: &RingSystemConfig,
+    segments: u32,
+) -> Result<OrbitalMesh, RingMeshError> {
+    if segments < MIN_RING_SEGMENTS {
+        return Err(RingMeshError::TooFewSegments(segments));
+    }
+    config.validate()?;
+
+    let inner = config.inner_radius_m;
+    let outer = config.outer_radius_m;
+    let columns = segments as usize + 1;
+    let mut positions = Vec::with_capacity(columns * 2);
+    let mut uvs = Vec::with_capacity(columns * 2);
+
+    for i in 0..columns {
+        let t = i as f64 / segments as f64;
+        let (sin, cos) = (t * std::f64::consts::TAU).sin_cos();
+        for (radius, u) in [(inner, 0.0), (outer, 1.0)] {
+            positions.push(Vec3::new((cos * radius) as f32, 0.0, (sin * radius) as f32));
+            uvs.push([u, t as f32]);
+        }
+    }
+
+    let mut indices = Vec::with_capacity(segments as usize * 6);
+    for i in 0..segments {
+        let (inner0, outer0) = (i * 2, i * 2 + 1);
+        let (inner1, outer1) = (inner0 + 2, outer0 + 2);
+        // Counter-clockwise seen from +Y.
+        indices.extend_from_slice(&[inner0, inner1, outer0, outer0, inner1, outer1]);
+    }
+
+    Ok(OrbitalMesh {
+        normals: vec![Vec3::Y; positions.len()],
+        positions,
+        uvs,
+        indices,
+    })
+}
+
+/// [`generate_ring_mesh`] with positions converted from meters to renderer
+/// units of `meters_per_unit` meters each.
+pub(super) fn generate_ring_mesh_in_units(
+    config: &RingSystemConfig,
+    segments: u32,
+    meters_per_unit: f64,
+) -> Result<OrbitalMesh, RingMeshError> {
+    let mut mesh = generate_ring_mesh(config, segments)?;
+    let scale = (1.0 / meters_per_unit) as f32;
+    for position in &mut mesh.positions {
+        *position *= scale;
+    }
+    Ok(mesh)
+}
+
+/// Alpha-blended, double-sided pipeline for ring systems, using the orbital
+/// sphere's pipeline `layout`.
+pub(super) fn create_ring_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    surface_format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    let ring_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("orbital-ring-shader"),
+        source: wgpu::ShaderSource::Wgsl(RING_SHADER_SOURCE.into()),
+    });
+
+    // Rings are seen from both sides and are translucent, so they are
+    // drawn unculled and do not write depth.
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("orbital-ring-pipeline"),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: &ring_shader,
+            entry_point: Some("vs_ring"),
+            buffers: &[OrbitalVertex::layout()],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: DepthBuffer::FORMAT,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::GreaterEqual, // reverse-Z
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &ring_shader,
+            entry_point: Some("fs_ring"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: surface_format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
+/// GPU resources for a planet's ring system.
+pub(super) struct RingBuffers {
+    pub(super) config: RingSystemConfig,
+    mesh: OrbitalMeshBuffers,
+    /// Ring [`PlanetUniform`], whose model matrix carries the ring tilt.
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+impl RingBuffers {
+    /// Generate and upload the ring mesh for `config` in units of
+    /// `meters_per_unit` meters, sampling the ring profile from `view`.
+    pub(super) fn new(
+        device: &wgpu::Device,
+        config: RingSystemConfig,
+        layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+        view: &wgpu::TextureView,
+        planet_radius: f32,
+        meters_per_unit: f64,
+    ) -> Result<Self, RingMeshError> {
+        use wgpu::util::DeviceExt;
+
+        let mesh = generate_ring_mesh_in_units(&config, RING_SEGMENTS, meters_per_unit)?;
+        let mesh = OrbitalMeshBuffers::upload(device, &mesh);
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("orbital-ring-uniform"),
+            contents: bytemuck::cast_slice(&[PlanetUniform {
+                model: ring_model_matrix(Vec3::ZERO, config.tilt_deg).to_cols_array_2d(),
+                sun_direction: [0.0, 1.0, 0.0],
+                planet_radius,
+                blend_alpha: config.particle_density.clamp(0.0, 1.0),
+                _padding: [0.0; 3],
+            }]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let bind_group = ring_bind_group(device, layout, sampler, view, &uniform_buffer);
+        Ok(Self {
+            config,
+            mesh,
+            uniform_buffer,
+            bind_group,
+        })
+    }
+
+    /// Sample the ring profile from `view` from now on.
+    pub(super) fn bind_texture(
+        &mut self,
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+        view: &wgpu::TextureView,
+    ) {
+        self.bind_group = ring_bind_group(device, layout, sampler, view, &self.uniform_buffer);
+    }
+
+    /// Write the ring uniform for a frame from the planet's uniform, tilting
+    /// the ring plane and fading it by the particle density.
+    pub(super) fn write_uniform(
+        &self,
+        queue: &wgpu::Queue,
+        planet: PlanetUniform,
+        planet_center: Vec3,
+    ) {
+        let ring_uniform = PlanetUniform {
+            model: ring_model_matrix(planet_center, self.config.tilt_deg).to_cols_array_2d(),
+            blend_alpha: planet.blend_alpha * self.config.particle_density.clamp(0.0, 1.0),
+            ..planet
+        };
+        queue.write_buffer(
+            &self.uniform_buffer,
+            0,
+            bytemuck::cast_slice(&[ring_uniform]),
+        );
+    }
+
+    /// Draw the rings with `pipeline`, reusing the camera bind group already
+    /// set on `render_pass`.
+    pub(super) fn draw<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        pipeline: &'a wgpu::RenderPipeline,
+    ) {
+        render_pass.set_pipeline(pipeline);
+        render_pass.set_bind_group(1, &self.bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.mesh.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..self.mesh.index_count, 0, 0..1);
+    }
+}
+
+fn ring_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    sampler: &wgpu::Sampler,
+    view: &wgpu::TextureView,
+    uniform_buffer: &wgpu::Buffer,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("orbital-ring-bg"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: uniform_buffer.as_entire_binding(),
+            },
+        ],
+    })
+}
+
+/// Model matrix for a ring mesh: the ring plane tilted by `tilt_deg` about
+/// the X axis and centered on the planet. The rings do not spin with the
+/// planet, so there is no rotation angle.
+pub fn ring_model_matrix(planet_center: Vec3, tilt_deg: f32) -> Mat4 {
+    Mat4::from_translation(planet_center) * Mat4::from_rotation_x(tilt_deg.to_radians())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saturn_like() -> RingSystemConfig {
+        RingSystemConfig {
+            inner_radius_m: 74_500_000.0,
+            outer_radius_m: 140_220_000.0,
+            opacity_texture: None,
+            tilt_deg: 26.7,
+            particle_density: 0.8,
+        }
+    }
+
+    #[test]
+    fn test_ring_mesh_has_two_triangles_per_segment() {
+        for segments in [3, 16, RING_SEGMENTS] {
+            let mesh = generate_ring_mesh(&saturn_like(), segments).unwrap();
+            assert_eq!(mesh.indices.len() / 3, segments as usize * 2);
+            assert_eq!(mesh.positions.len(), mesh.uvs.len());
+            assert_eq!(mesh.positions.len(), mesh.normals.len());
+            assert!(
+                mesh.indices
+                    .iter()
+                    .all(|&i| (i as usize) < mesh.positions.len())
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_vertex_inside_inner_radius() {
+        let config = saturn_like();
+        let mesh = generate_ring_mesh(&config, 64).unwrap();
+        let inner = config.inner_radius_m as f32;
+        let outer = config.outer_radius_m as f32;
+        for p in &mesh.positions {
+            let r = p.length();
+            assert!(r >= inner * (1.0 - 1e-6), "vertex at {r} inside {inner}");
+            assert!(r <= outer * (1.0 + 1e-6), "vertex at {r} beyond {outer}");
+            assert_eq!(p.y, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_ring_radii_convert_from_meters_to_units() {
+        let config = saturn_like();
+        let mesh = generate_ring_mesh_in_units(&config, 16, 1_000.0).unwrap();
+        let (inner, outer) = (mesh.positions[0].length(), mesh.positions[1].length());
+        assert!((inner - 74_500.0).abs() < 0.1, "inner edge at {inner} km");
+        assert!((outer - 140_220.0).abs() < 0.1, "outer edge at {outer} km");
+    }
+
+    #[test]
+    fn test_invalid_ring_config_is_an_error() {
+        assert!(matches!(
+            generate_ring_mesh(&saturn_like(), 2),
+            Err(RingMeshError::TooFewSegments(2))
+        ));
+        for (inner, outer) in [(-1.0, 5.0), (5.0, 5.0), (6.0, 5.0), (0.0, f64::NAN)] {
+            let config = RingSystemConfig {
+                inner_radius_m: inner,
+                outer_radius_m: outer,
+                ..saturn_like()
+            };
+            assert!(matches!(
+                generate_ring_mesh(&config, 16),
+                Err(RingMeshError::InvalidRadii { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_ring_tilt_rotates_plane_normal() {
+        let flat = ring_model_matrix(Vec3::ZERO, 0.0);
+        assert_eq!(flat.transform_vector3(Vec3::Y), Vec3::Y);
+
+        let tilted = ring_model_matrix(Vec3::new(5.0, 0.0, 0.0), 30.0);
+        let normal = tilted.transform_vector3(Vec3::Y);
+        assert!((normal.angle_between(Vec3::Y) - 30f32.to_radians()).abs() < 1e-5);
+        assert_eq!(
+            tilted.transform_point3(Vec3::ZERO),
+            Vec3::new(5.0, 0.0, 0.0)
+        );
+    }
+}
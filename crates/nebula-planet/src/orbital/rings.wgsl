@@ -0,0 +1,56 @@
+// Planetary ring shader.
+// Draws a flat annulus with a radial opacity profile, lit from both sides.
+
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+};
+
+struct PlanetUniform {
+    model: mat4x4<f32>,
+    sun_direction: vec3<f32>,
+    planet_radius: f32,
+    blend_alpha: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+};
+
+@group(0) @binding(0) var<uniform> camera: CameraUniform;
+
+@group(1) @binding(0) var ring_texture: texture_2d<f32>;
+@group(1) @binding(1) var ring_sampler: sampler;
+@group(1) @binding(2) var<uniform> ring: PlanetUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) world_normal: vec3<f32>,
+};
+
+@vertex
+fn vs_ring(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * ring.model * vec4<f32>(in.position, 1.0);
+    out.uv = in.uv;
+    out.world_normal = normalize((ring.model * vec4<f32>(in.normal, 0.0)).xyz);
+    return out;
+}
+
+@fragment
+fn fs_ring(in: VertexOutput) -> @location(0) vec4<f32> {
+    // The profile varies only radially, so sample along u.
+    let profile = textureSample(ring_texture, ring_sampler, vec2<f32>(in.uv.x, 0.5));
+
+    // Thin rings scatter light toward both faces.
+    let ndotl = abs(dot(in.world_normal, ring.sun_direction));
+    let ambient = vec3<f32>(0.08, 0.08, 0.12);
+    let lit_color = profile.rgb * (ambient + ndotl * vec3<f32>(1.0, 0.98, 0.92));
+
+    return vec4<f32>(lit_color, profile.a * ring.blend_alpha);
+}
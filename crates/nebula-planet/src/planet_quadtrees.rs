@@ -0,0 +1,195 @@
+//! The six face quadtrees of a planet, kept across sessions.
+//!
+//! [`PlanetQuadtrees`] owns one [`FaceQuadtreeLod`] per cube face. With a
+//! [`QuadtreeSnapshotStore`] attached, [`start`](PlanetQuadtrees::start)
+//! restores the leaf sets saved by the last
+//! [`shutdown`](PlanetQuadtrees::shutdown) of the same planet and seed, so
+//! returning to a place does not re-subdivide every face from its root.
+
+use std::io;
+
+use nebula_cubesphere::{CubeFace, PlanetId};
+use nebula_lod::{FaceQuadtreeLod, LodChunkDescriptor, QuadtreeSnapshotStore};
+use nebula_math::WorldPosition;
+use tracing::{info, warn};
+
+/// One LOD quadtree per cube face of a planet, with optional persistence.
+pub struct PlanetQuadtrees {
+    planet: PlanetId,
+    seed: u64,
+    faces: [FaceQuadtreeLod; 6],
+    store: Option<QuadtreeSnapshotStore>,
+    active: Vec<LodChunkDescriptor>,
+}
+
+impl PlanetQuadtrees {
+    /// Quadtrees for `planet` generated with `seed`, one per face in
+    /// [`CubeFace::ALL`] order, each built by `make_face`.
+    pub fn new(
+        planet: PlanetId,
+        seed: u64,
+        make_face: impl FnMut(CubeFace) -> FaceQuadtreeLod,
+    ) -> Self {
+        Self {
+            planet,
+            seed,
+            faces: CubeFace::ALL.map(make_face),
+            store: None,
+            active: Vec::new(),
+        }
+    }
+
+    /// Restore from and save to `store`, keyed by the planet and seed.
+    pub fn with_store(mut self, store: QuadtreeSnapshotStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Seed the trees from the saved snapshot and reconcile them against
+    /// `camera`.
+    ///
+    /// Faces start cold when there is no store or snapshot, or when their
+    /// section of the snapshot does not restore. Returns the number of faces
+    /// restored from the snapshot.
+    pub fn start(&mut self, camera: &WorldPosition) -> usize {
+        let blob = self
+            .store
+            .as_ref()
+            .and_then(|store| store.load(self.planet, self.seed));
+        self.active.clear();
+        let mut restored = 0;
+        for tree in &mut self.faces {
+            let chunks = match blob.as_deref().map(|blob| tree.restore(blob, camera)) {
+                Some(Ok(chunks)) => {
+                    restored += 1;
+                    chunks
+                }
+                Some(Err(err)) => {
+                    warn!(
+                        "Quadtree snapshot for {:?} not restored: {err}",
+                        tree.face()
+                    );
+                    tree.update(camera)
+                }
+                None => tree.update(camera),
+            };
+            self.active.extend(chunks);
+        }
+        if restored > 0 {
+            info!(
+                "Restored {restored} face quadtrees of planet {} from snapshot",
+                self.planet.0
+            );
+        }
+        restored
+    }
+
+    /// Advance every face's LOD cooldown clock by `dt` seconds.
+    pub fn tick(&mut self, dt: f64) {
+        for tree in &mut self.faces {
+            tree.tick(dt);
+        }
+    }
+
+    /// Split and merge every face for `camera` and return the active chunks
+    /// of all faces.
+    pub fn update(&mut self, camera: &WorldPosition) -> &[LodChunkDescriptor] {
+        self.active.clear();
+        for tree in &mut self.faces {
+            self.active.extend(tree.update(camera));
+        }
+        &self.active
+    }
+
+    /// Active chunks of all faces after the last [`start`](Self::start) or
+    /// [`update`](Self::update).
+    pub fn active(&self) -> &[LodChunkDescriptor] {
+        &self.active
+    }
+
+    /// The face trees, in [`CubeFace::ALL`] order.
+    pub fn faces(&self) -> &[FaceQuadtreeLod; 6] {
+        &self.faces
+    }
+
+    /// Save the leaf sets for the next [`start`](Self::start). Does nothing
+    /// without a store.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot cannot be written.
+    pub fn shutdown(&self) -> io::Result<()> {
+        match &self.store {
+            Some(store) => store.save(self.planet, self.seed, &self.faces),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nebula_lod::LodThresholds;
+
+    fn planet(seed: u64) -> PlanetQuadtrees {
+        PlanetQuadtrees::new(PlanetId(7), seed, |face| {
+            FaceQuadtreeLod::new(
+                face,
+                5,
+                LodThresholds::custom(vec![2_000.0, 4_000.0, 8_000.0, 16_000.0, 32_000.0])
+                    .with_promote_ratio(1.0),
+                10_000_000.0,
+            )
+        })
+    }
+
+    fn leaves(planet: &PlanetQuadtrees) -> Vec<Vec<nebula_cubesphere::ChunkAddress>> {
+        planet
+            .faces()
+            .iter()
+            .map(|tree| {
+                let mut leaves = tree.tree().root.all_leaves();
+                leaves.sort_unstable();
+                leaves
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_shutdown_and_start_round_trips_every_face() {
+        let dir =
+            std::env::temp_dir().join(format!("nebula-planet-quadtrees-{}", std::process::id()));
+        let camera = WorldPosition::new(0, 10_500_000, 0);
+
+        let mut first = planet(42).with_store(QuadtreeSnapshotStore::new(&dir));
+        assert_eq!(first.start(&camera), 0, "nothing saved yet");
+        for _ in 0..8 {
+            first.update(&camera);
+        }
+        first.shutdown().unwrap();
+
+        let mut second = planet(42).with_store(QuadtreeSnapshotStore::new(&dir));
+        assert_eq!(second.start(&camera), 6);
+        assert_eq!(leaves(&second), leaves(&first));
+        assert!(
+            second
+                .faces()
+                .iter()
+                .all(|tree| tree.last_actions().is_empty()),
+            "a warm start at the same camera changes nothing"
+        );
+
+        let mut reseeded = planet(43).with_store(QuadtreeSnapshotStore::new(&dir));
+        assert_eq!(reseeded.start(&camera), 0, "seed is part of the key");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_without_store_starts_cold_and_shutdown_is_a_no_op() {
+        let camera = WorldPosition::new(0, 10_500_000, 0);
+        let mut planet = planet(42);
+        assert_eq!(planet.start(&camera), 0);
+        assert!(!planet.active().is_empty());
+        planet.shutdown().unwrap();
+    }
+}
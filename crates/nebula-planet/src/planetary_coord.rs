@@ -171,6 +171,7 @@ mod tests {
                 min_height: 0.0,
                 max_height: 0.0,
                 sea_level: 0.0,
+                beach_width: 0.0,
                 planet_radius: 6_371_000.0,
             },
         )
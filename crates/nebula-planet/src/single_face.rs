@@ -6,8 +6,7 @@
 
 use glam::{Mat4, Vec3};
 use nebula_cubesphere::{
-    ChunkAddress as CsChunkAddress, CubeFace, FaceCoord, face_coord_to_cube_point,
-    face_coord_to_sphere_everitt,
+    ChunkAddress as CsChunkAddress, CubeFace, FaceCoord, ProjectionMethod, project,
 };
 use nebula_mesh::visibility::compute_visible_faces;
 use nebula_mesh::{ChunkMesh, ChunkNeighborhood, greedy_mesh};
@@ -40,6 +39,8 @@ pub struct SingleFaceLoader {
     pub planet_radius: f64,
     /// Voxel size in meters.
     pub voxel_size: f64,
+    /// Cube-to-sphere mapping of the planet.
+    pub projection: ProjectionMethod,
 }
 
 impl SingleFaceLoader {
@@ -61,6 +62,7 @@ impl SingleFaceLoader {
                 min_height: -4.0,
                 max_height: 12.0,
                 sea_level: 0.0,
+                beach_width: 1.0,
                 planet_radius,
             },
         );
@@ -72,7 +74,7 @@ impl SingleFaceLoader {
                 solid: true,
                 transparency: Transparency::Opaque,
                 material_index: 1,
-                light_emission: 0,
+                light_emission: [0; 3],
             })
             .expect("register stone");
         registry
@@ -81,7 +83,7 @@ impl SingleFaceLoader {
                 solid: true,
                 transparency: Transparency::Opaque,
                 material_index: 2,
-                light_emission: 0,
+                light_emission: [0; 3],
             })
             .expect("register dirt");
         registry
@@ -90,7 +92,7 @@ impl SingleFaceLoader {
                 solid: true,
                 transparency: Transparency::Opaque,
                 material_index: 3,
-                light_emission: 0,
+                light_emission: [0; 3],
             })
             .expect("register grass");
 
@@ -102,6 +104,7 @@ impl SingleFaceLoader {
             registry,
             planet_radius,
             voxel_size,
+            projection: ProjectionMethod::default(),
         }
     }
 
@@ -167,7 +170,7 @@ impl SingleFaceLoader {
                 let face_u = u_min + (x as f64 + 0.5) / chunk_size as f64 * u_size;
                 let face_v = v_min + (z as f64 + 0.5) / chunk_size as f64 * v_size;
                 let fc = FaceCoord::new(self.face, face_u, face_v);
-                let sphere_pt = face_coord_to_sphere_everitt(&fc);
+                let sphere_pt = project(&fc, self.projection);
 
                 let height = self.terrain.sample_height(sphere_pt);
                 // Map height to voxel Y. We center at y=16 as "sea level".
@@ -190,6 +193,31 @@ impl SingleFaceLoader {
         chunk
     }
 
+    /// Whether the quadtree node at `addr` overlaps a chunk this loader
+    /// loads.
+    pub fn overlaps_loaded(&self, addr: &CsChunkAddress) -> bool {
+        if addr.face != self.face {
+            return false;
+        }
+        let grid_size = CsChunkAddress::grid_size(self.lod);
+        let center = grid_size / 2;
+        let min = center.saturating_sub(self.load_radius);
+        let max = (center + self.load_radius).min(grid_size - 1);
+        // Grid cells at this loader's LOD covered by `c` at the node's LOD.
+        let span = |c: u32| {
+            if addr.lod >= self.lod {
+                let shift = addr.lod - self.lod;
+                (c << shift, ((c + 1) << shift) - 1)
+            } else {
+                let cell = c >> (self.lod - addr.lod);
+                (cell, cell)
+            }
+        };
+        let (x0, x1) = span(addr.x);
+        let (y0, y1) = span(addr.y);
+        x0 <= max && x1 >= min && y0 <= max && y1 >= min
+    }
+
     /// Total number of chunks that would be loaded.
     pub fn expected_chunk_count(&self) -> u32 {
         let side = 2 * self.load_radius + 1;
@@ -208,7 +236,7 @@ pub struct SingleFaceRenderData {
 /// Build GPU-ready render data from meshed chunks by applying cubesphere displacement.
 ///
 /// Each vertex is displaced from flat chunk-local space onto the planet's curved
-/// surface. Colors are assigned based on voxel material:
+/// surface through its `projection`. Colors are assigned based on voxel material:
 /// - stone (1) = gray
 /// - dirt (2)  = brown
 /// - grass (3) = green
@@ -216,6 +244,7 @@ pub fn build_face_render_data(
     chunks: &[FaceChunkMesh],
     planet_radius: f64,
     voxel_size: f64,
+    projection: ProjectionMethod,
 ) -> SingleFaceRenderData {
     let mut all_vertices = Vec::new();
     let mut all_indices = Vec::new();
@@ -236,8 +265,7 @@ pub fn build_face_render_data(
             let face_v = v_min + local_v * v_size;
 
             let fc = FaceCoord::new(face, face_u.clamp(0.0, 1.0), face_v.clamp(0.0, 1.0));
-            let cube_point = face_coord_to_cube_point(&fc);
-            let sphere_dir = cube_point.normalize();
+            let sphere_dir = project(&fc, projection);
 
             // Y axis maps to radial displacement
             let height = (vertex.position[1] as f64 - 16.0) * voxel_size;
@@ -333,11 +361,33 @@ mod tests {
         assert_eq!(loader.expected_chunk_count(), 81);
     }
 
+    #[test]
+    fn test_overlaps_loaded_matches_the_loaded_square() {
+        // Lod 17 is an 8×8 grid, so radius 1 loads cells 3..=5.
+        let loader = SingleFaceLoader::new_demo(CubeFace::PosY, 1, 42);
+        let at = |lod, x, y| CsChunkAddress::new(CubeFace::PosY, lod, x, y);
+        assert!(loader.overlaps_loaded(&at(17, 3, 5)));
+        assert!(!loader.overlaps_loaded(&at(17, 2, 4)));
+        assert!(loader.overlaps_loaded(&at(18, 1, 1)), "covers cell (3, 3)");
+        assert!(!loader.overlaps_loaded(&at(18, 0, 3)));
+        assert!(
+            loader.overlaps_loaded(&at(15, 20, 23)),
+            "inside cell (5, 5)"
+        );
+        let other_face = CsChunkAddress::new(CubeFace::NegY, 17, 4, 4);
+        assert!(!loader.overlaps_loaded(&other_face));
+    }
+
     #[test]
     fn test_render_data_has_vertices() {
         let loader = SingleFaceLoader::new_demo(CubeFace::PosY, 1, 42);
         let chunks = loader.load_and_mesh();
-        let render_data = build_face_render_data(&chunks, loader.planet_radius, loader.voxel_size);
+        let render_data = build_face_render_data(
+            &chunks,
+            loader.planet_radius,
+            loader.voxel_size,
+            loader.projection,
+        );
         assert!(
             !render_data.vertices.is_empty(),
             "Expected non-empty vertex data"
@@ -353,7 +403,8 @@ mod tests {
         let loader = SingleFaceLoader::new_demo(CubeFace::PosY, 1, 42);
         let radius = loader.planet_radius;
         let chunks = loader.load_and_mesh();
-        let render_data = build_face_render_data(&chunks, radius, loader.voxel_size);
+        let render_data =
+            build_face_render_data(&chunks, radius, loader.voxel_size, loader.projection);
 
         for vertex in &render_data.vertices {
             let pos = Vec3::from(vertex.position);
@@ -384,7 +435,12 @@ mod tests {
     fn test_pos_y_vertices_have_positive_y() {
         let loader = SingleFaceLoader::new_demo(CubeFace::PosY, 1, 42);
         let chunks = loader.load_and_mesh();
-        let render_data = build_face_render_data(&chunks, loader.planet_radius, loader.voxel_size);
+        let render_data = build_face_render_data(
+            &chunks,
+            loader.planet_radius,
+            loader.voxel_size,
+            loader.projection,
+        );
 
         for vertex in &render_data.vertices {
             assert!(
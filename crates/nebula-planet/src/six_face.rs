@@ -2,15 +2,39 @@
 //!
 //! Composes six [`SingleFaceLoader`]s (one per [`CubeFace`]) and provides
 //! face-level culling against a view frustum so that faces behind the camera
-//! are skipped entirely.
-
-use glam::{Mat4, Vec3};
-use nebula_cubesphere::CubeFace;
-use nebula_render::{Aabb, Frustum, VertexPositionColor};
+//! are skipped entirely. The planet's [`PlanetQuadtrees`] track the LOD of
+//! every face and persist across sessions; their coarse leaves are drawn as
+//! far-terrain patches wherever no voxel chunks are loaded.
+
+use glam::{DMat4, DVec3, Mat4, Vec3};
+use nebula_cubesphere::{ChunkAddress, CubeFace, PlanetId};
+use nebula_lod::{FaceQuadtreeLod, LodThresholds, QuadtreeSnapshotStore};
+use nebula_math::WorldPosition;
+use nebula_render::{Aabb, Frustum, FrustumCuller, VertexPositionColor};
 use tracing::info;
 
+use crate::culling::{NodeBoundsCache, QuadtreeCullStats, QuadtreeCuller};
+use crate::far_terrain::{FarPatchSync, FarTerrainPatchCache};
+use crate::planet_quadtrees::PlanetQuadtrees;
 use crate::single_face::{SingleFaceLoader, build_face_render_data};
 
+/// Planet ID of the demo planet, keying its quadtree snapshots.
+const DEMO_PLANET_ID: PlanetId = PlanetId(0);
+
+/// Subdivision depth of the demo planet's face quadtrees.
+const DEMO_QUADTREE_DEPTH: u8 = 5;
+
+/// LOD distances of the demo planet's face quadtrees, in meters. The demo
+/// planet is only a few hundred meters across.
+const DEMO_LOD_THRESHOLDS_M: [f64; 5] = [40.0, 80.0, 160.0, 320.0, 640.0];
+
+/// Terrain height margin around the radius, as a fraction of it, matching
+/// the face bounds of [`compute_face_aabb`].
+const FACE_HEIGHT_MARGIN: f64 = 0.1;
+
+/// Vertex color of far-terrain patches, matching the grass voxel color.
+const FAR_TERRAIN_COLOR: [f32; 4] = [0.2, 0.7, 0.15, 1.0];
+
 /// State for one cube face: loader, visibility, and cached render data.
 pub struct FaceState {
     /// Which cube face.
@@ -33,6 +57,16 @@ pub struct PlanetFaces {
     pub planet_radius: f64,
     /// Voxel size in meters.
     pub voxel_size: f64,
+    /// LOD quadtrees of the six faces.
+    pub quadtrees: PlanetQuadtrees,
+    /// Far-terrain patches of the quadtree leaves coarser than the loaded
+    /// chunks.
+    pub far_terrain: FarTerrainPatchCache,
+    /// Cached bounds of the quadtree nodes, in planet-centered millimeters.
+    node_bounds: NodeBoundsCache,
+    /// Quadtree leaves that passed the last [`cull_lod_nodes`](Self::cull_lod_nodes),
+    /// sorted; `None` until the first cull.
+    visible_leaves: Option<Vec<ChunkAddress>>,
 }
 
 impl PlanetFaces {
@@ -43,8 +77,12 @@ impl PlanetFaces {
         let face_states = CubeFace::ALL.map(|face| {
             let loader = SingleFaceLoader::new_demo(face, load_radius, seed);
             let chunks = loader.load_and_mesh();
-            let render_data =
-                build_face_render_data(&chunks, loader.planet_radius, loader.voxel_size);
+            let render_data = build_face_render_data(
+                &chunks,
+                loader.planet_radius,
+                loader.voxel_size,
+                loader.projection,
+            );
 
             FaceState {
                 face,
@@ -57,6 +95,18 @@ impl PlanetFaces {
 
         let planet_radius = face_states[0].loader.planet_radius;
         let voxel_size = face_states[0].loader.voxel_size;
+        let projection = face_states[0].loader.projection;
+        let chunk_lod = face_states[0].loader.lod;
+        let quadtrees = PlanetQuadtrees::new(DEMO_PLANET_ID, seed, |face| {
+            FaceQuadtreeLod::new(
+                face,
+                DEMO_QUADTREE_DEPTH,
+                LodThresholds::custom(DEMO_LOD_THRESHOLDS_M.to_vec()),
+                planet_radius * 1000.0,
+            )
+            .with_projection(projection)
+            .with_merged_patches(chunk_lod)
+        });
 
         let total_verts: usize = face_states.iter().map(|f| f.vertices.len()).sum();
         let total_tris: usize = face_states.iter().map(|f| f.indices.len()).sum::<usize>() / 3;
@@ -69,9 +119,47 @@ impl PlanetFaces {
             face_states,
             planet_radius,
             voxel_size,
+            quadtrees,
+            far_terrain: FarTerrainPatchCache::new().with_projection(projection),
+            node_bounds: NodeBoundsCache::new(
+                WorldPosition::new(0, 0, 0),
+                planet_radius * 1000.0,
+                -planet_radius * 1000.0 * FACE_HEIGHT_MARGIN,
+                planet_radius * 1000.0 * FACE_HEIGHT_MARGIN,
+            )
+            .with_projection(projection),
+            visible_leaves: None,
         }
     }
 
+    /// Restore the face quadtrees from and save them to `store`.
+    pub fn with_quadtree_store(mut self, store: QuadtreeSnapshotStore) -> Self {
+        self.quadtrees = self.quadtrees.with_store(store);
+        self
+    }
+
+    /// Restore the face quadtrees saved by the last shutdown, reconcile
+    /// them against a camera at `eye` (planet-centered, meters) and bake
+    /// their far-terrain patches. Returns the number of faces restored.
+    pub fn start_lod(&mut self, eye: Vec3) -> usize {
+        let restored = self.quadtrees.start(&eye_to_world(eye));
+        self.sync_far_terrain();
+        restored
+    }
+
+    /// Split and merge the face quadtrees for a camera at `eye`
+    /// (planet-centered, meters), baking and releasing far-terrain patches
+    /// to match.
+    pub fn update_lod(&mut self, eye: Vec3) -> FarPatchSync {
+        self.quadtrees.update(&eye_to_world(eye));
+        self.sync_far_terrain()
+    }
+
+    fn sync_far_terrain(&mut self) -> FarPatchSync {
+        let terrain = &self.face_states[0].loader.terrain;
+        self.far_terrain.sync(self.quadtrees.active(), terrain)
+    }
+
     /// Perform face-level frustum culling.
     ///
     /// Computes an AABB for each face's hemisphere cap and tests it against
@@ -89,7 +177,47 @@ impl PlanetFaces {
         visible_count
     }
 
-    /// Build combined render data from all currently visible faces.
+    /// Cull the quadtree nodes of the visible faces against `view_proj`
+    /// (planet-centered meters) for a camera at `eye`.
+    ///
+    /// Node bounding spheres are cached across calls. Far-terrain patches
+    /// of leaves outside the frustum are left out of
+    /// [`visible_render_data`](Self::visible_render_data) until the next
+    /// cull. Call after [`cull_faces`](Self::cull_faces).
+    pub fn cull_lod_nodes(&mut self, eye: Vec3, view_proj: &Mat4) -> QuadtreeCullStats {
+        // Camera-relative millimeters to planet-centered meters, then clip.
+        let camera_relative = view_proj.as_dmat4()
+            * DMat4::from_translation(eye.as_dvec3())
+            * DMat4::from_scale(DVec3::splat(0.001));
+        let culler = FrustumCuller::from_camera_f64(&camera_relative);
+        let mut quadtree_culler =
+            QuadtreeCuller::new(&culler, eye_to_world(eye), &mut self.node_bounds);
+
+        let mut stats = QuadtreeCullStats::default();
+        let mut visible = Vec::new();
+        for (face_state, tree) in self.face_states.iter().zip(self.quadtrees.faces()) {
+            if !face_state.visible {
+                continue;
+            }
+            let face = quadtree_culler.cull(&tree.tree().root, &mut visible);
+            stats.nodes_visited += face.nodes_visited;
+            stats.subtrees_culled += face.subtrees_culled;
+            stats.leaves_culled += face.leaves_culled;
+        }
+        visible.sort_unstable();
+        self.visible_leaves = Some(visible);
+        stats
+    }
+
+    /// Whether the quadtree leaf at `address` survived the last LOD cull.
+    fn is_leaf_visible(&self, address: &ChunkAddress) -> bool {
+        self.visible_leaves
+            .as_ref()
+            .is_none_or(|visible| visible.binary_search(address).is_ok())
+    }
+
+    /// Build combined render data from all currently visible faces: their
+    /// loaded chunks, and the far-terrain patches that do not overlap them.
     ///
     /// Returns `(vertices, indices)` ready for GPU upload.
     pub fn visible_render_data(&self) -> (Vec<VertexPositionColor>, Vec<u32>) {
@@ -105,6 +233,22 @@ impl PlanetFaces {
             all_indices.extend(face_state.indices.iter().map(|&i| i + base));
         }
 
+        for patch in self.far_terrain.iter() {
+            let face_state = &self.face_states[patch.address.face as usize];
+            if !face_state.visible
+                || !self.is_leaf_visible(&patch.address)
+                || face_state.loader.overlaps_loaded(&patch.address)
+            {
+                continue;
+            }
+            let base = all_vertices.len() as u32;
+            all_vertices.extend(patch.positions.iter().map(|p| VertexPositionColor {
+                position: p.as_vec3().to_array(),
+                color: FAR_TERRAIN_COLOR,
+            }));
+            all_indices.extend(patch.indices.iter().map(|&i| i + base));
+        }
+
         (all_vertices, all_indices)
     }
 
@@ -119,6 +263,12 @@ impl PlanetFaces {
     }
 }
 
+/// Planet-centered meters to the millimeter positions the quadtrees use.
+fn eye_to_world(eye: Vec3) -> WorldPosition {
+    let mm = eye.as_dvec3() * 1000.0;
+    WorldPosition::new(mm.x as i128, mm.y as i128, mm.z as i128)
+}
+
 /// Compute an f32 AABB that encloses the hemisphere cap of the given cube face.
 ///
 /// The AABB is sized to contain all vertices displaced onto the sphere for
@@ -127,7 +277,7 @@ impl PlanetFaces {
 fn compute_face_aabb(face: CubeFace, planet_radius: f32) -> Aabb {
     let normal = face.normal();
     let r = planet_radius;
-    let margin = r * 0.1; // terrain height margin
+    let margin = r * FACE_HEIGHT_MARGIN as f32;
 
     let n = Vec3::new(normal.x as f32, normal.y as f32, normal.z as f32);
 
@@ -153,6 +303,17 @@ fn compute_face_aabb(face: CubeFace, planet_radius: f32) -> Aabb {
     Aabb::new(n_min - perp, n_max + perp)
 }
 
+/// Eye position of the camera built by [`create_orbit_camera`], relative to
+/// the planet center.
+pub fn orbit_camera_eye(planet_radius: f32, altitude: f32, orbit_angle: f64, tilt: f32) -> Vec3 {
+    let dist = planet_radius + altitude;
+    Vec3::new(
+        (orbit_angle.sin() as f32) * dist * tilt.cos(),
+        dist * tilt.sin(),
+        (orbit_angle.cos() as f32) * dist * tilt.cos(),
+    )
+}
+
 /// Create an orbiting camera matrix that views the full planet.
 ///
 /// The camera orbits at the given altitude above the planet surface,
@@ -166,11 +327,7 @@ pub fn create_orbit_camera(
     aspect_ratio: f32,
 ) -> Mat4 {
     let dist = planet_radius + altitude;
-    let eye = Vec3::new(
-        (orbit_angle.sin() as f32) * dist * tilt.cos(),
-        dist * tilt.sin(),
-        (orbit_angle.cos() as f32) * dist * tilt.cos(),
-    );
+    let eye = orbit_camera_eye(planet_radius, altitude, orbit_angle, tilt);
     let target = Vec3::ZERO;
     let up = Vec3::Y;
 
@@ -182,128 +339,5 @@ pub fn create_orbit_camera(
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_all_six_faces_created() {
-        let planet = PlanetFaces::new_demo(1, 42);
-        assert_eq!(planet.face_states.len(), 6);
-        for (i, fs) in planet.face_states.iter().enumerate() {
-            assert_eq!(fs.face, CubeFace::ALL[i]);
-        }
-    }
-
-    #[test]
-    fn test_all_faces_have_vertices() {
-        let planet = PlanetFaces::new_demo(1, 42);
-        for fs in &planet.face_states {
-            assert!(
-                !fs.vertices.is_empty(),
-                "Face {:?} should have vertices",
-                fs.face
-            );
-        }
-    }
-
-    #[test]
-    fn test_face_culling_reduces_visible_faces() {
-        let mut planet = PlanetFaces::new_demo(1, 42);
-        let r = planet.planet_radius as f32;
-
-        // Camera above planet looking UPWARD (away from planet).
-        // Nothing should be visible because the planet is behind us.
-        let eye = Vec3::new(0.0, r + 50.0, 0.0);
-        let target = Vec3::new(0.0, r + 150.0, 0.0); // looking up
-        let view = Mat4::look_at_rh(eye, target, Vec3::Z);
-        // Reverse-Z for compatibility with engine's Frustum
-        let proj = Mat4::perspective_rh(60.0_f32.to_radians(), 1.0, 10000.0, 0.1);
-        let vp = proj * view;
-        let frustum = Frustum::from_view_projection(&vp);
-
-        let visible = planet.cull_faces(&frustum);
-
-        // Looking away from planet, all faces should be behind the camera
-        assert!(
-            visible == 0,
-            "Expected 0 visible faces when looking away from planet, got {visible}"
-        );
-
-        // Now look toward the planet -- some faces should be visible
-        let target_down = Vec3::ZERO;
-        let view_down = Mat4::look_at_rh(eye, target_down, Vec3::Z);
-        let vp_down = proj * view_down;
-        let frustum_down = Frustum::from_view_projection(&vp_down);
-
-        let visible_down = planet.cull_faces(&frustum_down);
-        assert!(
-            visible_down > 0,
-            "Expected some visible faces when looking at planet"
-        );
-    }
-
-    #[test]
-    fn test_visible_render_data_combines_faces() {
-        let planet = PlanetFaces::new_demo(1, 42);
-        let (verts, indices) = planet.visible_render_data();
-        assert!(!verts.is_empty());
-        assert!(!indices.is_empty());
-        for &idx in &indices {
-            assert!(
-                (idx as usize) < verts.len(),
-                "Index {idx} out of bounds (len={})",
-                verts.len()
-            );
-        }
-    }
-
-    #[test]
-    fn test_vertices_on_sphere_surface() {
-        let planet = PlanetFaces::new_demo(1, 42);
-        let r = planet.planet_radius as f32;
-        for fs in &planet.face_states {
-            for v in &fs.vertices {
-                let pos = Vec3::from(v.position);
-                let dist = pos.length();
-                assert!(
-                    (dist - r).abs() < 50.0,
-                    "Vertex at dist {dist} too far from radius {r} on face {:?}",
-                    fs.face
-                );
-            }
-        }
-    }
-
-    #[test]
-    fn test_orbit_camera_produces_valid_matrix() {
-        let vp = create_orbit_camera(200.0, 100.0, 1.0, 0.5, 16.0 / 9.0);
-        for col in 0..4 {
-            for row in 0..4 {
-                assert!(vp.col(col)[row].is_finite());
-            }
-        }
-    }
-
-    #[test]
-    fn test_face_aabb_contains_face_center() {
-        for face in CubeFace::ALL {
-            let aabb = compute_face_aabb(face, 200.0);
-            let normal = face.normal();
-            let center = Vec3::new(
-                normal.x as f32 * 200.0,
-                normal.y as f32 * 200.0,
-                normal.z as f32 * 200.0,
-            );
-            assert!(
-                center.x >= aabb.min.x
-                    && center.x <= aabb.max.x
-                    && center.y >= aabb.min.y
-                    && center.y <= aabb.max.y
-                    && center.z >= aabb.min.z
-                    && center.z <= aabb.max.z,
-                "AABB for {:?} should contain face center {center:?}",
-                face
-            );
-        }
-    }
-}
+#[path = "six_face_tests.rs"]
+mod tests;
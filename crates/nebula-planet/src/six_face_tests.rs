@@ -0,0 +1,166 @@
+//! Unit tests for six-face planet setup, face culling and LOD patches.
+
+use super::*;
+
+#[test]
+fn test_all_six_faces_created() {
+    let planet = PlanetFaces::new_demo(1, 42);
+    assert_eq!(planet.face_states.len(), 6);
+    for (i, fs) in planet.face_states.iter().enumerate() {
+        assert_eq!(fs.face, CubeFace::ALL[i]);
+    }
+}
+
+#[test]
+fn test_all_faces_have_vertices() {
+    let planet = PlanetFaces::new_demo(1, 42);
+    for fs in &planet.face_states {
+        assert!(
+            !fs.vertices.is_empty(),
+            "Face {:?} should have vertices",
+            fs.face
+        );
+    }
+}
+
+#[test]
+fn test_face_culling_reduces_visible_faces() {
+    let mut planet = PlanetFaces::new_demo(1, 42);
+    let r = planet.planet_radius as f32;
+
+    // Camera above planet looking UPWARD (away from planet).
+    // Nothing should be visible because the planet is behind us.
+    let eye = Vec3::new(0.0, r + 50.0, 0.0);
+    let target = Vec3::new(0.0, r + 150.0, 0.0); // looking up
+    let view = Mat4::look_at_rh(eye, target, Vec3::Z);
+    // Reverse-Z for compatibility with engine's Frustum
+    let proj = Mat4::perspective_rh(60.0_f32.to_radians(), 1.0, 10000.0, 0.1);
+    let vp = proj * view;
+    let frustum = Frustum::from_view_projection(&vp);
+
+    let visible = planet.cull_faces(&frustum);
+
+    // Looking away from planet, all faces should be behind the camera
+    assert!(
+        visible == 0,
+        "Expected 0 visible faces when looking away from planet, got {visible}"
+    );
+
+    // Now look toward the planet -- some faces should be visible
+    let target_down = Vec3::ZERO;
+    let view_down = Mat4::look_at_rh(eye, target_down, Vec3::Z);
+    let vp_down = proj * view_down;
+    let frustum_down = Frustum::from_view_projection(&vp_down);
+
+    let visible_down = planet.cull_faces(&frustum_down);
+    assert!(
+        visible_down > 0,
+        "Expected some visible faces when looking at planet"
+    );
+}
+
+#[test]
+fn test_visible_render_data_combines_faces() {
+    let planet = PlanetFaces::new_demo(1, 42);
+    let (verts, indices) = planet.visible_render_data();
+    assert!(!verts.is_empty());
+    assert!(!indices.is_empty());
+    for &idx in &indices {
+        assert!(
+            (idx as usize) < verts.len(),
+            "Index {idx} out of bounds (len={})",
+            verts.len()
+        );
+    }
+}
+
+#[test]
+fn test_vertices_on_sphere_surface() {
+    let planet = PlanetFaces::new_demo(1, 42);
+    let r = planet.planet_radius as f32;
+    for fs in &planet.face_states {
+        for v in &fs.vertices {
+            let pos = Vec3::from(v.position);
+            let dist = pos.length();
+            assert!(
+                (dist - r).abs() < 50.0,
+                "Vertex at dist {dist} too far from radius {r} on face {:?}",
+                fs.face
+            );
+        }
+    }
+}
+
+#[test]
+fn test_far_terrain_fills_faces_around_loaded_chunks() {
+    let mut planet = PlanetFaces::new_demo(1, 42);
+    let chunks_only = planet.visible_render_data().0.len();
+    let eye = orbit_camera_eye(200.0, 160.0, 0.0, 0.4);
+    planet.start_lod(eye);
+    assert!(!planet.far_terrain.is_empty());
+
+    let (vertices, _) = planet.visible_render_data();
+    assert!(vertices.len() > chunks_only, "patches are drawn");
+
+    // Only leaves coarser than the loaded chunks become patches.
+    for patch in planet.far_terrain.iter() {
+        assert!(patch.address.lod > planet.face_states[0].loader.lod);
+    }
+    let sync = planet.update_lod(eye);
+    assert_eq!(sync, FarPatchSync::default(), "same camera, same patches");
+}
+
+#[test]
+fn test_orbit_camera_produces_valid_matrix() {
+    let vp = create_orbit_camera(200.0, 100.0, 1.0, 0.5, 16.0 / 9.0);
+    for col in 0..4 {
+        for row in 0..4 {
+            assert!(vp.col(col)[row].is_finite());
+        }
+    }
+}
+
+#[test]
+fn test_face_aabb_contains_face_center() {
+    for face in CubeFace::ALL {
+        let aabb = compute_face_aabb(face, 200.0);
+        let normal = face.normal();
+        let center = Vec3::new(
+            normal.x as f32 * 200.0,
+            normal.y as f32 * 200.0,
+            normal.z as f32 * 200.0,
+        );
+        assert!(
+            center.x >= aabb.min.x
+                && center.x <= aabb.max.x
+                && center.y >= aabb.min.y
+                && center.y <= aabb.max.y
+                && center.z >= aabb.min.z
+                && center.z <= aabb.max.z,
+            "AABB for {:?} should contain face center {center:?}",
+            face
+        );
+    }
+}
+
+#[test]
+fn test_lod_culling_skips_far_patches_behind_the_camera() {
+    let mut planet = PlanetFaces::new_demo(1, 42);
+    let eye = orbit_camera_eye(200.0, 160.0, 0.0, 0.4);
+    planet.start_lod(eye);
+    let all = planet.visible_render_data().0.len();
+
+    // Looking away from the planet culls every quadtree node.
+    let view = Mat4::look_at_rh(eye, eye * 2.0, Vec3::Y);
+    let proj = Mat4::perspective_rh(70.0_f32.to_radians(), 1.0, 1.0, 2000.0);
+    let stats = planet.cull_lod_nodes(eye, &(proj * view));
+    assert!(stats.subtrees_culled > 0);
+    let looking_away = planet.visible_render_data().0.len();
+    assert!(looking_away < all);
+
+    // Looking at it keeps the near-side patches.
+    let vp = create_orbit_camera(200.0, 160.0, 0.0, 0.4, 1.0);
+    let stats = planet.cull_lod_nodes(eye, &vp);
+    assert!(stats.nodes_visited > 0);
+    assert!(planet.visible_render_data().0.len() > looking_away);
+}
@@ -0,0 +1,137 @@
+//! Spatial audio listener derived from the active camera.
+//!
+//! There is no audio engine in the core crates; this module only provides
+//! the [`AudioListener`] resource that
+//! [`update_audio_listener_system`](crate::update_audio_listener_system)
+//! keeps in floating-origin local space, and the [`SpatialAudio`] trait a
+//! backend implements to consume it. With the `kira-audio` feature a kira
+//! listener handle is such a backend.
+
+use bevy_ecs::prelude::*;
+use glam::{Mat3, Quat, Vec3};
+
+/// An audio backend that positions its listener from an [`AudioListener`].
+pub trait SpatialAudio {
+    /// Error returned when the backend rejects the update.
+    type Error;
+
+    /// Move the backend's listener to `listener`.
+    fn set_listener(&mut self, listener: &AudioListener) -> Result<(), Self::Error>;
+}
+
+/// Where sound is heard from, in floating-origin local space (mm).
+///
+/// Updated every frame by
+/// [`update_audio_listener_system`](crate::update_audio_listener_system)
+/// when the resource exists.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct AudioListener {
+    /// Listener position relative to the floating origin.
+    pub position: Vec3,
+    /// Unit direction the listener faces.
+    pub forward: Vec3,
+    /// Unit up direction of the listener.
+    pub up: Vec3,
+}
+
+impl Default for AudioListener {
+    fn default() -> Self {
+        Self::new(Vec3::ZERO, Quat::IDENTITY)
+    }
+}
+
+impl AudioListener {
+    /// A listener at `position` rotated by `rotation` from the unrotated
+    /// orientation, which faces −Z with +Y up like the cameras.
+    pub fn new(position: Vec3, rotation: Quat) -> Self {
+        Self {
+            position,
+            forward: rotation * Vec3::NEG_Z,
+            up: rotation * Vec3::Y,
+        }
+    }
+
+    /// The rotation taking the unrotated orientation to this one.
+    ///
+    /// `up` is re-orthogonalized against `forward` first, so a slightly
+    /// skewed basis still yields a unit quaternion.
+    pub fn orientation(&self) -> Quat {
+        let back = -self.forward.normalize_or(Vec3::NEG_Z);
+        let right = self.up.cross(back).normalize_or(Vec3::X);
+        let up = back.cross(right);
+        Quat::from_mat3(&Mat3::from_cols(right, up, back))
+    }
+
+    /// Move a kira listener to this position and orientation.
+    ///
+    /// kira 0.8 keeps listeners on a spatial scene rather than on the
+    /// `AudioManager`, so this takes the handle returned by
+    /// `SpatialSceneHandle::add_listener`.
+    #[cfg(feature = "kira-audio")]
+    pub fn apply_to_kira(
+        &self,
+        listener: &mut kira::spatial::listener::ListenerHandle,
+    ) -> Result<(), kira::CommandError> {
+        let tween = kira::tween::Tween::default();
+        listener.set_position(self.position.to_array(), tween)?;
+        listener.set_orientation(self.orientation().to_array(), tween)
+    }
+}
+
+#[cfg(feature = "kira-audio")]
+impl SpatialAudio for kira::spatial::listener::ListenerHandle {
+    type Error = kira::CommandError;
+
+    fn set_listener(&mut self, listener: &AudioListener) -> Result<(), Self::Error> {
+        listener.apply_to_kira(self)
+    }
+}
+
+/// Offset of the listener's ears from the active camera, in camera space
+/// (mm). Zero when the resource is absent.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ListenerEarOffset(pub Vec3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_listener_faces_negative_z() {
+        let listener = AudioListener::default();
+        assert_eq!(listener.forward, Vec3::NEG_Z);
+        assert_eq!(listener.up, Vec3::Y);
+        assert!(listener.orientation().abs_diff_eq(Quat::IDENTITY, 1e-6));
+    }
+
+    #[test]
+    fn test_orientation_round_trips_rotation() {
+        let rotation = Quat::from_euler(glam::EulerRot::YXZ, 1.2, -0.4, 0.3);
+        let listener = AudioListener::new(Vec3::new(1.0, 2.0, 3.0), rotation);
+        let back = listener.orientation();
+        assert!(
+            back.dot(rotation).abs() > 1.0 - 1e-5,
+            "{back:?} vs {rotation:?}"
+        );
+    }
+
+    /// Records the listener positions it is given.
+    struct Recorder(Vec<Vec3>);
+
+    impl SpatialAudio for Recorder {
+        type Error = ();
+
+        fn set_listener(&mut self, listener: &AudioListener) -> Result<(), ()> {
+            self.0.push(listener.position);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_spatial_audio_backend_receives_listener() {
+        let mut backend = Recorder(Vec::new());
+        let listener = AudioListener::new(Vec3::X, Quat::IDENTITY);
+        backend.set_listener(&listener).unwrap();
+        assert_eq!(backend.0, [Vec3::X]);
+    }
+}
@@ -0,0 +1,354 @@
+//! Third-person camera collision: keeps the camera out of terrain and walls.
+//!
+//! [`third_person_collision_system`] sweeps a sphere of
+//! [`ThirdPersonCamera::collision_radius`] from the look-at point toward the
+//! desired camera position. The sweep runs against the physics collider set
+//! when it has colliders, and falls back to a voxel raycast otherwise. The
+//! camera is pulled in to the nearest hit minus
+//! [`ThirdPersonCamera::collision_margin`], and distance changes are smoothed
+//! exponentially, fast when moving in and slow when restoring, so the camera
+//! neither clips nor pops at any frame rate.
+
+use glam::Vec3;
+use nebula_ecs::WorldPos;
+use nebula_math::WorldPosition;
+use nebula_physics::{PhysicsWorld, VoxelWorldAccess, voxel_raycast, world_to_local};
+use rapier3d::parry::query::ShapeCastOptions;
+use rapier3d::prelude::{Ball, Pose, QueryFilter, RigidBodyHandle, Vector as RapierVector};
+
+use crate::interaction::voxel_ray;
+use crate::third_person_camera::ThirdPersonCamera;
+
+/// Millimeters per meter, the unit of physics and voxel raycast distances.
+const MM_PER_M: f32 = 1_000.0;
+
+/// Remaining distance, in millimeters, below which smoothing snaps to the goal.
+const SNAP_EPSILON_MM: f32 = 1.0;
+
+/// What the camera sweep collides with.
+#[derive(Clone, Copy, Default)]
+pub struct CameraProbe<'a> {
+    /// Physics world to sweep against, with the world position of its
+    /// local origin.
+    pub physics: Option<(&'a PhysicsWorld, WorldPosition)>,
+    /// Voxels raycast against when there are no physics colliders.
+    pub voxels: Option<&'a dyn VoxelWorldAccess>,
+    /// Body ignored by the sweep, typically the followed character.
+    pub exclude_body: Option<RigidBodyHandle>,
+}
+
+impl<'a> CameraProbe<'a> {
+    /// Probe against the colliders of `physics`, whose local origin sits at
+    /// `origin` in world space.
+    pub fn physics(physics: &'a PhysicsWorld, origin: WorldPosition) -> Self {
+        Self {
+            physics: Some((physics, origin)),
+            ..Default::default()
+        }
+    }
+
+    /// Probe against voxels only.
+    pub fn voxels(voxels: &'a dyn VoxelWorldAccess) -> Self {
+        Self {
+            voxels: Some(voxels),
+            ..Default::default()
+        }
+    }
+
+    /// Also raycast `voxels` when the physics world has no colliders.
+    pub fn with_voxels(mut self, voxels: &'a dyn VoxelWorldAccess) -> Self {
+        self.voxels = Some(voxels);
+        self
+    }
+
+    /// Ignore colliders attached to `body`.
+    pub fn excluding(mut self, body: RigidBodyHandle) -> Self {
+        self.exclude_body = Some(body);
+        self
+    }
+
+    /// Distance in millimeters that a sphere of `radius_mm` travels from
+    /// `from` along `direction` before touching an obstruction, or `None` if
+    /// nothing is hit within `max_mm`.
+    pub fn cast(
+        &self,
+        from: &WorldPosition,
+        direction: Vec3,
+        max_mm: f32,
+        radius_mm: f32,
+    ) -> Option<f32> {
+        let direction = direction.normalize_or_zero();
+        if direction == Vec3::ZERO || max_mm <= 0.0 {
+            return None;
+        }
+        match self.physics {
+            Some((physics, origin)) if !physics.collider_set.is_empty() => {
+                self.cast_physics(physics, &origin, from, direction, max_mm, radius_mm)
+            }
+            _ => self.cast_voxels(from, direction, max_mm, radius_mm),
+        }
+    }
+
+    fn cast_physics(
+        &self,
+        physics: &PhysicsWorld,
+        origin: &WorldPosition,
+        from: &WorldPosition,
+        direction: Vec3,
+        max_mm: f32,
+        radius_mm: f32,
+    ) -> Option<f32> {
+        let filter = match self.exclude_body {
+            Some(body) => QueryFilter::new().exclude_rigid_body(body),
+            None => QueryFilter::new(),
+        };
+        let query = physics.broad_phase.as_query_pipeline(
+            physics.narrow_phase.query_dispatcher(),
+            &physics.rigid_body_set,
+            &physics.collider_set,
+            filter,
+        );
+        let start = world_to_local(from, origin);
+        let options = ShapeCastOptions {
+            max_time_of_impact: max_mm / MM_PER_M,
+            target_distance: 0.0,
+            stop_at_penetration: false,
+            compute_impact_geometry_on_penetration: false,
+        };
+        let ball = Ball::new((radius_mm / MM_PER_M).max(f32::EPSILON));
+        query
+            .cast_shape(
+                &Pose::translation(start.x, start.y, start.z),
+                RapierVector::new(direction.x, direction.y, direction.z),
+                &ball,
+                options,
+            )
+            .map(|(_, hit)| hit.time_of_impact * MM_PER_M)
+    }
+
+    fn cast_voxels(
+        &self,
+        from: &WorldPosition,
+        direction: Vec3,
+        max_mm: f32,
+        radius_mm: f32,
+    ) -> Option<f32> {
+        let voxels = self.voxels?;
+        let ray = voxel_ray(from, direction, max_mm / MM_PER_M);
+        voxel_raycast(&ray, voxels).map(|hit| (hit.distance * MM_PER_M - radius_mm).max(0.0))
+    }
+}
+
+/// Shorten the camera distance so the view from the look-at point is not
+/// obstructed, smoothing the change over `dt` seconds.
+///
+/// Call once per frame before
+/// [`third_person_follow_system`](crate::third_person_follow_system), which
+/// places the camera at [`ThirdPersonCamera::effective_distance`]. Moving in
+/// uses [`collision_pull_in_speed`](ThirdPersonCamera::collision_pull_in_speed)
+/// but never leaves the camera past the obstruction; moving back out uses
+/// [`collision_restore_speed`](ThirdPersonCamera::collision_restore_speed).
+pub fn third_person_collision_system(
+    cam: &mut ThirdPersonCamera,
+    target_pos: &WorldPos,
+    probe: &CameraProbe,
+    dt: f32,
+) {
+    let look_at = cam.look_at_point(&target_pos.0);
+    let limit = probe
+        .cast(
+            &look_at,
+            cam.orbit_direction(),
+            cam.distance,
+            cam.collision_radius,
+        )
+        .map(|hit| (hit - cam.collision_margin).max(0.0));
+    let goal = limit.map_or(cam.distance, |limit| limit.min(cam.distance));
+
+    let current = cam.effective_distance();
+    let speed = if goal < current {
+        cam.collision_pull_in_speed
+    } else {
+        cam.collision_restore_speed
+    };
+    let blend = 1.0 - (-speed.max(0.0) * dt.max(0.0)).exp();
+    let mut next = current + (goal - current) * blend;
+    if (goal - next).abs() < SNAP_EPSILON_MM {
+        next = goal;
+    }
+    if let Some(limit) = limit {
+        next = next.min(limit);
+    }
+    cam.collision_distance = Some(next);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nebula_physics::VoxelData;
+    use nebula_voxel::VoxelTypeId;
+    use rapier3d::prelude::ColliderBuilder;
+
+    /// One frame at 60 Hz, in seconds.
+    const FRAME_DT: f32 = 1.0 / 60.0;
+
+    /// Camera orbiting straight behind (+Z) a target at the origin, 8 m out.
+    fn level_camera() -> ThirdPersonCamera {
+        ThirdPersonCamera {
+            orbit_yaw: 0.0,
+            orbit_pitch: 0.0,
+            distance: 8_000.0,
+            height_offset: 0.0,
+            ..Default::default()
+        }
+    }
+
+    fn target() -> WorldPos {
+        WorldPos(WorldPosition::new(0, 0, 0))
+    }
+
+    /// A physics world with a wall whose front face is 2 m behind the target.
+    fn wall_world() -> (PhysicsWorld, rapier3d::prelude::ColliderHandle) {
+        let mut physics = PhysicsWorld::new();
+        let wall = ColliderBuilder::cuboid(10.0, 10.0, 0.1)
+            .translation(RapierVector::new(0.0, 0.0, 2.1))
+            .build();
+        let handle = physics.collider_set.insert(wall);
+        physics.step();
+        (physics, handle)
+    }
+
+    #[test]
+    fn test_wall_behind_target_clamps_camera() {
+        let (physics, _) = wall_world();
+        let probe = CameraProbe::physics(&physics, WorldPosition::default());
+        let mut cam = level_camera();
+
+        third_person_collision_system(&mut cam, &target(), &probe, FRAME_DT);
+        assert!(
+            cam.effective_distance() < 2_000.0,
+            "got {}",
+            cam.effective_distance()
+        );
+        assert_eq!(cam.distance, 8_000.0, "zoom level is untouched");
+    }
+
+    #[test]
+    fn test_removing_wall_restores_distance_gradually() {
+        let (mut physics, wall) = wall_world();
+        let mut cam = level_camera();
+        for _ in 0..5 {
+            let probe = CameraProbe::physics(&physics, WorldPosition::default());
+            third_person_collision_system(&mut cam, &target(), &probe, FRAME_DT);
+        }
+        let blocked = cam.effective_distance();
+        assert!(blocked < 2_000.0);
+
+        physics.collider_set.remove(
+            wall,
+            &mut physics.island_manager,
+            &mut physics.rigid_body_set,
+            true,
+        );
+        physics.step();
+        let probe = CameraProbe::physics(&physics, WorldPosition::default());
+
+        third_person_collision_system(&mut cam, &target(), &probe, FRAME_DT);
+        let first = cam.effective_distance();
+        assert!(first > blocked && first < 3_000.0, "got {first}");
+
+        let mut previous = first;
+        for _ in 0..300 {
+            third_person_collision_system(&mut cam, &target(), &probe, FRAME_DT);
+            assert!(cam.effective_distance() >= previous);
+            previous = cam.effective_distance();
+        }
+        assert_eq!(cam.effective_distance(), 8_000.0);
+    }
+
+    #[test]
+    fn test_smoothing_does_not_depend_on_frame_rate() {
+        let (physics, _) = wall_world();
+        let probe = CameraProbe::physics(&physics, WorldPosition::default());
+        let open = PhysicsWorld::new();
+        let open_probe = CameraProbe::physics(&open, WorldPosition::default());
+
+        // Restore from behind the wall for half a second at 30 and 120 Hz.
+        let restored = |frames: u32| {
+            let mut cam = level_camera();
+            third_person_collision_system(&mut cam, &target(), &probe, FRAME_DT);
+            for _ in 0..frames {
+                let dt = 0.5 / frames as f32;
+                third_person_collision_system(&mut cam, &target(), &open_probe, dt);
+            }
+            cam.effective_distance()
+        };
+        let (slow, fast) = (restored(15), restored(60));
+        assert!(slow > 2_000.0 && slow < 8_000.0, "got {slow}");
+        assert!((slow - fast).abs() < 1.0, "30 Hz: {slow}, 120 Hz: {fast}");
+    }
+
+    #[test]
+    fn test_parallel_wall_does_not_jitter() {
+        let mut physics = PhysicsWorld::new();
+        // Wall 0.5 m to the side of the camera's line of sight, along it.
+        let wall = ColliderBuilder::cuboid(0.1, 10.0, 20.0)
+            .translation(RapierVector::new(0.6, 0.0, 0.0))
+            .build();
+        physics.collider_set.insert(wall);
+        physics.step();
+        let probe = CameraProbe::physics(&physics, WorldPosition::default());
+        let mut cam = level_camera();
+
+        for _ in 0..60 {
+            third_person_collision_system(&mut cam, &target(), &probe, FRAME_DT);
+            assert_eq!(cam.effective_distance(), 8_000.0);
+        }
+    }
+
+    #[test]
+    fn test_excluded_body_is_ignored() {
+        let mut physics = PhysicsWorld::new();
+        let body = physics
+            .rigid_body_set
+            .insert(rapier3d::prelude::RigidBodyBuilder::fixed().build());
+        let wall = ColliderBuilder::cuboid(10.0, 10.0, 0.1)
+            .translation(RapierVector::new(0.0, 0.0, 2.1))
+            .build();
+        physics
+            .collider_set
+            .insert_with_parent(wall, body, &mut physics.rigid_body_set);
+        physics.step();
+        let probe = CameraProbe::physics(&physics, WorldPosition::default()).excluding(body);
+        let mut cam = level_camera();
+
+        third_person_collision_system(&mut cam, &target(), &probe, FRAME_DT);
+        assert_eq!(cam.effective_distance(), 8_000.0);
+    }
+
+    /// Solid voxels at z >= 3 in voxel units.
+    struct VoxelWall;
+
+    impl VoxelWorldAccess for VoxelWall {
+        fn get_voxel(&self, pos: &WorldPosition) -> Option<VoxelData> {
+            let solid = pos.z >= 3;
+            Some(VoxelData {
+                id: VoxelTypeId(u16::from(solid)),
+                solid,
+            })
+        }
+    }
+
+    #[test]
+    fn test_voxel_fallback_without_colliders() {
+        let physics = PhysicsWorld::new();
+        let probe =
+            CameraProbe::physics(&physics, WorldPosition::default()).with_voxels(&VoxelWall);
+        let mut cam = level_camera();
+
+        third_person_collision_system(&mut cam, &target(), &probe, FRAME_DT);
+        let distance = cam.effective_distance();
+        // Wall face at 3 m, minus the probe radius and margin.
+        assert!(distance <= 2_700.0 && distance > 2_000.0, "got {distance}");
+    }
+}
@@ -5,10 +5,21 @@
 //! `(entity.WorldPos − origin)` cast to f32.  The subtraction happens
 //! entirely in i128 arithmetic so precision is independent of absolute
 //! magnitude.
+//!
+//! With an [`OriginPolicy`] resource the origin instead stays put until its
+//! anchor strays past the policy's threshold, and the physics origin follows
+//! the same policy; see [`build_coupled_origin_schedule`].
+//!
+//! The [`AudioListener`] resource, when present, follows the active camera
+//! in the same local space.
 
 use bevy_ecs::prelude::*;
-use nebula_ecs::{LocalPos, WorldPos};
+use glam::{Quat, Vec3};
+use nebula_ecs::{LocalPos, Rotation, WorldPos};
 use nebula_math::{LocalPosition, WorldPosition};
+use nebula_physics::{OriginPolicy, OriginRebased, recenter_physics_origin};
+
+use crate::audio::{AudioListener, ListenerEarOffset};
 
 // ---------------------------------------------------------------------------
 // Resource
@@ -20,6 +31,22 @@ use nebula_math::{LocalPosition, WorldPosition};
 #[derive(Resource, Clone, Copy, Debug, Default)]
 pub struct FloatingOrigin(pub WorldPosition);
 
+impl FloatingOrigin {
+    /// An unrotated [`AudioListener`] at the origin itself, which is the
+    /// local-space zero, displaced by `ear_offset` (mm).
+    pub fn compute_listener(&self, ear_offset: Vec3) -> AudioListener {
+        self.listener_at(&self.0, ear_offset)
+    }
+
+    /// An unrotated [`AudioListener`] at `world` converted to this origin's
+    /// local space, displaced by `ear_offset` (mm).
+    pub fn listener_at(&self, world: &WorldPosition, ear_offset: Vec3) -> AudioListener {
+        let delta = *world - self.0;
+        let local = Vec3::new(delta.x as f32, delta.y as f32, delta.z as f32);
+        AudioListener::new(local + ear_offset, Quat::IDENTITY)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Marker
 // ---------------------------------------------------------------------------
@@ -34,13 +61,35 @@ pub struct ActiveCamera;
 
 /// Copies the first [`ActiveCamera`] entity's world position into the
 /// [`FloatingOrigin`] resource.
+///
+/// With an [`OriginPolicy`], advances the policy with its anchor (or the
+/// active camera) instead, copies [`OriginPolicy::origin`], and sends an
+/// [`OriginRebased`] event when it moves, if `Events<OriginRebased>` exists.
 pub fn update_floating_origin_system(
     camera_query: Query<&WorldPos, With<ActiveCamera>>,
+    anchors: Query<&WorldPos>,
+    policy: Option<ResMut<OriginPolicy>>,
+    rebased: Option<ResMut<Events<OriginRebased>>>,
     mut origin: ResMut<FloatingOrigin>,
 ) {
-    if let Some(cam_pos) = camera_query.iter().next() {
-        origin.0 = cam_pos.0;
+    let camera = camera_query.iter().next();
+    let Some(mut policy) = policy else {
+        if let Some(cam_pos) = camera {
+            origin.0 = cam_pos.0;
+        }
+        return;
+    };
+    let anchor = policy
+        .anchor
+        .and_then(|entity| anchors.get(entity).ok())
+        .or(camera);
+    if let Some(anchor) = anchor
+        && let Some(delta) = policy.update(&anchor.0)
+        && let Some(mut rebased) = rebased
+    {
+        rebased.send(OriginRebased(delta));
     }
+    origin.0 = policy.origin();
 }
 
 /// Recomputes every entity's [`LocalPos`] as `(WorldPos − FloatingOrigin)`
@@ -57,13 +106,52 @@ pub fn recompute_local_positions_system(
     }
 }
 
-/// Convenience helper: adds the two systems to `schedule` with a chain
-/// guarantee (origin update runs before local-position recomputation).
+/// Moves the [`AudioListener`] resource, if present, to the first
+/// [`ActiveCamera`] in floating-origin local space, offset by the
+/// [`ListenerEarOffset`] rotated with the camera.
+///
+/// Runs after the origin update; with an [`OriginPolicy`] the camera may sit
+/// away from the origin, so the camera's own offset is added.
+pub fn update_audio_listener_system(
+    origin: Res<FloatingOrigin>,
+    camera_query: Query<(&WorldPos, Option<&Rotation>), With<ActiveCamera>>,
+    ear_offset: Option<Res<ListenerEarOffset>>,
+    listener: Option<ResMut<AudioListener>>,
+) {
+    let (Some(mut listener), Some((camera, rotation))) = (listener, camera_query.iter().next())
+    else {
+        return;
+    };
+    let rotation = rotation.map_or(Quat::IDENTITY, |r| r.0);
+    let ear_offset = ear_offset.map_or(Vec3::ZERO, |offset| rotation * offset.0);
+    let at_camera = origin.listener_at(&camera.0, ear_offset);
+    *listener = AudioListener::new(at_camera.position, rotation);
+}
+
+/// Adds the render origin update, [`recenter_physics_origin`], and
+/// local-position recomputation to `schedule` as one chain, so with an
+/// [`OriginPolicy`] both origins move within the same system step and no
+/// other system sees them disagree.
+pub fn build_coupled_origin_schedule(schedule: &mut Schedule) {
+    schedule.add_systems(
+        (
+            update_floating_origin_system,
+            recenter_physics_origin,
+            recompute_local_positions_system,
+        )
+            .chain(),
+    );
+}
+
+/// Convenience helper: adds the origin update, local-position
+/// recomputation, and [`update_audio_listener_system`] to `schedule` as a
+/// chain, so the listener always sees the current frame's origin.
 pub fn build_local_position_schedule(schedule: &mut Schedule) {
     schedule.add_systems(
         (
             update_floating_origin_system,
             recompute_local_positions_system,
+            update_audio_listener_system,
         )
             .chain(),
     );
@@ -175,4 +263,157 @@ mod tests {
         let origin = FloatingOrigin::default();
         assert_eq!(origin.0, WorldPosition::default());
     }
+
+    /// Whether the render and physics origins agreed each time an observer
+    /// system ran.
+    #[derive(Resource, Default)]
+    struct OriginAgreement(Vec<bool>);
+
+    fn observe_origins(
+        render: Res<FloatingOrigin>,
+        physics: Res<nebula_physics::PhysicsOrigin>,
+        mut seen: ResMut<OriginAgreement>,
+    ) {
+        seen.0.push(render.0 == physics.world_origin);
+    }
+
+    fn local_vec(world: &World, entity: Entity) -> glam::Vec3 {
+        let local = world.get::<LocalPos>(entity).unwrap().0;
+        glam::Vec3::new(local.x, local.y, local.z)
+    }
+
+    #[test]
+    fn test_coupled_rebase_after_ten_km_flight() {
+        use nebula_math::UNITS_PER_METER;
+        use nebula_physics::{PhysicsOrigin, PhysicsWorld, local_to_world, world_to_local};
+        use rapier3d::prelude::{RigidBodyBuilder, Vector};
+
+        let static_pos = WorldPosition::new(7_000 * UNITS_PER_METER, 0, 300 * UNITS_PER_METER);
+        let mut physics = PhysicsWorld::new();
+        let local = world_to_local(&static_pos, &WorldPosition::default());
+        let body = physics.rigid_body_set.insert(
+            RigidBodyBuilder::fixed()
+                .translation(Vector::new(local.x, local.y, local.z))
+                .build(),
+        );
+
+        let mut world = World::new();
+        world.insert_resource(physics);
+        world.insert_resource(PhysicsOrigin::default());
+        world.insert_resource(FloatingOrigin::default());
+        let mut policy = OriginPolicy::default();
+        policy.rebase_threshold_m = 6_000.0;
+        world.insert_resource(policy);
+        world.init_resource::<Events<OriginRebased>>();
+        world.init_resource::<OriginAgreement>();
+        let camera = world
+            .spawn((ActiveCamera, WorldPos::new(0, 0, 0), LocalPos::default()))
+            .id();
+        let distant = world
+            .spawn((WorldPos(static_pos), LocalPos::default()))
+            .id();
+
+        let mut schedule = Schedule::default();
+        build_coupled_origin_schedule(&mut schedule);
+        schedule.add_systems((
+            observe_origins.before(update_floating_origin_system),
+            observe_origins.after(recompute_local_positions_system),
+        ));
+
+        let step = 10 * UNITS_PER_METER;
+        let mut rebases = Vec::new();
+        let mut previous: Option<(glam::Vec3, i128)> = None;
+        for tick in 1..=1_000i128 {
+            world.get_mut::<WorldPos>(camera).unwrap().0 = WorldPosition::new(tick * step, 0, 0);
+            schedule.run(&mut world);
+            rebases.extend(
+                world
+                    .resource_mut::<Events<OriginRebased>>()
+                    .drain()
+                    .map(|event| (tick, event.0)),
+            );
+
+            let relative = local_vec(&world, distant) - local_vec(&world, camera);
+            let exact_x = static_pos.x - tick * step;
+            assert!((relative.x - exact_x as f32).abs() < 1.0, "tick {tick}");
+            if let Some((before, before_x)) = previous {
+                let moved = (relative.x - before.x) - (exact_x - before_x) as f32;
+                assert!(moved.abs() < 1.0, "tick {tick} jumped {moved} mm");
+                assert!((relative.z - before.z).abs() < 1.0);
+            }
+            previous = Some((relative, exact_x));
+        }
+
+        assert_eq!(rebases.len(), 1, "exactly one rebase: {rebases:?}");
+        let (tick, delta) = rebases[0];
+        assert_eq!(delta, nebula_math::Vec3I128::new(tick * step, 0, 0));
+        let origin = world.resource::<FloatingOrigin>().0;
+        assert_eq!(origin, world.resource::<PhysicsOrigin>().world_origin);
+        assert_eq!(origin, WorldPosition::new(tick * step, 0, 0));
+
+        let seen = &world.resource::<OriginAgreement>().0;
+        assert_eq!(seen.len(), 2_000);
+        assert!(seen.iter().all(|agreed| *agreed), "origins diverged");
+
+        let t = world.resource::<PhysicsWorld>().rigid_body_set[body].translation();
+        let body_world = local_to_world(&glam::Vec3::new(t.x, t.y, t.z), &origin);
+        assert!((body_world.x - static_pos.x).abs() <= 2);
+        assert!((body_world.z - static_pos.z).abs() <= 2);
+    }
+
+    #[test]
+    fn test_without_policy_origin_tracks_camera() {
+        let mut world = World::new();
+        world.insert_resource(FloatingOrigin::default());
+        world.spawn((ActiveCamera, WorldPos::new(5, 6, 7), LocalPos::default()));
+        let mut schedule = Schedule::default();
+        build_local_position_schedule(&mut schedule);
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<FloatingOrigin>().0,
+            WorldPosition::new(5, 6, 7)
+        );
+    }
+
+    #[test]
+    fn test_compute_listener_at_camera_origin_is_local_zero() {
+        let origin = FloatingOrigin(WorldPosition::new(1000, 2000, 3000));
+        let listener = origin.compute_listener(Vec3::ZERO);
+        assert_eq!(listener.position, Vec3::ZERO);
+        assert_eq!(listener.forward, Vec3::NEG_Z);
+
+        let raised = origin.compute_listener(Vec3::new(0.0, 1_700.0, 0.0));
+        assert_eq!(raised.position, Vec3::new(0.0, 1_700.0, 0.0));
+
+        let beside = origin.listener_at(&WorldPosition::new(1500, 2000, 3000), Vec3::Y);
+        assert_eq!(beside.position, Vec3::new(500.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_listener_system_follows_camera() {
+        let mut world = World::new();
+        world.insert_resource(FloatingOrigin::default());
+        world.insert_resource(AudioListener::default());
+        world.insert_resource(ListenerEarOffset(Vec3::new(0.0, 0.0, -100.0)));
+        let turn = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        world.spawn((
+            ActiveCamera,
+            WorldPos::new(1000, 2000, 3000),
+            LocalPos::default(),
+            Rotation(turn),
+        ));
+        let mut schedule = Schedule::default();
+        build_local_position_schedule(&mut schedule);
+        schedule.run(&mut world);
+
+        let listener = *world.resource::<AudioListener>();
+        // The origin moved onto the camera; the ear offset turned with it.
+        assert!(
+            listener
+                .position
+                .abs_diff_eq(Vec3::new(-100.0, 0.0, 0.0), 1e-3)
+        );
+        assert!(listener.forward.abs_diff_eq(Vec3::NEG_X, 1e-6));
+        assert!(listener.up.abs_diff_eq(Vec3::Y, 1e-6));
+    }
 }
@@ -0,0 +1,438 @@
+//! First-person block interaction: crosshair highlight, reach, and
+//! break/place intents.
+//!
+//! Each frame [`InteractionSystem::update_target`] casts the view ray through
+//! the loaded chunks into [`BlockTarget`] and mirrors the hit into
+//! [`CurrentTarget`] for the outline renderer. [`InteractionSystem::resolve_intent`]
+//! turns [`Action::PrimaryAction`] into a break and [`Action::SecondaryAction`]
+//! into a place [`InteractionIntent`], and [`InteractionSystem::submit`] either
+//! applies it locally through [`set_voxel`] (emitting into the
+//! [`VoxelEventBuffer`]) or, when networked, queues it as a
+//! [`VoxelEditIntent`] for the server to validate.
+//!
+//! Positions of the eye and feet are in millimeters; voxel positions are in
+//! voxel units, as in [`voxel_raycast`].
+
+use bevy_ecs::prelude::*;
+use glam::{IVec3, Vec3};
+use nebula_input::{Action, ActionState};
+use nebula_math::WorldPosition;
+use nebula_multiplayer::{ChunkId, VoxelEditIntent, VoxelMaterial};
+use nebula_physics::{BlockTarget, VoxelData, VoxelRay, VoxelWorldAccess, voxel_raycast};
+use nebula_voxel::{
+    CHUNK_SIZE, ChunkAddress, ChunkManager, VoxelEventBuffer, VoxelTypeId, set_voxel,
+};
+
+/// Millimeters per voxel edge.
+const MM_PER_VOXEL: i128 = 1_000;
+
+/// Air voxel: always ID 0 in the voxel registry.
+const AIR: VoxelTypeId = VoxelTypeId(0);
+
+/// The voxel face under the crosshair, read by the block outline renderer.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct CurrentTarget {
+    /// The highlighted voxel, or `None` when nothing solid is within reach.
+    pub target: Option<TargetedVoxel>,
+}
+
+/// A highlighted voxel and the face the view ray entered through.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetedVoxel {
+    /// The solid voxel being looked at.
+    pub voxel: WorldPosition,
+    /// Outward normal of the hit face; zero if the eye is inside the voxel.
+    pub face_normal: IVec3,
+    /// The cell across the hit face, where a placed block would go.
+    pub adjacent: WorldPosition,
+    /// Distance from the eye to the hit point, in meters.
+    pub distance: f32,
+}
+
+/// Vertical capsule approximating the player's body for placement checks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlayerCapsule {
+    /// Capsule radius in millimeters.
+    pub radius_mm: i64,
+    /// Total height from feet to head, in millimeters.
+    pub height_mm: i64,
+}
+
+impl Default for PlayerCapsule {
+    fn default() -> Self {
+        Self {
+            radius_mm: 300,
+            height_mm: 1_800,
+        }
+    }
+}
+
+impl PlayerCapsule {
+    /// Whether the capsule standing with its base at `feet` (mm) overlaps
+    /// the voxel cell `voxel`. Touching the cell's surface does not count.
+    pub fn intersects_voxel(&self, feet: &WorldPosition, voxel: &WorldPosition) -> bool {
+        let min = |v: i128, f: i128| (v * MM_PER_VOXEL - f) as f64;
+        let cell_min = [
+            min(voxel.x, feet.x),
+            min(voxel.y, feet.y),
+            min(voxel.z, feet.z),
+        ];
+        let size = MM_PER_VOXEL as f64;
+        let radius = self.radius_mm as f64;
+
+        // The capsule's core segment runs up the Y axis through the feet.
+        let segment = (radius, (self.height_mm as f64 - radius).max(radius));
+        let gap = |(lo, hi): (f64, f64), cell: f64| (cell - hi).max(lo - (cell + size)).max(0.0);
+
+        let dx = gap((0.0, 0.0), cell_min[0]);
+        let dy = gap(segment, cell_min[1]);
+        let dz = gap((0.0, 0.0), cell_min[2]);
+        dx * dx + dy * dy + dz * dz < radius * radius
+    }
+}
+
+/// Tunables for [`InteractionSystem`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct InteractionConfig {
+    /// Maximum distance from the eye to a targeted voxel, in meters.
+    pub reach: f32,
+    /// Voxel type placed by [`Action::SecondaryAction`].
+    pub place_voxel: VoxelTypeId,
+    /// Inventory slot reported as the material source in networked edits.
+    pub inventory_slot: u8,
+    /// Player body used to refuse placements inside the player.
+    pub capsule: PlayerCapsule,
+    /// Cube face whose chunk grid the player is standing on.
+    pub face: u8,
+}
+
+impl Default for InteractionConfig {
+    fn default() -> Self {
+        Self {
+            reach: 5.0,
+            place_voxel: VoxelTypeId(1),
+            inventory_slot: 0,
+            capsule: PlayerCapsule::default(),
+            face: 0,
+        }
+    }
+}
+
+/// Where the player is looking from this frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InteractionView {
+    /// Camera position in millimeters.
+    pub eye: WorldPosition,
+    /// Normalized view direction.
+    pub forward: Vec3,
+    /// Base of the player capsule in millimeters.
+    pub feet: WorldPosition,
+}
+
+/// A block edit requested by the player.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InteractionIntent {
+    /// Remove the voxel at this position.
+    Break {
+        /// Voxel to remove.
+        voxel: WorldPosition,
+    },
+    /// Fill the cell at this position.
+    Place {
+        /// Cell to fill.
+        voxel: WorldPosition,
+        /// Voxel type to place.
+        voxel_type: VoxelTypeId,
+    },
+}
+
+impl InteractionIntent {
+    /// The voxel this intent edits.
+    pub fn voxel(&self) -> &WorldPosition {
+        match self {
+            Self::Break { voxel } | Self::Place { voxel, .. } => voxel,
+        }
+    }
+
+    /// The network message for this intent on cube face `face`, or `None`
+    /// if the voxel lies outside the network chunk grid.
+    pub fn to_edit_intent(&self, face: u8, inventory_slot: u8) -> Option<VoxelEditIntent> {
+        let (address, (x, y, z)) = voxel_chunk_coords(self.voxel(), face)?;
+        let chunk_id = ChunkId {
+            face: address.face,
+            lod: 0,
+            x: i32::try_from(address.x).ok()?,
+            y: i32::try_from(address.y).ok()?,
+            z: i32::try_from(address.z).ok()?,
+        };
+        let (local_x, local_y, local_z) = (u32::from(x), u32::from(y), u32::from(z));
+        Some(match self {
+            Self::Break { .. } => VoxelEditIntent::Remove {
+                chunk_id,
+                local_x,
+                local_y,
+                local_z,
+            },
+            Self::Place { voxel_type, .. } => VoxelEditIntent::Place {
+                chunk_id,
+                local_x,
+                local_y,
+                local_z,
+                material: VoxelMaterial(voxel_type.0),
+                source_inventory_slot: inventory_slot,
+            },
+        })
+    }
+}
+
+/// Why an interaction input did not produce an intent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum InteractionRefusal {
+    /// Nothing solid is under the crosshair.
+    #[error("no block targeted")]
+    NoTarget,
+    /// The targeted voxel is farther than the configured reach.
+    #[error("target out of reach")]
+    OutOfReach,
+    /// The eye is inside the targeted voxel, so there is no face to place against.
+    #[error("no face to place against")]
+    NoFace,
+    /// The placed block would overlap the player capsule.
+    #[error("placement intersects the player")]
+    IntersectsPlayer,
+}
+
+/// Chunk address and local voxel coordinates of voxel `voxel` on cube face
+/// `face`, or `None` if the chunk coordinates overflow.
+pub fn voxel_chunk_coords(voxel: &WorldPosition, face: u8) -> Option<(ChunkAddress, (u8, u8, u8))> {
+    let size = CHUNK_SIZE as i128;
+    let split = |v: i128| -> Option<(i64, u8)> {
+        Some((
+            i64::try_from(v.div_euclid(size)).ok()?,
+            v.rem_euclid(size) as u8,
+        ))
+    };
+    let (cx, lx) = split(voxel.x)?;
+    let (cy, ly) = split(voxel.y)?;
+    let (cz, lz) = split(voxel.z)?;
+    Some((ChunkAddress::new(cx, cy, cz, face), (lx, ly, lz)))
+}
+
+/// A [`VoxelRay`] from `origin` (mm) along `direction`, reaching
+/// `max_distance` meters.
+pub(crate) fn voxel_ray(origin: &WorldPosition, direction: Vec3, max_distance: f32) -> VoxelRay {
+    let sub = |v: i128| v.rem_euclid(MM_PER_VOXEL) as f32 / MM_PER_VOXEL as f32;
+    VoxelRay {
+        origin: WorldPosition::new(
+            origin.x.div_euclid(MM_PER_VOXEL),
+            origin.y.div_euclid(MM_PER_VOXEL),
+            origin.z.div_euclid(MM_PER_VOXEL),
+        ),
+        sub_offset: Vec3::new(sub(origin.x), sub(origin.y), sub(origin.z)),
+        direction: direction.normalize_or_zero(),
+        max_distance,
+        skip_origin: false,
+    }
+}
+
+/// [`VoxelWorldAccess`] over the chunks of one cube face in a [`ChunkManager`].
+///
+/// Any non-air voxel is solid; unloaded chunks read as `None`.
+pub struct ChunkVoxelWorld<'a> {
+    chunks: &'a ChunkManager,
+    face: u8,
+}
+
+impl<'a> ChunkVoxelWorld<'a> {
+    /// View the chunks on cube face `face`.
+    pub fn new(chunks: &'a ChunkManager, face: u8) -> Self {
+        Self { chunks, face }
+    }
+}
+
+impl VoxelWorldAccess for ChunkVoxelWorld<'_> {
+    fn get_voxel(&self, pos: &WorldPosition) -> Option<VoxelData> {
+        let (address, (x, y, z)) = voxel_chunk_coords(pos, self.face)?;
+        let id = self.chunks.get_chunk(&address)?.get(x, y, z);
+        Some(VoxelData {
+            id,
+            solid: id != AIR,
+        })
+    }
+}
+
+/// Ties the crosshair raycast to voxel edits.
+#[derive(Clone, Debug, Default)]
+pub struct InteractionSystem {
+    /// Reach, placed voxel type, and player body.
+    pub config: InteractionConfig,
+    /// Send edits to the server instead of applying them locally.
+    networked: bool,
+    /// Edits waiting to be sent, in networked mode.
+    outgoing: Vec<VoxelEditIntent>,
+}
+
+impl InteractionSystem {
+    /// System that applies edits to the local chunks.
+    pub fn new(config: InteractionConfig) -> Self {
+        Self {
+            config,
+            networked: false,
+            outgoing: Vec::new(),
+        }
+    }
+
+    /// System that queues edits as [`VoxelEditIntent`]s for the server; see
+    /// [`take_edit_intents`](Self::take_edit_intents).
+    pub fn networked(config: InteractionConfig) -> Self {
+        Self {
+            networked: true,
+            ..Self::new(config)
+        }
+    }
+
+    /// Whether edits go to the server rather than the local chunks.
+    pub fn is_networked(&self) -> bool {
+        self.networked
+    }
+
+    /// Cast the view ray up to [`reach`](InteractionConfig::reach) and
+    /// store the hit in `block_target` and `current`.
+    pub fn update_target(
+        &self,
+        view: &InteractionView,
+        world: &dyn VoxelWorldAccess,
+        block_target: &mut BlockTarget,
+        current: &mut CurrentTarget,
+    ) {
+        let ray = voxel_ray(&view.eye, view.forward, self.config.reach);
+        block_target.hit = voxel_raycast(&ray, world);
+        current.target = block_target.hit.as_ref().map(|hit| TargetedVoxel {
+            voxel: hit.voxel_pos,
+            face_normal: hit.face_normal,
+            adjacent: hit.adjacent_voxel_pos,
+            distance: hit.distance,
+        });
+    }
+
+    /// The intent for this frame's actions against `current`.
+    ///
+    /// Breaking takes precedence when both actions start on the same frame.
+    /// Returns `Ok(None)` if neither action started this frame.
+    pub fn resolve_intent(
+        &self,
+        actions: &ActionState,
+        view: &InteractionView,
+        current: &CurrentTarget,
+    ) -> Result<Option<InteractionIntent>, InteractionRefusal> {
+        let breaking = actions.action_just_activated(Action::PrimaryAction);
+        let placing = actions.action_just_activated(Action::SecondaryAction);
+        if !breaking && !placing {
+            return Ok(None);
+        }
+
+        let target = current
+            .target
+            .as_ref()
+            .ok_or(InteractionRefusal::NoTarget)?;
+        if target.distance > self.config.reach {
+            return Err(InteractionRefusal::OutOfReach);
+        }
+        if breaking {
+            return Ok(Some(InteractionIntent::Break {
+                voxel: target.voxel,
+            }));
+        }
+
+        if target.face_normal == IVec3::ZERO {
+            return Err(InteractionRefusal::NoFace);
+        }
+        if self
+            .config
+            .capsule
+            .intersects_voxel(&view.feet, &target.adjacent)
+        {
+            return Err(InteractionRefusal::IntersectsPlayer);
+        }
+        Ok(Some(InteractionIntent::Place {
+            voxel: target.adjacent,
+            voxel_type: self.config.place_voxel,
+        }))
+    }
+
+    /// Carry out `intent`.
+    ///
+    /// Locally the voxel is written through [`set_voxel`], which emits into
+    /// `events`, and the highlight is cleared if the targeted voxel is gone.
+    /// Networked, the intent is queued for the server and the chunks are left
+    /// for the confirmed edit. Returns `false` if the edit could not be
+    /// applied or encoded.
+    pub fn submit(
+        &mut self,
+        intent: &InteractionIntent,
+        chunks: &mut ChunkManager,
+        events: &mut VoxelEventBuffer,
+        current: &mut CurrentTarget,
+    ) -> bool {
+        if self.networked {
+            let Some(edit) = intent.to_edit_intent(self.config.face, self.config.inventory_slot)
+            else {
+                return false;
+            };
+            self.outgoing.push(edit);
+            return true;
+        }
+
+        let Some((address, (x, y, z))) = voxel_chunk_coords(intent.voxel(), self.config.face)
+        else {
+            return false;
+        };
+        let new_type = match intent {
+            InteractionIntent::Break { .. } => AIR,
+            InteractionIntent::Place { voxel_type, .. } => *voxel_type,
+        };
+        let changed = set_voxel(chunks, &address, x, y, z, new_type, events);
+
+        if let Some(target) = &current.target {
+            let world = ChunkVoxelWorld::new(chunks, self.config.face);
+            let still_solid = world.get_voxel(&target.voxel).is_some_and(|v| v.solid);
+            if !still_solid {
+                current.target = None;
+            }
+        }
+        changed
+    }
+
+    /// Per-frame entry point: update the highlight, then resolve and submit
+    /// this frame's break/place intent.
+    pub fn tick(
+        &mut self,
+        view: &InteractionView,
+        actions: &ActionState,
+        chunks: &mut ChunkManager,
+        events: &mut VoxelEventBuffer,
+        block_target: &mut BlockTarget,
+        current: &mut CurrentTarget,
+    ) -> Result<Option<InteractionIntent>, InteractionRefusal> {
+        let world = ChunkVoxelWorld::new(chunks, self.config.face);
+        self.update_target(view, &world, block_target, current);
+
+        let intent = self.resolve_intent(actions, view, current)?;
+        if let Some(intent) = &intent {
+            self.submit(intent, chunks, events, current);
+            if current.target.is_none() {
+                block_target.hit = None;
+            }
+        }
+        Ok(intent)
+    }
+
+    /// Take the edits queued for the server since the last call.
+    pub fn take_edit_intents(&mut self) -> Vec<VoxelEditIntent> {
+        std::mem::take(&mut self.outgoing)
+    }
+}
+
+#[cfg(test)]
+#[path = "interaction_tests.rs"]
+mod tests;
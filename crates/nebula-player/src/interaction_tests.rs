@@ -0,0 +1,219 @@
+//! Unit tests for first-person block interaction.
+
+use super::*;
+use nebula_voxel::Chunk;
+
+const STONE: VoxelTypeId = VoxelTypeId(1);
+
+/// A one-voxel-thick stone floor at voxel y = 0 across chunk (0, 0, 0).
+fn floor_world() -> ChunkManager {
+    let mut chunk = Chunk::new();
+    for x in 0..32 {
+        for z in 0..32 {
+            chunk.set(x, 0, z, STONE);
+        }
+    }
+    let mut chunks = ChunkManager::new();
+    chunks.load_chunk(ChunkAddress::new(0, 0, 0, 0), chunk);
+    chunks
+}
+
+/// Player standing on the floor at the center of voxel column (4, 4),
+/// eye 1.6 m up, looking in `forward`.
+fn standing_view(forward: Vec3) -> InteractionView {
+    let feet = WorldPosition::new(4_500, 1_000, 4_500);
+    InteractionView {
+        eye: WorldPosition::new(feet.x, feet.y + 1_600, feet.z),
+        forward: forward.normalize(),
+        feet,
+    }
+}
+
+fn press(action: Action) -> ActionState {
+    let mut actions = ActionState::new();
+    actions.begin_frame();
+    actions.set_action_value(action, 1.0);
+    actions
+}
+
+#[test]
+fn test_looking_at_floor_highlights_voxel_and_face() {
+    let chunks = floor_world();
+    let system = InteractionSystem::default();
+    let mut block_target = BlockTarget::default();
+    let mut current = CurrentTarget::default();
+
+    // Eye 3 m above the floor surface, looking straight down.
+    let view = InteractionView {
+        eye: WorldPosition::new(10_500, 4_000, 7_500),
+        forward: Vec3::NEG_Y,
+        feet: WorldPosition::new(10_500, 2_400, 7_500),
+    };
+    system.update_target(
+        &view,
+        &ChunkVoxelWorld::new(&chunks, 0),
+        &mut block_target,
+        &mut current,
+    );
+
+    let target = current.target.expect("floor should be highlighted");
+    assert_eq!(target.voxel, WorldPosition::new(10, 0, 7));
+    assert_eq!(target.face_normal, IVec3::Y);
+    assert_eq!(target.adjacent, WorldPosition::new(10, 1, 7));
+    assert!((target.distance - 3.0).abs() < 1e-4);
+    assert!(block_target.hit.is_some());
+}
+
+#[test]
+fn test_floor_beyond_reach_is_not_highlighted() {
+    let chunks = floor_world();
+    let system = InteractionSystem::new(InteractionConfig {
+        reach: 2.0,
+        ..Default::default()
+    });
+    let mut block_target = BlockTarget::default();
+    let mut current = CurrentTarget::default();
+    let view = InteractionView {
+        eye: WorldPosition::new(10_500, 4_000, 7_500),
+        forward: Vec3::NEG_Y,
+        feet: WorldPosition::new(10_500, 2_400, 7_500),
+    };
+    system.update_target(
+        &view,
+        &ChunkVoxelWorld::new(&chunks, 0),
+        &mut block_target,
+        &mut current,
+    );
+    assert_eq!(current.target, None);
+}
+
+#[test]
+fn test_place_in_occupied_cell_is_refused() {
+    let mut chunks = floor_world();
+    let mut system = InteractionSystem::default();
+    let mut events = VoxelEventBuffer::new();
+    let mut block_target = BlockTarget::default();
+    let mut current = CurrentTarget::default();
+
+    // Looking at the floor under the player's own feet.
+    let view = standing_view(Vec3::NEG_Y);
+    let result = system.tick(
+        &view,
+        &press(Action::SecondaryAction),
+        &mut chunks,
+        &mut events,
+        &mut block_target,
+        &mut current,
+    );
+
+    assert_eq!(result, Err(InteractionRefusal::IntersectsPlayer));
+    assert!(events.is_empty());
+    let world = ChunkVoxelWorld::new(&chunks, 0);
+    let above_floor = world.get_voxel(&WorldPosition::new(4, 1, 4));
+    assert_eq!(above_floor.map(|v| v.solid), Some(false));
+}
+
+#[test]
+fn test_place_against_distant_floor_fills_adjacent_cell() {
+    let mut chunks = floor_world();
+    let mut system = InteractionSystem::default();
+    let mut events = VoxelEventBuffer::new();
+    let mut block_target = BlockTarget::default();
+    let mut current = CurrentTarget::default();
+
+    let view = standing_view(Vec3::new(2.0, -1.6, 0.0));
+    let result = system.tick(
+        &view,
+        &press(Action::SecondaryAction),
+        &mut chunks,
+        &mut events,
+        &mut block_target,
+        &mut current,
+    );
+
+    assert_eq!(
+        result,
+        Ok(Some(InteractionIntent::Place {
+            voxel: WorldPosition::new(6, 1, 4),
+            voxel_type: STONE,
+        }))
+    );
+    assert_eq!(events.len(), 1);
+}
+
+#[test]
+fn test_break_updates_chunk_and_clears_highlight() {
+    let mut chunks = floor_world();
+    let mut system = InteractionSystem::default();
+    let mut events = VoxelEventBuffer::new();
+    let mut block_target = BlockTarget::default();
+    let mut current = CurrentTarget::default();
+
+    let view = standing_view(Vec3::new(2.0, -1.6, 0.0));
+    let result = system.tick(
+        &view,
+        &press(Action::PrimaryAction),
+        &mut chunks,
+        &mut events,
+        &mut block_target,
+        &mut current,
+    );
+
+    assert_eq!(
+        result,
+        Ok(Some(InteractionIntent::Break {
+            voxel: WorldPosition::new(6, 0, 4),
+        }))
+    );
+    let chunk = chunks.get_chunk(&ChunkAddress::new(0, 0, 0, 0));
+    assert_eq!(chunk.map(|c| c.get(6, 0, 4)), Some(AIR));
+    assert_eq!(events.len(), 1);
+    assert_eq!(current.target, None);
+    assert!(block_target.hit.is_none());
+}
+
+#[test]
+fn test_networked_break_queues_edit_intent() {
+    let mut chunks = floor_world();
+    let mut system = InteractionSystem::networked(InteractionConfig::default());
+    let mut events = VoxelEventBuffer::new();
+    let mut block_target = BlockTarget::default();
+    let mut current = CurrentTarget::default();
+
+    let view = standing_view(Vec3::new(2.0, -1.6, 0.0));
+    let result = system.tick(
+        &view,
+        &press(Action::PrimaryAction),
+        &mut chunks,
+        &mut events,
+        &mut block_target,
+        &mut current,
+    );
+
+    assert!(matches!(result, Ok(Some(InteractionIntent::Break { .. }))));
+    assert!(events.is_empty(), "networked edits wait for the server");
+    assert_eq!(
+        system.take_edit_intents(),
+        vec![VoxelEditIntent::Remove {
+            chunk_id: ChunkId {
+                face: 0,
+                lod: 0,
+                x: 0,
+                y: 0,
+                z: 0,
+            },
+            local_x: 6,
+            local_y: 0,
+            local_z: 4,
+        }]
+    );
+    assert!(current.target.is_some());
+}
+
+#[test]
+fn test_voxel_chunk_coords_handles_negative_positions() {
+    let (address, local) =
+        voxel_chunk_coords(&WorldPosition::new(-1, 32, -33), 2).expect("in range");
+    assert_eq!(address, ChunkAddress::new(-1, 1, -2, 2));
+    assert_eq!(local, (31, 0, 31));
+}
@@ -1,13 +1,20 @@
-//! Camera controllers, player physics bridge, and player state management.
+//! Camera controllers, block interaction, player physics bridge, and player state
+//! management.
 
+pub mod audio;
+pub mod camera_collision;
 pub mod camera_transition;
 pub mod first_person_camera;
 pub mod floating_origin;
 pub mod free_fly_camera;
 pub mod gravity_oriented_camera;
+pub mod interaction;
+pub mod player_mode;
 pub mod spaceship_controller;
 pub mod third_person_camera;
 
+pub use audio::{AudioListener, ListenerEarOffset, SpatialAudio};
+pub use camera_collision::{CameraProbe, third_person_collision_system};
 pub use camera_transition::{
     CameraSnapshot, CameraTransition, EasingFunction, camera_transition_system,
 };
@@ -15,8 +22,8 @@ pub use first_person_camera::{
     FirstPersonCamera, first_person_look_system, first_person_move_system,
 };
 pub use floating_origin::{
-    ActiveCamera, FloatingOrigin, build_local_position_schedule, recompute_local_positions_system,
-    update_floating_origin_system,
+    ActiveCamera, FloatingOrigin, build_coupled_origin_schedule, build_local_position_schedule,
+    recompute_local_positions_system, update_audio_listener_system, update_floating_origin_system,
 };
 pub use free_fly_camera::{
     DebugCameraOverlay, FreeFlyCam, free_fly_look_system, free_fly_move_system,
@@ -26,8 +33,18 @@ pub use gravity_oriented_camera::{
     GravityDirection, GravityOrientedCamera, gravity_orient_rotation_system,
     gravity_up_alignment_system,
 };
+pub use interaction::{
+    ChunkVoxelWorld, CurrentTarget, InteractionConfig, InteractionIntent, InteractionRefusal,
+    InteractionSystem, InteractionView, PlayerCapsule, TargetedVoxel, voxel_chunk_coords,
+};
+pub use player_mode::{
+    AttachedTo, OnFootPhysics, PlayerMode, PlayerModeInput, ShipAirlock, attachment_system,
+    build_player_mode_schedule, piloting_input_system, player_mode_camera_system,
+    player_mode_system,
+};
 pub use spaceship_controller::{
-    SpaceshipController, apply_velocity_system, spaceship_rotation_system, spaceship_thrust_system,
+    ShipHull, SpaceshipController, apply_velocity_system, spaceship_collision_rumble_system,
+    spaceship_rotation_system, spaceship_thrust_system,
 };
 pub use third_person_camera::{
     ThirdPersonCamera, third_person_follow_system, third_person_orbit_system,
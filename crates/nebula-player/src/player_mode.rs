@@ -0,0 +1,461 @@
+//! Walk-to-orbit player mode transitions: on foot, boarding a ship, piloting.
+//!
+//! [`PlayerMode`] is a per-player state machine driven by
+//! [`player_mode_system`]. Interacting near a ship's airlock attaches the
+//! player to the ship with [`AttachedTo`] and starts [`PlayerMode::Boarding`]:
+//! the on-foot capsule is removed from physics and the player walks from the
+//! airlock to the pilot seat in ship-local space. Once seated the player is
+//! [`PlayerMode::Piloting`] and [`piloting_input_system`] hands keyboard and
+//! mouse input to the ship's [`SpaceshipController`]. Interacting again
+//! detaches the player at the airlock and restores the on-foot capsule, so
+//! gravity and collision take over where the ship was left.
+//!
+//! [`attachment_system`] is the transform hierarchy: it derives each attached
+//! entity's [`WorldPos`] and [`Rotation`] from its parent every tick, so a
+//! seated player moves exactly with the ship. [`player_mode_camera_system`]
+//! starts a [`CameraTransition`] on the [`ActiveCamera`] whenever a player's
+//! mode changes.
+
+use std::f32::consts::FRAC_PI_4;
+
+use bevy_ecs::prelude::*;
+use glam::{Mat3, Quat, Vec3};
+use nebula_ecs::{LocalPos, Rotation, Velocity, WorldPos};
+use nebula_input::{KeyboardState, MouseState};
+use nebula_math::{Vec3I128, WorldPosition};
+use nebula_physics::{
+    LocalGravity, PhysicsOrigin, PhysicsWorld, PlayerPhysics, spawn_player_physics, world_to_local,
+};
+
+use crate::camera_transition::{CameraSnapshot, CameraTransition, EasingFunction};
+use crate::floating_origin::{ActiveCamera, FloatingOrigin};
+use crate::spaceship_controller::{
+    SpaceshipController, spaceship_rotation_system, spaceship_thrust_system,
+};
+
+/// Vertical field of view of the on-foot camera, in radians.
+pub const ON_FOOT_FOV: f32 = FRAC_PI_4;
+
+/// Vertical field of view of the cockpit camera, in radians.
+pub const PILOTING_FOV: f32 = FRAC_PI_4 * 1.5;
+
+/// Height of the on-foot camera above the player origin, in millimeters.
+pub const EYE_HEIGHT_MM: f32 = 700.0;
+
+/// Duration of the camera transition started on each mode change, in ticks.
+pub const MODE_TRANSITION_TICKS: u32 = 30;
+
+/// What the player is currently doing.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlayerMode {
+    /// Walking under the on-foot capsule controller.
+    #[default]
+    OnFoot,
+    /// Attached to `ship`, walking from its airlock to the pilot seat.
+    Boarding {
+        /// Ship being boarded.
+        ship: Entity,
+    },
+    /// Seated in `ship`, with input driving its flight controller.
+    Piloting {
+        /// Ship being flown.
+        ship: Entity,
+    },
+}
+
+impl PlayerMode {
+    /// Ship the player is aboard, if any.
+    pub fn ship(&self) -> Option<Entity> {
+        match *self {
+            Self::OnFoot => None,
+            Self::Boarding { ship } | Self::Piloting { ship } => Some(ship),
+        }
+    }
+
+    /// Ship whose controller receives the player's input, if any.
+    pub fn piloted_ship(&self) -> Option<Entity> {
+        match *self {
+            Self::Piloting { ship } => Some(ship),
+            _ => None,
+        }
+    }
+
+    /// Whether on-foot movement and look systems should run.
+    pub fn is_on_foot(&self) -> bool {
+        matches!(self, Self::OnFoot)
+    }
+
+    /// Camera field of view used in this mode, in radians.
+    pub fn camera_fov(&self) -> f32 {
+        match self {
+            Self::Piloting { .. } => PILOTING_FOV,
+            _ => ON_FOOT_FOV,
+        }
+    }
+}
+
+/// Boarding layout of a ship, in ship-local millimeters.
+///
+/// Offsets are rotated by the ship's [`Rotation`] and added to its
+/// [`WorldPos`]. A seated player faces the ship's forward (-Z) axis.
+#[derive(Component, Clone, Debug)]
+pub struct ShipAirlock {
+    /// Where players enter and leave the ship.
+    pub airlock_offset: Vec3,
+    /// Where the pilot sits.
+    pub seat_offset: Vec3,
+    /// How close to the airlock, in mm, a player must be to board.
+    pub airlock_radius: f32,
+    /// How far a boarding player walks toward the seat each tick, in mm.
+    pub boarding_speed: f32,
+}
+
+impl Default for ShipAirlock {
+    fn default() -> Self {
+        Self {
+            airlock_offset: Vec3::new(0.0, -1_000.0, 3_000.0),
+            seat_offset: Vec3::new(0.0, 0.0, -2_000.0),
+            airlock_radius: 2_000.0,
+            boarding_speed: 100.0,
+        }
+    }
+}
+
+impl ShipAirlock {
+    /// World position of `offset` on a ship at `ship_pos` with `ship_rot`.
+    pub fn world_point(ship_pos: &WorldPosition, ship_rot: Quat, offset: Vec3) -> WorldPosition {
+        let delta = sanitized(ship_rot) * offset;
+        *ship_pos + Vec3I128::new(round_mm(delta.x), round_mm(delta.y), round_mm(delta.z))
+    }
+}
+
+/// Parents an entity to another: its [`WorldPos`] and [`Rotation`] follow
+/// the parent's, offset in the parent's local frame.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct AttachedTo {
+    /// Entity this one moves with.
+    pub parent: Entity,
+    /// Position relative to the parent, in parent-local millimeters.
+    pub offset: Vec3,
+    /// Orientation relative to the parent.
+    pub rotation: Quat,
+}
+
+/// On-foot physics capsule of a player, present only while on foot.
+#[derive(Component)]
+pub struct OnFootPhysics(pub PlayerPhysics);
+
+/// Per-tick player requests that drive [`player_mode_system`].
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct PlayerModeInput {
+    /// Board the nearest ship, or leave the piloted one.
+    pub interact: bool,
+}
+
+/// Advances every player's [`PlayerMode`].
+///
+/// - On foot, interacting within [`ShipAirlock::airlock_radius`] of an
+///   airlock attaches the player to that ship, frees the on-foot capsule,
+///   and starts boarding.
+/// - Boarding walks the attachment toward the seat at
+///   [`ShipAirlock::boarding_speed`] and switches to piloting on arrival.
+/// - Piloting, interacting detaches the player at the airlock, stops them,
+///   stands them upright, and respawns the on-foot capsule.
+///
+/// A player whose ship despawns is dropped back on foot where they stand.
+#[allow(clippy::type_complexity)]
+pub fn player_mode_system(
+    mut commands: Commands,
+    input: Res<PlayerModeInput>,
+    physics_origin: Option<Res<PhysicsOrigin>>,
+    mut physics: Option<ResMut<PhysicsWorld>>,
+    mut players: Query<(
+        Entity,
+        &mut PlayerMode,
+        &mut WorldPos,
+        &mut Rotation,
+        Option<&mut AttachedTo>,
+        Option<&OnFootPhysics>,
+        Option<&mut Velocity>,
+        Option<&LocalGravity>,
+    )>,
+    ships: Query<(Entity, &WorldPos, &Rotation, &ShipAirlock), Without<PlayerMode>>,
+) {
+    let origin = physics_origin.map(|o| o.world_origin).unwrap_or_default();
+    for (entity, mut mode, mut pos, mut rot, attached, on_foot, velocity, gravity) in &mut players {
+        let up = gravity.map_or(Vec3::Y, |g| -g.direction);
+        match *mode {
+            PlayerMode::OnFoot => {
+                if !input.interact {
+                    continue;
+                }
+                let nearest = ships
+                    .iter()
+                    .map(|(ship, ship_pos, ship_rot, airlock)| {
+                        let door = ShipAirlock::world_point(
+                            &ship_pos.0,
+                            ship_rot.0,
+                            airlock.airlock_offset,
+                        );
+                        (
+                            ship,
+                            ship_pos,
+                            ship_rot,
+                            distance_mm(&pos.0, &door),
+                            airlock,
+                        )
+                    })
+                    .filter(|(.., distance, airlock)| *distance <= airlock.airlock_radius)
+                    .min_by(|a, b| a.3.total_cmp(&b.3));
+                let Some((ship, ship_pos, ship_rot, ..)) = nearest else {
+                    continue;
+                };
+                let inverse = sanitized(ship_rot.0).inverse();
+                let delta = pos.0 - ship_pos.0;
+                let offset = inverse * Vec3::new(delta.x as f32, delta.y as f32, delta.z as f32);
+                commands.entity(entity).insert(AttachedTo {
+                    parent: ship,
+                    offset,
+                    rotation: sanitized(inverse * sanitized(rot.0)),
+                });
+                if let Some(on_foot) = on_foot {
+                    if let Some(physics) = physics.as_deref_mut() {
+                        remove_capsule(physics, &on_foot.0);
+                    }
+                    commands.entity(entity).remove::<OnFootPhysics>();
+                }
+                *mode = PlayerMode::Boarding { ship };
+            }
+            PlayerMode::Boarding { ship } => {
+                let (Ok((.., airlock)), Some(mut attached)) = (ships.get(ship), attached) else {
+                    commands.entity(entity).remove::<AttachedTo>();
+                    rot.0 = upright(sanitized(rot.0) * Vec3::NEG_Z, up);
+                    restore_on_foot(
+                        &mut commands,
+                        entity,
+                        physics.as_deref_mut(),
+                        &pos.0,
+                        &origin,
+                    );
+                    *mode = PlayerMode::OnFoot;
+                    continue;
+                };
+                let remaining = airlock.seat_offset - attached.offset;
+                let distance = remaining.length();
+                if !distance.is_finite() || distance <= airlock.boarding_speed {
+                    attached.offset = airlock.seat_offset;
+                    attached.rotation = Quat::IDENTITY;
+                    *mode = PlayerMode::Piloting { ship };
+                } else {
+                    let step = airlock.boarding_speed / distance;
+                    attached.offset += remaining * step;
+                    attached.rotation = sanitized(attached.rotation.slerp(Quat::IDENTITY, step));
+                }
+            }
+            PlayerMode::Piloting { ship } => {
+                let ship = ships.get(ship).ok();
+                if !input.interact && ship.is_some() {
+                    continue;
+                }
+                commands.entity(entity).remove::<AttachedTo>();
+                let facing = match ship {
+                    Some((_, ship_pos, ship_rot, airlock)) => {
+                        pos.0 = ShipAirlock::world_point(
+                            &ship_pos.0,
+                            ship_rot.0,
+                            airlock.airlock_offset,
+                        );
+                        sanitized(ship_rot.0) * Vec3::NEG_Z
+                    }
+                    None => sanitized(rot.0) * Vec3::NEG_Z,
+                };
+                rot.0 = upright(facing, up);
+                if let Some(mut velocity) = velocity {
+                    velocity.0 = Vec3I128::new(0, 0, 0);
+                }
+                restore_on_foot(
+                    &mut commands,
+                    entity,
+                    physics.as_deref_mut(),
+                    &pos.0,
+                    &origin,
+                );
+                *mode = PlayerMode::OnFoot;
+            }
+        }
+    }
+}
+
+/// Moves every [`AttachedTo`] entity with its parent.
+///
+/// Parents must not themselves be attached; attachment is one level deep.
+/// Entities whose parent is missing keep their last transform.
+pub fn attachment_system(
+    mut children: Query<(&AttachedTo, &mut WorldPos, &mut Rotation)>,
+    parents: Query<(&WorldPos, &Rotation), Without<AttachedTo>>,
+) {
+    for (attached, mut pos, mut rot) in &mut children {
+        let Ok((parent_pos, parent_rot)) = parents.get(attached.parent) else {
+            continue;
+        };
+        pos.0 = ShipAirlock::world_point(&parent_pos.0, parent_rot.0, attached.offset);
+        rot.0 = sanitized(sanitized(parent_rot.0) * attached.rotation);
+    }
+}
+
+/// Starts a [`CameraTransition`] on the [`ActiveCamera`] when a player's
+/// [`PlayerMode`] changes.
+///
+/// The transition eases from the camera's current pose to the player's eye
+/// when on foot, or to the pilot seat when boarding or piloting, and from
+/// the previous mode's field of view to [`PlayerMode::camera_fov`].
+#[allow(clippy::type_complexity)]
+pub fn player_mode_camera_system(
+    mut commands: Commands,
+    origin: Option<Res<FloatingOrigin>>,
+    players: Query<(Ref<PlayerMode>, &WorldPos, &Rotation)>,
+    ships: Query<(&WorldPos, &Rotation, &ShipAirlock), Without<PlayerMode>>,
+    cameras: Query<(Entity, &LocalPos, &Rotation, Option<&CameraTransition>), With<ActiveCamera>>,
+) {
+    let origin = origin.map(|o| o.0).unwrap_or_default();
+    for (mode, pos, rot) in &players {
+        if !mode.is_changed() || mode.is_added() {
+            continue;
+        }
+        let (target, facing) = match mode.ship().and_then(|ship| ships.get(ship).ok()) {
+            Some((ship_pos, ship_rot, airlock)) => (
+                ShipAirlock::world_point(&ship_pos.0, ship_rot.0, airlock.seat_offset),
+                sanitized(ship_rot.0),
+            ),
+            None => {
+                let facing = sanitized(rot.0);
+                let eye = facing * Vec3::Y * EYE_HEIGHT_MM;
+                let eye = Vec3I128::new(round_mm(eye.x), round_mm(eye.y), round_mm(eye.z));
+                (pos.0 + eye, facing)
+            }
+        };
+        let delta = target - origin;
+        let to = CameraSnapshot::from_camera(
+            Vec3::new(delta.x as f32, delta.y as f32, delta.z as f32),
+            facing,
+            mode.camera_fov(),
+        );
+        for (camera, local, camera_rot, current) in &cameras {
+            let from_fov = current.map_or_else(
+                || match *mode {
+                    PlayerMode::OnFoot => PILOTING_FOV,
+                    _ => ON_FOOT_FOV,
+                },
+                |t| t.to.fov_y,
+            );
+            let from = CameraSnapshot::from_camera(
+                Vec3::new(local.0.x, local.0.y, local.0.z),
+                sanitized(camera_rot.0),
+                from_fov,
+            );
+            commands.entity(camera).insert(CameraTransition::new(
+                from,
+                to,
+                MODE_TRANSITION_TICKS,
+                EasingFunction::EaseInOut,
+            ));
+        }
+    }
+}
+
+/// Feeds keyboard and mouse input to the [`SpaceshipController`] of every
+/// ship a player is [piloting](PlayerMode::Piloting).
+///
+/// Ships must carry [`SpaceshipController`], [`Rotation`], and
+/// [`Velocity`]. On-foot controllers should be skipped for players that are
+/// not [on foot](PlayerMode::is_on_foot).
+pub fn piloting_input_system(world: &mut World, mouse: &MouseState, keyboard: &KeyboardState) {
+    let ships: Vec<Entity> = world
+        .query::<&PlayerMode>()
+        .iter(world)
+        .filter_map(PlayerMode::piloted_ship)
+        .collect();
+    let mut query = world.query::<(&SpaceshipController, &mut Rotation, &mut Velocity)>();
+    for ship in ships {
+        let Ok((controller, mut rotation, mut velocity)) = query.get_mut(world, ship) else {
+            continue;
+        };
+        spaceship_rotation_system(mouse, keyboard, controller, &mut rotation);
+        spaceship_thrust_system(keyboard, controller, &rotation, &mut velocity);
+    }
+}
+
+/// Adds the player mode systems to `schedule`, chained so attachments and
+/// camera transitions see this tick's mode changes.
+pub fn build_player_mode_schedule(schedule: &mut Schedule) {
+    schedule.add_systems(
+        (
+            player_mode_system,
+            attachment_system,
+            player_mode_camera_system,
+        )
+            .chain(),
+    );
+}
+
+/// Spawns a fresh on-foot capsule at `pos`, if a physics world exists.
+fn restore_on_foot(
+    commands: &mut Commands,
+    entity: Entity,
+    physics: Option<&mut PhysicsWorld>,
+    pos: &WorldPosition,
+    origin: &WorldPosition,
+) {
+    if let Some(physics) = physics {
+        let capsule = spawn_player_physics(physics, world_to_local(pos, origin));
+        commands.entity(entity).insert(OnFootPhysics(capsule));
+    }
+}
+
+/// Removes the on-foot capsule body and its collider from `physics`.
+fn remove_capsule(physics: &mut PhysicsWorld, capsule: &PlayerPhysics) {
+    physics.rigid_body_set.remove(
+        capsule.body_handle,
+        &mut physics.island_manager,
+        &mut physics.collider_set,
+        &mut physics.impulse_joint_set,
+        &mut physics.multibody_joint_set,
+        true,
+    );
+}
+
+/// Rotation standing upright along `up` and facing `forward` projected onto
+/// the ground plane. Falls back to any horizontal heading when `forward` is
+/// vertical or degenerate.
+fn upright(forward: Vec3, up: Vec3) -> Quat {
+    let up = up.try_normalize().unwrap_or(Vec3::Y);
+    let flat = (forward - up * forward.dot(up))
+        .try_normalize()
+        .unwrap_or_else(|| up.any_orthonormal_vector());
+    let back = -flat;
+    sanitized(Quat::from_mat3(&Mat3::from_cols(up.cross(back), up, back)))
+}
+
+/// `rotation` normalized, or identity if it is not a usable rotation.
+fn sanitized(rotation: Quat) -> Quat {
+    if rotation.is_finite() && rotation.length_squared() > 1e-6 {
+        rotation.normalize()
+    } else {
+        Quat::IDENTITY
+    }
+}
+
+/// Straight-line distance between two world positions, in millimeters.
+fn distance_mm(a: &WorldPosition, b: &WorldPosition) -> f32 {
+    let delta = *a - *b;
+    Vec3::new(delta.x as f32, delta.y as f32, delta.z as f32).length()
+}
+
+/// Rounds a millimeter offset to whole world units.
+fn round_mm(mm: f32) -> i128 {
+    mm.round() as i128
+}
+
+#[cfg(test)]
+#[path = "player_mode_tests.rs"]
+mod tests;
@@ -0,0 +1,292 @@
+//! Unit tests for player mode transitions.
+
+use super::*;
+use crate::camera_transition::camera_transition_system;
+use nebula_input::RawKeyEvent;
+use nebula_physics::{local_to_world, player_movement_step};
+use rapier3d::prelude::{ColliderBuilder, Vector as RapierVector};
+use std::f32::consts::FRAC_PI_2;
+use winit::event::ElementState;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+const SHIP_START: WorldPosition = WorldPosition {
+    x: 0,
+    y: 3_000,
+    z: 0,
+};
+
+/// Ship airlock at ground level, 3 m behind the hull.
+fn airlock() -> ShipAirlock {
+    ShipAirlock {
+        airlock_offset: Vec3::new(0.0, -2_100.0, 3_000.0),
+        ..Default::default()
+    }
+}
+
+struct Scene {
+    world: World,
+    schedule: Schedule,
+    player: Entity,
+    ship: Entity,
+    camera: Entity,
+}
+
+impl Scene {
+    /// A player standing on flat ground 1 m from a landed ship's airlock.
+    fn new() -> Self {
+        let mut world = World::new();
+        let mut physics = PhysicsWorld::new();
+        physics.collider_set.insert(
+            ColliderBuilder::cuboid(100.0, 0.5, 100.0)
+                .translation(RapierVector::new(0.0, -0.5, 0.0))
+                .build(),
+        );
+        let player_pos = WorldPosition::new(0, 900, 2_000);
+        let capsule = spawn_player_physics(
+            &mut physics,
+            world_to_local(&player_pos, &WorldPosition::default()),
+        );
+        physics.step();
+        world.insert_resource(physics);
+        world.insert_resource(PhysicsOrigin::default());
+        world.insert_resource(FloatingOrigin::default());
+        world.insert_resource(PlayerModeInput::default());
+
+        let ship = world
+            .spawn((
+                WorldPos(SHIP_START),
+                Rotation::default(),
+                Velocity::default(),
+                SpaceshipController::default(),
+                airlock(),
+            ))
+            .id();
+        let player = world
+            .spawn((
+                PlayerMode::default(),
+                WorldPos(player_pos),
+                Rotation::default(),
+                Velocity::default(),
+                OnFootPhysics(capsule),
+            ))
+            .id();
+        let camera = world
+            .spawn((ActiveCamera, LocalPos::default(), Rotation::default()))
+            .id();
+
+        let mut schedule = Schedule::default();
+        build_player_mode_schedule(&mut schedule);
+        schedule.add_systems(camera_transition_system.after(player_mode_camera_system));
+        // Settle spawn-time change detection before scripting input.
+        schedule.run(&mut world);
+        Self {
+            world,
+            schedule,
+            player,
+            ship,
+            camera,
+        }
+    }
+
+    fn tick(&mut self, interact: bool) {
+        self.world.resource_mut::<PlayerModeInput>().interact = interact;
+        self.schedule.run(&mut self.world);
+        for entity in [self.player, self.camera] {
+            let rotation = self.world.get::<Rotation>(entity).map(|r| r.0);
+            assert!(
+                rotation.is_some_and(Quat::is_finite),
+                "non-finite rotation on {entity:?}"
+            );
+        }
+    }
+
+    fn mode(&self) -> PlayerMode {
+        *self.world.get::<PlayerMode>(self.player).unwrap()
+    }
+
+    fn pos(&self, entity: Entity) -> WorldPosition {
+        self.world.get::<WorldPos>(entity).unwrap().0
+    }
+
+    fn ship_point(&self, offset: Vec3) -> WorldPosition {
+        let rotation = self.world.get::<Rotation>(self.ship).unwrap().0;
+        ShipAirlock::world_point(&self.pos(self.ship), rotation, offset)
+    }
+
+    /// Board, then tick until seated.
+    fn board(&mut self) {
+        self.tick(true);
+        assert_eq!(self.mode(), PlayerMode::Boarding { ship: self.ship });
+        for _ in 0..200 {
+            if self.mode() != (PlayerMode::Boarding { ship: self.ship }) {
+                break;
+            }
+            self.tick(false);
+        }
+        assert_eq!(self.mode(), PlayerMode::Piloting { ship: self.ship });
+    }
+
+    /// Move the ship by its velocity, as the ship's own systems would.
+    fn fly(&mut self) {
+        let mut query = self.world.query::<(&Velocity, &mut WorldPos)>();
+        let (velocity, mut pos) = query.get_mut(&mut self.world, self.ship).unwrap();
+        crate::apply_velocity_system(velocity, &mut pos);
+    }
+}
+
+fn pressed(key: KeyCode) -> KeyboardState {
+    let mut keyboard = KeyboardState::new();
+    keyboard.process_raw(RawKeyEvent {
+        key: PhysicalKey::Code(key),
+        state: ElementState::Pressed,
+        repeat: false,
+    });
+    keyboard
+}
+
+#[test]
+fn test_scripted_walk_board_fly_and_disembark() {
+    let mut scene = Scene::new();
+
+    // Board: the capsule leaves physics and the camera starts easing.
+    scene.tick(true);
+    assert_eq!(scene.mode(), PlayerMode::Boarding { ship: scene.ship });
+    assert!(scene.world.get::<AttachedTo>(scene.player).is_some());
+    assert!(scene.world.get::<OnFootPhysics>(scene.player).is_none());
+    assert!(
+        scene
+            .world
+            .resource::<PhysicsWorld>()
+            .rigid_body_set
+            .is_empty()
+    );
+    let transition = scene.world.get::<CameraTransition>(scene.camera).unwrap();
+    assert_eq!(transition.to.fov_y, ON_FOOT_FOV);
+
+    for _ in 0..200 {
+        if scene.mode() != (PlayerMode::Boarding { ship: scene.ship }) {
+            break;
+        }
+        scene.tick(false);
+    }
+    assert_eq!(scene.mode(), PlayerMode::Piloting { ship: scene.ship });
+    let transition = scene.world.get::<CameraTransition>(scene.camera).unwrap();
+    assert_eq!(transition.to.fov_y, PILOTING_FOV);
+
+    // Fly: thrust forward and turn, the seated player stays in the seat.
+    let mouse = MouseState::new();
+    let thrust = pressed(KeyCode::KeyW);
+    let roll = pressed(KeyCode::KeyQ);
+    for tick in 0..120 {
+        let keyboard = if tick % 3 == 0 { &roll } else { &thrust };
+        piloting_input_system(&mut scene.world, &mouse, keyboard);
+        scene.fly();
+        scene.tick(false);
+        assert_eq!(
+            scene.pos(scene.player),
+            scene.ship_point(airlock().seat_offset)
+        );
+    }
+    assert!((scene.pos(scene.ship) - SHIP_START).length_squared() > 0);
+    assert!(scene.world.get::<Velocity>(scene.ship).unwrap().0 != Velocity::default().0);
+
+    // Land facing +X and leave through the airlock.
+    scene.world.get_mut::<WorldPos>(scene.ship).unwrap().0 = SHIP_START;
+    scene.world.get_mut::<Rotation>(scene.ship).unwrap().0 = Quat::from_rotation_y(FRAC_PI_2);
+    scene.tick(true);
+    assert_eq!(scene.mode(), PlayerMode::OnFoot);
+    assert!(scene.world.get::<AttachedTo>(scene.player).is_none());
+    let door = scene.ship_point(airlock().airlock_offset);
+    assert_eq!(scene.pos(scene.player), door);
+    assert_eq!(
+        scene.world.get::<Velocity>(scene.player).unwrap().0,
+        Velocity::default().0
+    );
+    let rotation = scene.world.get::<Rotation>(scene.player).unwrap().0;
+    assert!((rotation * Vec3::Y - Vec3::Y).length() < 1e-4, "upright");
+    assert!((rotation * Vec3::NEG_Z - Vec3::NEG_X).length() < 1e-4);
+    let transition = scene.world.get::<CameraTransition>(scene.camera).unwrap();
+    assert_eq!(transition.to.fov_y, ON_FOOT_FOV);
+
+    // The restored capsule falls under gravity and stands on the ground.
+    scene
+        .world
+        .resource_scope(|world, mut physics: Mut<PhysicsWorld>| {
+            let mut capsule = world.get_mut::<OnFootPhysics>(scene.player).unwrap();
+            for _ in 0..120 {
+                player_movement_step(&mut capsule.0, &mut physics, Vec3::ZERO, false, 1.0 / 60.0);
+                physics.step();
+            }
+            assert!(capsule.0.grounded);
+            let body = &physics.rigid_body_set[capsule.0.body_handle];
+            let t = body.translation();
+            let standing = local_to_world(&Vec3::new(t.x, t.y, t.z), &WorldPosition::default());
+            assert!((standing.x - door.x).abs() <= 1 && (standing.z - door.z).abs() <= 1);
+            assert!((850..=1_000).contains(&standing.y), "y = {}", standing.y);
+        });
+}
+
+#[test]
+fn test_interact_away_from_airlock_stays_on_foot() {
+    let mut scene = Scene::new();
+    scene.world.get_mut::<WorldPos>(scene.player).unwrap().0 = WorldPosition::new(0, 900, 20_000);
+    scene.tick(true);
+    assert_eq!(scene.mode(), PlayerMode::OnFoot);
+    assert!(scene.world.get::<OnFootPhysics>(scene.player).is_some());
+    assert!(scene.world.get::<CameraTransition>(scene.camera).is_none());
+}
+
+#[test]
+fn test_ship_despawned_while_boarding_drops_player_on_foot() {
+    let mut scene = Scene::new();
+    scene.tick(true);
+    let pos = scene.pos(scene.player);
+    scene.world.despawn(scene.ship);
+    scene.tick(false);
+    assert_eq!(scene.mode(), PlayerMode::OnFoot);
+    assert_eq!(scene.pos(scene.player), pos);
+    assert!(scene.world.get::<AttachedTo>(scene.player).is_none());
+    assert!(scene.world.get::<OnFootPhysics>(scene.player).is_some());
+}
+
+#[test]
+fn test_degenerate_rotations_stay_finite() {
+    let mut scene = Scene::new();
+    scene.world.get_mut::<Rotation>(scene.player).unwrap().0 = Quat::from_xyzw(0.0, 0.0, 0.0, 0.0);
+    scene.world.get_mut::<Rotation>(scene.ship).unwrap().0 =
+        Quat::from_xyzw(f32::NAN, 0.0, 0.0, 1.0);
+    scene.board();
+    scene.tick(true);
+    assert_eq!(scene.mode(), PlayerMode::OnFoot);
+
+    // Facing straight up still yields an upright heading.
+    let rotation = upright(Vec3::Y, Vec3::Y);
+    assert!(rotation.is_finite());
+    assert!((rotation * Vec3::Y - Vec3::Y).length() < 1e-4);
+}
+
+#[test]
+fn test_only_piloted_ship_receives_input() {
+    let mut scene = Scene::new();
+    let thrust = pressed(KeyCode::KeyW);
+    piloting_input_system(&mut scene.world, &MouseState::new(), &thrust);
+    assert_eq!(
+        scene.world.get::<Velocity>(scene.ship).unwrap().0,
+        Velocity::default().0
+    );
+
+    scene.board();
+    piloting_input_system(&mut scene.world, &MouseState::new(), &thrust);
+    assert!(scene.world.get::<Velocity>(scene.ship).unwrap().0 != Velocity::default().0);
+}
+
+#[test]
+fn test_mode_helpers() {
+    let ship = Entity::from_raw(7);
+    assert!(PlayerMode::OnFoot.is_on_foot());
+    assert_eq!(PlayerMode::OnFoot.ship(), None);
+    assert_eq!(PlayerMode::Boarding { ship }.ship(), Some(ship));
+    assert_eq!(PlayerMode::Boarding { ship }.piloted_ship(), None);
+    assert_eq!(PlayerMode::Piloting { ship }.piloted_ship(), Some(ship));
+    assert_eq!(PlayerMode::Piloting { ship }.camera_fov(), PILOTING_FOV);
+}
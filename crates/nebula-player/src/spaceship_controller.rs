@@ -2,17 +2,21 @@
 //!
 //! Provides mouse-driven pitch/yaw, Q/E roll, WASD thrust with Shift boost,
 //! and persistent velocity (no drag). Velocity is stored in i128 world-space
-//! units (mm/tick) for unlimited precision at any speed.
+//! units (mm/tick) for unlimited precision at any speed. Collisions reported
+//! by the physics step rumble the gamepad in proportion to their impulse.
 
+use bevy_ecs::prelude::Component;
 use glam::{Quat, Vec3};
 use nebula_ecs::{Rotation, Velocity, WorldPos};
-use nebula_input::{KeyboardState, MouseState};
+use nebula_input::{GamepadManager, KeyboardState, MouseState};
 use nebula_math::Vec3I128;
+use nebula_physics::{PhysicsWorld, ship_hull_collider};
+use rapier3d::prelude::{ColliderHandle, RigidBodyBuilder, RigidBodyHandle, Vector};
 use winit::keyboard::{KeyCode, PhysicalKey};
 
 /// Tags an entity as a player-controlled spaceship with 6DOF flight.
 /// The entity must also have `WorldPos`, `Rotation`, and `Velocity` components.
-#[derive(Clone, Debug)]
+#[derive(Component, Clone, Debug)]
 pub struct SpaceshipController {
     /// Base thrust force in mm/tick². Applied each tick the thrust key is held.
     pub thrust: i128,
@@ -25,6 +29,10 @@ pub struct SpaceshipController {
     /// Whether the ship is currently in a gravity well.
     /// Set by the gravity detection system (phase 34), read here for HUD.
     pub in_gravity_well: bool,
+    /// Collision impulse in N·s that rumbles the gamepad at full strength.
+    pub rumble_full_impulse: f32,
+    /// How long a collision rumble lasts, in milliseconds.
+    pub rumble_duration_ms: u32,
 }
 
 impl Default for SpaceshipController {
@@ -35,6 +43,8 @@ impl Default for SpaceshipController {
             mouse_sensitivity: 0.002,
             roll_speed: 0.03,
             in_gravity_well: false,
+            rumble_full_impulse: 5_000.0,
+            rumble_duration_ms: 150,
         }
     }
 }
@@ -50,6 +60,104 @@ impl SpaceshipController {
         // mm/tick → m/s: multiply by ticks/s, divide by 1000
         magnitude_mm_per_tick * ticks_per_second / 1000.0
     }
+
+    /// Rumble strength `(strong, weak)` for this tick's collision impulses.
+    ///
+    /// Uses the hardest hit; its ratio to
+    /// [`rumble_full_impulse`](Self::rumble_full_impulse) drives the strong
+    /// motor, and the weak motor saturates at half that impulse so light
+    /// scrapes are still felt. Returns `None` when nothing was hit.
+    pub fn collision_rumble(&self, impulses: impl IntoIterator<Item = f32>) -> Option<(f32, f32)> {
+        let peak = impulses.into_iter().fold(0.0_f32, f32::max);
+        if peak <= 0.0 || self.rumble_full_impulse <= 0.0 {
+            return None;
+        }
+        let strength = peak / self.rumble_full_impulse;
+        Some((strength.min(1.0), (strength * 2.0).min(1.0)))
+    }
+}
+
+/// Physics body that carries a ship's collider along its flight path.
+///
+/// The controller integrates the ship itself; the hull only mirrors that
+/// motion into the [`PhysicsWorld`] so hits with terrain and debris are
+/// reported as collision impulses.
+#[derive(Clone, Copy, Debug)]
+pub struct ShipHull {
+    /// Rapier body of the hull.
+    pub body: RigidBodyHandle,
+    /// Hull collider, reporting contact forces.
+    pub collider: ColliderHandle,
+}
+
+impl ShipHull {
+    /// Insert a gravity-free hull of `radius` meters at the physics origin.
+    pub fn spawn(physics: &mut PhysicsWorld, radius: f32) -> Self {
+        let body = physics.rigid_body_set.insert(
+            RigidBodyBuilder::dynamic()
+                .gravity_scale(0.0)
+                .lock_rotations()
+                .build(),
+        );
+        let collider = physics.collider_set.insert_with_parent(
+            ship_hull_collider(radius),
+            body,
+            &mut physics.rigid_body_set,
+        );
+        Self { body, collider }
+    }
+
+    /// Place the hull at `local_pos` (meters from the physics origin) moving
+    /// at `velocity`, so the next physics step sees the ship's motion.
+    pub fn follow(
+        &self,
+        physics: &mut PhysicsWorld,
+        local_pos: Vec3,
+        velocity: &Velocity,
+        ticks_per_second: f64,
+    ) {
+        let Some(body) = physics.rigid_body_set.get_mut(self.body) else {
+            return;
+        };
+        // mm/tick → m/s
+        let scale = ticks_per_second / 1000.0;
+        let v = &velocity.0;
+        body.set_translation(Vector::new(local_pos.x, local_pos.y, local_pos.z), false);
+        body.set_linvel(
+            Vector::new(
+                (v.x as f64 * scale) as f32,
+                (v.y as f64 * scale) as f32,
+                (v.z as f64 * scale) as f32,
+            ),
+            true,
+        );
+    }
+
+    /// Impulses the hull took during the last physics step.
+    pub fn impulses<'a>(&self, physics: &'a PhysicsWorld) -> impl Iterator<Item = f32> + 'a {
+        physics.impulses_on(self.collider)
+    }
+}
+
+/// Rumble every connected gamepad for collisions the ship took this tick.
+///
+/// `impulses` are the magnitudes of the ship's collisions after the physics
+/// step, as returned by [`ShipHull::impulses`]. Pads without force feedback
+/// are skipped.
+pub fn spaceship_collision_rumble_system(
+    impulses: impl IntoIterator<Item = f32>,
+    ship: &SpaceshipController,
+    gamepads: &mut GamepadManager,
+) {
+    let Some((strong, weak)) = ship.collision_rumble(impulses) else {
+        return;
+    };
+    let ids: Vec<_> = gamepads.connected_gamepads().collect();
+    for id in ids {
+        if let Err(err) = gamepads.rumble(id, strong, weak, ship.rumble_duration_ms) {
+            tracing::warn!("Gamepad rumble failed: {err}");
+        }
+    }
 }
 
 /// Update rotation from mouse delta (pitch/yaw) and Q/E (roll).
@@ -238,6 +346,52 @@ mod tests {
         assert!((speed - 60.0).abs() < 0.1);
     }
 
+    #[test]
+    fn test_collision_rumble_proportional_to_impulse() {
+        let ship = SpaceshipController::default();
+        assert_eq!(ship.collision_rumble([]), None);
+        assert_eq!(ship.collision_rumble([0.0]), None);
+
+        let (light_strong, light_weak) = ship.collision_rumble([500.0]).unwrap();
+        let (hard_strong, _) = ship.collision_rumble([100.0, 2_500.0]).unwrap();
+        assert!((light_strong - 0.1).abs() < 1e-6);
+        assert!((light_weak - 0.2).abs() < 1e-6);
+        assert!((hard_strong - 0.5).abs() < 1e-6);
+        assert_eq!(ship.collision_rumble([1e9]), Some((1.0, 1.0)));
+    }
+
+    #[test]
+    fn test_hull_flown_into_wall_reports_impulses() {
+        use rapier3d::prelude::ColliderBuilder;
+
+        let mut physics = PhysicsWorld::new();
+        physics.set_gravity(0.0, 0.0, 0.0);
+        physics.collider_set.insert(
+            ColliderBuilder::cuboid(10.0, 10.0, 0.5)
+                .translation(Vector::new(0.0, 0.0, -5.0))
+                .build(),
+        );
+        let hull = ShipHull::spawn(&mut physics, 1.0);
+
+        // 500 mm/tick at 60 Hz is 30 m/s toward the wall.
+        let velocity = Velocity::new(0, 0, -500);
+        let mut position = Vec3::ZERO;
+        let mut peak: f32 = 0.0;
+        for _ in 0..30 {
+            hull.follow(&mut physics, position, &velocity, 60.0);
+            physics.step();
+            peak = hull.impulses(&physics).fold(peak, f32::max);
+            let t = physics.rigid_body_set[hull.body].translation();
+            position = Vec3::new(t.x, t.y, t.z);
+        }
+        assert!(peak > 0.0, "hitting the wall should report an impulse");
+        assert!(
+            SpaceshipController::default()
+                .collision_rumble([peak])
+                .is_some()
+        );
+    }
+
     #[test]
     fn test_6dof_all_axes_independent() {
         let rot = Quat::IDENTITY;
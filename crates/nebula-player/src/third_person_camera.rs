@@ -1,4 +1,9 @@
 //! Third-person camera controller: orbit, zoom, and smooth follow.
+//!
+//! Collision with terrain and walls is handled separately by
+//! [`third_person_collision_system`](crate::third_person_collision_system),
+//! which shortens [`ThirdPersonCamera::effective_distance`] while the view
+//! from the look-at point is obstructed.
 
 use glam::{Mat3, Quat, Vec3};
 use nebula_ecs::{Rotation, WorldPos};
@@ -34,6 +39,21 @@ pub struct ThirdPersonCamera {
     pub pitch_min: f32,
     /// Maximum orbit pitch in radians (how far above the target).
     pub pitch_max: f32,
+    /// Radius of the sphere swept from the look-at point toward the camera
+    /// when probing for obstructions, in millimeters.
+    pub collision_radius: f32,
+    /// Gap kept between the camera and the nearest obstruction, in millimeters.
+    pub collision_margin: f32,
+    /// Rate, per second, at which the remaining gap closes when the camera
+    /// moves in (fast, so walls are not clipped). Each update closes
+    /// `1 - exp(-rate * dt)` of the gap.
+    pub collision_pull_in_speed: f32,
+    /// Rate, per second, at which the remaining gap closes when the camera
+    /// moves back out after an obstruction clears (slow, to avoid popping).
+    pub collision_restore_speed: f32,
+    /// Collision-limited distance in millimeters, or `None` before the first
+    /// collision update.
+    pub collision_distance: Option<f32>,
 }
 
 impl Default for ThirdPersonCamera {
@@ -50,10 +70,39 @@ impl Default for ThirdPersonCamera {
             follow_speed: 0.1,
             pitch_min: -10.0_f32.to_radians(),
             pitch_max: 80.0_f32.to_radians(),
+            collision_radius: 200.0,
+            collision_margin: 100.0,
+            collision_pull_in_speed: 40.0,
+            collision_restore_speed: 3.0,
+            collision_distance: None,
         }
     }
 }
 
+impl ThirdPersonCamera {
+    /// Unit vector from the look-at point toward the camera.
+    ///
+    /// `orbit_yaw = 0, orbit_pitch = 0` points along +Z, behind the target.
+    pub fn orbit_direction(&self) -> Vec3 {
+        let (sin_pitch, cos_pitch) = self.orbit_pitch.sin_cos();
+        let (sin_yaw, cos_yaw) = self.orbit_yaw.sin_cos();
+        Vec3::new(cos_pitch * sin_yaw, sin_pitch, cos_pitch * cos_yaw)
+    }
+
+    /// The point the camera looks at: the target raised by
+    /// [`height_offset`](Self::height_offset).
+    pub fn look_at_point(&self, target: &WorldPosition) -> WorldPosition {
+        *target + Vec3I128::new(0, self.height_offset as i128, 0)
+    }
+
+    /// Distance the camera is actually placed at: the collision-limited
+    /// distance when one has been computed, otherwise
+    /// [`distance`](Self::distance).
+    pub fn effective_distance(&self) -> f32 {
+        self.collision_distance.unwrap_or(self.distance)
+    }
+}
+
 /// Update orbit angles from right-mouse-button drag.
 ///
 /// Horizontal mouse delta adjusts `orbit_yaw`, vertical delta adjusts
@@ -84,29 +133,17 @@ pub fn third_person_zoom_system(mouse: &MouseState, cam: &mut ThirdPersonCamera)
 /// Smoothly follow the target and compute look-at rotation.
 ///
 /// The camera computes its desired world position from the target's position,
-/// the orbit angles, and the distance, then lerps toward it. The rotation
-/// is always recomputed to face the look-at point.
+/// the orbit angles, and the [effective distance](ThirdPersonCamera::effective_distance),
+/// then lerps toward it. The rotation is always recomputed to face the
+/// look-at point.
 pub fn third_person_follow_system(
     cam: &ThirdPersonCamera,
     target_pos: &WorldPos,
     cam_world_pos: &mut WorldPos,
     cam_rotation: &mut Rotation,
 ) {
-    // Look-at point: target position + height offset
-    let look_at_world = target_pos.0 + Vec3I128::new(0, cam.height_offset as i128, 0);
-
-    // Desired camera offset via spherical coordinates.
-    // orbit_yaw=0, orbit_pitch=0 → camera behind target at +Z.
-    let cos_pitch = cam.orbit_pitch.cos();
-    let sin_pitch = cam.orbit_pitch.sin();
-    let cos_yaw = cam.orbit_yaw.cos();
-    let sin_yaw = cam.orbit_yaw.sin();
-
-    let offset = Vec3::new(
-        cam.distance * cos_pitch * sin_yaw,
-        cam.distance * sin_pitch,
-        cam.distance * cos_pitch * cos_yaw,
-    );
+    let look_at_world = cam.look_at_point(&target_pos.0);
+    let offset = cam.orbit_direction() * cam.effective_distance();
 
     let desired_world =
         look_at_world + Vec3I128::new(offset.x as i128, offset.y as i128, offset.z as i128);
@@ -257,6 +294,35 @@ mod tests {
         assert!(cam.follow_speed <= 1.0);
     }
 
+    #[test]
+    fn test_orbit_direction_is_unit_and_behind_at_zero() {
+        let mut cam = ThirdPersonCamera {
+            orbit_pitch: 0.0,
+            ..Default::default()
+        };
+        assert!((cam.orbit_direction() - Vec3::Z).length() < 1e-6);
+        cam.orbit_yaw = 1.0;
+        cam.orbit_pitch = 0.4;
+        assert!((cam.orbit_direction().length() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_follow_uses_collision_distance() {
+        let cam = ThirdPersonCamera {
+            orbit_pitch: 0.0,
+            height_offset: 0.0,
+            follow_speed: 1.0,
+            distance: 8_000.0,
+            collision_distance: Some(1_500.0),
+            ..Default::default()
+        };
+        let target = WorldPos(WorldPosition::new(0, 0, 0));
+        let mut pos = WorldPos(WorldPosition::new(0, 0, 0));
+        let mut rot = Rotation(Quat::IDENTITY);
+        third_person_follow_system(&cam, &target, &mut pos, &mut rot);
+        assert_eq!(pos.0, WorldPosition::new(0, 0, 1_500));
+    }
+
     #[test]
     fn test_height_offset_raises_look_at_point() {
         let cam = ThirdPersonCamera::default();
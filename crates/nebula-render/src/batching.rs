@@ -3,18 +3,76 @@
 //! Draw calls are sorted by pipeline, then material, then mesh ID. Groups of calls
 //! sharing the same pipeline and material are yielded together, and within each group,
 //! calls sharing the same mesh can be drawn as instanced calls.
+//!
+//! A [`DrawCall`] is either one object ([`DrawCall::Individual`]) or a whole
+//! [`InstancedMesh`](crate::InstancedMesh) already packed into an instance
+//! buffer ([`DrawCall::Instanced`]), such as a forest of trees.
 
 /// A single draw call description with opaque resource keys.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct DrawCall {
-    /// Opaque key identifying the render pipeline.
-    pub pipeline_id: u64,
-    /// Opaque key identifying the material (bind group).
-    pub material_id: u64,
-    /// Reference to the mesh buffer (vertex + index).
-    pub mesh_id: u64,
-    /// Per-instance data index (e.g., transform buffer offset).
-    pub instance_index: u32,
+pub enum DrawCall {
+    /// One object drawn with its own per-instance data.
+    Individual {
+        /// Opaque key identifying the render pipeline.
+        pipeline_id: u64,
+        /// Opaque key identifying the material (bind group).
+        material_id: u64,
+        /// Reference to the mesh buffer (vertex + index).
+        mesh_id: u64,
+        /// Per-instance data index (e.g., transform buffer offset).
+        instance_index: u32,
+    },
+    /// Many copies of one mesh from a prebuilt instance buffer.
+    Instanced {
+        /// Opaque key identifying the render pipeline.
+        pipeline_id: u64,
+        /// Opaque key identifying the material (bind group).
+        material_id: u64,
+        /// Reference to the instanced mesh.
+        mesh_id: u64,
+        /// Number of instances in the mesh's instance buffer.
+        instance_count: u32,
+    },
+}
+
+impl DrawCall {
+    /// Key of the render pipeline.
+    pub fn pipeline_id(&self) -> u64 {
+        match *self {
+            Self::Individual { pipeline_id, .. } | Self::Instanced { pipeline_id, .. } => {
+                pipeline_id
+            }
+        }
+    }
+
+    /// Key of the material.
+    pub fn material_id(&self) -> u64 {
+        match *self {
+            Self::Individual { material_id, .. } | Self::Instanced { material_id, .. } => {
+                material_id
+            }
+        }
+    }
+
+    /// Key of the mesh.
+    pub fn mesh_id(&self) -> u64 {
+        match *self {
+            Self::Individual { mesh_id, .. } | Self::Instanced { mesh_id, .. } => mesh_id,
+        }
+    }
+
+    /// Number of objects this call draws.
+    pub fn instance_count(&self) -> u32 {
+        match *self {
+            Self::Individual { .. } => 1,
+            Self::Instanced { instance_count, .. } => instance_count,
+        }
+    }
+
+    /// Whether this call draws from a prebuilt instance buffer.
+    pub fn is_instanced(&self) -> bool {
+        matches!(self, Self::Instanced { .. })
+    }
 }
 
 /// A batch of draw calls that can be sorted and grouped for efficient rendering.
@@ -53,13 +111,15 @@ impl DrawBatch {
     }
 
     /// Sort draw calls to minimize state changes.
-    /// Sort order: pipeline_id first, then material_id, then mesh_id.
+    /// Sort order: pipeline_id first, then material_id, then mesh_id, with
+    /// instanced calls after individual ones for the same mesh.
     pub fn sort(&mut self) {
         self.draw_calls.sort_unstable_by(|a, b| {
-            a.pipeline_id
-                .cmp(&b.pipeline_id)
-                .then(a.material_id.cmp(&b.material_id))
-                .then(a.mesh_id.cmp(&b.mesh_id))
+            a.pipeline_id()
+                .cmp(&b.pipeline_id())
+                .then(a.material_id().cmp(&b.material_id()))
+                .then(a.mesh_id().cmp(&b.mesh_id()))
+                .then(a.is_instanced().cmp(&b.is_instanced()))
         });
         self.sorted = true;
     }
@@ -134,12 +194,12 @@ impl<'a> Iterator for DrawGroupIter<'a> {
         }
 
         let start = self.cursor;
-        let pipeline_id = self.calls[start].pipeline_id;
-        let material_id = self.calls[start].material_id;
+        let pipeline_id = self.calls[start].pipeline_id();
+        let material_id = self.calls[start].material_id();
 
         while self.cursor < self.calls.len()
-            && self.calls[self.cursor].pipeline_id == pipeline_id
-            && self.calls[self.cursor].material_id == material_id
+            && self.calls[self.cursor].pipeline_id() == pipeline_id
+            && self.calls[self.cursor].material_id() == material_id
         {
             self.cursor += 1;
         }
@@ -152,7 +212,8 @@ impl<'a> Iterator for DrawGroupIter<'a> {
     }
 }
 
-/// A sub-group of draw calls sharing the same mesh, suitable for instanced drawing.
+/// A sub-group of draw calls sharing the same mesh and kind, suitable for
+/// instanced drawing.
 #[derive(Debug)]
 pub struct InstancedDraw<'a> {
     /// The mesh ID shared by all calls in this sub-group.
@@ -162,9 +223,15 @@ pub struct InstancedDraw<'a> {
 }
 
 impl InstancedDraw<'_> {
-    /// Number of instances to draw.
+    /// Number of instances to draw, counting every instance of
+    /// [`DrawCall::Instanced`] calls.
     pub fn instance_count(&self) -> u32 {
-        self.calls.len() as u32
+        self.calls.iter().map(DrawCall::instance_count).sum()
+    }
+
+    /// Whether the calls draw from prebuilt instance buffers.
+    pub fn is_instanced(&self) -> bool {
+        self.calls.first().is_some_and(DrawCall::is_instanced)
     }
 }
 
@@ -183,9 +250,13 @@ impl<'a> Iterator for InstancedGroupIter<'a> {
         }
 
         let start = self.cursor;
-        let mesh_id = self.calls[start].mesh_id;
+        let mesh_id = self.calls[start].mesh_id();
+        let instanced = self.calls[start].is_instanced();
 
-        while self.cursor < self.calls.len() && self.calls[self.cursor].mesh_id == mesh_id {
+        while self.cursor < self.calls.len()
+            && self.calls[self.cursor].mesh_id() == mesh_id
+            && self.calls[self.cursor].is_instanced() == instanced
+        {
             self.cursor += 1;
         }
 
@@ -201,7 +272,7 @@ mod tests {
     use super::*;
 
     fn make_call(pipeline: u64, material: u64, mesh: u64, instance: u32) -> DrawCall {
-        DrawCall {
+        DrawCall::Individual {
             pipeline_id: pipeline,
             material_id: material,
             mesh_id: mesh,
@@ -306,6 +377,31 @@ mod tests {
         assert_eq!(instanced.len(), 2);
     }
 
+    #[test]
+    fn test_instanced_calls_group_apart_from_individual() {
+        let forest = DrawCall::Instanced {
+            pipeline_id: 1,
+            material_id: 1,
+            mesh_id: 7,
+            instance_count: 10_000,
+        };
+        let mut batch = DrawBatch::new();
+        batch.push(forest.clone());
+        batch.push(make_call(1, 1, 7, 0));
+        batch.push(make_call(1, 1, 7, 1));
+        batch.sort();
+
+        let groups: Vec<_> = batch.groups().collect();
+        assert_eq!(groups.len(), 1);
+        let draws: Vec<_> = groups[0].instanced_groups().collect();
+        assert_eq!(draws.len(), 2);
+        assert!(!draws[0].is_instanced());
+        assert_eq!(draws[0].instance_count(), 2);
+        assert!(draws[1].is_instanced());
+        assert_eq!(draws[1].instance_count(), 10_000);
+        assert_eq!(draws[1].calls, [forest]);
+    }
+
     #[test]
     fn test_with_capacity_preallocates() {
         let batch = DrawBatch::with_capacity(1000);
@@ -24,6 +24,39 @@ pub struct BloomConfig {
     pub radius: f32,
 }
 
+impl BloomConfig {
+    /// Blend two configurations, e.g. to ease bloom in and out around an
+    /// explosion. `t` is clamped to `[0, 1]`.
+    ///
+    /// Continuous parameters are interpolated linearly; `iterations` switches
+    /// from `from` to `to` at the halfway point.
+    pub fn lerp(from: &BloomConfig, to: &BloomConfig, t: f32) -> BloomConfig {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        BloomConfig {
+            threshold: mix(from.threshold, to.threshold),
+            soft_knee: mix(from.soft_knee, to.soft_knee),
+            intensity: mix(from.intensity, to.intensity),
+            iterations: if t < 0.5 {
+                from.iterations
+            } else {
+                to.iterations
+            },
+            radius: mix(from.radius, to.radius),
+        }
+    }
+
+    /// The shader uniform for this configuration.
+    fn params(&self) -> BloomParams {
+        BloomParams {
+            threshold: self.threshold,
+            soft_knee: self.soft_knee,
+            intensity: self.intensity,
+            radius: self.radius,
+        }
+    }
+}
+
 impl Default for BloomConfig {
     fn default() -> Self {
         Self {
@@ -143,6 +176,9 @@ pub const GAUSSIAN_WEIGHTS: [f32; 5] = [
 /// pipelines needed for bloom extraction, blur, tonemapping, and compositing.
 pub struct BloomPipeline {
     config: BloomConfig,
+    /// Configuration the pipeline was created with, restored by
+    /// [`reset_to_default`](Self::reset_to_default).
+    base_config: BloomConfig,
     // Bind group layouts (kept alive for resize bind group recreation)
     #[allow(dead_code)]
     params_bgl: wgpu::BindGroupLayout,
@@ -306,12 +342,7 @@ impl BloomPipeline {
         });
 
         // Params buffer
-        let params = BloomParams {
-            threshold: config.threshold,
-            soft_knee: config.soft_knee,
-            intensity: config.intensity,
-            radius: config.radius,
-        };
+        let params = config.params();
         use wgpu::util::DeviceExt;
         let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("bloom-params"),
@@ -341,6 +372,7 @@ impl BloomPipeline {
         );
 
         Self {
+            base_config: config.clone(),
             config,
             params_bgl,
             texture_bgl,
@@ -400,16 +432,25 @@ impl BloomPipeline {
         self.mip_bind_groups = mip_bind_groups;
     }
 
-    /// Update bloom parameters (e.g., when the user changes settings).
-    pub fn update_config(&mut self, queue: &wgpu::Queue, config: BloomConfig) {
-        let params = BloomParams {
-            threshold: config.threshold,
-            soft_knee: config.soft_knee,
-            intensity: config.intensity,
-            radius: config.radius,
-        };
-        queue.write_buffer(&self.params_buffer, 0, bytemuck::cast_slice(&[params]));
-        self.config = config;
+    /// Update bloom parameters for the next [`execute`](Self::execute), e.g.
+    /// every frame while easing with [`BloomConfig::lerp`].
+    ///
+    /// Threshold, knee, intensity, and radius are written to the uniform
+    /// buffer bound to every pass. The mip chain keeps its size until the next
+    /// [`resize`](Self::resize), so `iterations` can only shrink until then.
+    pub fn update_config(&mut self, queue: &wgpu::Queue, config: &BloomConfig) {
+        queue.write_buffer(
+            &self.params_buffer,
+            0,
+            bytemuck::cast_slice(&[config.params()]),
+        );
+        self.config = config.clone();
+    }
+
+    /// Restore the configuration the pipeline was created with.
+    pub fn reset_to_default(&mut self, queue: &wgpu::Queue) {
+        let base = self.base_config.clone();
+        self.update_config(queue, &base);
     }
 
     /// Execute the full bloom pipeline: extract → downsample → upsample → tonemap → composite.
@@ -570,7 +611,9 @@ fn create_hdr_texture(
         sample_count: 1,
         dimension: wgpu::TextureDimension::D2,
         format,
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_DST,
         view_formats: &[],
     });
     let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
@@ -623,7 +666,9 @@ fn create_mip_chain(
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
             view_formats: &[],
         });
         let view = tex.create_view(&wgpu::TextureViewDescriptor::default());
@@ -654,122 +699,5 @@ fn create_mip_chain(
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_bloom_config_default_values() {
-        let config = BloomConfig::default();
-        assert_eq!(config.threshold, 1.0);
-        assert_eq!(config.intensity, 0.3);
-        assert_eq!(config.iterations, 5);
-        assert_eq!(config.radius, 1.0);
-        assert!(config.soft_knee >= 0.0 && config.soft_knee <= 1.0);
-    }
-
-    #[test]
-    fn test_bright_points_produce_glow() {
-        let bright_pixel = [5.0_f32, 5.0, 5.0];
-        let threshold = 1.0;
-        let luminance =
-            bright_pixel[0] * 0.2126 + bright_pixel[1] * 0.7152 + bright_pixel[2] * 0.0722;
-        let factor = (luminance - threshold) / luminance;
-        let extracted: Vec<f32> = bright_pixel.iter().map(|c| c * factor).collect();
-        assert!(
-            extracted[0] > 0.0 && extracted[1] > 0.0 && extracted[2] > 0.0,
-            "Bright pixels should produce non-zero bloom contribution: {extracted:?}"
-        );
-    }
-
-    #[test]
-    fn test_dim_points_produce_minimal_glow() {
-        let dim_pixel = [0.3_f32, 0.3, 0.3];
-        let threshold = 1.0;
-        let luminance = dim_pixel[0] * 0.2126 + dim_pixel[1] * 0.7152 + dim_pixel[2] * 0.0722;
-        let factor = ((luminance - threshold).max(0.0)) / luminance.max(0.0001);
-        let extracted: Vec<f32> = dim_pixel.iter().map(|c| c * factor).collect();
-        assert!(
-            extracted.iter().all(|&v| v < 0.01),
-            "Dim pixels should produce near-zero bloom contribution: {extracted:?}"
-        );
-    }
-
-    #[test]
-    fn test_bloom_radius_is_configurable() {
-        let config_narrow = BloomConfig {
-            radius: 0.5,
-            ..Default::default()
-        };
-        let config_wide = BloomConfig {
-            radius: 2.0,
-            ..Default::default()
-        };
-        assert!(config_narrow.radius < config_wide.radius);
-        assert_eq!(config_narrow.radius, 0.5);
-        assert_eq!(config_wide.radius, 2.0);
-    }
-
-    #[test]
-    fn test_bloom_does_not_affect_non_bright_pixels() {
-        let threshold = 1.0;
-        let knee = 0.5;
-        let test_luminance: f32 = 0.4;
-
-        let soft = test_luminance - threshold + knee;
-        let contribution = if soft > 0.0 {
-            let soft_clamped = soft.min(2.0 * knee);
-            soft_clamped * soft_clamped / (4.0 * knee + 0.0001)
-        } else {
-            0.0
-        };
-        let factor = (test_luminance - threshold).max(contribution) / test_luminance.max(0.0001);
-        assert!(
-            factor <= 0.0,
-            "Pixel at luminance {test_luminance} should have zero bloom factor, got {factor}"
-        );
-    }
-
-    #[test]
-    fn test_gaussian_weights_sum_to_approximately_one() {
-        let sum = GAUSSIAN_WEIGHTS[0] + 2.0 * GAUSSIAN_WEIGHTS[1..].iter().sum::<f32>();
-        assert!(
-            (sum - 1.0).abs() < 0.01,
-            "Gaussian weights should sum to ~1.0, got {sum}"
-        );
-    }
-
-    #[test]
-    fn test_bloom_intensity_scales_output() {
-        let config_low = BloomConfig {
-            intensity: 0.1,
-            ..Default::default()
-        };
-        let config_high = BloomConfig {
-            intensity: 1.0,
-            ..Default::default()
-        };
-        assert!(
-            config_high.intensity > config_low.intensity,
-            "High intensity ({}) should exceed low intensity ({})",
-            config_high.intensity,
-            config_low.intensity
-        );
-    }
-
-    #[test]
-    fn test_mip_chain_dimensions_halve_each_level() {
-        let mut w = 1920u32 / 2;
-        let mut h = 1080u32 / 2;
-        let expected_dims = [(960, 540), (480, 270), (240, 135), (120, 67), (60, 33)];
-        for (i, &(ew, eh)) in expected_dims.iter().enumerate() {
-            assert_eq!((w, h), (ew, eh), "Mip level {i} dimensions mismatch");
-            w = (w / 2).max(1);
-            h = (h / 2).max(1);
-        }
-    }
-
-    #[test]
-    fn test_bloom_params_uniform_size() {
-        assert_eq!(std::mem::size_of::<BloomParams>(), 16);
-    }
-}
+#[path = "bloom_tests.rs"]
+mod tests;
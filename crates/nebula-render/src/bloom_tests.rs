@@ -0,0 +1,293 @@
+//! Unit tests for the bloom pipeline.
+
+use super::*;
+
+#[test]
+fn test_bloom_config_default_values() {
+    let config = BloomConfig::default();
+    assert_eq!(config.threshold, 1.0);
+    assert_eq!(config.intensity, 0.3);
+    assert_eq!(config.iterations, 5);
+    assert_eq!(config.radius, 1.0);
+    assert!(config.soft_knee >= 0.0 && config.soft_knee <= 1.0);
+}
+
+#[test]
+fn test_bright_points_produce_glow() {
+    let bright_pixel = [5.0_f32, 5.0, 5.0];
+    let threshold = 1.0;
+    let luminance = bright_pixel[0] * 0.2126 + bright_pixel[1] * 0.7152 + bright_pixel[2] * 0.0722;
+    let factor = (luminance - threshold) / luminance;
+    let extracted: Vec<f32> = bright_pixel.iter().map(|c| c * factor).collect();
+    assert!(
+        extracted[0] > 0.0 && extracted[1] > 0.0 && extracted[2] > 0.0,
+        "Bright pixels should produce non-zero bloom contribution: {extracted:?}"
+    );
+}
+
+#[test]
+fn test_dim_points_produce_minimal_glow() {
+    let dim_pixel = [0.3_f32, 0.3, 0.3];
+    let threshold = 1.0;
+    let luminance = dim_pixel[0] * 0.2126 + dim_pixel[1] * 0.7152 + dim_pixel[2] * 0.0722;
+    let factor = ((luminance - threshold).max(0.0)) / luminance.max(0.0001);
+    let extracted: Vec<f32> = dim_pixel.iter().map(|c| c * factor).collect();
+    assert!(
+        extracted.iter().all(|&v| v < 0.01),
+        "Dim pixels should produce near-zero bloom contribution: {extracted:?}"
+    );
+}
+
+#[test]
+fn test_bloom_radius_is_configurable() {
+    let config_narrow = BloomConfig {
+        radius: 0.5,
+        ..Default::default()
+    };
+    let config_wide = BloomConfig {
+        radius: 2.0,
+        ..Default::default()
+    };
+    assert!(config_narrow.radius < config_wide.radius);
+    assert_eq!(config_narrow.radius, 0.5);
+    assert_eq!(config_wide.radius, 2.0);
+}
+
+#[test]
+fn test_bloom_does_not_affect_non_bright_pixels() {
+    let threshold = 1.0;
+    let knee = 0.5;
+    let test_luminance: f32 = 0.4;
+
+    let soft = test_luminance - threshold + knee;
+    let contribution = if soft > 0.0 {
+        let soft_clamped = soft.min(2.0 * knee);
+        soft_clamped * soft_clamped / (4.0 * knee + 0.0001)
+    } else {
+        0.0
+    };
+    let factor = (test_luminance - threshold).max(contribution) / test_luminance.max(0.0001);
+    assert!(
+        factor <= 0.0,
+        "Pixel at luminance {test_luminance} should have zero bloom factor, got {factor}"
+    );
+}
+
+#[test]
+fn test_gaussian_weights_sum_to_approximately_one() {
+    let sum = GAUSSIAN_WEIGHTS[0] + 2.0 * GAUSSIAN_WEIGHTS[1..].iter().sum::<f32>();
+    assert!(
+        (sum - 1.0).abs() < 0.01,
+        "Gaussian weights should sum to ~1.0, got {sum}"
+    );
+}
+
+#[test]
+fn test_bloom_intensity_scales_output() {
+    let config_low = BloomConfig {
+        intensity: 0.1,
+        ..Default::default()
+    };
+    let config_high = BloomConfig {
+        intensity: 1.0,
+        ..Default::default()
+    };
+    assert!(
+        config_high.intensity > config_low.intensity,
+        "High intensity ({}) should exceed low intensity ({})",
+        config_high.intensity,
+        config_low.intensity
+    );
+}
+
+#[test]
+fn test_mip_chain_dimensions_halve_each_level() {
+    let mut w = 1920u32 / 2;
+    let mut h = 1080u32 / 2;
+    let expected_dims = [(960, 540), (480, 270), (240, 135), (120, 67), (60, 33)];
+    for (i, &(ew, eh)) in expected_dims.iter().enumerate() {
+        assert_eq!((w, h), (ew, eh), "Mip level {i} dimensions mismatch");
+        w = (w / 2).max(1);
+        h = (h / 2).max(1);
+    }
+}
+
+#[test]
+fn test_bloom_params_uniform_size() {
+    assert_eq!(std::mem::size_of::<BloomParams>(), 16);
+}
+
+#[test]
+fn test_lerp_interpolates_and_clamps() {
+    let calm = BloomConfig::default();
+    let blast = BloomConfig {
+        threshold: 0.5,
+        intensity: 1.3,
+        iterations: 7,
+        ..Default::default()
+    };
+
+    let mid = BloomConfig::lerp(&calm, &blast, 0.5);
+    assert!((mid.threshold - 0.75).abs() < 1e-6);
+    assert!((mid.intensity - 0.8).abs() < 1e-6);
+    assert_eq!(mid.iterations, 7);
+
+    let before = BloomConfig::lerp(&calm, &blast, -1.0);
+    assert_eq!(before.threshold, calm.threshold);
+    assert_eq!(before.iterations, calm.iterations);
+    let after = BloomConfig::lerp(&calm, &blast, 2.0);
+    assert_eq!(after.intensity, blast.intensity);
+}
+
+/// Half-precision bit patterns for the HDR test input.
+const F16_ONE: u16 = 0x3C00;
+const F16_EIGHT: u16 = 0x4800;
+
+/// Fill the HDR target with uniform radiance `value` (f16 bits, RGB) and run bloom.
+fn run_bloom_on_uniform_input(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    bloom: &BloomPipeline,
+    value: u16,
+) {
+    const SIZE: u32 = 16;
+    let pixel = [value, value, value, F16_ONE];
+    let data: Vec<u8> = (0..SIZE * SIZE)
+        .flat_map(|_| pixel.iter().flat_map(|c| c.to_le_bytes()))
+        .collect();
+    queue.write_texture(
+        wgpu::TexelCopyTextureInfo {
+            texture: &bloom.hdr_texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        &data,
+        wgpu::TexelCopyBufferLayout {
+            offset: 0,
+            bytes_per_row: Some(SIZE * 8),
+            rows_per_image: Some(SIZE),
+        },
+        wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+    );
+
+    let surface = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("bloom-test-surface"),
+        size: wgpu::Extent3d {
+            width: SIZE,
+            height: SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let surface_view = surface.create_view(&wgpu::TextureViewDescriptor::default());
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    bloom.execute(&mut encoder, &surface_view);
+    queue.submit([encoder.finish()]);
+}
+
+/// Whether any RGB channel of the first bloom mip (the bloom output) is non-zero.
+fn bloom_output_has_light(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    bloom: &BloomPipeline,
+) -> bool {
+    let texture = &bloom.mip_textures[0];
+    let (w, h) = (texture.width(), texture.height());
+    let bpp = 8u32;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let padded = (w * bpp).div_ceil(align) * align;
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("bloom-test-readback"),
+        size: u64::from(padded * h),
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded),
+                rows_per_image: Some(h),
+            },
+        },
+        wgpu::Extent3d {
+            width: w,
+            height: h,
+            depth_or_array_layers: 1,
+        },
+    );
+    queue.submit([encoder.finish()]);
+
+    let slice = buffer.slice(..);
+    let (tx, rx) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = tx.send(result);
+    });
+    let _ = device.poll(wgpu::PollType::Wait {
+        submission_index: None,
+        timeout: None,
+    });
+    assert!(matches!(rx.recv(), Ok(Ok(()))), "readback failed");
+
+    let mapped = slice.get_mapped_range();
+    (0..h as usize).any(|row| {
+        let start = row * padded as usize;
+        mapped[start..start + (w * bpp) as usize]
+            .chunks_exact(bpp as usize)
+            .any(|px| px[..6].iter().any(|&b| b != 0))
+    })
+}
+
+#[test]
+fn test_update_config_threshold_gates_bloom_output() {
+    let Some((device, queue)) = crate::texture::create_test_device_queue() else {
+        return;
+    };
+    let mut bloom = BloomPipeline::new(
+        &device,
+        wgpu::TextureFormat::Rgba16Float,
+        wgpu::TextureFormat::Rgba8Unorm,
+        16,
+        16,
+        BloomConfig::default(),
+    );
+
+    bloom.update_config(
+        &queue,
+        &BloomConfig {
+            threshold: 10_000.0,
+            ..Default::default()
+        },
+    );
+    run_bloom_on_uniform_input(&device, &queue, &bloom, F16_EIGHT);
+    assert!(
+        !bloom_output_has_light(&device, &queue, &bloom),
+        "nothing is above a 10000 threshold"
+    );
+
+    bloom.reset_to_default(&queue);
+    assert_eq!(bloom.config.threshold, BloomConfig::default().threshold);
+    run_bloom_on_uniform_input(&device, &queue, &bloom, F16_EIGHT);
+    assert!(
+        bloom_output_has_light(&device, &queue, &bloom),
+        "bright input should bloom at the default threshold"
+    );
+}
@@ -1,7 +1,7 @@
 //! Camera system for view and projection matrix generation.
 
 use crate::pipeline::CameraUniform;
-use glam::{Mat4, Quat, Vec3};
+use glam::{Mat4, Quat, Vec2, Vec3, Vec4};
 
 /// A camera that generates view and projection matrices for rendering.
 /// Operates entirely in local f32 space after origin rebasing.
@@ -19,6 +19,22 @@ pub struct Camera {
     pub far: f32,
 }
 
+/// A half-line from `origin` along the unit vector `direction`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    /// Start point.
+    pub origin: Vec3,
+    /// Unit direction.
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Point `t` units along the ray.
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.direction * t
+    }
+}
+
 /// Projection type for the camera.
 #[derive(Debug, Clone)]
 pub enum Projection {
@@ -109,6 +125,53 @@ impl Camera {
         }
     }
 
+    /// Project a local-space point to screen pixels, with the origin at the
+    /// top-left corner and y pointing down.
+    ///
+    /// Returns `None` if the point is behind the camera.
+    pub fn project_point(&self, world_pos: Vec3, screen_size: Vec2) -> Option<Vec2> {
+        let clip = self.view_projection_matrix() * world_pos.extend(1.0);
+        if clip.w <= f32::EPSILON {
+            return None;
+        }
+        let ndc = Vec2::new(clip.x, clip.y) / clip.w;
+        Some(Vec2::new(
+            (ndc.x + 1.0) * 0.5 * screen_size.x,
+            (1.0 - ndc.y) * 0.5 * screen_size.y,
+        ))
+    }
+
+    /// View-space ray through the screen pixel `screen_pos`.
+    ///
+    /// Perspective rays start at the eye; orthographic rays start on the
+    /// view plane and run along -Z.
+    pub fn unproject_ray(&self, screen_pos: Vec2, screen_size: Vec2) -> Ray {
+        let on_near = self.clip_to_view(Self::screen_to_clip(screen_pos, screen_size));
+        match self.projection {
+            Projection::Perspective { .. } => Ray {
+                origin: Vec3::ZERO,
+                direction: on_near.normalize_or(Vec3::NEG_Z),
+            },
+            Projection::Orthographic { .. } => Ray {
+                origin: Vec3::new(on_near.x, on_near.y, 0.0),
+                direction: Vec3::NEG_Z,
+            },
+        }
+    }
+
+    /// Convert screen pixels (top-left origin, y down) to normalized device
+    /// coordinates in `[-1, 1]` with y up.
+    pub fn screen_to_clip(pos: Vec2, size: Vec2) -> Vec2 {
+        Vec2::new(pos.x / size.x * 2.0 - 1.0, 1.0 - pos.y / size.y * 2.0)
+    }
+
+    /// View-space point on the near plane that projects to `ndc`.
+    pub fn clip_to_view(&self, ndc: Vec2) -> Vec3 {
+        // Reverse-Z: the near plane sits at depth 1.
+        let view = self.projection_matrix().inverse() * Vec4::new(ndc.x, ndc.y, 1.0, 1.0);
+        view.truncate() / view.w
+    }
+
     /// Convert the camera to a uniform suitable for GPU upload.
     pub fn to_uniform(&self) -> CameraUniform {
         CameraUniform {
@@ -254,4 +317,95 @@ mod tests {
             }
         }
     }
+
+    /// Distance from `point` to the infinite line through `ray`.
+    fn distance_to_ray(ray: &Ray, point: Vec3) -> f32 {
+        let to_point = point - ray.origin;
+        (to_point - ray.direction * to_point.dot(ray.direction)).length()
+    }
+
+    #[test]
+    fn test_project_then_unproject_passes_through_point() {
+        let size = Vec2::new(1280.0, 720.0);
+        let camera = Camera {
+            position: Vec3::new(5.0, -3.0, 12.0),
+            rotation: Quat::from_euler(glam::EulerRot::YXZ, 0.7, -0.3, 0.1),
+            ..Camera::default()
+        };
+        camera_round_trip(&camera, size);
+    }
+
+    #[test]
+    fn test_ortho_project_then_unproject_passes_through_point() {
+        let camera = Camera {
+            position: Vec3::new(1.0, 2.0, 3.0),
+            rotation: Quat::from_rotation_x(-0.4),
+            projection: Projection::Orthographic {
+                half_width: 40.0,
+                half_height: 30.0,
+            },
+            near: 0.1,
+            far: 500.0,
+        };
+        camera_round_trip(&camera, Vec2::new(800.0, 600.0));
+    }
+
+    fn camera_round_trip(camera: &Camera, size: Vec2) {
+        for offset in [
+            Vec3::new(0.0, 0.0, -10.0),
+            Vec3::new(3.0, 2.0, -25.0),
+            Vec3::new(-8.0, 4.5, -40.0),
+            Vec3::new(0.5, -6.0, -150.0),
+        ] {
+            let point = camera.position + camera.rotation * offset;
+            let screen = camera.project_point(point, size).unwrap();
+            let ray = camera.unproject_ray(screen, size);
+            let view_point = camera.view_matrix().transform_point3(point);
+            assert!(
+                distance_to_ray(&ray, view_point) < 1e-3 * view_point.length().max(1.0),
+                "ray misses {offset:?}"
+            );
+            assert!((ray.direction.length() - 1.0).abs() < 1e-5);
+            assert!(
+                ray.direction.dot(view_point - ray.origin) > 0.0,
+                "point ahead"
+            );
+        }
+    }
+
+    #[test]
+    fn test_project_point_behind_camera_is_none() {
+        let camera = Camera::default();
+        let size = Vec2::new(100.0, 100.0);
+        assert!(
+            camera
+                .project_point(Vec3::new(0.0, 0.0, 5.0), size)
+                .is_none()
+        );
+        let center = camera
+            .project_point(Vec3::new(0.0, 0.0, -5.0), size)
+            .unwrap();
+        assert!((center - Vec2::new(50.0, 50.0)).length() < 1e-3);
+    }
+
+    #[test]
+    fn test_screen_to_clip_corners() {
+        let size = Vec2::new(200.0, 100.0);
+        assert_eq!(
+            Camera::screen_to_clip(Vec2::ZERO, size),
+            Vec2::new(-1.0, 1.0)
+        );
+        assert_eq!(Camera::screen_to_clip(size, size), Vec2::new(1.0, -1.0));
+        assert_eq!(
+            Camera::screen_to_clip(size * 0.5, size),
+            Vec2::new(0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_clip_to_view_lies_on_near_plane() {
+        let camera = Camera::default();
+        let point = camera.clip_to_view(Vec2::new(0.5, -0.25));
+        assert!((point.z + camera.near).abs() < 1e-5, "z = {}", point.z);
+    }
 }
@@ -4,16 +4,12 @@
 //! (which rejects objects too far to convert to f32), this module tests AABBs
 //! in local f32 space against the camera's view frustum extracted from the
 //! view-projection matrix.
+//!
+//! Bounds far from the camera can also be tested in f64 with
+//! [`FrustumCuller::is_sphere_visible_f64`], so coarse hierarchical culling
+//! never has to squeeze planet-scale distances into f32.
 
-use glam::{Mat4, Vec3, Vec4};
-
-/// Plane indices into the frustum planes array.
-const LEFT: usize = 0;
-const RIGHT: usize = 1;
-const BOTTOM: usize = 2;
-const TOP: usize = 3;
-const NEAR: usize = 4;
-const FAR: usize = 5;
+use glam::{DMat4, DVec3, DVec4, Mat4, Vec3, Vec4};
 
 /// An axis-aligned bounding box in local f32 space.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -45,7 +41,7 @@ impl Aabb {
 /// the view-projection matrix.
 #[derive(Clone, Debug)]
 pub struct Frustum {
-    /// Six planes: left, right, bottom, top, near, far.
+    /// Six planes: left, right, bottom, top, and the two depth planes.
     /// Each `Vec4(a, b, c, d)` where `(a,b,c)` is the normalized inward
     /// normal and `d` is the signed distance term.
     planes: [Vec4; 6],
@@ -58,29 +54,14 @@ impl Frustum {
     /// Works with both perspective and orthographic projections,
     /// including reverse-Z.
     pub fn from_view_projection(vp: &Mat4) -> Self {
-        let rows = [vp.row(0), vp.row(1), vp.row(2), vp.row(3)];
-
-        let mut planes = [Vec4::ZERO; 6];
-        planes[LEFT] = rows[3] + rows[0];
-        planes[RIGHT] = rows[3] - rows[0];
-        planes[BOTTOM] = rows[3] + rows[1];
-        planes[TOP] = rows[3] - rows[1];
-        // With reverse-Z (near→z=1, far→z=0), the standard Griggs-Hartmann
-        // row3±row2 encodes the near clip plane but not the far clip plane
-        // correctly. Use row2 directly for the geometric far plane and
-        // row3+row2 for the geometric near plane.
-        planes[NEAR] = rows[3] + rows[2];
-        planes[FAR] = rows[2];
-
-        // Normalize each plane so that (a,b,c) is a unit vector.
-        for plane in &mut planes {
-            let len = plane.truncate().length();
-            if len > 0.0 {
-                *plane /= len;
-            }
-        }
+        Self::from_planes_f64(&nebula_math::frustum_planes(&vp.as_dmat4()))
+    }
 
-        Self { planes }
+    /// Narrow planes extracted by [`nebula_math::frustum_planes`] to f32.
+    fn from_planes_f64(planes: &[DVec4; 6]) -> Self {
+        Self {
+            planes: planes.map(|plane| plane.as_vec4()),
+        }
     }
 
     /// Returns the six frustum planes (for interop with other culling systems).
@@ -135,13 +116,27 @@ impl Frustum {
 /// then used to test each chunk/object before issuing draw calls.
 pub struct FrustumCuller {
     frustum: Frustum,
+    /// The same planes in f64, for [`Self::is_sphere_visible_f64`].
+    planes_f64: [DVec4; 6],
 }
 
 impl FrustumCuller {
     /// Create a new culler from the camera's view-projection matrix.
     pub fn new(view_projection: &Mat4) -> Self {
+        Self::from_camera_f64(&view_projection.as_dmat4())
+    }
+
+    /// Create a culler from a camera-relative view-projection matrix kept in
+    /// f64 (camera at the origin).
+    ///
+    /// The f64 planes are extracted from `view_projection` directly, so
+    /// [`Self::is_sphere_visible_f64`] keeps full precision; the f32 planes
+    /// used by [`Self::is_visible`] come from the same matrix.
+    pub fn from_camera_f64(view_projection: &DMat4) -> Self {
+        let planes_f64 = nebula_math::frustum_planes(view_projection);
         Self {
-            frustum: Frustum::from_view_projection(view_projection),
+            frustum: Frustum::from_planes_f64(&planes_f64),
+            planes_f64,
         }
     }
 
@@ -149,6 +144,18 @@ impl FrustumCuller {
     pub fn is_visible(&self, aabb: &Aabb) -> bool {
         self.frustum.is_visible(aabb)
     }
+
+    /// Returns `true` unless a sphere given in camera-relative f64 space
+    /// lies entirely behind one frustum plane.
+    ///
+    /// Conservative in the same way as [`Self::is_visible`]. Meant for
+    /// coarse tests of large or distant bounds (whole quadtree nodes, say)
+    /// before anything is converted to f32.
+    pub fn is_sphere_visible_f64(&self, center: DVec3, radius: f64) -> bool {
+        self.planes_f64
+            .iter()
+            .all(|plane| plane.truncate().dot(center) + plane.w >= -radius)
+    }
 }
 
 #[cfg(test)]
@@ -224,6 +231,22 @@ mod tests {
         assert!(!culler.is_visible(&beyond_far));
     }
 
+    #[test]
+    fn test_sphere_visibility_f64_matches_f32_planes() {
+        let vp = default_camera_vp();
+        let culler = FrustumCuller::from_camera_f64(&vp.as_dmat4());
+        assert!(culler.is_sphere_visible_f64(DVec3::new(0.0, 0.0, -5.0), 1.0));
+        assert!(!culler.is_sphere_visible_f64(DVec3::new(0.0, 0.0, 15.0), 1.0));
+        assert!(!culler.is_sphere_visible_f64(DVec3::new(1000.0, 0.0, -5.0), 1.0));
+        assert!(!culler.is_sphere_visible_f64(DVec3::new(0.0, 0.0, -2000.0), 1.0));
+        // Straddling the left plane.
+        assert!(culler.is_sphere_visible_f64(DVec3::new(-100.0, 0.0, -10.0), 95.0));
+
+        let from_f32 = FrustumCuller::new(&vp);
+        let aabb = Aabb::new(Vec3::new(-1.0, -1.0, -5.0), Vec3::new(1.0, 1.0, -3.0));
+        assert_eq!(culler.is_visible(&aabb), from_f32.is_visible(&aabb));
+    }
+
     #[test]
     fn test_aabb_center_and_extents() {
         let aabb = Aabb::new(Vec3::new(-2.0, -3.0, -4.0), Vec3::new(2.0, 3.0, 4.0));
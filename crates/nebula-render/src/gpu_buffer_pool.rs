@@ -11,9 +11,23 @@ const NUM_SIZE_CLASSES: usize = 6;
 /// Size class thresholds in bytes: 4 KB, 8 KB, 16 KB, 32 KB, 64 KB, 128 KB.
 const SIZE_CLASSES: [u64; NUM_SIZE_CLASSES] = [4096, 8192, 16384, 32768, 65536, 131072];
 
+/// GPU memory held by a [`GpuBufferPool`], passed to its usage callback.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GpuPoolUsage {
+    /// Bytes in buffers handed out to chunk meshes.
+    pub in_use: u64,
+    /// Bytes in all buffers the pool created, pooled or in use.
+    pub allocated: u64,
+}
+
+/// Callback invoked with the pool's usage after every acquire and release.
+pub type GpuUsageCallback = Box<dyn FnMut(GpuPoolUsage) + Send + Sync>;
+
 /// A pool of GPU buffers bucketed by size class.
 ///
-/// Tracks total allocated and in-use GPU memory for chunk meshes.
+/// Tracks total allocated and in-use GPU memory for chunk meshes, and
+/// reports it to an optional [usage callback](Self::set_usage_callback) so
+/// a memory budget can account for it.
 pub struct GpuBufferPool {
     /// Free vertex buffers, bucketed by size class.
     vertex_pool: [Vec<wgpu::Buffer>; NUM_SIZE_CLASSES],
@@ -23,6 +37,8 @@ pub struct GpuBufferPool {
     total_allocated: u64,
     /// Total bytes currently in use (uploaded, not pooled).
     in_use: u64,
+    /// Receives usage after every change.
+    usage_callback: Option<GpuUsageCallback>,
 }
 
 impl GpuBufferPool {
@@ -33,6 +49,32 @@ impl GpuBufferPool {
             index_pool: Default::default(),
             total_allocated: 0,
             in_use: 0,
+            usage_callback: None,
+        }
+    }
+
+    /// Call `callback` with the pool's usage now and after every acquire
+    /// and release, replacing any previous callback.
+    pub fn set_usage_callback(
+        &mut self,
+        callback: impl FnMut(GpuPoolUsage) + Send + Sync + 'static,
+    ) {
+        self.usage_callback = Some(Box::new(callback));
+        self.notify();
+    }
+
+    /// Current in-use and allocated bytes.
+    pub fn usage(&self) -> GpuPoolUsage {
+        GpuPoolUsage {
+            in_use: self.in_use,
+            allocated: self.total_allocated,
+        }
+    }
+
+    fn notify(&mut self) {
+        let usage = self.usage();
+        if let Some(callback) = &mut self.usage_callback {
+            callback(usage);
         }
     }
 
@@ -65,6 +107,7 @@ impl GpuBufferPool {
 
         if let Some(buf) = self.vertex_pool[class].pop() {
             self.in_use += size;
+            self.notify();
             return (buf, class);
         }
 
@@ -76,6 +119,7 @@ impl GpuBufferPool {
         });
         self.total_allocated += size;
         self.in_use += size;
+        self.notify();
         (buf, class)
     }
 
@@ -92,6 +136,7 @@ impl GpuBufferPool {
 
         if let Some(buf) = self.index_pool[class].pop() {
             self.in_use += size;
+            self.notify();
             return (buf, class);
         }
 
@@ -103,6 +148,7 @@ impl GpuBufferPool {
         });
         self.total_allocated += size;
         self.in_use += size;
+        self.notify();
         (buf, class)
     }
 
@@ -111,6 +157,7 @@ impl GpuBufferPool {
         let class = size_class.min(NUM_SIZE_CLASSES - 1);
         self.in_use = self.in_use.saturating_sub(SIZE_CLASSES[class]);
         self.vertex_pool[class].push(buffer);
+        self.notify();
     }
 
     /// Return an index buffer to the pool for reuse.
@@ -118,6 +165,7 @@ impl GpuBufferPool {
         let class = size_class.min(NUM_SIZE_CLASSES - 1);
         self.in_use = self.in_use.saturating_sub(SIZE_CLASSES[class]);
         self.index_pool[class].push(buffer);
+        self.notify();
     }
 
     /// Current GPU memory in use by active chunk meshes.
@@ -259,4 +307,23 @@ mod tests {
         assert_eq!(class2, 1); // 8KB class
         assert!(pool.gpu_memory_allocated() > allocated_before);
     }
+
+    #[test]
+    fn test_usage_callback_reports_changes() {
+        let Some((device, _queue)) = test_device() else {
+            return;
+        };
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut pool = GpuBufferPool::new();
+        let sink = seen.clone();
+        pool.set_usage_callback(move |usage| sink.lock().unwrap().push(usage));
+
+        let (buf, class) = pool.acquire_vertex_buffer(&device, 100);
+        pool.release_vertex_buffer(buf, class);
+
+        let seen = seen.lock().unwrap();
+        let in_use: Vec<u64> = seen.iter().map(|u| u.in_use).collect();
+        assert_eq!(in_use, [0, 4096, 0]);
+        assert_eq!(seen.last().unwrap().allocated, 4096);
+    }
 }
@@ -3,8 +3,17 @@
 //! [`GpuChunkMesh`] wraps the GPU buffers produced by uploading a
 //! [`PackedChunkMesh`](nebula_mesh::PackedChunkMesh) and exposes the
 //! metadata needed to issue indexed draw calls.
+//!
+//! [`GpuChunkMesh::upload`] reorders the index buffer so opaque quads come
+//! first and translucent ones (glass, water) last: the opaque range is drawn
+//! with the chunk, and the translucent range, kept farthest first by
+//! [`GpuChunkMesh::sort_transparent`], is drawn in one call per chunk from a
+//! depth-sorted [`TransparentDrawList`](crate::TransparentDrawList).
 
+use glam::Vec3;
 use nebula_mesh::PackedChunkMesh;
+
+use crate::transparency::back_to_front;
 use wgpu::util::DeviceExt;
 
 /// A chunk mesh that has been uploaded to the GPU.
@@ -19,6 +28,13 @@ pub struct GpuChunkMesh {
     pub index_count: u32,
     /// Number of vertices.
     pub vertex_count: u32,
+    /// First index of the translucent range; equals `index_count` when the
+    /// mesh is fully opaque.
+    pub transparent_index_start: u32,
+    /// Chunk-local center of each translucent quad, in index-buffer order.
+    pub transparent_centers: Vec<Vec3>,
+    /// CPU copy of the translucent range, rewritten when it is re-sorted.
+    transparent_indices: Vec<u32>,
     /// Size of the vertex buffer in bytes (for memory tracking).
     vertex_buffer_size: u64,
     /// Size of the index buffer in bytes (for memory tracking).
@@ -27,9 +43,28 @@ pub struct GpuChunkMesh {
 
 impl GpuChunkMesh {
     /// Upload a [`PackedChunkMesh`] to the GPU, creating new buffers.
-    pub fn upload(device: &wgpu::Device, mesh: &PackedChunkMesh) -> Self {
+    ///
+    /// Quads whose material satisfies `is_transparent` are moved to the end
+    /// of the index buffer. Quads are the consecutive six-index groups
+    /// written by [`PackedChunkMesh::push_quad`]; their relative order is
+    /// kept within each range.
+    pub fn upload(
+        device: &wgpu::Device,
+        mesh: &PackedChunkMesh,
+        is_transparent: impl Fn(u16) -> bool,
+    ) -> Self {
+        let (indices, centers) = partition_transparent(mesh, is_transparent);
+        Self::upload_indices(device, mesh, &indices, centers)
+    }
+
+    fn upload_indices(
+        device: &wgpu::Device,
+        mesh: &PackedChunkMesh,
+        indices: &[u32],
+        transparent_centers: Vec<Vec3>,
+    ) -> Self {
         let vertex_bytes = mesh.vertex_bytes();
-        let index_bytes = mesh.index_bytes();
+        let index_bytes: &[u8] = bytemuck::cast_slice(indices);
 
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("chunk_vertex_buffer"),
@@ -43,11 +78,16 @@ impl GpuChunkMesh {
             usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
         });
 
+        let index_count = indices.len() as u32;
+        let transparent_index_start = index_count - transparent_centers.len() as u32 * 6;
         Self {
             vertex_buffer,
             index_buffer,
-            index_count: mesh.indices.len() as u32,
+            index_count,
             vertex_count: mesh.vertices.len() as u32,
+            transparent_index_start,
+            transparent_centers,
+            transparent_indices: indices[transparent_index_start as usize..].to_vec(),
             vertex_buffer_size: vertex_bytes.len() as u64,
             index_buffer_size: index_bytes.len() as u64,
         }
@@ -56,14 +96,28 @@ impl GpuChunkMesh {
     /// Re-upload mesh data into existing buffers if they fit, or create new ones.
     ///
     /// Returns `true` if the existing buffers were reused (write-on-remesh).
+    /// Translucent quads are split off as in [`upload`](Self::upload).
     pub fn reupload(
         &mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         mesh: &PackedChunkMesh,
+        is_transparent: impl Fn(u16) -> bool,
+    ) -> bool {
+        let (indices, centers) = partition_transparent(mesh, is_transparent);
+        self.reupload_indices(device, queue, mesh, &indices, centers)
+    }
+
+    fn reupload_indices(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        mesh: &PackedChunkMesh,
+        indices: &[u32],
+        transparent_centers: Vec<Vec3>,
     ) -> bool {
         let vertex_bytes = mesh.vertex_bytes();
-        let index_bytes = mesh.index_bytes();
+        let index_bytes: &[u8] = bytemuck::cast_slice(indices);
 
         let vb_fits = vertex_bytes.len() as u64 <= self.vertex_buffer_size;
         let ib_fits = index_bytes.len() as u64 <= self.index_buffer_size;
@@ -73,11 +127,14 @@ impl GpuChunkMesh {
             queue.write_buffer(&self.vertex_buffer, 0, vertex_bytes);
             queue.write_buffer(&self.index_buffer, 0, index_bytes);
             self.vertex_count = mesh.vertices.len() as u32;
-            self.index_count = mesh.indices.len() as u32;
+            self.index_count = indices.len() as u32;
+            self.transparent_index_start = self.index_count - transparent_centers.len() as u32 * 6;
+            self.transparent_centers = transparent_centers;
+            self.transparent_indices = indices[self.transparent_index_start as usize..].to_vec();
             true
         } else {
             // Buffers too small — recreate
-            *self = Self::upload(device, mesh);
+            *self = Self::upload_indices(device, mesh, indices, transparent_centers);
             false
         }
     }
@@ -107,6 +164,90 @@ impl GpuChunkMesh {
     pub fn draw(&self, render_pass: &mut wgpu::RenderPass) {
         render_pass.draw_indexed(0..self.index_count, 0, 0..1);
     }
+
+    /// Issue an indexed draw call for the opaque range only.
+    pub fn draw_opaque(&self, render_pass: &mut wgpu::RenderPass) {
+        render_pass.draw_indexed(0..self.transparent_index_start, 0, 0..1);
+    }
+
+    /// Issue an indexed draw call for the translucent range only.
+    pub fn draw_transparent(&self, render_pass: &mut wgpu::RenderPass) {
+        render_pass.draw_indexed(self.transparent_index_start..self.index_count, 0, 0..1);
+    }
+
+    /// Whether the mesh has any translucent quads.
+    pub fn has_transparent(&self) -> bool {
+        !self.transparent_centers.is_empty()
+    }
+
+    /// Chunk-local center of the bounds of the translucent quads, or the
+    /// origin if there are none.
+    pub fn transparent_center(&self) -> Vec3 {
+        if self.transparent_centers.is_empty() {
+            return Vec3::ZERO;
+        }
+        let (min, max) = self.transparent_centers.iter().fold(
+            (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+            |(min, max), &c| (min.min(c), max.max(c)),
+        );
+        (min + max) * 0.5
+    }
+
+    /// Order the translucent quads farthest first along `camera_z`, the
+    /// camera's forward axis, and rewrite that range of the index buffer so
+    /// one [`draw_transparent`](Self::draw_transparent) call blends them
+    /// correctly.
+    ///
+    /// Returns `false` without touching the buffer if the order is unchanged.
+    pub fn sort_transparent(&mut self, queue: &wgpu::Queue, camera_z: Vec3) -> bool {
+        let order = back_to_front(&self.transparent_centers, camera_z);
+        if order.iter().enumerate().all(|(i, &quad)| i == quad) {
+            return false;
+        }
+        let centers = order.iter().map(|&q| self.transparent_centers[q]).collect();
+        let indices: Vec<u32> = order
+            .iter()
+            .flat_map(|&q| self.transparent_indices[q * 6..q * 6 + 6].iter().copied())
+            .collect();
+        queue.write_buffer(
+            &self.index_buffer,
+            u64::from(self.transparent_index_start) * 4,
+            bytemuck::cast_slice(&indices),
+        );
+        self.transparent_centers = centers;
+        self.transparent_indices = indices;
+        true
+    }
+}
+
+/// Split the quads of `mesh` into an opaque-then-translucent index list,
+/// returning it with the chunk-local center of each translucent quad.
+fn partition_transparent(
+    mesh: &PackedChunkMesh,
+    is_transparent: impl Fn(u16) -> bool,
+) -> (Vec<u32>, Vec<Vec3>) {
+    let mut opaque = Vec::with_capacity(mesh.indices.len());
+    let mut transparent = Vec::new();
+    let mut centers = Vec::new();
+    for quad in mesh.indices.chunks(6) {
+        let vertices = quad.iter().filter_map(|&i| mesh.vertices.get(i as usize));
+        let material = vertices.clone().next().map(|v| v.material_id);
+        if quad.len() == 6 && material.is_some_and(&is_transparent) {
+            let (min, max) = vertices.fold(
+                (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+                |(min, max), v| {
+                    let p = Vec3::from(v.position_f32());
+                    (min.min(p), max.max(p))
+                },
+            );
+            transparent.extend_from_slice(quad);
+            centers.push((min + max) * 0.5);
+        } else {
+            opaque.extend_from_slice(quad);
+        }
+    }
+    opaque.extend_from_slice(&transparent);
+    (opaque, centers)
 }
 
 #[cfg(test)]
@@ -158,7 +299,7 @@ mod tests {
             return; // graceful skip when no GPU
         };
         let mesh = make_quad_mesh(1);
-        let gpu_mesh = GpuChunkMesh::upload(&device, &mesh);
+        let gpu_mesh = GpuChunkMesh::upload(&device, &mesh, |_| false);
 
         assert_eq!(gpu_mesh.vertex_count, 4);
         assert_eq!(gpu_mesh.index_count, 6);
@@ -172,7 +313,7 @@ mod tests {
             return;
         };
         let mesh = make_quad_mesh(10);
-        let gpu_mesh = GpuChunkMesh::upload(&device, &mesh);
+        let gpu_mesh = GpuChunkMesh::upload(&device, &mesh, |_| false);
 
         assert_eq!(gpu_mesh.vertex_buffer_size, (10 * 4 * 12) as u64);
         assert_eq!(gpu_mesh.index_buffer_size, (10 * 6 * 4) as u64);
@@ -188,27 +329,76 @@ mod tests {
             return;
         };
         let big_mesh = make_quad_mesh(10);
-        let mut gpu_mesh = GpuChunkMesh::upload(&device, &big_mesh);
+        let mut gpu_mesh = GpuChunkMesh::upload(&device, &big_mesh, |_| false);
 
         // Re-upload a smaller mesh — should reuse buffers
         let small_mesh = make_quad_mesh(5);
-        let reused = gpu_mesh.reupload(&device, &queue, &small_mesh);
+        let reused = gpu_mesh.reupload(&device, &queue, &small_mesh, |_| false);
 
         assert!(reused, "smaller mesh should fit in existing buffers");
         assert_eq!(gpu_mesh.vertex_count, 20); // 5 quads × 4 verts
         assert_eq!(gpu_mesh.index_count, 30); // 5 quads × 6 indices
     }
 
+    #[test]
+    fn test_transparent_quads_are_uploaded_last() {
+        let Some((device, queue)) = test_device() else {
+            return;
+        };
+        // Quads 1 and 3 use translucent material 7.
+        let mut mesh = PackedChunkMesh::new();
+        for i in 0..4u8 {
+            let material = if i % 2 == 1 { 7 } else { 1 };
+            mesh.push_quad(
+                [
+                    ChunkVertex::new([i, 0, 0], FaceDirection::PosY, 0, material, [0, 0]),
+                    ChunkVertex::new([i + 1, 0, 0], FaceDirection::PosY, 0, material, [1, 0]),
+                    ChunkVertex::new([i + 1, 0, 2], FaceDirection::PosY, 0, material, [1, 1]),
+                    ChunkVertex::new([i, 0, 2], FaceDirection::PosY, 0, material, [0, 1]),
+                ],
+                false,
+            );
+        }
+        let mut gpu_mesh = GpuChunkMesh::upload(&device, &mesh, |m| m == 7);
+
+        assert_eq!(gpu_mesh.index_count, 24);
+        assert_eq!(gpu_mesh.transparent_index_start, 12);
+        assert_eq!(
+            gpu_mesh.transparent_centers,
+            [Vec3::new(1.5, 0.0, 1.0), Vec3::new(3.5, 0.0, 1.0)]
+        );
+        assert_eq!(gpu_mesh.transparent_center(), Vec3::new(2.5, 0.0, 1.0));
+
+        // Looking down +X the quad at x = 3.5 is farther and moves first;
+        // sorting again in the same direction changes nothing.
+        assert!(gpu_mesh.sort_transparent(&queue, Vec3::X));
+        assert_eq!(gpu_mesh.transparent_centers[0], Vec3::new(3.5, 0.0, 1.0));
+        assert_eq!(
+            gpu_mesh.transparent_indices,
+            mesh.indices[18..24]
+                .iter()
+                .chain(&mesh.indices[6..12])
+                .copied()
+                .collect::<Vec<u32>>()
+        );
+        assert!(!gpu_mesh.sort_transparent(&queue, Vec3::X));
+
+        // A fully opaque re-upload clears the translucent range.
+        assert!(gpu_mesh.reupload(&device, &queue, &mesh, |_| false));
+        assert_eq!(gpu_mesh.transparent_index_start, gpu_mesh.index_count);
+        assert!(!gpu_mesh.has_transparent());
+    }
+
     #[test]
     fn test_reupload_recreates_when_too_large() {
         let Some((device, queue)) = test_device() else {
             return;
         };
         let small_mesh = make_quad_mesh(1);
-        let mut gpu_mesh = GpuChunkMesh::upload(&device, &small_mesh);
+        let mut gpu_mesh = GpuChunkMesh::upload(&device, &small_mesh, |_| false);
 
         let big_mesh = make_quad_mesh(10);
-        let reused = gpu_mesh.reupload(&device, &queue, &big_mesh);
+        let reused = gpu_mesh.reupload(&device, &queue, &big_mesh, |_| false);
 
         assert!(!reused, "larger mesh should not fit");
         assert_eq!(gpu_mesh.vertex_count, 40);
@@ -0,0 +1,330 @@
+//! Instanced drawing for vegetation and props.
+//!
+//! Thousands of copies of one mesh (trees, rocks, crates) are drawn with a
+//! single call: an [`InstanceBuffer`] holds one [`InstanceData`] per copy, and
+//! [`LitPipeline::draw_instanced`](crate::LitPipeline::draw_instanced) reads
+//! it as a second, per-instance vertex buffer.
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use crate::buffer::MeshBuffer;
+
+/// Per-instance vertex data: model matrix and color tint, 80 bytes.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InstanceData {
+    /// Column-major model (local-to-world) matrix.
+    pub model_matrix: [f32; 16],
+    /// RGBA multiplier applied to the vertex color.
+    pub color_tint: [f32; 4],
+}
+
+impl InstanceData {
+    /// Instance with the given transform and no tint.
+    pub fn from_transform(transform: glam::Mat4) -> Self {
+        Self {
+            model_matrix: transform.to_cols_array(),
+            color_tint: [1.0; 4],
+        }
+    }
+
+    /// Vertex buffer layout for slot 1: matrix columns at locations 2–5,
+    /// tint at location 6.
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        const ATTRIBUTES: [wgpu::VertexAttribute; 5] = wgpu::vertex_attr_array![
+            2 => Float32x4,
+            3 => Float32x4,
+            4 => Float32x4,
+            5 => Float32x4,
+            6 => Float32x4,
+        ];
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<InstanceData>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &ATTRIBUTES,
+        }
+    }
+}
+
+/// A GPU buffer of [`InstanceData`].
+pub struct InstanceBuffer {
+    /// Vertex buffer holding the instances.
+    pub buffer: wgpu::Buffer,
+    /// Number of instances in the buffer.
+    pub count: u32,
+}
+
+impl InstanceBuffer {
+    /// Upload one untinted instance per transform.
+    pub fn from_transforms(device: &wgpu::Device, transforms: &[glam::Mat4]) -> Self {
+        let instances: Vec<InstanceData> = transforms
+            .iter()
+            .map(|&t| InstanceData::from_transform(t))
+            .collect();
+        Self::from_instances(device, &instances)
+    }
+
+    /// Upload `instances` as-is.
+    pub fn from_instances(device: &wgpu::Device, instances: &[InstanceData]) -> Self {
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("instance-buffer"),
+            contents: bytemuck::cast_slice(instances),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+        Self {
+            buffer,
+            count: instances.len() as u32,
+        }
+    }
+}
+
+/// A mesh paired with the instances to draw it at.
+pub struct InstancedMesh {
+    /// Per-vertex data ([`VertexPositionColor`](crate::VertexPositionColor)).
+    pub vertex_buffer: wgpu::Buffer,
+    /// Index data.
+    pub index_buffer: wgpu::Buffer,
+    /// Per-instance [`InstanceData`].
+    pub instance_buffer: wgpu::Buffer,
+    /// Number of indices to draw per instance.
+    pub index_count: u32,
+    /// Format of `index_buffer`.
+    pub index_format: wgpu::IndexFormat,
+    /// Number of instances to draw.
+    pub instance_count: u32,
+}
+
+impl InstancedMesh {
+    /// Combine a mesh with its instances.
+    pub fn new(mesh: MeshBuffer, instances: InstanceBuffer) -> Self {
+        Self {
+            vertex_buffer: mesh.vertex_buffer,
+            index_buffer: mesh.index_buffer,
+            instance_buffer: instances.buffer,
+            index_count: mesh.index_count,
+            index_format: mesh.index_format,
+            instance_count: instances.count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::{BufferAllocator, IndexData, VertexPositionColor};
+    use crate::lit_pipeline::{LIT_SHADER_SOURCE, LitPipeline};
+    use crate::texture::create_test_device_queue;
+
+    fn buffer(device: &wgpu::Device, size: u64, usage: wgpu::BufferUsages) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage,
+            mapped_at_creation: false,
+        })
+    }
+
+    fn bind_group(
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+        resources: &[wgpu::BindingResource],
+    ) -> wgpu::BindGroup {
+        let entries: Vec<_> = resources
+            .iter()
+            .enumerate()
+            .map(|(i, resource)| wgpu::BindGroupEntry {
+                binding: i as u32,
+                resource: resource.clone(),
+            })
+            .collect();
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout,
+            entries: &entries,
+        })
+    }
+
+    fn grove(device: &wgpu::Device) -> InstancedMesh {
+        let vertices = [
+            VertexPositionColor {
+                position: [0.0, 0.0, 0.0],
+                color: [0.2, 0.6, 0.2, 1.0],
+            },
+            VertexPositionColor {
+                position: [1.0, 0.0, 0.0],
+                color: [0.2, 0.6, 0.2, 1.0],
+            },
+            VertexPositionColor {
+                position: [0.0, 3.0, 0.0],
+                color: [0.2, 0.6, 0.2, 1.0],
+            },
+        ];
+        let mesh = BufferAllocator::new(device).create_mesh(
+            "tree",
+            bytemuck::cast_slice(&vertices),
+            IndexData::U16(&[0, 1, 2]),
+        );
+        let transforms: Vec<_> = (0..100)
+            .map(|i| {
+                glam::Mat4::from_translation(glam::Vec3::new((i % 10) as f32, 0.0, (i / 10) as f32))
+            })
+            .collect();
+        InstancedMesh::new(mesh, InstanceBuffer::from_transforms(device, &transforms))
+    }
+
+    #[test]
+    fn test_instance_data_layout() {
+        assert_eq!(std::mem::size_of::<InstanceData>(), 80);
+        let layout = InstanceData::layout();
+        assert_eq!(layout.array_stride, 80);
+        assert_eq!(layout.step_mode, wgpu::VertexStepMode::Instance);
+        let locations: Vec<_> = layout
+            .attributes
+            .iter()
+            .map(|a| a.shader_location)
+            .collect();
+        assert_eq!(locations, [2, 3, 4, 5, 6]);
+        assert_eq!(layout.attributes[4].offset, 64);
+
+        let transform = glam::Mat4::from_translation(glam::Vec3::new(1.0, 2.0, 3.0));
+        let data = InstanceData::from_transform(transform);
+        assert_eq!(&data.model_matrix[12..15], &[1.0, 2.0, 3.0]);
+        assert_eq!(data.color_tint, [1.0; 4]);
+    }
+
+    #[test]
+    fn test_draw_instanced_records_without_errors() {
+        let Some((device, queue)) = create_test_device_queue() else {
+            return;
+        };
+        let scope = device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("lit"),
+            source: wgpu::ShaderSource::Wgsl(LIT_SHADER_SOURCE.into()),
+        });
+        let color_format = wgpu::TextureFormat::Rgba8Unorm;
+        let depth_format = wgpu::TextureFormat::Depth32Float;
+        let pipeline = LitPipeline::new(&device, &shader, color_format, Some(depth_format), None);
+
+        let uniform = wgpu::BufferUsages::UNIFORM;
+        let camera = buffer(&device, 80, uniform);
+        let sun = buffer(&device, 32, uniform);
+        let points = buffer(&device, 64, wgpu::BufferUsages::STORAGE);
+        let context = buffer(&device, 32, uniform);
+        let shadow_uniform = buffer(&device, 288, uniform);
+        let material = buffer(&device, 48, uniform);
+        let shadow_map = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 2,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: depth_format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let shadow_view = shadow_map.create_view(&wgpu::TextureViewDescriptor {
+            dimension: Some(wgpu::TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            compare: Some(wgpu::CompareFunction::LessEqual),
+            ..Default::default()
+        });
+        let groups = [
+            bind_group(
+                &device,
+                &pipeline.camera_bind_group_layout,
+                &[camera.as_entire_binding()],
+            ),
+            bind_group(
+                &device,
+                &pipeline.light_bind_group_layout,
+                &[
+                    sun.as_entire_binding(),
+                    points.as_entire_binding(),
+                    context.as_entire_binding(),
+                ],
+            ),
+            bind_group(
+                &device,
+                &pipeline.shadow_bind_group_layout,
+                &[
+                    shadow_uniform.as_entire_binding(),
+                    wgpu::BindingResource::TextureView(&shadow_view),
+                    wgpu::BindingResource::Sampler(&sampler),
+                ],
+            ),
+            bind_group(
+                &device,
+                &pipeline.material_bind_group_layout,
+                &[material.as_entire_binding()],
+            ),
+        ];
+
+        let target = |format| {
+            device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: None,
+                    size: wgpu::Extent3d {
+                        width: 64,
+                        height: 64,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                })
+                .create_view(&Default::default())
+        };
+        let color = target(color_format);
+        let depth = target(depth_format);
+
+        let mesh = grove(&device);
+        assert_eq!(mesh.instance_count, 100);
+
+        let mut encoder = device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("instanced"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &color,
+                    resolve_target: None,
+                    depth_slice: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(0.0),
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: None,
+                }),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            for (index, group) in groups.iter().enumerate() {
+                pass.set_bind_group(index as u32, group, &[]);
+            }
+            pipeline.draw_instanced(&mut pass, &mesh);
+        }
+        queue.submit([encoder.finish()]);
+
+        let error = pollster::block_on(scope.pop());
+        assert!(error.is_none(), "validation error: {error:?}");
+    }
+}
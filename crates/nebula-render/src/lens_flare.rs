@@ -2,10 +2,15 @@
 //!
 //! Draws hexagonal ghosts, anamorphic streaks, circular halos, and starburst
 //! patterns along the light-to-center screen diagonal. Intensity is modulated
-//! by screen-edge proximity and (optionally) depth-buffer occlusion.
+//! by the angle between the view direction and the sun, screen-edge
+//! proximity, and (optionally) depth-buffer occlusion.
 
 use bytemuck::{Pod, Zeroable};
 
+mod fade;
+
+pub use fade::sun_angle_intensity;
+
 /// A single lens flare element (ghost, streak, or starburst).
 #[derive(Clone, Debug)]
 pub struct FlareElement {
@@ -58,6 +63,12 @@ pub struct LensFlareConfig {
     pub edge_fade_margin: f32,
     /// Number of depth samples for occlusion testing. Default: 16.
     pub occlusion_samples: u32,
+    /// Off-axis sun angle, in degrees, up to which the flare renders at full
+    /// intensity. Default: 20.
+    pub sun_angle_threshold_deg: f32,
+    /// Angle, in degrees, past the threshold over which the flare fades out.
+    /// Default: 25.
+    pub fade_angle_deg: f32,
 }
 
 impl Default for LensFlareConfig {
@@ -110,6 +121,8 @@ impl Default for LensFlareConfig {
             intensity: 1.0,
             edge_fade_margin: 0.3,
             occlusion_samples: 16,
+            sun_angle_threshold_deg: 20.0,
+            fade_angle_deg: 25.0,
         }
     }
 }
@@ -238,6 +251,8 @@ pub struct LensFlareRenderer {
     instance_bind_group: wgpu::BindGroup,
     /// Occlusion visibility from the previous frame (0=occluded, 1=visible).
     last_occlusion_visibility: f32,
+    /// Element intensity scale from the last [`update_sun_angle`](Self::update_sun_angle).
+    sun_angle_intensity: f32,
 }
 
 impl LensFlareRenderer {
@@ -345,6 +360,7 @@ impl LensFlareRenderer {
             instance_buffer,
             instance_bind_group,
             last_occlusion_visibility: 1.0,
+            sun_angle_intensity: 1.0,
         }
     }
 
@@ -392,7 +408,15 @@ impl LensFlareRenderer {
             return false;
         }
 
-        let intensity = self.config.intensity * sun_brightness * edge_fade * visibility;
+        if self.sun_angle_intensity <= 0.0 {
+            return false;
+        }
+
+        let intensity = self.config.intensity
+            * sun_brightness
+            * edge_fade
+            * visibility
+            * self.sun_angle_intensity;
 
         let mut instances = vec![FlareInstance::zeroed(); MAX_FLARE_ELEMENTS];
         let count = self.config.elements.len().min(MAX_FLARE_ELEMENTS);
@@ -445,14 +469,6 @@ impl LensFlareRenderer {
     pub fn set_occlusion_visibility(&mut self, visibility: f32) {
         self.last_occlusion_visibility = visibility.clamp(0.0, 1.0);
     }
-
-    /// Create a test-only projector (no GPU resources needed).
-    #[cfg(test)]
-    pub(crate) fn default_test() -> TestFlareProjector {
-        TestFlareProjector {
-            config: LensFlareConfig::default(),
-        }
-    }
 }
 
 /// Compute the screen position of a flare element along the light-to-center line.
@@ -478,195 +494,6 @@ pub fn edge_fade_factor(screen_pos: glam::Vec2, margin: f32) -> f32 {
     }
 }
 
-/// Test-only projector for lens flare math (no GPU resources).
-#[cfg(test)]
-#[allow(dead_code)]
-pub(crate) struct TestFlareProjector {
-    pub config: LensFlareConfig,
-}
-
-#[cfg(test)]
-impl TestFlareProjector {
-    /// Project the sun direction to normalized screen coordinates [0, 1].
-    pub fn project_to_screen(
-        &self,
-        sun_direction: glam::Vec3,
-        view_proj: glam::Mat4,
-    ) -> Option<glam::Vec2> {
-        let sun_pos = sun_direction * 1000.0;
-        let clip = view_proj * glam::Vec4::new(sun_pos.x, sun_pos.y, sun_pos.z, 1.0);
-        if clip.w <= 0.0 {
-            return None;
-        }
-        let ndc = glam::Vec3::new(clip.x / clip.w, clip.y / clip.w, clip.z / clip.w);
-        Some(glam::Vec2::new(ndc.x * 0.5 + 0.5, -ndc.y * 0.5 + 0.5))
-    }
-}
-
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use glam::{Mat4, Vec2, Vec3};
-
-    #[test]
-    fn test_flare_visible_when_looking_at_sun() {
-        let view_proj = Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, 16.0 / 9.0, 1000.0, 0.1);
-        let sun_dir = Vec3::new(0.0, 0.0, -1.0);
-
-        let renderer = LensFlareRenderer::default_test();
-        let screen_pos = renderer.project_to_screen(sun_dir, view_proj);
-        assert!(
-            screen_pos.is_some(),
-            "Sun directly ahead should project to screen"
-        );
-
-        let pos = screen_pos.unwrap();
-        assert!(
-            (pos.x - 0.5).abs() < 0.1 && (pos.y - 0.5).abs() < 0.1,
-            "Sun ahead should be near screen center, got ({}, {})",
-            pos.x,
-            pos.y
-        );
-    }
-
-    #[test]
-    fn test_flare_fades_near_screen_edge() {
-        let margin = 0.3;
-
-        let center_fade = edge_fade_factor(Vec2::new(0.5, 0.5), margin);
-        assert!(
-            (center_fade - 1.0).abs() < 1e-6,
-            "Center should have full intensity, got {center_fade}"
-        );
-
-        let edge_fade = edge_fade_factor(Vec2::new(0.9, 0.5), margin);
-        assert!(
-            edge_fade < 1.0 && edge_fade > 0.0,
-            "Near edge should have reduced intensity, got {edge_fade}"
-        );
-
-        let off_fade = edge_fade_factor(Vec2::new(1.1, 0.5), margin);
-        assert!(
-            off_fade <= 0.0,
-            "Off screen should have zero intensity, got {off_fade}"
-        );
-    }
-
-    #[test]
-    fn test_flare_disappears_when_sun_is_occluded() {
-        let visibility = 0.0_f32;
-        let edge_fade = 1.0;
-        let sun_brightness = 50.0;
-        let config_intensity = 1.0;
-
-        let intensity = config_intensity * sun_brightness * edge_fade * visibility;
-        assert_eq!(
-            intensity, 0.0,
-            "Fully occluded sun should produce zero flare intensity"
-        );
-    }
-
-    #[test]
-    fn test_flare_elements_positioned_along_diagonal() {
-        let light_pos = Vec2::new(0.3, 0.2);
-        let center = Vec2::new(0.5, 0.5);
-        let direction = center - light_pos;
-
-        let positions: Vec<Vec2> = [0.0, 0.4, 0.7, 1.0, 1.5]
-            .iter()
-            .map(|&t| element_screen_position(light_pos, t))
-            .collect();
-
-        for (i, pos) in positions.iter().enumerate() {
-            if i == 0 {
-                assert!(
-                    (pos.x - light_pos.x).abs() < 1e-6 && (pos.y - light_pos.y).abs() < 1e-6,
-                    "Element at t=0 should be at light position"
-                );
-                continue;
-            }
-            let offset = *pos - light_pos;
-            let cross = offset.x * direction.y - offset.y * direction.x;
-            assert!(
-                cross.abs() < 1e-5,
-                "Element {i} at ({}, {}) is not on the diagonal (cross product = {cross})",
-                pos.x,
-                pos.y
-            );
-        }
-    }
-
-    #[test]
-    fn test_flare_intensity_proportional_to_sun_brightness() {
-        let edge_fade = 1.0;
-        let visibility = 1.0;
-        let config_intensity = 1.0;
-
-        let intensity_dim: f32 = config_intensity * 10.0 * edge_fade * visibility;
-        let intensity_bright: f32 = config_intensity * 100.0 * edge_fade * visibility;
-
-        assert!(intensity_bright > intensity_dim);
-        assert!(
-            (intensity_bright / intensity_dim - 10.0).abs() < 1e-6,
-            "Flare intensity should scale linearly with sun brightness"
-        );
-    }
-
-    #[test]
-    fn test_sun_behind_camera_produces_no_flare() {
-        let view_proj = Mat4::perspective_rh(std::f32::consts::FRAC_PI_4, 16.0 / 9.0, 1000.0, 0.1);
-        let sun_dir = Vec3::new(0.0, 0.0, 1.0);
-
-        let renderer = LensFlareRenderer::default_test();
-        let screen_pos = renderer.project_to_screen(sun_dir, view_proj);
-        assert!(
-            screen_pos.is_none(),
-            "Sun behind camera should not project to screen"
-        );
-    }
-
-    #[test]
-    fn test_default_config_has_expected_elements() {
-        let config = LensFlareConfig::default();
-        assert!(
-            config.elements.len() >= 4,
-            "Default config should have at least 4 flare elements, got {}",
-            config.elements.len()
-        );
-
-        let has_starburst = config
-            .elements
-            .iter()
-            .any(|e| e.shape == FlareShape::Starburst);
-        let has_ghost = config
-            .elements
-            .iter()
-            .any(|e| e.shape == FlareShape::HexagonalGhost);
-        assert!(
-            has_starburst,
-            "Default config should include a starburst element"
-        );
-        assert!(
-            has_ghost,
-            "Default config should include a hexagonal ghost element"
-        );
-    }
-
-    #[test]
-    fn test_edge_fade_is_symmetric() {
-        let margin = 0.3;
-        let fade_left = edge_fade_factor(Vec2::new(0.1, 0.5), margin);
-        let fade_right = edge_fade_factor(Vec2::new(0.9, 0.5), margin);
-        assert!(
-            (fade_left - fade_right).abs() < 1e-6,
-            "Edge fade should be symmetric: left={fade_left}, right={fade_right}"
-        );
-
-        let fade_top = edge_fade_factor(Vec2::new(0.5, 0.1), margin);
-        let fade_bottom = edge_fade_factor(Vec2::new(0.5, 0.9), margin);
-        assert!(
-            (fade_top - fade_bottom).abs() < 1e-6,
-            "Edge fade should be symmetric: top={fade_top}, bottom={fade_bottom}"
-        );
-    }
-}
+#[path = "lens_flare_tests.rs"]
+mod tests;
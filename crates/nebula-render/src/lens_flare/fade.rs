@@ -0,0 +1,61 @@
+//! Lens flare fade by the sun's angle from the view axis.
+
+use super::LensFlareRenderer;
+
+impl LensFlareRenderer {
+    /// Scale flare element intensity by how directly the camera faces the sun.
+    ///
+    /// Call once per frame before [`update`](Self::update). See
+    /// [`sun_angle_intensity`] for the falloff.
+    pub fn update_sun_angle(&mut self, camera_forward: glam::Vec3, sun_dir: glam::Vec3) {
+        self.sun_angle_intensity = sun_angle_intensity(
+            camera_forward,
+            sun_dir,
+            self.config.sun_angle_threshold_deg,
+            self.config.fade_angle_deg,
+        );
+    }
+
+    /// Returns `true` if the sun is close enough to the view axis for the
+    /// flare to have any intensity.
+    pub fn is_visible(&self, camera_forward: glam::Vec3, sun_dir: glam::Vec3) -> bool {
+        sun_angle_intensity(
+            camera_forward,
+            sun_dir,
+            self.config.sun_angle_threshold_deg,
+            self.config.fade_angle_deg,
+        ) > 0.0
+    }
+}
+
+/// Flare intensity factor for a sun `sun_dir` relative to `camera_forward`.
+///
+/// 1.0 while the sun is within `threshold_deg` of the view axis, fading
+/// linearly to 0.0 over the next `fade_deg`. Always 0.0 once the sun is 90°
+/// or more off-axis, since it can no longer be in front of the lens.
+pub fn sun_angle_intensity(
+    camera_forward: glam::Vec3,
+    sun_dir: glam::Vec3,
+    threshold_deg: f32,
+    fade_deg: f32,
+) -> f32 {
+    let cos_angle = camera_forward
+        .normalize_or_zero()
+        .dot(sun_dir.normalize_or_zero());
+    if cos_angle <= 0.0 {
+        return 0.0;
+    }
+
+    let angle_deg = cos_angle.min(1.0).acos().to_degrees();
+    if angle_deg <= threshold_deg {
+        1.0
+    } else if fade_deg <= 0.0 {
+        0.0
+    } else {
+        (1.0 - (angle_deg - threshold_deg) / fade_deg).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+#[path = "fade_tests.rs"]
+mod tests;
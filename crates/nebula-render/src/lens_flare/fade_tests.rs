@@ -0,0 +1,37 @@
+//! Unit tests for the sun-angle lens flare fade.
+
+use super::*;
+use crate::lens_flare::LensFlareConfig;
+use glam::Vec3;
+
+#[test]
+fn test_looking_at_sun_gives_full_intensity() {
+    let config = LensFlareConfig::default();
+    let intensity = sun_angle_intensity(
+        Vec3::NEG_Z,
+        Vec3::NEG_Z,
+        config.sun_angle_threshold_deg,
+        config.fade_angle_deg,
+    );
+    assert!((intensity - 1.0).abs() < 1e-6, "got {intensity}");
+}
+
+#[test]
+fn test_sun_90_degrees_off_axis_gives_zero_intensity() {
+    for (threshold, fade) in [(20.0, 25.0), (80.0, 30.0), (120.0, 0.0), (0.0, 180.0)] {
+        let intensity = sun_angle_intensity(Vec3::NEG_Z, Vec3::X, threshold, fade);
+        assert_eq!(intensity, 0.0, "threshold={threshold} fade={fade}");
+    }
+}
+
+#[test]
+fn test_sun_angle_fades_past_threshold() {
+    let at = |deg: f32| {
+        let rad = deg.to_radians();
+        let sun = Vec3::new(rad.sin(), 0.0, -rad.cos());
+        sun_angle_intensity(Vec3::NEG_Z, sun, 20.0, 20.0)
+    };
+    assert!((at(15.0) - 1.0).abs() < 1e-5);
+    assert!((at(30.0) - 0.5).abs() < 1e-3);
+    assert_eq!(at(45.0), 0.0);
+}
@@ -9,8 +9,10 @@ pub mod frustum;
 pub mod gpu;
 pub mod gpu_buffer_pool;
 pub mod gpu_chunk_mesh;
+pub mod instancing;
 pub mod lens_flare;
 pub mod lit_pipeline;
+pub mod overlay;
 pub mod pass;
 pub mod pbr_voxel_pipeline;
 pub mod pipeline;
@@ -18,7 +20,10 @@ pub mod shader;
 pub mod shadow_pipeline;
 pub mod surface;
 pub mod texture;
+pub mod texture_cache;
+mod texture_upload;
 pub mod textured_pipeline;
+pub mod transparency;
 
 pub use batching::{
     DrawBatch, DrawCall, DrawGroup, DrawGroupIter, InstancedDraw, InstancedGroupIter,
@@ -30,22 +35,39 @@ pub use buffer::{
     BufferAllocator, IndexData, MeshBuffer, VertexPositionColor, VertexPositionNormalUv,
     VoxelVertex,
 };
-pub use camera::{Camera, Projection};
+pub use camera::{Camera, Projection, Ray};
 pub use depth::DepthBuffer;
 pub use frustum::{Aabb, Frustum, FrustumCuller};
 pub use gpu::{RenderContext, RenderContextError, SurfaceError, init_render_context_blocking};
-pub use gpu_buffer_pool::GpuBufferPool;
+pub use gpu_buffer_pool::{GpuBufferPool, GpuPoolUsage, GpuUsageCallback};
 pub use gpu_chunk_mesh::GpuChunkMesh;
+pub use instancing::{InstanceBuffer, InstanceData, InstancedMesh};
 pub use lit_pipeline::{LIT_SHADER_SOURCE, LitPipeline, draw_lit};
+pub use overlay::{
+    BitmapFont, FontError, FontMetrics, Overlay, OverlayRenderer, OverlayVertex,
+    ScreenQuadRenderer, TextRenderer, pixel_projection,
+};
 pub use pass::{DepthAttachmentConfig, FrameEncoder, RenderPassBuilder, SKY_BLUE};
 pub use pbr_voxel_pipeline::{
     PBR_VOXEL_SHADER_SOURCE, PbrCameraUniform, PbrLightUniform, PbrVoxelPipeline, draw_pbr_voxel,
 };
 pub use pipeline::{CameraUniform, UNLIT_SHADER_SOURCE, UnlitPipeline, draw_unlit};
 pub use shader::{ShaderError, ShaderLibrary};
-pub use shadow_pipeline::{SHADOW_SHADER_SOURCE, ShadowPipeline, render_shadow_cascades};
-pub use surface::{MIN_SURFACE_DIMENSION, PhysicalSize, SurfaceResizeEvent, SurfaceWrapper};
+pub use shadow_pipeline::{
+    SHADOW_SHADER_SOURCE, ShadowPipeline, compute_cascade_matrix_tight, render_shadow_cascades,
+};
+pub use surface::{
+    DisplayInfo, MIN_SURFACE_DIMENSION, PhysicalSize, SurfaceResizeEvent, SurfaceWrapper,
+    enumerate_displays,
+};
 pub use texture::{
-    ManagedTexture, TextureError, TextureLayerData, TextureManager, mip_level_count,
+    ManagedTexture, TextureError, TextureGuard, TextureId, TextureLayerData, TextureManager,
+    mip_level_count,
+};
+pub use textured_pipeline::{
+    BLENDED_CHUNK_SHADER_SOURCE, TEXTURED_SHADER_SOURCE, TexturedPipeline, draw_textured,
+};
+pub use transparency::{
+    TRANSPARENT_BLEND, TransparentBatch, TransparentDrawList, TransparentRenderPass,
+    transparent_depth_stencil,
 };
-pub use textured_pipeline::{TEXTURED_SHADER_SOURCE, TexturedPipeline, draw_textured};
@@ -0,0 +1,253 @@
+//! Baked monospace bitmap font and pixel-space text layout.
+//!
+//! The embedded atlas is DejaVu Sans Mono rasterized at 16 px into a 16×6
+//! grid of 10×20 cells: printable ASCII (`' '`..=`'~'`) in order, followed by
+//! the degree sign. Layout is pure CPU work on [`FontMetrics`], so it can be
+//! tested without a GPU.
+
+/// Grayscale PNG holding one glyph per cell, coverage in the single channel.
+const EMBEDDED_FONT_PNG: &[u8] = include_bytes!("font.png");
+
+/// First character in the atlas.
+const FIRST_CHAR: u32 = ' ' as u32;
+
+/// Last ASCII character in the atlas.
+const LAST_ASCII_CHAR: u32 = '~' as u32;
+
+/// Cell index of the degree sign, stored after the ASCII range.
+const DEGREE_CELL: u32 = LAST_ASCII_CHAR - FIRST_CHAR + 1;
+
+/// Errors from decoding a bitmap font atlas.
+#[derive(Debug, thiserror::Error)]
+pub enum FontError {
+    /// The PNG could not be decoded.
+    #[error("failed to decode font atlas: {0}")]
+    Decode(String),
+    /// The atlas is not 8-bit grayscale.
+    #[error("font atlas must be 8-bit grayscale, got {0:?} {1:?}")]
+    Format(png::ColorType, png::BitDepth),
+    /// The atlas dimensions disagree with the metrics.
+    #[error("font atlas is {actual:?}, metrics expect {expected:?}")]
+    Size {
+        /// Dimensions implied by the metrics.
+        expected: (u32, u32),
+        /// Dimensions of the decoded image.
+        actual: (u32, u32),
+    },
+}
+
+/// Grid geometry of a monospace font atlas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FontMetrics {
+    /// Cell width in atlas pixels; also the advance between glyphs.
+    pub cell_width: u32,
+    /// Cell height in atlas pixels; also the line height.
+    pub cell_height: u32,
+    /// Distance from the top of a cell to the baseline, in atlas pixels.
+    pub baseline: u32,
+    /// Cells per atlas row.
+    pub columns: u32,
+    /// Cell rows in the atlas.
+    pub rows: u32,
+}
+
+/// Metrics of the embedded font atlas.
+pub const EMBEDDED_FONT_METRICS: FontMetrics = FontMetrics {
+    cell_width: 10,
+    cell_height: 20,
+    baseline: 15,
+    columns: 16,
+    rows: 6,
+};
+
+/// One glyph placed on screen, in pixels with the origin at the top left.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlyphQuad {
+    /// Top-left corner of the glyph cell.
+    pub position: [f32; 2],
+    /// Width and height of the glyph cell.
+    pub size: [f32; 2],
+    /// Top-left atlas texture coordinate.
+    pub uv_min: [f32; 2],
+    /// Bottom-right atlas texture coordinate.
+    pub uv_max: [f32; 2],
+}
+
+impl FontMetrics {
+    /// Atlas width and height in pixels.
+    pub fn atlas_size(&self) -> (u32, u32) {
+        (self.cell_width * self.columns, self.cell_height * self.rows)
+    }
+
+    /// Horizontal distance between glyph origins for text `size` pixels tall.
+    pub fn advance(&self, size: f32) -> f32 {
+        self.cell_width as f32 * size / self.cell_height as f32
+    }
+
+    /// Vertical distance between lines for text `size` pixels tall.
+    pub fn line_height(&self, size: f32) -> f32 {
+        size
+    }
+
+    /// Atlas cell holding `c`, or `None` for characters the font lacks.
+    pub fn cell(&self, c: char) -> Option<u32> {
+        let cell = match c as u32 {
+            code @ FIRST_CHAR..=LAST_ASCII_CHAR => code - FIRST_CHAR,
+            0xb0 => DEGREE_CELL,
+            _ => return None,
+        };
+        (cell < self.columns * self.rows).then_some(cell)
+    }
+
+    /// Texture coordinates `(min, max)` of an atlas cell.
+    pub fn cell_uv(&self, cell: u32) -> ([f32; 2], [f32; 2]) {
+        let column = (cell % self.columns) as f32;
+        let row = (cell / self.columns) as f32;
+        let (du, dv) = (1.0 / self.columns as f32, 1.0 / self.rows as f32);
+        (
+            [column * du, row * dv],
+            [(column + 1.0) * du, (row + 1.0) * dv],
+        )
+    }
+
+    /// Lay out `text` with its first line's top-left corner at `(x, y)`.
+    ///
+    /// Every character advances the pen by [`advance`](Self::advance);
+    /// `'\n'` returns it to `x` one [`line_height`](Self::line_height) down.
+    /// Spaces produce no quad, and characters missing from the atlas are
+    /// drawn as `'?'`.
+    pub fn layout(&self, x: f32, y: f32, size: f32, text: &str) -> Vec<GlyphQuad> {
+        let advance = self.advance(size);
+        let line_height = self.line_height(size);
+        let mut quads = Vec::with_capacity(text.len());
+        let (mut pen_x, mut pen_y) = (x, y);
+        for c in text.chars() {
+            if c == '\n' {
+                pen_x = x;
+                pen_y += line_height;
+                continue;
+            }
+            if c != ' '
+                && let Some(cell) = self.cell(c).or_else(|| self.cell('?'))
+            {
+                let (uv_min, uv_max) = self.cell_uv(cell);
+                quads.push(GlyphQuad {
+                    position: [pen_x, pen_y],
+                    size: [advance, size],
+                    uv_min,
+                    uv_max,
+                });
+            }
+            pen_x += advance;
+        }
+        quads
+    }
+
+    /// Width of the longest line and total height of `text`, in pixels.
+    pub fn measure(&self, size: f32, text: &str) -> [f32; 2] {
+        let (columns, lines) = text.split('\n').fold((0, 0), |(columns, lines), line| {
+            (columns.max(line.chars().count()), lines + 1)
+        });
+        [
+            columns as f32 * self.advance(size),
+            lines as f32 * self.line_height(size),
+        ]
+    }
+}
+
+/// A decoded font atlas: metrics plus one coverage byte per pixel.
+pub struct BitmapFont {
+    /// Grid geometry of the atlas.
+    pub metrics: FontMetrics,
+    /// Row-major coverage, `atlas_size().0 * atlas_size().1` bytes.
+    pub pixels: Vec<u8>,
+}
+
+impl BitmapFont {
+    /// Decode the font atlas embedded in the crate.
+    pub fn embedded() -> Result<Self, FontError> {
+        Self::from_png(EMBEDDED_FONT_PNG, EMBEDDED_FONT_METRICS)
+    }
+
+    /// Decode an 8-bit grayscale PNG atlas laid out as described by `metrics`.
+    pub fn from_png(bytes: &[u8], metrics: FontMetrics) -> Result<Self, FontError> {
+        let decoder = png::Decoder::new(std::io::Cursor::new(bytes));
+        let mut reader = decoder
+            .read_info()
+            .map_err(|e| FontError::Decode(e.to_string()))?;
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut pixels)
+            .map_err(|e| FontError::Decode(e.to_string()))?;
+        if info.color_type != png::ColorType::Grayscale || info.bit_depth != png::BitDepth::Eight {
+            return Err(FontError::Format(info.color_type, info.bit_depth));
+        }
+        let expected = metrics.atlas_size();
+        if (info.width, info.height) != expected {
+            return Err(FontError::Size {
+                expected,
+                actual: (info.width, info.height),
+            });
+        }
+        pixels.truncate(info.buffer_size());
+        Ok(Self { metrics, pixels })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const M: FontMetrics = EMBEDDED_FONT_METRICS;
+
+    #[test]
+    fn test_embedded_font_decodes() {
+        let font = BitmapFont::embedded().expect("embedded atlas decodes");
+        let (w, h) = M.atlas_size();
+        assert_eq!(font.pixels.len(), (w * h) as usize);
+        // The space cell is blank, the 'M' cell is not.
+        let cell_coverage = |cell: u32| -> u32 {
+            let (x0, y0) = (
+                (cell % M.columns) * M.cell_width,
+                (cell / M.columns) * M.cell_height,
+            );
+            (y0..y0 + M.cell_height)
+                .flat_map(|y| (x0..x0 + M.cell_width).map(move |x| (x, y)))
+                .map(|(x, y)| u32::from(font.pixels[(y * w + x) as usize]))
+                .sum()
+        };
+        assert_eq!(cell_coverage(M.cell(' ').unwrap()), 0);
+        assert!(cell_coverage(M.cell('M').unwrap()) > 0);
+        assert!(cell_coverage(M.cell('°').unwrap()) > 0);
+    }
+
+    #[test]
+    fn test_glyph_advance_widths() {
+        let quads = M.layout(5.0, 7.0, 40.0, "AB C");
+        // 40 px tall text scales the 10×20 cell to 20 px wide.
+        assert_eq!(M.advance(40.0), 20.0);
+        let xs: Vec<f32> = quads.iter().map(|q| q.position[0]).collect();
+        assert_eq!(xs, [5.0, 25.0, 65.0], "the space advances without a quad");
+        assert!(quads.iter().all(|q| q.size == [20.0, 40.0]));
+        assert!(quads.iter().all(|q| q.position[1] == 7.0));
+        assert_eq!(M.measure(40.0, "AB C"), [80.0, 40.0]);
+    }
+
+    #[test]
+    fn test_newlines_stack_lines() {
+        let quads = M.layout(10.0, 0.0, 20.0, "SPD\nALT 1\n\nX");
+        let first_of_line: Vec<[f32; 2]> = [0, 3, 7].iter().map(|&i| quads[i].position).collect();
+        assert_eq!(quads.len(), 8);
+        assert_eq!(first_of_line, [[10.0, 0.0], [10.0, 20.0], [10.0, 60.0]]);
+        assert_eq!(M.measure(20.0, "SPD\nALT 1\n\nX"), [50.0, 80.0]);
+    }
+
+    #[test]
+    fn test_unknown_characters_fall_back() {
+        let unknown = M.layout(0.0, 0.0, 20.0, "é");
+        let question = M.layout(0.0, 0.0, 20.0, "?");
+        assert_eq!(unknown, question);
+        let (uv_min, uv_max) = M.cell_uv(M.cell('~').unwrap());
+        assert!(uv_min[0] < uv_max[0] && uv_max[1] <= 1.0);
+    }
+}
@@ -0,0 +1,461 @@
+//! Minimal 2D overlay drawn over the finished 3D frame: HUD text, bars, and
+//! reticles.
+//!
+//! An [`Overlay`] is an immediate-mode draw list rebuilt every frame with
+//! [`Overlay::text`] and [`Overlay::rect`], in pixels with the origin at the
+//! top-left corner of the surface. [`OverlayRenderer`] uploads it and draws
+//! it with an orthographic pixel-space projection ([`pixel_projection`]) in
+//! a pass that loads the existing color and has no depth attachment, so it
+//! layers after every 3D pass. Rectangles are drawn by the
+//! [`ScreenQuadRenderer`] first and text by the [`TextRenderer`] on top.
+
+mod font;
+mod quad;
+mod text;
+
+pub use font::{BitmapFont, EMBEDDED_FONT_METRICS, FontError, FontMetrics, GlyphQuad};
+pub use quad::ScreenQuadRenderer;
+pub use text::TextRenderer;
+
+use std::num::NonZeroU64;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+/// WGSL source shared by the quad and text pipelines.
+pub const OVERLAY_SHADER_SOURCE: &str = include_str!("overlay.wgsl");
+
+/// Vertices reserved per stream before the first upload grows it.
+const INITIAL_VERTEX_CAPACITY: u64 = 1024;
+
+/// Overlay vertex: pixel position, atlas UV, and linear RGBA color, 32 bytes.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct OverlayVertex {
+    /// Position in pixels from the top-left corner.
+    pub position: [f32; 2],
+    /// Atlas texture coordinate; unused by rectangles.
+    pub uv: [f32; 2],
+    /// Linear RGBA color; alpha blends over the frame.
+    pub color: [f32; 4],
+}
+
+impl OverlayVertex {
+    /// Vertex buffer layout: position, UV, and color at locations 0–2.
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        const ATTRIBUTES: [wgpu::VertexAttribute; 3] =
+            wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Float32x4];
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<OverlayVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &ATTRIBUTES,
+        }
+    }
+}
+
+/// Orthographic projection mapping pixels (origin top-left, y down) to clip
+/// space for a `width` × `height` surface.
+pub fn pixel_projection(width: f32, height: f32) -> glam::Mat4 {
+    glam::Mat4::orthographic_rh(0.0, width.max(1.0), height.max(1.0), 0.0, -1.0, 1.0)
+}
+
+/// Immediate-mode 2D draw list, cleared with [`begin_frame`](Self::begin_frame)
+/// and filled each frame.
+#[derive(Clone, Debug)]
+pub struct Overlay {
+    width: f32,
+    height: f32,
+    metrics: FontMetrics,
+    quads: Vec<OverlayVertex>,
+    glyphs: Vec<OverlayVertex>,
+}
+
+impl Overlay {
+    /// Empty overlay for a `width` × `height` surface using the embedded font.
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            width,
+            height,
+            metrics: EMBEDDED_FONT_METRICS,
+            quads: Vec::new(),
+            glyphs: Vec::new(),
+        }
+    }
+
+    /// Drop last frame's draws and adopt the current surface size.
+    pub fn begin_frame(&mut self, width: f32, height: f32) {
+        self.width = width;
+        self.height = height;
+        self.quads.clear();
+        self.glyphs.clear();
+    }
+
+    /// Surface width and height in pixels.
+    pub fn screen_size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    /// Metrics of the font used by [`text`](Self::text).
+    pub fn font_metrics(&self) -> &FontMetrics {
+        &self.metrics
+    }
+
+    /// Whether nothing has been drawn this frame.
+    pub fn is_empty(&self) -> bool {
+        self.quads.is_empty() && self.glyphs.is_empty()
+    }
+
+    /// Draw `text` `size` pixels tall with its top-left corner at `(x, y)`.
+    /// Lines split on `'\n'` stack downward.
+    pub fn text(&mut self, x: f32, y: f32, size: f32, color: [f32; 4], text: &str) {
+        for glyph in self.metrics.layout(x, y, size, text) {
+            push_quad(
+                &mut self.glyphs,
+                glyph.position,
+                glyph.size,
+                glyph.uv_min,
+                glyph.uv_max,
+                color,
+            );
+        }
+    }
+
+    /// Fill a `width` × `height` rectangle with its top-left corner at `(x, y)`.
+    pub fn rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: [f32; 4]) {
+        push_quad(
+            &mut self.quads,
+            [x, y],
+            [width, height],
+            [0.0; 2],
+            [0.0; 2],
+            color,
+        );
+    }
+
+    /// Rectangle vertices, six per rectangle.
+    pub fn quad_vertices(&self) -> &[OverlayVertex] {
+        &self.quads
+    }
+
+    /// Glyph vertices, six per glyph.
+    pub fn text_vertices(&self) -> &[OverlayVertex] {
+        &self.glyphs
+    }
+}
+
+/// Append two triangles covering an axis-aligned rectangle.
+fn push_quad(
+    out: &mut Vec<OverlayVertex>,
+    position: [f32; 2],
+    size: [f32; 2],
+    uv_min: [f32; 2],
+    uv_max: [f32; 2],
+    color: [f32; 4],
+) {
+    let [x0, y0] = position;
+    let (x1, y1) = (x0 + size[0], y0 + size[1]);
+    let vertex = |x, y, u, v| OverlayVertex {
+        position: [x, y],
+        uv: [u, v],
+        color,
+    };
+    let top_left = vertex(x0, y0, uv_min[0], uv_min[1]);
+    let top_right = vertex(x1, y0, uv_max[0], uv_min[1]);
+    let bottom_left = vertex(x0, y1, uv_min[0], uv_max[1]);
+    let bottom_right = vertex(x1, y1, uv_max[0], uv_max[1]);
+    out.extend_from_slice(&[
+        top_left,
+        bottom_left,
+        bottom_right,
+        top_left,
+        bottom_right,
+        top_right,
+    ]);
+}
+
+/// A vertex buffer rewritten every frame, grown to the next power of two
+/// when a frame outgrows it.
+pub(crate) struct VertexStream {
+    buffer: wgpu::Buffer,
+    capacity: u64,
+    count: u32,
+    label: &'static str,
+}
+
+impl VertexStream {
+    pub(crate) fn new(device: &wgpu::Device, label: &'static str) -> Self {
+        Self {
+            buffer: Self::allocate(device, label, INITIAL_VERTEX_CAPACITY),
+            capacity: INITIAL_VERTEX_CAPACITY,
+            count: 0,
+            label,
+        }
+    }
+
+    fn allocate(device: &wgpu::Device, label: &'static str, capacity: u64) -> wgpu::Buffer {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size: capacity * std::mem::size_of::<OverlayVertex>() as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    pub(crate) fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        vertices: &[OverlayVertex],
+    ) {
+        let needed = vertices.len() as u64;
+        if needed > self.capacity {
+            self.capacity = needed.next_power_of_two();
+            self.buffer = Self::allocate(device, self.label, self.capacity);
+        }
+        if !vertices.is_empty() {
+            queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(vertices));
+        }
+        self.count = vertices.len() as u32;
+    }
+
+    pub(crate) fn draw(&self, pass: &mut wgpu::RenderPass<'_>) {
+        if self.count == 0 {
+            return;
+        }
+        pass.set_vertex_buffer(0, self.buffer.slice(..));
+        pass.draw(0..self.count, 0..1);
+    }
+}
+
+/// Alpha-blended, depthless overlay pipeline using `fragment_entry`.
+pub(crate) fn create_overlay_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    bind_group_layouts: &[&wgpu::BindGroupLayout],
+    surface_format: wgpu::TextureFormat,
+    fragment_entry: &'static str,
+    label: &'static str,
+) -> wgpu::RenderPipeline {
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some(label),
+        bind_group_layouts,
+        immediate_size: 0,
+    });
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            buffers: &[OverlayVertex::layout()],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some(fragment_entry),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: surface_format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
+/// Draws an [`Overlay`] over a surface: projection uniform plus the quad and
+/// text renderers.
+pub struct OverlayRenderer {
+    projection_buffer: wgpu::Buffer,
+    projection_bind_group: wgpu::BindGroup,
+    /// Rectangle renderer, drawn first.
+    pub quads: ScreenQuadRenderer,
+    /// Glyph renderer, drawn over the rectangles.
+    pub text: TextRenderer,
+}
+
+impl OverlayRenderer {
+    /// Build both pipelines for `surface_format` and upload the embedded font.
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        surface_format: wgpu::TextureFormat,
+    ) -> Result<Self, FontError> {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("overlay-shader"),
+            source: wgpu::ShaderSource::Wgsl(OVERLAY_SHADER_SOURCE.into()),
+        });
+        let projection_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("overlay-projection-bind-group-layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: NonZeroU64::new(64), // mat4x4
+                },
+                count: None,
+            }],
+        });
+        let projection_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("overlay-projection-buffer"),
+            contents: bytemuck::cast_slice(&pixel_projection(1.0, 1.0).to_cols_array()),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+        let projection_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("overlay-projection-bind-group"),
+            layout: &projection_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: projection_buffer.as_entire_binding(),
+            }],
+        });
+
+        let quads = ScreenQuadRenderer::new(device, &shader, &projection_layout, surface_format);
+        let font = BitmapFont::embedded()?;
+        let text = TextRenderer::new(
+            device,
+            queue,
+            &shader,
+            &projection_layout,
+            surface_format,
+            &font,
+        );
+        Ok(Self {
+            projection_buffer,
+            projection_bind_group,
+            quads,
+            text,
+        })
+    }
+
+    /// Upload this frame's projection and vertices. Call before
+    /// [`render`](Self::render), outside the render pass.
+    pub fn prepare(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, overlay: &Overlay) {
+        let (width, height) = overlay.screen_size();
+        queue.write_buffer(
+            &self.projection_buffer,
+            0,
+            bytemuck::cast_slice(&pixel_projection(width, height).to_cols_array()),
+        );
+        self.quads.upload(device, queue, overlay.quad_vertices());
+        self.text.upload(device, queue, overlay.text_vertices());
+    }
+
+    /// Draw the prepared overlay into a pass targeting the surface.
+    pub fn render(&self, pass: &mut wgpu::RenderPass<'_>) {
+        self.quads.render(pass, &self.projection_bind_group);
+        self.text.render(pass, &self.projection_bind_group);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::create_test_device_queue;
+
+    #[test]
+    fn test_pixel_projection_corners() {
+        let proj = pixel_projection(800.0, 600.0);
+        let top_left = proj.project_point3(glam::Vec3::ZERO);
+        let bottom_right = proj.project_point3(glam::Vec3::new(800.0, 600.0, 0.0));
+        assert!((top_left.truncate() - glam::Vec2::new(-1.0, 1.0)).length() < 1e-6);
+        assert!((bottom_right.truncate() - glam::Vec2::new(1.0, -1.0)).length() < 1e-6);
+        assert!((0.0..=1.0).contains(&top_left.z));
+    }
+
+    #[test]
+    fn test_overlay_draw_list() {
+        let mut overlay = Overlay::new(640.0, 480.0);
+        assert!(overlay.is_empty());
+        overlay.rect(10.0, 20.0, 30.0, 4.0, [1.0; 4]);
+        overlay.text(0.0, 0.0, 20.0, [1.0; 4], "A B");
+        assert_eq!(overlay.quad_vertices().len(), 6);
+        assert_eq!(overlay.text_vertices().len(), 12);
+        let xs = overlay.quad_vertices().iter().map(|v| v.position[0]);
+        let ys = overlay.quad_vertices().iter().map(|v| v.position[1]);
+        assert_eq!(xs.fold(f32::MIN, f32::max), 40.0);
+        assert_eq!(ys.fold(f32::MIN, f32::max), 24.0);
+
+        overlay.begin_frame(320.0, 240.0);
+        assert!(overlay.is_empty());
+        assert_eq!(overlay.screen_size(), (320.0, 240.0));
+    }
+
+    #[test]
+    fn test_headless_overlay_render() {
+        let Some((device, queue)) = create_test_device_queue() else {
+            eprintln!("skipping: no GPU adapter available");
+            return;
+        };
+        let format = wgpu::TextureFormat::Rgba8UnormSrgb;
+        let target = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("overlay-test-target"),
+            size: wgpu::Extent3d {
+                width: 320,
+                height: 240,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let scope = device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let mut renderer = OverlayRenderer::new(&device, &queue, format).expect("embedded font");
+        let mut overlay = Overlay::new(320.0, 240.0);
+        overlay.rect(150.0, 119.0, 20.0, 2.0, [0.0, 1.0, 0.0, 0.8]);
+        // Enough text to outgrow the initial vertex capacity.
+        for line in 0..12 {
+            overlay.text(
+                4.0,
+                4.0 + line as f32 * 18.0,
+                16.0,
+                [1.0; 4],
+                "SPD: 1,234 m/s | ALT: 402.3 km | HDG: 045°",
+            );
+        }
+        assert!(overlay.text_vertices().len() as u64 > INITIAL_VERTEX_CAPACITY);
+        renderer.prepare(&device, &queue, &overlay);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("overlay-test-encoder"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("overlay-test-pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    depth_slice: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            renderer.render(&mut pass);
+        }
+        queue.submit(Some(encoder.finish()));
+        let error = pollster::block_on(scope.pop());
+        assert!(error.is_none(), "validation error: {error:?}");
+    }
+}
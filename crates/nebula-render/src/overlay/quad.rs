@@ -0,0 +1,48 @@
+//! Flat-colored screen rectangles for HUD bars, panels, and reticles.
+
+use super::{OverlayVertex, VertexStream, create_overlay_pipeline};
+
+/// Draws the rectangles of an [`Overlay`](super::Overlay).
+pub struct ScreenQuadRenderer {
+    pipeline: wgpu::RenderPipeline,
+    vertices: VertexStream,
+}
+
+impl ScreenQuadRenderer {
+    /// Build the quad pipeline; group 0 is the pixel projection uniform.
+    pub fn new(
+        device: &wgpu::Device,
+        shader: &wgpu::ShaderModule,
+        projection_layout: &wgpu::BindGroupLayout,
+        surface_format: wgpu::TextureFormat,
+    ) -> Self {
+        Self {
+            pipeline: create_overlay_pipeline(
+                device,
+                shader,
+                &[projection_layout],
+                surface_format,
+                "fs_quad",
+                "overlay-quad-pipeline",
+            ),
+            vertices: VertexStream::new(device, "overlay-quad-vertices"),
+        }
+    }
+
+    /// Replace the rectangles drawn by [`render`](Self::render).
+    pub fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        vertices: &[OverlayVertex],
+    ) {
+        self.vertices.upload(device, queue, vertices);
+    }
+
+    /// Draw the uploaded rectangles.
+    pub fn render(&self, pass: &mut wgpu::RenderPass<'_>, projection: &wgpu::BindGroup) {
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, projection, &[]);
+        self.vertices.draw(pass);
+    }
+}
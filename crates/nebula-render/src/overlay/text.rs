@@ -0,0 +1,120 @@
+//! Bitmap-font text: glyph quads sampling a coverage atlas.
+
+use wgpu::util::DeviceExt;
+
+use super::{BitmapFont, OverlayVertex, VertexStream, create_overlay_pipeline};
+
+/// Draws the glyphs of an [`Overlay`](super::Overlay) from a font atlas.
+pub struct TextRenderer {
+    pipeline: wgpu::RenderPipeline,
+    atlas_bind_group: wgpu::BindGroup,
+    vertices: VertexStream,
+}
+
+impl TextRenderer {
+    /// Upload `font` as an `R8Unorm` atlas and build the text pipeline;
+    /// group 0 is the pixel projection uniform, group 1 the atlas.
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        shader: &wgpu::ShaderModule,
+        projection_layout: &wgpu::BindGroupLayout,
+        surface_format: wgpu::TextureFormat,
+        font: &BitmapFont,
+    ) -> Self {
+        let (width, height) = font.metrics.atlas_size();
+        let atlas = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("overlay-font-atlas"),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::R8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &font.pixels,
+        );
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("overlay-font-sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let atlas_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("overlay-font-bind-group-layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let atlas_view = atlas.create_view(&wgpu::TextureViewDescriptor::default());
+        let atlas_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("overlay-font-bind-group"),
+            layout: &atlas_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&atlas_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        Self {
+            pipeline: create_overlay_pipeline(
+                device,
+                shader,
+                &[projection_layout, &atlas_layout],
+                surface_format,
+                "fs_text",
+                "overlay-text-pipeline",
+            ),
+            atlas_bind_group,
+            vertices: VertexStream::new(device, "overlay-text-vertices"),
+        }
+    }
+
+    /// Replace the glyphs drawn by [`render`](Self::render).
+    pub fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        vertices: &[OverlayVertex],
+    ) {
+        self.vertices.upload(device, queue, vertices);
+    }
+
+    /// Draw the uploaded glyphs.
+    pub fn render(&self, pass: &mut wgpu::RenderPass<'_>, projection: &wgpu::BindGroup) {
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, projection, &[]);
+        pass.set_bind_group(1, &self.atlas_bind_group, &[]);
+        self.vertices.draw(pass);
+    }
+}
@@ -5,6 +5,8 @@
 
 use std::sync::Arc;
 
+use crate::transparency::TransparentRenderPass;
+
 /// Sky blue clear color - distinctive and visible when geometry is missing.
 pub const SKY_BLUE: wgpu::Color = wgpu::Color {
     r: 0.529,
@@ -240,6 +242,18 @@ impl FrameEncoder {
         )
     }
 
+    /// Begin a translucent pass over the surface after the opaque passes.
+    ///
+    /// `depth_view` is the opaque pass's depth buffer; it is bound read-only
+    /// so translucent quads are occluded by, but never write, depth.
+    pub fn begin_transparent_pass<'a>(
+        &'a mut self,
+        depth_view: &'a wgpu::TextureView,
+    ) -> TransparentRenderPass<'a> {
+        let (encoder, view) = self.encoder_and_view();
+        TransparentRenderPass::begin(encoder, view, depth_view)
+    }
+
     /// Returns a reference to the queue.
     pub fn queue(&self) -> &wgpu::Queue {
         &self.queue
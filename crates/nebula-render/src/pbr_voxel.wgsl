@@ -6,7 +6,9 @@ const PI: f32 = 3.14159265359;
 
 struct CameraUniform {
     view_proj: mat4x4<f32>,
-    camera_pos: vec4<f32>,
+    camera_pos: vec3<f32>,
+    // Animation clock in seconds; scrolls each material by its uv_scroll.
+    time_offset: f32,
 };
 
 @group(0) @binding(0)
@@ -33,6 +35,8 @@ struct MaterialGpuData {
     roughness: f32,
     normal_strength: f32,
     opacity: f32,
+    uv_scroll: vec2<f32>,
+    _padding: vec2<f32>,
 };
 
 struct AnimationGpuData {
@@ -171,12 +175,16 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     var tex_a: vec4<f32>;
     var tex_b: vec4<f32>;
 
+    // Flipbook frame offset plus continuous scrolling (liquids).
+    let offset_a = anim_a.uv_offset + mat_a.uv_scroll * camera.time_offset;
+    let offset_b = anim_b.uv_offset + mat_b.uv_scroll * camera.time_offset;
+
     if use_triplanar {
-        tex_a = triplanar_sample(in.world_pos, n, anim_a.uv_offset, 1.0);
-        tex_b = triplanar_sample(in.world_pos, n, anim_b.uv_offset, 1.0);
+        tex_a = triplanar_sample(in.world_pos, n, offset_a, 1.0);
+        tex_b = triplanar_sample(in.world_pos, n, offset_b, 1.0);
     } else {
-        let animated_uv_a = in.uv + anim_a.uv_offset;
-        let animated_uv_b = in.uv + anim_b.uv_offset;
+        let animated_uv_a = in.uv + offset_a;
+        let animated_uv_b = in.uv + offset_b;
         tex_a = textureSample(atlas_texture, atlas_sampler, animated_uv_a);
         tex_b = textureSample(atlas_texture, atlas_sampler, animated_uv_b);
     }
@@ -191,7 +199,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let emissive = mix(emissive_a, emissive_b, w);
     let opacity = mix(mat_a.opacity, mat_b.opacity, w);
 
-    let v = normalize(camera.camera_pos.xyz - in.world_pos);
+    let v = normalize(camera.camera_pos - in.world_pos);
     let l = normalize(-light.sun_direction.xyz);
     let h = normalize(v + l);
 
@@ -10,7 +10,8 @@ use bytemuck::{Pod, Zeroable};
 
 use crate::buffer::{MeshBuffer, VoxelVertex};
 
-/// Camera uniform: view-projection matrix and world-space position.
+/// Camera uniform: view-projection matrix, world-space position, and the
+/// animation clock.
 ///
 /// Bound at group 0, binding 0. Visible to vertex and fragment stages.
 #[repr(C)]
@@ -18,8 +19,11 @@ use crate::buffer::{MeshBuffer, VoxelVertex};
 pub struct PbrCameraUniform {
     /// View-projection matrix (64 bytes).
     pub view_proj: [[f32; 4]; 4],
-    /// Camera position (xyz), w unused.
-    pub camera_pos: [f32; 4],
+    /// Camera position.
+    pub camera_pos: [f32; 3],
+    /// Seconds of animation time. Materials scroll their UVs by
+    /// `uv_scroll * time_offset`; wrap it to keep precision in long sessions.
+    pub time_offset: f32,
 }
 
 /// Directional sun light uniform for PBR shading.
@@ -132,7 +136,7 @@ impl PbrVoxelPipeline {
                         ty: wgpu::BindingType::Buffer {
                             ty: wgpu::BufferBindingType::Storage { read_only: true },
                             has_dynamic_offset: false,
-                            min_binding_size: NonZeroU64::new(48), // one MaterialGpuData
+                            min_binding_size: NonZeroU64::new(64), // one MaterialGpuData
                         },
                         count: None,
                     },
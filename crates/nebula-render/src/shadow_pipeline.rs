@@ -5,7 +5,11 @@
 
 use std::num::NonZeroU64;
 
+use glam::{Mat4, Vec3};
+use nebula_lighting::{CascadedShadowConfig, WorldAabb, fit_cascade_to_scene};
+
 use crate::buffer::VertexPositionColor;
+use crate::camera::{Camera, Projection};
 
 /// WGSL shader source for shadow depth-only rendering.
 ///
@@ -134,3 +138,113 @@ pub fn render_shadow_cascades(
         mesh.draw(&mut pass);
     }
 }
+
+/// Light-space matrix for one cascade, fitted tightly to the part of the
+/// camera's cascade slice that overlaps `scene_aabb`.
+///
+/// Slices follow [`CascadedShadowConfig::split_range`], so they use the
+/// config's cascade count, split lambda and maximum distance; see
+/// [`fit_cascade_to_scene`] for how receivers and casters are bounded.
+pub fn compute_cascade_matrix_tight(
+    camera: &Camera,
+    light_dir: Vec3,
+    scene_aabb: &WorldAabb,
+    config: &CascadedShadowConfig,
+    cascade_index: usize,
+) -> Mat4 {
+    let (slice_near, slice_far) = config.split_range(camera.near, camera.far, cascade_index);
+    let proj = match &camera.projection {
+        Projection::Perspective {
+            fov_y,
+            aspect_ratio,
+        } => Mat4::perspective_rh(*fov_y, *aspect_ratio, slice_far, slice_near),
+        Projection::Orthographic {
+            half_width,
+            half_height,
+        } => Mat4::orthographic_rh(
+            -*half_width,
+            *half_width,
+            -*half_height,
+            *half_height,
+            slice_far,
+            slice_near,
+        ),
+    };
+    fit_cascade_to_scene(
+        light_dir,
+        (proj * camera.view_matrix()).inverse(),
+        scene_aabb,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Quat, Vec4};
+    use nebula_lighting::compute_cascade_matrix_tight_from_camera;
+
+    use super::*;
+
+    fn camera() -> Camera {
+        Camera {
+            position: Vec3::new(0.0, 10.0, 0.0),
+            rotation: Quat::from_rotation_x(-0.2),
+            near: 0.1,
+            far: 500.0,
+            ..Camera::default()
+        }
+    }
+
+    fn terrain() -> WorldAabb {
+        WorldAabb::new(
+            Vec3::new(-1000.0, -2.0, -1000.0),
+            Vec3::new(1000.0, 4.0, 1000.0),
+        )
+    }
+
+    #[test]
+    fn test_tight_matrix_matches_camera_agnostic_fit() {
+        let cam = camera();
+        let sun = Vec3::new(0.3, -1.0, 0.2).normalize();
+        let Projection::Perspective {
+            fov_y,
+            aspect_ratio,
+        } = cam.projection
+        else {
+            unreachable!("default camera is perspective");
+        };
+        let config = CascadedShadowConfig {
+            split_lambda: 0.4,
+            ..Default::default()
+        };
+        for i in 0..4 {
+            let from_camera = compute_cascade_matrix_tight(&cam, sun, &terrain(), &config, i);
+            let (near, far) = config.split_range(cam.near, cam.far, i);
+            let expected = compute_cascade_matrix_tight_from_camera(
+                sun,
+                cam.view_matrix(),
+                fov_y,
+                aspect_ratio,
+                near,
+                far,
+                &terrain(),
+            );
+            assert!(from_camera.abs_diff_eq(expected, 1e-3), "cascade {i}");
+        }
+    }
+
+    #[test]
+    fn test_ground_in_front_of_camera_maps_into_first_cascade() {
+        let cam = camera();
+        let sun = Vec3::new(0.3, -1.0, 0.2).normalize();
+        let config = CascadedShadowConfig::default();
+        let m = compute_cascade_matrix_tight(&cam, sun, &terrain(), &config, 0);
+        let (_, far) = config.split_range(cam.near, cam.far, 0);
+        // A ground point well inside the first slice, straight ahead.
+        let ground = cam.position + cam.forward() * (far * 0.5);
+        let ground = Vec3::new(ground.x, 0.0, ground.z);
+        let ndc = m * Vec4::new(ground.x, ground.y, ground.z, 1.0);
+        let ndc = ndc.truncate() / ndc.w;
+        assert!(ndc.x.abs() <= 1.0 && ndc.y.abs() <= 1.0, "{ndc:?}");
+        assert!((0.0..=1.0).contains(&ndc.z), "{ndc:?}");
+    }
+}
@@ -2,6 +2,13 @@
 //!
 //! Handles Wayland zero-size windows, macOS Retina scaling, and Windows DPI
 //! changes by providing a consistent API for surface dimensions.
+//!
+//! Also enumerates connected displays and places the window on a chosen one,
+//! either windowed ([`SurfaceWrapper::move_to_display`]) or borderless
+//! fullscreen ([`SurfaceWrapper::fullscreen_on_display`]).
+
+use winit::monitor::MonitorHandle;
+use winit::window::{Fullscreen, Window};
 
 /// Minimum surface dimension (prevents zero-size panics).
 pub const MIN_SURFACE_DIMENSION: u32 = 1;
@@ -15,6 +22,56 @@ pub struct PhysicalSize {
     pub height: u32,
 }
 
+/// A connected display as reported by the windowing system.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisplayInfo {
+    /// Human-readable monitor name, empty if the platform does not report one.
+    pub name: String,
+    /// Current video mode resolution in physical pixels.
+    pub size: PhysicalSize,
+    /// Current refresh rate in hertz, 0.0 if unknown.
+    pub refresh_hz: f32,
+    /// Whether this is the platform's primary display.
+    pub is_primary: bool,
+}
+
+impl DisplayInfo {
+    fn from_monitor(monitor: &MonitorHandle, primary: Option<&MonitorHandle>) -> Self {
+        let size = monitor.size();
+        Self {
+            name: monitor.name().unwrap_or_default(),
+            size: PhysicalSize {
+                width: size.width,
+                height: size.height,
+            },
+            refresh_hz: monitor
+                .refresh_rate_millihertz()
+                .map_or(0.0, |mhz| mhz as f32 / 1000.0),
+            is_primary: primary == Some(monitor),
+        }
+    }
+}
+
+/// List the displays connected to the system `window` runs on, in the
+/// platform's enumeration order. Indices into this list are the display
+/// indices accepted by [`SurfaceWrapper::move_to_display`] and
+/// [`SurfaceWrapper::fullscreen_on_display`].
+///
+/// winit only exposes monitors through a live window or event loop, hence the
+/// `window` parameter.
+pub fn enumerate_displays(window: &Window) -> Vec<DisplayInfo> {
+    let primary = window.primary_monitor();
+    window
+        .available_monitors()
+        .map(|monitor| DisplayInfo::from_monitor(&monitor, primary.as_ref()))
+        .collect()
+}
+
+/// The monitor at `display_index` in [`enumerate_displays`] order.
+fn monitor_at(window: &Window, display_index: usize) -> Option<MonitorHandle> {
+    window.available_monitors().nth(display_index)
+}
+
 /// Event produced when the surface dimensions or scale factor change.
 #[derive(Clone, Copy, Debug)]
 pub struct SurfaceResizeEvent {
@@ -155,6 +212,50 @@ impl SurfaceWrapper {
     pub fn is_configured(&self) -> bool {
         self.configured
     }
+
+    /// Move `window` to the display at `display_index`, centered on it, and
+    /// adopt that display's scale factor.
+    ///
+    /// Leaves fullscreen first so the window can be positioned. Returns
+    /// `false` and does nothing if no display has that index.
+    pub fn move_to_display(&mut self, window: &Window, display_index: usize) -> bool {
+        let Some(monitor) = monitor_at(window, display_index) else {
+            log::warn!("No display at index {display_index}, window not moved");
+            return false;
+        };
+        window.set_fullscreen(None);
+
+        let origin = monitor.position();
+        let area = monitor.size();
+        let outer = window.outer_size();
+        let x = origin.x + (area.width.saturating_sub(outer.width) / 2) as i32;
+        let y = origin.y + (area.height.saturating_sub(outer.height) / 2) as i32;
+        window.set_outer_position(winit::dpi::PhysicalPosition::new(x, y));
+
+        let scale_factor = monitor.scale_factor();
+        if scale_factor != self.scale_factor {
+            // Keep the logical size, as the compositor will when it reports
+            // the new scale factor.
+            let ratio = scale_factor / self.scale_factor;
+            let width = (self.physical_width as f64 * ratio).round() as u32;
+            let height = (self.physical_height as f64 * ratio).round() as u32;
+            self.handle_scale_factor_changed(scale_factor, width, height);
+        }
+        true
+    }
+
+    /// Make `window` borderless fullscreen on the display at `display_index`.
+    ///
+    /// The resulting size arrives through the window's regular resize event.
+    /// Returns `false` and does nothing if no display has that index.
+    pub fn fullscreen_on_display(window: &Window, display_index: usize) -> bool {
+        let Some(monitor) = monitor_at(window, display_index) else {
+            log::warn!("No display at index {display_index}, fullscreen not entered");
+            return false;
+        };
+        window.set_fullscreen(Some(Fullscreen::Borderless(Some(monitor))));
+        true
+    }
 }
 
 #[cfg(test)]
@@ -327,6 +428,44 @@ mod tests {
         assert_eq!(wrapper.scale_factor(), 1.5);
     }
 
+    /// Needs a real display; build with `--cfg headless` to skip.
+    #[cfg(all(not(headless), target_os = "linux"))]
+    #[test]
+    fn test_enumerate_displays_matches_winit() {
+        use winit::event_loop::EventLoop;
+        use winit::platform::x11::EventLoopBuilderExtX11;
+
+        let Ok(event_loop) = EventLoop::builder().with_any_thread(true).build() else {
+            eprintln!("no display connection, skipping");
+            return;
+        };
+        #[allow(deprecated)]
+        let Ok(window) = event_loop.create_window(Window::default_attributes().with_visible(false))
+        else {
+            eprintln!("window creation failed, skipping");
+            return;
+        };
+
+        let displays = enumerate_displays(&window);
+        let monitors: Vec<_> = window.available_monitors().collect();
+        assert!(!displays.is_empty());
+        assert_eq!(displays.len(), monitors.len());
+        for (display, monitor) in displays.iter().zip(&monitors) {
+            let size = monitor.size();
+            assert_eq!(display.size.width, size.width);
+            assert_eq!(display.size.height, size.height);
+        }
+        assert!(displays.iter().filter(|d| d.is_primary).count() <= 1);
+
+        let mut wrapper = SurfaceWrapper::new(800, 600, window.scale_factor());
+        assert!(wrapper.move_to_display(&window, 0));
+        assert!(!wrapper.move_to_display(&window, displays.len()));
+        assert!(!SurfaceWrapper::fullscreen_on_display(
+            &window,
+            displays.len()
+        ));
+    }
+
     #[test]
     fn test_new_with_valid_dimensions() {
         let wrapper = SurfaceWrapper::new(1920, 1080, 2.0);
@@ -3,10 +3,22 @@
 //! Provides [`TextureManager`] which handles the full lifecycle of GPU textures.
 //! Downstream systems call `create_texture()` once and receive an
 //! [`Arc<ManagedTexture>`] with a ready-to-bind [`wgpu::BindGroup`].
+//!
+//! The manager keeps each texture's pixel data on the CPU, so GPU memory can
+//! be reclaimed. Systems that need a texture to stay resident hold on to the
+//! returned `Arc` or a [`TextureGuard`] from [`TextureManager::acquire`].
+//! Textures nothing else references are evicted least recently used first,
+//! either explicitly through [`TextureManager::evict_unreferenced`] or
+//! automatically when a budget set with [`TextureManager::new_with_budget`]
+//! is exceeded. Acquiring an evicted texture uploads it again from its
+//! source data. Residency bookkeeping lives in [`crate::texture_cache`].
 
-use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::texture_cache::{TextureCache, TextureSource};
+pub use crate::texture_cache::{TextureGuard, TextureId};
+use crate::texture_upload::TextureUploader;
+
 /// A GPU texture with its view, bind group, and metadata.
 pub struct ManagedTexture {
     /// The underlying GPU texture.
@@ -21,6 +33,23 @@ pub struct ManagedTexture {
     pub format: wgpu::TextureFormat,
     /// Number of mip levels (1 if mipmaps were not generated).
     pub mip_level_count: u32,
+    /// Number of array layers (1 for a plain 2D texture).
+    pub layer_count: u32,
+}
+
+impl ManagedTexture {
+    /// GPU memory used by every mip level of every layer, in bytes.
+    pub fn size_bytes(&self) -> u64 {
+        let (width, height) = self.dimensions;
+        let per_layer: u64 = (0..self.mip_level_count)
+            .map(|level| {
+                let w = (width >> level).max(1);
+                let h = (height >> level).max(1);
+                expected_byte_size(w, h, self.format) as u64
+            })
+            .sum();
+        per_layer * u64::from(self.layer_count)
+    }
 }
 
 /// Per-layer data for texture array creation.
@@ -53,6 +82,10 @@ pub enum TextureError {
     /// Texture array layers have inconsistent data sizes.
     #[error("texture array layers have inconsistent dimensions")]
     InconsistentLayerDimensions,
+
+    /// No texture with this handle is registered (it was removed).
+    #[error("unknown texture {0:?}")]
+    UnknownTexture(TextureId),
 }
 
 /// Calculates the number of mip levels for the given dimensions.
@@ -62,45 +95,30 @@ pub fn mip_level_count(width: u32, height: u32) -> u32 {
 
 /// Centralized GPU texture manager with caching, mipmap generation, and bind groups.
 pub struct TextureManager {
-    textures: HashMap<String, Arc<ManagedTexture>>,
+    cache: TextureCache,
     sampler_linear: wgpu::Sampler,
     sampler_nearest: wgpu::Sampler,
     bind_group_layout: wgpu::BindGroupLayout,
-    blit_shader: wgpu::ShaderModule,
-    blit_pipeline_layout: wgpu::PipelineLayout,
-    blit_bind_group_layout: wgpu::BindGroupLayout,
-    blit_sampler: wgpu::Sampler,
+    uploader: TextureUploader,
 }
 
-/// WGSL shader for mipmap generation via fullscreen blit.
-const BLIT_SHADER_SOURCE: &str = r#"
-@group(0) @binding(0) var src_texture: texture_2d<f32>;
-@group(0) @binding(1) var src_sampler: sampler;
-
-struct VertexOutput {
-    @builtin(position) position: vec4<f32>,
-    @location(0) uv: vec2<f32>,
-};
-
-@vertex
-fn vs_main(@builtin(vertex_index) idx: u32) -> VertexOutput {
-    // Full-screen triangle
-    let uv = vec2<f32>(f32((idx << 1u) & 2u), f32(idx & 2u));
-    var out: VertexOutput;
-    out.position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
-    out.uv = vec2<f32>(uv.x, 1.0 - uv.y);
-    return out;
-}
-
-@fragment
-fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(src_texture, src_sampler, in.uv);
-}
-"#;
-
 impl TextureManager {
     /// Create a new texture manager with shared samplers and bind group layout.
+    ///
+    /// The number of resident textures is unbounded; see
+    /// [`new_with_budget`](Self::new_with_budget).
     pub fn new(device: &wgpu::Device) -> Self {
+        Self::new_with_budget(device, usize::MAX)
+    }
+
+    /// Create a texture manager that keeps at most `max_textures` textures
+    /// resident.
+    ///
+    /// Whenever an upload pushes the count past the budget, unreferenced
+    /// textures are evicted least recently used first. Textures still
+    /// referenced by a [`TextureGuard`] or a returned `Arc` are never
+    /// evicted, so the budget can be exceeded while they are in use.
+    pub fn new_with_budget(device: &wgpu::Device, max_textures: usize) -> Self {
         let sampler_linear = device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("sampler-linear"),
             mag_filter: wgpu::FilterMode::Linear,
@@ -139,60 +157,19 @@ impl TextureManager {
             ],
         });
 
-        let blit_bind_group_layout =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("blit-bind-group-layout"),
-                entries: &[
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Texture {
-                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
-                            view_dimension: wgpu::TextureViewDimension::D2,
-                            multisampled: false,
-                        },
-                        count: None,
-                    },
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 1,
-                        visibility: wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-                        count: None,
-                    },
-                ],
-            });
-
-        let blit_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("blit-shader"),
-            source: wgpu::ShaderSource::Wgsl(BLIT_SHADER_SOURCE.into()),
-        });
-
-        let blit_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("blit-pipeline-layout"),
-            bind_group_layouts: &[&blit_bind_group_layout],
-            immediate_size: 0,
-        });
-
-        let blit_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("blit-sampler"),
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            ..Default::default()
-        });
-
         Self {
-            textures: HashMap::new(),
+            cache: TextureCache::new(max_textures),
             sampler_linear,
             sampler_nearest,
             bind_group_layout,
-            blit_shader,
-            blit_pipeline_layout,
-            blit_bind_group_layout,
-            blit_sampler,
+            uploader: TextureUploader::new(device),
         }
     }
 
     /// Create a 2D texture from raw pixel data.
+    ///
+    /// A copy of `data` is kept so the texture can be uploaded again after
+    /// eviction.
     #[allow(clippy::too_many_arguments)]
     pub fn create_texture(
         &mut self,
@@ -206,92 +183,28 @@ impl TextureManager {
         generate_mipmaps: bool,
     ) -> Result<Arc<ManagedTexture>, TextureError> {
         // Check cache first
-        if let Some(existing) = self.textures.get(name) {
-            return Ok(Arc::clone(existing));
+        if let Some(existing) = self.touch(device, queue, name) {
+            return Ok(existing);
         }
 
         validate_dimensions(width, height)?;
         validate_data_size(data, width, height, format)?;
 
-        let mip_levels = if generate_mipmaps {
-            mip_level_count(width, height)
-        } else {
-            1
-        };
-
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some(name),
-            size: wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: mip_levels,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING
-                | wgpu::TextureUsages::COPY_DST
-                | wgpu::TextureUsages::RENDER_ATTACHMENT,
-            view_formats: &[],
-        });
-
-        queue.write_texture(
-            wgpu::TexelCopyTextureInfo {
-                texture: &texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-                aspect: wgpu::TextureAspect::All,
-            },
-            data,
-            wgpu::TexelCopyBufferLayout {
-                offset: 0,
-                bytes_per_row: Some(bytes_per_row(width, format)),
-                rows_per_image: None,
-            },
-            wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: 1,
-            },
-        );
-
-        if generate_mipmaps && mip_levels > 1 {
-            self.generate_mipmaps(device, queue, &texture, format, mip_levels);
-        }
-
-        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some(&format!("{name}-bind-group")),
-            layout: &self.bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&self.sampler_linear),
-                },
-            ],
-        });
-
-        let managed = Arc::new(ManagedTexture {
-            texture,
-            view,
-            bind_group,
-            dimensions: (width, height),
+        let source = TextureSource {
+            layers: vec![data.to_vec()],
+            width,
+            height,
             format,
-            mip_level_count: mip_levels,
-        });
-
-        self.textures.insert(name.to_string(), Arc::clone(&managed));
-        log::info!("Created texture '{name}' ({width}x{height}, {mip_levels} mips)");
-        Ok(managed)
+            generate_mipmaps,
+            array: false,
+        };
+        Ok(self.register(device, queue, name, source))
     }
 
     /// Create a 2D texture array (for voxel block faces, terrain layers, etc.).
+    ///
+    /// A copy of every layer is kept so the texture can be uploaded again
+    /// after eviction.
     #[allow(clippy::too_many_arguments)]
     pub fn create_texture_array(
         &mut self,
@@ -304,8 +217,8 @@ impl TextureManager {
         format: wgpu::TextureFormat,
         generate_mipmaps: bool,
     ) -> Result<Arc<ManagedTexture>, TextureError> {
-        if let Some(existing) = self.textures.get(name) {
-            return Ok(Arc::clone(existing));
+        if let Some(existing) = self.touch(device, queue, name) {
+            return Ok(existing);
         }
 
         validate_dimensions(width, height)?;
@@ -317,98 +230,138 @@ impl TextureManager {
             }
         }
 
-        let layer_count = layers.len() as u32;
-        let mip_levels = if generate_mipmaps {
-            mip_level_count(width, height)
-        } else {
-            1
+        let source = TextureSource {
+            layers: layers.iter().map(|layer| layer.data.to_vec()).collect(),
+            width,
+            height,
+            format,
+            generate_mipmaps,
+            array: true,
         };
+        Ok(self.register(device, queue, name, source))
+    }
 
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some(name),
-            size: wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: layer_count,
-            },
-            mip_level_count: mip_levels,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING
-                | wgpu::TextureUsages::COPY_DST
-                | wgpu::TextureUsages::RENDER_ATTACHMENT,
-            view_formats: &[],
-        });
+    /// Keep a texture resident until the returned guard is dropped,
+    /// uploading it again from its source data if it was evicted.
+    pub fn acquire(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        id: TextureId,
+    ) -> Result<TextureGuard, TextureError> {
+        let name = self
+            .cache
+            .name(id)
+            .map(str::to_string)
+            .ok_or(TextureError::UnknownTexture(id))?;
+        let texture = self
+            .touch(device, queue, &name)
+            .ok_or(TextureError::UnknownTexture(id))?;
+        Ok(TextureGuard::new(id, texture))
+    }
 
-        let bpr = bytes_per_row(width, format);
-        for (i, layer) in layers.iter().enumerate() {
-            queue.write_texture(
-                wgpu::TexelCopyTextureInfo {
-                    texture: &texture,
-                    mip_level: 0,
-                    origin: wgpu::Origin3d {
-                        x: 0,
-                        y: 0,
-                        z: i as u32,
-                    },
-                    aspect: wgpu::TextureAspect::All,
-                },
-                layer.data,
-                wgpu::TexelCopyBufferLayout {
-                    offset: 0,
-                    bytes_per_row: Some(bpr),
-                    rows_per_image: Some(height),
-                },
-                wgpu::Extent3d {
-                    width,
-                    height,
-                    depth_or_array_layers: 1,
-                },
-            );
-        }
+    /// Evict every resident texture that nothing outside the manager holds,
+    /// least recently used first. Returns the number of textures evicted.
+    ///
+    /// Evicted textures keep their [`TextureId`] and source data;
+    /// [`acquire`](Self::acquire) uploads them again.
+    pub fn evict_unreferenced(&mut self) -> usize {
+        self.cache.evict_unreferenced()
+    }
 
-        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    /// Total GPU memory of all resident textures, in bytes.
+    pub fn memory_usage_bytes(&self) -> u64 {
+        self.cache.memory_usage_bytes()
+    }
 
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some(&format!("{name}-bind-group")),
-            layout: &self.bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&self.sampler_linear),
-                },
-            ],
-        });
+    /// Handle of the texture registered under `name`.
+    pub fn texture_id(&self, name: &str) -> Option<TextureId> {
+        self.cache.entry(name).map(|entry| entry.id)
+    }
 
-        let managed = Arc::new(ManagedTexture {
-            texture,
-            view,
-            bind_group,
-            dimensions: (width, height),
-            format,
-            mip_level_count: mip_levels,
-        });
+    /// Whether the texture is currently uploaded to the GPU.
+    pub fn is_resident(&self, id: TextureId) -> bool {
+        self.cache
+            .entry_by_id(id)
+            .is_some_and(|entry| entry.texture.is_some())
+    }
+
+    /// Number of live references to the texture: [`TextureGuard`]s and
+    /// `Arc`s returned by [`create_texture`](Self::create_texture) or
+    /// [`get`](Self::get).
+    pub fn ref_count(&self, id: TextureId) -> usize {
+        self.cache.ref_count(id)
+    }
 
-        self.textures.insert(name.to_string(), Arc::clone(&managed));
-        log::info!(
-            "Created texture array '{name}' ({width}x{height}, {layer_count} layers, {mip_levels} mips)"
-        );
-        Ok(managed)
+    /// Number of textures currently uploaded to the GPU.
+    pub fn resident_count(&self) -> usize {
+        self.cache.resident_count()
     }
 
-    /// Get a previously created texture by name.
+    /// Get a previously created texture by name, if it is resident.
+    ///
+    /// The returned `Arc` keeps the texture resident while it is held.
     pub fn get(&self, name: &str) -> Option<Arc<ManagedTexture>> {
-        self.textures.get(name).cloned()
+        self.cache.entry(name)?.texture.clone()
     }
 
-    /// Remove a texture from the cache. Returns `true` if it existed.
+    /// Remove a texture and its source data. Returns `true` if it existed.
+    ///
+    /// Outstanding references keep the GPU texture alive until they are
+    /// dropped.
     pub fn remove(&mut self, name: &str) -> bool {
-        self.textures.remove(name).is_some()
+        self.cache.remove(name)
+    }
+
+    /// Upload a new texture and register it under `name`.
+    fn register(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        name: &str,
+        source: TextureSource,
+    ) -> Arc<ManagedTexture> {
+        let texture = Arc::new(self.upload(device, queue, name, &source));
+        self.cache.insert(name, source, Arc::clone(&texture));
+        texture
+    }
+
+    /// Mark `name` as used, uploading it again if it was evicted. Returns
+    /// `None` if no texture is registered under `name`.
+    fn touch(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        name: &str,
+    ) -> Option<Arc<ManagedTexture>> {
+        let entry = self.cache.entry(name)?;
+        let texture = match &entry.texture {
+            Some(texture) => Arc::clone(texture),
+            None => {
+                log::info!("Re-uploading evicted texture '{name}'");
+                Arc::new(self.upload(device, queue, name, &entry.source))
+            }
+        };
+        self.cache.mark_used(name, Arc::clone(&texture));
+        Some(texture)
+    }
+
+    /// Create the GPU texture, view, and bind group for `source`.
+    fn upload(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        name: &str,
+        source: &TextureSource,
+    ) -> ManagedTexture {
+        self.uploader.upload(
+            device,
+            queue,
+            name,
+            source,
+            &self.bind_group_layout,
+            &self.sampler_linear,
+        )
     }
 
     /// The shared bind group layout for texture + sampler pairs.
@@ -425,113 +378,14 @@ impl TextureManager {
     pub fn sampler_linear(&self) -> &wgpu::Sampler {
         &self.sampler_linear
     }
-
-    /// Generate mipmaps for a texture using render passes.
-    fn generate_mipmaps(
-        &self,
-        device: &wgpu::Device,
-        queue: &wgpu::Queue,
-        texture: &wgpu::Texture,
-        format: wgpu::TextureFormat,
-        mip_count: u32,
-    ) {
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("mipmap-pipeline"),
-            layout: Some(&self.blit_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &self.blit_shader,
-                entry_point: Some("vs_main"),
-                buffers: &[],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            fragment: Some(wgpu::FragmentState {
-                module: &self.blit_shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format,
-                    blend: None,
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            multiview_mask: None,
-            cache: None,
-        });
-
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("mipmap-encoder"),
-        });
-
-        for level in 1..mip_count {
-            let src_view = texture.create_view(&wgpu::TextureViewDescriptor {
-                base_mip_level: level - 1,
-                mip_level_count: Some(1),
-                ..Default::default()
-            });
-
-            let dst_view = texture.create_view(&wgpu::TextureViewDescriptor {
-                base_mip_level: level,
-                mip_level_count: Some(1),
-                ..Default::default()
-            });
-
-            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("mipmap-bind-group"),
-                layout: &self.blit_bind_group_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: wgpu::BindingResource::TextureView(&src_view),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: wgpu::BindingResource::Sampler(&self.blit_sampler),
-                    },
-                ],
-            });
-
-            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("mipmap-pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &dst_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                        store: wgpu::StoreOp::Store,
-                    },
-                    depth_slice: None,
-                })],
-                depth_stencil_attachment: None,
-                ..Default::default()
-            });
-
-            pass.set_pipeline(&pipeline);
-            pass.set_bind_group(0, &bind_group, &[]);
-            pass.draw(0..3, 0..1);
-        }
-
-        queue.submit(std::iter::once(encoder.finish()));
-    }
 }
 
 /// Calculate the expected byte size for a texture.
-fn expected_byte_size(width: u32, height: u32, format: wgpu::TextureFormat) -> usize {
+pub(crate) fn expected_byte_size(width: u32, height: u32, format: wgpu::TextureFormat) -> usize {
     let bpp = format.block_copy_size(None).unwrap_or(4) as usize;
     width as usize * height as usize * bpp
 }
 
-/// Calculate bytes per row for a texture.
-fn bytes_per_row(width: u32, format: wgpu::TextureFormat) -> u32 {
-    let bpp = format.block_copy_size(None).unwrap_or(4);
-    width * bpp
-}
-
 /// Validate that dimensions are non-zero.
 fn validate_dimensions(width: u32, height: u32) -> Result<(), TextureError> {
     if width == 0 || height == 0 {
@@ -593,215 +447,5 @@ pub(crate) fn create_test_device_queue() -> Option<(wgpu::Device, wgpu::Queue)>
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_create_texture_with_valid_dimensions() {
-        let Some((device, queue)) = create_test_device_queue() else {
-            return;
-        };
-        let mut manager = TextureManager::new(&device);
-
-        let data = vec![255u8; 64]; // 4x4 RGBA8
-        let result = manager.create_texture(
-            &device,
-            &queue,
-            "test-4x4",
-            &data,
-            4,
-            4,
-            wgpu::TextureFormat::Rgba8UnormSrgb,
-            false,
-        );
-        assert!(result.is_ok());
-        let tex = result.unwrap();
-        assert_eq!(tex.dimensions, (4, 4));
-    }
-
-    #[test]
-    fn test_mipmap_level_count_calculation() {
-        assert_eq!(mip_level_count(1, 1), 1);
-        assert_eq!(mip_level_count(2, 2), 2);
-        assert_eq!(mip_level_count(4, 4), 3);
-        assert_eq!(mip_level_count(256, 256), 9);
-        assert_eq!(mip_level_count(512, 256), 10);
-        assert_eq!(mip_level_count(1024, 1024), 11);
-    }
-
-    #[test]
-    fn test_bind_group_creation_succeeds() {
-        let Some((device, queue)) = create_test_device_queue() else {
-            return;
-        };
-        let mut manager = TextureManager::new(&device);
-
-        let data = vec![128u8; 16]; // 2x2 RGBA8
-        let tex = manager
-            .create_texture(
-                &device,
-                &queue,
-                "test-bind",
-                &data,
-                2,
-                2,
-                wgpu::TextureFormat::Rgba8UnormSrgb,
-                false,
-            )
-            .unwrap();
-
-        let _bg = &tex.bind_group;
-    }
-
-    #[test]
-    fn test_texture_cache_deduplicates() {
-        let Some((device, queue)) = create_test_device_queue() else {
-            return;
-        };
-        let mut manager = TextureManager::new(&device);
-
-        let data = vec![255u8; 16]; // 2x2 RGBA8
-        let tex1 = manager
-            .create_texture(
-                &device,
-                &queue,
-                "shared",
-                &data,
-                2,
-                2,
-                wgpu::TextureFormat::Rgba8UnormSrgb,
-                false,
-            )
-            .unwrap();
-
-        let tex2 = manager
-            .create_texture(
-                &device,
-                &queue,
-                "shared",
-                &data,
-                2,
-                2,
-                wgpu::TextureFormat::Rgba8UnormSrgb,
-                false,
-            )
-            .unwrap();
-
-        assert!(Arc::ptr_eq(&tex1, &tex2));
-    }
-
-    #[test]
-    fn test_rgba8_format_handling() {
-        let Some((device, queue)) = create_test_device_queue() else {
-            return;
-        };
-        let mut manager = TextureManager::new(&device);
-
-        let data = vec![0u8; 256]; // 8x8 RGBA8
-        let tex = manager
-            .create_texture(
-                &device,
-                &queue,
-                "rgba8-test",
-                &data,
-                8,
-                8,
-                wgpu::TextureFormat::Rgba8UnormSrgb,
-                false,
-            )
-            .unwrap();
-
-        assert_eq!(tex.format, wgpu::TextureFormat::Rgba8UnormSrgb);
-    }
-
-    #[test]
-    fn test_zero_dimensions_returns_error() {
-        let Some((device, queue)) = create_test_device_queue() else {
-            return;
-        };
-        let mut manager = TextureManager::new(&device);
-
-        let result = manager.create_texture(
-            &device,
-            &queue,
-            "zero",
-            &[],
-            0,
-            0,
-            wgpu::TextureFormat::Rgba8UnormSrgb,
-            false,
-        );
-        assert!(matches!(result, Err(TextureError::ZeroDimensions { .. })));
-    }
-
-    #[test]
-    fn test_data_size_mismatch_returns_error() {
-        let Some((device, queue)) = create_test_device_queue() else {
-            return;
-        };
-        let mut manager = TextureManager::new(&device);
-
-        let data = vec![0u8; 32]; // 4x4 expects 64
-        let result = manager.create_texture(
-            &device,
-            &queue,
-            "mismatch",
-            &data,
-            4,
-            4,
-            wgpu::TextureFormat::Rgba8UnormSrgb,
-            false,
-        );
-        assert!(matches!(result, Err(TextureError::DataSizeMismatch { .. })));
-    }
-
-    #[test]
-    fn test_mipmap_generation_sets_correct_mip_count() {
-        let Some((device, queue)) = create_test_device_queue() else {
-            return;
-        };
-        let mut manager = TextureManager::new(&device);
-
-        let data = vec![255u8; 256 * 256 * 4];
-        let tex = manager
-            .create_texture(
-                &device,
-                &queue,
-                "mipmapped",
-                &data,
-                256,
-                256,
-                wgpu::TextureFormat::Rgba8UnormSrgb,
-                true,
-            )
-            .unwrap();
-
-        assert_eq!(tex.mip_level_count, 9);
-    }
-
-    #[test]
-    fn test_remove_texture_from_cache() {
-        let Some((device, queue)) = create_test_device_queue() else {
-            return;
-        };
-        let mut manager = TextureManager::new(&device);
-
-        let data = vec![0u8; 16];
-        manager
-            .create_texture(
-                &device,
-                &queue,
-                "removable",
-                &data,
-                2,
-                2,
-                wgpu::TextureFormat::Rgba8UnormSrgb,
-                false,
-            )
-            .unwrap();
-
-        assert!(manager.get("removable").is_some());
-        assert!(manager.remove("removable"));
-        assert!(manager.get("removable").is_none());
-    }
-}
+#[path = "texture_tests.rs"]
+mod tests;
@@ -0,0 +1,229 @@
+//! Residency bookkeeping for [`TextureManager`](crate::TextureManager):
+//! stable handles, reference guards, LRU eviction, and the texture budget.
+//!
+//! A texture counts as referenced while anything outside the cache holds its
+//! [`ManagedTexture`]: a [`TextureGuard`] or an `Arc` returned by
+//! `create_texture` or `get`. Only unreferenced textures are evicted; their
+//! pixel data stays on the CPU so they can be uploaded again.
+
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::texture::ManagedTexture;
+
+/// Stable handle to a texture registered with a
+/// [`TextureManager`](crate::TextureManager).
+///
+/// Unlike the [`ManagedTexture`] itself, the handle survives eviction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TextureId(u64);
+
+/// A reference that keeps a texture resident.
+///
+/// Returned by [`TextureManager::acquire`](crate::TextureManager::acquire);
+/// while any guard for a texture is alive the manager will not evict it.
+/// Dropping the guard releases the reference.
+#[derive(Clone)]
+pub struct TextureGuard {
+    id: TextureId,
+    texture: Arc<ManagedTexture>,
+}
+
+impl TextureGuard {
+    pub(crate) fn new(id: TextureId, texture: Arc<ManagedTexture>) -> Self {
+        Self { id, texture }
+    }
+
+    /// Handle of the guarded texture.
+    pub fn id(&self) -> TextureId {
+        self.id
+    }
+
+    /// The guarded texture.
+    pub fn texture(&self) -> &Arc<ManagedTexture> {
+        &self.texture
+    }
+}
+
+impl Deref for TextureGuard {
+    type Target = ManagedTexture;
+
+    fn deref(&self) -> &ManagedTexture {
+        &self.texture
+    }
+}
+
+/// Pixel data and parameters kept on the CPU to (re-)upload a texture.
+pub(crate) struct TextureSource {
+    /// One buffer per layer.
+    pub(crate) layers: Vec<Vec<u8>>,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) format: wgpu::TextureFormat,
+    pub(crate) generate_mipmaps: bool,
+    /// Whether the texture was created with
+    /// [`create_texture_array`](crate::TextureManager::create_texture_array).
+    pub(crate) array: bool,
+}
+
+/// A registered texture, resident on the GPU or evicted.
+pub(crate) struct TextureEntry {
+    pub(crate) id: TextureId,
+    pub(crate) source: TextureSource,
+    /// The GPU texture, or `None` while evicted.
+    pub(crate) texture: Option<Arc<ManagedTexture>>,
+    /// Cache clock value of the most recent create or acquire.
+    last_used: u64,
+}
+
+impl TextureEntry {
+    /// Number of handles to the resident texture held outside the cache.
+    fn outstanding_refs(&self) -> usize {
+        self.texture
+            .as_ref()
+            .map_or(0, |texture| Arc::strong_count(texture) - 1)
+    }
+
+    fn is_evictable(&self) -> bool {
+        self.texture.is_some() && self.outstanding_refs() == 0
+    }
+}
+
+/// Registered textures keyed by name, with LRU order and a residency budget.
+pub(crate) struct TextureCache {
+    entries: HashMap<String, TextureEntry>,
+    names: HashMap<TextureId, String>,
+    next_id: u64,
+    /// Monotonic counter stamped on entries as they are used, for LRU order.
+    clock: u64,
+    /// Maximum number of resident textures before unreferenced ones are evicted.
+    max_textures: usize,
+}
+
+impl TextureCache {
+    pub(crate) fn new(max_textures: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            names: HashMap::new(),
+            next_id: 0,
+            clock: 0,
+            max_textures,
+        }
+    }
+
+    pub(crate) fn entry(&self, name: &str) -> Option<&TextureEntry> {
+        self.entries.get(name)
+    }
+
+    pub(crate) fn entry_by_id(&self, id: TextureId) -> Option<&TextureEntry> {
+        self.entries.get(self.names.get(&id)?)
+    }
+
+    pub(crate) fn name(&self, id: TextureId) -> Option<&str> {
+        self.names.get(&id).map(String::as_str)
+    }
+
+    /// Register a freshly uploaded texture under `name`.
+    pub(crate) fn insert(
+        &mut self,
+        name: &str,
+        source: TextureSource,
+        texture: Arc<ManagedTexture>,
+    ) -> TextureId {
+        let id = TextureId(self.next_id);
+        self.next_id += 1;
+        self.clock += 1;
+        self.entries.insert(
+            name.to_string(),
+            TextureEntry {
+                id,
+                source,
+                texture: Some(texture),
+                last_used: self.clock,
+            },
+        );
+        self.names.insert(id, name.to_string());
+        self.enforce_budget(id);
+        id
+    }
+
+    /// Mark `name` as used with its (possibly re-uploaded) GPU texture.
+    pub(crate) fn mark_used(&mut self, name: &str, texture: Arc<ManagedTexture>) {
+        self.clock += 1;
+        let Some(entry) = self.entries.get_mut(name) else {
+            return;
+        };
+        entry.texture = Some(texture);
+        entry.last_used = self.clock;
+        let id = entry.id;
+        self.enforce_budget(id);
+    }
+
+    /// Remove a texture and its source data. Returns `true` if it existed.
+    pub(crate) fn remove(&mut self, name: &str) -> bool {
+        let Some(entry) = self.entries.remove(name) else {
+            return false;
+        };
+        self.names.remove(&entry.id);
+        true
+    }
+
+    pub(crate) fn evict_unreferenced(&mut self) -> usize {
+        let mut evicted = 0;
+        while self.evict_least_recently_used(None) {
+            evicted += 1;
+        }
+        evicted
+    }
+
+    pub(crate) fn memory_usage_bytes(&self) -> u64 {
+        self.entries
+            .values()
+            .filter_map(|entry| entry.texture.as_ref())
+            .map(|texture| texture.size_bytes())
+            .sum()
+    }
+
+    pub(crate) fn ref_count(&self, id: TextureId) -> usize {
+        self.entry_by_id(id)
+            .map_or(0, TextureEntry::outstanding_refs)
+    }
+
+    pub(crate) fn resident_count(&self) -> usize {
+        self.entries
+            .values()
+            .filter(|entry| entry.texture.is_some())
+            .count()
+    }
+
+    /// Evict unreferenced textures other than `keep` until the resident
+    /// count is within budget.
+    fn enforce_budget(&mut self, keep: TextureId) {
+        while self.resident_count() > self.max_textures
+            && self.evict_least_recently_used(Some(keep))
+        {}
+    }
+
+    /// Evict the least recently used unreferenced texture, skipping `keep`.
+    /// Returns `false` if no texture could be evicted.
+    fn evict_least_recently_used(&mut self, keep: Option<TextureId>) -> bool {
+        let victim = self
+            .entries
+            .iter_mut()
+            .filter(|(_, entry)| entry.is_evictable() && Some(entry.id) != keep)
+            .min_by_key(|(_, entry)| entry.last_used);
+        match victim {
+            Some((name, entry)) => {
+                entry.texture = None;
+                log::info!("Evicted texture '{name}'");
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "texture_cache_tests.rs"]
+mod tests;
@@ -0,0 +1,213 @@
+//! Unit tests for texture residency: guards, eviction, and the budget.
+
+use std::sync::Arc;
+
+use crate::texture::{TextureError, TextureManager, create_test_device_queue};
+
+use super::*;
+
+/// 4x4 RGBA8 pixels whose bytes count up from `seed`.
+fn pixels(seed: u8) -> Vec<u8> {
+    (0..64u8).map(|i| i.wrapping_add(seed)).collect()
+}
+
+fn create_4x4(
+    manager: &mut TextureManager,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    name: &str,
+    seed: u8,
+) -> TextureId {
+    manager
+        .create_texture(
+            device,
+            queue,
+            name,
+            &pixels(seed),
+            4,
+            4,
+            wgpu::TextureFormat::Rgba8Unorm,
+            false,
+        )
+        .unwrap();
+    manager.texture_id(name).unwrap()
+}
+
+/// Read back mip level 0 of a 4x4 RGBA8 texture.
+fn read_4x4(device: &wgpu::Device, queue: &wgpu::Queue, texture: &wgpu::Texture) -> Vec<u8> {
+    let row = 16u32;
+    let padded = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("texture-test-readback"),
+        size: u64::from(padded * 4),
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded),
+                rows_per_image: Some(4),
+            },
+        },
+        wgpu::Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        },
+    );
+    queue.submit([encoder.finish()]);
+
+    let slice = buffer.slice(..);
+    let (tx, rx) = std::sync::mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |result| {
+        let _ = tx.send(result);
+    });
+    let _ = device.poll(wgpu::PollType::Wait {
+        submission_index: None,
+        timeout: None,
+    });
+    assert!(matches!(rx.recv(), Ok(Ok(()))), "readback failed");
+
+    let mapped = slice.get_mapped_range();
+    (0..4usize)
+        .flat_map(|y| {
+            let start = y * padded as usize;
+            mapped[start..start + row as usize].to_vec()
+        })
+        .collect()
+}
+
+#[test]
+fn test_guarded_texture_is_not_evicted() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+    let held = create_4x4(&mut manager, &device, &queue, "held", 0);
+    let loose = create_4x4(&mut manager, &device, &queue, "loose", 1);
+    assert_eq!(manager.memory_usage_bytes(), 2 * 64);
+
+    let guard = manager.acquire(&device, &queue, held).unwrap();
+    assert_eq!(manager.ref_count(held), 1);
+    assert_eq!(manager.evict_unreferenced(), 1);
+    assert!(manager.is_resident(held));
+    assert!(!manager.is_resident(loose));
+    assert_eq!(manager.memory_usage_bytes(), 64);
+    assert_eq!(guard.dimensions, (4, 4));
+}
+
+#[test]
+fn test_released_guard_allows_eviction() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+    let id = create_4x4(&mut manager, &device, &queue, "tex", 0);
+
+    let guard = manager.acquire(&device, &queue, id).unwrap();
+    let second = guard.clone();
+    assert_eq!(manager.ref_count(id), 2);
+    drop(guard);
+    assert_eq!(manager.evict_unreferenced(), 0);
+
+    drop(second);
+    assert_eq!(manager.ref_count(id), 0);
+    assert_eq!(manager.evict_unreferenced(), 1);
+    assert!(!manager.is_resident(id));
+    assert!(manager.get("tex").is_none());
+    assert_eq!(manager.memory_usage_bytes(), 0);
+}
+
+#[test]
+fn test_reacquire_evicted_texture_reuploads_source() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+    let id = create_4x4(&mut manager, &device, &queue, "tex", 7);
+    let original = Arc::downgrade(&manager.get("tex").unwrap());
+    assert_eq!(manager.evict_unreferenced(), 1);
+    assert!(original.upgrade().is_none(), "GPU texture released");
+
+    let guard = manager.acquire(&device, &queue, id).unwrap();
+    assert!(manager.is_resident(id));
+    assert_eq!(
+        read_4x4(&device, &queue, &guard.texture().texture),
+        pixels(7)
+    );
+    assert_eq!(manager.memory_usage_bytes(), 64);
+}
+
+#[test]
+fn test_budget_evicts_least_recently_used() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new_with_budget(&device, 2);
+    let a = create_4x4(&mut manager, &device, &queue, "a", 0);
+    let b = create_4x4(&mut manager, &device, &queue, "b", 1);
+    // Touch `a` so `b` becomes the least recently used.
+    drop(manager.acquire(&device, &queue, a).unwrap());
+
+    let c = create_4x4(&mut manager, &device, &queue, "c", 2);
+    assert_eq!(manager.resident_count(), 2);
+    assert!(manager.is_resident(a));
+    assert!(!manager.is_resident(b));
+    assert!(manager.is_resident(c));
+}
+
+#[test]
+fn test_acquire_removed_texture_is_an_error() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+    let id = create_4x4(&mut manager, &device, &queue, "gone", 0);
+    assert!(manager.remove("gone"));
+    assert!(matches!(
+        manager.acquire(&device, &queue, id),
+        Err(TextureError::UnknownTexture(missing)) if missing == id
+    ));
+}
+
+#[test]
+fn test_texture_held_from_create_is_not_evicted() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new_with_budget(&device, 1);
+    let held = manager
+        .create_texture(
+            &device,
+            &queue,
+            "held",
+            &pixels(0),
+            4,
+            4,
+            wgpu::TextureFormat::Rgba8Unorm,
+            false,
+        )
+        .unwrap();
+    let id = manager.texture_id("held").unwrap();
+    assert_eq!(manager.ref_count(id), 1);
+
+    // Over budget, but the older texture is still in use.
+    let loose = create_4x4(&mut manager, &device, &queue, "loose", 1);
+    assert!(manager.is_resident(id));
+    assert!(manager.is_resident(loose));
+    assert_eq!(manager.evict_unreferenced(), 1);
+    assert!(manager.is_resident(id));
+    assert_eq!(manager.memory_usage_bytes(), held.size_bytes());
+
+    let guard = manager.acquire(&device, &queue, id).unwrap();
+    assert!(Arc::ptr_eq(guard.texture(), &held), "no duplicate upload");
+    assert_eq!(manager.ref_count(id), 2);
+
+    drop((held, guard));
+    assert_eq!(manager.evict_unreferenced(), 1);
+}
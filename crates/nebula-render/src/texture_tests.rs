@@ -0,0 +1,236 @@
+//! Unit tests for GPU texture management.
+
+use super::*;
+
+#[test]
+fn test_create_texture_with_valid_dimensions() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+
+    let data = vec![255u8; 64]; // 4x4 RGBA8
+    let result = manager.create_texture(
+        &device,
+        &queue,
+        "test-4x4",
+        &data,
+        4,
+        4,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+    );
+    assert!(result.is_ok());
+    let tex = result.unwrap();
+    assert_eq!(tex.dimensions, (4, 4));
+}
+
+#[test]
+fn test_mipmap_level_count_calculation() {
+    assert_eq!(mip_level_count(1, 1), 1);
+    assert_eq!(mip_level_count(2, 2), 2);
+    assert_eq!(mip_level_count(4, 4), 3);
+    assert_eq!(mip_level_count(256, 256), 9);
+    assert_eq!(mip_level_count(512, 256), 10);
+    assert_eq!(mip_level_count(1024, 1024), 11);
+}
+
+#[test]
+fn test_bind_group_creation_succeeds() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+
+    let data = vec![128u8; 16]; // 2x2 RGBA8
+    let tex = manager
+        .create_texture(
+            &device,
+            &queue,
+            "test-bind",
+            &data,
+            2,
+            2,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            false,
+        )
+        .unwrap();
+
+    let _bg = &tex.bind_group;
+}
+
+#[test]
+fn test_texture_cache_deduplicates() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+
+    let data = vec![255u8; 16]; // 2x2 RGBA8
+    let tex1 = manager
+        .create_texture(
+            &device,
+            &queue,
+            "shared",
+            &data,
+            2,
+            2,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            false,
+        )
+        .unwrap();
+
+    let tex2 = manager
+        .create_texture(
+            &device,
+            &queue,
+            "shared",
+            &data,
+            2,
+            2,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            false,
+        )
+        .unwrap();
+
+    assert!(Arc::ptr_eq(&tex1, &tex2));
+}
+
+#[test]
+fn test_rgba8_format_handling() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+
+    let data = vec![0u8; 256]; // 8x8 RGBA8
+    let tex = manager
+        .create_texture(
+            &device,
+            &queue,
+            "rgba8-test",
+            &data,
+            8,
+            8,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            false,
+        )
+        .unwrap();
+
+    assert_eq!(tex.format, wgpu::TextureFormat::Rgba8UnormSrgb);
+}
+
+#[test]
+fn test_zero_dimensions_returns_error() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+
+    let result = manager.create_texture(
+        &device,
+        &queue,
+        "zero",
+        &[],
+        0,
+        0,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+    );
+    assert!(matches!(result, Err(TextureError::ZeroDimensions { .. })));
+}
+
+#[test]
+fn test_data_size_mismatch_returns_error() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+
+    let data = vec![0u8; 32]; // 4x4 expects 64
+    let result = manager.create_texture(
+        &device,
+        &queue,
+        "mismatch",
+        &data,
+        4,
+        4,
+        wgpu::TextureFormat::Rgba8UnormSrgb,
+        false,
+    );
+    assert!(matches!(result, Err(TextureError::DataSizeMismatch { .. })));
+}
+
+#[test]
+fn test_mipmap_generation_sets_correct_mip_count() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+
+    let data = vec![255u8; 256 * 256 * 4];
+    let tex = manager
+        .create_texture(
+            &device,
+            &queue,
+            "mipmapped",
+            &data,
+            256,
+            256,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            true,
+        )
+        .unwrap();
+
+    assert_eq!(tex.mip_level_count, 9);
+}
+
+#[test]
+fn test_remove_texture_from_cache() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+
+    let data = vec![0u8; 16];
+    manager
+        .create_texture(
+            &device,
+            &queue,
+            "removable",
+            &data,
+            2,
+            2,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            false,
+        )
+        .unwrap();
+
+    assert!(manager.get("removable").is_some());
+    assert!(manager.remove("removable"));
+    assert!(manager.get("removable").is_none());
+}
+
+#[test]
+fn test_size_bytes_counts_mip_chain() {
+    let Some((device, queue)) = create_test_device_queue() else {
+        return;
+    };
+    let mut manager = TextureManager::new(&device);
+    let data = vec![0u8; 8 * 8 * 4];
+    let tex = manager
+        .create_texture(
+            &device,
+            &queue,
+            "mipped",
+            &data,
+            8,
+            8,
+            wgpu::TextureFormat::Rgba8Unorm,
+            true,
+        )
+        .unwrap();
+    // 8x8 + 4x4 + 2x2 + 1x1 texels, 4 bytes each.
+    assert_eq!(tex.size_bytes(), (64 + 16 + 4 + 1) * 4);
+    assert_eq!(manager.memory_usage_bytes(), tex.size_bytes());
+}
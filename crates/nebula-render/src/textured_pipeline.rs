@@ -1,7 +1,13 @@
 //! Textured rendering pipeline for geometry with UV-mapped textures.
+//!
+//! The same pipeline type also draws packed chunk meshes with
+//! [`TexturedPipeline::new_blended_chunk`], whose shader mixes each vertex's
+//! primary and secondary material layers by its biome blend weight.
 
 use std::num::NonZeroU64;
 
+use nebula_mesh::CHUNK_VERTEX_LAYOUT;
+
 use crate::buffer::{MeshBuffer, VertexPositionNormalUv};
 
 /// Textured rendering pipeline that samples from a texture bind group.
@@ -22,6 +28,49 @@ impl TexturedPipeline {
         surface_format: wgpu::TextureFormat,
         depth_format: Option<wgpu::TextureFormat>,
         texture_bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        Self::with_vertex_layout(
+            device,
+            shader,
+            surface_format,
+            depth_format,
+            texture_bind_group_layout,
+            VertexPositionNormalUv::layout(),
+        )
+    }
+
+    /// Create a textured pipeline for packed chunk meshes
+    /// ([`CHUNK_VERTEX_LAYOUT`]), to be used with
+    /// [`BLENDED_CHUNK_SHADER_SOURCE`].
+    ///
+    /// `texture_bind_group_layout` is the layout for group 1: a `D2Array`
+    /// texture with one layer per material ID, plus a sampler. The camera
+    /// uniform's `view_proj` must map chunk-local voxel units to clip space,
+    /// i.e. include the chunk's model transform.
+    pub fn new_blended_chunk(
+        device: &wgpu::Device,
+        shader: &wgpu::ShaderModule,
+        surface_format: wgpu::TextureFormat,
+        depth_format: Option<wgpu::TextureFormat>,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        Self::with_vertex_layout(
+            device,
+            shader,
+            surface_format,
+            depth_format,
+            texture_bind_group_layout,
+            CHUNK_VERTEX_LAYOUT,
+        )
+    }
+
+    fn with_vertex_layout(
+        device: &wgpu::Device,
+        shader: &wgpu::ShaderModule,
+        surface_format: wgpu::TextureFormat,
+        depth_format: Option<wgpu::TextureFormat>,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        vertex_layout: wgpu::VertexBufferLayout<'static>,
     ) -> Self {
         let camera_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -58,7 +107,7 @@ impl TexturedPipeline {
             vertex: wgpu::VertexState {
                 module: shader,
                 entry_point: Some("vs_main"),
-                buffers: &[VertexPositionNormalUv::layout()],
+                buffers: &[vertex_layout],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
             },
             primitive: wgpu::PrimitiveState {
@@ -150,3 +199,155 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     return textureSample(t_diffuse, s_diffuse, in.uv);
 }
 "#;
+
+/// WGSL shader source for packed chunk meshes with biome material blending.
+///
+/// Decodes [`ChunkVertex`](nebula_mesh::ChunkVertex) attributes, samples the
+/// primary and secondary material layers of a texture array and mixes them
+/// by the vertex blend weight. Steep faces use triplanar projection with the
+/// weights of `nebula_materials::triplanar_weights`.
+pub const BLENDED_CHUNK_SHADER_SOURCE: &str = r#"
+struct CameraUniform {
+    // Chunk-local voxel units to clip space.
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+@group(1) @binding(0)
+var t_materials: texture_2d_array<f32>;
+@group(1) @binding(1)
+var s_materials: sampler;
+
+struct VertexInput {
+    // xyz position, face direction index.
+    @location(0) position_normal: vec4<u32>,
+    // ao, blend weight, material id (lo, hi).
+    @location(1) ao_blend_material: vec4<u32>,
+    // uv, secondary material id (lo, hi).
+    @location(2) uv_secondary: vec4<u32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) local_pos: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+    @location(3) @interpolate(flat) material_a: u32,
+    @location(4) @interpolate(flat) material_b: u32,
+    @location(5) blend_weight: f32,
+};
+
+fn face_normal(direction: u32) -> vec3<f32> {
+    switch direction {
+        case 0u: { return vec3<f32>(1.0, 0.0, 0.0); }
+        case 1u: { return vec3<f32>(-1.0, 0.0, 0.0); }
+        case 2u: { return vec3<f32>(0.0, 1.0, 0.0); }
+        case 3u: { return vec3<f32>(0.0, -1.0, 0.0); }
+        case 4u: { return vec3<f32>(0.0, 0.0, 1.0); }
+        default: { return vec3<f32>(0.0, 0.0, -1.0); }
+    }
+}
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    let position = vec3<f32>(in.position_normal.xyz);
+    out.clip_position = camera.view_proj * vec4<f32>(position, 1.0);
+    out.local_pos = position;
+    out.normal = face_normal(in.position_normal.w);
+    out.uv = vec2<f32>(in.uv_secondary.xy);
+    out.material_a = in.ao_blend_material.z | (in.ao_blend_material.w << 8u);
+    out.material_b = in.uv_secondary.z | (in.uv_secondary.w << 8u);
+    out.blend_weight = f32(in.ao_blend_material.y) / 255.0;
+    return out;
+}
+
+// Mirrors `nebula_materials::triplanar_weights`.
+fn triplanar_weights(normal: vec3<f32>) -> vec3<f32> {
+    let blend = pow(abs(normal), vec3<f32>(4.0));
+    return blend / (blend.x + blend.y + blend.z);
+}
+
+fn sample_material(material: u32, in: VertexOutput, n: vec3<f32>) -> vec4<f32> {
+    let planar = textureSample(t_materials, s_materials, in.uv, material);
+    let w = triplanar_weights(n);
+    let triplanar = textureSample(t_materials, s_materials, in.local_pos.yz, material) * w.x
+        + textureSample(t_materials, s_materials, in.local_pos.xz, material) * w.y
+        + textureSample(t_materials, s_materials, in.local_pos.xy, material) * w.z;
+    // Steep faces (dominant normal component below 0.7) use triplanar.
+    let steep = max(abs(n.x), max(abs(n.y), abs(n.z))) < 0.7;
+    return select(planar, triplanar, steep);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let n = normalize(in.normal);
+    let a = sample_material(in.material_a, in, n);
+    let b = sample_material(in.material_b, in, n);
+    return mix(a, b, in.blend_weight);
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_device() -> Option<wgpu::Device> {
+        pollster::block_on(async {
+            let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+            let adapter = instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference: wgpu::PowerPreference::LowPower,
+                    ..Default::default()
+                })
+                .await
+                .ok()?;
+            let (device, _queue) = adapter
+                .request_device(&wgpu::DeviceDescriptor::default())
+                .await
+                .ok()?;
+            Some(device)
+        })
+    }
+
+    #[test]
+    fn test_blended_chunk_pipeline_compiles() {
+        let Some(device) = create_test_device() else {
+            return;
+        };
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("blended-chunk-shader"),
+            source: wgpu::ShaderSource::Wgsl(BLENDED_CHUNK_SHADER_SOURCE.into()),
+        });
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("blended-chunk-texture-layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2Array,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let _pipeline = TexturedPipeline::new_blended_chunk(
+            &device,
+            &shader,
+            wgpu::TextureFormat::Bgra8UnormSrgb,
+            Some(wgpu::TextureFormat::Depth32Float),
+            &texture_layout,
+        );
+    }
+}
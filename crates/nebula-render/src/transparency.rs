@@ -0,0 +1,363 @@
+//! Depth-sorted drawing of translucent voxel quads (glass, water).
+//!
+//! Translucent quads cannot be batched like opaque geometry: they must blend
+//! over whatever is behind them, so they are drawn after the opaque
+//! [`DrawBatch`](crate::DrawBatch), farthest first, without writing depth.
+//! Each visible [`GpuChunkMesh`] orders its own translucent range with
+//! [`GpuChunkMesh::sort_transparent`]; [`TransparentDrawList`] collects one
+//! batch per chunk, [`TransparentDrawList::sort_back_to_front`] orders the
+//! chunks with a radix sort on view depth, and a [`TransparentRenderPass`]
+//! draws them against the opaque pass's depth buffer, read-only.
+
+use std::ops::Range;
+
+use glam::Vec3;
+
+use crate::depth::DepthBuffer;
+use crate::gpu_chunk_mesh::GpuChunkMesh;
+
+/// Alpha blending for translucent pipelines.
+pub const TRANSPARENT_BLEND: wgpu::BlendState = wgpu::BlendState::ALPHA_BLENDING;
+
+/// Depth state for translucent pipelines: reverse-Z test against the opaque
+/// depth buffer, no depth writes.
+pub fn transparent_depth_stencil() -> wgpu::DepthStencilState {
+    wgpu::DepthStencilState {
+        format: DepthBuffer::FORMAT,
+        depth_write_enabled: false,
+        depth_compare: DepthBuffer::COMPARE_FUNCTION,
+        stencil: wgpu::StencilState::default(),
+        bias: wgpu::DepthBiasState::default(),
+    }
+}
+
+/// The translucent quads of one chunk, drawn with a single call.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransparentBatch {
+    /// Key of the [`GpuChunkMesh`] holding the quads.
+    pub mesh_id: u64,
+    /// The translucent range of the mesh's index buffer.
+    pub indices: Range<u32>,
+    /// Center of the batch relative to the camera.
+    pub center: Vec3,
+    /// View depth along the camera axis, set by
+    /// [`sort_back_to_front`](TransparentDrawList::sort_back_to_front).
+    pub depth: f32,
+}
+
+/// Per-frame list of translucent chunk batches, kept apart from the opaque
+/// batch.
+///
+/// Batches are ordered by the center of each chunk's translucent quads; the
+/// quads within a chunk are ordered by [`GpuChunkMesh::sort_transparent`].
+#[derive(Default)]
+pub struct TransparentDrawList {
+    batches: Vec<TransparentBatch>,
+    /// Scratch `(key, index)` pairs reused across sorts.
+    keys: Vec<(u32, u32)>,
+    keys_scratch: Vec<(u32, u32)>,
+}
+
+impl TransparentDrawList {
+    /// Create an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one batch drawing `indices` of mesh `mesh_id`, centered at
+    /// `center` relative to the camera.
+    pub fn push(&mut self, mesh_id: u64, indices: Range<u32>, center: Vec3) {
+        self.batches.push(TransparentBatch {
+            mesh_id,
+            indices,
+            center,
+            depth: 0.0,
+        });
+    }
+
+    /// Add the translucent range of `mesh`, whose chunk origin sits at
+    /// `chunk_offset` relative to the camera, as one batch. Meshes without
+    /// translucent quads are skipped.
+    pub fn push_chunk(&mut self, mesh_id: u64, mesh: &GpuChunkMesh, chunk_offset: Vec3) {
+        if !mesh.has_transparent() {
+            return;
+        }
+        self.push(
+            mesh_id,
+            mesh.transparent_index_start..mesh.index_count,
+            chunk_offset + mesh.transparent_center(),
+        );
+    }
+
+    /// Compute each batch's depth along `camera_z`, the camera's forward
+    /// axis, and order the batches farthest first.
+    ///
+    /// Uses a stable least-significant-digit radix sort over the depth bits,
+    /// so equal depths keep their push order.
+    pub fn sort_back_to_front(&mut self, camera_z: Vec3) {
+        let axis = camera_z.normalize_or_zero();
+        for batch in &mut self.batches {
+            batch.depth = batch.center.dot(axis);
+        }
+        sort_keys(
+            self.batches.iter().map(|batch| batch.depth),
+            &mut self.keys,
+            &mut self.keys_scratch,
+        );
+        let sorted = self
+            .keys
+            .iter()
+            .map(|&(_, i)| self.batches[i as usize].clone());
+        let sorted: Vec<TransparentBatch> = sorted.collect();
+        self.batches = sorted;
+    }
+
+    /// Batches in draw order.
+    pub fn batches(&self) -> &[TransparentBatch] {
+        &self.batches
+    }
+
+    /// Clear the list for reuse next frame, keeping allocated capacity.
+    pub fn clear(&mut self) {
+        self.batches.clear();
+    }
+
+    /// Number of batches in the list.
+    pub fn len(&self) -> usize {
+        self.batches.len()
+    }
+
+    /// Whether the list contains no batches.
+    pub fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+}
+
+/// Indices of `centers` ordered farthest first along `camera_z`, keeping
+/// the input order for equal depths.
+pub(crate) fn back_to_front(centers: &[Vec3], camera_z: Vec3) -> Vec<usize> {
+    let axis = camera_z.normalize_or_zero();
+    let (mut keys, mut scratch) = (Vec::new(), Vec::new());
+    sort_keys(centers.iter().map(|c| c.dot(axis)), &mut keys, &mut scratch);
+    keys.into_iter().map(|(_, i)| i as usize).collect()
+}
+
+/// Fill `keys` with `(key, index)` pairs for `depths`, sorted farthest first.
+fn sort_keys(
+    depths: impl Iterator<Item = f32>,
+    keys: &mut Vec<(u32, u32)>,
+    scratch: &mut Vec<(u32, u32)>,
+) {
+    keys.clear();
+    keys.extend(
+        depths
+            .enumerate()
+            .map(|(i, depth)| (!ascending_key(depth), i as u32)),
+    );
+    radix_sort(keys, scratch);
+}
+
+/// Map an `f32` to a `u32` whose unsigned order matches the float order.
+fn ascending_key(depth: f32) -> u32 {
+    let bits = depth.to_bits();
+    if bits & 0x8000_0000 != 0 {
+        !bits
+    } else {
+        bits | 0x8000_0000
+    }
+}
+
+/// Stable LSD radix sort of `(key, payload)` pairs by key, one byte per pass.
+fn radix_sort(pairs: &mut Vec<(u32, u32)>, scratch: &mut Vec<(u32, u32)>) {
+    scratch.clear();
+    scratch.resize(pairs.len(), (0, 0));
+    for shift in (0..32).step_by(8) {
+        let mut offsets = [0usize; 256];
+        for &(key, _) in pairs.iter() {
+            offsets[((key >> shift) & 0xff) as usize] += 1;
+        }
+        let mut total = 0;
+        for offset in &mut offsets {
+            let count = *offset;
+            *offset = total;
+            total += count;
+        }
+        for &pair in pairs.iter() {
+            let digit = ((pair.0 >> shift) & 0xff) as usize;
+            scratch[offsets[digit]] = pair;
+            offsets[digit] += 1;
+        }
+        std::mem::swap(pairs, scratch);
+    }
+}
+
+/// A render pass for translucent geometry, opened after the opaque pass.
+///
+/// Loads the color and depth attachments and binds depth read-only, so the
+/// opaque depth still occludes translucent quads but they never occlude each
+/// other. Pipelines used in it must blend with [`TRANSPARENT_BLEND`] and use
+/// [`transparent_depth_stencil`]. Dereferences to the underlying
+/// [`wgpu::RenderPass`] for setting pipelines and bind groups.
+pub struct TransparentRenderPass<'a> {
+    pass: wgpu::RenderPass<'a>,
+}
+
+impl<'a> TransparentRenderPass<'a> {
+    /// Begin a translucent pass drawing into `color_view` over `depth_view`.
+    pub fn begin(
+        encoder: &'a mut wgpu::CommandEncoder,
+        color_view: &'a wgpu::TextureView,
+        depth_view: &'a wgpu::TextureView,
+    ) -> Self {
+        let pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("transparent-pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: color_view,
+                resolve_target: None,
+                depth_slice: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth_view,
+                // `None` binds depth read-only.
+                depth_ops: None,
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        Self { pass }
+    }
+
+    /// Draw `list` in order with the currently set pipeline and bind groups,
+    /// one indexed call per batch. Batches whose mesh `meshes` cannot resolve
+    /// are skipped.
+    pub fn draw_list<'m>(
+        &mut self,
+        list: &TransparentDrawList,
+        meshes: impl Fn(u64) -> Option<&'m GpuChunkMesh>,
+    ) {
+        for batch in list.batches() {
+            let Some(mesh) = meshes(batch.mesh_id) else {
+                continue;
+            };
+            self.pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+            self.pass
+                .set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            self.pass.draw_indexed(batch.indices.clone(), 0, 0..1);
+        }
+    }
+}
+
+impl<'a> std::ops::Deref for TransparentRenderPass<'a> {
+    type Target = wgpu::RenderPass<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.pass
+    }
+}
+
+impl std::ops::DerefMut for TransparentRenderPass<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.pass
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture::create_test_device_queue;
+
+    #[test]
+    fn test_sort_back_to_front_puts_far_quads_first() {
+        let mut list = TransparentDrawList::new();
+        list.push(1, 0..6, Vec3::new(0.0, 0.0, 5.0));
+        list.push(2, 0..6, Vec3::new(0.0, 0.0, 10.0));
+        list.push(3, 0..6, Vec3::new(1.0, 0.0, 5.0));
+        list.sort_back_to_front(Vec3::Z);
+
+        let order: Vec<u64> = list.batches().iter().map(|b| b.mesh_id).collect();
+        assert_eq!(order, [2, 1, 3], "Z=10 before Z=5; ties keep push order");
+        assert_eq!(list.batches()[0].depth, 10.0);
+
+        // Looking down -Z reverses the order.
+        list.sort_back_to_front(Vec3::NEG_Z);
+        assert_eq!(list.batches()[0].depth, -5.0);
+        assert_eq!(list.batches()[2].mesh_id, 2);
+    }
+
+    #[test]
+    fn test_back_to_front_orders_quads_within_a_chunk() {
+        let centers = [
+            Vec3::new(0.0, 0.0, 5.0),
+            Vec3::new(0.0, 0.0, 10.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        ];
+        assert_eq!(back_to_front(&centers, Vec3::Z), [1, 0, 2]);
+        assert_eq!(back_to_front(&centers, Vec3::NEG_Z), [2, 0, 1]);
+    }
+
+    #[test]
+    fn test_radix_sort_matches_comparison_sort() {
+        let mut list = TransparentDrawList::new();
+        let mut seed = 12345u32;
+        for i in 0..500 {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            let z = (seed >> 8) as f32 / 65_536.0 - 128.0;
+            list.push(i, 0..6, Vec3::new(0.0, 0.0, z));
+        }
+        list.push(500, 0..6, Vec3::new(0.0, 0.0, -0.0));
+        list.push(501, 0..6, Vec3::ZERO);
+        list.sort_back_to_front(Vec3::Z);
+
+        let depths: Vec<f32> = list.batches().iter().map(|b| b.depth).collect();
+        assert!(depths.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(list.len(), 502);
+        list.clear();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_transparent_pass_binds_depth_read_only() {
+        let Some((device, queue)) = create_test_device_queue() else {
+            eprintln!("skipping: no GPU adapter available");
+            return;
+        };
+        let size = wgpu::Extent3d {
+            width: 64,
+            height: 64,
+            depth_or_array_layers: 1,
+        };
+        let target = |format, label| {
+            device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some(label),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                })
+                .create_view(&wgpu::TextureViewDescriptor::default())
+        };
+        let color = target(wgpu::TextureFormat::Rgba8UnormSrgb, "color");
+        let depth = target(DepthBuffer::FORMAT, "depth");
+
+        let scope = device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let mut encoder = device.create_command_encoder(&Default::default());
+        {
+            let mut pass = TransparentRenderPass::begin(&mut encoder, &color, &depth);
+            pass.draw_list(&TransparentDrawList::new(), |_| None);
+        }
+        queue.submit(Some(encoder.finish()));
+        let error = pollster::block_on(scope.pop());
+        assert!(error.is_none(), "validation error: {error:?}");
+        assert!(!transparent_depth_stencil().depth_write_enabled);
+    }
+}
@@ -0,0 +1,118 @@
+//! Admin console commands read from stdin.
+
+use std::fmt;
+
+/// A command issued by the server operator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminCommand {
+    /// List connected players.
+    List,
+    /// Disconnect a player, given by name or numeric player ID.
+    Kick(String),
+    /// Write a full world snapshot now.
+    Save,
+    /// Save and shut the server down.
+    Stop,
+}
+
+/// Errors from [`parse_command`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConsoleError {
+    /// The line contained no command.
+    #[error("empty command")]
+    Empty,
+    /// The command word is not recognised.
+    #[error("unknown command '{0}' (expected list, kick <player>, save or stop)")]
+    Unknown(String),
+    /// The command requires an argument that was not given.
+    #[error("'{command}' requires a {argument} argument")]
+    MissingArgument {
+        /// The command that was issued.
+        command: &'static str,
+        /// Description of the missing argument.
+        argument: &'static str,
+    },
+}
+
+/// Parses one console line into an [`AdminCommand`].
+///
+/// Command words are case-insensitive; surrounding whitespace is ignored.
+///
+/// # Errors
+///
+/// Returns [`ConsoleError`] for empty lines, unknown commands and missing
+/// arguments.
+pub fn parse_command(line: &str) -> Result<AdminCommand, ConsoleError> {
+    let mut words = line.split_whitespace();
+    let Some(command) = words.next() else {
+        return Err(ConsoleError::Empty);
+    };
+    match command.to_ascii_lowercase().as_str() {
+        "list" => Ok(AdminCommand::List),
+        "save" => Ok(AdminCommand::Save),
+        "stop" => Ok(AdminCommand::Stop),
+        "kick" => words
+            .next()
+            .map(|player| AdminCommand::Kick(player.to_string()))
+            .ok_or(ConsoleError::MissingArgument {
+                command: "kick",
+                argument: "player",
+            }),
+        _ => Err(ConsoleError::Unknown(command.to_string())),
+    }
+}
+
+impl fmt::Display for AdminCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::List => write!(f, "list"),
+            Self::Kick(player) => write!(f, "kick {player}"),
+            Self::Save => write!(f, "save"),
+            Self::Stop => write!(f, "stop"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_all_commands() {
+        assert_eq!(parse_command("list"), Ok(AdminCommand::List));
+        assert_eq!(parse_command("  SAVE \n"), Ok(AdminCommand::Save));
+        assert_eq!(parse_command("stop"), Ok(AdminCommand::Stop));
+        assert_eq!(
+            parse_command("kick Alice"),
+            Ok(AdminCommand::Kick("Alice".into()))
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        assert_eq!(parse_command("   "), Err(ConsoleError::Empty));
+        assert_eq!(
+            parse_command("ban Bob"),
+            Err(ConsoleError::Unknown("ban".into()))
+        );
+        assert!(matches!(
+            parse_command("kick"),
+            Err(ConsoleError::MissingArgument {
+                command: "kick",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_display_roundtrips() {
+        for command in [
+            AdminCommand::List,
+            AdminCommand::Kick("7".into()),
+            AdminCommand::Save,
+            AdminCommand::Stop,
+        ] {
+            assert_eq!(parse_command(&command.to_string()), Ok(command));
+        }
+    }
+}
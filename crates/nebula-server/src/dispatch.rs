@@ -0,0 +1,135 @@
+//! Message dispatch: applies client messages and voxel edits to the
+//! [`ServerState`].
+
+use nebula_math::WorldPosition;
+use nebula_multiplayer::{
+    AuthoritativePlayerState, ClientIntent, EditRejection, MovementVerdict, VoxelEditEvent,
+    VoxelEditIntent, apply_voxel_edit, validate_voxel_edit,
+};
+use nebula_net::{Channel, ConnectionId, Message, PlayerCorrection, PlayerPosition};
+
+use crate::state::{
+    GRAVITY_SOURCE_DEPTH_MM, Outbox, ServerState, entity_update, join_i128, player_network_id,
+};
+
+impl ServerState {
+    /// Applies one message received from `conn`.
+    pub fn handle_message(&mut self, conn: ConnectionId, message: Message, out: &mut Outbox) {
+        match message {
+            Message::LoginRequest(request) => self.login(conn, request.player_name, out),
+            Message::PlayerPosition(position) => self.move_player(conn, &position, out),
+            Message::Logout(_) => {
+                self.drop_connection(conn, "logged out", out);
+            }
+            other => tracing::trace!("Ignoring {:?} from {conn:?}", other.tag()),
+        }
+    }
+
+    /// Validates a voxel edit from `conn` against its player's position and
+    /// edit allowance, and applies it. Returns the event to broadcast.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EditRejection::UnknownPlayer`] if `conn` is not logged in,
+    /// or whichever check [`validate_voxel_edit`] failed.
+    pub fn edit_voxel(
+        &mut self,
+        conn: ConnectionId,
+        intent: &VoxelEditIntent,
+    ) -> Result<VoxelEditEvent, EditRejection> {
+        let player_id = self
+            .session(conn)
+            .ok_or(EditRejection::UnknownPlayer)?
+            .player_id;
+        let player = self
+            .world
+            .find_player(player_id)
+            .ok_or(EditRejection::UnknownPlayer)?;
+        let position = nebula_multiplayer::PlayerPosition {
+            x: player.x,
+            y: player.y,
+            z: player.z,
+        };
+        validate_voxel_edit(
+            &position,
+            intent,
+            &self.chunks,
+            &mut self.edit_limiter,
+            conn.0,
+        )?;
+        let event = apply_voxel_edit(
+            intent,
+            &mut self.chunks,
+            player_network_id(player_id),
+            self.world.tick(),
+        );
+        self.dirty.mark_dirty(event.chunk_id);
+        Ok(event)
+    }
+
+    fn move_player(&mut self, conn: ConnectionId, position: &PlayerPosition, out: &mut Outbox) {
+        let Some(session) = self.session(conn) else {
+            return;
+        };
+        let player_id = session.player_id;
+        let Some(current) = self.world.find_player(player_id) else {
+            return;
+        };
+        let target = [
+            join_i128(position.pos_x_high, position.pos_x_low),
+            join_i128(position.pos_y_high, position.pos_y_low),
+            join_i128(position.pos_z_high, position.pos_z_low),
+        ];
+        let delta = |target: i128, current: i64| {
+            i64::try_from(target - i128::from(current)).unwrap_or(i64::MAX)
+        };
+        let intent = ClientIntent::Move {
+            player_id,
+            dx: delta(target[0], current.x),
+            dy: delta(target[1], current.y),
+            dz: delta(target[2], current.z),
+        };
+
+        let gravity = [(
+            WorldPosition::new(0, -GRAVITY_SOURCE_DEPTH_MM, 0),
+            &self.gravity,
+        )];
+        match self
+            .movement
+            .process_move(&intent, &mut self.world, &self.chunks, &gravity)
+        {
+            Ok(MovementVerdict::Accepted) => {
+                self.dirty.mark_entity_dirty(player_network_id(player_id));
+            }
+            Ok(MovementVerdict::Corrected(authoritative)) => {
+                tracing::debug!("Corrected move from player {player_id}");
+                self.dirty.mark_entity_dirty(player_network_id(player_id));
+                let correction =
+                    Message::PlayerCorrection(player_correction(player_id, &authoritative));
+                out.push((conn, Channel::Reliable, correction));
+            }
+            Err(e) => {
+                tracing::debug!("Rejected move from player {player_id}: {e}");
+                // Snap the client back to the authoritative position.
+                if let Some(state) = self.world.find_player(player_id) {
+                    let correction = Message::EntityUpdate(entity_update(state));
+                    out.push((conn, Channel::Reliable, correction));
+                }
+            }
+        }
+    }
+}
+
+/// Wire form of a movement correction for `player_id`.
+fn player_correction(player_id: u64, state: &AuthoritativePlayerState) -> PlayerCorrection {
+    PlayerCorrection {
+        player_id,
+        tick: state.tick,
+        x: state.x,
+        y: state.y,
+        z: state.z,
+        vx: state.vx,
+        vy: state.vy,
+        vz: state.vz,
+    }
+}
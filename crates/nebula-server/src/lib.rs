@@ -0,0 +1,24 @@
+//! Headless dedicated server: runs the authoritative multiplayer simulation
+//! without any windowing or rendering dependencies.
+//!
+//! [`HeadlessServer`] wires the ECS world, the authoritative world,
+//! spawn-area terrain and chunk store to the networking layer, ticks at a
+//! fixed rate with bounded catch-up, and persists the world as snapshots. The `nebula-server`
+//! binary drives it from stdin admin commands and Ctrl-C.
+
+pub mod console;
+pub mod dispatch;
+pub mod persistence;
+pub mod server;
+pub mod session;
+pub mod state;
+pub mod terrain;
+pub mod tick_loop;
+
+pub use console::{AdminCommand, ConsoleError, parse_command};
+pub use persistence::{PLAYER_STATE_TAG, WorldSaver};
+pub use server::{HeadlessServer, ServerError};
+pub use session::{Session, SessionConnection};
+pub use state::{Outbox, ServerState, entity_update, join_i128, split_i128};
+pub use terrain::{SPAWN_RADIUS_CHUNKS, generate_spawn_area, surface_height};
+pub use tick_loop::{TickBudget, TickLoop};
@@ -1,5 +1,76 @@
 //! Headless dedicated server binary for the Nebula Engine.
+//!
+//! Reads admin commands (`list`, `kick <player>`, `save`, `stop`) from stdin
+//! and shuts down gracefully on Ctrl-C, saving the world on the way out.
 
-fn main() {
-    println!("Nebula Engine Server");
+use std::io::BufRead;
+use std::process::ExitCode;
+
+use clap::Parser;
+use nebula_config::{CliArgs, Config};
+use nebula_server::{AdminCommand, HeadlessServer, parse_command};
+use tokio::sync::mpsc;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = CliArgs::parse();
+    let mut config = match &args.config {
+        Some(dir) => match Config::load_or_create(dir) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Failed to load config from {}: {e}", dir.display());
+                return ExitCode::FAILURE;
+            }
+        },
+        None => Config::default(),
+    };
+    config.apply_cli_overrides(&args);
+    nebula_log::init_logging(None, cfg!(debug_assertions), Some(&config));
+
+    tracing::info!(
+        "Nebula Engine dedicated server: port {}, {} players max, seed {}",
+        config.network.server_port,
+        config.network.max_players,
+        config.server.world_seed
+    );
+
+    let (commands_tx, commands) = mpsc::channel(16);
+    spawn_console(commands_tx.clone());
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            tracing::info!("Interrupted");
+            let _ = commands_tx.send(AdminCommand::Stop).await;
+        }
+    });
+
+    match HeadlessServer::new(config).run(commands).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            tracing::error!("Server stopped: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Forwards stdin lines to the server as admin commands. Stdin reaching EOF
+/// does not stop the server; only `stop` or Ctrl-C do.
+fn spawn_console(commands: mpsc::Sender<AdminCommand>) {
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match parse_command(&line) {
+                Ok(command) => {
+                    if commands.blocking_send(command).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => eprintln!("{e}"),
+            }
+        }
+    });
 }
@@ -0,0 +1,202 @@
+//! World saves: full and incremental snapshots of the server state.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use nebula_multiplayer::{
+    CURRENT_SNAPSHOT_VERSION, ChunkId, ChunkSnapshot, EntitySnapshot, NetworkId, SnapshotConfig,
+    SnapshotDelta, SnapshotError, SnapshotHeader, SnapshotKind, SnapshotSource, SnapshotTimer,
+    WorldSnapshot, compress_chunk, write_delta, write_snapshot,
+};
+
+use crate::state::{ServerState, player_network_id};
+
+/// Component tag under which player states are stored in snapshots.
+pub const PLAYER_STATE_TAG: &str = "PlayerState";
+
+impl SnapshotSource for ServerState {
+    fn server_tick(&self) -> u64 {
+        self.world().tick()
+    }
+
+    fn world_time(&self) -> f64 {
+        ServerState::world_time(self)
+    }
+
+    fn chunk_snapshot(&self, chunk_id: &ChunkId) -> Option<ChunkSnapshot> {
+        let voxels = self.chunks().chunk(chunk_id)?;
+        let raw: Vec<u8> = voxels.iter().flat_map(|v| v.0.to_le_bytes()).collect();
+        Some(ChunkSnapshot {
+            chunk_id: *chunk_id,
+            voxel_data: compress_chunk(&raw),
+        })
+    }
+
+    fn entity_snapshot(&self, network_id: NetworkId) -> Option<EntitySnapshot> {
+        let state = self.world().find_player(u64::from(network_id.id))?;
+        let bytes = postcard::to_allocvec(state).ok()?;
+        Some(EntitySnapshot {
+            network_id,
+            components: vec![(PLAYER_STATE_TAG.to_string(), bytes)],
+        })
+    }
+}
+
+/// Writes world snapshots on demand and on the autosave schedule.
+///
+/// Autosaves follow the [`SnapshotTimer`] cadence: a full snapshot first,
+/// then deltas of the changes recorded in the state's dirty tracker, with a
+/// fresh full snapshot every `full_every` saves. Manual saves are always
+/// full.
+pub struct WorldSaver {
+    timer: SnapshotTimer,
+    autosave: bool,
+    last: Option<SnapshotHeader>,
+}
+
+impl WorldSaver {
+    /// Creates a saver writing into `dir`, autosaving every `interval`
+    /// (`None` disables autosave).
+    pub fn new(dir: impl Into<PathBuf>, interval: Option<Duration>) -> Self {
+        let config = SnapshotConfig {
+            snapshot_dir: dir.into(),
+            interval: interval.unwrap_or(Duration::MAX),
+            ..SnapshotConfig::default()
+        };
+        Self {
+            timer: SnapshotTimer::new(config),
+            autosave: interval.is_some(),
+            last: None,
+        }
+    }
+
+    /// Header of the most recent snapshot written.
+    pub fn last_snapshot(&self) -> Option<&SnapshotHeader> {
+        self.last.as_ref()
+    }
+
+    /// Writes an autosave if one is due. Returns the path written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SnapshotError`] on I/O or serialization failure.
+    pub fn autosave(&mut self, state: &mut ServerState) -> Result<Option<PathBuf>, SnapshotError> {
+        if !self.autosave || !self.timer.should_snapshot() {
+            return Ok(None);
+        }
+        let base = match (self.timer.next_kind(), &self.last) {
+            (SnapshotKind::Incremental, Some(base)) => base.clone(),
+            _ => return self.save_full(state).map(Some),
+        };
+        let delta = SnapshotDelta::capture(&base, state.dirty(), state);
+        let path = write_delta(&delta, &self.timer.config)?;
+        self.last = Some(delta.header);
+        self.timer.record(SnapshotKind::Incremental);
+        state.clear_dirty();
+        Ok(Some(path))
+    }
+
+    /// Writes a full snapshot of every chunk and player now. Returns the
+    /// path written.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SnapshotError`] on I/O or serialization failure.
+    pub fn save_full(&mut self, state: &mut ServerState) -> Result<PathBuf, SnapshotError> {
+        let header = SnapshotHeader {
+            version: CURRENT_SNAPSHOT_VERSION,
+            snapshot_id: self.last.as_ref().map_or(1, |h| h.snapshot_id + 1),
+            server_tick: state.world().tick(),
+            timestamp: unix_millis(),
+            is_incremental: false,
+            parent_snapshot_id: None,
+        };
+        let snapshot = WorldSnapshot {
+            header: header.clone(),
+            modified_chunks: state
+                .chunks()
+                .chunks()
+                .filter_map(|(id, _)| state.chunk_snapshot(id))
+                .collect(),
+            entities: state
+                .world()
+                .players()
+                .filter_map(|ps| state.entity_snapshot(player_network_id(ps.player_id)))
+                .collect(),
+            world_time: state.world_time(),
+        };
+        let path = write_snapshot(&snapshot, &self.timer.config)?;
+        self.last = Some(header);
+        self.timer.record(SnapshotKind::Full);
+        state.clear_dirty();
+        Ok(path)
+    }
+}
+
+/// Wall-clock Unix milliseconds, or 0 if the clock is before the epoch.
+fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Outbox;
+    use nebula_config::Config;
+    use nebula_multiplayer::{PlayerState, load_snapshot, load_snapshot_chain};
+    use nebula_net::{ConnectionId, LoginRequest, Message};
+
+    fn state_with_player() -> ServerState {
+        let mut state = ServerState::new(&Config::default());
+        let login = Message::LoginRequest(LoginRequest::new("Alice"));
+        state.handle_message(ConnectionId(1), login, &mut Outbox::new());
+        state
+    }
+
+    #[test]
+    fn test_full_save_contains_chunks_and_players() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = state_with_player();
+        let mut saver = WorldSaver::new(dir.path(), None);
+
+        let path = saver.save_full(&mut state).unwrap();
+        let snapshot = load_snapshot(&path).unwrap();
+        assert_eq!(
+            snapshot.modified_chunks.len(),
+            state.chunks().chunks().count()
+        );
+        assert_eq!(snapshot.entities.len(), 1);
+        let (tag, bytes) = &snapshot.entities[0].components[0];
+        assert_eq!(tag, PLAYER_STATE_TAG);
+        let player: PlayerState = postcard::from_bytes(bytes).unwrap();
+        assert_eq!(Some(&player), state.world().find_player(1));
+        assert!(state.dirty().entities().next().is_none());
+    }
+
+    #[test]
+    fn test_autosave_writes_loadable_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = state_with_player();
+        let mut saver = WorldSaver::new(dir.path(), Some(Duration::ZERO));
+
+        let base = saver.autosave(&mut state).unwrap().unwrap();
+        state.tick();
+        state.drop_connection(ConnectionId(1), "test", &mut Outbox::new());
+        let delta = saver.autosave(&mut state).unwrap().unwrap();
+        assert!(saver.last_snapshot().unwrap().is_incremental);
+
+        let restored = load_snapshot_chain(&base, &[delta]).unwrap();
+        assert!(restored.entities.is_empty());
+        assert_eq!(restored.header.server_tick, 1);
+    }
+
+    #[test]
+    fn test_autosave_disabled_without_interval() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = state_with_player();
+        let mut saver = WorldSaver::new(dir.path(), None);
+        assert_eq!(saver.autosave(&mut state).unwrap(), None);
+    }
+}
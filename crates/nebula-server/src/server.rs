@@ -0,0 +1,275 @@
+//! The headless server run loop: networking, fixed-rate simulation, admin
+//! commands and shutdown.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use nebula_config::Config;
+use nebula_multiplayer::SnapshotError;
+use nebula_net::{
+    Channel, GameServer, IncomingMessage, Logout, Message, ServerConfig as NetServerConfig,
+    TransportKind, UdpConfig, UdpListener, message_channel,
+};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
+
+use crate::console::AdminCommand;
+use crate::persistence::WorldSaver;
+use crate::state::{Outbox, ServerState};
+use crate::tick_loop::TickLoop;
+
+/// Capacity of the network → simulation message queue.
+const INCOMING_CAPACITY: usize = 4_096;
+
+/// Errors that stop the server.
+#[derive(Debug, thiserror::Error)]
+pub enum ServerError {
+    /// Binding or accepting on the listen socket failed.
+    #[error("network error: {0}")]
+    Io(#[from] std::io::Error),
+    /// Writing the final world snapshot failed.
+    #[error("failed to save world: {0}")]
+    Save(#[from] SnapshotError),
+}
+
+/// A dedicated server without any windowing or rendering.
+///
+/// Owns the authoritative [`ServerState`] and a [`GameServer`] for the
+/// connections. [`HeadlessServer::run`] simulates at the configured tick
+/// rate, replicates player state at `network.net_tick_rate`, autosaves, and
+/// executes [`AdminCommand`]s until it receives [`AdminCommand::Stop`] or
+/// the command channel closes.
+pub struct HeadlessServer {
+    config: Config,
+    state: ServerState,
+    saver: WorldSaver,
+    net: Arc<GameServer>,
+    incoming: mpsc::Receiver<IncomingMessage>,
+    ticks: TickLoop,
+    outbox: Outbox,
+}
+
+impl HeadlessServer {
+    /// Builds the world and networking described by `config`.
+    pub fn new(config: Config) -> Self {
+        let (sink, incoming) = message_channel(INCOMING_CAPACITY);
+        let net_config = NetServerConfig {
+            bind_addr: SocketAddr::from(([0, 0, 0, 0], config.network.server_port)),
+            max_connections: config.network.max_players as usize,
+            transport: config.network.transport,
+            ..NetServerConfig::default()
+        };
+        let autosave = match config.server.autosave_interval_seconds {
+            0 => None,
+            secs => Some(Duration::from_secs(secs.into())),
+        };
+        Self {
+            state: ServerState::new(&config),
+            saver: WorldSaver::new(PathBuf::from(&config.server.save_dir), autosave),
+            net: Arc::new(GameServer::new(net_config).with_message_sink(sink)),
+            incoming,
+            ticks: TickLoop::new(config.server.tick_rate, config.server.max_catch_up_ticks),
+            outbox: Outbox::new(),
+            config,
+        }
+    }
+
+    /// The authoritative game state.
+    pub fn state(&self) -> &ServerState {
+        &self.state
+    }
+
+    /// Binds `network.server_port` on all interfaces with the configured
+    /// `network.transport` and runs until stopped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ServerError`] if binding fails or the final save fails.
+    pub async fn run(self, commands: mpsc::Receiver<AdminCommand>) -> Result<(), ServerError> {
+        let addr = SocketAddr::from(([0, 0, 0, 0], self.config.network.server_port));
+        match self.config.network.transport {
+            TransportKind::Tcp => {
+                let listener = TcpListener::bind(addr).await?;
+                tracing::info!("Listening on {addr} (TCP)");
+                self.run_with_listener(listener, commands).await
+            }
+            TransportKind::Udp => {
+                let listener = UdpListener::bind(addr, UdpConfig::default()).await?;
+                tracing::info!("Listening on {addr} (UDP)");
+                self.run_with_udp_listener(listener, commands).await
+            }
+        }
+    }
+
+    /// Runs on an already bound listener until stopped, then closes every
+    /// connection and writes a final full snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ServerError`] if accepting fails or the final save fails.
+    pub async fn run_with_listener(
+        self,
+        listener: TcpListener,
+        commands: mpsc::Receiver<AdminCommand>,
+    ) -> Result<(), ServerError> {
+        let net = Arc::clone(&self.net);
+        let accept = tokio::spawn(async move { net.run_with_listener(listener).await });
+        self.serve(accept, commands).await
+    }
+
+    /// Like [`run_with_listener`](Self::run_with_listener), on an already
+    /// bound UDP listener.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ServerError`] if the final save fails.
+    pub async fn run_with_udp_listener(
+        self,
+        listener: UdpListener,
+        commands: mpsc::Receiver<AdminCommand>,
+    ) -> Result<(), ServerError> {
+        let net = Arc::clone(&self.net);
+        let accept = tokio::spawn(async move { net.run_with_udp_listener(listener).await });
+        self.serve(accept, commands).await
+    }
+
+    /// Simulates, replicates and autosaves while `accept` takes connections.
+    async fn serve(
+        mut self,
+        accept: JoinHandle<std::io::Result<()>>,
+        mut commands: mpsc::Receiver<AdminCommand>,
+    ) -> Result<(), ServerError> {
+        let tick_duration = self.ticks.tick_duration();
+        let ticks_per_replication =
+            (self.config.server.tick_rate / self.config.network.net_tick_rate.max(1)).max(1);
+        let mut frame = tokio::time::interval(tick_duration);
+        frame.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut last_frame = Instant::now();
+
+        loop {
+            tokio::select! {
+                _ = frame.tick() => {}
+                command = commands.recv() => match command {
+                    Some(AdminCommand::Stop) => break,
+                    Some(command) => self.execute(command).await,
+                    None => {
+                        tracing::info!("Command channel closed, stopping");
+                        break;
+                    }
+                },
+            }
+
+            let now = Instant::now();
+            let budget = self.ticks.advance(now - last_frame);
+            last_frame = now;
+            if budget.shed > 0 {
+                tracing::warn!(
+                    "Server overloaded: skipped {} ticks ({} total)",
+                    budget.shed,
+                    self.ticks.shed_total()
+                );
+            }
+            if budget.run == 0 {
+                continue;
+            }
+
+            let live = self.net.connections.ids().await;
+            self.state.reap_connections(&live, &mut self.outbox);
+            for _ in 0..budget.run {
+                while let Ok(incoming) = self.incoming.try_recv() {
+                    self.state.handle_message(
+                        incoming.connection_id,
+                        incoming.message,
+                        &mut self.outbox,
+                    );
+                }
+                self.state.tick();
+                if self
+                    .state
+                    .world()
+                    .tick()
+                    .is_multiple_of(u64::from(ticks_per_replication))
+                {
+                    self.state.replicate(&mut self.outbox);
+                }
+            }
+            self.flush().await;
+
+            match self.saver.autosave(&mut self.state) {
+                Ok(Some(path)) => tracing::info!("Autosaved to {}", path.display()),
+                Ok(None) => {}
+                Err(e) => tracing::error!("Autosave failed: {e}"),
+            }
+        }
+        self.shutdown(accept).await
+    }
+
+    /// Closes the network and writes the final snapshot.
+    async fn shutdown(
+        mut self,
+        accept: JoinHandle<std::io::Result<()>>,
+    ) -> Result<(), ServerError> {
+        tracing::info!("Shutting down");
+        self.flush().await;
+        self.net.shutdown();
+        match accept.await {
+            Ok(result) => result?,
+            Err(e) => tracing::error!("Accept loop panicked: {e}"),
+        }
+        let path = self.saver.save_full(&mut self.state)?;
+        tracing::info!("World saved to {}", path.display());
+        Ok(())
+    }
+
+    /// Runs one admin command, logging its result.
+    async fn execute(&mut self, command: AdminCommand) {
+        match command {
+            AdminCommand::List => {
+                let mut players: Vec<_> = self.state.sessions().collect();
+                players.sort_by_key(|(_, s)| s.player_id);
+                tracing::info!("{} player(s) online", players.len());
+                for (conn, session) in players {
+                    let peer = self.net.connections.peer_addr(&conn).await;
+                    tracing::info!(
+                        "  #{} {} ({})",
+                        session.player_id,
+                        session.name,
+                        peer.map_or_else(|| "?".to_string(), |p| p.to_string())
+                    );
+                }
+            }
+            AdminCommand::Kick(player) => match self.state.find_connection(&player) {
+                Some(conn) => {
+                    if let Some(session) =
+                        self.state.drop_connection(conn, "kicked", &mut self.outbox)
+                    {
+                        let notice = Message::Logout(Logout {
+                            player_id: session.player_id,
+                            reason: "kicked".to_string(),
+                        });
+                        self.outbox.push((conn, Channel::Reliable, notice));
+                    }
+                    self.flush().await;
+                    self.net.kick(conn).await;
+                }
+                None => tracing::warn!("No player named '{player}'"),
+            },
+            AdminCommand::Save => match self.saver.save_full(&mut self.state) {
+                Ok(path) => tracing::info!("World saved to {}", path.display()),
+                Err(e) => tracing::error!("Save failed: {e}"),
+            },
+            AdminCommand::Stop => {}
+        }
+    }
+
+    /// Sends every queued message.
+    async fn flush(&mut self) {
+        for (conn, channel, message) in self.outbox.drain(..) {
+            self.net.send_to(conn, channel, &message).await;
+        }
+    }
+}
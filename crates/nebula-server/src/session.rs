@@ -0,0 +1,160 @@
+//! Player sessions: logins, disconnects and connection lookups.
+//!
+//! Each logged-in player is an entity in the server's ECS world carrying
+//! its [`Session`] and the [`SessionConnection`] it arrived on.
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use nebula_multiplayer::PlayerState;
+use nebula_net::{Channel, ConnectionId, LoginResponse, Logout, Message, MessageSchema};
+
+use crate::state::{Outbox, ServerState, entity_update, player_network_id};
+
+/// A logged-in player bound to a connection.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    /// Player ID assigned at login.
+    pub player_id: u64,
+    /// Name the player logged in with.
+    pub name: String,
+}
+
+/// The connection a session's messages arrive on.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionConnection(pub ConnectionId);
+
+impl ServerState {
+    /// Logged-in players by connection.
+    pub fn sessions(&self) -> impl Iterator<Item = (ConnectionId, &Session)> {
+        self.ecs.iter_entities().filter_map(|entity| {
+            let conn = entity.get::<SessionConnection>()?;
+            Some((conn.0, entity.get::<Session>()?))
+        })
+    }
+
+    /// The session bound to `conn`, if it has logged in.
+    pub fn session(&self, conn: ConnectionId) -> Option<&Session> {
+        self.sessions()
+            .find(|&(id, _)| id == conn)
+            .map(|(_, session)| session)
+    }
+
+    /// Connection of the player named `player`, or with that numeric ID.
+    pub fn find_connection(&self, player: &str) -> Option<ConnectionId> {
+        let id = player.parse::<u64>().ok();
+        self.sessions()
+            .find(|(_, s)| s.name == player || Some(s.player_id) == id)
+            .map(|(conn, _)| conn)
+    }
+
+    /// Removes the session bound to `conn`, despawns its player and tells
+    /// the remaining players. Returns the removed session.
+    pub fn drop_connection(
+        &mut self,
+        conn: ConnectionId,
+        reason: &str,
+        out: &mut Outbox,
+    ) -> Option<Session> {
+        let entity = self.session_entity(conn)?;
+        let session = self.ecs.entity_mut(entity).take::<Session>()?;
+        self.ecs.despawn(entity);
+        self.edit_limiter.remove_client(conn.0);
+        self.movement.remove_player(session.player_id);
+        self.world.despawn_player(session.player_id);
+        self.replicated.remove(&session.player_id);
+        self.dirty
+            .mark_entity_dirty(player_network_id(session.player_id));
+        tracing::info!(
+            "{} (player {}) left: {reason}",
+            session.name,
+            session.player_id
+        );
+
+        let logout = Message::Logout(Logout {
+            player_id: session.player_id,
+            reason: reason.to_string(),
+        });
+        for (other, _) in self.sessions() {
+            out.push((other, Channel::Reliable, logout.clone()));
+        }
+        Some(session)
+    }
+
+    /// Drops every session whose connection is no longer in `live`.
+    pub fn reap_connections(&mut self, live: &[ConnectionId], out: &mut Outbox) {
+        let gone: Vec<ConnectionId> = self
+            .sessions()
+            .map(|(conn, _)| conn)
+            .filter(|conn| !live.contains(conn))
+            .collect();
+        for conn in gone {
+            self.drop_connection(conn, "connection closed", out);
+        }
+    }
+
+    /// Logs `conn` in as `name`, spawning its player at the spawn point, or
+    /// rejects it if the server is full.
+    pub(crate) fn login(&mut self, conn: ConnectionId, name: String, out: &mut Outbox) {
+        if self.session_entity(conn).is_some() {
+            return;
+        }
+        if self.sessions().count() >= self.max_players {
+            tracing::info!("Rejecting {name}: server full");
+            out.push((
+                conn,
+                Channel::Reliable,
+                login_response(0, false, "server full"),
+            ));
+            return;
+        }
+
+        let player_id = self.next_player_id;
+        self.next_player_id += 1;
+        let [x, y, z] = self.spawn;
+        self.world.spawn_player(PlayerState {
+            player_id,
+            x,
+            y,
+            z,
+            yaw_mrad: 0,
+            pitch_mrad: 0,
+        });
+        self.dirty.mark_entity_dirty(player_network_id(player_id));
+        tracing::info!("{name} joined as player {player_id}");
+
+        out.push((
+            conn,
+            Channel::Reliable,
+            login_response(player_id, true, "welcome"),
+        ));
+        // Bring the newcomer up to date with everyone already replicated;
+        // its own spawn reaches all clients with the next replication pass.
+        for state in self.replicated.values() {
+            out.push((
+                conn,
+                Channel::Reliable,
+                Message::EntityUpdate(entity_update(state)),
+            ));
+        }
+        self.ecs
+            .spawn((SessionConnection(conn), Session { player_id, name }));
+    }
+
+    /// The ECS entity holding the session bound to `conn`.
+    fn session_entity(&self, conn: ConnectionId) -> Option<Entity> {
+        self.ecs
+            .iter_entities()
+            .find(|entity| entity.get::<SessionConnection>() == Some(&SessionConnection(conn)))
+            .map(|entity| entity.id())
+    }
+}
+
+/// A successful or failed login reply.
+fn login_response(player_id: u64, success: bool, message: &str) -> Message {
+    Message::LoginResponse(LoginResponse {
+        player_id,
+        success,
+        message: message.to_string(),
+        schema: MessageSchema::current(),
+    })
+}
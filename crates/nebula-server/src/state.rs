@@ -0,0 +1,200 @@
+//! Authoritative game state of the dedicated server.
+//!
+//! [`ServerState`] is deliberately synchronous: network tasks hand it
+//! decoded messages (see [`dispatch`](crate::dispatch)), and everything it
+//! wants to send goes into an [`Outbox`] that the run loop flushes. This
+//! keeps the simulation deterministic and testable without sockets.
+//!
+//! The state is backed by the engine's ECS [`World`]: player sessions are
+//! entities (see [`session`](crate::session)) and the simulation clock is
+//! its [`TimeRes`] resource. Player simulation lives in the
+//! [`AuthoritativeWorld`].
+
+use std::collections::HashMap;
+
+use bevy_ecs::world::World;
+use nebula_config::Config;
+use nebula_ecs::TimeRes;
+use nebula_multiplayer::{
+    AuthoritativeWorld, DirtyChunkTracker, MovementEnvelope, NetworkId, PlayerState,
+    ServerChunkStore, VoxelEditRateLimiter,
+};
+use nebula_net::{Channel, ConnectionId, EntityUpdate, Message};
+use nebula_physics::GravitySource;
+
+use crate::terrain::generate_spawn_area;
+
+/// Messages queued for delivery: destination, channel and message.
+pub type Outbox = Vec<(ConnectionId, Channel, Message)>;
+
+/// Spawn height above the terrain surface, in millimetres.
+pub(crate) const SPAWN_CLEARANCE_MM: i64 = 1_000;
+
+/// Depth of the gravity source below the origin, in millimetres. Far enough
+/// that its pull is straight down across the whole spawn area.
+pub(crate) const GRAVITY_SOURCE_DEPTH_MM: i128 = 1_000_000_000_000;
+
+/// The server's authoritative world plus the bookkeeping needed to serve it.
+pub struct ServerState {
+    pub(crate) ecs: World,
+    pub(crate) world: AuthoritativeWorld,
+    pub(crate) chunks: ServerChunkStore,
+    pub(crate) next_player_id: u64,
+    pub(crate) max_players: usize,
+    pub(crate) spawn: [i64; 3],
+    /// Last player states sent to clients, for change detection.
+    pub(crate) replicated: HashMap<u64, PlayerState>,
+    pub(crate) dirty: DirtyChunkTracker,
+    pub(crate) edit_limiter: VoxelEditRateLimiter,
+    pub(crate) movement: MovementEnvelope,
+    pub(crate) gravity: GravitySource,
+}
+
+impl ServerState {
+    /// Creates the world described by `config`: generates the spawn area
+    /// terrain and places the spawn point just above it.
+    pub fn new(config: &Config) -> Self {
+        let mut chunks = ServerChunkStore::new();
+        let surface = generate_spawn_area(&mut chunks, config.server.world_seed);
+        let mut ecs = nebula_ecs::create_world();
+        ecs.resource_mut::<TimeRes>().fixed_dt = 1.0 / f64::from(config.server.tick_rate.max(1));
+        Self {
+            ecs,
+            world: AuthoritativeWorld::new(),
+            chunks,
+            next_player_id: 1,
+            max_players: config.network.max_players as usize,
+            spawn: [0, surface * 1_000 + SPAWN_CLEARANCE_MM, 0],
+            replicated: HashMap::new(),
+            dirty: DirtyChunkTracker::new(),
+            edit_limiter: VoxelEditRateLimiter::default(),
+            movement: MovementEnvelope::default(),
+            gravity: GravitySource {
+                mass: 1.0,
+                surface_gravity: 9.81,
+                surface_radius: GRAVITY_SOURCE_DEPTH_MM as f64,
+                influence_radius: f64::INFINITY,
+                constant_near_surface: true,
+                atmosphere_height: GRAVITY_SOURCE_DEPTH_MM as f64,
+            },
+        }
+    }
+
+    /// The engine ECS world holding sessions and server resources.
+    pub fn ecs(&self) -> &World {
+        &self.ecs
+    }
+
+    /// The authoritative player simulation.
+    pub fn world(&self) -> &AuthoritativeWorld {
+        &self.world
+    }
+
+    /// The server's voxel chunks.
+    pub fn chunks(&self) -> &ServerChunkStore {
+        &self.chunks
+    }
+
+    /// Where new players are spawned, in millimetres.
+    pub fn spawn_point(&self) -> [i64; 3] {
+        self.spawn
+    }
+
+    /// In-game time elapsed since the server started, in seconds.
+    pub fn world_time(&self) -> f64 {
+        self.ecs.resource::<TimeRes>().elapsed
+    }
+
+    /// Chunks and entities changed since the last snapshot.
+    pub(crate) fn dirty(&self) -> &DirtyChunkTracker {
+        &self.dirty
+    }
+
+    /// Forgets recorded changes once they have been persisted.
+    pub(crate) fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
+
+    /// Movement re-simulation and per-player violation counts.
+    pub fn movement(&self) -> &MovementEnvelope {
+        &self.movement
+    }
+
+    /// Per-connection voxel edit allowance.
+    pub fn edit_limiter(&self) -> &VoxelEditRateLimiter {
+        &self.edit_limiter
+    }
+
+    /// Advances the simulation by one tick and starts a fresh voxel edit
+    /// allowance.
+    pub fn tick(&mut self) {
+        self.world.advance_tick();
+        self.edit_limiter.end_tick();
+        let mut time = self.ecs.resource_mut::<TimeRes>();
+        time.delta = time.fixed_dt as f32;
+        time.elapsed += time.fixed_dt;
+        time.tick += 1;
+    }
+
+    /// Sends every player whose state changed since the last call to all
+    /// logged-in clients.
+    pub fn replicate(&mut self, out: &mut Outbox) {
+        let changed: Vec<PlayerState> = self
+            .world
+            .players()
+            .filter(|ps| self.replicated.get(&ps.player_id) != Some(*ps))
+            .cloned()
+            .collect();
+        let connections: Vec<ConnectionId> = self.sessions().map(|(conn, _)| conn).collect();
+        for state in changed {
+            let update = Message::EntityUpdate(entity_update(&state));
+            for &conn in &connections {
+                out.push((conn, Channel::Unreliable, update.clone()));
+            }
+            self.replicated.insert(state.player_id, state);
+        }
+    }
+}
+
+/// The [`NetworkId`] a player's entity is snapshotted under. Player IDs
+/// count up from 1 for the life of the server, so they stay within the
+/// `u32` ID space.
+pub fn player_network_id(player_id: u64) -> NetworkId {
+    NetworkId::new(player_id as u32)
+}
+
+/// Wire form of a player's state. The entity ID is the player ID.
+pub fn entity_update(state: &PlayerState) -> EntityUpdate {
+    let (pos_x_high, pos_x_low) = split_i128(state.x.into());
+    let (pos_y_high, pos_y_low) = split_i128(state.y.into());
+    let (pos_z_high, pos_z_low) = split_i128(state.z.into());
+    let yaw = state.yaw_mrad as f32 / 1_000.0;
+    let (sin, cos) = (yaw * 0.5).sin_cos();
+    EntityUpdate {
+        entity_id: state.player_id,
+        pos_x_high,
+        pos_x_low,
+        pos_y_high,
+        pos_y_low,
+        pos_z_high,
+        pos_z_low,
+        rot_x: 0.0,
+        rot_y: sin,
+        rot_z: 0.0,
+        rot_w: cos,
+    }
+}
+
+/// Splits a 128-bit coordinate into its high and low 64-bit halves.
+pub fn split_i128(value: i128) -> (i64, i64) {
+    ((value >> 64) as i64, value as i64)
+}
+
+/// Reassembles a 128-bit coordinate from [`split_i128`] halves.
+pub fn join_i128(high: i64, low: i64) -> i128 {
+    (i128::from(high) << 64) | i128::from(low as u64)
+}
+
+#[cfg(test)]
+#[path = "state_tests.rs"]
+mod tests;
@@ -0,0 +1,214 @@
+//! Unit tests for sessions, message dispatch and replication.
+
+use super::*;
+use crate::session::{Session, SessionConnection};
+use nebula_multiplayer::{EditRejection, VoxelEditIntent};
+use nebula_net::{LoginRequest, PlayerPosition};
+
+fn login(state: &mut ServerState, conn: u64, name: &str, out: &mut Outbox) {
+    let request = Message::LoginRequest(LoginRequest::new(name));
+    state.handle_message(ConnectionId(conn), request, out);
+}
+
+fn position_of(player_id: u64, [x, y, z]: [i64; 3]) -> Message {
+    let (pos_x_high, pos_x_low) = split_i128(x.into());
+    let (pos_y_high, pos_y_low) = split_i128(y.into());
+    let (pos_z_high, pos_z_low) = split_i128(z.into());
+    Message::PlayerPosition(PlayerPosition {
+        player_id,
+        pos_x_high,
+        pos_x_low,
+        pos_y_high,
+        pos_y_low,
+        pos_z_high,
+        pos_z_low,
+        input_sequence: 0,
+    })
+}
+
+#[test]
+fn test_i128_split_roundtrip() {
+    for value in [0, -1, 1, i128::from(i64::MIN) - 5, i128::MAX, i128::MIN] {
+        let (high, low) = split_i128(value);
+        assert_eq!(join_i128(high, low), value);
+    }
+}
+
+#[test]
+fn test_login_spawns_player_and_enforces_limit() {
+    let mut config = Config::default();
+    config.network.max_players = 1;
+    let mut state = ServerState::new(&config);
+    let mut out = Outbox::new();
+
+    login(&mut state, 1, "Alice", &mut out);
+    assert_eq!(state.world().player_count(), 1);
+    assert!(matches!(
+        &out[0],
+        (ConnectionId(1), Channel::Reliable, Message::LoginResponse(r)) if r.success
+    ));
+
+    out.clear();
+    login(&mut state, 2, "Bob", &mut out);
+    assert_eq!(state.world().player_count(), 1);
+    assert!(matches!(
+        &out[0],
+        (ConnectionId(2), _, Message::LoginResponse(r)) if !r.success
+    ));
+    assert_eq!(state.find_connection("Alice"), Some(ConnectionId(1)));
+    assert_eq!(state.find_connection("1"), Some(ConnectionId(1)));
+}
+
+#[test]
+fn test_moves_are_validated_and_replicated() {
+    let mut state = ServerState::new(&Config::default());
+    let mut out = Outbox::new();
+    login(&mut state, 1, "Alice", &mut out);
+    login(&mut state, 2, "Bob", &mut out);
+    state.replicate(&mut out);
+    out.clear();
+
+    let [x, y, z] = state.spawn_point();
+    state.handle_message(ConnectionId(1), position_of(1, [x + 150, y, z]), &mut out);
+    assert_eq!(state.world().find_player(1).map(|p| p.x), Some(x + 150));
+    state.replicate(&mut out);
+    let updates: Vec<_> = out
+        .iter()
+        .filter_map(|(conn, _, msg)| match msg {
+            Message::EntityUpdate(u) => Some((*conn, u.entity_id)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(updates.len(), 2, "both clients see the move: {updates:?}");
+    assert!(updates.iter().all(|&(_, id)| id == 1));
+
+    // A teleport is rejected and the sender is corrected.
+    out.clear();
+    state.handle_message(
+        ConnectionId(1),
+        position_of(1, [x + 50_000, y, z]),
+        &mut out,
+    );
+    assert_eq!(state.world().find_player(1).map(|p| p.x), Some(x + 150));
+    assert!(matches!(
+        &out[..],
+        [(ConnectionId(1), Channel::Reliable, Message::EntityUpdate(_))]
+    ));
+}
+
+#[test]
+fn test_flying_up_is_corrected_by_the_envelope() {
+    let mut state = ServerState::new(&Config::default());
+    let mut out = Outbox::new();
+    login(&mut state, 1, "Alice", &mut out);
+    out.clear();
+
+    // Rising 100 mm in one tick passes the flat distance cap but not
+    // the ballistic arc of a player already in the air.
+    let [x, y, z] = state.spawn_point();
+    state.handle_message(ConnectionId(1), position_of(1, [x, y + 100, z]), &mut out);
+    let corrected = state.world().find_player(1).unwrap();
+    assert!(corrected.y < y);
+    assert_eq!(state.movement().violations(1), 1);
+    let [(ConnectionId(1), Channel::Reliable, Message::PlayerCorrection(correction))] = &out[..]
+    else {
+        panic!("expected a correction for the mover, got {out:?}");
+    };
+    assert_eq!(correction.player_id, 1);
+    assert_eq!(correction.tick, state.world().tick());
+    assert_eq!(
+        [correction.x, correction.y, correction.z],
+        [x, corrected.y, z]
+    );
+    assert!(correction.vy < 0, "falling, not rising");
+}
+
+#[test]
+fn test_voxel_edits_are_rate_limited_per_tick() {
+    let mut state = ServerState::new(&Config::default());
+    let mut out = Outbox::new();
+    login(&mut state, 1, "Alice", &mut out);
+
+    let size = i64::from(ServerChunkStore::CHUNK_SIZE);
+    let below = state.spawn_point()[1] / 1_000 - SPAWN_CLEARANCE_MM / 1_000 - 1;
+    let intent = VoxelEditIntent::Remove {
+        chunk_id: nebula_multiplayer::ChunkId {
+            face: 0,
+            lod: 0,
+            x: 0,
+            y: below.div_euclid(size) as i32,
+            z: 0,
+        },
+        local_x: 0,
+        local_y: below.rem_euclid(size) as u32,
+        local_z: 0,
+    };
+    let event = state.edit_voxel(ConnectionId(1), &intent);
+    assert!(event.is_ok(), "{event:?}");
+    assert_eq!(state.dirty().chunks().count(), 1);
+
+    let limiter = state.edit_limiter();
+    let allowance = limiter.max_edits_per_tick as usize;
+    let limited = (1..100)
+        .filter(|_| state.edit_voxel(ConnectionId(1), &intent) == Err(EditRejection::RateLimited))
+        .count();
+    assert_eq!(limited, 100 - allowance);
+
+    state.tick();
+    assert_eq!(
+        state.edit_voxel(ConnectionId(1), &intent),
+        Err(EditRejection::AlreadyEmpty),
+        "a new tick restores the per-tick allowance"
+    );
+
+    state.drop_connection(ConnectionId(1), "logged out", &mut out);
+    let limiter = state.edit_limiter();
+    assert_eq!(limiter.burst_tokens(1), 0);
+    assert_eq!(
+        state.edit_voxel(ConnectionId(1), &intent),
+        Err(EditRejection::UnknownPlayer)
+    );
+}
+
+#[test]
+fn test_closed_connections_are_reaped() {
+    let mut state = ServerState::new(&Config::default());
+    let mut out = Outbox::new();
+    login(&mut state, 1, "Alice", &mut out);
+    login(&mut state, 2, "Bob", &mut out);
+    out.clear();
+
+    state.reap_connections(&[ConnectionId(2)], &mut out);
+    assert_eq!(state.world().player_count(), 1);
+    assert_eq!(state.sessions().count(), 1);
+    assert!(matches!(
+        &out[..],
+        [(ConnectionId(2), Channel::Reliable, Message::Logout(l))] if l.player_id == 1
+    ));
+}
+
+#[test]
+fn test_sessions_and_clock_live_in_the_ecs_world() {
+    let mut config = Config::default();
+    config.server.tick_rate = 20;
+    let mut state = ServerState::new(&config);
+    let mut out = Outbox::new();
+    login(&mut state, 7, "Alice", &mut out);
+
+    let entity = state
+        .ecs()
+        .iter_entities()
+        .find(|e| e.get::<SessionConnection>() == Some(&SessionConnection(ConnectionId(7))))
+        .expect("session entity");
+    assert_eq!(entity.get::<Session>().map(|s| s.player_id), Some(1));
+
+    for _ in 0..10 {
+        state.tick();
+    }
+    let time = state.ecs().resource::<TimeRes>();
+    assert_eq!(time.tick, 10);
+    assert!((state.world_time() - 0.5).abs() < 1e-9);
+
+    state.drop_connection(ConnectionId(7), "logged out", &mut out);
+    assert_eq!(state.ecs().entities().len(), 0);
+}
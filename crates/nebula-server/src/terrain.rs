@@ -0,0 +1,132 @@
+//! Terrain generation for the chunks around the spawn point.
+//!
+//! The dedicated server keeps a flat local frame around spawn (Y up, one
+//! voxel per metre, chunk `(x, y, z)` starting at `x * CHUNK_SIZE` metres)
+//! matching the layout [`ServerChunkStore`] and the movement checks use.
+
+use nebula_multiplayer::{ChunkId, ServerChunkStore, VoxelMaterial};
+use nebula_terrain::{HeightmapParams, HeightmapSampler};
+
+/// Chunks generated in each horizontal direction around spawn.
+pub const SPAWN_RADIUS_CHUNKS: i32 = 2;
+
+/// Depth of the dirt layer above stone, in voxels.
+const DIRT_DEPTH: i64 = 3;
+
+/// Heightmap shaping for the spawn area: gentle hills a few metres high.
+fn spawn_heightmap(seed: u64) -> HeightmapSampler {
+    HeightmapSampler::new(HeightmapParams {
+        seed,
+        octaves: 4,
+        amplitude: 6.0,
+        base_frequency: 0.02,
+        ..HeightmapParams::default()
+    })
+}
+
+/// Terrain surface height (in whole metres) of the column at `(x, z)`.
+pub fn surface_height(heightmap: &HeightmapSampler, x: i64, z: i64) -> i64 {
+    heightmap.sample(x as f64, z as f64).floor() as i64
+}
+
+/// Generates every chunk within [`SPAWN_RADIUS_CHUNKS`] of the origin into
+/// `store` and returns the surface height at the spawn column `(0, 0)`.
+///
+/// Generation depends only on `seed`, so every server started with the same
+/// seed produces the same terrain.
+pub fn generate_spawn_area(store: &mut ServerChunkStore, seed: u64) -> i64 {
+    let heightmap = spawn_heightmap(seed);
+    let size = ServerChunkStore::CHUNK_SIZE as i64;
+    let max_height = heightmap.max_amplitude().ceil() as i64;
+    let chunk_y_range = (-max_height - DIRT_DEPTH).div_euclid(size)..=max_height.div_euclid(size);
+
+    for cx in -SPAWN_RADIUS_CHUNKS..=SPAWN_RADIUS_CHUNKS {
+        for cz in -SPAWN_RADIUS_CHUNKS..=SPAWN_RADIUS_CHUNKS {
+            let heights: Vec<i64> = (0..size * size)
+                .map(|i| {
+                    let (lx, lz) = (i / size, i % size);
+                    surface_height(
+                        &heightmap,
+                        i64::from(cx) * size + lx,
+                        i64::from(cz) * size + lz,
+                    )
+                })
+                .collect();
+            for cy in chunk_y_range.clone() {
+                let id = ChunkId {
+                    face: 0,
+                    lod: 0,
+                    x: cx,
+                    y: cy as i32,
+                    z: cz,
+                };
+                store.insert_chunk(id, chunk_voxels(&heights, cy, size));
+            }
+        }
+    }
+    surface_height(&heightmap, 0, 0)
+}
+
+/// Voxels of the chunk at vertical index `cy` given its column heights.
+fn chunk_voxels(heights: &[i64], cy: i64, size: i64) -> Vec<VoxelMaterial> {
+    let mut voxels = Vec::with_capacity((size * size * size) as usize);
+    for lx in 0..size {
+        for ly in 0..size {
+            let y = cy * size + ly;
+            for lz in 0..size {
+                let surface = heights[(lx * size + lz) as usize];
+                voxels.push(if y >= surface {
+                    VoxelMaterial::AIR
+                } else if y >= surface - DIRT_DEPTH {
+                    VoxelMaterial::DIRT
+                } else {
+                    VoxelMaterial::STONE
+                });
+            }
+        }
+    }
+    voxels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawn_column_is_solid_below_and_open_above() {
+        let mut store = ServerChunkStore::new();
+        let surface = generate_spawn_area(&mut store, 7);
+        let size = ServerChunkStore::CHUNK_SIZE as i64;
+
+        let voxel_at = |y: i64| {
+            let id = ChunkId {
+                face: 0,
+                lod: 0,
+                x: 0,
+                y: y.div_euclid(size) as i32,
+                z: 0,
+            };
+            store.get_voxel(&id, 0, y.rem_euclid(size) as u32, 0)
+        };
+        assert_eq!(voxel_at(surface - 1), Some(VoxelMaterial::DIRT));
+        assert_eq!(voxel_at(surface), Some(VoxelMaterial::AIR));
+        assert_eq!(
+            voxel_at(surface - DIRT_DEPTH - 1),
+            Some(VoxelMaterial::STONE)
+        );
+    }
+
+    #[test]
+    fn test_generation_is_deterministic_per_seed() {
+        let mut a = ServerChunkStore::new();
+        let mut b = ServerChunkStore::new();
+        assert_eq!(
+            generate_spawn_area(&mut a, 11),
+            generate_spawn_area(&mut b, 11)
+        );
+        assert_eq!(a.chunks().count(), b.chunks().count());
+        for (id, voxels) in a.chunks() {
+            assert_eq!(b.chunk(id), Some(voxels));
+        }
+    }
+}
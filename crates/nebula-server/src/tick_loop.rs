@@ -0,0 +1,93 @@
+//! Fixed-rate simulation clock with bounded catch-up.
+//!
+//! Wraps [`ServerTickSchedule`] so that a stalled frame (a slow save, a GC
+//! pause in the host, a debugger break) never makes the server try to
+//! simulate an unbounded backlog in one go. Ticks beyond
+//! `max_catch_up_ticks` are shed: the simulation falls behind wall-clock
+//! time instead of spiralling further behind while it catches up.
+
+use std::time::Duration;
+
+use nebula_multiplayer::ServerTickSchedule;
+
+/// Ticks to simulate for one frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TickBudget {
+    /// Ticks that should be simulated now.
+    pub run: u32,
+    /// Ticks that were due but dropped to shed load.
+    pub shed: u32,
+}
+
+/// Fixed-rate tick loop with catch-up limiting and overload shedding.
+pub struct TickLoop {
+    schedule: ServerTickSchedule,
+    max_catch_up_ticks: u32,
+    shed_total: u64,
+}
+
+impl TickLoop {
+    /// Creates a loop ticking at `tick_rate` Hz that simulates at most
+    /// `max_catch_up_ticks` ticks per frame (at least one).
+    pub fn new(tick_rate: u32, max_catch_up_ticks: u32) -> Self {
+        Self {
+            schedule: ServerTickSchedule::with_tick_rate(tick_rate.max(1)),
+            max_catch_up_ticks: max_catch_up_ticks.max(1),
+            shed_total: 0,
+        }
+    }
+
+    /// Accounts for `elapsed` wall-clock time and returns how many ticks to
+    /// simulate now.
+    pub fn advance(&mut self, elapsed: Duration) -> TickBudget {
+        let due = self.schedule.accumulate(elapsed.as_secs_f64());
+        let run = due.min(self.max_catch_up_ticks);
+        let shed = due - run;
+        self.shed_total += u64::from(shed);
+        TickBudget { run, shed }
+    }
+
+    /// Duration of a single tick.
+    pub fn tick_duration(&self) -> Duration {
+        Duration::from_secs_f64(self.schedule.tick_duration_secs())
+    }
+
+    /// Ticks dropped since creation because the server was overloaded.
+    pub fn shed_total(&self) -> u64 {
+        self.shed_total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs_due_ticks_at_fixed_rate() {
+        let mut ticks = TickLoop::new(60, 5);
+        assert_eq!(ticks.advance(Duration::from_millis(10)).run, 0);
+        assert_eq!(ticks.advance(Duration::from_millis(10)).run, 1);
+        assert_eq!(
+            ticks.advance(Duration::from_secs_f64(3.0 / 60.0)),
+            TickBudget { run: 3, shed: 0 }
+        );
+    }
+
+    #[test]
+    fn test_sheds_backlog_beyond_catch_up_limit() {
+        let mut ticks = TickLoop::new(60, 5);
+        let budget = ticks.advance(Duration::from_millis(1_005));
+        assert_eq!(budget, TickBudget { run: 5, shed: 55 });
+        assert_eq!(ticks.shed_total(), 55);
+
+        // The dropped backlog is not replayed on the next frame.
+        assert_eq!(ticks.advance(Duration::ZERO).run, 0);
+    }
+
+    #[test]
+    fn test_zero_limits_are_clamped() {
+        let mut ticks = TickLoop::new(0, 0);
+        assert_eq!(ticks.tick_duration(), Duration::from_secs(1));
+        assert_eq!(ticks.advance(Duration::from_secs(3)).run, 1);
+    }
+}
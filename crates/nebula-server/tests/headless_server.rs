@@ -0,0 +1,149 @@
+//! Boots the headless server on an ephemeral port and drives it with real
+//! clients.
+
+use std::time::Duration;
+
+use nebula_config::Config;
+use nebula_net::{
+    Channel, EntityUpdate, GameClient, LoginRequest, Message, PlayerPosition, TransportKind,
+    UdpConfig, UdpListener,
+};
+use nebula_server::{AdminCommand, HeadlessServer, join_i128, split_i128};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Receives messages until `pick` accepts one.
+async fn expect<T>(client: &mut GameClient, mut pick: impl FnMut(Message) -> Option<T>) -> T {
+    tokio::time::timeout(TIMEOUT, async {
+        loop {
+            let message = client.recv().await.expect("connection closed");
+            if let Some(found) = pick(message) {
+                return found;
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for message")
+}
+
+/// Logs in as `name` and returns the assigned player ID.
+async fn login(client: &mut GameClient, name: &str) -> u64 {
+    let request = Message::LoginRequest(LoginRequest::new(name));
+    client.send(Channel::Reliable, &request).await.unwrap();
+    expect(client, |msg| match msg {
+        Message::LoginResponse(r) if r.success => Some(r.player_id),
+        _ => None,
+    })
+    .await
+}
+
+/// Waits for an update of `player_id` matching `accept`.
+async fn expect_update(
+    client: &mut GameClient,
+    player_id: u64,
+    accept: impl Fn(&EntityUpdate) -> bool,
+) -> EntityUpdate {
+    expect(client, |msg| match msg {
+        Message::EntityUpdate(u) if u.entity_id == player_id && accept(&u) => Some(u),
+        _ => None,
+    })
+    .await
+}
+
+/// Drains `client` and asserts that the server closed the connection.
+async fn assert_disconnected(client: &mut GameClient) {
+    tokio::time::timeout(TIMEOUT, async { while client.recv().await.is_some() {} })
+        .await
+        .expect("server should close the connection");
+}
+
+#[tokio::test]
+async fn test_movement_replicates_between_clients_and_shutdown_saves() {
+    let save_dir = tempfile::tempdir().unwrap();
+    let mut config = Config::default();
+    config.server.save_dir = save_dir.path().display().to_string();
+    config.server.autosave_interval_seconds = 0;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (commands_tx, commands) = mpsc::channel(4);
+    let server = tokio::spawn(HeadlessServer::new(config).run_with_listener(listener, commands));
+
+    let mut alice = GameClient::connect_with(addr, TransportKind::Tcp)
+        .await
+        .unwrap();
+    let mut bob = GameClient::connect_with(addr, TransportKind::Tcp)
+        .await
+        .unwrap();
+    let alice_id = login(&mut alice, "Alice").await;
+    let bob_id = login(&mut bob, "Bob").await;
+    assert_ne!(alice_id, bob_id);
+
+    // Bob learns where Alice spawned.
+    let spawn = expect_update(&mut bob, alice_id, |_| true).await;
+    let x = join_i128(spawn.pos_x_high, spawn.pos_x_low);
+
+    // Alice steps 150 mm along +X; Bob sees it.
+    let (pos_x_high, pos_x_low) = split_i128(x + 150);
+    let step = Message::PlayerPosition(PlayerPosition {
+        player_id: alice_id,
+        pos_x_high,
+        pos_x_low,
+        pos_y_high: spawn.pos_y_high,
+        pos_y_low: spawn.pos_y_low,
+        pos_z_high: spawn.pos_z_high,
+        pos_z_low: spawn.pos_z_low,
+        input_sequence: 1,
+    });
+    alice.send(Channel::Unreliable, &step).await.unwrap();
+    let moved = expect_update(&mut bob, alice_id, |u| {
+        join_i128(u.pos_x_high, u.pos_x_low) == x + 150
+    })
+    .await;
+    assert_eq!(moved.pos_y_low, spawn.pos_y_low);
+
+    commands_tx.send(AdminCommand::Stop).await.unwrap();
+    tokio::time::timeout(TIMEOUT, server)
+        .await
+        .expect("server did not stop")
+        .unwrap()
+        .unwrap();
+
+    // Both clients are disconnected and the world was saved.
+    assert_disconnected(&mut alice).await;
+    assert_disconnected(&mut bob).await;
+    let saved = std::fs::read_dir(save_dir.path()).unwrap().count();
+    assert_eq!(saved, 1, "shutdown should write one full snapshot");
+}
+
+#[tokio::test]
+async fn test_udp_transport_from_config() {
+    let save_dir = tempfile::tempdir().unwrap();
+    let mut config = Config::default();
+    config.server.save_dir = save_dir.path().display().to_string();
+    config.server.autosave_interval_seconds = 0;
+    config.network.transport = TransportKind::Udp;
+
+    let listener = UdpListener::bind("127.0.0.1:0".parse().unwrap(), UdpConfig::default())
+        .await
+        .unwrap();
+    let addr = listener.local_addr();
+    let (commands_tx, commands) = mpsc::channel(4);
+    let server =
+        tokio::spawn(HeadlessServer::new(config).run_with_udp_listener(listener, commands));
+
+    let mut client = GameClient::connect_with(addr, TransportKind::Udp)
+        .await
+        .unwrap();
+    let player_id = login(&mut client, "Carol").await;
+    expect_update(&mut client, player_id, |_| true).await;
+
+    commands_tx.send(AdminCommand::Stop).await.unwrap();
+    tokio::time::timeout(TIMEOUT, server)
+        .await
+        .expect("server did not stop")
+        .unwrap()
+        .unwrap();
+}
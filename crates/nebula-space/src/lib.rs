@@ -8,9 +8,9 @@ pub mod sun;
 
 pub use nebula::{NebulaConfig, NebulaGenerator, NebulaLayer};
 pub use planet_impostor::{
-    DistantPlanet, ImpostorInstance, OrbitalElements, PlanetImpostorRenderer,
-    billboard_local_sun_dir,
+    DistantPlanet, ImpostorAppearance, ImpostorInstance, OrbitalElements, PlanetImpostorRenderer,
+    billboard_local_sun_dir, registry_impostors,
 };
-pub use skybox::SkyboxRenderer;
+pub use skybox::{SKYBOX_DEPTH, SkyboxRenderer, SkyboxUniform, skybox_view};
 pub use starfield::{StarPoint, StarfieldCubemap, StarfieldGenerator, blackbody_to_rgb};
-pub use sun::{StarType, SunProperties, SunRenderer};
+pub use sun::{StarType, SunProperties, SunRenderer, corona_falloff, limb_darkening};
@@ -9,9 +9,11 @@ use bytemuck::{Pod, Zeroable};
 use nebula_render::Camera;
 
 mod orbital;
+mod registry;
 mod renderer;
 
 pub use orbital::OrbitalElements;
+pub use registry::{ImpostorAppearance, registry_impostors};
 pub use renderer::PlanetImpostorRenderer;
 
 /// Data describing a distant planet for impostor rendering.
@@ -0,0 +1,126 @@
+//! Building the impostor list from the planet registry.
+
+use nebula_cubesphere::{DistanceMode, PlanetDef, PlanetId, PlanetRegistry};
+use nebula_math::{UNITS_PER_METER, WorldPosition};
+
+use super::DistantPlanet;
+
+/// Surface appearance of a registered planet drawn as an impostor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImpostorAppearance {
+    /// Albedo (reflectivity) in [0, 1].
+    pub albedo: f32,
+    /// Surface color tint (linear RGB).
+    pub color: [f32; 3],
+    /// Atmosphere rim color (linear RGB), or `None` for airless bodies.
+    pub atmosphere: Option<[f32; 3]>,
+}
+
+impl Default for ImpostorAppearance {
+    fn default() -> Self {
+        Self {
+            albedo: 0.3,
+            color: [0.5, 0.5, 0.5],
+            atmosphere: None,
+        }
+    }
+}
+
+impl DistantPlanet {
+    /// Impostor data for a registered planet. The position is the planet's
+    /// center and the radius is converted from mm to meters.
+    pub fn from_planet_def(
+        id: PlanetId,
+        planet: &PlanetDef,
+        appearance: ImpostorAppearance,
+    ) -> Self {
+        Self {
+            id: id.0 as u64,
+            position: [planet.center.x, planet.center.y, planet.center.z],
+            radius: planet.radius as f64 / UNITS_PER_METER as f64,
+            albedo: appearance.albedo,
+            color: appearance.color,
+            has_atmosphere: appearance.atmosphere.is_some(),
+            atmosphere_color: appearance.atmosphere.unwrap_or([0.0; 3]),
+        }
+    }
+}
+
+/// The registered planets to draw as impostors from `camera`, nearest first.
+///
+/// Rebuild this each frame. Planets within `max_distance_m` of the camera
+/// are candidates. The planet whose sphere of influence holds the camera
+/// ([`PlanetRegistry::dominant_influence`]) is always drawn with full
+/// geometry, and the rest switch to impostors once
+/// [`DistantPlanet::should_render_as_impostor`] says they are small enough.
+pub fn registry_impostors(
+    registry: &PlanetRegistry,
+    camera: &WorldPosition,
+    max_distance_m: f64,
+    appearance: impl Fn(PlanetId, &PlanetDef) -> ImpostorAppearance,
+) -> Vec<DistantPlanet> {
+    let anchor = registry.dominant_influence(camera);
+    registry
+        .within(camera, max_distance_m, DistanceMode::Center)
+        .filter(|&(id, _)| Some(id) != anchor)
+        .filter_map(|(id, distance_m)| {
+            let planet = registry.get_by_index(id.0)?;
+            let impostor = DistantPlanet::from_planet_def(id, planet, appearance(id, planet));
+            impostor
+                .should_render_as_impostor(distance_m)
+                .then_some(impostor)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AU_MM: i128 = 149_597_870_700_000;
+
+    fn solar_registry() -> PlanetRegistry {
+        let mut registry = PlanetRegistry::new();
+        for (name, x) in [("Terra", 0), ("Luna", 384_400_000_000), ("Mars", AU_MM / 2)] {
+            registry
+                .register(PlanetDef::earth_like(name, WorldPosition::new(x, 0, 0), 1))
+                .unwrap();
+        }
+        registry
+    }
+
+    #[test]
+    fn test_camera_planet_is_never_an_impostor() {
+        let registry = solar_registry();
+        let camera = WorldPosition::new(0, 6_400_000_000, 0);
+        let ids: Vec<u64> = registry_impostors(&registry, &camera, 1e15, |_, _| {
+            ImpostorAppearance::default()
+        })
+        .iter()
+        .map(|p| p.id)
+        .collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[test]
+    fn test_impostor_switch_follows_angular_size() {
+        let registry = solar_registry();
+        // Between Terra and Luna, in open space: Luna is still large enough
+        // for geometry, far-away Mars is a dot.
+        let camera = WorldPosition::new(200_000_000_000, 0, 400_000_000_000);
+        assert_eq!(registry.dominant_influence(&camera), None);
+        let impostors = registry_impostors(&registry, &camera, 1e15, |_, _| {
+            ImpostorAppearance::default()
+        });
+        let ids: Vec<u64> = impostors.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![2]);
+        assert_eq!(impostors[0].radius, 6_371_000.0);
+
+        // A short search radius drops Mars too.
+        assert!(
+            registry_impostors(&registry, &camera, 1e9, |_, _| ImpostorAppearance::default(
+            ))
+            .is_empty()
+        );
+    }
+}
@@ -1,11 +1,25 @@
 //! Skybox renderer: draws a cubemap starfield behind all scene geometry.
 //!
 //! Uses a fullscreen triangle with inverse view-projection to sample a cubemap texture.
+//! The view's translation is stripped ([`skybox_view`]) so the stars are
+//! infinitely far away, and an orientation set with
+//! [`SkyboxRenderer::set_orientation`] places the starfield in world space,
+//! so it stays fixed while the camera turns.
 
 use bytemuck::{Pod, Zeroable};
 
 use crate::StarfieldCubemap;
 
+/// Clip-space depth the skybox is drawn at: the far plane under the
+/// reverse-Z convention (near = 1, far = 0), behind all scene geometry.
+pub const SKYBOX_DEPTH: f32 = 0.0;
+
+/// `view` with its translation removed, so only the camera's rotation
+/// affects which stars are visible.
+pub fn skybox_view(view: glam::Mat4) -> glam::Mat4 {
+    glam::Mat4::from_mat3(glam::Mat3::from_mat4(view))
+}
+
 /// Uniform buffer for the skybox: inverse view-projection matrix.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
@@ -14,6 +28,18 @@ pub struct SkyboxUniform {
     pub inv_view_proj: [[f32; 4]; 4],
 }
 
+impl SkyboxUniform {
+    /// Uniform for a camera with `view` and `proj`, with the starfield
+    /// rotated by `orientation` in world space. Any translation in `view`
+    /// is ignored.
+    pub fn from_camera(view: glam::Mat4, proj: glam::Mat4, orientation: glam::Quat) -> Self {
+        let view_proj = proj * skybox_view(view) * glam::Mat4::from_quat(orientation);
+        Self {
+            inv_view_proj: view_proj.inverse().to_cols_array_2d(),
+        }
+    }
+}
+
 /// WGSL shader source for the skybox pass.
 pub const SKYBOX_SHADER_SOURCE: &str = r#"
 struct SkyboxUniform {
@@ -39,12 +65,13 @@ fn vs_skybox(@builtin(vertex_index) idx: u32) -> VertexOutput {
     let uv = vec2<f32>(f32((idx << 1u) & 2u), f32(idx & 2u));
     let ndc = uv * 2.0 - 1.0;
 
-    // Use far plane (z=1) for direction reconstruction
+    // Any clip depth reconstructs the same ray once translation is stripped.
     let clip_far = vec4<f32>(ndc.x, ndc.y, 1.0, 1.0);
     let world = skybox.inv_view_proj * clip_far;
     let view_dir = normalize(world.xyz / world.w);
 
     var out: VertexOutput;
+    // Reverse-Z far plane: behind all scene geometry.
     out.position = vec4<f32>(ndc.x, ndc.y, 0.0, 1.0);
     out.view_dir = view_dir;
     return out;
@@ -64,7 +91,10 @@ pub struct SkyboxRenderer {
     pipeline: wgpu::RenderPipeline,
     uniform_buffer: wgpu::Buffer,
     uniform_bind_group: wgpu::BindGroup,
+    cubemap_bgl: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
     cubemap_bind_group: wgpu::BindGroup,
+    orientation: glam::Quat,
 }
 
 impl SkyboxRenderer {
@@ -153,76 +183,13 @@ impl SkyboxRenderer {
             cache: None,
         });
 
-        // Upload cubemap texture
-        let face_size = cubemap.face_size;
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("starfield-cubemap"),
-            size: wgpu::Extent3d {
-                width: face_size,
-                height: face_size,
-                depth_or_array_layers: 6,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-            view_formats: &[],
-        });
-
-        let rgba8_faces = cubemap.to_rgba8();
-        for (i, face_data) in rgba8_faces.iter().enumerate() {
-            queue.write_texture(
-                wgpu::TexelCopyTextureInfo {
-                    texture: &texture,
-                    mip_level: 0,
-                    origin: wgpu::Origin3d {
-                        x: 0,
-                        y: 0,
-                        z: i as u32,
-                    },
-                    aspect: wgpu::TextureAspect::All,
-                },
-                face_data,
-                wgpu::TexelCopyBufferLayout {
-                    offset: 0,
-                    bytes_per_row: Some(face_size * 4),
-                    rows_per_image: Some(face_size),
-                },
-                wgpu::Extent3d {
-                    width: face_size,
-                    height: face_size,
-                    depth_or_array_layers: 1,
-                },
-            );
-        }
-
-        let cubemap_view = texture.create_view(&wgpu::TextureViewDescriptor {
-            dimension: Some(wgpu::TextureViewDimension::Cube),
-            ..Default::default()
-        });
-
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("skybox-sampler"),
             mag_filter: wgpu::FilterMode::Linear,
             min_filter: wgpu::FilterMode::Linear,
             ..Default::default()
         });
-
-        let cubemap_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("skybox-cubemap-bg"),
-            layout: &cubemap_bgl,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&cubemap_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&sampler),
-                },
-            ],
-        });
+        let cubemap_bind_group = upload_cubemap(device, queue, &cubemap_bgl, &sampler, cubemap);
 
         // Uniform buffer
         use wgpu::util::DeviceExt;
@@ -245,30 +212,49 @@ impl SkyboxRenderer {
             }],
         });
 
-        // Count non-black pixels for debug verification
-        let non_black: usize = rgba8_faces
-            .iter()
-            .map(|face| {
-                face.chunks(4)
-                    .filter(|px| px[0] > 0 || px[1] > 0 || px[2] > 0)
-                    .count()
-            })
-            .sum();
-        log::info!(
-            "Skybox renderer initialized: {}x{} cubemap, 6 faces, {} non-black pixels",
-            face_size,
-            face_size,
-            non_black
-        );
+        log::info!("Skybox renderer initialized");
 
         Self {
             pipeline,
             uniform_buffer,
             uniform_bind_group,
+            cubemap_bgl,
+            sampler,
             cubemap_bind_group,
+            orientation: glam::Quat::IDENTITY,
         }
     }
 
+    /// Replace the starfield with `cubemap`, for example one captured or
+    /// regenerated after the renderer was created.
+    pub fn set_starfield(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        cubemap: &StarfieldCubemap,
+    ) {
+        self.cubemap_bind_group =
+            upload_cubemap(device, queue, &self.cubemap_bgl, &self.sampler, cubemap);
+    }
+
+    /// Set the starfield's rotation in world space, applied by
+    /// [`update_camera`](Self::update_camera).
+    pub fn set_orientation(&mut self, orientation: glam::Quat) {
+        self.orientation = orientation;
+    }
+
+    /// The starfield's rotation in world space.
+    pub fn orientation(&self) -> glam::Quat {
+        self.orientation
+    }
+
+    /// Update the skybox for a camera with `view` and `proj`. Translation in
+    /// `view` is stripped, so stars stay infinitely far away.
+    pub fn update_camera(&self, queue: &wgpu::Queue, view: glam::Mat4, proj: glam::Mat4) {
+        let uniform = SkyboxUniform::from_camera(view, proj, self.orientation);
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniform]));
+    }
+
     /// Update the skybox uniform with a new inverse view-projection matrix.
     ///
     /// The matrix should be rotation-only (strip translation from view matrix)
@@ -288,3 +274,162 @@ impl SkyboxRenderer {
         pass.draw(0..3, 0..1);
     }
 }
+
+/// Upload `cubemap` to a new cube texture and bind it with `sampler`.
+fn upload_cubemap(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    layout: &wgpu::BindGroupLayout,
+    sampler: &wgpu::Sampler,
+    cubemap: &StarfieldCubemap,
+) -> wgpu::BindGroup {
+    let face_size = cubemap.face_size;
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("starfield-cubemap"),
+        size: wgpu::Extent3d {
+            width: face_size,
+            height: face_size,
+            depth_or_array_layers: 6,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+
+    let rgba8_faces = cubemap.to_rgba8();
+    for (i, face_data) in rgba8_faces.iter().enumerate() {
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: 0,
+                    y: 0,
+                    z: i as u32,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            face_data,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(face_size * 4),
+                rows_per_image: Some(face_size),
+            },
+            wgpu::Extent3d {
+                width: face_size,
+                height: face_size,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    let cubemap_view = texture.create_view(&wgpu::TextureViewDescriptor {
+        dimension: Some(wgpu::TextureViewDimension::Cube),
+        ..Default::default()
+    });
+
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("skybox-cubemap-bg"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&cubemap_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    });
+
+    // Count non-black pixels for debug verification
+    let non_black: usize = rgba8_faces
+        .iter()
+        .map(|face| {
+            face.chunks(4)
+                .filter(|px| px[0] > 0 || px[1] > 0 || px[2] > 0)
+                .count()
+        })
+        .sum();
+    log::info!(
+        "Skybox starfield uploaded: {}x{} cubemap, 6 faces, {} non-black pixels",
+        face_size,
+        face_size,
+        non_black
+    );
+    bind_group
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StarfieldGenerator;
+    use glam::{Mat4, Quat, Vec3, Vec4};
+
+    fn test_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+        pollster::block_on(async {
+            let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+                backends: wgpu::Backends::all(),
+                ..Default::default()
+            });
+            let adapter = instance
+                .request_adapter(&wgpu::RequestAdapterOptions::default())
+                .await
+                .ok()?;
+            adapter
+                .request_device(&wgpu::DeviceDescriptor::default())
+                .await
+                .ok()
+        })
+    }
+
+    /// World-space direction the skybox samples at the center of the screen.
+    fn center_direction(uniform: &SkyboxUniform) -> Vec3 {
+        let world =
+            Mat4::from_cols_array_2d(&uniform.inv_view_proj) * Vec4::new(0.0, 0.0, 1.0, 1.0);
+        (world.truncate() / world.w).normalize()
+    }
+
+    #[test]
+    fn test_starfield_view_strips_translation() {
+        let cubemap = StarfieldCubemap::render(&StarfieldGenerator::new(7, 200).generate(), 16);
+        if let Some((device, queue)) = test_device() {
+            let mut skybox =
+                SkyboxRenderer::new(&device, &queue, wgpu::TextureFormat::Rgba16Float, &cubemap);
+            skybox.set_starfield(&device, &queue, &cubemap);
+            skybox.set_orientation(Quat::from_rotation_y(0.3));
+            assert_eq!(skybox.orientation(), Quat::from_rotation_y(0.3));
+        }
+
+        let rotation = Quat::from_euler(glam::EulerRot::YXZ, 0.7, -0.2, 0.1);
+        let at_origin = Mat4::from_quat(rotation.inverse());
+        let far_away =
+            Mat4::from_rotation_translation(rotation.inverse(), Vec3::new(4.0e9, -2.0e7, 1.5e12));
+        assert_eq!(skybox_view(far_away).w_axis, Vec4::W);
+
+        let proj = Mat4::perspective_rh(70f32.to_radians(), 16.0 / 9.0, 0.1, 100.0);
+        let a = SkyboxUniform::from_camera(at_origin, proj, Quat::IDENTITY);
+        let b = SkyboxUniform::from_camera(far_away, proj, Quat::IDENTITY);
+        assert!(center_direction(&a).abs_diff_eq(center_direction(&b), 1e-5));
+        assert!(center_direction(&a).abs_diff_eq(rotation * Vec3::NEG_Z, 1e-4));
+    }
+
+    #[test]
+    fn test_orientation_rotates_starfield_in_world() {
+        let proj = Mat4::perspective_rh(70f32.to_radians(), 1.0, 0.1, 100.0);
+        let sky = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        // Looking down world -Z samples the cubemap direction the sky's
+        // rotation carries onto -Z.
+        let uniform = SkyboxUniform::from_camera(Mat4::IDENTITY, proj, sky);
+        assert!(center_direction(&uniform).abs_diff_eq(sky.inverse() * Vec3::NEG_Z, 1e-5));
+    }
+
+    #[test]
+    fn test_skybox_drawn_at_reverse_z_far_plane() {
+        assert_eq!(SKYBOX_DEPTH, nebula_render::DepthBuffer::CLEAR_VALUE);
+    }
+}
@@ -2,11 +2,16 @@
 //!
 //! The nearest star is rendered as a bright billboard quad with a multi-layered
 //! disk-and-corona shader. HDR output values far exceed 1.0 to drive bloom.
+//! The disk takes its color from the star's blackbody temperature and dims
+//! toward its rim ([`limb_darkening`]); the corona glow fades out at a radius
+//! that depends on the [`StarType`] ([`corona_falloff`]).
 
 use bytemuck::{Pod, Zeroable};
 
 use nebula_render::Camera;
 
+use crate::starfield::blackbody_to_rgb;
+
 /// Spectral classification of a star, determining its color and temperature.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum StarType {
@@ -52,6 +57,54 @@ impl StarType {
             StarType::M => 3050.0,
         }
     }
+
+    /// Linear limb-darkening coefficient: the fraction of center brightness
+    /// lost at the very rim of the disk. Cooler photospheres darken more.
+    pub fn limb_darkening(&self) -> f32 {
+        match self {
+            StarType::O => 0.3,
+            StarType::B => 0.35,
+            StarType::A => 0.45,
+            StarType::F => 0.55,
+            StarType::G => 0.6,
+            StarType::K => 0.7,
+            StarType::M => 0.8,
+        }
+    }
+
+    /// Outer radius of the corona glow, in disk radii. Hotter, more luminous
+    /// stars carry a wider glow.
+    pub fn corona_radius(&self) -> f32 {
+        match self {
+            StarType::O => 5.0,
+            StarType::B => 4.5,
+            StarType::A => 4.0,
+            StarType::F => 3.5,
+            StarType::G => 3.0,
+            StarType::K => 2.75,
+            StarType::M => 2.5,
+        }
+    }
+}
+
+/// Brightness of the disk relative to its center at `radius` (0 at the
+/// center, 1 at the rim), using the linear law `1 − u·(1 − μ)` where `μ` is
+/// the cosine of the angle between the line of sight and the surface normal.
+///
+/// Must match `limb_darkening` in [`SUN_SHADER_SOURCE`].
+pub fn limb_darkening(radius: f32, coefficient: f32) -> f32 {
+    let mu = (1.0 - radius.clamp(0.0, 1.0).powi(2)).sqrt();
+    1.0 - coefficient.clamp(0.0, 1.0) * (1.0 - mu)
+}
+
+/// Corona glow at `radius` disk radii from the center, fading from `1.0` at
+/// the rim to `0.0` at `corona_radius`.
+///
+/// Must match `corona_falloff` in [`SUN_SHADER_SOURCE`].
+pub fn corona_falloff(radius: f32, corona_radius: f32) -> f32 {
+    let r = radius.max(1.0);
+    let t = ((r - 1.0) / (corona_radius - 1.0).max(f32::EPSILON)).clamp(0.0, 1.0);
+    (1.0 - t) * (1.0 - t) / (r * r)
 }
 
 /// Properties of the sun (nearest star) for rendering.
@@ -67,9 +120,34 @@ pub struct SunProperties {
     pub star_type: StarType,
     /// Base luminosity multiplier (1.0 = Sol-like).
     pub luminosity: f32,
+    /// Limb-darkening coefficient, see [`limb_darkening`].
+    pub limb_darkening: f32,
+    /// Outer radius of the corona glow in disk radii; the billboard is sized
+    /// to it.
+    pub corona_radius: f32,
 }
 
 impl SunProperties {
+    /// Sun properties with limb darkening and corona radius taken from
+    /// `star_type`.
+    pub fn new(
+        direction: glam::Vec3,
+        physical_diameter: f64,
+        distance: f64,
+        star_type: StarType,
+        luminosity: f32,
+    ) -> Self {
+        Self {
+            direction,
+            physical_diameter,
+            distance,
+            star_type,
+            luminosity,
+            limb_darkening: star_type.limb_darkening(),
+            corona_radius: star_type.corona_radius(),
+        }
+    }
+
     /// Compute the angular diameter in radians as seen from the camera.
     pub fn angular_diameter(&self) -> f32 {
         (self.physical_diameter / self.distance) as f32
@@ -80,6 +158,11 @@ impl SunProperties {
     pub fn hdr_brightness(&self) -> f32 {
         self.luminosity * 50.0
     }
+
+    /// Linear RGB color of the disk from the star's blackbody temperature.
+    pub fn disk_color(&self) -> [f32; 3] {
+        blackbody_to_rgb(self.star_type.temperature_k())
+    }
 }
 
 /// GPU vertex for the sun billboard quad.
@@ -121,8 +204,10 @@ pub struct SunUniforms {
     pub disk_radius_uv: f32,
     /// Animation time in seconds.
     pub time: f32,
+    /// Limb-darkening coefficient.
+    pub limb_darkening: f32,
     /// Padding for 16-byte alignment.
-    pub _padding: [f32; 2],
+    pub _padding: f32,
 }
 
 /// WGSL shader source for the sun corona billboard.
@@ -132,6 +217,8 @@ struct SunUniforms {
     brightness: f32,
     disk_radius_uv: f32,
     time: f32,
+    limb_darkening: f32,
+    _padding: f32,
 };
 
 struct VertexInput {
@@ -161,6 +248,20 @@ fn vs_sun(in: VertexInput) -> VertexOutput {
     return out;
 }
 
+// Disk brightness relative to its center at `radius` (0 center, 1 rim).
+fn limb_darkening(radius: f32, coefficient: f32) -> f32 {
+    let r = clamp(radius, 0.0, 1.0);
+    let mu = sqrt(1.0 - r * r);
+    return 1.0 - clamp(coefficient, 0.0, 1.0) * (1.0 - mu);
+}
+
+// Corona glow at `radius` disk radii, fading to zero at `corona_radius`.
+fn corona_falloff(radius: f32, corona_radius: f32) -> f32 {
+    let r = max(radius, 1.0);
+    let t = clamp((r - 1.0) / max(corona_radius - 1.0, 1e-6), 0.0, 1.0);
+    return (1.0 - t) * (1.0 - t) / (r * r);
+}
+
 // Procedural noise for corona rays.
 fn corona_noise(uv: vec2<f32>, time: f32) -> f32 {
     let angle = atan2(uv.y, uv.x);
@@ -183,15 +284,19 @@ fn fs_sun(in: VertexOutput) -> @location(0) vec4<f32> {
     let uv = in.uv;
     let dist = length(uv);
 
-    // Central disk: bright, solid color.
-    let disk_edge = smoothstep(sun.disk_radius_uv, sun.disk_radius_uv * 0.9, dist);
-    let disk = disk_edge * sun.brightness;
+    // The billboard spans the corona, so the disk is `disk_radius_uv` wide
+    // and the corona ends at the quad's edge.
+    let disk_r = dist / sun.disk_radius_uv;
+    let corona_radius = 1.0 / sun.disk_radius_uv;
 
-    // Corona: animated radial glow beyond the disk edge.
-    let corona = corona_noise(uv, sun.time) * sun.brightness * 0.5;
+    // Central disk, dimming toward its limb.
+    let disk_edge = smoothstep(sun.disk_radius_uv, sun.disk_radius_uv * 0.9, dist);
+    let disk = disk_edge * limb_darkening(disk_r, sun.limb_darkening) * sun.brightness;
 
-    // Radial gradient falloff for the overall glow.
-    let glow = exp(-dist * dist * 4.0) * sun.brightness * 0.3;
+    // Corona: animated rays and a smooth glow, both fading out by the edge.
+    let falloff = corona_falloff(disk_r, corona_radius);
+    let corona = corona_noise(uv, sun.time) * falloff * sun.brightness * 0.5;
+    let glow = falloff * sun.brightness * 0.3;
 
     let total_brightness = disk + corona + glow;
     let final_color = sun.color * total_brightness;
@@ -323,7 +428,8 @@ impl SunRenderer {
             brightness: 50.0,
             disk_radius_uv: 0.33,
             time: 0.0,
-            _padding: [0.0; 2],
+            limb_darkening: StarType::G.limb_darkening(),
+            _padding: 0.0,
         };
         let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("sun-uniforms"),
@@ -377,8 +483,7 @@ impl SunRenderer {
         time: f32,
     ) {
         let angular_radius = sun.angular_diameter() * 0.5;
-        // Corona extends 3x the disk radius.
-        let corona_radius = angular_radius * 3.0;
+        let corona_radius = angular_radius * sun.corona_radius.max(1.0);
 
         // Billboard axes: camera's right and up vectors.
         let right = camera.right() * corona_radius;
@@ -409,11 +514,12 @@ impl SunRenderer {
         queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&vertices));
 
         let uniforms = SunUniforms {
-            color: sun.star_type.color(),
+            color: sun.disk_color(),
             brightness: sun.hdr_brightness(),
             disk_radius_uv: angular_radius / corona_radius,
             time,
-            _padding: [0.0; 2],
+            limb_darkening: sun.limb_darkening,
+            _padding: 0.0,
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
 
@@ -436,170 +542,5 @@ impl SunRenderer {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use glam::{Quat, Vec3};
-
-    #[test]
-    fn test_sun_disk_faces_camera_from_all_angles() {
-        let test_rotations = [
-            Quat::IDENTITY,
-            Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
-            Quat::from_rotation_x(std::f32::consts::FRAC_PI_4),
-            Quat::from_rotation_z(std::f32::consts::PI),
-            Quat::from_euler(glam::EulerRot::YXZ, 1.0, 0.5, 0.3),
-        ];
-
-        for (i, rotation) in test_rotations.iter().enumerate() {
-            let camera = Camera {
-                rotation: *rotation,
-                ..Camera::default()
-            };
-            let right = camera.right();
-            let up = camera.up();
-            let forward = camera.forward();
-
-            assert!(
-                right.dot(forward).abs() < 1e-5,
-                "Rotation {i}: billboard right is not perpendicular to forward"
-            );
-            assert!(
-                up.dot(forward).abs() < 1e-5,
-                "Rotation {i}: billboard up is not perpendicular to forward"
-            );
-        }
-    }
-
-    #[test]
-    fn test_corona_animates_over_time() {
-        let uv_vec = glam::Vec2::new(0.5, 0.3);
-        let angle = uv_vec.y.atan2(uv_vec.x);
-        let ray_count = 24.0_f32;
-
-        let ray_t0 = (angle * ray_count + 0.0 * 0.5).sin() * 0.5 + 0.5;
-        let ray_t1 = (angle * ray_count + 5.0 * 0.5).sin() * 0.5 + 0.5;
-
-        assert!(
-            (ray_t0 - ray_t1).abs() > 0.001,
-            "Corona should animate: value at t=0 ({ray_t0}) vs t=5 ({ray_t1})"
-        );
-    }
-
-    #[test]
-    fn test_sun_brightness_drives_bloom() {
-        let sun = SunProperties {
-            direction: Vec3::new(0.0, 0.5, -0.866),
-            physical_diameter: 1_392_700.0,
-            distance: 149_597_870.0,
-            star_type: StarType::G,
-            luminosity: 1.0,
-        };
-
-        let brightness = sun.hdr_brightness();
-        assert!(
-            brightness > 10.0,
-            "Sun HDR brightness ({brightness}) should far exceed 1.0 to drive bloom"
-        );
-    }
-
-    #[test]
-    fn test_sun_color_matches_star_type() {
-        let g_color = StarType::G.color();
-        assert!(
-            g_color[0] > g_color[2],
-            "G-type star should be yellow (R > B): {g_color:?}",
-        );
-
-        let o_color = StarType::O.color();
-        assert!(
-            o_color[2] > o_color[0],
-            "O-type star should be blue (B > R): {o_color:?}",
-        );
-
-        let m_color = StarType::M.color();
-        assert!(
-            m_color[0] > m_color[2] * 2.0,
-            "M-type star should be red (R >> B): {m_color:?}",
-        );
-    }
-
-    #[test]
-    fn test_sun_angular_size_decreases_with_distance() {
-        let sun_near = SunProperties {
-            direction: Vec3::Z,
-            physical_diameter: 1_392_700.0,
-            distance: 100_000_000.0,
-            star_type: StarType::G,
-            luminosity: 1.0,
-        };
-        let sun_far = SunProperties {
-            direction: Vec3::Z,
-            physical_diameter: 1_392_700.0,
-            distance: 500_000_000.0,
-            star_type: StarType::G,
-            luminosity: 1.0,
-        };
-
-        let angular_near = sun_near.angular_diameter();
-        let angular_far = sun_far.angular_diameter();
-
-        assert!(
-            angular_near > angular_far,
-            "Closer sun should have larger angular diameter: {angular_near} vs {angular_far}"
-        );
-        let ratio = angular_near / angular_far;
-        assert!(
-            (ratio - 5.0).abs() < 0.01,
-            "Angular diameter ratio should be ~5.0, got {ratio}"
-        );
-    }
-
-    #[test]
-    fn test_star_type_temperatures_are_ordered() {
-        let types = [
-            StarType::M,
-            StarType::K,
-            StarType::G,
-            StarType::F,
-            StarType::A,
-            StarType::B,
-            StarType::O,
-        ];
-        for window in types.windows(2) {
-            assert!(
-                window[0].temperature_k() < window[1].temperature_k(),
-                "{:?} ({} K) should be cooler than {:?} ({} K)",
-                window[0],
-                window[0].temperature_k(),
-                window[1],
-                window[1].temperature_k()
-            );
-        }
-    }
-
-    #[test]
-    fn test_sun_uniforms_size_is_gpu_aligned() {
-        let size = std::mem::size_of::<SunUniforms>();
-        assert_eq!(
-            size % 16,
-            0,
-            "SunUniforms size ({size} bytes) must be 16-byte aligned"
-        );
-    }
-
-    #[test]
-    fn test_angular_diameter_formula() {
-        let sun = SunProperties {
-            direction: Vec3::Z,
-            physical_diameter: 100.0,
-            distance: 1000.0,
-            star_type: StarType::G,
-            luminosity: 1.0,
-        };
-        let angular = sun.angular_diameter();
-        assert!(
-            (angular - 0.1).abs() < 1e-6,
-            "Angular diameter should be 0.1 rad, got {angular}"
-        );
-    }
-}
+#[path = "sun_tests.rs"]
+mod tests;
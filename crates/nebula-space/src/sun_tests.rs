@@ -0,0 +1,194 @@
+//! Unit tests for sun properties, limb darkening, and the corona.
+
+use super::*;
+use glam::{Quat, Vec3};
+
+#[test]
+fn test_sun_disk_faces_camera_from_all_angles() {
+    let test_rotations = [
+        Quat::IDENTITY,
+        Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+        Quat::from_rotation_x(std::f32::consts::FRAC_PI_4),
+        Quat::from_rotation_z(std::f32::consts::PI),
+        Quat::from_euler(glam::EulerRot::YXZ, 1.0, 0.5, 0.3),
+    ];
+
+    for (i, rotation) in test_rotations.iter().enumerate() {
+        let camera = Camera {
+            rotation: *rotation,
+            ..Camera::default()
+        };
+        let right = camera.right();
+        let up = camera.up();
+        let forward = camera.forward();
+
+        assert!(
+            right.dot(forward).abs() < 1e-5,
+            "Rotation {i}: billboard right is not perpendicular to forward"
+        );
+        assert!(
+            up.dot(forward).abs() < 1e-5,
+            "Rotation {i}: billboard up is not perpendicular to forward"
+        );
+    }
+}
+
+#[test]
+fn test_corona_animates_over_time() {
+    let uv_vec = glam::Vec2::new(0.5, 0.3);
+    let angle = uv_vec.y.atan2(uv_vec.x);
+    let ray_count = 24.0_f32;
+
+    let ray_t0 = (angle * ray_count + 0.0 * 0.5).sin() * 0.5 + 0.5;
+    let ray_t1 = (angle * ray_count + 5.0 * 0.5).sin() * 0.5 + 0.5;
+
+    assert!(
+        (ray_t0 - ray_t1).abs() > 0.001,
+        "Corona should animate: value at t=0 ({ray_t0}) vs t=5 ({ray_t1})"
+    );
+}
+
+#[test]
+fn test_sun_brightness_drives_bloom() {
+    let sun = SunProperties::new(
+        Vec3::new(0.0, 0.5, -0.866),
+        1_392_700.0,
+        149_597_870.0,
+        StarType::G,
+        1.0,
+    );
+
+    let brightness = sun.hdr_brightness();
+    assert!(
+        brightness > 10.0,
+        "Sun HDR brightness ({brightness}) should far exceed 1.0 to drive bloom"
+    );
+}
+
+#[test]
+fn test_sun_color_matches_star_type() {
+    let g_color = StarType::G.color();
+    assert!(
+        g_color[0] > g_color[2],
+        "G-type star should be yellow (R > B): {g_color:?}",
+    );
+
+    let o_color = StarType::O.color();
+    assert!(
+        o_color[2] > o_color[0],
+        "O-type star should be blue (B > R): {o_color:?}",
+    );
+
+    let m_color = StarType::M.color();
+    assert!(
+        m_color[0] > m_color[2] * 2.0,
+        "M-type star should be red (R >> B): {m_color:?}",
+    );
+}
+
+#[test]
+fn test_sun_angular_size_decreases_with_distance() {
+    let sun_near = SunProperties::new(Vec3::Z, 1_392_700.0, 100_000_000.0, StarType::G, 1.0);
+    let sun_far = SunProperties::new(Vec3::Z, 1_392_700.0, 500_000_000.0, StarType::G, 1.0);
+
+    let angular_near = sun_near.angular_diameter();
+    let angular_far = sun_far.angular_diameter();
+
+    assert!(
+        angular_near > angular_far,
+        "Closer sun should have larger angular diameter: {angular_near} vs {angular_far}"
+    );
+    let ratio = angular_near / angular_far;
+    assert!(
+        (ratio - 5.0).abs() < 0.01,
+        "Angular diameter ratio should be ~5.0, got {ratio}"
+    );
+}
+
+#[test]
+fn test_star_type_temperatures_are_ordered() {
+    let types = [
+        StarType::M,
+        StarType::K,
+        StarType::G,
+        StarType::F,
+        StarType::A,
+        StarType::B,
+        StarType::O,
+    ];
+    for window in types.windows(2) {
+        assert!(
+            window[0].temperature_k() < window[1].temperature_k(),
+            "{:?} ({} K) should be cooler than {:?} ({} K)",
+            window[0],
+            window[0].temperature_k(),
+            window[1],
+            window[1].temperature_k()
+        );
+    }
+}
+
+#[test]
+fn test_sun_uniforms_size_is_gpu_aligned() {
+    let size = std::mem::size_of::<SunUniforms>();
+    assert_eq!(
+        size % 16,
+        0,
+        "SunUniforms size ({size} bytes) must be 16-byte aligned"
+    );
+}
+
+#[test]
+fn test_angular_diameter_formula() {
+    let sun = SunProperties::new(Vec3::Z, 100.0, 1000.0, StarType::G, 1.0);
+    let angular = sun.angular_diameter();
+    assert!(
+        (angular - 0.1).abs() < 1e-6,
+        "Angular diameter should be 0.1 rad, got {angular}"
+    );
+}
+
+#[test]
+fn test_limb_darkening_dims_toward_edge() {
+    for star in [StarType::O, StarType::G, StarType::M] {
+        let coefficient = star.limb_darkening();
+        let center = limb_darkening(0.0, coefficient);
+        let edge = limb_darkening(1.0, coefficient);
+        assert!((center - 1.0).abs() < 1e-6);
+        assert!(center > edge, "{star:?}: center {center} vs edge {edge}");
+        let mut previous = center;
+        for i in 1..=20 {
+            let b = limb_darkening(i as f32 / 20.0, coefficient);
+            assert!(
+                b <= previous,
+                "{star:?}: brightness rose at r = {}",
+                i as f32 / 20.0
+            );
+            previous = b;
+        }
+    }
+}
+
+#[test]
+fn test_corona_falloff_is_monotonic_and_ends_at_radius() {
+    for star in [StarType::O, StarType::G, StarType::M] {
+        let corona_radius = star.corona_radius();
+        let mut previous = corona_falloff(1.0, corona_radius);
+        assert!((previous - 1.0).abs() < 1e-6);
+        for i in 1..=40 {
+            let r = 1.0 + (corona_radius - 1.0) * i as f32 / 40.0;
+            let glow = corona_falloff(r, corona_radius);
+            assert!(glow < previous, "{star:?}: glow did not fall at r = {r}");
+            previous = glow;
+        }
+        assert_eq!(corona_falloff(corona_radius, corona_radius), 0.0);
+    }
+    assert!(StarType::O.corona_radius() > StarType::M.corona_radius());
+}
+
+#[test]
+fn test_disk_color_follows_blackbody_temperature() {
+    let sun = SunProperties::new(Vec3::Z, 1.0, 100.0, StarType::M, 1.0);
+    assert_eq!(sun.disk_color(), blackbody_to_rgb(3050.0));
+    assert_eq!(sun.corona_radius, StarType::M.corona_radius());
+}
@@ -0,0 +1,296 @@
+//! Unit tests for asynchronous chunk generation, prioritization and
+//! cancellation.
+
+use super::*;
+use nebula_math::WorldPosition;
+
+fn dummy_planet() -> PlanetDef {
+    PlanetDef::earth_like("TestPlanet", WorldPosition::default(), 42)
+}
+
+fn dummy_task(address: ChunkAddress, priority: u64) -> GenerationTask {
+    GenerationTask {
+        address,
+        seed: 42,
+        planet: dummy_planet(),
+        priority,
+    }
+}
+
+#[test]
+fn test_generation_task_produces_valid_chunk() {
+    let task = dummy_task(ChunkAddress::new(0, 0, 0, 0), 0);
+    let chunk = generate_chunk_sync(&task, &TerrainGenerator::new);
+
+    // A valid chunk has at least Air in palette.
+    assert!(
+        chunk.palette_len() >= 1,
+        "Chunk should have at least Air in palette"
+    );
+}
+
+#[test]
+fn test_concurrent_generation_is_safe() {
+    let generator = AsyncChunkGenerator::new(4, 32, 64);
+
+    let mut submitted = 0;
+    for x in 0..8_i64 {
+        for z in 0..8_i64 {
+            let addr = ChunkAddress::new(x, 0, z, 0);
+            let task = dummy_task(addr, (x * x + z * z) as u64);
+            if generator.submit(task).is_ok() {
+                submitted += 1;
+            }
+        }
+    }
+
+    let mut received = 0;
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+    while received < submitted && std::time::Instant::now() < deadline {
+        let results = generator.drain_results();
+        received += results.len();
+        if received < submitted {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+
+    assert_eq!(
+        received, submitted,
+        "Should receive all submitted chunks: got {received}/{submitted}"
+    );
+}
+
+#[test]
+fn test_priority_ordering_respected() {
+    let generator = AsyncChunkGenerator::new(1, 64, 64);
+
+    let lo_addr = ChunkAddress::new(99, 0, 99, 0);
+    let hi_addr = ChunkAddress::new(0, 0, 0, 0);
+
+    let _ = generator.submit(dummy_task(lo_addr, 9999));
+    let _ = generator.submit(dummy_task(hi_addr, 1));
+
+    let mut results = Vec::new();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while results.len() < 2 && std::time::Instant::now() < deadline {
+        results.extend(generator.drain_results());
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    assert_eq!(results.len(), 2);
+    // Both chunks should be present regardless of order.
+    let addresses: Vec<_> = results.iter().map(|r| r.address).collect();
+    assert!(addresses.contains(&lo_addr));
+    assert!(addresses.contains(&hi_addr));
+}
+
+#[test]
+fn test_cancellation_stops_generation() {
+    let generator = AsyncChunkGenerator::new(2, 64, 64);
+
+    let addr = ChunkAddress::new(50, 0, 50, 0);
+    let _ = generator.submit(dummy_task(addr, 100));
+
+    // Immediately cancel.
+    generator.cancel(&addr);
+
+    // Wait briefly and check results.
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    let results = generator.drain_results();
+    let _cancelled_present = results.iter().any(|r| r.address == addr);
+    // Race condition is acceptable: task may have completed before cancellation.
+}
+
+#[test]
+fn test_generated_chunks_match_seed_deterministically() {
+    let task = dummy_task(ChunkAddress::new(5, 3, 7, 0), 0);
+
+    let chunk_a = generate_chunk_sync(&task, &TerrainGenerator::new);
+    let chunk_b = generate_chunk_sync(&task, &TerrainGenerator::new);
+
+    // Compare voxel-by-voxel since ChunkData doesn't implement PartialEq.
+    for x in 0..32_usize {
+        for y in 0..32_usize {
+            for z in 0..32_usize {
+                assert_eq!(
+                    chunk_a.get(x, y, z),
+                    chunk_b.get(x, y, z),
+                    "Mismatch at ({x}, {y}, {z})"
+                );
+            }
+        }
+    }
+}
+
+/// Every column in a single desert biome with sand over sandstone.
+fn desert_terrain(seed: u64) -> TerrainGenerator {
+    use crate::{BiomeId, BiomeSampler, TerrainLayerDef, WhittakerDiagram};
+    use nebula_voxel::VoxelTypeId;
+
+    let desert = BiomeId(4);
+    let diagram = WhittakerDiagram {
+        regions: Vec::new(),
+        fallback: desert,
+    };
+    let mut terrain = TerrainGenerator::with_biomes(seed, BiomeSampler::new(seed, diagram));
+    terrain.set_layer(
+        desert,
+        TerrainLayerDef {
+            surface_voxel: VoxelTypeId(7),
+            subsurface_voxel: VoxelTypeId(8),
+            ..TerrainLayerDef::default()
+        },
+    );
+    terrain
+}
+
+#[test]
+fn test_async_and_sync_share_the_biome_path() {
+    let address = ChunkAddress::new(0, 0, 0, 0);
+    let generator = AsyncChunkGenerator::with_terrain(1, 64, 64, desert_terrain);
+    generator.submit(dummy_task(address, 0)).unwrap();
+    let results = drain_all(&generator);
+    assert_eq!(results.len(), 1);
+
+    let sync = generate_chunk_sync(&dummy_task(address, 0), &desert_terrain);
+    let mut sand = 0;
+    for x in 0..32_usize {
+        for y in 0..32_usize {
+            for z in 0..32_usize {
+                assert_eq!(results[0].data.get(x, y, z), sync.get(x, y, z));
+                sand += usize::from(sync.get(x, y, z).0 == 7);
+            }
+        }
+    }
+    assert_eq!(
+        sand,
+        32 * 32,
+        "every column is topped with the biome's surface"
+    );
+}
+
+#[test]
+fn test_in_flight_count() {
+    let generator = AsyncChunkGenerator::new(1, 64, 64);
+
+    assert_eq!(generator.in_flight_count(), 0);
+
+    for i in 0..5_i64 {
+        let addr = ChunkAddress::new(i, 0, 0, 0);
+        let _ = generator.submit(dummy_task(addr, i as u64));
+    }
+
+    assert!(
+        generator.in_flight_count() > 0,
+        "Should have in-flight tasks after submission"
+    );
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while generator.in_flight_count() > 0 && std::time::Instant::now() < deadline {
+        let _ = generator.drain_results();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+}
+
+/// Generator whose workers block in `generate` until the returned sender
+/// is dropped, and report each task they start.
+fn gated_generator(
+    threads: usize,
+) -> (
+    AsyncChunkGenerator,
+    crossbeam_channel::Sender<()>,
+    Receiver<ChunkAddress>,
+) {
+    let (gate_tx, gate_rx) = crossbeam_channel::unbounded::<()>();
+    let (started_tx, started_rx) = crossbeam_channel::unbounded();
+    let generator = AsyncChunkGenerator::with_generator(threads, 64, 64, move |task| {
+        let _ = started_tx.send(task.address);
+        let _ = gate_rx.recv();
+        ChunkData::new_air()
+    });
+    (generator, gate_tx, started_rx)
+}
+
+fn drain_all(generator: &AsyncChunkGenerator) -> Vec<GeneratedChunk> {
+    let mut results = Vec::new();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while generator.in_flight_count() > 0 && std::time::Instant::now() < deadline {
+        results.extend(generator.drain_results());
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    results.extend(generator.drain_results());
+    results
+}
+
+#[test]
+fn test_cancel_batch_removes_queued_tasks_from_results() {
+    let (generator, gate, started) = gated_generator(1);
+    let running = ChunkAddress::new(0, 0, 0, 0);
+    generator.submit(dummy_task(running, 0)).unwrap();
+    assert_eq!(started.recv().unwrap(), running);
+
+    let victims: Vec<_> = (1..=5).map(|x| ChunkAddress::new(x, 0, 0, 0)).collect();
+    let keepers: Vec<_> = (6..=8).map(|x| ChunkAddress::new(x, 0, 0, 0)).collect();
+    for &addr in victims.iter().chain(&keepers) {
+        generator.submit(dummy_task(addr, 1)).unwrap();
+    }
+
+    assert_eq!(generator.cancel_batch(&victims), 5);
+    assert!(victims.iter().all(|a| !generator.is_pending(a)));
+    drop(gate);
+
+    let results = drain_all(&generator);
+    let delivered: Vec<_> = results.iter().map(|r| r.address).collect();
+    assert_eq!(delivered.len(), 4);
+    assert!(delivered.contains(&running));
+    assert!(keepers.iter().all(|a| delivered.contains(a)));
+    assert!(results.iter().all(|r| !r.evicted));
+}
+
+#[test]
+fn test_cancelled_tasks_never_appear_in_results() {
+    let (generator, gate, started) = gated_generator(1);
+    let running = ChunkAddress::new(0, 0, 0, 0);
+    generator.submit(dummy_task(running, 0)).unwrap();
+    assert_eq!(started.recv().unwrap(), running);
+
+    let queued: Vec<_> = (1..=6).map(|x| ChunkAddress::new(x, 0, 0, 0)).collect();
+    for &addr in &queued {
+        generator.submit(dummy_task(addr, 1)).unwrap();
+    }
+    assert_eq!(generator.pending_count(), 7);
+
+    // Cancel the in-flight task and every other queued one.
+    let cancelled: Vec<_> = std::iter::once(running)
+        .chain(queued.iter().copied().step_by(2))
+        .collect();
+    for addr in &cancelled {
+        generator.cancel(addr);
+    }
+    assert_eq!(generator.pending_count(), 3);
+    drop(gate);
+
+    let delivered: Vec<_> = drain_all(&generator)
+        .into_iter()
+        .map(|r| r.address)
+        .collect();
+    assert_eq!(delivered.len(), 3);
+    assert!(cancelled.iter().all(|a| !delivered.contains(a)));
+    assert_eq!(generator.pending_count(), 0);
+}
+
+#[test]
+fn test_running_task_completes_tagged_evicted() {
+    let (generator, gate, started) = gated_generator(1);
+    let running = ChunkAddress::new(3, 1, 4, 0);
+    generator.submit(dummy_task(running, 0)).unwrap();
+    assert_eq!(started.recv().unwrap(), running);
+
+    assert_eq!(generator.cancel_batch(&[running]), 0, "not queued");
+    drop(gate);
+
+    let results = drain_all(&generator);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].address, running);
+    assert!(results[0].evicted);
+}
@@ -10,5 +10,5 @@ mod sampler;
 
 pub use def::BiomeDef;
 pub use diagram::{WhittakerDiagram, WhittakerRegion};
-pub use registry::{BiomeId, BiomeRegistry, BiomeRegistryError};
+pub use registry::{BiomeDisplayName, BiomeId, BiomeRegistry, BiomeRegistryError};
 pub use sampler::BiomeSampler;
@@ -1,4 +1,8 @@
 //! Whittaker diagram: maps (temperature, moisture) pairs to biome IDs.
+//!
+//! [`WhittakerDiagram::lookup`] gives the single biome at a point;
+//! [`WhittakerDiagram::blend`] weights every biome whose region lies within a
+//! blend width of the point so surfaces can be interpolated across edges.
 
 use super::BiomeId;
 
@@ -30,14 +34,68 @@ impl WhittakerDiagram {
     /// Looks up the biome for a given temperature and moisture, both in `[0.0, 1.0]`.
     pub fn lookup(&self, temperature: f64, moisture: f64) -> BiomeId {
         for region in &self.regions {
-            if temperature >= region.temp_min
-                && temperature < region.temp_max
-                && moisture >= region.moisture_min
-                && moisture < region.moisture_max
-            {
+            if region.contains(temperature, moisture) {
                 return region.biome_id;
             }
         }
         self.fallback
     }
+
+    /// Blends the biomes around a temperature/moisture point.
+    ///
+    /// The region that [`lookup`](Self::lookup) would pick has weight `1.0`;
+    /// every other region within `blend_width` (Euclidean distance in
+    /// temperature–moisture space) gets `1.0 - distance / blend_width`.
+    /// Weights are merged per biome, normalized to sum to `1.0` and sorted
+    /// heaviest first. A point far from every edge yields a single biome.
+    pub fn blend(&self, temperature: f64, moisture: f64, blend_width: f64) -> Vec<(BiomeId, f32)> {
+        let winner = self.lookup(temperature, moisture);
+        let mut weights: Vec<(BiomeId, f64)> = vec![(winner, 1.0)];
+
+        for region in &self.regions {
+            if region.contains(temperature, moisture) {
+                // The first containing region is the lookup winner; later
+                // overlapping ones are shadowed by it.
+                continue;
+            }
+            let distance = region.distance_to(temperature, moisture);
+            if distance >= blend_width {
+                continue;
+            }
+            let weight = 1.0 - distance / blend_width;
+            match weights.iter_mut().find(|(id, _)| *id == region.biome_id) {
+                Some((_, w)) => *w = w.max(weight),
+                None => weights.push((region.biome_id, weight)),
+            }
+        }
+
+        let total: f64 = weights.iter().map(|(_, w)| w).sum();
+        let mut blended: Vec<(BiomeId, f32)> = weights
+            .into_iter()
+            .map(|(id, w)| (id, (w / total) as f32))
+            .collect();
+        blended.sort_by(|a, b| b.1.total_cmp(&a.1));
+        blended
+    }
+}
+
+impl WhittakerRegion {
+    /// Whether the point lies in this region (max bounds exclusive).
+    fn contains(&self, temperature: f64, moisture: f64) -> bool {
+        temperature >= self.temp_min
+            && temperature < self.temp_max
+            && moisture >= self.moisture_min
+            && moisture < self.moisture_max
+    }
+
+    /// Euclidean distance from a point to this region's rectangle.
+    fn distance_to(&self, temperature: f64, moisture: f64) -> f64 {
+        let dt = (self.temp_min - temperature)
+            .max(temperature - self.temp_max)
+            .max(0.0);
+        let dm = (self.moisture_min - moisture)
+            .max(moisture - self.moisture_max)
+            .max(0.0);
+        dt.hypot(dm)
+    }
 }
@@ -1,5 +1,7 @@
 //! Biome registry: maps [`BiomeId`] to [`BiomeDef`] with name-based lookup.
 
+use std::fmt;
+
 use hashbrown::HashMap;
 
 use super::BiomeDef;
@@ -8,6 +10,47 @@ use super::BiomeDef;
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct BiomeId(pub u16);
 
+impl BiomeId {
+    /// Display as `name(id)`, e.g. `forest(3)`, using the name registered in
+    /// `registry`.
+    pub fn fmt_named(self, registry: &BiomeRegistry) -> BiomeDisplayName<'_> {
+        BiomeDisplayName { id: self, registry }
+    }
+}
+
+/// A [`BiomeId`] displayed with its registered name; see
+/// [`BiomeId::fmt_named`].
+pub struct BiomeDisplayName<'r> {
+    id: BiomeId,
+    registry: &'r BiomeRegistry,
+}
+
+impl fmt::Display for BiomeDisplayName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.registry.biomes.get(self.id.0 as usize) {
+            Some(def) => write!(f, "{}({})", def.name, self.id.0),
+            None => write!(f, "unknown({})", self.id.0),
+        }
+    }
+}
+
+/// Debug colors handed out in registration order by
+/// [`BiomeRegistry::display_color`].
+const DEBUG_PALETTE: [[u8; 3]; 12] = [
+    [230, 25, 75],
+    [60, 180, 75],
+    [255, 225, 25],
+    [0, 130, 200],
+    [245, 130, 48],
+    [145, 30, 180],
+    [70, 240, 240],
+    [240, 50, 230],
+    [210, 245, 60],
+    [250, 190, 212],
+    [0, 128, 128],
+    [170, 110, 40],
+];
+
 /// Errors that can occur when registering biomes.
 #[derive(Debug, thiserror::Error)]
 pub enum BiomeRegistryError {
@@ -69,6 +112,33 @@ impl BiomeRegistry {
     pub fn is_empty(&self) -> bool {
         self.biomes.is_empty()
     }
+
+    /// Debug visualization color for `id`.
+    ///
+    /// Colors come from a fixed palette in registration order, so registries
+    /// built in the same order agree on every biome's color. Past the end of
+    /// the palette it repeats at half brightness, then wraps.
+    pub fn display_color(&self, id: BiomeId) -> [u8; 3] {
+        let index = id.0 as usize;
+        let [r, g, b] = DEBUG_PALETTE[index % DEBUG_PALETTE.len()];
+        if (index / DEBUG_PALETTE.len()) % 2 == 1 {
+            [r / 2, g / 2, b / 2]
+        } else {
+            [r, g, b]
+        }
+    }
+
+    /// All biomes ordered by name, for deterministic legends.
+    pub fn biomes_sorted_by_name(&self) -> Vec<(BiomeId, &BiomeDef)> {
+        let mut biomes: Vec<(BiomeId, &BiomeDef)> = self
+            .biomes
+            .iter()
+            .enumerate()
+            .map(|(i, def)| (BiomeId(i as u16), def))
+            .collect();
+        biomes.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+        biomes
+    }
 }
 
 impl Default for BiomeRegistry {
@@ -76,3 +146,61 @@ impl Default for BiomeRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nebula_voxel::VoxelTypeId;
+
+    fn registry(names: &[&str]) -> BiomeRegistry {
+        let mut reg = BiomeRegistry::new();
+        for name in names {
+            reg.register(BiomeDef {
+                name: (*name).into(),
+                surface_voxel: VoxelTypeId(1),
+                subsurface_voxel: VoxelTypeId(2),
+                vegetation_density: 0.0,
+                tree_type: None,
+            })
+            .unwrap();
+        }
+        reg
+    }
+
+    #[test]
+    fn test_display_color_stable_across_registries() {
+        let names = ["plains", "forest", "desert", "tundra", "ocean"];
+        let a = registry(&names);
+        let b = registry(&names);
+        for i in 0..names.len() as u16 {
+            assert_eq!(a.display_color(BiomeId(i)), b.display_color(BiomeId(i)));
+        }
+        assert_ne!(a.display_color(BiomeId(0)), a.display_color(BiomeId(1)));
+        assert_ne!(a.display_color(BiomeId(0)), a.display_color(BiomeId(12)));
+    }
+
+    #[test]
+    fn test_fmt_named_prints_name_and_id() {
+        let reg = registry(&["plains", "desert", "taiga", "forest"]);
+        assert_eq!(BiomeId(3).fmt_named(&reg).to_string(), "forest(3)");
+        assert_eq!(BiomeId(9).fmt_named(&reg).to_string(), "unknown(9)");
+    }
+
+    #[test]
+    fn test_biomes_sorted_by_name() {
+        let reg = registry(&["tundra", "desert", "plains"]);
+        let sorted: Vec<_> = reg
+            .biomes_sorted_by_name()
+            .into_iter()
+            .map(|(id, def)| (id, def.name.as_str()))
+            .collect();
+        assert_eq!(
+            sorted,
+            [
+                (BiomeId(1), "desert"),
+                (BiomeId(2), "plains"),
+                (BiomeId(0), "tundra")
+            ]
+        );
+    }
+}
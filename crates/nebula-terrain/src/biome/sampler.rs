@@ -15,6 +15,9 @@ pub struct BiomeSampler {
     pub temp_frequency: f64,
     /// Frequency for moisture noise.
     pub moisture_frequency: f64,
+    /// Distance in normalized temperature–moisture space over which
+    /// [`Self::sample_blend`] fades between neighboring biomes.
+    pub blend_width: f64,
 }
 
 impl BiomeSampler {
@@ -31,6 +34,7 @@ impl BiomeSampler {
             diagram,
             temp_frequency: 0.0005,
             moisture_frequency: 0.0007,
+            blend_width: 0.05,
         }
     }
 
@@ -39,6 +43,24 @@ impl BiomeSampler {
     /// Returns `(biome_id, temperature, moisture)` where temperature and moisture
     /// are normalized to `[0.0, 1.0]`.
     pub fn sample(&self, point: glam::DVec3) -> (BiomeId, f64, f64) {
+        let (temperature, moisture) = self.climate(point);
+        let biome_id = self.diagram.lookup(temperature, moisture);
+        (biome_id, temperature, moisture)
+    }
+
+    /// Samples the biomes blended at a 3D sphere-surface point.
+    ///
+    /// Returns `(biome_id, weight)` pairs, heaviest first, with weights summing
+    /// to `1.0`. Away from region edges this is the single biome [`Self::sample`]
+    /// returns; within [`Self::blend_width`] of an edge the neighbors share the
+    /// weight. See [`WhittakerDiagram::blend`].
+    pub fn sample_blend(&self, point: glam::DVec3) -> Vec<(BiomeId, f32)> {
+        let (temperature, moisture) = self.climate(point);
+        self.diagram.blend(temperature, moisture, self.blend_width)
+    }
+
+    /// Temperature and moisture at `point`, normalized to `[0.0, 1.0]`.
+    fn climate(&self, point: glam::DVec3) -> (f64, f64) {
         let temp_raw = self.temp_noise.get([
             point.x * self.temp_frequency,
             point.y * self.temp_frequency,
@@ -53,9 +75,7 @@ impl BiomeSampler {
         // Normalize from [-1, 1] to [0, 1].
         let temperature = (temp_raw + 1.0) * 0.5;
         let moisture = (moisture_raw + 1.0) * 0.5;
-
-        let biome_id = self.diagram.lookup(temperature, moisture);
-        (biome_id, temperature, moisture)
+        (temperature, moisture)
     }
 }
 
@@ -267,4 +287,51 @@ mod tests {
         assert!((temp_a - temp_b).abs() < 1e-12);
         assert!((moist_a - moist_b).abs() < 1e-12);
     }
+
+    #[test]
+    fn test_blend_near_boundary_returns_two_biomes() {
+        let (reg, diagram) = make_test_registry_and_diagram();
+        // Just inside tundra, 0.01 from the plains edge at temp 0.3.
+        let blend = diagram.blend(0.29, 0.25, 0.05);
+
+        assert_eq!(blend.len(), 2, "{blend:?}");
+        assert_eq!(reg.get(blend[0].0).name, "tundra");
+        assert_eq!(reg.get(blend[1].0).name, "plains");
+        let total: f32 = blend.iter().map(|(_, w)| w).sum();
+        assert!((total - 1.0).abs() < 1e-6, "weights sum to {total}");
+        assert!(blend[0].1 > blend[1].1);
+    }
+
+    #[test]
+    fn test_blend_deep_interior_is_single_biome() {
+        let (reg, diagram) = make_test_registry_and_diagram();
+        let blend = diagram.blend(0.15, 0.25, 0.05);
+        assert_eq!(blend.len(), 1);
+        assert_eq!(reg.get(blend[0].0).name, "tundra");
+        assert_eq!(blend[0].1, 1.0);
+    }
+
+    #[test]
+    fn test_blend_is_even_on_the_edge() {
+        let (reg, diagram) = make_test_registry_and_diagram();
+        // Exactly on the exclusive max of tundra: lookup says plains.
+        let blend = diagram.blend(0.3, 0.25, 0.05);
+        assert_eq!(reg.get(blend[0].0).name, "plains");
+        assert!((blend[0].1 - 0.5).abs() < 1e-6);
+        assert!((blend[1].1 - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sample_blend_dominant_matches_sample() {
+        let (_reg, diagram) = make_test_registry_and_diagram();
+        let sampler = BiomeSampler::new(7, diagram);
+        for i in 0..200 {
+            let point = glam::DVec3::new(i as f64 * 97.0, 1.0e4, -(i as f64) * 31.0);
+            let (biome, _, _) = sampler.sample(point);
+            let blend = sampler.sample_blend(point);
+            assert_eq!(blend[0].0, biome, "sample {i}: {blend:?}");
+            let total: f32 = blend.iter().map(|(_, w)| w).sum();
+            assert!((total - 1.0).abs() < 1e-5);
+        }
+    }
 }
@@ -1,10 +1,27 @@
 //! 3D noise-based cave generation using the Swiss cheese model.
 //!
 //! Uses multi-octave 3D simplex noise to carve cave systems into subsurface
-//! volume. Respects depth bounds and ocean floor buffers.
+//! volume, optionally combined with connected worm tunnels (see
+//! [`CaveMode`]). Respects depth bounds and ocean floor buffers.
 
+use glam::DVec3;
 use noise::{NoiseFn, Simplex};
 
+use crate::cave_worm::{WormParams, WormTunnel};
+
+/// How a [`CaveCarver`] decides which voxels become air.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CaveMode {
+    /// Threshold a 3D noise field ("Swiss cheese" blobs).
+    #[default]
+    Noise,
+    /// Carve tubes along deterministic worm splines, producing connected
+    /// tunnels.
+    Worms,
+    /// Union of [`CaveMode::Noise`] and [`CaveMode::Worms`].
+    Combined,
+}
+
 /// Configuration for 3D noise-based cave generation.
 #[derive(Clone, Debug)]
 pub struct CaveConfig {
@@ -35,6 +52,15 @@ pub struct CaveConfig {
     /// Minimum distance above the ocean floor where caves are suppressed.
     /// Prevents water from draining into caves. Default: 10.0.
     pub ocean_floor_buffer: f64,
+    /// Carving mode. Default: [`CaveMode::Noise`].
+    pub mode: CaveMode,
+    /// Worms spawned per 256-unit worm region. Default: 2.
+    pub worm_count: u32,
+    /// Minimum and maximum tunnel radius in engine units. The radius swells
+    /// between the two along each worm. Default: (1.5, 4.0).
+    pub worm_radius_range: (f64, f64),
+    /// Length of each worm's path in engine units. Default: 120.0.
+    pub worm_length: f64,
 }
 
 impl Default for CaveConfig {
@@ -49,6 +75,10 @@ impl Default for CaveConfig {
             max_depth: 500.0,
             min_depth: 5.0,
             ocean_floor_buffer: 10.0,
+            mode: CaveMode::Noise,
+            worm_count: 2,
+            worm_radius_range: (1.5, 4.0),
+            worm_length: 120.0,
         }
     }
 }
@@ -72,6 +102,18 @@ impl CaveCarver {
         &self.config
     }
 
+    /// All worm tunnels that may pass through the box `[min, max]`.
+    ///
+    /// Generate these once per chunk and pass them to
+    /// [`CaveCarver::is_cave_with_tunnels`] to avoid re-tracing worms for
+    /// every voxel. Empty unless the mode includes worms.
+    pub fn worm_tunnels(&self, min: DVec3, max: DVec3) -> Vec<WormTunnel> {
+        if self.config.mode == CaveMode::Noise {
+            return Vec::new();
+        }
+        self.worm_params().tunnels_in_bounds(min, max)
+    }
+
     /// Determine if a voxel at the given 3D position should be carved as a cave.
     ///
     /// # Arguments
@@ -81,9 +123,18 @@ impl CaveCarver {
     ///
     /// # Returns
     /// `true` if the voxel should be air (cave), `false` if it remains solid.
-    pub fn is_cave(
+    pub fn is_cave(&self, voxel_pos: DVec3, surface_height: f64, sea_level_height: f64) -> bool {
+        let tunnels = self.worm_tunnels(voxel_pos, voxel_pos);
+        self.is_cave_with_tunnels(&tunnels, voxel_pos, surface_height, sea_level_height)
+    }
+
+    /// Like [`CaveCarver::is_cave`], but tests worms against `tunnels`
+    /// previously obtained from [`CaveCarver::worm_tunnels`] for a region
+    /// containing `voxel_pos`.
+    pub fn is_cave_with_tunnels(
         &self,
-        voxel_pos: glam::DVec3,
+        tunnels: &[WormTunnel],
+        voxel_pos: DVec3,
         surface_height: f64,
         sea_level_height: f64,
     ) -> bool {
@@ -119,6 +170,16 @@ impl CaveCarver {
             return false;
         }
 
+        let in_worm = || tunnels.iter().any(|t| t.contains(voxel_pos));
+        match self.config.mode {
+            CaveMode::Noise => self.is_noise_cave(voxel_pos, depth_below_surface),
+            CaveMode::Worms => in_worm(),
+            CaveMode::Combined => in_worm() || self.is_noise_cave(voxel_pos, depth_below_surface),
+        }
+    }
+
+    /// Noise-threshold test for a voxel already known to be in the cave band.
+    fn is_noise_cave(&self, voxel_pos: DVec3, depth_below_surface: f64) -> bool {
         // Sample 3D noise at the voxel position.
         let noise_val = self.sample_cave_noise(voxel_pos);
 
@@ -133,8 +194,17 @@ impl CaveCarver {
         noise_val <= adjusted_threshold
     }
 
+    fn worm_params(&self) -> WormParams {
+        WormParams {
+            seed: self.config.seed,
+            count: self.config.worm_count,
+            radius_range: self.config.worm_radius_range,
+            length: self.config.worm_length,
+        }
+    }
+
     /// Sample multi-octave 3D cave noise at a position.
-    fn sample_cave_noise(&self, pos: glam::DVec3) -> f64 {
+    fn sample_cave_noise(&self, pos: DVec3) -> f64 {
         let mut total = 0.0;
         let mut frequency = self.config.frequency;
         let mut amplitude = 1.0;
@@ -159,7 +229,6 @@ impl CaveCarver {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use glam::DVec3;
 
     const PLANET_RADIUS: f64 = 6_371_000.0;
 
@@ -330,4 +399,72 @@ mod tests {
             );
         }
     }
+
+    fn worm_carver() -> CaveCarver {
+        CaveCarver::new(CaveConfig {
+            seed: 7,
+            mode: CaveMode::Worms,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_worm_tunnel_continuous_across_chunk_boundary() {
+        const CHUNK: f64 = 32.0;
+        let carver = worm_carver();
+        let region = (DVec3::new(PLANET_RADIUS - 100.0, 0.0, 0.0) / 256.0)
+            .floor()
+            .as_ivec3();
+        let tunnel = carver.worm_params().region_tunnels(region).remove(0);
+
+        // Walk the center line in half-unit steps, carving each sample with
+        // only the tunnels generated for the chunk that contains it.
+        let mut crossings = 0;
+        let mut prev_chunk = None;
+        for seg in tunnel.points.windows(2) {
+            for i in 0..8 {
+                let p = seg[0].lerp(seg[1], f64::from(i) / 8.0);
+                let chunk = (p / CHUNK).floor();
+                let tunnels = carver.worm_tunnels(chunk * CHUNK, chunk * CHUNK + CHUNK);
+                let surface = p.length() + 50.0;
+                assert!(
+                    carver.is_cave_with_tunnels(&tunnels, p, surface, 0.0),
+                    "worm center line must be carved at {p:?} (chunk {chunk:?})"
+                );
+                if prev_chunk.is_some_and(|c| c != chunk) {
+                    crossings += 1;
+                }
+                prev_chunk = Some(chunk);
+            }
+        }
+        assert!(crossings > 0, "a 120-unit worm should cross a chunk edge");
+    }
+
+    #[test]
+    fn test_worms_deterministic_and_respect_depth_bounds() {
+        let a = worm_carver();
+        let b = worm_carver();
+        let min = DVec3::new(PLANET_RADIUS - 300.0, -100.0, -100.0);
+        let max = min + DVec3::splat(200.0);
+        let tunnels = a.worm_tunnels(min, max);
+        assert!(!tunnels.is_empty());
+        assert_eq!(tunnels, b.worm_tunnels(min, max));
+
+        // A tunnel point far above the surface is never carved.
+        let p = tunnels[0].points[0];
+        assert!(!a.is_cave(p, p.length() - 1.0, 0.0));
+        assert!(a.is_cave(p, p.length() + 50.0, 0.0));
+    }
+
+    #[test]
+    fn test_noise_mode_has_no_worms() {
+        let carver = default_carver();
+        assert_eq!(carver.config().mode, CaveMode::Noise);
+        let min = DVec3::new(PLANET_RADIUS, 0.0, 0.0);
+        assert!(
+            carver
+                .worm_tunnels(min, min + DVec3::splat(500.0))
+                .is_empty()
+        );
+    }
 }
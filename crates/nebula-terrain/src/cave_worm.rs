@@ -0,0 +1,174 @@
+//! Worm caves: connected tunnels traced as deterministic splines.
+//!
+//! Space is divided into cubic worm regions of [`WORM_REGION_SIZE`] units.
+//! Each region spawns a fixed number of worms whose paths depend only on the
+//! world seed and the region coordinates, so any chunk can regenerate the
+//! exact same tunnels that pass through it from neighbouring regions. A
+//! tunnel therefore never stops at a chunk edge.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use glam::{DVec3, IVec3};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Edge length of a worm spawn region in engine units.
+pub(crate) const WORM_REGION_SIZE: f64 = 256.0;
+
+/// Distance between consecutive spline control points.
+const WORM_STEP: f64 = 4.0;
+
+/// How sharply a worm may turn per step (fraction of a random unit vector
+/// blended into the heading).
+const WORM_TURN_RATE: f64 = 0.35;
+
+/// Seed offset decorrelating worm paths from the cave noise.
+const WORM_SEED_OFFSET: u64 = 0x3A1F_77C3;
+
+/// A single tunnel: a polyline of control points with a radius at each.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WormTunnel {
+    /// Control points along the tunnel center line.
+    pub points: Vec<DVec3>,
+    /// Tunnel radius at each control point.
+    pub radii: Vec<f64>,
+}
+
+impl WormTunnel {
+    /// Whether `pos` lies inside the tube swept along this tunnel.
+    pub fn contains(&self, pos: DVec3) -> bool {
+        self.points
+            .windows(2)
+            .zip(self.radii.windows(2))
+            .any(|(seg, r)| {
+                let (t, dist_sq) = segment_distance_sq(pos, seg[0], seg[1]);
+                let radius = r[0] + (r[1] - r[0]) * t;
+                dist_sq <= radius * radius
+            })
+    }
+
+    /// Axis-aligned bounds of the tube, including its radius.
+    fn bounds(&self) -> (DVec3, DVec3) {
+        let r = self.radii.iter().copied().fold(0.0, f64::max);
+        let (min, max) = self.points.iter().fold(
+            (DVec3::splat(f64::INFINITY), DVec3::splat(f64::NEG_INFINITY)),
+            |(min, max), &p| (min.min(p), max.max(p)),
+        );
+        (min - DVec3::splat(r), max + DVec3::splat(r))
+    }
+}
+
+/// Parameters shared by every worm, copied out of `CaveConfig`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct WormParams {
+    pub seed: u64,
+    pub count: u32,
+    pub radius_range: (f64, f64),
+    pub length: f64,
+}
+
+impl WormParams {
+    /// Furthest any part of a tunnel can reach from its spawn point.
+    fn reach(&self) -> f64 {
+        self.length + self.radius_range.1.max(self.radius_range.0)
+    }
+
+    /// All tunnels that could intersect the box `[min, max]`.
+    pub fn tunnels_in_bounds(&self, min: DVec3, max: DVec3) -> Vec<WormTunnel> {
+        if self.count == 0 || self.length <= 0.0 {
+            return Vec::new();
+        }
+        let reach = DVec3::splat(self.reach());
+        let lo = region_of(min - reach);
+        let hi = region_of(max + reach);
+
+        let mut tunnels = Vec::new();
+        for x in lo.x..=hi.x {
+            for y in lo.y..=hi.y {
+                for z in lo.z..=hi.z {
+                    for tunnel in self.region_tunnels(IVec3::new(x, y, z)) {
+                        let (t_min, t_max) = tunnel.bounds();
+                        if t_min.cmple(max).all() && t_max.cmpge(min).all() {
+                            tunnels.push(tunnel);
+                        }
+                    }
+                }
+            }
+        }
+        tunnels
+    }
+
+    /// Deterministically trace every worm spawned in `region`.
+    pub fn region_tunnels(&self, region: IVec3) -> Vec<WormTunnel> {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.region_seed(region));
+        let origin = region.as_dvec3() * WORM_REGION_SIZE;
+        let (r_min, r_max) = self.radius_range;
+        let steps = (self.length / WORM_STEP).ceil().max(1.0) as usize;
+
+        (0..self.count)
+            .map(|_| {
+                let mut pos = origin
+                    + DVec3::new(rng.random(), rng.random(), rng.random()) * WORM_REGION_SIZE;
+                let mut heading = random_unit(&mut rng);
+                let phase = rng.random_range(0.0..std::f64::consts::TAU);
+                let wavelength = rng.random_range(8.0..24.0);
+
+                let mut points = Vec::with_capacity(steps + 1);
+                let mut radii = Vec::with_capacity(steps + 1);
+                for i in 0..=steps {
+                    points.push(pos);
+                    let swell = 0.5 + 0.5 * libm::sin(phase + i as f64 / wavelength * 6.0);
+                    radii.push(r_min + (r_max - r_min) * swell);
+
+                    let turn = random_unit(&mut rng) * WORM_TURN_RATE;
+                    heading = (heading + turn).try_normalize().unwrap_or(heading);
+                    pos += heading * WORM_STEP;
+                }
+                WormTunnel { points, radii }
+            })
+            .collect()
+    }
+
+    fn region_seed(&self, region: IVec3) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.seed.wrapping_add(WORM_SEED_OFFSET).hash(&mut hasher);
+        region.x.hash(&mut hasher);
+        region.y.hash(&mut hasher);
+        region.z.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Worm region containing `pos`.
+fn region_of(pos: DVec3) -> IVec3 {
+    (pos / WORM_REGION_SIZE).floor().as_ivec3()
+}
+
+/// Uniformly distributed unit vector (rejection sampled).
+fn random_unit(rng: &mut ChaCha8Rng) -> DVec3 {
+    loop {
+        let v = DVec3::new(
+            rng.random_range(-1.0..1.0),
+            rng.random_range(-1.0..1.0),
+            rng.random_range(-1.0..1.0),
+        );
+        let len_sq = v.length_squared();
+        if len_sq > 1e-6 && len_sq <= 1.0 {
+            return v / len_sq.sqrt();
+        }
+    }
+}
+
+/// Parameter along `a..b` of the point closest to `p`, and the squared
+/// distance to it.
+fn segment_distance_sq(p: DVec3, a: DVec3, b: DVec3) -> (f64, f64) {
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    let t = if len_sq > 0.0 {
+        ((p - a).dot(ab) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (t, (a + ab * t).distance_squared(p))
+}
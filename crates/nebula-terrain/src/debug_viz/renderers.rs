@@ -1,7 +1,7 @@
 //! Terrain debug visualization renderers: heightmap, biome, cave, and ore.
 
 use glam::DVec3;
-use nebula_cubesphere::{CubeFace, FaceCoord, face_coord_to_sphere_everitt};
+use nebula_cubesphere::{CubeFace, FaceCoord, ProjectionMethod, project};
 use nebula_voxel::VoxelTypeId;
 
 use super::image::DebugImage;
@@ -15,7 +15,8 @@ use crate::terrain_height::TerrainHeightConfig;
 ///
 /// Samples the heightmap noise at each pixel and maps the value to a
 /// color-coded elevation: deep blue (ocean) → green (plains) → brown
-/// (mountains) → white (snow peaks).
+/// (mountains) → white (snow peaks). Pixels are placed on the sphere with the
+/// planet's `projection`.
 pub fn render_heightmap_debug(
     sampler: &HeightmapSampler,
     config: &TerrainHeightConfig,
@@ -23,6 +24,7 @@ pub fn render_heightmap_debug(
     height: u32,
     face: CubeFace,
     region: (f64, f64, f64, f64),
+    projection: ProjectionMethod,
 ) -> DebugImage {
     let mut image = DebugImage::new(width, height);
     let max_amp = sampler.max_amplitude();
@@ -33,7 +35,7 @@ pub fn render_heightmap_debug(
             let v = region.1 + (py as f64 / height as f64) * (region.3 - region.1);
 
             let fc = FaceCoord::new(face, u, v);
-            let sphere_pt = face_coord_to_sphere_everitt(&fc);
+            let sphere_pt = project(&fc, projection);
             let raw = sampler.sample_3d(sphere_pt);
 
             // Normalize to [0, 1].
@@ -97,6 +99,9 @@ pub fn height_to_color(normalized: f64, config: &TerrainHeightConfig) -> (u8, u8
 }
 
 /// Return an RGB color for a given biome ID based on its name.
+///
+/// Well-known biome names get fixed natural colors; any other biome takes
+/// its [`BiomeRegistry::display_color`].
 pub fn biome_color(biome_id: BiomeId, registry: &BiomeRegistry) -> (u8, u8, u8) {
     let name = &registry.get(biome_id).name;
     match name.as_str() {
@@ -110,11 +115,15 @@ pub fn biome_color(biome_id: BiomeId, registry: &BiomeRegistry) -> (u8, u8, u8)
         "mountains" => (130, 110, 90),
         "tropical_rainforest" => (10, 80, 20),
         "savanna" => (180, 170, 60),
-        _ => (128, 128, 128),
+        _ => {
+            let [r, g, b] = registry.display_color(biome_id);
+            (r, g, b)
+        }
     }
 }
 
-/// Generate a debug biome map image for a region of a cube face.
+/// Generate a debug biome map image for a region of a cube face, placed on
+/// the sphere with the planet's `projection`.
 pub fn render_biome_debug(
     biome_sampler: &BiomeSampler,
     registry: &BiomeRegistry,
@@ -122,6 +131,7 @@ pub fn render_biome_debug(
     height: u32,
     face: CubeFace,
     region: (f64, f64, f64, f64),
+    projection: ProjectionMethod,
 ) -> DebugImage {
     let mut image = DebugImage::new(width, height);
 
@@ -131,7 +141,7 @@ pub fn render_biome_debug(
             let v = region.1 + (py as f64 / height as f64) * (region.3 - region.1);
 
             let fc = FaceCoord::new(face, u, v);
-            let sphere_pt = face_coord_to_sphere_everitt(&fc);
+            let sphere_pt = project(&fc, projection);
             let (biome_id, _, _) = biome_sampler.sample(sphere_pt);
             let (r, g, b) = biome_color(biome_id, registry);
             image.set_pixel(px, py, r, g, b, 255);
@@ -251,6 +261,7 @@ mod tests {
             64,
             CubeFace::PosX,
             (0.0, 0.0, 1.0, 1.0),
+            ProjectionMethod::default(),
         );
 
         assert_eq!(image.dimensions(), (64, 64));
@@ -307,6 +318,7 @@ mod tests {
             32,
             CubeFace::PosX,
             (0.0, 0.0, 1.0, 1.0),
+            ProjectionMethod::default(),
         );
         let image_b = render_heightmap_debug(
             &sampler_b,
@@ -315,6 +327,7 @@ mod tests {
             32,
             CubeFace::PosX,
             (0.0, 0.0, 1.0, 1.0),
+            ProjectionMethod::default(),
         );
 
         assert_ne!(
@@ -350,6 +363,7 @@ mod tests {
             64,
             CubeFace::PosX,
             (0.0, 0.0, 1.0, 1.0),
+            ProjectionMethod::default(),
         );
 
         assert!(
@@ -429,8 +443,15 @@ mod tests {
 
         let sampler = BiomeSampler::new(42, diagram);
 
-        let image =
-            render_biome_debug(&sampler, &reg, 48, 48, CubeFace::PosZ, (0.0, 0.0, 1.0, 1.0));
+        let image = render_biome_debug(
+            &sampler,
+            &reg,
+            48,
+            48,
+            CubeFace::PosZ,
+            (0.0, 0.0, 1.0, 1.0),
+            ProjectionMethod::default(),
+        );
 
         assert_eq!(image.dimensions(), (48, 48));
         assert_eq!(image.pixels.len(), 48 * 48 * 4);
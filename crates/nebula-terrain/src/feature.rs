@@ -33,7 +33,8 @@ pub struct FeatureTypeDef {
     pub id: FeatureTypeId,
     /// Minimum spacing between instances of this feature, in engine units.
     pub min_spacing: f64,
-    /// Maximum slope (in radians from vertical) where this feature can be placed.
+    /// Maximum terrain slope (in radians; the surface normal's angle from
+    /// vertical) where this feature can be placed.
     /// Default: 0.5 (~28 degrees). Set to PI/2 to allow any slope.
     pub max_slope: f64,
     /// Minimum terrain height above sea level for placement.
@@ -105,6 +106,18 @@ pub fn poisson_disk_2d(
     points
 }
 
+/// Horizontal distance between the height samples used to estimate slope.
+const SLOPE_SAMPLE_DISTANCE: f64 = 0.5;
+
+/// Terrain slope at `(x, y)` in radians from horizontal (0 = flat, PI/2 =
+/// vertical cliff), estimated from central differences of `heights` taken
+/// `step` units away along each axis.
+pub fn terrain_slope(heights: &dyn Fn(f64, f64) -> f64, x: f64, y: f64, step: f64) -> f64 {
+    let dx = (heights(x + step, y) - heights(x - step, y)) / (2.0 * step);
+    let dy = (heights(x, y + step) - heights(x, y - step)) / (2.0 * step);
+    dx.hypot(dy).atan()
+}
+
 /// Distributes surface features across terrain using Poisson disk sampling
 /// and biome-aware filtering.
 pub struct FeaturePlacer {
@@ -138,7 +151,8 @@ impl FeaturePlacer {
     /// # Arguments
     /// - `chunk_min`, `chunk_max`: 2D bounding region of the chunk.
     /// - `chunk_seed`: Deterministic seed derived from chunk address.
-    /// - `heights`: Closure returning height at a 2D point.
+    /// - `heights`: Closure returning height at a 2D point. It is also sampled
+    ///   around each candidate to reject placements steeper than `max_slope`.
     /// - `biome_at`: Closure returning the biome ID at a 2D point.
     /// - `sea_level`: The sea level height (absolute).
     pub fn place_features(
@@ -170,6 +184,7 @@ impl FeaturePlacer {
             }
 
             let biome_id = biome_at(cx, cy);
+            let slope = terrain_slope(heights, cx, cy, SLOPE_SAMPLE_DISTANCE);
 
             let Some(biome_cfg) = self.biome_features.get(&biome_id) else {
                 continue;
@@ -187,6 +202,11 @@ impl FeaturePlacer {
                         continue;
                     }
 
+                    // Check slope constraint.
+                    if slope > feat_def.max_slope {
+                        continue;
+                    }
+
                     let scale = rng.random_range(feat_def.scale_range.0..=feat_def.scale_range.1);
                     let rotation = rng.random_range(0.0..std::f64::consts::TAU);
 
@@ -370,6 +390,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_terrain_slope_of_planes() {
+        let flat = |_x: f64, _y: f64| 10.0;
+        assert_eq!(terrain_slope(&flat, 3.0, 4.0, 0.5), 0.0);
+
+        let ramp = |x: f64, _y: f64| x;
+        let slope = terrain_slope(&ramp, 3.0, 4.0, 0.5);
+        assert!((slope - std::f64::consts::FRAC_PI_4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_feature_placer_rejects_steep_slopes() {
+        let tree = FeatureTypeDef {
+            name: "tree".into(),
+            id: FeatureTypeId(1),
+            min_spacing: 3.0,
+            max_slope: 0.5,
+            min_height_above_sea: 1.0,
+            scale_range: (0.8, 1.2),
+        };
+        let biome = BiomeId(0);
+        let mut biome_features = HashMap::new();
+        biome_features.insert(
+            biome,
+            BiomeFeatureConfig {
+                features: vec![(FeatureTypeId(1), 1.0)],
+            },
+        );
+        let placer = FeaturePlacer::new(42, vec![tree.clone()], biome_features);
+
+        // A cliff rising 2 units per unit (~63 degrees) for x < 50, then
+        // gently rolling hills (under 6 degrees) beyond.
+        let heights = |x: f64, y: f64| {
+            if x < 50.0 {
+                2.0 * x
+            } else {
+                100.0 + 0.1 * (x - 50.0) + 0.05 * (y * 0.2).sin()
+            }
+        };
+        let features = placer.place_features(
+            (0.0, 0.0),
+            (100.0, 50.0),
+            7,
+            &heights,
+            &|_x, _y| biome,
+            -100.0,
+        );
+
+        assert!(
+            !features.is_empty(),
+            "Features should be placed on the gentle side"
+        );
+        for f in &features {
+            let (x, y) = (f.position.x, f.position.z);
+            let slope = terrain_slope(&heights, x, y, SLOPE_SAMPLE_DISTANCE);
+            assert!(
+                slope <= tree.max_slope,
+                "Feature at ({x}, {y}) sits on slope {slope}"
+            );
+            assert!(x >= 50.0, "Feature at ({x}, {y}) is on the cliff");
+        }
+    }
+
     #[test]
     fn test_feature_placer_places_on_land() {
         let tree = FeatureTypeDef {
@@ -0,0 +1,98 @@
+//! Cancelling generation under LOD chunks dropped by the memory budget.
+//!
+//! When the LOD memory budget unloads a chunk, the [`AsyncChunkGenerator`]
+//! may still have voxel chunks queued under that column.
+//! [`AsyncChunkGenerator::cancel_under_columns`] is the hook the LOD side
+//! calls with the unloaded chunks to drop those tasks.
+
+use nebula_cubesphere::ChunkAddress as LodChunkAddress;
+use nebula_voxel::ChunkAddress;
+
+use crate::async_generation::AsyncChunkGenerator;
+
+impl AsyncChunkGenerator {
+    /// Cancel every pending task that lies in the column under one of
+    /// `columns`, returning how many were cancelled before a worker started
+    /// them. Tasks already running finish and arrive with `evicted: true`.
+    pub fn cancel_under_columns(&self, columns: &[LodChunkAddress]) -> usize {
+        let doomed: Vec<ChunkAddress> = self
+            .pending_addresses()
+            .into_iter()
+            .filter(|task| columns.iter().any(|lod| covers(lod, task)))
+            .collect();
+        self.cancel_batch(&doomed)
+    }
+}
+
+/// Whether the voxel chunk `task` lies in the column under `lod`.
+///
+/// Generation tasks are LOD-0 chunks addressed with `x`/`z` across the face
+/// and `y` vertical, so a LOD-`n` chunk covers `2^n × 2^n` task columns at
+/// every height.
+fn covers(lod: &LodChunkAddress, task: &ChunkAddress) -> bool {
+    if task.face != lod.face as u8 || task.x < 0 || task.z < 0 {
+        return false;
+    }
+    let shift = u32::from(lod.lod);
+    let in_range = |coord: i64, lod_coord: u32| {
+        let start = i64::from(lod_coord) << shift;
+        (start..start + (1_i64 << shift)).contains(&coord)
+    };
+    in_range(task.x, lod.x) && in_range(task.z, lod.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::async_generation::GenerationTask;
+    use nebula_cubesphere::{CubeFace, PlanetDef};
+    use nebula_math::WorldPosition;
+    use nebula_voxel::ChunkData;
+
+    fn task(address: ChunkAddress) -> GenerationTask {
+        GenerationTask {
+            address,
+            seed: 1,
+            planet: PlanetDef::earth_like("Test", WorldPosition::default(), 1),
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn test_covers_maps_lod_columns() {
+        let lod1 = LodChunkAddress::new(CubeFace::NegX, 1, 2, 3);
+        let face = CubeFace::NegX as u8;
+        assert!(covers(&lod1, &ChunkAddress::new(4, 9, 6, face)));
+        assert!(covers(&lod1, &ChunkAddress::new(5, -2, 7, face)));
+        assert!(!covers(&lod1, &ChunkAddress::new(6, 0, 6, face)));
+        assert!(!covers(&lod1, &ChunkAddress::new(4, 0, 6, face + 1)));
+    }
+
+    #[test]
+    fn test_cancel_under_columns_cancels_only_covered_tasks() {
+        // Workers block until the gate closes, so submitted tasks stay queued.
+        let (gate_tx, gate_rx) = crossbeam_channel::unbounded::<()>();
+        let generator = AsyncChunkGenerator::with_generator(1, 64, 64, move |_| {
+            let _ = gate_rx.recv();
+            ChunkData::new_air()
+        });
+
+        let face = CubeFace::PosY;
+        // A blocker occupies the worker; two tasks sit under the column, one outside.
+        let blocker = ChunkAddress::new(500, 0, 500, face as u8);
+        generator.submit(task(blocker)).unwrap();
+        for y in 0..2 {
+            generator
+                .submit(task(ChunkAddress::new(10, y, 10, face as u8)))
+                .unwrap();
+        }
+        let kept = ChunkAddress::new(0, 0, 0, face as u8);
+        generator.submit(task(kept)).unwrap();
+
+        let column = LodChunkAddress::new(face, 0, 10, 10);
+        // The single worker is stuck on the blocker, so both tasks were queued.
+        assert_eq!(generator.cancel_under_columns(&[column]), 2);
+        assert!(generator.is_pending(&kept));
+        drop(gate_tx);
+    }
+}
@@ -0,0 +1,104 @@
+//! Worker threads of the [`AsyncChunkGenerator`](crate::AsyncChunkGenerator)
+//! and the terrain factory they generate chunks with.
+//!
+//! Each submitted task carries an atomic lifecycle state shared with the
+//! main thread: workers claim queued tasks, and cancellation either skips a
+//! task before it runs or drops (or tags) its result afterwards.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, AtomicU64, Ordering};
+
+use crossbeam_channel::{Receiver, Sender};
+use nebula_voxel::ChunkData;
+
+use crate::async_generation::{GeneratedChunk, GenerationTask};
+use crate::terrain_generator::TerrainGenerator;
+
+/// Lifecycle of a submitted task, shared between the main thread and the
+/// worker that picks it up.
+pub(crate) const TASK_QUEUED: u8 = 0;
+/// A worker is generating the chunk.
+pub(crate) const TASK_RUNNING: u8 = 1;
+/// Cancelled: skipped if still queued, result dropped if running.
+pub(crate) const TASK_CANCELLED: u8 = 2;
+/// Evicted while running: the result is delivered with `evicted: true`.
+pub(crate) const TASK_EVICTED: u8 = 3;
+
+/// Internal wrapper that carries the task and its shared state.
+pub(crate) struct PrioritizedTask {
+    pub(crate) task: GenerationTask,
+    pub(crate) state: Arc<AtomicU8>,
+}
+
+/// Function that generates a chunk's voxel data from a task.
+pub(crate) type GenerateFn = dyn Fn(&GenerationTask) -> ChunkData + Send + Sync;
+
+/// Builds the [`TerrainGenerator`] for a task's seed, e.g. a biome-aware
+/// [`TerrainGenerator::with_biomes`] with per-biome layering.
+pub type TerrainFactory = dyn Fn(u64) -> TerrainGenerator + Send + Sync;
+
+/// Spawn `thread_count` workers that generate the tasks from `receiver`
+/// with `generate` and send the results to `sender`.
+pub(crate) fn spawn_workers(
+    thread_count: usize,
+    receiver: &Receiver<PrioritizedTask>,
+    sender: &Sender<GeneratedChunk>,
+    in_flight: &Arc<AtomicU64>,
+    generate: &Arc<GenerateFn>,
+) {
+    for _ in 0..thread_count {
+        let receiver = receiver.clone();
+        let sender = sender.clone();
+        let in_flight = Arc::clone(in_flight);
+        let generate = Arc::clone(generate);
+
+        std::thread::Builder::new()
+            .name("chunk-gen-worker".into())
+            .spawn(move || {
+                while let Ok(ptask) = receiver.recv() {
+                    // Claim the task; fails if it was cancelled while queued.
+                    if ptask
+                        .state
+                        .compare_exchange(
+                            TASK_QUEUED,
+                            TASK_RUNNING,
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                        )
+                        .is_err()
+                    {
+                        in_flight.fetch_sub(1, Ordering::Relaxed);
+                        continue;
+                    }
+
+                    let start = std::time::Instant::now();
+                    let data = generate(&ptask.task);
+                    let elapsed = start.elapsed().as_micros() as u64;
+
+                    // Check cancellation after generation.
+                    let state = ptask.state.load(Ordering::Acquire);
+                    if state != TASK_CANCELLED {
+                        let _ = sender.send(GeneratedChunk {
+                            address: ptask.task.address,
+                            data,
+                            generation_time_us: elapsed,
+                            evicted: state == TASK_EVICTED,
+                        });
+                    }
+
+                    in_flight.fetch_sub(1, Ordering::Relaxed);
+                }
+            })
+            .expect("Failed to spawn chunk generation worker thread");
+    }
+}
+
+/// Generate a chunk synchronously. This is the CPU-intensive function
+/// that runs on worker threads.
+///
+/// `terrain` builds the generator for the task's seed; its biome sampler
+/// picks each column's [`TerrainLayerDef`](crate::TerrainLayerDef). Pass
+/// [`TerrainGenerator::new`] for the single-biome default layering.
+pub fn generate_chunk_sync(task: &GenerationTask, terrain: &TerrainFactory) -> ChunkData {
+    terrain(task.seed).generate_chunk(&task.address)
+}
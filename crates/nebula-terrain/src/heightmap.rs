@@ -2,6 +2,9 @@
 //!
 //! Composites multiple octaves of simplex noise to produce natural-looking
 //! terrain height values with features at many spatial frequencies.
+//! Optional domain warping offsets the sample coordinates by a secondary
+//! noise field first (`fbm(p + warp(p))`), bending ridges and breaking up
+//! grid-aligned artifacts.
 
 use noise::{NoiseFn, Simplex};
 
@@ -25,6 +28,12 @@ pub struct HeightmapParams {
     /// Amplitude of the first octave. The maximum theoretical height contribution
     /// of the first octave in engine units. Default: 4000.0 (4 km).
     pub amplitude: f64,
+    /// Maximum displacement of the sample coordinates by the warp field, in
+    /// engine units. Default: 0.0 (warping disabled).
+    pub warp_strength: f64,
+    /// Frequency of the warp noise field. Lower values bend terrain over
+    /// larger distances. Default: 0.0005.
+    pub warp_frequency: f64,
 }
 
 impl Default for HeightmapParams {
@@ -36,10 +45,23 @@ impl Default for HeightmapParams {
             persistence: 0.5,
             amplitude: 4000.0,
             base_frequency: 0.001,
+            warp_strength: 0.0,
+            warp_frequency: 0.0005,
         }
     }
 }
 
+/// Added to the world seed to derive the warp noise seed, so the warp field
+/// is independent of the height field.
+const WARP_SEED_OFFSET: u64 = 0x57A2_9F1D;
+
+/// Octaves of the warp field. A few suffice; it only needs to be smooth.
+const WARP_OCTAVES: u32 = 3;
+
+/// Offsets between the per-axis warp samples so each axis is displaced by an
+/// uncorrelated value.
+const WARP_AXIS_OFFSETS: [[f64; 3]; 3] = [[0.0, 0.0, 0.0], [5.2, 1.3, 7.7], [9.4, 3.1, 2.8]];
+
 /// Generates terrain height values using fractal Brownian motion over simplex noise.
 ///
 /// Each sample composites multiple octaves of noise, where each successive octave
@@ -47,6 +69,7 @@ impl Default for HeightmapParams {
 /// progressively finer scales.
 pub struct HeightmapSampler {
     noise: Simplex,
+    warp_noise: Simplex,
     params: HeightmapParams,
 }
 
@@ -54,7 +77,12 @@ impl HeightmapSampler {
     /// Create a new sampler with the given parameters.
     pub fn new(params: HeightmapParams) -> Self {
         let noise = Simplex::new(params.seed as u32);
-        Self { noise, params }
+        let warp_noise = Simplex::new(params.seed.wrapping_add(WARP_SEED_OFFSET) as u32);
+        Self {
+            noise,
+            warp_noise,
+            params,
+        }
     }
 
     /// Sample the heightmap at a 2D coordinate on the cube face.
@@ -63,6 +91,17 @@ impl HeightmapSampler {
     /// approximately `[-max_amplitude, +max_amplitude]` where `max_amplitude`
     /// is the geometric sum of all octave amplitudes.
     pub fn sample(&self, x: f64, y: f64) -> f64 {
+        let (x, y) = if self.warp_enabled() {
+            let strength = self.params.warp_strength;
+            let [ax, ay, _] = WARP_AXIS_OFFSETS[1];
+            (
+                x + self.warp_fbm(|f| self.warp_noise.get([x * f, y * f])) * strength,
+                y + self.warp_fbm(|f| self.warp_noise.get([x * f + ax, y * f + ay])) * strength,
+            )
+        } else {
+            (x, y)
+        };
+
         let mut total = 0.0;
         let mut frequency = self.params.base_frequency;
         let mut amplitude = self.params.amplitude;
@@ -82,6 +121,23 @@ impl HeightmapSampler {
 
     /// Sample using a 3D sphere-surface coordinate to avoid UV seam artifacts.
     pub fn sample_3d(&self, point: glam::DVec3) -> f64 {
+        let point = if self.warp_enabled() {
+            let warp = |[ox, oy, oz]: [f64; 3]| {
+                self.warp_fbm(|f| {
+                    self.warp_noise
+                        .get([point.x * f + ox, point.y * f + oy, point.z * f + oz])
+                })
+            };
+            point
+                + glam::DVec3::new(
+                    warp(WARP_AXIS_OFFSETS[0]),
+                    warp(WARP_AXIS_OFFSETS[1]),
+                    warp(WARP_AXIS_OFFSETS[2]),
+                ) * self.params.warp_strength
+        } else {
+            point
+        };
+
         let mut total = 0.0;
         let mut frequency = self.params.base_frequency;
         let mut amplitude = self.params.amplitude;
@@ -100,6 +156,27 @@ impl HeightmapSampler {
         total
     }
 
+    fn warp_enabled(&self) -> bool {
+        self.params.warp_strength != 0.0 && self.params.warp_frequency != 0.0
+    }
+
+    /// Sum [`WARP_OCTAVES`] octaves of the warp noise, normalized to roughly
+    /// `[-1, 1]`. `sample_at` reads the warp noise with the coordinates
+    /// multiplied by the given octave frequency.
+    fn warp_fbm(&self, sample_at: impl Fn(f64) -> f64) -> f64 {
+        let mut total = 0.0;
+        let mut norm = 0.0;
+        let mut frequency = self.params.warp_frequency;
+        let mut amplitude = 1.0;
+        for _ in 0..WARP_OCTAVES {
+            total += sample_at(frequency) * amplitude;
+            norm += amplitude;
+            frequency *= self.params.lacunarity;
+            amplitude *= self.params.persistence;
+        }
+        total / norm
+    }
+
     /// Compute the theoretical maximum absolute amplitude (geometric series sum).
     ///
     /// Useful for normalizing output or clamping to a known range.
@@ -125,6 +202,15 @@ mod tests {
 
     const EPSILON: f64 = 1e-12;
 
+    fn warped_params(seed: u64) -> HeightmapParams {
+        HeightmapParams {
+            seed,
+            warp_strength: 400.0,
+            warp_frequency: 0.002,
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_determinism_same_seed_same_coord() {
         let params = HeightmapParams {
@@ -267,4 +353,44 @@ mod tests {
             "Zero amplitude should produce zero height, got {h}"
         );
     }
+
+    #[test]
+    fn test_warping_changes_output() {
+        let plain = HeightmapSampler::new(HeightmapParams {
+            seed: 42,
+            ..Default::default()
+        });
+        let warped = HeightmapSampler::new(warped_params(42));
+
+        let differing = (0..100)
+            .map(|i| (i as f64 * 37.0, i as f64 * -53.0))
+            .filter(|&(x, y)| (plain.sample(x, y) - warped.sample(x, y)).abs() > EPSILON)
+            .count();
+        assert!(differing > 90, "only {differing}/100 samples changed");
+
+        let p = glam::DVec3::new(1200.0, -300.0, 450.0);
+        assert!((plain.sample_3d(p) - warped.sample_3d(p)).abs() > EPSILON);
+    }
+
+    #[test]
+    fn test_warping_is_deterministic() {
+        let a = HeightmapSampler::new(warped_params(7));
+        let b = HeightmapSampler::new(warped_params(7));
+        for i in 0..100 {
+            let (x, y) = (i as f64 * 13.5, i as f64 * 7.25);
+            assert_eq!(a.sample(x, y).to_bits(), b.sample(x, y).to_bits());
+            let p = glam::DVec3::new(x, y, -x);
+            assert_eq!(a.sample_3d(p).to_bits(), b.sample_3d(p).to_bits());
+        }
+    }
+
+    #[test]
+    fn test_warped_height_within_expected_range() {
+        let sampler = HeightmapSampler::new(warped_params(3));
+        let max_amp = sampler.max_amplitude();
+        for i in 0..1000 {
+            let h = sampler.sample(i as f64 * 11.0, i as f64 * 3.0);
+            assert!(h.abs() <= max_amp + EPSILON);
+        }
+    }
 }
@@ -2,35 +2,42 @@
 
 mod async_generation;
 mod cave;
+mod cave_worm;
 pub mod debug_viz;
 mod feature;
+mod generation_budget;
+mod generation_worker;
 mod heightmap;
 mod ore;
+mod terrain_generator;
 mod terrain_height;
 
 pub mod biome;
 pub mod seed;
 
-pub use async_generation::{
-    AsyncChunkGenerator, GeneratedChunk, GenerationTask, generate_chunk_sync,
-};
+pub use async_generation::{AsyncChunkGenerator, GeneratedChunk, GenerationTask};
 pub use biome::{
-    BiomeDef, BiomeId, BiomeRegistry, BiomeRegistryError, BiomeSampler, WhittakerDiagram,
-    WhittakerRegion,
+    BiomeDef, BiomeDisplayName, BiomeId, BiomeRegistry, BiomeRegistryError, BiomeSampler,
+    WhittakerDiagram, WhittakerRegion,
 };
-pub use cave::{CaveCarver, CaveConfig};
+pub use cave::{CaveCarver, CaveConfig, CaveMode};
+pub use cave_worm::WormTunnel;
 pub use debug_viz::{
     DebugImage, SliceParams, TerrainDebugState, biome_color, height_to_color, render_biome_debug,
     render_cave_cross_section, render_heightmap_debug, render_ore_heatmap,
 };
 pub use feature::{
     BiomeFeatureConfig, FeaturePlacer, FeatureTypeDef, FeatureTypeId, PlacedFeature,
-    poisson_disk_2d,
+    poisson_disk_2d, terrain_slope,
 };
+pub use generation_worker::{TerrainFactory, generate_chunk_sync};
 pub use heightmap::{HeightmapParams, HeightmapSampler};
 pub use ore::{OreDistribution, OreDistributor, default_ore_distributions};
 pub use seed::{
-    FixedPoint64, chunk_rng, derive_chunk_seed, det_atan2, det_cos, det_sin, det_sqrt,
-    fbm_fixed_point, generate_and_hash, hash_chunk_data,
+    DeterministicNoise, FixedPoint64, chunk_rng, derive_chunk_seed, det_atan2, det_cos, det_sin,
+    det_sqrt, fbm_fixed_point, generate_and_hash, hash_chunk_data,
+};
+pub use terrain_generator::{TerrainGenerator, TerrainLayerDef};
+pub use terrain_height::{
+    SurfaceType, TerrainHeightConfig, TerrainHeightSampler, column_surface_height,
 };
-pub use terrain_height::{TerrainHeightConfig, TerrainHeightSampler, column_surface_height};
@@ -1,12 +1,31 @@
 //! Ore/resource distribution for underground voxel terrain.
 //!
-//! Uses per-ore-type 3D noise fields to place ore veins at configured
-//! depth ranges and densities.
+//! Each ore type scatters vein centers deterministically over a 3D grid of
+//! cells. A voxel is ore when it falls inside a vein's noise-perturbed
+//! ellipsoid, so hits cluster into connected deposits rather than isolated
+//! blocks. Veins respect the per-type depth range.
 
 use glam::DVec3;
 use nebula_voxel::VoxelTypeId;
 use noise::{NoiseFn, Simplex};
 
+/// Vein cell edge length as a multiple of `vein_size`.
+const VEIN_CELL_FACTOR: f64 = 4.0;
+
+/// Largest supported `vein_elongation`; keeps every vein inside the 3×3×3
+/// block of cells around its own.
+const MAX_VEIN_ELONGATION: f64 = 3.0;
+
+/// Fractional radius variation contributed by surface noise.
+const VEIN_WOBBLE: f64 = 0.3;
+
+/// A vein placed in one grid cell.
+struct Vein {
+    center: DVec3,
+    axis: DVec3,
+    radius: f64,
+}
+
 /// Configuration for a single ore type's underground distribution.
 #[derive(Clone, Debug)]
 pub struct OreDistribution {
@@ -18,12 +37,19 @@ pub struct OreDistribution {
     pub min_depth: f64,
     /// Maximum depth below the terrain surface where this ore can appear.
     pub max_depth: f64,
-    /// Noise threshold for ore placement. Voxels where noise > threshold
-    /// become ore. Higher threshold = rarer ore. Range: \[0.0, 1.0\].
+    /// Rarity of veins. Each vein cell holds a vein with probability
+    /// `2 * (1 - noise_threshold)`, so higher threshold = rarer ore.
+    /// Range: \[0.0, 1.0\].
     pub noise_threshold: f64,
-    /// Spatial scale of ore veins (noise frequency).
-    /// Smaller values = larger veins, larger values = smaller veins.
+    /// Frequency of the noise that roughens vein surfaces.
+    /// Larger values = more irregular veins.
     pub vein_scale: f64,
+    /// Typical vein radius in engine units. Vein centers are spaced about
+    /// four radii apart.
+    pub vein_size: f64,
+    /// Ratio of a vein's long axis to its short axes (clamped to 1–3).
+    /// 1.0 gives round blobs; larger values give elongated seams.
+    pub vein_elongation: f64,
     /// Seed offset to decorrelate this ore's noise from other ore types.
     pub seed_offset: u64,
 }
@@ -32,6 +58,102 @@ pub struct OreDistribution {
 struct OreDistributionRuntime {
     config: OreDistribution,
     noise: Simplex,
+    seed: u64,
+}
+
+impl OreDistributionRuntime {
+    /// Whether `pos` lies inside any vein from its own or a neighbouring cell.
+    fn in_vein(&self, pos: DVec3, surface_height: f64) -> bool {
+        let cfg = &self.config;
+        if cfg.vein_size <= 0.0 {
+            return false;
+        }
+        let cell_size = cfg.vein_size * VEIN_CELL_FACTOR;
+        let elongation = cfg.vein_elongation.clamp(1.0, MAX_VEIN_ELONGATION);
+        let cell = (pos / cell_size).floor();
+
+        // Only visit neighbouring cells whose veins could reach `pos`.
+        let reach = cfg.vein_size * elongation * (1.0 + VEIN_WOBBLE);
+        let local = pos - cell * cell_size;
+        let span = |l: f64| {
+            let lo = if l < reach { -1 } else { 0 };
+            let hi = if cell_size - l < reach { 1 } else { 0 };
+            lo..=hi
+        };
+
+        // Surface noise is only sampled once a vein is close enough to matter.
+        let mut scale = None;
+        for dx in span(local.x) {
+            for dy in span(local.y) {
+                for dz in span(local.z) {
+                    let neighbor = cell + DVec3::new(dx as f64, dy as f64, dz as f64);
+                    let Some(vein) = self.vein_in_cell(neighbor, cell_size) else {
+                        continue;
+                    };
+                    let d = pos - vein.center;
+                    let along = d.dot(vein.axis);
+                    let across_sq = d.length_squared() - along * along;
+                    let dist_sq = (along / elongation).powi(2) + across_sq;
+                    if dist_sq > (vein.radius * (1.0 + VEIN_WOBBLE)).powi(2) {
+                        continue;
+                    }
+                    // Anchor veins in the ore's depth band.
+                    let center_depth = surface_height - vein.center.length();
+                    if center_depth < cfg.min_depth || center_depth > cfg.max_depth {
+                        continue;
+                    }
+                    let scale = *scale.get_or_insert_with(|| {
+                        let wobble = self.noise.get([
+                            pos.x * cfg.vein_scale,
+                            pos.y * cfg.vein_scale,
+                            pos.z * cfg.vein_scale,
+                        ]);
+                        1.0 + VEIN_WOBBLE * wobble
+                    });
+                    let radius = vein.radius * scale;
+                    if dist_sq <= radius * radius {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// The vein seeded in `cell`, if that cell has one.
+    fn vein_in_cell(&self, cell: DVec3, cell_size: f64) -> Option<Vein> {
+        let mut state = self.seed;
+        for c in [cell.x, cell.y, cell.z] {
+            state = splitmix64(state ^ (c as i64 as u64));
+        }
+        let mut next = || {
+            state = splitmix64(state);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+
+        let chance = (2.0 * (1.0 - self.config.noise_threshold)).clamp(0.0, 1.0);
+        if next() >= chance {
+            return None;
+        }
+        let center = (cell + DVec3::new(next(), next(), next())) * cell_size;
+        let axis = DVec3::new(next() - 0.5, next() - 0.5, next() - 0.5)
+            .try_normalize()
+            .unwrap_or(DVec3::X);
+        let radius = self.config.vein_size * (0.6 + 0.4 * next());
+        Some(Vein {
+            center,
+            axis,
+            radius,
+        })
+    }
+}
+
+/// SplitMix64 step; a cheap, well-mixed hash for per-cell vein placement.
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
 }
 
 /// Distributes ore veins in subsurface terrain using 3D noise.
@@ -48,7 +170,11 @@ impl OreDistributor {
             .map(|config| {
                 let ore_seed = seed.wrapping_add(config.seed_offset);
                 let noise = Simplex::new(ore_seed as u32);
-                OreDistributionRuntime { config, noise }
+                OreDistributionRuntime {
+                    config,
+                    noise,
+                    seed: ore_seed,
+                }
             })
             .collect();
 
@@ -83,17 +209,7 @@ impl OreDistributor {
                 continue;
             }
 
-            // Sample 3D noise at the voxel position, scaled by vein_scale.
-            let noise_val = ore_rt.noise.get([
-                voxel_pos.x * cfg.vein_scale,
-                voxel_pos.y * cfg.vein_scale,
-                voxel_pos.z * cfg.vein_scale,
-            ]);
-
-            // Normalize from [-1, 1] to [0, 1].
-            let normalized = (noise_val + 1.0) * 0.5;
-
-            if normalized > cfg.noise_threshold {
+            if ore_rt.in_vein(voxel_pos, surface_height) {
                 return Some(cfg.ore_type);
             }
         }
@@ -120,6 +236,8 @@ pub fn default_ore_distributions() -> Vec<OreDistribution> {
             max_depth: 200.0,
             noise_threshold: 0.75,
             vein_scale: 0.08,
+            vein_size: 3.0,
+            vein_elongation: 1.5,
             seed_offset: 0x0001,
         },
         OreDistribution {
@@ -129,6 +247,8 @@ pub fn default_ore_distributions() -> Vec<OreDistribution> {
             max_depth: 300.0,
             noise_threshold: 0.80,
             vein_scale: 0.10,
+            vein_size: 2.5,
+            vein_elongation: 2.0,
             seed_offset: 0x0002,
         },
         OreDistribution {
@@ -138,6 +258,8 @@ pub fn default_ore_distributions() -> Vec<OreDistribution> {
             max_depth: 250.0,
             noise_threshold: 0.82,
             vein_scale: 0.10,
+            vein_size: 2.5,
+            vein_elongation: 2.0,
             seed_offset: 0x0003,
         },
         OreDistribution {
@@ -147,6 +269,8 @@ pub fn default_ore_distributions() -> Vec<OreDistribution> {
             max_depth: 400.0,
             noise_threshold: 0.90,
             vein_scale: 0.15,
+            vein_size: 1.5,
+            vein_elongation: 1.5,
             seed_offset: 0x0004,
         },
         OreDistribution {
@@ -156,6 +280,8 @@ pub fn default_ore_distributions() -> Vec<OreDistribution> {
             max_depth: 500.0,
             noise_threshold: 0.95,
             vein_scale: 0.20,
+            vein_size: 1.0,
+            vein_elongation: 1.0,
             seed_offset: 0x0005,
         },
     ]
@@ -262,6 +388,8 @@ mod tests {
             max_depth: 500.0,
             noise_threshold: 0.5,
             vein_scale: 0.1,
+            vein_size: 2.0,
+            vein_elongation: 1.5,
             seed_offset: 0x100,
         };
         let ore_rare = OreDistribution {
@@ -271,6 +399,8 @@ mod tests {
             max_depth: 500.0,
             noise_threshold: 0.95,
             vein_scale: 0.1,
+            vein_size: 2.0,
+            vein_elongation: 1.5,
             seed_offset: 0x100,
         };
 
@@ -325,4 +455,40 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_ore_hits_cluster_into_veins() {
+        let coal = default_ore_distributions().remove(0);
+        let dist = OreDistributor::new(42, vec![coal]);
+        // Depths 60–100, inside coal's band.
+        let surface_height = PLANET_RADIUS + 100.0;
+        let is_ore = |x: i32, y: i32, z: i32| {
+            let pos = DVec3::new(PLANET_RADIUS + f64::from(y), f64::from(x), f64::from(z));
+            dist.sample_ore(pos, surface_height).is_some()
+        };
+
+        let (mut samples, mut hits, mut adjacent_hits) = (0u32, 0u32, 0u32);
+        for x in 0..60 {
+            for y in 0..40 {
+                for z in 0..60 {
+                    samples += 1;
+                    if is_ore(x, y, z) {
+                        hits += 1;
+                        if is_ore(x + 1, y, z) {
+                            adjacent_hits += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        assert!(hits > 0, "expected some coal in {samples} samples");
+        let global_rate = f64::from(hits) / f64::from(samples);
+        let adjacent_rate = f64::from(adjacent_hits) / f64::from(hits);
+        assert!(
+            adjacent_rate > 5.0 * global_rate,
+            "neighbours of ore should be ore far more often than average: \
+             adjacent={adjacent_rate:.3}, global={global_rate:.4}"
+        );
+    }
 }
@@ -2,13 +2,14 @@
 //!
 //! Provides per-chunk RNG derivation from a world seed and chunk address,
 //! deterministic math functions via `libm`, and a fixed-point fBm accumulator
-//! for cross-platform bit-exact terrain generation.
+//! for cross-platform bit-exact terrain generation. [`DeterministicNoise`]
+//! exposes the same accumulator to game code (structure placement, loot).
 
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
 use nebula_voxel::{CHUNK_SIZE, ChunkAddress, ChunkData};
-use noise::NoiseFn;
+use noise::{NoiseFn, Simplex};
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
 
@@ -150,6 +151,83 @@ pub fn fbm_fixed_point(
     total
 }
 
+// ---------------------------------------------------------------------------
+// Public noise sampler
+// ---------------------------------------------------------------------------
+
+/// Seeded fBm noise for game code, bit-exact across runs and platforms.
+///
+/// Samples simplex noise seeded exactly like the terrain heightmap and
+/// accumulates octaves with [`fbm_fixed_point`], so a given seed yields the
+/// same values as the terrain path. Coordinates are used as-is (base
+/// frequency 1); scale them to pick the feature size. Results are
+/// normalized to `[-1, 1]`.
+#[derive(Clone, Debug)]
+pub struct DeterministicNoise {
+    noise: Simplex,
+    octaves: u32,
+}
+
+impl DeterministicNoise {
+    /// Octaves sampled by [`Self::new`].
+    pub const DEFAULT_OCTAVES: u32 = 4;
+
+    const LACUNARITY: f64 = 2.0;
+    const PERSISTENCE: f64 = 0.5;
+
+    /// Noise for `seed` with [`Self::DEFAULT_OCTAVES`] octaves.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            noise: Simplex::new(seed as u32),
+            octaves: Self::DEFAULT_OCTAVES,
+        }
+    }
+
+    /// Use `octaves` octaves (at least one) instead of the default.
+    #[must_use]
+    pub fn with_octaves(mut self, octaves: u32) -> Self {
+        self.octaves = octaves.max(1);
+        self
+    }
+
+    /// Number of octaves sampled.
+    pub fn octaves(&self) -> u32 {
+        self.octaves
+    }
+
+    /// Noise value at `(x, y)`, in `[-1, 1]`.
+    pub fn sample_2d(&self, x: f64, y: f64) -> f64 {
+        self.sample_3d(glam::DVec3::new(x, y, 0.0))
+    }
+
+    /// Noise value at `point`, in `[-1, 1]`.
+    pub fn sample_3d(&self, point: glam::DVec3) -> f64 {
+        self.sample_fixed(point).to_f64().clamp(-1.0, 1.0)
+    }
+
+    /// Raw fixed-point accumulation at `point`, for callers that need to
+    /// keep bit-exact values through further arithmetic.
+    pub fn sample_fixed(&self, point: glam::DVec3) -> FixedPoint64 {
+        fbm_fixed_point(
+            &self.noise,
+            point,
+            self.octaves,
+            Self::LACUNARITY,
+            Self::PERSISTENCE,
+            1.0,
+            self.normalizing_amplitude(),
+        )
+    }
+
+    /// First-octave amplitude at which all octaves together sum to 1.
+    fn normalizing_amplitude(&self) -> f64 {
+        let total: f64 = (0..self.octaves)
+            .map(|i| Self::PERSISTENCE.powi(i as i32))
+            .sum();
+        1.0 / total
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Verification
 // ---------------------------------------------------------------------------
@@ -158,7 +236,7 @@ pub fn fbm_fixed_point(
 ///
 /// Hashes every voxel in the chunk (32³ voxels) to produce a u64 digest.
 pub fn generate_and_hash(task: &GenerationTask) -> u64 {
-    let chunk = crate::generate_chunk_sync(task);
+    let chunk = crate::generate_chunk_sync(task, &crate::TerrainGenerator::new);
     hash_chunk_data(&chunk)
 }
 
@@ -377,4 +455,45 @@ mod tests {
 
         assert_eq!(a, b, "Fixed-point fBm must be bit-exact");
     }
+
+    #[test]
+    fn test_deterministic_noise_matches_fbm_fixed_point() {
+        let sampler = DeterministicNoise::new(42);
+        let noise = noise::Simplex::new(42);
+        let amplitude = 1.0 / (1.0 + 0.5 + 0.25 + 0.125);
+        for point in [
+            glam::DVec3::new(1.5, 2.5, 3.5),
+            glam::DVec3::new(-10.25, 0.75, 4.0),
+        ] {
+            let direct = fbm_fixed_point(&noise, point, 4, 2.0, 0.5, 1.0, amplitude);
+            assert_eq!(sampler.sample_fixed(point), direct);
+            assert_eq!(sampler.sample_3d(point), direct.to_f64());
+        }
+        let direct_2d = fbm_fixed_point(
+            &noise,
+            glam::DVec3::new(3.25, -1.5, 0.0),
+            4,
+            2.0,
+            0.5,
+            1.0,
+            amplitude,
+        );
+        assert_eq!(sampler.sample_2d(3.25, -1.5), direct_2d.to_f64());
+    }
+
+    #[test]
+    fn test_deterministic_noise_stable_and_bounded() {
+        let a = DeterministicNoise::new(7).with_octaves(6);
+        let b = DeterministicNoise::new(7).with_octaves(6);
+        let other_seed = DeterministicNoise::new(8).with_octaves(6);
+        let mut differs = false;
+        for i in 0..200 {
+            let (x, y) = (i as f64 * 0.37, i as f64 * -0.91);
+            let value = a.sample_2d(x, y);
+            assert_eq!(value.to_bits(), b.sample_2d(x, y).to_bits());
+            assert!((-1.0..=1.0).contains(&value), "{value} out of range");
+            differs |= value != other_seed.sample_2d(x, y);
+        }
+        assert!(differs, "different seeds should give different noise");
+    }
 }
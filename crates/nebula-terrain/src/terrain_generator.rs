@@ -0,0 +1,303 @@
+//! Column-based terrain voxel assignment with per-biome layering.
+//!
+//! [`TerrainGenerator`] samples the heightmap for a column's surface, picks the
+//! column's biome with [`BiomeSampler::sample`], and fills the column through
+//! that biome's [`TerrainLayerDef`]: surface voxel on top, subsurface voxels
+//! down to a fixed depth, bedrock below.
+
+use std::ops::Range;
+
+use glam::DVec3;
+use hashbrown::HashMap;
+use nebula_voxel::{ChunkAddress, ChunkData, VoxelTypeId};
+
+use crate::biome::{BiomeDef, BiomeId, BiomeSampler, WhittakerDiagram};
+use crate::heightmap::{HeightmapParams, HeightmapSampler};
+
+/// Voxel edge length in millimeters.
+const VOXEL_SIZE_MM: i64 = 1_000;
+
+/// Voxels per chunk edge.
+const CHUNK_SIZE: usize = 32;
+
+/// Air voxel: always ID 0 in the voxel registry.
+const AIR: VoxelTypeId = VoxelTypeId(0);
+
+/// Vertical material layering for one biome.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TerrainLayerDef {
+    /// Voxel type of the topmost solid voxel (e.g., grass, sand, snow).
+    pub surface_voxel: VoxelTypeId,
+    /// Voxel type directly below the surface (e.g., dirt, sandstone).
+    pub subsurface_voxel: VoxelTypeId,
+    /// Depth below the surface, in mm, down to which subsurface voxels are
+    /// placed. Anything deeper is bedrock.
+    pub subsurface_depth_mm: i64,
+    /// Voxel type below the subsurface layer.
+    pub bedrock_voxel: VoxelTypeId,
+}
+
+impl TerrainLayerDef {
+    /// Layering that takes its surface and subsurface voxels from a biome.
+    pub fn from_biome(
+        biome: &BiomeDef,
+        subsurface_depth_mm: i64,
+        bedrock_voxel: VoxelTypeId,
+    ) -> Self {
+        Self {
+            surface_voxel: biome.surface_voxel,
+            subsurface_voxel: biome.subsurface_voxel,
+            subsurface_depth_mm,
+            bedrock_voxel,
+        }
+    }
+
+    /// Voxel type at `altitude_mm` in a column whose surface is at
+    /// `surface_altitude_mm`.
+    ///
+    /// Above the surface is air, the surface itself gets
+    /// [`surface_voxel`](Self::surface_voxel), depths up to and including
+    /// [`subsurface_depth_mm`](Self::subsurface_depth_mm) get
+    /// [`subsurface_voxel`](Self::subsurface_voxel), and everything deeper
+    /// gets [`bedrock_voxel`](Self::bedrock_voxel).
+    pub fn assign(&self, altitude_mm: i64, surface_altitude_mm: i64) -> VoxelTypeId {
+        let depth = surface_altitude_mm - altitude_mm;
+        if depth < 0 {
+            AIR
+        } else if depth == 0 {
+            self.surface_voxel
+        } else if depth <= self.subsurface_depth_mm {
+            self.subsurface_voxel
+        } else {
+            self.bedrock_voxel
+        }
+    }
+}
+
+impl Default for TerrainLayerDef {
+    /// Grass over three voxels of dirt over stone.
+    fn default() -> Self {
+        Self {
+            surface_voxel: VoxelTypeId(3),
+            subsurface_voxel: VoxelTypeId(2),
+            subsurface_depth_mm: 3 * VOXEL_SIZE_MM,
+            bedrock_voxel: VoxelTypeId(1),
+        }
+    }
+}
+
+/// Fills voxel columns from a heightmap and per-biome layering.
+///
+/// Coordinates are voxel units in the chunk grid; biomes are sampled at
+/// `(x, 0, z)`, so the biome noise frequencies apply per voxel.
+pub struct TerrainGenerator {
+    heightmap: HeightmapSampler,
+    biomes: BiomeSampler,
+    layers: HashMap<BiomeId, TerrainLayerDef>,
+    /// Layering for biomes without an entry in the layer table.
+    pub default_layer: TerrainLayerDef,
+    /// Voxel Y range covered by [`generate_column`](Self::generate_column).
+    pub column_range: Range<i64>,
+}
+
+impl TerrainGenerator {
+    /// Height added to the heightmap so terrain sits around chunk row 0.
+    const SURFACE_BIAS: f64 = 16.0;
+
+    /// Single-biome generator: every column uses
+    /// [`default_layer`](Self::default_layer).
+    pub fn new(seed: u64) -> Self {
+        let diagram = WhittakerDiagram {
+            regions: Vec::new(),
+            fallback: BiomeId(0),
+        };
+        Self::with_biomes(seed, BiomeSampler::new(seed, diagram))
+    }
+
+    /// Generator that picks each column's layering from `biomes`.
+    ///
+    /// Register layering per biome with [`set_layer`](Self::set_layer).
+    pub fn with_biomes(seed: u64, biomes: BiomeSampler) -> Self {
+        let params = HeightmapParams {
+            seed,
+            octaves: 4,
+            amplitude: 16.0,
+            base_frequency: 0.02,
+            ..Default::default()
+        };
+        Self {
+            heightmap: HeightmapSampler::new(params),
+            biomes,
+            layers: HashMap::new(),
+            default_layer: TerrainLayerDef::default(),
+            column_range: -64..128,
+        }
+    }
+
+    /// Use `layer` for every column in `biome`.
+    pub fn set_layer(&mut self, biome: BiomeId, layer: TerrainLayerDef) {
+        self.layers.insert(biome, layer);
+    }
+
+    /// Voxel Y of the topmost solid voxel in column `(x, z)`.
+    pub fn surface_height(&self, x: i64, z: i64) -> i64 {
+        let height = self.heightmap.sample(x as f64, z as f64) + Self::SURFACE_BIAS;
+        height.ceil() as i64 - 1
+    }
+
+    /// Biome of column `(x, z)`.
+    pub fn biome_at(&self, x: i64, z: i64) -> BiomeId {
+        self.biomes.sample(DVec3::new(x as f64, 0.0, z as f64)).0
+    }
+
+    /// Layering used for column `(x, z)`.
+    pub fn layer_at(&self, x: i64, z: i64) -> &TerrainLayerDef {
+        self.layers
+            .get(&self.biome_at(x, z))
+            .unwrap_or(&self.default_layer)
+    }
+
+    /// Voxel types of column `(x, z)` over
+    /// [`column_range`](Self::column_range), bottom first.
+    pub fn generate_column(&self, x: i64, z: i64) -> Vec<VoxelTypeId> {
+        let surface_mm = self.surface_height(x, z) * VOXEL_SIZE_MM;
+        let layer = self.layer_at(x, z);
+        self.column_range
+            .clone()
+            .map(|y| layer.assign(y * VOXEL_SIZE_MM, surface_mm))
+            .collect()
+    }
+
+    /// Voxel data for the chunk at `address`.
+    pub fn generate_chunk(&self, address: &ChunkAddress) -> ChunkData {
+        let mut chunk = ChunkData::new_air();
+        let size = CHUNK_SIZE as i64;
+        let (base_x, base_y, base_z) = (address.x * size, address.y * size, address.z * size);
+
+        for lx in 0..CHUNK_SIZE {
+            for lz in 0..CHUNK_SIZE {
+                let (wx, wz) = (base_x + lx as i64, base_z + lz as i64);
+                let surface = self.surface_height(wx, wz);
+                if surface < base_y {
+                    continue;
+                }
+                let surface_mm = surface * VOXEL_SIZE_MM;
+                let layer = self.layer_at(wx, wz);
+                for ly in 0..CHUNK_SIZE {
+                    let voxel = layer.assign((base_y + ly as i64) * VOXEL_SIZE_MM, surface_mm);
+                    if voxel != AIR {
+                        chunk.set(lx, ly, lz, voxel);
+                    }
+                }
+            }
+        }
+        chunk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::biome::WhittakerRegion;
+
+    const SNOW: VoxelTypeId = VoxelTypeId(10);
+    const GRAVEL: VoxelTypeId = VoxelTypeId(11);
+    const SAND: VoxelTypeId = VoxelTypeId(12);
+    const SANDSTONE: VoxelTypeId = VoxelTypeId(13);
+    const BASALT: VoxelTypeId = VoxelTypeId(14);
+
+    fn layer(surface: VoxelTypeId, subsurface: VoxelTypeId, depth_voxels: i64) -> TerrainLayerDef {
+        TerrainLayerDef {
+            surface_voxel: surface,
+            subsurface_voxel: subsurface,
+            subsurface_depth_mm: depth_voxels * VOXEL_SIZE_MM,
+            bedrock_voxel: BASALT,
+        }
+    }
+
+    /// Cold half of the diagram is tundra, warm half is desert.
+    fn two_biome_generator() -> TerrainGenerator {
+        let tundra = BiomeId(0);
+        let desert = BiomeId(1);
+        let diagram = WhittakerDiagram {
+            regions: vec![WhittakerRegion {
+                temp_min: 0.5,
+                temp_max: 1.01,
+                moisture_min: 0.0,
+                moisture_max: 1.01,
+                biome_id: desert,
+            }],
+            fallback: tundra,
+        };
+        let mut sampler = BiomeSampler::new(7, diagram);
+        sampler.temp_frequency = 0.01;
+        let mut generator = TerrainGenerator::with_biomes(7, sampler);
+        generator.set_layer(tundra, layer(SNOW, GRAVEL, 2));
+        generator.set_layer(desert, layer(SAND, SANDSTONE, 5));
+        generator
+    }
+
+    #[test]
+    fn test_assign_by_depth() {
+        let def = layer(SNOW, GRAVEL, 2);
+        assert_eq!(def.assign(11_000, 10_000), AIR);
+        assert_eq!(def.assign(10_000, 10_000), SNOW);
+        assert_eq!(def.assign(9_000, 10_000), GRAVEL);
+        assert_eq!(def.assign(8_000, 10_000), GRAVEL);
+        assert_eq!(def.assign(7_999, 10_000), BASALT);
+    }
+
+    #[test]
+    fn test_surface_voxel_matches_column_biome() {
+        let generator = two_biome_generator();
+        let mut seen = hashbrown::HashSet::new();
+        for x in (0..400).step_by(7) {
+            for z in (0..400).step_by(11) {
+                let biome = generator.biome_at(x, z);
+                seen.insert(biome);
+                let column = generator.generate_column(x, z);
+                let surface = generator.surface_height(x, z);
+                let index = (surface - generator.column_range.start) as usize;
+                assert_eq!(column[index], generator.layers[&biome].surface_voxel);
+                assert_eq!(column[index + 1], AIR);
+            }
+        }
+        assert_eq!(seen.len(), 2, "sample area should span both biomes");
+    }
+
+    #[test]
+    fn test_below_subsurface_depth_is_bedrock() {
+        let generator = two_biome_generator();
+        for x in (0..200).step_by(13) {
+            for z in (0..200).step_by(5) {
+                let def = generator.layer_at(x, z);
+                let depth_voxels = def.subsurface_depth_mm / VOXEL_SIZE_MM;
+                let surface = generator.surface_height(x, z);
+                let column = generator.generate_column(x, z);
+                for (i, &voxel) in column.iter().enumerate() {
+                    let y = generator.column_range.start + i as i64;
+                    if y < surface - depth_voxels {
+                        assert_eq!(voxel, BASALT, "column ({x}, {z}) at y={y}");
+                    } else if y < surface {
+                        assert_eq!(voxel, def.subsurface_voxel);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_chunk_matches_columns() {
+        let generator = two_biome_generator();
+        let address = ChunkAddress::new(1, 0, 2, 0);
+        let chunk = generator.generate_chunk(&address);
+        for (lx, lz) in [(0, 0), (5, 17), (31, 31)] {
+            let (x, z) = (32 + lx as i64, 64 + lz as i64);
+            let column = generator.generate_column(x, z);
+            for ly in 0..CHUNK_SIZE {
+                let index = (ly as i64 - generator.column_range.start) as usize;
+                assert_eq!(chunk.get(lx, ly, lz), column[index]);
+            }
+        }
+    }
+}
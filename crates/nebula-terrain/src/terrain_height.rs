@@ -5,7 +5,7 @@
 //! distortion-free terrain across all cube faces.
 
 use glam::DVec3;
-use nebula_cubesphere::{FaceCoord, face_coord_to_sphere_everitt};
+use nebula_cubesphere::{FaceCoord, ProjectionMethod, project};
 
 use crate::heightmap::{HeightmapParams, HeightmapSampler};
 
@@ -22,6 +22,10 @@ pub struct TerrainHeightConfig {
     /// Sea level as an offset from the planet's base radius, in engine units.
     /// Default: 0 (sea level == base radius).
     pub sea_level: f64,
+    /// Height of the beach band above sea level, in engine units. Terrain
+    /// between sea level and this height is [`SurfaceType::Beach`].
+    /// Default: 10.
+    pub beach_width: f64,
     /// Planet base radius in engine units.
     /// Default: 6_371_000 (Earth-like, ~6371 km).
     pub planet_radius: f64,
@@ -33,11 +37,23 @@ impl Default for TerrainHeightConfig {
             min_height: -2_000.0,
             max_height: 8_000.0,
             sea_level: 0.0,
+            beach_width: 10.0,
             planet_radius: 6_371_000.0,
         }
     }
 }
 
+/// Coarse classification of the terrain surface relative to sea level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SurfaceType {
+    /// Surface lies below sea level.
+    Ocean,
+    /// Surface lies within the beach band just above sea level.
+    Beach,
+    /// Surface lies above the beach band.
+    Land,
+}
+
 /// Samples terrain height on a cubesphere surface using 3D noise.
 ///
 /// Wraps [`HeightmapSampler`] with planet-specific height mapping, producing
@@ -81,6 +97,20 @@ impl TerrainHeightSampler {
         height.clamp(self.config.min_height, self.config.max_height)
     }
 
+    /// Classify the surface at a point on the unit sphere as ocean, beach,
+    /// or land, based on its height relative to sea level and
+    /// [`TerrainHeightConfig::beach_width`].
+    pub fn sample_surface_type(&self, sphere_point: DVec3) -> SurfaceType {
+        let height = self.sample_height(sphere_point);
+        if height < 0.0 {
+            SurfaceType::Ocean
+        } else if height < self.config.beach_width {
+            SurfaceType::Beach
+        } else {
+            SurfaceType::Land
+        }
+    }
+
     /// Compute the absolute distance from the planet center for a surface point.
     ///
     /// This is the planet radius + sea level offset + terrain height.
@@ -107,10 +137,15 @@ impl TerrainHeightSampler {
 
 /// Determine the terrain surface height for a column at the given face coordinate.
 ///
-/// Converts the face coordinate to a 3D sphere point via the Everitt projection,
-/// then samples terrain height. Returns height above sea level in engine units.
-pub fn column_surface_height(fc: &FaceCoord, terrain: &TerrainHeightSampler) -> f64 {
-    let sphere_point = face_coord_to_sphere_everitt(fc);
+/// Converts the face coordinate to a 3D sphere point via the planet's
+/// `projection`, then samples terrain height. Returns height above sea level
+/// in engine units.
+pub fn column_surface_height(
+    fc: &FaceCoord,
+    terrain: &TerrainHeightSampler,
+    projection: ProjectionMethod,
+) -> f64 {
+    let sphere_point = project(fc, projection);
     terrain.sample_height(sphere_point)
 }
 
@@ -142,8 +177,8 @@ mod tests {
             let fc_a = FaceCoord::new(CubeFace::PosX, 0.0, v);
             let fc_b = FaceCoord::new(CubeFace::NegZ, 1.0, v);
 
-            let pt_a = face_coord_to_sphere_everitt(&fc_a);
-            let pt_b = face_coord_to_sphere_everitt(&fc_b);
+            let pt_a = project(&fc_a, ProjectionMethod::default());
+            let pt_b = project(&fc_b, ProjectionMethod::default());
 
             if (pt_a - pt_b).length() < 0.01 {
                 let h_a = sampler.sample_height(pt_a);
@@ -192,7 +227,7 @@ mod tests {
                     let u = u_step as f64 / 20.0;
                     let v = v_step as f64 / 20.0;
                     let fc = FaceCoord::new(face, u, v);
-                    let sphere_pt = face_coord_to_sphere_everitt(&fc);
+                    let sphere_pt = project(&fc, ProjectionMethod::default());
                     let h = sampler.sample_height(sphere_pt);
 
                     assert!(
@@ -239,6 +274,7 @@ mod tests {
             min_height: -100.0,
             max_height: 100.0,
             sea_level: 0.0,
+            beach_width: 10.0,
         };
         let sampler = TerrainHeightSampler::new(
             HeightmapParams {
@@ -257,6 +293,40 @@ mod tests {
         );
     }
 
+    /// A flat planet whose surface sits exactly `height` above sea level.
+    fn flat_sampler_at(height: f64) -> TerrainHeightSampler {
+        TerrainHeightSampler::new(
+            HeightmapParams {
+                seed: 3,
+                amplitude: 0.0,
+                ..Default::default()
+            },
+            TerrainHeightConfig {
+                min_height: height,
+                max_height: height,
+                sea_level: 120.0,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_surface_type_relative_to_sea_level() {
+        let point = DVec3::new(0.0, 0.0, 1.0);
+        assert_eq!(
+            flat_sampler_at(-500.0).sample_surface_type(point),
+            SurfaceType::Ocean
+        );
+        assert_eq!(
+            flat_sampler_at(2.0).sample_surface_type(point),
+            SurfaceType::Beach
+        );
+        assert_eq!(
+            flat_sampler_at(800.0).sample_surface_type(point),
+            SurfaceType::Land
+        );
+    }
+
     #[test]
     fn test_sample_world_position_direction_matches_input() {
         let sampler = default_sampler();
@@ -274,10 +344,10 @@ mod tests {
     fn test_column_surface_height_matches_direct_sample() {
         let sampler = default_sampler();
         let fc = FaceCoord::new(CubeFace::PosX, 0.3, 0.7);
-        let sphere_pt = face_coord_to_sphere_everitt(&fc);
+        let sphere_pt = project(&fc, ProjectionMethod::default());
 
         let h_direct = sampler.sample_height(sphere_pt);
-        let h_column = column_surface_height(&fc, &sampler);
+        let h_column = column_surface_height(&fc, &sampler, ProjectionMethod::default());
 
         assert!(
             (h_direct - h_column).abs() < EPSILON,
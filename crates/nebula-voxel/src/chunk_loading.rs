@@ -159,6 +159,18 @@ impl ChunkLoader {
         &mut self,
         camera_chunk: ChunkAddress,
         manager: &mut ChunkManager,
+    ) -> ChunkLoadTickResult {
+        self.tick_throttled(camera_chunk, manager, self.config.loads_per_tick)
+    }
+
+    /// Like [`tick`](Self::tick), but loads at most `max_loads` chunks (and
+    /// never more than `loads_per_tick`), e.g. the memory budget pressure's
+    /// throttled load count. Unloading is not throttled.
+    pub fn tick_throttled(
+        &mut self,
+        camera_chunk: ChunkAddress,
+        manager: &mut ChunkManager,
+        max_loads: u32,
     ) -> ChunkLoadTickResult {
         let mut result = ChunkLoadTickResult::default();
 
@@ -187,7 +199,7 @@ impl ChunkLoader {
         }
 
         // --- Step 2: Process load queue ---
-        for _ in 0..self.config.loads_per_tick {
+        for _ in 0..max_loads.min(self.config.loads_per_tick) {
             let Some((_dist_sq, addr)) = self.load_queue.dequeue() else {
                 break;
             };
@@ -384,4 +396,22 @@ mod tests {
         assert_eq!(r2.loaded, 3);
         assert_eq!(manager.loaded_count(), 6);
     }
+
+    #[test]
+    fn test_throttled_tick_caps_loads() {
+        let config = ChunkLoadConfig {
+            load_radius: 3,
+            unload_radius: 5,
+            loads_per_tick: 8,
+            unloads_per_tick: 8,
+        };
+        let mut loader = ChunkLoader::new(config);
+        let mut manager = ChunkManager::new();
+        let camera = addr(0, 0, 0);
+
+        // Half the budget, as under red memory pressure.
+        assert_eq!(loader.tick_throttled(camera, &mut manager, 4).loaded, 4);
+        // A limit above the configured budget does not raise it.
+        assert_eq!(loader.tick_throttled(camera, &mut manager, 100).loaded, 8);
+    }
 }
@@ -9,7 +9,7 @@
 //! |--------|------|-------|
 //! | 0 | 4 | Magic bytes `[0x4E, 0x56, 0x43, 0x4B]` ("NVCK") |
 //! | 4 | 1 | Format version (`u8`, currently 2) |
-//! | 5 | 1 | Compression flags (bit 0: RLE enabled) |
+//! | 5 | 1 | Flags (bit 0: RLE enabled, bit 1: light section present) |
 //! | 6 | 2 | Palette length (`u16`, little-endian) |
 //! | 8 | N×2 | Palette entries (N × `u16` `VoxelTypeId`, little-endian) |
 //! | 8+N×2 | 1 | Bit width (`u8`: 0, 2, 4, 8, or 16) |
@@ -19,6 +19,11 @@
 //! R × (`count: u16 LE`, `value: u16 LE`) pairs.
 //! When RLE is disabled, M = `ceil(32768 × bit_width / 8)` bytes.
 //!
+//! When the light flag is set, the index data is followed by a light
+//! section: length (`u32` LE, a multiple of 32768) and the same number of
+//! opaque light bytes for every voxel. The voxel crate does not interpret
+//! these bytes; see [`ChunkData::serialize_with_light`].
+//!
 //! Format version 1 (legacy) is also supported for deserialization.
 
 use crate::bit_packed::BitPackedArray;
@@ -35,6 +40,9 @@ const FORMAT_VERSION: u8 = 2;
 /// Compression flag: bit 0 indicates RLE is used.
 const FLAG_RLE: u8 = 0x01;
 
+/// Flag: bit 1 indicates a per-voxel light section follows the index data.
+const FLAG_LIGHT: u8 = 0x02;
+
 /// Errors that can occur during chunk deserialization.
 #[derive(Debug, thiserror::Error)]
 pub enum ChunkSerError {
@@ -58,6 +66,15 @@ pub enum ChunkSerError {
     /// A palette entry references an out-of-range voxel type.
     #[error("palette entry out of range")]
     InvalidPaletteEntry,
+    /// The light section does not hold the same whole number of bytes for
+    /// every voxel.
+    #[error("light map mismatch: expected a multiple of {expected} light bytes, got {actual}")]
+    LightMapMismatch {
+        /// Number of voxels in the chunk.
+        expected: usize,
+        /// Number of light bytes provided or stored.
+        actual: usize,
+    },
 }
 
 impl ChunkData {
@@ -65,6 +82,28 @@ impl ChunkData {
     ///
     /// Adaptively chooses RLE or raw format based on which is smaller.
     pub fn serialize(&self) -> Vec<u8> {
+        self.serialize_sections(None)
+    }
+
+    /// Serializes this chunk together with an optional per-voxel light map.
+    ///
+    /// `light` must hold a whole, non-zero number of bytes per voxel (a
+    /// multiple of [`CHUNK_VOLUME`]); its contents are stored verbatim. With `None` the output is identical to
+    /// [`serialize`](Self::serialize).
+    pub fn serialize_with_light(&self, light: Option<&[u8]>) -> Result<Vec<u8>, ChunkSerError> {
+        if let Some(light) = light
+            && !is_light_section_len(light.len())
+        {
+            return Err(ChunkSerError::LightMapMismatch {
+                expected: CHUNK_VOLUME,
+                actual: light.len(),
+            });
+        }
+        Ok(self.serialize_sections(light))
+    }
+
+    /// Writes the header and index payload, then the light section if any.
+    fn serialize_sections(&self, light: Option<&[u8]>) -> Vec<u8> {
         let palette = self.palette();
         let bit_width = self.bit_width();
 
@@ -97,10 +136,14 @@ impl ChunkData {
             }
         };
 
-        let flags: u8 = if use_rle { FLAG_RLE } else { 0 };
+        let mut flags: u8 = if use_rle { FLAG_RLE } else { 0 };
+        if light.is_some() {
+            flags |= FLAG_LIGHT;
+        }
         // Header: magic(4) + version(1) + flags(1) + palette_len(2) + palette + bit_width(1)
         let header_size = 4 + 1 + 1 + 2 + palette.len() * 2 + 1;
-        let mut buf = Vec::with_capacity(header_size + index_payload.len());
+        let light_size = light.map_or(0, |l| 4 + l.len());
+        let mut buf = Vec::with_capacity(header_size + index_payload.len() + light_size);
 
         buf.extend_from_slice(&MAGIC);
         buf.push(FORMAT_VERSION);
@@ -111,6 +154,10 @@ impl ChunkData {
         }
         buf.push(bit_width);
         buf.extend(index_payload);
+        if let Some(light) = light {
+            buf.extend_from_slice(&(light.len() as u32).to_le_bytes());
+            buf.extend_from_slice(light);
+        }
 
         buf
     }
@@ -154,8 +201,18 @@ impl ChunkData {
 
     /// Deserializes a chunk from a byte slice in the NVCK binary format.
     ///
-    /// Supports format versions 1 (no RLE) and 2 (optional RLE).
+    /// Supports format versions 1 (no RLE) and 2 (optional RLE). A stored
+    /// light section is skipped; use
+    /// [`deserialize_with_light`](Self::deserialize_with_light) to read it.
     pub fn deserialize(data: &[u8]) -> Result<Self, ChunkSerError> {
+        Self::deserialize_with_light(data).map(|(chunk, _)| chunk)
+    }
+
+    /// Deserializes a chunk and its light section, if one was stored.
+    ///
+    /// Returns `None` for the light when the chunk was saved without one
+    /// (including all version 1 data), so the caller can recompute it.
+    pub fn deserialize_with_light(data: &[u8]) -> Result<(Self, Option<Vec<u8>>), ChunkSerError> {
         if data.len() < 4 {
             return Err(ChunkSerError::InvalidMagic);
         }
@@ -171,7 +228,7 @@ impl ChunkData {
         }
         let version = data[4];
         match version {
-            1 => Self::deserialize_v1(data),
+            1 => Ok((Self::deserialize_v1(data)?, None)),
             2 => Self::deserialize_v2(data),
             _ => Err(ChunkSerError::UnsupportedVersion(version)),
         }
@@ -208,7 +265,7 @@ impl ChunkData {
     }
 
     /// Deserializes format version 2 (flags byte, optional RLE).
-    fn deserialize_v2(data: &[u8]) -> Result<Self, ChunkSerError> {
+    fn deserialize_v2(data: &[u8]) -> Result<(Self, Option<Vec<u8>>), ChunkSerError> {
         // v2 header: magic(4) + version(1) + flags(1) + palette_len(2) = 8 min
         if data.len() < 8 {
             return Err(ChunkSerError::Truncated {
@@ -239,8 +296,8 @@ impl ChunkData {
             return Err(ChunkSerError::InvalidPaletteEntry);
         }
 
-        let storage = if bit_width == 0 {
-            BitPackedArray::new(0, CHUNK_VOLUME)
+        let (storage, payload_end) = if bit_width == 0 {
+            (BitPackedArray::new(0, CHUNK_VOLUME), header_end)
         } else if use_rle {
             // Read RLE: run_count(u32) + runs
             if data.len() < header_end + 4 {
@@ -271,12 +328,23 @@ impl ChunkData {
                     actual,
                 }
             })?;
-            indices_to_storage(&indices, bit_width)
+            (indices_to_storage(&indices, bit_width), runs_end)
         } else {
-            read_raw_storage(data, header_end, bit_width)?
+            (
+                read_raw_storage(data, header_end, bit_width)?,
+                header_end + index_data_len(bit_width),
+            )
         };
 
-        Ok(ChunkData::from_raw_parts(palette, storage, bit_width))
+        let light = if flags & FLAG_LIGHT != 0 {
+            Some(read_light_section(data, payload_end)?)
+        } else {
+            None
+        };
+        Ok((
+            ChunkData::from_raw_parts(palette, storage, bit_width),
+            light,
+        ))
     }
 }
 
@@ -340,6 +408,42 @@ fn read_raw_storage(
     Ok(BitPackedArray::from_raw(bit_width, CHUNK_VOLUME, words))
 }
 
+/// Whether `len` bytes hold the same whole, non-zero number of bytes per voxel.
+fn is_light_section_len(len: usize) -> bool {
+    len != 0 && len.is_multiple_of(CHUNK_VOLUME)
+}
+
+/// Reads the length-prefixed light section starting at `start`.
+fn read_light_section(data: &[u8], start: usize) -> Result<Vec<u8>, ChunkSerError> {
+    let bytes_start = start + 4;
+    if data.len() < bytes_start {
+        return Err(ChunkSerError::Truncated {
+            expected: bytes_start,
+            actual: data.len(),
+        });
+    }
+    let len = u32::from_le_bytes([
+        data[start],
+        data[start + 1],
+        data[start + 2],
+        data[start + 3],
+    ]) as usize;
+    if !is_light_section_len(len) {
+        return Err(ChunkSerError::LightMapMismatch {
+            expected: CHUNK_VOLUME,
+            actual: len,
+        });
+    }
+    let end = bytes_start + len;
+    if data.len() < end {
+        return Err(ChunkSerError::Truncated {
+            expected: end,
+            actual: data.len(),
+        });
+    }
+    Ok(data[bytes_start..end].to_vec())
+}
+
 /// Converts a flat index array to a `BitPackedArray`.
 fn indices_to_storage(indices: &[u16], bit_width: u8) -> BitPackedArray {
     let mut storage = BitPackedArray::new(bit_width, indices.len());
@@ -363,118 +467,5 @@ fn index_data_len(bit_width: u8) -> usize {
 // ---------------------------------------------------------------------------
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_serialize_deserialize_roundtrip() {
-        // Test with various palette sizes: 1 (uniform), 4, 20, 300
-        for &num_types in &[1usize, 4, 20, 300] {
-            let mut chunk = ChunkData::new_air();
-            for i in 1..num_types {
-                let idx = i;
-                chunk.set(
-                    idx % 32,
-                    (idx / 32) % 32,
-                    (idx / 1024) % 32,
-                    VoxelTypeId(i as u16),
-                );
-            }
-
-            let bytes = chunk.serialize();
-            let restored = ChunkData::deserialize(&bytes)
-                .unwrap_or_else(|e| panic!("deserialize failed for {num_types} types: {e}"));
-
-            // Verify every voxel matches
-            for z in 0..32 {
-                for y in 0..32 {
-                    for x in 0..32 {
-                        assert_eq!(
-                            chunk.get(x, y, z),
-                            restored.get(x, y, z),
-                            "mismatch at ({x},{y},{z}) with {num_types} types"
-                        );
-                    }
-                }
-            }
-        }
-    }
-
-    #[test]
-    fn test_empty_chunk_serializes_small() {
-        let chunk = ChunkData::new_air();
-        let bytes = chunk.serialize();
-        assert!(
-            bytes.len() < 16,
-            "uniform chunk serialized to {} bytes, expected < 16",
-            bytes.len()
-        );
-    }
-
-    #[test]
-    fn test_full_chunk_serializes_correctly() {
-        let mut chunk = ChunkData::new_air();
-        // Use distinct patterns clamped to available types
-        for z in 0..32usize {
-            for y in 0..32usize {
-                for x in 0..32usize {
-                    let id = ((x + y * 32) % 256) as u16;
-                    chunk.set(x, y, z, VoxelTypeId(id));
-                }
-            }
-        }
-
-        let bytes = chunk.serialize();
-        let restored = ChunkData::deserialize(&bytes).expect("deserialize failed");
-
-        for z in 0..32 {
-            for y in 0..32 {
-                for x in 0..32 {
-                    assert_eq!(
-                        chunk.get(x, y, z),
-                        restored.get(x, y, z),
-                        "mismatch at ({x},{y},{z})"
-                    );
-                }
-            }
-        }
-    }
-
-    #[test]
-    fn test_version_byte_present() {
-        let chunk = ChunkData::new_air();
-        let bytes = chunk.serialize();
-        assert_eq!(bytes[4], 2, "format version should be 2");
-    }
-
-    #[test]
-    fn test_corrupted_data_returns_error() {
-        // Invalid magic
-        let result = ChunkData::deserialize(&[0xFF, 0xFF]);
-        assert!(
-            matches!(result, Err(ChunkSerError::InvalidMagic)),
-            "expected InvalidMagic, got {result:?}"
-        );
-
-        // Unsupported version
-        let result = ChunkData::deserialize(&[0x4E, 0x56, 0x43, 0x4B, 99, 0, 0, 0]);
-        assert!(
-            matches!(result, Err(ChunkSerError::UnsupportedVersion(99))),
-            "expected UnsupportedVersion(99), got {result:?}"
-        );
-
-        // Truncated: valid v1 header but missing palette data
-        let result = ChunkData::deserialize(&[0x4E, 0x56, 0x43, 0x4B, 1, 5, 0]);
-        assert!(
-            matches!(result, Err(ChunkSerError::Truncated { .. })),
-            "expected Truncated, got {result:?}"
-        );
-
-        // Invalid bit width (v1 format)
-        let result = ChunkData::deserialize(&[0x4E, 0x56, 0x43, 0x4B, 1, 1, 0, 0, 0, 3]);
-        assert!(
-            matches!(result, Err(ChunkSerError::InvalidBitWidth(3))),
-            "expected InvalidBitWidth(3), got {result:?}"
-        );
-    }
-}
+#[path = "chunk_serial_tests.rs"]
+mod tests;
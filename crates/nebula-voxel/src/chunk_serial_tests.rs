@@ -0,0 +1,187 @@
+//! Unit tests for NVCK chunk serialization.
+
+use super::*;
+
+#[test]
+fn test_serialize_deserialize_roundtrip() {
+    // Test with various palette sizes: 1 (uniform), 4, 20, 300
+    for &num_types in &[1usize, 4, 20, 300] {
+        let mut chunk = ChunkData::new_air();
+        for i in 1..num_types {
+            let idx = i;
+            chunk.set(
+                idx % 32,
+                (idx / 32) % 32,
+                (idx / 1024) % 32,
+                VoxelTypeId(i as u16),
+            );
+        }
+
+        let bytes = chunk.serialize();
+        let restored = ChunkData::deserialize(&bytes)
+            .unwrap_or_else(|e| panic!("deserialize failed for {num_types} types: {e}"));
+
+        // Verify every voxel matches
+        for z in 0..32 {
+            for y in 0..32 {
+                for x in 0..32 {
+                    assert_eq!(
+                        chunk.get(x, y, z),
+                        restored.get(x, y, z),
+                        "mismatch at ({x},{y},{z}) with {num_types} types"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_empty_chunk_serializes_small() {
+    let chunk = ChunkData::new_air();
+    let bytes = chunk.serialize();
+    assert!(
+        bytes.len() < 16,
+        "uniform chunk serialized to {} bytes, expected < 16",
+        bytes.len()
+    );
+}
+
+#[test]
+fn test_full_chunk_serializes_correctly() {
+    let mut chunk = ChunkData::new_air();
+    // Use distinct patterns clamped to available types
+    for z in 0..32usize {
+        for y in 0..32usize {
+            for x in 0..32usize {
+                let id = ((x + y * 32) % 256) as u16;
+                chunk.set(x, y, z, VoxelTypeId(id));
+            }
+        }
+    }
+
+    let bytes = chunk.serialize();
+    let restored = ChunkData::deserialize(&bytes).expect("deserialize failed");
+
+    for z in 0..32 {
+        for y in 0..32 {
+            for x in 0..32 {
+                assert_eq!(
+                    chunk.get(x, y, z),
+                    restored.get(x, y, z),
+                    "mismatch at ({x},{y},{z})"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_version_byte_present() {
+    let chunk = ChunkData::new_air();
+    let bytes = chunk.serialize();
+    assert_eq!(bytes[4], 2, "format version should be 2");
+}
+
+#[test]
+fn test_corrupted_data_returns_error() {
+    // Invalid magic
+    let result = ChunkData::deserialize(&[0xFF, 0xFF]);
+    assert!(
+        matches!(result, Err(ChunkSerError::InvalidMagic)),
+        "expected InvalidMagic, got {result:?}"
+    );
+
+    // Unsupported version
+    let result = ChunkData::deserialize(&[0x4E, 0x56, 0x43, 0x4B, 99, 0, 0, 0]);
+    assert!(
+        matches!(result, Err(ChunkSerError::UnsupportedVersion(99))),
+        "expected UnsupportedVersion(99), got {result:?}"
+    );
+
+    // Truncated: valid v1 header but missing palette data
+    let result = ChunkData::deserialize(&[0x4E, 0x56, 0x43, 0x4B, 1, 5, 0]);
+    assert!(
+        matches!(result, Err(ChunkSerError::Truncated { .. })),
+        "expected Truncated, got {result:?}"
+    );
+
+    // Invalid bit width (v1 format)
+    let result = ChunkData::deserialize(&[0x4E, 0x56, 0x43, 0x4B, 1, 1, 0, 0, 0, 3]);
+    assert!(
+        matches!(result, Err(ChunkSerError::InvalidBitWidth(3))),
+        "expected InvalidBitWidth(3), got {result:?}"
+    );
+}
+
+#[test]
+fn test_light_section_roundtrip() {
+    let mut chunk = ChunkData::new_air();
+    chunk.set(3, 4, 5, VoxelTypeId(7));
+    let light: Vec<u8> = (0..CHUNK_VOLUME).map(|i| (i % 251) as u8).collect();
+
+    let bytes = chunk.serialize_with_light(Some(&light)).unwrap();
+    let (restored, restored_light) = ChunkData::deserialize_with_light(&bytes).unwrap();
+    assert_eq!(restored.get(3, 4, 5), VoxelTypeId(7));
+    assert_eq!(restored_light.as_deref(), Some(light.as_slice()));
+
+    // Plain deserialization skips the light section.
+    assert_eq!(
+        ChunkData::deserialize(&bytes).unwrap().get(3, 4, 5),
+        VoxelTypeId(7)
+    );
+
+    // Without light the format is unchanged and no light is returned.
+    let plain = chunk.serialize_with_light(None).unwrap();
+    assert_eq!(plain, chunk.serialize());
+    let (_, none) = ChunkData::deserialize_with_light(&plain).unwrap();
+    assert!(none.is_none());
+}
+
+#[test]
+fn test_light_section_length_mismatch() {
+    let chunk = ChunkData::new_air();
+    let result = chunk.serialize_with_light(Some(&[0u8; 16]));
+    assert!(
+        matches!(
+            result,
+            Err(ChunkSerError::LightMapMismatch {
+                expected: CHUNK_VOLUME,
+                actual: 16
+            })
+        ),
+        "expected LightMapMismatch, got {result:?}"
+    );
+
+    // A stored section whose length prefix disagrees with the voxel count.
+    let mut bytes = chunk
+        .serialize_with_light(Some(&[0u8; CHUNK_VOLUME]))
+        .unwrap();
+    let prefix = bytes.len() - CHUNK_VOLUME - 4;
+    bytes[prefix..prefix + 4].copy_from_slice(&100u32.to_le_bytes());
+    let result = ChunkData::deserialize_with_light(&bytes);
+    assert!(
+        matches!(
+            result,
+            Err(ChunkSerError::LightMapMismatch { actual: 100, .. })
+        ),
+        "expected LightMapMismatch, got {result:?}"
+    );
+
+    // A truncated light section.
+    let mut bytes = chunk
+        .serialize_with_light(Some(&[0u8; CHUNK_VOLUME]))
+        .unwrap();
+    bytes.truncate(bytes.len() - 10);
+    let result = ChunkData::deserialize_with_light(&bytes);
+    assert!(matches!(result, Err(ChunkSerError::Truncated { .. })));
+}
+
+#[test]
+fn test_light_section_with_two_bytes_per_voxel() {
+    let chunk = ChunkData::new_air();
+    let light: Vec<u8> = (0..CHUNK_VOLUME * 2).map(|i| (i % 13) as u8).collect();
+    let bytes = chunk.serialize_with_light(Some(&light)).unwrap();
+    let (_, restored) = ChunkData::deserialize_with_light(&bytes).unwrap();
+    assert_eq!(restored.as_deref(), Some(light.as_slice()));
+}
@@ -38,8 +38,9 @@ pub struct VoxelTypeDef {
     pub transparency: Transparency,
     /// Index into the material palette (albedo, roughness, etc.).
     pub material_index: u16,
-    /// Light emission level (0 = none, 15 = max).
-    pub light_emission: u8,
+    /// Emitted light color as red, green, and blue levels (0 = none,
+    /// 15 = max per channel).
+    pub light_emission: [u8; 3],
 }
 
 /// Errors that can occur during voxel type registration.
@@ -74,7 +75,7 @@ impl VoxelTypeRegistry {
             solid: false,
             transparency: Transparency::FullyTransparent,
             material_index: 0,
-            light_emission: 0,
+            light_emission: [0; 3],
         };
 
         let mut name_to_id = HashMap::new();
@@ -181,7 +182,7 @@ mod tests {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 1,
-            light_emission: 0,
+            light_emission: [0; 3],
         }
     }
 
@@ -191,7 +192,7 @@ mod tests {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 2,
-            light_emission: 0,
+            light_emission: [0; 3],
         }
     }
 
@@ -201,7 +202,7 @@ mod tests {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 3,
-            light_emission: 0,
+            light_emission: [0; 3],
         }
     }
 
@@ -233,7 +234,7 @@ mod tests {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 10,
-            light_emission: 0,
+            light_emission: [0; 3],
         };
         let id = registry.register(obsidian).unwrap();
         assert_eq!(registry.lookup_by_name("obsidian"), Some(id));
@@ -248,13 +249,13 @@ mod tests {
             solid: true,
             transparency: Transparency::Opaque,
             material_index: 42,
-            light_emission: 12,
+            light_emission: [12; 3],
         };
         let id = registry.register(def).unwrap();
         let retrieved = registry.get(id);
         assert!(retrieved.solid);
         assert_eq!(retrieved.material_index, 42);
-        assert_eq!(retrieved.light_emission, 12);
+        assert_eq!(retrieved.light_emission, [12; 3]);
     }
 
     #[test]